@@ -0,0 +1,169 @@
+//! User-defined aliases, git style: a JSON object mapping an alias to the
+//! command line it stands for (`{ "st": "status --short" }`), read from
+//! `$HOME/.<app>/aliases.json` or the file `<APP>_ALIASES` names.
+//!
+//! Core expands an alias in front of argv before it resolves the command, so
+//! the expanded command line is dispatched and validated like a typed one.
+//! Everything here works on the text, so it can be tested without a host;
+//! `lib.rs` reads the file through `host-fs`.
+
+use serde_json::Value;
+use wacli_argparse::args;
+
+/// File name of the alias map under `$HOME/.<app>/`.
+pub(crate) const FILE_NAME: &str = "aliases.json";
+
+/// How many aliases one expansion may go through (`a` -> `b` -> ...).
+pub(crate) const MAX_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Alias {
+    pub(crate) name: String,
+    /// The command line as written in the file.
+    pub(crate) definition: String,
+    /// `definition` split into words.
+    pub(crate) words: Vec<String>,
+}
+
+/// The aliases in `text`. Definitions are split like a shell would split
+/// them (quotes and backslash escapes, no expansions).
+pub(crate) fn parse(text: &str) -> Result<Vec<Alias>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let Value::Object(map) = value else {
+        return Err("expected a JSON object".to_string());
+    };
+    map.into_iter()
+        .map(|(name, value)| {
+            let Value::String(definition) = value else {
+                return Err(format!("alias '{name}' must be a string"));
+            };
+            let words =
+                args::split_words(&definition).map_err(|err| format!("alias '{name}': {err}"))?;
+            if words.is_empty() {
+                return Err(format!("alias '{name}' is empty"));
+            }
+            Ok(Alias {
+                name,
+                definition,
+                words,
+            })
+        })
+        .collect()
+}
+
+/// The alias named `name`.
+pub(crate) fn find<'a>(aliases: &'a [Alias], name: &str) -> Option<&'a Alias> {
+    aliases.iter().find(|alias| alias.name == name)
+}
+
+/// `argv` with the alias in front expanded, again while the expansion starts
+/// with another alias. Names `reserved` accepts (commands, groups, built-ins)
+/// are never expanded, so aliases cannot shadow them.
+pub(crate) fn expand(
+    aliases: &[Alias],
+    argv: &[String],
+    reserved: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, String> {
+    let mut argv = argv.to_vec();
+    let mut chain: Vec<&str> = Vec::new();
+    while let Some(alias) = argv
+        .first()
+        .filter(|first| !reserved(first))
+        .and_then(|first| find(aliases, first))
+    {
+        if chain.contains(&alias.name.as_str()) {
+            return Err(format!(
+                "alias cycle: {} -> {}",
+                chain.join(" -> "),
+                alias.name
+            ));
+        }
+        if chain.len() == MAX_DEPTH {
+            return Err(format!(
+                "alias '{}' expands through more than {MAX_DEPTH} aliases",
+                chain[0]
+            ));
+        }
+        chain.push(&alias.name);
+        argv.splice(..1, alias.words.iter().cloned());
+    }
+    Ok(argv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn aliases(text: &str) -> Vec<Alias> {
+        parse(text).unwrap()
+    }
+
+    #[test]
+    fn parse_splits_definitions_into_words() {
+        let parsed = aliases(r#"{ "st": "status --short", "say": "greet 'Ada Lovelace'" }"#);
+        assert_eq!(parsed[0].name, "say");
+        assert_eq!(parsed[0].definition, "greet 'Ada Lovelace'");
+        assert_eq!(parsed[0].words, ["greet", "Ada Lovelace"]);
+        assert_eq!(parsed[1].words, ["status", "--short"]);
+
+        assert_eq!(parse("[]").unwrap_err(), "expected a JSON object");
+        assert_eq!(
+            parse(r#"{ "st": ["status"] }"#).unwrap_err(),
+            "alias 'st' must be a string"
+        );
+        assert_eq!(
+            parse(r#"{ "st": "status 'open" }"#).unwrap_err(),
+            "alias 'st': unterminated quote"
+        );
+        assert_eq!(
+            parse(r#"{ "st": "  " }"#).unwrap_err(),
+            "alias 'st' is empty"
+        );
+    }
+
+    #[test]
+    fn expand_replaces_the_alias_and_keeps_the_rest() {
+        let map = aliases(r#"{ "st": "status --short", "s": "st -v", "greet": "status" }"#);
+        let none = |_: &str| false;
+        assert_eq!(
+            expand(&map, &argv(&["st", "src"]), none).unwrap(),
+            ["status", "--short", "src"]
+        );
+        // Aliases may build on each other.
+        assert_eq!(
+            expand(&map, &argv(&["s"]), none).unwrap(),
+            ["status", "--short", "-v"]
+        );
+        // Only the first word is an alias, and reserved names never are.
+        assert_eq!(
+            expand(&map, &argv(&["log", "st"]), none).unwrap(),
+            ["log", "st"]
+        );
+        assert_eq!(
+            expand(&map, &argv(&["greet"]), |name| name == "greet").unwrap(),
+            ["greet"]
+        );
+        assert!(expand(&map, &[], none).unwrap().is_empty());
+    }
+
+    #[test]
+    fn expand_rejects_cycles_and_deep_chains() {
+        let none = |_: &str| false;
+        let cycle = aliases(r#"{ "a": "b x", "b": "a y" }"#);
+        assert_eq!(
+            expand(&cycle, &argv(&["a"]), none).unwrap_err(),
+            "alias cycle: a -> b -> a"
+        );
+
+        let deep = aliases(r#"{ "a1": "a2", "a2": "a3", "a3": "a4", "a4": "a5", "a5": "ok" }"#);
+        assert_eq!(expand(&deep, &argv(&["a2"]), none).unwrap(), ["ok"]);
+        assert_eq!(
+            expand(&deep, &argv(&["a1"]), none).unwrap_err(),
+            "alias 'a1' expands through more than 4 aliases"
+        );
+    }
+}
@@ -100,90 +100,1566 @@ pub mod wacli {
           result11
         }
       }
+      #[allow(unused_unsafe, clippy::all)]
+      /// The value of the environment variable `key` (case-sensitive).
+      #[allow(async_fn_in_trait)]
+      pub fn get(key: &str,) -> Option<_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = key;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "get"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result7 = match l3 {
+            0 => None,
+            1 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Some(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result7
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// The working directory relative paths resolve against (`.` when the
+      /// runtime reports none).
+      #[allow(async_fn_in_trait)]
+      pub fn cwd() -> _rt::String{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "cwd"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = *ptr0.add(0).cast::<*mut u8>();
+          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len4 = l3;
+          let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+          let result5 = _rt::string_lift(bytes4);
+          result5
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Monotonic clock reading in nanoseconds, for measuring durations only.
+      #[allow(async_fn_in_trait)]
+      pub fn monotonic_now() -> u64{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "monotonic-now"]
+            fn wit_import0() -> i64;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i64 { unreachable!() }
+          let ret = wit_import0();
+          ret as u64
+        }
+      }
+
+    }
+
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod host_io {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn stdout_write(bytes: &[u8],) -> (){
+        unsafe {
+          let vec0 = bytes;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdout-write"]
+            fn wit_import1(_: *mut u8, _: usize, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, _: usize, ) { unreachable!() }
+          wit_import1(ptr0.cast_mut(), len0);
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn stderr_write(bytes: &[u8],) -> (){
+        unsafe {
+          let vec0 = bytes;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stderr-write"]
+            fn wit_import1(_: *mut u8, _: usize, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, _: usize, ) { unreachable!() }
+          wit_import1(ptr0.cast_mut(), len0);
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn stdout_flush() -> (){
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdout-flush"]
+            fn wit_import0();
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() { unreachable!() }
+          wit_import0();
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn stderr_flush() -> (){
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stderr-flush"]
+            fn wit_import0();
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() { unreachable!() }
+          wit_import0();
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Whether stdout is attached to a terminal (false when piped or captured).
+      #[allow(async_fn_in_trait)]
+      pub fn stdout_is_terminal() -> bool{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdout-is-terminal"]
+            fn wit_import0() -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i32 { unreachable!() }
+          let ret = wit_import0();
+          _rt::bool_lift(ret as u8)
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Whether stderr is attached to a terminal (false when piped or captured).
+      #[allow(async_fn_in_trait)]
+      pub fn stderr_is_terminal() -> bool{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stderr-is-terminal"]
+            fn wit_import0() -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i32 { unreachable!() }
+          let ret = wit_import0();
+          _rt::bool_lift(ret as u8)
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Whether stdin is attached to a terminal (false when piped or empty).
+      #[allow(async_fn_in_trait)]
+      pub fn stdin_is_terminal() -> bool{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdin-is-terminal"]
+            fn wit_import0() -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i32 { unreachable!() }
+          let ret = wit_import0();
+          _rt::bool_lift(ret as u8)
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Read one line from stdin, without its line ending; `none` at end of
+      /// input, `err` when the read fails.
+      #[allow(async_fn_in_trait)]
+      pub fn stdin_read_line() -> Result<Option<_rt::String>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdin-read-line"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result10 = match l2 {
+            0 => {
+              let e = {
+                let l3 = i32::from(*ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                match l3 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l5 = *ptr0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len6 = l5;
+                      let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                      _rt::string_lift(bytes6)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                }
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l7 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l8 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len9 = l8;
+                let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                _rt::string_lift(bytes9)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result10
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Write `prompt` to stderr and read one line from stdin like
+      /// `stdin-read-line`, without echoing it when stdin is a terminal (a plain
+      /// read otherwise). End of input is an `err`.
+      #[allow(async_fn_in_trait)]
+      pub fn read_secret(prompt: &str,) -> Result<_rt::String,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = prompt;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "read-secret"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result10 = match l3 {
+            0 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l7 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l8 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len9 = l8;
+                let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                _rt::string_lift(bytes9)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result10
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Read up to `len` bytes from stdin; empty at end of input, `err` when
+      /// the read fails.
+      #[allow(async_fn_in_trait)]
+      pub fn stdin_read(len: u64,) -> Result<_rt::Vec::<u8>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdin-read"]
+            fn wit_import1(_: i64, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: i64, _: *mut u8, ) { unreachable!() }
+          wit_import1(_rt::as_i64(&len), ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result9 = match l2 {
+            0 => {
+              let e = {
+                let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len5 = l4;
+
+                <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l6 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l7 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len8 = l7;
+                let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                _rt::string_lift(bytes8)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result9
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Read the rest of stdin; empty at end of input, `err` when the read
+      /// fails.
+      #[allow(async_fn_in_trait)]
+      pub fn stdin_read_all() -> Result<_rt::Vec::<u8>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "stdin-read-all"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result9 = match l2 {
+            0 => {
+              let e = {
+                let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len5 = l4;
+
+                <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l6 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l7 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len8 = l7;
+                let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                _rt::string_lift(bytes8)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result9
+        }
+      }
+
+    }
+
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod host_log {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      /// Message severity, least severe first.
+      #[repr(u8)]
+      #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+      pub enum Level {
+        Trace,
+        Debug,
+        Info,
+        Warn,
+        Error,
+      }
+      impl ::core::fmt::Debug for Level {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          match self {
+            Level::Trace => {
+              f.debug_tuple("Level::Trace").finish()
+            }
+            Level::Debug => {
+              f.debug_tuple("Level::Debug").finish()
+            }
+            Level::Info => {
+              f.debug_tuple("Level::Info").finish()
+            }
+            Level::Warn => {
+              f.debug_tuple("Level::Warn").finish()
+            }
+            Level::Error => {
+              f.debug_tuple("Level::Error").finish()
+            }
+          }
+        }
+      }
+
+      impl Level{
+        #[doc(hidden)]
+        pub unsafe fn _lift(val: u8) -> Level{
+          if !cfg!(debug_assertions) {
+            return unsafe { ::core::mem::transmute(val) };
+          }
+
+          match val {
+            0 => Level::Trace,
+            1 => Level::Debug,
+            2 => Level::Info,
+            3 => Level::Warn,
+            4 => Level::Error,
+
+            _ => panic!("invalid enum discriminant"),
+          }
+        }
+      }
+
+      #[allow(unused_unsafe, clippy::all)]
+      /// Write `message` to stderr as `[LEVEL target] message`, unless `level`
+      /// is below the max level (then nothing is formatted or written).
+      #[allow(async_fn_in_trait)]
+      pub fn log(level: Level,target: &str,message: &str,) -> (){
+        unsafe {
+          let vec0 = target;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let vec1 = message;
+          let ptr1 = vec1.as_ptr().cast::<u8>();
+          let len1 = vec1.len();
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-log@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "log"]
+            fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, _: usize, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, _: usize, ) { unreachable!() }
+          wit_import2(level.clone() as i32, ptr0.cast_mut(), len0, ptr1.cast_mut(), len1);
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Whether a message at `level` would be written; check it before
+      /// building an expensive message. The max level is `warn` unless core
+      /// changes it (see `host-control`).
+      #[allow(async_fn_in_trait)]
+      pub fn enabled(level: Level,) -> bool{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-log@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "enabled"]
+            fn wit_import0(_: i32, ) -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i32, ) -> i32 { unreachable!() }
+          let ret = wit_import0(level.clone() as i32);
+          _rt::bool_lift(ret as u8)
+        }
+      }
+
+    }
+
+    /// Run settings only core changes. The host exports this interface, but the
+    /// composition wires it into core alone, so commands cannot change them.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod host_control {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      pub type Level = super::super::super::wacli::cli::host_log::Level;
+      #[allow(unused_unsafe, clippy::all)]
+      /// Set (`some`) or remove (`none`) the variable `key`, as seen by
+      /// `host-env.env` and `host-env.get` for the rest of the run; the process
+      /// environment itself is unchanged. Core hands global args to commands
+      /// this way.
+      #[allow(async_fn_in_trait)]
+      pub fn set_env(key: &str,value: Option<&str>,) -> (){
+        unsafe {
+          let vec0 = key;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let (result2_0,result2_1,result2_2,) = match value {
+            Some(e) => {
+              let vec1 = e;
+              let ptr1 = vec1.as_ptr().cast::<u8>();
+              let len1 = vec1.len();
+
+              (1i32, ptr1.cast_mut(), len1)
+            },
+            None => {
+              (0i32, ::core::ptr::null_mut(), 0usize)
+            },
+          };
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-control@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "set-env"]
+            fn wit_import3(_: *mut u8, _: usize, _: i32, _: *mut u8, _: usize, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: i32, _: *mut u8, _: usize, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, result2_0, result2_1, result2_2);
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Drop `host-log` messages below `level` (`none`: drop every message).
+      /// Defaults to `warn`; core sets it from `<APP>_LOG` and the
+      /// `verbose`/`quiet` global args before dispatching a command.
+      #[allow(async_fn_in_trait)]
+      pub fn set_max_log_level(level: Option<Level>,) -> (){
+        unsafe {
+          let (result0_0,result0_1,) = match level {
+            Some(e) => (1i32, e.clone() as i32),
+            None => {
+              (0i32, 0i32)
+            },
+          };
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-control@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "set-max-log-level"]
+            fn wit_import1(_: i32, _: i32, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: i32, _: i32, ) { unreachable!() }
+          wit_import1(result0_0, result0_1);
+        }
+      }
+
+    }
+
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod host_fs {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      #[repr(u8)]
+      #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+      pub enum FileKind {
+        File,
+        Dir,
+        Symlink,
+        Other,
+      }
+      impl ::core::fmt::Debug for FileKind {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          match self {
+            FileKind::File => {
+              f.debug_tuple("FileKind::File").finish()
+            }
+            FileKind::Dir => {
+              f.debug_tuple("FileKind::Dir").finish()
+            }
+            FileKind::Symlink => {
+              f.debug_tuple("FileKind::Symlink").finish()
+            }
+            FileKind::Other => {
+              f.debug_tuple("FileKind::Other").finish()
+            }
+          }
+        }
+      }
+
+      impl FileKind{
+        #[doc(hidden)]
+        pub unsafe fn _lift(val: u8) -> FileKind{
+          if !cfg!(debug_assertions) {
+            return unsafe { ::core::mem::transmute(val) };
+          }
+
+          match val {
+            0 => FileKind::File,
+            1 => FileKind::Dir,
+            2 => FileKind::Symlink,
+            3 => FileKind::Other,
+
+            _ => panic!("invalid enum discriminant"),
+          }
+        }
+      }
+
+      #[repr(C)]
+      #[derive(Clone, Copy)]
+      pub struct FileInfo {
+        pub kind: FileKind,
+        pub size: u64,
+        /// Last modification time in unix milliseconds (0 if unknown).
+        pub modified_at: u64,
+      }
+      impl ::core::fmt::Debug for FileInfo {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("FileInfo").field("kind", &self.kind).field("size", &self.size).field("modified-at", &self.modified_at).finish()
+        }
+      }
+      #[derive(Clone)]
+      pub struct DirEntry {
+        /// Path relative to the walked directory, `/`-separated.
+        pub path: _rt::String,
+        pub kind: FileKind,
+      }
+      impl ::core::fmt::Debug for DirEntry {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("DirEntry").field("path", &self.path).field("kind", &self.kind).finish()
+        }
+      }
+      /// A file kept open across writes (see `open-writer`).
+
+      #[derive(Debug)]
+      #[repr(transparent)]
+      pub struct Writer{
+        handle: _rt::Resource<Writer>,
+      }
+
+      impl Writer{
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+          Self {
+            handle: unsafe { _rt::Resource::from_handle(handle) },
+          }
+        }
+
+        #[doc(hidden)]
+        pub fn take_handle(&self) -> u32 {
+          _rt::Resource::take_handle(&self.handle)
+        }
+
+        #[doc(hidden)]
+        pub fn handle(&self) -> u32 {
+          _rt::Resource::handle(&self.handle)
+        }
+      }
+      
+
+      unsafe impl _rt::WasmResource for Writer{
+        #[inline]
+        unsafe fn drop(_handle: u32) {
+          
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "[resource-drop]writer"]
+            fn drop(_: i32, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn drop(_: i32, ) { unreachable!() }
+          
+          unsafe { drop(_handle as i32); }
+        }
+      }
+      
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn read_file(path: &str,) -> Result<_rt::Vec::<u8>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "read-file"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result10 = match l3 {
+            0 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+
+                <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(l4.cast(), len6, len6))
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l7 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l8 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len9 = l8;
+                let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                _rt::string_lift(bytes9)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result10
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn write_file(path: &str,contents: &[u8],) -> Result<(),_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let vec1 = contents;
+          let ptr1 = vec1.as_ptr().cast::<u8>();
+          let len1 = vec1.len();
+          let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "write-file"]
+            fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+          let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+          let result8 = match l4 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l5 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l6 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len7 = l6;
+                let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                _rt::string_lift(bytes7)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result8
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn create_dir(path: &str,) -> Result<(),_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "create-dir"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result7 = match l3 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result7
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn list_dir(path: &str,) -> Result<_rt::Vec::<_rt::String>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "list-dir"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result13 = match l3 {
+            0 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let base9 = l4;
+                let len9 = l5;
+                let mut result9 = _rt::Vec::with_capacity(len9);
+                for i in 0..len9 {
+                  let base = base9.add(i * (2*::core::mem::size_of::<*const u8>()));
+                  let e9 = {
+                    let l6 = *base.add(0).cast::<*mut u8>();
+                    let l7 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len8 = l7;
+                    let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                    _rt::string_lift(bytes8)
+                  };
+                  result9.push(e9);
+                }
+                _rt::cabi_dealloc(base9, len9 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+
+                result9
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l10 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l11 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len12 = l11;
+                let bytes12 = _rt::Vec::from_raw_parts(l10.cast(), len12, len12);
+
+                _rt::string_lift(bytes12)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result13
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Copy `src` to `dst` (created or truncated), streaming in chunks so large
+      /// files are never held in memory. Returns the number of bytes copied.
+      #[allow(async_fn_in_trait)]
+      pub fn copy_file(src: &str,dst: &str,) -> Result<u64,_rt::String>{
+        unsafe {
+
+          #[repr(align(8))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 8+2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8+2*::core::mem::size_of::<*const u8>()]);
+          let vec0 = src;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let vec1 = dst;
+          let ptr1 = vec1.as_ptr().cast::<u8>();
+          let len1 = vec1.len();
+          let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "copy-file"]
+            fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+          let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+          let result9 = match l4 {
+            0 => {
+              let e = {
+                let l5 = *ptr2.add(8).cast::<i64>();
 
-    }
+                l5 as u64
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l6 = *ptr2.add(8).cast::<*mut u8>();
+                let l7 = *ptr2.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len8 = l7;
+                let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                _rt::string_lift(bytes8)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result9
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Move `src` to `dst`, replacing a file (or empty directory) already
+      /// there. Both must be under the same preopened directory; across mounts
+      /// it fails, and `copy-file` then `remove-file` has to be used instead.
+      #[allow(async_fn_in_trait)]
+      pub fn rename(src: &str,dst: &str,) -> Result<(),_rt::String>{
+        unsafe {
 
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = src;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let vec1 = dst;
+          let ptr1 = vec1.as_ptr().cast::<u8>();
+          let len1 = vec1.len();
+          let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "rename"]
+            fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, );
+          }
 
-    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-    pub mod host_io {
-      #[used]
-      #[doc(hidden)]
-      static __FORCE_SECTION_REF: fn() =
-      super::super::super::__link_custom_section_describing_imports;
-      
-      use super::super::super::_rt;
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+          let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+          let result8 = match l4 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l5 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l6 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len7 = l6;
+                let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                _rt::string_lift(bytes7)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result8
+        }
+      }
       #[allow(unused_unsafe, clippy::all)]
+      /// Remove a file (or symlink).
       #[allow(async_fn_in_trait)]
-      pub fn stdout_write(bytes: &[u8],) -> (){
+      pub fn remove_file(path: &str,) -> Result<(),_rt::String>{
         unsafe {
-          let vec0 = bytes;
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
           let ptr0 = vec0.as_ptr().cast::<u8>();
           let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "remove-file"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result7 = match l3 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result7
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Remove an empty directory.
+      #[allow(async_fn_in_trait)]
+      pub fn remove_dir(path: &str,) -> Result<(),_rt::String>{
+        unsafe {
 
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
           #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
           unsafe extern "C" {
-            #[link_name = "stdout-write"]
-            fn wit_import1(_: *mut u8, _: usize, );
+            #[link_name = "remove-dir"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
           }
 
           #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import1(_: *mut u8, _: usize, ) { unreachable!() }
-          wit_import1(ptr0.cast_mut(), len0);
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result7 = match l3 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result7
         }
       }
       #[allow(unused_unsafe, clippy::all)]
+      /// Remove a directory and everything in it. Symlinks are removed, not followed.
       #[allow(async_fn_in_trait)]
-      pub fn stderr_write(bytes: &[u8],) -> (){
+      pub fn remove_dir_all(path: &str,) -> Result<(),_rt::String>{
         unsafe {
-          let vec0 = bytes;
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
           let ptr0 = vec0.as_ptr().cast::<u8>();
           let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "remove-dir-all"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result7 = match l3 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len6 = l5;
+                let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                _rt::string_lift(bytes6)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result7
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Metadata of `path` itself: a symlink is reported as `symlink`, not
+      /// as what it points at.
+      #[allow(async_fn_in_trait)]
+      pub fn stat(path: &str,) -> Result<FileInfo,_rt::String>{
+        unsafe {
 
+          #[repr(align(8))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 32]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 32]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
           #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
           unsafe extern "C" {
-            #[link_name = "stderr-write"]
-            fn wit_import1(_: *mut u8, _: usize, );
+            #[link_name = "stat"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
           }
 
           #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import1(_: *mut u8, _: usize, ) { unreachable!() }
-          wit_import1(ptr0.cast_mut(), len0);
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result10 = match l3 {
+            0 => {
+              let e = {
+                let l4 = i32::from(*ptr1.add(8).cast::<u8>());
+                let l5 = *ptr1.add(16).cast::<i64>();
+                let l6 = *ptr1.add(24).cast::<i64>();
+
+                FileInfo{
+                  kind: FileKind::_lift(l4 as u8),
+                  size: l5 as u64,
+                  modified_at: l6 as u64,
+                }
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l7 = *ptr1.add(8).cast::<*mut u8>();
+                let l8 = *ptr1.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len9 = l8;
+                let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                _rt::string_lift(bytes9)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result10
         }
       }
       #[allow(unused_unsafe, clippy::all)]
+      /// Whether `path` exists (following symlinks). Never fails.
       #[allow(async_fn_in_trait)]
-      pub fn stdout_flush() -> (){
+      pub fn exists(path: &str,) -> bool{
         unsafe {
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
 
           #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
           unsafe extern "C" {
-            #[link_name = "stdout-flush"]
-            fn wit_import0();
+            #[link_name = "exists"]
+            fn wit_import1(_: *mut u8, _: usize, ) -> i32;
           }
 
           #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import0() { unreachable!() }
-          wit_import0();
+          unsafe extern "C" fn wit_import1(_: *mut u8, _: usize, ) -> i32 { unreachable!() }
+          let ret = wit_import1(ptr0.cast_mut(), len0);
+          _rt::bool_lift(ret as u8)
         }
       }
       #[allow(unused_unsafe, clippy::all)]
+      /// Everything below `path`, depth-first with each directory's entries
+      /// sorted by name. `max-depth` 1 lists direct children only; `none` has no
+      /// limit. Symlinks are listed but never followed.
       #[allow(async_fn_in_trait)]
-      pub fn stderr_flush() -> (){
+      pub fn walk_dir(path: &str,max_depth: Option<u32>,) -> Result<_rt::Vec::<DirEntry>,_rt::String>{
         unsafe {
 
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let (result1_0,result1_1,) = match max_depth {
+            Some(e) => (1i32, _rt::as_i32(e)),
+            None => {
+              (0i32, 0i32)
+            },
+          };let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
           #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
           unsafe extern "C" {
-            #[link_name = "stderr-flush"]
-            fn wit_import0();
+            #[link_name = "walk-dir"]
+            fn wit_import3(_: *mut u8, _: usize, _: i32, _: i32, _: *mut u8, );
           }
 
           #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import0() { unreachable!() }
-          wit_import0();
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: i32, _: i32, _: *mut u8, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, result1_0, result1_1, ptr2);
+          let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+          let result15 = match l4 {
+            0 => {
+              let e = {
+                let l5 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l6 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let base11 = l5;
+                let len11 = l6;
+                let mut result11 = _rt::Vec::with_capacity(len11);
+                for i in 0..len11 {
+                  let base = base11.add(i * (3*::core::mem::size_of::<*const u8>()));
+                  let e11 = {
+                    let l7 = *base.add(0).cast::<*mut u8>();
+                    let l8 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len9 = l8;
+                    let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                    let l10 = i32::from(*base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                    DirEntry{
+                      path: _rt::string_lift(bytes9),
+                      kind: FileKind::_lift(l10 as u8),
+                    }
+                  };
+                  result11.push(e11);
+                }
+                _rt::cabi_dealloc(base11, len11 * (3*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+
+                result11
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l12 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l13 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len14 = l13;
+                let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                _rt::string_lift(bytes14)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result15
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Append `contents` to `path`, creating it if missing.
+      #[allow(async_fn_in_trait)]
+      pub fn append_file(path: &str,contents: &[u8],) -> Result<(),_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let vec1 = contents;
+          let ptr1 = vec1.as_ptr().cast::<u8>();
+          let len1 = vec1.len();
+          let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "append-file"]
+            fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+          let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+          let result8 = match l4 {
+            0 => {
+              let e = ();
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l5 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l6 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len7 = l6;
+                let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                _rt::string_lift(bytes7)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result8
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Open `path` for incremental writes, creating it if missing. Without
+      /// `append` it is truncated first.
+      #[allow(async_fn_in_trait)]
+      pub fn open_writer(path: &str,append: bool,) -> Result<Writer,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+          let vec0 = path;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "open-writer"]
+            fn wit_import2(_: *mut u8, _: usize, _: i32, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: i32, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, match &append { true => 1, false => 0 }, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result8 = match l3 {
+            0 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<i32>();
+
+                Writer::from_handle(l4 as u32)
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l5 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l6 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len7 = l6;
+                let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                _rt::string_lift(bytes7)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result8
+        }
+      }
+      impl Writer {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Write all of `bytes` after what was written so far.
+        #[allow(async_fn_in_trait)]
+        pub fn write(&self,bytes: &[u8],) -> Result<(),_rt::String>{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+            let vec0 = bytes;
+            let ptr0 = vec0.as_ptr().cast::<u8>();
+            let len0 = vec0.len();
+            let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+            unsafe extern "C" {
+              #[link_name = "[method]writer.write"]
+              fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+            wit_import2((self).handle() as i32, ptr0.cast_mut(), len0, ptr1);
+            let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+            let result7 = match l3 {
+              0 => {
+                let e = ();
+                Ok(e)
+              }
+              1 => {
+                let e = {
+                  let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len6 = l5;
+                  let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                  _rt::string_lift(bytes6)
+                };
+                Err(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            };
+            result7
+          }
+        }
+      }
+      impl Writer {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Flush and close. Writing afterwards fails; closing again does nothing.
+        #[allow(async_fn_in_trait)]
+        pub fn close(&self,) -> Result<(),_rt::String>{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+            let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+            unsafe extern "C" {
+              #[link_name = "[method]writer.close"]
+              fn wit_import1(_: i32, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import1(_: i32, _: *mut u8, ) { unreachable!() }
+            wit_import1((self).handle() as i32, ptr0);
+            let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+            let result6 = match l2 {
+              0 => {
+                let e = ();
+                Ok(e)
+              }
+              1 => {
+                let e = {
+                  let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len5 = l4;
+                  let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+
+                  _rt::string_lift(bytes5)
+                };
+                Err(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            };
+            result6
+          }
         }
       }
 
@@ -232,6 +1708,12 @@ pub mod wacli {
           f.debug_struct("CommandMeta").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).finish()
         }
       }
+      /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+      /// the same case count, so older plugins would stop composing). Until a
+      /// new major version adds an exit-code case, a `failed` message of the
+      /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+      /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+      /// `unknown-command` with 127 and `failed` / `io` with 1.
       #[derive(Clone)]
       pub enum CommandError {
         UnknownCommand(_rt::String),
@@ -305,9 +1787,23 @@ pub mod wacli {
         pub summary: _rt::String,
         pub path: _rt::String,
       }
-      impl ::core::fmt::Debug for PipeInfo {
+      impl ::core::fmt::Debug for PipeInfo {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("PipeInfo").field("name", &self.name).field("summary", &self.summary).field("path", &self.path).finish()
+        }
+      }
+      /// A discovered pipe with the descriptive fields it embeds in its
+      /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+      #[derive(Clone)]
+      pub struct PipeDetails {
+        pub info: PipeInfo,
+        pub input_types: _rt::Vec::<_rt::String>,
+        pub output_type: _rt::String,
+        pub version: _rt::String,
+      }
+      impl ::core::fmt::Debug for PipeDetails {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("PipeInfo").field("name", &self.name).field("summary", &self.summary).field("path", &self.path).finish()
+          f.debug_struct("PipeDetails").field("info", &self.info).field("input-types", &self.input_types).field("output-type", &self.output_type).field("version", &self.version).finish()
         }
       }
 
@@ -691,10 +2187,64 @@ pub mod wacli {
         pub conflicts_with: _rt::Vec::<_rt::String>,
         pub requires: _rt::Vec::<_rt::String>,
         pub hidden: bool,
+        /// Registry command that prints completion candidates for this arg's
+        /// value, one per line; it receives the word being completed as its
+        /// only argument (`my-cli __complete`).
+        pub complete_command: Option<_rt::String>,
+        /// Name of the `arg-group` this arg belongs to.
+        pub group: Option<_rt::String>,
+        /// The value may start with `-`: the next token is always taken as the
+        /// value, even when it names a flag.
+        pub allow_hyphen_values: bool,
+        /// A boolean flag that counts its occurrences (`-vvv`); help shows it as
+        /// `-v...` and it may repeat even when not `multiple`.
+        pub countable: bool,
+        /// On the last positional: take every remaining positional token
+        /// (`[FILES]...`) instead of leaving extras to the plugin.
+        pub var_arg: bool,
       }
       impl ::core::fmt::Debug for ArgSchema {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("ArgSchema").field("name", &self.name).field("short", &self.short).field("long", &self.long).field("help", &self.help).field("required", &self.required).field("default-value", &self.default_value).field("env", &self.env).field("value-name", &self.value_name).field("takes-value", &self.takes_value).field("multiple", &self.multiple).field("value-type", &self.value_type).field("possible-values", &self.possible_values).field("conflicts-with", &self.conflicts_with).field("requires", &self.requires).field("hidden", &self.hidden).finish()
+          f.debug_struct("ArgSchema").field("name", &self.name).field("short", &self.short).field("long", &self.long).field("help", &self.help).field("required", &self.required).field("default-value", &self.default_value).field("env", &self.env).field("value-name", &self.value_name).field("takes-value", &self.takes_value).field("multiple", &self.multiple).field("value-type", &self.value_type).field("possible-values", &self.possible_values).field("conflicts-with", &self.conflicts_with).field("requires", &self.requires).field("hidden", &self.hidden).field("complete-command", &self.complete_command).field("group", &self.group).field("allow-hyphen-values", &self.allow_hyphen_values).field("countable", &self.countable).field("var-arg", &self.var_arg).finish()
+        }
+      }
+      /// A named set of args validated together, e.g. exactly one of
+      /// `--json`/`--yaml`/`--toml`. A group args name without the command
+      /// declaring it is neither required nor multiple.
+      #[derive(Clone)]
+      pub struct ArgGroup {
+        pub name: _rt::String,
+        /// At least one member must be given in argv.
+        pub required: bool,
+        /// More than one member may be given.
+        pub multiple: bool,
+      }
+      impl ::core::fmt::Debug for ArgGroup {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("ArgGroup").field("name", &self.name).field("required", &self.required).field("multiple", &self.multiple).finish()
+        }
+      }
+      /// A subcommand of a `command-schema`, selected by the first positional
+      /// token in argv.
+      #[derive(Clone)]
+      pub struct SubcommandSchema {
+        /// Names from the first-level subcommand down, e.g. `["remote", "add"]`.
+        pub path: _rt::Vec::<_rt::String>,
+        pub summary: _rt::String,
+        pub usage: _rt::String,
+        pub aliases: _rt::Vec::<_rt::String>,
+        /// Empty to inherit the parent's version.
+        pub version: _rt::String,
+        pub hidden: bool,
+        pub description: _rt::String,
+        pub examples: _rt::Vec::<_rt::String>,
+        pub args: _rt::Vec::<ArgSchema>,
+        pub groups: _rt::Vec::<ArgGroup>,
+        pub hidden_aliases: _rt::Vec::<_rt::String>,
+      }
+      impl ::core::fmt::Debug for SubcommandSchema {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("SubcommandSchema").field("path", &self.path).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).field("groups", &self.groups).field("hidden-aliases", &self.hidden_aliases).finish()
         }
       }
       #[derive(Clone)]
@@ -708,10 +2258,17 @@ pub mod wacli {
         pub description: _rt::String,
         pub examples: _rt::Vec::<_rt::String>,
         pub args: _rt::Vec::<ArgSchema>,
+        /// Expand `@path` tokens in argv into the lines of `path` before parsing.
+        pub allow_arg_files: bool,
+        /// Nested commands, flattened depth-first (records cannot be recursive).
+        pub subcommands: _rt::Vec::<SubcommandSchema>,
+        pub groups: _rt::Vec::<ArgGroup>,
+        /// Aliases that dispatch like `aliases` but are not shown in help.
+        pub hidden_aliases: _rt::Vec::<_rt::String>,
       }
       impl ::core::fmt::Debug for CommandSchema {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("CommandSchema").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).finish()
+          f.debug_struct("CommandSchema").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).field("allow-arg-files", &self.allow_arg_files).field("subcommands", &self.subcommands).field("groups", &self.groups).field("hidden-aliases", &self.hidden_aliases).finish()
         }
       }
 
@@ -726,7 +2283,25 @@ pub mod wacli {
       super::super::super::__link_custom_section_describing_imports;
       
       use super::super::super::_rt;
+      pub type ArgSchema = super::super::super::wacli::cli::schema::ArgSchema;
       pub type CommandSchema = super::super::super::wacli::cli::schema::CommandSchema;
+      /// Build provenance recorded by the builder (wacli).
+      ///
+      /// Rendered by core for `--version --build-info` (or `-V --verbose`).
+      #[derive(Clone)]
+      pub struct BuildInfo {
+        /// Version of wacli that generated the registry.
+        pub wacli_version: _rt::String,
+        /// Source revision, only when explicitly provided to `wacli build`.
+        pub git_revision: Option<_rt::String>,
+        /// Extra `key: value` build facts (e.g. an opt-in build timestamp).
+        pub built_with: _rt::Vec::<_rt::String>,
+      }
+      impl ::core::fmt::Debug for BuildInfo {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("BuildInfo").field("wacli-version", &self.wacli_version).field("git-revision", &self.git_revision).field("built-with", &self.built_with).finish()
+        }
+      }
       /// App-level metadata, provided by the builder (wacli).
       ///
       /// This is used by core to render global `--help/--version` consistently.
@@ -735,10 +2310,43 @@ pub mod wacli {
         pub name: _rt::String,
         pub version: _rt::String,
         pub description: _rt::String,
+        pub build_info: BuildInfo,
+        /// Hidden command that core runs after each command with a JSON usage
+        /// event (command name, exit code, duration; never arguments). Users opt
+        /// out with `<APP>_NO_TELEMETRY`.
+        pub telemetry_command: Option<_rt::String>,
+        /// Command core runs for an unknown command `foo`, with `foo` and the
+        /// rest of argv as its arguments. When it reports `foo` as unknown too,
+        /// core prints the usual unknown-command error.
+        pub fallback_command: Option<_rt::String>,
+        /// Show an interactive command picker instead of the global help when
+        /// the app runs with no arguments on a terminal.
+        pub interactive_picker: bool,
+        /// Warn on stderr when an arg's environment variable is unset but a
+        /// near-miss of its name (another case, a typo) is set.
+        pub env_warnings: bool,
+        /// Options every command accepts (`build.globalArgs`). Core takes them
+        /// out of the command's argv and sets `WACLI_GLOBAL_<NAME>` instead.
+        pub global_args: _rt::Vec::<ArgSchema>,
       }
       impl ::core::fmt::Debug for AppMeta {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("AppMeta").field("name", &self.name).field("version", &self.version).field("description", &self.description).finish()
+          f.debug_struct("AppMeta").field("name", &self.name).field("version", &self.version).field("description", &self.description).field("build-info", &self.build_info).field("telemetry-command", &self.telemetry_command).field("fallback-command", &self.fallback_command).field("interactive-picker", &self.interactive_picker).field("env-warnings", &self.env_warnings).field("global-args", &self.global_args).finish()
+        }
+      }
+      /// A named group of commands, declared in the build manifest (`build.groups`).
+      ///
+      /// Member commands are listed by `list-schemas` under `<group>/<command>`
+      /// (e.g. `db/migrate`) and invoked as `<group> <command>`.
+      #[derive(Clone)]
+      pub struct CommandGroup {
+        pub name: _rt::String,
+        pub summary: _rt::String,
+        pub description: _rt::String,
+      }
+      impl ::core::fmt::Debug for CommandGroup {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("CommandGroup").field("name", &self.name).field("summary", &self.summary).field("description", &self.description).finish()
         }
       }
       #[allow(unused_unsafe, clippy::all)]
@@ -749,8 +2357,8 @@ pub mod wacli {
 
           #[cfg_attr(target_pointer_width="64", repr(align(8)))]
           #[cfg_attr(target_pointer_width="32", repr(align(4)))]
-          struct RetArea([::core::mem::MaybeUninit::<u8>; 6*::core::mem::size_of::<*const u8>()]);
-          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 6*::core::mem::size_of::<*const u8>()]);
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 22*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 22*::core::mem::size_of::<*const u8>()]);
           let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
           #[cfg(target_arch = "wasm32")]
           #[link(wasm_import_module = "wacli:cli/registry-schema@2.0.0")]
@@ -774,12 +2382,370 @@ pub mod wacli {
           let l9 = *ptr0.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
           let len10 = l9;
           let bytes10 = _rt::Vec::from_raw_parts(l8.cast(), len10, len10);
-          let result11 = AppMeta{
+          let l11 = *ptr0.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l12 = *ptr0.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let len13 = l12;
+          let bytes13 = _rt::Vec::from_raw_parts(l11.cast(), len13, len13);
+          let l14 = i32::from(*ptr0.add(8*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          let l18 = *ptr0.add(11*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l19 = *ptr0.add(12*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base23 = l18;
+          let len23 = l19;
+          let mut result23 = _rt::Vec::with_capacity(len23);
+          for i in 0..len23 {
+            let base = base23.add(i * (2*::core::mem::size_of::<*const u8>()));
+            let e23 = {
+              let l20 = *base.add(0).cast::<*mut u8>();
+              let l21 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len22 = l21;
+              let bytes22 = _rt::Vec::from_raw_parts(l20.cast(), len22, len22);
+
+              _rt::string_lift(bytes22)
+            };
+            result23.push(e23);
+          }
+          _rt::cabi_dealloc(base23, len23 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let l24 = i32::from(*ptr0.add(13*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          let l28 = i32::from(*ptr0.add(16*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          let l32 = i32::from(*ptr0.add(19*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          let l33 = i32::from(*ptr0.add(1+19*::core::mem::size_of::<*const u8>()).cast::<u8>());
+          let l34 = *ptr0.add(20*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l35 = *ptr0.add(21*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base99 = l34;
+          let len99 = l35;
+          let mut result99 = _rt::Vec::with_capacity(len99);
+          for i in 0..len99 {
+            let base = base99.add(i * (38*::core::mem::size_of::<*const u8>()));
+            let e99 = {
+              let l36 = *base.add(0).cast::<*mut u8>();
+              let l37 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len38 = l37;
+              let bytes38 = _rt::Vec::from_raw_parts(l36.cast(), len38, len38);
+              let l39 = i32::from(*base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l43 = i32::from(*base.add(5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l47 = *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l48 = *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len49 = l48;
+              let bytes49 = _rt::Vec::from_raw_parts(l47.cast(), len49, len49);
+              let l50 = i32::from(*base.add(10*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l51 = i32::from(*base.add(11*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l55 = i32::from(*base.add(14*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l59 = i32::from(*base.add(17*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l63 = i32::from(*base.add(20*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l64 = i32::from(*base.add(1+20*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l65 = i32::from(*base.add(21*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l69 = *base.add(24*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l70 = *base.add(25*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base74 = l69;
+              let len74 = l70;
+              let mut result74 = _rt::Vec::with_capacity(len74);
+              for i in 0..len74 {
+                let base = base74.add(i * (2*::core::mem::size_of::<*const u8>()));
+                let e74 = {
+                  let l71 = *base.add(0).cast::<*mut u8>();
+                  let l72 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len73 = l72;
+                  let bytes73 = _rt::Vec::from_raw_parts(l71.cast(), len73, len73);
+
+                  _rt::string_lift(bytes73)
+                };
+                result74.push(e74);
+              }
+              _rt::cabi_dealloc(base74, len74 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l75 = *base.add(26*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l76 = *base.add(27*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base80 = l75;
+              let len80 = l76;
+              let mut result80 = _rt::Vec::with_capacity(len80);
+              for i in 0..len80 {
+                let base = base80.add(i * (2*::core::mem::size_of::<*const u8>()));
+                let e80 = {
+                  let l77 = *base.add(0).cast::<*mut u8>();
+                  let l78 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len79 = l78;
+                  let bytes79 = _rt::Vec::from_raw_parts(l77.cast(), len79, len79);
+
+                  _rt::string_lift(bytes79)
+                };
+                result80.push(e80);
+              }
+              _rt::cabi_dealloc(base80, len80 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l81 = *base.add(28*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l82 = *base.add(29*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base86 = l81;
+              let len86 = l82;
+              let mut result86 = _rt::Vec::with_capacity(len86);
+              for i in 0..len86 {
+                let base = base86.add(i * (2*::core::mem::size_of::<*const u8>()));
+                let e86 = {
+                  let l83 = *base.add(0).cast::<*mut u8>();
+                  let l84 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len85 = l84;
+                  let bytes85 = _rt::Vec::from_raw_parts(l83.cast(), len85, len85);
+
+                  _rt::string_lift(bytes85)
+                };
+                result86.push(e86);
+              }
+              _rt::cabi_dealloc(base86, len86 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l87 = i32::from(*base.add(30*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l88 = i32::from(*base.add(31*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l92 = i32::from(*base.add(34*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l96 = i32::from(*base.add(37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l97 = i32::from(*base.add(1+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l98 = i32::from(*base.add(2+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+              super::super::super::wacli::cli::schema::ArgSchema{
+                name: _rt::string_lift(bytes38),
+                short: match l39 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l40 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l41 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len42 = l41;
+                      let bytes42 = _rt::Vec::from_raw_parts(l40.cast(), len42, len42);
+
+                      _rt::string_lift(bytes42)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                long: match l43 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l44 = *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l45 = *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len46 = l45;
+                      let bytes46 = _rt::Vec::from_raw_parts(l44.cast(), len46, len46);
+
+                      _rt::string_lift(bytes46)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                help: _rt::string_lift(bytes49),
+                required: _rt::bool_lift(l50 as u8),
+                default_value: match l51 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l52 = *base.add(12*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l53 = *base.add(13*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len54 = l53;
+                      let bytes54 = _rt::Vec::from_raw_parts(l52.cast(), len54, len54);
+
+                      _rt::string_lift(bytes54)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                env: match l55 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l56 = *base.add(15*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l57 = *base.add(16*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len58 = l57;
+                      let bytes58 = _rt::Vec::from_raw_parts(l56.cast(), len58, len58);
+
+                      _rt::string_lift(bytes58)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                value_name: match l59 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l60 = *base.add(18*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l61 = *base.add(19*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len62 = l61;
+                      let bytes62 = _rt::Vec::from_raw_parts(l60.cast(), len62, len62);
+
+                      _rt::string_lift(bytes62)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                takes_value: _rt::bool_lift(l63 as u8),
+                multiple: _rt::bool_lift(l64 as u8),
+                value_type: match l65 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l66 = *base.add(22*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l67 = *base.add(23*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len68 = l67;
+                      let bytes68 = _rt::Vec::from_raw_parts(l66.cast(), len68, len68);
+
+                      _rt::string_lift(bytes68)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                possible_values: result74,
+                conflicts_with: result80,
+                requires: result86,
+                hidden: _rt::bool_lift(l87 as u8),
+                complete_command: match l88 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l89 = *base.add(32*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l90 = *base.add(33*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len91 = l90;
+                      let bytes91 = _rt::Vec::from_raw_parts(l89.cast(), len91, len91);
+
+                      _rt::string_lift(bytes91)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                group: match l92 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l93 = *base.add(35*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l94 = *base.add(36*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len95 = l94;
+                      let bytes95 = _rt::Vec::from_raw_parts(l93.cast(), len95, len95);
+
+                      _rt::string_lift(bytes95)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                },
+                allow_hyphen_values: _rt::bool_lift(l96 as u8),
+                countable: _rt::bool_lift(l97 as u8),
+                var_arg: _rt::bool_lift(l98 as u8),
+              }
+            };
+            result99.push(e99);
+          }
+          _rt::cabi_dealloc(base99, len99 * (38*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result100 = AppMeta{
             name: _rt::string_lift(bytes4),
             version: _rt::string_lift(bytes7),
             description: _rt::string_lift(bytes10),
+            build_info: BuildInfo{
+              wacli_version: _rt::string_lift(bytes13),
+              git_revision: match l14 {
+                0 => None,
+                1 => {
+                  let e = {
+                    let l15 = *ptr0.add(9*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                    let l16 = *ptr0.add(10*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                    let len17 = l16;
+                    let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                    _rt::string_lift(bytes17)
+                  };
+                  Some(e)
+                }
+                _ => _rt::invalid_enum_discriminant(),
+              },
+              built_with: result23,
+            },
+            telemetry_command: match l24 {
+              0 => None,
+              1 => {
+                let e = {
+                  let l25 = *ptr0.add(14*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l26 = *ptr0.add(15*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len27 = l26;
+                  let bytes27 = _rt::Vec::from_raw_parts(l25.cast(), len27, len27);
+
+                  _rt::string_lift(bytes27)
+                };
+                Some(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            },
+            fallback_command: match l28 {
+              0 => None,
+              1 => {
+                let e = {
+                  let l29 = *ptr0.add(17*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l30 = *ptr0.add(18*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len31 = l30;
+                  let bytes31 = _rt::Vec::from_raw_parts(l29.cast(), len31, len31);
+
+                  _rt::string_lift(bytes31)
+                };
+                Some(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            },
+            interactive_picker: _rt::bool_lift(l32 as u8),
+            env_warnings: _rt::bool_lift(l33 as u8),
+            global_args: result99,
           };
-          result11
+          result100
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Return command groups, in declaration order.
+      #[allow(async_fn_in_trait)]
+      pub fn list_groups() -> _rt::Vec::<CommandGroup>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/registry-schema@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "list-groups"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = *ptr0.add(0).cast::<*mut u8>();
+          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base13 = l2;
+          let len13 = l3;
+          let mut result13 = _rt::Vec::with_capacity(len13);
+          for i in 0..len13 {
+            let base = base13.add(i * (6*::core::mem::size_of::<*const u8>()));
+            let e13 = {
+              let l4 = *base.add(0).cast::<*mut u8>();
+              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len6 = l5;
+              let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+              let l7 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l8 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len9 = l8;
+              let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+              let l10 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l11 = *base.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len12 = l11;
+              let bytes12 = _rt::Vec::from_raw_parts(l10.cast(), len12, len12);
+
+              CommandGroup{
+                name: _rt::string_lift(bytes6),
+                summary: _rt::string_lift(bytes9),
+                description: _rt::string_lift(bytes12),
+              }
+            };
+            result13.push(e13);
+          }
+          _rt::cabi_dealloc(base13, len13 * (6*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result14 = result13;
+          result14
         }
       }
       #[allow(unused_unsafe, clippy::all)]
@@ -807,12 +2773,12 @@ pub mod wacli {
           wit_import1(ptr0);
           let l2 = *ptr0.add(0).cast::<*mut u8>();
           let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
-          let base87 = l2;
-          let len87 = l3;
-          let mut result87 = _rt::Vec::with_capacity(len87);
-          for i in 0..len87 {
-            let base = base87.add(i * (17*::core::mem::size_of::<*const u8>()));
-            let e87 = {
+          let base227 = l2;
+          let len227 = l3;
+          let mut result227 = _rt::Vec::with_capacity(len227);
+          for i in 0..len227 {
+            let base = base227.add(i * (24*::core::mem::size_of::<*const u8>()));
+            let e227 = {
               let l4 = *base.add(0).cast::<*mut u8>();
               let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
               let len6 = l5;
@@ -872,12 +2838,12 @@ pub mod wacli {
               _rt::cabi_dealloc(base31, len31 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
               let l32 = *base.add(15*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
               let l33 = *base.add(16*::core::mem::size_of::<*const u8>()).cast::<usize>();
-              let base86 = l32;
-              let len86 = l33;
-              let mut result86 = _rt::Vec::with_capacity(len86);
-              for i in 0..len86 {
-                let base = base86.add(i * (31*::core::mem::size_of::<*const u8>()));
-                let e86 = {
+              let base97 = l32;
+              let len97 = l33;
+              let mut result97 = _rt::Vec::with_capacity(len97);
+              for i in 0..len97 {
+                let base = base97.add(i * (38*::core::mem::size_of::<*const u8>()));
+                let e97 = {
                   let l34 = *base.add(0).cast::<*mut u8>();
                   let l35 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
                   let len36 = l35;
@@ -950,6 +2916,11 @@ pub mod wacli {
                   }
                   _rt::cabi_dealloc(base84, len84 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
                   let l85 = i32::from(*base.add(30*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l86 = i32::from(*base.add(31*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l90 = i32::from(*base.add(34*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l94 = i32::from(*base.add(37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l95 = i32::from(*base.add(1+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l96 = i32::from(*base.add(2+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
 
                   super::super::super::wacli::cli::schema::ArgSchema{
                     name: _rt::string_lift(bytes36),
@@ -1051,11 +3022,450 @@ pub mod wacli {
                     conflicts_with: result78,
                     requires: result84,
                     hidden: _rt::bool_lift(l85 as u8),
+                    complete_command: match l86 {
+                      0 => None,
+                      1 => {
+                        let e = {
+                          let l87 = *base.add(32*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                          let l88 = *base.add(33*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                          let len89 = l88;
+                          let bytes89 = _rt::Vec::from_raw_parts(l87.cast(), len89, len89);
+
+                          _rt::string_lift(bytes89)
+                        };
+                        Some(e)
+                      }
+                      _ => _rt::invalid_enum_discriminant(),
+                    },
+                    group: match l90 {
+                      0 => None,
+                      1 => {
+                        let e = {
+                          let l91 = *base.add(35*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                          let l92 = *base.add(36*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                          let len93 = l92;
+                          let bytes93 = _rt::Vec::from_raw_parts(l91.cast(), len93, len93);
+
+                          _rt::string_lift(bytes93)
+                        };
+                        Some(e)
+                      }
+                      _ => _rt::invalid_enum_discriminant(),
+                    },
+                    allow_hyphen_values: _rt::bool_lift(l94 as u8),
+                    countable: _rt::bool_lift(l95 as u8),
+                    var_arg: _rt::bool_lift(l96 as u8),
                   }
                 };
-                result86.push(e86);
+                result97.push(e97);
+              }
+              _rt::cabi_dealloc(base97, len97 * (38*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l98 = i32::from(*base.add(17*::core::mem::size_of::<*const u8>()).cast::<u8>());
+              let l99 = *base.add(18*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l100 = *base.add(19*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base212 = l99;
+              let len212 = l100;
+              let mut result212 = _rt::Vec::with_capacity(len212);
+              for i in 0..len212 {
+                let base = base212.add(i * (21*::core::mem::size_of::<*const u8>()));
+                let e212 = {
+                  let l101 = *base.add(0).cast::<*mut u8>();
+                  let l102 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base106 = l101;
+                  let len106 = l102;
+                  let mut result106 = _rt::Vec::with_capacity(len106);
+                  for i in 0..len106 {
+                    let base = base106.add(i * (2*::core::mem::size_of::<*const u8>()));
+                    let e106 = {
+                      let l103 = *base.add(0).cast::<*mut u8>();
+                      let l104 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len105 = l104;
+                      let bytes105 = _rt::Vec::from_raw_parts(l103.cast(), len105, len105);
+
+                      _rt::string_lift(bytes105)
+                    };
+                    result106.push(e106);
+                  }
+                  _rt::cabi_dealloc(base106, len106 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                  let l107 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l108 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len109 = l108;
+                  let bytes109 = _rt::Vec::from_raw_parts(l107.cast(), len109, len109);
+                  let l110 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l111 = *base.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len112 = l111;
+                  let bytes112 = _rt::Vec::from_raw_parts(l110.cast(), len112, len112);
+                  let l113 = *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l114 = *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base118 = l113;
+                  let len118 = l114;
+                  let mut result118 = _rt::Vec::with_capacity(len118);
+                  for i in 0..len118 {
+                    let base = base118.add(i * (2*::core::mem::size_of::<*const u8>()));
+                    let e118 = {
+                      let l115 = *base.add(0).cast::<*mut u8>();
+                      let l116 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len117 = l116;
+                      let bytes117 = _rt::Vec::from_raw_parts(l115.cast(), len117, len117);
+
+                      _rt::string_lift(bytes117)
+                    };
+                    result118.push(e118);
+                  }
+                  _rt::cabi_dealloc(base118, len118 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                  let l119 = *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l120 = *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len121 = l120;
+                  let bytes121 = _rt::Vec::from_raw_parts(l119.cast(), len121, len121);
+                  let l122 = i32::from(*base.add(10*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l123 = *base.add(11*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l124 = *base.add(12*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len125 = l124;
+                  let bytes125 = _rt::Vec::from_raw_parts(l123.cast(), len125, len125);
+                  let l126 = *base.add(13*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l127 = *base.add(14*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base131 = l126;
+                  let len131 = l127;
+                  let mut result131 = _rt::Vec::with_capacity(len131);
+                  for i in 0..len131 {
+                    let base = base131.add(i * (2*::core::mem::size_of::<*const u8>()));
+                    let e131 = {
+                      let l128 = *base.add(0).cast::<*mut u8>();
+                      let l129 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len130 = l129;
+                      let bytes130 = _rt::Vec::from_raw_parts(l128.cast(), len130, len130);
+
+                      _rt::string_lift(bytes130)
+                    };
+                    result131.push(e131);
+                  }
+                  _rt::cabi_dealloc(base131, len131 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                  let l132 = *base.add(15*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l133 = *base.add(16*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base197 = l132;
+                  let len197 = l133;
+                  let mut result197 = _rt::Vec::with_capacity(len197);
+                  for i in 0..len197 {
+                    let base = base197.add(i * (38*::core::mem::size_of::<*const u8>()));
+                    let e197 = {
+                      let l134 = *base.add(0).cast::<*mut u8>();
+                      let l135 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len136 = l135;
+                      let bytes136 = _rt::Vec::from_raw_parts(l134.cast(), len136, len136);
+                      let l137 = i32::from(*base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l141 = i32::from(*base.add(5*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l145 = *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l146 = *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len147 = l146;
+                      let bytes147 = _rt::Vec::from_raw_parts(l145.cast(), len147, len147);
+                      let l148 = i32::from(*base.add(10*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l149 = i32::from(*base.add(11*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l153 = i32::from(*base.add(14*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l157 = i32::from(*base.add(17*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l161 = i32::from(*base.add(20*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l162 = i32::from(*base.add(1+20*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l163 = i32::from(*base.add(21*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l167 = *base.add(24*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l168 = *base.add(25*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let base172 = l167;
+                      let len172 = l168;
+                      let mut result172 = _rt::Vec::with_capacity(len172);
+                      for i in 0..len172 {
+                        let base = base172.add(i * (2*::core::mem::size_of::<*const u8>()));
+                        let e172 = {
+                          let l169 = *base.add(0).cast::<*mut u8>();
+                          let l170 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                          let len171 = l170;
+                          let bytes171 = _rt::Vec::from_raw_parts(l169.cast(), len171, len171);
+
+                          _rt::string_lift(bytes171)
+                        };
+                        result172.push(e172);
+                      }
+                      _rt::cabi_dealloc(base172, len172 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                      let l173 = *base.add(26*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l174 = *base.add(27*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let base178 = l173;
+                      let len178 = l174;
+                      let mut result178 = _rt::Vec::with_capacity(len178);
+                      for i in 0..len178 {
+                        let base = base178.add(i * (2*::core::mem::size_of::<*const u8>()));
+                        let e178 = {
+                          let l175 = *base.add(0).cast::<*mut u8>();
+                          let l176 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                          let len177 = l176;
+                          let bytes177 = _rt::Vec::from_raw_parts(l175.cast(), len177, len177);
+
+                          _rt::string_lift(bytes177)
+                        };
+                        result178.push(e178);
+                      }
+                      _rt::cabi_dealloc(base178, len178 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                      let l179 = *base.add(28*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l180 = *base.add(29*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let base184 = l179;
+                      let len184 = l180;
+                      let mut result184 = _rt::Vec::with_capacity(len184);
+                      for i in 0..len184 {
+                        let base = base184.add(i * (2*::core::mem::size_of::<*const u8>()));
+                        let e184 = {
+                          let l181 = *base.add(0).cast::<*mut u8>();
+                          let l182 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                          let len183 = l182;
+                          let bytes183 = _rt::Vec::from_raw_parts(l181.cast(), len183, len183);
+
+                          _rt::string_lift(bytes183)
+                        };
+                        result184.push(e184);
+                      }
+                      _rt::cabi_dealloc(base184, len184 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                      let l185 = i32::from(*base.add(30*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l186 = i32::from(*base.add(31*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l190 = i32::from(*base.add(34*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l194 = i32::from(*base.add(37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l195 = i32::from(*base.add(1+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l196 = i32::from(*base.add(2+37*::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                      super::super::super::wacli::cli::schema::ArgSchema{
+                        name: _rt::string_lift(bytes136),
+                        short: match l137 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l138 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l139 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len140 = l139;
+                              let bytes140 = _rt::Vec::from_raw_parts(l138.cast(), len140, len140);
+
+                              _rt::string_lift(bytes140)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        long: match l141 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l142 = *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l143 = *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len144 = l143;
+                              let bytes144 = _rt::Vec::from_raw_parts(l142.cast(), len144, len144);
+
+                              _rt::string_lift(bytes144)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        help: _rt::string_lift(bytes147),
+                        required: _rt::bool_lift(l148 as u8),
+                        default_value: match l149 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l150 = *base.add(12*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l151 = *base.add(13*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len152 = l151;
+                              let bytes152 = _rt::Vec::from_raw_parts(l150.cast(), len152, len152);
+
+                              _rt::string_lift(bytes152)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        env: match l153 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l154 = *base.add(15*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l155 = *base.add(16*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len156 = l155;
+                              let bytes156 = _rt::Vec::from_raw_parts(l154.cast(), len156, len156);
+
+                              _rt::string_lift(bytes156)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        value_name: match l157 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l158 = *base.add(18*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l159 = *base.add(19*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len160 = l159;
+                              let bytes160 = _rt::Vec::from_raw_parts(l158.cast(), len160, len160);
+
+                              _rt::string_lift(bytes160)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        takes_value: _rt::bool_lift(l161 as u8),
+                        multiple: _rt::bool_lift(l162 as u8),
+                        value_type: match l163 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l164 = *base.add(22*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l165 = *base.add(23*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len166 = l165;
+                              let bytes166 = _rt::Vec::from_raw_parts(l164.cast(), len166, len166);
+
+                              _rt::string_lift(bytes166)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        possible_values: result172,
+                        conflicts_with: result178,
+                        requires: result184,
+                        hidden: _rt::bool_lift(l185 as u8),
+                        complete_command: match l186 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l187 = *base.add(32*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l188 = *base.add(33*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len189 = l188;
+                              let bytes189 = _rt::Vec::from_raw_parts(l187.cast(), len189, len189);
+
+                              _rt::string_lift(bytes189)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        group: match l190 {
+                          0 => None,
+                          1 => {
+                            let e = {
+                              let l191 = *base.add(35*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                              let l192 = *base.add(36*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                              let len193 = l192;
+                              let bytes193 = _rt::Vec::from_raw_parts(l191.cast(), len193, len193);
+
+                              _rt::string_lift(bytes193)
+                            };
+                            Some(e)
+                          }
+                          _ => _rt::invalid_enum_discriminant(),
+                        },
+                        allow_hyphen_values: _rt::bool_lift(l194 as u8),
+                        countable: _rt::bool_lift(l195 as u8),
+                        var_arg: _rt::bool_lift(l196 as u8),
+                      }
+                    };
+                    result197.push(e197);
+                  }
+                  _rt::cabi_dealloc(base197, len197 * (38*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                  let l198 = *base.add(17*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l199 = *base.add(18*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base205 = l198;
+                  let len205 = l199;
+                  let mut result205 = _rt::Vec::with_capacity(len205);
+                  for i in 0..len205 {
+                    let base = base205.add(i * (3*::core::mem::size_of::<*const u8>()));
+                    let e205 = {
+                      let l200 = *base.add(0).cast::<*mut u8>();
+                      let l201 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len202 = l201;
+                      let bytes202 = _rt::Vec::from_raw_parts(l200.cast(), len202, len202);
+                      let l203 = i32::from(*base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                      let l204 = i32::from(*base.add(1+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                      super::super::super::wacli::cli::schema::ArgGroup{
+                        name: _rt::string_lift(bytes202),
+                        required: _rt::bool_lift(l203 as u8),
+                        multiple: _rt::bool_lift(l204 as u8),
+                      }
+                    };
+                    result205.push(e205);
+                  }
+                  _rt::cabi_dealloc(base205, len205 * (3*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+                  let l206 = *base.add(19*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l207 = *base.add(20*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let base211 = l206;
+                  let len211 = l207;
+                  let mut result211 = _rt::Vec::with_capacity(len211);
+                  for i in 0..len211 {
+                    let base = base211.add(i * (2*::core::mem::size_of::<*const u8>()));
+                    let e211 = {
+                      let l208 = *base.add(0).cast::<*mut u8>();
+                      let l209 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len210 = l209;
+                      let bytes210 = _rt::Vec::from_raw_parts(l208.cast(), len210, len210);
+
+                      _rt::string_lift(bytes210)
+                    };
+                    result211.push(e211);
+                  }
+                  _rt::cabi_dealloc(base211, len211 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+
+                  super::super::super::wacli::cli::schema::SubcommandSchema{
+                    path: result106,
+                    summary: _rt::string_lift(bytes109),
+                    usage: _rt::string_lift(bytes112),
+                    aliases: result118,
+                    version: _rt::string_lift(bytes121),
+                    hidden: _rt::bool_lift(l122 as u8),
+                    description: _rt::string_lift(bytes125),
+                    examples: result131,
+                    args: result197,
+                    groups: result205,
+                    hidden_aliases: result211,
+                  }
+                };
+                result212.push(e212);
+              }
+              _rt::cabi_dealloc(base212, len212 * (21*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l213 = *base.add(20*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l214 = *base.add(21*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base220 = l213;
+              let len220 = l214;
+              let mut result220 = _rt::Vec::with_capacity(len220);
+              for i in 0..len220 {
+                let base = base220.add(i * (3*::core::mem::size_of::<*const u8>()));
+                let e220 = {
+                  let l215 = *base.add(0).cast::<*mut u8>();
+                  let l216 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len217 = l216;
+                  let bytes217 = _rt::Vec::from_raw_parts(l215.cast(), len217, len217);
+                  let l218 = i32::from(*base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  let l219 = i32::from(*base.add(1+2*::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                  super::super::super::wacli::cli::schema::ArgGroup{
+                    name: _rt::string_lift(bytes217),
+                    required: _rt::bool_lift(l218 as u8),
+                    multiple: _rt::bool_lift(l219 as u8),
+                  }
+                };
+                result220.push(e220);
               }
-              _rt::cabi_dealloc(base86, len86 * (31*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              _rt::cabi_dealloc(base220, len220 * (3*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l221 = *base.add(22*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l222 = *base.add(23*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base226 = l221;
+              let len226 = l222;
+              let mut result226 = _rt::Vec::with_capacity(len226);
+              for i in 0..len226 {
+                let base = base226.add(i * (2*::core::mem::size_of::<*const u8>()));
+                let e226 = {
+                  let l223 = *base.add(0).cast::<*mut u8>();
+                  let l224 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len225 = l224;
+                  let bytes225 = _rt::Vec::from_raw_parts(l223.cast(), len225, len225);
+
+                  _rt::string_lift(bytes225)
+                };
+                result226.push(e226);
+              }
+              _rt::cabi_dealloc(base226, len226 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
 
               super::super::super::wacli::cli::schema::CommandSchema{
                 name: _rt::string_lift(bytes6),
@@ -1066,14 +3476,18 @@ pub mod wacli {
                 hidden: _rt::bool_lift(l22 as u8),
                 description: _rt::string_lift(bytes25),
                 examples: result31,
-                args: result86,
+                args: result97,
+                allow_arg_files: _rt::bool_lift(l98 as u8),
+                subcommands: result212,
+                groups: result220,
+                hidden_aliases: result226,
               }
             };
-            result87.push(e87);
+            result227.push(e227);
           }
-          _rt::cabi_dealloc(base87, len87 * (17*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
-          let result88 = result87;
-          result88
+          _rt::cabi_dealloc(base227, len227 * (24*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result228 = result227;
+          result228
         }
       }
 
@@ -1149,6 +3563,148 @@ mod _rt {
       alloc::dealloc(ptr, layout);
     }
   }
+  pub unsafe fn invalid_enum_discriminant<T>() -> T {
+    if cfg!(debug_assertions) {
+      panic!("invalid enum discriminant")
+    } else {
+      unsafe { core::hint::unreachable_unchecked() }
+    }
+  }
+  pub unsafe fn bool_lift(val: u8) -> bool {
+    if cfg!(debug_assertions) {
+      match val {
+        0 => false,
+        1 => true,
+        _ => panic!("invalid bool discriminant"),
+      }
+    } else {
+      val != 0
+    }
+  }
+  
+  pub fn as_i64<T: AsI64>(t: T) -> i64 {
+    t.as_i64()
+  }
+
+  pub trait AsI64 {
+    fn as_i64(self) -> i64;
+  }
+
+  impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+    fn as_i64(self) -> i64 {
+      (*self).as_i64()
+    }
+  }
+  
+  impl AsI64 for i64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
+  
+  impl AsI64 for u64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
+  
+
+  use core::fmt;
+  use core::marker;
+  use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+  /// A type which represents a component model resource, either imported or
+  /// exported into this component.
+  ///
+  /// This is a low-level wrapper which handles the lifetime of the resource
+  /// (namely this has a destructor). The `T` provided defines the component model
+  /// intrinsics that this wrapper uses.
+  ///
+  /// One of the chief purposes of this type is to provide `Deref` implementations
+  /// to access the underlying data when it is owned.
+  ///
+  /// This type is primarily used in generated code for exported and imported
+  /// resources.
+  #[repr(transparent)]
+  pub struct Resource<T: WasmResource> {
+    // NB: This would ideally be `u32` but it is not. The fact that this has
+    // interior mutability is not exposed in the API of this type except for the
+    // `take_handle` method which is supposed to in theory be private.
+    //
+    // This represents, almost all the time, a valid handle value. When it's
+    // invalid it's stored as `u32::MAX`.
+    handle: AtomicU32,
+    _marker: marker::PhantomData<T>,
+  }
+
+  /// A trait which all wasm resources implement, namely providing the ability to
+  /// drop a resource.
+  ///
+  /// This generally is implemented by generated code, not user-facing code.
+  #[allow(clippy::missing_safety_doc)]
+  pub unsafe trait WasmResource {
+    /// Invokes the `[resource-drop]...` intrinsic.
+    unsafe fn drop(handle: u32);
+  }
+
+  impl<T: WasmResource> Resource<T> {
+    #[doc(hidden)]
+    pub unsafe fn from_handle(handle: u32) -> Self {
+      debug_assert!(handle != 0 && handle != u32::MAX);
+      Self {
+        handle: AtomicU32::new(handle),
+        _marker: marker::PhantomData,
+      }
+    }
+
+    /// Takes ownership of the handle owned by `resource`.
+    ///
+    /// Note that this ideally would be `into_handle` taking `Resource<T>` by
+    /// ownership. The code generator does not enable that in all situations,
+    /// unfortunately, so this is provided instead.
+    ///
+    /// Also note that `take_handle` is in theory only ever called on values
+    /// owned by a generated function. For example a generated function might
+    /// take `Resource<T>` as an argument but then call `take_handle` on a
+    /// reference to that argument. In that sense the dynamic nature of
+    /// `take_handle` should only be exposed internally to generated code, not
+    /// to user code.
+    #[doc(hidden)]
+    pub fn take_handle(resource: &Resource<T>) -> u32 {
+      resource.handle.swap(u32::MAX, Relaxed)
+    }
+
+    #[doc(hidden)]
+    pub fn handle(resource: &Resource<T>) -> u32 {
+      resource.handle.load(Relaxed)
+    }
+  }
+
+  impl<T: WasmResource> fmt::Debug for Resource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.debug_struct("Resource")
+      .field("handle", &self.handle)
+      .finish()
+    }
+  }
+
+  impl<T: WasmResource> Drop for Resource<T> {
+    fn drop(&mut self) {
+      unsafe {
+        match self.handle.load(Relaxed) {
+          // If this handle was "taken" then don't do anything in the
+          // destructor.
+          u32::MAX => {}
+
+          // ... but otherwise do actually destroy it with the imported
+          // component model intrinsic as defined through `T`.
+          other => T::drop(other),
+        }
+      }
+    }
+  }
   
   pub fn as_i32<T: AsI32>(t: T) -> i32 {
     t.as_i32()
@@ -1219,24 +3775,6 @@ mod _rt {
       self as i32
     }
   }
-  pub unsafe fn bool_lift(val: u8) -> bool {
-    if cfg!(debug_assertions) {
-      match val {
-        0 => false,
-        1 => true,
-        _ => panic!("invalid bool discriminant"),
-      }
-    } else {
-      val != 0
-    }
-  }
-  pub unsafe fn invalid_enum_discriminant<T>() -> T {
-    if cfg!(debug_assertions) {
-      panic!("invalid enum discriminant")
-    } else {
-      unsafe { core::hint::unreachable_unchecked() }
-    }
-  }
   pub use alloc_crate::alloc;
 
   #[cfg(target_arch = "wasm32")]
@@ -1278,13 +3816,41 @@ pub(crate) use __export_core_impl as export;
 #[unsafe(link_section = "component-type:wit-bindgen:0.52.0:wacli:cli@2.0.0:core:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1653] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xfa\x0b\x01A\x02\x01\
-A\x14\x01B\x07\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\
-\0\x03\x04\0\x03env\x01\x04\x03\0\x18wacli:cli/host-env@2.0.0\x05\0\x01B\x07\x01\
-p}\x01@\x01\x05bytes\0\x01\0\x04\0\x0cstdout-write\x01\x01\x04\0\x0cstderr-write\
-\x01\x01\x01@\0\x01\0\x04\0\x0cstdout-flush\x01\x02\x04\0\x0cstderr-flush\x01\x02\
-\x03\0\x17wacli:cli/host-io@2.0.0\x05\x01\x01B\x13\x01y\x04\0\x09exit-code\x03\0\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 3445] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xfa\x19\x01A\x02\x01\
+A\x1c\x01B\x0e\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\
+\0\x03\x04\0\x03env\x01\x04\x01ks\x01@\x01\x03keys\0\x05\x04\0\x03get\x01\x06\x01\
+@\0\0s\x04\0\x03cwd\x01\x07\x01@\0\0w\x04\0\x0dmonotonic-now\x01\x08\x03\0\x18wa\
+cli:cli/host-env@2.0.0\x05\0\x01B\x17\x01p}\x01@\x01\x05bytes\0\x01\0\x04\0\x0cs\
+tdout-write\x01\x01\x04\0\x0cstderr-write\x01\x01\x01@\0\x01\0\x04\0\x0cstdout-f\
+lush\x01\x02\x04\0\x0cstderr-flush\x01\x02\x01@\0\0\x7f\x04\0\x12stdout-is-termi\
+nal\x01\x03\x04\0\x12stderr-is-terminal\x01\x03\x04\0\x11stdin-is-terminal\x01\x03\
+\x01ks\x01j\x01\x04\x01s\x01@\0\0\x05\x04\0\x0fstdin-read-line\x01\x06\x01j\x01s\
+\x01s\x01@\x01\x06prompts\0\x07\x04\0\x0bread-secret\x01\x08\x01j\x01\0\x01s\x01\
+@\x01\x03lenw\0\x09\x04\0\x0astdin-read\x01\x0a\x01@\0\0\x09\x04\0\x0estdin-read\
+-all\x01\x0b\x03\0\x17wacli:cli/host-io@2.0.0\x05\x01\x01B\x06\x01m\x05\x05trace\
+\x05debug\x04info\x04warn\x05error\x04\0\x05level\x03\0\0\x01@\x03\x05level\x01\x06\
+targets\x07messages\x01\0\x04\0\x03log\x01\x02\x01@\x01\x05level\x01\0\x7f\x04\0\
+\x07enabled\x01\x03\x03\0\x18wacli:cli/host-log@2.0.0\x05\x02\x02\x03\0\x02\x05l\
+evel\x01B\x08\x02\x03\x02\x01\x03\x04\0\x05level\x03\0\0\x01ks\x01@\x02\x03keys\x05\
+value\x02\x01\0\x04\0\x07set-env\x01\x03\x01k\x01\x01@\x01\x05level\x04\x01\0\x04\
+\0\x11set-max-log-level\x01\x05\x03\0\x1cwacli:cli/host-control@2.0.0\x05\x04\x01\
+B0\x01m\x04\x04file\x03dir\x07symlink\x05other\x04\0\x09file-kind\x03\0\0\x01r\x03\
+\x04kind\x01\x04sizew\x0bmodified-atw\x04\0\x09file-info\x03\0\x02\x01r\x02\x04p\
+aths\x04kind\x01\x04\0\x09dir-entry\x03\0\x04\x04\0\x06writer\x03\x01\x01h\x06\x01\
+p}\x01j\0\x01s\x01@\x02\x04self\x07\x05bytes\x08\0\x09\x04\0\x14[method]writer.w\
+rite\x01\x0a\x01@\x01\x04self\x07\0\x09\x04\0\x14[method]writer.close\x01\x0b\x01\
+j\x01\x08\x01s\x01@\x01\x04paths\0\x0c\x04\0\x09read-file\x01\x0d\x01@\x02\x04pa\
+ths\x08contents\x08\0\x09\x04\0\x0awrite-file\x01\x0e\x01@\x01\x04paths\0\x09\x04\
+\0\x0acreate-dir\x01\x0f\x01ps\x01j\x01\x10\x01s\x01@\x01\x04paths\0\x11\x04\0\x08\
+list-dir\x01\x12\x01j\x01w\x01s\x01@\x02\x03srcs\x03dsts\0\x13\x04\0\x09copy-fil\
+e\x01\x14\x01@\x02\x03srcs\x03dsts\0\x09\x04\0\x06rename\x01\x15\x04\0\x0bremove\
+-file\x01\x0f\x04\0\x0aremove-dir\x01\x0f\x04\0\x0eremove-dir-all\x01\x0f\x01j\x01\
+\x03\x01s\x01@\x01\x04paths\0\x16\x04\0\x04stat\x01\x17\x01@\x01\x04paths\0\x7f\x04\
+\0\x06exists\x01\x18\x01ky\x01p\x05\x01j\x01\x1a\x01s\x01@\x02\x04paths\x09max-d\
+epth\x19\0\x1b\x04\0\x08walk-dir\x01\x1c\x04\0\x0bappend-file\x01\x0e\x01i\x06\x01\
+j\x01\x1d\x01s\x01@\x02\x04paths\x06append\x7f\0\x1e\x04\0\x0bopen-writer\x01\x1f\
+\x03\0\x17wacli:cli/host-fs@2.0.0\x05\x05\x01B\x15\x01y\x04\0\x09exit-code\x03\0\
 \0\x01ks\x01r\x08\x04names\x05short\x02\x04long\x02\x04helps\x08required\x7f\x0d\
 default-value\x02\x0avalue-name\x02\x0btakes-value\x7f\x04\0\x07arg-def\x03\0\x03\
 \x01ps\x01p\x04\x01r\x09\x04names\x07summarys\x05usages\x07aliases\x05\x07versio\
@@ -1294,27 +3860,40 @@ ta\x03\0\x07\x01q\x04\x0funknown-command\x01s\0\x0cinvalid-args\x01s\0\x06failed
 \x0ecommand-result\x03\0\x0b\x01r\x05\x04names\x07summarys\x0binput-types\x05\x0b\
 output-types\x07versions\x04\0\x09pipe-meta\x03\0\x0d\x01q\x03\x0bparse-error\x01\
 s\0\x0ftransform-error\x01s\0\x0einvalid-option\x01s\0\x04\0\x0apipe-error\x03\0\
-\x0f\x01r\x03\x04names\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x03\0\x15\
-wacli:cli/types@2.0.0\x05\x02\x02\x03\0\x02\x09exit-code\x01B\x04\x02\x03\x02\x01\
-\x03\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\x02\
-\x03\0\x1cwacli:cli/host-process@2.0.0\x05\x04\x02\x03\0\x02\x0ccommand-meta\x02\
-\x03\0\x02\x0ecommand-result\x01B\x0a\x02\x03\x02\x01\x05\x04\0\x0ccommand-meta\x03\
-\0\0\x02\x03\x02\x01\x06\x04\0\x0ecommand-result\x03\0\x02\x01p\x01\x01@\0\0\x04\
-\x04\0\x0dlist-commands\x01\x05\x01ps\x01@\x02\x04names\x04argv\x06\0\x03\x04\0\x03\
-run\x01\x07\x03\0\x18wacli:cli/registry@2.0.0\x05\x07\x01B\x07\x01ks\x01ps\x01r\x0f\
-\x04names\x05short\0\x04long\0\x04helps\x08required\x7f\x0ddefault-value\0\x03en\
-v\0\x0avalue-name\0\x0btakes-value\x7f\x08multiple\x7f\x0avalue-type\0\x0fpossib\
-le-values\x01\x0econflicts-with\x01\x08requires\x01\x06hidden\x7f\x04\0\x0aarg-s\
-chema\x03\0\x02\x01p\x03\x01r\x09\x04names\x07summarys\x05usages\x07aliases\x01\x07\
-versions\x06hidden\x7f\x0bdescriptions\x08examples\x01\x04args\x04\x04\0\x0ecomm\
-and-schema\x03\0\x05\x03\0\x16wacli:cli/schema@2.0.0\x05\x08\x02\x03\0\x05\x0eco\
-mmand-schema\x01B\x09\x02\x03\x02\x01\x09\x04\0\x0ecommand-schema\x03\0\0\x01r\x03\
-\x04names\x07versions\x0bdescriptions\x04\0\x08app-meta\x03\0\x02\x01@\0\0\x03\x04\
-\0\x0cget-app-meta\x01\x04\x01p\x01\x01@\0\0\x05\x04\0\x0clist-schemas\x01\x06\x03\
-\0\x1fwacli:cli/registry-schema@2.0.0\x05\x0a\x01B\x03\x01j\0\0\x01@\0\0\0\x04\0\
-\x03run\x01\x01\x04\0\x12wasi:cli/run@0.2.9\x05\x0b\x04\0\x14wacli:cli/core@2.0.\
-0\x04\0\x0b\x0a\x01\0\x04core\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0d\
-wit-component\x070.244.0\x10wit-bindgen-rust\x060.52.0";
+\x0f\x01r\x03\x04names\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x01r\x04\
+\x04info\x12\x0binput-types\x05\x0boutput-types\x07versions\x04\0\x0cpipe-detail\
+s\x03\0\x13\x03\0\x15wacli:cli/types@2.0.0\x05\x06\x02\x03\0\x05\x09exit-code\x01\
+B\x04\x02\x03\x02\x01\x07\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\
+\0\x04exit\x01\x02\x03\0\x1cwacli:cli/host-process@2.0.0\x05\x08\x02\x03\0\x05\x0c\
+command-meta\x02\x03\0\x05\x0ecommand-result\x01B\x0a\x02\x03\x02\x01\x09\x04\0\x0c\
+command-meta\x03\0\0\x02\x03\x02\x01\x0a\x04\0\x0ecommand-result\x03\0\x02\x01p\x01\
+\x01@\0\0\x04\x04\0\x0dlist-commands\x01\x05\x01ps\x01@\x02\x04names\x04argv\x06\
+\0\x03\x04\0\x03run\x01\x07\x03\0\x18wacli:cli/registry@2.0.0\x05\x0b\x01B\x0d\x01\
+ks\x01ps\x01r\x14\x04names\x05short\0\x04long\0\x04helps\x08required\x7f\x0ddefa\
+ult-value\0\x03env\0\x0avalue-name\0\x0btakes-value\x7f\x08multiple\x7f\x0avalue\
+-type\0\x0fpossible-values\x01\x0econflicts-with\x01\x08requires\x01\x06hidden\x7f\
+\x10complete-command\0\x05group\0\x13allow-hyphen-values\x7f\x09countable\x7f\x07\
+var-arg\x7f\x04\0\x0aarg-schema\x03\0\x02\x01r\x03\x04names\x08required\x7f\x08m\
+ultiple\x7f\x04\0\x09arg-group\x03\0\x04\x01p\x03\x01p\x05\x01r\x0b\x04path\x01\x07\
+summarys\x05usages\x07aliases\x01\x07versions\x06hidden\x7f\x0bdescriptions\x08e\
+xamples\x01\x04args\x06\x06groups\x07\x0ehidden-aliases\x01\x04\0\x11subcommand-\
+schema\x03\0\x08\x01p\x09\x01r\x0d\x04names\x07summarys\x05usages\x07aliases\x01\
+\x07versions\x06hidden\x7f\x0bdescriptions\x08examples\x01\x04args\x06\x0fallow-\
+arg-files\x7f\x0bsubcommands\x0a\x06groups\x07\x0ehidden-aliases\x01\x04\0\x0eco\
+mmand-schema\x03\0\x0b\x03\0\x16wacli:cli/schema@2.0.0\x05\x0c\x02\x03\0\x08\x0a\
+arg-schema\x02\x03\0\x08\x0ecommand-schema\x01B\x15\x02\x03\x02\x01\x0d\x04\0\x0a\
+arg-schema\x03\0\0\x02\x03\x02\x01\x0e\x04\0\x0ecommand-schema\x03\0\x02\x01ks\x01\
+ps\x01r\x03\x0dwacli-versions\x0cgit-revision\x04\x0abuilt-with\x05\x04\0\x0abui\
+ld-info\x03\0\x06\x01p\x01\x01r\x09\x04names\x07versions\x0bdescriptions\x0abuil\
+d-info\x07\x11telemetry-command\x04\x10fallback-command\x04\x12interactive-picke\
+r\x7f\x0cenv-warnings\x7f\x0bglobal-args\x08\x04\0\x08app-meta\x03\0\x09\x01r\x03\
+\x04names\x07summarys\x0bdescriptions\x04\0\x0dcommand-group\x03\0\x0b\x01@\0\0\x0a\
+\x04\0\x0cget-app-meta\x01\x0d\x01p\x0c\x01@\0\0\x0e\x04\0\x0blist-groups\x01\x0f\
+\x01p\x03\x01@\0\0\x10\x04\0\x0clist-schemas\x01\x11\x03\0\x1fwacli:cli/registry\
+-schema@2.0.0\x05\x0f\x01B\x03\x01j\0\0\x01@\0\0\0\x04\0\x03run\x01\x01\x04\0\x12\
+wasi:cli/run@0.2.9\x05\x10\x04\0\x14wacli:cli/core@2.0.0\x04\0\x0b\x0a\x01\0\x04\
+core\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.244.0\
+\x10wit-bindgen-rust\x060.52.0";
 
 #[inline(never)]
 #[doc(hidden)]
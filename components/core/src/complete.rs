@@ -0,0 +1,210 @@
+//! `__complete`: value completion for shell completion scripts.
+//!
+//! Scripts call `<app> __complete <command...> <arg> <word>`, where
+//! `<command...>` is a command (or `<group> <command>`) as typed, aliases
+//! included, `<arg>` names the arg whose value is being completed (by name,
+//! `--long` or `-s`), and `<word>` is the partial value. Candidates are printed
+//! one per line on stdout. Anything that does not resolve prints nothing and
+//! still exits 0, so a script never shows an error while the user types.
+
+use crate::bindings::wacli::cli::{registry_schema, schema};
+
+/// Where the candidates for a value come from.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Completion<'a> {
+    /// Known up front (possible values, `true`/`false`), already filtered.
+    Values(Vec<String>),
+    /// Run this registry command with the partial word; it prints candidates.
+    Command { name: &'a str, word: &'a str },
+    /// Nothing to suggest.
+    None,
+}
+
+/// Resolve a `__complete` request (the argv after `__complete`).
+pub(crate) fn resolve<'a>(
+    schemas: &'a [schema::CommandSchema],
+    groups: &[registry_schema::CommandGroup],
+    words: &'a [String],
+) -> Completion<'a> {
+    let [command @ .., arg, word] = words else {
+        return Completion::None;
+    };
+    let Some(schema) = find_schema(schemas, groups, command) else {
+        return Completion::None;
+    };
+    let Some(arg) = schema.args.iter().find(|a| names_arg(a, arg)) else {
+        return Completion::None;
+    };
+    if !arg.takes_value {
+        return Completion::None;
+    }
+
+    if !arg.possible_values.is_empty() {
+        return Completion::Values(matching(arg.possible_values.iter().map(String::as_str), word));
+    }
+    if arg.value_type.as_deref() == Some("bool") {
+        return Completion::Values(matching(["true", "false"], word));
+    }
+    match arg.complete_command.as_deref() {
+        Some(name) => Completion::Command { name, word },
+        None => Completion::None,
+    }
+}
+
+fn matching<'v>(values: impl IntoIterator<Item = &'v str>, word: &str) -> Vec<String> {
+    values
+        .into_iter()
+        .filter(|v| v.starts_with(word))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `<command>` or `<group> <command>`, by name or alias.
+fn find_schema<'a>(
+    schemas: &'a [schema::CommandSchema],
+    groups: &[registry_schema::CommandGroup],
+    command: &[String],
+) -> Option<&'a schema::CommandSchema> {
+    match command {
+        [group, sub] => {
+            let group = crate::find_group(groups, group)?;
+            crate::find_member_schema(schemas, group, sub)
+        }
+        [name] => crate::find_command_schema(schemas, name),
+        _ => None,
+    }
+}
+
+fn names_arg(arg: &schema::ArgSchema, raw: &str) -> bool {
+    arg.name == raw
+        || arg.long.as_deref().is_some_and(|l| {
+            l == raw || l.strip_prefix("--") == Some(raw)
+        })
+        || arg.short.as_deref() == Some(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, long: &str) -> schema::ArgSchema {
+        schema::ArgSchema {
+            name: name.to_string(),
+            short: None,
+            long: Some(long.to_string()),
+            help: String::new(),
+            required: false,
+            default_value: None,
+            env: None,
+            value_name: None,
+            takes_value: true,
+            multiple: false,
+            value_type: None,
+            possible_values: Vec::new(),
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            hidden: false,
+            complete_command: None,
+            group: None,
+            allow_hyphen_values: false,
+            countable: false,
+            var_arg: false,
+        }
+    }
+
+    fn command(name: &str, aliases: &[&str], args: Vec<schema::ArgSchema>) -> schema::CommandSchema {
+        schema::CommandSchema {
+            name: name.to_string(),
+            summary: String::new(),
+            usage: String::new(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden: false,
+            description: String::new(),
+            examples: Vec::new(),
+            args,
+            allow_arg_files: false,
+            subcommands: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn schemas() -> Vec<schema::CommandSchema> {
+        let mut level = arg("level", "--level");
+        level.short = Some("-l".to_string());
+        level.possible_values = vec!["debug".into(), "info".into(), "warn".into()];
+        let mut cache = arg("cache", "--cache");
+        cache.value_type = Some("bool".to_string());
+        let mut format = arg("format", "--format");
+        format.complete_command = Some("pipes/list".to_string());
+        let mut quiet = arg("quiet", "--quiet");
+        quiet.takes_value = false;
+        quiet.possible_values = vec!["x".into()];
+
+        let mut seed_file = arg("file", "--file");
+        seed_file.possible_values = vec!["a.sql".into(), "b.sql".into()];
+
+        vec![
+            command("show", &["s"], vec![level, cache, format, quiet, arg("out", "--out")]),
+            command("db/seed", &["db/load"], vec![seed_file]),
+            command("pipes/list", &[], Vec::new()),
+        ]
+    }
+
+    fn groups() -> Vec<registry_schema::CommandGroup> {
+        vec![registry_schema::CommandGroup {
+            name: "db".to_string(),
+            summary: String::new(),
+            description: String::new(),
+        }]
+    }
+
+    fn complete(words: &[&str]) -> String {
+        let schemas = schemas();
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        match resolve(&schemas, &groups(), &words) {
+            Completion::Values(values) => values.join("\n"),
+            Completion::Command { name, word } => format!("run {name} {word}"),
+            Completion::None => "none".to_string(),
+        }
+    }
+
+    #[test]
+    fn possible_values_are_filtered_by_the_partial_word() {
+        assert_eq!(complete(&["show", "level", ""]), "debug\ninfo\nwarn");
+        assert_eq!(complete(&["show", "--level", "d"]), "debug");
+        assert_eq!(complete(&["show", "-l", "i"]), "info");
+        assert_eq!(complete(&["show", "level", "z"]), "");
+    }
+
+    #[test]
+    fn bool_args_complete_to_bool_values() {
+        assert_eq!(complete(&["show", "cache", "t"]), "true");
+        assert_eq!(complete(&["show", "cache", ""]), "true\nfalse");
+    }
+
+    #[test]
+    fn complete_command_gets_the_partial_word() {
+        assert_eq!(complete(&["show", "format", "js"]), "run pipes/list js");
+        assert_eq!(complete(&["show", "format", ""]), "run pipes/list ");
+    }
+
+    #[test]
+    fn aliases_and_groups_resolve() {
+        assert_eq!(complete(&["s", "--level", "w"]), "warn");
+        assert_eq!(complete(&["db", "load", "file", "b"]), "b.sql");
+        assert_eq!(complete(&["db", "seed", "--file", ""]), "a.sql\nb.sql");
+        assert_eq!(complete(&["db", "nope", "file", "x"]), "none");
+    }
+
+    #[test]
+    fn unresolvable_requests_suggest_nothing() {
+        assert_eq!(complete(&[]), "none");
+        assert_eq!(complete(&["show", "level"]), "none");
+        assert_eq!(complete(&["nope", "level", ""]), "none");
+        assert_eq!(complete(&["show", "nope", ""]), "none");
+        assert_eq!(complete(&["show", "quiet", ""]), "none");
+        assert_eq!(complete(&["show", "out", ""]), "none");
+    }
+}
@@ -0,0 +1,83 @@
+//! The app's config file: a flat JSON object of arg values keyed by arg name,
+//! read between env and defaults when core parses a command's args
+//! (`claplike::parse_with_sources`).
+//!
+//! Everything here works on the path and text, so it can be tested without a
+//! host; `lib.rs` reads the file through `host-fs`.
+
+use serde_json::Value;
+
+/// The global arg whose value names the config file (`--config <PATH>`).
+pub(crate) const GLOBAL_ARG: &str = "config";
+
+/// `$HOME/.<app>/config.json`, or `None` when `HOME` is unset.
+pub(crate) fn default_path(env: &[(String, String)], app: &str) -> Option<String> {
+    app_file(env, app, "config.json")
+}
+
+/// `$HOME/.<app>/<file>`, or `None` when `HOME` is unset.
+pub(crate) fn app_file(env: &[(String, String)], app: &str, file: &str) -> Option<String> {
+    let home = env
+        .iter()
+        .find(|(key, _)| key == "HOME")
+        .map(|(_, value)| value.trim_end_matches('/'))
+        .filter(|home| !home.is_empty())?;
+    Some(format!("{home}/.{app}/{file}"))
+}
+
+/// The values in `text`. Strings are taken as is; numbers and booleans as
+/// they are written (`3`, `true`).
+pub(crate) fn parse(text: &str) -> Result<Vec<(String, String)>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let Value::Object(map) = value else {
+        return Err("expected a JSON object".to_string());
+    };
+    map.into_iter()
+        .map(|(key, value)| match value {
+            Value::String(s) => Ok((key, s)),
+            Value::Number(n) => Ok((key, n.to_string())),
+            Value::Bool(b) => Ok((key, b.to_string())),
+            _ => Err(format!(
+                "value of '{key}' must be a string, number or boolean"
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn default_path_is_under_home() {
+        assert_eq!(
+            default_path(&env(&[("HOME", "/home/me/")]), "my-cli").as_deref(),
+            Some("/home/me/.my-cli/config.json")
+        );
+        assert_eq!(default_path(&env(&[("HOME", "")]), "my-cli"), None);
+        assert_eq!(default_path(&[], "my-cli"), None);
+    }
+
+    #[test]
+    fn parse_reads_a_flat_object() {
+        let values = parse(r#"{ "name": "Ada", "count": 3, "cache": false }"#).unwrap();
+        assert_eq!(
+            values,
+            env(&[("cache", "false"), ("count", "3"), ("name", "Ada")])
+        );
+
+        assert_eq!(parse("[]").unwrap_err(), "expected a JSON object");
+        assert_eq!(
+            parse(r#"{ "tags": ["a"] }"#).unwrap_err(),
+            "value of 'tags' must be a string, number or boolean"
+        );
+        assert!(parse("{").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! `--help=json`: command schemas as JSON, for tools that drive the CLI.
+//!
+//! `<app> <command> --help=json` prints the command's schema as one JSON
+//! object, `<app> --help=json` (and `<app> <group> --help=json`) an array of
+//! them. Records use the kebab-case field names of the
+//! `wacli:cli/command-metadata@1` section (`wacli_metadata::CommandSchema`),
+//! with subcommands nested. Commands keep their registry names, so members of
+//! a group are named `<group>/<command>`. Hidden commands, subcommands and
+//! args are left out unless `--all` is given as well.
+
+use crate::bindings::wacli::cli::schema;
+
+/// The token that asks for JSON help.
+pub(crate) const HELP_JSON: &str = "--help=json";
+
+/// Whether `args` (before `--`) ask for JSON help, and if so whether hidden
+/// entries are included (`--all`).
+pub(crate) fn requested(args: &[String]) -> Option<bool> {
+    let args = args.split(|a| a == "--").next().unwrap_or_default();
+    if !args.iter().any(|a| a == HELP_JSON) {
+        return None;
+    }
+    Some(args.iter().any(|a| a == "--all"))
+}
+
+/// `schema` as a JSON object.
+pub(crate) fn command(schema: &schema::CommandSchema, all: bool) -> String {
+    serde_json::to_string(&to_metadata(schema, all)).unwrap_or_default()
+}
+
+/// `schemas` as a JSON array, without hidden commands unless `all`.
+pub(crate) fn commands<'a>(
+    schemas: impl IntoIterator<Item = &'a schema::CommandSchema>,
+    all: bool,
+) -> String {
+    let records: Vec<wacli_metadata::CommandSchema> = schemas
+        .into_iter()
+        .filter(|s| all || !s.hidden)
+        .map(|s| to_metadata(s, all))
+        .collect();
+    serde_json::to_string(&records).unwrap_or_default()
+}
+
+/// Convert a registry schema back into the metadata model, nesting its
+/// flattened subcommands.
+fn to_metadata(schema: &schema::CommandSchema, all: bool) -> wacli_metadata::CommandSchema {
+    let mut root = wacli_metadata::CommandSchema {
+        name: schema.name.clone(),
+        summary: schema.summary.clone(),
+        usage: schema.usage.clone(),
+        aliases: schema.aliases.clone(),
+        hidden_aliases: if all {
+            schema.hidden_aliases.clone()
+        } else {
+            Vec::new()
+        },
+        version: schema.version.clone(),
+        hidden: schema.hidden,
+        description: schema.description.clone(),
+        examples: schema.examples.clone(),
+        args: args(&schema.args, all),
+        groups: groups(&schema.groups),
+        allow_arg_files: schema.allow_arg_files,
+        subcommands: Vec::new(),
+    };
+    for sub in &schema.subcommands {
+        let Some((name, parents)) = sub.path.split_last() else {
+            continue;
+        };
+        if !all && sub.hidden {
+            continue;
+        }
+        let mut node = &mut root;
+        let mut attached = true;
+        for parent in parents {
+            match node.subcommands.iter().position(|n| &n.name == parent) {
+                Some(i) => node = &mut node.subcommands[i],
+                // The parent was hidden (and left out).
+                None => {
+                    attached = false;
+                    break;
+                }
+            }
+        }
+        if !attached {
+            continue;
+        }
+        node.subcommands.push(wacli_metadata::CommandSchema {
+            name: name.clone(),
+            summary: sub.summary.clone(),
+            usage: sub.usage.clone(),
+            aliases: sub.aliases.clone(),
+            hidden_aliases: if all {
+                sub.hidden_aliases.clone()
+            } else {
+                Vec::new()
+            },
+            version: sub.version.clone(),
+            hidden: sub.hidden,
+            description: sub.description.clone(),
+            examples: sub.examples.clone(),
+            args: args(&sub.args, all),
+            groups: groups(&sub.groups),
+            allow_arg_files: false,
+            subcommands: Vec::new(),
+        });
+    }
+    root
+}
+
+fn args(args: &[schema::ArgSchema], all: bool) -> Vec<wacli_metadata::ArgSchema> {
+    args.iter()
+        .filter(|a| all || !a.hidden)
+        .map(|a| wacli_metadata::ArgSchema {
+            name: a.name.clone(),
+            short: a.short.clone(),
+            long: a.long.clone(),
+            help: a.help.clone(),
+            required: a.required,
+            default_value: a.default_value.clone(),
+            env: a.env.clone(),
+            value_name: a.value_name.clone(),
+            takes_value: a.takes_value,
+            multiple: a.multiple,
+            value_type: a.value_type.clone(),
+            possible_values: a.possible_values.clone(),
+            conflicts_with: a.conflicts_with.clone(),
+            requires: a.requires.clone(),
+            hidden: a.hidden,
+            complete_command: a.complete_command.clone(),
+            shared: None,
+            group: a.group.clone(),
+            allow_hyphen_values: a.allow_hyphen_values,
+            countable: a.countable,
+            var_arg: a.var_arg,
+        })
+        .collect()
+}
+
+fn groups(groups: &[schema::ArgGroup]) -> Vec<wacli_metadata::ArgGroup> {
+    groups
+        .iter()
+        .map(|g| wacli_metadata::ArgGroup {
+            name: g.name.clone(),
+            required: g.required,
+            multiple: g.multiple,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arg(name: &str, hidden: bool) -> schema::ArgSchema {
+        schema::ArgSchema {
+            name: name.to_string(),
+            short: None,
+            long: Some(format!("--{name}")),
+            help: String::new(),
+            required: false,
+            default_value: None,
+            env: None,
+            value_name: None,
+            takes_value: true,
+            multiple: false,
+            value_type: None,
+            possible_values: Vec::new(),
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            hidden,
+            complete_command: None,
+            group: None,
+            allow_hyphen_values: false,
+            countable: false,
+            var_arg: false,
+        }
+    }
+
+    fn subcommand(path: &[&str], hidden: bool) -> schema::SubcommandSchema {
+        schema::SubcommandSchema {
+            path: path.iter().map(|p| p.to_string()).collect(),
+            summary: String::new(),
+            usage: String::new(),
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden,
+            description: String::new(),
+            examples: Vec::new(),
+            args: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn remote(name: &str, hidden: bool) -> schema::CommandSchema {
+        schema::CommandSchema {
+            name: name.to_string(),
+            summary: "Manage remotes".to_string(),
+            usage: String::new(),
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden,
+            description: String::new(),
+            examples: Vec::new(),
+            args: vec![arg("url", false), arg("debug", true)],
+            allow_arg_files: false,
+            subcommands: vec![
+                subcommand(&["add"], false),
+                subcommand(&["add", "mirror"], false),
+                subcommand(&["gc"], true),
+                subcommand(&["gc", "now"], false),
+            ],
+            groups: Vec::new(),
+        }
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn requested_before_the_separator_only() {
+        assert_eq!(requested(&strings(&["x", "--help=json"])), Some(false));
+        assert_eq!(requested(&strings(&["--all", "--help=json"])), Some(true));
+        assert_eq!(requested(&strings(&["--help"])), None);
+        assert_eq!(requested(&strings(&["--", "--help=json"])), None);
+        assert_eq!(
+            requested(&strings(&["--help=json", "--", "--all"])),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn command_json_uses_metadata_field_names_and_nests_subcommands() {
+        let json = command(&remote("remote", false), false);
+        assert_eq!(
+            json,
+            r#"{"name":"remote","summary":"Manage remotes","hidden":false,"args":[{"name":"url","long":"--url","required":false,"takes-value":true,"multiple":false,"hidden":false}],"allow-arg-files":false,"subcommands":[{"name":"add","hidden":false,"allow-arg-files":false,"subcommands":[{"name":"mirror","hidden":false,"allow-arg-files":false}]}]}"#
+        );
+    }
+
+    #[test]
+    fn all_includes_hidden_commands_subcommands_and_args() {
+        let schemas = [remote("remote", false), remote("debug", true)];
+        let value: serde_json::Value = serde_json::from_str(&commands(&schemas, false)).unwrap();
+        let names: Vec<&str> = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["remote"]);
+
+        let value: serde_json::Value = serde_json::from_str(&commands(&schemas, true)).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        let remote = &value[0];
+        assert_eq!(remote["args"][1]["name"], "debug");
+        assert_eq!(remote["subcommands"][1]["name"], "gc");
+        assert_eq!(remote["subcommands"][1]["subcommands"][0]["name"], "now");
+    }
+}
@@ -1,25 +1,44 @@
 #![allow(clippy::all)]
 
+mod aliases;
 mod bindings;
+mod complete;
+mod config;
+mod help_json;
+mod out;
+mod picker;
 
 use bindings::export;
 use bindings::exports::wasi::cli::run;
-use bindings::wacli::cli::{host_env, host_io, host_process, registry, registry_schema, schema, types};
+use bindings::wacli::cli::{
+    host_control, host_env, host_fs, host_io, host_log, registry, registry_schema, schema,
+    types,
+};
+use out::{Out, Sink, Stream};
 
-use wacli_argparse::{args as argv, claplike};
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use wacli_argparse::claplike::{self, completions, messages};
+use wacli_argparse::args as argv;
+use wacli_exit_code::decode_exit_code;
 
 struct Core;
 
 impl run::Guest for Core {
     fn run() -> Result<(), ()> {
-        let (program, argv) = split_program_and_argv(host_env::args());
+        let mut out = Out::host();
+        let (program, mut argv) = split_program_and_argv(host_env::args());
 
         // App-level metadata is provided by the registry as pure data (no plugin execution).
         let app = registry_schema::get_app_meta();
 
         // Fast path: top-level version does not require loading command schemas.
         if argv.first().is_some_and(|a| a == "-V" || a == "--version") {
-            print_global_version(&program, &app);
+            // `--version --build-info` / `-V --verbose` also print build provenance.
+            let with_build_info = argv[1..]
+                .iter()
+                .any(|a| a == "--build-info" || a == "--verbose");
+            print_global_version(&mut out, &program, &app, with_build_info);
             return Ok(());
         }
 
@@ -28,109 +47,776 @@ impl run::Guest for Core {
         // - command-level help/version
         // - command-level validation
         let schemas = registry_schema::list_schemas();
+        let groups = registry_schema::list_groups();
 
         if let Err(err) = claplike::validate_aliases(&schemas) {
-            print_internal_error(err.message());
-            host_process::exit(1);
+            print_internal_error(&mut out, err.message());
+            out.exit(1);
             return Ok(());
         }
 
+        // Help is colored per stream, see `color_enabled`.
+        let env = host_env::env();
+        let styles = HelpStyles::new(&env);
+
         if argv.is_empty() {
-            print_global_help(&app, &schemas);
-            return Ok(());
+            if !picker_enabled(&app, &program, &env, || {
+                host_io::stdin_is_terminal() && host_io::stdout_is_terminal()
+            }) {
+                print_global_help(&mut out, &app, &schemas, &groups, &styles.out());
+                return Ok(());
+            }
+            // A failed read ends the picker like end of input does.
+            let read_line = || host_io::stdin_read_line().unwrap_or(None);
+            let Some(picked) = picker::pick(&mut out, &schemas, &env, read_line) else {
+                print_global_help(&mut out, &app, &schemas, &groups, &styles.out());
+                out.exit(1);
+                return Ok(());
+            };
+            argv = picked;
         }
 
+        // User aliases (`st` -> `status --short`) expand before anything
+        // resolves argv[0]; they never shadow commands, groups or built-ins.
+        let aliases = match load_aliases(&app, &program, &env) {
+            Ok(aliases) => aliases,
+            Err((path, err)) => {
+                print_alias_file_error(&mut out, &path, &err);
+                out.exit(1);
+                return Ok(());
+            }
+        };
+        let reserved = |name: &str| is_reserved_name(&schemas, &groups, name);
+        argv = match aliases::expand(&aliases, &argv, reserved) {
+            Ok(argv) => argv,
+            Err(msg) => {
+                out.line(Stream::Stderr, &msg);
+                out.exit(1);
+                return Ok(());
+            }
+        };
+
         // Top-level built-ins.
         match argv[0].as_str() {
+            // Hidden: called back by shell completion scripts.
+            "__complete" => {
+                match complete::resolve(&schemas, &groups, &argv[1..]) {
+                    complete::Completion::Values(values) => {
+                        for value in values {
+                            out.line(Stream::Stdout, &value);
+                        }
+                    }
+                    complete::Completion::Command { name, word } => {
+                        // The command prints its own candidates; a failing
+                        // command just means no suggestions.
+                        out.flush();
+                        let _ = registry::run(name, &[word.to_string()]);
+                    }
+                    complete::Completion::None => {}
+                }
+                return Ok(());
+            }
+            // Hidden: `my-cli completions bash | source`. A command or group
+            // of that name wins.
+            "completions"
+                if find_command_schema(&schemas, "completions").is_none()
+                    && find_group(&groups, "completions").is_none() =>
+            {
+                let name = app_display_name(&app, &program);
+                let shell = argv.get(1).map(String::as_str).unwrap_or_default();
+                match shell.parse::<completions::Shell>() {
+                    Ok(shell) => {
+                        let trees: Vec<SchemaNode> = schemas
+                            .iter()
+                            .map(|schema| command_tree(schema, &app.global_args))
+                            .collect();
+                        out.block(Stream::Stdout, &completions::render(shell, name, &trees));
+                    }
+                    Err(msg) => {
+                        out.line(Stream::Stderr, msg.trim_end());
+                        let shells = completions::Shell::NAMES.join("|");
+                        out.line(
+                            Stream::Stderr,
+                            &format!("Usage: {name} completions <{shells}>"),
+                        );
+                        out.exit(EXIT_USAGE);
+                    }
+                }
+                return Ok(());
+            }
             "help" => {
                 if let Some(topic) = argv.get(1) {
-                    if let Some(schema) = find_command_schema(&schemas, topic) {
-                        print_command_help(schema);
+                    if let Some(group) = find_group(&groups, topic) {
+                        match argv.get(2) {
+                            Some(sub) => match find_member_schema(&schemas, group, sub) {
+                                Some(schema) => print_command_help(
+                                    &mut out,
+                                    schema,
+                                    &app.global_args,
+                                    &styles.out(),
+                                ),
+                                None => {
+                                    print_unknown_command(
+                                        &mut out,
+                                        &format!("{} {sub}", group.name),
+                                    );
+                                    print_group_help(&mut out, group, &schemas, &styles.out());
+                                    out.exit(EXIT_UNKNOWN_COMMAND);
+                                }
+                            },
+                            None => print_group_help(&mut out, group, &schemas, &styles.out()),
+                        }
+                    } else if let Some(schema) = find_command_schema(&schemas, topic) {
+                        print_command_help(&mut out, schema, &app.global_args, &styles.out());
+                    } else if let Some(alias) = aliases::find(&aliases, topic) {
+                        out.line(
+                            Stream::Stdout,
+                            &format!("'{}' is an alias for '{}'", alias.name, alias.definition),
+                        );
+                        // Follow it through any aliases it names.
+                        match aliases::expand(&aliases, &alias.words, reserved) {
+                            Ok(expanded) => print_expanded_alias_help(
+                                &mut out,
+                                &expanded,
+                                &schemas,
+                                &groups,
+                                &app.global_args,
+                                &styles.out(),
+                            ),
+                            Err(msg) => {
+                                out.line(Stream::Stderr, &msg);
+                                out.exit(1);
+                            }
+                        }
                     } else {
-                        print_unknown_command(topic);
-                        print_global_help(&app, &schemas);
-                        host_process::exit(1);
+                        print_unknown_command(&mut out, topic);
+                        print_global_help(&mut out, &app, &schemas, &groups, &styles.out());
+                        out.exit(EXIT_UNKNOWN_COMMAND);
                     }
                 } else {
-                    print_global_help(&app, &schemas);
+                    print_global_help(&mut out, &app, &schemas, &groups, &styles.out());
                 }
                 return Ok(());
             }
             "-h" | "--help" => {
-                print_global_help(&app, &schemas);
+                print_global_help(&mut out, &app, &schemas, &groups, &styles.out());
+                return Ok(());
+            }
+            help_json::HELP_JSON => {
+                let all = argv.iter().any(|a| a == "--all");
+                out.block(Stream::Stdout, &help_json::commands(&schemas, all));
                 return Ok(());
             }
             _ => {}
         }
 
-        let cmd_name = argv[0].as_str();
-        let cmd_args = argv.get(1..).unwrap_or(&[]);
+        // `fallback_for` is the unknown command the fallback stands in for; it
+        // goes first in the fallback's argv.
+        let (schema, cmd_args, fallback_for) = if let Some(group) = find_group(&groups, &argv[0]) {
+            // Two-level dispatch: `<group> <command> [args...]`.
+            if argv.get(1).is_some_and(|a| a == help_json::HELP_JSON) {
+                let all = argv.iter().any(|a| a == "--all");
+                let members = schemas
+                    .iter()
+                    .filter(|c| group_of(&c.name) == Some(group.name.as_str()));
+                out.block(Stream::Stdout, &help_json::commands(members, all));
+                return Ok(());
+            }
+            let Some(sub) = argv
+                .get(1)
+                .filter(|a| !matches!(a.as_str(), "-h" | "--help"))
+            else {
+                print_group_help(&mut out, group, &schemas, &styles.out());
+                return Ok(());
+            };
+            let Some(schema) = find_member_schema(&schemas, group, sub) else {
+                print_unknown_command(&mut out, &format!("{} {sub}", group.name));
+                print_group_help(&mut out, group, &schemas, &styles.out());
+                out.exit(EXIT_UNKNOWN_COMMAND);
+                return Ok(());
+            };
+            (schema, argv.get(2..).unwrap_or(&[]), None)
+        } else {
+            let cmd_name = argv[0].as_str();
+            let cmd_args = argv.get(1..).unwrap_or(&[]);
 
-        // Unknown command: let the registry decide the canonical error type/message.
-        let Some(schema) = find_command_schema(&schemas, cmd_name) else {
-            return dispatch_to_registry(cmd_name, cmd_args);
+            // Unknown command: hand it to the app's fallback command if there
+            // is one, else let the registry decide the canonical error type/message.
+            // The fallback still goes through global args, the log level and
+            // config below.
+            match find_command_schema(&schemas, cmd_name) {
+                Some(schema) => (schema, cmd_args, None),
+                None => match fallback_command(&app, &schemas, cmd_name)
+                    .and_then(|fallback| find_command_schema(&schemas, fallback))
+                {
+                    Some(schema) => (schema, cmd_args, Some(cmd_name)),
+                    None => return dispatch_to_registry(&mut out, cmd_name, cmd_args),
+                },
+            }
         };
 
-        // Command-level built-ins should work even if the plugin doesn't call `parse()`.
-        if argv::flag(cmd_args, ["-h", "--help"]) {
-            print_command_help(schema);
-            return Ok(());
-        }
-        if argv::flag(cmd_args, ["-V", "--version"]) {
-            print_command_version(schema);
+        // Machine-readable help, before anything can reject the token.
+        if fallback_for.is_none()
+            && let Some(all) = help_json::requested(cmd_args)
+        {
+            out.block(Stream::Stdout, &help_json::command(schema, all));
             return Ok(());
         }
 
-        let env = host_env::env();
-        match claplike::validate_with_env(schema, cmd_args, &env) {
-            Ok(()) => {}
+        // Splice `@file` arg files into argv (for commands that opt in); the
+        // command receives the expanded argv. `origins` maps each token back
+        // to the one typed, for pointing at it in parse errors.
+        let typed_args = cmd_args;
+        let (cmd_args, origins) =
+            match claplike::expand_arg_files_with_origins(schema, typed_args, read_arg_file) {
+                Ok(expanded) => expanded,
+                Err(err) => {
+                    print_invalid_args(
+                        &mut out,
+                        err.message(),
+                        schema,
+                        &app.global_args,
+                        &command_line(&app, &program, schema),
+                        &styles.err(),
+                    );
+                    out.exit(EXIT_USAGE);
+                    return Ok(());
+                }
+            };
+
+        // Global options never reach the command's argv; their values are
+        // handed over as `WACLI_GLOBAL_<NAME>` instead.
+        let config_var = claplike::global_arg_env_var(config::GLOBAL_ARG);
+        let verbose_var = claplike::global_arg_env_var(VERBOSE_GLOBAL_ARG);
+        let quiet_var = claplike::global_arg_env_var(QUIET_GLOBAL_ARG);
+        let mut config_path = None;
+        let (mut verbose, mut quiet) = (false, false);
+        let (cmd_args, origins) = match take_global_args(schema, &app.global_args, &cmd_args, &env)
+        {
+            Ok((rest, values)) => {
+                for (var, value) in values {
+                    host_control::set_env(&var, value.as_deref());
+                    verbose |= var == verbose_var && value.is_some();
+                    quiet |= var == quiet_var && value.is_some();
+                    if var == config_var && takes_config_path(&app.global_args) {
+                        config_path = value;
+                    }
+                }
+                let args: Vec<String> = rest.iter().map(|&i| cmd_args[i].clone()).collect();
+                (args, rest.iter().map(|&i| origins[i]).collect::<Vec<_>>())
+            }
             Err(claplike::ParseError::InvalidArgs(msg)) => {
-                print_invalid_args(&msg, schema);
-                host_process::exit(1);
+                print_invalid_args(
+                    &mut out,
+                    &msg,
+                    schema,
+                    &app.global_args,
+                    &command_line(&app, &program, schema),
+                    &styles.err(),
+                );
+                out.exit(EXIT_USAGE);
                 return Ok(());
             }
             Err(claplike::ParseError::Failed(msg)) => {
-                print_internal_error(&msg);
-                host_process::exit(1);
+                print_internal_error(&mut out, &msg);
+                out.exit(1);
                 return Ok(());
             }
+        };
+        let cmd_args = cmd_args.as_slice();
+
+        // Commands log through host-log; drop what this run should not show
+        // before they format it.
+        match max_log_level(&app, &program, &env, verbose, quiet) {
+            Ok(level) => host_control::set_max_log_level(level),
+            Err(warning) => print_warnings(&mut out, &[warning]),
         }
 
-        // Run by canonical name (so aliases work everywhere).
-        match registry::run(&schema.name, cmd_args) {
-            Ok(code) => {
-                if code != 0 {
-                    host_process::exit(code);
+        // Command-level built-ins should work even if the plugin doesn't call `parse()`.
+        // With subcommands, `-h` may belong to a subcommand, so leave it to the parser;
+        // a fallback's `-h` belongs to the command it stands in for.
+        let has_subcommands = !schema.subcommands.is_empty();
+        let builtins = !has_subcommands && fallback_for.is_none();
+        if builtins && argv::flag(cmd_args, ["-h", "--help"]) {
+            print_command_help(&mut out, schema, &app.global_args, &styles.out());
+            return Ok(());
+        }
+        if builtins && argv::flag(cmd_args, ["-V", "--version"]) {
+            print_command_version(&mut out, schema);
+            return Ok(());
+        }
+
+        // Config values come from `--config <PATH>` when the app declares it,
+        // else from `~/.<app>/config.json` if there is one.
+        let config_path = config_path.or_else(|| {
+            config::default_path(&env, app_display_name(&app, &program))
+                .filter(|path| host_fs::exists(path))
+        });
+        let config = match config_path {
+            Some(path) => match read_config(&path) {
+                Ok(values) => {
+                    let warnings: Vec<String> = unknown_config_keys(&schemas, &values)
+                        .into_iter()
+                        .map(|key| format!("unknown key '{key}' in config file {path} \u{2014} ignoring"))
+                        .collect();
+                    print_warnings(&mut out, &warnings);
+                    values
+                }
+                Err(err) => {
+                    print_config_error(&mut out, &path, &err);
+                    out.exit(1);
+                    return Ok(());
+                }
+            },
+            None => Vec::new(),
+        };
+
+        let mut tree = command_tree(schema, &app.global_args);
+        tree.style = stdout_help_style;
+        if let Some(name) = fallback_for {
+            // The fallback parses `<name> [args...]` itself; hand over the
+            // config values its declared args would take, if they parse.
+            let fallback_args: Vec<String> = std::iter::once(name.to_string())
+                .chain(cmd_args.iter().cloned())
+                .collect();
+            if let Ok(claplike::ParseOutcome::Matches(m)) =
+                claplike::parse_with_sources(&tree, &fallback_args, &env, &config)
+            {
+                for (var, value) in config_handoff(&m, &config) {
+                    host_control::set_env(&var, Some(&value));
+                }
+            }
+            return dispatch_to_fallback(&mut out, &schema.name, name, cmd_args);
+        }
+        let parsed = claplike::parse_spanned(&tree, cmd_args, &env, &config, app.env_warnings);
+        // Plugins parse with `arg-def`, which has no value types, so a bare
+        // `--cache` (value-type bool) reaches them as `--cache=true`.
+        let cmd_args = match parsed {
+            Ok(claplike::ParseOutcome::Matches(m)) => {
+                print_warnings(&mut out, m.warnings());
+                for (var, value) in config_handoff(&m, &config) {
+                    host_control::set_env(&var, Some(&value));
                 }
-                Ok(())
+                claplike::spell_out_bool_flags(cmd_args, &m)
+            }
+            Ok(claplike::ParseOutcome::Help(text) | claplike::ParseOutcome::Version(text))
+                if has_subcommands =>
+            {
+                out.block(Stream::Stdout, &text);
+                return Ok(());
+            }
+            Ok(claplike::ParseOutcome::Help(_) | claplike::ParseOutcome::Version(_)) => {
+                cmd_args.to_vec()
+            }
+            Err(claplike::SpannedError {
+                error: claplike::ParseError::Failed(msg),
+                ..
+            }) => {
+                print_internal_error(&mut out, &msg);
+                out.exit(1);
+                return Ok(());
+            }
+            Err(err) => {
+                let command_line = command_line(&app, &program, schema);
+                print_invalid_args(
+                    &mut out,
+                    &render_parse_error(err, &command_line, typed_args, cmd_args, &origins),
+                    schema,
+                    &app.global_args,
+                    &command_line,
+                    &styles.err(),
+                );
+                out.exit(EXIT_USAGE);
+                return Ok(());
+            }
+        };
+
+        // Run by canonical name (so aliases work everywhere).
+        out.flush();
+        let started = host_env::monotonic_now();
+        let code = match registry::run(&schema.name, &cmd_args) {
+            Ok(code) => code,
+            // Same presentation and exit code as the pre-dispatch check above.
+            Err(types::CommandError::InvalidArgs(msg)) => {
+                print_invalid_args(
+                    &mut out,
+                    &msg,
+                    schema,
+                    &app.global_args,
+                    &command_line(&app, &program, schema),
+                    &styles.err(),
+                );
+                EXIT_USAGE
             }
             Err(err) => {
-                report_command_error(cmd_name, err);
-                host_process::exit(1);
-                Ok(())
+                let code = exit_code(&err);
+                report_command_error(&mut out, &schema.name, err);
+                code
             }
+        };
+        let elapsed = host_env::monotonic_now().saturating_sub(started);
+        out.flush();
+
+        send_telemetry(&app, &program, &env, &schema.name, code, elapsed);
+
+        if code != 0 {
+            out.exit(code);
         }
+        Ok(())
     }
 }
 
 export!(Core with_types_in bindings);
 
-fn dispatch_to_registry(cmd_name: &str, cmd_args: &[String]) -> Result<(), ()> {
+fn dispatch_to_registry(
+    out: &mut Out<impl Sink>,
+    cmd_name: &str,
+    cmd_args: &[String],
+) -> Result<(), ()> {
+    out.flush();
     match registry::run(cmd_name, cmd_args) {
         Ok(code) => {
             if code != 0 {
-                host_process::exit(code);
+                out.exit(code);
             }
             Ok(())
         }
         Err(err) => {
-            report_command_error(cmd_name, err);
-            host_process::exit(1);
+            let code = exit_code(&err);
+            report_command_error(out, cmd_name, err);
+            out.exit(code);
             Ok(())
         }
     }
 }
 
+/// The app's fallback command for the unknown command `name`, when it is
+/// composed. It never stands in for itself, so a fallback that is not found
+/// (or that reports itself unknown) cannot loop.
+fn fallback_command<'a>(
+    app: &'a registry_schema::AppMeta,
+    schemas: &[schema::CommandSchema],
+    name: &str,
+) -> Option<&'a str> {
+    let fallback = app.fallback_command.as_deref()?;
+    (fallback != name && schemas.iter().any(|s| s.name == fallback)).then_some(fallback)
+}
+
+/// Run `fallback` for the unknown command `name`, with `name` as its first
+/// argument. When the fallback does not know `name` either, report `name`
+/// as if there were no fallback.
+fn dispatch_to_fallback(
+    out: &mut Out<impl Sink>,
+    fallback: &str,
+    name: &str,
+    args: &[String],
+) -> Result<(), ()> {
+    let mut argv = Vec::with_capacity(args.len() + 1);
+    argv.push(name.to_string());
+    argv.extend_from_slice(args);
+    out.flush();
+    let (reported, err) = match registry::run(fallback, &argv) {
+        Ok(0) => return Ok(()),
+        Ok(code) => {
+            out.exit(code);
+            return Ok(());
+        }
+        Err(types::CommandError::UnknownCommand(_)) => {
+            (name, types::CommandError::UnknownCommand(name.to_string()))
+        }
+        Err(err) => (fallback, err),
+    };
+    let code = exit_code(&err);
+    report_command_error(out, reported, err);
+    out.exit(code);
+    Ok(())
+}
+
+/// Hand a usage event to the app's telemetry command, if one is configured.
+///
+/// Privacy constraints:
+/// - core never talks to the network; the telemetry command decides what to do
+///   with the event
+/// - the event carries only the command name, exit code and duration, never
+///   argv, env values or paths
+/// - `<APP>_NO_TELEMETRY` (any non-empty value) disables it, see
+///   [`telemetry_opt_out_var`]
+///
+/// Any failure of the telemetry command is ignored.
+fn send_telemetry(
+    app: &registry_schema::AppMeta,
+    program: &str,
+    env: &[(String, String)],
+    command: &str,
+    exit_code: u32,
+    elapsed_ns: u64,
+) {
+    let Some(telemetry) = app.telemetry_command.as_deref() else {
+        return;
+    };
+    if command == telemetry {
+        return;
+    }
+    let opt_out = telemetry_opt_out_var(app, program);
+    if env.iter().any(|(k, v)| *k == opt_out && !v.is_empty()) {
+        return;
+    }
+    let event = telemetry_event_json(command, exit_code, elapsed_ns / 1_000_000);
+    let _ = registry::run(telemetry, &[event]);
+}
+
+/// `<APP>_NO_TELEMETRY`, see [`app_env_var`].
+fn telemetry_opt_out_var(app: &registry_schema::AppMeta, program: &str) -> String {
+    app_env_var(app, program, "NO_TELEMETRY")
+}
+
+/// Whether to show the command picker for a bare invocation: only when the
+/// app enables it and stdin and stdout are terminals. `<APP>_INTERACTIVE=1`
+/// forces it (for scripted input) and `=0` turns it off.
+fn picker_enabled(
+    app: &registry_schema::AppMeta,
+    program: &str,
+    env: &[(String, String)],
+    terminals: impl FnOnce() -> bool,
+) -> bool {
+    if !app.interactive_picker {
+        return false;
+    }
+    let var = app_env_var(app, program, "INTERACTIVE");
+    match env.iter().find(|(k, _)| *k == var).map(|(_, v)| v.as_str()) {
+        Some("1") => true,
+        Some("0") => false,
+        _ => terminals(),
+    }
+}
+
+/// Global flags that raise (`verbose`) or lower (`quiet`) the log level.
+const VERBOSE_GLOBAL_ARG: &str = "verbose";
+const QUIET_GLOBAL_ARG: &str = "quiet";
+
+/// The max `host-log` level for a command run. The `verbose` (`debug`) and
+/// `quiet` (`error`) global flags, when the app declares them, win over
+/// `<APP>_LOG` (`trace`, `debug`, `info`, `warn`, `error` or `off`); the
+/// default is `warn`. An `<APP>_LOG` value it does not know is an `Err`
+/// warning, and the default applies.
+fn max_log_level(
+    app: &registry_schema::AppMeta,
+    program: &str,
+    env: &[(String, String)],
+    verbose: bool,
+    quiet: bool,
+) -> Result<Option<host_log::Level>, String> {
+    use host_log::Level;
+
+    if verbose {
+        return Ok(Some(Level::Debug));
+    }
+    if quiet {
+        return Ok(Some(Level::Error));
+    }
+    let var = app_env_var(app, program, "LOG");
+    let Some(value) = env.iter().find(|(k, _)| *k == var).map(|(_, v)| v.as_str()) else {
+        return Ok(Some(Level::Warn));
+    };
+    match value.to_ascii_lowercase().as_str() {
+        "trace" => Ok(Some(Level::Trace)),
+        "debug" => Ok(Some(Level::Debug)),
+        "info" => Ok(Some(Level::Info)),
+        "warn" | "" => Ok(Some(Level::Warn)),
+        "error" => Ok(Some(Level::Error)),
+        "off" => Ok(None),
+        _ => Err(format!(
+            "ignoring {var}={value} (expected trace, debug, info, warn, error or off)"
+        )),
+    }
+}
+
+/// Whether help on a stream may use ANSI colors: `CLICOLOR_FORCE` (set and
+/// not `0`) forces them, `NO_COLOR` (set and non-empty) turns them off, and
+/// otherwise they follow `is_terminal`.
+fn color_enabled(env: &[(String, String)], is_terminal: impl FnOnce() -> bool) -> bool {
+    let var = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+    if var("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") {
+        return true;
+    }
+    if var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    is_terminal()
+}
+
+/// The help style for output on `stream`, see [`color_enabled`].
+fn help_style(env: &[(String, String)], stream: Stream) -> claplike::HelpStyle {
+    let is_terminal = || match stream {
+        Stream::Stdout => host_io::stdout_is_terminal(),
+        Stream::Stderr => host_io::stderr_is_terminal(),
+    };
+    if color_enabled(env, is_terminal) {
+        claplike::HelpStyle::colored()
+    } else {
+        claplike::HelpStyle::plain()
+    }
+}
+
+/// [`help_style`] for stdout, with the environment read at call time.
+fn stdout_help_style() -> claplike::HelpStyle {
+    help_style(&host_env::env(), Stream::Stdout)
+}
+
+/// The help style of each stream, resolved on first use so that a run which
+/// prints no help never asks the host whether a stream is a terminal.
+struct HelpStyles<'a> {
+    env: &'a [(String, String)],
+    out: OnceCell<claplike::HelpStyle>,
+    err: OnceCell<claplike::HelpStyle>,
+}
+
+impl<'a> HelpStyles<'a> {
+    fn new(env: &'a [(String, String)]) -> Self {
+        Self {
+            env,
+            out: OnceCell::new(),
+            err: OnceCell::new(),
+        }
+    }
+
+    fn out(&self) -> claplike::HelpStyle {
+        *self.out.get_or_init(|| help_style(self.env, Stream::Stdout))
+    }
+
+    fn err(&self) -> claplike::HelpStyle {
+        *self.err.get_or_init(|| help_style(self.env, Stream::Stderr))
+    }
+}
+
+/// `<APP>_<suffix>`, where `<APP>` is the app name without its package
+/// namespace, upper-cased with non-alphanumerics mapped to `_`
+/// (`example:my-cli` => `MY_CLI_NO_TELEMETRY`).
+fn app_env_var(app: &registry_schema::AppMeta, program: &str, suffix: &str) -> String {
+    let mut var: String = app_display_name(app, program)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    var.push('_');
+    var.push_str(suffix);
+    var
+}
+
+/// The single JSON argument passed to the telemetry command.
+fn telemetry_event_json(command: &str, exit_code: u32, duration_ms: u64) -> String {
+    let mut escaped = String::with_capacity(command.len());
+    for c in command.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    format!(r#"{{"command":"{escaped}","exit_code":{exit_code},"duration_ms":{duration_ms}}}"#)
+}
+
+fn read_arg_file(path: &str) -> Result<String, String> {
+    let bytes = host_fs::read_file(path)?;
+    String::from_utf8(bytes).map_err(|_| "not valid UTF-8".to_string())
+}
+
+fn read_config(path: &str) -> Result<Vec<(String, String)>, String> {
+    config::parse(&read_arg_file(path)?)
+}
+
+/// The user's aliases, from the file `<APP>_ALIASES` names (set but empty
+/// turns them off), else from `$HOME/.<app>/aliases.json` if there is one.
+/// Errors come with the path of the file.
+fn load_aliases(
+    app: &registry_schema::AppMeta,
+    program: &str,
+    env: &[(String, String)],
+) -> Result<Vec<aliases::Alias>, (String, String)> {
+    let var = app_env_var(app, program, "ALIASES");
+    let path = match env.iter().find(|(k, _)| *k == var) {
+        Some((_, path)) if path.is_empty() => return Ok(Vec::new()),
+        Some((_, path)) => path.clone(),
+        None => {
+            let name = app_display_name(app, program);
+            match config::app_file(env, name, aliases::FILE_NAME) {
+                Some(path) if host_fs::exists(&path) => path,
+                _ => return Ok(Vec::new()),
+            }
+        }
+    };
+    read_arg_file(&path)
+        .and_then(|text| aliases::parse(&text))
+        .map_err(|err| (path, err))
+}
+
+/// Names an alias cannot take over: flags, built-ins, groups and commands
+/// (with their aliases).
+fn is_reserved_name(
+    schemas: &[schema::CommandSchema],
+    groups: &[registry_schema::CommandGroup],
+    name: &str,
+) -> bool {
+    name.starts_with('-')
+        || matches!(name, "__complete" | "completions" | "help")
+        || find_group(groups, name).is_some()
+        || find_command_schema(schemas, name).is_some()
+}
+
+/// The config values `m` (or a subcommand of it) took, as the
+/// `WACLI_CONFIG_<NAME>` variables commands read them from. Values that argv
+/// or env overrode are left out: commands don't know which env vars their
+/// args read, so they can't rank them above config themselves.
+fn config_handoff(m: &argv::Matches<'_>, config: &[(String, String)]) -> Vec<(String, String)> {
+    let mut vars = Vec::new();
+    let mut next = Some(m);
+    while let Some(m) = next {
+        for (name, value) in config {
+            let occ = m.occurrences(name);
+            if occ.first().is_some_and(|o| o.source() == argv::ValueSource::Config) {
+                vars.push((claplike::config_env_var(name), value.clone()));
+            }
+        }
+        next = m.subcommand().map(|(_, sub)| sub);
+    }
+    vars
+}
+
+/// Config keys no arg of any command reads, most likely typos. The file is
+/// shared by every command, so a key only has to fit one of them.
+fn unknown_config_keys<'c>(
+    schemas: &[schema::CommandSchema],
+    config: &'c [(String, String)],
+) -> Vec<&'c str> {
+    let declared = |key: &str| {
+        schemas.iter().any(|schema| {
+            schema
+                .args
+                .iter()
+                .chain(schema.subcommands.iter().flat_map(|sub| &sub.args))
+                .any(|arg| arg.name == key)
+        })
+    };
+    config
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .filter(|key| !declared(key))
+        .collect()
+}
+
+/// Whether the app declares a global `--config <PATH>`.
+fn takes_config_path(globals: &[schema::ArgSchema]) -> bool {
+    globals
+        .iter()
+        .any(|arg| arg.name == config::GLOBAL_ARG && arg.takes_value)
+}
+
 fn split_program_and_argv(mut args: Vec<String>) -> (String, Vec<String>) {
     let program = if args.is_empty() {
         String::new()
@@ -140,6 +826,29 @@ fn split_program_and_argv(mut args: Vec<String>) -> (String, Vec<String>) {
     (program, args)
 }
 
+/// The app name without its package namespace, or the program name when the
+/// app has none (`example:my-cli` => `my-cli`).
+fn app_display_name<'a>(app: &'a registry_schema::AppMeta, program: &'a str) -> &'a str {
+    let name = app.name.trim();
+    if name.is_empty() {
+        return program_display_name(program);
+    }
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// What the user typed to reach `schema` (`my-cli db seed`), for hints.
+fn command_line(
+    app: &registry_schema::AppMeta,
+    program: &str,
+    schema: &schema::CommandSchema,
+) -> String {
+    format!(
+        "{} {}",
+        app_display_name(app, program),
+        display_schema(schema).name
+    )
+}
+
 fn program_display_name(program: &str) -> &str {
     program
         .rsplit('/')
@@ -148,76 +857,356 @@ fn program_display_name(program: &str) -> &str {
         .unwrap_or(program)
 }
 
-fn print_global_version(program: &str, app: &registry_schema::AppMeta) {
+fn print_global_version(
+    out: &mut Out<impl Sink>,
+    program: &str,
+    app: &registry_schema::AppMeta,
+    with_build_info: bool,
+) {
     let name = if app.name.trim().is_empty() {
         program_display_name(program)
     } else {
         app.name.trim()
     };
-    let mut out = String::new();
-    out.push_str(name);
-    out.push(' ');
-    if app.version.trim().is_empty() {
-        out.push_str(env!("CARGO_PKG_VERSION"));
+    let version = if app.version.trim().is_empty() {
+        env!("CARGO_PKG_VERSION")
     } else {
-        out.push_str(app.version.trim());
+        app.version.trim()
+    };
+    out.line(Stream::Stdout, &format!("{name} {version}"));
+    if with_build_info {
+        let info = &app.build_info;
+        if !info.wacli_version.trim().is_empty() {
+            out.line(
+                Stream::Stdout,
+                &format!("wacli {}", info.wacli_version.trim()),
+            );
+        }
+        if let Some(rev) = info.git_revision.as_deref() {
+            out.line(Stream::Stdout, &format!("git-revision: {}", rev.trim()));
+        }
+        for line in &info.built_with {
+            out.line(Stream::Stdout, line.trim_end());
+        }
     }
-    out.push('\n');
-    host_io::stdout_write(out.as_bytes());
-    host_io::stdout_flush();
 }
 
-fn print_internal_error(msg: &str) {
-    let mut out = String::new();
-    out.push_str("Internal error: ");
-    out.push_str(msg.trim_end());
-    out.push('\n');
-    host_io::stderr_write(out.as_bytes());
-    host_io::stderr_flush();
+fn print_internal_error(out: &mut Out<impl Sink>, msg: &str) {
+    out.line(
+        Stream::Stderr,
+        &format!("Internal error: {}", msg.trim_end()),
+    );
+}
+
+fn print_config_error(out: &mut Out<impl Sink>, path: &str, msg: &str) {
+    out.line(
+        Stream::Stderr,
+        &format!("Invalid config file {path}: {}", msg.trim_end()),
+    );
 }
 
-fn print_unknown_command(name: &str) {
-    let mut out = String::new();
-    out.push_str(&format!("Unknown command: {name}\n"));
-    host_io::stderr_write(out.as_bytes());
-    host_io::stderr_flush();
+fn print_alias_file_error(out: &mut Out<impl Sink>, path: &str, msg: &str) {
+    out.line(
+        Stream::Stderr,
+        &format!("Invalid alias file {path}: {}", msg.trim_end()),
+    );
 }
 
-fn print_global_help(app: &registry_schema::AppMeta, schemas: &[schema::CommandSchema]) {
-    let mut out = String::new();
+/// After `help <alias>`: the help of the command (or group) the alias runs,
+/// if it runs one.
+fn print_expanded_alias_help(
+    out: &mut Out<impl Sink>,
+    expanded: &[String],
+    schemas: &[schema::CommandSchema],
+    groups: &[registry_schema::CommandGroup],
+    globals: &[schema::ArgSchema],
+    style: &claplike::HelpStyle,
+) {
+    let Some(first) = expanded.first() else {
+        return;
+    };
+    if let Some(group) = find_group(groups, first) {
+        out.line(Stream::Stdout, "");
+        match expanded
+            .get(1)
+            .and_then(|sub| find_member_schema(schemas, group, sub))
+        {
+            Some(schema) => print_command_help(out, schema, globals, style),
+            None => print_group_help(out, group, schemas, style),
+        }
+    } else if let Some(schema) = find_command_schema(schemas, first) {
+        out.line(Stream::Stdout, "");
+        print_command_help(out, schema, globals, style);
+    }
+}
+
+fn print_warnings(out: &mut Out<impl Sink>, warnings: &[String]) {
+    for warning in warnings {
+        out.line(Stream::Stderr, &format!("warning: {warning}"));
+    }
+}
+
+fn print_unknown_command(out: &mut Out<impl Sink>, name: &str) {
+    out.line(Stream::Stderr, &format!("Unknown command: {name}"));
+}
+
+fn print_global_help(
+    out: &mut Out<impl Sink>,
+    app: &registry_schema::AppMeta,
+    schemas: &[schema::CommandSchema],
+    groups: &[registry_schema::CommandGroup],
+    style: &claplike::HelpStyle,
+) {
+    let mut text = String::new();
     if !app.name.trim().is_empty() {
-        out.push_str(app.name.trim());
+        text.push_str(app.name.trim());
         if !app.version.trim().is_empty() {
-            out.push(' ');
-            out.push_str(app.version.trim());
+            text.push(' ');
+            text.push_str(app.version.trim());
         }
-        out.push('\n');
+        text.push('\n');
         if !app.description.trim().is_empty() {
-            out.push_str(app.description.trim_end());
-            out.push('\n');
+            text.push_str(app.description.trim_end());
+            text.push('\n');
         }
-        out.push('\n');
+        text.push('\n');
     }
-    out.push_str("Available commands:\n");
+    text.push_str(&format!("{}\n", claplike::paint(style.header, "Available commands:")));
 
-    let mut cmds: Vec<&schema::CommandSchema> = schemas.iter().collect();
-    cmds.sort_by(|a, b| a.name.cmp(&b.name));
+    // Top-level commands and groups share one sorted list; group members are
+    // listed (indented) under their group.
+    enum Entry<'a> {
+        Command(&'a schema::CommandSchema),
+        Group(&'a registry_schema::CommandGroup),
+    }
 
-    for cmd in cmds {
-        if cmd.name.is_empty() || cmd.hidden {
-            continue;
+    let mut entries: Vec<(&str, Entry<'_>)> = schemas
+        .iter()
+        .filter(|c| group_of(&c.name).is_none())
+        .map(|c| (c.name.as_str(), Entry::Command(c)))
+        .chain(groups.iter().map(|g| (g.name.as_str(), Entry::Group(g))))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (_, entry) in entries {
+        match entry {
+            Entry::Command(cmd) => {
+                if cmd.name.is_empty() || cmd.hidden {
+                    continue;
+                }
+                let label = command_label(&cmd.name, &cmd.aliases);
+                push_command_row(&mut text, 2, 16, &label, &cmd.summary, style);
+            }
+            Entry::Group(group) => {
+                push_command_row(&mut text, 2, 16, &group.name, &group.summary, style);
+                for cmd in group_members(schemas, group) {
+                    let aliases: Vec<String> = cmd
+                        .aliases
+                        .iter()
+                        .map(|a| member_name(group, a).to_string())
+                        .collect();
+                    let label = command_label(member_name(group, &cmd.name), &aliases);
+                    push_command_row(&mut text, 4, 14, &label, &cmd.summary, style);
+                }
+            }
         }
-        if cmd.summary.is_empty() {
-            out.push_str(&format!("  {}\n", cmd.name));
-        } else {
-            out.push_str(&format!("  {:<16} {}\n", cmd.name, cmd.summary));
+    }
+
+    text.push_str("\nRun `help <command>` or `<command> --help` for more information.\n");
+
+    out.block(Stream::Stdout, &text);
+}
+
+/// `greet (hi, hello)`: a command name with its visible aliases.
+fn command_label(name: &str, aliases: &[String]) -> String {
+    if aliases.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} ({})", aliases.join(", "))
+    }
+}
+
+fn push_command_row(
+    out: &mut String,
+    indent: usize,
+    width: usize,
+    name: &str,
+    summary: &str,
+    style: &claplike::HelpStyle,
+) {
+    let painted = claplike::paint(style.literal, name);
+    if summary.is_empty() {
+        out.push_str(&format!("{:indent$}{}\n", "", painted));
+    } else {
+        let pad = width.saturating_sub(name.chars().count());
+        out.push_str(&format!("{:indent$}{painted}{:pad$} {}\n", "", "", summary));
+    }
+}
+
+fn print_group_help(
+    out: &mut Out<impl Sink>,
+    group: &registry_schema::CommandGroup,
+    schemas: &[schema::CommandSchema],
+    style: &claplike::HelpStyle,
+) {
+    let mut text = String::new();
+    if group.summary.trim().is_empty() {
+        text.push_str(&format!("{}\n", group.name));
+    } else {
+        text.push_str(&format!("{} - {}\n", group.name, group.summary.trim()));
+    }
+    text.push_str(&format!(
+        "\n{} {} <COMMAND> [ARGS]...\n",
+        claplike::paint(style.header, "Usage:"),
+        group.name
+    ));
+
+    if !group.description.trim().is_empty() {
+        text.push('\n');
+        text.push_str(group.description.trim_end());
+        text.push('\n');
+    }
+
+    let rows: Vec<(&str, &str)> = group_members(schemas, group)
+        .map(|cmd| (member_name(group, &cmd.name), cmd.summary.trim()))
+        .collect();
+    if !rows.is_empty() {
+        text.push_str(&format!("\n{}\n", claplike::paint(style.header, "Commands:")));
+        let width = rows.iter().map(|(n, _)| n.chars().count()).max().unwrap_or(0);
+        for (name, summary) in rows {
+            let painted = claplike::paint(style.literal, name);
+            if summary.is_empty() {
+                text.push_str(&format!("  {}\n", painted));
+            } else {
+                let pad = width - name.chars().count();
+                text.push_str(&format!("  {painted}{:pad$}  {}\n", "", summary));
+            }
+        }
+    }
+
+    text.push_str(&format!(
+        "\nRun `help {0} <command>` or `{0} <command> --help` for more information.\n",
+        group.name
+    ));
+
+    out.block(Stream::Stdout, &text);
+}
+
+fn find_group<'a>(
+    groups: &'a [registry_schema::CommandGroup],
+    raw: &str,
+) -> Option<&'a registry_schema::CommandGroup> {
+    groups.iter().find(|g| g.name == raw)
+}
+
+/// Visible member schemas of `group`, sorted by name.
+fn group_members<'a>(
+    schemas: &'a [schema::CommandSchema],
+    group: &registry_schema::CommandGroup,
+) -> impl Iterator<Item = &'a schema::CommandSchema> {
+    let mut members: Vec<&schema::CommandSchema> = schemas
+        .iter()
+        .filter(|c| !c.hidden && group_of(&c.name) == Some(group.name.as_str()))
+        .collect();
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+    members.into_iter()
+}
+
+/// Look up `<group>/<raw>` by name or alias.
+fn find_member_schema<'a>(
+    schemas: &'a [schema::CommandSchema],
+    group: &registry_schema::CommandGroup,
+    raw: &str,
+) -> Option<&'a schema::CommandSchema> {
+    find_command_schema(schemas, &format!("{}/{raw}", group.name))
+}
+
+/// The group part of a registry name like `db/migrate`.
+fn group_of(name: &str) -> Option<&str> {
+    name.split_once('/').map(|(group, _)| group)
+}
+
+/// The member part of a registry name like `db/migrate`.
+fn member_name<'a>(group: &registry_schema::CommandGroup, name: &'a str) -> &'a str {
+    name.strip_prefix(group.name.as_str())
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(name)
+}
+
+/// Present grouped commands as `<group> <command>` in help/version output.
+fn display_schema(schema: &schema::CommandSchema) -> Cow<'_, schema::CommandSchema> {
+    let Some((group, member)) = schema.name.split_once('/') else {
+        return Cow::Borrowed(schema);
+    };
+    let mut display = schema.clone();
+    display.name = format!("{group} {member}");
+    // Registry aliases are qualified like the name (`db/up`).
+    let prefix = format!("{group}/");
+    for alias in display.aliases.iter_mut().chain(&mut display.hidden_aliases) {
+        if let Some(bare) = alias.strip_prefix(&prefix) {
+            *alias = bare.to_string();
         }
     }
+    let usage = schema.usage.trim();
+    if usage == member || usage.starts_with(&format!("{member} ")) {
+        display.usage = format!("{group} {usage}");
+    }
+    Cow::Owned(display)
+}
 
-    out.push_str("\nRun `help <command>` or `<command> --help` for more information.\n");
+/// A command schema with its flattened `subcommands` rebuilt into a tree, so
+/// the shared parser can dispatch on them.
+struct SchemaNode {
+    schema: schema::CommandSchema,
+    /// The app's global args, listed in the help of every node.
+    globals: Vec<schema::ArgSchema>,
+    /// Style of the help `parse` renders (the root's applies to the whole
+    /// tree), resolved only when help is rendered.
+    style: fn() -> claplike::HelpStyle,
+    subcommands: Vec<SchemaNode>,
+}
 
-    host_io::stdout_write(out.as_bytes());
-    host_io::stdout_flush();
+/// The display form of `schema` (see [`display_schema`]) as a [`SchemaNode`].
+fn command_tree(schema: &schema::CommandSchema, globals: &[schema::ArgSchema]) -> SchemaNode {
+    let mut root = SchemaNode {
+        schema: display_schema(schema).into_owned(),
+        globals: globals.to_vec(),
+        style: claplike::HelpStyle::plain,
+        subcommands: Vec::new(),
+    };
+    for sub in std::mem::take(&mut root.schema.subcommands) {
+        let Some((name, parents)) = sub.path.split_last() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for parent in parents {
+            match node.subcommands.iter().position(|n| &n.schema.name == parent) {
+                Some(i) => node = &mut node.subcommands[i],
+                None => break,
+            }
+        }
+        node.subcommands.push(SchemaNode {
+            schema: schema::CommandSchema {
+                name: name.clone(),
+                summary: sub.summary,
+                usage: sub.usage,
+                aliases: sub.aliases,
+                hidden_aliases: sub.hidden_aliases,
+                version: sub.version,
+                hidden: sub.hidden,
+                description: sub.description,
+                examples: sub.examples,
+                args: sub.args,
+                allow_arg_files: false,
+                subcommands: Vec::new(),
+                groups: sub.groups,
+            },
+            globals: globals.to_vec(),
+            style: claplike::HelpStyle::plain,
+            subcommands: Vec::new(),
+        });
+    }
+    root
 }
 
 fn find_command_schema<'a>(
@@ -227,51 +1216,175 @@ fn find_command_schema<'a>(
     schemas
         .iter()
         .find(|m| m.name == raw)
-        .or_else(|| schemas.iter().find(|m| m.aliases.iter().any(|a| a == raw)))
+        .or_else(|| {
+            schemas.iter().find(|m| {
+                m.aliases
+                    .iter()
+                    .chain(&m.hidden_aliases)
+                    .any(|a| a == raw)
+            })
+        })
 }
 
-fn print_command_help(schema: &schema::CommandSchema) {
-    let text = claplike::help(schema);
-    host_io::stdout_write(text.as_bytes());
-    host_io::stdout_flush();
+/// Split the app's `globals` out of `cmd_args` (see
+/// [`claplike::split_global_args`]) and validate them. Returns the indices of
+/// the command's argv in `cmd_args` and, for every global arg, its `WACLI_GLOBAL_<NAME>` variable with
+/// the value to hand to the command: the last value given (or its env var or
+/// default), `1` for a flag that is set, and `None` (unset) otherwise.
+fn take_global_args(
+    schema: &schema::CommandSchema,
+    globals: &[schema::ArgSchema],
+    cmd_args: &[String],
+    env: &[(String, String)],
+) -> Result<(Vec<usize>, Vec<(String, Option<String>)>), claplike::ParseError> {
+    if globals.is_empty() {
+        return Ok(((0..cmd_args.len()).collect(), Vec::new()));
+    }
+    let (global, rest) =
+        claplike::split_global_arg_indices(&command_tree(schema, globals), cmd_args);
+    let global: Vec<String> = global.into_iter().map(|i| cmd_args[i].clone()).collect();
+    let meta = schema::CommandSchema {
+        args: globals.to_vec(),
+        subcommands: Vec::new(),
+        groups: Vec::new(),
+        ..schema.clone()
+    };
+    let m = match claplike::parse_with_env(&meta, &global, env)? {
+        claplike::ParseOutcome::Matches(m) => m,
+        // `-h`/`-V` are never global, so they stay in `rest`.
+        _ => return Ok((rest, Vec::new())),
+    };
+    let values = globals
+        .iter()
+        .map(|arg| {
+            let value = if arg.takes_value {
+                m.get(&arg.name).map(str::to_string)
+            } else {
+                m.get_flag(&arg.name).then(|| "1".to_string())
+            };
+            (claplike::global_arg_env_var(&arg.name), value)
+        })
+        .collect();
+    Ok((rest, values))
 }
 
-fn print_command_version(schema: &schema::CommandSchema) {
-    let text = claplike::version(schema);
-    host_io::stdout_write(text.as_bytes());
-    host_io::stdout_flush();
+fn print_command_help(
+    out: &mut Out<impl Sink>,
+    schema: &schema::CommandSchema,
+    globals: &[schema::ArgSchema],
+    style: &claplike::HelpStyle,
+) {
+    out.block(
+        Stream::Stdout,
+        &claplike::help_styled(&command_tree(schema, globals), style),
+    );
 }
 
-fn print_invalid_args(msg: &str, schema: &schema::CommandSchema) {
-    let mut out = String::new();
-    out.push_str(msg.trim_end());
-    out.push('\n');
-    out.push('\n');
-    out.push_str(&claplike::help(schema));
+fn print_command_version(out: &mut Out<impl Sink>, schema: &schema::CommandSchema) {
+    out.block(
+        Stream::Stdout,
+        &claplike::version(&command_tree(schema, &[])),
+    );
+}
 
-    host_io::stderr_write(out.as_bytes());
-    host_io::stderr_flush();
+/// Print a parse error for `schema`, whether core or the plugin caught it:
+/// the message, a `see '<cmd> --help'` hint, and (only when required
+/// arguments are missing) the full command help.
+fn print_invalid_args(
+    out: &mut Out<impl Sink>,
+    msg: &str,
+    schema: &schema::CommandSchema,
+    globals: &[schema::ArgSchema],
+    command_line: &str,
+    style: &claplike::HelpStyle,
+) {
+    out.line(Stream::Stderr, msg.trim_end());
+    out.line(Stream::Stderr, &messages::help_hint(command_line));
+    if messages::is_missing_required(msg) {
+        out.line(Stream::Stderr, "");
+        out.block(
+            Stream::Stderr,
+            &claplike::help_styled(&command_tree(schema, globals), style),
+        );
+    }
 }
 
-fn report_command_error(name: &str, err: types::CommandError) {
-    let message = match err {
-        types::CommandError::UnknownCommand(cmd) => format!("Unknown command: {cmd}\n"),
-        types::CommandError::InvalidArgs(msg)
-        | types::CommandError::Failed(msg)
-        | types::CommandError::Io(msg) => format!("{msg}\n"),
-    };
+/// `err` followed by the command line as typed (`demo deploy --formt json`)
+/// with carets under the token it points at, when it points at one.
+///
+/// `err` is about `parsed`, which `origins` maps back to `typed`: a token read
+/// from an `@file` is pointed at through its `@file` argument, and global
+/// args taken out of `parsed` are still shown.
+fn render_parse_error(
+    err: claplike::SpannedError,
+    command_line: &str,
+    typed: &[String],
+    parsed: &[String],
+    origins: &[usize],
+) -> String {
+    let mut argv: Vec<String> = command_line.split_whitespace().map(str::to_string).collect();
+    let offset = argv.len();
+    argv.extend_from_slice(typed);
+    let span = err.span.and_then(|span| {
+        let index = *origins.get(span.index)?;
+        let token = typed.get(index)?;
+        let range = if parsed.get(span.index) == Some(token) {
+            span.range
+        } else {
+            0..token.len()
+        };
+        Some(claplike::ArgSpan {
+            index: index + offset,
+            range,
+        })
+    });
+    claplike::render_error(&claplike::SpannedError { span, ..err }, &argv)
+}
 
-    let mut out = String::new();
-    out.push_str(&message);
-    out.push_str("Run with --help to see available commands.\n");
+/// Print a command failure to stderr. Returns `true` when the caller should
+/// follow up with the global help (the registry reported an unnamed command).
+fn print_command_error(out: &mut Out<impl Sink>, name: &str, err: types::CommandError) -> bool {
+    match err {
+        types::CommandError::UnknownCommand(cmd) => {
+            out.line(Stream::Stderr, &format!("Unknown command: {cmd}"));
+        }
+        types::CommandError::Failed(msg) => {
+            let msg = decode_exit_code(&msg).map_or(msg.as_str(), |(_, msg)| msg);
+            out.line(Stream::Stderr, msg);
+        }
+        types::CommandError::InvalidArgs(msg) | types::CommandError::Io(msg) => {
+            out.line(Stream::Stderr, &msg)
+        }
+    }
+    out.line(Stream::Stderr, "Run with --help to see available commands.");
+    name.is_empty()
+}
 
-    host_io::stderr_write(out.as_bytes());
-    host_io::stderr_flush();
+/// Exit code for arguments rejected by core or by the command.
+const EXIT_USAGE: u32 = 2;
 
-    if name.is_empty() {
+/// Exit code for a command that does not exist (what shells use for one
+/// that is not found).
+const EXIT_UNKNOWN_COMMAND: u32 = 127;
+
+/// The exit code for a command error: 2 for invalid arguments, 127 for an
+/// unknown command, and for other failures the code a `failed` message
+/// carries, otherwise 1.
+fn exit_code(err: &types::CommandError) -> u32 {
+    match err {
+        types::CommandError::InvalidArgs(_) => EXIT_USAGE,
+        types::CommandError::UnknownCommand(_) => EXIT_UNKNOWN_COMMAND,
+        types::CommandError::Failed(msg) => decode_exit_code(msg).map_or(1, |(code, _)| code),
+        types::CommandError::Io(_) => 1,
+    }
+}
+
+fn report_command_error(out: &mut Out<impl Sink>, name: &str, err: types::CommandError) {
+    if print_command_error(out, name, err) {
         let app = registry_schema::get_app_meta();
         let schemas = registry_schema::list_schemas();
-        print_global_help(&app, &schemas);
+        let groups = registry_schema::list_groups();
+        print_global_help(out, &app, &schemas, &groups, &stdout_help_style());
     }
 }
 
@@ -337,6 +1450,36 @@ impl claplike::ArgDefLike for schema::ArgSchema {
     fn hidden(&self) -> bool {
         self.hidden
     }
+
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    fn allow_hyphen_values(&self) -> bool {
+        self.allow_hyphen_values
+    }
+
+    fn countable(&self) -> bool {
+        self.countable
+    }
+
+    fn var_arg(&self) -> bool {
+        self.var_arg
+    }
+}
+
+impl claplike::ArgGroupLike for schema::ArgGroup {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn required(&self) -> bool {
+        self.required
+    }
+
+    fn multiple(&self) -> bool {
+        self.multiple
+    }
 }
 
 impl claplike::CommandMetaLike for schema::CommandSchema {
@@ -358,6 +1501,10 @@ impl claplike::CommandMetaLike for schema::CommandSchema {
         self.aliases.as_slice()
     }
 
+    fn hidden_aliases(&self) -> &[String] {
+        self.hidden_aliases.as_slice()
+    }
+
     fn version(&self) -> &str {
         &self.version
     }
@@ -377,4 +1524,886 @@ impl claplike::CommandMetaLike for schema::CommandSchema {
     fn args(&self) -> &[Self::ArgDef] {
         self.args.as_slice()
     }
+
+    fn groups(&self) -> Vec<&dyn claplike::ArgGroupLike> {
+        self.groups
+            .iter()
+            .map(|g| g as &dyn claplike::ArgGroupLike)
+            .collect()
+    }
+
+    fn allow_arg_files(&self) -> bool {
+        self.allow_arg_files
+    }
+}
+
+impl claplike::CommandMetaLike for SchemaNode {
+    type ArgDef = schema::ArgSchema;
+
+    fn name(&self) -> &str {
+        &self.schema.name
+    }
+
+    fn summary(&self) -> &str {
+        &self.schema.summary
+    }
+
+    fn usage(&self) -> &str {
+        &self.schema.usage
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.schema.aliases.as_slice()
+    }
+
+    fn hidden_aliases(&self) -> &[String] {
+        self.schema.hidden_aliases.as_slice()
+    }
+
+    fn version(&self) -> &str {
+        &self.schema.version
+    }
+
+    fn hidden(&self) -> bool {
+        self.schema.hidden
+    }
+
+    fn description(&self) -> &str {
+        &self.schema.description
+    }
+
+    fn examples(&self) -> &[String] {
+        self.schema.examples.as_slice()
+    }
+
+    fn args(&self) -> &[Self::ArgDef] {
+        self.schema.args.as_slice()
+    }
+
+    fn groups(&self) -> Vec<&dyn claplike::ArgGroupLike> {
+        self.schema
+            .groups
+            .iter()
+            .map(|g| g as &dyn claplike::ArgGroupLike)
+            .collect()
+    }
+
+    fn allow_arg_files(&self) -> bool {
+        self.schema.allow_arg_files
+    }
+
+    fn global_args(&self) -> Vec<&dyn claplike::ArgDefLike> {
+        self.globals
+            .iter()
+            .map(|a| a as &dyn claplike::ArgDefLike)
+            .collect()
+    }
+
+    fn help_style(&self) -> claplike::HelpStyle {
+        (self.style)()
+    }
+
+    fn subcommands(&self) -> &[Self] {
+        &self.subcommands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::out::testing::{Capture, Event};
+
+    const PLAIN: claplike::HelpStyle = claplike::HelpStyle::plain();
+
+    fn command(name: &str, summary: &str) -> schema::CommandSchema {
+        schema::CommandSchema {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            usage: String::new(),
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden: false,
+            description: String::new(),
+            examples: Vec::new(),
+            args: Vec::new(),
+            allow_arg_files: false,
+            subcommands: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn positional(name: &str, value_name: &str, help: &str) -> schema::ArgSchema {
+        schema::ArgSchema {
+            name: name.to_string(),
+            short: None,
+            long: None,
+            help: help.to_string(),
+            required: true,
+            default_value: None,
+            env: None,
+            value_name: Some(value_name.to_string()),
+            takes_value: true,
+            multiple: false,
+            value_type: None,
+            possible_values: Vec::new(),
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            hidden: false,
+            complete_command: None,
+            group: None,
+            allow_hyphen_values: false,
+            countable: false,
+            var_arg: false,
+        }
+    }
+
+    fn app(name: &str, version: &str, description: &str) -> registry_schema::AppMeta {
+        registry_schema::AppMeta {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: description.to_string(),
+            build_info: registry_schema::BuildInfo {
+                wacli_version: String::new(),
+                git_revision: None,
+                built_with: Vec::new(),
+            },
+            telemetry_command: None,
+            fallback_command: None,
+            interactive_picker: false,
+            env_warnings: false,
+            global_args: Vec::new(),
+        }
+    }
+
+    fn db_group() -> registry_schema::CommandGroup {
+        registry_schema::CommandGroup {
+            name: "db".to_string(),
+            summary: "Database tasks".to_string(),
+            description: "Manage the app database.".to_string(),
+        }
+    }
+
+    fn fixture_schemas() -> Vec<schema::CommandSchema> {
+        let mut secret = command("secret", "Hidden command");
+        secret.hidden = true;
+        let mut seed = command("db/seed", "Load seed data");
+        seed.usage = "seed <FILE>".to_string();
+        seed.args = vec![positional("file", "FILE", "Seed data file")];
+        let mut greet = command("greet", "Greet someone");
+        greet.aliases = vec!["hi".to_string()];
+        greet.hidden_aliases = vec!["g".to_string()];
+        let mut migrate = command("db/migrate", "Apply pending migrations");
+        migrate.aliases = vec!["db/up".to_string()];
+        vec![
+            greet,
+            migrate,
+            seed,
+            secret,
+            command("zzz", ""),
+        ]
+    }
+
+    fn capture(f: impl FnOnce(&mut Out<&mut Capture>)) -> Capture {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            f(&mut out);
+        }
+        cap
+    }
+
+    #[test]
+    fn global_help_golden() {
+        let cap = capture(|out| {
+            print_global_help(
+                out,
+                &app("demo", "1.2.3", "A demo app."),
+                &fixture_schemas(),
+                &[db_group()],
+                &PLAIN,
+            )
+        });
+        assert_eq!(
+            cap.stdout(),
+            "demo 1.2.3\n\
+             A demo app.\n\
+             \n\
+             Available commands:\n\
+             \x20 db               Database tasks\n\
+             \x20   migrate (up)   Apply pending migrations\n\
+             \x20   seed           Load seed data\n\
+             \x20 greet (hi)       Greet someone\n\
+             \x20 zzz\n\
+             \n\
+             Run `help <command>` or `<command> --help` for more information.\n"
+        );
+        assert_eq!(cap.stderr(), "");
+    }
+
+    #[test]
+    fn group_help_golden() {
+        let cap = capture(|out| print_group_help(out, &db_group(), &fixture_schemas(), &PLAIN));
+        assert_eq!(
+            cap.stdout(),
+            "db - Database tasks\n\
+             \n\
+             Usage: db <COMMAND> [ARGS]...\n\
+             \n\
+             Manage the app database.\n\
+             \n\
+             Commands:\n\
+             \x20 migrate  Apply pending migrations\n\
+             \x20 seed     Load seed data\n\
+             \n\
+             Run `help db <command>` or `db <command> --help` for more information.\n"
+        );
+    }
+
+    #[test]
+    fn global_version_golden() {
+        let mut meta = app("demo", "1.2.3", "");
+        meta.build_info = registry_schema::BuildInfo {
+            wacli_version: "0.0.43".to_string(),
+            git_revision: Some("abc123".to_string()),
+            built_with: vec!["rustc 1.92.0".to_string()],
+        };
+        let cap = capture(|out| print_global_version(out, "/bin/demo", &meta, true));
+        assert_eq!(
+            cap.stdout(),
+            "demo 1.2.3\nwacli 0.0.43\ngit-revision: abc123\nrustc 1.92.0\n"
+        );
+
+        let cap =
+            capture(|out| print_global_version(out, "/usr/bin/tool", &app("", "", ""), false));
+        assert_eq!(
+            cap.stdout(),
+            format!("tool {}\n", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn command_help_and_version_golden() {
+        let schemas = fixture_schemas();
+        let seed = find_command_schema(&schemas, "db/seed").unwrap();
+        let mut migrate = command("db/migrate", "Apply pending migrations");
+        migrate.version = "0.1.0".to_string();
+
+        let cap = capture(|out| {
+            print_command_help(out, seed, &[], &PLAIN);
+            print_command_version(out, &migrate);
+        });
+        assert_eq!(
+            cap.stdout(),
+            "db seed - Load seed data\n\
+             \n\
+             Usage: db seed <FILE>\n\
+             \n\
+             Arguments:\n\
+             \x20 <FILE>  Seed data file\n\
+             \n\
+             Options:\n\
+             \x20 -h, --help     Show help information\n\
+             \x20 -V, --version  Show version information\n\
+             db migrate 0.1.0\n"
+        );
+    }
+
+    #[test]
+    fn hidden_aliases_resolve_and_group_aliases_show_unqualified() {
+        let schemas = fixture_schemas();
+        let greet = find_command_schema(&schemas, "g").unwrap();
+        assert_eq!(greet.name, "greet");
+
+        let migrate = find_command_schema(&schemas, "db/up").unwrap();
+        let cap = capture(|out| print_command_help(out, migrate, &[], &PLAIN));
+        assert!(
+            cap.stdout().starts_with(
+                "db migrate - Apply pending migrations\n\
+                 \n\
+                 Usage: db migrate\n\
+                 Aliases: up\n"
+            ),
+            "{}",
+            cap.stdout()
+        );
+        let cap = capture(|out| print_command_help(out, greet, &[], &PLAIN));
+        assert!(!cap.stdout().contains("g,") && !cap.stdout().contains(", g"));
+    }
+
+    fn global_args() -> Vec<schema::ArgSchema> {
+        let mut verbose = option("--verbose", None);
+        verbose.name = "verbose".to_string();
+        verbose.short = Some("-v".to_string());
+        verbose.help = "Print more".to_string();
+        let mut color = option("--color", Some("WHEN"));
+        color.name = "color".to_string();
+        color.possible_values = vec!["auto".to_string(), "never".to_string()];
+        color.default_value = Some("auto".to_string());
+        vec![verbose, color]
+    }
+
+    #[test]
+    fn global_args_leave_argv_and_become_env_vars() {
+        let schemas = fixture_schemas();
+        let seed = find_command_schema(&schemas, "db/seed").unwrap();
+        let argv: Vec<String> = ["-v", "seed.sql", "--color", "never"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let (rest, values) = take_global_args(seed, &global_args(), &argv, &[]).unwrap();
+        assert_eq!(rest, [1]);
+        assert_eq!(
+            values,
+            [
+                ("WACLI_GLOBAL_VERBOSE".to_string(), Some("1".to_string())),
+                ("WACLI_GLOBAL_COLOR".to_string(), Some("never".to_string())),
+            ]
+        );
+
+        // Unset flags are removed; defaults are still handed over.
+        let (_, values) = take_global_args(seed, &global_args(), &argv[1..2], &[]).unwrap();
+        assert_eq!(values[0], ("WACLI_GLOBAL_VERBOSE".to_string(), None));
+        assert_eq!(values[1].1.as_deref(), Some("auto"));
+
+        let (rest, values) = take_global_args(seed, &[], &argv, &[]).unwrap();
+        assert_eq!(rest, [0, 1, 2, 3]);
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn invalid_global_args_are_reported_with_the_command_help() {
+        let schemas = fixture_schemas();
+        let seed = find_command_schema(&schemas, "db/seed").unwrap();
+        let argv = ["seed.sql".to_string(), "--color=always".to_string()];
+        let err = take_global_args(seed, &global_args(), &argv, &[]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "invalid value 'always' for '--color'. possible values: auto, never"
+        );
+
+        let cap = capture(|out| print_command_help(out, seed, &global_args(), &PLAIN));
+        assert!(
+            cap.stdout().ends_with(
+                "Global options:\n\
+                 \x20 -v, --verbose   Print more\n\
+                 \x20 --color <WHEN>  [default: auto]\n"
+            ),
+            "{}",
+            cap.stdout()
+        );
+    }
+
+    #[test]
+    fn invalid_args_golden() {
+        let schemas = fixture_schemas();
+        let seed = find_command_schema(&schemas, "db/seed").unwrap();
+        let cap = capture(|out| {
+            print_invalid_args(
+                out,
+                "missing required argument: <FILE>\n",
+                seed,
+                &[],
+                "demo db seed",
+                &PLAIN,
+            )
+        });
+        assert_eq!(cap.stdout(), "");
+        assert_eq!(
+            cap.stderr(),
+            format!(
+                "missing required argument: <FILE>\n\
+                 see 'demo db seed --help'\n\
+                 \n{}",
+                claplike::help(display_schema(seed).as_ref())
+            )
+        );
+
+        let cap = capture(|out| {
+            print_invalid_args(out, "unknown flag: --nope", seed, &[], "demo db seed", &PLAIN);
+        });
+        assert_eq!(
+            cap.stderr(),
+            "unknown flag: --nope\nsee 'demo db seed --help'\n"
+        );
+    }
+
+    fn option(name: &str, value_name: Option<&str>) -> schema::ArgSchema {
+        schema::ArgSchema {
+            long: Some(name.to_string()),
+            required: false,
+            value_name: value_name.map(str::to_string),
+            takes_value: value_name.is_some(),
+            ..positional(name, "", "")
+        }
+    }
+
+    /// The same mistake must read the same whether core's pre-dispatch check
+    /// or the plugin (returning `invalid-args` built from `messages`) catches it.
+    /// Core's check adds a caret line on top (see `parse_errors_point_at_the_token`).
+    #[test]
+    fn invalid_args_match_between_core_and_plugin() {
+        let mut export = command("db/export", "Export data");
+        let mut format = option("format", Some("FMT"));
+        format.possible_values = vec!["json".to_string(), "csv".to_string()];
+        let mut quiet = option("quiet", None);
+        quiet.conflicts_with = vec!["verbose".to_string()];
+        let mut gzip = option("gzip", None);
+        gzip.requires = vec!["out".to_string()];
+        export.args = vec![
+            positional("file", "FILE", "Input file"),
+            format,
+            quiet,
+            option("verbose", None),
+            gzip,
+            option("out", Some("PATH")),
+        ];
+        let help = claplike::help(display_schema(&export).as_ref());
+        let hint = "see 'demo db export --help'\n";
+
+        let cases: [(&[&str], String, String); 5] = [
+            (
+                &["a.db", "--nope"],
+                messages::unknown_flag("--nope"),
+                format!("unknown flag: --nope\n{hint}"),
+            ),
+            (
+                &[],
+                messages::missing_required(&["<FILE>"]),
+                format!("missing required argument: <FILE>\n{hint}\n{help}"),
+            ),
+            (
+                &["a.db", "--format", "xml"],
+                messages::invalid_value("xml", "--format", &["json", "csv"]),
+                format!("invalid value 'xml' for '--format'. possible values: json, csv\n{hint}"),
+            ),
+            (
+                &["a.db", "--quiet", "--verbose"],
+                messages::conflict("--quiet", "--verbose"),
+                format!("argument '--quiet' cannot be used with '--verbose'\n{hint}"),
+            ),
+            (
+                &["a.db", "--gzip"],
+                messages::requires("--gzip", "--out"),
+                format!("argument '--gzip' requires '--out'\n{hint}"),
+            ),
+        ];
+        for (argv, plugin_msg, golden) in cases {
+            let argv: Vec<String> = argv.iter().map(|a| a.to_string()).collect();
+            let core_msg = match claplike::validate_with_env(&export, &argv, &[]) {
+                Err(claplike::ParseError::InvalidArgs(msg)) => msg,
+                other => panic!("expected invalid args for {argv:?}, got {other:?}"),
+            };
+            let cmd = "demo db export";
+            let core = capture(|out| print_invalid_args(out, &core_msg, &export, &[], cmd, &PLAIN));
+            let plugin =
+                capture(|out| print_invalid_args(out, &plugin_msg, &export, &[], cmd, &PLAIN));
+            assert_eq!(core.stderr(), plugin.stderr(), "{argv:?}");
+            assert_eq!(core.stderr(), golden, "{argv:?}");
+        }
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let mut export = command("db/export", "Export data");
+        export.args = vec![option("format", Some("FMT")), option("verbose", None)];
+        let strings = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        // `parsed` is what `typed` became after `@file`s and global args.
+        let render_typed =
+            |export: &schema::CommandSchema, typed: &[&str], parsed: &[&str], origins: &[usize]| {
+                let parsed = strings(parsed);
+                let err = claplike::parse_spanned(export, &parsed, &[], &[], false).unwrap_err();
+                render_parse_error(err, "demo db export", &strings(typed), &parsed, origins)
+            };
+        let render = |export: &schema::CommandSchema, argv: &[&str]| {
+            let origins: Vec<usize> = (0..argv.len()).collect();
+            render_typed(export, argv, argv, &origins)
+        };
+
+        assert_eq!(
+            render(&export, &["--verbose", "--formt", "json"]),
+            "unknown flag: --formt\n  demo db export --verbose --formt json\n  \
+             \x20                        ^^^^^^^"
+        );
+        assert_eq!(
+            render(&export, &["--verbose=yes"]),
+            "flag does not take a value: --verbose\n  demo db export --verbose=yes\n  \
+             \x20                       ^^^^"
+        );
+        // The carets point at what was typed: the `@file` a token came
+        // from, past global args the parser never saw.
+        assert_eq!(
+            render_typed(
+                &export,
+                &["--verbose", "@opts.txt"],
+                &["--verbose", "--formt", "json"],
+                &[0, 1, 1]
+            ),
+            "unknown flag: --formt\n  demo db export --verbose @opts.txt\n  \
+             \x20                        ^^^^^^^^^"
+        );
+        assert_eq!(
+            render_typed(
+                &export,
+                &["--color", "never", "--formt"],
+                &["--formt"],
+                &[2]
+            ),
+            "unknown flag: --formt\n  demo db export --color never --formt\n  \
+             \x20                            ^^^^^^^"
+        );
+        // Errors about no particular token keep just the message.
+        export.args[0].required = true;
+        assert_eq!(
+            render(&export, &[]),
+            "missing required argument: --format <FMT>"
+        );
+    }
+
+    #[test]
+    fn config_handoff_skips_values_argv_or_env_overrode() {
+        let mut export = command("export", "Export data");
+        let mut format = option("format", Some("FMT"));
+        format.env = Some("EXPORT_FORMAT".to_string());
+        export.args = vec![
+            format,
+            option("level", Some("LEVEL")),
+            option("out", Some("PATH")),
+            option("force", None),
+        ];
+        let pairs = |kv: &[(&str, &str)]| -> Vec<(String, String)> {
+            kv.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let config = pairs(&[
+            ("format", "csv"),
+            ("level", "debug"),
+            ("out", "a.txt"),
+            ("force", "yes"),
+            ("unknown", "x"),
+        ]);
+        let env = pairs(&[("EXPORT_FORMAT", "json")]);
+        let argv = vec!["--out".to_string(), "b.txt".to_string()];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_sources(&export, &argv, &env, &config).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(
+            config_handoff(&m, &config),
+            pairs(&[("WACLI_CONFIG_LEVEL", "debug"), ("WACLI_CONFIG_FORCE", "yes")])
+        );
+    }
+
+    #[test]
+    fn unknown_config_keys_fit_no_command() {
+        let schemas = fixture_schemas();
+        let config: Vec<(String, String)> = [("file", "a.sql"), ("fiel", "b.sql")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(unknown_config_keys(&schemas, &config), ["fiel"]);
+    }
+
+    #[test]
+    fn missing_required_groups_print_the_help() {
+        let mut export = command("export", "Export data");
+        for name in ["json", "yaml"] {
+            let mut flag = option(name, None);
+            flag.group = Some("format".to_string());
+            export.args.push(flag);
+        }
+        export.groups = vec![schema::ArgGroup {
+            name: "format".to_string(),
+            required: true,
+            multiple: false,
+        }];
+        let help = claplike::help(&export);
+        assert!(help.contains("(exactly one of: --json, --yaml)\n"), "{help}");
+
+        let msg = match claplike::validate_with_env(&export, &[], &[]) {
+            Err(claplike::ParseError::InvalidArgs(msg)) => msg,
+            other => panic!("expected invalid args, got {other:?}"),
+        };
+        let cap = capture(|out| print_invalid_args(out, &msg, &export, &[], "demo export", &PLAIN));
+        assert_eq!(
+            cap.stderr(),
+            format!(
+                "missing required argument: one of --json, --yaml\n\
+                 see 'demo export --help'\n\n{help}"
+            )
+        );
+    }
+
+    #[test]
+    fn app_display_name_drops_namespace() {
+        let mut meta = app("example:my-cli", "", "");
+        assert_eq!(app_display_name(&meta, "/bin/x"), "my-cli");
+        meta.name = String::new();
+        assert_eq!(app_display_name(&meta, "/bin/x.wasm"), "x.wasm");
+    }
+
+    #[test]
+    fn error_messages_golden() {
+        let cap = capture(|out| {
+            print_internal_error(out, "duplicate alias 'x'  \n");
+            print_unknown_command(out, "db nope");
+        });
+        assert_eq!(
+            cap.stderr(),
+            "Internal error: duplicate alias 'x'\nUnknown command: db nope\n"
+        );
+
+        let cap = capture(|out| {
+            let show_help = print_command_error(
+                out,
+                "greet",
+                types::CommandError::UnknownCommand("greet".to_string()),
+            );
+            assert!(!show_help);
+        });
+        assert_eq!(
+            cap.stderr(),
+            "Unknown command: greet\nRun with --help to see available commands.\n"
+        );
+    }
+
+    #[test]
+    fn command_error_does_not_double_trailing_newline() {
+        // Deliberate fix: messages that already end in `\n` used to be
+        // followed by a blank line before the hint.
+        let cap = capture(|out| {
+            let show_help =
+                print_command_error(out, "", types::CommandError::Failed("boom\n".to_string()));
+            assert!(show_help);
+        });
+        assert_eq!(
+            cap.stderr(),
+            "boom\nRun with --help to see available commands.\n"
+        );
+    }
+
+    #[test]
+    fn failed_errors_may_carry_an_exit_code() {
+        let err = types::CommandError::Failed("wacli-exit:3:usage: probe <WORD>".to_string());
+        assert_eq!(exit_code(&err), 3);
+        let cap = capture(|out| {
+            print_command_error(out, "probe", err);
+        });
+        assert_eq!(
+            cap.stderr(),
+            "usage: probe <WORD>\nRun with --help to see available commands.\n"
+        );
+
+        // A failure never exits with 0, which would skip the exit call.
+        for msg in ["wacli-exit:three:oops", "plain failure", "wacli-exit:0:oops"] {
+            let err = types::CommandError::Failed(msg.to_string());
+            assert_eq!(exit_code(&err), 1, "{msg}");
+        }
+        assert_eq!(
+            exit_code(&types::CommandError::InvalidArgs("wacli-exit:3:x".to_string())),
+            EXIT_USAGE
+        );
+    }
+
+    #[test]
+    fn unknown_commands_fall_back_to_a_composed_fallback_only() {
+        let schemas = fixture_schemas();
+        let mut app = app("demo", "1.2.3", "");
+        assert_eq!(fallback_command(&app, &schemas, "foo"), None);
+
+        app.fallback_command = Some("secret".to_string());
+        assert_eq!(fallback_command(&app, &schemas, "foo"), Some("secret"));
+        // Never its own fallback.
+        assert_eq!(fallback_command(&app, &schemas, "secret"), None);
+
+        app.fallback_command = Some("gone".to_string());
+        assert_eq!(fallback_command(&app, &schemas, "foo"), None);
+    }
+
+    #[test]
+    fn exit_codes_separate_usage_errors_from_failures() {
+        let code = |err| exit_code(&err);
+        assert_eq!(code(types::CommandError::InvalidArgs("bad".to_string())), 2);
+        assert_eq!(code(types::CommandError::UnknownCommand("nope".to_string())), 127);
+        assert_eq!(code(types::CommandError::Failed("boom".to_string())), 1);
+        assert_eq!(code(types::CommandError::Io("disk full".to_string())), 1);
+    }
+
+    #[test]
+    fn telemetry_event_carries_only_name_code_and_duration() {
+        assert_eq!(
+            telemetry_event_json("db/migrate", 2, 15),
+            r#"{"command":"db/migrate","exit_code":2,"duration_ms":15}"#
+        );
+        assert_eq!(
+            telemetry_event_json("a\"b\\c\n", 0, 0),
+            r#"{"command":"a\"b\\c\u000a","exit_code":0,"duration_ms":0}"#
+        );
+    }
+
+    #[test]
+    fn telemetry_opt_out_var_is_derived_from_app_name() {
+        let app_meta = app("example:my-cli", "0.1.0", "");
+        assert_eq!(
+            telemetry_opt_out_var(&app_meta, "ignored"),
+            "MY_CLI_NO_TELEMETRY"
+        );
+        assert_eq!(
+            telemetry_opt_out_var(&app("", "", ""), "/usr/bin/tool.wasm"),
+            "TOOL_WASM_NO_TELEMETRY"
+        );
+    }
+
+    #[test]
+    fn picker_needs_opt_in_and_terminals_unless_forced() {
+        let mut meta = app("example:my-cli", "", "");
+        let env = |v: &str| vec![("MY_CLI_INTERACTIVE".to_string(), v.to_string())];
+        assert!(!picker_enabled(&meta, "", &env("1"), || true));
+
+        meta.interactive_picker = true;
+        assert!(picker_enabled(&meta, "", &[], || true));
+        assert!(!picker_enabled(&meta, "", &[], || false));
+        assert!(picker_enabled(&meta, "", &env("1"), || false));
+        assert!(!picker_enabled(&meta, "", &env("0"), || true));
+        assert!(!picker_enabled(&meta, "", &env("yes"), || false));
+    }
+
+    #[test]
+    fn log_level_follows_flags_then_env() {
+        use host_log::Level;
+
+        let meta = app("example:my-cli", "", "");
+        let env = |v: &str| vec![("MY_CLI_LOG".to_string(), v.to_string())];
+        let level = |env: &[(String, String)], verbose, quiet| {
+            max_log_level(&meta, "", env, verbose, quiet)
+        };
+        assert_eq!(level(&[], false, false), Ok(Some(Level::Warn)));
+        assert_eq!(level(&env("Info"), false, false), Ok(Some(Level::Info)));
+        assert_eq!(level(&env("off"), false, false), Ok(None));
+        assert_eq!(level(&env("off"), true, false), Ok(Some(Level::Debug)));
+        assert_eq!(level(&env("trace"), false, true), Ok(Some(Level::Error)));
+        assert_eq!(
+            level(&env("loud"), false, false),
+            Err(
+                "ignoring MY_CLI_LOG=loud (expected trace, debug, info, warn, error or off)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn help_color_follows_env_then_terminal() {
+        let env = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        assert!(color_enabled(&[], || true));
+        assert!(!color_enabled(&[], || false));
+        assert!(!color_enabled(&env(&[("NO_COLOR", "1")]), || true));
+        assert!(color_enabled(&env(&[("NO_COLOR", "")]), || true));
+        assert!(color_enabled(&env(&[("CLICOLOR_FORCE", "1")]), || false));
+        assert!(!color_enabled(&env(&[("CLICOLOR_FORCE", "0")]), || false));
+        assert!(color_enabled(
+            &env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            || false
+        ));
+    }
+
+    #[test]
+    fn colored_help_keeps_the_plain_layout() {
+        let strip = |text: &str| {
+            let mut out = String::new();
+            let mut rest = text;
+            while let Some(at) = rest.find('\x1b') {
+                out.push_str(&rest[..at]);
+                rest = &rest[at + rest[at..].find('m').unwrap() + 1..];
+            }
+            out.push_str(rest);
+            out
+        };
+        let colored = claplike::HelpStyle::colored();
+        let plain = PLAIN;
+        let schemas = fixture_schemas();
+        let demo = app("demo", "1.2.3", "A demo app.");
+        let global =
+            |style| capture(|out| print_global_help(out, &demo, &schemas, &[db_group()], style));
+        let text = global(&colored).stdout();
+        assert!(text.contains("\x1b[1mAvailable commands:\x1b[0m\n"), "{text}");
+        assert!(text.contains("  \x1b[36mgreet (hi)\x1b[0m       Greet someone\n"), "{text}");
+        assert_eq!(strip(&text), global(&plain).stdout());
+
+        let group = |style| capture(|out| print_group_help(out, &db_group(), &schemas, style));
+        assert_eq!(strip(&group(&colored).stdout()), group(&plain).stdout());
+
+        let seed = find_command_schema(&schemas, "db/seed").unwrap();
+        let invalid = |style| {
+            let msg = "missing required argument: <FILE>";
+            capture(|out| print_invalid_args(out, msg, seed, &global_args(), "demo db seed", style))
+        };
+        let text = invalid(&colored).stderr();
+        assert!(text.contains("\x1b[1mGlobal options:\x1b[0m\n"), "{text}");
+        assert_eq!(strip(&text), invalid(&plain).stderr());
+    }
+
+    #[test]
+    fn unknown_member_is_flushed_before_group_help_and_exit() {
+        let cap = capture(|out| {
+            print_unknown_command(out, "db nope");
+            print_group_help(out, &db_group(), &fixture_schemas(), &PLAIN);
+            out.exit(EXIT_UNKNOWN_COMMAND);
+        });
+        assert!(matches!(
+            cap.events.as_slice(),
+            [
+                Event::Write(Stream::Stderr, err),
+                Event::Flush(Stream::Stderr),
+                Event::Write(Stream::Stdout, help),
+                Event::Flush(Stream::Stdout),
+                Event::Exit(EXIT_UNKNOWN_COMMAND),
+            ] if err == "Unknown command: db nope\n" && help.starts_with("db - Database tasks\n")
+        ));
+    }
+
+    fn subcommand(path: &[&str], summary: &str) -> schema::SubcommandSchema {
+        schema::SubcommandSchema {
+            path: path.iter().map(|p| p.to_string()).collect(),
+            summary: summary.to_string(),
+            usage: String::new(),
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden: false,
+            description: String::new(),
+            examples: Vec::new(),
+            args: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn command_tree_nests_flattened_subcommands() {
+        let mut remote = command("db/remote", "Manage remotes");
+        remote.subcommands = vec![
+            subcommand(&["add"], "Add a remote"),
+            subcommand(&["add", "mirror"], "Add a mirror"),
+            subcommand(&["list"], "List remotes"),
+        ];
+        let tree = command_tree(&remote, &[]);
+        assert_eq!(tree.schema.name, "db remote");
+        assert!(tree.schema.subcommands.is_empty());
+        let names: Vec<&str> = tree.subcommands.iter().map(|n| n.schema.name.as_str()).collect();
+        assert_eq!(names, ["add", "list"]);
+        assert_eq!(tree.subcommands[0].subcommands[0].schema.name, "mirror");
+
+        let argv = ["add".to_string(), "--help".to_string()];
+        match claplike::parse(&tree, &argv) {
+            Ok(claplike::ParseOutcome::Help(text)) => {
+                assert!(text.starts_with("db remote add - Add a remote\n"), "{text}");
+                assert!(text.contains("Commands:\n  mirror"), "{text}");
+            }
+            _ => panic!("expected subcommand help"),
+        }
+    }
 }
@@ -0,0 +1,305 @@
+//! Output writer used for everything `core` prints itself.
+//!
+//! All help, version and error output goes through [`Out`] so that it follows a
+//! single policy:
+//!
+//! - every `line()` / `block()` ends with exactly one `\n` (a missing trailing
+//!   newline is added, an existing one is not doubled)
+//! - output is buffered per stream; switching streams flushes the other one
+//!   first, so pending stdout always reaches the host before any stderr write
+//!   (and vice versa)
+//! - `exit()` flushes before handing control to the host, and dropping an `Out`
+//!   flushes whatever is still pending
+//!
+//! Call `flush()` before running a command so anything `core` printed lands
+//! ahead of the command's own output.
+
+use crate::bindings::wacli::cli::{host_io, host_process, types};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Where [`Out`] sends bytes. The host implementation is [`HostSink`]; tests
+/// capture the calls instead.
+pub(crate) trait Sink {
+    fn write(&mut self, stream: Stream, bytes: &[u8]);
+    fn flush(&mut self, stream: Stream);
+    fn exit(&mut self, code: types::ExitCode);
+}
+
+impl<T: Sink + ?Sized> Sink for &mut T {
+    fn write(&mut self, stream: Stream, bytes: &[u8]) {
+        (**self).write(stream, bytes);
+    }
+
+    fn flush(&mut self, stream: Stream) {
+        (**self).flush(stream);
+    }
+
+    fn exit(&mut self, code: types::ExitCode) {
+        (**self).exit(code);
+    }
+}
+
+/// Writes through the `host-io` / `host-process` imports.
+pub(crate) struct HostSink;
+
+impl Sink for HostSink {
+    fn write(&mut self, stream: Stream, bytes: &[u8]) {
+        match stream {
+            Stream::Stdout => host_io::stdout_write(bytes),
+            Stream::Stderr => host_io::stderr_write(bytes),
+        }
+    }
+
+    fn flush(&mut self, stream: Stream) {
+        match stream {
+            Stream::Stdout => host_io::stdout_flush(),
+            Stream::Stderr => host_io::stderr_flush(),
+        }
+    }
+
+    fn exit(&mut self, code: types::ExitCode) {
+        host_process::exit(code);
+    }
+}
+
+pub(crate) struct Out<S: Sink = HostSink> {
+    sink: S,
+    pending: Option<(Stream, String)>,
+}
+
+impl Out<HostSink> {
+    pub(crate) fn host() -> Self {
+        Self::new(HostSink)
+    }
+}
+
+impl<S: Sink> Out<S> {
+    pub(crate) fn new(sink: S) -> Self {
+        Self {
+            sink,
+            pending: None,
+        }
+    }
+
+    /// Write one line; trailing newlines in `text` are replaced by a single `\n`.
+    pub(crate) fn line(&mut self, stream: Stream, text: &str) {
+        let buf = self.buffer(stream);
+        buf.push_str(text.trim_end_matches(['\r', '\n']));
+        buf.push('\n');
+    }
+
+    /// Write pre-formatted multi-line text, adding a final `\n` if it is missing.
+    pub(crate) fn block(&mut self, stream: Stream, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let buf = self.buffer(stream);
+        buf.push_str(text);
+        if !text.ends_with('\n') {
+            buf.push('\n');
+        }
+    }
+
+    /// Write `text` without a newline and flush it, for a prompt answered on
+    /// the same line.
+    pub(crate) fn prompt(&mut self, stream: Stream, text: &str) {
+        self.buffer(stream).push_str(text);
+        self.flush();
+    }
+
+    /// Hand any pending output to the host and flush it.
+    pub(crate) fn flush(&mut self) {
+        if let Some((stream, buf)) = self.pending.take() {
+            self.sink.write(stream, buf.as_bytes());
+            self.sink.flush(stream);
+        }
+    }
+
+    /// Flush, then ask the host to exit with `code`.
+    pub(crate) fn exit(&mut self, code: types::ExitCode) {
+        self.flush();
+        self.sink.exit(code);
+    }
+
+    fn buffer(&mut self, stream: Stream) -> &mut String {
+        if self.pending.as_ref().is_some_and(|(s, _)| *s != stream) {
+            self.flush();
+        }
+        &mut self
+            .pending
+            .get_or_insert_with(|| (stream, String::new()))
+            .1
+    }
+}
+
+impl<S: Sink> Drop for Out<S> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::{Sink, Stream};
+    use crate::bindings::wacli::cli::types;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub(crate) enum Event {
+        Write(Stream, String),
+        Flush(Stream),
+        Exit(types::ExitCode),
+    }
+
+    /// Records every sink call for assertions.
+    #[derive(Default)]
+    pub(crate) struct Capture {
+        pub(crate) events: Vec<Event>,
+    }
+
+    impl Capture {
+        pub(crate) fn text(&self, stream: Stream) -> String {
+            self.events
+                .iter()
+                .filter_map(|e| match e {
+                    Event::Write(s, text) if *s == stream => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        pub(crate) fn stdout(&self) -> String {
+            self.text(Stream::Stdout)
+        }
+
+        pub(crate) fn stderr(&self) -> String {
+            self.text(Stream::Stderr)
+        }
+    }
+
+    impl Sink for Capture {
+        fn write(&mut self, stream: Stream, bytes: &[u8]) {
+            let text = String::from_utf8(bytes.to_vec()).expect("output is UTF-8");
+            self.events.push(Event::Write(stream, text));
+        }
+
+        fn flush(&mut self, stream: Stream) {
+            self.events.push(Event::Flush(stream));
+        }
+
+        fn exit(&mut self, code: types::ExitCode) {
+            self.events.push(Event::Exit(code));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{Capture, Event};
+    use super::{Out, Stream};
+
+    #[test]
+    fn line_ends_with_exactly_one_newline() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.line(Stream::Stdout, "a");
+            out.line(Stream::Stdout, "b\n");
+            out.line(Stream::Stdout, "c\r\n\n");
+            out.line(Stream::Stdout, "");
+        }
+        assert_eq!(cap.stdout(), "a\nb\nc\n\n");
+    }
+
+    #[test]
+    fn prompt_is_flushed_without_a_newline() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.line(Stream::Stderr, "Select:");
+            out.prompt(Stream::Stderr, "> ");
+        }
+        assert_eq!(
+            cap.events,
+            [
+                Event::Write(Stream::Stderr, "Select:\n> ".to_string()),
+                Event::Flush(Stream::Stderr),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_adds_missing_trailing_newline_only() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.block(Stream::Stdout, "one\ntwo");
+            out.block(Stream::Stdout, "three\n\n");
+            out.block(Stream::Stdout, "");
+        }
+        assert_eq!(cap.stdout(), "one\ntwo\nthree\n\n");
+    }
+
+    #[test]
+    fn buffers_until_flush_and_writes_once() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.line(Stream::Stdout, "a");
+            out.block(Stream::Stdout, "b\nc");
+            out.flush();
+            out.flush();
+        }
+        assert_eq!(
+            cap.events,
+            vec![
+                Event::Write(Stream::Stdout, "a\nb\nc\n".to_string()),
+                Event::Flush(Stream::Stdout),
+            ]
+        );
+    }
+
+    #[test]
+    fn switching_streams_flushes_pending_output_first() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.line(Stream::Stdout, "out");
+            out.line(Stream::Stderr, "err");
+            out.line(Stream::Stdout, "out again");
+        }
+        assert_eq!(
+            cap.events,
+            vec![
+                Event::Write(Stream::Stdout, "out\n".to_string()),
+                Event::Flush(Stream::Stdout),
+                Event::Write(Stream::Stderr, "err\n".to_string()),
+                Event::Flush(Stream::Stderr),
+                Event::Write(Stream::Stdout, "out again\n".to_string()),
+                Event::Flush(Stream::Stdout),
+            ]
+        );
+    }
+
+    #[test]
+    fn exit_flushes_before_exiting() {
+        let mut cap = Capture::default();
+        {
+            let mut out = Out::new(&mut cap);
+            out.line(Stream::Stderr, "boom");
+            out.exit(1);
+        }
+        assert_eq!(
+            cap.events,
+            vec![
+                Event::Write(Stream::Stderr, "boom\n".to_string()),
+                Event::Flush(Stream::Stderr),
+                Event::Exit(1),
+            ]
+        );
+    }
+}
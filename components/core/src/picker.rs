@@ -0,0 +1,333 @@
+//! Interactive command picker, shown instead of the global help when the app
+//! enables `interactive-picker` and runs with no arguments on a terminal.
+//!
+//! Everything here works on the schema list and a line reader, so the whole
+//! exchange can be driven from tests; `lib.rs` supplies `host-io` as the reader.
+
+use crate::bindings::wacli::cli::schema;
+use crate::out::{Out, Sink, Stream};
+use wacli_argparse::claplike::{self, ArgDefLike};
+
+pub(crate) const PROMPT: &str = "Command (number or name): ";
+
+/// A command the user can pick.
+pub(crate) struct Entry<'a> {
+    /// What the user types to run it (`db seed`).
+    pub(crate) name: String,
+    pub(crate) schema: &'a schema::CommandSchema,
+}
+
+/// A required argument to ask for after a command is picked.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Prompt {
+    /// Shown before the answer (`<FILE>`, `--out <PATH>`).
+    pub(crate) label: String,
+    /// Flag to put before the answer; `None` for positionals.
+    flag: Option<String>,
+}
+
+impl Prompt {
+    /// The argv tokens for `answer`.
+    pub(crate) fn tokens(&self, answer: String) -> Vec<String> {
+        match &self.flag {
+            Some(flag) => vec![flag.clone(), answer],
+            None => vec![answer],
+        }
+    }
+}
+
+/// Visible commands, sorted by name.
+pub(crate) fn entries(schemas: &[schema::CommandSchema]) -> Vec<Entry<'_>> {
+    let mut entries: Vec<Entry<'_>> = schemas
+        .iter()
+        .filter(|s| !s.hidden)
+        .map(|s| Entry {
+            name: s.name.replace('/', " "),
+            schema: s,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// The numbered list printed before [`PROMPT`].
+pub(crate) fn render(entries: &[Entry<'_>]) -> String {
+    let num_width = entries.len().to_string().len();
+    let name_width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    let mut out = String::from("Select a command:\n");
+    for (i, entry) in entries.iter().enumerate() {
+        let line = format!(
+            "  {:>num_width$}  {:<name_width$}  {}",
+            i + 1,
+            entry.name,
+            entry.schema.summary
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Resolve an answer to an index into `entries`: a list number, an exact
+/// name, or a prefix of exactly one name.
+pub(crate) fn select(entries: &[Entry<'_>], input: &str) -> Result<usize, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("no command selected".to_string());
+    }
+    if let Ok(n) = input.parse::<usize>() {
+        return match n.checked_sub(1).filter(|i| *i < entries.len()) {
+            Some(i) => Ok(i),
+            None => Err(format!("no command numbered {n}")),
+        };
+    }
+    if let Some(i) = entries.iter().position(|e| e.name == input) {
+        return Ok(i);
+    }
+    let matches: Vec<usize> = (0..entries.len())
+        .filter(|i| entries[*i].name.starts_with(input))
+        .collect();
+    match matches.as_slice() {
+        [i] => Ok(*i),
+        [] => Err(format!("no command matches '{input}'")),
+        _ => {
+            let names: Vec<&str> = matches.iter().map(|i| entries[*i].name.as_str()).collect();
+            Err(format!(
+                "'{input}' matches several commands: {}",
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// Required arguments of `schema` that nothing else would supply (no
+/// default, no value in `env`).
+pub(crate) fn required_prompts(
+    schema: &schema::CommandSchema,
+    env: &[(String, String)],
+) -> Vec<Prompt> {
+    schema
+        .args
+        .iter()
+        .filter(|a| a.required && a.takes_value && a.default_value.is_none())
+        .filter(|a| {
+            a.env
+                .as_deref()
+                .is_none_or(|var| !env.iter().any(|(k, v)| k == var && !v.is_empty()))
+        })
+        .map(|a| Prompt {
+            label: claplike::required_arg_display(a),
+            flag: (a.short().is_some() || a.long().is_some())
+                .then(|| claplike::arg_display_name(a)),
+        })
+        .collect()
+}
+
+/// Run the picker: list the commands, read a selection, ask for the picked
+/// command's required arguments, and return the argv to dispatch.
+///
+/// Returns `None` (after saying why on stderr) at end of input, on an invalid
+/// selection or an empty answer; the caller then falls back to the help.
+pub(crate) fn pick(
+    out: &mut Out<impl Sink>,
+    schemas: &[schema::CommandSchema],
+    env: &[(String, String)],
+    mut read_line: impl FnMut() -> Option<String>,
+) -> Option<Vec<String>> {
+    let entries = entries(schemas);
+    if entries.is_empty() {
+        return None;
+    }
+    out.block(Stream::Stderr, &render(&entries));
+
+    let mut ask = |out: &mut Out<_>, prompt: &str| {
+        out.prompt(Stream::Stderr, prompt);
+        let answer = read_line();
+        if answer.is_none() {
+            // Keep whatever follows off the prompt line.
+            out.line(Stream::Stderr, "");
+        }
+        answer
+    };
+
+    let answer = ask(out, PROMPT)?;
+    let entry = match select(&entries, &answer) {
+        Ok(i) => &entries[i],
+        Err(msg) => {
+            out.line(Stream::Stderr, &msg);
+            return None;
+        }
+    };
+
+    let mut argv: Vec<String> = entry.schema.name.split('/').map(str::to_string).collect();
+    for prompt in required_prompts(entry.schema, env) {
+        let answer = ask(out, &format!("{}: ", prompt.label))?;
+        if answer.trim().is_empty() {
+            out.line(Stream::Stderr, &format!("no value for {}", prompt.label));
+            return None;
+        }
+        argv.extend(prompt.tokens(answer));
+    }
+    Some(argv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::out::testing::Capture;
+
+    fn command(name: &str, summary: &str) -> schema::CommandSchema {
+        schema::CommandSchema {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            usage: String::new(),
+            aliases: Vec::new(),
+            hidden_aliases: Vec::new(),
+            version: String::new(),
+            hidden: false,
+            description: String::new(),
+            examples: Vec::new(),
+            args: Vec::new(),
+            allow_arg_files: false,
+            subcommands: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn arg(name: &str, long: Option<&str>, value_name: &str) -> schema::ArgSchema {
+        schema::ArgSchema {
+            name: name.to_string(),
+            short: None,
+            long: long.map(str::to_string),
+            help: String::new(),
+            required: true,
+            default_value: None,
+            env: None,
+            value_name: Some(value_name.to_string()),
+            takes_value: true,
+            multiple: false,
+            value_type: None,
+            possible_values: Vec::new(),
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            hidden: false,
+            complete_command: None,
+            group: None,
+            allow_hyphen_values: false,
+            countable: false,
+            var_arg: false,
+        }
+    }
+
+    fn schemas() -> Vec<schema::CommandSchema> {
+        let mut seed = command("db/seed", "Load seed data");
+        seed.args = vec![arg("file", None, "FILE")];
+        let mut secret = command("secret", "Hidden");
+        secret.hidden = true;
+        vec![
+            command("greet", "Greet someone"),
+            seed,
+            command("db/migrate", "Apply pending migrations"),
+            secret,
+            command("zzz", ""),
+        ]
+    }
+
+    fn run(answers: &[&str]) -> (Option<Vec<String>>, String) {
+        let schemas = schemas();
+        let mut answers = answers.iter().map(|a| a.to_string());
+        let mut cap = Capture::default();
+        let argv = {
+            let mut out = Out::new(&mut cap);
+            pick(&mut out, &schemas, &[], || answers.next())
+        };
+        (argv, cap.stderr())
+    }
+
+    #[test]
+    fn render_golden() {
+        let schemas = schemas();
+        assert_eq!(
+            render(&entries(&schemas)),
+            "Select a command:\n\
+             \x20 1  db migrate  Apply pending migrations\n\
+             \x20 2  db seed     Load seed data\n\
+             \x20 3  greet       Greet someone\n\
+             \x20 4  zzz\n"
+        );
+    }
+
+    #[test]
+    fn select_by_number_name_or_unique_prefix() {
+        let schemas = schemas();
+        let entries = entries(&schemas);
+        assert_eq!(select(&entries, "3"), Ok(2));
+        assert_eq!(select(&entries, " db seed\n"), Ok(1));
+        assert_eq!(select(&entries, "gr"), Ok(2));
+        assert_eq!(select(&entries, "db m"), Ok(0));
+
+        assert_eq!(select(&entries, "0"), Err("no command numbered 0".into()));
+        assert_eq!(select(&entries, "5"), Err("no command numbered 5".into()));
+        assert_eq!(
+            select(&entries, "db"),
+            Err("'db' matches several commands: db migrate, db seed".into())
+        );
+        assert_eq!(
+            select(&entries, "secret"),
+            Err("no command matches 'secret'".into())
+        );
+        assert_eq!(select(&entries, ""), Err("no command selected".into()));
+    }
+
+    #[test]
+    fn required_prompts_skip_values_supplied_elsewhere() {
+        let mut cmd = command("export", "");
+        let mut token = arg("token", Some("token"), "TOKEN");
+        token.env = Some("APP_TOKEN".to_string());
+        let mut format = arg("format", Some("format"), "FMT");
+        format.default_value = Some("json".to_string());
+        let mut optional = arg("limit", Some("limit"), "N");
+        optional.required = false;
+        cmd.args = vec![arg("file", None, "FILE"), arg("out", Some("out"), "PATH"), token, format, optional];
+
+        let labels = |env: &[(String, String)]| -> Vec<String> {
+            required_prompts(&cmd, env).into_iter().map(|p| p.label).collect()
+        };
+        assert_eq!(labels(&[]), ["<FILE>", "--out <PATH>", "--token <TOKEN>"]);
+        let env = [("APP_TOKEN".to_string(), "t".to_string())];
+        assert_eq!(labels(&env), ["<FILE>", "--out <PATH>"]);
+
+        let prompts = required_prompts(&cmd, &env);
+        assert_eq!(prompts[0].tokens("a.db".into()), ["a.db"]);
+        assert_eq!(prompts[1].tokens("o.json".into()), ["--out", "o.json"]);
+    }
+
+    #[test]
+    fn pick_returns_argv_with_prompted_args() {
+        let (argv, stderr) = run(&["db s", "fixtures.sql"]);
+        assert_eq!(argv.unwrap(), ["db", "seed", "fixtures.sql"]);
+        assert!(stderr.starts_with("Select a command:\n"), "{stderr}");
+        assert!(stderr.ends_with(&format!("{PROMPT}<FILE>: ")), "{stderr}");
+
+        let (argv, _) = run(&["3"]);
+        assert_eq!(argv.unwrap(), ["greet"]);
+    }
+
+    #[test]
+    fn pick_gives_up_on_eof_or_invalid_input() {
+        let (argv, stderr) = run(&[]);
+        assert!(argv.is_none());
+        assert!(stderr.ends_with(&format!("{PROMPT}\n")), "{stderr}");
+
+        let (argv, stderr) = run(&["nope"]);
+        assert!(argv.is_none());
+        assert!(stderr.ends_with("no command matches 'nope'\n"), "{stderr}");
+
+        let (argv, stderr) = run(&["db seed", " "]);
+        assert!(argv.is_none());
+        assert!(stderr.ends_with("no value for <FILE>\n"), "{stderr}");
+
+        let (argv, _) = run(&["db seed"]);
+        assert!(argv.is_none());
+    }
+}
@@ -46,6 +46,12 @@ pub mod wacli {
           f.debug_struct("CommandMeta").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).finish()
         }
       }
+      /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+      /// the same case count, so older plugins would stop composing). Until a
+      /// new major version adds an exit-code case, a `failed` message of the
+      /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+      /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+      /// `unknown-command` with 127 and `failed` / `io` with 1.
       #[derive(Clone)]
       pub enum CommandError {
         UnknownCommand(_rt::String),
@@ -124,6 +130,20 @@ pub mod wacli {
           f.debug_struct("PipeInfo").field("name", &self.name).field("summary", &self.summary).field("path", &self.path).finish()
         }
       }
+      /// A discovered pipe with the descriptive fields it embeds in its
+      /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+      #[derive(Clone)]
+      pub struct PipeDetails {
+        pub info: PipeInfo,
+        pub input_types: _rt::Vec::<_rt::String>,
+        pub output_type: _rt::String,
+        pub version: _rt::String,
+      }
+      impl ::core::fmt::Debug for PipeDetails {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("PipeDetails").field("info", &self.info).field("input-types", &self.input_types).field("output-type", &self.output_type).field("version", &self.version).finish()
+        }
+      }
 
     }
 
@@ -139,6 +159,39 @@ pub mod wacli {
       pub type PipeMeta = super::super::super::wacli::cli::types::PipeMeta;
       pub type PipeError = super::super::super::wacli::cli::types::PipeError;
       pub type PipeInfo = super::super::super::wacli::cli::types::PipeInfo;
+      pub type PipeDetails = super::super::super::wacli::cli::types::PipeDetails;
+      /// Why `open-pipe` failed.
+      #[derive(Clone)]
+      pub enum PipeLoadError {
+        /// No pipe by that name (a typo, or not installed).
+        NotFound(_rt::String),
+        /// The name itself is malformed.
+        InvalidName(_rt::String),
+        /// The pipe exists but could not be read or instantiated.
+        LoadFailed(_rt::String),
+      }
+      impl ::core::fmt::Debug for PipeLoadError {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          match self {
+            PipeLoadError::NotFound(e) => {
+              f.debug_tuple("PipeLoadError::NotFound").field(e).finish()
+            }
+            PipeLoadError::InvalidName(e) => {
+              f.debug_tuple("PipeLoadError::InvalidName").field(e).finish()
+            }
+            PipeLoadError::LoadFailed(e) => {
+              f.debug_tuple("PipeLoadError::LoadFailed").field(e).finish()
+            }
+          }
+        }
+      }
+      impl ::core::fmt::Display for PipeLoadError {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          write!(f, "{:?}", self)
+        }
+      }
+
+      impl ::core::error::Error for PipeLoadError {}
 
       #[derive(Debug)]
       #[repr(transparent)]
@@ -239,6 +292,96 @@ pub mod wacli {
         }
       }
       #[allow(unused_unsafe, clippy::all)]
+      /// Like `list-pipes`, with each pipe's types and version. Read from the
+      /// pipes' metadata sections without instantiating them.
+      #[allow(async_fn_in_trait)]
+      pub fn describe_pipes() -> _rt::Vec::<PipeDetails>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/pipe-runtime@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "describe-pipes"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = *ptr0.add(0).cast::<*mut u8>();
+          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base25 = l2;
+          let len25 = l3;
+          let mut result25 = _rt::Vec::with_capacity(len25);
+          for i in 0..len25 {
+            let base = base25.add(i * (12*::core::mem::size_of::<*const u8>()));
+            let e25 = {
+              let l4 = *base.add(0).cast::<*mut u8>();
+              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len6 = l5;
+              let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+              let l7 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l8 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len9 = l8;
+              let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+              let l10 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l11 = *base.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len12 = l11;
+              let bytes12 = _rt::Vec::from_raw_parts(l10.cast(), len12, len12);
+              let l13 = *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l14 = *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let base18 = l13;
+              let len18 = l14;
+              let mut result18 = _rt::Vec::with_capacity(len18);
+              for i in 0..len18 {
+                let base = base18.add(i * (2*::core::mem::size_of::<*const u8>()));
+                let e18 = {
+                  let l15 = *base.add(0).cast::<*mut u8>();
+                  let l16 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len17 = l16;
+                  let bytes17 = _rt::Vec::from_raw_parts(l15.cast(), len17, len17);
+
+                  _rt::string_lift(bytes17)
+                };
+                result18.push(e18);
+              }
+              _rt::cabi_dealloc(base18, len18 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+              let l19 = *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l20 = *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len21 = l20;
+              let bytes21 = _rt::Vec::from_raw_parts(l19.cast(), len21, len21);
+              let l22 = *base.add(10*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l23 = *base.add(11*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len24 = l23;
+              let bytes24 = _rt::Vec::from_raw_parts(l22.cast(), len24, len24);
+
+              super::super::super::wacli::cli::types::PipeDetails{
+                info: super::super::super::wacli::cli::types::PipeInfo{
+                  name: _rt::string_lift(bytes6),
+                  summary: _rt::string_lift(bytes9),
+                  path: _rt::string_lift(bytes12),
+                },
+                input_types: result18,
+                output_type: _rt::string_lift(bytes21),
+                version: _rt::string_lift(bytes24),
+              }
+            };
+            result25.push(e25);
+          }
+          _rt::cabi_dealloc(base25, len25 * (12*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result26 = result25;
+          result26
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Load a pipe by name. The error is the message of the `pipe-load-error`
+      /// that `open-pipe` returns; its type stays `string` so components built
+      /// against earlier `wacli:cli@2.0.0` releases still compose.
       #[allow(async_fn_in_trait)]
       pub fn load_pipe(name: &str,) -> Result<Pipe,_rt::String>{
         unsafe {
@@ -287,6 +430,91 @@ pub mod wacli {
           result8
         }
       }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Like `load-pipe`, saying why loading failed. Added within
+      /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+      /// import it.
+      #[allow(async_fn_in_trait)]
+      pub fn open_pipe(name: &str,) -> Result<Pipe,PipeLoadError>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+          let vec0 = name;
+          let ptr0 = vec0.as_ptr().cast::<u8>();
+          let len0 = vec0.len();
+          let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/pipe-runtime@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "open-pipe"]
+            fn wit_import2(_: *mut u8, _: usize, _: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+          wit_import2(ptr0.cast_mut(), len0, ptr1);
+          let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+          let result16 = match l3 {
+            0 => {
+              let e = {
+                let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<i32>();
+
+                Pipe::from_handle(l4 as u32)
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l5 = i32::from(*ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+                let v15 = match l5 {
+                  0 => {
+                    let e15 = {
+                      let l6 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l7 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len8 = l7;
+                      let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
+
+                      _rt::string_lift(bytes8)
+                    };
+                    PipeLoadError::NotFound(e15)
+                  }
+                  1 => {
+                    let e15 = {
+                      let l9 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l10 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len11 = l10;
+                      let bytes11 = _rt::Vec::from_raw_parts(l9.cast(), len11, len11);
+
+                      _rt::string_lift(bytes11)
+                    };
+                    PipeLoadError::InvalidName(e15)
+                  }
+                  n => {
+                    debug_assert_eq!(n, 2, "invalid enum discriminant");
+                    let e15 = {
+                      let l12 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l13 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len14 = l13;
+                      let bytes14 = _rt::Vec::from_raw_parts(l12.cast(), len14, len14);
+
+                      _rt::string_lift(bytes14)
+                    };
+                    PipeLoadError::LoadFailed(e15)
+                  }
+                };
+
+                v15
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result16
+        }
+      }
       impl Pipe {
         #[allow(unused_unsafe, clippy::all)]
         #[allow(async_fn_in_trait)]
@@ -453,108 +681,453 @@ pub mod wacli {
           }
         }
       }
+      impl Pipe {
+        #[allow(unused_unsafe, clippy::all)]
+        /// Start a chunked run (see `pipe-stream`): feed the input to
+        /// `process-chunk` and finish with `process-end`, collecting the output
+        /// each returns. The options are checked like those of `process`.
+        #[allow(async_fn_in_trait)]
+        pub fn process_start(&self,options: &[_rt::String],) -> Result<u32,PipeError>{
+          unsafe {
 
-    }
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+            let vec1 = options;
+            let len1 = vec1.len();
+            let layout1 = _rt::alloc::Layout::from_size_align(vec1.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
+            let (result1, _cleanup1) = wit_bindgen::rt::Cleanup::new(layout1);for (i, e) in vec1.into_iter().enumerate() {
+              let base = result1.add(i * (2*::core::mem::size_of::<*const u8>()));
+              {
+                let vec0 = e;
+                let ptr0 = vec0.as_ptr().cast::<u8>();
+                let len0 = vec0.len();
+                *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len0;
+                *base.add(0).cast::<*mut u8>() = ptr0.cast_mut();
+              }
+            }
+            let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wacli:cli/pipe-runtime@2.0.0")]
+            unsafe extern "C" {
+              #[link_name = "[method]pipe.process-start"]
+              fn wit_import3(_: i32, _: *mut u8, _: usize, _: *mut u8, );
+            }
 
-  }
-}
-#[allow(dead_code, clippy::all)]
-pub mod wasi {
-  pub mod cli {
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import3(_: i32, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+            wit_import3((self).handle() as i32, result1, len1, ptr2);
+            let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+            let result17 = match l4 {
+              0 => {
+                let e = {
+                  let l5 = *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<i32>();
 
-    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-    pub mod environment {
-      #[used]
-      #[doc(hidden)]
-      static __FORCE_SECTION_REF: fn() =
-      super::super::super::__link_custom_section_describing_imports;
-      
-      use super::super::super::_rt;
-      #[allow(unused_unsafe, clippy::all)]
-      /// Get the POSIX-style environment variables.
-      ///
-      /// Each environment variable is provided as a pair of string variable names
-      /// and string value.
-      ///
-      /// Morally, these are a value import, but until value imports are available
-      /// in the component model, this import function should return the same
-      /// values each time it is called.
-      #[allow(async_fn_in_trait)]
-      pub fn get_environment() -> _rt::Vec::<(_rt::String,_rt::String,)>{
-        unsafe {
+                  l5 as u32
+                };
+                Ok(e)
+              }
+              1 => {
+                let e = {
+                  let l6 = i32::from(*ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  use super::super::super::wacli::cli::types::PipeError as V16;
+                  let v16 = match l6 {
+                    0 => {
+                      let e16 = {
+                        let l7 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l8 = *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len9 = l8;
+                        let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
 
-          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
-          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
-          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
-          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
-          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-          #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wasi:cli/environment@0.2.9")]
-          unsafe extern "C" {
-            #[link_name = "get-environment"]
-            fn wit_import1(_: *mut u8, );
-          }
+                        _rt::string_lift(bytes9)
+                      };
+                      V16::ParseError(e16)
+                    }
+                    1 => {
+                      let e16 = {
+                        let l10 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l11 = *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len12 = l11;
+                        let bytes12 = _rt::Vec::from_raw_parts(l10.cast(), len12, len12);
 
-          #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
-          wit_import1(ptr0);
-          let l2 = *ptr0.add(0).cast::<*mut u8>();
-          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
-          let base10 = l2;
-          let len10 = l3;
-          let mut result10 = _rt::Vec::with_capacity(len10);
-          for i in 0..len10 {
-            let base = base10.add(i * (4*::core::mem::size_of::<*const u8>()));
-            let e10 = {
-              let l4 = *base.add(0).cast::<*mut u8>();
-              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
-              let len6 = l5;
-              let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
-              let l7 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
-              let l8 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
-              let len9 = l8;
-              let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                        _rt::string_lift(bytes12)
+                      };
+                      V16::TransformError(e16)
+                    }
+                    n => {
+                      debug_assert_eq!(n, 2, "invalid enum discriminant");
+                      let e16 = {
+                        let l13 = *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l14 = *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len15 = l14;
+                        let bytes15 = _rt::Vec::from_raw_parts(l13.cast(), len15, len15);
 
-              (_rt::string_lift(bytes6), _rt::string_lift(bytes9))
+                        _rt::string_lift(bytes15)
+                      };
+                      V16::InvalidOption(e16)
+                    }
+                  };
+
+                  v16
+                };
+                Err(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
             };
-            result10.push(e10);
+            result17
           }
-          _rt::cabi_dealloc(base10, len10 * (4*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
-          let result11 = result10;
-          result11
         }
       }
-      #[allow(unused_unsafe, clippy::all)]
-      /// Get the POSIX-style arguments to the program.
-      #[allow(async_fn_in_trait)]
-      pub fn get_arguments() -> _rt::Vec::<_rt::String>{
-        unsafe {
+      impl Pipe {
+        #[allow(unused_unsafe, clippy::all)]
+        #[allow(async_fn_in_trait)]
+        pub fn process_chunk(&self,session: u32,bytes: &[u8],) -> Result<_rt::Vec::<u8>,PipeError>{
+          unsafe {
 
-          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
-          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
-          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
-          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
-          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
-          #[cfg(target_arch = "wasm32")]
-          #[link(wasm_import_module = "wasi:cli/environment@0.2.9")]
-          unsafe extern "C" {
-            #[link_name = "get-arguments"]
-            fn wit_import1(_: *mut u8, );
-          }
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+            let vec0 = bytes;
+            let ptr0 = vec0.as_ptr().cast::<u8>();
+            let len0 = vec0.len();
+            let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wacli:cli/pipe-runtime@2.0.0")]
+            unsafe extern "C" {
+              #[link_name = "[method]pipe.process-chunk"]
+              fn wit_import2(_: i32, _: i32, _: *mut u8, _: usize, _: *mut u8, );
+            }
 
-          #[cfg(not(target_arch = "wasm32"))]
-          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
-          wit_import1(ptr0);
-          let l2 = *ptr0.add(0).cast::<*mut u8>();
-          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
-          let base7 = l2;
-          let len7 = l3;
-          let mut result7 = _rt::Vec::with_capacity(len7);
-          for i in 0..len7 {
-            let base = base7.add(i * (2*::core::mem::size_of::<*const u8>()));
-            let e7 = {
-              let l4 = *base.add(0).cast::<*mut u8>();
-              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import2(_: i32, _: i32, _: *mut u8, _: usize, _: *mut u8, ) { unreachable!() }
+            wit_import2((self).handle() as i32, _rt::as_i32(&session), ptr0.cast_mut(), len0, ptr1);
+            let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+            let result18 = match l3 {
+              0 => {
+                let e = {
+                  let l4 = *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l5 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len6 = l5;
+
+                  <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(l4.cast(), len6, len6))
+                };
+                Ok(e)
+              }
+              1 => {
+                let e = {
+                  let l7 = i32::from(*ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  use super::super::super::wacli::cli::types::PipeError as V17;
+                  let v17 = match l7 {
+                    0 => {
+                      let e17 = {
+                        let l8 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l9 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len10 = l9;
+                        let bytes10 = _rt::Vec::from_raw_parts(l8.cast(), len10, len10);
+
+                        _rt::string_lift(bytes10)
+                      };
+                      V17::ParseError(e17)
+                    }
+                    1 => {
+                      let e17 = {
+                        let l11 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l12 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len13 = l12;
+                        let bytes13 = _rt::Vec::from_raw_parts(l11.cast(), len13, len13);
+
+                        _rt::string_lift(bytes13)
+                      };
+                      V17::TransformError(e17)
+                    }
+                    n => {
+                      debug_assert_eq!(n, 2, "invalid enum discriminant");
+                      let e17 = {
+                        let l14 = *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l15 = *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len16 = l15;
+                        let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+
+                        _rt::string_lift(bytes16)
+                      };
+                      V17::InvalidOption(e17)
+                    }
+                  };
+
+                  v17
+                };
+                Err(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            };
+            result18
+          }
+        }
+      }
+      impl Pipe {
+        #[allow(unused_unsafe, clippy::all)]
+        #[allow(async_fn_in_trait)]
+        pub fn process_end(&self,session: u32,) -> Result<_rt::Vec::<u8>,PipeError>{
+          unsafe {
+
+            #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+            #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+            struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+            let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+            let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+            #[cfg(target_arch = "wasm32")]
+            #[link(wasm_import_module = "wacli:cli/pipe-runtime@2.0.0")]
+            unsafe extern "C" {
+              #[link_name = "[method]pipe.process-end"]
+              fn wit_import1(_: i32, _: i32, _: *mut u8, );
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8, ) { unreachable!() }
+            wit_import1((self).handle() as i32, _rt::as_i32(&session), ptr0);
+            let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+            let result17 = match l2 {
+              0 => {
+                let e = {
+                  let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                  let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                  let len5 = l4;
+
+                  <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
+                };
+                Ok(e)
+              }
+              1 => {
+                let e = {
+                  let l6 = i32::from(*ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+                  use super::super::super::wacli::cli::types::PipeError as V16;
+                  let v16 = match l6 {
+                    0 => {
+                      let e16 = {
+                        let l7 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l8 = *ptr0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len9 = l8;
+                        let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                        _rt::string_lift(bytes9)
+                      };
+                      V16::ParseError(e16)
+                    }
+                    1 => {
+                      let e16 = {
+                        let l10 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l11 = *ptr0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len12 = l11;
+                        let bytes12 = _rt::Vec::from_raw_parts(l10.cast(), len12, len12);
+
+                        _rt::string_lift(bytes12)
+                      };
+                      V16::TransformError(e16)
+                    }
+                    n => {
+                      debug_assert_eq!(n, 2, "invalid enum discriminant");
+                      let e16 = {
+                        let l13 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                        let l14 = *ptr0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                        let len15 = l14;
+                        let bytes15 = _rt::Vec::from_raw_parts(l13.cast(), len15, len15);
+
+                        _rt::string_lift(bytes15)
+                      };
+                      V16::InvalidOption(e16)
+                    }
+                  };
+
+                  v16
+                };
+                Err(e)
+              }
+              _ => _rt::invalid_enum_discriminant(),
+            };
+            result17
+          }
+        }
+      }
+
+    }
+
+    /// Terminal control the host component cannot do through WASI; provided by
+    /// `wacli run`, like `pipe-runtime`.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_runtime {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      #[allow(unused_unsafe, clippy::all)]
+      /// Read one line from stdin, which is a terminal, with echo turned off.
+      /// The terminal mode is restored before this returns, also when the read
+      /// is interrupted (Ctrl-C, an `err`). `none` at end of input (Ctrl-D).
+      #[allow(async_fn_in_trait)]
+      pub fn read_hidden_line() -> Result<Option<_rt::String>,_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wacli:cli/terminal-runtime@2.0.0")]
+          unsafe extern "C" {
+            #[link_name = "read-hidden-line"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result10 = match l2 {
+            0 => {
+              let e = {
+                let l3 = i32::from(*ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+
+                match l3 {
+                  0 => None,
+                  1 => {
+                    let e = {
+                      let l4 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                      let l5 = *ptr0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                      let len6 = l5;
+                      let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+
+                      _rt::string_lift(bytes6)
+                    };
+                    Some(e)
+                  }
+                  _ => _rt::invalid_enum_discriminant(),
+                }
+              };
+              Ok(e)
+            }
+            1 => {
+              let e = {
+                let l7 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+                let l8 = *ptr0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+                let len9 = l8;
+                let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+                _rt::string_lift(bytes9)
+              };
+              Err(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result10
+        }
+      }
+
+    }
+
+  }
+}
+#[allow(dead_code, clippy::all)]
+pub mod wasi {
+  pub mod cli {
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod environment {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      #[allow(unused_unsafe, clippy::all)]
+      /// Get the POSIX-style environment variables.
+      ///
+      /// Each environment variable is provided as a pair of string variable names
+      /// and string value.
+      ///
+      /// Morally, these are a value import, but until value imports are available
+      /// in the component model, this import function should return the same
+      /// values each time it is called.
+      #[allow(async_fn_in_trait)]
+      pub fn get_environment() -> _rt::Vec::<(_rt::String,_rt::String,)>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/environment@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-environment"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = *ptr0.add(0).cast::<*mut u8>();
+          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base10 = l2;
+          let len10 = l3;
+          let mut result10 = _rt::Vec::with_capacity(len10);
+          for i in 0..len10 {
+            let base = base10.add(i * (4*::core::mem::size_of::<*const u8>()));
+            let e10 = {
+              let l4 = *base.add(0).cast::<*mut u8>();
+              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len6 = l5;
+              let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+              let l7 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+              let l8 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+              let len9 = l8;
+              let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+
+              (_rt::string_lift(bytes6), _rt::string_lift(bytes9))
+            };
+            result10.push(e10);
+          }
+          _rt::cabi_dealloc(base10, len10 * (4*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+          let result11 = result10;
+          result11
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Get the POSIX-style arguments to the program.
+      #[allow(async_fn_in_trait)]
+      pub fn get_arguments() -> _rt::Vec::<_rt::String>{
+        unsafe {
+
+          #[cfg_attr(target_pointer_width="64", repr(align(8)))]
+          #[cfg_attr(target_pointer_width="32", repr(align(4)))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/environment@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-arguments"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = *ptr0.add(0).cast::<*mut u8>();
+          let l3 = *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          let base7 = l2;
+          let len7 = l3;
+          let mut result7 = _rt::Vec::with_capacity(len7);
+          for i in 0..len7 {
+            let base = base7.add(i * (2*::core::mem::size_of::<*const u8>()));
+            let e7 = {
+              let l4 = *base.add(0).cast::<*mut u8>();
+              let l5 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
               let len6 = l5;
               let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
 
@@ -619,6 +1192,7 @@ pub mod wasi {
       static __FORCE_SECTION_REF: fn() =
       super::super::super::__link_custom_section_describing_imports;
       
+      use super::super::super::_rt;
       #[allow(unused_unsafe, clippy::all)]
       /// Exit the current instance and any linked instances.
       #[allow(async_fn_in_trait)]
@@ -640,6 +1214,31 @@ pub mod wasi {
           wit_import1(result0);
         }
       }
+      #[allow(unused_unsafe, clippy::all)]
+      /// Exit the current instance and any linked instances, reporting the
+      /// specified status code to the host.
+      ///
+      /// The meaning of the code depends on the context, with 0 usually meaning
+      /// "success", and other values indicating various types of failure.
+      ///
+      /// This function does not return; the effect is analogous to a trap, but
+      /// without the connotation that something bad has happened.
+      #[allow(async_fn_in_trait)]
+      pub fn exit_with_code(status_code: u8,) -> (){
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/exit@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "exit-with-code"]
+            fn wit_import0(_: i32, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i32, ) { unreachable!() }
+          wit_import0(_rt::as_i32(&status_code));
+        }
+      }
 
     }
 
@@ -703,6 +1302,313 @@ pub mod wasi {
 
     }
 
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod stdin {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      pub type InputStream = super::super::super::wasi::io::streams::InputStream;
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn get_stdin() -> InputStream{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/stdin@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-stdin"]
+            fn wit_import0() -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i32 { unreachable!() }
+          let ret = wit_import0();
+          super::super::super::wasi::io::streams::InputStream::from_handle(ret as u32)
+        }
+      }
+
+    }
+
+    /// Terminal input.
+    ///
+    /// In the future, this may include functions for disabling echoing,
+    /// disabling input buffering so that keyboard events are sent through
+    /// immediately, querying supported features, and so on.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_input {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      /// The input side of a terminal.
+
+      #[derive(Debug)]
+      #[repr(transparent)]
+      pub struct TerminalInput{
+        handle: _rt::Resource<TerminalInput>,
+      }
+
+      impl TerminalInput{
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+          Self {
+            handle: unsafe { _rt::Resource::from_handle(handle) },
+          }
+        }
+
+        #[doc(hidden)]
+        pub fn take_handle(&self) -> u32 {
+          _rt::Resource::take_handle(&self.handle)
+        }
+
+        #[doc(hidden)]
+        pub fn handle(&self) -> u32 {
+          _rt::Resource::handle(&self.handle)
+        }
+      }
+      
+
+      unsafe impl _rt::WasmResource for TerminalInput{
+        #[inline]
+        unsafe fn drop(_handle: u32) {
+          
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/terminal-input@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "[resource-drop]terminal-input"]
+            fn drop(_: i32, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn drop(_: i32, ) { unreachable!() }
+          
+          unsafe { drop(_handle as i32); }
+        }
+      }
+      
+
+    }
+
+    /// An interface providing an optional `terminal-input` for stdin as a
+    /// link-time authority.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_stdin {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      pub type TerminalInput = super::super::super::wasi::cli::terminal_input::TerminalInput;
+      #[allow(unused_unsafe, clippy::all)]
+      /// If stdin is connected to a terminal, return a `terminal-input` handle
+      /// allowing further interaction with it.
+      #[allow(async_fn_in_trait)]
+      pub fn get_terminal_stdin() -> Option<TerminalInput>{
+        unsafe {
+
+          #[repr(align(4))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 8]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/terminal-stdin@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-terminal-stdin"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result4 = match l2 {
+            0 => None,
+            1 => {
+              let e = {
+                let l3 = *ptr0.add(4).cast::<i32>();
+
+                super::super::super::wasi::cli::terminal_input::TerminalInput::from_handle(l3 as u32)
+              };
+              Some(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result4
+        }
+      }
+
+    }
+
+    /// Terminal output.
+    ///
+    /// In the future, this may include functions for querying the terminal
+    /// size, being notified of terminal size changes, querying supported
+    /// features, and so on.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_output {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      /// The output side of a terminal.
+
+      #[derive(Debug)]
+      #[repr(transparent)]
+      pub struct TerminalOutput{
+        handle: _rt::Resource<TerminalOutput>,
+      }
+
+      impl TerminalOutput{
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+          Self {
+            handle: unsafe { _rt::Resource::from_handle(handle) },
+          }
+        }
+
+        #[doc(hidden)]
+        pub fn take_handle(&self) -> u32 {
+          _rt::Resource::take_handle(&self.handle)
+        }
+
+        #[doc(hidden)]
+        pub fn handle(&self) -> u32 {
+          _rt::Resource::handle(&self.handle)
+        }
+      }
+      
+
+      unsafe impl _rt::WasmResource for TerminalOutput{
+        #[inline]
+        unsafe fn drop(_handle: u32) {
+          
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/terminal-output@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "[resource-drop]terminal-output"]
+            fn drop(_: i32, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn drop(_: i32, ) { unreachable!() }
+          
+          unsafe { drop(_handle as i32); }
+        }
+      }
+      
+
+    }
+
+    /// An interface providing an optional `terminal-output` for stdout as a
+    /// link-time authority.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_stdout {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      pub type TerminalOutput = super::super::super::wasi::cli::terminal_output::TerminalOutput;
+      #[allow(unused_unsafe, clippy::all)]
+      /// If stdout is connected to a terminal, return a `terminal-output` handle
+      /// allowing further interaction with it.
+      #[allow(async_fn_in_trait)]
+      pub fn get_terminal_stdout() -> Option<TerminalOutput>{
+        unsafe {
+
+          #[repr(align(4))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 8]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/terminal-stdout@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-terminal-stdout"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result4 = match l2 {
+            0 => None,
+            1 => {
+              let e = {
+                let l3 = *ptr0.add(4).cast::<i32>();
+
+                super::super::super::wasi::cli::terminal_output::TerminalOutput::from_handle(l3 as u32)
+              };
+              Some(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result4
+        }
+      }
+
+    }
+
+    /// An interface providing an optional `terminal-output` for stderr as a
+    /// link-time authority.
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod terminal_stderr {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      pub type TerminalOutput = super::super::super::wasi::cli::terminal_output::TerminalOutput;
+      #[allow(unused_unsafe, clippy::all)]
+      /// If stderr is connected to a terminal, return a `terminal-output` handle
+      /// allowing further interaction with it.
+      #[allow(async_fn_in_trait)]
+      pub fn get_terminal_stderr() -> Option<TerminalOutput>{
+        unsafe {
+
+          #[repr(align(4))]
+          struct RetArea([::core::mem::MaybeUninit::<u8>; 8]);
+          let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+          let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:cli/terminal-stderr@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "get-terminal-stderr"]
+            fn wit_import1(_: *mut u8, );
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import1(_: *mut u8, ) { unreachable!() }
+          wit_import1(ptr0);
+          let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+          let result4 = match l2 {
+            0 => None,
+            1 => {
+              let e = {
+                let l3 = *ptr0.add(4).cast::<i32>();
+
+                super::super::super::wasi::cli::terminal_output::TerminalOutput::from_handle(l3 as u32)
+              };
+              Some(e)
+            }
+            _ => _rt::invalid_enum_discriminant(),
+          };
+          result4
+        }
+      }
+
+    }
+
   }
   pub mod clocks {
 
@@ -783,34 +1689,121 @@ pub mod wasi {
 
     }
 
-  }
-  pub mod filesystem {
 
     #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-    pub mod types {
+    pub mod monotonic_clock {
       #[used]
       #[doc(hidden)]
       static __FORCE_SECTION_REF: fn() =
       super::super::super::__link_custom_section_describing_imports;
       
       use super::super::super::_rt;
-      pub type InputStream = super::super::super::wasi::io::streams::InputStream;
-      pub type OutputStream = super::super::super::wasi::io::streams::OutputStream;
-      pub type Error = super::super::super::wasi::io::streams::Error;
-      pub type Datetime = super::super::super::wasi::clocks::wall_clock::Datetime;
-      pub type Filesize = u64;
-      #[repr(u8)]
-      #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
-      pub enum DescriptorType {
-        Unknown,
-        BlockDevice,
-        CharacterDevice,
-        Directory,
-        Fifo,
-        SymbolicLink,
-        RegularFile,
-        Socket,
-      }
+      pub type Pollable = super::super::super::wasi::io::poll::Pollable;
+      pub type Instant = u64;
+      pub type Duration = u64;
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn now() -> Instant{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "now"]
+            fn wit_import0() -> i64;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i64 { unreachable!() }
+          let ret = wit_import0();
+          ret as u64
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn resolution() -> Duration{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "resolution"]
+            fn wit_import0() -> i64;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0() -> i64 { unreachable!() }
+          let ret = wit_import0();
+          ret as u64
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn subscribe_instant(when: Instant,) -> Pollable{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "subscribe-instant"]
+            fn wit_import0(_: i64, ) -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i64, ) -> i32 { unreachable!() }
+          let ret = wit_import0(_rt::as_i64(when));
+          super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
+        }
+      }
+      #[allow(unused_unsafe, clippy::all)]
+      #[allow(async_fn_in_trait)]
+      pub fn subscribe_duration(when: Duration,) -> Pollable{
+        unsafe {
+
+          #[cfg(target_arch = "wasm32")]
+          #[link(wasm_import_module = "wasi:clocks/monotonic-clock@0.2.9")]
+          unsafe extern "C" {
+            #[link_name = "subscribe-duration"]
+            fn wit_import0(_: i64, ) -> i32;
+          }
+
+          #[cfg(not(target_arch = "wasm32"))]
+          unsafe extern "C" fn wit_import0(_: i64, ) -> i32 { unreachable!() }
+          let ret = wit_import0(_rt::as_i64(when));
+          super::super::super::wasi::io::poll::Pollable::from_handle(ret as u32)
+        }
+      }
+
+    }
+
+  }
+  pub mod filesystem {
+
+    #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+    pub mod types {
+      #[used]
+      #[doc(hidden)]
+      static __FORCE_SECTION_REF: fn() =
+      super::super::super::__link_custom_section_describing_imports;
+      
+      use super::super::super::_rt;
+      pub type InputStream = super::super::super::wasi::io::streams::InputStream;
+      pub type OutputStream = super::super::super::wasi::io::streams::OutputStream;
+      pub type Error = super::super::super::wasi::io::streams::Error;
+      pub type Datetime = super::super::super::wasi::clocks::wall_clock::Datetime;
+      pub type Filesize = u64;
+      #[repr(u8)]
+      #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+      pub enum DescriptorType {
+        Unknown,
+        BlockDevice,
+        CharacterDevice,
+        Directory,
+        Fifo,
+        SymbolicLink,
+        RegularFile,
+        Socket,
+      }
       impl ::core::fmt::Debug for DescriptorType {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
           match self {
@@ -3972,6 +4965,12 @@ pub mod exports {
             f.debug_struct("CommandMeta").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).finish()
           }
         }
+        /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+        /// the same case count, so older plugins would stop composing). Until a
+        /// new major version adds an exit-code case, a `failed` message of the
+        /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+        /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+        /// `unknown-command` with 127 and `failed` / `io` with 1.
         #[derive(Clone)]
         pub enum CommandError {
           UnknownCommand(_rt::String),
@@ -4050,6 +5049,20 @@ pub mod exports {
             f.debug_struct("PipeInfo").field("name", &self.name).field("summary", &self.summary).field("path", &self.path).finish()
           }
         }
+        /// A discovered pipe with the descriptive fields it embeds in its
+        /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+        #[derive(Clone)]
+        pub struct PipeDetails {
+          pub info: PipeInfo,
+          pub input_types: _rt::Vec::<_rt::String>,
+          pub output_type: _rt::String,
+          pub version: _rt::String,
+        }
+        impl ::core::fmt::Debug for PipeDetails {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("PipeDetails").field("info", &self.info).field("input-types", &self.input_types).field("output-type", &self.output_type).field("version", &self.version).finish()
+          }
+        }
         #[doc(hidden)]
 
         macro_rules! __export_wacli_cli_types_2_0_0_cabi{
@@ -4091,10 +5104,64 @@ pub mod exports {
           pub conflicts_with: _rt::Vec::<_rt::String>,
           pub requires: _rt::Vec::<_rt::String>,
           pub hidden: bool,
+          /// Registry command that prints completion candidates for this arg's
+          /// value, one per line; it receives the word being completed as its
+          /// only argument (`my-cli __complete`).
+          pub complete_command: Option<_rt::String>,
+          /// Name of the `arg-group` this arg belongs to.
+          pub group: Option<_rt::String>,
+          /// The value may start with `-`: the next token is always taken as the
+          /// value, even when it names a flag.
+          pub allow_hyphen_values: bool,
+          /// A boolean flag that counts its occurrences (`-vvv`); help shows it as
+          /// `-v...` and it may repeat even when not `multiple`.
+          pub countable: bool,
+          /// On the last positional: take every remaining positional token
+          /// (`[FILES]...`) instead of leaving extras to the plugin.
+          pub var_arg: bool,
         }
         impl ::core::fmt::Debug for ArgSchema {
           fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            f.debug_struct("ArgSchema").field("name", &self.name).field("short", &self.short).field("long", &self.long).field("help", &self.help).field("required", &self.required).field("default-value", &self.default_value).field("env", &self.env).field("value-name", &self.value_name).field("takes-value", &self.takes_value).field("multiple", &self.multiple).field("value-type", &self.value_type).field("possible-values", &self.possible_values).field("conflicts-with", &self.conflicts_with).field("requires", &self.requires).field("hidden", &self.hidden).finish()
+            f.debug_struct("ArgSchema").field("name", &self.name).field("short", &self.short).field("long", &self.long).field("help", &self.help).field("required", &self.required).field("default-value", &self.default_value).field("env", &self.env).field("value-name", &self.value_name).field("takes-value", &self.takes_value).field("multiple", &self.multiple).field("value-type", &self.value_type).field("possible-values", &self.possible_values).field("conflicts-with", &self.conflicts_with).field("requires", &self.requires).field("hidden", &self.hidden).field("complete-command", &self.complete_command).field("group", &self.group).field("allow-hyphen-values", &self.allow_hyphen_values).field("countable", &self.countable).field("var-arg", &self.var_arg).finish()
+          }
+        }
+        /// A named set of args validated together, e.g. exactly one of
+        /// `--json`/`--yaml`/`--toml`. A group args name without the command
+        /// declaring it is neither required nor multiple.
+        #[derive(Clone)]
+        pub struct ArgGroup {
+          pub name: _rt::String,
+          /// At least one member must be given in argv.
+          pub required: bool,
+          /// More than one member may be given.
+          pub multiple: bool,
+        }
+        impl ::core::fmt::Debug for ArgGroup {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("ArgGroup").field("name", &self.name).field("required", &self.required).field("multiple", &self.multiple).finish()
+          }
+        }
+        /// A subcommand of a `command-schema`, selected by the first positional
+        /// token in argv.
+        #[derive(Clone)]
+        pub struct SubcommandSchema {
+          /// Names from the first-level subcommand down, e.g. `["remote", "add"]`.
+          pub path: _rt::Vec::<_rt::String>,
+          pub summary: _rt::String,
+          pub usage: _rt::String,
+          pub aliases: _rt::Vec::<_rt::String>,
+          /// Empty to inherit the parent's version.
+          pub version: _rt::String,
+          pub hidden: bool,
+          pub description: _rt::String,
+          pub examples: _rt::Vec::<_rt::String>,
+          pub args: _rt::Vec::<ArgSchema>,
+          pub groups: _rt::Vec::<ArgGroup>,
+          pub hidden_aliases: _rt::Vec::<_rt::String>,
+        }
+        impl ::core::fmt::Debug for SubcommandSchema {
+          fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            f.debug_struct("SubcommandSchema").field("path", &self.path).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).field("groups", &self.groups).field("hidden-aliases", &self.hidden_aliases).finish()
           }
         }
         #[derive(Clone)]
@@ -4108,10 +5175,17 @@ pub mod exports {
           pub description: _rt::String,
           pub examples: _rt::Vec::<_rt::String>,
           pub args: _rt::Vec::<ArgSchema>,
+          /// Expand `@path` tokens in argv into the lines of `path` before parsing.
+          pub allow_arg_files: bool,
+          /// Nested commands, flattened depth-first (records cannot be recursive).
+          pub subcommands: _rt::Vec::<SubcommandSchema>,
+          pub groups: _rt::Vec::<ArgGroup>,
+          /// Aliases that dispatch like `aliases` but are not shown in help.
+          pub hidden_aliases: _rt::Vec::<_rt::String>,
         }
         impl ::core::fmt::Debug for CommandSchema {
           fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-            f.debug_struct("CommandSchema").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).finish()
+            f.debug_struct("CommandSchema").field("name", &self.name).field("summary", &self.summary).field("usage", &self.usage).field("aliases", &self.aliases).field("version", &self.version).field("hidden", &self.hidden).field("description", &self.description).field("examples", &self.examples).field("args", &self.args).field("allow-arg-files", &self.allow_arg_files).field("subcommands", &self.subcommands).field("groups", &self.groups).field("hidden-aliases", &self.hidden_aliases).finish()
           }
         }
         #[doc(hidden)]
@@ -4231,125 +5305,147 @@ pub mod exports {
       }
       _rt::cabi_dealloc(base6, len6 * (4*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
     } }
-    pub trait Guest {
-      #[allow(async_fn_in_trait)]
-      fn args() -> _rt::Vec::<_rt::String>;
-      #[allow(async_fn_in_trait)]
-      fn env() -> _rt::Vec::<(_rt::String,_rt::String,)>;
-    }
-    #[doc(hidden)]
-
-    macro_rules! __export_wacli_cli_host_env_2_0_0_cabi{
-      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
-
-        #[unsafe(export_name = "wacli:cli/host-env@2.0.0#args")]
-        unsafe extern "C" fn export_args() -> *mut u8 {
-          unsafe { $($path_to_types)*::_export_args_cabi::<$ty>() }
-        }
-        #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#args")]
-        unsafe extern "C" fn _post_return_args(arg0: *mut u8,) {
-          unsafe { $($path_to_types)*::__post_return_args::<$ty>(arg0) }
-        }
-        #[unsafe(export_name = "wacli:cli/host-env@2.0.0#env")]
-        unsafe extern "C" fn export_env() -> *mut u8 {
-          unsafe { $($path_to_types)*::_export_env_cabi::<$ty>() }
-        }
-        #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#env")]
-        unsafe extern "C" fn _post_return_env(arg0: *mut u8,) {
-          unsafe { $($path_to_types)*::__post_return_env::<$ty>(arg0) }
-        }
-      };);
-    }
-    #[doc(hidden)]
-    pub(crate) use __export_wacli_cli_host_env_2_0_0_cabi;
-
-    #[cfg_attr(target_pointer_width="64", repr(align(8)))]
-    #[cfg_attr(target_pointer_width="32", repr(align(4)))]
-    struct _RetArea([::core::mem::MaybeUninit::<u8>; 2*::core::mem::size_of::<*const u8>()]);
-    static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 2*::core::mem::size_of::<*const u8>()]);
-
-  }
-
-
-  #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-  pub mod host_io {
-    #[used]
-    #[doc(hidden)]
-    static __FORCE_SECTION_REF: fn() =
-    super::super::super::super::__link_custom_section_describing_imports;
-    
-    use super::super::super::super::_rt;
     #[doc(hidden)]
     #[allow(non_snake_case, unused_unsafe)]
-    pub unsafe fn _export_stdout_write_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) { unsafe {#[cfg(target_arch="wasm32")]
-    _rt::run_ctors_once();{
+    pub unsafe fn _export_get_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+    _rt::run_ctors_once();let result1 = {
       let len0 = arg1;
-      T::stdout_write(<_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)))
+      let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+      T::get(_rt::string_lift(bytes0))
     };
+    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+    match result1 {
+      Some(e) => {
+        *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+        let vec3 = (e.into_bytes()).into_boxed_slice();
+        let ptr3 = vec3.as_ptr().cast::<u8>();
+        let len3 = vec3.len();
+        ::core::mem::forget(vec3);
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+      },
+      None => {
+        {
+          *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+        }
+      },
+    };ptr2
+  } }
+  #[doc(hidden)]
+  #[allow(non_snake_case)]
+  pub unsafe fn __post_return_get<T: Guest>(arg0: *mut u8,) { unsafe {
+    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+    match l0 {
+      0 => (),
+      _ => {
+        let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+        let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+        _rt::cabi_dealloc(l1, l2, 1);
+      },
+    }
   } }
   #[doc(hidden)]
   #[allow(non_snake_case, unused_unsafe)]
-  pub unsafe fn _export_stderr_write_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) { unsafe {#[cfg(target_arch="wasm32")]
-  _rt::run_ctors_once();{
-    let len0 = arg1;
-    T::stderr_write(<_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)))
+  pub unsafe fn _export_cwd_cabi<T: Guest>() -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();let result0 = {
+    T::cwd()
   };
+  let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+  let vec2 = (result0.into_bytes()).into_boxed_slice();
+  let ptr2 = vec2.as_ptr().cast::<u8>();
+  let len2 = vec2.len();
+  ::core::mem::forget(vec2);
+  *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+  *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+  ptr1
 } }
 #[doc(hidden)]
-#[allow(non_snake_case, unused_unsafe)]
-pub unsafe fn _export_stdout_flush_cabi<T: Guest>() { unsafe {#[cfg(target_arch="wasm32")]
-_rt::run_ctors_once();{
-  T::stdout_flush()
-};
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_cwd<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = *arg0.add(0).cast::<*mut u8>();
+  let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+  _rt::cabi_dealloc(l0, l1, 1);
 } }
 #[doc(hidden)]
 #[allow(non_snake_case, unused_unsafe)]
-pub unsafe fn _export_stderr_flush_cabi<T: Guest>() { unsafe {#[cfg(target_arch="wasm32")]
-_rt::run_ctors_once();{
-  T::stderr_flush()
+pub unsafe fn _export_monotonic_now_cabi<T: Guest>() -> i64 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::monotonic_now()
 };
+_rt::as_i64(result0)
 } }
 pub trait Guest {
   #[allow(async_fn_in_trait)]
-  fn stdout_write(bytes: _rt::Vec::<u8>,) -> ();
+  fn args() -> _rt::Vec::<_rt::String>;
   #[allow(async_fn_in_trait)]
-  fn stderr_write(bytes: _rt::Vec::<u8>,) -> ();
+  fn env() -> _rt::Vec::<(_rt::String,_rt::String,)>;
+  /// The value of the environment variable `key` (case-sensitive).
   #[allow(async_fn_in_trait)]
-  fn stdout_flush() -> ();
+  fn get(key: _rt::String,) -> Option<_rt::String>;
+  /// The working directory relative paths resolve against (`.` when the
+  /// runtime reports none).
   #[allow(async_fn_in_trait)]
-  fn stderr_flush() -> ();
+  fn cwd() -> _rt::String;
+  /// Monotonic clock reading in nanoseconds, for measuring durations only.
+  #[allow(async_fn_in_trait)]
+  fn monotonic_now() -> u64;
 }
 #[doc(hidden)]
 
-macro_rules! __export_wacli_cli_host_io_2_0_0_cabi{
+macro_rules! __export_wacli_cli_host_env_2_0_0_cabi{
   ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
 
-    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdout-write")]
-    unsafe extern "C" fn export_stdout_write(arg0: *mut u8,arg1: usize,) {
-      unsafe { $($path_to_types)*::_export_stdout_write_cabi::<$ty>(arg0, arg1) }
+    #[unsafe(export_name = "wacli:cli/host-env@2.0.0#args")]
+    unsafe extern "C" fn export_args() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_args_cabi::<$ty>() }
     }
-    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stderr-write")]
-    unsafe extern "C" fn export_stderr_write(arg0: *mut u8,arg1: usize,) {
-      unsafe { $($path_to_types)*::_export_stderr_write_cabi::<$ty>(arg0, arg1) }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#args")]
+    unsafe extern "C" fn _post_return_args(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_args::<$ty>(arg0) }
     }
-    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdout-flush")]
-    unsafe extern "C" fn export_stdout_flush() {
-      unsafe { $($path_to_types)*::_export_stdout_flush_cabi::<$ty>() }
+    #[unsafe(export_name = "wacli:cli/host-env@2.0.0#env")]
+    unsafe extern "C" fn export_env() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_env_cabi::<$ty>() }
     }
-    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stderr-flush")]
-    unsafe extern "C" fn export_stderr_flush() {
-      unsafe { $($path_to_types)*::_export_stderr_flush_cabi::<$ty>() }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#env")]
+    unsafe extern "C" fn _post_return_env(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_env::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-env@2.0.0#get")]
+    unsafe extern "C" fn export_get(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_get_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#get")]
+    unsafe extern "C" fn _post_return_get(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_get::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-env@2.0.0#cwd")]
+    unsafe extern "C" fn export_cwd() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_cwd_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-env@2.0.0#cwd")]
+    unsafe extern "C" fn _post_return_cwd(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_cwd::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-env@2.0.0#monotonic-now")]
+    unsafe extern "C" fn export_monotonic_now() -> i64 {
+      unsafe { $($path_to_types)*::_export_monotonic_now_cabi::<$ty>() }
     }
   };);
 }
 #[doc(hidden)]
-pub(crate) use __export_wacli_cli_host_io_2_0_0_cabi;
+pub(crate) use __export_wacli_cli_host_env_2_0_0_cabi;
+
+#[cfg_attr(target_pointer_width="64", repr(align(8)))]
+#[cfg_attr(target_pointer_width="32", repr(align(4)))]
+struct _RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
+static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
 
 }
 
 
 #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-pub mod host_fs {
+pub mod host_io {
   #[used]
   #[doc(hidden)]
   static __FORCE_SECTION_REF: fn() =
@@ -4358,37 +5454,243 @@ pub mod host_fs {
   use super::super::super::super::_rt;
   #[doc(hidden)]
   #[allow(non_snake_case, unused_unsafe)]
-  pub unsafe fn _export_read_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
-  _rt::run_ctors_once();let result1 = {
+  pub unsafe fn _export_stdout_write_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) { unsafe {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();{
     let len0 = arg1;
-    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-    T::read_file(_rt::string_lift(bytes0))
+    T::stdout_write(<_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)))
   };
-  let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
-  match result1 {
-    Ok(e) => { {
-      *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-      let vec3 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stderr_write_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();{
+  let len0 = arg1;
+  T::stderr_write(<_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)))
+};
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdout_flush_cabi<T: Guest>() { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();{
+  T::stdout_flush()
+};
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stderr_flush_cabi<T: Guest>() { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();{
+  T::stderr_flush()
+};
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdout_is_terminal_cabi<T: Guest>() -> i32 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stdout_is_terminal()
+};
+match result0 { true => 1, false => 0 }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stderr_is_terminal_cabi<T: Guest>() -> i32 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stderr_is_terminal()
+};
+match result0 { true => 1, false => 0 }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdin_is_terminal_cabi<T: Guest>() -> i32 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stdin_is_terminal()
+};
+match result0 { true => 1, false => 0 }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdin_read_line_cabi<T: Guest>() -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stdin_read_line()
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result0 {
+  Ok(e) => { {
+    *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+    match e {
+      Some(e) => {
+        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+        let vec2 = (e.into_bytes()).into_boxed_slice();
+        let ptr2 = vec2.as_ptr().cast::<u8>();
+        let len2 = vec2.len();
+        ::core::mem::forget(vec2);
+        *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+        *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr2.cast_mut();
+      },
+      None => {
+        {
+          *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+        }
+      },
+    };} },
+    Err(e) => { {
+      *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+      let vec3 = (e.into_bytes()).into_boxed_slice();
       let ptr3 = vec3.as_ptr().cast::<u8>();
       let len3 = vec3.len();
       ::core::mem::forget(vec3);
-      *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
-      *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+      *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+      *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
     } },
-    Err(e) => { {
-      *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-      let vec4 = (e.into_bytes()).into_boxed_slice();
-      let ptr4 = vec4.as_ptr().cast::<u8>();
-      let len4 = vec4.len();
-      ::core::mem::forget(vec4);
-      *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
-      *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
-    } },
-  };ptr2
+  };ptr1
 } }
 #[doc(hidden)]
 #[allow(non_snake_case)]
-pub unsafe fn __post_return_read_file<T: Guest>(arg0: *mut u8,) { unsafe {
+pub unsafe fn __post_return_stdin_read_line<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = i32::from(*arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+      match l1 {
+        0 => (),
+        _ => {
+          let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l3 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l2, l3, 1);
+        },
+      }
+    },
+    _ => {
+      let l4 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l5 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l4, l5, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_read_secret_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::read_secret(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec4 = (e.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_read_secret<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+    _ => {
+      let l3 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l4 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l3, l4, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdin_read_cabi<T: Guest>(arg0: i64,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stdin_read(arg0 as u64)
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result0 {
+  Ok(e) => { {
+    *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+    let vec2 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+    let ptr2 = vec2.as_ptr().cast::<u8>();
+    let len2 = vec2.len();
+    ::core::mem::forget(vec2);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr2.cast_mut();
+  } },
+  Err(e) => { {
+    *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr1
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_stdin_read<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base3 = l1;
+      let len3 = l2;
+      _rt::cabi_dealloc(base3, len3 * 1, 1);
+    },
+    _ => {
+      let l4 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l5 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l4, l5, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stdin_read_all_cabi<T: Guest>() -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::stdin_read_all()
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result0 {
+  Ok(e) => { {
+    *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+    let vec2 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+    let ptr2 = vec2.as_ptr().cast::<u8>();
+    let len2 = vec2.len();
+    ::core::mem::forget(vec2);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr2.cast_mut();
+  } },
+  Err(e) => { {
+    *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr1
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_stdin_read_all<T: Guest>(arg0: *mut u8,) { unsafe {
   let l0 = i32::from(*arg0.add(0).cast::<u8>());
   match l0 {
     0 => {
@@ -4405,14 +5707,1046 @@ pub unsafe fn __post_return_read_file<T: Guest>(arg0: *mut u8,) { unsafe {
     },
   }
 } }
+pub trait Guest {
+  #[allow(async_fn_in_trait)]
+  fn stdout_write(bytes: _rt::Vec::<u8>,) -> ();
+  #[allow(async_fn_in_trait)]
+  fn stderr_write(bytes: _rt::Vec::<u8>,) -> ();
+  #[allow(async_fn_in_trait)]
+  fn stdout_flush() -> ();
+  #[allow(async_fn_in_trait)]
+  fn stderr_flush() -> ();
+  /// Whether stdout is attached to a terminal (false when piped or captured).
+  #[allow(async_fn_in_trait)]
+  fn stdout_is_terminal() -> bool;
+  /// Whether stderr is attached to a terminal (false when piped or captured).
+  #[allow(async_fn_in_trait)]
+  fn stderr_is_terminal() -> bool;
+  /// Whether stdin is attached to a terminal (false when piped or empty).
+  #[allow(async_fn_in_trait)]
+  fn stdin_is_terminal() -> bool;
+  /// Read one line from stdin, without its line ending; `none` at end of
+  /// input, `err` when the read fails.
+  #[allow(async_fn_in_trait)]
+  fn stdin_read_line() -> Result<Option<_rt::String>,_rt::String>;
+  /// Write `prompt` to stderr and read one line from stdin like
+  /// `stdin-read-line`, without echoing it when stdin is a terminal (a plain
+  /// read otherwise). End of input is an `err`.
+  #[allow(async_fn_in_trait)]
+  fn read_secret(prompt: _rt::String,) -> Result<_rt::String,_rt::String>;
+  /// Read up to `len` bytes from stdin; empty at end of input, `err` when
+  /// the read fails.
+  #[allow(async_fn_in_trait)]
+  fn stdin_read(len: u64,) -> Result<_rt::Vec::<u8>,_rt::String>;
+  /// Read the rest of stdin; empty at end of input, `err` when the read
+  /// fails.
+  #[allow(async_fn_in_trait)]
+  fn stdin_read_all() -> Result<_rt::Vec::<u8>,_rt::String>;
+}
+#[doc(hidden)]
+
+macro_rules! __export_wacli_cli_host_io_2_0_0_cabi{
+  ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdout-write")]
+    unsafe extern "C" fn export_stdout_write(arg0: *mut u8,arg1: usize,) {
+      unsafe { $($path_to_types)*::_export_stdout_write_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stderr-write")]
+    unsafe extern "C" fn export_stderr_write(arg0: *mut u8,arg1: usize,) {
+      unsafe { $($path_to_types)*::_export_stderr_write_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdout-flush")]
+    unsafe extern "C" fn export_stdout_flush() {
+      unsafe { $($path_to_types)*::_export_stdout_flush_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stderr-flush")]
+    unsafe extern "C" fn export_stderr_flush() {
+      unsafe { $($path_to_types)*::_export_stderr_flush_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdout-is-terminal")]
+    unsafe extern "C" fn export_stdout_is_terminal() -> i32 {
+      unsafe { $($path_to_types)*::_export_stdout_is_terminal_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stderr-is-terminal")]
+    unsafe extern "C" fn export_stderr_is_terminal() -> i32 {
+      unsafe { $($path_to_types)*::_export_stderr_is_terminal_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdin-is-terminal")]
+    unsafe extern "C" fn export_stdin_is_terminal() -> i32 {
+      unsafe { $($path_to_types)*::_export_stdin_is_terminal_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdin-read-line")]
+    unsafe extern "C" fn export_stdin_read_line() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_stdin_read_line_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-io@2.0.0#stdin-read-line")]
+    unsafe extern "C" fn _post_return_stdin_read_line(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_stdin_read_line::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#read-secret")]
+    unsafe extern "C" fn export_read_secret(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_read_secret_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-io@2.0.0#read-secret")]
+    unsafe extern "C" fn _post_return_read_secret(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_read_secret::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdin-read")]
+    unsafe extern "C" fn export_stdin_read(arg0: i64,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_stdin_read_cabi::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-io@2.0.0#stdin-read")]
+    unsafe extern "C" fn _post_return_stdin_read(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_stdin_read::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-io@2.0.0#stdin-read-all")]
+    unsafe extern "C" fn export_stdin_read_all() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_stdin_read_all_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-io@2.0.0#stdin-read-all")]
+    unsafe extern "C" fn _post_return_stdin_read_all(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_stdin_read_all::<$ty>(arg0) }
+    }
+  };);
+}
+#[doc(hidden)]
+pub(crate) use __export_wacli_cli_host_io_2_0_0_cabi;
+
+#[cfg_attr(target_pointer_width="64", repr(align(8)))]
+#[cfg_attr(target_pointer_width="32", repr(align(4)))]
+struct _RetArea([::core::mem::MaybeUninit::<u8>; 4*::core::mem::size_of::<*const u8>()]);
+static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 4*::core::mem::size_of::<*const u8>()]);
+
+}
+
+
+#[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+pub mod host_log {
+  #[used]
+  #[doc(hidden)]
+  static __FORCE_SECTION_REF: fn() =
+  super::super::super::super::__link_custom_section_describing_imports;
+  
+  use super::super::super::super::_rt;
+  /// Message severity, least severe first.
+  #[repr(u8)]
+  #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+  pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+  }
+  impl ::core::fmt::Debug for Level {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      match self {
+        Level::Trace => {
+          f.debug_tuple("Level::Trace").finish()
+        }
+        Level::Debug => {
+          f.debug_tuple("Level::Debug").finish()
+        }
+        Level::Info => {
+          f.debug_tuple("Level::Info").finish()
+        }
+        Level::Warn => {
+          f.debug_tuple("Level::Warn").finish()
+        }
+        Level::Error => {
+          f.debug_tuple("Level::Error").finish()
+        }
+      }
+    }
+  }
+
+  impl Level{
+    #[doc(hidden)]
+    pub unsafe fn _lift(val: u8) -> Level{
+      if !cfg!(debug_assertions) {
+        return unsafe { ::core::mem::transmute(val) };
+      }
+
+      match val {
+        0 => Level::Trace,
+        1 => Level::Debug,
+        2 => Level::Info,
+        3 => Level::Warn,
+        4 => Level::Error,
+
+        _ => panic!("invalid enum discriminant"),
+      }
+    }
+  }
+
+  #[doc(hidden)]
+  #[allow(non_snake_case, unused_unsafe)]
+  pub unsafe fn _export_log_cabi<T: Guest>(arg0: i32,arg1: *mut u8,arg2: usize,arg3: *mut u8,arg4: usize,) { unsafe {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();{
+    let len0 = arg2;
+    let bytes0 = _rt::Vec::from_raw_parts(arg1.cast(), len0, len0);
+    let len1 = arg4;
+    let bytes1 = _rt::Vec::from_raw_parts(arg3.cast(), len1, len1);
+    T::log(Level::_lift(arg0 as u8), _rt::string_lift(bytes0), _rt::string_lift(bytes1))
+  };
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_enabled_cabi<T: Guest>(arg0: i32,) -> i32 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::enabled(Level::_lift(arg0 as u8))
+};
+match result0 { true => 1, false => 0 }
+} }
+pub trait Guest {
+  /// Write `message` to stderr as `[LEVEL target] message`, unless `level`
+  /// is below the max level (then nothing is formatted or written).
+  #[allow(async_fn_in_trait)]
+  fn log(level: Level,target: _rt::String,message: _rt::String,) -> ();
+  /// Whether a message at `level` would be written; check it before
+  /// building an expensive message. The max level is `warn` unless core
+  /// changes it (see `host-control`).
+  #[allow(async_fn_in_trait)]
+  fn enabled(level: Level,) -> bool;
+}
+#[doc(hidden)]
+
+macro_rules! __export_wacli_cli_host_log_2_0_0_cabi{
+  ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+    #[unsafe(export_name = "wacli:cli/host-log@2.0.0#log")]
+    unsafe extern "C" fn export_log(arg0: i32,arg1: *mut u8,arg2: usize,arg3: *mut u8,arg4: usize,) {
+      unsafe { $($path_to_types)*::_export_log_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-log@2.0.0#enabled")]
+    unsafe extern "C" fn export_enabled(arg0: i32,) -> i32 {
+      unsafe { $($path_to_types)*::_export_enabled_cabi::<$ty>(arg0) }
+    }
+  };);
+}
+#[doc(hidden)]
+pub(crate) use __export_wacli_cli_host_log_2_0_0_cabi;
+
+}
+
+/// Run settings only core changes. The host exports this interface, but the
+/// composition wires it into core alone, so commands cannot change them.
+#[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+pub mod host_control {
+  #[used]
+  #[doc(hidden)]
+  static __FORCE_SECTION_REF: fn() =
+  super::super::super::super::__link_custom_section_describing_imports;
+  
+  use super::super::super::super::_rt;
+  pub type Level = super::super::super::super::exports::wacli::cli::host_log::Level;
+  #[doc(hidden)]
+  #[allow(non_snake_case, unused_unsafe)]
+  pub unsafe fn _export_set_env_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: i32,arg3: *mut u8,arg4: usize,) { unsafe {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();{
+    let len0 = arg1;
+    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+    T::set_env(_rt::string_lift(bytes0), match arg2 {
+      0 => None,
+      1 => {
+        let e = {
+          let len1 = arg4;
+          let bytes1 = _rt::Vec::from_raw_parts(arg3.cast(), len1, len1);
+
+          _rt::string_lift(bytes1)
+        };
+        Some(e)
+      }
+      _ => _rt::invalid_enum_discriminant(),
+    })
+  };
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_set_max_log_level_cabi<T: Guest>(arg0: i32,arg1: i32,) { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();{
+  T::set_max_log_level(match arg0 {
+    0 => None,
+    1 => {
+      let e = super::super::super::super::exports::wacli::cli::host_log::Level::_lift(arg1 as u8);
+      Some(e)
+    }
+    _ => _rt::invalid_enum_discriminant(),
+  })
+};
+} }
+pub trait Guest {
+  /// Set (`some`) or remove (`none`) the variable `key`, as seen by
+  /// `host-env.env` and `host-env.get` for the rest of the run; the process
+  /// environment itself is unchanged. Core hands global args to commands
+  /// this way.
+  #[allow(async_fn_in_trait)]
+  fn set_env(key: _rt::String,value: Option<_rt::String>,) -> ();
+  /// Drop `host-log` messages below `level` (`none`: drop every message).
+  /// Defaults to `warn`; core sets it from `<APP>_LOG` and the
+  /// `verbose`/`quiet` global args before dispatching a command.
+  #[allow(async_fn_in_trait)]
+  fn set_max_log_level(level: Option<Level>,) -> ();
+}
+#[doc(hidden)]
+
+macro_rules! __export_wacli_cli_host_control_2_0_0_cabi{
+  ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+    #[unsafe(export_name = "wacli:cli/host-control@2.0.0#set-env")]
+    unsafe extern "C" fn export_set_env(arg0: *mut u8,arg1: usize,arg2: i32,arg3: *mut u8,arg4: usize,) {
+      unsafe { $($path_to_types)*::_export_set_env_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-control@2.0.0#set-max-log-level")]
+    unsafe extern "C" fn export_set_max_log_level(arg0: i32,arg1: i32,) {
+      unsafe { $($path_to_types)*::_export_set_max_log_level_cabi::<$ty>(arg0, arg1) }
+    }
+  };);
+}
+#[doc(hidden)]
+pub(crate) use __export_wacli_cli_host_control_2_0_0_cabi;
+
+}
+
+
+#[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+pub mod host_fs {
+  #[used]
+  #[doc(hidden)]
+  static __FORCE_SECTION_REF: fn() =
+  super::super::super::super::__link_custom_section_describing_imports;
+  
+  use super::super::super::super::_rt;
+  #[repr(u8)]
+  #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+  pub enum FileKind {
+    File,
+    Dir,
+    Symlink,
+    Other,
+  }
+  impl ::core::fmt::Debug for FileKind {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      match self {
+        FileKind::File => {
+          f.debug_tuple("FileKind::File").finish()
+        }
+        FileKind::Dir => {
+          f.debug_tuple("FileKind::Dir").finish()
+        }
+        FileKind::Symlink => {
+          f.debug_tuple("FileKind::Symlink").finish()
+        }
+        FileKind::Other => {
+          f.debug_tuple("FileKind::Other").finish()
+        }
+      }
+    }
+  }
+
+  impl FileKind{
+    #[doc(hidden)]
+    pub unsafe fn _lift(val: u8) -> FileKind{
+      if !cfg!(debug_assertions) {
+        return unsafe { ::core::mem::transmute(val) };
+      }
+
+      match val {
+        0 => FileKind::File,
+        1 => FileKind::Dir,
+        2 => FileKind::Symlink,
+        3 => FileKind::Other,
+
+        _ => panic!("invalid enum discriminant"),
+      }
+    }
+  }
+
+  #[repr(C)]
+  #[derive(Clone, Copy)]
+  pub struct FileInfo {
+    pub kind: FileKind,
+    pub size: u64,
+    /// Last modification time in unix milliseconds (0 if unknown).
+    pub modified_at: u64,
+  }
+  impl ::core::fmt::Debug for FileInfo {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      f.debug_struct("FileInfo").field("kind", &self.kind).field("size", &self.size).field("modified-at", &self.modified_at).finish()
+    }
+  }
+  #[derive(Clone)]
+  pub struct DirEntry {
+    /// Path relative to the walked directory, `/`-separated.
+    pub path: _rt::String,
+    pub kind: FileKind,
+  }
+  impl ::core::fmt::Debug for DirEntry {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      f.debug_struct("DirEntry").field("path", &self.path).field("kind", &self.kind).finish()
+    }
+  }
+  /// A file kept open across writes (see `open-writer`).
+
+  #[derive(Debug)]
+  #[repr(transparent)]
+  pub struct Writer{
+    handle: _rt::Resource<Writer>,
+  }
+
+  type _WriterRep<T> = Option<T>;
+
+  impl Writer{
+    /// Creates a new resource from the specified representation.
+    ///
+    /// This function will create a new resource handle by moving `val` onto
+    /// the heap and then passing that heap pointer to the component model to
+    /// create a handle. The owned handle is then returned as `Writer`.
+    pub fn new<T: GuestWriter>(val: T) -> Self {
+      Self::type_guard::<T>();
+      let val: _WriterRep<T> = Some(val);
+      let ptr: *mut _WriterRep<T> =
+      _rt::Box::into_raw(_rt::Box::new(val));
+      unsafe {
+        Self::from_handle(T::_resource_new(ptr.cast()))
+      }
+    }
+
+    /// Gets access to the underlying `T` which represents this resource.
+    pub fn get<T: GuestWriter>(&self) -> &T {
+      let ptr = unsafe { &*self.as_ptr::<T>() };
+      ptr.as_ref().unwrap()
+    }
+
+    /// Gets mutable access to the underlying `T` which represents this
+    /// resource.
+    pub fn get_mut<T: GuestWriter>(&mut self) -> &mut T {
+      let ptr = unsafe { &mut *self.as_ptr::<T>() };
+      ptr.as_mut().unwrap()
+    }
+
+    /// Consumes this resource and returns the underlying `T`.
+    pub fn into_inner<T: GuestWriter>(self) -> T {
+      let ptr = unsafe { &mut *self.as_ptr::<T>() };
+      ptr.take().unwrap()
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn from_handle(handle: u32) -> Self {
+      Self {
+        handle: unsafe { _rt::Resource::from_handle(handle) },
+      }
+    }
+
+    #[doc(hidden)]
+    pub fn take_handle(&self) -> u32 {
+      _rt::Resource::take_handle(&self.handle)
+    }
+
+    #[doc(hidden)]
+    pub fn handle(&self) -> u32 {
+      _rt::Resource::handle(&self.handle)
+    }
+
+    // It's theoretically possible to implement the `GuestWriter` trait twice
+    // so guard against using it with two different types here.
+    #[doc(hidden)]
+    fn type_guard<T: 'static>() {
+      use core::any::TypeId;
+      static mut LAST_TYPE: Option<TypeId> = None;
+      unsafe {
+        assert!(!cfg!(target_feature = "atomics"));
+        let id = TypeId::of::<T>();
+        match LAST_TYPE {
+          Some(ty) => assert!(ty == id, "cannot use two types with this resource type"),
+          None => LAST_TYPE = Some(id),
+        }
+      }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn dtor<T: 'static>(handle: *mut u8) {
+      Self::type_guard::<T>();
+      let _ = unsafe { _rt::Box::from_raw(handle as *mut _WriterRep<T>) };
+    }
+
+    fn as_ptr<T: GuestWriter>(&self) -> *mut _WriterRep<T> {
+      Writer::type_guard::<T>();
+      T::_resource_rep(self.handle()).cast()
+    }
+  }
+
+  /// A borrowed version of [`Writer`] which represents a borrowed value
+  /// with the lifetime `'a`.
+  #[derive(Debug)]
+  #[repr(transparent)]
+  pub struct WriterBorrow<'a> {
+    rep: *mut u8,
+    _marker: core::marker::PhantomData<&'a Writer>,
+  }
+
+  impl<'a> WriterBorrow<'a>{
+    #[doc(hidden)]
+    pub unsafe fn lift(rep: usize) -> Self {
+      Self {
+        rep: rep as *mut u8,
+        _marker: core::marker::PhantomData,
+      }
+    }
+
+    /// Gets access to the underlying `T` in this resource.
+    pub fn get<T: GuestWriter>(&self) -> &'a T {
+      let ptr = unsafe { &mut *self.as_ptr::<T>() };
+      ptr.as_ref().unwrap()
+    }
+
+    // NB: mutable access is not allowed due to the component model allowing
+    // multiple borrows of the same resource.
+
+    fn as_ptr<T: 'static>(&self) -> *mut _WriterRep<T> {
+      Writer::type_guard::<T>();
+      self.rep.cast()
+    }
+  }
+  
+
+  unsafe impl _rt::WasmResource for Writer{
+    #[inline]
+    unsafe fn drop(_handle: u32) {
+      
+      #[cfg(target_arch = "wasm32")]
+      #[link(wasm_import_module = "[export]wacli:cli/host-fs@2.0.0")]
+      unsafe extern "C" {
+        #[link_name = "[resource-drop]writer"]
+        fn drop(_: i32, );
+      }
+
+      #[cfg(not(target_arch = "wasm32"))]
+      unsafe extern "C" fn drop(_: i32, ) { unreachable!() }
+      
+      unsafe { drop(_handle as i32); }
+    }
+  }
+  
+  #[doc(hidden)]
+  #[allow(non_snake_case, unused_unsafe)]
+  pub unsafe fn _export_read_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+  _rt::run_ctors_once();let result1 = {
+    let len0 = arg1;
+    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+    T::read_file(_rt::string_lift(bytes0))
+  };
+  let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+  match result1 {
+    Ok(e) => { {
+      *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+      let vec3 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+      let ptr3 = vec3.as_ptr().cast::<u8>();
+      let len3 = vec3.len();
+      ::core::mem::forget(vec3);
+      *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+      *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+    } },
+    Err(e) => { {
+      *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+      let vec4 = (e.into_bytes()).into_boxed_slice();
+      let ptr4 = vec4.as_ptr().cast::<u8>();
+      let len4 = vec4.len();
+      ::core::mem::forget(vec4);
+      *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+      *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+    } },
+  };ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_read_file<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base3 = l1;
+      let len3 = l2;
+      _rt::cabi_dealloc(base3, len3 * 1, 1);
+    },
+    _ => {
+      let l4 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l5 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l4, l5, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_write_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result2 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  let len1 = arg3;
+  T::write_file(_rt::string_lift(bytes0), <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg2.cast(), len1, len1)))
+};
+let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result2 {
+  Ok(_) => { {
+    *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+    let vec4 = (e.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *ptr3.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr3.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+  } },
+};ptr3
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_write_file<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_create_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::create_dir(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(_) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_create_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_list_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::list_dir(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    let vec4 = e;
+    let len4 = vec4.len();
+    let layout4 = _rt::alloc::Layout::from_size_align(vec4.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
+    let (result4, _cleanup4) = wit_bindgen::rt::Cleanup::new(layout4);if let Some(cleanup) = _cleanup4 { cleanup.forget(); }
+    for (i, e) in vec4.into_iter().enumerate() {
+      let base = result4.add(i * (2*::core::mem::size_of::<*const u8>()));
+      {
+        let vec3 = (e.into_bytes()).into_boxed_slice();
+        let ptr3 = vec3.as_ptr().cast::<u8>();
+        let len3 = vec3.len();
+        ::core::mem::forget(vec3);
+        *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+        *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+      }
+    }
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result4;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec5 = (e.into_bytes()).into_boxed_slice();
+    let ptr5 = vec5.as_ptr().cast::<u8>();
+    let len5 = vec5.len();
+    ::core::mem::forget(vec5);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_list_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base5 = l1;
+      let len5 = l2;
+      for i in 0..len5 {
+        let base = base5.add(i * (2*::core::mem::size_of::<*const u8>()));
+        {
+          let l3 = *base.add(0).cast::<*mut u8>();
+          let l4 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l3, l4, 1);
+        }
+      }
+      _rt::cabi_dealloc(base5, len5 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+    },
+    _ => {
+      let l6 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l7 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l6, l7, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_copy_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result2 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  let len1 = arg3;
+  let bytes1 = _rt::Vec::from_raw_parts(arg2.cast(), len1, len1);
+  T::copy_file(_rt::string_lift(bytes0), _rt::string_lift(bytes1))
+};
+let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result2 {
+  Ok(e) => { {
+    *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+    *ptr3.add(8).cast::<i64>() = _rt::as_i64(e);
+  } },
+  Err(e) => { {
+    *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+    let vec4 = (e.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *ptr3.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr3.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+  } },
+};ptr3
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_copy_file<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(8).cast::<*mut u8>();
+      let l2 = *arg0.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_rename_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result2 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  let len1 = arg3;
+  let bytes1 = _rt::Vec::from_raw_parts(arg2.cast(), len1, len1);
+  T::rename(_rt::string_lift(bytes0), _rt::string_lift(bytes1))
+};
+let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result2 {
+  Ok(_) => { {
+    *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+    let vec4 = (e.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *ptr3.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr3.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+  } },
+};ptr3
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_rename<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_remove_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::remove_file(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(_) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_remove_file<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_remove_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::remove_dir(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(_) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_remove_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_remove_dir_all_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::remove_dir_all(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(_) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_remove_dir_all<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_stat_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::stat(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    let FileInfo{ kind:kind3, size:size3, modified_at:modified_at3, } = e;
+    *ptr2.add(8).cast::<u8>() = (kind3.clone() as i32) as u8;
+    *ptr2.add(16).cast::<i64>() = _rt::as_i64(size3);
+    *ptr2.add(24).cast::<i64>() = _rt::as_i64(modified_at3);
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec4 = (e.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *ptr2.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *ptr2.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_stat<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(8).cast::<*mut u8>();
+      let l2 = *arg0.add(8+1*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_exists_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> i32 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::exists(_rt::string_lift(bytes0))
+};
+match result1 { true => 1, false => 0 }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_walk_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: i32,arg3: i32,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::walk_dir(_rt::string_lift(bytes0), match arg2 {
+    0 => None,
+    1 => {
+      let e = arg3 as u32;
+      Some(e)
+    }
+    _ => _rt::invalid_enum_discriminant(),
+  })
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    let vec5 = e;
+    let len5 = vec5.len();
+    let layout5 = _rt::alloc::Layout::from_size_align(vec5.len() * (3*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
+    let (result5, _cleanup5) = wit_bindgen::rt::Cleanup::new(layout5);if let Some(cleanup) = _cleanup5 { cleanup.forget(); }
+    for (i, e) in vec5.into_iter().enumerate() {
+      let base = result5.add(i * (3*::core::mem::size_of::<*const u8>()));
+      {
+        let DirEntry{ path:path3, kind:kind3, } = e;
+        let vec4 = (path3.into_bytes()).into_boxed_slice();
+        let ptr4 = vec4.as_ptr().cast::<u8>();
+        let len4 = vec4.len();
+        ::core::mem::forget(vec4);
+        *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+        *base.add(0).cast::<*mut u8>() = ptr4.cast_mut();
+        *base.add(2*::core::mem::size_of::<*const u8>()).cast::<u8>() = (kind3.clone() as i32) as u8;
+      }
+    }
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result5;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    let vec6 = (e.into_bytes()).into_boxed_slice();
+    let ptr6 = vec6.as_ptr().cast::<u8>();
+    let len6 = vec6.len();
+    ::core::mem::forget(vec6);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr6.cast_mut();
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_walk_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base5 = l1;
+      let len5 = l2;
+      for i in 0..len5 {
+        let base = base5.add(i * (3*::core::mem::size_of::<*const u8>()));
+        {
+          let l3 = *base.add(0).cast::<*mut u8>();
+          let l4 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l3, l4, 1);
+        }
+      }
+      _rt::cabi_dealloc(base5, len5 * (3*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+    },
+    _ => {
+      let l6 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l7 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l6, l7, 1);
+    },
+  }
+} }
 #[doc(hidden)]
 #[allow(non_snake_case, unused_unsafe)]
-pub unsafe fn _export_write_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+pub unsafe fn _export_append_file_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
 _rt::run_ctors_once();let result2 = {
   let len0 = arg1;
   let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
   let len1 = arg3;
-  T::write_file(_rt::string_lift(bytes0), <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg2.cast(), len1, len1)))
+  T::append_file(_rt::string_lift(bytes0), <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg2.cast(), len1, len1)))
 };
 let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
 match result2 {
@@ -4432,7 +6766,7 @@ match result2 {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case)]
-pub unsafe fn __post_return_write_file<T: Guest>(arg0: *mut u8,) { unsafe {
+pub unsafe fn __post_return_append_file<T: Guest>(arg0: *mut u8,) { unsafe {
   let l0 = i32::from(*arg0.add(0).cast::<u8>());
   match l0 {
     0 => (),
@@ -4445,16 +6779,17 @@ pub unsafe fn __post_return_write_file<T: Guest>(arg0: *mut u8,) { unsafe {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case, unused_unsafe)]
-pub unsafe fn _export_create_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+pub unsafe fn _export_open_writer_cabi<T: Guest>(arg0: *mut u8,arg1: usize,arg2: i32,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
 _rt::run_ctors_once();let result1 = {
   let len0 = arg1;
   let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-  T::create_dir(_rt::string_lift(bytes0))
+  T::open_writer(_rt::string_lift(bytes0), _rt::bool_lift(arg2 as u8))
 };
 let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
 match result1 {
-  Ok(_) => { {
+  Ok(e) => { {
     *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<i32>() = (e).take_handle() as i32;
   } },
   Err(e) => { {
     *ptr2.add(0).cast::<u8>() = (1i32) as u8;
@@ -4469,7 +6804,7 @@ match result1 {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case)]
-pub unsafe fn __post_return_create_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+pub unsafe fn __post_return_open_writer<T: Guest>(arg0: *mut u8,) { unsafe {
   let l0 = i32::from(*arg0.add(0).cast::<u8>());
   match l0 {
     0 => (),
@@ -4482,73 +6817,77 @@ pub unsafe fn __post_return_create_dir<T: Guest>(arg0: *mut u8,) { unsafe {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case, unused_unsafe)]
-pub unsafe fn _export_list_dir_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+pub unsafe fn _export_method_writer_write_cabi<T: GuestWriter>(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
 _rt::run_ctors_once();let result1 = {
-  let len0 = arg1;
-  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-  T::list_dir(_rt::string_lift(bytes0))
+  let len0 = arg2;
+  T::write(WriterBorrow::lift(arg0 as u32 as usize).get(), <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg1.cast(), len0, len0)))
 };
 let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
 match result1 {
-  Ok(e) => { {
+  Ok(_) => { {
     *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-    let vec4 = e;
-    let len4 = vec4.len();
-    let layout4 = _rt::alloc::Layout::from_size_align(vec4.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
-    let (result4, _cleanup4) = wit_bindgen::rt::Cleanup::new(layout4);if let Some(cleanup) = _cleanup4 { cleanup.forget(); }
-    for (i, e) in vec4.into_iter().enumerate() {
-      let base = result4.add(i * (2*::core::mem::size_of::<*const u8>()));
-      {
-        let vec3 = (e.into_bytes()).into_boxed_slice();
-        let ptr3 = vec3.as_ptr().cast::<u8>();
-        let len3 = vec3.len();
-        ::core::mem::forget(vec3);
-        *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
-        *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
-      }
-    }
-    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
-    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result4;
   } },
   Err(e) => { {
     *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-    let vec5 = (e.into_bytes()).into_boxed_slice();
-    let ptr5 = vec5.as_ptr().cast::<u8>();
-    let len5 = vec5.len();
-    ::core::mem::forget(vec5);
-    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
-    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+    let vec3 = (e.into_bytes()).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
   } },
 };ptr2
 } }
 #[doc(hidden)]
 #[allow(non_snake_case)]
-pub unsafe fn __post_return_list_dir<T: Guest>(arg0: *mut u8,) { unsafe {
+pub unsafe fn __post_return_method_writer_write<T: GuestWriter>(arg0: *mut u8,) { unsafe {
   let l0 = i32::from(*arg0.add(0).cast::<u8>());
   match l0 {
-    0 => {
+    0 => (),
+    _ => {
       let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
       let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
-      let base5 = l1;
-      let len5 = l2;
-      for i in 0..len5 {
-        let base = base5.add(i * (2*::core::mem::size_of::<*const u8>()));
-        {
-          let l3 = *base.add(0).cast::<*mut u8>();
-          let l4 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
-          _rt::cabi_dealloc(l3, l4, 1);
-        }
-      }
-      _rt::cabi_dealloc(base5, len5 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      _rt::cabi_dealloc(l1, l2, 1);
     },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_method_writer_close_cabi<T: GuestWriter>(arg0: *mut u8,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::close(WriterBorrow::lift(arg0 as u32 as usize).get())
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result0 {
+  Ok(_) => { {
+    *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+  } },
+  Err(e) => { {
+    *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+    let vec2 = (e.into_bytes()).into_boxed_slice();
+    let ptr2 = vec2.as_ptr().cast::<u8>();
+    let len2 = vec2.len();
+    ::core::mem::forget(vec2);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr2.cast_mut();
+  } },
+};ptr1
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_writer_close<T: GuestWriter>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
     _ => {
-      let l6 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
-      let l7 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
-      _rt::cabi_dealloc(l6, l7, 1);
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
     },
   }
 } }
 pub trait Guest {
+  type Writer: GuestWriter;
   #[allow(async_fn_in_trait)]
   fn read_file(path: _rt::String,) -> Result<_rt::Vec::<u8>,_rt::String>;
   #[allow(async_fn_in_trait)]
@@ -4557,6 +6896,89 @@ pub trait Guest {
   fn create_dir(path: _rt::String,) -> Result<(),_rt::String>;
   #[allow(async_fn_in_trait)]
   fn list_dir(path: _rt::String,) -> Result<_rt::Vec::<_rt::String>,_rt::String>;
+  /// Copy `src` to `dst` (created or truncated), streaming in chunks so large
+  /// files are never held in memory. Returns the number of bytes copied.
+  #[allow(async_fn_in_trait)]
+  fn copy_file(src: _rt::String,dst: _rt::String,) -> Result<u64,_rt::String>;
+  /// Move `src` to `dst`, replacing a file (or empty directory) already
+  /// there. Both must be under the same preopened directory; across mounts
+  /// it fails, and `copy-file` then `remove-file` has to be used instead.
+  #[allow(async_fn_in_trait)]
+  fn rename(src: _rt::String,dst: _rt::String,) -> Result<(),_rt::String>;
+  /// Remove a file (or symlink).
+  #[allow(async_fn_in_trait)]
+  fn remove_file(path: _rt::String,) -> Result<(),_rt::String>;
+  /// Remove an empty directory.
+  #[allow(async_fn_in_trait)]
+  fn remove_dir(path: _rt::String,) -> Result<(),_rt::String>;
+  /// Remove a directory and everything in it. Symlinks are removed, not followed.
+  #[allow(async_fn_in_trait)]
+  fn remove_dir_all(path: _rt::String,) -> Result<(),_rt::String>;
+  /// Metadata of `path` itself: a symlink is reported as `symlink`, not
+  /// as what it points at.
+  #[allow(async_fn_in_trait)]
+  fn stat(path: _rt::String,) -> Result<FileInfo,_rt::String>;
+  /// Whether `path` exists (following symlinks). Never fails.
+  #[allow(async_fn_in_trait)]
+  fn exists(path: _rt::String,) -> bool;
+  /// Everything below `path`, depth-first with each directory's entries
+  /// sorted by name. `max-depth` 1 lists direct children only; `none` has no
+  /// limit. Symlinks are listed but never followed.
+  #[allow(async_fn_in_trait)]
+  fn walk_dir(path: _rt::String,max_depth: Option<u32>,) -> Result<_rt::Vec::<DirEntry>,_rt::String>;
+  /// Append `contents` to `path`, creating it if missing.
+  #[allow(async_fn_in_trait)]
+  fn append_file(path: _rt::String,contents: _rt::Vec::<u8>,) -> Result<(),_rt::String>;
+  /// Open `path` for incremental writes, creating it if missing. Without
+  /// `append` it is truncated first.
+  #[allow(async_fn_in_trait)]
+  fn open_writer(path: _rt::String,append: bool,) -> Result<Writer,_rt::String>;
+}
+pub trait GuestWriter: 'static {
+
+  #[doc(hidden)]
+  unsafe fn _resource_new(val: *mut u8) -> u32
+  where Self: Sized
+  {
+    
+    #[cfg(target_arch = "wasm32")]
+    #[link(wasm_import_module = "[export]wacli:cli/host-fs@2.0.0")]
+    unsafe extern "C" {
+      #[link_name = "[resource-new]writer"]
+      fn new(_: *mut u8, ) -> i32;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe extern "C" fn new(_: *mut u8, ) -> i32 { unreachable!() }
+    
+    unsafe { new(val) as u32 }
+  }
+
+  #[doc(hidden)]
+  fn _resource_rep(handle: u32) -> *mut u8
+  where Self: Sized
+  {
+    
+    #[cfg(target_arch = "wasm32")]
+    #[link(wasm_import_module = "[export]wacli:cli/host-fs@2.0.0")]
+    unsafe extern "C" {
+      #[link_name = "[resource-rep]writer"]
+      fn rep(_: i32, ) -> *mut u8;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    unsafe extern "C" fn rep(_: i32, ) -> *mut u8 { unreachable!() }
+    
+    unsafe { rep(handle as i32) }
+  }
+
+  
+  /// Write all of `bytes` after what was written so far.
+  #[allow(async_fn_in_trait)]
+  fn write(&self,bytes: _rt::Vec::<u8>,) -> Result<(),_rt::String>;
+  /// Flush and close. Writing afterwards fails; closing again does nothing.
+  #[allow(async_fn_in_trait)]
+  fn close(&self,) -> Result<(),_rt::String>;
 }
 #[doc(hidden)]
 
@@ -4595,15 +7017,120 @@ macro_rules! __export_wacli_cli_host_fs_2_0_0_cabi{
     unsafe extern "C" fn _post_return_list_dir(arg0: *mut u8,) {
       unsafe { $($path_to_types)*::__post_return_list_dir::<$ty>(arg0) }
     }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#copy-file")]
+    unsafe extern "C" fn export_copy_file(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_copy_file_cabi::<$ty>(arg0, arg1, arg2, arg3) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#copy-file")]
+    unsafe extern "C" fn _post_return_copy_file(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_copy_file::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#rename")]
+    unsafe extern "C" fn export_rename(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_rename_cabi::<$ty>(arg0, arg1, arg2, arg3) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#rename")]
+    unsafe extern "C" fn _post_return_rename(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_rename::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#remove-file")]
+    unsafe extern "C" fn export_remove_file(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_remove_file_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#remove-file")]
+    unsafe extern "C" fn _post_return_remove_file(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_remove_file::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#remove-dir")]
+    unsafe extern "C" fn export_remove_dir(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_remove_dir_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#remove-dir")]
+    unsafe extern "C" fn _post_return_remove_dir(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_remove_dir::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#remove-dir-all")]
+    unsafe extern "C" fn export_remove_dir_all(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_remove_dir_all_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#remove-dir-all")]
+    unsafe extern "C" fn _post_return_remove_dir_all(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_remove_dir_all::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#stat")]
+    unsafe extern "C" fn export_stat(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_stat_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#stat")]
+    unsafe extern "C" fn _post_return_stat(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_stat::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#exists")]
+    unsafe extern "C" fn export_exists(arg0: *mut u8,arg1: usize,) -> i32 {
+      unsafe { $($path_to_types)*::_export_exists_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#walk-dir")]
+    unsafe extern "C" fn export_walk_dir(arg0: *mut u8,arg1: usize,arg2: i32,arg3: i32,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_walk_dir_cabi::<$ty>(arg0, arg1, arg2, arg3) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#walk-dir")]
+    unsafe extern "C" fn _post_return_walk_dir(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_walk_dir::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#append-file")]
+    unsafe extern "C" fn export_append_file(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_append_file_cabi::<$ty>(arg0, arg1, arg2, arg3) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#append-file")]
+    unsafe extern "C" fn _post_return_append_file(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_append_file::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#open-writer")]
+    unsafe extern "C" fn export_open_writer(arg0: *mut u8,arg1: usize,arg2: i32,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_open_writer_cabi::<$ty>(arg0, arg1, arg2) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#open-writer")]
+    unsafe extern "C" fn _post_return_open_writer(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_open_writer::<$ty>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#[method]writer.write")]
+    unsafe extern "C" fn export_method_writer_write(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_writer_write_cabi::<<$ty as $($path_to_types)*::Guest>::Writer>(arg0, arg1, arg2) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#[method]writer.write")]
+    unsafe extern "C" fn _post_return_method_writer_write(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_writer_write::<<$ty as $($path_to_types)*::Guest>::Writer>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#[method]writer.close")]
+    unsafe extern "C" fn export_method_writer_close(arg0: *mut u8,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_writer_close_cabi::<<$ty as $($path_to_types)*::Guest>::Writer>(arg0) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-fs@2.0.0#[method]writer.close")]
+    unsafe extern "C" fn _post_return_method_writer_close(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_writer_close::<<$ty as $($path_to_types)*::Guest>::Writer>(arg0) }
+    }
+
+    const _: () = {
+      #[doc(hidden)]
+      #[unsafe(export_name = "wacli:cli/host-fs@2.0.0#[dtor]writer")]
+      #[allow(non_snake_case)]
+      unsafe extern "C" fn dtor(rep: *mut u8) {
+        unsafe {
+          $($path_to_types)*::Writer::dtor::<
+          <$ty as $($path_to_types)*::Guest>::Writer
+          >(rep)
+        }
+      }
+    };
+    
   };);
 }
 #[doc(hidden)]
 pub(crate) use __export_wacli_cli_host_fs_2_0_0_cabi;
 
-#[cfg_attr(target_pointer_width="64", repr(align(8)))]
-#[cfg_attr(target_pointer_width="32", repr(align(4)))]
-struct _RetArea([::core::mem::MaybeUninit::<u8>; 3*::core::mem::size_of::<*const u8>()]);
-static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 3*::core::mem::size_of::<*const u8>()]);
+#[repr(align(8))]
+struct _RetArea([::core::mem::MaybeUninit::<u8>; 32]);
+static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 32]);
 
 }
 
@@ -4656,6 +7183,39 @@ pub mod host_pipes {
   pub type PipeMeta = super::super::super::super::exports::wacli::cli::types::PipeMeta;
   pub type PipeError = super::super::super::super::exports::wacli::cli::types::PipeError;
   pub type PipeInfo = super::super::super::super::exports::wacli::cli::types::PipeInfo;
+  pub type PipeDetails = super::super::super::super::exports::wacli::cli::types::PipeDetails;
+  /// Why `open-pipe` failed.
+  #[derive(Clone)]
+  pub enum PipeLoadError {
+    /// No pipe by that name (a typo, or not installed).
+    NotFound(_rt::String),
+    /// The name itself is malformed.
+    InvalidName(_rt::String),
+    /// The pipe exists but could not be read or instantiated.
+    LoadFailed(_rt::String),
+  }
+  impl ::core::fmt::Debug for PipeLoadError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      match self {
+        PipeLoadError::NotFound(e) => {
+          f.debug_tuple("PipeLoadError::NotFound").field(e).finish()
+        }
+        PipeLoadError::InvalidName(e) => {
+          f.debug_tuple("PipeLoadError::InvalidName").field(e).finish()
+        }
+        PipeLoadError::LoadFailed(e) => {
+          f.debug_tuple("PipeLoadError::LoadFailed").field(e).finish()
+        }
+      }
+    }
+  }
+  impl ::core::fmt::Display for PipeLoadError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+      write!(f, "{:?}", self)
+    }
+  }
+
+  impl ::core::error::Error for PipeLoadError {}
 
   #[derive(Debug)]
   #[repr(transparent)]
@@ -4861,6 +7421,117 @@ pub unsafe fn __post_return_list_pipes<T: Guest>(arg0: *mut u8,) { unsafe {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_describe_pipes_cabi<T: Guest>() -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::describe_pipes()
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+let vec11 = result0;
+let len11 = vec11.len();
+let layout11 = _rt::alloc::Layout::from_size_align(vec11.len() * (12*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
+let (result11, _cleanup11) = wit_bindgen::rt::Cleanup::new(layout11);if let Some(cleanup) = _cleanup11 { cleanup.forget(); }
+for (i, e) in vec11.into_iter().enumerate() {
+  let base = result11.add(i * (12*::core::mem::size_of::<*const u8>()));
+  {
+    let super::super::super::super::exports::wacli::cli::types::PipeDetails{ info:info2, input_types:input_types2, output_type:output_type2, version:version2, } = e;
+    let super::super::super::super::exports::wacli::cli::types::PipeInfo{ name:name3, summary:summary3, path:path3, } = info2;
+    let vec4 = (name3.into_bytes()).into_boxed_slice();
+    let ptr4 = vec4.as_ptr().cast::<u8>();
+    let len4 = vec4.len();
+    ::core::mem::forget(vec4);
+    *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+    *base.add(0).cast::<*mut u8>() = ptr4.cast_mut();
+    let vec5 = (summary3.into_bytes()).into_boxed_slice();
+    let ptr5 = vec5.as_ptr().cast::<u8>();
+    let len5 = vec5.len();
+    ::core::mem::forget(vec5);
+    *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+    *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+    let vec6 = (path3.into_bytes()).into_boxed_slice();
+    let ptr6 = vec6.as_ptr().cast::<u8>();
+    let len6 = vec6.len();
+    ::core::mem::forget(vec6);
+    *base.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+    *base.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr6.cast_mut();
+    let vec8 = input_types2;
+    let len8 = vec8.len();
+    let layout8 = _rt::alloc::Layout::from_size_align(vec8.len() * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>()).unwrap();
+    let (result8, _cleanup8) = wit_bindgen::rt::Cleanup::new(layout8);if let Some(cleanup) = _cleanup8 { cleanup.forget(); }
+    for (i, e) in vec8.into_iter().enumerate() {
+      let base = result8.add(i * (2*::core::mem::size_of::<*const u8>()));
+      {
+        let vec7 = (e.into_bytes()).into_boxed_slice();
+        let ptr7 = vec7.as_ptr().cast::<u8>();
+        let len7 = vec7.len();
+        ::core::mem::forget(vec7);
+        *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+        *base.add(0).cast::<*mut u8>() = ptr7.cast_mut();
+      }
+    }
+    *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+    *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = result8;
+    let vec9 = (output_type2.into_bytes()).into_boxed_slice();
+    let ptr9 = vec9.as_ptr().cast::<u8>();
+    let len9 = vec9.len();
+    ::core::mem::forget(vec9);
+    *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>() = len9;
+    *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr9.cast_mut();
+    let vec10 = (version2.into_bytes()).into_boxed_slice();
+    let ptr10 = vec10.as_ptr().cast::<u8>();
+    let len10 = vec10.len();
+    ::core::mem::forget(vec10);
+    *base.add(11*::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
+    *base.add(10*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr10.cast_mut();
+  }
+}
+*ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
+*ptr1.add(0).cast::<*mut u8>() = result11;
+ptr1
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_describe_pipes<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = *arg0.add(0).cast::<*mut u8>();
+  let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+  let base17 = l0;
+  let len17 = l1;
+  for i in 0..len17 {
+    let base = base17.add(i * (12*::core::mem::size_of::<*const u8>()));
+    {
+      let l2 = *base.add(0).cast::<*mut u8>();
+      let l3 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l2, l3, 1);
+      let l4 = *base.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l5 = *base.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l4, l5, 1);
+      let l6 = *base.add(4*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l7 = *base.add(5*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l6, l7, 1);
+      let l8 = *base.add(6*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l9 = *base.add(7*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base12 = l8;
+      let len12 = l9;
+      for i in 0..len12 {
+        let base = base12.add(i * (2*::core::mem::size_of::<*const u8>()));
+        {
+          let l10 = *base.add(0).cast::<*mut u8>();
+          let l11 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l10, l11, 1);
+        }
+      }
+      _rt::cabi_dealloc(base12, len12 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+      let l13 = *base.add(8*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l14 = *base.add(9*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l13, l14, 1);
+      let l15 = *base.add(10*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l16 = *base.add(11*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l15, l16, 1);
+    }
+  }
+  _rt::cabi_dealloc(base17, len17 * (12*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
 pub unsafe fn _export_load_pipe_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
 _rt::run_ctors_once();let result1 = {
   let len0 = arg1;
@@ -4886,14 +7557,90 @@ match result1 {
 } }
 #[doc(hidden)]
 #[allow(non_snake_case)]
-pub unsafe fn __post_return_load_pipe<T: Guest>(arg0: *mut u8,) { unsafe {
+pub unsafe fn __post_return_load_pipe<T: Guest>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      _rt::cabi_dealloc(l1, l2, 1);
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_open_pipe_cabi<T: Guest>(arg0: *mut u8,arg1: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg1;
+  let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+  T::open_pipe(_rt::string_lift(bytes0))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<i32>() = (e).take_handle() as i32;
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    match e {
+      PipeLoadError::NotFound(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+        let vec3 = (e.into_bytes()).into_boxed_slice();
+        let ptr3 = vec3.as_ptr().cast::<u8>();
+        let len3 = vec3.len();
+        ::core::mem::forget(vec3);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+      },
+      PipeLoadError::InvalidName(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+        let vec4 = (e.into_bytes()).into_boxed_slice();
+        let ptr4 = vec4.as_ptr().cast::<u8>();
+        let len4 = vec4.len();
+        ::core::mem::forget(vec4);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+      },
+      PipeLoadError::LoadFailed(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (2i32) as u8;
+        let vec5 = (e.into_bytes()).into_boxed_slice();
+        let ptr5 = vec5.as_ptr().cast::<u8>();
+        let len5 = vec5.len();
+        ::core::mem::forget(vec5);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+      },
+    }
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_open_pipe<T: Guest>(arg0: *mut u8,) { unsafe {
   let l0 = i32::from(*arg0.add(0).cast::<u8>());
   match l0 {
     0 => (),
     _ => {
-      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
-      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
-      _rt::cabi_dealloc(l1, l2, 1);
+      let l1 = i32::from(*arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+      match l1 {
+        0 => {
+          let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l3 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l2, l3, 1);
+        },
+        1 => {
+          let l4 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l5 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l4, l5, 1);
+        },
+        _ => {
+          let l6 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l7 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l6, l7, 1);
+        },
+      }
     },
   }
 } }
@@ -5080,12 +7827,288 @@ pub unsafe fn __post_return_method_pipe_process<T: GuestPipe>(arg0: *mut u8,) {
     },
   }
 } }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_method_pipe_process_start_cabi<T: GuestPipe>(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result4 = {
+  let base3 = arg1;
+  let len3 = arg2;
+  let mut result3 = _rt::Vec::with_capacity(len3);
+  for i in 0..len3 {
+    let base = base3.add(i * (2*::core::mem::size_of::<*const u8>()));
+    let e3 = {
+      let l0 = *base.add(0).cast::<*mut u8>();
+      let l1 = *base.add(::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let len2 = l1;
+      let bytes2 = _rt::Vec::from_raw_parts(l0.cast(), len2, len2);
+
+      _rt::string_lift(bytes2)
+    };
+    result3.push(e3);
+  }
+  _rt::cabi_dealloc(base3, len3 * (2*::core::mem::size_of::<*const u8>()), ::core::mem::size_of::<*const u8>());
+  T::process_start(PipeBorrow::lift(arg0 as u32 as usize).get(), result3)
+};
+let ptr5 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result4 {
+  Ok(e) => { {
+    *ptr5.add(0).cast::<u8>() = (0i32) as u8;
+    *ptr5.add(::core::mem::size_of::<*const u8>()).cast::<i32>() = _rt::as_i32(e);
+  } },
+  Err(e) => { {
+    *ptr5.add(0).cast::<u8>() = (1i32) as u8;
+    use super::super::super::super::exports::wacli::cli::types::PipeError as V9;
+    match e {
+      V9::ParseError(e) => {
+        *ptr5.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+        let vec6 = (e.into_bytes()).into_boxed_slice();
+        let ptr6 = vec6.as_ptr().cast::<u8>();
+        let len6 = vec6.len();
+        ::core::mem::forget(vec6);
+        *ptr5.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+        *ptr5.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr6.cast_mut();
+      },
+      V9::TransformError(e) => {
+        *ptr5.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+        let vec7 = (e.into_bytes()).into_boxed_slice();
+        let ptr7 = vec7.as_ptr().cast::<u8>();
+        let len7 = vec7.len();
+        ::core::mem::forget(vec7);
+        *ptr5.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+        *ptr5.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr7.cast_mut();
+      },
+      V9::InvalidOption(e) => {
+        *ptr5.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (2i32) as u8;
+        let vec8 = (e.into_bytes()).into_boxed_slice();
+        let ptr8 = vec8.as_ptr().cast::<u8>();
+        let len8 = vec8.len();
+        ::core::mem::forget(vec8);
+        *ptr5.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+        *ptr5.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr8.cast_mut();
+      },
+    }
+  } },
+};ptr5
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_pipe_process_start<T: GuestPipe>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => (),
+    _ => {
+      let l1 = i32::from(*arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+      match l1 {
+        0 => {
+          let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l3 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l2, l3, 1);
+        },
+        1 => {
+          let l4 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l5 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l4, l5, 1);
+        },
+        _ => {
+          let l6 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l7 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l6, l7, 1);
+        },
+      }
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_method_pipe_process_chunk_cabi<T: GuestPipe>(arg0: *mut u8,arg1: i32,arg2: *mut u8,arg3: usize,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result1 = {
+  let len0 = arg3;
+  T::process_chunk(PipeBorrow::lift(arg0 as u32 as usize).get(), arg1 as u32, <_ as From<_rt::Vec<_>>>::from(_rt::Vec::from_raw_parts(arg2.cast(), len0, len0)))
+};
+let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result1 {
+  Ok(e) => { {
+    *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+    let vec3 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+    let ptr3 = vec3.as_ptr().cast::<u8>();
+    let len3 = vec3.len();
+    ::core::mem::forget(vec3);
+    *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+  } },
+  Err(e) => { {
+    *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+    use super::super::super::super::exports::wacli::cli::types::PipeError as V7;
+    match e {
+      V7::ParseError(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+        let vec4 = (e.into_bytes()).into_boxed_slice();
+        let ptr4 = vec4.as_ptr().cast::<u8>();
+        let len4 = vec4.len();
+        ::core::mem::forget(vec4);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+      },
+      V7::TransformError(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+        let vec5 = (e.into_bytes()).into_boxed_slice();
+        let ptr5 = vec5.as_ptr().cast::<u8>();
+        let len5 = vec5.len();
+        ::core::mem::forget(vec5);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+      },
+      V7::InvalidOption(e) => {
+        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (2i32) as u8;
+        let vec6 = (e.into_bytes()).into_boxed_slice();
+        let ptr6 = vec6.as_ptr().cast::<u8>();
+        let len6 = vec6.len();
+        ::core::mem::forget(vec6);
+        *ptr2.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len6;
+        *ptr2.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr6.cast_mut();
+      },
+    }
+  } },
+};ptr2
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_pipe_process_chunk<T: GuestPipe>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base3 = l1;
+      let len3 = l2;
+      _rt::cabi_dealloc(base3, len3 * 1, 1);
+    },
+    _ => {
+      let l4 = i32::from(*arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+      match l4 {
+        0 => {
+          let l5 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l6 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l5, l6, 1);
+        },
+        1 => {
+          let l7 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l8 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l7, l8, 1);
+        },
+        _ => {
+          let l9 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l10 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l9, l10, 1);
+        },
+      }
+    },
+  }
+} }
+#[doc(hidden)]
+#[allow(non_snake_case, unused_unsafe)]
+pub unsafe fn _export_method_pipe_process_end_cabi<T: GuestPipe>(arg0: *mut u8,arg1: i32,) -> *mut u8 { unsafe {#[cfg(target_arch="wasm32")]
+_rt::run_ctors_once();let result0 = {
+  T::process_end(PipeBorrow::lift(arg0 as u32 as usize).get(), arg1 as u32)
+};
+let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+match result0 {
+  Ok(e) => { {
+    *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+    let vec2 = <_ as Into<_rt::Vec<_>>>::into(e).into_boxed_slice();
+    let ptr2 = vec2.as_ptr().cast::<u8>();
+    let len2 = vec2.len();
+    ::core::mem::forget(vec2);
+    *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
+    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr2.cast_mut();
+  } },
+  Err(e) => { {
+    *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+    use super::super::super::super::exports::wacli::cli::types::PipeError as V6;
+    match e {
+      V6::ParseError(e) => {
+        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (0i32) as u8;
+        let vec3 = (e.into_bytes()).into_boxed_slice();
+        let ptr3 = vec3.as_ptr().cast::<u8>();
+        let len3 = vec3.len();
+        ::core::mem::forget(vec3);
+        *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+        *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr3.cast_mut();
+      },
+      V6::TransformError(e) => {
+        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (1i32) as u8;
+        let vec4 = (e.into_bytes()).into_boxed_slice();
+        let ptr4 = vec4.as_ptr().cast::<u8>();
+        let len4 = vec4.len();
+        ::core::mem::forget(vec4);
+        *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+        *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr4.cast_mut();
+      },
+      V6::InvalidOption(e) => {
+        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>() = (2i32) as u8;
+        let vec5 = (e.into_bytes()).into_boxed_slice();
+        let ptr5 = vec5.as_ptr().cast::<u8>();
+        let len5 = vec5.len();
+        ::core::mem::forget(vec5);
+        *ptr1.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+        *ptr1.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>() = ptr5.cast_mut();
+      },
+    }
+  } },
+};ptr1
+} }
+#[doc(hidden)]
+#[allow(non_snake_case)]
+pub unsafe fn __post_return_method_pipe_process_end<T: GuestPipe>(arg0: *mut u8,) { unsafe {
+  let l0 = i32::from(*arg0.add(0).cast::<u8>());
+  match l0 {
+    0 => {
+      let l1 = *arg0.add(::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+      let l2 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<usize>();
+      let base3 = l1;
+      let len3 = l2;
+      _rt::cabi_dealloc(base3, len3 * 1, 1);
+    },
+    _ => {
+      let l4 = i32::from(*arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>());
+      match l4 {
+        0 => {
+          let l5 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l6 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l5, l6, 1);
+        },
+        1 => {
+          let l7 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l8 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l7, l8, 1);
+        },
+        _ => {
+          let l9 = *arg0.add(2*::core::mem::size_of::<*const u8>()).cast::<*mut u8>();
+          let l10 = *arg0.add(3*::core::mem::size_of::<*const u8>()).cast::<usize>();
+          _rt::cabi_dealloc(l9, l10, 1);
+        },
+      }
+    },
+  }
+} }
 pub trait Guest {
   type Pipe: GuestPipe;
   #[allow(async_fn_in_trait)]
   fn list_pipes() -> _rt::Vec::<PipeInfo>;
+  /// Like `list-pipes`, with each pipe's types and version. Read from the
+  /// pipes' metadata sections without instantiating them.
+  #[allow(async_fn_in_trait)]
+  fn describe_pipes() -> _rt::Vec::<PipeDetails>;
+  /// Load a pipe by name. The error is the message of the `pipe-load-error`
+  /// that `open-pipe` returns; its type stays `string` so components built
+  /// against earlier `wacli:cli@2.0.0` releases still compose.
   #[allow(async_fn_in_trait)]
   fn load_pipe(name: _rt::String,) -> Result<Pipe,_rt::String>;
+  /// Like `load-pipe`, saying why loading failed. Added within
+  /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+  /// import it.
+  #[allow(async_fn_in_trait)]
+  fn open_pipe(name: _rt::String,) -> Result<Pipe,PipeLoadError>;
 }
 pub trait GuestPipe: 'static {
 
@@ -5130,6 +8153,15 @@ pub trait GuestPipe: 'static {
   fn meta(&self,) -> PipeMeta;
   #[allow(async_fn_in_trait)]
   fn process(&self,input: _rt::Vec::<u8>,options: _rt::Vec::<_rt::String>,) -> Result<_rt::Vec::<u8>,PipeError>;
+  /// Start a chunked run (see `pipe-stream`): feed the input to
+  /// `process-chunk` and finish with `process-end`, collecting the output
+  /// each returns. The options are checked like those of `process`.
+  #[allow(async_fn_in_trait)]
+  fn process_start(&self,options: _rt::Vec::<_rt::String>,) -> Result<u32,PipeError>;
+  #[allow(async_fn_in_trait)]
+  fn process_chunk(&self,session: u32,bytes: _rt::Vec::<u8>,) -> Result<_rt::Vec::<u8>,PipeError>;
+  #[allow(async_fn_in_trait)]
+  fn process_end(&self,session: u32,) -> Result<_rt::Vec::<u8>,PipeError>;
 }
 #[doc(hidden)]
 
@@ -5144,6 +8176,14 @@ macro_rules! __export_wacli_cli_host_pipes_2_0_0_cabi{
     unsafe extern "C" fn _post_return_list_pipes(arg0: *mut u8,) {
       unsafe { $($path_to_types)*::__post_return_list_pipes::<$ty>(arg0) }
     }
+    #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#describe-pipes")]
+    unsafe extern "C" fn export_describe_pipes() -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_describe_pipes_cabi::<$ty>() }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-pipes@2.0.0#describe-pipes")]
+    unsafe extern "C" fn _post_return_describe_pipes(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_describe_pipes::<$ty>(arg0) }
+    }
     #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#load-pipe")]
     unsafe extern "C" fn export_load_pipe(arg0: *mut u8,arg1: usize,) -> *mut u8 {
       unsafe { $($path_to_types)*::_export_load_pipe_cabi::<$ty>(arg0, arg1) }
@@ -5152,6 +8192,14 @@ macro_rules! __export_wacli_cli_host_pipes_2_0_0_cabi{
     unsafe extern "C" fn _post_return_load_pipe(arg0: *mut u8,) {
       unsafe { $($path_to_types)*::__post_return_load_pipe::<$ty>(arg0) }
     }
+    #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#open-pipe")]
+    unsafe extern "C" fn export_open_pipe(arg0: *mut u8,arg1: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_open_pipe_cabi::<$ty>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-pipes@2.0.0#open-pipe")]
+    unsafe extern "C" fn _post_return_open_pipe(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_open_pipe::<$ty>(arg0) }
+    }
     #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#[method]pipe.meta")]
     unsafe extern "C" fn export_method_pipe_meta(arg0: *mut u8,) -> *mut u8 {
       unsafe { $($path_to_types)*::_export_method_pipe_meta_cabi::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0) }
@@ -5168,6 +8216,30 @@ macro_rules! __export_wacli_cli_host_pipes_2_0_0_cabi{
     unsafe extern "C" fn _post_return_method_pipe_process(arg0: *mut u8,) {
       unsafe { $($path_to_types)*::__post_return_method_pipe_process::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0) }
     }
+    #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#[method]pipe.process-start")]
+    unsafe extern "C" fn export_method_pipe_process_start(arg0: *mut u8,arg1: *mut u8,arg2: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_pipe_process_start_cabi::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0, arg1, arg2) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-pipes@2.0.0#[method]pipe.process-start")]
+    unsafe extern "C" fn _post_return_method_pipe_process_start(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_pipe_process_start::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#[method]pipe.process-chunk")]
+    unsafe extern "C" fn export_method_pipe_process_chunk(arg0: *mut u8,arg1: i32,arg2: *mut u8,arg3: usize,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_pipe_process_chunk_cabi::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0, arg1, arg2, arg3) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-pipes@2.0.0#[method]pipe.process-chunk")]
+    unsafe extern "C" fn _post_return_method_pipe_process_chunk(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_pipe_process_chunk::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0) }
+    }
+    #[unsafe(export_name = "wacli:cli/host-pipes@2.0.0#[method]pipe.process-end")]
+    unsafe extern "C" fn export_method_pipe_process_end(arg0: *mut u8,arg1: i32,) -> *mut u8 {
+      unsafe { $($path_to_types)*::_export_method_pipe_process_end_cabi::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0, arg1) }
+    }
+    #[unsafe(export_name = "cabi_post_wacli:cli/host-pipes@2.0.0#[method]pipe.process-end")]
+    unsafe extern "C" fn _post_return_method_pipe_process_end(arg0: *mut u8,) {
+      unsafe { $($path_to_types)*::__post_return_method_pipe_process_end::<<$ty as $($path_to_types)*::Guest>::Pipe>(arg0) }
+    }
 
     const _: () = {
       #[doc(hidden)]
@@ -5321,46 +8393,7 @@ mod _rt {
     }
   }
   pub use alloc_crate::alloc;
-  pub unsafe fn bool_lift(val: u8) -> bool {
-    if cfg!(debug_assertions) {
-      match val {
-        0 => false,
-        1 => true,
-        _ => panic!("invalid bool discriminant"),
-      }
-    } else {
-      val != 0
-    }
-  }
-  
-  pub fn as_i64<T: AsI64>(t: T) -> i64 {
-    t.as_i64()
-  }
-
-  pub trait AsI64 {
-    fn as_i64(self) -> i64;
-  }
 
-  impl<'a, T: Copy + AsI64> AsI64 for &'a T {
-    fn as_i64(self) -> i64 {
-      (*self).as_i64()
-    }
-  }
-  
-  impl AsI64 for i64 {
-    #[inline]
-    fn as_i64(self) -> i64 {
-      self as i64
-    }
-  }
-  
-  impl AsI64 for u64 {
-    #[inline]
-    fn as_i64(self) -> i64 {
-      self as i64
-    }
-  }
-  
   pub fn as_i32<T: AsI32>(t: T) -> i32 {
     t.as_i32()
   }
@@ -5430,6 +8463,45 @@ mod _rt {
       self as i32
     }
   }
+  pub unsafe fn bool_lift(val: u8) -> bool {
+    if cfg!(debug_assertions) {
+      match val {
+        0 => false,
+        1 => true,
+        _ => panic!("invalid bool discriminant"),
+      }
+    } else {
+      val != 0
+    }
+  }
+  
+  pub fn as_i64<T: AsI64>(t: T) -> i64 {
+    t.as_i64()
+  }
+
+  pub trait AsI64 {
+    fn as_i64(self) -> i64;
+  }
+
+  impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+    fn as_i64(self) -> i64 {
+      (*self).as_i64()
+    }
+  }
+  
+  impl AsI64 for i64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
+  
+  impl AsI64 for u64 {
+    #[inline]
+    fn as_i64(self) -> i64 {
+      self as i64
+    }
+  }
   
   #[cfg(target_arch = "wasm32")]
   pub fn run_ctors_once() {
@@ -5465,6 +8537,8 @@ macro_rules! __export_host_provider_impl {
   $($path_to_types_root)*::exports::wacli::cli::schema::__export_wacli_cli_schema_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::schema);
   $($path_to_types_root)*::exports::wacli::cli::host_env::__export_wacli_cli_host_env_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_env);
   $($path_to_types_root)*::exports::wacli::cli::host_io::__export_wacli_cli_host_io_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_io);
+  $($path_to_types_root)*::exports::wacli::cli::host_log::__export_wacli_cli_host_log_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_log);
+  $($path_to_types_root)*::exports::wacli::cli::host_control::__export_wacli_cli_host_control_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_control);
   $($path_to_types_root)*::exports::wacli::cli::host_fs::__export_wacli_cli_host_fs_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_fs);
   $($path_to_types_root)*::exports::wacli::cli::host_process::__export_wacli_cli_host_process_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_process);
   $($path_to_types_root)*::exports::wacli::cli::host_pipes::__export_wacli_cli_host_pipes_2_0_0_cabi!($ty with_types_in $($path_to_types_root)*::exports::wacli::cli::host_pipes);
@@ -5477,9 +8551,9 @@ pub(crate) use __export_host_provider_impl as export;
 #[unsafe(link_section = "component-type:wit-bindgen:0.52.0:wacli:cli@2.0.0:host-provider:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 7370] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc68\x01A\x02\x01A1\x01\
-B\x13\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\x04names\x05short\x02\x04lo\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 10253] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x89O\x01A\x02\x01AI\x01\
+B\x15\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\x04names\x05short\x02\x04lo\
 ng\x02\x04helps\x08required\x7f\x0ddefault-value\x02\x0avalue-name\x02\x0btakes-\
 value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01r\x09\x04names\x07summary\
 s\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0bdescriptions\x08examples\
@@ -5489,26 +8563,37 @@ s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\0\x0dcommand-error\
 summarys\x0binput-types\x05\x0boutput-types\x07versions\x04\0\x09pipe-meta\x03\0\
 \x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\x01s\0\x0einvalid-option\x01\
 s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\x07summarys\x04paths\x04\0\x09\
-pipe-info\x03\0\x11\x03\0\x15wacli:cli/types@2.0.0\x05\0\x02\x03\0\0\x09pipe-met\
-a\x02\x03\0\0\x0apipe-error\x02\x03\0\0\x09pipe-info\x01B\x16\x02\x03\x02\x01\x01\
-\x04\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x02\x04\0\x0apipe-error\x03\0\x02\x02\
-\x03\x02\x01\x03\x04\0\x09pipe-info\x03\0\x04\x04\0\x04pipe\x03\x01\x01h\x06\x01\
-@\x01\x04self\x07\0\x01\x04\0\x11[method]pipe.meta\x01\x08\x01p}\x01ps\x01j\x01\x09\
-\x01\x03\x01@\x03\x04self\x07\x05input\x09\x07options\x0a\0\x0b\x04\0\x14[method\
-]pipe.process\x01\x0c\x01p\x05\x01@\0\0\x0d\x04\0\x0alist-pipes\x01\x0e\x01i\x06\
-\x01j\x01\x0f\x01s\x01@\x01\x04names\0\x10\x04\0\x09load-pipe\x01\x11\x03\0\x1cw\
-acli:cli/pipe-runtime@2.0.0\x05\x04\x01B\x0a\x01o\x02ss\x01p\0\x01@\0\0\x01\x04\0\
-\x0fget-environment\x01\x02\x01ps\x01@\0\0\x03\x04\0\x0dget-arguments\x01\x04\x01\
-ks\x01@\0\0\x05\x04\0\x0binitial-cwd\x01\x06\x03\0\x1awasi:cli/environment@0.2.9\
-\x05\x05\x01B\x03\x01j\0\0\x01@\x01\x06status\0\x01\0\x04\0\x04exit\x01\x01\x03\0\
-\x13wasi:cli/exit@0.2.9\x05\x06\x01B\x04\x04\0\x05error\x03\x01\x01h\0\x01@\x01\x04\
+pipe-info\x03\0\x11\x01r\x04\x04info\x12\x0binput-types\x05\x0boutput-types\x07v\
+ersions\x04\0\x0cpipe-details\x03\0\x13\x03\0\x15wacli:cli/types@2.0.0\x05\0\x02\
+\x03\0\0\x09pipe-meta\x02\x03\0\0\x0apipe-error\x02\x03\0\0\x09pipe-info\x02\x03\
+\0\0\x0cpipe-details\x01B'\x02\x03\x02\x01\x01\x04\0\x09pipe-meta\x03\0\0\x02\x03\
+\x02\x01\x02\x04\0\x0apipe-error\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x09pipe-inf\
+o\x03\0\x04\x02\x03\x02\x01\x04\x04\0\x0cpipe-details\x03\0\x06\x01q\x03\x09not-\
+found\x01s\0\x0cinvalid-name\x01s\0\x0bload-failed\x01s\0\x04\0\x0fpipe-load-err\
+or\x03\0\x08\x04\0\x04pipe\x03\x01\x01h\x0a\x01@\x01\x04self\x0b\0\x01\x04\0\x11\
+[method]pipe.meta\x01\x0c\x01p}\x01ps\x01j\x01\x0d\x01\x03\x01@\x03\x04self\x0b\x05\
+input\x0d\x07options\x0e\0\x0f\x04\0\x14[method]pipe.process\x01\x10\x01j\x01y\x01\
+\x03\x01@\x02\x04self\x0b\x07options\x0e\0\x11\x04\0\x1a[method]pipe.process-sta\
+rt\x01\x12\x01@\x03\x04self\x0b\x07sessiony\x05bytes\x0d\0\x0f\x04\0\x1a[method]\
+pipe.process-chunk\x01\x13\x01@\x02\x04self\x0b\x07sessiony\0\x0f\x04\0\x18[meth\
+od]pipe.process-end\x01\x14\x01p\x05\x01@\0\0\x15\x04\0\x0alist-pipes\x01\x16\x01\
+p\x07\x01@\0\0\x17\x04\0\x0edescribe-pipes\x01\x18\x01i\x0a\x01j\x01\x19\x01s\x01\
+@\x01\x04names\0\x1a\x04\0\x09load-pipe\x01\x1b\x01j\x01\x19\x01\x09\x01@\x01\x04\
+names\0\x1c\x04\0\x09open-pipe\x01\x1d\x03\0\x1cwacli:cli/pipe-runtime@2.0.0\x05\
+\x05\x01B\x04\x01ks\x01j\x01\0\x01s\x01@\0\0\x01\x04\0\x10read-hidden-line\x01\x02\
+\x03\0\x20wacli:cli/terminal-runtime@2.0.0\x05\x06\x01B\x0a\x01o\x02ss\x01p\0\x01\
+@\0\0\x01\x04\0\x0fget-environment\x01\x02\x01ps\x01@\0\0\x03\x04\0\x0dget-argum\
+ents\x01\x04\x01ks\x01@\0\0\x05\x04\0\x0binitial-cwd\x01\x06\x03\0\x1awasi:cli/e\
+nvironment@0.2.9\x05\x07\x01B\x05\x01j\0\0\x01@\x01\x06status\0\x01\0\x04\0\x04e\
+xit\x01\x01\x01@\x01\x0bstatus-code}\x01\0\x04\0\x0eexit-with-code\x01\x02\x03\0\
+\x13wasi:cli/exit@0.2.9\x05\x08\x01B\x04\x04\0\x05error\x03\x01\x01h\0\x01@\x01\x04\
 self\x01\0s\x04\0\x1d[method]error.to-debug-string\x01\x02\x03\0\x13wasi:io/erro\
-r@0.2.9\x05\x07\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04self\x01\0\
+r@0.2.9\x05\x09\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04self\x01\0\
 \x7f\x04\0\x16[method]pollable.ready\x01\x02\x01@\x01\x04self\x01\x01\0\x04\0\x16\
 [method]pollable.block\x01\x03\x01p\x01\x01py\x01@\x01\x02in\x04\0\x05\x04\0\x04\
-poll\x01\x06\x03\0\x12wasi:io/poll@0.2.9\x05\x08\x02\x03\0\x04\x05error\x02\x03\0\
-\x05\x08pollable\x01B(\x02\x03\x02\x01\x09\x04\0\x05error\x03\0\0\x02\x03\x02\x01\
-\x0a\x04\0\x08pollable\x03\0\x02\x01i\x01\x01q\x02\x15last-operation-failed\x01\x04\
+poll\x01\x06\x03\0\x12wasi:io/poll@0.2.9\x05\x0a\x02\x03\0\x05\x05error\x02\x03\0\
+\x06\x08pollable\x01B(\x02\x03\x02\x01\x0b\x04\0\x05error\x03\0\0\x02\x03\x02\x01\
+\x0c\x04\0\x08pollable\x03\0\x02\x01i\x01\x01q\x02\x15last-operation-failed\x01\x04\
 \0\x06closed\0\0\x04\0\x0cstream-error\x03\0\x05\x04\0\x0cinput-stream\x03\x01\x04\
 \0\x0doutput-stream\x03\x01\x01h\x07\x01p}\x01j\x01\x0a\x01\x06\x01@\x02\x04self\
 \x09\x03lenw\0\x0b\x04\0\x19[method]input-stream.read\x01\x0c\x04\0\"[method]inp\
@@ -5524,112 +8609,166 @@ self\x11\0\x0f\x04\0\x1f[method]output-stream.subscribe\x01\x16\x01@\x02\x04self
 \x11\x03lenw\0\x13\x04\0\"[method]output-stream.write-zeroes\x01\x17\x04\05[meth\
 od]output-stream.blocking-write-zeroes-and-flush\x01\x17\x01@\x03\x04self\x11\x03\
 src\x09\x03lenw\0\x0d\x04\0\x1c[method]output-stream.splice\x01\x18\x04\0%[metho\
-d]output-stream.blocking-splice\x01\x18\x03\0\x15wasi:io/streams@0.2.9\x05\x0b\x02\
-\x03\0\x06\x0doutput-stream\x01B\x05\x02\x03\x02\x01\x0c\x04\0\x0doutput-stream\x03\
+d]output-stream.blocking-splice\x01\x18\x03\0\x15wasi:io/streams@0.2.9\x05\x0d\x02\
+\x03\0\x07\x0doutput-stream\x01B\x05\x02\x03\x02\x01\x0e\x04\0\x0doutput-stream\x03\
 \0\0\x01i\x01\x01@\0\0\x02\x04\0\x0aget-stdout\x01\x03\x03\0\x15wasi:cli/stdout@\
-0.2.9\x05\x0d\x01B\x05\x02\x03\x02\x01\x0c\x04\0\x0doutput-stream\x03\0\0\x01i\x01\
-\x01@\0\0\x02\x04\0\x0aget-stderr\x01\x03\x03\0\x15wasi:cli/stderr@0.2.9\x05\x0e\
-\x01B\x05\x01r\x02\x07secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\x01@\0\0\
-\x01\x04\0\x03now\x01\x02\x04\0\x0aresolution\x01\x02\x03\0\x1cwasi:clocks/wall-\
-clock@0.2.9\x05\x0f\x02\x03\0\x06\x0cinput-stream\x02\x03\0\x06\x05error\x02\x03\
-\0\x09\x08datetime\x01Br\x02\x03\x02\x01\x10\x04\0\x0cinput-stream\x03\0\0\x02\x03\
-\x02\x01\x0c\x04\0\x0doutput-stream\x03\0\x02\x02\x03\x02\x01\x11\x04\0\x05error\
-\x03\0\x04\x02\x03\x02\x01\x12\x04\0\x08datetime\x03\0\x06\x01w\x04\0\x08filesiz\
-e\x03\0\x08\x01m\x08\x07unknown\x0cblock-device\x10character-device\x09directory\
-\x04fifo\x0dsymbolic-link\x0cregular-file\x06socket\x04\0\x0fdescriptor-type\x03\
-\0\x0a\x01n\x06\x04read\x05write\x13file-integrity-sync\x13data-integrity-sync\x14\
-requested-write-sync\x10mutate-directory\x04\0\x10descriptor-flags\x03\0\x0c\x01\
-n\x01\x0esymlink-follow\x04\0\x0apath-flags\x03\0\x0e\x01n\x04\x06create\x09dire\
-ctory\x09exclusive\x08truncate\x04\0\x0aopen-flags\x03\0\x10\x01w\x04\0\x0alink-\
-count\x03\0\x12\x01k\x07\x01r\x06\x04type\x0b\x0alink-count\x13\x04size\x09\x15d\
-ata-access-timestamp\x14\x1bdata-modification-timestamp\x14\x17status-change-tim\
-estamp\x14\x04\0\x0fdescriptor-stat\x03\0\x15\x01q\x03\x09no-change\0\0\x03now\0\
-\0\x09timestamp\x01\x07\0\x04\0\x0dnew-timestamp\x03\0\x17\x01r\x02\x04type\x0b\x04\
-names\x04\0\x0fdirectory-entry\x03\0\x19\x01m%\x06access\x0bwould-block\x07alrea\
-dy\x0ebad-descriptor\x04busy\x08deadlock\x05quota\x05exist\x0efile-too-large\x15\
-illegal-byte-sequence\x0bin-progress\x0binterrupted\x07invalid\x02io\x0cis-direc\
-tory\x04loop\x0etoo-many-links\x0cmessage-size\x0dname-too-long\x09no-device\x08\
-no-entry\x07no-lock\x13insufficient-memory\x12insufficient-space\x0dnot-director\
-y\x09not-empty\x0fnot-recoverable\x0bunsupported\x06no-tty\x0eno-such-device\x08\
-overflow\x0dnot-permitted\x04pipe\x09read-only\x0cinvalid-seek\x0etext-file-busy\
-\x0ccross-device\x04\0\x0aerror-code\x03\0\x1b\x01m\x06\x06normal\x0asequential\x06\
-random\x09will-need\x09dont-need\x08no-reuse\x04\0\x06advice\x03\0\x1d\x01r\x02\x05\
-lowerw\x05upperw\x04\0\x13metadata-hash-value\x03\0\x1f\x04\0\x0adescriptor\x03\x01\
-\x04\0\x16directory-entry-stream\x03\x01\x01h!\x01i\x01\x01j\x01$\x01\x1c\x01@\x02\
-\x04self#\x06offset\x09\0%\x04\0\"[method]descriptor.read-via-stream\x01&\x01i\x03\
-\x01j\x01'\x01\x1c\x01@\x02\x04self#\x06offset\x09\0(\x04\0#[method]descriptor.w\
-rite-via-stream\x01)\x01@\x01\x04self#\0(\x04\0$[method]descriptor.append-via-st\
-ream\x01*\x01j\0\x01\x1c\x01@\x04\x04self#\x06offset\x09\x06length\x09\x06advice\
-\x1e\0+\x04\0\x19[method]descriptor.advise\x01,\x01@\x01\x04self#\0+\x04\0\x1c[m\
-ethod]descriptor.sync-data\x01-\x01j\x01\x0d\x01\x1c\x01@\x01\x04self#\0.\x04\0\x1c\
-[method]descriptor.get-flags\x01/\x01j\x01\x0b\x01\x1c\x01@\x01\x04self#\00\x04\0\
-\x1b[method]descriptor.get-type\x011\x01@\x02\x04self#\x04size\x09\0+\x04\0\x1b[\
-method]descriptor.set-size\x012\x01@\x03\x04self#\x15data-access-timestamp\x18\x1b\
-data-modification-timestamp\x18\0+\x04\0\x1c[method]descriptor.set-times\x013\x01\
-p}\x01o\x024\x7f\x01j\x015\x01\x1c\x01@\x03\x04self#\x06length\x09\x06offset\x09\
-\06\x04\0\x17[method]descriptor.read\x017\x01j\x01\x09\x01\x1c\x01@\x03\x04self#\
-\x06buffer4\x06offset\x09\08\x04\0\x18[method]descriptor.write\x019\x01i\"\x01j\x01\
-:\x01\x1c\x01@\x01\x04self#\0;\x04\0![method]descriptor.read-directory\x01<\x04\0\
-\x17[method]descriptor.sync\x01-\x01@\x02\x04self#\x04paths\0+\x04\0&[method]des\
-criptor.create-directory-at\x01=\x01j\x01\x16\x01\x1c\x01@\x01\x04self#\0>\x04\0\
-\x17[method]descriptor.stat\x01?\x01@\x03\x04self#\x0apath-flags\x0f\x04paths\0>\
-\x04\0\x1a[method]descriptor.stat-at\x01@\x01@\x05\x04self#\x0apath-flags\x0f\x04\
-paths\x15data-access-timestamp\x18\x1bdata-modification-timestamp\x18\0+\x04\0\x1f\
-[method]descriptor.set-times-at\x01A\x01@\x05\x04self#\x0eold-path-flags\x0f\x08\
-old-paths\x0enew-descriptor#\x08new-paths\0+\x04\0\x1a[method]descriptor.link-at\
-\x01B\x01i!\x01j\x01\xc3\0\x01\x1c\x01@\x05\x04self#\x0apath-flags\x0f\x04paths\x0a\
-open-flags\x11\x05flags\x0d\0\xc4\0\x04\0\x1a[method]descriptor.open-at\x01E\x01\
-j\x01s\x01\x1c\x01@\x02\x04self#\x04paths\0\xc6\0\x04\0\x1e[method]descriptor.re\
-adlink-at\x01G\x04\0&[method]descriptor.remove-directory-at\x01=\x01@\x04\x04sel\
-f#\x08old-paths\x0enew-descriptor#\x08new-paths\0+\x04\0\x1c[method]descriptor.r\
-ename-at\x01H\x01@\x03\x04self#\x08old-paths\x08new-paths\0+\x04\0\x1d[method]de\
-scriptor.symlink-at\x01I\x04\0![method]descriptor.unlink-file-at\x01=\x01@\x02\x04\
-self#\x05other#\0\x7f\x04\0![method]descriptor.is-same-object\x01J\x01j\x01\x20\x01\
-\x1c\x01@\x01\x04self#\0\xcb\0\x04\0\x20[method]descriptor.metadata-hash\x01L\x01\
-@\x03\x04self#\x0apath-flags\x0f\x04paths\0\xcb\0\x04\0#[method]descriptor.metad\
-ata-hash-at\x01M\x01h\"\x01k\x1a\x01j\x01\xcf\0\x01\x1c\x01@\x01\x04self\xce\0\0\
-\xd0\0\x04\03[method]directory-entry-stream.read-directory-entry\x01Q\x01h\x05\x01\
-k\x1c\x01@\x01\x03err\xd2\0\0\xd3\0\x04\0\x15filesystem-error-code\x01T\x03\0\x1b\
-wasi:filesystem/types@0.2.9\x05\x13\x02\x03\0\x0a\x0adescriptor\x01B\x07\x02\x03\
-\x02\x01\x14\x04\0\x0adescriptor\x03\0\0\x01i\x01\x01o\x02\x02s\x01p\x03\x01@\0\0\
-\x04\x04\0\x0fget-directories\x01\x05\x03\0\x1ewasi:filesystem/preopens@0.2.9\x05\
-\x15\x01B\x13\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\x04names\x05short\x02\
-\x04long\x02\x04helps\x08required\x7f\x0ddefault-value\x02\x0avalue-name\x02\x0b\
-takes-value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01r\x09\x04names\x07s\
-ummarys\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0bdescriptions\x08ex\
-amples\x05\x04args\x06\x04\0\x0ccommand-meta\x03\0\x07\x01q\x04\x0funknown-comma\
-nd\x01s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\0\x0dcommand-\
-error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\x0ecommand-result\x03\0\x0b\x01r\x05\x04\
-names\x07summarys\x0binput-types\x05\x0boutput-types\x07versions\x04\0\x09pipe-m\
-eta\x03\0\x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\x01s\0\x0einvali\
-d-option\x01s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\x07summarys\x04p\
-aths\x04\0\x09pipe-info\x03\0\x11\x04\0\x15wacli:cli/types@2.0.0\x05\x16\x01B\x07\
-\x01ks\x01ps\x01r\x0f\x04names\x05short\0\x04long\0\x04helps\x08required\x7f\x0d\
-default-value\0\x03env\0\x0avalue-name\0\x0btakes-value\x7f\x08multiple\x7f\x0av\
-alue-type\0\x0fpossible-values\x01\x0econflicts-with\x01\x08requires\x01\x06hidd\
-en\x7f\x04\0\x0aarg-schema\x03\0\x02\x01p\x03\x01r\x09\x04names\x07summarys\x05u\
-sages\x07aliases\x01\x07versions\x06hidden\x7f\x0bdescriptions\x08examples\x01\x04\
-args\x04\x04\0\x0ecommand-schema\x03\0\x05\x04\0\x16wacli:cli/schema@2.0.0\x05\x17\
-\x01B\x07\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\0\x03\
-\x04\0\x03env\x01\x04\x04\0\x18wacli:cli/host-env@2.0.0\x05\x18\x01B\x07\x01p}\x01\
-@\x01\x05bytes\0\x01\0\x04\0\x0cstdout-write\x01\x01\x04\0\x0cstderr-write\x01\x01\
-\x01@\0\x01\0\x04\0\x0cstdout-flush\x01\x02\x04\0\x0cstderr-flush\x01\x02\x04\0\x17\
-wacli:cli/host-io@2.0.0\x05\x19\x01B\x0d\x01p}\x01j\x01\0\x01s\x01@\x01\x04paths\
-\0\x01\x04\0\x09read-file\x01\x02\x01j\0\x01s\x01@\x02\x04paths\x08contents\0\0\x03\
-\x04\0\x0awrite-file\x01\x04\x01@\x01\x04paths\0\x03\x04\0\x0acreate-dir\x01\x05\
-\x01ps\x01j\x01\x06\x01s\x01@\x01\x04paths\0\x07\x04\0\x08list-dir\x01\x08\x04\0\
-\x17wacli:cli/host-fs@2.0.0\x05\x1a\x02\x03\0\x0c\x09exit-code\x01B\x04\x02\x03\x02\
-\x01\x1b\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\
-\x02\x04\0\x1cwacli:cli/host-process@2.0.0\x05\x1c\x01B\x16\x02\x03\x02\x01\x01\x04\
-\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x02\x04\0\x0apipe-error\x03\0\x02\x02\x03\
-\x02\x01\x03\x04\0\x09pipe-info\x03\0\x04\x04\0\x04pipe\x03\x01\x01h\x06\x01@\x01\
-\x04self\x07\0\x01\x04\0\x11[method]pipe.meta\x01\x08\x01p}\x01ps\x01j\x01\x09\x01\
-\x03\x01@\x03\x04self\x07\x05input\x09\x07options\x0a\0\x0b\x04\0\x14[method]pip\
-e.process\x01\x0c\x01p\x05\x01@\0\0\x0d\x04\0\x0alist-pipes\x01\x0e\x01i\x06\x01\
-j\x01\x0f\x01s\x01@\x01\x04names\0\x10\x04\0\x09load-pipe\x01\x11\x04\0\x1awacli\
-:cli/host-pipes@2.0.0\x05\x1d\x04\0\x1dwacli:cli/host-provider@2.0.0\x04\0\x0b\x13\
-\x01\0\x0dhost-provider\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-c\
-omponent\x070.244.0\x10wit-bindgen-rust\x060.52.0";
+0.2.9\x05\x0f\x01B\x05\x02\x03\x02\x01\x0e\x04\0\x0doutput-stream\x03\0\0\x01i\x01\
+\x01@\0\0\x02\x04\0\x0aget-stderr\x01\x03\x03\0\x15wasi:cli/stderr@0.2.9\x05\x10\
+\x02\x03\0\x07\x0cinput-stream\x01B\x05\x02\x03\x02\x01\x11\x04\0\x0cinput-strea\
+m\x03\0\0\x01i\x01\x01@\0\0\x02\x04\0\x09get-stdin\x01\x03\x03\0\x14wasi:cli/std\
+in@0.2.9\x05\x12\x01B\x01\x04\0\x0eterminal-input\x03\x01\x03\0\x1dwasi:cli/term\
+inal-input@0.2.9\x05\x13\x02\x03\0\x0b\x0eterminal-input\x01B\x06\x02\x03\x02\x01\
+\x14\x04\0\x0eterminal-input\x03\0\0\x01i\x01\x01k\x02\x01@\0\0\x03\x04\0\x12get\
+-terminal-stdin\x01\x04\x03\0\x1dwasi:cli/terminal-stdin@0.2.9\x05\x15\x01B\x01\x04\
+\0\x0fterminal-output\x03\x01\x03\0\x1ewasi:cli/terminal-output@0.2.9\x05\x16\x02\
+\x03\0\x0d\x0fterminal-output\x01B\x06\x02\x03\x02\x01\x17\x04\0\x0fterminal-out\
+put\x03\0\0\x01i\x01\x01k\x02\x01@\0\0\x03\x04\0\x13get-terminal-stdout\x01\x04\x03\
+\0\x1ewasi:cli/terminal-stdout@0.2.9\x05\x18\x01B\x06\x02\x03\x02\x01\x17\x04\0\x0f\
+terminal-output\x03\0\0\x01i\x01\x01k\x02\x01@\0\0\x03\x04\0\x13get-terminal-std\
+err\x01\x04\x03\0\x1ewasi:cli/terminal-stderr@0.2.9\x05\x19\x01B\x05\x01r\x02\x07\
+secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\x01@\0\0\x01\x04\0\x03now\x01\
+\x02\x04\0\x0aresolution\x01\x02\x03\0\x1cwasi:clocks/wall-clock@0.2.9\x05\x1a\x02\
+\x03\0\x07\x05error\x02\x03\0\x10\x08datetime\x01Br\x02\x03\x02\x01\x11\x04\0\x0c\
+input-stream\x03\0\0\x02\x03\x02\x01\x0e\x04\0\x0doutput-stream\x03\0\x02\x02\x03\
+\x02\x01\x1b\x04\0\x05error\x03\0\x04\x02\x03\x02\x01\x1c\x04\0\x08datetime\x03\0\
+\x06\x01w\x04\0\x08filesize\x03\0\x08\x01m\x08\x07unknown\x0cblock-device\x10cha\
+racter-device\x09directory\x04fifo\x0dsymbolic-link\x0cregular-file\x06socket\x04\
+\0\x0fdescriptor-type\x03\0\x0a\x01n\x06\x04read\x05write\x13file-integrity-sync\
+\x13data-integrity-sync\x14requested-write-sync\x10mutate-directory\x04\0\x10des\
+criptor-flags\x03\0\x0c\x01n\x01\x0esymlink-follow\x04\0\x0apath-flags\x03\0\x0e\
+\x01n\x04\x06create\x09directory\x09exclusive\x08truncate\x04\0\x0aopen-flags\x03\
+\0\x10\x01w\x04\0\x0alink-count\x03\0\x12\x01k\x07\x01r\x06\x04type\x0b\x0alink-\
+count\x13\x04size\x09\x15data-access-timestamp\x14\x1bdata-modification-timestam\
+p\x14\x17status-change-timestamp\x14\x04\0\x0fdescriptor-stat\x03\0\x15\x01q\x03\
+\x09no-change\0\0\x03now\0\0\x09timestamp\x01\x07\0\x04\0\x0dnew-timestamp\x03\0\
+\x17\x01r\x02\x04type\x0b\x04names\x04\0\x0fdirectory-entry\x03\0\x19\x01m%\x06a\
+ccess\x0bwould-block\x07already\x0ebad-descriptor\x04busy\x08deadlock\x05quota\x05\
+exist\x0efile-too-large\x15illegal-byte-sequence\x0bin-progress\x0binterrupted\x07\
+invalid\x02io\x0cis-directory\x04loop\x0etoo-many-links\x0cmessage-size\x0dname-\
+too-long\x09no-device\x08no-entry\x07no-lock\x13insufficient-memory\x12insuffici\
+ent-space\x0dnot-directory\x09not-empty\x0fnot-recoverable\x0bunsupported\x06no-\
+tty\x0eno-such-device\x08overflow\x0dnot-permitted\x04pipe\x09read-only\x0cinval\
+id-seek\x0etext-file-busy\x0ccross-device\x04\0\x0aerror-code\x03\0\x1b\x01m\x06\
+\x06normal\x0asequential\x06random\x09will-need\x09dont-need\x08no-reuse\x04\0\x06\
+advice\x03\0\x1d\x01r\x02\x05lowerw\x05upperw\x04\0\x13metadata-hash-value\x03\0\
+\x1f\x04\0\x0adescriptor\x03\x01\x04\0\x16directory-entry-stream\x03\x01\x01h!\x01\
+i\x01\x01j\x01$\x01\x1c\x01@\x02\x04self#\x06offset\x09\0%\x04\0\"[method]descri\
+ptor.read-via-stream\x01&\x01i\x03\x01j\x01'\x01\x1c\x01@\x02\x04self#\x06offset\
+\x09\0(\x04\0#[method]descriptor.write-via-stream\x01)\x01@\x01\x04self#\0(\x04\0\
+$[method]descriptor.append-via-stream\x01*\x01j\0\x01\x1c\x01@\x04\x04self#\x06o\
+ffset\x09\x06length\x09\x06advice\x1e\0+\x04\0\x19[method]descriptor.advise\x01,\
+\x01@\x01\x04self#\0+\x04\0\x1c[method]descriptor.sync-data\x01-\x01j\x01\x0d\x01\
+\x1c\x01@\x01\x04self#\0.\x04\0\x1c[method]descriptor.get-flags\x01/\x01j\x01\x0b\
+\x01\x1c\x01@\x01\x04self#\00\x04\0\x1b[method]descriptor.get-type\x011\x01@\x02\
+\x04self#\x04size\x09\0+\x04\0\x1b[method]descriptor.set-size\x012\x01@\x03\x04s\
+elf#\x15data-access-timestamp\x18\x1bdata-modification-timestamp\x18\0+\x04\0\x1c\
+[method]descriptor.set-times\x013\x01p}\x01o\x024\x7f\x01j\x015\x01\x1c\x01@\x03\
+\x04self#\x06length\x09\x06offset\x09\06\x04\0\x17[method]descriptor.read\x017\x01\
+j\x01\x09\x01\x1c\x01@\x03\x04self#\x06buffer4\x06offset\x09\08\x04\0\x18[method\
+]descriptor.write\x019\x01i\"\x01j\x01:\x01\x1c\x01@\x01\x04self#\0;\x04\0![meth\
+od]descriptor.read-directory\x01<\x04\0\x17[method]descriptor.sync\x01-\x01@\x02\
+\x04self#\x04paths\0+\x04\0&[method]descriptor.create-directory-at\x01=\x01j\x01\
+\x16\x01\x1c\x01@\x01\x04self#\0>\x04\0\x17[method]descriptor.stat\x01?\x01@\x03\
+\x04self#\x0apath-flags\x0f\x04paths\0>\x04\0\x1a[method]descriptor.stat-at\x01@\
+\x01@\x05\x04self#\x0apath-flags\x0f\x04paths\x15data-access-timestamp\x18\x1bda\
+ta-modification-timestamp\x18\0+\x04\0\x1f[method]descriptor.set-times-at\x01A\x01\
+@\x05\x04self#\x0eold-path-flags\x0f\x08old-paths\x0enew-descriptor#\x08new-path\
+s\0+\x04\0\x1a[method]descriptor.link-at\x01B\x01i!\x01j\x01\xc3\0\x01\x1c\x01@\x05\
+\x04self#\x0apath-flags\x0f\x04paths\x0aopen-flags\x11\x05flags\x0d\0\xc4\0\x04\0\
+\x1a[method]descriptor.open-at\x01E\x01j\x01s\x01\x1c\x01@\x02\x04self#\x04paths\
+\0\xc6\0\x04\0\x1e[method]descriptor.readlink-at\x01G\x04\0&[method]descriptor.r\
+emove-directory-at\x01=\x01@\x04\x04self#\x08old-paths\x0enew-descriptor#\x08new\
+-paths\0+\x04\0\x1c[method]descriptor.rename-at\x01H\x01@\x03\x04self#\x08old-pa\
+ths\x08new-paths\0+\x04\0\x1d[method]descriptor.symlink-at\x01I\x04\0![method]de\
+scriptor.unlink-file-at\x01=\x01@\x02\x04self#\x05other#\0\x7f\x04\0![method]des\
+criptor.is-same-object\x01J\x01j\x01\x20\x01\x1c\x01@\x01\x04self#\0\xcb\0\x04\0\
+\x20[method]descriptor.metadata-hash\x01L\x01@\x03\x04self#\x0apath-flags\x0f\x04\
+paths\0\xcb\0\x04\0#[method]descriptor.metadata-hash-at\x01M\x01h\"\x01k\x1a\x01\
+j\x01\xcf\0\x01\x1c\x01@\x01\x04self\xce\0\0\xd0\0\x04\03[method]directory-entry\
+-stream.read-directory-entry\x01Q\x01h\x05\x01k\x1c\x01@\x01\x03err\xd2\0\0\xd3\0\
+\x04\0\x15filesystem-error-code\x01T\x03\0\x1bwasi:filesystem/types@0.2.9\x05\x1d\
+\x02\x03\0\x11\x0adescriptor\x01B\x07\x02\x03\x02\x01\x1e\x04\0\x0adescriptor\x03\
+\0\0\x01i\x01\x01o\x02\x02s\x01p\x03\x01@\0\0\x04\x04\0\x0fget-directories\x01\x05\
+\x03\0\x1ewasi:filesystem/preopens@0.2.9\x05\x1f\x01B\x0f\x02\x03\x02\x01\x0c\x04\
+\0\x08pollable\x03\0\0\x01w\x04\0\x07instant\x03\0\x02\x01w\x04\0\x08duration\x03\
+\0\x04\x01@\0\0\x03\x04\0\x03now\x01\x06\x01@\0\0\x05\x04\0\x0aresolution\x01\x07\
+\x01i\x01\x01@\x01\x04when\x03\0\x08\x04\0\x11subscribe-instant\x01\x09\x01@\x01\
+\x04when\x05\0\x08\x04\0\x12subscribe-duration\x01\x0a\x03\0!wasi:clocks/monoton\
+ic-clock@0.2.9\x05\x20\x01B\x15\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\x04\
+names\x05short\x02\x04long\x02\x04helps\x08required\x7f\x0ddefault-value\x02\x0a\
+value-name\x02\x0btakes-value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01r\
+\x09\x04names\x07summarys\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0b\
+descriptions\x08examples\x05\x04args\x06\x04\0\x0ccommand-meta\x03\0\x07\x01q\x04\
+\x0funknown-command\x01s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\
+\0\x0dcommand-error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\x0ecommand-result\x03\0\
+\x0b\x01r\x05\x04names\x07summarys\x0binput-types\x05\x0boutput-types\x07version\
+s\x04\0\x09pipe-meta\x03\0\x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\
+\x01s\0\x0einvalid-option\x01s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\
+\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x01r\x04\x04info\x12\x0binput\
+-types\x05\x0boutput-types\x07versions\x04\0\x0cpipe-details\x03\0\x13\x04\0\x15\
+wacli:cli/types@2.0.0\x05!\x01B\x0d\x01ks\x01ps\x01r\x14\x04names\x05short\0\x04\
+long\0\x04helps\x08required\x7f\x0ddefault-value\0\x03env\0\x0avalue-name\0\x0bt\
+akes-value\x7f\x08multiple\x7f\x0avalue-type\0\x0fpossible-values\x01\x0econflic\
+ts-with\x01\x08requires\x01\x06hidden\x7f\x10complete-command\0\x05group\0\x13al\
+low-hyphen-values\x7f\x09countable\x7f\x07var-arg\x7f\x04\0\x0aarg-schema\x03\0\x02\
+\x01r\x03\x04names\x08required\x7f\x08multiple\x7f\x04\0\x09arg-group\x03\0\x04\x01\
+p\x03\x01p\x05\x01r\x0b\x04path\x01\x07summarys\x05usages\x07aliases\x01\x07vers\
+ions\x06hidden\x7f\x0bdescriptions\x08examples\x01\x04args\x06\x06groups\x07\x0e\
+hidden-aliases\x01\x04\0\x11subcommand-schema\x03\0\x08\x01p\x09\x01r\x0d\x04nam\
+es\x07summarys\x05usages\x07aliases\x01\x07versions\x06hidden\x7f\x0bdescription\
+s\x08examples\x01\x04args\x06\x0fallow-arg-files\x7f\x0bsubcommands\x0a\x06group\
+s\x07\x0ehidden-aliases\x01\x04\0\x0ecommand-schema\x03\0\x0b\x04\0\x16wacli:cli\
+/schema@2.0.0\x05\"\x01B\x0e\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01\
+p\x02\x01@\0\0\x03\x04\0\x03env\x01\x04\x01ks\x01@\x01\x03keys\0\x05\x04\0\x03ge\
+t\x01\x06\x01@\0\0s\x04\0\x03cwd\x01\x07\x01@\0\0w\x04\0\x0dmonotonic-now\x01\x08\
+\x04\0\x18wacli:cli/host-env@2.0.0\x05#\x01B\x17\x01p}\x01@\x01\x05bytes\0\x01\0\
+\x04\0\x0cstdout-write\x01\x01\x04\0\x0cstderr-write\x01\x01\x01@\0\x01\0\x04\0\x0c\
+stdout-flush\x01\x02\x04\0\x0cstderr-flush\x01\x02\x01@\0\0\x7f\x04\0\x12stdout-\
+is-terminal\x01\x03\x04\0\x12stderr-is-terminal\x01\x03\x04\0\x11stdin-is-termin\
+al\x01\x03\x01ks\x01j\x01\x04\x01s\x01@\0\0\x05\x04\0\x0fstdin-read-line\x01\x06\
+\x01j\x01s\x01s\x01@\x01\x06prompts\0\x07\x04\0\x0bread-secret\x01\x08\x01j\x01\0\
+\x01s\x01@\x01\x03lenw\0\x09\x04\0\x0astdin-read\x01\x0a\x01@\0\0\x09\x04\0\x0es\
+tdin-read-all\x01\x0b\x04\0\x17wacli:cli/host-io@2.0.0\x05$\x01B\x06\x01m\x05\x05\
+trace\x05debug\x04info\x04warn\x05error\x04\0\x05level\x03\0\0\x01@\x03\x05level\
+\x01\x06targets\x07messages\x01\0\x04\0\x03log\x01\x02\x01@\x01\x05level\x01\0\x7f\
+\x04\0\x07enabled\x01\x03\x04\0\x18wacli:cli/host-log@2.0.0\x05%\x02\x03\0\x18\x05\
+level\x01B\x08\x02\x03\x02\x01&\x04\0\x05level\x03\0\0\x01ks\x01@\x02\x03keys\x05\
+value\x02\x01\0\x04\0\x07set-env\x01\x03\x01k\x01\x01@\x01\x05level\x04\x01\0\x04\
+\0\x11set-max-log-level\x01\x05\x04\0\x1cwacli:cli/host-control@2.0.0\x05'\x01B0\
+\x01m\x04\x04file\x03dir\x07symlink\x05other\x04\0\x09file-kind\x03\0\0\x01r\x03\
+\x04kind\x01\x04sizew\x0bmodified-atw\x04\0\x09file-info\x03\0\x02\x01r\x02\x04p\
+aths\x04kind\x01\x04\0\x09dir-entry\x03\0\x04\x04\0\x06writer\x03\x01\x01h\x06\x01\
+p}\x01j\0\x01s\x01@\x02\x04self\x07\x05bytes\x08\0\x09\x04\0\x14[method]writer.w\
+rite\x01\x0a\x01@\x01\x04self\x07\0\x09\x04\0\x14[method]writer.close\x01\x0b\x01\
+j\x01\x08\x01s\x01@\x01\x04paths\0\x0c\x04\0\x09read-file\x01\x0d\x01@\x02\x04pa\
+ths\x08contents\x08\0\x09\x04\0\x0awrite-file\x01\x0e\x01@\x01\x04paths\0\x09\x04\
+\0\x0acreate-dir\x01\x0f\x01ps\x01j\x01\x10\x01s\x01@\x01\x04paths\0\x11\x04\0\x08\
+list-dir\x01\x12\x01j\x01w\x01s\x01@\x02\x03srcs\x03dsts\0\x13\x04\0\x09copy-fil\
+e\x01\x14\x01@\x02\x03srcs\x03dsts\0\x09\x04\0\x06rename\x01\x15\x04\0\x0bremove\
+-file\x01\x0f\x04\0\x0aremove-dir\x01\x0f\x04\0\x0eremove-dir-all\x01\x0f\x01j\x01\
+\x03\x01s\x01@\x01\x04paths\0\x16\x04\0\x04stat\x01\x17\x01@\x01\x04paths\0\x7f\x04\
+\0\x06exists\x01\x18\x01ky\x01p\x05\x01j\x01\x1a\x01s\x01@\x02\x04paths\x09max-d\
+epth\x19\0\x1b\x04\0\x08walk-dir\x01\x1c\x04\0\x0bappend-file\x01\x0e\x01i\x06\x01\
+j\x01\x1d\x01s\x01@\x02\x04paths\x06append\x7f\0\x1e\x04\0\x0bopen-writer\x01\x1f\
+\x04\0\x17wacli:cli/host-fs@2.0.0\x05(\x02\x03\0\x14\x09exit-code\x01B\x04\x02\x03\
+\x02\x01)\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\
+\x02\x04\0\x1cwacli:cli/host-process@2.0.0\x05*\x01B'\x02\x03\x02\x01\x01\x04\0\x09\
+pipe-meta\x03\0\0\x02\x03\x02\x01\x02\x04\0\x0apipe-error\x03\0\x02\x02\x03\x02\x01\
+\x03\x04\0\x09pipe-info\x03\0\x04\x02\x03\x02\x01\x04\x04\0\x0cpipe-details\x03\0\
+\x06\x01q\x03\x09not-found\x01s\0\x0cinvalid-name\x01s\0\x0bload-failed\x01s\0\x04\
+\0\x0fpipe-load-error\x03\0\x08\x04\0\x04pipe\x03\x01\x01h\x0a\x01@\x01\x04self\x0b\
+\0\x01\x04\0\x11[method]pipe.meta\x01\x0c\x01p}\x01ps\x01j\x01\x0d\x01\x03\x01@\x03\
+\x04self\x0b\x05input\x0d\x07options\x0e\0\x0f\x04\0\x14[method]pipe.process\x01\
+\x10\x01j\x01y\x01\x03\x01@\x02\x04self\x0b\x07options\x0e\0\x11\x04\0\x1a[metho\
+d]pipe.process-start\x01\x12\x01@\x03\x04self\x0b\x07sessiony\x05bytes\x0d\0\x0f\
+\x04\0\x1a[method]pipe.process-chunk\x01\x13\x01@\x02\x04self\x0b\x07sessiony\0\x0f\
+\x04\0\x18[method]pipe.process-end\x01\x14\x01p\x05\x01@\0\0\x15\x04\0\x0alist-p\
+ipes\x01\x16\x01p\x07\x01@\0\0\x17\x04\0\x0edescribe-pipes\x01\x18\x01i\x0a\x01j\
+\x01\x19\x01s\x01@\x01\x04names\0\x1a\x04\0\x09load-pipe\x01\x1b\x01j\x01\x19\x01\
+\x09\x01@\x01\x04names\0\x1c\x04\0\x09open-pipe\x01\x1d\x04\0\x1awacli:cli/host-\
+pipes@2.0.0\x05+\x04\0\x1dwacli:cli/host-provider@2.0.0\x04\0\x0b\x13\x01\0\x0dh\
+ost-provider\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x07\
+0.244.0\x10wit-bindgen-rust\x060.52.0";
 
 #[inline(never)]
 #[doc(hidden)]
@@ -3,23 +3,94 @@
 mod bindings;
 
 use bindings::export;
-use bindings::exports::wacli::cli::{host_env, host_fs, host_io, host_pipes, host_process};
-use bindings::wacli::cli::pipe_runtime;
+use bindings::exports::wacli::cli::{
+    host_control, host_env, host_fs, host_io, host_log, host_pipes, host_process,
+};
+use bindings::wacli::cli::{pipe_runtime, terminal_runtime};
 use bindings::wasi;
-use wasi::filesystem::types::{Descriptor, DescriptorFlags, ErrorCode, OpenFlags, PathFlags};
+use std::cell::{Cell, RefCell};
+use wasi::filesystem::types::{
+    Descriptor, DescriptorFlags, DescriptorType, ErrorCode, OpenFlags, PathFlags,
+};
+use wasi::io::streams::{OutputStream, StreamError};
 
 struct HostProvider;
 struct HostPipe {
     inner: pipe_runtime::Pipe,
 }
 
+/// An open `host-fs` writer; `None` once closed. The stream is declared
+/// first so it is dropped before the file it writes to.
+struct HostWriter {
+    path: String,
+    file: RefCell<Option<(OutputStream, Descriptor)>>,
+}
+
+thread_local! {
+    /// Variables changed through `host-control.set-env`, in the order they
+    /// were set; `None` removes the variable.
+    static ENV_OVERRIDES: RefCell<Vec<(String, Option<String>)>> = RefCell::new(Vec::new());
+
+    /// Least severe `host-log` level written; `None` drops every message.
+    static MAX_LOG_LEVEL: Cell<Option<host_log::Level>> =
+        const { Cell::new(Some(host_log::Level::Warn)) };
+}
+
 impl host_env::Guest for HostProvider {
     fn args() -> Vec<String> {
         wasi::cli::environment::get_arguments()
     }
 
     fn env() -> Vec<(String, String)> {
+        let mut env = wasi::cli::environment::get_environment();
+        ENV_OVERRIDES.with(|overrides| {
+            for (key, value) in overrides.borrow().iter() {
+                env.retain(|(k, _)| k != key);
+                if let Some(value) = value {
+                    env.push((key.clone(), value.clone()));
+                }
+            }
+        });
+        env
+    }
+
+    fn get(key: String) -> Option<String> {
+        let overridden = ENV_OVERRIDES.with(|overrides| {
+            overrides
+                .borrow()
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.clone())
+        });
+        if let Some(value) = overridden {
+            return value;
+        }
         wasi::cli::environment::get_environment()
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    fn cwd() -> String {
+        wasi::cli::environment::initial_cwd().unwrap_or_else(|| ".".to_string())
+    }
+
+    fn monotonic_now() -> u64 {
+        wasi::clocks::monotonic_clock::now()
+    }
+}
+
+impl host_control::Guest for HostProvider {
+    fn set_env(key: String, value: Option<String>) {
+        ENV_OVERRIDES.with(|overrides| {
+            let mut overrides = overrides.borrow_mut();
+            overrides.retain(|(k, _)| *k != key);
+            overrides.push((key, value));
+        });
+    }
+
+    fn set_max_log_level(level: Option<host_log::Level>) {
+        MAX_LOG_LEVEL.with(|max| max.set(level));
     }
 }
 
@@ -39,9 +110,115 @@ impl host_io::Guest for HostProvider {
     fn stderr_flush() {
         flush_output(StreamTarget::Stderr);
     }
+
+    fn stdout_is_terminal() -> bool {
+        // The runtime returns `none` unless the stream really is a terminal
+        // (e.g. wasmtime reports captured pipes and redirected files as none).
+        wasi::cli::terminal_stdout::get_terminal_stdout().is_some()
+    }
+
+    fn stderr_is_terminal() -> bool {
+        wasi::cli::terminal_stderr::get_terminal_stderr().is_some()
+    }
+
+    fn stdin_is_terminal() -> bool {
+        wasi::cli::terminal_stdin::get_terminal_stdin().is_some()
+    }
+
+    fn stdin_read_line() -> Result<Option<String>, String> {
+        // One byte at a time, so nothing past the line is consumed and a
+        // command dispatched afterwards still sees the rest of stdin.
+        let stream = wasi::cli::stdin::get_stdin();
+        let mut line = Vec::new();
+        loop {
+            match stream.blocking_read(1) {
+                Ok(bytes) => match bytes.first() {
+                    Some(b'\n') => break,
+                    Some(&b) => line.push(b),
+                    None => {}
+                },
+                Err(StreamError::Closed) if line.is_empty() => return Ok(None),
+                Err(StreamError::Closed) => break,
+                Err(StreamError::LastOperationFailed(err)) => {
+                    return Err(format!("failed to read stdin: {}", err.to_debug_string()));
+                }
+            }
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+
+    fn read_secret(prompt: String) -> Result<String, String> {
+        write_output(prompt.into_bytes(), StreamTarget::Stderr);
+        flush_output(StreamTarget::Stderr);
+        let line = if Self::stdin_is_terminal() {
+            // WASI cannot turn echo off; `wacli run` reads the line for us.
+            let line = terminal_runtime::read_hidden_line();
+            // The Enter that ended the line was not echoed either.
+            write_output(b"\n".to_vec(), StreamTarget::Stderr);
+            line?
+        } else {
+            Self::stdin_read_line()?
+        };
+        line.ok_or_else(|| "end of input".to_string())
+    }
+
+    fn stdin_read(len: u64) -> Result<Vec<u8>, String> {
+        // A closed stream errors right away, so reads past the end never block.
+        match wasi::cli::stdin::get_stdin().blocking_read(len) {
+            Ok(chunk) => Ok(chunk),
+            Err(StreamError::Closed) => Ok(Vec::new()),
+            Err(StreamError::LastOperationFailed(err)) => {
+                Err(format!("failed to read stdin: {}", err.to_debug_string()))
+            }
+        }
+    }
+
+    fn stdin_read_all() -> Result<Vec<u8>, String> {
+        let stream = wasi::cli::stdin::get_stdin();
+        let mut out = Vec::new();
+        loop {
+            match stream.blocking_read(64 * 1024) {
+                Ok(chunk) => out.extend_from_slice(&chunk),
+                Err(StreamError::Closed) => return Ok(out),
+                Err(StreamError::LastOperationFailed(err)) => {
+                    return Err(format!("failed to read stdin: {}", err.to_debug_string()));
+                }
+            }
+        }
+    }
+}
+
+impl host_log::Guest for HostProvider {
+    fn log(level: host_log::Level, target: String, message: String) {
+        if !Self::enabled(level) {
+            return;
+        }
+        let level = match level {
+            host_log::Level::Trace => "TRACE",
+            host_log::Level::Debug => "DEBUG",
+            host_log::Level::Info => "INFO",
+            host_log::Level::Warn => "WARN",
+            host_log::Level::Error => "ERROR",
+        };
+        let line = if target.is_empty() {
+            format!("[{level}] {message}\n")
+        } else {
+            format!("[{level} {target}] {message}\n")
+        };
+        write_output(line.into_bytes(), StreamTarget::Stderr);
+    }
+
+    fn enabled(level: host_log::Level) -> bool {
+        MAX_LOG_LEVEL.with(|max| max.get().is_some_and(|max| level >= max))
+    }
 }
 
 impl host_fs::Guest for HostProvider {
+    type Writer = HostWriter;
+
     fn read_file(path: String) -> Result<Vec<u8>, String> {
         if path.is_empty() {
             return Err("path is empty".to_string());
@@ -143,14 +320,337 @@ impl host_fs::Guest for HostProvider {
         }
         Ok(out)
     }
+
+    fn copy_file(src: String, dst: String) -> Result<u64, String> {
+        if src.is_empty() || dst.is_empty() {
+            return Err("path is empty".to_string());
+        }
+        let (src_dir, src_rel) = resolve_preopen_path(&src)?;
+        let (dst_dir, dst_rel) = resolve_preopen_path(&dst)?;
+        let from = src_dir
+            .open_at(
+                PathFlags::SYMLINK_FOLLOW,
+                &src_rel,
+                OpenFlags::empty(),
+                DescriptorFlags::READ,
+            )
+            .map_err(|e| fs_error("copy", &src, e))?;
+        let to = dst_dir
+            .open_at(
+                PathFlags::SYMLINK_FOLLOW,
+                &dst_rel,
+                OpenFlags::CREATE,
+                DescriptorFlags::WRITE,
+            )
+            .map_err(|e| fs_error("copy", &dst, e))?;
+        // Truncating only after the check: `dst` may be `src` under another
+        // name (`./a`, a symlink, another mount), and would be emptied.
+        if from.is_same_object(&to) {
+            return Err(format!(
+                "copy: source and destination are the same file: {src} -> {dst}"
+            ));
+        }
+        to.set_size(0).map_err(|e| fs_error("copy", &dst, e))?;
+
+        // One chunk in memory at a time, whatever the file size.
+        let mut offset = 0u64;
+        loop {
+            let (chunk, eof) = from
+                .read(COPY_CHUNK_SIZE, offset)
+                .map_err(|e| fs_error("copy", &src, e))?;
+            let mut written = 0usize;
+            while written < chunk.len() {
+                let n = to
+                    .write(&chunk[written..], offset + written as u64)
+                    .map_err(|e| fs_error("copy", &dst, e))?;
+                if n == 0 {
+                    return Err("write returned 0 bytes".to_string());
+                }
+                written += n as usize;
+            }
+            offset += chunk.len() as u64;
+            if eof || chunk.is_empty() {
+                break;
+            }
+        }
+        Ok(offset)
+    }
+
+    fn rename(src: String, dst: String) -> Result<(), String> {
+        if src.is_empty() || dst.is_empty() {
+            return Err("path is empty".to_string());
+        }
+        let (src_dir, src_rel) = resolve_preopen_path(&src)?;
+        let (dst_dir, dst_rel) = resolve_preopen_path(&dst)?;
+        for (path, rel_path) in [(&src, &src_rel), (&dst, &dst_rel)] {
+            if rel_path == "." {
+                return Err(format!(
+                    "cannot rename the preopened directory itself: {path}"
+                ));
+            }
+        }
+        // Each mount is a directory of its own on the host side, possibly on
+        // another filesystem; moving between them is a copy, which the guest
+        // has to ask for.
+        let paths = format!("{src} -> {dst}");
+        let cross_mount =
+            || format!("rename: cross-mount rename not supported, use copy: {paths}");
+        if !src_dir.is_same_object(&dst_dir) {
+            return Err(cross_mount());
+        }
+        src_dir
+            .rename_at(&src_rel, &dst_dir, &dst_rel)
+            .map_err(|e| match e {
+                ErrorCode::CrossDevice => cross_mount(),
+                e => fs_error("rename", &paths, e),
+            })
+    }
+
+    fn remove_file(path: String) -> Result<(), String> {
+        let (dir, rel_path) = resolve_removable_path(&path)?;
+        dir.unlink_file_at(&rel_path)
+            .map_err(|e| fs_error("remove-file", &path, e))
+    }
+
+    fn remove_dir(path: String) -> Result<(), String> {
+        let (dir, rel_path) = resolve_removable_path(&path)?;
+        dir.remove_directory_at(&rel_path)
+            .map_err(|e| fs_error("remove-dir", &path, e))
+    }
+
+    fn remove_dir_all(path: String) -> Result<(), String> {
+        let (dir, rel_path) = resolve_removable_path(&path)?;
+        // A symlink to a directory goes itself; what it points at stays.
+        let remove = || match dir.stat_at(PathFlags::empty(), &rel_path)?.type_ {
+            DescriptorType::SymbolicLink => dir.unlink_file_at(&rel_path),
+            _ => remove_tree(&dir, &rel_path),
+        };
+        remove().map_err(|e| fs_error("remove-dir-all", &path, e))
+    }
+
+    fn stat(path: String) -> Result<host_fs::FileInfo, String> {
+        if path.is_empty() {
+            return Err("path is empty".to_string());
+        }
+        let (dir, rel_path) = resolve_preopen_path(&path)?;
+        // A mount root such as `/data` resolves to the preopen itself.
+        let stat = if rel_path == "." {
+            dir.stat()
+        } else {
+            dir.stat_at(PathFlags::empty(), &rel_path)
+        }
+        .map_err(|e| fs_error("stat", &path, e))?;
+        Ok(host_fs::FileInfo {
+            kind: file_kind(stat.type_),
+            size: stat.size,
+            modified_at: stat.data_modification_timestamp.map_or(0, |t| {
+                t.seconds * 1000 + u64::from(t.nanoseconds / 1_000_000)
+            }),
+        })
+    }
+
+    fn exists(path: String) -> bool {
+        let Ok((dir, rel_path)) = resolve_preopen_path(&path) else {
+            return false;
+        };
+        // Unlike `stat`, follow symlinks: a dangling one is missing.
+        rel_path == "." || dir.stat_at(PathFlags::SYMLINK_FOLLOW, &rel_path).is_ok()
+    }
+
+    fn walk_dir(path: String, max_depth: Option<u32>) -> Result<Vec<host_fs::DirEntry>, String> {
+        let requested = if path.is_empty() { "." } else { path.as_str() };
+        let (dir, rel_path) = resolve_preopen_path(requested)?;
+        let mut out = Vec::new();
+        walk(&dir, &rel_path, "", 1, max_depth, &mut out)
+            .map_err(|e| fs_error("walk-dir", requested, e))?;
+        Ok(out)
+    }
+
+    fn append_file(path: String, contents: Vec<u8>) -> Result<(), String> {
+        let (stream, _file) = open_for_write(&path, true, "append")?;
+        let written = write_all_to(&stream, &contents);
+        drop(stream);
+        written.map_err(|e| fs_error("append", &path, e))
+    }
+
+    fn open_writer(path: String, append: bool) -> Result<host_fs::Writer, String> {
+        let (stream, file) = open_for_write(&path, append, "open-writer")?;
+        Ok(host_fs::Writer::new(HostWriter {
+            path,
+            file: RefCell::new(Some((stream, file))),
+        }))
+    }
+}
+
+impl host_fs::GuestWriter for HostWriter {
+    fn write(&self, bytes: Vec<u8>) -> Result<(), String> {
+        let file = self.file.borrow();
+        let Some((stream, _)) = file.as_ref() else {
+            return Err(format!("write: writer is closed: {}", self.path));
+        };
+        write_all_to(stream, &bytes).map_err(|e| fs_error("write", &self.path, e))
+    }
+
+    fn close(&self) -> Result<(), String> {
+        match self.file.borrow_mut().take() {
+            Some((stream, file)) => {
+                drop(stream);
+                file.sync_data()
+                    .map_err(|e| fs_error("close", &self.path, e))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn file_kind(type_: DescriptorType) -> host_fs::FileKind {
+    match type_ {
+        DescriptorType::RegularFile => host_fs::FileKind::File,
+        DescriptorType::Directory => host_fs::FileKind::Dir,
+        DescriptorType::SymbolicLink => host_fs::FileKind::Symlink,
+        _ => host_fs::FileKind::Other,
+    }
+}
+
+/// Append the entries of `rel_path` (under `dir`) to `out`, recursing into
+/// subdirectories up to `max_depth`. Symlinks are never followed, so a link
+/// back up the tree cannot loop.
+fn walk(
+    dir: &Descriptor,
+    rel_path: &str,
+    prefix: &str,
+    depth: u32,
+    max_depth: Option<u32>,
+    out: &mut Vec<host_fs::DirEntry>,
+) -> Result<(), ErrorCode> {
+    if max_depth.is_some_and(|max| depth > max) {
+        return Ok(());
+    }
+    let opened;
+    let target = if rel_path == "." {
+        dir
+    } else {
+        opened = dir.open_at(
+            PathFlags::empty(),
+            rel_path,
+            OpenFlags::DIRECTORY,
+            DescriptorFlags::READ,
+        )?;
+        &opened
+    };
+    let stream = target.read_directory()?;
+    let mut entries = Vec::new();
+    while let Some(entry) = stream.read_directory_entry()? {
+        entries.push(entry);
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    for entry in entries {
+        let path = format!("{prefix}{}", entry.name);
+        out.push(host_fs::DirEntry {
+            path: path.clone(),
+            kind: file_kind(entry.type_),
+        });
+        if entry.type_ == DescriptorType::Directory {
+            let child = format!("{rel_path}/{}", entry.name);
+            walk(dir, &child, &format!("{path}/"), depth + 1, max_depth, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Open (creating) `path` for writing. Returns a stream that writes at the
+/// end of the file with `append`, else from 0 after truncating, and the
+/// descriptor it belongs to (which must outlive the stream).
+fn open_for_write(
+    path: &str,
+    append: bool,
+    op: &str,
+) -> Result<(OutputStream, Descriptor), String> {
+    if path.is_empty() {
+        return Err("path is empty".to_string());
+    }
+    let (dir, rel_path) = resolve_preopen_path(path)?;
+    let open_flags = if append {
+        OpenFlags::CREATE
+    } else {
+        OpenFlags::CREATE | OpenFlags::TRUNCATE
+    };
+    let file = dir
+        .open_at(
+            PathFlags::SYMLINK_FOLLOW,
+            &rel_path,
+            open_flags,
+            DescriptorFlags::WRITE,
+        )
+        .map_err(|e| fs_error(op, path, e))?;
+    // An append stream writes at the end as it is when each write lands, so
+    // concurrent appenders never overwrite each other.
+    let stream = if append {
+        file.append_via_stream()
+    } else {
+        file.write_via_stream(0)
+    }
+    .map_err(|e| fs_error(op, path, e))?;
+    Ok((stream, file))
+}
+
+/// Write all of `bytes` to `stream`, flushing them to the file.
+fn write_all_to(stream: &OutputStream, bytes: &[u8]) -> Result<(), ErrorCode> {
+    // `blocking-write-and-flush` takes at most 4096 bytes per call.
+    for chunk in bytes.chunks(4096) {
+        stream.blocking_write_and_flush(chunk).map_err(|e| match e {
+            StreamError::LastOperationFailed(err) => {
+                wasi::filesystem::types::filesystem_error_code(&err).unwrap_or(ErrorCode::Io)
+            }
+            StreamError::Closed => ErrorCode::Io,
+        })?;
+    }
+    Ok(())
+}
+
+/// Like [`resolve_preopen_path`], but refuses the preopened directory itself.
+fn resolve_removable_path(path: &str) -> Result<(Descriptor, String), String> {
+    if path.is_empty() {
+        return Err("path is empty".to_string());
+    }
+    let (dir, rel_path) = resolve_preopen_path(path)?;
+    if rel_path == "." {
+        return Err(format!(
+            "cannot remove the preopened directory itself: {path}"
+        ));
+    }
+    Ok((dir, rel_path))
+}
+
+/// Depth-first removal of `rel_path` under `dir`. Entries are removed through
+/// `dir` by path, and symlinks are unlinked rather than followed.
+fn remove_tree(dir: &Descriptor, rel_path: &str) -> Result<(), ErrorCode> {
+    let target = dir.open_at(
+        PathFlags::empty(),
+        rel_path,
+        OpenFlags::DIRECTORY,
+        DescriptorFlags::READ,
+    )?;
+    let stream = target.read_directory()?;
+    while let Some(entry) = stream.read_directory_entry()? {
+        let child = format!("{rel_path}/{}", entry.name);
+        match entry.type_ {
+            DescriptorType::Directory => remove_tree(dir, &child)?,
+            _ => dir.unlink_file_at(&child)?,
+        }
+    }
+    dir.remove_directory_at(rel_path)
 }
 
+/// Bytes read (and written) per step of `copy-file`.
+const COPY_CHUNK_SIZE: u64 = 1024 * 1024;
+
 impl host_process::Guest for HostProvider {
     fn exit(code: u32) {
-        if code == 0 {
-            wasi::cli::exit::exit(Ok(()));
-        } else {
-            wasi::cli::exit::exit(Err(()));
+        // Process exit statuses are a byte; codes past it stay failures.
+        match code {
+            0 => wasi::cli::exit::exit(Ok(())),
+            code => wasi::cli::exit::exit_with_code(u8::try_from(code).unwrap_or(u8::MAX)),
         }
     }
 }
@@ -165,8 +665,30 @@ impl host_pipes::Guest for HostProvider {
             .collect()
     }
 
+    fn describe_pipes() -> Vec<host_pipes::PipeDetails> {
+        pipe_runtime::describe_pipes()
+            .into_iter()
+            .map(|details| host_pipes::PipeDetails {
+                info: convert_pipe_info(details.info),
+                input_types: details.input_types,
+                output_type: details.output_type,
+                version: details.version,
+            })
+            .collect()
+    }
+
     fn load_pipe(name: String) -> Result<host_pipes::Pipe, String> {
-        pipe_runtime::load_pipe(&name).map(|pipe| host_pipes::Pipe::new(HostPipe { inner: pipe }))
+        Self::open_pipe(name).map_err(|e| match e {
+            host_pipes::PipeLoadError::NotFound(msg)
+            | host_pipes::PipeLoadError::InvalidName(msg)
+            | host_pipes::PipeLoadError::LoadFailed(msg) => msg,
+        })
+    }
+
+    fn open_pipe(name: String) -> Result<host_pipes::Pipe, host_pipes::PipeLoadError> {
+        pipe_runtime::open_pipe(&name)
+            .map(|pipe| host_pipes::Pipe::new(HostPipe { inner: pipe }))
+            .map_err(convert_pipe_load_error)
     }
 }
 
@@ -184,6 +706,28 @@ impl host_pipes::GuestPipe for HostPipe {
             .process(&input, &options)
             .map_err(convert_pipe_error)
     }
+
+    fn process_start(&self, options: Vec<String>) -> Result<u32, host_pipes::PipeError> {
+        self.inner
+            .process_start(&options)
+            .map_err(convert_pipe_error)
+    }
+
+    fn process_chunk(
+        &self,
+        session: u32,
+        bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, host_pipes::PipeError> {
+        self.inner
+            .process_chunk(session, &bytes)
+            .map_err(convert_pipe_error)
+    }
+
+    fn process_end(&self, session: u32) -> Result<Vec<u8>, host_pipes::PipeError> {
+        self.inner
+            .process_end(session)
+            .map_err(convert_pipe_error)
+    }
 }
 
 export!(HostProvider with_types_in bindings);
@@ -320,10 +864,21 @@ fn fs_error(op: &str, path: &str, err: ErrorCode) -> String {
         ErrorCode::NotDirectory => format!("{op}: not a directory: {path} ({})", err.name()),
         ErrorCode::IsDirectory => format!("{op}: is a directory: {path} ({})", err.name()),
         ErrorCode::ReadOnly => format!("{op}: read-only filesystem: {path} ({})", err.name()),
+        ErrorCode::NotEmpty => format!("{op}: directory not empty: {path} ({})", err.name()),
         _ => format!("{op}: filesystem error: {path} ({})", err.name()),
     }
 }
 
+fn convert_pipe_load_error(err: pipe_runtime::PipeLoadError) -> host_pipes::PipeLoadError {
+    match err {
+        pipe_runtime::PipeLoadError::NotFound(msg) => host_pipes::PipeLoadError::NotFound(msg),
+        pipe_runtime::PipeLoadError::InvalidName(msg) => {
+            host_pipes::PipeLoadError::InvalidName(msg)
+        }
+        pipe_runtime::PipeLoadError::LoadFailed(msg) => host_pipes::PipeLoadError::LoadFailed(msg),
+    }
+}
+
 fn convert_pipe_info(info: pipe_runtime::PipeInfo) -> host_pipes::PipeInfo {
     host_pipes::PipeInfo {
         name: info.name,
@@ -0,0 +1,148 @@
+//! `wacli build --emit-manifest`: every composed command's metadata as one
+//! JSON document, for docs and completion generators that should not parse
+//! wasm custom sections themselves.
+//!
+//! The shape is stable: `{"app": {...}, "commands": [...]}`, with commands
+//! sorted by the name the registry dispatches on (`<group>/<name>` for
+//! grouped commands) and each entry carrying the full `CommandMetadataV1`.
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use wacli_metadata::CommandMetadataV1;
+
+use crate::component_scan::CommandInfo;
+use crate::registry_gen_wat::AppMeta;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct BuildManifest<'a> {
+    app: App<'a>,
+    commands: Vec<Command<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct App<'a> {
+    name: &'a str,
+    version: &'a str,
+    description: &'a str,
+    wacli_version: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_revision: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    built_with: &'a [String],
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Command<'a> {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<&'a str>,
+    #[serde(flatten)]
+    metadata: &'a CommandMetadataV1,
+}
+
+/// Render the manifest for `app` and its resolved (local and registry) commands.
+pub fn render(app: &AppMeta, commands: &[CommandInfo]) -> Result<String> {
+    let mut entries: Vec<Command> = commands
+        .iter()
+        .map(|cmd| Command {
+            name: cmd.registry_name(),
+            group: cmd.group.as_deref(),
+            metadata: &cmd.metadata,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Some(pair) = entries.windows(2).find(|w| w[0].name == w[1].name) {
+        bail!(
+            "cannot emit manifest: two commands are named '{}'",
+            pair[0].name
+        );
+    }
+
+    let manifest = BuildManifest {
+        app: App {
+            name: &app.name,
+            version: &app.version,
+            description: &app.description,
+            wacli_version: &app.build_info.wacli_version,
+            git_revision: app.build_info.git_revision.as_deref(),
+            built_with: &app.build_info.built_with,
+        },
+        commands: entries,
+    };
+    let mut json = serde_json::to_string_pretty(&manifest)?;
+    json.push('\n');
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use wacli_metadata::CommandMeta;
+
+    fn command(name: &str, group: Option<&str>) -> CommandInfo {
+        CommandInfo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("commands/{name}.component.wasm")),
+            imports: Vec::new(),
+            metadata: CommandMetadataV1::new(
+                CommandMeta {
+                    name: name.to_string(),
+                    summary: format!("Run {name}"),
+                    ..Default::default()
+                },
+                None,
+            ),
+            group: group.map(str::to_string),
+            bundle: None,
+        }
+    }
+
+    #[test]
+    fn commands_are_sorted_by_registry_name() {
+        let app = AppMeta {
+            name: "example:my-cli".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        };
+        let json = render(
+            &app,
+            &[
+                command("seed", Some("db")),
+                command("greet", None),
+                command("ab", None),
+            ],
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["app"]["name"], "example:my-cli");
+        assert!(value["app"].get("git-revision").is_none());
+        let names: Vec<&str> = value["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["ab", "db/seed", "greet"]);
+        assert_eq!(value["commands"][1]["group"], "db");
+        assert_eq!(value["commands"][1]["command-meta"]["summary"], "Run seed");
+        assert_eq!(value["commands"][1]["format-version"], 1);
+    }
+
+    #[test]
+    fn duplicate_names_fail() {
+        let err = render(
+            &AppMeta::default(),
+            &[command("seed", Some("db")), command("seed", Some("db"))],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot emit manifest: two commands are named 'db/seed'"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! `wacli clean`: remove what `wacli build` leaves behind.
+//!
+//! Paths are resolved like `build` resolves them (against the manifest's
+//! directory), and nothing outside that directory is ever removed, even when
+//! the manifest points elsewhere with `..` or through a symlink.
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::manifest;
+
+#[derive(Debug, Parser)]
+pub struct CleanArgs {
+    /// Path to a wacli manifest (defaults to ./wacli.json if present)
+    #[arg(long, value_name = "FILE")]
+    manifest: Option<PathBuf>,
+
+    /// Remove pulled components (.wacli/commands, .wacli/framework, .wacli/git)
+    #[arg(long)]
+    cache: bool,
+
+    /// Remove the generated .wacli/registry.component.wasm
+    #[arg(long)]
+    registry: bool,
+
+    /// Remove the built component at the manifest's output path
+    #[arg(long)]
+    output: bool,
+
+    /// Remove all of .wacli/ and the built component
+    #[arg(long)]
+    all: bool,
+
+    /// Print what would be removed without removing it
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Cache directories under `.wacli/` removed by `--cache`.
+const CACHE_DIRS: &[&str] = &["commands", "framework", "git"];
+
+pub fn clean(args: CleanArgs) -> Result<()> {
+    if !(args.cache || args.registry || args.output || args.all) {
+        bail!("nothing to clean: pass --cache, --registry, --output or --all");
+    }
+
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let loaded = manifest::load_manifest(args.manifest.as_deref())?;
+    let base_dir = loaded
+        .as_ref()
+        .map(|m| m.base_dir.clone())
+        .unwrap_or_else(|| cwd.clone());
+    let output = loaded
+        .as_ref()
+        .and_then(|m| m.manifest.build.as_ref())
+        .and_then(|b| b.output.clone())
+        .unwrap_or_else(|| PathBuf::from("my-cli.component.wasm"));
+
+    let base = fs::canonicalize(&base_dir)
+        .with_context(|| format!("failed to resolve {}", base_dir.display()))?;
+    let mut resolved = Vec::new();
+    for target in targets(&args, &base_dir, &output) {
+        let path = resolve(&target);
+        if !is_inside(&base, &path) {
+            bail!(
+                "refusing to remove {}: it is outside the project directory {}",
+                target.display(),
+                base_dir.display()
+            );
+        }
+        resolved.push((target, path));
+    }
+
+    let mut removed = 0;
+    for (target, path) in resolved {
+        // An existing `target` has existing parents, so `path` is exactly
+        // what the OS resolves it to.
+        if fs::symlink_metadata(&target).is_err() {
+            continue;
+        }
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let shown = target.strip_prefix(&cwd).unwrap_or(&target).display();
+        removed += 1;
+        if args.dry_run {
+            eprintln!("Would remove {shown}");
+            continue;
+        }
+        if meta.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        }
+        .with_context(|| format!("failed to remove {}", target.display()))?;
+        eprintln!("Removed {shown}");
+    }
+    if removed == 0 {
+        eprintln!("Nothing to clean");
+    }
+    Ok(())
+}
+
+/// What the flags select, in removal order.
+fn targets(args: &CleanArgs, base_dir: &Path, output: &Path) -> Vec<PathBuf> {
+    let cache_dir = base_dir.join(".wacli");
+    let mut out = Vec::new();
+    if args.all {
+        out.push(cache_dir.clone());
+    } else {
+        if args.cache {
+            out.extend(CACHE_DIRS.iter().map(|d| cache_dir.join(d)));
+        }
+        if args.registry {
+            out.push(cache_dir.join("registry.component.wasm"));
+        }
+    }
+    if args.output || args.all {
+        out.push(base_dir.join(output));
+    }
+    out
+}
+
+/// Whether `path` (as returned by [`resolve`]) lies strictly inside `base`
+/// (already canonical).
+fn is_inside(base: &Path, path: &Path) -> bool {
+    path != base && path.starts_with(base)
+}
+
+/// What removing `path` acts on: `path` with symlinks followed in its
+/// directories, and `.`/`..` taken after them the way the OS takes them.
+/// The last component is not followed: removing a symlink leaves its target
+/// alone. Below the deepest existing directory nothing can be a symlink, so
+/// the rest is resolved lexically.
+fn resolve(path: &Path) -> PathBuf {
+    let (dir, name) = match path.file_name() {
+        Some(name) => (path.parent().unwrap_or(Path::new("")), Some(name)),
+        // Ends in `..`: that is a directory to resolve as a whole.
+        None => (path, None),
+    };
+    let mut existing = Some(dir);
+    while let Some(d) = existing {
+        if let Ok(mut out) = fs::canonicalize(d) {
+            for component in dir.strip_prefix(d).unwrap_or(Path::new("")).components() {
+                match component {
+                    Component::CurDir => {}
+                    Component::ParentDir => {
+                        out.pop();
+                    }
+                    c => out.push(c),
+                }
+            }
+            out.extend(name);
+            return out;
+        }
+        existing = d.parent();
+    }
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(flags: &[&str]) -> CleanArgs {
+        CleanArgs::parse_from(std::iter::once("clean").chain(flags.iter().copied()))
+    }
+
+    #[test]
+    fn flags_select_their_targets() {
+        let base = Path::new("/p");
+        let out = Path::new("dist/cli.wasm");
+        assert_eq!(
+            targets(&args(&["--cache", "--registry"]), base, out),
+            [
+                "/p/.wacli/commands",
+                "/p/.wacli/framework",
+                "/p/.wacli/git",
+                "/p/.wacli/registry.component.wasm"
+            ]
+            .map(PathBuf::from)
+        );
+        assert_eq!(
+            targets(&args(&["--output"]), base, out),
+            [PathBuf::from("/p/dist/cli.wasm")]
+        );
+        assert_eq!(
+            targets(&args(&["--all", "--cache"]), base, out),
+            ["/p/.wacli", "/p/dist/cli.wasm"].map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn paths_escaping_the_base_are_outside() {
+        let base = Path::new("/p");
+        let inside = |path: &str| is_inside(base, &resolve(Path::new(path)));
+        assert!(inside("/p/.wacli"));
+        assert!(inside("/p/./out/../cli.wasm"));
+        assert!(!inside("/p/../cli.wasm"));
+        assert!(!inside("/p/out/../../etc/passwd"));
+        assert!(!inside("/elsewhere/cli.wasm"));
+        assert!(!inside("/p"));
+        assert!(!inside("/p/."));
+    }
+
+    #[test]
+    fn symlinked_directories_are_followed() {
+        let dir = std::env::temp_dir().join(format!("wacli-clean-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let project = dir.join("project");
+        let outside = dir.join("outside");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        let base = fs::canonicalize(&project).unwrap();
+
+        let inside = |path: &Path| is_inside(&base, &resolve(path));
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&outside, project.join("dist")).unwrap();
+            assert!(!inside(&project.join("dist/cli.wasm")));
+            // The link itself is inside; removing it leaves the target alone.
+            assert!(inside(&project.join("dist")));
+
+            // `..` after the link leaves `outside`, not the link: the OS
+            // resolves `dist/../cli.wasm` to a sibling of `project`.
+            fs::write(dir.join("cli.wasm"), "").unwrap();
+            fs::write(project.join("cli.wasm"), "").unwrap();
+            let escaping = project.join("dist/../cli.wasm");
+            assert_eq!(
+                resolve(&escaping),
+                fs::canonicalize(dir.join("cli.wasm")).unwrap()
+            );
+            assert!(!inside(&escaping));
+            assert!(!inside(&project.join("dist/..")));
+        }
+        assert!(inside(&project.join("cli.wasm")));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
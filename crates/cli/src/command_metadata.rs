@@ -1,40 +1,424 @@
 use anyhow::{Context, Result, bail};
+use serde::Deserialize;
 use wasmparser::{Parser, Payload};
 
-use wacli_metadata::{COMMAND_METADATA_SECTION, CommandMetadataV1};
+use wacli_metadata::{
+    COMMAND_METADATA_FORMAT_VERSION, COMMAND_METADATA_SECTION, COMMAND_METADATA_SECTION_V2,
+    CommandMetadataV1, CommandMetadataV2, PIPE_METADATA_SECTION, PipeMetadataV1, unknown_fields,
+};
 
-pub fn extract_command_metadata(component_bytes: &[u8]) -> Result<Option<CommandMetadataV1>> {
-    let Some(raw) = find_custom_section_in_component(component_bytes, COMMAND_METADATA_SECTION)?
+use crate::scan_limits::{Deadline, ScanLimits};
+
+/// Command metadata sections, in order of preference.
+const COMMAND_METADATA_SECTIONS: [&str; 2] =
+    [COMMAND_METADATA_SECTION_V2, COMMAND_METADATA_SECTION];
+
+/// Read the command metadata of a command component, from its `@2` section
+/// or, failing that, its `@1` section.
+///
+/// Either payload is normalized to the `CommandMetadataV1` model the build
+/// works with. A payload from a newer format version is read with a warning
+/// rather than rejected; fields this wacli does not know are dropped.
+pub fn extract_command_metadata(
+    component_bytes: &[u8],
+    limits: &ScanLimits,
+    deadline: &Deadline,
+) -> Result<Option<CommandMetadataV1>> {
+    let mut metas = extract_bundled_command_metadata(component_bytes, limits, deadline)?;
+    if metas.len() > 1 {
+        let names: Vec<&str> = metas.iter().map(|m| m.command_meta.name.as_str()).collect();
+        bail!(
+            "the command metadata section declares {} commands ({}); \
+             export them with `wacli_cdk::export_multi!`",
+            metas.len(),
+            names.join(", ")
+        );
+    }
+    Ok(metas.pop())
+}
+
+/// Like [`extract_command_metadata`], for a multi-command component: its
+/// section holds one payload per command, back to back (the linker
+/// concatenates the sections each `declare_command_metadata!` emits).
+///
+/// Empty when the component has no command metadata section.
+pub fn extract_bundled_command_metadata(
+    component_bytes: &[u8],
+    limits: &ScanLimits,
+    deadline: &Deadline,
+) -> Result<Vec<CommandMetadataV1>> {
+    let Some(raw) = find_custom_section_in_component(
+        component_bytes,
+        &COMMAND_METADATA_SECTIONS,
+        limits,
+        deadline,
+    )?
     else {
-        return Ok(None);
+        return Ok(Vec::new());
     };
 
-    let meta: CommandMetadataV1 =
-        serde_json::from_slice(&raw).context("failed to parse command metadata JSON")?;
+    parse_command_metadata(&raw, limits)
+}
 
+/// Collect the command metadata of every command embedded in a composed CLI.
+///
+/// Payloads are returned in the order they appear (a multi-command component
+/// contributes one per command); a command embedded more
+/// than once is only reported the first time. A command carrying both
+/// sections is read from the `@2` one.
+pub fn extract_all_command_metadata(
+    component_bytes: &[u8],
+    limits: &ScanLimits,
+) -> Result<Vec<CommandMetadataV1>> {
+    let deadline = limits.deadline();
+    let mut out: Vec<(CommandMetadataV1, &str)> = Vec::new();
+    for payload in Parser::new(0).parse_all(component_bytes) {
+        deadline.check()?;
+        let payload = payload.context("failed to parse WASM")?;
+        let Payload::CustomSection(reader) = payload else {
+            continue;
+        };
+        let Some(&section) = COMMAND_METADATA_SECTIONS
+            .iter()
+            .find(|name| **name == reader.name())
+        else {
+            continue;
+        };
+        limits.check_section(section, reader.data().len())?;
+        for meta in parse_command_metadata(reader.data(), limits)? {
+            match out
+                .iter_mut()
+                .find(|(m, _)| m.command_meta.name == meta.command_meta.name)
+            {
+                None => out.push((meta, section)),
+                Some(seen)
+                    if seen.1 == COMMAND_METADATA_SECTION
+                        && section == COMMAND_METADATA_SECTION_V2 =>
+                {
+                    *seen = (meta, section);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    Ok(out.into_iter().map(|(meta, _)| meta).collect())
+}
+
+/// Read the `PIPE_METADATA_SECTION` of a pipe component, if it has one.
+pub fn extract_pipe_metadata(
+    component_bytes: &[u8],
+    limits: &ScanLimits,
+    deadline: &Deadline,
+) -> Result<Option<PipeMetadataV1>> {
+    let Some(raw) = find_custom_section_in_component(
+        component_bytes,
+        &[PIPE_METADATA_SECTION],
+        limits,
+        deadline,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    limits.check_json_depth(&raw)?;
+    let meta: PipeMetadataV1 =
+        serde_json::from_slice(&raw).context("failed to parse pipe metadata JSON")?;
     if meta.format_version != 1 {
         bail!(
-            "unsupported command metadata format-version {} (expected 1)",
+            "unsupported pipe metadata format-version {} (expected 1)",
             meta.format_version
         );
     }
-
     Ok(Some(meta))
 }
 
-fn find_custom_section_in_component(bytes: &[u8], section_name: &str) -> Result<Option<Vec<u8>>> {
+/// The payloads in a command metadata section: one, or one per command of a
+/// multi-command component.
+fn parse_command_metadata(raw: &[u8], limits: &ScanLimits) -> Result<Vec<CommandMetadataV1>> {
+    limits.check_json_depth(raw)?;
+    let mut metas = Vec::new();
+    // Both sections parse as V2, a superset of V1.
+    for value in serde_json::Deserializer::from_slice(raw).into_iter::<serde_json::Value>() {
+        let value = value.context("failed to parse command metadata JSON")?;
+        let meta = CommandMetadataV2::deserialize(&value)
+            .context("failed to parse command metadata JSON")?;
+        if let Some(warning) = format_version_warning(&meta, &value)? {
+            tracing::warn!("command '{}': {warning}", meta.command_meta.name);
+        }
+        metas.push(meta.into());
+    }
+    if metas.is_empty() {
+        bail!("the command metadata section is empty");
+    }
+
+    Ok(metas)
+}
+
+/// Check `format-version`: anything up to [`COMMAND_METADATA_FORMAT_VERSION`]
+/// is read as is; a newer version is read as far as this wacli understands
+/// it, and gets a warning naming the fields it skips, at any depth of `raw`.
+fn format_version_warning(
+    meta: &CommandMetadataV2,
+    raw: &serde_json::Value,
+) -> Result<Option<String>> {
+    let version = meta.format_version;
+    if version == 0 {
+        bail!("unsupported command metadata format-version 0");
+    }
+    if version <= COMMAND_METADATA_FORMAT_VERSION {
+        return Ok(None);
+    }
+    let mut warning = format!(
+        "metadata format-version {version} is newer than this wacli reads \
+         ({COMMAND_METADATA_FORMAT_VERSION}); update wacli to use all of it"
+    );
+    let fields = unknown_fields(raw);
+    if !fields.is_empty() {
+        warning.push_str(&format!(
+            " (ignoring unknown fields: {})",
+            fields.join(", ")
+        ));
+    }
+    Ok(Some(warning))
+}
+
+/// Find the custom section whose name comes first in `section_names`.
+fn find_custom_section_in_component(
+    bytes: &[u8],
+    section_names: &[&str],
+    limits: &ScanLimits,
+    deadline: &Deadline,
+) -> Result<Option<Vec<u8>>> {
     // `Parser::parse_all` automatically descends into nested modules/components
     // and yields their payloads as well, so scanning for `CustomSection` is
     // sufficient here.
+    let mut best: Option<(usize, Vec<u8>)> = None;
     for payload in Parser::new(0).parse_all(bytes) {
+        deadline.check()?;
         let payload = payload.context("failed to parse WASM")?;
         let Payload::CustomSection(reader) = payload else {
             continue;
         };
-        if reader.name() == section_name {
+        let Some(rank) = section_names.iter().position(|name| *name == reader.name()) else {
+            continue;
+        };
+        if best
+            .as_ref()
+            .is_some_and(|(best_rank, _)| *best_rank <= rank)
+        {
+            continue;
+        }
+        limits.check_section(section_names[rank], reader.data().len())?;
+        if rank == 0 {
             return Ok(Some(reader.data().to_vec()));
         }
+        best = Some((rank, reader.data().to_vec()));
     }
 
-    Ok(None)
+    Ok(best.map(|(_, data)| data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wasm_encoder::{CustomSection, Section};
+
+    fn module_with_metadata(json: &[u8]) -> Vec<u8> {
+        module_with_sections(&[(COMMAND_METADATA_SECTION, json)])
+    }
+
+    fn module_with_sections(sections: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut bytes = wat::parse_str("(module)").unwrap();
+        for (name, data) in sections {
+            CustomSection {
+                name: (*name).into(),
+                data: (*data).into(),
+            }
+            .append_to(&mut bytes);
+        }
+        bytes
+    }
+
+    fn extract(bytes: &[u8], limits: &ScanLimits) -> Result<Option<CommandMetadataV1>> {
+        extract_command_metadata(bytes, limits, &limits.deadline())
+    }
+
+    #[test]
+    fn reads_metadata_within_limits() {
+        let json = br#"{"format-version":1,"command-meta":{"name":"greet"}}"#;
+        let bytes = module_with_metadata(json);
+        let meta = extract(&bytes, &ScanLimits::default()).unwrap().unwrap();
+        assert_eq!(meta.command_meta.name, "greet");
+        assert_eq!(
+            extract_all_command_metadata(&bytes, &ScanLimits::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn reads_one_payload_per_bundled_command() {
+        let json = br#"{"format-version":1,"command-meta":{"name":"shout"}}
+            {"format-version":1,"command-meta":{"name":"whisper"}}"#;
+        let bytes = module_with_metadata(json);
+        let limits = ScanLimits::default();
+        let metas = extract_bundled_command_metadata(&bytes, &limits, &limits.deadline()).unwrap();
+        let names: Vec<&str> = metas.iter().map(|m| m.command_meta.name.as_str()).collect();
+        assert_eq!(names, ["shout", "whisper"]);
+        assert_eq!(
+            extract_all_command_metadata(&bytes, &limits).unwrap().len(),
+            2
+        );
+
+        let err = extract(&bytes, &limits).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "the command metadata section declares 2 commands (shout, whisper); \
+             export them with `wacli_cdk::export_multi!`"
+        );
+        let empty = module_with_metadata(b" ");
+        assert_eq!(
+            extract(&empty, &limits).unwrap_err().to_string(),
+            "the command metadata section is empty"
+        );
+        assert!(
+            extract_bundled_command_metadata(
+                &wat::parse_str("(module)").unwrap(),
+                &limits,
+                &limits.deadline()
+            )
+            .unwrap()
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn prefers_the_v2_section() {
+        let v1 = br#"{"format-version":1,"command-meta":{"name":"greet","summary":"v1"}}"#;
+        let v2 = br#"{"format-version":2,"command-meta":{"name":"greet","summary":"v2"}}"#;
+        for sections in [
+            [
+                (COMMAND_METADATA_SECTION, &v1[..]),
+                (COMMAND_METADATA_SECTION_V2, &v2[..]),
+            ],
+            [
+                (COMMAND_METADATA_SECTION_V2, &v2[..]),
+                (COMMAND_METADATA_SECTION, &v1[..]),
+            ],
+        ] {
+            let bytes = module_with_sections(&sections);
+            let meta = extract(&bytes, &ScanLimits::default()).unwrap().unwrap();
+            assert_eq!(meta.command_meta.summary, "v2");
+            assert_eq!(meta.format_version, 1);
+            let all = extract_all_command_metadata(&bytes, &ScanLimits::default()).unwrap();
+            assert_eq!(all.len(), 1);
+            assert_eq!(all[0].command_meta.summary, "v2");
+        }
+    }
+
+    #[test]
+    fn reads_future_format_versions_with_a_warning() {
+        // A format-version 3 payload with fields this wacli does not know,
+        // at the top level and inside command-meta and an arg.
+        let json = br#"{
+            "format-version": 3,
+            "command-meta": {"name": "greet", "summary": "Greet", "value-types": ["path"]},
+            "command-schema": {
+                "name": "greet",
+                "args": [{"name": "file", "value-type": "path", "required": true,
+                          "completion": "file"}]
+            },
+            "value-types": {"path": {"kind": "string"}},
+            "completions": []
+        }"#;
+        for section in COMMAND_METADATA_SECTIONS {
+            let bytes = module_with_sections(&[(section, json)]);
+            let meta = extract(&bytes, &ScanLimits::default()).unwrap().unwrap();
+            assert_eq!(meta.format_version, 1);
+            assert_eq!(meta.command_meta.summary, "Greet");
+            let schema = meta.command_schema.unwrap();
+            assert_eq!(schema.args[0].name, "file");
+            assert!(schema.args[0].required);
+        }
+
+        let raw: serde_json::Value = serde_json::from_slice(json).unwrap();
+        let meta = CommandMetadataV2::deserialize(&raw).unwrap();
+        let warning = format_version_warning(&meta, &raw).unwrap().unwrap();
+        assert_eq!(
+            warning,
+            "metadata format-version 3 is newer than this wacli reads (2); update wacli to use \
+             all of it (ignoring unknown fields: command-meta.value-types, \
+             command-schema.args[0].completion, completions, value-types)"
+        );
+    }
+
+    #[test]
+    fn older_format_versions_read_without_a_warning() {
+        for version in 1..=COMMAND_METADATA_FORMAT_VERSION {
+            let json = format!(r#"{{"format-version":{version},"command-meta":{{"name":"x"}}}}"#);
+            let raw: serde_json::Value = serde_json::from_str(&json).unwrap();
+            let meta = CommandMetadataV2::deserialize(&raw).unwrap();
+            assert_eq!(format_version_warning(&meta, &raw).unwrap(), None);
+        }
+        let bytes = module_with_metadata(br#"{"format-version":0,"command-meta":{"name":"x"}}"#);
+        let err = extract(&bytes, &ScanLimits::default())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "unsupported command metadata format-version 0");
+    }
+
+    #[test]
+    fn rejects_oversized_sections() {
+        let padding = " ".repeat(2048);
+        let json = format!(r#"{{"format-version":1,"command-meta":{{"name":"greet"}}}}{padding}"#);
+        let bytes = module_with_metadata(json.as_bytes());
+        let limits = ScanLimits {
+            max_section_bytes: 1024,
+            ..Default::default()
+        };
+        let err = extract(&bytes, &limits).unwrap_err().to_string();
+        assert!(
+            err.starts_with(&format!(
+                "custom section '{COMMAND_METADATA_SECTION}' is {} bytes, over the 1024 byte limit",
+                json.len()
+            )),
+            "{err}"
+        );
+        assert!(extract_all_command_metadata(&bytes, &limits).is_err());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_json() {
+        let json = format!(
+            r#"{{"format-version":1,"command-meta":{{"name":"x","examples":{}1{}}}}}"#,
+            "[".repeat(10_000),
+            "]".repeat(10_000)
+        );
+        let bytes = module_with_metadata(json.as_bytes());
+        let err = extract(&bytes, &ScanLimits::default())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("metadata JSON is nested deeper than 64 levels"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn stops_at_the_deadline() {
+        let bytes = module_with_metadata(b"{}");
+        let limits = ScanLimits {
+            timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        let deadline = limits.deadline();
+        std::thread::sleep(Duration::from_millis(1));
+        let err = extract_command_metadata(&bytes, &limits, &deadline)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("scan took longer than 0s"), "{err}");
+    }
 }
@@ -0,0 +1,592 @@
+//! Pre-flight compatibility checks between the framework core, the command
+//! components, and the components that satisfy their imports (host and
+//! registry).
+//!
+//! Mixing a core or a command built against one wacli release with the host
+//! or the generated registry of another otherwise fails inside WAC with an
+//! error that names neither the component at fault nor the fix.
+
+use anyhow::{Context, Result, bail};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use wasmparser::component_types::ComponentEntityType;
+use wasmparser::types::Types;
+use wasmparser::{Parser, Payload, Validator};
+use wit_parser::{Resolve, UnresolvedPackageGroup, WorldItem};
+
+use crate::registry_gen_wat::append_without_package;
+use crate::wit;
+
+/// Interfaces the generated composition passes to core (see `wac_gen`).
+const CORE_IMPORTS: &[&str] = &[
+    "wacli:cli/host-env@2.0.0",
+    "wacli:cli/host-io@2.0.0",
+    "wacli:cli/host-log@2.0.0",
+    "wacli:cli/host-control@2.0.0",
+    "wacli:cli/host-fs@2.0.0",
+    "wacli:cli/host-process@2.0.0",
+    "wacli:cli/registry@2.0.0",
+    "wacli:cli/registry-schema@2.0.0",
+];
+
+/// Which component provides a core import.
+pub struct Provider<'a> {
+    /// Shown in errors (`defaults/host.component.wasm`, `the generated registry`).
+    pub label: String,
+    /// Remedy shown when this provider lacks something core imports.
+    pub fix: &'a str,
+    pub bytes: &'a [u8],
+}
+
+/// Check that `core` imports every interface the composition wires into it,
+/// and that `host`/`registry` export every function core imports.
+pub fn check_core(
+    core_path: &Path,
+    core: &[u8],
+    host: &Provider<'_>,
+    registry: &Provider<'_>,
+) -> Result<()> {
+    let core_name = core_path.display();
+    let imports = instance_functions(core, Direction::Imports)
+        .with_context(|| format!("failed to inspect {core_name}"))?;
+
+    let missing: Vec<&str> = CORE_IMPORTS
+        .iter()
+        .copied()
+        .filter(|name| !imports.contains_key(*name))
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "{core_name} is too old for this wacli (missing {} import{}); \
+             update defaults/ or rebuild with --update-lock",
+            missing.join(", "),
+            if missing.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    let host_exports = instance_functions(host.bytes, Direction::Exports)
+        .with_context(|| format!("failed to inspect {}", host.label))?;
+    let registry_exports = instance_functions(registry.bytes, Direction::Exports)
+        .with_context(|| format!("failed to inspect {}", registry.label))?;
+
+    for (provider, exports, is_registry) in [
+        (registry, &registry_exports, true),
+        (host, &host_exports, false),
+    ] {
+        let mut unmet = Vec::new();
+        for (interface, funcs) in &imports {
+            if interface.starts_with("wacli:cli/registry") != is_registry {
+                continue;
+            }
+            match exports.get(interface) {
+                None => unmet.push(interface.clone()),
+                Some(have) => unmet.extend(
+                    funcs
+                        .iter()
+                        .filter(|f| !have.contains(f))
+                        .map(|f| format!("{interface}#{f}")),
+                ),
+            }
+        }
+        if !unmet.is_empty() {
+            bail!(
+                "{core_name} is newer than {} (core imports {}, which it does not provide); {}",
+                provider.label,
+                unmet.join(", "),
+                provider.fix
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Command components importing `wacli:cli/*` interfaces (or functions) the
+/// host does not export.
+#[derive(Debug)]
+pub struct InterfaceMismatch {
+    /// One line per unmet import, naming the command component.
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for InterfaceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "command components import what the host does not provide:\n  {}",
+            self.problems.join("\n  ")
+        )
+    }
+}
+
+impl std::error::Error for InterfaceMismatch {}
+
+/// Check that `host` exports every `wacli:cli/*` interface and function the
+/// `commands` (label and bytes) import, at the same version.
+pub fn check_commands(commands: &[(String, Vec<u8>)], host: &Provider<'_>) -> Result<()> {
+    let exports = instance_functions(host.bytes, Direction::Exports)
+        .with_context(|| format!("failed to inspect {}", host.label))?;
+
+    let mut problems = Vec::new();
+    for (label, bytes) in commands {
+        let imports = instance_functions(bytes, Direction::Imports)
+            .with_context(|| format!("failed to inspect {label}"))?;
+        for (interface, funcs) in &imports {
+            if let Some(have) = exports.get(&*composed_name(interface)) {
+                problems.extend(funcs.iter().filter(|f| !have.contains(f)).map(|f| {
+                    format!(
+                        "{label} imports {interface}#{f}, which {} does not provide",
+                        host.label
+                    )
+                }));
+                continue;
+            }
+            let provided = other_versions(&exports, interface);
+            problems.push(if provided.is_empty() {
+                format!(
+                    "{label} imports {interface}, which {} does not provide",
+                    host.label
+                )
+            } else {
+                format!(
+                    "{label} imports {interface} but {} provides @{}",
+                    host.label,
+                    provided.join(", @")
+                )
+            });
+        }
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(InterfaceMismatch { problems }.into())
+}
+
+/// Where a command component departs from `world` (`plugin` in `command.wit`,
+/// or `multi-plugin` of `wacli:cli@2.1.0`): interfaces or functions of the
+/// world it does not export, and `wacli:cli/*` imports the world does not
+/// include.
+///
+/// Unlike [`check_commands`] this needs no host, so a component can be
+/// checked on its own before it is published.
+#[cfg_attr(not(feature = "runtime"), allow(dead_code))]
+pub fn plugin_world_problems(bytes: &[u8], world: &str) -> Result<Vec<String>> {
+    let (world_imports, world_exports) = world_functions(world)?;
+    let exports = instance_functions(bytes, Direction::Exports)?;
+    let imports = instance_functions(bytes, Direction::Imports)?;
+
+    let mut problems = Vec::new();
+    for (interface, funcs) in &world_exports {
+        match exports.get(interface) {
+            Some(have) => problems.extend(
+                funcs
+                    .iter()
+                    .filter(|f| !have.contains(f))
+                    .map(|f| format!("does not export {interface}#{f}")),
+            ),
+            None => problems.push(match other_versions(&exports, interface).as_slice() {
+                [] => format!("does not export {interface}"),
+                found => format!("exports @{} instead of {interface}", found.join(", @")),
+            }),
+        }
+    }
+    for (interface, funcs) in &imports {
+        match world_imports.get(&*composed_name(interface)) {
+            Some(allowed) => {
+                problems.extend(funcs.iter().filter(|f| !allowed.contains(f)).map(|f| {
+                    format!("imports {interface}#{f}, which the {world} world does not include")
+                }))
+            }
+            None => problems.push(match other_versions(&world_imports, interface).as_slice() {
+                [] => format!("imports {interface}, which the {world} world does not include"),
+                found => format!(
+                    "imports {interface} but the {world} world has @{}",
+                    found.join(", @")
+                ),
+            }),
+        }
+    }
+    Ok(problems)
+}
+
+/// Versions of `interface` in `functions` other than its own.
+fn other_versions<'a>(
+    functions: &'a BTreeMap<String, Vec<String>>,
+    interface: &str,
+) -> Vec<&'a str> {
+    let (base, _) = split_version(interface);
+    functions
+        .keys()
+        .filter(|name| split_version(name).0 == base)
+        .map(|name| split_version(name).1)
+        .collect()
+}
+
+/// Functions of each interface the plugin world `world` imports and
+/// exports, keyed like [`instance_functions`].
+type WorldFunctions = (BTreeMap<String, Vec<String>>, BTreeMap<String, Vec<String>>);
+
+fn world_functions(world: &str) -> Result<WorldFunctions> {
+    let mut source = wit::TYPES_WIT.trim_end().to_string();
+    for file in [
+        wit::HOST_ENV_WIT,
+        wit::HOST_IO_WIT,
+        wit::HOST_LOG_WIT,
+        wit::HOST_FS_WIT,
+        wit::HOST_PROCESS_WIT,
+        wit::HOST_PIPES_WIT,
+        wit::COMMAND_WIT,
+    ] {
+        source.push_str("\n\n");
+        append_without_package(&mut source, file);
+    }
+    let mut resolve = Resolve::default();
+    resolve.push_group(
+        UnresolvedPackageGroup::parse(Path::new("command.wit"), &source)
+            .context("failed to parse the plugin WIT")?,
+    )?;
+    resolve.push_group(
+        UnresolvedPackageGroup::parse(Path::new("multi-command.wit"), wit::MULTI_COMMAND_WIT)
+            .context("failed to parse the plugin WIT")?,
+    )?;
+    let id = resolve
+        .worlds
+        .iter()
+        .find(|(_, w)| w.name == world)
+        .map(|(id, _)| id)
+        .with_context(|| format!("no {world} world in the plugin WIT"))?;
+
+    let functions = |items: &indexmap::IndexMap<_, WorldItem>| {
+        items
+            .values()
+            .filter_map(|item| match item {
+                WorldItem::Interface { id, .. } => Some((
+                    resolve.id_of(*id)?,
+                    resolve.interfaces[*id].functions.keys().cloned().collect(),
+                )),
+                _ => None,
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let world = &resolve.worlds[id];
+    Ok((functions(&world.imports), functions(&world.exports)))
+}
+
+/// The interface an import of `interface` is wired to: the composer takes an
+/// unversioned `wacli:cli/host-io` for `@2.0.0` (`CommandInfo::import_name`).
+fn composed_name(interface: &str) -> Cow<'_, str> {
+    match split_version(interface) {
+        (_, "") => Cow::Owned(format!("{interface}@2.0.0")),
+        _ => Cow::Borrowed(interface),
+    }
+}
+
+/// `wacli:cli/host-io@2.0.0` as (`wacli:cli/host-io`, `2.0.0`).
+fn split_version(interface: &str) -> (&str, &str) {
+    interface.split_once('@').unwrap_or((interface, ""))
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Imports,
+    Exports,
+}
+
+/// Function names of each top-level `wacli:cli/*` instance imported or
+/// exported by a component.
+fn instance_functions(bytes: &[u8], direction: Direction) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut names = Vec::new();
+    let mut depth = 0;
+    for payload in Parser::new(0).parse_all(bytes) {
+        match payload.context("failed to parse WASM")? {
+            Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => depth += 1,
+            Payload::End(_) if depth > 0 => depth -= 1,
+            Payload::ComponentImportSection(reader)
+                if depth == 0 && matches!(direction, Direction::Imports) =>
+            {
+                for import in reader {
+                    names.push(import.context("failed to read component import")?.name.0);
+                }
+            }
+            Payload::ComponentExportSection(reader)
+                if depth == 0 && matches!(direction, Direction::Exports) =>
+            {
+                for export in reader {
+                    names.push(export.context("failed to read component export")?.name.0);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let types: Types = Validator::new()
+        .validate_all(bytes)
+        .context("component failed validation")?;
+    let mut out = BTreeMap::new();
+    for name in names.into_iter().filter(|n| n.starts_with("wacli:cli/")) {
+        let ty = match direction {
+            Direction::Imports => types.component_entity_type_of_import(name),
+            Direction::Exports => types.component_entity_type_of_export(name),
+        };
+        let Some(ComponentEntityType::Instance(id)) = ty else {
+            continue;
+        };
+        let funcs = types[id]
+            .exports
+            .iter()
+            .filter(|(_, ty)| matches!(ty, ComponentEntityType::Func(_)))
+            .map(|(name, _)| name.clone())
+            .collect();
+        out.insert(name.to_string(), funcs);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A component importing (`import`) or exporting (`export`) empty
+    /// functions grouped by interface.
+    fn component(kind: &str, interfaces: &[(&str, &[&str])]) -> Vec<u8> {
+        let mut wat = String::from("(component\n");
+        for (i, (interface, funcs)) in interfaces.iter().enumerate() {
+            let full = if interface.contains('@') {
+                format!("wacli:cli/{interface}")
+            } else {
+                format!("wacli:cli/{interface}@2.0.0")
+            };
+            let decls: String = funcs
+                .iter()
+                .map(|f| format!(" (export \"{f}\" (func))"))
+                .collect();
+            if kind == "import" {
+                wat.push_str(&format!("  (import \"{full}\" (instance{decls}))\n"));
+            } else {
+                wat.push_str(&format!("  (import \"x{i}\" (instance $i{i}{decls}))\n"));
+                wat.push_str(&format!("  (export \"{full}\" (instance $i{i}))\n"));
+            }
+        }
+        wat.push(')');
+        wat::parse_str(&wat).unwrap()
+    }
+
+    const HOST: &[(&str, &[&str])] = &[
+        ("host-env", &["args"]),
+        ("host-io", &["stdout-write"]),
+        ("host-log", &["log"]),
+        ("host-control", &["set-env", "set-max-log-level"]),
+        ("host-fs", &["read-file"]),
+        ("host-process", &["exit"]),
+    ];
+
+    fn check(core: &[(&str, &[&str])], registry: &[(&str, &[&str])]) -> Result<()> {
+        let core = component("import", core);
+        let host = component("export", HOST);
+        let registry = component("export", registry);
+        check_core(
+            Path::new("defaults/core.component.wasm"),
+            &core,
+            &Provider {
+                label: "host".to_string(),
+                fix: "update the host",
+                bytes: &host,
+            },
+            &Provider {
+                label: "the generated registry".to_string(),
+                fix: "update wacli",
+                bytes: &registry,
+            },
+        )
+    }
+
+    fn with(
+        extra: &[(&'static str, &'static [&'static str])],
+    ) -> Vec<(&'static str, &'static [&'static str])> {
+        let mut out = HOST.to_vec();
+        out.extend_from_slice(extra);
+        out
+    }
+
+    const REGISTRY: &[(&str, &[&str])] = &[
+        ("registry", &["list-commands", "run"]),
+        ("registry-schema", &["list-schemas", "get-app-meta"]),
+    ];
+
+    #[test]
+    fn matching_core_passes() {
+        check(&with(REGISTRY), REGISTRY).unwrap();
+        // Core may use a subset of what is provided.
+        check(
+            &with(&[
+                ("registry", &["run"]),
+                ("registry-schema", &["get-app-meta"]),
+            ]),
+            REGISTRY,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn core_without_registry_schema_is_too_old() {
+        let err = check(&with(&[("registry", &["run"])]), REGISTRY).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "defaults/core.component.wasm is too old for this wacli (missing \
+             wacli:cli/registry-schema@2.0.0 import); update defaults/ or rebuild with --update-lock"
+        );
+    }
+
+    #[test]
+    fn core_importing_unknown_functions_is_too_new() {
+        let core = with(&[
+            ("registry", &["run"]),
+            ("registry-schema", &["get-app-meta", "list-plugins"]),
+        ]);
+        let err = check(&core, REGISTRY).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "defaults/core.component.wasm is newer than the generated registry (core imports \
+             wacli:cli/registry-schema@2.0.0#list-plugins, which it does not provide); update wacli"
+        );
+
+        let mut core = with(REGISTRY);
+        core[1] = ("host-io", &["stdout-write", "stdout-is-terminal"]);
+        let err = check(&core, REGISTRY).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "newer than host (core imports wacli:cli/host-io@2.0.0#stdout-is-terminal"
+            ),
+            "{err}"
+        );
+    }
+
+    fn check_command(imports: &[(&str, &[&str])]) -> Result<()> {
+        let host = component("export", HOST);
+        check_commands(
+            &[
+                (
+                    "commands/ok.component.wasm".to_string(),
+                    component("import", &HOST[..2]),
+                ),
+                (
+                    "commands/greet.component.wasm".to_string(),
+                    component("import", imports),
+                ),
+            ],
+            &Provider {
+                label: "defaults/host.component.wasm".to_string(),
+                fix: "update the host",
+                bytes: &host,
+            },
+        )
+    }
+
+    /// What the `plugin` world expects of a command.
+    const COMMAND: &[(&str, &[&str])] = &[("command", &["meta", "run"])];
+
+    #[test]
+    fn plugin_world_problems_list_missing_exports_and_foreign_imports() {
+        let problems = |kind: &str, interfaces: &[(&str, &[&str])], world: &str| {
+            plugin_world_problems(&component(kind, interfaces), world).unwrap()
+        };
+        assert!(problems("export", COMMAND, "plugin").is_empty());
+
+        assert_eq!(
+            problems("import", &[("host-io", &["stdout-write"])], "plugin"),
+            ["does not export wacli:cli/command@2.0.0"]
+        );
+        assert_eq!(
+            problems("export", &[("command@1.0.0", &["meta", "run"])], "plugin"),
+            ["exports @1.0.0 instead of wacli:cli/command@2.0.0"]
+        );
+        assert_eq!(
+            problems("export", COMMAND, "multi-plugin"),
+            ["does not export wacli:cli/multi-command@2.1.0"]
+        );
+        assert_eq!(
+            problems("export", &[("command", &["run"])], "plugin"),
+            ["does not export wacli:cli/command@2.0.0#meta"]
+        );
+
+        assert_eq!(
+            problems(
+                "import",
+                &[
+                    ("types", &[]),
+                    ("host-io", &["stdout-write", "stdout-is-tty"]),
+                    ("host-net", &["connect"]),
+                    ("host-fs@2.1.0", &["read-file"]),
+                ],
+                "plugin",
+            )[1..],
+            [
+                "imports wacli:cli/host-fs@2.1.0 but the plugin world has @2.0.0",
+                "imports wacli:cli/host-io@2.0.0#stdout-is-tty, which the plugin world does not include",
+                "imports wacli:cli/host-net@2.0.0, which the plugin world does not include",
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_may_import_a_subset_of_the_host() {
+        check_command(&[("host-io", &["stdout-write"])]).unwrap();
+        check_command(&[]).unwrap();
+    }
+
+    #[test]
+    fn unversioned_imports_match_the_host_at_2_0_0() {
+        let unversioned = wat::parse_str(
+            r#"(component
+                (import "wacli:cli/host-io" (instance (export "stdout-write" (func))))
+                (import "wacli:cli/host-env" (instance (export "vars" (func))))
+            )"#,
+        )
+        .unwrap();
+        let host = component("export", HOST);
+        let err = check_commands(
+            &[("commands/old.component.wasm".to_string(), unversioned)],
+            &Provider {
+                label: "defaults/host.component.wasm".to_string(),
+                fix: "update the host",
+                bytes: &host,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InterfaceMismatch>().unwrap().problems,
+            [
+                "commands/old.component.wasm imports wacli:cli/host-env#vars, \
+              which defaults/host.component.wasm does not provide"
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_importing_what_the_host_lacks_are_listed() {
+        let err = check_command(&[
+            ("host-io@2.1.0", &["stdout-write"]),
+            ("host-env", &["args", "vars"]),
+            ("host-pipes", &["load-pipe"]),
+        ])
+        .unwrap_err();
+        let mismatch = err.downcast_ref::<InterfaceMismatch>().unwrap();
+        assert_eq!(
+            mismatch.problems,
+            [
+                "commands/greet.component.wasm imports wacli:cli/host-env@2.0.0#vars, \
+                 which defaults/host.component.wasm does not provide",
+                "commands/greet.component.wasm imports wacli:cli/host-io@2.1.0 \
+                 but defaults/host.component.wasm provides @2.0.0",
+                "commands/greet.component.wasm imports wacli:cli/host-pipes@2.0.0, \
+                 which defaults/host.component.wasm does not provide",
+            ]
+        );
+        assert!(
+            err.to_string()
+                .starts_with("command components import what the host does not provide:\n  "),
+            "{err}"
+        );
+    }
+}
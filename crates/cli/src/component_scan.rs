@@ -1,13 +1,16 @@
 //! Scan and validate command components in the commands/ directory.
 
-use anyhow::{Context, Result, bail};
-use std::collections::HashMap;
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
-use wacli_metadata::CommandMetadataV1;
+use wacli_metadata::{ArgSchema, CommandMeta, CommandMetadataV1, CommandSchema, ValidationError};
 use wasmparser::{Parser, Payload};
 
-use crate::command_metadata::extract_command_metadata;
+use crate::command_metadata::{extract_bundled_command_metadata, extract_command_metadata};
+use crate::manifest::CommandGroup;
+use crate::scan_limits::{Deadline, ScanLimits};
 
 /// Information about a discovered command component.
 #[derive(Debug, Clone)]
@@ -20,37 +23,110 @@ pub struct CommandInfo {
     pub imports: Vec<String>,
     /// Embedded command metadata (extracted from a custom section).
     pub metadata: CommandMetadataV1,
+    /// Command group this command belongs to (from `build.groups`).
+    pub group: Option<String>,
+    /// For a command of a multi-command component, the component's name
+    /// (from its file name); its other commands share the same value.
+    pub bundle: Option<String>,
 }
 
 impl CommandInfo {
-    /// Returns a variable-safe name (hyphens replaced with underscores).
+    /// Returns the WAC variable name for this command's instance.
+    ///
+    /// WAC identifiers are kebab-case, so this is the same as the interface
+    /// stem; the commands of a multi-command component share its instance.
     pub fn var_name(&self) -> String {
-        self.name.replace('-', "_")
+        match &self.bundle {
+            Some(bundle) => bundle.clone(),
+            None => self.interface_name(),
+        }
+    }
+
+    /// Returns the name the registry dispatches on (`<group>/<name>` for grouped commands).
+    pub fn registry_name(&self) -> String {
+        self.qualify(&self.name)
+    }
+
+    /// Prefix `name` (the command name or one of its aliases) with the group, if any.
+    pub fn qualify(&self, name: &str) -> String {
+        match &self.group {
+            Some(group) => format!("{group}/{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Returns the WIT-safe stem of the registry import interface (`<stem>-command`).
+    ///
+    /// `/` is not allowed in WIT identifiers, so grouped commands use `<group>-<name>`.
+    pub fn interface_name(&self) -> String {
+        match &self.group {
+            Some(group) => format!("{group}-{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the interface the registry imports this command's exports as:
+    /// `<stem>-command`, or `<component>-multi-command` for the commands of a
+    /// multi-command component.
+    pub fn registry_import(&self) -> String {
+        match &self.bundle {
+            Some(bundle) => format!("{bundle}-multi-command"),
+            None => format!("{}-command", self.interface_name()),
+        }
+    }
+
+    /// Whether the command is hidden from help (schema first, then meta).
+    pub fn is_hidden(&self) -> bool {
+        self.metadata
+            .command_schema
+            .as_ref()
+            .map_or(self.metadata.command_meta.hidden, |s| s.hidden)
+    }
+
+    /// Whether the embedded payload carries a full `command_schema`.
+    ///
+    /// Without one, core validates against the flattened meta only (no env
+    /// fallbacks, possible values, conflicts or requirements).
+    pub fn has_schema(&self) -> bool {
+        self.metadata.command_schema.is_some()
     }
 
     /// Returns the package name for WAC composition.
     pub fn package_name(&self) -> String {
-        format!("wacli:cmd-{}", self.name)
+        format!("wacli:cmd-{}", self.bundle.as_ref().unwrap_or(&self.name))
     }
 
     /// Resolve the preferred import name for a given base (e.g. "host-env").
     /// Falls back to the fully qualified name if no match is found.
     pub fn import_name(&self, base: &str) -> String {
+        self.find_import(base)
+            .unwrap_or_else(|| format!("wacli:cli/{base}@2.0.0"))
+    }
+
+    /// Whether the component imports the interface `base` under any of the
+    /// names [`Self::import_name`] accepts.
+    pub fn imports_interface(&self, base: &str) -> bool {
+        self.find_import(base).is_some()
+    }
+
+    fn find_import(&self, base: &str) -> Option<String> {
         let fqn = format!("wacli:cli/{base}@2.0.0");
-        if self.imports.iter().any(|i| i == &fqn) {
-            return fqn;
-        }
         let pkg = format!("wacli:cli/{base}");
-        if self.imports.iter().any(|i| i == &pkg) {
-            return pkg;
-        }
-        if self.imports.iter().any(|i| i == base) {
-            return base.to_string();
-        }
-        fqn
+        [fqn, pkg, base.to_string()]
+            .into_iter()
+            .find(|name| self.imports.iter().any(|i| i == name))
     }
 }
 
+/// One command per component: the commands of a multi-command component
+/// after the first are skipped.
+pub fn components(commands: &[CommandInfo]) -> impl Iterator<Item = &CommandInfo> {
+    let mut seen = HashSet::new();
+    commands
+        .iter()
+        .filter(move |cmd| cmd.bundle.is_none() || seen.insert(cmd.var_name()))
+}
+
 /// Validate that a command name matches the required pattern: [a-z][a-z0-9-]*
 pub fn is_valid_command_name(name: &str) -> bool {
     if name.is_empty() {
@@ -79,8 +155,9 @@ pub fn is_valid_command_name(name: &str) -> bool {
 /// Inspect a single `*.component.wasm` file and return validated command info.
 ///
 /// This is useful when the command component was resolved outside of `commandsDir`
-/// (e.g. pulled from a registry cache).
-pub fn inspect_command_component(path: &Path) -> Result<CommandInfo> {
+/// (e.g. pulled from a registry cache). See [`scan_commands`] for
+/// `allow_meta_exec`.
+pub fn inspect_command_component(path: &Path, allow_meta_exec: bool) -> Result<CommandInfo> {
     if !path.exists() {
         bail!("command component not found: {}", path.display());
     }
@@ -111,10 +188,33 @@ pub fn inspect_command_component(path: &Path) -> Result<CommandInfo> {
         );
     }
 
+    inspect_command_component_as(path, &name, allow_meta_exec)
+}
+
+/// Like [`inspect_command_component`], but for a component whose command name
+/// comes from elsewhere (e.g. a `build.commands` entry) rather than from its
+/// `<name>.component.wasm` file name.
+pub fn inspect_command_component_as(
+    path: &Path,
+    name: &str,
+    allow_meta_exec: bool,
+) -> Result<CommandInfo> {
+    if !path.exists() {
+        bail!("command component not found: {}", path.display());
+    }
+    if !path.is_file() {
+        bail!("command component path is not a file: {}", path.display());
+    }
+    let name = name.to_string();
+
     let wasm_bytes =
         fs::read(path).with_context(|| format!("failed to read component: {}", path.display()))?;
 
-    let (exports, imports) = match analyze_wasm(&wasm_bytes)? {
+    let limits = ScanLimits::from_env()?;
+    let deadline = limits.deadline();
+    let (exports, imports) = match analyze_wasm(&wasm_bytes, &deadline)
+        .map_err(|e| anyhow!("{}: {e:#}", path.display()))?
+    {
         WasmKind::CoreModule => {
             bail!(
                 "'{}' is a core WebAssembly module, not a component.\n\
@@ -127,6 +227,12 @@ pub fn inspect_command_component(path: &Path) -> Result<CommandInfo> {
         WasmKind::Component { exports, imports } => (exports, imports),
     };
 
+    if exports_multi_command_interface(&exports) {
+        bail!(
+            "'{}' is a multi-command component; those are only read from the commands directory",
+            path.display()
+        );
+    }
     if !exports_command_interface(&exports) {
         bail!(
             "'{}' does not export wacli:cli/command interface",
@@ -134,29 +240,24 @@ pub fn inspect_command_component(path: &Path) -> Result<CommandInfo> {
         );
     }
 
-    let Some(metadata) = extract_command_metadata(&wasm_bytes)
-        .with_context(|| format!("failed to extract command metadata from {}", path.display()))?
-    else {
-        bail!(
-            "missing embedded command metadata in {}\n\
-\n\
-Expected a WASM custom section named '{}'.\n\
-\n\
-Fix:\n\
-- Update your plugin to use `wacli_cdk::declare_command_metadata!(...)` (and rebuild the component).",
-            path.display(),
-            wacli_metadata::COMMAND_METADATA_SECTION
-        );
+    let metadata = match extract_command_metadata(&wasm_bytes, &limits, &deadline).map_err(|e| {
+        anyhow!(
+            "failed to extract command metadata from {}: {e:#}",
+            path.display()
+        )
+    })? {
+        Some(metadata) => metadata,
+        None => metadata_without_section(&wasm_bytes, &name, path, &limits, allow_meta_exec)?,
     };
 
     if metadata.command_meta.name != name {
         bail!(
             "command metadata name mismatch for {}\n\
 \n\
-file name: {}\n\
-meta.name:  {}\n\
+expected:  {}\n\
+meta.name: {}\n\
 \n\
-The command name is derived from the `.component.wasm` file name and must match `meta.name`.",
+The command name (from the `.component.wasm` file name or the build.commands entry) must match `meta.name`.",
             path.display(),
             name,
             metadata.command_meta.name
@@ -169,26 +270,30 @@ The command name is derived from the `.component.wasm` file name and must match
         bail!(
             "command schema name mismatch for {}\n\
 \n\
-file name:   {}\n\
+expected:    {}\n\
 schema.name: {}\n\
 \n\
-The command name is derived from the `.component.wasm` file name and must match `schema.name`.",
+The command name (from the `.component.wasm` file name or the build.commands entry) must match `schema.name`.",
             path.display(),
             name,
             schema.name
         );
     }
 
+    limits.warn_on_large_lists(&metadata, path);
+
     Ok(CommandInfo {
         name,
         path: path.to_path_buf(),
         imports,
         metadata,
+        group: None,
+        bundle: None,
     })
 }
 
 /// Result of analyzing a WASM binary.
-enum WasmKind {
+pub enum WasmKind {
     /// A WebAssembly Component with its exports.
     Component {
         exports: Vec<String>,
@@ -199,7 +304,7 @@ enum WasmKind {
 }
 
 /// Analyze a WASM binary to determine its kind and extract exports.
-fn analyze_wasm(wasm_bytes: &[u8]) -> Result<WasmKind> {
+pub fn analyze_wasm(wasm_bytes: &[u8], deadline: &Deadline) -> Result<WasmKind> {
     let parser = Parser::new(0);
     let mut is_component = false;
     let mut exports = Vec::new();
@@ -207,6 +312,7 @@ fn analyze_wasm(wasm_bytes: &[u8]) -> Result<WasmKind> {
     let mut depth = 0;
 
     for payload in parser.parse_all(wasm_bytes) {
+        deadline.check()?;
         let payload = payload.context("failed to parse WASM")?;
 
         match payload {
@@ -216,10 +322,8 @@ fn analyze_wasm(wasm_bytes: &[u8]) -> Result<WasmKind> {
             Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => {
                 depth += 1;
             }
-            Payload::End(_) => {
-                if depth > 0 {
-                    depth -= 1;
-                }
+            Payload::End(_) if depth > 0 => {
+                depth -= 1;
             }
             // Only capture exports at the top level (depth == 0)
             Payload::ComponentExportSection(reader) if depth == 0 => {
@@ -246,14 +350,35 @@ fn analyze_wasm(wasm_bytes: &[u8]) -> Result<WasmKind> {
 }
 
 /// Check if a component exports the wacli:cli/command interface.
-fn exports_command_interface(exports: &[String]) -> bool {
+pub fn exports_command_interface(exports: &[String]) -> bool {
     exports
         .iter()
         .any(|e| e == "wacli:cli/command@2.0.0" || e == "wacli:cli/command" || e == "command")
 }
 
+/// Check if a component exports the wacli:cli/multi-command interface
+/// (`multi-plugin` world).
+pub fn exports_multi_command_interface(exports: &[String]) -> bool {
+    exports.iter().any(|e| {
+        e == "wacli:cli/multi-command@2.1.0"
+            || e == "wacli:cli/multi-command"
+            || e == "multi-command"
+    })
+}
+
+/// Check if a component exports the wacli:cli/pipe interface (`pipe-plugin` world).
+pub fn exports_pipe_interface(exports: &[String]) -> bool {
+    exports
+        .iter()
+        .any(|e| e == "wacli:cli/pipe@2.0.0" || e == "wacli:cli/pipe" || e == "pipe")
+}
+
 /// Scan the commands directory and return validated command info.
-pub fn scan_commands(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
+///
+/// A component without the command metadata section is an error, unless
+/// `allow_meta_exec` lets its metadata be read by running its `meta()`
+/// export (see [`metadata_without_section`]).
+pub fn scan_commands(commands_dir: &Path, allow_meta_exec: bool) -> Result<Vec<CommandInfo>> {
     if !commands_dir.exists() {
         bail!("commands directory not found: {}", commands_dir.display());
     }
@@ -267,8 +392,15 @@ pub fn scan_commands(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
 
     let mut commands = Vec::new();
     let mut seen = HashMap::new();
+    let limits = ScanLimits::from_env()?;
 
-    collect_commands(commands_dir, &mut commands, &mut seen)?;
+    collect_commands(
+        commands_dir,
+        &limits,
+        allow_meta_exec,
+        &mut commands,
+        &mut seen,
+    )?;
 
     // Sort by name for deterministic output
     commands.sort_by(|a, b| a.name.cmp(&b.name));
@@ -284,7 +416,10 @@ pub fn scan_commands(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
 ///
 /// Unlike `scan_commands`, this returns an empty list when the directory is
 /// missing or contains no `*.component.wasm` files.
-pub fn scan_commands_optional(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
+pub fn scan_commands_optional(
+    commands_dir: &Path,
+    allow_meta_exec: bool,
+) -> Result<Vec<CommandInfo>> {
     if !commands_dir.exists() {
         return Ok(Vec::new());
     }
@@ -298,7 +433,14 @@ pub fn scan_commands_optional(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
 
     let mut commands = Vec::new();
     let mut seen = HashMap::new();
-    collect_commands(commands_dir, &mut commands, &mut seen)?;
+    let limits = ScanLimits::from_env()?;
+    collect_commands(
+        commands_dir,
+        &limits,
+        allow_meta_exec,
+        &mut commands,
+        &mut seen,
+    )?;
 
     // Sort by name for deterministic output
     commands.sort_by(|a, b| a.name.cmp(&b.name));
@@ -308,6 +450,8 @@ pub fn scan_commands_optional(commands_dir: &Path) -> Result<Vec<CommandInfo>> {
 
 fn collect_commands(
     dir: &Path,
+    limits: &ScanLimits,
+    allow_meta_exec: bool,
     out: &mut Vec<CommandInfo>,
     seen: &mut HashMap<String, PathBuf>,
 ) -> Result<()> {
@@ -319,7 +463,7 @@ fn collect_commands(
         let path = entry.path();
 
         if path.is_dir() {
-            collect_commands(&path, out, seen)?;
+            collect_commands(&path, limits, allow_meta_exec, out, seen)?;
             continue;
         }
 
@@ -361,7 +505,10 @@ fn collect_commands(
         let wasm_bytes = fs::read(&path)
             .with_context(|| format!("failed to read component: {}", path.display()))?;
 
-        let (exports, imports) = match analyze_wasm(&wasm_bytes)? {
+        let deadline = limits.deadline();
+        let (exports, imports) = match analyze_wasm(&wasm_bytes, &deadline)
+            .map_err(|e| anyhow!("{}: {e:#}", path.display()))?
+        {
             WasmKind::CoreModule => {
                 bail!(
                     "'{}' is a core WebAssembly module, not a component.\n\
@@ -374,6 +521,25 @@ fn collect_commands(
             WasmKind::Component { exports, imports } => (exports, imports),
         };
 
+        if exports_multi_command_interface(&exports) {
+            let bundled = bundled_commands(&path, &name, imports, &wasm_bytes, limits, &deadline)?;
+            for cmd in bundled {
+                // The component's own name was recorded above.
+                if cmd.name != name {
+                    if let Some(prev) = seen.get(&cmd.name) {
+                        bail!(
+                            "duplicate command name '{}':\n  {}\n  {}",
+                            cmd.name,
+                            prev.display(),
+                            path.display()
+                        );
+                    }
+                    seen.insert(cmd.name.clone(), path.clone());
+                }
+                out.push(cmd);
+            }
+            continue;
+        }
         if !exports_command_interface(&exports) {
             bail!(
                 "'{}' does not export wacli:cli/command interface",
@@ -381,21 +547,18 @@ fn collect_commands(
             );
         }
 
-        let Some(metadata) = extract_command_metadata(&wasm_bytes).with_context(|| {
-            format!("failed to extract command metadata from {}", path.display())
-        })?
-        else {
-            bail!(
-                "missing embedded command metadata in {}\n\
-\n\
-Expected a WASM custom section named '{}'.\n\
-\n\
-Fix:\n\
-- Update your plugin to use `wacli_cdk::declare_command_metadata!(...)` (and rebuild the component).",
-                path.display(),
-                wacli_metadata::COMMAND_METADATA_SECTION
-            );
-        };
+        let metadata =
+            match extract_command_metadata(&wasm_bytes, limits, &deadline).map_err(|e| {
+                anyhow!(
+                    "failed to extract command metadata from {}: {e:#}",
+                    path.display()
+                )
+            })? {
+                Some(metadata) => metadata,
+                None => {
+                    metadata_without_section(&wasm_bytes, &name, &path, limits, allow_meta_exec)?
+                }
+            };
 
         if metadata.command_meta.name != name {
             bail!(
@@ -427,17 +590,499 @@ The command name is derived from the `.component.wasm` file name and must match
             );
         }
 
+        limits.warn_on_large_lists(&metadata, &path);
+
         out.push(CommandInfo {
             name,
             path,
             imports,
             metadata,
+            group: None,
+            bundle: None,
         });
     }
 
     Ok(())
 }
 
+/// The commands of the multi-command component `bundle` at `path`, one per
+/// payload in its metadata section.
+///
+/// Unlike a single command, each name comes from its metadata; there is no
+/// `meta()` fallback for a component without the section.
+fn bundled_commands(
+    path: &Path,
+    bundle: &str,
+    imports: Vec<String>,
+    wasm_bytes: &[u8],
+    limits: &ScanLimits,
+    deadline: &Deadline,
+) -> Result<Vec<CommandInfo>> {
+    let metas = extract_bundled_command_metadata(wasm_bytes, limits, deadline).map_err(|e| {
+        anyhow!(
+            "failed to extract command metadata from {}: {e:#}",
+            path.display()
+        )
+    })?;
+    if metas.is_empty() {
+        bail!(
+            "missing embedded command metadata in {}\n\
+\n\
+A multi-command component needs a `wacli_cdk::declare_command_metadata!(...)` for each of its commands.",
+            path.display()
+        );
+    }
+
+    let mut names = HashSet::new();
+    let mut out = Vec::new();
+    for metadata in metas {
+        let name = metadata.command_meta.name.clone();
+        if !is_valid_command_name(&name) {
+            bail!(
+                "invalid command name '{}': must match pattern [a-z][a-z0-9-]* (file: {})",
+                name,
+                path.display()
+            );
+        }
+        if !names.insert(name.clone()) {
+            bail!("{} declares command '{}' twice", path.display(), name);
+        }
+        if let Some(schema) = metadata.command_schema.as_ref()
+            && schema.name != name
+        {
+            bail!(
+                "command schema name mismatch for {}\n\
+\n\
+meta.name:   {}\n\
+schema.name: {}",
+                path.display(),
+                name,
+                schema.name
+            );
+        }
+        limits.warn_on_large_lists(&metadata, path);
+        out.push(CommandInfo {
+            name,
+            path: path.to_path_buf(),
+            imports: imports.clone(),
+            metadata,
+            group: None,
+            bundle: Some(bundle.to_string()),
+        });
+    }
+    Ok(out)
+}
+
+/// Metadata for a command component that has no `COMMAND_METADATA_SECTION`.
+///
+/// Without `allow_meta_exec` this is an error. With it, the component is
+/// instantiated in a sandbox and its `meta()` export called; the result has
+/// the same shape as a section written by `declare_command_metadata!`
+/// (`command-meta` plus a schema derived from it). A component whose `meta()`
+/// cannot be called only gets a warning and a name-only schema, so one old
+/// command does not fail the whole build; one whose `meta()` reports another
+/// name is warned about and keeps the name it is registered under.
+fn metadata_without_section(
+    wasm_bytes: &[u8],
+    name: &str,
+    path: &Path,
+    limits: &ScanLimits,
+    allow_meta_exec: bool,
+) -> Result<CommandMetadataV1> {
+    if !allow_meta_exec {
+        bail!(
+            "missing embedded command metadata in {}\n\
+\n\
+Expected a WASM custom section named '{}'.\n\
+\n\
+Fix:\n\
+- Update your plugin to use `wacli_cdk::declare_command_metadata!(...)` (and rebuild the component).\n\
+- Or pass --allow-meta-exec (or set build.allowMetaExec) to read it by running the component's meta().",
+            path.display(),
+            wacli_metadata::COMMAND_METADATA_SECTION
+        );
+    }
+
+    let meta = match call_command_meta(wasm_bytes, limits.meta_fuel) {
+        Ok(meta) if meta.name != name => {
+            tracing::warn!(
+                "meta() of command '{name}' ({}) reports the name '{}'; using '{name}'",
+                path.display(),
+                meta.name
+            );
+            CommandMeta {
+                name: name.to_string(),
+                ..meta
+            }
+        }
+        Ok(meta) => {
+            tracing::info!("read metadata of command '{name}' from its meta() export");
+            meta
+        }
+        Err(e) => {
+            tracing::warn!(
+                "command '{name}' ({}) has no embedded metadata and its meta() could not be \
+                 called: {e:#}; building it without args, aliases or help",
+                path.display()
+            );
+            CommandMeta {
+                name: name.to_string(),
+                ..Default::default()
+            }
+        }
+    };
+    let schema = CommandSchema::from_meta(&meta);
+    Ok(CommandMetadataV1::new(meta, Some(schema)))
+}
+
+#[cfg(feature = "runtime")]
+fn call_command_meta(wasm_bytes: &[u8], fuel: u64) -> Result<CommandMeta> {
+    plugin_loader::call_command_meta(wasm_bytes, fuel)
+}
+
+#[cfg(not(feature = "runtime"))]
+fn call_command_meta(_wasm_bytes: &[u8], _fuel: u64) -> Result<CommandMeta> {
+    bail!("this wacli was built without the `runtime` feature")
+}
+
+/// Assign commands to the groups declared in `build.groups`.
+///
+/// Group names follow the command name rules and must not collide with
+/// top-level command names or aliases. Each command may belong to at most one
+/// group, and every member must be among `commands`.
+pub fn assign_groups(commands: &mut [CommandInfo], groups: &[CommandGroup]) -> Result<()> {
+    let mut group_names: HashSet<&str> = HashSet::new();
+    for group in groups {
+        if !is_valid_command_name(&group.name) {
+            bail!(
+                "invalid group name '{}': must match pattern [a-z][a-z0-9-]*",
+                group.name
+            );
+        }
+        if !group_names.insert(group.name.as_str()) {
+            bail!("duplicate group name '{}'", group.name);
+        }
+        if group.commands.is_empty() {
+            bail!("group '{}' has no commands", group.name);
+        }
+
+        for member in &group.commands {
+            let Some(cmd) = commands.iter_mut().find(|c| &c.name == member) else {
+                bail!(
+                    "group '{}' references unknown command '{}'",
+                    group.name,
+                    member
+                );
+            };
+            if let Some(prev) = &cmd.group {
+                bail!(
+                    "command '{}' is listed in both group '{}' and group '{}'",
+                    member,
+                    prev,
+                    group.name
+                );
+            }
+            cmd.group = Some(group.name.clone());
+        }
+    }
+
+    for cmd in commands.iter().filter(|c| c.group.is_none()) {
+        let meta = &cmd.metadata.command_meta;
+        let names = std::iter::once(&cmd.name)
+            .chain(&meta.aliases)
+            .chain(&meta.hidden_aliases);
+        for name in names {
+            if group_names.contains(name.as_str()) {
+                bail!("group name '{}' collides with command '{}'", name, cmd.name);
+            }
+        }
+    }
+
+    // The commands of a multi-command component share all three.
+    let mut claimed: HashMap<(&str, String), String> = HashMap::new();
+    for cmd in components(commands) {
+        let names = [
+            ("registry interface", cmd.registry_import()),
+            ("WAC instance", cmd.var_name()),
+            ("package", cmd.package_name()),
+        ];
+        for (what, key) in names {
+            if let Some(prev) = claimed.insert((what, key.clone()), cmd.registry_name()) {
+                bail!(
+                    "commands '{}' and '{}' both map to {what} '{key}'; rename one of them",
+                    prev,
+                    cmd.registry_name()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report commands whose embedded metadata lacks a `command_schema`.
+///
+/// With `require` set this fails the build, listing every offending component;
+/// otherwise each one gets a warning.
+pub fn check_schemas(commands: &[CommandInfo], require: bool) -> Result<()> {
+    let missing: Vec<&CommandInfo> = commands.iter().filter(|c| !c.has_schema()).collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if require {
+        let list: String = missing
+            .iter()
+            .map(|c| format!("  {}: {}\n", c.registry_name(), c.path.display()))
+            .collect();
+        bail!(
+            "{} command(s) have no embedded command schema:\n{list}\n\
+Hint: older wacli-cdk versions of `declare_command_metadata!` did not emit `command_schema`;\n\
+rebuild these components against wacli-cdk {} (or drop --require-schema / build.requireSchema).",
+            missing.len(),
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    for cmd in missing {
+        tracing::warn!(
+            "command '{}' ({}) has no embedded command schema; env, possible-values and \
+             conflicts validation is skipped (rebuild it with a current wacli-cdk)",
+            cmd.registry_name(),
+            cmd.path.display()
+        );
+    }
+    Ok(())
+}
+
+/// A problem with one command's embedded metadata, found by
+/// [`metadata_problems`].
+#[derive(Debug)]
+pub enum MetadataProblem<'a> {
+    /// [`CommandMetadataV1::validate`] failed.
+    Invalid(ValidationError),
+    /// An alias is also the name or an alias of `other`.
+    AliasClash {
+        /// `aliases` or `hidden-aliases`.
+        field: &'static str,
+        alias: &'a str,
+        /// `the name` or `an alias`.
+        role: &'static str,
+        other: &'a CommandInfo,
+    },
+}
+
+impl fmt::Display for MetadataProblem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(err) => err.fmt(f),
+            Self::AliasClash {
+                field,
+                alias,
+                role,
+                other,
+            } => write!(
+                f,
+                "command-meta.{field}: '{alias}' is also {role} of command '{}' ({})",
+                other.registry_name(),
+                other.path.display()
+            ),
+        }
+    }
+}
+
+/// Every problem with the embedded metadata of `commands`, paired with the
+/// command it was found in: inconsistent metadata (see
+/// [`CommandMetadataV1::validate`]) and aliases claimed by two commands.
+pub fn metadata_problems(commands: &[CommandInfo]) -> Vec<(&CommandInfo, MetadataProblem<'_>)> {
+    let mut problems = Vec::new();
+    for cmd in commands {
+        if let Err(err) = cmd.metadata.validate() {
+            problems.push((cmd, MetadataProblem::Invalid(err)));
+        }
+    }
+
+    // Aliases (hidden or not) share the dispatch namespace with command names
+    // (within a group).
+    let owners: HashMap<String, &CommandInfo> =
+        commands.iter().map(|c| (c.registry_name(), c)).collect();
+    let mut alias_owners: HashMap<String, &CommandInfo> = HashMap::new();
+    for cmd in commands {
+        let meta = &cmd.metadata.command_meta;
+        let aliases = meta
+            .aliases
+            .iter()
+            .map(|a| ("aliases", a))
+            .chain(meta.hidden_aliases.iter().map(|a| ("hidden-aliases", a)));
+        for (field, alias) in aliases {
+            // Commands of one multi-command component share a path, so
+            // compare them by name.
+            let key = cmd.qualify(alias);
+            let is_other = |other: &&CommandInfo| other.registry_name() != cmd.registry_name();
+            let clash = match owners.get(&key) {
+                Some(other) if is_other(other) => Some(("the name", *other)),
+                _ => alias_owners
+                    .insert(key, cmd)
+                    .filter(is_other)
+                    .map(|other| ("an alias", other)),
+            };
+            if let Some((role, other)) = clash {
+                problems.push((
+                    cmd,
+                    MetadataProblem::AliasClash {
+                        field,
+                        alias,
+                        role,
+                        other,
+                    },
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// Fail when a command's embedded metadata is inconsistent (see
+/// [`CommandMetadataV1::validate`]) or an alias is claimed by two commands.
+///
+/// These problems would otherwise only surface when the command runs. Every
+/// problem is reported, prefixed with the component it came from.
+pub fn check_metadata(commands: &[CommandInfo]) -> Result<()> {
+    let mut report = String::new();
+    for (cmd, problem) in metadata_problems(commands) {
+        let problem = problem.to_string().replace('\n', "\n  ");
+        report.push_str(&format!("  {}: {problem}\n", cmd.path.display()));
+    }
+
+    if !report.is_empty() {
+        bail!(
+            "invalid command metadata:\n{report}\n\
+Hint: fix the command's metadata declaration and rebuild the component\n\
+(or pass --no-validate-metadata to compose it anyway)."
+        );
+    }
+    Ok(())
+}
+
+/// Fail when an arg's `complete-command` names no command in the build.
+pub fn check_complete_commands(commands: &[CommandInfo]) -> Result<()> {
+    for cmd in commands {
+        let Some(schema) = cmd.metadata.command_schema.as_ref() else {
+            continue;
+        };
+        for arg in &schema.args {
+            let Some(target) = arg.complete_command.as_deref() else {
+                continue;
+            };
+            if !commands.iter().any(|c| c.registry_name() == target) {
+                bail!(
+                    "command '{}': arg '{}' has complete-command '{target}', which does not match any command",
+                    cmd.registry_name(),
+                    arg.name
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fail when commands were built against different versions of the same
+/// shared arg template (their recorded digests differ).
+pub fn check_shared_args(commands: &[CommandInfo]) -> Result<()> {
+    // template -> digest -> commands using it
+    let mut uses: BTreeMap<&str, BTreeMap<&str, Vec<String>>> = BTreeMap::new();
+    for cmd in commands {
+        let Some(schema) = cmd.metadata.command_schema.as_ref() else {
+            continue;
+        };
+        for shared in schema.args.iter().filter_map(|a| a.shared.as_ref()) {
+            uses.entry(&shared.name)
+                .or_default()
+                .entry(&shared.digest)
+                .or_default()
+                .push(cmd.registry_name());
+        }
+    }
+
+    let mut report = String::new();
+    for (template, digests) in &uses {
+        if digests.len() < 2 {
+            continue;
+        }
+        report.push_str(&format!("  {template}:\n"));
+        for (digest, names) in digests {
+            report.push_str(&format!("    {digest}: {}\n", names.join(", ")));
+        }
+    }
+    if !report.is_empty() {
+        bail!(
+            "shared arg templates differ between commands:\n{report}\n\
+Hint: rebuild these commands against the same shared args file."
+        );
+    }
+    Ok(())
+}
+
+/// Fail when `build.globalArgs` is invalid, or when a command (or one of its
+/// subcommands) declares a short or long flag that is also a global arg's:
+/// core takes global flags out of argv, so the command would never see it.
+pub fn check_global_args(globals: &[ArgSchema], commands: &[CommandInfo]) -> Result<()> {
+    if globals.is_empty() {
+        return Ok(());
+    }
+    let issues = wacli_metadata::global_arg_issues(globals);
+    if !issues.is_empty() {
+        bail!("invalid build.globalArgs:\n  - {}", issues.join("\n  - "));
+    }
+
+    let mut owners: HashMap<String, &str> = HashMap::new();
+    for arg in globals {
+        owners.extend(arg_flags(arg).map(|flag| (flag, arg.name.as_str())));
+    }
+
+    let mut report = String::new();
+    for cmd in commands {
+        let schema_owned;
+        let schema = match cmd.metadata.command_schema.as_ref() {
+            Some(schema) => schema,
+            None => {
+                schema_owned = CommandSchema::from_meta(&cmd.metadata.command_meta);
+                &schema_owned
+            }
+        };
+        let mut stack = vec![(cmd.registry_name().replace('/', " "), schema)];
+        while let Some((path, schema)) = stack.pop() {
+            for arg in &schema.args {
+                for flag in arg_flags(arg) {
+                    if let Some(global) = owners.get(&flag) {
+                        report.push_str(&format!(
+                            "  {path}: '{flag}' of arg '{}' is also global arg '{global}'\n",
+                            arg.name
+                        ));
+                    }
+                }
+            }
+            for sub in schema.subcommands.iter().rev() {
+                stack.push((format!("{path} {}", sub.name), sub));
+            }
+        }
+    }
+    if !report.is_empty() {
+        bail!(
+            "command flags collide with build.globalArgs:\n{report}\n\
+Hint: rename the command's flag, or the global arg in build.globalArgs."
+        );
+    }
+    Ok(())
+}
+
+/// `arg`'s short and long flags in their `-v`/`--verbose` form.
+fn arg_flags(arg: &ArgSchema) -> impl Iterator<Item = String> + '_ {
+    let short = arg.short.as_deref().map(wacli_metadata::normalize_short);
+    let long = arg.long.as_deref().map(wacli_metadata::normalize_long);
+    short.into_iter().chain(long)
+}
+
 /// Verify that required default components exist.
 pub fn verify_defaults(defaults_dir: &Path) -> Result<(PathBuf, PathBuf)> {
     let host_path = defaults_dir.join("host.component.wasm");
@@ -457,6 +1102,7 @@ pub fn verify_defaults(defaults_dir: &Path) -> Result<(PathBuf, PathBuf)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
 
     #[test]
     fn test_valid_command_names() {
@@ -494,9 +1140,367 @@ mod tests {
                     ..Default::default()
                 },
                 command_schema: None,
+                self_tests: Vec::new(),
+                cdk_version: None,
             },
+            group: None,
+            bundle: None,
         };
-        assert_eq!(cmd.var_name(), "my_command");
+        assert_eq!(cmd.var_name(), "my-command");
+    }
+
+    fn command(name: &str, aliases: &[&str]) -> CommandInfo {
+        CommandInfo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("{name}.component.wasm")),
+            imports: Vec::new(),
+            metadata: CommandMetadataV1 {
+                format_version: 1,
+                command_meta: wacli_metadata::CommandMeta {
+                    name: name.to_string(),
+                    aliases: aliases.iter().map(|s| s.to_string()).collect(),
+                    ..Default::default()
+                },
+                command_schema: None,
+                self_tests: Vec::new(),
+                cdk_version: None,
+            },
+            group: None,
+            bundle: None,
+        }
+    }
+
+    fn group(name: &str, commands: &[&str]) -> CommandGroup {
+        CommandGroup {
+            name: name.to_string(),
+            commands: commands.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn assign_groups_sets_registry_and_interface_names() {
+        let mut commands = vec![command("greet", &[]), command("migrate", &["up"])];
+        assign_groups(&mut commands, &[group("db", &["migrate"])]).unwrap();
+
+        assert_eq!(commands[0].group, None);
+        assert_eq!(commands[0].registry_name(), "greet");
+        assert_eq!(commands[1].group.as_deref(), Some("db"));
+        assert_eq!(commands[1].registry_name(), "db/migrate");
+        assert_eq!(commands[1].qualify("up"), "db/up");
+        assert_eq!(commands[1].interface_name(), "db-migrate");
+        assert_eq!(commands[1].var_name(), "db-migrate");
+        assert_eq!(commands[1].package_name(), "wacli:cmd-migrate");
+    }
+
+    #[test]
+    fn assign_groups_rejects_invalid_configurations() {
+        let cases: Vec<(Vec<CommandInfo>, Vec<CommandGroup>, &str)> = vec![
+            (
+                vec![command("db", &[]), command("migrate", &[])],
+                vec![group("db", &["migrate"])],
+                "collides with command 'db'",
+            ),
+            (
+                vec![command("greet", &["db"]), command("migrate", &[])],
+                vec![group("db", &["migrate"])],
+                "collides with command 'greet'",
+            ),
+            (
+                vec![command("migrate", &[])],
+                vec![group("db", &["seed"])],
+                "unknown command 'seed'",
+            ),
+            (
+                vec![command("migrate", &[])],
+                vec![group("db", &["migrate"]), group("ops", &["migrate"])],
+                "listed in both group 'db' and group 'ops'",
+            ),
+            (
+                vec![command("migrate", &[])],
+                vec![group("db", &["migrate"]), group("db", &[])],
+                "duplicate group name 'db'",
+            ),
+            (
+                vec![command("migrate", &[])],
+                vec![group("Db", &["migrate"])],
+                "invalid group name 'Db'",
+            ),
+            (
+                vec![command("migrate", &[])],
+                vec![group("db", &[])],
+                "group 'db' has no commands",
+            ),
+            (
+                vec![command("db-migrate", &[]), command("migrate", &[])],
+                vec![group("db", &["migrate"])],
+                "registry interface 'db-migrate-command'",
+            ),
+        ];
+
+        for (mut commands, groups, expected) in cases {
+            let err = assign_groups(&mut commands, &groups)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "expected '{expected}' in: {err}");
+        }
+    }
+
+    #[test]
+    fn check_schemas_fails_only_in_strict_mode() {
+        let mut with_schema = command("greet", &[]);
+        with_schema.metadata.command_schema = Some(wacli_metadata::CommandSchema {
+            name: "greet".to_string(),
+            ..Default::default()
+        });
+        let mut legacy = command("legacy", &[]);
+        legacy.group = Some("old".to_string());
+
+        assert!(with_schema.has_schema());
+        assert!(!legacy.has_schema());
+        check_schemas(&[with_schema.clone()], true).unwrap();
+        check_schemas(&[with_schema.clone(), legacy.clone()], false).unwrap();
+
+        let err = check_schemas(&[with_schema, legacy], true)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with(
+                "1 command(s) have no embedded command schema:\n  old/legacy: legacy.component.wasm\n"
+            ),
+            "{err}"
+        );
+        assert!(err.contains("wacli-cdk"), "{err}");
+    }
+
+    #[test]
+    fn complete_commands_must_name_a_command() {
+        let mut show = command("show", &[]);
+        show.metadata.command_schema = Some(wacli_metadata::CommandSchema {
+            name: "show".to_string(),
+            args: vec![
+                wacli_metadata::ArgSchemaBuilder::new("format")
+                    .long("format")
+                    .complete_command("pipes/list")
+                    .build(),
+            ],
+            ..Default::default()
+        });
+        let mut list = command("list", &[]);
+        list.group = Some("pipes".to_string());
+
+        check_complete_commands(&[show.clone(), list]).unwrap();
+        let err = check_complete_commands(&[show]).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "command 'show': arg 'format' has complete-command 'pipes/list', which does not match any command"
+        );
+    }
+
+    fn with_shared(name: &str, template: &str, digest: &str) -> CommandInfo {
+        let mut cmd = command(name, &[]);
+        cmd.metadata.command_schema = Some(wacli_metadata::CommandSchema {
+            name: name.to_string(),
+            args: vec![
+                wacli_metadata::ArgSchemaBuilder::new(template)
+                    .long(template)
+                    .shared(wacli_metadata::SharedArgRef {
+                        name: template.to_string(),
+                        digest: digest.to_string(),
+                    })
+                    .build(),
+            ],
+            ..Default::default()
+        });
+        cmd
+    }
+
+    #[test]
+    fn shared_arg_drift_fails_the_build() {
+        let a = with_shared("export", "output", "fnv1a64:aaaa");
+        let b = with_shared("report", "output", "fnv1a64:aaaa");
+        let c = with_shared("dump", "output", "fnv1a64:bbbb");
+        let other = with_shared("sync", "verbose", "fnv1a64:cccc");
+
+        check_shared_args(&[a.clone(), b.clone(), other.clone(), command("plain", &[])]).unwrap();
+        let err = check_shared_args(&[a, b, c, other])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "shared arg templates differ between commands:\n  \
+             output:\n    fnv1a64:aaaa: export, report\n    fnv1a64:bbbb: dump\n\n\
+             Hint: rebuild these commands against the same shared args file."
+        );
+    }
+
+    #[test]
+    fn check_metadata_names_components_and_fields() {
+        let mut commands = vec![
+            command("greet", &["hi"]),
+            command("hello", &["hi"]),
+            command("list", &["greet"]),
+            command("migrate", &["hi"]),
+        ];
+        assign_groups(&mut commands, &[group("db", &["migrate"])]).unwrap();
+        commands[2].metadata.command_meta.hidden_aliases = vec!["hello".to_string()];
+        commands[0].metadata.command_meta.args = vec![wacli_metadata::ArgDef {
+            name: "loud".to_string(),
+            long: Some("--loud".to_string()),
+            ..Default::default()
+        }];
+        let mut schema =
+            wacli_metadata::CommandSchema::from_meta(&commands[0].metadata.command_meta);
+        schema.args[0].requires = vec!["quiet".to_string()];
+        commands[0].metadata.command_schema = Some(schema);
+        assert!(check_metadata(&commands[1..2]).is_ok());
+
+        let err = check_metadata(&commands).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "invalid command metadata:\n  \
+             greet.component.wasm: invalid command metadata for 'greet':\n    \
+             - 'loud' requires unknown arg 'quiet'\n  \
+             hello.component.wasm: command-meta.aliases: 'hi' is also an alias of command 'greet' (greet.component.wasm)\n  \
+             list.component.wasm: command-meta.aliases: 'greet' is also the name of command 'greet' (greet.component.wasm)\n  \
+             list.component.wasm: command-meta.hidden-aliases: 'hello' is also the name of command 'hello' (hello.component.wasm)\n\n\
+             Hint: fix the command's metadata declaration and rebuild the component\n\
+             (or pass --no-validate-metadata to compose it anyway)."
+        );
+    }
+
+    fn bundled(name: &str, aliases: &[&str]) -> CommandInfo {
+        let mut cmd = command(name, aliases);
+        cmd.path = PathBuf::from("textkit.component.wasm");
+        cmd.bundle = Some("textkit".to_string());
+        cmd
+    }
+
+    #[test]
+    fn scan_expands_multi_command_components() {
+        let dir = std::env::temp_dir().join(format!("wacli-scan-multi-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata");
+        for file in ["textkit.component.wasm", "greet.component.wasm"] {
+            fs::copy(testdata.join(file), dir.join(file)).unwrap();
+        }
+
+        let commands = scan_commands(&dir, false).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["greet", "shout", "whisper"]);
+        let shout = &commands[1];
+        assert_eq!(shout.bundle.as_deref(), Some("textkit"));
+        assert_eq!(shout.metadata.command_meta.aliases, ["yell"]);
+        assert_eq!(shout.package_name(), "wacli:cmd-textkit");
+        assert_eq!(shout.var_name(), "textkit");
+        assert_eq!(shout.registry_import(), "textkit-multi-command");
+        assert_eq!(commands[0].registry_import(), "greet-command");
+        let instances: Vec<String> = components(&commands).map(CommandInfo::var_name).collect();
+        assert_eq!(instances, ["greet", "textkit"]);
+
+        // Bundled commands clash with the names of other components' commands.
+        fs::copy(
+            testdata.join("textkit.component.wasm"),
+            dir.join("shout.component.wasm"),
+        )
+        .unwrap();
+        let err = scan_commands(&dir, false).unwrap_err().to_string();
+        assert!(err.starts_with("duplicate command name 'shout':"), "{err}");
+        // And build.commands entries cannot be multi-command components.
+        let err = inspect_command_component(&dir.join("textkit.component.wasm"), false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.ends_with(
+                "is a multi-command component; those are only read from the commands directory"
+            ),
+            "{err}"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn multi_command_components_are_checked_as_a_whole() {
+        // One instance per component; it must not collide with another one.
+        let mut commands = vec![bundled("shout", &[]), bundled("whisper", &[])];
+        assign_groups(&mut commands, &[group("quiet", &["whisper"])]).unwrap();
+        assert_eq!(commands[1].registry_name(), "quiet/whisper");
+
+        let mut commands = vec![bundled("shout", &[]), command("textkit", &[])];
+        let err = assign_groups(&mut commands, &[]).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "commands 'shout' and 'textkit' both map to WAC instance 'textkit'; rename one of them"
+        );
+        let mut commands = vec![bundled("shout", &[]), command("textkit", &[])];
+        let err = assign_groups(&mut commands, &[group("db", &["textkit"])])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "commands 'shout' and 'db/textkit' both map to package 'wacli:cmd-textkit'; rename one of them"
+        );
+
+        // Aliases clash across the commands of one component too.
+        let commands = vec![
+            bundled("shout", &["loud", "whisper"]),
+            bundled("whisper", &["loud"]),
+        ];
+        let err = check_metadata(&commands).unwrap_err().to_string();
+        assert!(
+            err.starts_with(
+                "invalid command metadata:\n  \
+                 textkit.component.wasm: command-meta.aliases: 'whisper' is also the name of command 'whisper' (textkit.component.wasm)\n  \
+                 textkit.component.wasm: command-meta.aliases: 'loud' is also an alias of command 'shout' (textkit.component.wasm)\n"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn check_global_args_reports_colliding_command_flags() {
+        let globals = [
+            ArgSchemaBuilder::new("verbose").short("v").long("verbose"),
+            ArgSchemaBuilder::new("color")
+                .long("color")
+                .takes_value(true),
+        ]
+        .map(ArgSchemaBuilder::build);
+        let mut commands = vec![command("deploy", &[]), command("greet", &[])];
+        commands[0].metadata = CommandMetaBuilder::new("deploy")
+            .arg(ArgSchemaBuilder::new("message").short("m"))
+            .subcommand(
+                CommandMetaBuilder::new("rollback")
+                    .arg(ArgSchemaBuilder::new("color").long("color")),
+            )
+            .build();
+        commands[1].metadata.command_meta.args = vec![wacli_metadata::ArgDef {
+            name: "volume".to_string(),
+            short: Some("-v".to_string()),
+            ..Default::default()
+        }];
+        check_global_args(&globals, &[command("plain", &[])]).unwrap();
+        check_global_args(&[], &commands).unwrap();
+
+        let err = check_global_args(&globals, &commands)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "command flags collide with build.globalArgs:\n  \
+             deploy rollback: '--color' of arg 'color' is also global arg 'color'\n  \
+             greet: '-v' of arg 'volume' is also global arg 'verbose'\n\n\
+             Hint: rename the command's flag, or the global arg in build.globalArgs."
+        );
+
+        let err = check_global_args(&[ArgSchemaBuilder::new("file").build()], &[])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "invalid build.globalArgs:\n  - 'file' needs a short or long flag"
+        );
     }
 
     #[test]
@@ -512,7 +1516,11 @@ mod tests {
                     ..Default::default()
                 },
                 command_schema: None,
+                self_tests: Vec::new(),
+                cdk_version: None,
             },
+            group: None,
+            bundle: None,
         };
         assert_eq!(cmd.package_name(), "wacli:cmd-greet");
     }
@@ -0,0 +1,293 @@
+//! Package resolution for `wacli compose`.
+//!
+//! A package comes from, in order: an explicit `--dep PKG=PATH`, the deps
+//! directory, or the OCI registry for `--dep PKG=oci://REPO:TAG`. Registry
+//! packages are pinned in `wacli.lock` (`deps`) by manifest digest and cached
+//! under `.wacli/deps/<digest>/`, the same way `build.commands` entries are.
+
+use anyhow::{Context, Result, bail};
+use indexmap::IndexMap;
+use molt_registry_client::{OciWasmClient, sanitize_path_segment};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use wac_resolver::FileSystemPackageResolver;
+use wac_types::BorrowedPackageKey;
+
+use crate::lock::{LockFile, LockedDep};
+use crate::offline::Offline;
+
+/// Where a `--dep` points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepSource {
+    Path(PathBuf),
+    /// `oci://REPO:TAG` or `oci://REPO@DIGEST`.
+    Oci {
+        repo: String,
+        reference: String,
+    },
+}
+
+/// Parse a `--dep PKG=PATH` or `--dep PKG=oci://REPO:TAG` argument.
+pub fn parse_dep(s: &str) -> Result<(String, DepSource)> {
+    let (name, value) = s
+        .split_once('=')
+        .context("dependency format should be PKG=PATH or PKG=oci://REPO:TAG")?;
+    let (name, value) = (name.trim().to_string(), value.trim());
+    let Some(oci) = value.strip_prefix("oci://") else {
+        return Ok((name, DepSource::Path(PathBuf::from(value))));
+    };
+    let (repo, reference) = match oci.split_once('@') {
+        Some(split) => split,
+        // A `:` after the last `/` starts the tag (`host:5000/repo` is a repo).
+        None => match oci.rsplit_once(':') {
+            Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+            _ => bail!("dependency '{name}' needs a tag or digest: oci://{oci}:<TAG>"),
+        },
+    };
+    if repo.is_empty() || reference.is_empty() {
+        bail!("invalid registry reference for dependency '{name}': oci://{oci}");
+    }
+    let source = DepSource::Oci {
+        repo: repo.to_string(),
+        reference: reference.to_string(),
+    };
+    Ok((name, source))
+}
+
+/// Resolves the packages of a WAC document; see the module docs.
+pub struct DepResolver {
+    deps_dir: PathBuf,
+    paths: HashMap<String, PathBuf>,
+    registry: BTreeMap<String, (String, String)>,
+    cache_dir: PathBuf,
+    client: Option<OciWasmClient>,
+    offline: Offline,
+}
+
+impl DepResolver {
+    /// `base_dir` holds `wacli.lock` and the `.wacli/deps` cache. The
+    /// registry client is only created when some dependency needs it.
+    pub fn new(
+        deps_dir: &Path,
+        base_dir: &Path,
+        deps: Vec<(String, DepSource)>,
+        offline: bool,
+    ) -> Self {
+        let mut paths = HashMap::new();
+        let mut registry = BTreeMap::new();
+        for (name, source) in deps {
+            match source {
+                DepSource::Path(path) => {
+                    registry.remove(&name);
+                    paths.insert(name, path);
+                }
+                DepSource::Oci { repo, reference } => {
+                    paths.remove(&name);
+                    registry.insert(name, (repo, reference));
+                }
+            }
+        }
+        Self {
+            deps_dir: deps_dir.to_path_buf(),
+            paths,
+            registry,
+            cache_dir: base_dir.join(".wacli").join("deps"),
+            client: None,
+            offline: Offline::new(offline),
+        }
+    }
+
+    /// Resolves explicit paths and the deps directory.
+    pub fn filesystem(&self) -> FileSystemPackageResolver {
+        FileSystemPackageResolver::new(&self.deps_dir, self.paths.clone(), false)
+    }
+
+    /// Pull the registry packages among `keys` that [`Self::filesystem`]
+    /// did not find into `packages`. Packages found nowhere are left out;
+    /// registry failures are errors. New pins are written into `lock`, and
+    /// `lock_dirty` is set when it changed.
+    pub fn pull_missing<'a, S>(
+        &mut self,
+        keys: &IndexMap<BorrowedPackageKey<'a>, S>,
+        packages: &mut IndexMap<BorrowedPackageKey<'a>, Vec<u8>>,
+        lock: &mut LockFile,
+        lock_dirty: &mut bool,
+    ) -> Result<()> {
+        for key in keys.keys() {
+            if packages.contains_key(key) {
+                continue;
+            }
+            let Some((repo, reference)) = self.registry.get(key.name).cloned() else {
+                continue;
+            };
+            let Some(path) = self.pull(key.name, &repo, &reference, lock, lock_dirty)? else {
+                continue;
+            };
+            let bytes =
+                fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+            packages.insert(*key, bytes);
+        }
+        self.offline.check()
+    }
+
+    /// The cached file for `name`, pulled first if needed. `None` when it
+    /// is missing offline (recorded as a miss).
+    fn pull(
+        &mut self,
+        name: &str,
+        repo: &str,
+        reference: &str,
+        lock: &mut LockFile,
+        lock_dirty: &mut bool,
+    ) -> Result<Option<PathBuf>> {
+        let source = format!("{repo}:{reference}");
+        let locked = lock
+            .find_dep(name)
+            .filter(|d| d.repo == repo && d.reference == reference && !d.digest.trim().is_empty());
+        let (digest, layer_digest) = match locked {
+            Some(d) => (d.digest.trim().to_string(), d.layer_digest.clone()),
+            None if self.offline.enabled => {
+                self.offline.miss(format!("package {name}"), source, None);
+                return Ok(None);
+            }
+            None => {
+                let client = self.client()?;
+                let rt = runtime()?;
+                let (digest, layer) = rt
+                    .block_on(client.resolve_component_digests(repo, reference))
+                    .with_context(|| {
+                        format!("registry pull failed for package {name}: cannot resolve {source}")
+                    })?;
+                lock.set_dep(LockedDep {
+                    name: name.to_string(),
+                    repo: repo.to_string(),
+                    reference: reference.to_string(),
+                    digest: digest.clone(),
+                    layer_digest: Some(layer.clone()),
+                    extra: Default::default(),
+                });
+                *lock_dirty = true;
+                (digest, Some(layer))
+            }
+        };
+
+        let dest = cached_dep(&self.cache_dir, name, &digest);
+        let can_pull = !self.offline.enabled;
+        if crate::use_cached_component(&dest, layer_digest.as_deref(), can_pull)? {
+            tracing::info!("using cached package {name} from {repo}@{digest}");
+            return Ok(Some(dest));
+        }
+        if self.offline.enabled {
+            self.offline.miss(
+                format!("package {name}"),
+                format!("{repo}@{digest}"),
+                Some(&dest),
+            );
+            return Ok(None);
+        }
+        tracing::info!("pulling package {name} from registry {repo}@{digest}");
+        let client = self.client()?;
+        crate::registry_pull::pull_component_wasm_to_file(client, repo, &digest, &dest, false)
+            .with_context(|| {
+                format!("registry pull failed for package {name} ({repo}@{digest})")
+            })?;
+        Ok(Some(dest))
+    }
+
+    fn client(&mut self) -> Result<&OciWasmClient> {
+        if self.client.is_none() {
+            self.client = OciWasmClient::from_env()?;
+        }
+        self.client.as_ref().context(
+            "registry pull failed: MOLT_REGISTRY is not configured.\n\n\
+             Set MOLT_REGISTRY (and auth) to pull oci:// dependencies.",
+        )
+    }
+}
+
+/// Where the package `name` at manifest `digest` is cached.
+pub fn cached_dep(cache_dir: &Path, name: &str, digest: &str) -> PathBuf {
+    cache_dir
+        .join(sanitize_path_segment(digest))
+        .join(format!("{}.wasm", sanitize_path_segment(name)))
+}
+
+fn runtime() -> Result<tokio::runtime::Runtime> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialize async runtime")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oci(repo: &str, reference: &str) -> DepSource {
+        DepSource::Oci {
+            repo: repo.to_string(),
+            reference: reference.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_dep_reads_paths_and_registry_refs() {
+        assert_eq!(
+            parse_dep("wacli:host = deps/host.wasm").unwrap(),
+            (
+                "wacli:host".to_string(),
+                DepSource::Path(PathBuf::from("deps/host.wasm"))
+            )
+        );
+        assert_eq!(
+            parse_dep("ex:adder=oci://example/adder:0.1.0").unwrap().1,
+            oci("example/adder", "0.1.0")
+        );
+        assert_eq!(
+            parse_dep("ex:adder=oci://localhost:5000/adder@sha256:abc")
+                .unwrap()
+                .1,
+            oci("localhost:5000/adder", "sha256:abc")
+        );
+
+        for (arg, err) in [
+            ("ex:adder", "dependency format should be PKG=PATH"),
+            (
+                "ex:adder=oci://localhost:5000/adder",
+                "dependency 'ex:adder' needs a tag or digest",
+            ),
+            ("ex:adder=oci://:1", "invalid registry reference"),
+        ] {
+            let msg = parse_dep(arg).unwrap_err().to_string();
+            assert!(msg.starts_with(err), "{arg}: {msg}");
+        }
+    }
+
+    #[test]
+    fn later_deps_replace_earlier_ones() {
+        let resolver = DepResolver::new(
+            Path::new("deps"),
+            Path::new("/p"),
+            vec![
+                ("ex:a".to_string(), oci("example/a", "1")),
+                ("ex:a".to_string(), DepSource::Path(PathBuf::from("a.wasm"))),
+                ("ex:b".to_string(), DepSource::Path(PathBuf::from("b.wasm"))),
+                ("ex:b".to_string(), oci("example/b", "2")),
+            ],
+            false,
+        );
+        assert_eq!(resolver.paths.get("ex:a"), Some(&PathBuf::from("a.wasm")));
+        assert!(!resolver.registry.contains_key("ex:a"));
+        assert!(!resolver.paths.contains_key("ex:b"));
+        assert_eq!(
+            resolver.cache_dir,
+            Path::new("/p/.wacli/deps"),
+            "cache lives next to wacli.lock"
+        );
+        assert_eq!(
+            cached_dep(&resolver.cache_dir, "ex:b", "sha256:abc"),
+            Path::new("/p/.wacli/deps/sha256_abc/ex_b.wasm")
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Minimal dotenv parser for `wacli run --env-file`.
+//!
+//! Supported syntax, one `KEY=VALUE` per line:
+//! - blank lines and `#` comments are skipped; an `export ` prefix is allowed
+//! - unquoted values are trimmed and end at ` #` (inline comment)
+//! - `'single'` quotes are literal
+//! - `"double"` quotes understand `\n`, `\t`, `\r`, `\"` and `\\`
+//!
+//! Values are never expanded: `$VAR` and `$(cmd)` stay literal. Malformed
+//! lines are skipped and reported as warnings instead of failing the run.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Variables parsed from one file, in order.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct EnvFile {
+    pub vars: Vec<(String, String)>,
+    /// `(line number, message)` for each skipped line.
+    pub warnings: Vec<(usize, String)>,
+}
+
+/// Parse dotenv `contents`.
+pub fn parse_env_file(contents: &str) -> EnvFile {
+    let mut out = EnvFile::default();
+    for (index, raw) in contents.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(pair) => out.vars.push(pair),
+            Err(message) => out.warnings.push((index + 1, message)),
+        }
+    }
+    out
+}
+
+/// Read and parse each file in order, logging warnings with their location.
+/// Later files override earlier ones (the caller layers `vars` in order).
+pub fn load_env_files(paths: &[impl AsRef<Path>]) -> Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read env file: {}", path.display()))?;
+        let parsed = parse_env_file(&contents);
+        for (line, message) in parsed.warnings {
+            tracing::warn!("{}:{line}: {message}; line ignored", path.display());
+        }
+        vars.extend(parsed.vars);
+    }
+    Ok(vars)
+}
+
+fn parse_line(line: &str) -> Result<(String, String), String> {
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let Some((key, value)) = line.split_once('=') else {
+        return Err("expected KEY=VALUE".to_string());
+    };
+    let key = key.trim();
+    if !is_valid_key(key) {
+        return Err(format!("invalid variable name '{key}'"));
+    }
+    Ok((key.to_string(), parse_value(value.trim())?))
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_value(value: &str) -> Result<String, String> {
+    let (parsed, rest) = if let Some(body) = value.strip_prefix('\'') {
+        let end = body
+            .find('\'')
+            .ok_or_else(|| "unterminated single quote".to_string())?;
+        (body[..end].to_string(), &body[end + 1..])
+    } else if let Some(body) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = body.char_indices();
+        let mut end = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    end = Some(i);
+                    break;
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some(c @ ('"' | '\\')) => out.push(c),
+                    Some(c) => {
+                        out.push('\\');
+                        out.push(c);
+                    }
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        let end = end.ok_or_else(|| "unterminated double quote".to_string())?;
+        (out, &body[end + 1..])
+    } else {
+        let value = match value.find(" #").or_else(|| value.find("\t#")) {
+            Some(i) => &value[..i],
+            None => value,
+        };
+        return Ok(value.trim_end().to_string());
+    };
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("unexpected text after closing quote: '{rest}'"));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(contents: &str) -> Vec<(String, String)> {
+        let parsed = parse_env_file(contents);
+        assert_eq!(parsed.warnings, Vec::new(), "{contents}");
+        parsed.vars
+    }
+
+    fn pair(k: &str, v: &str) -> (String, String) {
+        (k.to_string(), v.to_string())
+    }
+
+    #[test]
+    fn parses_plain_quoted_and_commented_values() {
+        let contents = r#"
+# comment
+API_URL=https://api.example.com
+export TOKEN = abc # trailing comment
+EMPTY=
+HASH=a#b
+SINGLE='literal $HOME \n # kept'
+DOUBLE="line1\nline2 \"q\" \\ \x" # comment
+SPACED="  padded  "
+"#;
+        assert_eq!(
+            vars(contents),
+            vec![
+                pair("API_URL", "https://api.example.com"),
+                pair("TOKEN", "abc"),
+                pair("EMPTY", ""),
+                pair("HASH", "a#b"),
+                pair("SINGLE", "literal $HOME \\n # kept"),
+                pair("DOUBLE", "line1\nline2 \"q\" \\ \\x"),
+                pair("SPACED", "  padded  "),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_expand_variables_or_commands() {
+        assert_eq!(
+            vars("A=$HOME\nB=\"$(whoami)\"\nC=`id`\n"),
+            vec![
+                pair("A", "$HOME"),
+                pair("B", "$(whoami)"),
+                pair("C", "`id`")
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_lines_become_warnings_with_line_numbers() {
+        let parsed = parse_env_file(
+            "OK=1\nno equals sign\n1BAD=x\nQ=\"open\nS='open\nT=\"x\" junk\nLAST=2\n",
+        );
+        assert_eq!(parsed.vars, vec![pair("OK", "1"), pair("LAST", "2")]);
+        assert_eq!(
+            parsed.warnings,
+            vec![
+                (2, "expected KEY=VALUE".to_string()),
+                (3, "invalid variable name '1BAD'".to_string()),
+                (4, "unterminated double quote".to_string()),
+                (5, "unterminated single quote".to_string()),
+                (6, "unexpected text after closing quote: 'junk'".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! `build.commands` entries with a `git` source.
+//!
+//! The repository is cloned with the `git` CLI (so credentials and URL
+//! rewrites from the user's git config apply), the component file is copied
+//! out of the checkout, and the checkout is removed again. Copies are cached
+//! under `.wacli/git/<url>/<commit>/`, so a build pinned by `wacli.lock` only
+//! touches the network the first time.
+
+use anyhow::{Context, Result, bail};
+use molt_registry_client::sanitize_path_segment;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// A component fetched from a git repository.
+#[derive(Debug, Clone)]
+pub struct FetchedComponent {
+    /// Full commit hash the component was read from.
+    pub commit: String,
+    /// Cached copy of the component file.
+    pub path: PathBuf,
+}
+
+/// Where the component for `name` at `commit` is cached.
+pub fn cached_component(cache_dir: &Path, url: &str, commit: &str, name: &str) -> PathBuf {
+    cache_dir
+        .join(sanitize_path_segment(url))
+        .join(sanitize_path_segment(commit))
+        .join(format!("{name}.component.wasm"))
+}
+
+/// Clone `url`, check out `rev` and copy `file` (relative to the repository
+/// root) into the cache. Relative URLs are resolved against `base_dir`.
+pub fn fetch_component(
+    base_dir: &Path,
+    cache_dir: &Path,
+    url: &str,
+    rev: &str,
+    file: &Path,
+    name: &str,
+) -> Result<FetchedComponent> {
+    check_repo_path(file)?;
+
+    let repo_cache = cache_dir.join(sanitize_path_segment(url));
+    let checkout = repo_cache.join(format!("checkout-{}", std::process::id()));
+    if checkout.exists() {
+        fs::remove_dir_all(&checkout)
+            .with_context(|| format!("failed to remove directory: {}", checkout.display()))?;
+    }
+    fs::create_dir_all(&repo_cache)
+        .with_context(|| format!("failed to create directory: {}", repo_cache.display()))?;
+
+    let result = (|| {
+        let mut clone = Command::new("git");
+        clone
+            .args(["clone", "--quiet", "--no-checkout", "--", url])
+            .arg(&checkout)
+            .current_dir(base_dir);
+        run_git(clone).with_context(|| format!("failed to clone {url}"))?;
+
+        let commit = resolve_rev(&checkout, rev)
+            .with_context(|| format!("failed to resolve rev '{rev}' in {url}"))?;
+        let mut co = Command::new("git");
+        co.args(["checkout", "--quiet", "--detach", &commit])
+            .current_dir(&checkout);
+        run_git(co).with_context(|| format!("failed to check out {commit} from {url}"))?;
+
+        let src = checkout.join(file);
+        if !src.is_file() {
+            bail!("{} not found in {url} at {rev} ({commit})", file.display());
+        }
+        let dest = cached_component(cache_dir, url, &commit, name);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        fs::copy(&src, &dest)
+            .with_context(|| format!("failed to copy {} to {}", src.display(), dest.display()))?;
+        Ok(FetchedComponent { commit, path: dest })
+    })();
+
+    let _ = fs::remove_dir_all(&checkout);
+    result
+}
+
+/// The commit `rev` names: a commit, a tag, or a branch of the remote.
+fn resolve_rev(checkout: &Path, rev: &str) -> Result<String> {
+    for candidate in [rev.to_string(), format!("origin/{rev}")] {
+        let out = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("{candidate}^{{commit}}"))
+            .current_dir(checkout)
+            .output()
+            .context("failed to run git (is it installed?)")?;
+        if out.status.success() {
+            return Ok(String::from_utf8_lossy(&out.stdout).trim().to_string());
+        }
+    }
+    bail!("no such branch, tag or commit")
+}
+
+fn run_git(mut cmd: Command) -> Result<()> {
+    let out = cmd
+        .output()
+        .context("failed to run git (is it installed?)")?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        bail!("git exited with {}: {}", out.status, stderr.trim());
+    }
+    Ok(())
+}
+
+/// The component path must stay inside the checkout.
+fn check_repo_path(file: &Path) -> Result<()> {
+    let inside = file
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !inside || file.as_os_str().is_empty() {
+        bail!(
+            "git component path must be relative to the repository root: {}",
+            file.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_paths_must_stay_inside_the_checkout() {
+        assert!(check_repo_path(Path::new("greet.component.wasm")).is_ok());
+        assert!(check_repo_path(Path::new("./target/greet.wasm")).is_ok());
+        for bad in ["", "../greet.wasm", "/abs/greet.wasm", "a/../../b.wasm"] {
+            assert!(check_repo_path(Path::new(bad)).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn cache_layout_is_keyed_by_url_and_commit() {
+        let path = cached_component(
+            Path::new("/p/.wacli/git"),
+            "https://example.com/tool.git",
+            "0123abcd",
+            "tool",
+        );
+        assert!(path.starts_with("/p/.wacli/git"));
+        assert!(path.ends_with("0123abcd/tool.component.wasm"));
+    }
+}
@@ -0,0 +1,545 @@
+//! `wacli inspect`: report what a component is, from its exports and its
+//! wacli custom sections, including the `APP_PROVENANCE_SECTION` that
+//! `wacli build` embeds.
+//!
+//! A CLI composed by `wacli build` carries its provenance at the top level; a
+//! single command carries only its own `COMMAND_METADATA_SECTION`, and a pipe
+//! its optional `PIPE_METADATA_SECTION`.
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Parser;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use wacli_metadata::{
+    APP_PROVENANCE_SECTION, AppProvenanceV1, ArgSchema, COMMAND_METADATA_SECTION,
+    CommandMetadataV1, CommandSchema, PIPE_METADATA_SECTION, PipeMetadataV1, ProvenanceCommand,
+};
+use wasm_encoder::{CustomSection, Section};
+use wasmparser::Payload;
+
+use crate::command_metadata::{extract_all_command_metadata, extract_pipe_metadata};
+use crate::component_scan::{
+    CommandInfo, WasmKind, analyze_wasm, exports_command_interface, exports_pipe_interface,
+};
+use crate::registry_gen_wat::AppMeta;
+use crate::scan_limits::ScanLimits;
+
+#[derive(Debug, Parser)]
+pub struct InspectArgs {
+    /// Component to inspect (a composed CLI, a command or a pipe)
+    #[arg(value_name = "COMPONENT")]
+    component: PathBuf,
+
+    /// Print the raw section as JSON: the app provenance of a composed CLI, or
+    /// the embedded metadata of a command or pipe
+    #[arg(long)]
+    json: bool,
+}
+
+/// The wacli interface a component exports at the top level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum World {
+    Command,
+    Pipe,
+    Other,
+}
+
+/// Everything `inspect` read from a component.
+struct Report {
+    provenance: Option<AppProvenanceV1>,
+    world: World,
+    commands: Vec<CommandMetadataV1>,
+    pipe: Option<PipeMetadataV1>,
+}
+
+/// The provenance of a CLI about to be composed from `commands`.
+///
+/// `digests` maps registry command names to their locked manifest digest.
+pub fn app_provenance(
+    app: &AppMeta,
+    profile: Option<&str>,
+    commands: &[CommandInfo],
+    digests: &HashMap<String, String>,
+) -> AppProvenanceV1 {
+    let mut meta = AppProvenanceV1::new(&app.name, &app.version);
+    meta.wacli_version = env!("CARGO_PKG_VERSION").to_string();
+    meta.profile = profile.map(str::to_string);
+    meta.commands = commands
+        .iter()
+        .map(|cmd| ProvenanceCommand {
+            name: cmd.registry_name(),
+            version: cmd.metadata.command_meta.version.clone(),
+            digest: digests.get(&cmd.name).cloned(),
+        })
+        .collect();
+    meta
+}
+
+/// Append the provenance section to the encoded composition.
+pub fn embed_provenance(mut component: Vec<u8>, meta: &AppProvenanceV1) -> Vec<u8> {
+    let data = meta.to_json_bytes();
+    CustomSection {
+        name: APP_PROVENANCE_SECTION.into(),
+        data: data.as_slice().into(),
+    }
+    .append_to(&mut component);
+    component
+}
+
+/// Read the provenance section from the top level of a composed component;
+/// the components nested in it are not searched.
+fn read_provenance(bytes: &[u8]) -> Result<Option<AppProvenanceV1>> {
+    let mut depth = 0usize;
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        match payload.context("failed to parse WASM")? {
+            Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => depth += 1,
+            Payload::End(_) => depth = depth.saturating_sub(1),
+            Payload::CustomSection(reader)
+                if depth == 0 && reader.name() == APP_PROVENANCE_SECTION =>
+            {
+                return AppProvenanceV1::from_json(reader.data())
+                    .map(Some)
+                    .map_err(|e| anyhow!(e));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+pub fn inspect(args: InspectArgs) -> Result<()> {
+    let path = &args.component;
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let provenance = read_provenance(&bytes).with_context(|| path.display().to_string())?;
+
+    let limits = ScanLimits::from_env()?;
+    let world = match analyze_wasm(&bytes, &limits.deadline())
+        .with_context(|| format!("failed to inspect {}", path.display()))?
+    {
+        WasmKind::Component { exports, .. } if exports_command_interface(&exports) => {
+            World::Command
+        }
+        WasmKind::Component { exports, .. } if exports_pipe_interface(&exports) => World::Pipe,
+        _ => World::Other,
+    };
+
+    // A broken section is reported rather than fatal: it is often the reason
+    // to inspect the component in the first place.
+    let commands = match extract_all_command_metadata(&bytes, &limits) {
+        Ok(commands) => {
+            if world == World::Command && commands.is_empty() {
+                tracing::warn!(
+                    "{} exports wacli:cli/command but has no '{COMMAND_METADATA_SECTION}' section",
+                    path.display()
+                );
+            }
+            commands
+        }
+        Err(err) => {
+            tracing::warn!("{}: {err:#}", path.display());
+            Vec::new()
+        }
+    };
+    let pipe = match world {
+        World::Pipe => match extract_pipe_metadata(&bytes, &limits, &limits.deadline()) {
+            Ok(None) => {
+                tracing::warn!(
+                    "{} has no '{PIPE_METADATA_SECTION}' section (pipe options are not validated)",
+                    path.display()
+                );
+                None
+            }
+            Ok(meta) => meta,
+            Err(err) => {
+                tracing::warn!("{}: {err:#}", path.display());
+                None
+            }
+        },
+        _ => None,
+    };
+    let report = Report {
+        provenance,
+        world,
+        commands,
+        pipe,
+    };
+
+    if args.json {
+        let json = if let Some(meta) = &report.provenance {
+            serde_json::to_string_pretty(meta)?
+        } else if let Some(meta) = &report.pipe {
+            serde_json::to_string_pretty(meta)?
+        } else {
+            match report.commands.as_slice() {
+                [] => bail!(
+                    "{} has no app provenance, command or pipe metadata section",
+                    path.display()
+                ),
+                [meta] => serde_json::to_string_pretty(meta)?,
+                many => serde_json::to_string_pretty(many)?,
+            }
+        };
+        println!("{json}");
+        return Ok(());
+    }
+
+    print!("{}", describe(&report));
+    Ok(())
+}
+
+/// Human-readable summary of a component's wacli sections.
+fn describe(report: &Report) -> String {
+    if let Some(meta) = &report.provenance {
+        let mut out = format!("composed CLI: {}", meta.name);
+        if !meta.version.is_empty() {
+            out.push_str(&format!(" {}", meta.version));
+        }
+        out.push_str(&format!("\nbuilt with: wacli {}\n", meta.wacli_version));
+        if let Some(profile) = &meta.profile {
+            out.push_str(&format!("profile: {profile}\n"));
+        }
+        out.push_str("commands:\n");
+        let width = meta
+            .commands
+            .iter()
+            .map(|c| c.name.len())
+            .max()
+            .unwrap_or(0);
+        for cmd in &meta.commands {
+            let line = format!(
+                "  {:<width$}  {:<8}  {}",
+                cmd.name,
+                cmd.version,
+                cmd.digest.as_deref().unwrap_or("")
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        return out;
+    }
+
+    if report.world == World::Pipe {
+        return describe_pipe(report.pipe.as_ref());
+    }
+
+    match report.commands.as_slice() {
+        [] if report.world == World::Command => {
+            "command component (no readable metadata)\nexports: wacli:cli/command@2.0.0\n"
+                .to_string()
+        }
+        [] => "plain component (no wacli metadata)\n".to_string(),
+        [meta] => describe_command(meta, report.world),
+        many => {
+            let names: Vec<&str> = many.iter().map(|m| m.command_meta.name.as_str()).collect();
+            format!(
+                "composed component without app provenance (not built by `wacli build`, \
+                 or by an older wacli)\ncommands: {}\n",
+                names.join(", ")
+            )
+        }
+    }
+}
+
+fn describe_command(meta: &CommandMetadataV1, world: World) -> String {
+    let cmd = &meta.command_meta;
+    let mut out = format!("command component: {}", cmd.name);
+    if !cmd.version.is_empty() {
+        out.push_str(&format!(" {}", cmd.version));
+    }
+    out.push('\n');
+    out.push_str(match world {
+        World::Command => "exports: wacli:cli/command@2.0.0\n",
+        _ => "exports: no wacli:cli/command interface\n",
+    });
+    if !cmd.summary.is_empty() {
+        out.push_str(&format!("summary: {}\n", cmd.summary));
+    }
+    let schema = meta
+        .command_schema
+        .clone()
+        .unwrap_or_else(|| CommandSchema::from_meta(cmd));
+    if !schema.aliases.is_empty() {
+        out.push_str(&format!("aliases: {}\n", schema.aliases.join(", ")));
+    }
+    if !schema.hidden_aliases.is_empty() {
+        out.push_str(&format!(
+            "hidden aliases: {}\n",
+            schema.hidden_aliases.join(", ")
+        ));
+    }
+    if !schema.args.is_empty() {
+        out.push_str("args:\n");
+        out.push_str(&args_table(&schema.args));
+    }
+    if !schema.subcommands.is_empty() {
+        let names: Vec<&str> = schema.subcommands.iter().map(|s| s.name.as_str()).collect();
+        out.push_str(&format!("subcommands: {}\n", names.join(", ")));
+    }
+    out
+}
+
+fn describe_pipe(meta: Option<&PipeMetadataV1>) -> String {
+    let mut out = "pipe component\nexports: wacli:cli/pipe@2.0.0\n".to_string();
+    let Some(meta) = meta else {
+        return out;
+    };
+    for (label, value) in [
+        ("summary", meta.summary.clone()),
+        ("version", meta.version.clone()),
+        ("input", meta.input_types.join(", ")),
+        ("output", meta.output_type.clone()),
+    ] {
+        if !value.is_empty() {
+            out.push_str(&format!("{label}: {value}\n"));
+        }
+    }
+    if !meta.options.is_empty() {
+        out.push_str("options:\n");
+        out.push_str(&args_table(&meta.options));
+    }
+    out
+}
+
+/// One aligned line per arg: name, flags (or `<VALUE>` for positionals), and
+/// help followed by its constraints.
+fn args_table(args: &[ArgSchema]) -> String {
+    let rows: Vec<(&str, String, String)> = args
+        .iter()
+        .map(|arg| (arg.name.as_str(), arg_flags(arg), arg_help(arg)))
+        .collect();
+    let name_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0);
+    let flags_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for (name, flags, help) in rows {
+        let line = format!("  {name:<name_width$}  {flags:<flags_width$}  {help}");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn arg_flags(arg: &ArgSchema) -> String {
+    let value = arg
+        .value_name
+        .clone()
+        .unwrap_or_else(|| arg.name.to_uppercase());
+    let flags: Vec<&str> = [arg.short.as_deref(), arg.long.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    if flags.is_empty() {
+        return format!("<{value}>");
+    }
+    let mut out = flags.join(", ");
+    if arg.takes_value {
+        out.push_str(&format!(" <{value}>"));
+    }
+    out
+}
+
+fn arg_help(arg: &ArgSchema) -> String {
+    let mut notes = Vec::new();
+    if arg.required {
+        notes.push("required".to_string());
+    }
+    if let Some(value_type) = &arg.value_type {
+        notes.push(format!("type: {value_type}"));
+    }
+    if let Some(default) = &arg.default_value {
+        notes.push(format!("default: {default}"));
+    }
+    if let Some(env) = &arg.env {
+        notes.push(format!("env: {env}"));
+    }
+    if !arg.possible_values.is_empty() {
+        notes.push(format!("values: {}", arg.possible_values.join(", ")));
+    }
+    if let Some(group) = &arg.group {
+        notes.push(format!("group: {group}"));
+    }
+    if arg.hidden {
+        notes.push("hidden".to_string());
+    }
+    let mut out = arg.help.clone();
+    for note in notes {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(&format!("[{note}]"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wacli_metadata::CommandMeta;
+
+    fn command(name: &str, version: &str) -> CommandMetadataV1 {
+        CommandMetadataV1::new(
+            CommandMeta {
+                name: name.to_string(),
+                version: version.to_string(),
+                summary: format!("Run {name}"),
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    fn report(world: World, commands: Vec<CommandMetadataV1>) -> Report {
+        Report {
+            provenance: None,
+            world,
+            commands,
+            pipe: None,
+        }
+    }
+
+    #[test]
+    fn describe_composed_cli() {
+        let mut meta = AppProvenanceV1::new("example:my-cli", "0.1.0");
+        meta.wacli_version = "1.2.3".to_string();
+        meta.profile = Some("edge".to_string());
+        meta.commands = vec![
+            ProvenanceCommand {
+                name: "db/seed".to_string(),
+                version: "2.0.0".to_string(),
+                digest: Some("sha256:abc".to_string()),
+            },
+            ProvenanceCommand {
+                name: "greet".to_string(),
+                version: "0.1.0".to_string(),
+                digest: None,
+            },
+        ];
+        let composed = Report {
+            provenance: Some(meta),
+            ..report(World::Other, vec![command("greet", "0.1.0")])
+        };
+        assert_eq!(
+            describe(&composed),
+            "composed CLI: example:my-cli 0.1.0\n\
+             built with: wacli 1.2.3\n\
+             profile: edge\n\
+             commands:\n\
+             \x20 db/seed  2.0.0     sha256:abc\n\
+             \x20 greet    0.1.0\n"
+        );
+    }
+
+    #[test]
+    fn describe_components_without_provenance() {
+        assert_eq!(
+            describe(&report(World::Command, vec![command("greet", "0.1.0")])),
+            "command component: greet 0.1.0\n\
+             exports: wacli:cli/command@2.0.0\n\
+             summary: Run greet\n"
+        );
+        assert!(
+            describe(&report(World::Other, vec![command("a", ""), command("b", "")]))
+                .ends_with("without app provenance (not built by `wacli build`, or by an older wacli)\ncommands: a, b\n")
+        );
+        assert_eq!(
+            describe(&report(World::Other, vec![])),
+            "plain component (no wacli metadata)\n"
+        );
+        assert_eq!(
+            describe(&report(World::Command, vec![])),
+            "command component (no readable metadata)\nexports: wacli:cli/command@2.0.0\n"
+        );
+    }
+
+    #[test]
+    fn describe_command_lists_aliases_and_args() {
+        let mut meta = command("greet", "0.1.0");
+        let mut schema = CommandSchema::from_meta(&meta.command_meta);
+        schema.aliases = vec!["hi".to_string()];
+        schema.hidden_aliases = vec!["gr".to_string()];
+        schema.args = vec![
+            ArgSchema {
+                name: "name".to_string(),
+                help: "Who to greet".to_string(),
+                required: true,
+                ..Default::default()
+            },
+            ArgSchema {
+                name: "style".to_string(),
+                short: Some("-s".to_string()),
+                long: Some("--style".to_string()),
+                value_name: Some("STYLE".to_string()),
+                takes_value: true,
+                default_value: Some("plain".to_string()),
+                possible_values: vec!["plain".to_string(), "loud".to_string()],
+                group: Some("look".to_string()),
+                ..Default::default()
+            },
+        ];
+        meta.command_schema = Some(schema);
+        assert_eq!(
+            describe(&report(World::Command, vec![meta])),
+            "command component: greet 0.1.0\n\
+             exports: wacli:cli/command@2.0.0\n\
+             summary: Run greet\n\
+             aliases: hi\n\
+             hidden aliases: gr\n\
+             args:\n\
+             \x20 name   <NAME>               Who to greet [required]\n\
+             \x20 style  -s, --style <STYLE>  [default: plain] [values: plain, loud] [group: look]\n"
+        );
+    }
+
+    #[test]
+    fn describe_pipe_components() {
+        let mut meta = PipeMetadataV1::new(vec![ArgSchema {
+            name: "width".to_string(),
+            long: Some("--width".to_string()),
+            takes_value: true,
+            value_type: Some("uint".to_string()),
+            ..Default::default()
+        }]);
+        meta.summary = "Render a table".to_string();
+        meta.input_types = vec!["application/json".to_string()];
+        meta.output_type = "text/plain".to_string();
+        let pipe = Report {
+            pipe: Some(meta),
+            ..report(World::Pipe, vec![])
+        };
+        assert_eq!(
+            describe(&pipe),
+            "pipe component\n\
+             exports: wacli:cli/pipe@2.0.0\n\
+             summary: Render a table\n\
+             input: application/json\n\
+             output: text/plain\n\
+             options:\n\
+             \x20 width  --width <WIDTH>  [type: uint]\n"
+        );
+        assert_eq!(
+            describe(&report(World::Pipe, vec![])),
+            "pipe component\nexports: wacli:cli/pipe@2.0.0\n"
+        );
+    }
+
+    #[test]
+    fn embedded_provenance_can_be_read_back() {
+        let module = wat::parse_str("(module)").unwrap();
+        let meta = AppProvenanceV1::new("example:my-cli", "0.1.0");
+        let bytes = embed_provenance(module, &meta);
+        assert_eq!(read_provenance(&bytes).unwrap(), Some(meta));
+
+        // Only the top level counts.
+        let nested = wat::parse_str(
+            r#"(component (core module (@custom "wacli:cli/app-provenance@1" "{}")))"#,
+        )
+        .unwrap();
+        assert_eq!(read_provenance(&nested).unwrap(), None);
+
+        let err = read_provenance(b"nope").unwrap_err();
+        assert!(
+            format!("{err:#}").starts_with("failed to parse WASM: "),
+            "{err:#}"
+        );
+    }
+}
@@ -1,15 +1,27 @@
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 pub const DEFAULT_LOCK_NAME: &str = "wacli.lock";
-pub const LOCK_SCHEMA_VERSION: u32 = 1;
+
+/// `lockfileVersion` written by this wacli. Locks from before the field
+/// existed (`"schemaVersion": 1`) are version 0.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// `MIGRATIONS[n]` upgrades a version `n` lock to version `n + 1`.
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LockFile {
-    pub schema_version: u32,
+    pub lockfile_version: u32,
 
     /// Optional registry URL used when the lock was last updated.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -20,27 +32,117 @@ pub struct LockFile {
 
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub commands: Vec<LockedRegistryCommand>,
+
+    /// Commands from git sources (`build.commands[].git`), pinned by commit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_commands: Vec<LockedGitCommand>,
+
+    /// Packages `wacli compose` pulled for `--dep PKG=oci://REPO:TAG`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deps: Vec<LockedDep>,
+
+    /// Fields this wacli does not know, kept so a rewrite is lossless.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl Default for LockFile {
     fn default() -> Self {
         Self {
-            schema_version: LOCK_SCHEMA_VERSION,
+            lockfile_version: LOCKFILE_VERSION,
             molt_registry: None,
             framework: None,
             commands: Vec::new(),
+            git_commands: Vec::new(),
+            deps: Vec::new(),
+            extra: Map::new(),
         }
     }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "RawFrameworkLock", into = "RawFrameworkLock")]
 pub struct FrameworkLock {
+    pub host: Option<LockedComponent>,
+
+    pub core: Option<LockedComponent>,
+
+    /// Components pinned by build profiles (`framework.<profile>.host`), so
+    /// switching `--profile` does not overwrite the default entries.
+    pub profiles: BTreeMap<String, ProfileFrameworkLock>,
+
+    /// Fields this wacli does not know, kept so a rewrite is lossless.
+    pub extra: Map<String, Value>,
+}
+
+/// `framework` as written: profiles sit beside `host` and `core`, so every
+/// other object that reads as a profile is one and the rest is `extra`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFrameworkLock {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    host: Option<LockedComponent>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    core: Option<LockedComponent>,
+
+    #[serde(flatten)]
+    rest: Map<String, Value>,
+}
+
+impl From<RawFrameworkLock> for FrameworkLock {
+    fn from(raw: RawFrameworkLock) -> Self {
+        let mut lock = FrameworkLock {
+            host: raw.host,
+            core: raw.core,
+            ..Default::default()
+        };
+        for (key, value) in raw.rest {
+            let profile = match &value {
+                Value::Object(_) => serde_json::from_value(value.clone()).ok(),
+                _ => None,
+            };
+            match profile {
+                Some(profile) => {
+                    lock.profiles.insert(key, profile);
+                }
+                None => {
+                    lock.extra.insert(key, value);
+                }
+            }
+        }
+        lock
+    }
+}
+
+impl From<FrameworkLock> for RawFrameworkLock {
+    fn from(lock: FrameworkLock) -> Self {
+        let mut rest = lock.extra;
+        for (name, profile) in lock.profiles {
+            // A profile is plain data, so this cannot fail.
+            if let Ok(value) = serde_json::to_value(profile) {
+                rest.insert(name, value);
+            }
+        }
+        RawFrameworkLock {
+            host: lock.host,
+            core: lock.core,
+            rest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileFrameworkLock {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub host: Option<LockedComponent>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub core: Option<LockedComponent>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +156,9 @@ pub struct LockedComponent {
     /// Digest of the selected WASM layer blob in the manifest.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub layer_digest: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +173,42 @@ pub struct LockedRegistryCommand {
     /// Digest of the selected WASM layer blob in the manifest.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub layer_digest: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedGitCommand {
+    pub name: String,
+    /// Repository URL (from `wacli.json`).
+    pub git: String,
+    /// Commit, tag or branch the user asked for (from `wacli.json`).
+    pub rev: String,
+    /// Commit `rev` resolved to. Used for deterministic checkouts.
+    pub commit: String,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedDep {
+    /// WAC package name (`ns:pkg`).
+    pub name: String,
+    pub repo: String,
+    /// Tag or digest the user asked for (from `--dep`).
+    pub reference: String,
+    /// Resolved manifest digest (e.g. `sha256:...`). Used for deterministic pulls.
+    pub digest: String,
+    /// Digest of the selected WASM layer blob in the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer_digest: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 pub fn lock_path(base_dir: &Path) -> PathBuf {
@@ -80,34 +221,79 @@ pub fn load_lock(path: &Path) -> Result<Option<LockFile>> {
     }
     let contents = fs::read_to_string(path)
         .with_context(|| format!("failed to read lock file: {}", path.display()))?;
-    let lock: LockFile = serde_json::from_str(&contents)
-        .with_context(|| format!("failed to parse lock file JSON: {}", path.display()))?;
-    if lock.schema_version != LOCK_SCHEMA_VERSION {
-        bail!(
-            "unsupported lock schemaVersion {} (expected {})",
-            lock.schema_version,
-            LOCK_SCHEMA_VERSION
-        );
-    }
+    let (lock, _) = parse_lock(&contents)
+        .with_context(|| format!("failed to parse lock file: {}", path.display()))?;
     Ok(Some(lock))
 }
 
-pub fn write_lock(path: &Path, lock: &mut LockFile) -> Result<()> {
-    if lock.schema_version != LOCK_SCHEMA_VERSION {
+/// Parse a lock, upgrading older versions in memory. Also returns the
+/// version found on disk.
+///
+/// Locks from a newer wacli are returned as-is so builds can still read
+/// them; [`write_lock`] refuses to rewrite them.
+pub fn parse_lock(contents: &str) -> Result<(LockFile, u32)> {
+    let value: Value = serde_json::from_str(contents).context("invalid JSON")?;
+    let Value::Object(mut map) = value else {
+        bail!("expected a JSON object");
+    };
+    let version = match map.get("lockfileVersion") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .with_context(|| format!("invalid lockfileVersion {v}"))?,
+    };
+    if version < LOCKFILE_VERSION {
+        for migrate in &MIGRATIONS[version as usize..] {
+            migrate(&mut map)?;
+        }
+        map.insert("lockfileVersion".to_string(), LOCKFILE_VERSION.into());
+    }
+    let lock = serde_json::from_value(Value::Object(map))?;
+    Ok((lock, version))
+}
+
+/// v0 locks carried `"schemaVersion": 1` instead of `lockfileVersion`.
+fn migrate_v0_to_v1(lock: &mut Map<String, Value>) -> Result<()> {
+    match lock.remove("schemaVersion") {
+        None => {}
+        Some(v) if v.as_u64() == Some(1) => {}
+        Some(v) => bail!("unsupported lock schemaVersion {v} (expected 1)"),
+    }
+    Ok(())
+}
+
+/// The on-disk form of `lock`: pretty JSON, commands sorted by name.
+pub fn render_lock(lock: &mut LockFile) -> Result<String> {
+    if lock.lockfile_version > LOCKFILE_VERSION {
+        bail!(
+            "written by a newer wacli (lockfileVersion {}, this wacli supports up to {}); \
+             upgrade wacli before updating it",
+            lock.lockfile_version,
+            LOCKFILE_VERSION
+        );
+    }
+    if lock.lockfile_version != LOCKFILE_VERSION {
         bail!(
-            "refusing to write lock with schemaVersion {} (expected {})",
-            lock.schema_version,
-            LOCK_SCHEMA_VERSION
+            "refusing to write lock with lockfileVersion {} (expected {})",
+            lock.lockfile_version,
+            LOCKFILE_VERSION
         );
     }
 
     // Keep deterministic order for diffs.
     lock.commands.sort_by(|a, b| a.name.cmp(&b.name));
+    lock.git_commands.sort_by(|a, b| a.name.cmp(&b.name));
+    lock.deps.sort_by(|a, b| a.name.cmp(&b.name));
 
     let bytes = serde_json::to_vec_pretty(lock).context("failed to serialize lock file")?;
     let mut out = String::from_utf8(bytes).context("lock file is not valid UTF-8")?;
     out.push('\n');
+    Ok(out)
+}
 
+pub fn write_lock(path: &Path, lock: &mut LockFile) -> Result<()> {
+    let out = render_lock(lock).map_err(|e| anyhow!("{}: {e}", path.display()))?;
     let tmp = path.with_extension("tmp");
     fs::write(&tmp, out.as_bytes())
         .with_context(|| format!("failed to write {}", tmp.display()))?;
@@ -119,23 +305,169 @@ pub fn write_lock(path: &Path, lock: &mut LockFile) -> Result<()> {
     Ok(())
 }
 
+/// `sha256:<hex>` digest of `bytes`, in the form OCI registries use.
+pub fn sha256_digest(bytes: &[u8]) -> String {
+    let hash = Sha256::digest(bytes);
+    let hex: String = hash.iter().map(|b| format!("{b:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
+/// Check `bytes` against a `sha256:<hex>` digest (a bare hex digest is
+/// accepted too; case is ignored).
+pub fn verify_digest_bytes(bytes: &[u8], digest: &str) -> Result<()> {
+    let digest = digest.trim();
+    if digest.is_empty() {
+        bail!("no digest to verify against");
+    }
+    let hex = match digest.split_once(':') {
+        Some((algo, hex)) if algo.eq_ignore_ascii_case("sha256") => hex,
+        Some((algo, _)) => {
+            bail!("unsupported digest algorithm '{algo}' (only sha256 is supported)")
+        }
+        None => digest,
+    };
+    let actual = sha256_digest(bytes);
+    if !actual["sha256:".len()..].eq_ignore_ascii_case(hex) {
+        bail!("digest mismatch: expected {digest}, got {actual}");
+    }
+    Ok(())
+}
+
+/// Check the file at `path` against `digest`; see [`verify_digest_bytes`].
+pub fn verify_digest(path: &Path, digest: &str) -> Result<()> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    verify_digest_bytes(&bytes, digest).map_err(|e| anyhow!("{}: {e}", path.display()))
+}
+
+#[derive(Debug, Parser)]
+pub struct LockArgs {
+    #[command(subcommand)]
+    command: LockCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum LockCommands {
+    /// Upgrade wacli.lock to the current lockfileVersion, showing the changes
+    Migrate(MigrateArgs),
+}
+
+#[derive(Debug, Parser)]
+struct MigrateArgs {
+    /// Lock file to upgrade [default: ./wacli.lock]
+    #[arg(long, value_name = "FILE")]
+    lock: Option<PathBuf>,
+
+    /// Print the changes without writing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub fn lock(args: LockArgs) -> Result<()> {
+    match args.command {
+        LockCommands::Migrate(args) => migrate(args),
+    }
+}
+
+fn migrate(args: MigrateArgs) -> Result<()> {
+    let path = args
+        .lock
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LOCK_NAME));
+    let before = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read lock file: {}", path.display()))?;
+    let (mut lock, from) = parse_lock(&before)
+        .with_context(|| format!("failed to parse lock file: {}", path.display()))?;
+    let after = render_lock(&mut lock).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+
+    if from == LOCKFILE_VERSION {
+        eprintln!(
+            "{} is already at lockfileVersion {LOCKFILE_VERSION}",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    print!("{}", line_diff(&before, &after));
+    if args.dry_run {
+        eprintln!(
+            "Would migrate {} from lockfileVersion {from} to {LOCKFILE_VERSION}",
+            path.display()
+        );
+        return Ok(());
+    }
+    write_lock(&path, &mut lock)
+        .with_context(|| format!("failed to write lock file: {}", path.display()))?;
+    eprintln!(
+        "Migrated {} from lockfileVersion {from} to {LOCKFILE_VERSION}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Changed lines of `before` -> `after`, prefixed `-`/`+` (longest common
+/// subsequence; lock files are small).
+fn line_diff(before: &str, after: &str) -> String {
+    let a: Vec<&str> = before.lines().collect();
+    let b: Vec<&str> = after.lines().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("-{}\n", a[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+{}\n", b[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
 impl LockFile {
-    pub fn framework_host(&self) -> Option<&LockedComponent> {
-        self.framework.as_ref().and_then(|f| f.host.as_ref())
+    /// Locked framework host for `profile` (`None` for the default entry).
+    pub fn framework_host(&self, profile: Option<&str>) -> Option<&LockedComponent> {
+        let f = self.framework.as_ref()?;
+        match profile {
+            None => f.host.as_ref(),
+            Some(p) => f.profiles.get(p)?.host.as_ref(),
+        }
     }
 
-    pub fn framework_core(&self) -> Option<&LockedComponent> {
-        self.framework.as_ref().and_then(|f| f.core.as_ref())
+    /// Locked framework core for `profile` (`None` for the default entry).
+    pub fn framework_core(&self, profile: Option<&str>) -> Option<&LockedComponent> {
+        let f = self.framework.as_ref()?;
+        match profile {
+            None => f.core.as_ref(),
+            Some(p) => f.profiles.get(p)?.core.as_ref(),
+        }
     }
 
-    pub fn set_framework_host(&mut self, v: LockedComponent) {
+    pub fn set_framework_host(&mut self, profile: Option<&str>, v: LockedComponent) {
         let f = self.framework.get_or_insert_with(FrameworkLock::default);
-        f.host = Some(v);
+        match profile {
+            None => f.host = Some(v),
+            Some(p) => f.profiles.entry(p.to_string()).or_default().host = Some(v),
+        }
     }
 
-    pub fn set_framework_core(&mut self, v: LockedComponent) {
+    pub fn set_framework_core(&mut self, profile: Option<&str>, v: LockedComponent) {
         let f = self.framework.get_or_insert_with(FrameworkLock::default);
-        f.core = Some(v);
+        match profile {
+            None => f.core = Some(v),
+            Some(p) => f.profiles.entry(p.to_string()).or_default().core = Some(v),
+        }
     }
 
     pub fn find_command(&self, name: &str) -> Option<&LockedRegistryCommand> {
@@ -149,38 +481,283 @@ impl LockFile {
             self.commands.push(v);
         }
     }
+
+    pub fn find_git_command(&self, name: &str) -> Option<&LockedGitCommand> {
+        self.git_commands.iter().find(|c| c.name == name)
+    }
+
+    pub fn set_git_command(&mut self, v: LockedGitCommand) {
+        if let Some(existing) = self.git_commands.iter_mut().find(|c| c.name == v.name) {
+            *existing = v;
+        } else {
+            self.git_commands.push(v);
+        }
+    }
+
+    pub fn find_dep(&self, name: &str) -> Option<&LockedDep> {
+        self.deps.iter().find(|d| d.name == name)
+    }
+
+    pub fn set_dep(&mut self, v: LockedDep) {
+        if let Some(existing) = self.deps.iter_mut().find(|d| d.name == v.name) {
+            *existing = v;
+        } else {
+            self.deps.push(v);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn digests_accept_the_sha256_prefix_or_bare_hex() {
+        // sha256("hello")
+        let hex = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert_eq!(sha256_digest(b"hello"), format!("sha256:{hex}"));
+        verify_digest_bytes(b"hello", &format!("sha256:{hex}")).unwrap();
+        verify_digest_bytes(b"hello", &format!(" SHA256:{} ", hex.to_uppercase())).unwrap();
+        verify_digest_bytes(b"hello", hex).unwrap();
+
+        let err = verify_digest_bytes(b"hello", "sha512:abcd").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported digest algorithm 'sha512' (only sha256 is supported)"
+        );
+    }
+
+    #[test]
+    fn digest_mismatches_and_missing_digests_are_errors() {
+        let err = verify_digest_bytes(b"hello", "sha256:00ff").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "digest mismatch: expected sha256:00ff, got {}",
+                sha256_digest(b"hello")
+            )
+        );
+        let err = verify_digest_bytes(b"hello", "  ").unwrap_err();
+        assert_eq!(err.to_string(), "no digest to verify against");
+
+        let path = std::env::temp_dir().join(format!("wacli-digest-{}.wasm", std::process::id()));
+        fs::write(&path, b"tampered").unwrap();
+        let err = verify_digest(&path, &sha256_digest(b"original")).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("{}: digest mismatch", path.display()))
+        );
+        verify_digest(&path, &sha256_digest(b"tampered")).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(verify_digest(&path, &sha256_digest(b"tampered")).is_err());
+    }
+
     #[test]
     fn lock_round_trips() {
-        let mut lock = LockFile::default();
-        lock.molt_registry = Some("https://registry.example.com".to_string());
-        lock.set_framework_host(LockedComponent {
-            repo: "wacli/host".to_string(),
-            reference: "v0.0.42".to_string(),
-            digest: "sha256:deadbeef".to_string(),
-            layer_digest: Some("sha256:cafebabe".to_string()),
-        });
+        let mut lock = LockFile {
+            molt_registry: Some("https://registry.example.com".to_string()),
+            ..Default::default()
+        };
+        lock.set_framework_host(
+            None,
+            LockedComponent {
+                repo: "wacli/host".to_string(),
+                reference: "v0.0.42".to_string(),
+                digest: "sha256:deadbeef".to_string(),
+                layer_digest: Some("sha256:cafebabe".to_string()),
+                extra: Default::default(),
+            },
+        );
         lock.set_command(LockedRegistryCommand {
             name: "greet".to_string(),
             repo: "example/greet".to_string(),
             reference: "1.0.0".to_string(),
             digest: "sha256:abc".to_string(),
             layer_digest: None,
+            extra: Default::default(),
+        });
+        lock.set_git_command(LockedGitCommand {
+            name: "tool".to_string(),
+            git: "https://example.com/tool.git".to_string(),
+            rev: "v1".to_string(),
+            commit: "0123abcd".to_string(),
+            extra: Default::default(),
+        });
+        lock.set_dep(LockedDep {
+            name: "example:adder".to_string(),
+            repo: "example/adder".to_string(),
+            reference: "0.1.0".to_string(),
+            digest: "sha256:add".to_string(),
+            layer_digest: None,
+            extra: Default::default(),
         });
 
         let json = serde_json::to_string_pretty(&lock).unwrap();
+        assert!(json.contains("\"gitCommands\""), "{json}");
+        assert!(json.contains("\"deps\""), "{json}");
         let decoded: LockFile = serde_json::from_str(&json).unwrap();
-        assert_eq!(decoded.schema_version, LOCK_SCHEMA_VERSION);
+        assert_eq!(decoded.lockfile_version, LOCKFILE_VERSION);
         assert_eq!(
             decoded.molt_registry.as_deref(),
             Some("https://registry.example.com")
         );
-        assert_eq!(decoded.framework_host().unwrap().digest, "sha256:deadbeef");
+        assert_eq!(
+            decoded.framework_host(None).unwrap().digest,
+            "sha256:deadbeef"
+        );
         assert_eq!(decoded.find_command("greet").unwrap().repo, "example/greet");
+        assert_eq!(decoded.find_git_command("tool").unwrap().commit, "0123abcd");
+        assert!(decoded.find_command("tool").is_none());
+        assert_eq!(
+            decoded.find_dep("example:adder").unwrap().digest,
+            "sha256:add"
+        );
+    }
+
+    #[test]
+    fn profile_framework_entries_sit_beside_the_default_ones() {
+        let component = |reference: &str, digest: &str| LockedComponent {
+            repo: "wacli/host".to_string(),
+            reference: reference.to_string(),
+            digest: digest.to_string(),
+            layer_digest: None,
+            extra: Default::default(),
+        };
+        let mut lock = LockFile::default();
+        lock.set_framework_host(None, component("v0.0.42", "sha256:stable"));
+        lock.set_framework_host(Some("edge"), component("v0.2.0-rc1", "sha256:edge"));
+
+        let json: serde_json::Value = serde_json::to_value(&lock).unwrap();
+        assert_eq!(json["framework"]["host"]["digest"], "sha256:stable");
+        assert_eq!(json["framework"]["edge"]["host"]["digest"], "sha256:edge");
+
+        let decoded: LockFile = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            decoded.framework_host(None).unwrap().digest,
+            "sha256:stable"
+        );
+        assert_eq!(
+            decoded.framework_host(Some("edge")).unwrap().digest,
+            "sha256:edge"
+        );
+        assert!(decoded.framework_core(Some("edge")).is_none());
+        assert!(decoded.framework_host(Some("nightly")).is_none());
+    }
+
+    #[test]
+    fn v0_locks_are_migrated_on_read() {
+        let legacy = r#"{
+  "schemaVersion": 1,
+  "commands": [
+    { "name": "greet", "repo": "example/greet", "reference": "1.0.0", "digest": "sha256:abc" }
+  ]
+}"#;
+        let (mut lock, from) = parse_lock(legacy).unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(lock.lockfile_version, LOCKFILE_VERSION);
+        assert_eq!(lock.find_command("greet").unwrap().digest, "sha256:abc");
+
+        let rendered = render_lock(&mut lock).unwrap();
+        assert!(
+            rendered.starts_with("{\n  \"lockfileVersion\": 1,\n"),
+            "{rendered}"
+        );
+        assert!(!rendered.contains("schemaVersion"), "{rendered}");
+        assert_eq!(parse_lock(&rendered).unwrap().1, LOCKFILE_VERSION);
+
+        // Hand-written locks without any version are v0 too.
+        assert_eq!(parse_lock("{}").unwrap().1, 0);
+
+        let err = parse_lock(r#"{ "schemaVersion": 2 }"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported lock schemaVersion 2 (expected 1)"
+        );
+        assert!(parse_lock(r#"{ "lockfileVersion": "1" }"#).is_err());
+    }
+
+    #[test]
+    fn unknown_fields_survive_a_rewrite() {
+        let json = serde_json::json!({
+            "lockfileVersion": 1,
+            "pipes": [{ "name": "format/table" }],
+            "framework": {
+                "host": {
+                    "repo": "wacli/host",
+                    "reference": "v0.0.42",
+                    "digest": "sha256:host",
+                    "license": "MIT"
+                },
+                "edge": {
+                    "core": { "repo": "acme/core", "reference": "rc1", "digest": "sha256:core" },
+                    "features": ["jit"]
+                },
+                "pinnedBy": "ci",
+                "mirrors": ["registry.example.com"]
+            },
+            "commands": [{
+                "name": "greet",
+                "repo": "example/greet",
+                "reference": "1.0.0",
+                "digest": "sha256:abc",
+                "signature": { "keyId": "k1" }
+            }]
+        });
+        let (mut lock, _) = parse_lock(&json.to_string()).unwrap();
+        lock.set_command(LockedRegistryCommand {
+            name: "alpha".to_string(),
+            repo: "example/alpha".to_string(),
+            reference: "0.1.0".to_string(),
+            digest: "sha256:def".to_string(),
+            layer_digest: None,
+            extra: Default::default(),
+        });
+        let rendered: Value = serde_json::from_str(&render_lock(&mut lock).unwrap()).unwrap();
+
+        assert_eq!(rendered["pipes"], json["pipes"]);
+        assert_eq!(rendered["framework"], json["framework"]);
+        assert_eq!(lock.framework.as_ref().unwrap().extra.len(), 2);
+        assert_eq!(rendered["commands"][0]["name"], "alpha");
+        assert_eq!(rendered["commands"][1], json["commands"][0]);
+    }
+
+    #[test]
+    fn locks_from_a_newer_wacli_are_read_but_not_rewritten() {
+        let future = r#"{
+  "lockfileVersion": 7,
+  "framework": { "host": { "repo": "wacli/host", "reference": "v9", "digest": "sha256:h" } },
+  "attestations": {}
+}"#;
+        let (mut lock, from) = parse_lock(future).unwrap();
+        assert_eq!(from, 7);
+        assert_eq!(lock.framework_host(None).unwrap().digest, "sha256:h");
+
+        let dir = std::env::temp_dir().join(format!("wacli-lock-future-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(DEFAULT_LOCK_NAME);
+        fs::write(&path, future).unwrap();
+        let err = write_lock(&path, &mut lock).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{}: written by a newer wacli (lockfileVersion 7, this wacli supports up to 1); \
+                 upgrade wacli before updating it",
+                path.display()
+            )
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), future);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn line_diff_marks_changed_lines() {
+        assert_eq!(
+            line_diff(
+                "{\n  \"a\": 1,\n  \"b\": 2\n}\n",
+                "{\n  \"v\": 1,\n  \"b\": 2\n}\n"
+            ),
+            "-  \"a\": 1,\n+  \"v\": 1,\n"
+        );
+        assert_eq!(line_diff("same\n", "same\n"), "");
     }
 }
@@ -1,11 +1,30 @@
+mod build_manifest;
+mod clean;
 mod command_metadata;
+mod compat;
 mod component_scan;
+mod compose_deps;
+#[cfg(feature = "runtime")]
+mod env_file;
+mod git_source;
+mod inspect;
 mod lock;
+mod man_page;
 mod manifest;
+mod offline;
+mod progress;
+mod project_template;
 mod registry_gen_wat;
+mod registry_layout;
 mod registry_pull;
+mod report;
+mod scan_limits;
+mod strip;
+#[cfg(feature = "runtime")]
+mod verify;
 mod wac_gen;
 mod wasm_registry;
+mod watch;
 mod wit;
 
 use anyhow::{Context, Result, bail};
@@ -13,9 +32,9 @@ use clap::{Parser, Subcommand};
 use indexmap::IndexMap;
 use self_update::{Status, backends::github::Update};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs,
-    io::{IsTerminal, Write},
+    io::{IsTerminal, Read, Write},
     path::{Path, PathBuf},
 };
 use tracing_subscriber::{EnvFilter, fmt};
@@ -23,9 +42,13 @@ use wac_graph::{CompositionGraph, EncodeOptions};
 use wac_parser::Document;
 use wac_resolver::{FileSystemPackageResolver, packages};
 use wac_types::{BorrowedPackageKey, Package};
+use wacli_metadata::CommandSchema;
 
-use crate::component_scan::{scan_commands, scan_commands_optional};
-use crate::registry_gen_wat::{AppMeta, generate_registry_wat, get_prebuilt_registry};
+use crate::component_scan::{
+    CommandInfo, assign_groups, check_complete_commands, check_global_args, check_metadata,
+    check_schemas, check_shared_args, components, scan_commands, scan_commands_optional,
+};
+use crate::registry_gen_wat::{AppMeta, BuildInfo, generate_registry_wat, get_prebuilt_registry};
 use crate::wac_gen::generate_wac;
 
 #[derive(Parser)]
@@ -53,15 +76,28 @@ enum Commands {
     /// Molt WASM-aware registry helper commands (/wasm/v1)
     Wasm(wasm_registry::WasmArgs),
 
+    /// Manage wacli.lock
+    Lock(lock::LockArgs),
+
+    /// Remove build caches, the generated registry or the built component
+    Clean(clean::CleanArgs),
+
+    /// Show the wacli metadata embedded in a component
+    Inspect(inspect::InspectArgs),
+
     #[cfg(feature = "runtime")]
     /// Run a composed CLI component with dynamic pipes
     Run(RunArgs),
 
+    #[cfg(feature = "runtime")]
+    /// Run every command's --help and examples against a composed CLI
+    Verify(verify::VerifyArgs),
+
     /// Update wacli from GitHub Releases
     SelfUpdate(SelfUpdateArgs),
 }
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 struct BuildArgs {
     /// Path to a wacli manifest (defaults to ./wacli.json if present)
     #[arg(long, value_name = "FILE")]
@@ -95,6 +131,14 @@ struct BuildArgs {
     #[arg(long)]
     no_validate: bool,
 
+    /// Skip the consistency checks of each command's embedded metadata
+    ///
+    /// By default the build fails on metadata that would only break at run
+    /// time: unknown `conflicts-with`/`requires` targets, flags shared by two
+    /// args, invalid names, or an alias claimed by two commands.
+    #[arg(long)]
+    no_validate_metadata: bool,
+
     /// Print generated WAC without composing
     #[arg(long)]
     print_wac: bool,
@@ -110,6 +154,101 @@ struct BuildArgs {
     /// Without this flag, wacli will prefer digests already pinned in `wacli.lock`.
     #[arg(long)]
     update_lock: bool,
+
+    /// Never reach a registry or git remote; fail if a component is not cached
+    ///
+    /// Every missing component is listed with the cache path it was expected
+    /// at. Also enabled by WACLI_OFFLINE=1. Cannot be combined with
+    /// --update-lock.
+    #[arg(long)]
+    offline: bool,
+
+    /// Source revision to embed in the build info (e.g. output of `git describe`)
+    ///
+    /// Falls back to WACLI_GIT_REV, then GIT_SHA. wacli does not ask git for it.
+    #[arg(long = "git-rev", value_name = "REV")]
+    git_rev: Option<String>,
+
+    /// Embed the build time in the build info (uses SOURCE_DATE_EPOCH if set)
+    ///
+    /// Off by default to keep builds reproducible.
+    #[arg(long)]
+    build_timestamp: bool,
+
+    /// Extra fact to embed in the build info, shown as `KEY: VALUE` (repeatable)
+    #[arg(long = "build-info", value_name = "KEY=VALUE")]
+    build_info: Vec<String>,
+
+    /// Fail if a command's embedded metadata has no command schema
+    ///
+    /// Without it, such commands only produce a warning and core validates them
+    /// against the flattened meta.
+    #[arg(long)]
+    require_schema: bool,
+
+    /// Read the metadata of commands that embed none by running their meta()
+    ///
+    /// The component is instantiated in a sandbox with no filesystem, env or
+    /// stdio and a fuel limit. Off by default: a build never runs plugin code
+    /// unless asked to.
+    #[arg(long)]
+    allow_meta_exec: bool,
+
+    /// Also write roff man pages (`<app>.1`, `<app>-<command>.1`) into DIR
+    #[arg(long = "emit-man", value_name = "DIR")]
+    emit_man: Option<PathBuf>,
+
+    /// Also write every command's metadata and the app metadata to FILE as JSON
+    #[arg(long = "emit-manifest", value_name = "FILE")]
+    emit_manifest: Option<PathBuf>,
+
+    /// Build with the overrides of `build.profiles.<NAME>` from the manifest
+    ///
+    /// Selects that profile's host/core references and features, and adds
+    /// `-<NAME>` to the output file name unless --output is given.
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Rebuild whenever the manifest, defaults/ or commands/ change (Ctrl-C to stop)
+    ///
+    /// Build errors are printed and the watch continues with the next change.
+    #[arg(long, conflicts_with = "print_wac")]
+    watch: bool,
+
+    /// Build twice and fail unless both builds produce identical bytes
+    ///
+    /// The second build is written next to the output with a `.check.wasm`
+    /// suffix and kept only on a mismatch, for diffing.
+    #[arg(long, conflicts_with_all = ["print_wac", "watch"])]
+    check_reproducible: bool,
+
+    /// Remove debug info (`debug`, the default) or also names (`all`) from
+    /// each component before composing
+    ///
+    /// Only custom sections are removed; command metadata and everything
+    /// composition needs are kept. Overrides `build.strip`.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LEVEL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "debug"
+    )]
+    strip: Option<strip::StripLevel>,
+
+    /// Print each component's size, share of the output and wacli-cdk
+    /// version after composing (`text`, the default, or `json`)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text",
+        conflicts_with = "print_wac"
+    )]
+    report: Option<report::ReportFormat>,
 }
 
 #[derive(Parser)]
@@ -122,18 +261,26 @@ struct InitArgs {
     #[arg(long)]
     with_components: bool,
 
-    /// Overwrite existing component and WIT files when initializing
+    /// Overwrite existing component, WIT and template files when initializing
     #[arg(long)]
     overwrite: bool,
+
+    /// Also scaffold a plugin crate that builds into commands/ (or plugins/)
+    #[arg(long, value_enum, value_name = "TEMPLATE")]
+    template: Option<project_template::Template>,
+
+    /// Name of the scaffolded plugin (default: greet, or case for a pipe)
+    #[arg(long, value_name = "NAME", requires = "template")]
+    name: Option<String>,
 }
 
 #[derive(Parser)]
 struct ComposeArgs {
-    /// The WAC source file
+    /// The WAC source file (`-` reads from stdin)
     #[arg(value_name = "FILE")]
     path: PathBuf,
 
-    /// Output file path
+    /// Output file path (`-` or omitted writes to stdout)
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
 
@@ -141,26 +288,33 @@ struct ComposeArgs {
     #[arg(long, default_value = "deps")]
     deps_dir: PathBuf,
 
-    /// Specify dependency location: PKG=PATH
+    /// Specify dependency location: PKG=PATH, or PKG=oci://REPO:TAG to pull
+    /// it from the registry (pinned in wacli.lock, cached under .wacli/deps/)
     #[arg(short = 'd', long = "dep", value_name = "PKG=PATH")]
     deps: Vec<String>,
 
     /// Skip validation of the composed component
     #[arg(long)]
     no_validate: bool,
+
+    /// Never contact the registry; oci:// dependencies must be locked and cached
+    ///
+    /// Also enabled by WACLI_OFFLINE=1.
+    #[arg(long)]
+    offline: bool,
 }
 
 #[derive(Parser)]
 struct PlugArgs {
-    /// The socket component (receives imports)
+    /// The socket component (receives imports; `-` reads from stdin)
     #[arg(value_name = "SOCKET")]
     socket: PathBuf,
 
-    /// Plug components (provide exports)
+    /// Plug components (provide exports; `-` reads from stdin)
     #[arg(long = "plug", value_name = "FILE", required = true)]
     plugs: Vec<PathBuf>,
 
-    /// Output file path
+    /// Output file path (`-` or omitted writes to stdout)
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
 }
@@ -176,6 +330,49 @@ struct RunArgs {
     #[arg(long = "dir", value_name = "HOST[::GUEST]")]
     dirs: Vec<String>,
 
+    /// Load guest environment variables from a dotenv file (repeatable; later files win)
+    /// [default: $WACLI_ENV_FILE]
+    #[arg(long = "env-file", value_name = "FILE")]
+    env_files: Vec<PathBuf>,
+
+    /// Let the guest open sockets and look up names (denied by default)
+    #[arg(long)]
+    allow_network: bool,
+
+    /// With --allow-network, only allow these addresses (HOST:PORT, `*` matches any; repeatable)
+    #[arg(
+        long = "allow-host",
+        value_name = "HOST:PORT",
+        requires = "allow_network"
+    )]
+    allow_hosts: Vec<plugin_loader::HostPattern>,
+
+    /// Load pipes from DIR (relative to the current directory)
+    /// [default: $WACLI_PLUGINS_DIR, then ./plugins]
+    #[arg(long = "plugins-dir", value_name = "DIR")]
+    plugins_dir: Option<PathBuf>,
+
+    /// Top-level guest flag that takes a separate value (e.g. --config), so the
+    /// command after it is still found for pipe lookup (repeatable)
+    #[arg(long = "global-flag", value_name = "FLAG", allow_hyphen_values = true)]
+    global_flags: Vec<String>,
+
+    /// Interrupt the component (and each pipe call) after SECS seconds
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: Option<u64>,
+
+    /// Stop the component (and each pipe call) after N units of fuel (roughly wasm instructions)
+    #[arg(long, value_name = "N")]
+    fuel: Option<u64>,
+
+    /// Write the guest's stdout to FILE (raw bytes) instead of the terminal
+    #[arg(long = "stdout", value_name = "FILE")]
+    stdout: Option<PathBuf>,
+
+    /// Write the guest's stderr to FILE (e.g. /dev/null to quiet it)
+    #[arg(long = "stderr", value_name = "FILE")]
+    stderr: Option<PathBuf>,
+
     /// Arguments passed to the command
     #[arg(value_name = "ARGS", trailing_var_arg = true)]
     args: Vec<String>,
@@ -209,23 +406,38 @@ fn dispatch(cli: Cli) -> Result<()> {
         Commands::Compose(args) => compose(args),
         Commands::Plug(args) => plug(args),
         Commands::Wasm(args) => wasm_registry::wasm(args),
+        Commands::Lock(args) => lock::lock(args),
+        Commands::Clean(args) => clean::clean(args),
+        Commands::Inspect(args) => inspect::inspect(args),
         #[cfg(feature = "runtime")]
         Commands::Run(args) => run(args),
+        #[cfg(feature = "runtime")]
+        Commands::Verify(args) => verify::verify(args),
         Commands::SelfUpdate(args) => self_update(args),
     }
 }
 
 fn report_error(err: anyhow::Error) {
-    if is_component_interface_mismatch(&err) {
+    // The pre-flight check names the components; WAC's errors only hint.
+    let mismatch = err.downcast_ref::<compat::InterfaceMismatch>();
+    if mismatch.is_some() || is_component_interface_mismatch(&err) {
         eprintln!("Error: Component interface mismatch\n");
+        if let Some(mismatch) = mismatch {
+            for problem in &mismatch.problems {
+                eprintln!("- {problem}");
+            }
+            eprintln!();
+        }
         eprintln!("This usually happens when:");
         eprintln!("1. wacli and wacli-cdk versions don't match");
         eprintln!("2. Old component files remain in commands/\n");
         eprintln!("Solutions:");
         eprintln!("- Update: wacli self-update && update wacli-cdk in Cargo.toml");
         eprintln!("- Clean: rm commands/**/*.component.wasm && rebuild");
-        eprintln!("- Verify: wacli --version && rg wacli-cdk commands/*/Cargo.toml\n");
-        eprintln!("Details: {err}");
+        eprintln!("- Verify: wacli --version && rg wacli-cdk commands/*/Cargo.toml");
+        if mismatch.is_none() {
+            eprintln!("\nDetails: {err}");
+        }
     } else {
         eprintln!("Error: {err}");
     }
@@ -269,24 +481,56 @@ fn init(args: InitArgs) -> Result<()> {
 
     manifest::write_default_manifest(&dir, args.overwrite)?;
 
+    let template_dir = match args.template {
+        Some(template) => {
+            let name = args.name.as_deref().unwrap_or(template.default_name());
+            Some(project_template::scaffold(
+                &dir,
+                template,
+                name,
+                args.overwrite,
+            )?)
+        }
+        None => None,
+    };
+
     eprintln!("Created:");
     eprintln!("  {}", defaults_dir.display());
     eprintln!("  {}", commands_dir.display());
     eprintln!("  {}", dir.join("wit").display());
     eprintln!("  {}", dir.join(manifest::DEFAULT_MANIFEST_NAME).display());
+    if let Some(template_dir) = &template_dir {
+        eprintln!("  {}", template_dir.display());
+    }
     eprintln!();
     eprintln!("Next steps:");
-    if args.with_components {
-        eprintln!("  1. Place your command components in commands/");
-        eprintln!("  2. Run: wacli build");
-    } else {
-        eprintln!(
-            "  1. Place host.component.wasm and core.component.wasm in defaults/ (or set MOLT_REGISTRY to pull them)"
+    let mut steps = Vec::new();
+    if !args.with_components {
+        steps.push(
+            "Place host.component.wasm and core.component.wasm in defaults/ (or set MOLT_REGISTRY to pull them)"
+                .to_string(),
         );
-        eprintln!(
-            "  2. Place your command components in commands/ (or set build.commands in wacli.json to pull them)"
-        );
-        eprintln!("  3. Run: wacli build");
+    }
+    match (&template_dir, args.template) {
+        (Some(template_dir), Some(project_template::Template::Pipe)) => steps.push(format!(
+            "Run make in {} to install the pipe into plugins/",
+            template_dir.display()
+        )),
+        (Some(template_dir), _) => steps.push(format!(
+            "Run make in {} to install the command into commands/",
+            template_dir.display()
+        )),
+        (None, _) if args.with_components => {
+            steps.push("Place your command components in commands/".to_string())
+        }
+        (None, _) => steps.push(
+            "Place your command components in commands/ (or set build.commands in wacli.json to pull them)"
+                .to_string(),
+        ),
+    }
+    steps.push("Run: wacli build".to_string());
+    for (i, step) in steps.iter().enumerate() {
+        eprintln!("  {}. {step}", i + 1);
     }
 
     Ok(())
@@ -296,6 +540,7 @@ const PLUGIN_WIT: &str = wit::COMMAND_WIT;
 const TYPES_WIT: &str = wit::TYPES_WIT;
 const HOST_ENV_WIT: &str = wit::HOST_ENV_WIT;
 const HOST_IO_WIT: &str = wit::HOST_IO_WIT;
+const HOST_LOG_WIT: &str = wit::HOST_LOG_WIT;
 const HOST_FS_WIT: &str = wit::HOST_FS_WIT;
 const HOST_PROCESS_WIT: &str = wit::HOST_PROCESS_WIT;
 const HOST_PIPES_WIT: &str = wit::HOST_PIPES_WIT;
@@ -313,6 +558,7 @@ fn write_plugin_wit(project_dir: &Path, overwrite: bool) -> Result<()> {
         ("types.wit", TYPES_WIT),
         ("host-env.wit", HOST_ENV_WIT),
         ("host-io.wit", HOST_IO_WIT),
+        ("host-log.wit", HOST_LOG_WIT),
         ("host-fs.wit", HOST_FS_WIT),
         ("host-process.wit", HOST_PROCESS_WIT),
         ("host-pipes.wit", HOST_PIPES_WIT),
@@ -392,12 +638,16 @@ Set MOLT_REGISTRY (and auth) to download framework components, or omit --with-co
         )
         .context("failed to pull host.component.wasm from registry")?;
         if let Some(pulled) = pulled {
-            lock.set_framework_host(crate::lock::LockedComponent {
-                repo: host_repo.clone(),
-                reference: host_ref.clone(),
-                digest: pulled.manifest_digest,
-                layer_digest: Some(pulled.layer_digest),
-            });
+            lock.set_framework_host(
+                None,
+                crate::lock::LockedComponent {
+                    repo: host_repo.clone(),
+                    reference: host_ref.clone(),
+                    digest: pulled.manifest_digest,
+                    layer_digest: Some(pulled.layer_digest),
+                    extra: Default::default(),
+                },
+            );
             lock_dirty = true;
         }
         tracing::info!("downloaded host.component.wasm -> {}", host_path.display());
@@ -411,12 +661,16 @@ Set MOLT_REGISTRY (and auth) to download framework components, or omit --with-co
         )
         .context("failed to pull core.component.wasm from registry")?;
         if let Some(pulled) = pulled {
-            lock.set_framework_core(crate::lock::LockedComponent {
-                repo: core_repo.clone(),
-                reference: core_ref.clone(),
-                digest: pulled.manifest_digest,
-                layer_digest: Some(pulled.layer_digest),
-            });
+            lock.set_framework_core(
+                None,
+                crate::lock::LockedComponent {
+                    repo: core_repo.clone(),
+                    reference: core_ref.clone(),
+                    digest: pulled.manifest_digest,
+                    layer_digest: Some(pulled.layer_digest),
+                    extra: Default::default(),
+                },
+            );
             lock_dirty = true;
         }
         tracing::info!("downloaded core.component.wasm -> {}", core_path.display());
@@ -429,8 +683,9 @@ Set MOLT_REGISTRY (and auth) to download framework components, or omit --with-co
                 lock.molt_registry = Some(v.to_string());
             }
         }
-        crate::lock::write_lock(&lock_path, &mut lock)
-            .with_context(|| format!("failed to write lock file: {}", lock_path.display()))?;
+        // No extra context: write_lock errors name the file, and a refusal to
+        // rewrite a newer lock must reach the user verbatim.
+        crate::lock::write_lock(&lock_path, &mut lock)?;
         tracing::info!("updated lock file: {}", lock_path.display());
     }
 
@@ -438,6 +693,76 @@ Set MOLT_REGISTRY (and auth) to download framework components, or omit --with-co
 }
 
 fn build(args: BuildArgs) -> Result<()> {
+    if args.check_reproducible {
+        return check_reproducible(args);
+    }
+    if !args.watch {
+        return build_once(args, &mut watch::WatchedInputs::default()).map(drop);
+    }
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let manifest_path = cwd.join(
+        args.manifest
+            .as_deref()
+            .unwrap_or(Path::new(manifest::DEFAULT_MANIFEST_NAME)),
+    );
+    watch::watch(manifest_path, |inputs| {
+        build_once(args.clone(), inputs).map(drop)
+    })
+}
+
+/// Build, then build again into a scratch file and compare the two outputs.
+///
+/// The second build reuses the lock the first one wrote and skips the side
+/// outputs (`--emit-man`, `--emit-manifest`), so only the component is compared.
+fn check_reproducible(args: BuildArgs) -> Result<()> {
+    let mut inputs = watch::WatchedInputs::default();
+    let Some(first) = build_once(args.clone(), &mut inputs)? else {
+        bail!("--check-reproducible needs a build that writes a component");
+    };
+    let check = first.with_extension("check.wasm");
+    build_once(
+        BuildArgs {
+            output: Some(check.clone()),
+            emit_man: None,
+            emit_manifest: None,
+            update_lock: false,
+            ..args
+        },
+        &mut inputs,
+    )?;
+
+    let a = fs::read(&first).with_context(|| format!("failed to read {}", first.display()))?;
+    let b = fs::read(&check).with_context(|| format!("failed to read {}", check.display()))?;
+    if let Some(offset) = first_difference(&a, &b) {
+        bail!(
+            "build is not reproducible: {} and {} differ at byte {offset} (sizes {} and {})",
+            first.display(),
+            check.display(),
+            a.len(),
+            b.len(),
+        );
+    }
+    fs::remove_file(&check).with_context(|| format!("failed to remove {}", check.display()))?;
+    eprintln!(
+        "Reproducible: {} ({})",
+        first.display(),
+        crate::lock::sha256_digest(&a)
+    );
+    Ok(())
+}
+
+/// The offset of the first byte where `a` and `b` differ, or `None` if they
+/// are identical.
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    match a.iter().zip(b).position(|(x, y)| x != y) {
+        Some(offset) => Some(offset),
+        None => (a.len() != b.len()).then(|| a.len().min(b.len())),
+    }
+}
+
+/// Run one build, recording the directories it reads into `inputs`, and
+/// return the path it wrote (`None` with `--print-wac`).
+fn build_once(args: BuildArgs, inputs: &mut watch::WatchedInputs) -> Result<Option<PathBuf>> {
     tracing::debug!("executing build command");
 
     let cwd = std::env::current_dir().context("failed to get current directory")?;
@@ -470,15 +795,39 @@ fn build(args: BuildArgs) -> Result<()> {
         format!("{name}@{version}")
     };
 
+    let profile = args
+        .profile
+        .as_deref()
+        .map(|name| manifest::select_profile(m_build, name).map(|p| (name, p)))
+        .transpose()?;
+
     let (app_name, app_version) = package_name
         .split_once('@')
         .map(|(n, v)| (n.to_string(), v.to_string()))
         .unwrap_or((package_name.clone(), String::new()));
-    let app_meta = AppMeta {
+    let mut app_meta = AppMeta {
         name: app_name,
         version: app_version,
         description,
+        build_info: resolve_build_info(args.git_rev, args.build_timestamp, &args.build_info)?,
+        telemetry_command: None,
+        fallback_command: None,
+        interactive_picker: false,
+        env_warnings: false,
+        global_args: Vec::new(),
     };
+    if let Some((name, p)) = profile {
+        app_meta
+            .build_info
+            .built_with
+            .push(format!("profile: {name}"));
+        if !p.features.is_empty() {
+            app_meta
+                .build_info
+                .built_with
+                .push(format!("features: {}", p.features.join(", ")));
+        }
+    }
 
     #[derive(Clone, Copy)]
     enum PathOrigin {
@@ -514,6 +863,8 @@ fn build(args: BuildArgs) -> Result<()> {
         },
     };
     let commands_dir = resolve_path(commands_origin, commands_raw);
+    inputs.watch(&defaults_dir);
+    inputs.watch(&commands_dir);
 
     let (output_raw, output_origin) = match args.output {
         Some(p) => (p, PathOrigin::Cli),
@@ -522,7 +873,20 @@ fn build(args: BuildArgs) -> Result<()> {
             None => (PathBuf::from("my-cli.component.wasm"), PathOrigin::Default),
         },
     };
-    let output_path = resolve_path(output_origin, output_raw);
+    let mut output_path = resolve_path(output_origin, output_raw);
+    if let Some((name, _)) = profile
+        && !matches!(output_origin, PathOrigin::Cli)
+    {
+        output_path = manifest::profile_output_path(&output_path, name);
+    }
+
+    let offline = args.offline || offline::offline_from_env();
+    if offline && args.update_lock {
+        bail!(
+            "--offline cannot be combined with --update-lock, which resolves tags in the registry"
+        );
+    }
+    let mut offline = offline::Offline::new(offline);
 
     // Lock file (digest pinning for registry pulls).
     let lock_path = crate::lock::lock_path(base_dir);
@@ -535,28 +899,41 @@ fn build(args: BuildArgs) -> Result<()> {
     let (host_path, core_path) = resolve_framework_components(
         &defaults_dir,
         base_dir,
+        profile,
         args.update_lock,
+        &mut offline,
         &mut lock,
         &mut lock_dirty,
     )?;
 
     // Resolve command plugins (local + optional registry sources).
     let registry_commands = m_build.and_then(|m| m.commands.clone()).unwrap_or_default();
+    for cmd in &registry_commands {
+        if let Ok(manifest::CommandSource::Path(path)) = cmd.source() {
+            inputs.watch(&base_dir.join(path));
+        }
+    }
 
+    let allow_meta_exec =
+        args.allow_meta_exec || m_build.and_then(|m| m.allow_meta_exec).unwrap_or(false);
     let mut commands = if registry_commands.is_empty() {
-        scan_commands(&commands_dir)?
+        scan_commands(&commands_dir, allow_meta_exec)?
     } else {
-        scan_commands_optional(&commands_dir)?
+        scan_commands_optional(&commands_dir, allow_meta_exec)?
     };
 
+    // No extra context: every error names the build.commands entry it is
+    // about, and only the outermost message is shown to the user.
     let mut registry_resolved = resolve_registry_commands(
         base_dir,
         &registry_commands,
         args.update_lock,
+        allow_meta_exec,
+        &mut offline,
         &mut lock,
         &mut lock_dirty,
-    )
-    .context("failed to resolve registry commands")?;
+    )?;
+    offline.check()?;
     commands.append(&mut registry_resolved);
 
     // Enforce global uniqueness and deterministic ordering.
@@ -577,9 +954,66 @@ fn build(args: BuildArgs) -> Result<()> {
         bail!("no commands configured (commandsDir empty/missing, and build.commands is not set)");
     }
 
+    // Command groups (`db migrate`, `db seed`, ...).
+    let groups = m_build.and_then(|m| m.groups.clone()).unwrap_or_default();
+    assign_groups(&mut commands, &groups)?;
+
+    if let Some(name) = m_build.and_then(|m| m.telemetry_command.as_deref()) {
+        let Some(cmd) = commands.iter().find(|c| c.registry_name() == name) else {
+            bail!("build.telemetryCommand '{name}' does not match any command");
+        };
+        if !cmd.is_hidden() {
+            tracing::warn!("telemetry command '{name}' is not hidden; it will appear in help");
+        }
+        app_meta.telemetry_command = Some(name.to_string());
+    }
+
+    if let Some(name) = m_build.and_then(|m| m.fallback_command.as_deref()) {
+        if !commands.iter().any(|c| c.registry_name() == name) {
+            bail!("build.fallbackCommand '{name}' does not match any command");
+        }
+        app_meta.fallback_command = Some(name.to_string());
+    }
+
+    app_meta.interactive_picker = m_build.and_then(|m| m.interactive_picker).unwrap_or(false);
+    app_meta.env_warnings = m_build.and_then(|m| m.env_warnings).unwrap_or(false);
+    app_meta.global_args = m_build
+        .and_then(|m| m.global_args.clone())
+        .unwrap_or_default();
+
+    let require_schema =
+        args.require_schema || m_build.and_then(|m| m.require_schema).unwrap_or(false);
+    if !args.no_validate_metadata {
+        check_metadata(&commands)?;
+    }
+    check_schemas(&commands, require_schema)?;
+    check_complete_commands(&commands)?;
+    check_shared_args(&commands)?;
+    check_global_args(&app_meta.global_args, &commands)?;
+
+    if let Some(dir) = args.emit_man {
+        write_man_pages(&cwd.join(dir), &app_meta, &commands)?;
+    }
+    if let Some(file) = args.emit_manifest {
+        let path = cwd.join(file);
+        let json = build_manifest::render(&app_meta, &commands)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(&path, json)
+            .with_context(|| format!("failed to write manifest: {}", path.display()))?;
+        tracing::info!("wrote manifest to {}", path.display());
+    }
+
     tracing::info!("found {} command(s)", commands.len());
     for cmd in &commands {
-        tracing::debug!("  - {}: {}", cmd.name, cmd.path.display());
+        tracing::debug!(
+            "  - {}: {}{}",
+            cmd.registry_name(),
+            cmd.path.display(),
+            if cmd.has_schema() { "" } else { " (no schema)" }
+        );
     }
 
     // Get registry component.
@@ -594,7 +1028,7 @@ fn build(args: BuildArgs) -> Result<()> {
         // Generate registry component on every build. Keep build artifacts out of defaults/.
         tracing::info!("generating registry component...");
         tracing::info!("using WAT template registry generator");
-        let registry_bytes = generate_registry_wat(&commands, &app_meta)
+        let registry_bytes = generate_registry_wat(&commands, &groups, &app_meta)
             .context("failed to generate registry (WAT)")?;
 
         // Write to a local build cache directory.
@@ -614,9 +1048,48 @@ fn build(args: BuildArgs) -> Result<()> {
 
     if args.print_wac {
         println!("{}", wac_source);
-        return Ok(());
+        return Ok(None);
     }
 
+    // Pre-flight: report core/host/registry and command/host version skew
+    // before WAC does.
+    let read =
+        |path: &Path| fs::read(path).with_context(|| format!("failed to read {}", path.display()));
+    let (host_bytes, registry_bytes) = (read(&host_path)?, read(&registry_path)?);
+    let relative = |path: &Path| {
+        path.strip_prefix(base_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let host = compat::Provider {
+        label: relative(&host_path),
+        fix: "update defaults/ or rebuild with --update-lock",
+        bytes: &host_bytes,
+    };
+    compat::check_core(
+        &core_path,
+        &read(&core_path)?,
+        &host,
+        &compat::Provider {
+            label: if args.use_prebuilt_registry {
+                registry_path.display().to_string()
+            } else {
+                "the registry generated by this wacli".to_string()
+            },
+            fix: if args.use_prebuilt_registry {
+                "regenerate it (drop --use-prebuilt-registry) or use a matching core"
+            } else {
+                "update wacli (`wacli self-update`) or use a core built for this version"
+            },
+            bytes: &registry_bytes,
+        },
+    )?;
+    let command_bytes = components(&commands)
+        .map(|cmd| Ok((relative(&cmd.path), read(&cmd.path)?)))
+        .collect::<Result<Vec<_>>>()?;
+    compat::check_commands(&command_bytes, &host)?;
+
     // Build dependency map
     let mut deps: HashMap<String, PathBuf> = HashMap::new();
 
@@ -630,6 +1103,9 @@ fn build(args: BuildArgs) -> Result<()> {
         deps.insert(cmd.package_name(), cmd.path.clone());
     }
 
+    // Kept for --report, which needs the files after the resolver took them.
+    let report_deps = args.report.map(|_| deps.clone());
+
     // Parse WAC document
     let wac_path = PathBuf::from("<generated>");
     let document = Document::parse(&wac_source).map_err(|e| fmt_err(e, &wac_path))?;
@@ -637,7 +1113,20 @@ fn build(args: BuildArgs) -> Result<()> {
     // Resolve packages
     let resolver = FileSystemPackageResolver::new(base_dir, deps, false);
     let keys = packages(&document).map_err(|e| fmt_err(e, &wac_path))?;
-    let resolved_packages: IndexMap<BorrowedPackageKey<'_>, Vec<u8>> = resolver.resolve(&keys)?;
+    let mut resolved_packages: IndexMap<BorrowedPackageKey<'_>, Vec<u8>> =
+        resolver.resolve(&keys)?;
+
+    let strip_level = args
+        .strip
+        .or_else(|| m_build.and_then(|m| m.strip))
+        .unwrap_or_default();
+    if strip_level != strip::StripLevel::None {
+        strip_packages(&mut resolved_packages, strip_level)?;
+    }
+    let encoded_sizes: HashMap<String, u64> = resolved_packages
+        .iter()
+        .map(|(key, bytes)| (key.name.to_string(), bytes.len() as u64))
+        .collect();
 
     // Check for unresolved packages
     let mut missing: Vec<_> = keys
@@ -662,6 +1151,21 @@ fn build(args: BuildArgs) -> Result<()> {
         ..Default::default()
     })?;
 
+    // Record what went into the app for external tooling (`wacli inspect`).
+    let digests: HashMap<String, String> = registry_commands
+        .iter()
+        .filter(|c| matches!(c.source(), Ok(manifest::CommandSource::Oci { .. })))
+        .filter_map(|c| lock.find_command(c.name.trim()))
+        .map(|c| (c.name.clone(), c.digest.clone()))
+        .collect();
+    let provenance = inspect::app_provenance(
+        &app_meta,
+        profile.map(|(name, _)| name),
+        &commands,
+        &digests,
+    );
+    let bytes = inspect::embed_provenance(bytes, &provenance);
+
     // Create output directory if needed
     if let Some(parent) = output_path.parent()
         && !parent.as_os_str().is_empty()
@@ -681,41 +1185,183 @@ fn build(args: BuildArgs) -> Result<()> {
                 lock.molt_registry = Some(v.to_string());
             }
         }
-        crate::lock::write_lock(&lock_path, &mut lock)
-            .with_context(|| format!("failed to write lock file: {}", lock_path.display()))?;
+        // No extra context: write_lock errors name the file, and a refusal to
+        // rewrite a newer lock must reach the user verbatim.
+        crate::lock::write_lock(&lock_path, &mut lock)?;
         tracing::info!("updated lock file: {}", lock_path.display());
     }
 
     eprintln!("Built: {}", output_path.display());
 
+    if let (Some(format), Some(deps)) = (args.report, report_deps) {
+        let report = report::collect(
+            &commands,
+            &deps,
+            &encoded_sizes,
+            &output_path,
+            bytes.len() as u64,
+        )?;
+        print!("{}", report::render(&report, format)?);
+    }
+
+    Ok(Some(output_path))
+}
+
+/// Strip every package to be composed in place, reporting what each one saved.
+fn strip_packages(
+    packages: &mut IndexMap<BorrowedPackageKey<'_>, Vec<u8>>,
+    level: strip::StripLevel,
+) -> Result<()> {
+    let (mut before, mut after) = (0, 0);
+    for (key, bytes) in packages.iter_mut() {
+        let stripped =
+            strip::strip(bytes, level).with_context(|| format!("failed to strip {}", key.name))?;
+        eprintln!(
+            "Stripped {}: {} -> {} (saved {})",
+            key.name,
+            progress::human_bytes(bytes.len() as u64),
+            progress::human_bytes(stripped.len() as u64),
+            progress::human_bytes((bytes.len() - stripped.len()) as u64),
+        );
+        before += bytes.len();
+        after += stripped.len();
+        *bytes = stripped;
+    }
+    eprintln!(
+        "Stripped {} component(s): saved {} of {}",
+        packages.len(),
+        progress::human_bytes((before - after) as u64),
+        progress::human_bytes(before as u64),
+    );
     Ok(())
 }
 
+fn resolve_build_info(
+    git_rev: Option<String>,
+    build_timestamp: bool,
+    facts: &[String],
+) -> Result<BuildInfo> {
+    let git_revision = git_rev
+        .or_else(|| std::env::var("WACLI_GIT_REV").ok())
+        .or_else(|| std::env::var("GIT_SHA").ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let mut built_with = Vec::new();
+    if build_timestamp {
+        let secs = match std::env::var("SOURCE_DATE_EPOCH") {
+            Ok(v) => v
+                .trim()
+                .parse::<u64>()
+                .with_context(|| format!("invalid SOURCE_DATE_EPOCH: {v}"))?,
+            Err(_) => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .context("system clock is before UNIX_EPOCH")?
+                .as_secs(),
+        };
+        built_with.push(format!("build-timestamp: {secs}"));
+    }
+    for fact in facts {
+        built_with.push(parse_build_fact(fact)?);
+    }
+
+    Ok(BuildInfo {
+        wacli_version: env!("CARGO_PKG_VERSION").to_string(),
+        git_revision,
+        built_with,
+    })
+}
+
+/// `--build-info KEY=VALUE` as the `KEY: VALUE` line it adds to `built-with`.
+/// Keys wacli sets itself are refused so the build info stays unambiguous.
+fn parse_build_fact(s: &str) -> Result<String> {
+    let (key, value) = s
+        .split_once('=')
+        .with_context(|| format!("--build-info should be KEY=VALUE, got '{s}'"))?;
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() || key.contains(|c: char| c == ':' || c.is_whitespace()) {
+        bail!("invalid --build-info key '{key}': use a non-empty key without ':' or spaces");
+    }
+    if matches!(
+        key,
+        "git-revision" | "build-timestamp" | "profile" | "features"
+    ) {
+        bail!("--build-info cannot set '{key}', wacli sets it itself");
+    }
+    Ok(format!("{key}: {value}"))
+}
+
+/// Whether the cached component at `dest` can be used: it exists and, when the
+/// lock carries its layer digest, still hashes to it. A corrupted copy is
+/// removed so the caller pulls it again, or reported if it cannot be pulled.
+fn use_cached_component(dest: &Path, layer_digest: Option<&str>, can_pull: bool) -> Result<bool> {
+    if !dest.exists() {
+        return Ok(false);
+    }
+    let Some(digest) = layer_digest.filter(|d| !d.trim().is_empty()) else {
+        return Ok(true);
+    };
+    let Err(err) = crate::lock::verify_digest(dest, digest) else {
+        return Ok(true);
+    };
+    if !can_pull {
+        bail!(
+            "cache corrupted: {err}\n\n\
+Run `wacli clean --cache` (or delete .wacli/), or configure MOLT_REGISTRY to download it again."
+        );
+    }
+    tracing::warn!("cache corrupted, pulling it again: {err}");
+    fs::remove_file(dest).with_context(|| format!("failed to remove {}", dest.display()))?;
+    Ok(false)
+}
+
 fn resolve_framework_components(
     defaults_dir: &Path,
     base_dir: &Path,
+    profile: Option<(&str, &manifest::BuildProfile)>,
     update_lock: bool,
+    offline: &mut offline::Offline,
     lock: &mut crate::lock::LockFile,
     lock_dirty: &mut bool,
 ) -> Result<(PathBuf, PathBuf)> {
+    // A profile that pins a component gets its own lock entry and never uses
+    // the defaults/ copy, which belongs to the default build.
+    let host_lock_key = profile.filter(|(_, p)| p.pins_host()).map(|(name, _)| name);
+    let core_lock_key = profile.filter(|(_, p)| p.pins_core()).map(|(name, _)| name);
     let host_local = defaults_dir.join("host.component.wasm");
     let core_local = defaults_dir.join("core.component.wasm");
+    let use_host_local = host_lock_key.is_none() && host_local.exists();
+    let use_core_local = core_lock_key.is_none() && core_local.exists();
 
     // Preserve defaults/ semantics: if both are present locally, prefer them.
-    if host_local.exists() && core_local.exists() {
+    if use_host_local && use_core_local {
         return Ok((host_local, core_local));
     }
 
-    let client = molt_registry_client::OciWasmClient::from_env()?;
+    let client = if offline.enabled {
+        None
+    } else {
+        molt_registry_client::OciWasmClient::from_env()?
+    };
 
     let version_tag = format!("v{}", env!("CARGO_PKG_VERSION"));
-    let desired_host_repo =
-        std::env::var("WACLI_HOST_REPO").unwrap_or_else(|_| "wacli/host".to_string());
-    let desired_core_repo =
-        std::env::var("WACLI_CORE_REPO").unwrap_or_else(|_| "wacli/core".to_string());
-    let desired_host_ref =
-        std::env::var("WACLI_HOST_REFERENCE").unwrap_or_else(|_| version_tag.clone());
-    let desired_core_ref = std::env::var("WACLI_CORE_REFERENCE").unwrap_or(version_tag);
+    let profile = profile.map(|(_, p)| p);
+    let desired_host_repo = profile
+        .and_then(|p| p.host_repo.clone())
+        .or_else(|| std::env::var("WACLI_HOST_REPO").ok())
+        .unwrap_or_else(|| "wacli/host".to_string());
+    let desired_core_repo = profile
+        .and_then(|p| p.core_repo.clone())
+        .or_else(|| std::env::var("WACLI_CORE_REPO").ok())
+        .unwrap_or_else(|| "wacli/core".to_string());
+    let desired_host_ref = profile
+        .and_then(|p| p.host_reference.clone())
+        .or_else(|| std::env::var("WACLI_HOST_REFERENCE").ok())
+        .unwrap_or_else(|| version_tag.clone());
+    let desired_core_ref = profile
+        .and_then(|p| p.core_reference.clone())
+        .or_else(|| std::env::var("WACLI_CORE_REFERENCE").ok())
+        .unwrap_or(version_tag);
 
     let cache_dir = base_dir.join(".wacli").join("framework");
     fs::create_dir_all(&cache_dir)
@@ -730,19 +1376,26 @@ fn resolve_framework_components(
 
     // Resolve host/core individually. Prefer local defaults if present; otherwise use lock+cache,
     // then fall back to resolving from the registry.
-    let host_path = if host_local.exists() {
+    let host_path = if use_host_local {
         host_local
     } else if !update_lock {
-        if let Some(locked) = lock.framework_host() {
+        if let Some(locked) = lock.framework_host(host_lock_key) {
             let digest = locked.digest.trim();
             if digest.is_empty() {
                 bail!("wacli.lock has an empty digest for framework host");
             }
 
             let dest = cache_path(&locked.repo, digest, "host.component.wasm");
-            if dest.exists() {
+            if use_cached_component(&dest, locked.layer_digest.as_deref(), client.is_some())? {
                 tracing::info!("using cached host (locked): {}", dest.display());
                 dest
+            } else if offline.enabled {
+                offline.miss(
+                    "framework host",
+                    format!("{}@{}", locked.repo, digest),
+                    Some(&dest),
+                );
+                dest
             } else {
                 let Some(client) = client.as_ref() else {
                     bail!(
@@ -768,9 +1421,22 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                 tracing::info!("cached host: {}", dest.display());
                 dest
             }
+        } else if offline.enabled {
+            offline.miss(
+                "framework host",
+                format!("{desired_host_repo}:{desired_host_ref}"),
+                host_lock_key.is_none().then_some(host_local.as_path()),
+            );
+            host_local
         } else {
             // No lock entry; resolve via registry.
             let Some(client) = client.as_ref() else {
+                if let Some(name) = host_lock_key {
+                    bail!(
+                        "profile '{name}' pins the framework host ({desired_host_repo}:{desired_host_ref}), \
+                         but wacli.lock has no digest for it; set MOLT_REGISTRY to resolve it"
+                    );
+                }
                 return crate::component_scan::verify_defaults(defaults_dir);
             };
 
@@ -787,16 +1453,20 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                     )
                 })?;
 
-            lock.set_framework_host(crate::lock::LockedComponent {
-                repo: desired_host_repo.clone(),
-                reference: desired_host_ref.clone(),
-                digest: manifest_digest.clone(),
-                layer_digest: Some(layer_digest),
-            });
+            lock.set_framework_host(
+                host_lock_key,
+                crate::lock::LockedComponent {
+                    repo: desired_host_repo.clone(),
+                    reference: desired_host_ref.clone(),
+                    digest: manifest_digest.clone(),
+                    layer_digest: Some(layer_digest.clone()),
+                    extra: Default::default(),
+                },
+            );
             *lock_dirty = true;
 
             let dest = cache_path(&desired_host_repo, &manifest_digest, "host.component.wasm");
-            if dest.exists() {
+            if use_cached_component(&dest, Some(&layer_digest), true)? {
                 tracing::info!("using cached host: {}", dest.display());
                 dest
             } else {
@@ -836,16 +1506,20 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                 )
             })?;
 
-        lock.set_framework_host(crate::lock::LockedComponent {
-            repo: desired_host_repo.clone(),
-            reference: desired_host_ref.clone(),
-            digest: manifest_digest.clone(),
-            layer_digest: Some(layer_digest),
-        });
+        lock.set_framework_host(
+            host_lock_key,
+            crate::lock::LockedComponent {
+                repo: desired_host_repo.clone(),
+                reference: desired_host_ref.clone(),
+                digest: manifest_digest.clone(),
+                layer_digest: Some(layer_digest.clone()),
+                extra: Default::default(),
+            },
+        );
         *lock_dirty = true;
 
         let dest = cache_path(&desired_host_repo, &manifest_digest, "host.component.wasm");
-        if dest.exists() {
+        if use_cached_component(&dest, Some(&layer_digest), true)? {
             tracing::info!("using cached host: {}", dest.display());
             dest
         } else {
@@ -868,19 +1542,26 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
         }
     };
 
-    let core_path = if core_local.exists() {
+    let core_path = if use_core_local {
         core_local
     } else if !update_lock {
-        if let Some(locked) = lock.framework_core() {
+        if let Some(locked) = lock.framework_core(core_lock_key) {
             let digest = locked.digest.trim();
             if digest.is_empty() {
                 bail!("wacli.lock has an empty digest for framework core");
             }
 
             let dest = cache_path(&locked.repo, digest, "core.component.wasm");
-            if dest.exists() {
+            if use_cached_component(&dest, locked.layer_digest.as_deref(), client.is_some())? {
                 tracing::info!("using cached core (locked): {}", dest.display());
                 dest
+            } else if offline.enabled {
+                offline.miss(
+                    "framework core",
+                    format!("{}@{}", locked.repo, digest),
+                    Some(&dest),
+                );
+                dest
             } else {
                 let Some(client) = client.as_ref() else {
                     bail!(
@@ -906,8 +1587,21 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                 tracing::info!("cached core: {}", dest.display());
                 dest
             }
+        } else if offline.enabled {
+            offline.miss(
+                "framework core",
+                format!("{desired_core_repo}:{desired_core_ref}"),
+                core_lock_key.is_none().then_some(core_local.as_path()),
+            );
+            core_local
         } else {
             let Some(client) = client.as_ref() else {
+                if let Some(name) = core_lock_key {
+                    bail!(
+                        "profile '{name}' pins the framework core ({desired_core_repo}:{desired_core_ref}), \
+                         but wacli.lock has no digest for it; set MOLT_REGISTRY to resolve it"
+                    );
+                }
                 return crate::component_scan::verify_defaults(defaults_dir);
             };
 
@@ -924,16 +1618,20 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                     )
                 })?;
 
-            lock.set_framework_core(crate::lock::LockedComponent {
-                repo: desired_core_repo.clone(),
-                reference: desired_core_ref.clone(),
-                digest: manifest_digest.clone(),
-                layer_digest: Some(layer_digest),
-            });
+            lock.set_framework_core(
+                core_lock_key,
+                crate::lock::LockedComponent {
+                    repo: desired_core_repo.clone(),
+                    reference: desired_core_ref.clone(),
+                    digest: manifest_digest.clone(),
+                    layer_digest: Some(layer_digest.clone()),
+                    extra: Default::default(),
+                },
+            );
             *lock_dirty = true;
 
             let dest = cache_path(&desired_core_repo, &manifest_digest, "core.component.wasm");
-            if dest.exists() {
+            if use_cached_component(&dest, Some(&layer_digest), true)? {
                 tracing::info!("using cached core: {}", dest.display());
                 dest
             } else {
@@ -973,16 +1671,20 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
                 )
             })?;
 
-        lock.set_framework_core(crate::lock::LockedComponent {
-            repo: desired_core_repo.clone(),
-            reference: desired_core_ref.clone(),
-            digest: manifest_digest.clone(),
-            layer_digest: Some(layer_digest),
-        });
+        lock.set_framework_core(
+            core_lock_key,
+            crate::lock::LockedComponent {
+                repo: desired_core_repo.clone(),
+                reference: desired_core_ref.clone(),
+                digest: manifest_digest.clone(),
+                layer_digest: Some(layer_digest.clone()),
+                extra: Default::default(),
+            },
+        );
         *lock_dirty = true;
 
         let dest = cache_path(&desired_core_repo, &manifest_digest, "core.component.wasm");
-        if dest.exists() {
+        if use_cached_component(&dest, Some(&layer_digest), true)? {
             tracing::info!("using cached core: {}", dest.display());
             dest
         } else {
@@ -1005,6 +1707,10 @@ Set MOLT_REGISTRY (and auth) to download framework components.",
         }
     };
 
+    // Reported with the missing commands once those are resolved too.
+    if offline.has_misses() {
+        return Ok((host_path, core_path));
+    }
     if !host_path.exists() {
         bail!("failed to resolve host.component.wasm (local missing, cache missing)");
     }
@@ -1019,6 +1725,8 @@ fn resolve_registry_commands(
     base_dir: &Path,
     commands: &[manifest::RegistryCommand],
     update_lock: bool,
+    allow_meta_exec: bool,
+    offline: &mut offline::Offline,
     lock: &mut crate::lock::LockFile,
     lock_dirty: &mut bool,
 ) -> Result<Vec<crate::component_scan::CommandInfo>> {
@@ -1026,7 +1734,188 @@ fn resolve_registry_commands(
         return Ok(Vec::new());
     }
 
-    let client = molt_registry_client::OciWasmClient::from_env()?;
+    // Validate every entry before fetching anything, whatever its source.
+    let mut seen = HashSet::new();
+    let mut sources = Vec::with_capacity(commands.len());
+    for cmd in commands {
+        let source = cmd.source()?;
+        let name = cmd.name.trim();
+        if !crate::component_scan::is_valid_command_name(name) {
+            bail!(
+                "invalid command name '{}' in build.commands (must match [a-z][a-z0-9-]*)",
+                cmd.name
+            );
+        }
+        if !seen.insert(name) {
+            bail!("duplicate command name '{name}' in build.commands");
+        }
+        sources.push((name, source));
+    }
+
+    let mut out = Vec::with_capacity(sources.len());
+    let mut oci = Vec::new();
+    let mut git_names = Vec::new();
+    for (name, source) in sources {
+        match source {
+            manifest::CommandSource::Oci { repo, reference } => oci.push((name, repo, reference)),
+            manifest::CommandSource::Path(path) => {
+                out.push(resolve_path_command(base_dir, name, path, allow_meta_exec)?);
+            }
+            manifest::CommandSource::Git { url, rev, path } => {
+                git_names.push(name);
+                out.extend(resolve_git_command(
+                    base_dir,
+                    name,
+                    url,
+                    rev,
+                    path,
+                    update_lock,
+                    allow_meta_exec,
+                    offline,
+                    lock,
+                    lock_dirty,
+                )?);
+            }
+        }
+    }
+    out.extend(resolve_oci_commands(
+        base_dir,
+        &oci,
+        update_lock,
+        allow_meta_exec,
+        offline,
+        lock,
+        lock_dirty,
+    )?);
+
+    if update_lock {
+        lock.commands
+            .retain(|c| oci.iter().any(|(n, ..)| *n == c.name));
+        lock.git_commands
+            .retain(|c| git_names.contains(&c.name.as_str()));
+    }
+
+    Ok(out)
+}
+
+/// A `{ "path" }` entry: used as is, never locked.
+fn resolve_path_command(
+    base_dir: &Path,
+    name: &str,
+    path: &Path,
+    allow_meta_exec: bool,
+) -> Result<crate::component_scan::CommandInfo> {
+    let path = base_dir.join(path);
+    tracing::info!("using local command {} from {}", name, path.display());
+    crate::component_scan::inspect_command_component_as(&path, name, allow_meta_exec)
+}
+
+/// A `{ "git", "rev" }` entry: locked by the commit `rev` resolved to.
+/// `None` if it is missing from the cache in an offline build.
+#[allow(clippy::too_many_arguments)]
+fn resolve_git_command(
+    base_dir: &Path,
+    name: &str,
+    url: &str,
+    rev: &str,
+    path: Option<&Path>,
+    update_lock: bool,
+    allow_meta_exec: bool,
+    offline: &mut offline::Offline,
+    lock: &mut crate::lock::LockFile,
+    lock_dirty: &mut bool,
+) -> Result<Option<crate::component_scan::CommandInfo>> {
+    let cache_dir = base_dir.join(".wacli").join("git");
+    let default_file = PathBuf::from(format!("{name}.component.wasm"));
+    let file = path.unwrap_or(&default_file);
+
+    let locked = lock.find_git_command(name).cloned();
+    let pinned = match locked.as_ref() {
+        Some(locked) if !update_lock => {
+            if locked.git != url || locked.rev != rev {
+                bail!(
+                    "wacli.lock is out of date for command '{}':\n  lock: {}@{}\n  manifest: {}@{}\n\nRun: wacli build --update-lock",
+                    name,
+                    locked.git,
+                    locked.rev,
+                    url,
+                    rev
+                );
+            }
+            let commit = locked.commit.trim();
+            if commit.is_empty() {
+                bail!("wacli.lock has an empty commit for command '{name}'");
+            }
+            Some(commit.to_string())
+        }
+        _ => None,
+    };
+
+    let cached = pinned
+        .as_deref()
+        .map(|commit| crate::git_source::cached_component(&cache_dir, url, commit, name))
+        .filter(|p| p.exists());
+    let dest = match cached {
+        Some(dest) => {
+            tracing::info!("using cached command {} from {}@{}", name, url, rev);
+            dest
+        }
+        None if offline.enabled => {
+            let expected = pinned
+                .as_deref()
+                .map(|commit| crate::git_source::cached_component(&cache_dir, url, commit, name));
+            offline.miss(
+                format!("command {name}"),
+                format!("{url}@{rev}"),
+                expected.as_deref(),
+            );
+            return Ok(None);
+        }
+        None => {
+            let checkout = pinned.as_deref().unwrap_or(rev);
+            tracing::info!("fetching command {} from git {}@{}", name, url, checkout);
+            let fetched =
+                crate::git_source::fetch_component(base_dir, &cache_dir, url, checkout, file, name)
+                    .map_err(|e| {
+                        anyhow::anyhow!("failed to fetch command {name} from {url}@{rev}: {e:#}")
+                    })?;
+            if pinned.is_none() {
+                lock.set_git_command(crate::lock::LockedGitCommand {
+                    name: name.to_string(),
+                    git: url.to_string(),
+                    rev: rev.to_string(),
+                    commit: fetched.commit,
+                    extra: Default::default(),
+                });
+                *lock_dirty = true;
+            }
+            fetched.path
+        }
+    };
+
+    crate::component_scan::inspect_command_component_as(&dest, name, allow_meta_exec).map(Some)
+}
+
+/// `{ "repo", "reference" }` entries: pulled from the OCI registry and locked
+/// by manifest digest.
+fn resolve_oci_commands(
+    base_dir: &Path,
+    commands: &[(&str, &str, &str)],
+    update_lock: bool,
+    allow_meta_exec: bool,
+    offline: &mut offline::Offline,
+    lock: &mut crate::lock::LockFile,
+    lock_dirty: &mut bool,
+) -> Result<Vec<crate::component_scan::CommandInfo>> {
+    if commands.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = if offline.enabled {
+        None
+    } else {
+        molt_registry_client::OciWasmClient::from_env()?
+    };
     if update_lock && client.is_none() {
         bail!("--update-lock requires MOLT_REGISTRY to be configured");
     }
@@ -1035,9 +1924,10 @@ fn resolve_registry_commands(
     fs::create_dir_all(&cache_dir)
         .with_context(|| format!("failed to create directory: {}", cache_dir.display()))?;
 
-    let refresh = std::env::var("WACLI_REGISTRY_REFRESH")
-        .map(|v| !v.trim().is_empty() && v.trim() != "0")
-        .unwrap_or(false);
+    let refresh = !offline.enabled
+        && std::env::var("WACLI_REGISTRY_REFRESH")
+            .map(|v| !v.trim().is_empty() && v.trim() != "0")
+            .unwrap_or(false);
 
     // Runtime for digest resolution when MOLT_REGISTRY is configured.
     let rt = if client.is_some() {
@@ -1051,30 +1941,9 @@ fn resolve_registry_commands(
         None
     };
 
-    let referenced_names: Vec<String> =
-        commands.iter().map(|c| c.name.trim().to_string()).collect();
-
     let mut out = Vec::with_capacity(commands.len());
-    for cmd in commands {
-        if cmd.repo.trim().is_empty() {
-            bail!("build.commands entry for '{}' has an empty repo", cmd.name);
-        }
-        if cmd.reference.trim().is_empty() {
-            bail!(
-                "build.commands entry for '{}' has an empty reference",
-                cmd.name
-            );
-        }
-        if !crate::component_scan::is_valid_command_name(cmd.name.trim()) {
-            bail!(
-                "invalid command name '{}' in build.commands (must match [a-z][a-z0-9-]*)",
-                cmd.name
-            );
-        }
-
-        let repo = cmd.repo.trim().to_string();
-        let reference = cmd.reference.trim().to_string();
-        let name = cmd.name.trim().to_string();
+    for &(name, repo, reference) in commands {
+        let (name, repo, reference) = (name.to_string(), repo.to_string(), reference.to_string());
 
         let locked = lock.find_command(&name).cloned();
 
@@ -1107,6 +1976,13 @@ fn resolve_registry_commands(
                 bail!("wacli.lock has an empty digest for command '{name}'");
             }
             (digest.to_string(), locked.layer_digest.clone())
+        } else if offline.enabled {
+            offline.miss(
+                format!("command {name}"),
+                format!("{repo}:{reference}"),
+                None,
+            );
+            continue;
         } else {
             let Some(client) = client.as_ref() else {
                 bail!(
@@ -1133,7 +2009,8 @@ Set MOLT_REGISTRY (and auth) or run once with registry configured to generate wa
                 repo: repo.clone(),
                 reference: reference.clone(),
                 digest: manifest_digest.clone(),
-                layer_digest,
+                layer_digest: layer_digest.clone(),
+                extra: Default::default(),
             });
             *lock_dirty = true;
         }
@@ -1145,13 +2022,20 @@ Set MOLT_REGISTRY (and auth) or run once with registry configured to generate wa
             ))
             .join(format!("{}.component.wasm", name));
 
-        if dest.exists() && !refresh {
+        if !refresh && use_cached_component(&dest, layer_digest.as_deref(), client.is_some())? {
             tracing::info!(
                 "using cached command {} from {}@{}",
                 name,
                 repo,
                 manifest_digest
             );
+        } else if offline.enabled {
+            offline.miss(
+                format!("command {name}"),
+                format!("{repo}@{manifest_digest}"),
+                Some(&dest),
+            );
+            continue;
         } else {
             let Some(client) = client.as_ref() else {
                 bail!(
@@ -1181,7 +2065,7 @@ Set MOLT_REGISTRY (and auth) to download.",
             })?;
         }
 
-        let info = crate::component_scan::inspect_command_component(&dest)
+        let info = crate::component_scan::inspect_command_component(&dest, allow_meta_exec)
             .with_context(|| format!("invalid command component for '{name}'"))?;
         if info.name != name {
             bail!(
@@ -1194,11 +2078,6 @@ Set MOLT_REGISTRY (and auth) to download.",
         out.push(info);
     }
 
-    if update_lock {
-        lock.commands
-            .retain(|c| referenced_names.iter().any(|n| n == &c.name));
-    }
-
     Ok(out)
 }
 
@@ -1206,34 +2085,94 @@ fn fmt_err(e: impl std::fmt::Display, path: &Path) -> anyhow::Error {
     anyhow::Error::msg(format!("{}: {}", path.display(), e))
 }
 
-fn parse_dep(s: &str) -> Result<(String, PathBuf)> {
-    let (k, v) = s
-        .split_once('=')
-        .context("dependency format should be PKG=PATH")?;
-    Ok((k.trim().to_string(), PathBuf::from(v.trim())))
+/// Whether `path` is `-`, meaning stdin (for inputs) or stdout (for outputs).
+fn is_stdio_path(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Read all bytes from `path`, or from stdin if `path` is `-`.
+fn read_input(path: &Path) -> std::io::Result<Vec<u8>> {
+    if is_stdio_path(path) {
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
+        Ok(buf)
+    } else {
+        fs::read(path)
+    }
+}
+
+/// Stdin can only be consumed once, so at most one input may be `-`.
+fn ensure_single_stdin_input<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Result<()> {
+    let count = paths.into_iter().filter(|p| is_stdio_path(p)).count();
+    if count > 1 {
+        bail!("only one input may be read from stdin (`-`), but {count} were given");
+    }
+    Ok(())
+}
+
+/// Resolve the output destination; `None` means stdout.
+///
+/// Refuses to write binary wasm to an interactive terminal.
+fn output_target(output: Option<PathBuf>) -> Result<Option<PathBuf>> {
+    let output = output.filter(|p| !is_stdio_path(p));
+    if output.is_none() && std::io::stdout().is_terminal() {
+        bail!("cannot print binary wasm output to terminal; use -o to specify output file");
+    }
+    Ok(output)
+}
+
+fn write_output(output: Option<PathBuf>, bytes: &[u8], verb: &str) -> Result<()> {
+    match output {
+        Some(path) => {
+            fs::write(&path, bytes)
+                .with_context(|| format!("failed to write output file `{}`", path.display()))?;
+            eprintln!("{verb}: {}", path.display());
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout
+                .write_all(bytes)
+                .context("failed to write to stdout")?;
+            stdout.flush().context("failed to write to stdout")?;
+        }
+    }
+    Ok(())
 }
 
 fn compose(args: ComposeArgs) -> Result<()> {
     tracing::debug!("executing compose command");
 
     // Read the WAC source file
-    let contents = fs::read_to_string(&args.path)
+    let contents = read_input(&args.path)
         .with_context(|| format!("failed to read file `{}`", args.path.display()))?;
+    let contents = String::from_utf8(contents)
+        .with_context(|| format!("file `{}` is not valid UTF-8", args.path.display()))?;
 
     // Parse the document
     let document = Document::parse(&contents).map_err(|e| fmt_err(e, &args.path))?;
 
     // Parse dependency overrides
-    let overrides: HashMap<String, PathBuf> = args
+    let deps = args
         .deps
         .iter()
-        .map(|s| parse_dep(s))
-        .collect::<Result<_>>()?;
+        .map(|s| compose_deps::parse_dep(s))
+        .collect::<Result<Vec<_>>>()?;
 
-    // Resolve packages
-    let resolver = FileSystemPackageResolver::new(&args.deps_dir, overrides, false);
+    // Resolve packages: explicit paths, the deps directory, then the registry.
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let offline = args.offline || offline::offline_from_env();
+    let mut resolver = compose_deps::DepResolver::new(&args.deps_dir, &cwd, deps, offline);
     let keys = packages(&document).map_err(|e| fmt_err(e, &args.path))?;
-    let resolved_packages: IndexMap<BorrowedPackageKey<'_>, Vec<u8>> = resolver.resolve(&keys)?;
+    let mut resolved_packages: IndexMap<BorrowedPackageKey<'_>, Vec<u8>> =
+        resolver.filesystem().resolve(&keys)?;
+    let lock_path = crate::lock::lock_path(&cwd);
+    let mut lock = crate::lock::load_lock(&lock_path)?.unwrap_or_default();
+    let mut lock_dirty = false;
+    resolver.pull_missing(&keys, &mut resolved_packages, &mut lock, &mut lock_dirty)?;
+    if lock_dirty {
+        crate::lock::write_lock(&lock_path, &mut lock)?;
+        tracing::info!("updated lock file: {}", lock_path.display());
+    }
 
     // Check for unresolved packages
     let mut missing: Vec<_> = keys
@@ -1255,9 +2194,7 @@ fn compose(args: ComposeArgs) -> Result<()> {
         .map_err(|e| fmt_err(e, &args.path))?;
 
     // Check output
-    if args.output.is_none() && std::io::stdout().is_terminal() {
-        bail!("cannot print binary wasm output to terminal; use -o to specify output file");
-    }
+    let output = output_target(args.output)?;
 
     // Encode the composition
     let bytes = resolution.encode(EncodeOptions {
@@ -1266,30 +2203,20 @@ fn compose(args: ComposeArgs) -> Result<()> {
         ..Default::default()
     })?;
 
-    // Write output
-    match args.output {
-        Some(path) => {
-            fs::write(&path, &bytes)
-                .with_context(|| format!("failed to write output file `{}`", path.display()))?;
-            eprintln!("Composed: {}", path.display());
-        }
-        None => {
-            std::io::stdout()
-                .write_all(&bytes)
-                .context("failed to write to stdout")?;
-        }
-    }
-
-    Ok(())
+    write_output(output, &bytes, "Composed")
 }
 
 fn plug(args: PlugArgs) -> Result<()> {
     tracing::debug!("executing plug command");
 
+    ensure_single_stdin_input(
+        std::iter::once(args.socket.as_path()).chain(args.plugs.iter().map(PathBuf::as_path)),
+    )?;
+
     let mut graph = CompositionGraph::new();
 
     // Load socket component
-    let socket_bytes = fs::read(&args.socket)
+    let socket_bytes = read_input(&args.socket)
         .with_context(|| format!("failed to read socket `{}`", args.socket.display()))?;
     let socket_pkg = Package::from_bytes("socket", None, socket_bytes, graph.types_mut())?;
     let socket = graph.register_package(socket_pkg)?;
@@ -1297,7 +2224,7 @@ fn plug(args: PlugArgs) -> Result<()> {
     // Load plug components
     let mut plug_ids = Vec::new();
     for (i, plug_path) in args.plugs.iter().enumerate() {
-        let plug_bytes = fs::read(plug_path)
+        let plug_bytes = read_input(plug_path)
             .with_context(|| format!("failed to read plug `{}`", plug_path.display()))?;
         let name = format!("plug{}", i);
         let plug_pkg = Package::from_bytes(&name, None, plug_bytes, graph.types_mut())?;
@@ -1312,30 +2239,81 @@ fn plug(args: PlugArgs) -> Result<()> {
     let bytes = graph.encode(EncodeOptions::default())?;
 
     // Check output
-    if args.output.is_none() && std::io::stdout().is_terminal() {
-        bail!("cannot print binary wasm output to terminal; use -o to specify output file");
-    }
+    let output = output_target(args.output)?;
 
-    // Write output
-    match args.output {
-        Some(path) => {
-            fs::write(&path, &bytes)
-                .with_context(|| format!("failed to write output file `{}`", path.display()))?;
-            eprintln!("Plugged: {}", path.display());
-        }
-        None => {
-            std::io::stdout()
-                .write_all(&bytes)
-                .context("failed to write to stdout")?;
-        }
-    }
+    write_output(output, &bytes, "Plugged")
+}
 
+fn write_man_pages(dir: &Path, app: &AppMeta, commands: &[CommandInfo]) -> Result<()> {
+    let schemas: Vec<_> = commands
+        .iter()
+        .map(|cmd| {
+            cmd.metadata
+                .command_schema
+                .clone()
+                .unwrap_or_else(|| CommandSchema::from_meta(&cmd.metadata.command_meta))
+        })
+        .collect();
+    let man_commands: Vec<man_page::ManCommand> = commands
+        .iter()
+        .zip(&schemas)
+        .map(|(cmd, schema)| man_page::ManCommand {
+            path: cmd
+                .group
+                .iter()
+                .cloned()
+                .chain([cmd.name.clone()])
+                .collect(),
+            schema,
+        })
+        .collect();
+    let man_app = man_page::ManApp {
+        name: man_page::app_binary_name(&app.name),
+        version: &app.version,
+        description: &app.description,
+    };
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create directory: {}", dir.display()))?;
+    for (file, contents) in man_page::render_man_pages(&man_app, &man_commands) {
+        let path = dir.join(file);
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write man page: {}", path.display()))?;
+    }
+    tracing::info!("wrote man pages to {}", dir.display());
     Ok(())
 }
 
 #[cfg(feature = "runtime")]
 fn run(args: RunArgs) -> Result<()> {
-    let runner = plugin_loader::Runner::new()?;
+    let mut env_files = args.env_files;
+    if env_files.is_empty()
+        && let Some(path) = std::env::var_os("WACLI_ENV_FILE").filter(|p| !p.is_empty())
+    {
+        env_files.push(PathBuf::from(path));
+    }
+    let env = plugin_loader::EnvPolicy {
+        vars: env_file::load_env_files(&env_files)?,
+        ..Default::default()
+    };
+    let network = plugin_loader::NetworkPolicy {
+        allow: args.allow_network,
+        hosts: args.allow_hosts,
+    };
+    tracing::debug!("network policy: {network}");
+    let limits = plugin_loader::RunnerConfig {
+        timeout: args.timeout.map(std::time::Duration::from_secs),
+        fuel: args.fuel,
+    };
+    let runner = plugin_loader::Runner::new_with_config(limits)?
+        .with_env_policy(env)
+        .with_network_policy(network)
+        .with_plugins_dir(plugin_loader::resolve_plugins_dir(
+            args.plugins_dir,
+            std::env::var_os(plugin_loader::PLUGINS_DIR_ENV),
+            &std::env::current_dir().context("failed to read current directory")?,
+        ))
+        .with_global_value_flags(args.global_flags);
     let mut preopens = Vec::new();
     for dir in &args.dirs {
         preopens.push(parse_preopen_dir(dir)?);
@@ -1344,7 +2322,20 @@ fn run(args: RunArgs) -> Result<()> {
     for dir in extra_dirs {
         preopens.push(parse_preopen_dir(&dir)?);
     }
-    let code = runner.run_component_with_preopens(&args.component, &passthrough_args, &preopens)?;
+    let stdio = plugin_loader::StdioOptions {
+        stdout: args.stdout.map_or(
+            plugin_loader::OutputTarget::Inherit,
+            plugin_loader::OutputTarget::File,
+        ),
+        stderr: args.stderr.map_or(
+            plugin_loader::OutputTarget::Inherit,
+            plugin_loader::OutputTarget::File,
+        ),
+    };
+    let loaded = runner.load(&args.component)?;
+    let code = runner
+        .run_redirected(&loaded, &passthrough_args, &preopens, &stdio)?
+        .code;
     if code != 0 {
         std::process::exit(code as i32);
     }
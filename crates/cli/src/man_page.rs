@@ -0,0 +1,498 @@
+//! roff man page generation from command schemas (`wacli build --emit-man`).
+//!
+//! Everything here is a pure function of the app identity and the command
+//! schemas: no wall-clock dates, so the output is reproducible. The `.TH`
+//! date field carries the app version instead.
+
+use wacli_metadata::{ArgSchema, CommandSchema};
+
+/// The app a set of pages documents.
+pub struct ManApp<'a> {
+    /// Binary name (`my-cli`), without package namespace or version.
+    pub name: &'a str,
+    pub version: &'a str,
+    pub description: &'a str,
+}
+
+/// One documented command.
+pub struct ManCommand<'a> {
+    /// How the command is invoked after the app name (`["db", "migrate"]`).
+    pub path: Vec<String>,
+    pub schema: &'a CommandSchema,
+}
+
+/// Binary name for a package name such as `example:my-cli@0.1.0`.
+pub fn app_binary_name(package: &str) -> &str {
+    let name = package.split('@').next().unwrap_or(package);
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Page name (without section) for a command: `my-cli-db-migrate`.
+pub fn page_name(app: &str, path: &[String]) -> String {
+    let mut name = app.to_string();
+    for part in path {
+        name.push('-');
+        name.push_str(part);
+    }
+    name
+}
+
+/// Render `<app>.1` plus one `<app>-<command>.1` per visible command, as
+/// `(file name, contents)` pairs. Hidden commands get no page.
+pub fn render_man_pages(app: &ManApp, commands: &[ManCommand]) -> Vec<(String, String)> {
+    let visible: Vec<&ManCommand> = commands.iter().filter(|c| !c.schema.hidden).collect();
+    let mut pages = vec![(format!("{}.1", app.name), render_app_page(app, &visible))];
+    for command in &visible {
+        pages.push((
+            format!("{}.1", page_name(app.name, &command.path)),
+            render_command_page(app, command, &visible),
+        ));
+    }
+    pages
+}
+
+/// The top-level page: NAME, SYNOPSIS, DESCRIPTION, COMMANDS and SEE ALSO.
+pub fn render_app_page(app: &ManApp, commands: &[&ManCommand]) -> String {
+    let mut out = header(app, app.name);
+    section(&mut out, "NAME");
+    let tagline = app.description.trim().lines().next().unwrap_or_default();
+    out.push_str(&name_line(app.name, tagline));
+    section(&mut out, "SYNOPSIS");
+    out.push_str(&format!(".B {}\n", escape(app.name)));
+    out.push_str(&escape("<COMMAND> [ARGS]..."));
+    out.push('\n');
+
+    if !app.description.trim().is_empty() {
+        section(&mut out, "DESCRIPTION");
+        paragraphs(&mut out, app.description);
+    }
+
+    if !commands.is_empty() {
+        section(&mut out, "COMMANDS");
+        for command in commands {
+            out.push_str(".TP\n");
+            out.push_str(&format!("\\fB{}\\fR\n", escape(&command.path.join(" "))));
+            let summary = command.schema.summary.trim();
+            if !summary.is_empty() {
+                out.push_str(&escape(summary));
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "See \\fB{}\\fR(1).\n",
+                escape(&page_name(app.name, &command.path))
+            ));
+        }
+    }
+
+    let see_also: Vec<String> = commands
+        .iter()
+        .map(|c| page_name(app.name, &c.path))
+        .collect();
+    see_also_section(&mut out, &see_also);
+    out
+}
+
+/// A command page: NAME, SYNOPSIS, DESCRIPTION, ARGUMENTS, OPTIONS,
+/// EXAMPLES and SEE ALSO (the app page and the other visible commands).
+pub fn render_command_page(app: &ManApp, command: &ManCommand, siblings: &[&ManCommand]) -> String {
+    let schema = command.schema;
+    let page = page_name(app.name, &command.path);
+    let invocation = format!("{} {}", app.name, command.path.join(" "));
+
+    let mut out = header(app, &page);
+    section(&mut out, "NAME");
+    out.push_str(&name_line(&page, &schema.summary));
+
+    section(&mut out, "SYNOPSIS");
+    out.push_str(&format!(".B {}\n", escape(&invocation)));
+    let rest = synopsis_rest(schema);
+    if !rest.is_empty() {
+        out.push_str(&escape(&rest));
+        out.push('\n');
+    }
+
+    if !schema.description.trim().is_empty() || !schema.aliases.is_empty() {
+        section(&mut out, "DESCRIPTION");
+        if !schema.description.trim().is_empty() {
+            paragraphs(&mut out, &schema.description);
+        }
+        if !schema.aliases.is_empty() {
+            out.push_str(".PP\n");
+            out.push_str(&escape(&format!("Aliases: {}", schema.aliases.join(", "))));
+            out.push('\n');
+        }
+    }
+
+    let visible = schema.args.iter().filter(|a| !a.hidden);
+    let (positionals, options): (Vec<&ArgSchema>, Vec<&ArgSchema>) =
+        visible.partition(|a| a.short.is_none() && a.long.is_none());
+
+    if !positionals.is_empty() {
+        section(&mut out, "ARGUMENTS");
+        for arg in positionals {
+            out.push_str(".TP\n");
+            out.push_str(&format!("\\fI{}\\fR\n", escape(&value_name(arg))));
+            arg_body(&mut out, arg);
+        }
+    }
+
+    section(&mut out, "OPTIONS");
+    for arg in options {
+        out.push_str(".TP\n");
+        let flags: Vec<String> = [arg.short.as_deref(), arg.long.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(|f| format!("\\fB{}\\fR", escape(f)))
+            .collect();
+        let mut term = flags.join(", ");
+        if arg.takes_value {
+            term.push_str(&format!(" \\fI<{}>\\fR", escape(&value_name(arg))));
+        }
+        out.push_str(&term);
+        out.push('\n');
+        arg_body(&mut out, arg);
+    }
+    out.push_str(".TP\n\\fB\\-h\\fR, \\fB\\-\\-help\\fR\nShow help information\n");
+    out.push_str(".TP\n\\fB\\-V\\fR, \\fB\\-\\-version\\fR\nShow version information\n");
+
+    let examples: Vec<&str> = schema
+        .examples
+        .iter()
+        .map(|e| e.trim_end())
+        .filter(|e| !e.trim().is_empty())
+        .collect();
+    if !examples.is_empty() {
+        section(&mut out, "EXAMPLES");
+        for example in examples {
+            out.push_str(".PP\n.RS 4\n.nf\n");
+            out.push_str(&escape(&format!("{} {example}", app.name)));
+            out.push_str("\n.fi\n.RE\n");
+        }
+    }
+
+    let mut see_also = vec![app.name.to_string()];
+    see_also.extend(
+        siblings
+            .iter()
+            .filter(|c| c.path != command.path)
+            .map(|c| page_name(app.name, &c.path)),
+    );
+    see_also_section(&mut out, &see_also);
+    out
+}
+
+/// Escape text for roff: backslashes and hyphens, plus a leading `.` or `'`
+/// on any line (which would otherwise start a request).
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if line.starts_with('.') || line.starts_with('\'') {
+            out.push_str("\\&");
+        }
+        for c in line.chars() {
+            match c {
+                '\\' => out.push_str("\\e"),
+                '-' => out.push_str("\\-"),
+                c => out.push(c),
+            }
+        }
+    }
+    out
+}
+
+fn header(app: &ManApp, page: &str) -> String {
+    let versioned = if app.version.is_empty() {
+        app.name.to_string()
+    } else {
+        format!("{} {}", app.name, app.version)
+    };
+    format!(
+        ".TH \"{}\" \"1\" \"{}\" \"{}\" \"{} Manual\"\n",
+        escape(&page.to_uppercase()),
+        escape(app.version),
+        escape(&versioned),
+        escape(app.name)
+    )
+}
+
+fn section(out: &mut String, title: &str) {
+    out.push_str(".SH ");
+    out.push_str(title);
+    out.push('\n');
+}
+
+fn name_line(name: &str, summary: &str) -> String {
+    if summary.trim().is_empty() {
+        format!("{}\n", escape(name))
+    } else {
+        format!("{} \\- {}\n", escape(name), escape(summary.trim()))
+    }
+}
+
+fn see_also_section(out: &mut String, pages: &[String]) {
+    if pages.is_empty() {
+        return;
+    }
+    section(out, "SEE ALSO");
+    let refs: Vec<String> = pages
+        .iter()
+        .map(|p| format!("\\fB{}\\fR(1)", escape(p)))
+        .collect();
+    out.push_str(&refs.join(", "));
+    out.push('\n');
+}
+
+/// Blank-line separated paragraphs, each started with `.PP`.
+fn paragraphs(out: &mut String, text: &str) {
+    for para in text.trim().split("\n\n").filter(|p| !p.trim().is_empty()) {
+        out.push_str(".PP\n");
+        out.push_str(&escape(para.trim()));
+        out.push('\n');
+    }
+}
+
+fn arg_body(out: &mut String, arg: &ArgSchema) {
+    let mut notes = Vec::new();
+    if arg.required && (arg.short.is_some() || arg.long.is_some()) {
+        notes.push("Required.".to_string());
+    }
+    if let Some(default) = &arg.default_value {
+        notes.push(format!("Default: {default}."));
+    }
+    if let Some(env) = &arg.env {
+        notes.push(format!("Environment: {env}."));
+    }
+    if !arg.possible_values.is_empty() {
+        notes.push(format!(
+            "Possible values: {}.",
+            arg.possible_values.join(", ")
+        ));
+    }
+
+    let help = arg.help.trim();
+    if !help.is_empty() {
+        out.push_str(&escape(help));
+        out.push('\n');
+    }
+    if !notes.is_empty() {
+        if !help.is_empty() {
+            out.push_str(".br\n");
+        }
+        out.push_str(&escape(&notes.join(" ")));
+        out.push('\n');
+    }
+}
+
+fn value_name(arg: &ArgSchema) -> String {
+    arg.value_name
+        .clone()
+        .unwrap_or_else(|| arg.name.to_uppercase())
+}
+
+/// What follows the invocation in SYNOPSIS: the declared usage without its
+/// leading command name, or one generated from the args.
+fn synopsis_rest(schema: &CommandSchema) -> String {
+    let usage = schema.usage.trim();
+    if !usage.is_empty() {
+        let first = usage.split_whitespace().next().unwrap_or_default();
+        if first == schema.name || schema.aliases.iter().any(|a| a == first) {
+            return usage[first.len()..].trim_start().to_string();
+        }
+        return usage.to_string();
+    }
+
+    let mut parts = Vec::new();
+    let visible: Vec<&ArgSchema> = schema.args.iter().filter(|a| !a.hidden).collect();
+    if visible
+        .iter()
+        .any(|a| a.short.is_some() || a.long.is_some())
+    {
+        parts.push("[OPTIONS]".to_string());
+    }
+    for arg in visible
+        .iter()
+        .filter(|a| a.short.is_none() && a.long.is_none())
+    {
+        let name = value_name(arg);
+        parts.push(if arg.required {
+            format!("<{name}>")
+        } else {
+            format!("[{name}]")
+        });
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
+
+    fn app() -> ManApp<'static> {
+        ManApp {
+            name: "my-cli",
+            version: "1.2.0",
+            description: "Tools for the app.\n\nSecond paragraph.",
+        }
+    }
+
+    fn show() -> CommandSchema {
+        CommandMetaBuilder::new("show")
+            .summary("Show text")
+            .usage("show [OPTIONS] <TEXT>")
+            .alias("s")
+            .description(".hidden starts with a dot\nC:\\path")
+            .example("show --format json hello")
+            .arg(
+                ArgSchemaBuilder::new("format")
+                    .short("f")
+                    .long("format")
+                    .value_name("FMT")
+                    .help("Output format")
+                    .env("SHOW_FORMAT")
+                    .default_value("plain")
+                    .possible_values(["plain", "json"]),
+            )
+            .arg(ArgSchemaBuilder::new("raw").long("raw").hidden(true))
+            .arg(
+                ArgSchemaBuilder::new("text")
+                    .required(true)
+                    .help("Text to show"),
+            )
+            .build_schema()
+    }
+
+    #[test]
+    fn escape_handles_hyphens_backslashes_and_control_lines() {
+        assert_eq!(escape("--long-flag"), "\\-\\-long\\-flag");
+        assert_eq!(escape("a\\b"), "a\\eb");
+        assert_eq!(escape(".TH\n'x\nok.\n"), "\\&.TH\n\\&'x\nok.\n");
+    }
+
+    #[test]
+    fn app_binary_name_strips_namespace_and_version() {
+        assert_eq!(app_binary_name("example:my-cli@0.1.0"), "my-cli");
+        assert_eq!(app_binary_name("my-cli"), "my-cli");
+    }
+
+    #[test]
+    fn command_page_snapshot() {
+        let show = show();
+        let seed = CommandMetaBuilder::new("seed").build_schema();
+        let commands = [
+            ManCommand {
+                path: vec!["show".to_string()],
+                schema: &show,
+            },
+            ManCommand {
+                path: vec!["db".to_string(), "seed".to_string()],
+                schema: &seed,
+            },
+        ];
+        let siblings: Vec<&ManCommand> = commands.iter().collect();
+        let page = render_command_page(&app(), &commands[0], &siblings);
+        assert_eq!(
+            page,
+            r#".TH "MY\-CLI\-SHOW" "1" "1.2.0" "my\-cli 1.2.0" "my\-cli Manual"
+.SH NAME
+my\-cli\-show \- Show text
+.SH SYNOPSIS
+.B my\-cli show
+[OPTIONS] <TEXT>
+.SH DESCRIPTION
+.PP
+\&.hidden starts with a dot
+C:\epath
+.PP
+Aliases: s
+.SH ARGUMENTS
+.TP
+\fITEXT\fR
+Text to show
+.SH OPTIONS
+.TP
+\fB\-f\fR, \fB\-\-format\fR \fI<FMT>\fR
+Output format
+.br
+Default: plain. Environment: SHOW_FORMAT. Possible values: plain, json.
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Show help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Show version information
+.SH EXAMPLES
+.PP
+.RS 4
+.nf
+my\-cli show \-\-format json hello
+.fi
+.RE
+.SH SEE ALSO
+\fBmy\-cli\fR(1), \fBmy\-cli\-db\-seed\fR(1)
+"#
+        );
+
+        let page = render_command_page(&app(), &commands[1], &siblings);
+        assert!(
+            page.contains(".SH SYNOPSIS\n.B my\\-cli db seed\n.SH OPTIONS\n"),
+            "{page}"
+        );
+    }
+
+    #[test]
+    fn app_page_snapshot_and_hidden_commands_are_skipped() {
+        let show = show();
+        let secret = CommandMetaBuilder::new("secret")
+            .hidden(true)
+            .build_schema();
+        let commands = [
+            ManCommand {
+                path: vec!["show".to_string()],
+                schema: &show,
+            },
+            ManCommand {
+                path: vec!["secret".to_string()],
+                schema: &secret,
+            },
+        ];
+        let pages = render_man_pages(&app(), &commands);
+        let names: Vec<&str> = pages.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["my-cli.1", "my-cli-show.1"]);
+        assert_eq!(
+            pages[0].1,
+            r#".TH "MY\-CLI" "1" "1.2.0" "my\-cli 1.2.0" "my\-cli Manual"
+.SH NAME
+my\-cli \- Tools for the app.
+.SH SYNOPSIS
+.B my\-cli
+<COMMAND> [ARGS]...
+.SH DESCRIPTION
+.PP
+Tools for the app.
+.PP
+Second paragraph.
+.SH COMMANDS
+.TP
+\fBshow\fR
+Show text
+See \fBmy\-cli\-show\fR(1).
+.SH SEE ALSO
+\fBmy\-cli\-show\fR(1)
+"#
+        );
+    }
+
+    #[test]
+    fn synopsis_is_generated_when_usage_is_empty() {
+        let schema = CommandMetaBuilder::new("copy")
+            .arg(ArgSchemaBuilder::new("force").long("force"))
+            .arg(ArgSchemaBuilder::new("src").required(true))
+            .arg(ArgSchemaBuilder::new("dst").value_name("DEST"))
+            .build_schema();
+        assert_eq!(synopsis_rest(&schema), "[OPTIONS] <SRC> [DEST]");
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -44,12 +45,157 @@ pub struct BuildManifest {
     )]
     pub commands_dir: Option<PathBuf>,
 
-    /// Optional list of command plugins to pull from an OCI registry.
+    /// Optional list of command plugins to pull from an OCI registry, a git
+    /// repository or a local path.
     ///
-    /// Each entry resolves to a `<name>.component.wasm` and is treated the same
-    /// as a file found under `commandsDir`.
+    /// Each entry resolves to a command component and is treated the same as a
+    /// file found under `commandsDir`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub commands: Option<Vec<RegistryCommand>>,
+
+    /// Optional command groups (e.g. `db` for `db migrate`, `db seed`).
+    ///
+    /// Member commands are looked up by name among all resolved commands and
+    /// are no longer available at the top level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<CommandGroup>>,
+
+    /// Optional hidden command that receives a usage event after each run.
+    ///
+    /// Core never sends anything itself; it only invokes this command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_command: Option<String>,
+
+    /// Optional command that handles unknown commands (`my-cli foo` runs it
+    /// as `<fallback> foo ...`), e.g. to dispatch to installed extensions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_command: Option<String>,
+
+    /// Show an interactive command picker when the app runs with no
+    /// arguments on a terminal (instead of the global help).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interactive_picker: Option<bool>,
+
+    /// Warn when an arg's environment variable is unset but a near-miss of
+    /// its name (another case, a typo) is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_warnings: Option<bool>,
+
+    /// Options every command accepts, in the command schema's `arg-schema`
+    /// form (`{ "name": "color", "long": "--color", "takes-value": true }`).
+    ///
+    /// Core takes them out of the command's argv and hands their values to
+    /// the command as `WACLI_GLOBAL_<NAME>` environment variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_args: Option<Vec<wacli_metadata::ArgSchema>>,
+
+    /// Fail the build when a command's embedded metadata lacks `command_schema`
+    /// (same as `wacli build --require-schema`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_schema: Option<bool>,
+
+    /// Read the metadata of commands without an embedded metadata section by
+    /// running their `meta()` (same as `wacli build --allow-meta-exec`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_meta_exec: Option<bool>,
+
+    /// Custom sections to remove from each component before composing:
+    /// `"none"`, `"debug"` or `"all"` (same as `wacli build --strip=<LEVEL>`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip: Option<crate::strip::StripLevel>,
+
+    /// Named build profiles, selected with `wacli build --profile <NAME>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<BTreeMap<String, BuildProfile>>,
+}
+
+/// Overrides applied by `wacli build --profile <NAME>`.
+///
+/// A profile that sets a host (or core) repo/reference resolves that
+/// component through the registry and its own `wacli.lock` entry
+/// (`framework.<profile>.host`), ignoring `defaults/`. The output file gets
+/// a `-<profile>` suffix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildProfile {
+    /// Overrides WACLI_HOST_REPO
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_repo: Option<String>,
+    /// Overrides WACLI_HOST_REFERENCE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_reference: Option<String>,
+    /// Overrides WACLI_CORE_REPO
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub core_repo: Option<String>,
+    /// Overrides WACLI_CORE_REFERENCE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub core_reference: Option<String>,
+    /// Recorded in the build info (`--version --build-info`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+}
+
+impl BuildProfile {
+    pub fn pins_host(&self) -> bool {
+        self.host_repo.is_some() || self.host_reference.is_some()
+    }
+
+    pub fn pins_core(&self) -> bool {
+        self.core_repo.is_some() || self.core_reference.is_some()
+    }
+}
+
+/// Look up `build.profiles.<name>`.
+pub fn select_profile<'a>(
+    build: Option<&'a BuildManifest>,
+    name: &str,
+) -> Result<&'a BuildProfile> {
+    // Profile names share the lock's `framework` object with `host`/`core`.
+    if matches!(name, "host" | "core") || !crate::component_scan::is_valid_command_name(name) {
+        bail!("invalid profile name '{name}' (must match [a-z][a-z0-9-]* and not be host/core)");
+    }
+    let profiles = build.and_then(|b| b.profiles.as_ref());
+    if let Some(profile) = profiles.and_then(|p| p.get(name)) {
+        return Ok(profile);
+    }
+    match profiles.filter(|p| !p.is_empty()) {
+        Some(p) => bail!(
+            "unknown profile '{name}' (available: {})",
+            p.keys().map(String::as_str).collect::<Vec<_>>().join(", ")
+        ),
+        None => bail!("unknown profile '{name}' (the manifest defines no build.profiles)"),
+    }
+}
+
+/// `out.component.wasm` -> `out-<profile>.component.wasm`.
+pub fn profile_output_path(path: &Path, profile: &str) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (stem, ext) = match file_name.strip_suffix(".component.wasm") {
+        Some(stem) => (stem, ".component.wasm"),
+        None => match file_name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => (stem, &file_name[stem.len()..]),
+            _ => (file_name.as_str(), ""),
+        },
+    };
+    path.with_file_name(format!("{stem}-{profile}{ext}"))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandGroup {
+    /// Group name (must match [a-z][a-z0-9-]* and not collide with a command)
+    pub name: String,
+    /// One-line summary shown in the global help
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    /// Longer description shown in the group help
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Member command names
+    pub commands: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -58,9 +204,87 @@ pub struct RegistryCommand {
     /// Command name (must match [a-z][a-z0-9-]*)
     pub name: String,
     /// OCI repository name (may include '/')
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub repo: String,
     /// Tag or manifest digest
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reference: String,
+    /// Local component file, relative to the manifest directory. For a git
+    /// source, the component file inside the repository instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Git repository URL (anything `git clone` accepts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// Branch, tag or commit to check out from `git`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+}
+
+/// Where a `build.commands` entry is fetched from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandSource<'a> {
+    /// `{ "repo", "reference" }`: pulled from the OCI registry and locked by digest.
+    Oci { repo: &'a str, reference: &'a str },
+    /// `{ "path" }`: a component file on disk, used as is and never locked.
+    Path(&'a Path),
+    /// `{ "git", "rev", "path"? }`: a component file committed to a git
+    /// repository, locked by the commit `rev` resolves to.
+    Git {
+        url: &'a str,
+        rev: &'a str,
+        path: Option<&'a Path>,
+    },
+}
+
+impl RegistryCommand {
+    /// Classify the entry by the fields it sets, rejecting combinations that
+    /// mix source kinds.
+    pub fn source(&self) -> Result<CommandSource<'_>> {
+        let name = &self.name;
+        let path = self.path.as_deref();
+        if path.is_some_and(|p| p.as_os_str().is_empty()) {
+            bail!("build.commands entry for '{name}' has an empty path");
+        }
+        let has_oci = !self.repo.trim().is_empty() || !self.reference.trim().is_empty();
+
+        if let Some(url) = self.git.as_deref() {
+            if has_oci {
+                bail!("build.commands entry for '{name}' sets both git and repo/reference");
+            }
+            let url = url.trim();
+            if url.is_empty() {
+                bail!("build.commands entry for '{name}' has an empty git URL");
+            }
+            let Some(rev) = self.rev.as_deref().map(str::trim) else {
+                bail!("build.commands entry for '{name}' has a git URL but no rev");
+            };
+            if rev.is_empty() {
+                bail!("build.commands entry for '{name}' has an empty rev");
+            }
+            return Ok(CommandSource::Git { url, rev, path });
+        }
+
+        if self.rev.is_some() {
+            bail!("build.commands entry for '{name}' sets rev without git");
+        }
+        if let Some(path) = path {
+            if has_oci {
+                bail!("build.commands entry for '{name}' sets both path and repo/reference");
+            }
+            return Ok(CommandSource::Path(path));
+        }
+
+        let repo = self.repo.trim();
+        let reference = self.reference.trim();
+        if repo.is_empty() {
+            bail!("build.commands entry for '{name}' has an empty repo");
+        }
+        if reference.is_empty() {
+            bail!("build.commands entry for '{name}' has an empty reference");
+        }
+        Ok(CommandSource::Oci { repo, reference })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -112,6 +336,16 @@ pub fn write_default_manifest(project_dir: &Path, overwrite: bool) -> Result<Pat
             defaults_dir: Some(PathBuf::from("defaults")),
             commands_dir: Some(PathBuf::from("commands")),
             commands: None,
+            groups: None,
+            telemetry_command: None,
+            fallback_command: None,
+            interactive_picker: None,
+            env_warnings: None,
+            global_args: None,
+            require_schema: None,
+            allow_meta_exec: None,
+            strip: None,
+            profiles: None,
         }),
     };
 
@@ -183,7 +417,21 @@ mod tests {
     "commandsDir": "commands",
     "commands": [
       { "name": "greet", "repo": "example/greet", "reference": "1.0.0" }
-    ]
+    ],
+    "groups": [
+      { "name": "db", "summary": "Database tasks", "commands": ["migrate", "seed"] }
+    ],
+    "telemetryCommand": "telemetry",
+    "fallbackCommand": "ext",
+    "interactivePicker": true,
+    "envWarnings": true,
+    "globalArgs": [
+      { "name": "verbose", "short": "-v", "long": "--verbose" },
+      { "name": "color", "long": "--color", "takes-value": true }
+    ],
+    "requireSchema": true,
+    "allowMetaExec": true,
+    "strip": "all"
   }
 }"#;
         let m: Manifest = serde_json::from_str(json).unwrap();
@@ -202,6 +450,145 @@ mod tests {
         assert_eq!(cmds[0].name, "greet");
         assert_eq!(cmds[0].repo, "example/greet");
         assert_eq!(cmds[0].reference, "1.0.0");
+        let groups = build.groups.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "db");
+        assert_eq!(groups[0].summary, "Database tasks");
+        assert_eq!(groups[0].description, "");
+        assert_eq!(groups[0].commands, vec!["migrate", "seed"]);
+        assert_eq!(build.telemetry_command.as_deref(), Some("telemetry"));
+        assert_eq!(build.fallback_command.as_deref(), Some("ext"));
+        assert_eq!(build.interactive_picker, Some(true));
+        assert_eq!(build.env_warnings, Some(true));
+        let globals = build.global_args.unwrap();
+        assert_eq!(globals.len(), 2);
+        assert_eq!(globals[0].short.as_deref(), Some("-v"));
+        assert!(!globals[0].takes_value);
+        assert!(globals[1].takes_value);
+        assert_eq!(build.require_schema, Some(true));
+        assert_eq!(build.allow_meta_exec, Some(true));
+        assert_eq!(build.strip, Some(crate::strip::StripLevel::All));
+    }
+
+    #[test]
+    fn registry_commands_classify_their_source() {
+        let json = r#"[
+  { "name": "greet", "repo": "example/greet", "reference": "1.0.0" },
+  { "name": "local", "path": "../greet/greet.component.wasm" },
+  { "name": "tool", "git": "https://example.com/tool.git", "rev": "v1" },
+  { "name": "sub", "git": "../tools", "rev": "main", "path": "dist/sub.wasm" }
+]"#;
+        let cmds: Vec<RegistryCommand> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            cmds[0].source().unwrap(),
+            CommandSource::Oci {
+                repo: "example/greet",
+                reference: "1.0.0"
+            }
+        );
+        assert_eq!(
+            cmds[1].source().unwrap(),
+            CommandSource::Path(Path::new("../greet/greet.component.wasm"))
+        );
+        assert_eq!(
+            cmds[2].source().unwrap(),
+            CommandSource::Git {
+                url: "https://example.com/tool.git",
+                rev: "v1",
+                path: None
+            }
+        );
+        assert_eq!(
+            cmds[3].source().unwrap(),
+            CommandSource::Git {
+                url: "../tools",
+                rev: "main",
+                path: Some(Path::new("dist/sub.wasm"))
+            }
+        );
+        let round_trip = serde_json::to_value(&cmds[1]).unwrap();
+        assert_eq!(
+            round_trip,
+            serde_json::json!({ "name": "local", "path": "../greet/greet.component.wasm" })
+        );
+
+        for (json, err) in [
+            (r#"{ "name": "x" }"#, "has an empty repo"),
+            (
+                r#"{ "name": "x", "repo": "a/x" }"#,
+                "has an empty reference",
+            ),
+            (r#"{ "name": "x", "path": "" }"#, "has an empty path"),
+            (r#"{ "name": "x", "git": "u" }"#, "has a git URL but no rev"),
+            (
+                r#"{ "name": "x", "git": " ", "rev": "v1" }"#,
+                "has an empty git URL",
+            ),
+            (
+                r#"{ "name": "x", "path": "x.wasm", "rev": "v1" }"#,
+                "sets rev without git",
+            ),
+            (
+                r#"{ "name": "x", "git": "u", "rev": "v1", "repo": "a/x" }"#,
+                "sets both git and repo/reference",
+            ),
+            (
+                r#"{ "name": "x", "path": "x.wasm", "reference": "1" }"#,
+                "sets both path and repo/reference",
+            ),
+        ] {
+            let cmd: RegistryCommand = serde_json::from_str(json).unwrap();
+            let got = cmd.source().unwrap_err().to_string();
+            assert_eq!(got, format!("build.commands entry for 'x' {err}"), "{json}");
+        }
+    }
+
+    #[test]
+    fn profiles_are_selected_by_name() {
+        let json = r#"{
+  "build": {
+    "profiles": {
+      "edge": { "hostReference": "v0.2.0-rc1", "coreRepo": "acme/core", "features": ["jit"] },
+      "stable": {}
+    }
+  }
+}"#;
+        let m: Manifest = serde_json::from_str(json).unwrap();
+        let build = m.build.as_ref();
+
+        let edge = select_profile(build, "edge").unwrap();
+        assert_eq!(edge.host_reference.as_deref(), Some("v0.2.0-rc1"));
+        assert_eq!(edge.core_repo.as_deref(), Some("acme/core"));
+        assert_eq!(edge.features, vec!["jit"]);
+        assert!(edge.pins_host() && edge.pins_core());
+        let stable = select_profile(build, "stable").unwrap();
+        assert!(!stable.pins_host() && !stable.pins_core());
+
+        let err = select_profile(build, "nightly").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown profile 'nightly' (available: edge, stable)"
+        );
+        let err = select_profile(None, "edge").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown profile 'edge' (the manifest defines no build.profiles)"
+        );
+        assert!(select_profile(build, "host").is_err());
+    }
+
+    #[test]
+    fn profile_output_path_adds_suffix() {
+        for (path, expected) in [
+            ("out.component.wasm", "out-edge.component.wasm"),
+            ("dist/cli.wasm", "dist/cli-edge.wasm"),
+            ("cli", "cli-edge"),
+        ] {
+            assert_eq!(
+                profile_output_path(Path::new(path), "edge"),
+                PathBuf::from(expected)
+            );
+        }
     }
 
     #[test]
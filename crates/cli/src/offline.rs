@@ -0,0 +1,122 @@
+//! `wacli build --offline`: resolve components from local files and caches
+//! only, never from a registry or a git remote.
+//!
+//! Components that are not cached do not fail the build one at a time; they
+//! are collected while resolving and reported together, with the paths they
+//! were expected at, so a CI job can pre-populate all of them in one go.
+
+use anyhow::{Result, bail};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that turns on `--offline` (`1`; empty or `0` is off).
+pub const OFFLINE_ENV: &str = "WACLI_OFFLINE";
+
+/// Whether [`OFFLINE_ENV`] asks for an offline build.
+pub fn offline_from_env() -> bool {
+    std::env::var(OFFLINE_ENV)
+        .map(|v| !v.trim().is_empty() && v.trim() != "0")
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Default)]
+pub struct Offline {
+    pub enabled: bool,
+    missing: Vec<CacheMiss>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CacheMiss {
+    component: String,
+    source: String,
+    expected: Option<PathBuf>,
+}
+
+impl Offline {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            missing: Vec::new(),
+        }
+    }
+
+    /// Record a component that is not available locally. `source` is what it
+    /// would have been fetched from; `expected` is where it is looked up, or
+    /// `None` when wacli.lock does not pin it (its cache path depends on the
+    /// digest or commit it resolves to).
+    pub fn miss(
+        &mut self,
+        component: impl Into<String>,
+        source: impl Into<String>,
+        expected: Option<&Path>,
+    ) {
+        self.missing.push(CacheMiss {
+            component: component.into(),
+            source: source.into(),
+            expected: expected.map(Path::to_path_buf),
+        });
+    }
+
+    pub fn has_misses(&self) -> bool {
+        !self.missing.is_empty()
+    }
+
+    /// Fail with every recorded miss.
+    pub fn check(&self) -> Result<()> {
+        if self.missing.is_empty() {
+            return Ok(());
+        }
+        let count = self.missing.len();
+        let mut msg = format!(
+            "--offline: {count} component{} not available locally:\n",
+            if count == 1 { " is" } else { "s are" }
+        );
+        for miss in &self.missing {
+            let _ = writeln!(msg, "  {}: {}", miss.component, miss.source);
+            match &miss.expected {
+                Some(path) => {
+                    let _ = writeln!(msg, "    expected at: {}", path.display());
+                }
+                None => {
+                    let _ = writeln!(msg, "    not pinned in wacli.lock");
+                }
+            }
+        }
+        msg.push_str(
+            "\nCopy the components to the expected paths, or build once without --offline \
+             to lock and cache them.",
+        );
+        bail!(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn misses_are_reported_together_with_their_cache_paths() {
+        let mut offline = Offline::new(true);
+        offline.check().unwrap();
+
+        offline.miss(
+            "framework host",
+            "wacli/host@sha256:abc",
+            Some(Path::new(
+                "/p/.wacli/framework/wacli_host/sha256_abc/host.component.wasm",
+            )),
+        );
+        offline.miss("command greet", "example/greet:1.0.0", None);
+        assert!(offline.has_misses());
+        assert_eq!(
+            offline.check().unwrap_err().to_string(),
+            "--offline: 2 components are not available locally:\n  \
+             framework host: wacli/host@sha256:abc\n    \
+             expected at: /p/.wacli/framework/wacli_host/sha256_abc/host.component.wasm\n  \
+             command greet: example/greet:1.0.0\n    \
+             not pinned in wacli.lock\n\n\
+             Copy the components to the expected paths, or build once without --offline \
+             to lock and cache them."
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Download progress for registry pulls.
+//!
+//! On a terminal, each pull gets one line on stderr that is redrawn in place
+//! and left behind, finished, when the pull ends, so tracing output (which
+//! only happens between pulls) never lands in the middle of it. Otherwise
+//! progress goes through `tracing` at info level every quarter of the
+//! download.
+
+use std::cell::Cell;
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// Smallest download worth reporting when stderr is not a terminal.
+const LOG_MIN_BYTES: u64 = 1024 * 1024;
+/// Downloads of unknown size are logged every this many bytes.
+const LOG_EVERY_BYTES: u64 = 8 * 1024 * 1024;
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+
+pub struct PullProgress {
+    label: String,
+    tty: bool,
+    last_draw: Cell<Option<Instant>>,
+    /// Bytes at which the last log line was written.
+    last_logged: Cell<u64>,
+    done: Cell<u64>,
+}
+
+impl PullProgress {
+    /// Progress for a pull shown as `label`. The line is only drawn when
+    /// stderr is a terminal and info logging is enabled.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            tty: std::io::stderr().is_terminal() && tracing::enabled!(tracing::Level::INFO),
+            last_draw: Cell::new(None),
+            last_logged: Cell::new(0),
+            done: Cell::new(0),
+        }
+    }
+
+    pub fn update(&self, done: u64, total: Option<u64>) {
+        // A retried download starts over.
+        if done < self.done.get() {
+            self.last_logged.set(0);
+        }
+        self.done.set(done);
+
+        if self.tty {
+            let now = Instant::now();
+            let due = self
+                .last_draw
+                .get()
+                .is_none_or(|last| now - last >= REDRAW_EVERY);
+            if due || total == Some(done) {
+                self.last_draw.set(Some(now));
+                draw(&format!("\r\x1b[2K{}", line(&self.label, done, total)));
+            }
+        } else if let Some(msg) = log_line(&self.label, self.last_logged.get(), done, total) {
+            self.last_logged.set(done);
+            tracing::info!("{msg}");
+        }
+    }
+
+    /// End the line: leave the final size on success, clear it otherwise
+    /// (the error explains what happened).
+    pub fn finish(&self, ok: bool) {
+        if !self.tty || self.last_draw.get().is_none() {
+            return;
+        }
+        if ok {
+            let done = self.done.get();
+            draw(&format!(
+                "\r\x1b[2K{}\n",
+                line(&self.label, done, Some(done))
+            ));
+        } else {
+            draw("\r\x1b[2K");
+        }
+    }
+}
+
+fn draw(s: &str) {
+    let mut err = std::io::stderr().lock();
+    let _ = err.write_all(s.as_bytes());
+    let _ = err.flush();
+}
+
+/// `label: 12.0 MiB / 30.0 MiB (40%)`, or just the bytes so far.
+fn line(label: &str, done: u64, total: Option<u64>) -> String {
+    match total {
+        Some(total) if total > 0 => format!(
+            "{label}: {} / {} ({}%)",
+            human_bytes(done),
+            human_bytes(total),
+            percent(done, total)
+        ),
+        _ => format!("{label}: {}", human_bytes(done)),
+    }
+}
+
+/// The log line due at `done` bytes, given the last one was written at
+/// `logged` bytes: one per quarter of a known size, or per
+/// [`LOG_EVERY_BYTES`] otherwise. Small downloads are not logged.
+fn log_line(label: &str, logged: u64, done: u64, total: Option<u64>) -> Option<String> {
+    let due = match total {
+        Some(total) if total >= LOG_MIN_BYTES => {
+            percent(done, total) / 25 > percent(logged, total) / 25
+        }
+        Some(_) => false,
+        None => done / LOG_EVERY_BYTES > logged / LOG_EVERY_BYTES,
+    };
+    due.then(|| format!("pulling {}", line(label, done, total)))
+}
+
+fn percent(done: u64, total: u64) -> u64 {
+    (done.min(total) * 100).checked_div(total).unwrap_or(100)
+}
+
+pub fn human_bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = n as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{n} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn lines_show_sizes_and_percentages() {
+        assert_eq!(
+            line("wacli/host", 12 * MIB, Some(30 * MIB)),
+            "wacli/host: 12.0 MiB / 30.0 MiB (40%)"
+        );
+        assert_eq!(line("greet", 1536, None), "greet: 1.5 KiB");
+        assert_eq!(line("greet", 0, Some(0)), "greet: 0 B");
+    }
+
+    #[test]
+    fn logs_are_written_every_quarter() {
+        let total = Some(40 * MIB);
+        let logged = [0, 5, 10, 15, 20, 39, 40]
+            .into_iter()
+            .scan(0, |last, mib| {
+                let msg = log_line("host", *last, mib * MIB, total);
+                if msg.is_some() {
+                    *last = mib * MIB;
+                }
+                Some(msg)
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            logged,
+            [
+                "pulling host: 10.0 MiB / 40.0 MiB (25%)",
+                "pulling host: 20.0 MiB / 40.0 MiB (50%)",
+                "pulling host: 39.0 MiB / 40.0 MiB (97%)",
+                "pulling host: 40.0 MiB / 40.0 MiB (100%)",
+            ]
+        );
+
+        assert_eq!(log_line("small", 0, 1000, Some(1000)), None);
+        assert_eq!(
+            log_line("unknown", 0, 9 * MIB, None).as_deref(),
+            Some("pulling unknown: 9.0 MiB")
+        );
+        assert_eq!(log_line("unknown", 9 * MIB, 10 * MIB, None), None);
+    }
+}
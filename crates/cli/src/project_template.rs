@@ -0,0 +1,202 @@
+//! `wacli init --template`: scaffold a plugin crate that builds as is.
+//!
+//! The files are embedded in the binary and filled in with the plugin name
+//! and this wacli's version, so the crate depends on the `wacli-cdk` release
+//! that matches the WIT `init` installs next to it.
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::component_scan::is_valid_command_name;
+
+/// A plugin crate `wacli init` can scaffold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Template {
+    /// A command crate in commands-src/<NAME>/, installed into commands/
+    RustCommand,
+    /// A pipe crate in pipes-src/<NAME>/, installed into plugins/
+    Pipe,
+}
+
+impl Template {
+    /// Plugin name used without `--name`.
+    pub fn default_name(self) -> &'static str {
+        match self {
+            Template::RustCommand => "greet",
+            Template::Pipe => "case",
+        }
+    }
+
+    fn source_dir(self) -> &'static str {
+        match self {
+            Template::RustCommand => "commands-src",
+            Template::Pipe => "pipes-src",
+        }
+    }
+
+    /// `(path in the crate, template)` pairs.
+    fn files(self) -> [(&'static str, &'static str); 4] {
+        match self {
+            Template::RustCommand => [
+                (
+                    "Cargo.toml",
+                    include_str!("templates/rust-command/Cargo.toml.tmpl"),
+                ),
+                (
+                    ".cargo/config.toml",
+                    include_str!("templates/rust-command/config.toml.tmpl"),
+                ),
+                (
+                    "src/lib.rs",
+                    include_str!("templates/rust-command/lib.rs.tmpl"),
+                ),
+                (
+                    "Makefile",
+                    include_str!("templates/rust-command/Makefile.tmpl"),
+                ),
+            ],
+            Template::Pipe => [
+                ("Cargo.toml", include_str!("templates/pipe/Cargo.toml.tmpl")),
+                (
+                    ".cargo/config.toml",
+                    include_str!("templates/pipe/config.toml.tmpl"),
+                ),
+                ("src/lib.rs", include_str!("templates/pipe/lib.rs.tmpl")),
+                ("Makefile", include_str!("templates/pipe/Makefile.tmpl")),
+            ],
+        }
+    }
+}
+
+/// Write the `template` crate for plugin `name` under `project_dir` and
+/// return its directory.
+///
+/// Existing files are only replaced with `overwrite`; without it, nothing is
+/// written if any of them exists, so a half-generated crate is never left
+/// behind.
+pub fn scaffold(
+    project_dir: &Path,
+    template: Template,
+    name: &str,
+    overwrite: bool,
+) -> Result<PathBuf> {
+    if !is_valid_command_name(name) {
+        bail!("invalid plugin name '{name}' (must match [a-z][a-z0-9-]*)");
+    }
+    let crate_dir = project_dir.join(template.source_dir()).join(name);
+    let files = template.files();
+
+    if !overwrite {
+        let existing: Vec<String> = files
+            .iter()
+            .map(|(path, _)| crate_dir.join(path))
+            .filter(|path| path.exists())
+            .map(|path| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            bail!(
+                "{} already exists ({}); pass --overwrite to replace it",
+                crate_dir.display(),
+                existing.join(", ")
+            );
+        }
+    }
+
+    for (path, contents) in files {
+        let dest = crate_dir.join(path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(&dest, render(contents, name))
+            .with_context(|| format!("failed to write {}", dest.display()))?;
+        tracing::info!("wrote {}", dest.display());
+    }
+    Ok(crate_dir)
+}
+
+/// Fill in the placeholders of a template for plugin `name`.
+fn render(template: &str, name: &str) -> String {
+    let ident = name.replace('-', "_");
+    let type_name: String = name
+        .split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    template
+        .replace("{{name}}", name)
+        .replace("{{ident}}", &ident)
+        .replace("{{type_name}}", &type_name)
+        .replace("{{wacli_version}}", env!("CARGO_PKG_VERSION"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("wacli-template-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn templates_fill_in_every_placeholder() {
+        for template in [Template::RustCommand, Template::Pipe] {
+            for (path, contents) in template.files() {
+                let rendered = render(contents, "say-hi");
+                assert!(!rendered.contains("{{"), "{template:?} {path}:\n{rendered}");
+            }
+        }
+
+        let lib = render(Template::RustCommand.files()[2].1, "say-hi");
+        assert!(lib.contains("declare_command_metadata!(say_hi_meta, {"));
+        assert!(lib.contains("name: \"say-hi\","));
+        assert!(lib.contains("wacli_cdk::export!(SayHi);"));
+        let cargo = render(Template::Pipe.files()[0].1, "say-hi");
+        assert!(cargo.contains(&format!("version = \"={}\"", env!("CARGO_PKG_VERSION"))));
+        let makefile = render(Template::Pipe.files()[3].1, "say-hi");
+        assert!(makefile.contains("release/say_hi.wasm -o $@"));
+    }
+
+    #[test]
+    fn existing_crates_need_overwrite() {
+        let dir = temp_dir("overwrite");
+        let crate_dir = scaffold(&dir, Template::RustCommand, "greet", false).unwrap();
+        assert_eq!(crate_dir, dir.join("commands-src/greet"));
+        assert!(crate_dir.join(".cargo/config.toml").is_file());
+
+        let lib = crate_dir.join("src/lib.rs");
+        fs::write(&lib, "// edited").unwrap();
+        let err = scaffold(&dir, Template::RustCommand, "greet", false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("pass --overwrite"), "{err}");
+        assert_eq!(fs::read_to_string(&lib).unwrap(), "// edited");
+
+        scaffold(&dir, Template::RustCommand, "greet", true).unwrap();
+        assert!(
+            fs::read_to_string(&lib)
+                .unwrap()
+                .contains("wacli_cdk::export!(Greet);")
+        );
+
+        let err = scaffold(&dir, Template::Pipe, "Bad_Name", false)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "invalid plugin name 'Bad_Name' (must match [a-z][a-z0-9-]*)"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -5,15 +5,17 @@
 //! This mirrors registry_gen.rs but emits a core module via WAT, then wraps it
 //! as a component with embedded WIT metadata.
 
-use crate::component_scan::CommandInfo;
+use crate::component_scan::{CommandInfo, components};
+use crate::manifest::CommandGroup;
+use crate::registry_layout::{Elem, Field, FieldKind, RecordLayout, RegistryLayouts};
 use crate::wit;
 use anyhow::{Context, Result, bail};
 use semver::Version;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use wasm_encoder::{CustomSection, Section};
 use wit_component::ComponentEncoder;
-use wit_parser::{PackageName, Resolve, UnresolvedPackageGroup};
+use wit_parser::{PackageName, Resolve, UnresolvedPackageGroup, WorldId};
 
 const REGISTRY_WIT_BASE: &str = wit::REGISTRY_WIT;
 const REGISTRY_WAT_TEMPLATE: &str = include_str!("registry_template.wat");
@@ -23,12 +25,33 @@ pub struct AppMeta {
     pub name: String,
     pub version: String,
     pub description: String,
+    pub build_info: BuildInfo,
+    /// Hidden command core runs with a usage event after each command.
+    pub telemetry_command: Option<String>,
+    /// Command core hands unknown commands to (`build.fallbackCommand`).
+    pub fallback_command: Option<String>,
+    /// Core shows a command picker when run with no arguments on a terminal.
+    pub interactive_picker: bool,
+    /// Core warns about near-miss names of env vars that args read.
+    pub env_warnings: bool,
+    /// Options every command accepts (`build.globalArgs`).
+    pub global_args: Vec<wacli_metadata::ArgSchema>,
+}
+
+/// Build provenance embedded into the registry (`registry-schema.build-info`).
+#[derive(Debug, Clone, Default)]
+pub struct BuildInfo {
+    pub wacli_version: String,
+    pub git_revision: Option<String>,
+    pub built_with: Vec<String>,
 }
 
 #[derive(Debug)]
+/// Strings laid out in first-interned order, so the same inputs always give
+/// the same data segment (and registry bytes).
 struct StringTable {
     data: Vec<u8>,
-    offsets: HashMap<String, (u32, u32)>,
+    offsets: BTreeMap<String, (u32, u32)>,
 }
 
 impl Default for StringTable {
@@ -38,7 +61,7 @@ impl Default for StringTable {
         // so we must ensure no non-empty string is ever stored at offset 0.
         Self {
             data: vec![0],
-            offsets: HashMap::new(),
+            offsets: BTreeMap::new(),
         }
     }
 }
@@ -74,27 +97,18 @@ pub fn get_prebuilt_registry(defaults_dir: &Path) -> Option<PathBuf> {
 }
 
 /// Generate a registry component from discovered commands using WAT template.
-pub fn generate_registry_wat(commands: &[CommandInfo], app: &AppMeta) -> Result<Vec<u8>> {
-    let string_table = build_string_table(commands, app);
-    let wat_source = build_wat_module(commands, app, &string_table)?;
+pub fn generate_registry_wat(
+    commands: &[CommandInfo],
+    groups: &[CommandGroup],
+    app: &AppMeta,
+) -> Result<Vec<u8>> {
+    let (resolve, world_id) = parse_registry_wit(commands)?;
+    let layouts = RegistryLayouts::from_resolve(&resolve)?;
+    let string_table = build_string_table(commands, groups, app);
+    let wat_source = build_wat_module(commands, groups, app, &string_table, &layouts)?;
 
     let core_module = wat::parse_str(&wat_source).context("failed to parse registry WAT")?;
 
-    let dynamic_wit = generate_dynamic_wit(commands);
-    let mut resolve = Resolve::default();
-    let wit_path = Path::new("registry.wit");
-    let pkg_group = UnresolvedPackageGroup::parse(wit_path, &dynamic_wit)
-        .context("failed to parse dynamic WIT")?;
-    let _pkg_ids = resolve.push_group(pkg_group)?;
-
-    let world_id = resolve
-        .packages
-        .iter()
-        .flat_map(|(_, pkg)| pkg.worlds.values())
-        .find(|world_id| resolve.worlds[**world_id].name == "dynamic-registry")
-        .copied()
-        .context("dynamic-registry world not found in generated WIT")?;
-
     let encoded_meta = wit_component::metadata::encode(
         &resolve,
         world_id,
@@ -114,23 +128,50 @@ pub fn generate_registry_wat(commands: &[CommandInfo], app: &AppMeta) -> Result<
     Ok(component)
 }
 
+/// Parse the registry WIT for `commands`; the record layouts the WAT writes
+/// come from the same `Resolve` the component is encoded with.
+fn parse_registry_wit(commands: &[CommandInfo]) -> Result<(Resolve, WorldId)> {
+    let dynamic_wit = generate_dynamic_wit(commands);
+    let mut resolve = Resolve::default();
+    let wit_path = Path::new("registry.wit");
+    let pkg_group = UnresolvedPackageGroup::parse(wit_path, &dynamic_wit)
+        .context("failed to parse dynamic WIT")?;
+    let _pkg_ids = resolve.push_group(pkg_group)?;
+
+    let world_id = resolve
+        .packages
+        .iter()
+        .flat_map(|(_, pkg)| pkg.worlds.values())
+        .find(|world_id| resolve.worlds[**world_id].name == "dynamic-registry")
+        .copied()
+        .context("dynamic-registry world not found in generated WIT")?;
+    Ok((resolve, world_id))
+}
+
 /// Generate WIT source dynamically based on discovered commands.
 fn generate_dynamic_wit(commands: &[CommandInfo]) -> String {
     let mut wit = String::new();
     append_wit_base(&mut wit);
 
-    for cmd in commands {
-        wit.push_str(&format!("interface {}-command {{\n", cmd.name));
+    for cmd in components(commands) {
+        wit.push_str(&format!("interface {} {{\n", cmd.registry_import()));
         wit.push_str("  use types.{command-meta, command-result};\n");
-        wit.push_str("  meta: func() -> command-meta;\n");
-        wit.push_str("  run: func(argv: list<string>) -> command-result;\n");
+        if cmd.bundle.is_some() {
+            wit.push_str("  list-commands: func() -> list<command-meta>;\n");
+            wit.push_str(
+                "  run-named: func(name: string, argv: list<string>) -> command-result;\n",
+            );
+        } else {
+            wit.push_str("  meta: func() -> command-meta;\n");
+            wit.push_str("  run: func(argv: list<string>) -> command-result;\n");
+        }
         wit.push_str("}\n\n");
     }
 
     wit.push_str("world dynamic-registry {\n");
 
-    for cmd in commands {
-        wit.push_str(&format!("  import {}-command;\n", cmd.name));
+    for cmd in components(commands) {
+        wit.push_str(&format!("  import {};\n", cmd.registry_import()));
     }
 
     wit.push_str("  export registry;\n");
@@ -140,7 +181,7 @@ fn generate_dynamic_wit(commands: &[CommandInfo]) -> String {
     wit
 }
 
-fn append_wit_base(dst: &mut String) {
+pub(crate) fn append_wit_base(dst: &mut String) {
     dst.push_str(wit::TYPES_WIT.trim_end());
     dst.push_str("\n\n");
     append_without_package(dst, wit::HOST_ENV_WIT);
@@ -159,7 +200,7 @@ fn append_wit_base(dst: &mut String) {
     dst.push_str("\n\n");
 }
 
-fn append_without_package(dst: &mut String, wit: &str) {
+pub(crate) fn append_without_package(dst: &mut String, wit: &str) {
     let lines = wit.lines();
     let mut saw_package = false;
     let mut started = false;
@@ -180,24 +221,54 @@ fn append_without_package(dst: &mut String, wit: &str) {
     }
 }
 
-fn build_string_table(commands: &[CommandInfo], app: &AppMeta) -> StringTable {
+fn build_string_table(
+    commands: &[CommandInfo],
+    groups: &[CommandGroup],
+    app: &AppMeta,
+) -> StringTable {
     let mut t = StringTable::default();
 
     // App metadata (used by core for global help/version).
     t.intern(&app.name);
     t.intern(&app.version);
     t.intern(&app.description);
+    t.intern(&app.build_info.wacli_version);
+    if let Some(s) = app.build_info.git_revision.as_deref() {
+        t.intern(s);
+    }
+    for s in &app.build_info.built_with {
+        t.intern(s);
+    }
+    if let Some(s) = app.telemetry_command.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = app.fallback_command.as_deref() {
+        t.intern(s);
+    }
+    for arg in &app.global_args {
+        intern_arg_schema(&mut t, arg);
+    }
+
+    for group in groups {
+        t.intern(&group.name);
+        t.intern(&group.summary);
+        t.intern(&group.description);
+    }
 
     for cmd in commands {
         let meta = &cmd.metadata.command_meta;
-        t.intern(&meta.name);
+        t.intern(&cmd.registry_name());
+        if cmd.bundle.is_some() {
+            // Passed to `run-named`.
+            t.intern(&cmd.name);
+        }
         t.intern(&meta.summary);
         t.intern(&meta.usage);
         t.intern(&meta.version);
         t.intern(&meta.description);
 
-        for a in &meta.aliases {
-            t.intern(a);
+        for a in meta.aliases.iter().chain(&meta.hidden_aliases) {
+            t.intern(&cmd.qualify(a));
         }
         for e in &meta.examples {
             t.intern(e);
@@ -221,41 +292,40 @@ fn build_string_table(commands: &[CommandInfo], app: &AppMeta) -> StringTable {
 
         if let Some(schema) = cmd.metadata.command_schema.as_ref() {
             // The schema may include additional strings beyond `command_meta`.
-            for a in &schema.aliases {
-                t.intern(a);
+            t.intern(&schema.summary);
+            t.intern(&schema.usage);
+            t.intern(&schema.version);
+            t.intern(&schema.description);
+            for a in schema.aliases.iter().chain(&schema.hidden_aliases) {
+                t.intern(&cmd.qualify(a));
             }
             for e in &schema.examples {
                 t.intern(e);
             }
             for arg in &schema.args {
-                t.intern(&arg.name);
-                if let Some(s) = arg.short.as_deref() {
-                    t.intern(s);
-                }
-                if let Some(s) = arg.long.as_deref() {
-                    t.intern(s);
-                }
-                t.intern(&arg.help);
-                if let Some(s) = arg.default_value.as_deref() {
-                    t.intern(s);
-                }
-                if let Some(s) = arg.env.as_deref() {
-                    t.intern(s);
-                }
-                if let Some(s) = arg.value_name.as_deref() {
-                    t.intern(s);
-                }
-                if let Some(s) = arg.value_type.as_deref() {
+                intern_arg_schema(&mut t, arg);
+            }
+            for group in &schema.groups {
+                t.intern(&group.name);
+            }
+            for (path, sub) in flatten_subcommands(schema) {
+                for s in path
+                    .iter()
+                    .chain(&sub.aliases)
+                    .chain(&sub.hidden_aliases)
+                    .chain(&sub.examples)
+                {
                     t.intern(s);
                 }
-                for v in &arg.possible_values {
-                    t.intern(v);
-                }
-                for v in &arg.conflicts_with {
-                    t.intern(v);
+                t.intern(&sub.summary);
+                t.intern(&sub.usage);
+                t.intern(&sub.version);
+                t.intern(&sub.description);
+                for arg in &sub.args {
+                    intern_arg_schema(&mut t, arg);
                 }
-                for v in &arg.requires {
-                    t.intern(v);
+                for group in &sub.groups {
+                    t.intern(&group.name);
                 }
             }
         }
@@ -264,15 +334,57 @@ fn build_string_table(commands: &[CommandInfo], app: &AppMeta) -> StringTable {
     t
 }
 
+/// Intern every string an `arg-schema` record points at.
+fn intern_arg_schema(t: &mut StringTable, arg: &wacli_metadata::ArgSchema) {
+    t.intern(&arg.name);
+    if let Some(s) = arg.short.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = arg.long.as_deref() {
+        t.intern(s);
+    }
+    t.intern(&arg.help);
+    if let Some(s) = arg.default_value.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = arg.env.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = arg.value_name.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = arg.value_type.as_deref() {
+        t.intern(s);
+    }
+    for v in &arg.possible_values {
+        t.intern(v);
+    }
+    for v in &arg.conflicts_with {
+        t.intern(v);
+    }
+    for v in &arg.requires {
+        t.intern(v);
+    }
+    if let Some(s) = arg.complete_command.as_deref() {
+        t.intern(s);
+    }
+    if let Some(s) = arg.group.as_deref() {
+        t.intern(s);
+    }
+}
+
 fn build_wat_module(
     commands: &[CommandInfo],
+    groups: &[CommandGroup],
     app: &AppMeta,
     strings: &StringTable,
+    layouts: &RegistryLayouts,
 ) -> Result<String> {
     let imports = build_imports(commands)?;
-    let list_body = build_list_commands_body(commands, strings);
-    let list_schemas_body = build_list_schemas_body(commands, strings);
-    let app_meta_body = build_app_meta_body(app, strings);
+    let list_body = build_list_commands_body(commands, strings, layouts)?;
+    let list_schemas_body = build_list_schemas_body(commands, strings, layouts)?;
+    let app_meta_body = build_app_meta_body(app, strings, layouts)?;
+    let list_groups_body = build_list_groups_body(groups, strings, layouts)?;
     let run_body = build_run_body(commands, strings);
     let heap_start = compute_heap_start(strings.data.len());
     let string_data = escape_bytes(&strings.data);
@@ -285,6 +397,7 @@ fn build_wat_module(
             ("{{LIST_COMMANDS_BODY}}", list_body),
             ("{{LIST_SCHEMAS_BODY}}", list_schemas_body),
             ("{{APP_META_BODY}}", app_meta_body),
+            ("{{LIST_GROUPS_BODY}}", list_groups_body),
             ("{{RUN_BODY}}", run_body),
             ("{{STRING_DATA}}", string_data),
         ],
@@ -295,9 +408,19 @@ fn build_imports(commands: &[CommandInfo]) -> Result<String> {
     let mut imports = String::new();
     let pkg = registry_package_name()?;
 
-    for cmd in commands {
+    for cmd in components(commands) {
+        let iface = pkg.interface_id(&cmd.registry_import());
+        if let Some(bundle) = &cmd.bundle {
+            let ident = bundle_ident(bundle);
+            imports.push_str(&format!(
+                "  (import \"{iface}\" \"list-commands\" (func ${ident}_list_commands (type $import_meta)))\n"
+            ));
+            imports.push_str(&format!(
+                "  (import \"{iface}\" \"run-named\" (func ${ident}_run_named (type $import_run_named)))\n"
+            ));
+            continue;
+        }
         let ident = command_ident(&cmd.name);
-        let iface = pkg.interface_id(&format!("{}-command", cmd.name));
         imports.push_str(&format!(
             "  (import \"{iface}\" \"meta\" (func ${ident}_meta (type $import_meta)))\n",
             iface = iface,
@@ -352,13 +475,14 @@ fn parse_package_name(name: &str) -> Result<PackageName> {
     })
 }
 
-fn build_list_commands_body(commands: &[CommandInfo], strings: &StringTable) -> String {
-    const CMD_RECORD_SIZE: i32 = 68;
-    const STR_ELEM_SIZE: i32 = 8;
-    const ARG_RECORD_SIZE: i32 = 72;
-
+fn build_list_commands_body(
+    commands: &[CommandInfo],
+    strings: &StringTable,
+    layouts: &RegistryLayouts,
+) -> Result<String> {
+    let layout = &layouts.command_meta;
     let count = commands.len() as i32;
-    let list_bytes = count * CMD_RECORD_SIZE;
+    let list_bytes = count * layout.size;
 
     let mut body = String::new();
 
@@ -384,13 +508,8 @@ fn build_list_commands_body(commands: &[CommandInfo], strings: &StringTable) ->
 
     for (i, cmd) in commands.iter().enumerate() {
         let meta = &cmd.metadata.command_meta;
-        let record_offset = (i as i32) * CMD_RECORD_SIZE;
-
-        let (name_ptr, name_len) = strings.get(&meta.name);
-        let (summary_ptr, summary_len) = strings.get(&meta.summary);
-        let (usage_ptr, usage_len) = strings.get(&meta.usage);
-        let (version_ptr, version_len) = strings.get(&meta.version);
-        let (desc_ptr, desc_len) = strings.get(&meta.description);
+        let record_offset = (i as i32) * layout.size;
+        let aliases = qualified_aliases(cmd, &meta.aliases);
 
         push_blank(&mut body);
         push_line(&mut body, 4, "local.get $list_ptr");
@@ -398,199 +517,65 @@ fn build_list_commands_body(commands: &[CommandInfo], strings: &StringTable) ->
         push_line(&mut body, 4, "i32.add");
         push_line(&mut body, 4, "local.set $record_ptr");
 
-        // name
-        emit_store_i32_const(&mut body, "$record_ptr", 0, name_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 4, name_len);
-
-        // summary
-        emit_store_i32_const(&mut body, "$record_ptr", 8, summary_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 12, summary_len);
-
-        // usage
-        emit_store_i32_const(&mut body, "$record_ptr", 16, usage_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 20, usage_len);
-
-        // aliases list<string>
-        if meta.aliases.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 24, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 28, 0);
-        } else {
-            let bytes = (meta.aliases.len() as i32) * STR_ELEM_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $aliases_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 24, "$aliases_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 28, meta.aliases.len() as u32);
-
-            for (j, alias) in meta.aliases.iter().enumerate() {
-                let (ap, al) = strings.get(alias);
-                let entry_off = (j as i32) * STR_ELEM_SIZE;
-                // ptr
-                push_line(&mut body, 4, "local.get $aliases_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", ap));
-                push_line(&mut body, 4, "i32.store offset=0 align=2");
-                // len
-                push_line(&mut body, 4, "local.get $aliases_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", al));
-                push_line(&mut body, 4, "i32.store offset=4 align=2");
-            }
-        }
-
-        // version
-        emit_store_i32_const(&mut body, "$record_ptr", 32, version_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 36, version_len);
-
-        // hidden (bool)
-        push_line(&mut body, 4, "local.get $record_ptr");
-        push_line(
-            &mut body,
-            4,
-            &format!("i32.const {}", if meta.hidden { 1 } else { 0 }),
-        );
-        push_line(&mut body, 4, "i32.store8 offset=40");
-
-        // description
-        emit_store_i32_const(&mut body, "$record_ptr", 44, desc_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 48, desc_len);
-
-        // examples list<string>
-        if meta.examples.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 52, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 56, 0);
-        } else {
-            let bytes = (meta.examples.len() as i32) * STR_ELEM_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $examples_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 52, "$examples_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 56, meta.examples.len() as u32);
-
-            for (j, ex) in meta.examples.iter().enumerate() {
-                let (ep, el) = strings.get(ex);
-                let entry_off = (j as i32) * STR_ELEM_SIZE;
-                // ptr
-                push_line(&mut body, 4, "local.get $examples_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", ep));
-                push_line(&mut body, 4, "i32.store offset=0 align=2");
-                // len
-                push_line(&mut body, 4, "local.get $examples_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", el));
-                push_line(&mut body, 4, "i32.store offset=4 align=2");
-            }
-        }
-
-        // args list<arg-def>
-        if meta.args.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 60, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 64, 0);
-        } else {
-            let bytes = (meta.args.len() as i32) * ARG_RECORD_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $args_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 60, "$args_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 64, meta.args.len() as u32);
-
-            for (j, arg) in meta.args.iter().enumerate() {
-                let arg_off = (j as i32) * ARG_RECORD_SIZE;
-                push_blank(&mut body);
-                push_line(&mut body, 4, "local.get $args_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", arg_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, "local.set $arg_ptr");
-
-                let (anp, anl) = strings.get(&arg.name);
-                emit_store_i32_const(&mut body, "$arg_ptr", 0, anp);
-                emit_store_i32_const(&mut body, "$arg_ptr", 4, anl);
-
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    8,
-                    12,
-                    16,
-                    arg.short.as_deref(),
-                    strings,
-                );
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    20,
-                    24,
-                    28,
-                    arg.long.as_deref(),
-                    strings,
-                );
-
-                let (hp, hl) = strings.get(&arg.help);
-                emit_store_i32_const(&mut body, "$arg_ptr", 32, hp);
-                emit_store_i32_const(&mut body, "$arg_ptr", 36, hl);
-
-                // required bool @40
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.required { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=40");
-
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    44,
-                    48,
-                    52,
-                    arg.default_value.as_deref(),
-                    strings,
-                );
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    56,
-                    60,
-                    64,
-                    arg.value_name.as_deref(),
-                    strings,
-                );
-
-                // takes_value bool @68
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.takes_value { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=68");
-            }
-        }
+        let mut record = RecordStores::new(layout, "$record_ptr");
+        record.string(&mut body, "name", strings.get(&cmd.registry_name()))?;
+        record.string(&mut body, "summary", strings.get(&meta.summary))?;
+        record.string(&mut body, "usage", strings.get(&meta.usage))?;
+        let slot = record.list_str("aliases")?;
+        emit_list_str(&mut body, &slot, "$aliases_ptr", &aliases, strings);
+        record.string(&mut body, "version", strings.get(&meta.version))?;
+        record.bool(&mut body, "hidden", meta.hidden)?;
+        record.string(&mut body, "description", strings.get(&meta.description))?;
+        let slot = record.list_str("examples")?;
+        emit_list_str(&mut body, &slot, "$examples_ptr", &meta.examples, strings);
+        let slot = record.list_records("args", &layouts.arg_def)?;
+        emit_arg_defs(&mut body, &slot, &meta.args, &layouts.arg_def, strings)?;
+        record.finish()?;
     }
 
     push_blank(&mut body);
     push_line(&mut body, 4, "local.get $result_ptr");
 
-    body
+    Ok(body)
 }
 
-fn build_list_schemas_body(commands: &[CommandInfo], strings: &StringTable) -> String {
-    const CMD_RECORD_SIZE: i32 = 68;
-    const STR_ELEM_SIZE: i32 = 8;
-    // arg-schema lowers to 31 * ptrsize bytes on wasm32 (124 bytes).
-    const ARG_RECORD_SIZE: i32 = 124;
+/// Store `args` as a `list<arg-def>` in `slot`.
+fn emit_arg_defs(
+    body: &mut String,
+    slot: &ListSlot,
+    args: &[wacli_metadata::ArgDef],
+    layout: &RecordLayout,
+    strings: &StringTable,
+) -> Result<()> {
+    if !slot.alloc(body, "$args_ptr", args.len()) {
+        return Ok(());
+    }
+
+    for (j, arg) in args.iter().enumerate() {
+        slot.select(body, "$args_ptr", "$arg_ptr", j);
+
+        let mut record = RecordStores::new(layout, "$arg_ptr");
+        record.string(body, "name", strings.get(&arg.name))?;
+        record.opt_str(body, "short", arg.short.as_deref(), strings)?;
+        record.opt_str(body, "long", arg.long.as_deref(), strings)?;
+        record.string(body, "help", strings.get(&arg.help))?;
+        record.bool(body, "required", arg.required)?;
+        record.opt_str(body, "default-value", arg.default_value.as_deref(), strings)?;
+        record.opt_str(body, "value-name", arg.value_name.as_deref(), strings)?;
+        record.bool(body, "takes-value", arg.takes_value)?;
+        record.finish()?;
+    }
+    Ok(())
+}
 
+fn build_list_schemas_body(
+    commands: &[CommandInfo],
+    strings: &StringTable,
+    layouts: &RegistryLayouts,
+) -> Result<String> {
+    let layout = &layouts.command_schema;
     let count = commands.len() as i32;
-    let list_bytes = count * CMD_RECORD_SIZE;
+    let list_bytes = count * layout.size;
 
     let mut body = String::new();
 
@@ -625,13 +610,9 @@ fn build_list_schemas_body(commands: &[CommandInfo], strings: &StringTable) -> S
                 &schema_owned
             };
 
-        let record_offset = (i as i32) * CMD_RECORD_SIZE;
+        let record_offset = (i as i32) * layout.size;
 
-        let (name_ptr, name_len) = strings.get(&schema.name);
-        let (summary_ptr, summary_len) = strings.get(&schema.summary);
-        let (usage_ptr, usage_len) = strings.get(&schema.usage);
-        let (version_ptr, version_len) = strings.get(&schema.version);
-        let (desc_ptr, desc_len) = strings.get(&schema.description);
+        let aliases = qualified_aliases(cmd, &schema.aliases);
 
         push_blank(&mut body);
         push_line(&mut body, 4, "local.get $list_ptr");
@@ -639,293 +620,289 @@ fn build_list_schemas_body(commands: &[CommandInfo], strings: &StringTable) -> S
         push_line(&mut body, 4, "i32.add");
         push_line(&mut body, 4, "local.set $record_ptr");
 
-        // name
-        emit_store_i32_const(&mut body, "$record_ptr", 0, name_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 4, name_len);
+        let mut record = RecordStores::new(layout, "$record_ptr");
+        record.string(&mut body, "name", strings.get(&cmd.registry_name()))?;
+        emit_schema_fields(&mut body, &mut record, schema, &aliases, layouts, strings)?;
+        record.bool(&mut body, "allow-arg-files", schema.allow_arg_files)?;
+
+        let hidden_aliases = qualified_aliases(cmd, &schema.hidden_aliases);
+        let slot = record.list_str("hidden-aliases")?;
+        emit_list_str(&mut body, &slot, "$aliases_ptr", &hidden_aliases, strings);
+
+        let sub_layout = &layouts.subcommand_schema;
+        let subs = flatten_subcommands(schema);
+        let slot = record.list_records("subcommands", sub_layout)?;
+        record.finish()?;
+        if !slot.alloc(&mut body, "$subs_ptr", subs.len()) {
+            continue;
+        }
+
+        for (k, (path, sub)) in subs.iter().enumerate() {
+            push_blank(&mut body);
+            slot.select(&mut body, "$subs_ptr", "$sub_ptr", k);
+
+            let mut record = RecordStores::new(sub_layout, "$sub_ptr");
+            let slot = record.list_str("path")?;
+            emit_list_str(&mut body, &slot, "$path_ptr", path, strings);
+            emit_schema_fields(&mut body, &mut record, sub, &sub.aliases, layouts, strings)?;
+            let slot = record.list_str("hidden-aliases")?;
+            emit_list_str(
+                &mut body,
+                &slot,
+                "$aliases_ptr",
+                &sub.hidden_aliases,
+                strings,
+            );
+            record.finish()?;
+        }
+    }
 
-        // summary
-        emit_store_i32_const(&mut body, "$record_ptr", 8, summary_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 12, summary_len);
+    push_blank(&mut body);
+    push_line(&mut body, 4, "local.get $result_ptr");
 
-        // usage
-        emit_store_i32_const(&mut body, "$record_ptr", 16, usage_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 20, usage_len);
+    Ok(body)
+}
 
-        // aliases list<string>
-        if schema.aliases.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 24, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 28, 0);
-        } else {
-            let bytes = (schema.aliases.len() as i32) * STR_ELEM_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $aliases_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 24, "$aliases_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 28, schema.aliases.len() as u32);
-
-            for (j, alias) in schema.aliases.iter().enumerate() {
-                let (ap, al) = strings.get(alias);
-                let entry_off = (j as i32) * STR_ELEM_SIZE;
-                // ptr
-                push_line(&mut body, 4, "local.get $aliases_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", ap));
-                push_line(&mut body, 4, "i32.store offset=0 align=2");
-                // len
-                push_line(&mut body, 4, "local.get $aliases_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", al));
-                push_line(&mut body, 4, "i32.store offset=4 align=2");
-            }
-        }
+/// Store the fields `command-schema` and `subcommand-schema` share, summary
+/// through groups, into `record`.
+fn emit_schema_fields(
+    body: &mut String,
+    record: &mut RecordStores,
+    schema: &wacli_metadata::CommandSchema,
+    aliases: &[String],
+    layouts: &RegistryLayouts,
+    strings: &StringTable,
+) -> Result<()> {
+    record.string(body, "summary", strings.get(&schema.summary))?;
+    record.string(body, "usage", strings.get(&schema.usage))?;
+    let slot = record.list_str("aliases")?;
+    emit_list_str(body, &slot, "$aliases_ptr", aliases, strings);
+    record.string(body, "version", strings.get(&schema.version))?;
+    record.bool(body, "hidden", schema.hidden)?;
+    record.string(body, "description", strings.get(&schema.description))?;
+    let slot = record.list_str("examples")?;
+    emit_list_str(body, &slot, "$examples_ptr", &schema.examples, strings);
+    let slot = record.list_records("args", &layouts.arg_schema)?;
+    emit_arg_schemas(body, &slot, &schema.args, &layouts.arg_schema, strings)?;
+    let slot = record.list_records("groups", &layouts.arg_group)?;
+    emit_arg_groups(body, &slot, &schema.groups, &layouts.arg_group, strings)
+}
 
-        // version
-        emit_store_i32_const(&mut body, "$record_ptr", 32, version_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 36, version_len);
+/// Store `args` as a `list<arg-schema>` in `slot`.
+fn emit_arg_schemas(
+    body: &mut String,
+    slot: &ListSlot,
+    args: &[wacli_metadata::ArgSchema],
+    layout: &RecordLayout,
+    strings: &StringTable,
+) -> Result<()> {
+    if !slot.alloc(body, "$args_ptr", args.len()) {
+        return Ok(());
+    }
 
-        // hidden (bool)
-        push_line(&mut body, 4, "local.get $record_ptr");
-        push_line(
-            &mut body,
-            4,
-            &format!("i32.const {}", if schema.hidden { 1 } else { 0 }),
+    for (j, arg) in args.iter().enumerate() {
+        push_blank(body);
+        slot.select(body, "$args_ptr", "$arg_ptr", j);
+
+        let mut record = RecordStores::new(layout, "$arg_ptr");
+        record.string(body, "name", strings.get(&arg.name))?;
+        record.opt_str(body, "short", arg.short.as_deref(), strings)?;
+        record.opt_str(body, "long", arg.long.as_deref(), strings)?;
+        record.string(body, "help", strings.get(&arg.help))?;
+        record.bool(body, "required", arg.required)?;
+        record.opt_str(body, "default-value", arg.default_value.as_deref(), strings)?;
+        record.opt_str(body, "env", arg.env.as_deref(), strings)?;
+        record.opt_str(body, "value-name", arg.value_name.as_deref(), strings)?;
+        record.bool(body, "takes-value", arg.takes_value)?;
+        record.bool(body, "multiple", arg.multiple)?;
+        record.opt_str(body, "value-type", arg.value_type.as_deref(), strings)?;
+        let values = record.list_str("possible-values")?;
+        emit_list_str(body, &values, "$values_ptr", &arg.possible_values, strings);
+        let conflicts = record.list_str("conflicts-with")?;
+        emit_list_str(
+            body,
+            &conflicts,
+            "$conflicts_ptr",
+            &arg.conflicts_with,
+            strings,
         );
-        push_line(&mut body, 4, "i32.store8 offset=40");
+        let requires = record.list_str("requires")?;
+        emit_list_str(body, &requires, "$requires_ptr", &arg.requires, strings);
+        record.bool(body, "hidden", arg.hidden)?;
+        record.opt_str(
+            body,
+            "complete-command",
+            arg.complete_command.as_deref(),
+            strings,
+        )?;
+        record.opt_str(body, "group", arg.group.as_deref(), strings)?;
+        record.bool(body, "allow-hyphen-values", arg.allow_hyphen_values)?;
+        record.bool(body, "countable", arg.countable)?;
+        record.bool(body, "var-arg", arg.var_arg)?;
+        record.finish()?;
+    }
+    Ok(())
+}
 
-        // description
-        emit_store_i32_const(&mut body, "$record_ptr", 44, desc_ptr);
-        emit_store_i32_const(&mut body, "$record_ptr", 48, desc_len);
+/// Store `groups` as a `list<arg-group>` in `slot`.
+fn emit_arg_groups(
+    body: &mut String,
+    slot: &ListSlot,
+    groups: &[wacli_metadata::ArgGroup],
+    layout: &RecordLayout,
+    strings: &StringTable,
+) -> Result<()> {
+    if !slot.alloc(body, "$groups_ptr", groups.len()) {
+        return Ok(());
+    }
 
-        // examples list<string>
-        if schema.examples.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 52, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 56, 0);
-        } else {
-            let bytes = (schema.examples.len() as i32) * STR_ELEM_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $examples_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 52, "$examples_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 56, schema.examples.len() as u32);
-
-            for (j, ex) in schema.examples.iter().enumerate() {
-                let (ep, el) = strings.get(ex);
-                let entry_off = (j as i32) * STR_ELEM_SIZE;
-                // ptr
-                push_line(&mut body, 4, "local.get $examples_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", ep));
-                push_line(&mut body, 4, "i32.store offset=0 align=2");
-                // len
-                push_line(&mut body, 4, "local.get $examples_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", entry_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, &format!("i32.const {}", el));
-                push_line(&mut body, 4, "i32.store offset=4 align=2");
-            }
-        }
+    for (k, group) in groups.iter().enumerate() {
+        let mut record = RecordStores::at(layout, "$groups_ptr", (k as i32) * layout.size);
+        record.string(body, "name", strings.get(&group.name))?;
+        record.bool(body, "required", group.required)?;
+        record.bool(body, "multiple", group.multiple)?;
+        record.finish()?;
+    }
+    Ok(())
+}
 
-        // args list<arg-schema>
-        if schema.args.is_empty() {
-            emit_store_i32_const(&mut body, "$record_ptr", 60, 0);
-            emit_store_i32_const(&mut body, "$record_ptr", 64, 0);
-        } else {
-            let bytes = (schema.args.len() as i32) * ARG_RECORD_SIZE;
-            push_line(&mut body, 4, &format!("i32.const {}", bytes));
-            push_line(&mut body, 4, "call $alloc");
-            push_line(&mut body, 4, "local.set $args_ptr");
-
-            emit_store_i32_local(&mut body, "$record_ptr", 60, "$args_ptr");
-            emit_store_i32_const(&mut body, "$record_ptr", 64, schema.args.len() as u32);
-
-            for (j, arg) in schema.args.iter().enumerate() {
-                let arg_off = (j as i32) * ARG_RECORD_SIZE;
-                push_blank(&mut body);
-                push_line(&mut body, 4, "local.get $args_ptr");
-                push_line(&mut body, 4, &format!("i32.const {}", arg_off));
-                push_line(&mut body, 4, "i32.add");
-                push_line(&mut body, 4, "local.set $arg_ptr");
-
-                let (anp, anl) = strings.get(&arg.name);
-                emit_store_i32_const(&mut body, "$arg_ptr", 0, anp);
-                emit_store_i32_const(&mut body, "$arg_ptr", 4, anl);
-
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    8,
-                    12,
-                    16,
-                    arg.short.as_deref(),
-                    strings,
-                );
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    20,
-                    24,
-                    28,
-                    arg.long.as_deref(),
-                    strings,
-                );
-
-                let (hp, hl) = strings.get(&arg.help);
-                emit_store_i32_const(&mut body, "$arg_ptr", 32, hp);
-                emit_store_i32_const(&mut body, "$arg_ptr", 36, hl);
-
-                // required bool @40
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.required { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=40");
-
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    44,
-                    48,
-                    52,
-                    arg.default_value.as_deref(),
-                    strings,
-                );
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    56,
-                    60,
-                    64,
-                    arg.env.as_deref(),
-                    strings,
-                );
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    68,
-                    72,
-                    76,
-                    arg.value_name.as_deref(),
-                    strings,
-                );
-
-                // takes_value bool @80
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.takes_value { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=80");
-
-                // multiple bool @81
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.multiple { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=81");
-
-                emit_store_opt_str(
-                    &mut body,
-                    "$arg_ptr",
-                    84,
-                    88,
-                    92,
-                    arg.value_type.as_deref(),
-                    strings,
-                );
-
-                // possible-values list<string> @96/@100
-                emit_list_str(
-                    &mut body,
-                    "$arg_ptr",
-                    96,
-                    100,
-                    "$values_ptr",
-                    &arg.possible_values,
-                    strings,
-                );
-
-                // conflicts-with list<string> @104/@108
-                emit_list_str(
-                    &mut body,
-                    "$arg_ptr",
-                    104,
-                    108,
-                    "$conflicts_ptr",
-                    &arg.conflicts_with,
-                    strings,
-                );
-
-                // requires list<string> @112/@116
-                emit_list_str(
-                    &mut body,
-                    "$arg_ptr",
-                    112,
-                    116,
-                    "$requires_ptr",
-                    &arg.requires,
-                    strings,
-                );
-
-                // hidden bool @120
-                push_line(&mut body, 4, "local.get $arg_ptr");
-                push_line(
-                    &mut body,
-                    4,
-                    &format!("i32.const {}", if arg.hidden { 1 } else { 0 }),
-                );
-                push_line(&mut body, 4, "i32.store8 offset=120");
-            }
+/// Subcommands of `schema`, depth-first, each with its path of names from
+/// the first level down.
+fn flatten_subcommands(
+    schema: &wacli_metadata::CommandSchema,
+) -> Vec<(Vec<String>, &wacli_metadata::CommandSchema)> {
+    let mut out = Vec::new();
+    for sub in &schema.subcommands {
+        out.push((vec![sub.name.clone()], sub));
+        for (mut path, nested) in flatten_subcommands(sub) {
+            path.insert(0, sub.name.clone());
+            out.push((path, nested));
         }
     }
+    out
+}
+
+fn build_app_meta_body(
+    app: &AppMeta,
+    strings: &StringTable,
+    layouts: &RegistryLayouts,
+) -> Result<String> {
+    let layout = &layouts.app_meta;
+    let build_info = &app.build_info;
+
+    let mut body = String::new();
+
+    // Allocate record storage.
+    push_line(&mut body, 4, &format!("i32.const {}", layout.size));
+    push_line(&mut body, 4, "call $alloc");
+    push_line(&mut body, 4, "local.set $result_ptr");
+    push_blank(&mut body);
+
+    let mut record = RecordStores::new(layout, "$result_ptr");
+    record.string(&mut body, "name", strings.get(&app.name))?;
+    record.string(&mut body, "version", strings.get(&app.version))?;
+    record.string(&mut body, "description", strings.get(&app.description))?;
+    record.string(
+        &mut body,
+        "build-info.wacli-version",
+        strings.get(&build_info.wacli_version),
+    )?;
+    record.opt_str(
+        &mut body,
+        "build-info.git-revision",
+        build_info.git_revision.as_deref(),
+        strings,
+    )?;
+    let slot = record.list_str("build-info.built-with")?;
+    emit_list_str(
+        &mut body,
+        &slot,
+        "$list_ptr",
+        &build_info.built_with,
+        strings,
+    );
+    record.opt_str(
+        &mut body,
+        "telemetry-command",
+        app.telemetry_command.as_deref(),
+        strings,
+    )?;
+    record.opt_str(
+        &mut body,
+        "fallback-command",
+        app.fallback_command.as_deref(),
+        strings,
+    )?;
+    record.bool(&mut body, "interactive-picker", app.interactive_picker)?;
+    record.bool(&mut body, "env-warnings", app.env_warnings)?;
+    let slot = record.list_records("global-args", &layouts.arg_schema)?;
+    emit_arg_schemas(
+        &mut body,
+        &slot,
+        &app.global_args,
+        &layouts.arg_schema,
+        strings,
+    )?;
+    record.finish()?;
 
     push_blank(&mut body);
     push_line(&mut body, 4, "local.get $result_ptr");
 
-    body
+    Ok(body)
 }
 
-fn build_app_meta_body(app: &AppMeta, strings: &StringTable) -> String {
-    // `app-meta` record lowers to 3 strings => 6 * ptrsize bytes on wasm32 (24 bytes).
-    const APP_META_RECORD_SIZE: i32 = 24;
-
-    let (name_ptr, name_len) = strings.get(&app.name);
-    let (version_ptr, version_len) = strings.get(&app.version);
-    let (desc_ptr, desc_len) = strings.get(&app.description);
+fn build_list_groups_body(
+    groups: &[CommandGroup],
+    strings: &StringTable,
+    layouts: &RegistryLayouts,
+) -> Result<String> {
+    let layout = &layouts.command_group;
+    let count = groups.len() as i32;
 
     let mut body = String::new();
 
-    // Allocate record storage.
-    push_line(&mut body, 4, &format!("i32.const {}", APP_META_RECORD_SIZE));
+    // Allocate the returned `(ptr, len)` pair.
+    push_line(&mut body, 4, "i32.const 8");
     push_line(&mut body, 4, "call $alloc");
     push_line(&mut body, 4, "local.set $result_ptr");
     push_blank(&mut body);
 
-    // name
-    emit_store_i32_const(&mut body, "$result_ptr", 0, name_ptr);
-    emit_store_i32_const(&mut body, "$result_ptr", 4, name_len);
+    // Allocate list storage.
+    push_line(&mut body, 4, &format!("i32.const {}", count * layout.size));
+    push_line(&mut body, 4, "call $alloc");
+    push_line(&mut body, 4, "local.set $list_ptr");
+    push_blank(&mut body);
 
-    // version
-    emit_store_i32_const(&mut body, "$result_ptr", 8, version_ptr);
-    emit_store_i32_const(&mut body, "$result_ptr", 12, version_len);
+    // Store list ptr/len in result.
+    push_line(&mut body, 4, "local.get $result_ptr");
+    push_line(&mut body, 4, "local.get $list_ptr");
+    push_line(&mut body, 4, "i32.store offset=0 align=2");
+    push_line(&mut body, 4, "local.get $result_ptr");
+    push_line(&mut body, 4, &format!("i32.const {}", count));
+    push_line(&mut body, 4, "i32.store offset=4 align=2");
+
+    for (i, group) in groups.iter().enumerate() {
+        push_blank(&mut body);
+        push_line(&mut body, 4, "local.get $list_ptr");
+        push_line(
+            &mut body,
+            4,
+            &format!("i32.const {}", (i as i32) * layout.size),
+        );
+        push_line(&mut body, 4, "i32.add");
+        push_line(&mut body, 4, "local.set $record_ptr");
 
-    // description
-    emit_store_i32_const(&mut body, "$result_ptr", 16, desc_ptr);
-    emit_store_i32_const(&mut body, "$result_ptr", 20, desc_len);
+        let mut record = RecordStores::new(layout, "$record_ptr");
+        record.string(&mut body, "name", strings.get(&group.name))?;
+        record.string(&mut body, "summary", strings.get(&group.summary))?;
+        record.string(&mut body, "description", strings.get(&group.description))?;
+        record.finish()?;
+    }
 
     push_blank(&mut body);
     push_line(&mut body, 4, "local.get $result_ptr");
 
-    body
+    Ok(body)
 }
 
 fn build_run_body(commands: &[CommandInfo], strings: &StringTable) -> String {
@@ -933,8 +910,13 @@ fn build_run_body(commands: &[CommandInfo], strings: &StringTable) -> String {
 
     for cmd in commands {
         let meta = &cmd.metadata.command_meta;
-        let (name_ptr, name_len) = strings.get(&meta.name);
-        let ident = command_ident(&cmd.name);
+        let aliases: Vec<String> = meta
+            .aliases
+            .iter()
+            .chain(&meta.hidden_aliases)
+            .map(|a| cmd.qualify(a))
+            .collect();
+        let (name_ptr, name_len) = strings.get(&cmd.registry_name());
 
         // Canonical name match.
         push_line(&mut body, 4, "local.get $name_ptr");
@@ -946,17 +928,14 @@ fn build_run_body(commands: &[CommandInfo], strings: &StringTable) -> String {
         push_line(&mut body, 6, "i32.const 16");
         push_line(&mut body, 6, "call $alloc");
         push_line(&mut body, 6, "local.set $ret_ptr");
-        push_line(&mut body, 6, "local.get $argv_ptr");
-        push_line(&mut body, 6, "local.get $argv_len");
-        push_line(&mut body, 6, "local.get $ret_ptr");
-        push_line(&mut body, 6, &format!("call ${}_run", ident));
+        push_run_call(&mut body, cmd, strings);
         push_line(&mut body, 6, "local.get $ret_ptr");
         push_line(&mut body, 6, "return");
         push_line(&mut body, 4, "end");
         push_blank(&mut body);
 
         // Alias matches (static, no `meta()` call).
-        for alias in &meta.aliases {
+        for alias in &aliases {
             let (ap, al) = strings.get(alias);
             push_line(&mut body, 4, "local.get $name_ptr");
             push_line(&mut body, 4, "local.get $name_len");
@@ -967,10 +946,7 @@ fn build_run_body(commands: &[CommandInfo], strings: &StringTable) -> String {
             push_line(&mut body, 6, "i32.const 16");
             push_line(&mut body, 6, "call $alloc");
             push_line(&mut body, 6, "local.set $ret_ptr");
-            push_line(&mut body, 6, "local.get $argv_ptr");
-            push_line(&mut body, 6, "local.get $argv_len");
-            push_line(&mut body, 6, "local.get $ret_ptr");
-            push_line(&mut body, 6, &format!("call ${}_run", ident));
+            push_run_call(&mut body, cmd, strings);
             push_line(&mut body, 6, "local.get $ret_ptr");
             push_line(&mut body, 6, "return");
             push_line(&mut body, 4, "end");
@@ -999,34 +975,218 @@ fn build_run_body(commands: &[CommandInfo], strings: &StringTable) -> String {
     body
 }
 
-fn emit_list_str(
-    out: &mut String,
-    base_local: &str,
+/// Call the command's `run` (or its component's `run-named`) with the
+/// `$argv_ptr`/`$argv_len` and `$ret_ptr` locals of `run`.
+fn push_run_call(body: &mut String, cmd: &CommandInfo, strings: &StringTable) {
+    if cmd.bundle.is_some() {
+        let (name_ptr, name_len) = strings.get(&cmd.name);
+        push_line(body, 6, &format!("i32.const {name_ptr}"));
+        push_line(body, 6, &format!("i32.const {name_len}"));
+    }
+    push_line(body, 6, "local.get $argv_ptr");
+    push_line(body, 6, "local.get $argv_len");
+    push_line(body, 6, "local.get $ret_ptr");
+    let call = match &cmd.bundle {
+        Some(bundle) => format!("call ${}_run_named", bundle_ident(bundle)),
+        None => format!("call ${}_run", command_ident(&cmd.name)),
+    };
+    push_line(body, 6, &call);
+}
+
+/// A `list<T>` field of a record being stored.
+struct ListSlot<'a> {
+    base_local: &'a str,
     ptr_offset: i32,
     len_offset: i32,
+    elem_size: i32,
+}
+
+impl ListSlot<'_> {
+    /// Allocate `len` elements into `tmp_local` and store the list's ptr/len.
+    /// An empty list is stored as `(0, 0)` and `false` returned.
+    fn alloc(&self, out: &mut String, tmp_local: &str, len: usize) -> bool {
+        if len == 0 {
+            emit_store_i32_const(out, self.base_local, self.ptr_offset, 0);
+            emit_store_i32_const(out, self.base_local, self.len_offset, 0);
+            return false;
+        }
+        let bytes = (len as i32) * self.elem_size;
+        push_line(out, 4, &format!("i32.const {}", bytes));
+        push_line(out, 4, "call $alloc");
+        push_line(out, 4, &format!("local.set {}", tmp_local));
+
+        emit_store_i32_local(out, self.base_local, self.ptr_offset, tmp_local);
+        emit_store_i32_const(out, self.base_local, self.len_offset, len as u32);
+        true
+    }
+
+    /// Point `elem_local` at element `index` of the list allocated into
+    /// `list_local`.
+    fn select(&self, out: &mut String, list_local: &str, elem_local: &str, index: usize) {
+        push_line(out, 4, &format!("local.get {list_local}"));
+        push_line(
+            out,
+            4,
+            &format!("i32.const {}", (index as i32) * self.elem_size),
+        );
+        push_line(out, 4, "i32.add");
+        push_line(out, 4, &format!("local.set {elem_local}"));
+    }
+}
+
+/// Stores into one record at `base_local` (plus `base_offset`), by WIT field
+/// name. [`finish`](Self::finish) fails unless every field was stored, so a
+/// field added to the WIT is an error here rather than uninitialized memory
+/// in the registry.
+struct RecordStores<'a> {
+    layout: &'a RecordLayout,
+    base_local: &'a str,
+    base_offset: i32,
+    stored: Vec<bool>,
+}
+
+impl<'a> RecordStores<'a> {
+    fn new(layout: &'a RecordLayout, base_local: &'a str) -> Self {
+        Self::at(layout, base_local, 0)
+    }
+
+    fn at(layout: &'a RecordLayout, base_local: &'a str, base_offset: i32) -> Self {
+        Self {
+            layout,
+            base_local,
+            base_offset,
+            stored: vec![false; layout.fields.len()],
+        }
+    }
+
+    fn take(&mut self, name: &str) -> Result<&'a Field> {
+        let field = self.layout.field(name)?;
+        let index = self
+            .layout
+            .fields
+            .iter()
+            .position(|f| std::ptr::eq(f, field))
+            .expect("field belongs to its layout");
+        if std::mem::replace(&mut self.stored[index], true) {
+            bail!("registry field `{}.{name}` stored twice", self.layout.name);
+        }
+        Ok(field)
+    }
+
+    fn mismatch(&self, name: &str, expected: &str) -> anyhow::Error {
+        anyhow::anyhow!(
+            "registry WIT field `{}.{name}` is not {expected}",
+            self.layout.name
+        )
+    }
+
+    fn string(&mut self, out: &mut String, name: &str, (ptr, len): (u32, u32)) -> Result<()> {
+        let field = self.take(name)?;
+        if field.kind != FieldKind::String {
+            return Err(self.mismatch(name, "a string"));
+        }
+        let offset = self.base_offset + field.offset;
+        emit_store_i32_const(out, self.base_local, offset, ptr);
+        emit_store_i32_const(out, self.base_local, offset + 4, len);
+        Ok(())
+    }
+
+    fn bool(&mut self, out: &mut String, name: &str, value: bool) -> Result<()> {
+        let field = self.take(name)?;
+        if field.kind != FieldKind::Bool {
+            return Err(self.mismatch(name, "a bool"));
+        }
+        push_line(out, 4, &format!("local.get {}", self.base_local));
+        push_line(out, 4, &format!("i32.const {}", u8::from(value)));
+        push_line(
+            out,
+            4,
+            &format!("i32.store8 offset={}", self.base_offset + field.offset),
+        );
+        Ok(())
+    }
+
+    fn opt_str(
+        &mut self,
+        out: &mut String,
+        name: &str,
+        value: Option<&str>,
+        strings: &StringTable,
+    ) -> Result<()> {
+        let field = self.take(name)?;
+        let FieldKind::OptionString { payload } = field.kind else {
+            return Err(self.mismatch(name, "an option<string>"));
+        };
+        let tag = self.base_offset + field.offset;
+        let ptr = tag + payload;
+        emit_store_opt_str(out, self.base_local, tag, ptr, ptr + 4, value, strings);
+        Ok(())
+    }
+
+    fn list_str(&mut self, name: &str) -> Result<ListSlot<'a>> {
+        self.list(name, &Elem::String, "a list<string>")
+    }
+
+    fn list_records(&mut self, name: &str, elem: &RecordLayout) -> Result<ListSlot<'a>> {
+        let expected = format!("a list<{}>", elem.name);
+        self.list(name, &Elem::Record(elem.name.clone()), &expected)
+    }
+
+    fn list(&mut self, name: &str, elem: &Elem, expected: &str) -> Result<ListSlot<'a>> {
+        let field = self.take(name)?;
+        let FieldKind::List {
+            elem: found,
+            elem_size,
+        } = &field.kind
+        else {
+            return Err(self.mismatch(name, expected));
+        };
+        if found != elem {
+            return Err(self.mismatch(name, expected));
+        }
+        let offset = self.base_offset + field.offset;
+        Ok(ListSlot {
+            base_local: self.base_local,
+            ptr_offset: offset,
+            len_offset: offset + 4,
+            elem_size: *elem_size,
+        })
+    }
+
+    fn finish(self) -> Result<()> {
+        let missing: Vec<&str> = self
+            .layout
+            .fields
+            .iter()
+            .zip(&self.stored)
+            .filter(|(_, stored)| !**stored)
+            .map(|(f, _)| f.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "registry generator does not store `{}` field(s): {}",
+                self.layout.name,
+                missing.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+fn emit_list_str(
+    out: &mut String,
+    slot: &ListSlot,
     tmp_local: &str,
     values: &[String],
     strings: &StringTable,
 ) {
-    const STR_ELEM_SIZE: i32 = 8;
-
-    if values.is_empty() {
-        emit_store_i32_const(out, base_local, ptr_offset, 0);
-        emit_store_i32_const(out, base_local, len_offset, 0);
+    if !slot.alloc(out, tmp_local, values.len()) {
         return;
     }
 
-    let bytes = (values.len() as i32) * STR_ELEM_SIZE;
-    push_line(out, 4, &format!("i32.const {}", bytes));
-    push_line(out, 4, "call $alloc");
-    push_line(out, 4, &format!("local.set {}", tmp_local));
-
-    emit_store_i32_local(out, base_local, ptr_offset, tmp_local);
-    emit_store_i32_const(out, base_local, len_offset, values.len() as u32);
-
     for (j, v) in values.iter().enumerate() {
         let (vp, vl) = strings.get(v);
-        let entry_off = (j as i32) * STR_ELEM_SIZE;
+        let entry_off = (j as i32) * slot.elem_size;
         // ptr
         push_line(out, 4, &format!("local.get {}", tmp_local));
         push_line(out, 4, &format!("i32.const {}", entry_off));
@@ -1042,6 +1202,11 @@ fn emit_list_str(
     }
 }
 
+/// Aliases as exposed by the registry (prefixed with the group like the name).
+fn qualified_aliases(cmd: &CommandInfo, aliases: &[String]) -> Vec<String> {
+    aliases.iter().map(|a| cmd.qualify(a)).collect()
+}
+
 fn command_ident(name: &str) -> String {
     let mut ident = String::from("cmd_");
     for ch in name.chars() {
@@ -1054,6 +1219,11 @@ fn command_ident(name: &str) -> String {
     ident
 }
 
+/// Identifier prefix for the imports of the multi-command component `bundle`.
+fn bundle_ident(bundle: &str) -> String {
+    command_ident(bundle).replacen("cmd_", "multi_", 1)
+}
+
 fn compute_heap_start(data_len: usize) -> u32 {
     let aligned = align_up(data_len as u32, 4);
     aligned + 1024
@@ -1147,3 +1317,725 @@ fn add_custom_section(module_bytes: &[u8], name: &str, data: &[u8]) -> Result<Ve
     custom.append_to(&mut result);
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layouts() -> RegistryLayouts {
+        let (resolve, _) = parse_registry_wit(&[]).unwrap();
+        RegistryLayouts::from_resolve(&resolve).unwrap()
+    }
+
+    fn app_with_build_info(git_revision: Option<&str>, built_with: &[&str]) -> AppMeta {
+        AppMeta {
+            name: "example:demo".to_string(),
+            version: "0.1.0".to_string(),
+            description: String::new(),
+            build_info: BuildInfo {
+                wacli_version: "9.9.9".to_string(),
+                git_revision: git_revision.map(str::to_string),
+                built_with: built_with.iter().map(|s| s.to_string()).collect(),
+            },
+            telemetry_command: None,
+            fallback_command: None,
+            interactive_picker: false,
+            env_warnings: false,
+            global_args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn app_meta_body_stores_present_build_info() {
+        let app = app_with_build_info(Some("v1.0.0-3-gabc"), &["build-timestamp: 42"]);
+        let strings = build_string_table(&[], &[], &app);
+        let (rev_ptr, rev_len) = strings.get("v1.0.0-3-gabc");
+        assert_ne!(rev_ptr, 0);
+
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("i32.const 88\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=32\n"));
+        assert!(body.contains(&format!(
+            "    i32.const {rev_ptr}\n    i32.store offset=36 align=2\n"
+        )));
+        assert!(body.contains(&format!(
+            "    i32.const {rev_len}\n    i32.store offset=40 align=2\n"
+        )));
+        // One `built-with` entry.
+        assert!(body.contains("    i32.const 1\n    i32.store offset=48 align=2\n"));
+    }
+
+    #[test]
+    fn app_meta_body_omits_absent_build_info() {
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&[], &[], &app);
+        assert_ne!(strings.get("9.9.9").0, 0);
+
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 0\n    i32.store8 offset=32\n"));
+        assert!(body.contains("    i32.const 0\n    i32.store offset=44 align=2\n"));
+        assert!(body.contains("    i32.const 0\n    i32.store offset=48 align=2\n"));
+    }
+
+    #[test]
+    fn app_meta_body_stores_telemetry_command() {
+        let mut app = app_with_build_info(None, &[]);
+        let body =
+            build_app_meta_body(&app, &build_string_table(&[], &[], &app), &layouts()).unwrap();
+        assert!(body.contains("    i32.const 0\n    i32.store8 offset=52\n"));
+
+        app.telemetry_command = Some("usage-report".to_string());
+        let strings = build_string_table(&[], &[], &app);
+        let (ptr, len) = strings.get("usage-report");
+        assert_ne!(ptr, 0);
+
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=52\n"));
+        assert!(body.contains(&format!(
+            "    i32.const {ptr}\n    i32.store offset=56 align=2\n"
+        )));
+        assert!(body.contains(&format!(
+            "    i32.const {len}\n    i32.store offset=60 align=2\n"
+        )));
+    }
+
+    #[test]
+    fn app_meta_body_stores_interactive_picker() {
+        let mut app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&[], &[], &app);
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 0\n    i32.store8 offset=76\n"));
+
+        app.interactive_picker = true;
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=76\n"));
+    }
+
+    #[test]
+    fn app_meta_body_stores_env_warnings() {
+        let mut app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&[], &[], &app);
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 0\n    i32.store8 offset=77\n"));
+
+        app.env_warnings = true;
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=77\n"));
+    }
+
+    #[test]
+    fn app_meta_body_stores_global_args() {
+        let mut app = app_with_build_info(None, &[]);
+        let body =
+            build_app_meta_body(&app, &build_string_table(&[], &[], &app), &layouts()).unwrap();
+        assert!(body.contains("    i32.const 0\n    i32.store offset=84 align=2\n"));
+
+        app.global_args = vec![
+            wacli_metadata::ArgSchemaBuilder::new("verbose")
+                .long("verbose")
+                .build(),
+            wacli_metadata::ArgSchemaBuilder::new("color")
+                .long("color")
+                .possible_values(["auto", "never"])
+                .build(),
+        ];
+        let strings = build_string_table(&[], &[], &app);
+        let (ptr, len) = strings.get("--color");
+        assert_ne!(ptr, 0);
+        assert_ne!(strings.get("never").0, 0);
+
+        let body = build_app_meta_body(&app, &strings, &layouts()).unwrap();
+        // Two 152-byte arg-schema records, stored at @80 with length 2 @84.
+        assert!(body.contains("    i32.const 304\n    call $alloc\n    local.set $args_ptr\n"));
+        assert!(body.contains("    local.get $args_ptr\n    i32.store offset=80 align=2\n"));
+        assert!(body.contains("    i32.const 2\n    i32.store offset=84 align=2\n"));
+        assert!(body.contains(&format!(
+            "    i32.const {ptr}\n    i32.store offset=24 align=2\n    \
+             local.get $arg_ptr\n    i32.const {len}\n"
+        )));
+
+        let bytes = generate_registry_wat(&[], &[], &app).unwrap();
+        assert_eq!(&bytes[0..4], b"\0asm");
+    }
+
+    #[test]
+    fn generate_registry_wat_accepts_build_info() {
+        let app = app_with_build_info(Some("abc123"), &["build-timestamp: 42"]);
+        let bytes = generate_registry_wat(&[], &[], &app).unwrap();
+        assert_eq!(&bytes[0..4], b"\0asm");
+    }
+
+    fn grouped_command() -> CommandInfo {
+        CommandInfo {
+            name: "migrate".to_string(),
+            path: PathBuf::from("migrate.component.wasm"),
+            imports: Vec::new(),
+            metadata: wacli_metadata::CommandMetadataV1 {
+                format_version: 1,
+                command_meta: wacli_metadata::CommandMeta {
+                    name: "migrate".to_string(),
+                    aliases: vec!["up".to_string()],
+                    ..Default::default()
+                },
+                command_schema: None,
+                self_tests: Vec::new(),
+                cdk_version: None,
+            },
+            group: Some("db".to_string()),
+            bundle: None,
+        }
+    }
+
+    fn db_group() -> CommandGroup {
+        CommandGroup {
+            name: "db".to_string(),
+            summary: "Database tasks".to_string(),
+            description: String::new(),
+            commands: vec!["migrate".to_string()],
+        }
+    }
+
+    #[test]
+    fn registry_bytes_are_reproducible() {
+        let commands = [grouped_command()];
+        let app = app_with_build_info(Some("abc123"), &["ci: 42"]);
+        let first = generate_registry_wat(&commands, &[db_group()], &app).unwrap();
+        let second = generate_registry_wat(&commands, &[db_group()], &app).unwrap();
+        assert!(first == second, "registry bytes differ between runs");
+    }
+
+    #[test]
+    fn grouped_commands_dispatch_on_qualified_names() {
+        let commands = [grouped_command()];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[db_group()], &app);
+        let (name_ptr, name_len) = strings.get("db/migrate");
+        let (alias_ptr, alias_len) = strings.get("db/up");
+        assert_ne!(name_ptr, 0);
+        assert_ne!(alias_ptr, 0);
+
+        let body = build_run_body(&commands, &strings);
+        assert!(body.contains(&format!(
+            "i32.const {name_ptr}\n    i32.const {name_len}\n    call $match-name"
+        )));
+        assert!(body.contains(&format!(
+            "i32.const {alias_ptr}\n    i32.const {alias_len}\n    call $match-name"
+        )));
+
+        let wit = generate_dynamic_wit(&commands);
+        assert!(wit.contains("interface db-migrate-command {"));
+        assert!(wit.contains("  import db-migrate-command;"));
+    }
+
+    #[test]
+    fn hidden_aliases_dispatch_and_are_listed_separately() {
+        use wacli_metadata::CommandMetaBuilder;
+
+        let mut cmd = grouped_command();
+        let payload = CommandMetaBuilder::new("migrate")
+            .alias("up")
+            .hidden_alias("mig")
+            .subcommand(CommandMetaBuilder::new("redo").hidden_alias("again"))
+            .build();
+        cmd.metadata.command_meta = payload.command_meta;
+        cmd.metadata.command_schema = payload.command_schema;
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[db_group()], &app);
+        let (hidden_ptr, hidden_len) = strings.get("db/mig");
+        let (again_ptr, _) = strings.get("again");
+        assert_ne!(hidden_ptr, 0);
+        assert_ne!(again_ptr, 0);
+
+        let run = build_run_body(&commands, &strings);
+        assert!(run.contains(&format!(
+            "i32.const {hidden_ptr}\n    i32.const {hidden_len}\n    call $match-name"
+        )));
+
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 1\n    i32.store offset=92 align=2\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store offset=80 align=2\n"));
+
+        generate_registry_wat(&commands, &[db_group()], &app).unwrap();
+    }
+
+    #[test]
+    fn list_groups_body_stores_group_records() {
+        let groups = [db_group()];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&[], &groups, &app);
+        let (summary_ptr, summary_len) = strings.get("Database tasks");
+
+        let body = build_list_groups_body(&groups, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 24\n    call $alloc\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store offset=4 align=2\n"));
+        assert!(body.contains(&format!(
+            "    i32.const {summary_ptr}\n    i32.store offset=8 align=2\n"
+        )));
+        assert!(body.contains(&format!(
+            "    i32.const {summary_len}\n    i32.store offset=12 align=2\n"
+        )));
+    }
+
+    #[test]
+    fn generate_registry_wat_accepts_groups() {
+        let app = app_with_build_info(None, &[]);
+        let bytes = generate_registry_wat(&[grouped_command()], &[db_group()], &app).unwrap();
+        assert_eq!(&bytes[0..4], b"\0asm");
+    }
+
+    #[test]
+    fn list_schemas_body_stores_allow_arg_files() {
+        let mut cmd = grouped_command();
+        cmd.group = None;
+        cmd.metadata.command_schema = Some(wacli_metadata::CommandSchema {
+            name: "migrate".to_string(),
+            allow_arg_files: true,
+            ..Default::default()
+        });
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 96\n    call $alloc\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=68\n"));
+    }
+
+    #[test]
+    fn list_schemas_body_flattens_subcommands() {
+        use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
+
+        let mut cmd = grouped_command();
+        cmd.group = None;
+        cmd.metadata.command_schema = Some(
+            CommandMetaBuilder::new("remote")
+                .subcommand(
+                    CommandMetaBuilder::new("add")
+                        .arg(ArgSchemaBuilder::new("name"))
+                        .subcommand(CommandMetaBuilder::new("mirror").summary("Add a mirror")),
+                )
+                .subcommand(CommandMetaBuilder::new("list"))
+                .build_schema(),
+        );
+        let schema = cmd.metadata.command_schema.as_ref().unwrap();
+        let paths: Vec<Vec<String>> = flatten_subcommands(schema)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, [vec!["add"], vec!["add", "mirror"], vec!["list"]]);
+
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        // Three 84-byte subcommand records, stored at offset 72 with length 3.
+        assert!(body.contains("    i32.const 252\n    call $alloc\n    local.set $subs_ptr\n"));
+        assert!(body.contains("    i32.const 3\n    i32.store offset=76 align=2\n"));
+        let (mirror_ptr, _) = strings.get("Add a mirror");
+        assert!(body.contains(&format!("    i32.const {mirror_ptr}\n")));
+
+        generate_registry_wat(&commands, &[], &app).unwrap();
+    }
+
+    #[test]
+    fn list_schemas_body_stores_arg_groups() {
+        use wacli_metadata::{ArgGroup, ArgSchemaBuilder, CommandMetaBuilder};
+
+        let mut cmd = grouped_command();
+        cmd.group = None;
+        cmd.metadata.command_schema = Some(
+            CommandMetaBuilder::new("export")
+                .arg(ArgSchemaBuilder::new("json").long("json").group("format"))
+                .arg(ArgSchemaBuilder::new("yaml").long("yaml").group("format"))
+                .group(ArgGroup::new("format").multiple(true))
+                .build_schema(),
+        );
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+        let (format_ptr, format_len) = strings.get("format");
+
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        // Two 152-byte arg records, each naming the group at 136.
+        assert!(body.contains("    i32.const 304\n    call $alloc\n    local.set $args_ptr\n"));
+        assert!(body.contains(&format!(
+            "    i32.const 1\n    i32.store8 offset=136\n    local.get $arg_ptr\n    \
+             i32.const {format_ptr}\n    i32.store offset=140 align=2\n"
+        )));
+        // One 12-byte group record at 80: not required (8), multiple (9).
+        assert!(body.contains("    i32.const 12\n    call $alloc\n    local.set $groups_ptr\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store offset=84 align=2\n"));
+        assert!(body.contains(&format!(
+            "    i32.const {format_len}\n    i32.store offset=4 align=2\n"
+        )));
+        assert!(body.contains("    i32.const 0\n    i32.store8 offset=8\n"));
+        assert!(body.contains("    i32.const 1\n    i32.store8 offset=9\n"));
+
+        generate_registry_wat(&commands, &[], &app).unwrap();
+    }
+
+    #[test]
+    fn list_schemas_body_stores_allow_hyphen_values() {
+        use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
+
+        let mut cmd = grouped_command();
+        cmd.group = None;
+        cmd.metadata.command_schema = Some(
+            CommandMetaBuilder::new("grep")
+                .arg(
+                    ArgSchemaBuilder::new("filter")
+                        .long("filter")
+                        .allow_hyphen_values(true),
+                )
+                .build_schema(),
+        );
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        assert!(body.contains("    i32.const 152\n    call $alloc\n    local.set $args_ptr\n"));
+        assert!(
+            body.contains("    local.get $arg_ptr\n    i32.const 1\n    i32.store8 offset=148\n")
+        );
+
+        generate_registry_wat(&commands, &[], &app).unwrap();
+    }
+
+    #[test]
+    fn list_schemas_body_stores_countable() {
+        use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
+
+        let mut cmd = grouped_command();
+        cmd.group = None;
+        cmd.metadata.command_schema = Some(
+            CommandMetaBuilder::new("log")
+                .arg(ArgSchemaBuilder::new("verbose").short("v").countable(true))
+                .build_schema(),
+        );
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+
+        let body = build_list_schemas_body(&commands, &strings, &layouts()).unwrap();
+        assert!(
+            body.contains("    local.get $arg_ptr\n    i32.const 1\n    i32.store8 offset=149\n")
+        );
+
+        generate_registry_wat(&commands, &[], &app).unwrap();
+    }
+
+    /// Call `registry-schema.<func>` on a generated registry, with every
+    /// command import trapping.
+    fn call_registry_schema(bytes: &[u8], func: &str) -> wasmtime::component::Val {
+        use wasmtime::component::{Component, Linker, Val};
+
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        let engine = wasmtime::Engine::new(&config).unwrap();
+        let component = Component::from_binary(&engine, bytes).unwrap();
+        let mut linker = Linker::<()>::new(&engine);
+        linker.define_unknown_imports_as_traps(&component).unwrap();
+        let mut store = wasmtime::Store::new(&engine, ());
+        let instance = linker.instantiate(&mut store, &component).unwrap();
+        let iface = instance
+            .get_export_index(&mut store, None, "wacli:cli/registry-schema@2.0.0")
+            .unwrap();
+        let index = instance
+            .get_export_index(&mut store, Some(&iface), func)
+            .unwrap();
+        let func = instance.get_func(&mut store, index).unwrap();
+        let mut results = [Val::Bool(false)];
+        func.call(&mut store, &[], &mut results).unwrap();
+        results[0].clone()
+    }
+
+    mod lift {
+        //! `Val`s from the registry back into `wacli_metadata` types.
+
+        use std::collections::BTreeMap;
+        use wacli_metadata::{ArgGroup, ArgSchema, CommandSchema};
+        use wasmtime::component::Val;
+
+        pub fn record(v: &Val) -> BTreeMap<&str, &Val> {
+            let Val::Record(fields) = v else {
+                panic!("expected a record, got {v:?}")
+            };
+            fields.iter().map(|(k, v)| (k.as_str(), v)).collect()
+        }
+
+        pub fn list(v: &Val) -> &[Val] {
+            let Val::List(items) = v else {
+                panic!("expected a list, got {v:?}")
+            };
+            items
+        }
+
+        pub fn string(v: &Val) -> String {
+            let Val::String(s) = v else {
+                panic!("expected a string, got {v:?}")
+            };
+            s.clone()
+        }
+
+        pub fn boolean(v: &Val) -> bool {
+            let Val::Bool(b) = v else {
+                panic!("expected a bool, got {v:?}")
+            };
+            *b
+        }
+
+        pub fn opt_string(v: &Val) -> Option<String> {
+            let Val::Option(o) = v else {
+                panic!("expected an option, got {v:?}")
+            };
+            o.as_deref().map(string)
+        }
+
+        pub fn strings(v: &Val) -> Vec<String> {
+            list(v).iter().map(string).collect()
+        }
+
+        pub fn arg_schema(v: &Val) -> ArgSchema {
+            let f = record(v);
+            ArgSchema {
+                name: string(f["name"]),
+                short: opt_string(f["short"]),
+                long: opt_string(f["long"]),
+                help: string(f["help"]),
+                required: boolean(f["required"]),
+                default_value: opt_string(f["default-value"]),
+                env: opt_string(f["env"]),
+                value_name: opt_string(f["value-name"]),
+                takes_value: boolean(f["takes-value"]),
+                multiple: boolean(f["multiple"]),
+                value_type: opt_string(f["value-type"]),
+                possible_values: strings(f["possible-values"]),
+                conflicts_with: strings(f["conflicts-with"]),
+                requires: strings(f["requires"]),
+                hidden: boolean(f["hidden"]),
+                complete_command: opt_string(f["complete-command"]),
+                shared: None,
+                group: opt_string(f["group"]),
+                allow_hyphen_values: boolean(f["allow-hyphen-values"]),
+                countable: boolean(f["countable"]),
+                var_arg: boolean(f["var-arg"]),
+            }
+        }
+
+        fn arg_group(v: &Val) -> ArgGroup {
+            let f = record(v);
+            ArgGroup {
+                name: string(f["name"]),
+                required: boolean(f["required"]),
+                multiple: boolean(f["multiple"]),
+            }
+        }
+
+        /// Fields `command-schema` and `subcommand-schema` share.
+        fn schema_fields(name: String, f: &BTreeMap<&str, &Val>) -> CommandSchema {
+            CommandSchema {
+                name,
+                summary: string(f["summary"]),
+                usage: string(f["usage"]),
+                aliases: strings(f["aliases"]),
+                hidden_aliases: strings(f["hidden-aliases"]),
+                version: string(f["version"]),
+                hidden: boolean(f["hidden"]),
+                description: string(f["description"]),
+                examples: strings(f["examples"]),
+                args: list(f["args"]).iter().map(arg_schema).collect(),
+                groups: list(f["groups"]).iter().map(arg_group).collect(),
+                ..Default::default()
+            }
+        }
+
+        /// A `command-schema`, with its flattened subcommands nested again.
+        pub fn command_schema(v: &Val) -> CommandSchema {
+            let f = record(v);
+            let mut schema = schema_fields(string(f["name"]), &f);
+            schema.allow_arg_files = boolean(f["allow-arg-files"]);
+            for sub in list(f["subcommands"]) {
+                let f = record(sub);
+                let path = strings(f["path"]);
+                let (last, parents) = path.split_last().unwrap();
+                let mut parent = &mut schema;
+                for name in parents {
+                    parent = parent
+                        .subcommands
+                        .iter_mut()
+                        .find(|s| &s.name == name)
+                        .unwrap();
+                }
+                parent.subcommands.push(schema_fields(last.clone(), &f));
+            }
+            schema
+        }
+    }
+
+    fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    fn schema_command(schema: wacli_metadata::CommandSchema) -> CommandInfo {
+        let mut cmd = grouped_command();
+        cmd.name = schema.name.clone();
+        cmd.group = None;
+        cmd.metadata.command_meta = wacli_metadata::CommandMeta {
+            name: schema.name.clone(),
+            ..Default::default()
+        };
+        cmd.metadata.command_schema = Some(schema);
+        cmd
+    }
+
+    fn every_arg_field() -> wacli_metadata::ArgSchema {
+        wacli_metadata::ArgSchema {
+            name: "format".to_string(),
+            short: Some("f".to_string()),
+            long: Some("format".to_string()),
+            help: "Output format".to_string(),
+            required: true,
+            default_value: Some("json".to_string()),
+            env: Some("DEMO_FORMAT".to_string()),
+            value_name: Some("FMT".to_string()),
+            takes_value: true,
+            multiple: true,
+            value_type: Some("string".to_string()),
+            possible_values: vec!["json".to_string(), "yaml".to_string()],
+            conflicts_with: vec!["raw".to_string()],
+            requires: vec!["out".to_string()],
+            hidden: true,
+            complete_command: Some("__complete-format".to_string()),
+            shared: None,
+            group: Some("output".to_string()),
+            allow_hyphen_values: true,
+            countable: true,
+            var_arg: true,
+        }
+    }
+
+    #[test]
+    fn list_schemas_round_trips_through_wasmtime() {
+        use wacli_metadata::{ArgGroup, ArgSchema, CommandSchema};
+
+        let rich = CommandSchema {
+            name: "export".to_string(),
+            summary: "Export data".to_string(),
+            usage: "export [OPTIONS]".to_string(),
+            aliases: vec!["ex".to_string(), "dump".to_string()],
+            hidden_aliases: vec!["exp".to_string()],
+            version: "1.2.3".to_string(),
+            hidden: true,
+            description: "Writes every record.".to_string(),
+            examples: vec!["export -f yaml".to_string()],
+            args: vec![
+                every_arg_field(),
+                ArgSchema {
+                    name: "raw".to_string(),
+                    ..Default::default()
+                },
+            ],
+            groups: vec![ArgGroup::new("output").required(true).multiple(true)],
+            allow_arg_files: true,
+            subcommands: vec![CommandSchema {
+                name: "remote".to_string(),
+                summary: "Export to a remote".to_string(),
+                aliases: vec!["r".to_string()],
+                hidden_aliases: vec!["rem".to_string()],
+                args: vec![every_arg_field()],
+                groups: vec![ArgGroup::new("output")],
+                subcommands: vec![CommandSchema {
+                    name: "s3".to_string(),
+                    version: "2.0.0".to_string(),
+                    examples: vec!["export remote s3".to_string()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let bare = CommandSchema {
+            name: "noop".to_string(),
+            ..Default::default()
+        };
+        let commands = [schema_command(rich.clone()), schema_command(bare.clone())];
+        let app = app_with_build_info(None, &[]);
+        let bytes = generate_registry_wat(&commands, &[], &app).unwrap();
+
+        let listed = call_registry_schema(&bytes, "list-schemas");
+        let decoded: Vec<CommandSchema> = lift::list(&listed)
+            .iter()
+            .map(lift::command_schema)
+            .collect();
+        assert_eq!(decoded.len(), 2);
+        for (got, want) in decoded.iter().zip([&rich, &bare]) {
+            assert_eq!(json(got), json(want), "schema `{}` differs", want.name);
+        }
+    }
+
+    #[test]
+    fn app_meta_round_trips_through_wasmtime() {
+        let mut app = app_with_build_info(Some("abc123"), &["ci: 42", "host: x"]);
+        app.description = "Demo app".to_string();
+        app.telemetry_command = Some("usage-report".to_string());
+        app.fallback_command = Some("ext".to_string());
+        app.env_warnings = true;
+        app.global_args = vec![every_arg_field()];
+        let bytes = generate_registry_wat(&[], &[], &app).unwrap();
+
+        let meta = call_registry_schema(&bytes, "get-app-meta");
+        let f = lift::record(&meta);
+        assert_eq!(lift::string(f["name"]), app.name);
+        assert_eq!(lift::string(f["version"]), app.version);
+        assert_eq!(lift::string(f["description"]), app.description);
+        let build = lift::record(f["build-info"]);
+        assert_eq!(lift::string(build["wacli-version"]), "9.9.9");
+        assert_eq!(
+            lift::opt_string(build["git-revision"]).as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(lift::strings(build["built-with"]), ["ci: 42", "host: x"]);
+        assert_eq!(
+            lift::opt_string(f["telemetry-command"]),
+            app.telemetry_command
+        );
+        assert_eq!(
+            lift::opt_string(f["fallback-command"]),
+            app.fallback_command
+        );
+        assert!(!lift::boolean(f["interactive-picker"]));
+        assert!(lift::boolean(f["env-warnings"]));
+        let globals: Vec<_> = lift::list(f["global-args"])
+            .iter()
+            .map(lift::arg_schema)
+            .collect();
+        assert_eq!(json(&globals), json(&app.global_args));
+    }
+
+    #[test]
+    fn records_missing_a_wit_field_are_rejected() {
+        let mut layouts = layouts();
+        layouts
+            .arg_group
+            .fields
+            .push(crate::registry_layout::Field {
+                name: "exclusive".to_string(),
+                offset: 10,
+                kind: FieldKind::Bool,
+            });
+        let mut cmd = grouped_command();
+        cmd.metadata.command_schema = Some(wacli_metadata::CommandSchema {
+            name: "migrate".to_string(),
+            groups: vec![wacli_metadata::ArgGroup::new("g")],
+            ..Default::default()
+        });
+        let commands = [cmd];
+        let app = app_with_build_info(None, &[]);
+        let strings = build_string_table(&commands, &[], &app);
+        let err = build_list_schemas_body(&commands, &strings, &layouts)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`arg-group` field(s): exclusive"), "{err}");
+    }
+}
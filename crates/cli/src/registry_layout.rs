@@ -0,0 +1,262 @@
+//! Canonical ABI layouts (wasm32) of the records the registry returns.
+//!
+//! The WAT emitters in `registry_gen_wat` write records straight into linear
+//! memory. Offsets and sizes come from the same parsed WIT the registry is
+//! encoded with, so a field added to `schema`, `types` or `registry-schema`
+//! moves every later offset without anyone recounting them. Field types the
+//! emitters cannot store are rejected here instead of being skipped.
+
+use anyhow::{Result, bail};
+use wit_parser::{Resolve, SizeAlign, Type, TypeDefKind, TypeId};
+
+/// How a record field is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// `(ptr, len)` at the field offset.
+    String,
+    /// One byte.
+    Bool,
+    /// Tag byte at the field offset, `(ptr, len)` at `payload` past it.
+    OptionString { payload: i32 },
+    /// `(ptr, len)` of `elem_size`-byte elements.
+    List { elem: Elem, elem_size: i32 },
+}
+
+/// Element type of a [`FieldKind::List`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Elem {
+    String,
+    /// A record, by its WIT name.
+    Record(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// WIT field name; fields of a nested record are `outer.inner`.
+    pub name: String,
+    pub offset: i32,
+    pub kind: FieldKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordLayout {
+    pub name: String,
+    pub size: i32,
+    pub fields: Vec<Field>,
+}
+
+impl RecordLayout {
+    /// Compute the layout of the record named `name` in `resolve`.
+    pub fn compute(resolve: &Resolve, sizes: &SizeAlign, name: &str) -> Result<Self> {
+        let id = find_record(resolve, name)?;
+        let mut fields = Vec::new();
+        flatten_fields(resolve, sizes, name, id, "", 0, &mut fields)?;
+        Ok(Self {
+            name: name.to_string(),
+            size: wasm32(sizes.size(&Type::Id(id)).size_wasm32()),
+            fields,
+        })
+    }
+
+    pub fn field(&self, name: &str) -> Result<&Field> {
+        match self.fields.iter().find(|f| f.name == name) {
+            Some(field) => Ok(field),
+            None => bail!("registry WIT record `{}` has no field `{name}`", self.name),
+        }
+    }
+}
+
+/// Layouts of every record the registry writes.
+#[derive(Debug, Clone)]
+pub struct RegistryLayouts {
+    pub command_meta: RecordLayout,
+    pub arg_def: RecordLayout,
+    pub command_schema: RecordLayout,
+    pub subcommand_schema: RecordLayout,
+    pub arg_schema: RecordLayout,
+    pub arg_group: RecordLayout,
+    pub app_meta: RecordLayout,
+    pub command_group: RecordLayout,
+}
+
+impl RegistryLayouts {
+    pub fn from_resolve(resolve: &Resolve) -> Result<Self> {
+        let mut sizes = SizeAlign::default();
+        sizes.fill(resolve);
+        let layout = |name| RecordLayout::compute(resolve, &sizes, name);
+        Ok(Self {
+            command_meta: layout("command-meta")?,
+            arg_def: layout("arg-def")?,
+            command_schema: layout("command-schema")?,
+            subcommand_schema: layout("subcommand-schema")?,
+            arg_schema: layout("arg-schema")?,
+            arg_group: layout("arg-group")?,
+            app_meta: layout("app-meta")?,
+            command_group: layout("command-group")?,
+        })
+    }
+}
+
+fn find_record(resolve: &Resolve, name: &str) -> Result<TypeId> {
+    let mut found = resolve
+        .types
+        .iter()
+        .filter(|(_, ty)| {
+            ty.name.as_deref() == Some(name) && matches!(ty.kind, TypeDefKind::Record(_))
+        })
+        .map(|(id, _)| id);
+    match (found.next(), found.next()) {
+        (Some(id), None) => Ok(id),
+        (None, _) => bail!("registry WIT has no record `{name}`"),
+        (Some(_), Some(_)) => bail!("registry WIT defines record `{name}` more than once"),
+    }
+}
+
+fn flatten_fields(
+    resolve: &Resolve,
+    sizes: &SizeAlign,
+    record: &str,
+    id: TypeId,
+    prefix: &str,
+    base: i32,
+    out: &mut Vec<Field>,
+) -> Result<()> {
+    let TypeDefKind::Record(r) = &resolve.types[id].kind else {
+        bail!("registry WIT type `{record}` is not a record");
+    };
+    let offsets = sizes.field_offsets(r.fields.iter().map(|f| &f.ty));
+    for (field, (offset, ty)) in r.fields.iter().zip(offsets) {
+        let name = format!("{prefix}{}", field.name);
+        let offset = base + wasm32(offset.size_wasm32());
+        let kind = match field_kind(resolve, sizes, ty) {
+            Some(kind) => kind,
+            None => match nested_record(resolve, ty) {
+                Some(nested) => {
+                    let prefix = format!("{name}.");
+                    flatten_fields(resolve, sizes, record, nested, &prefix, offset, out)?;
+                    continue;
+                }
+                None => bail!(
+                    "registry WIT field `{record}.{name}` has a type the registry generator \
+                     cannot store"
+                ),
+            },
+        };
+        out.push(Field { name, offset, kind });
+    }
+    Ok(())
+}
+
+fn field_kind(resolve: &Resolve, sizes: &SizeAlign, ty: &Type) -> Option<FieldKind> {
+    match ty {
+        Type::String => Some(FieldKind::String),
+        Type::Bool => Some(FieldKind::Bool),
+        Type::Id(id) => match &resolve.types[*id].kind {
+            TypeDefKind::Type(inner) => field_kind(resolve, sizes, inner),
+            TypeDefKind::Option(Type::String) => Some(FieldKind::OptionString {
+                payload: wasm32(
+                    sizes
+                        .payload_offset(wit_parser::Int::U8, [Some(&Type::String)])
+                        .size_wasm32(),
+                ),
+            }),
+            TypeDefKind::List(elem) => {
+                let elem_kind = match elem {
+                    Type::String => Elem::String,
+                    _ => Elem::Record(resolve.types[nested_record(resolve, elem)?].name.clone()?),
+                };
+                Some(FieldKind::List {
+                    elem: elem_kind,
+                    elem_size: wasm32(sizes.size(elem).size_wasm32()),
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The record `ty` names, looking through `use`d aliases.
+fn nested_record(resolve: &Resolve, ty: &Type) -> Option<TypeId> {
+    let Type::Id(id) = ty else { return None };
+    match &resolve.types[*id].kind {
+        TypeDefKind::Record(_) => Some(*id),
+        TypeDefKind::Type(inner) => nested_record(resolve, inner),
+        _ => None,
+    }
+}
+
+fn wasm32(bytes: usize) -> i32 {
+    i32::try_from(bytes).expect("registry record layout exceeds i32")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wit_parser::UnresolvedPackageGroup;
+
+    fn layouts(wit: &str) -> Result<RegistryLayouts> {
+        let mut resolve = Resolve::default();
+        resolve.push_group(UnresolvedPackageGroup::parse("test.wit", wit)?)?;
+        RegistryLayouts::from_resolve(&resolve)
+    }
+
+    fn base_wit() -> String {
+        let mut wit = String::new();
+        crate::registry_gen_wat::append_wit_base(&mut wit);
+        wit
+    }
+
+    #[test]
+    fn layouts_match_the_canonical_abi() {
+        let l = layouts(&base_wit()).unwrap();
+        assert_eq!(l.command_meta.size, 68);
+        assert_eq!(l.arg_def.size, 72);
+        assert_eq!(l.command_schema.size, 96);
+        assert_eq!(l.subcommand_schema.size, 84);
+        assert_eq!(l.arg_schema.size, 152);
+        assert_eq!(l.arg_group.size, 12);
+        assert_eq!(l.app_meta.size, 88);
+        assert_eq!(l.command_group.size, 24);
+
+        let countable = l.arg_schema.field("countable").unwrap();
+        assert_eq!((countable.offset, &countable.kind), (149, &FieldKind::Bool));
+        let var_arg = l.arg_schema.field("var-arg").unwrap();
+        assert_eq!((var_arg.offset, &var_arg.kind), (150, &FieldKind::Bool));
+        let group = l.arg_schema.field("group").unwrap();
+        assert_eq!(
+            (group.offset, &group.kind),
+            (136, &FieldKind::OptionString { payload: 4 })
+        );
+        let subs = l.command_schema.field("subcommands").unwrap();
+        assert_eq!(
+            (subs.offset, &subs.kind),
+            (
+                72,
+                &FieldKind::List {
+                    elem: Elem::Record("subcommand-schema".to_string()),
+                    elem_size: 84
+                }
+            )
+        );
+        // `build-info` is flattened into `app-meta`.
+        let rev = l.app_meta.field("build-info.git-revision").unwrap();
+        assert_eq!(rev.offset, 32);
+        let fallback = l.app_meta.field("fallback-command").unwrap();
+        assert_eq!(
+            (fallback.offset, &fallback.kind),
+            (64, &FieldKind::OptionString { payload: 4 })
+        );
+        assert_eq!(l.app_meta.field("global-args").unwrap().offset, 80);
+    }
+
+    #[test]
+    fn unsupported_field_types_are_rejected() {
+        let wit = base_wit().replace(
+            "    countable: bool,",
+            "    countable: bool,\n    weight: u32,",
+        );
+        let err = layouts(&wit).unwrap_err().to_string();
+        assert!(err.contains("`arg-schema.weight`"), "{err}");
+    }
+}
@@ -1,8 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use molt_registry_client::OciWasmClient;
 use std::fs;
 use std::path::Path;
 
+use crate::progress::PullProgress;
+
 #[derive(Debug, Clone)]
 pub struct PulledComponentDigests {
     pub manifest_digest: String,
@@ -20,12 +22,37 @@ pub fn pull_component_wasm_to_file(
     Ok(())
 }
 
+/// Pull into `dest`, showing progress labeled with `repo` on stderr (see
+/// [`crate::progress`]).
 pub fn pull_component_wasm_to_file_with_digests(
     client: &OciWasmClient,
     repo: &str,
     reference: &str,
     dest: &Path,
     overwrite: bool,
+) -> Result<Option<PulledComponentDigests>> {
+    let progress = PullProgress::new(repo);
+    let pulled = pull_component_wasm_to_file_with_progress(
+        client,
+        repo,
+        reference,
+        dest,
+        overwrite,
+        &|done, total| progress.update(done, total),
+    );
+    progress.finish(pulled.is_ok());
+    pulled
+}
+
+/// Like [`pull_component_wasm_to_file_with_digests`], reporting the bytes
+/// downloaded so far and the total (if known) to `on_progress` instead.
+pub fn pull_component_wasm_to_file_with_progress(
+    client: &OciWasmClient,
+    repo: &str,
+    reference: &str,
+    dest: &Path,
+    overwrite: bool,
+    on_progress: &dyn Fn(u64, Option<u64>),
 ) -> Result<Option<PulledComponentDigests>> {
     if dest.exists() && !overwrite {
         return Ok(None);
@@ -44,9 +71,11 @@ pub fn pull_component_wasm_to_file_with_digests(
         .context("failed to initialize async runtime")?;
 
     let pulled = rt
-        .block_on(client.pull_component_wasm_with_digests(repo, reference))
+        .block_on(client.pull_component_wasm_with_progress(repo, reference, on_progress))
         .with_context(|| format!("failed to pull component from registry: {repo}:{reference}"))?;
     let bytes = pulled.bytes;
+    crate::lock::verify_digest_bytes(&bytes, &pulled.layer_digest)
+        .map_err(|e| anyhow!("component pulled from {repo}:{reference} is corrupted: {e}"))?;
 
     let tmp = dest.with_extension("download");
     fs::write(&tmp, &bytes).with_context(|| format!("failed to write {}", tmp.display()))?;
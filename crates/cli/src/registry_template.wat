@@ -2,10 +2,12 @@
   (type $list-commands (func (result i32)))
   (type $list-schemas (func (result i32)))
   (type $app-meta (func (result i32)))
+  (type $list-groups (func (result i32)))
   (type $run (func (param i32 i32 i32 i32) (result i32)))
   (type $cabi_realloc (func (param i32 i32 i32 i32) (result i32)))
   (type $import_run (func (param i32 i32 i32)))
   (type $import_meta (func (param i32)))
+  (type $import_run_named (func (param i32 i32 i32 i32 i32)))
 
 {{IMPORTS}}
 
@@ -115,14 +117,31 @@
     (local $values_ptr i32)
     (local $conflicts_ptr i32)
     (local $requires_ptr i32)
+    (local $subs_ptr i32)
+    (local $sub_ptr i32)
+    (local $path_ptr i32)
+    (local $groups_ptr i32)
 {{LIST_SCHEMAS_BODY}}
   )
 
   (func $app-meta (type $app-meta) (result i32)
     (local $result_ptr i32)
+    (local $list_ptr i32)
+    (local $args_ptr i32)
+    (local $arg_ptr i32)
+    (local $values_ptr i32)
+    (local $conflicts_ptr i32)
+    (local $requires_ptr i32)
 {{APP_META_BODY}}
   )
 
+  (func $list-groups (type $list-groups) (result i32)
+    (local $result_ptr i32)
+    (local $list_ptr i32)
+    (local $record_ptr i32)
+{{LIST_GROUPS_BODY}}
+  )
+
   (func $run (type $run) (param $name_ptr i32) (param $name_len i32) (param $argv_ptr i32) (param $argv_len i32) (result i32)
     (local $ret_ptr i32)
 {{RUN_BODY}}
@@ -136,6 +155,7 @@
   (export "wacli:cli/registry@2.0.0#list-commands" (func $list-commands))
   (export "wacli:cli/registry-schema@2.0.0#list-schemas" (func $list-schemas))
   (export "wacli:cli/registry-schema@2.0.0#get-app-meta" (func $app-meta))
+  (export "wacli:cli/registry-schema@2.0.0#list-groups" (func $list-groups))
   (export "wacli:cli/registry@2.0.0#run" (func $run))
   (export "cabi_realloc" (func $cabi_realloc))
 
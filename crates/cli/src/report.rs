@@ -0,0 +1,399 @@
+//! `wacli build --report`: what each component contributes to the output.
+//!
+//! Sizes are taken twice: the file on disk, and the bytes the composition
+//! embedded (after `--strip`). The share of the output is approximate: the
+//! composition adds its own instantiation glue and the provenance section.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use wasmparser::{KnownCustom, Parser, Payload};
+
+use crate::component_scan::{CommandInfo, components};
+use crate::progress::human_bytes;
+
+/// How `--report` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// An aligned table
+    Text,
+    /// A JSON document
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentKind {
+    Command,
+    Framework,
+    Registry,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ComponentReport {
+    /// The registry name for commands (the component's name for a
+    /// multi-command component), `host`/`core`/`registry` otherwise.
+    pub name: String,
+    pub kind: ComponentKind,
+    /// Registry names of a multi-command component's commands.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    pub path: PathBuf,
+    /// Size of the file on disk.
+    pub size: u64,
+    /// Bytes the composition embedded.
+    pub encoded_size: u64,
+    /// `encoded_size` as a percentage of the output.
+    pub share: f64,
+    /// The wacli-cdk recorded in the command's metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cdk_version: Option<String>,
+    /// Whether `cdk_version` differs from the wacli that built the app.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cdk_mismatch: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BuildReport {
+    pub output: PathBuf,
+    pub output_size: u64,
+    /// The wacli-cdk version matching this wacli.
+    pub cdk_version: String,
+    /// Command components by encoded size (largest first), then host, core
+    /// and registry.
+    pub components: Vec<ComponentReport>,
+}
+
+/// Framework packages in the order they are reported.
+const FRAMEWORK: [(&str, &str, ComponentKind); 3] = [
+    ("wacli:host", "host", ComponentKind::Framework),
+    ("wacli:core", "core", ComponentKind::Framework),
+    ("wacli:registry", "registry", ComponentKind::Registry),
+];
+
+/// The report for an output of `output_size` bytes composed from `deps`
+/// (package name to file), where `encoded` holds the bytes each package
+/// contributed.
+pub fn collect(
+    commands: &[CommandInfo],
+    deps: &HashMap<String, PathBuf>,
+    encoded: &HashMap<String, u64>,
+    output: &Path,
+    output_size: u64,
+) -> Result<BuildReport> {
+    let expected = env!("CARGO_PKG_VERSION");
+    let entry = |package: &str, name: &str, kind: ComponentKind| -> Result<ComponentReport> {
+        let path = deps.get(package).cloned().unwrap_or_default();
+        let size = fs::metadata(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+            .len();
+        let encoded_size = encoded.get(package).copied().unwrap_or(size);
+        Ok(ComponentReport {
+            name: name.to_string(),
+            kind,
+            commands: Vec::new(),
+            path,
+            size,
+            encoded_size,
+            share: percent(encoded_size, output_size),
+            cdk_version: None,
+            cdk_mismatch: false,
+        })
+    };
+
+    let mut reports = Vec::new();
+    for cmd in components(commands) {
+        let mut report = match &cmd.bundle {
+            Some(bundle) => {
+                let mut report = entry(&cmd.package_name(), bundle, ComponentKind::Command)?;
+                report.commands = commands
+                    .iter()
+                    .filter(|c| c.bundle == cmd.bundle)
+                    .map(CommandInfo::registry_name)
+                    .collect();
+                report
+            }
+            None => entry(
+                &cmd.package_name(),
+                &cmd.registry_name(),
+                ComponentKind::Command,
+            )?,
+        };
+        report.cdk_version = cmd.metadata.cdk_version.clone().or_else(|| {
+            fs::read(&report.path)
+                .ok()
+                .and_then(|bytes| producers_cdk_version(&bytes))
+        });
+        report.cdk_mismatch = report
+            .cdk_version
+            .as_deref()
+            .is_some_and(|version| version != expected);
+        reports.push(report);
+    }
+    reports.sort_by(|a, b| {
+        b.encoded_size
+            .cmp(&a.encoded_size)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    for (package, name, kind) in FRAMEWORK {
+        if deps.contains_key(package) {
+            reports.push(entry(package, name, kind)?);
+        }
+    }
+
+    Ok(BuildReport {
+        output: output.to_path_buf(),
+        output_size,
+        cdk_version: expected.to_string(),
+        components: reports,
+    })
+}
+
+/// The `wacli-cdk` entry of a `producers` section anywhere in `bytes`, for
+/// commands whose metadata does not record the CDK. The CDK cannot write
+/// that entry itself (the compiler owns the section), so it is there only
+/// when the build added it (`wasm-tools metadata add --sdk wacli-cdk=<version>`).
+fn producers_cdk_version(bytes: &[u8]) -> Option<String> {
+    for payload in Parser::new(0).parse_all(bytes) {
+        let Ok(Payload::CustomSection(reader)) = payload else {
+            continue;
+        };
+        let KnownCustom::Producers(fields) = reader.as_known() else {
+            continue;
+        };
+        for field in fields.into_iter().flatten() {
+            for value in field.values.into_iter().flatten() {
+                if value.name == "wacli-cdk" {
+                    return Some(value.version.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / total as f64
+}
+
+/// `report` in `format`, ending with a newline.
+pub fn render(report: &BuildReport, format: ReportFormat) -> Result<String> {
+    match format {
+        ReportFormat::Text => Ok(render_text(report)),
+        ReportFormat::Json => {
+            let mut out =
+                serde_json::to_string_pretty(report).context("failed to serialize report")?;
+            out.push('\n');
+            Ok(out)
+        }
+    }
+}
+
+fn render_text(report: &BuildReport) -> String {
+    let mut rows = vec![[
+        "Component".to_string(),
+        "Kind".to_string(),
+        "Size".to_string(),
+        "In output".to_string(),
+        "Share".to_string(),
+        "wacli-cdk".to_string(),
+    ]];
+    for c in &report.components {
+        let cdk = match (&c.cdk_version, c.kind) {
+            (Some(version), _) if c.cdk_mismatch => format!("{version} (mismatch)"),
+            (Some(version), _) => version.clone(),
+            (None, ComponentKind::Command) => "unknown".to_string(),
+            (None, _) => String::new(),
+        };
+        let kind = match c.kind {
+            ComponentKind::Command => "command",
+            ComponentKind::Framework => "framework",
+            ComponentKind::Registry => "registry",
+        };
+        let name = if c.commands.is_empty() {
+            c.name.clone()
+        } else {
+            format!("{} ({})", c.name, c.commands.join(", "))
+        };
+        rows.push([
+            name,
+            kind.to_string(),
+            human_bytes(c.size),
+            human_bytes(c.encoded_size),
+            format!("{:.1}%", c.share),
+            cdk,
+        ]);
+    }
+
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    for row in &rows {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:>w4$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            row[5],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "Output: {} ({})\n",
+        report.output.display(),
+        human_bytes(report.output_size)
+    ));
+
+    let mismatched: Vec<&str> = report
+        .components
+        .iter()
+        .filter(|c| c.cdk_mismatch)
+        .map(|c| c.name.as_str())
+        .collect();
+    if !mismatched.is_empty() {
+        out.push_str(&format!(
+            "warning: built with a wacli-cdk other than {}: {}\n",
+            report.cdk_version,
+            mismatched.join(", ")
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component_scan::inspect_command_component;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../testdata")
+            .join(name)
+    }
+
+    /// greet and seed from testdata/, plus the framework components.
+    fn collect_fixtures(seed_cdk: Option<&str>) -> BuildReport {
+        let greet = inspect_command_component(&fixture("greet.component.wasm"), false).unwrap();
+        let mut seed = inspect_command_component(&fixture("seed.component.wasm"), false).unwrap();
+        seed.metadata.cdk_version = seed_cdk.map(str::to_string);
+        let commands = [greet, seed];
+
+        let components = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../components");
+        let mut deps: HashMap<String, PathBuf> = commands
+            .iter()
+            .map(|c| (c.package_name(), c.path.clone()))
+            .collect();
+        deps.insert("wacli:host".into(), components.join("host.component.wasm"));
+        deps.insert("wacli:core".into(), components.join("core.component.wasm"));
+        // Any file stands in for the generated registry.
+        deps.insert("wacli:registry".into(), fixture("greet.component.wasm"));
+        // As if --strip had halved greet.
+        let greet_size = fs::metadata(fixture("greet.component.wasm")).unwrap().len();
+        let encoded = HashMap::from([("wacli:cmd-greet".to_string(), greet_size / 2)]);
+
+        collect(
+            &commands,
+            &deps,
+            &encoded,
+            Path::new("app.wasm"),
+            greet_size * 10,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn collect_reports_sizes_shares_and_cdk_versions() {
+        let report = collect_fixtures(Some(env!("CARGO_PKG_VERSION")));
+        let names: Vec<&str> = report.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["seed", "greet", "host", "core", "registry"]);
+
+        let greet = &report.components[1];
+        assert_eq!(greet.kind, ComponentKind::Command);
+        assert_eq!(greet.encoded_size, greet.size / 2);
+        assert!((greet.share - 5.0).abs() < 0.1, "{}", greet.share);
+        assert_eq!(
+            greet.cdk_version.as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+        assert!(report.components.iter().all(|c| !c.cdk_mismatch));
+
+        // Components the composition did not re-encode count in full.
+        let host = &report.components[2];
+        assert_eq!(host.kind, ComponentKind::Framework);
+        assert_eq!(host.encoded_size, host.size);
+        assert_eq!(report.components[4].kind, ComponentKind::Registry);
+    }
+
+    #[test]
+    fn render_flags_mismatched_cdk_versions() {
+        let report = collect_fixtures(Some("0.0.1"));
+        assert!(report.components[0].cdk_mismatch);
+
+        let text = render(&report, ReportFormat::Text).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("Component  Kind"), "{text}");
+        assert!(
+            lines[1].starts_with("seed       command") && lines[1].ends_with("0.0.1 (mismatch)"),
+            "{text}"
+        );
+        assert!(lines[2].ends_with(env!("CARGO_PKG_VERSION")), "{text}");
+        assert!(lines[3].starts_with("host       framework"), "{text}");
+        assert!(
+            text.ends_with(&format!(
+                "warning: built with a wacli-cdk other than {}: seed\n",
+                env!("CARGO_PKG_VERSION")
+            )),
+            "{text}"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&report, ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["components"][0]["cdk-version"], "0.0.1");
+        assert_eq!(json["components"][0]["cdk-mismatch"], true);
+        assert!(json["components"][1].get("cdk-mismatch").is_none());
+        assert_eq!(json["components"][3]["kind"], "framework");
+    }
+
+    #[test]
+    fn producers_section_stands_in_for_a_missing_cdk_version() {
+        let wasm = wat::parse_str(
+            r#"(component
+                (core module (@producers (sdk "wacli-cdk" "0.0.7") (processed-by "rustc" "1.0")))
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(producers_cdk_version(&wasm).as_deref(), Some("0.0.7"));
+        let greet = fs::read(fixture("greet.component.wasm")).unwrap();
+        assert_eq!(producers_cdk_version(&greet), None);
+    }
+
+    #[test]
+    fn commands_without_a_recorded_cdk_are_unknown() {
+        let report = collect_fixtures(None);
+        assert_eq!(report.components[0].cdk_version, None);
+        assert!(!report.components[0].cdk_mismatch);
+        let text = render(&report, ReportFormat::Text).unwrap();
+        assert!(text.lines().nth(1).unwrap().ends_with("unknown"), "{text}");
+        assert!(!text.contains("warning"), "{text}");
+    }
+}
@@ -0,0 +1,258 @@
+//! Resource limits for reading command components.
+//!
+//! `wacli build` parses components it did not build (e.g. pulled from a
+//! registry), so a crafted one must not be able to exhaust memory or stall
+//! the build. Every limit can be raised through an environment variable for
+//! unusual but legitimate components.
+
+use anyhow::{Result, bail};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use wacli_metadata::CommandMetadataV1;
+
+pub const MAX_SECTION_BYTES_VAR: &str = "WACLI_SCAN_MAX_SECTION_BYTES";
+pub const MAX_JSON_DEPTH_VAR: &str = "WACLI_SCAN_MAX_JSON_DEPTH";
+pub const MAX_ITEMS_VAR: &str = "WACLI_SCAN_MAX_ITEMS";
+pub const TIMEOUT_SECS_VAR: &str = "WACLI_SCAN_TIMEOUT_SECS";
+pub const META_FUEL_VAR: &str = "WACLI_SCAN_META_FUEL";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanLimits {
+    /// Largest wacli custom section accepted, in bytes.
+    pub max_section_bytes: usize,
+    /// Deepest JSON nesting accepted in a metadata payload.
+    pub max_json_depth: usize,
+    /// Args, aliases or examples per command above which a warning is printed.
+    pub max_items: usize,
+    /// Time budget for scanning one component.
+    pub timeout: Duration,
+    /// Fuel for instantiating a component and calling its `meta()` under
+    /// `--allow-meta-exec`.
+    pub meta_fuel: u64,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self {
+            max_section_bytes: 1024 * 1024,
+            max_json_depth: 64,
+            max_items: 512,
+            timeout: Duration::from_secs(30),
+            meta_fuel: 10_000_000,
+        }
+    }
+}
+
+impl ScanLimits {
+    /// The defaults, with any `WACLI_SCAN_*` overrides applied.
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let read = |name: &str, default: usize| -> Result<usize> {
+            match lookup(name).as_deref().map(str::trim) {
+                None | Some("") => Ok(default),
+                Some(raw) => match raw.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(n),
+                    _ => bail!("{name} must be a positive integer, got '{raw}'"),
+                },
+            }
+        };
+        let defaults = Self::default();
+        Ok(Self {
+            max_section_bytes: read(MAX_SECTION_BYTES_VAR, defaults.max_section_bytes)?,
+            max_json_depth: read(MAX_JSON_DEPTH_VAR, defaults.max_json_depth)?,
+            max_items: read(MAX_ITEMS_VAR, defaults.max_items)?,
+            timeout: Duration::from_secs(read(
+                TIMEOUT_SECS_VAR,
+                defaults.timeout.as_secs() as usize,
+            )? as u64),
+            meta_fuel: read(META_FUEL_VAR, defaults.meta_fuel as usize)? as u64,
+        })
+    }
+
+    /// Start the clock for scanning one component.
+    pub fn deadline(&self) -> Deadline {
+        Deadline {
+            at: Instant::now() + self.timeout,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Reject a custom section before it is copied or parsed.
+    pub fn check_section(&self, name: &str, len: usize) -> Result<()> {
+        if len > self.max_section_bytes {
+            bail!(
+                "custom section '{name}' is {len} bytes, over the {} byte limit \
+                 (set {MAX_SECTION_BYTES_VAR} to raise it)",
+                self.max_section_bytes
+            );
+        }
+        Ok(())
+    }
+
+    /// Reject JSON nested deeper than the limit, without parsing it.
+    pub fn check_json_depth(&self, raw: &[u8]) -> Result<()> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for &b in raw {
+            if in_string {
+                match b {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match b {
+                b'"' => in_string = true,
+                b'{' | b'[' => {
+                    depth += 1;
+                    if depth > self.max_json_depth {
+                        bail!(
+                            "metadata JSON is nested deeper than {} levels \
+                             (set {MAX_JSON_DEPTH_VAR} to raise it)",
+                            self.max_json_depth
+                        );
+                    }
+                }
+                b'}' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Warn about commands declaring more args, aliases or examples than the
+    /// limit; they still build.
+    pub fn warn_on_large_lists(&self, meta: &CommandMetadataV1, path: &Path) {
+        let cmd = &meta.command_meta;
+        let args = meta
+            .command_schema
+            .as_ref()
+            .map_or(cmd.args.len(), |s| s.args.len());
+        for (what, count) in [
+            ("args", args),
+            ("aliases", cmd.aliases.len()),
+            ("examples", cmd.examples.len()),
+        ] {
+            if count > self.max_items {
+                tracing::warn!(
+                    "command '{}' ({}) declares {count} {what}, more than {} \
+                     (set {MAX_ITEMS_VAR} to silence this)",
+                    cmd.name,
+                    path.display(),
+                    self.max_items
+                );
+            }
+        }
+    }
+}
+
+/// When the scan of one component must stop.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    at: Instant,
+    timeout: Duration,
+}
+
+impl Deadline {
+    pub fn check(&self) -> Result<()> {
+        if Instant::now() > self.at {
+            bail!(
+                "scan took longer than {}s (set {TIMEOUT_SECS_VAR} to raise the limit)",
+                self.timeout.as_secs()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(vars: &[(&str, &str)]) -> Result<ScanLimits> {
+        ScanLimits::from_lookup(|name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    #[test]
+    fn env_overrides_defaults() {
+        assert_eq!(limits(&[]).unwrap(), ScanLimits::default());
+        let custom = limits(&[
+            (MAX_SECTION_BYTES_VAR, "4096"),
+            (MAX_JSON_DEPTH_VAR, " 8 "),
+            (TIMEOUT_SECS_VAR, ""),
+            (META_FUEL_VAR, "500"),
+        ])
+        .unwrap();
+        assert_eq!(custom.max_section_bytes, 4096);
+        assert_eq!(custom.max_json_depth, 8);
+        assert_eq!(custom.timeout, Duration::from_secs(30));
+        assert_eq!(custom.meta_fuel, 500);
+
+        for bad in ["0", "-1", "1MiB"] {
+            let err = limits(&[(MAX_ITEMS_VAR, bad)]).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                format!("WACLI_SCAN_MAX_ITEMS must be a positive integer, got '{bad}'")
+            );
+        }
+    }
+
+    #[test]
+    fn section_size_is_capped() {
+        let limits = ScanLimits {
+            max_section_bytes: 10,
+            ..Default::default()
+        };
+        limits.check_section("s", 10).unwrap();
+        assert_eq!(
+            limits.check_section("s", 11).unwrap_err().to_string(),
+            "custom section 's' is 11 bytes, over the 10 byte limit \
+             (set WACLI_SCAN_MAX_SECTION_BYTES to raise it)"
+        );
+    }
+
+    #[test]
+    fn json_depth_ignores_brackets_in_strings() {
+        let limits = ScanLimits {
+            max_json_depth: 2,
+            ..Default::default()
+        };
+        limits
+            .check_json_depth(br#"{"a": [1, 2], "b": {}}"#)
+            .unwrap();
+        limits.check_json_depth(br#"{"a": "[[[{{{\"[["}"#).unwrap();
+        let err = limits.check_json_depth(br#"{"a": [[1]]}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "metadata JSON is nested deeper than 2 levels (set WACLI_SCAN_MAX_JSON_DEPTH to raise it)"
+        );
+    }
+
+    #[test]
+    fn expired_deadline_fails() {
+        let limits = ScanLimits {
+            timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        let deadline = limits.deadline();
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(
+            deadline
+                .check()
+                .unwrap_err()
+                .to_string()
+                .starts_with("scan took longer than 0s")
+        );
+        ScanLimits::default().deadline().check().unwrap();
+    }
+}
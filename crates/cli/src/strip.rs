@@ -0,0 +1,166 @@
+//! `wacli build --strip`: drop debug info (and optionally names) from the
+//! components before they are composed.
+//!
+//! Only custom sections are removed, and only the ones listed here; every
+//! other section (including `wacli:cli/command-metadata@1` and the
+//! component-type sections composition needs) is copied byte for byte.
+//! Nested modules and components are rewritten the same way.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use wasm_encoder::{ComponentSectionId, Encode, RawSection, Section};
+use wasmparser::{Encoding, Parser, Payload};
+
+/// What `--strip` removes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StripLevel {
+    /// Keep every section
+    #[default]
+    None,
+    /// Remove DWARF and other debug info sections
+    Debug,
+    /// Also remove the `name` and `component-name` sections
+    All,
+}
+
+/// Whether `level` removes the custom section `name`.
+fn strips(level: StripLevel, name: &str) -> bool {
+    let debug =
+        name.starts_with(".debug_") || matches!(name, "sourceMappingURL" | "external_debug_info");
+    match level {
+        StripLevel::None => false,
+        StripLevel::Debug => debug,
+        StripLevel::All => debug || matches!(name, "name" | "component-name"),
+    }
+}
+
+/// `wasm` (a module or a component) without the custom sections `level`
+/// removes.
+pub fn strip(wasm: &[u8], level: StripLevel) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    // Outputs of the enclosing modules/components while a nested one is built.
+    let mut stack = Vec::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload.context("failed to parse WASM")?;
+        match &payload {
+            Payload::Version { encoding, .. } => {
+                output.extend_from_slice(match encoding {
+                    Encoding::Module => &wasm_encoder::Module::HEADER,
+                    Encoding::Component => &wasm_encoder::Component::HEADER,
+                });
+                continue;
+            }
+            Payload::ModuleSection { .. } | Payload::ComponentSection { .. } => {
+                stack.push(std::mem::take(&mut output));
+                continue;
+            }
+            Payload::End(_) => {
+                let Some(mut parent) = stack.pop() else {
+                    break;
+                };
+                let id = if output.starts_with(&wasm_encoder::Component::HEADER) {
+                    ComponentSectionId::Component
+                } else {
+                    ComponentSectionId::CoreModule
+                };
+                parent.push(id as u8);
+                output.encode(&mut parent);
+                output = parent;
+                continue;
+            }
+            Payload::CustomSection(reader) if strips(level, reader.name()) => continue,
+            _ => {}
+        }
+        if let Some((id, range)) = payload.as_section() {
+            RawSection {
+                id,
+                data: &wasm[range],
+            }
+            .append_to(&mut output);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use wasm_encoder::{Component, CustomSection, Module, ModuleSection, NestedComponentSection};
+
+    fn custom(name: &str) -> CustomSection<'_> {
+        CustomSection {
+            name: Cow::Borrowed(name),
+            data: Cow::Borrowed(b"data"),
+        }
+    }
+
+    /// Custom section names of `wasm` and everything nested in it, in order.
+    fn custom_sections(wasm: &[u8]) -> Vec<String> {
+        Parser::new(0)
+            .parse_all(wasm)
+            .filter_map(|payload| match payload.unwrap() {
+                Payload::CustomSection(reader) => Some(reader.name().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn component() -> Vec<u8> {
+        let mut module = Module::new();
+        for name in [
+            ".debug_info",
+            "name",
+            "wacli:cli/command-metadata@1",
+            "producers",
+        ] {
+            module.section(&custom(name));
+        }
+        let mut nested = Component::new();
+        nested.section(&custom(".debug_line"));
+        nested.section(&custom("component-name"));
+
+        let mut component = Component::new();
+        component.section(&ModuleSection(&module));
+        component.section(&NestedComponentSection(&nested));
+        component.section(&custom("component-type:command"));
+        component.section(&custom("component-name"));
+        component.finish()
+    }
+
+    #[test]
+    fn strip_levels_remove_only_their_sections() {
+        let wasm = component();
+        assert_eq!(strip(&wasm, StripLevel::None).unwrap(), wasm);
+        assert_eq!(
+            custom_sections(&strip(&wasm, StripLevel::Debug).unwrap()),
+            [
+                "name",
+                "wacli:cli/command-metadata@1",
+                "producers",
+                "component-name",
+                "component-type:command",
+                "component-name",
+            ]
+        );
+        assert_eq!(
+            custom_sections(&strip(&wasm, StripLevel::All).unwrap()),
+            [
+                "wacli:cli/command-metadata@1",
+                "producers",
+                "component-type:command",
+            ]
+        );
+    }
+
+    #[test]
+    fn stripped_components_still_validate() {
+        let stripped = strip(&component(), StripLevel::All).unwrap();
+        wasmparser::Validator::new()
+            .validate_all(&stripped)
+            .expect("stripped component is invalid");
+        assert!(stripped.len() < component().len());
+    }
+}
@@ -0,0 +1,847 @@
+//! `wacli verify`: run every command's `--help`, documented examples and
+//! declared self-tests against a composed CLI and summarize the results.
+//!
+//! The component is compiled once and shared by a pool of worker threads;
+//! each check runs in its own `Store` with stdout/stderr captured, so output
+//! from parallel runs never interleaves.
+//!
+//! Given a command component instead, it checks that the component would
+//! compose: the plugin world's imports and exports and the embedded metadata
+//! (with `--exec`, also that `meta()` agrees with it). Every problem is
+//! listed, as text or as JSON for CI annotations.
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use plugin_loader::{LoadedComponent, PreopenDir, RunOutput, Runner};
+
+use wacli_metadata::{CommandMeta, CommandMetadataV1, SelfTest};
+
+use crate::command_metadata::{extract_all_command_metadata, extract_bundled_command_metadata};
+use crate::compat::plugin_world_problems;
+use crate::component_scan::{
+    CommandInfo, MetadataProblem, WasmKind, analyze_wasm, exports_command_interface,
+    exports_multi_command_interface, metadata_problems,
+};
+use crate::manifest;
+use crate::scan_limits::ScanLimits;
+
+#[derive(Parser)]
+pub struct VerifyArgs {
+    /// Composed CLI or command component (.component.wasm)
+    #[arg(value_name = "COMPONENT")]
+    component: PathBuf,
+
+    /// Path to a wacli manifest used for command groups (defaults to ./wacli.json if present)
+    #[arg(long, value_name = "FILE")]
+    manifest: Option<PathBuf>,
+
+    /// Number of checks to run in parallel [default: available cores]
+    #[arg(short, long, value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Stop scheduling new checks after the first failure
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Preopen a directory (HOST[::GUEST], repeatable)
+    #[arg(long = "dir", value_name = "HOST[::GUEST]")]
+    dirs: Vec<String>,
+
+    /// Command components: also call meta() and compare it with the embedded metadata
+    #[arg(long)]
+    exec: bool,
+
+    /// Command components: print the problems found as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// One invocation of the CLI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Check {
+    /// Command as typed by a user (`greet`, `db migrate`).
+    command: String,
+    /// What is being checked (`--help`, `example `...``, `self-test `...``).
+    label: String,
+    argv: Vec<String>,
+    /// Expectations for self-tests; other checks expect exit code 0.
+    self_test: Option<SelfTest>,
+}
+
+enum Outcome {
+    Pass,
+    Fail { reason: String, output: RunOutput },
+}
+
+/// Outcome of checking a command component.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ComponentReport {
+    component: String,
+    /// `plugin` or `multi-plugin`; absent for a core module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    world: Option<&'static str>,
+    commands: Vec<String>,
+    problems: Vec<Problem>,
+}
+
+#[derive(Debug, Serialize)]
+struct Problem {
+    /// The command the problem is in, when it is not the whole component.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<String>,
+    message: String,
+}
+
+impl ComponentReport {
+    fn problem(&mut self, command: Option<&str>, message: impl Into<String>) {
+        self.problems.push(Problem {
+            command: command.map(str::to_string),
+            message: message.into(),
+        });
+    }
+}
+
+pub fn verify(args: VerifyArgs) -> Result<()> {
+    let bytes = std::fs::read(&args.component)
+        .with_context(|| format!("failed to read component: {}", args.component.display()))?;
+    let limits = ScanLimits::from_env()?;
+    if !is_composed_cli(&bytes, &limits)? {
+        return verify_command_component(&args, &bytes, &limits);
+    }
+    if args.exec || args.json {
+        bail!(
+            "--exec and --json only apply to command components; {} is a composed CLI",
+            args.component.display()
+        );
+    }
+    let metas = extract_all_command_metadata(&bytes, &limits)?;
+    if metas.is_empty() {
+        bail!("no command metadata found in {}", args.component.display());
+    }
+
+    let groups = manifest::load_manifest(args.manifest.as_deref())?
+        .and_then(|loaded| loaded.manifest.build)
+        .and_then(|build| build.groups)
+        .map(|groups| member_groups(&groups))
+        .unwrap_or_default();
+
+    let mut checks = Vec::new();
+    for meta in &metas {
+        let name = &meta.command_meta.name;
+        let prefix: Vec<String> = match groups.get(name) {
+            Some(group) => vec![group.clone(), name.clone()],
+            None => vec![name.clone()],
+        };
+        checks.extend(command_checks(meta, &prefix)?);
+    }
+    checks.sort_by(|a, b| a.command.cmp(&b.command));
+
+    let mut preopens = Vec::new();
+    for dir in &args.dirs {
+        preopens.push(crate::parse_preopen_dir(dir)?);
+    }
+
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+        .max(1);
+
+    let runner = Runner::new()?;
+    let loaded = runner.load(&args.component)?;
+    let results = run_checks(&runner, &loaded, &checks, &preopens, jobs, args.fail_fast);
+
+    let (report, failed) = render_report(&checks, &results);
+    print!("{report}");
+    if failed > 0 {
+        bail!("{failed} of {} check(s) failed", checks.len());
+    }
+    Ok(())
+}
+
+/// Whether `bytes` is a composed CLI (it exports `wasi:cli/run` and no
+/// command interface) rather than a command component.
+fn is_composed_cli(bytes: &[u8], limits: &ScanLimits) -> Result<bool> {
+    Ok(match analyze_wasm(bytes, &limits.deadline())? {
+        WasmKind::Component { exports, .. } => {
+            !exports_command_interface(&exports)
+                && !exports_multi_command_interface(&exports)
+                && exports.iter().any(|e| e.starts_with("wasi:cli/run@"))
+        }
+        WasmKind::CoreModule => false,
+    })
+}
+
+fn verify_command_component(args: &VerifyArgs, bytes: &[u8], limits: &ScanLimits) -> Result<()> {
+    let report = check_command_component(&args.component, bytes, limits, args.exec)?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", render_component_report(&report));
+    }
+    if !report.problems.is_empty() {
+        bail!(
+            "{} problem(s) found in {}",
+            report.problems.len(),
+            args.component.display()
+        );
+    }
+    Ok(())
+}
+
+/// Check a command component on its own, the way `wacli build` would see it:
+/// the plugin world, and the embedded metadata of each command it declares.
+/// With `exec`, it is also instantiated against a stub host to compare what
+/// `meta()` (and `list-commands()`) return with that metadata.
+fn check_command_component(
+    path: &Path,
+    bytes: &[u8],
+    limits: &ScanLimits,
+    exec: bool,
+) -> Result<ComponentReport> {
+    let mut report = ComponentReport {
+        component: path.display().to_string(),
+        world: None,
+        commands: Vec::new(),
+        problems: Vec::new(),
+    };
+    let deadline = limits.deadline();
+    let (exports, imports) = match analyze_wasm(bytes, &deadline)? {
+        WasmKind::Component { exports, imports } => (exports, imports),
+        WasmKind::CoreModule => {
+            report.problem(
+                None,
+                "is a core WebAssembly module, not a component \
+                 (run `wasm-tools component new` on it)",
+            );
+            return Ok(report);
+        }
+    };
+    let multi = exports_multi_command_interface(&exports);
+    let world = if multi { "multi-plugin" } else { "plugin" };
+    report.world = Some(world);
+    for problem in plugin_world_problems(bytes, world)? {
+        report.problem(None, problem);
+    }
+
+    let metas = match extract_bundled_command_metadata(bytes, limits, &deadline) {
+        Ok(metas) => metas,
+        Err(err) => {
+            report.problem(None, format!("failed to extract command metadata: {err:#}"));
+            return Ok(report);
+        }
+    };
+    if metas.is_empty() {
+        report.problem(
+            None,
+            format!(
+                "missing embedded command metadata (a `{}` section, written by \
+                 `wacli_cdk::declare_command_metadata!`)",
+                wacli_metadata::COMMAND_METADATA_SECTION
+            ),
+        );
+        return Ok(report);
+    }
+    if !multi && metas.len() > 1 {
+        report.problem(
+            None,
+            format!(
+                "the command metadata section declares {} commands; \
+                 export them with `wacli_cdk::export_multi!`",
+                metas.len()
+            ),
+        );
+    }
+
+    let bundle = path.file_name().map(|name| {
+        name.to_string_lossy()
+            .trim_end_matches(".component.wasm")
+            .to_string()
+    });
+    let mut seen = HashSet::new();
+    let mut commands = Vec::new();
+    for metadata in metas {
+        let name = metadata.command_meta.name.clone();
+        if !seen.insert(name.clone()) {
+            report.problem(Some(&name), "declared twice");
+            continue;
+        }
+        report.commands.push(name.clone());
+        commands.push(CommandInfo {
+            name,
+            path: path.to_path_buf(),
+            imports: imports.clone(),
+            metadata,
+            group: None,
+            bundle: bundle.clone().filter(|_| multi),
+        });
+    }
+    for (cmd, problem) in metadata_problems(&commands) {
+        match problem {
+            MetadataProblem::Invalid(err) => {
+                for issue in err.issues {
+                    report.problem(Some(&cmd.name), issue);
+                }
+            }
+            clash => report.problem(Some(&cmd.name), clash.to_string()),
+        }
+    }
+
+    if exec {
+        let embedded: Vec<&CommandMeta> =
+            commands.iter().map(|c| &c.metadata.command_meta).collect();
+        let mut calls = vec![(
+            "meta()",
+            plugin_loader::call_command_meta(bytes, limits.meta_fuel).map(|m| vec![m]),
+            &embedded[..1],
+        )];
+        if multi {
+            calls.push((
+                "list-commands()",
+                plugin_loader::call_listed_command_metas(bytes, limits.meta_fuel),
+                &embedded[..],
+            ));
+        }
+        for (call, result, expected) in calls {
+            let called = match result {
+                Ok(called) => called,
+                Err(err) => {
+                    report.problem(None, format!("{call} could not be called: {err:#}"));
+                    continue;
+                }
+            };
+            if called.len() != expected.len() {
+                report.problem(
+                    None,
+                    format!(
+                        "{call} returns {} command(s) but the metadata section declares {}",
+                        called.len(),
+                        expected.len()
+                    ),
+                );
+                continue;
+            }
+            for (embedded, called) in expected.iter().zip(&called) {
+                let fields = differing_fields(embedded, called)?;
+                if !fields.is_empty() {
+                    report.problem(
+                        Some(&embedded.name),
+                        format!(
+                            "{call} differs from the embedded metadata in: {}",
+                            fields.join(", ")
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// `command-meta` fields (kebab-case) whose values differ. Hidden aliases
+/// are skipped: `meta()` cannot return them.
+fn differing_fields(embedded: &CommandMeta, called: &CommandMeta) -> Result<Vec<String>> {
+    let fields = |meta: &CommandMeta| -> Result<serde_json::Map<String, serde_json::Value>> {
+        let meta = CommandMeta {
+            hidden_aliases: Vec::new(),
+            ..meta.clone()
+        };
+        match serde_json::to_value(meta)? {
+            serde_json::Value::Object(map) => Ok(map),
+            other => bail!("command-meta serialized as {other}"),
+        }
+    };
+    let (a, b) = (fields(embedded)?, fields(called)?);
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    Ok(keys
+        .into_iter()
+        .filter(|key| a.get(*key) != b.get(*key))
+        .cloned()
+        .collect())
+}
+
+fn render_component_report(report: &ComponentReport) -> String {
+    let mut out = String::new();
+    let world = report.world.unwrap_or("plugin");
+    if report.problems.is_empty() {
+        let _ = writeln!(
+            out,
+            "{}: conforms to the {world} world ({})",
+            report.component,
+            report.commands.join(", ")
+        );
+        return out;
+    }
+    let _ = writeln!(out, "{}:", report.component);
+    for problem in &report.problems {
+        match &problem.command {
+            Some(command) => {
+                let _ = writeln!(out, "  - {command}: {}", problem.message);
+            }
+            None => {
+                let _ = writeln!(out, "  - {}", problem.message);
+            }
+        }
+    }
+    out
+}
+
+/// Map each grouped command to the group it is invoked through.
+fn member_groups(groups: &[manifest::CommandGroup]) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for group in groups {
+        for member in &group.commands {
+            out.insert(member.clone(), group.name.clone());
+        }
+    }
+    out
+}
+
+/// `--help` plus one check per documented example and per self-test.
+fn command_checks(payload: &CommandMetadataV1, prefix: &[String]) -> Result<Vec<Check>> {
+    let meta = &payload.command_meta;
+    let command = prefix.join(" ");
+    let mut argv = prefix.to_vec();
+    argv.push("--help".to_string());
+    let mut checks = vec![Check {
+        command: command.clone(),
+        label: "--help".to_string(),
+        argv,
+        self_test: None,
+    }];
+
+    for example in &meta.examples {
+        checks.push(Check {
+            command: command.clone(),
+            label: format!("example `{example}`"),
+            argv: invocation(meta, prefix, example)
+                .with_context(|| format!("invalid example for '{}': {example}", meta.name))?,
+            self_test: None,
+        });
+    }
+    for test in &payload.self_tests {
+        checks.push(Check {
+            command: command.clone(),
+            label: format!("self-test `{}`", test.cmd),
+            argv: invocation(meta, prefix, &test.cmd)
+                .with_context(|| format!("invalid self-test for '{}': {}", meta.name, test.cmd))?,
+            self_test: Some(test.clone()),
+        });
+    }
+    Ok(checks)
+}
+
+/// Full argv for an example-style command line.
+fn invocation(
+    meta: &wacli_metadata::CommandMeta,
+    prefix: &[String],
+    line: &str,
+) -> Result<Vec<String>> {
+    let mut words = split_example(line)?;
+    // Examples are usually written with the command name first; it is
+    // replaced by the full invocation prefix (which includes any group).
+    if words
+        .first()
+        .is_some_and(|w| *w == meta.name || meta.aliases.contains(w))
+    {
+        words.remove(0);
+    }
+    let mut argv = prefix.to_vec();
+    argv.extend(words);
+    Ok(argv)
+}
+
+/// Split an example line into words, honoring single/double quotes and
+/// backslash escapes.
+fn split_example(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                let next = chars.next().context("trailing backslash")?;
+                current.push(next);
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("unterminated quote");
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Run `checks` on `jobs` worker threads; workers pull the next unclaimed
+/// check until none are left (or a failure stops the run under `fail_fast`).
+/// Returns one slot per check; `None` means it was never started.
+fn run_checks(
+    runner: &Runner,
+    loaded: &LoadedComponent,
+    checks: &[Check],
+    preopens: &[PreopenDir],
+    jobs: usize,
+    fail_fast: bool,
+) -> Vec<Option<Outcome>> {
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let results = Mutex::new((0..checks.len()).map(|_| None).collect::<Vec<_>>());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(checks.len()) {
+            scope.spawn(|| {
+                loop {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(check) = checks.get(index) else {
+                        break;
+                    };
+                    let outcome = run_check(runner, loaded, check, preopens);
+                    if fail_fast && matches!(outcome, Outcome::Fail { .. }) {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    results.lock().expect("results lock poisoned")[index] = Some(outcome);
+                }
+            });
+        }
+    });
+
+    results.into_inner().expect("results lock poisoned")
+}
+
+fn run_check(
+    runner: &Runner,
+    loaded: &LoadedComponent,
+    check: &Check,
+    preopens: &[PreopenDir],
+) -> Outcome {
+    let output = match runner.run_captured(loaded, &check.argv, preopens) {
+        Ok(output) => output,
+        Err(err) => {
+            return Outcome::Fail {
+                reason: format!("{err:#}"),
+                output: RunOutput::default(),
+            };
+        }
+    };
+    let expected_exit = check.self_test.as_ref().map_or(0, |t| t.expect_exit);
+    if output.code != expected_exit {
+        let mut reason = format!("exit code {}", output.code);
+        if expected_exit != 0 {
+            let _ = write!(reason, " (expected {expected_exit})");
+        }
+        return Outcome::Fail { reason, output };
+    }
+    if let Some(test) = &check.self_test
+        && let Err(reason) = test.check_stdout(&String::from_utf8_lossy(&output.stdout))
+    {
+        return Outcome::Fail { reason, output };
+    }
+    if check.label == "--help" && output.stdout.iter().all(u8::is_ascii_whitespace) {
+        return Outcome::Fail {
+            reason: "--help printed nothing".to_string(),
+            output,
+        };
+    }
+    Outcome::Pass
+}
+
+/// Render the per-command summary. `checks` must be sorted by command.
+/// The first failure of each command is shown with its captured stderr (or
+/// stdout, if stderr is empty); later failures are listed by label only.
+fn render_report(checks: &[Check], results: &[Option<Outcome>]) -> (String, usize) {
+    let width = checks.iter().map(|c| c.command.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    let (mut commands, mut passed, mut failed, mut skipped) = (0, 0, 0, 0);
+
+    let mut start = 0;
+    while start < checks.len() {
+        let command = &checks[start].command;
+        let end = start
+            + checks[start..]
+                .iter()
+                .take_while(|c| &c.command == command)
+                .count();
+        commands += 1;
+
+        let mut details = String::new();
+        let (mut ok, mut bad, mut none) = (0, 0, 0);
+        for (check, result) in checks[start..end].iter().zip(&results[start..end]) {
+            match result {
+                Some(Outcome::Pass) => ok += 1,
+                Some(Outcome::Fail { reason, output }) => {
+                    let _ = writeln!(details, "  FAIL {}: {reason}", check.label);
+                    if bad == 0 {
+                        let (name, text) = if output.stderr.is_empty() {
+                            ("stdout", &output.stdout)
+                        } else {
+                            ("stderr", &output.stderr)
+                        };
+                        let text = String::from_utf8_lossy(text);
+                        if !text.trim().is_empty() {
+                            let _ = writeln!(details, "    {name}:");
+                            for line in text.trim_end().lines() {
+                                let _ = writeln!(details, "    | {line}");
+                            }
+                        }
+                    }
+                    bad += 1;
+                }
+                None => none += 1,
+            }
+        }
+
+        let mut counts = format!("{ok} passed");
+        if bad > 0 {
+            let _ = write!(counts, ", {bad} failed");
+        }
+        if none > 0 {
+            let _ = write!(counts, ", {none} skipped");
+        }
+        let _ = writeln!(out, "{command:<width$}  {counts}");
+        out.push_str(&details);
+
+        passed += ok;
+        failed += bad;
+        skipped += none;
+        start = end;
+    }
+
+    let _ = write!(
+        out,
+        "\n{commands} command(s): {passed} passed, {failed} failed"
+    );
+    if skipped > 0 {
+        let _ = write!(out, ", {skipped} skipped (--fail-fast)");
+    }
+    out.push('\n');
+    (out, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_encoder::{CustomSection, Section};
+
+    #[test]
+    fn split_example_handles_quotes_and_escapes() {
+        assert_eq!(
+            split_example(r#"show "two words" 'it''s' a\ b"#).unwrap(),
+            vec!["show", "two words", "its", "a b"]
+        );
+        assert_eq!(split_example("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(split_example("x ''").unwrap(), vec!["x", ""]);
+        assert!(split_example("show 'open").is_err());
+    }
+
+    #[test]
+    fn command_checks_replace_leading_name_with_group_prefix() {
+        let mut meta = CommandMetadataV1::new(
+            wacli_metadata::CommandMeta {
+                name: "migrate".to_string(),
+                aliases: vec!["up".to_string()],
+                examples: vec!["migrate --steps 2".to_string(), "up --dry-run".to_string()],
+                ..Default::default()
+            },
+            None,
+        );
+        meta.self_tests
+            .push(SelfTest::new("up 'two words'").expect_exit(1));
+        let prefix = vec!["db".to_string(), "migrate".to_string()];
+        let checks = command_checks(&meta, &prefix).unwrap();
+        let argv: Vec<&[String]> = checks.iter().map(|c| c.argv.as_slice()).collect();
+        assert_eq!(
+            argv,
+            vec![
+                vec!["db", "migrate", "--help"],
+                vec!["db", "migrate", "--steps", "2"],
+                vec!["db", "migrate", "--dry-run"],
+                vec!["db", "migrate", "two words"],
+            ]
+        );
+        assert_eq!(checks[3].label, "self-test `up 'two words'`");
+        assert_eq!(checks[3].self_test.as_ref().unwrap().expect_exit, 1);
+    }
+
+    /// A component exporting `wacli:cli/command` that imports `host-net` and
+    /// embeds `metadata` as its command metadata section.
+    fn command_component(metadata: &str) -> Vec<u8> {
+        let mut bytes = wat::parse_str(
+            r#"(component
+                (import "wacli:cli/host-net@2.0.0" (instance (export "connect" (func))))
+                (import "impl" (instance $i (export "meta" (func)) (export "run" (func))))
+                (export "wacli:cli/command@2.0.0" (instance $i)))"#,
+        )
+        .unwrap();
+        CustomSection {
+            name: wacli_metadata::COMMAND_METADATA_SECTION.into(),
+            data: metadata.as_bytes().into(),
+        }
+        .append_to(&mut bytes);
+        bytes
+    }
+
+    fn check(bytes: &[u8]) -> ComponentReport {
+        let path = Path::new("bad.component.wasm");
+        check_command_component(path, bytes, &ScanLimits::default(), false).unwrap()
+    }
+
+    #[test]
+    fn command_component_problems_are_all_listed() {
+        let report = check(&command_component(
+            r#"{"format-version":1,"command-meta":{"name":"greet","aliases":["Hi"]}}
+               {"format-version":1,"command-meta":{"name":"hello","aliases":["greet"]}}"#,
+        ));
+        assert_eq!(report.world, Some("plugin"));
+        assert_eq!(report.commands, ["greet", "hello"]);
+        let problems: Vec<(Option<&str>, &str)> = report
+            .problems
+            .iter()
+            .map(|p| (p.command.as_deref(), p.message.as_str()))
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                (
+                    None,
+                    "imports wacli:cli/host-net@2.0.0, which the plugin world does not include"
+                ),
+                (
+                    None,
+                    "the command metadata section declares 2 commands; \
+                     export them with `wacli_cdk::export_multi!`"
+                ),
+                (Some("greet"), "invalid alias 'Hi'"),
+                (
+                    Some("hello"),
+                    "command-meta.aliases: 'greet' is also the name of command 'greet' \
+                     (bad.component.wasm)"
+                ),
+            ]
+        );
+        let text = render_component_report(&report);
+        assert!(
+            text.starts_with("bad.component.wasm:\n  - imports wacli:cli/host-net@2.0.0"),
+            "{text}"
+        );
+        assert!(text.contains("\n  - greet: invalid alias 'Hi'\n"), "{text}");
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["problems"][2],
+            serde_json::json!({ "command": "greet", "message": "invalid alias 'Hi'" })
+        );
+        assert!(json["problems"][0].get("command").is_none());
+
+        let report = check(&command_component(" "));
+        assert_eq!(report.problems.len(), 2);
+        assert_eq!(
+            report.problems[1].message,
+            "failed to extract command metadata: the command metadata section is empty"
+        );
+        let report = check(&wat::parse_str("(module)").unwrap());
+        assert_eq!(report.world, None);
+        assert!(
+            report.problems[0]
+                .message
+                .starts_with("is a core WebAssembly module")
+        );
+    }
+
+    #[test]
+    fn differing_fields_skip_hidden_aliases() {
+        let embedded = CommandMeta {
+            name: "greet".to_string(),
+            summary: "Greet someone".to_string(),
+            hidden_aliases: vec!["hey".to_string()],
+            ..Default::default()
+        };
+        assert!(differing_fields(&embedded, &embedded).unwrap().is_empty());
+        let called = CommandMeta {
+            name: "greet".to_string(),
+            usage: "greet [NAME]".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            differing_fields(&embedded, &called).unwrap(),
+            ["summary", "usage"]
+        );
+    }
+
+    #[test]
+    fn report_counts_each_failure_once_and_shows_first_detail() {
+        let check = |command: &str, label: &str| Check {
+            command: command.to_string(),
+            label: label.to_string(),
+            argv: Vec::new(),
+            self_test: None,
+        };
+        let fail = |stderr: &str| {
+            Some(Outcome::Fail {
+                reason: "exit code 2".to_string(),
+                output: RunOutput {
+                    code: 2,
+                    stdout: Vec::new(),
+                    stderr: stderr.as_bytes().to_vec(),
+                },
+            })
+        };
+        let checks = vec![
+            check("greet", "--help"),
+            check("probe", "--help"),
+            check("probe", "example `probe --fail`"),
+            check("probe", "example `probe --fail again`"),
+        ];
+        let results = vec![
+            Some(Outcome::Pass),
+            Some(Outcome::Pass),
+            fail("first boom"),
+            fail("second boom"),
+        ];
+        let (report, failed) = render_report(&checks, &results);
+        assert_eq!(failed, 2);
+        assert!(report.contains("greet  1 passed\n"), "{report}");
+        assert!(report.contains("probe  1 passed, 2 failed\n"), "{report}");
+        assert_eq!(report.matches("FAIL example `probe --fail`:").count(), 1);
+        assert!(report.contains("    | first boom\n"), "{report}");
+        assert!(!report.contains("second boom"), "{report}");
+        assert!(
+            report.ends_with("2 command(s): 2 passed, 2 failed\n"),
+            "{report}"
+        );
+    }
+}
@@ -1,19 +1,23 @@
 //! Generate WAC source for component composition.
 
-use crate::component_scan::CommandInfo;
+use crate::component_scan::{CommandInfo, components};
 
 /// Generate WAC source for composing a CLI from discovered commands.
 ///
 /// The generated WAC:
 /// 1. Instantiates the host component (WASI bridge)
 /// 2. Instantiates each command plugin with host dependencies
-/// 3. Instantiates the registry with all commands
+/// 3. Instantiates the registry with all commands (a multi-command component
+///    is passed once, as its `multi-command` export)
 /// 4. Instantiates core with host and registry
 /// 5. Exports the CLI entry point (wasi:cli/run)
 pub fn generate_wac(package_name: &str, commands: &[CommandInfo]) -> String {
     let mut wac = String::new();
     let host_env_import = "\"wacli:cli/host-env@2.0.0\"";
     let host_io_import = "\"wacli:cli/host-io@2.0.0\"";
+    let host_log_import = "\"wacli:cli/host-log@2.0.0\"";
+    let host_control_import = "\"wacli:cli/host-control@2.0.0\"";
+    let host_fs_import = "\"wacli:cli/host-fs@2.0.0\"";
     let host_process_import = "\"wacli:cli/host-process@2.0.0\"";
     let registry_import = "\"wacli:cli/registry@2.0.0\"";
     let registry_schema_import = "\"wacli:cli/registry-schema@2.0.0\"";
@@ -27,10 +31,10 @@ pub fn generate_wac(package_name: &str, commands: &[CommandInfo]) -> String {
     wac.push_str("// Host component (WASI bridge)\n");
     wac.push_str("let host = new wacli:host { ... };\n\n");
 
-    // Instantiate each command plugin
+    // Instantiate each command plugin (once per multi-command component)
     if !commands.is_empty() {
         wac.push_str("// Command plugins\n");
-        for cmd in commands {
+        for cmd in components(commands) {
             let var_name = cmd.var_name();
             let pkg_name = cmd.package_name();
             let cmd_types_import = cmd.import_name("types");
@@ -39,8 +43,14 @@ pub fn generate_wac(package_name: &str, commands: &[CommandInfo]) -> String {
             let cmd_host_fs_import = cmd.import_name("host-fs");
             let cmd_host_process_import = cmd.import_name("host-process");
             let cmd_host_pipes_import = cmd.import_name("host-pipes");
+            // Commands built before host-log existed do not import it.
+            let cmd_host_log_arg = if cmd.imports_interface("host-log") {
+                format!("  \"{}\": host.host-log,\n", cmd.import_name("host-log"))
+            } else {
+                String::new()
+            };
             wac.push_str(&format!(
-                "let {var_name} = new {pkg_name} {{\n  \"{cmd_types_import}\": host.types,\n  \"{cmd_host_env_import}\": host.host-env,\n  \"{cmd_host_io_import}\": host.host-io,\n  \"{cmd_host_fs_import}\": host.host-fs,\n  \"{cmd_host_process_import}\": host.host-process,\n  \"{cmd_host_pipes_import}\": host.host-pipes,\n  ...\n}};\n\n",
+                "let {var_name} = new {pkg_name} {{\n  \"{cmd_types_import}\": host.types,\n  \"{cmd_host_env_import}\": host.host-env,\n  \"{cmd_host_io_import}\": host.host-io,\n{cmd_host_log_arg}  \"{cmd_host_fs_import}\": host.host-fs,\n  \"{cmd_host_process_import}\": host.host-process,\n  \"{cmd_host_pipes_import}\": host.host-pipes,\n  ...\n}};\n\n",
             ));
         }
     }
@@ -50,9 +60,17 @@ pub fn generate_wac(package_name: &str, commands: &[CommandInfo]) -> String {
     wac.push_str("let registry = new wacli:registry {\n");
     wac.push_str(&format!("  {types_import}: host.types,\n"));
     wac.push_str(&format!("  {schema_import}: host.schema"));
-    for cmd in commands {
-        let var_name = cmd.var_name();
-        wac.push_str(&format!(",\n  {}-command: {}.command", cmd.name, var_name));
+    for cmd in components(commands) {
+        let export = if cmd.bundle.is_some() {
+            "multi-command"
+        } else {
+            "command"
+        };
+        wac.push_str(&format!(
+            ",\n  {}: {}.{export}",
+            cmd.registry_import(),
+            cmd.var_name()
+        ));
     }
     wac.push_str("\n};\n\n");
 
@@ -63,6 +81,10 @@ pub fn generate_wac(package_name: &str, commands: &[CommandInfo]) -> String {
     wac.push_str(&format!("  {schema_import}: host.schema,\n"));
     wac.push_str(&format!("  {host_env_import}: host.host-env,\n"));
     wac.push_str(&format!("  {host_io_import}: host.host-io,\n"));
+    wac.push_str(&format!("  {host_log_import}: host.host-log,\n"));
+    // Commands never get host-control: only core changes run settings.
+    wac.push_str(&format!("  {host_control_import}: host.host-control,\n"));
+    wac.push_str(&format!("  {host_fs_import}: host.host-fs,\n"));
     wac.push_str(&format!("  {host_process_import}: host.host-process,\n"));
     wac.push_str(&format!("  {registry_import}: registry.registry,\n"));
     wac.push_str(&format!(
@@ -90,6 +112,8 @@ mod tests {
                 ..Default::default()
             },
             command_schema: None,
+            self_tests: Vec::new(),
+            cdk_version: None,
         }
     }
 
@@ -101,6 +125,7 @@ mod tests {
         assert!(wac.contains("let registry = new wacli:registry"));
         assert!(wac.contains("\"wacli:cli/schema@2.0.0\": host.schema"));
         assert!(wac.contains("let core = new wacli:core"));
+        assert!(wac.contains("\"wacli:cli/host-fs@2.0.0\": host.host-fs,"));
         assert!(wac.contains("\"wacli:cli/registry-schema@2.0.0\": registry.registry-schema"));
         assert!(wac.contains("export core.run;"));
     }
@@ -113,12 +138,16 @@ mod tests {
                 path: PathBuf::from("commands/greet.component.wasm"),
                 imports: Vec::new(),
                 metadata: dummy_meta("greet"),
+                group: None,
+                bundle: None,
             },
             CommandInfo {
                 name: "hello-world".to_string(),
                 path: PathBuf::from("commands/hello-world.component.wasm"),
                 imports: Vec::new(),
                 metadata: dummy_meta("hello-world"),
+                group: None,
+                bundle: None,
             },
         ];
 
@@ -126,12 +155,118 @@ mod tests {
 
         assert!(wac.contains("package example:hello-cli;"));
         assert!(wac.contains("let greet = new wacli:cmd-greet"));
-        assert!(wac.contains("let hello_world = new wacli:cmd-hello-world"));
+        assert!(wac.contains("let hello-world = new wacli:cmd-hello-world"));
         assert!(wac.contains("greet-command: greet.command"));
-        assert!(wac.contains("hello-world-command: hello_world.command"));
+        assert!(wac.contains("hello-world-command: hello-world.command"));
         assert!(wac.contains("export core.run;"));
     }
 
+    #[test]
+    fn test_generate_wac_wires_host_log_only_where_imported() {
+        let command = |name: &str, imports: &[&str]| CommandInfo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("commands/{name}.component.wasm")),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            metadata: dummy_meta(name),
+            group: None,
+            bundle: None,
+        };
+        let commands = vec![
+            command(
+                "logs",
+                &["wacli:cli/host-io@2.0.0", "wacli:cli/host-log@2.0.0"],
+            ),
+            command("quiet", &["wacli:cli/host-io@2.0.0"]),
+        ];
+
+        let wac = generate_wac("example:log-cli", &commands);
+
+        let logs = &wac[wac.find("let logs =").unwrap()..wac.find("let quiet =").unwrap()];
+        assert!(logs.contains("\"wacli:cli/host-log@2.0.0\": host.host-log,"));
+        let quiet = &wac[wac.find("let quiet =").unwrap()..wac.find("let registry").unwrap()];
+        assert!(!quiet.contains("host-log"), "{quiet}");
+        let core = &wac[wac.find("let core =").unwrap()..];
+        assert!(core.contains("\"wacli:cli/host-log@2.0.0\": host.host-log,"));
+    }
+
+    #[test]
+    fn test_generate_wac_wires_host_control_only_into_core() {
+        let commands = vec![CommandInfo {
+            name: "greet".to_string(),
+            path: PathBuf::from("commands/greet.component.wasm"),
+            imports: Vec::new(),
+            metadata: dummy_meta("greet"),
+            group: None,
+            bundle: None,
+        }];
+
+        let wac = generate_wac("example:hello-cli", &commands);
+
+        let core = wac.find("let core =").unwrap();
+        assert!(!wac[..core].contains("host-control"), "{wac}");
+        assert!(wac[core..].contains("\"wacli:cli/host-control@2.0.0\": host.host-control,"));
+    }
+
+    #[test]
+    fn test_generate_wac_with_grouped_command() {
+        let commands = vec![CommandInfo {
+            name: "migrate".to_string(),
+            path: PathBuf::from("commands/migrate.component.wasm"),
+            imports: Vec::new(),
+            metadata: dummy_meta("migrate"),
+            group: Some("db".to_string()),
+            bundle: None,
+        }];
+
+        let wac = generate_wac("example:db-cli", &commands);
+
+        assert!(wac.contains("let db-migrate = new wacli:cmd-migrate"));
+        assert!(wac.contains("db-migrate-command: db-migrate.command"));
+    }
+
+    #[test]
+    fn test_generate_wac_instantiates_multi_command_components_once() {
+        let bundled = |name: &str, group: Option<&str>| CommandInfo {
+            name: name.to_string(),
+            path: PathBuf::from("commands/textkit.component.wasm"),
+            imports: Vec::new(),
+            metadata: dummy_meta(name),
+            group: group.map(str::to_string),
+            bundle: Some("textkit".to_string()),
+        };
+        let commands = vec![bundled("shout", None), bundled("whisper", Some("quiet"))];
+
+        let wac = generate_wac("example:kit-cli", &commands);
+
+        assert_eq!(wac.matches("new wacli:cmd-textkit").count(), 1, "{wac}");
+        assert!(wac.contains("let textkit = new wacli:cmd-textkit"));
+        assert!(wac.contains("textkit-multi-command: textkit.multi-command"));
+        assert!(!wac.contains(".command"), "{wac}");
+    }
+
+    #[test]
+    fn test_generated_wac_parses_with_hyphenated_names() {
+        // WAC identifiers are kebab-case (`hello_world` is a syntax error),
+        // so hyphens in command and group names must survive into `let`.
+        let command = |name: &str, group: Option<&str>| CommandInfo {
+            name: name.to_string(),
+            path: PathBuf::from(format!("commands/{name}.component.wasm")),
+            imports: Vec::new(),
+            metadata: dummy_meta(name),
+            group: group.map(str::to_string),
+            bundle: None,
+        };
+        let commands = vec![command("hello-world", None), command("migrate", Some("db"))];
+
+        let wac = generate_wac("example:hello-cli", &commands);
+
+        wac_parser::Document::parse(&wac).unwrap_or_else(|e| panic!("{e}\n{wac}"));
+        assert!(
+            wac_parser::Document::parse("package a:b;\nlet hello_world = new a:c { ... };\n")
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_var_name_conversion() {
         let cmd = CommandInfo {
@@ -139,7 +274,9 @@ mod tests {
             path: PathBuf::from("test.wasm"),
             imports: Vec::new(),
             metadata: dummy_meta("my-command"),
+            group: None,
+            bundle: None,
         };
-        assert_eq!(cmd.var_name(), "my_command");
+        assert_eq!(cmd.var_name(), "my-command");
     }
 }
@@ -1,11 +1,19 @@
 use anyhow::{Context, Result, bail};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use molt_registry_client::{
-    RegistryEndpoint, WasmV1Client, WitRequest, auth_from_env, auth_from_header_line,
+    ClientConfig, InterfacesResponse, OciWasmClient, RegistryEndpoint, SearchResponse,
+    WasmV1Client, WitRequest, auth_from_env, auth_from_header_line,
 };
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use wacli_metadata::CommandMetadataV1;
+
+use crate::command_metadata::extract_command_metadata;
+use crate::component_scan::{WasmKind, analyze_wasm, exports_command_interface};
+use crate::lock::LockedRegistryCommand;
+use crate::scan_limits::ScanLimits;
 
 #[derive(Debug, Parser)]
 pub struct WasmArgs {
@@ -26,6 +34,9 @@ enum WasmCommands {
 
     /// Search components by imports/exports
     Search(SearchArgs),
+
+    /// Push a component to the registry and print its manifest digest
+    Push(PushArgs),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -39,6 +50,21 @@ struct RegistryOpts {
     header: Vec<String>,
 }
 
+/// How index responses are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// The response as pretty JSON (the client structs, unchanged)
+    #[default]
+    Json,
+    /// Aligned columns for reading in a terminal
+    Table,
+    /// One line per item, for shell pipelines
+    Plain,
+}
+
+/// Longest table cell, in characters; longer ones end in `…`.
+const MAX_CELL_WIDTH: usize = 48;
+
 #[derive(Debug, Parser)]
 struct WitArgs {
     #[command(flatten)]
@@ -81,6 +107,10 @@ struct InterfacesArgs {
     /// Tag or manifest digest
     #[arg(value_name = "REFERENCE")]
     reference: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
 }
 
 #[derive(Debug, Parser)]
@@ -95,6 +125,10 @@ struct DependenciesArgs {
     /// Tag or manifest digest
     #[arg(value_name = "REFERENCE")]
     reference: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
 }
 
 #[derive(Debug, Parser)]
@@ -114,13 +148,40 @@ struct SearchArgs {
     #[arg(long, value_name = "OS")]
     os: Option<String>,
 
-    /// Page size (default 50, max 200)
+    /// Page size (default 50, max 200); also applied to the response
     #[arg(long, value_name = "N")]
     limit: Option<u32>,
 
     /// Pagination cursor (base64url JSON token)
     #[arg(long)]
     cursor: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+#[derive(Debug, Parser)]
+struct PushArgs {
+    #[command(flatten)]
+    registry: RegistryOpts,
+
+    /// Component file (*.component.wasm)
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Destination, e.g. example/greet:1.0.0
+    #[arg(value_name = "REPO:TAG")]
+    target: String,
+
+    /// Extra manifest annotation (repeatable); overrides the ones taken from
+    /// the command metadata
+    #[arg(long = "annotation", value_name = "KEY=VALUE")]
+    annotations: Vec<String>,
+
+    /// Pin the pushed digest in ./wacli.lock (as a build.commands entry)
+    #[arg(long)]
+    lock: bool,
 }
 
 pub fn wasm(args: WasmArgs) -> Result<()> {
@@ -129,6 +190,7 @@ pub fn wasm(args: WasmArgs) -> Result<()> {
         WasmCommands::Interfaces(args) => interfaces(args),
         WasmCommands::Dependencies(args) => dependencies(args),
         WasmCommands::Search(args) => search(args),
+        WasmCommands::Push(args) => push(args),
     }
 }
 
@@ -171,10 +233,7 @@ fn interfaces(args: InterfacesArgs) -> Result<()> {
     let rt = runtime()?;
 
     let parsed = rt.block_on(client.interfaces(&args.name, &args.reference))?;
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&parsed).unwrap_or_default()
-    );
+    print!("{}", render_interfaces(&parsed, args.output)?);
     Ok(())
 }
 
@@ -183,10 +242,7 @@ fn dependencies(args: DependenciesArgs) -> Result<()> {
     let rt = runtime()?;
 
     let parsed = rt.block_on(client.dependencies(&args.name, &args.reference))?;
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&parsed).unwrap_or_default()
-    );
+    print!("{}", render_interfaces(&parsed, args.output)?);
     Ok(())
 }
 
@@ -202,14 +258,233 @@ fn search(args: SearchArgs) -> Result<()> {
         cursor: args.cursor,
     };
 
-    let parsed = rt.block_on(client.search(&q))?;
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&parsed).unwrap_or_default()
-    );
+    let mut parsed = rt.block_on(client.search(&q))?;
+    // Servers may ignore (or cap differently) the requested page size.
+    if let Some(limit) = args.limit {
+        parsed.results.truncate(limit as usize);
+    }
+    print!("{}", render_search(&parsed, args.output)?);
+    if args.output != OutputFormat::Json
+        && let Some(cursor) = parsed.next_cursor.as_deref()
+    {
+        eprintln!("More results: --cursor {cursor}");
+    }
+    // Non-zero so scripts can branch on "nothing found".
+    if parsed.results.is_empty() {
+        bail!("no components matched the search");
+    }
+    Ok(())
+}
+
+fn push(args: PushArgs) -> Result<()> {
+    let (repo, tag) = parse_push_target(&args.target)?;
+    let bytes =
+        fs::read(&args.file).with_context(|| format!("failed to read {}", args.file.display()))?;
+    let metadata = push_metadata(&bytes, &args.file)?;
+    if args.lock && metadata.is_none() {
+        bail!(
+            "--lock needs a command component (with embedded command metadata): {}",
+            args.file.display()
+        );
+    }
+
+    let mut annotations = metadata
+        .as_ref()
+        .map(metadata_annotations)
+        .unwrap_or_default();
+    for a in &args.annotations {
+        let (k, v) = a
+            .split_once('=')
+            .with_context(|| format!("invalid --annotation '{a}' (expected KEY=VALUE)"))?;
+        annotations.insert(k.trim().to_string(), v.to_string());
+    }
+
+    let client = oci_client_from_opts(&args.registry)?;
+    let rt = runtime()?;
+    let pushed = rt.block_on(client.push_component_wasm(repo, tag, bytes, annotations))?;
+    eprintln!("Pushed {repo}:{tag}");
+    println!("{}", pushed.manifest_digest);
+
+    if let Some(metadata) = metadata.filter(|_| args.lock) {
+        let cwd = std::env::current_dir().context("failed to get current directory")?;
+        let lock_path = crate::lock::lock_path(&cwd);
+        let mut lock = crate::lock::load_lock(&lock_path)?.unwrap_or_default();
+        lock.set_command(LockedRegistryCommand {
+            name: metadata.command_meta.name,
+            repo: repo.to_string(),
+            reference: tag.to_string(),
+            digest: pushed.manifest_digest,
+            layer_digest: Some(pushed.layer_digest),
+            extra: Default::default(),
+        });
+        crate::lock::write_lock(&lock_path, &mut lock)?;
+        eprintln!("Updated {}", lock_path.display());
+    }
     Ok(())
 }
 
+/// Split `REPO:TAG`; a `:` before the last `/` belongs to a registry port.
+fn parse_push_target(target: &str) -> Result<(&str, &str)> {
+    match target.rsplit_once(':') {
+        Some((repo, tag)) if !repo.is_empty() && !tag.is_empty() && !tag.contains('/') => {
+            Ok((repo, tag))
+        }
+        _ => bail!("invalid push target '{target}' (expected REPO:TAG)"),
+    }
+}
+
+/// Check that `bytes` is a component; returns its command metadata if it
+/// is a command.
+fn push_metadata(bytes: &[u8], path: &Path) -> Result<Option<CommandMetadataV1>> {
+    let limits = ScanLimits::from_env()?;
+    let deadline = limits.deadline();
+    let exports = match analyze_wasm(bytes, &deadline)
+        .map_err(|e| anyhow::anyhow!("{}: {e:#}", path.display()))?
+    {
+        WasmKind::CoreModule => bail!(
+            "'{}' is a core WebAssembly module, not a component",
+            path.display()
+        ),
+        WasmKind::Component { exports, .. } => exports,
+    };
+    if !exports_command_interface(&exports) {
+        return Ok(None);
+    }
+    extract_command_metadata(bytes, &limits, &deadline)
+        .with_context(|| format!("failed to extract command metadata from {}", path.display()))
+}
+
+/// The command summary as standard OCI annotations, plus the aliases.
+fn metadata_annotations(metadata: &CommandMetadataV1) -> BTreeMap<String, String> {
+    let meta = &metadata.command_meta;
+    let mut out = BTreeMap::new();
+    let mut put = |key: &str, value: String| {
+        if !value.is_empty() {
+            out.insert(key.to_string(), value);
+        }
+    };
+    put("org.opencontainers.image.title", meta.name.clone());
+    put("org.opencontainers.image.description", meta.summary.clone());
+    put("org.opencontainers.image.version", meta.version.clone());
+    put("dev.wacli.command.aliases", meta.aliases.join(","));
+    out
+}
+
+fn render_search(resp: &SearchResponse, format: OutputFormat) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Json => to_json(resp)?,
+        OutputFormat::Table if resp.results.is_empty() => String::new(),
+        OutputFormat::Table => {
+            let rows = resp
+                .results
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.repo.clone(),
+                        r.tags.join(", "),
+                        short_digest(&r.digest).to_string(),
+                        r.os.clone(),
+                        r.exports.join(", "),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            render_table(&["REPO", "TAGS", "DIGEST", "OS", "EXPORTS"], &rows)
+        }
+        OutputFormat::Plain => resp
+            .results
+            .iter()
+            .map(|r| format!("{}@{}\n", r.repo, r.digest))
+            .collect(),
+    })
+}
+
+fn render_interfaces(resp: &InterfacesResponse, format: OutputFormat) -> Result<String> {
+    let items = resp
+        .imports
+        .iter()
+        .map(|i| ("import", i))
+        .chain(resp.exports.iter().map(|e| ("export", e)));
+    Ok(match format {
+        OutputFormat::Json => to_json(resp)?,
+        OutputFormat::Table => {
+            let rows = items
+                .map(|(kind, iface)| vec![kind.to_string(), iface.clone()])
+                .collect::<Vec<_>>();
+            format!(
+                "{}:{} ({}, {})\n\n{}",
+                resp.repo,
+                resp.reference,
+                short_digest(&resp.digest),
+                resp.os,
+                render_table(&["KIND", "INTERFACE"], &rows)
+            )
+        }
+        OutputFormat::Plain => items
+            .map(|(kind, iface)| format!("{kind} {iface}\n"))
+            .collect(),
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    let mut out = serde_json::to_string_pretty(value).context("failed to encode response")?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// `sha256:` plus the first 12 hex digits, like `docker images`.
+fn short_digest(digest: &str) -> &str {
+    let end = digest.find(':').map_or(0, |i| i + 1) + 12;
+    digest.get(..end).unwrap_or(digest)
+}
+
+/// Left-aligned columns sized to their widest cell (after truncation), two
+/// spaces apart. The last column is not padded.
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| truncate_cell(cell))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut widths = headers
+        .iter()
+        .map(|h| h.chars().count())
+        .collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let headers = headers.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+    for row in std::iter::once(&headers).chain(&rows) {
+        let last = row.len().saturating_sub(1);
+        for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            if i == last {
+                out.push_str(cell);
+            } else {
+                let pad = width - cell.chars().count();
+                out.push_str(cell);
+                out.push_str(&" ".repeat(pad + 2));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn truncate_cell(cell: &str) -> String {
+    if cell.chars().count() <= MAX_CELL_WIDTH {
+        return cell.to_string();
+    }
+    let mut out = cell.chars().take(MAX_CELL_WIDTH - 1).collect::<String>();
+    out.push('…');
+    out
+}
+
 fn runtime() -> Result<tokio::runtime::Runtime> {
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -244,10 +519,31 @@ fn client_from_opts(opts: &RegistryOpts) -> Result<WasmV1Client> {
 
     // Extra headers: pass through non-Authorization headers.
     let extra_headers = header_map_without_authorization(&header_lines)?;
-    let client = WasmV1Client::new_with_headers(endpoint, auth.clone(), extra_headers)?;
+    let client = WasmV1Client::new_with_headers(endpoint, auth.clone(), extra_headers)?
+        .with_config(ClientConfig::from_env()?)?;
     Ok(client)
 }
 
+/// An OCI client for `opts`. Only `Authorization` headers apply here; without
+/// one, credentials come from the environment like for `wasm` queries.
+fn oci_client_from_opts(opts: &RegistryOpts) -> Result<OciWasmClient> {
+    let base_url = match &opts.registry {
+        Some(u) => u.clone(),
+        None => {
+            std::env::var("MOLT_REGISTRY").context("missing --registry (or set MOLT_REGISTRY)")?
+        }
+    };
+    let endpoint = RegistryEndpoint::parse(&base_url)?;
+    if !header_map_without_authorization(&opts.header)?.is_empty() {
+        bail!("push only supports Authorization headers in --header");
+    }
+    let auth = match last_authorization_header(&opts.header)? {
+        Some(line) => auth_from_header_line(&line)?,
+        None => auth_from_env()?,
+    };
+    OciWasmClient::new(endpoint, auth)?.with_config(ClientConfig::from_env()?)
+}
+
 fn last_authorization_header(lines: &[String]) -> Result<Option<String>> {
     let mut out = None;
     for h in lines {
@@ -279,3 +575,138 @@ fn header_map_without_authorization(lines: &[String]) -> Result<HeaderMap> {
     }
     Ok(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use molt_registry_client::SearchResult;
+
+    fn search_response(repos: &[&str]) -> SearchResponse {
+        SearchResponse {
+            results: repos
+                .iter()
+                .map(|repo| SearchResult {
+                    repo: repo.to_string(),
+                    digest: format!("sha256:{}", "ab".repeat(32)),
+                    tags: vec!["1.0.0".to_string(), "latest".to_string()],
+                    os: "wasip2".to_string(),
+                    imports: vec!["wasi:cli/environment@0.2.9".to_string()],
+                    exports: vec!["wacli:cli/command@2.0.0".to_string()],
+                    updated_at: 1_700_000_000,
+                })
+                .collect(),
+            next_cursor: Some("next".to_string()),
+        }
+    }
+
+    #[test]
+    fn search_renders_table_and_plain_output() {
+        let resp = search_response(&["example/greet", "acme/a-much-longer-repo"]);
+        assert_eq!(
+            render_search(&resp, OutputFormat::Table).unwrap(),
+            "REPO                     TAGS           DIGEST               OS      EXPORTS\n\
+             example/greet            1.0.0, latest  sha256:abababababab  wasip2  wacli:cli/command@2.0.0\n\
+             acme/a-much-longer-repo  1.0.0, latest  sha256:abababababab  wasip2  wacli:cli/command@2.0.0\n"
+        );
+        assert_eq!(
+            render_search(&resp, OutputFormat::Plain).unwrap(),
+            format!(
+                "example/greet@sha256:{0}\nacme/a-much-longer-repo@sha256:{0}\n",
+                "ab".repeat(32)
+            )
+        );
+        assert_eq!(
+            render_search(&search_response(&[]), OutputFormat::Table).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn search_json_round_trips_the_client_structs() {
+        let resp = search_response(&["example/greet"]);
+        let json = render_search(&resp, OutputFormat::Json).unwrap();
+        let parsed: SearchResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(to_json(&parsed).unwrap(), json);
+        assert!(json.contains("\"nextCursor\": \"next\""), "{json}");
+    }
+
+    #[test]
+    fn interfaces_render_imports_then_exports() {
+        let resp = InterfacesResponse {
+            repo: "example/greet".to_string(),
+            reference: "1.0.0".to_string(),
+            digest: "sha256:0123456789abcdef".to_string(),
+            os: "wasip2".to_string(),
+            imports: vec!["wasi:cli/environment@0.2.9".to_string()],
+            exports: vec!["wacli:cli/command@2.0.0".to_string()],
+            target: None,
+            subject_digest: None,
+            referrers: Vec::new(),
+        };
+        assert_eq!(
+            render_interfaces(&resp, OutputFormat::Table).unwrap(),
+            "example/greet:1.0.0 (sha256:0123456789ab, wasip2)\n\n\
+             KIND    INTERFACE\n\
+             import  wasi:cli/environment@0.2.9\n\
+             export  wacli:cli/command@2.0.0\n"
+        );
+        assert_eq!(
+            render_interfaces(&resp, OutputFormat::Plain).unwrap(),
+            "import wasi:cli/environment@0.2.9\nexport wacli:cli/command@2.0.0\n"
+        );
+    }
+
+    #[test]
+    fn push_targets_split_at_the_tag() {
+        assert_eq!(
+            parse_push_target("example/greet:1.0.0").unwrap(),
+            ("example/greet", "1.0.0")
+        );
+        assert_eq!(
+            parse_push_target("localhost:5000/greet:dev").unwrap(),
+            ("localhost:5000/greet", "dev")
+        );
+        for bad in ["example/greet", "localhost:5000/greet", ":1.0.0", "greet:"] {
+            assert!(parse_push_target(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn push_annotations_summarize_the_command() {
+        let metadata = CommandMetadataV1 {
+            format_version: 1,
+            command_meta: wacli_metadata::CommandMeta {
+                name: "greet".to_string(),
+                summary: "Greet someone".to_string(),
+                aliases: vec!["hi".to_string(), "hello".to_string()],
+                ..Default::default()
+            },
+            command_schema: None,
+            self_tests: Vec::new(),
+            cdk_version: None,
+        };
+        let annotations = metadata_annotations(&metadata);
+        assert_eq!(
+            annotations.into_iter().collect::<Vec<_>>(),
+            [
+                ("dev.wacli.command.aliases", "hi,hello"),
+                ("org.opencontainers.image.description", "Greet someone"),
+                ("org.opencontainers.image.title", "greet"),
+            ]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+        );
+    }
+
+    #[test]
+    fn long_cells_are_truncated_with_an_ellipsis() {
+        let long = "x".repeat(60);
+        let table = render_table(&["A", "B"], &[vec![long.clone(), "b".to_string()]]);
+        let cell = format!("{}…", "x".repeat(MAX_CELL_WIDTH - 1));
+        assert_eq!(
+            table,
+            format!("A{}B\n{cell}  b\n", " ".repeat(MAX_CELL_WIDTH + 1))
+        );
+        assert_eq!(truncate_cell("short"), "short");
+        assert_eq!(short_digest("sha256:ab"), "sha256:ab");
+    }
+}
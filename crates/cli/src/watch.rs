@@ -0,0 +1,257 @@
+//! `wacli build --watch`: rebuild whenever the build inputs change.
+//!
+//! Inputs are polled rather than watched with OS notifications: the manifest
+//! file, every file under the defaults and commands directories and any local
+//! `build.commands` paths, compared by mtime and size. A change is built once
+//! the inputs have been quiet for [`DEBOUNCE`], so a plugin written in several
+//! steps triggers one build.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// How often the inputs are polled for changes.
+const POLL: Duration = Duration::from_millis(300);
+
+/// How long the inputs must stay unchanged before a rebuild starts.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Paths a build read its inputs from, filled in by the build as it resolves
+/// them. Missing paths are watched too, so creating them triggers a build.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WatchedInputs {
+    pub paths: Vec<PathBuf>,
+}
+
+impl WatchedInputs {
+    pub fn watch(&mut self, path: &Path) {
+        if !self.paths.iter().any(|p| p == path) {
+            self.paths.push(path.to_path_buf());
+        }
+    }
+}
+
+/// `(mtime, size)` of every file under the watched paths.
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+fn snapshot(inputs: &WatchedInputs) -> Snapshot {
+    let mut out = Snapshot::new();
+    for path in &inputs.paths {
+        collect(path, &mut out);
+    }
+    out
+}
+
+fn collect(path: &Path, out: &mut Snapshot) {
+    let Ok(meta) = fs::metadata(path) else {
+        return;
+    };
+    if meta.is_file() {
+        out.insert(path.to_path_buf(), (meta.modified().ok(), meta.len()));
+        return;
+    }
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            collect(&entry.path(), out);
+        }
+    }
+}
+
+/// Files that were added, removed or modified between two snapshots.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let removed = before.keys().filter(|p| !after.contains_key(*p));
+    let added_or_modified = after
+        .iter()
+        .filter(|(p, stamp)| before.get(*p) != Some(stamp))
+        .map(|(p, _)| p);
+    let mut changed: Vec<PathBuf> = removed.chain(added_or_modified).cloned().collect();
+    changed.sort();
+    changed
+}
+
+/// Run `build` once, then again after every change to its inputs, until
+/// Ctrl-C. Build errors are reported and the watch goes on.
+pub fn watch<F>(manifest_path: PathBuf, mut build: F) -> Result<()>
+where
+    F: FnMut(&mut WatchedInputs) -> Result<()>,
+{
+    let stop = stop_on_ctrl_c()?;
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+
+    let mut inputs = WatchedInputs::default();
+    inputs.watch(&manifest_path);
+    let mut seen = snapshot(&inputs);
+    let mut changed = Vec::new();
+
+    for round in 1.. {
+        let header = match changed.as_slice() {
+            [] => format!("build #{round}"),
+            files => format!("build #{round}: {}", describe_changes(files, &cwd)),
+        };
+        eprintln!("── {header} ──");
+        let watched = inputs.clone();
+        if let Err(err) = build(&mut inputs) {
+            crate::report_error(err);
+        }
+        if inputs != watched {
+            seen = snapshot(&inputs);
+        }
+        eprintln!("Watching for changes (Ctrl-C to stop)...");
+
+        match wait_for_change(&inputs, &seen, &stop) {
+            Some((files, current)) => {
+                changed = files;
+                seen = current;
+                eprintln!();
+            }
+            None => break,
+        }
+    }
+    eprintln!("Stopped watching.");
+    Ok(())
+}
+
+/// Poll until the inputs differ from `seen` and then stay quiet for
+/// [`DEBOUNCE`]. Returns `None` once `stop` is set.
+fn wait_for_change(
+    inputs: &WatchedInputs,
+    seen: &Snapshot,
+    stop: &AtomicBool,
+) -> Option<(Vec<PathBuf>, Snapshot)> {
+    let mut current = loop {
+        std::thread::sleep(POLL);
+        if stop.load(Ordering::SeqCst) {
+            return None;
+        }
+        let current = snapshot(inputs);
+        if current != *seen {
+            break current;
+        }
+    };
+    loop {
+        std::thread::sleep(DEBOUNCE);
+        if stop.load(Ordering::SeqCst) {
+            return None;
+        }
+        let next = snapshot(inputs);
+        if next == current {
+            break;
+        }
+        current = next;
+    }
+    let changed = changed_files(seen, &current);
+    Some((changed, current))
+}
+
+fn describe_changes(files: &[PathBuf], cwd: &Path) -> String {
+    const SHOWN: usize = 3;
+    let mut names: Vec<String> = files
+        .iter()
+        .take(SHOWN)
+        .map(|p| p.strip_prefix(cwd).unwrap_or(p).display().to_string())
+        .collect();
+    if files.len() > SHOWN {
+        names.push(format!("and {} more", files.len() - SHOWN));
+    }
+    format!("changed {}", names.join(", "))
+}
+
+/// Set a flag on the first Ctrl-C so the watch loop can stop between builds
+/// (a running build is finished first). A second Ctrl-C exits immediately.
+fn stop_on_ctrl_c() -> Result<Arc<AtomicBool>> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .context("failed to create signal runtime")?;
+    let flag = stop.clone();
+    std::thread::spawn(move || {
+        rt.block_on(async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            flag.store(true, Ordering::SeqCst);
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(130);
+            }
+        });
+    });
+    Ok(stop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wacli-watch-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn snapshots_see_new_nested_files_and_missing_inputs() {
+        let dir = temp_dir("snapshot");
+        let commands = dir.join("commands");
+        let mut inputs = WatchedInputs::default();
+        inputs.watch(&dir.join("wacli.json"));
+        inputs.watch(&commands);
+        inputs.watch(&commands);
+        assert_eq!(inputs.paths.len(), 2);
+
+        let empty = snapshot(&inputs);
+        assert!(empty.is_empty());
+
+        fs::create_dir_all(commands.join("db")).unwrap();
+        fs::write(commands.join("db/migrate.component.wasm"), b"v1").unwrap();
+        fs::write(dir.join("wacli.json"), b"{}").unwrap();
+        let after = snapshot(&inputs);
+        assert_eq!(
+            changed_files(&empty, &after),
+            [
+                commands.join("db/migrate.component.wasm"),
+                dir.join("wacli.json")
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn changes_cover_modified_and_removed_files() {
+        let dir = temp_dir("changes");
+        let a = dir.join("a.component.wasm");
+        let b = dir.join("b.component.wasm");
+        fs::write(&a, b"a").unwrap();
+        fs::write(&b, b"b").unwrap();
+        let mut inputs = WatchedInputs::default();
+        inputs.watch(&dir);
+        let before = snapshot(&inputs);
+        assert!(changed_files(&before, &snapshot(&inputs)).is_empty());
+
+        fs::write(&a, b"longer").unwrap();
+        fs::remove_file(&b).unwrap();
+        assert_eq!(changed_files(&before, &snapshot(&inputs)), [a, b]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn change_descriptions_are_relative_and_truncated() {
+        let cwd = Path::new("/p");
+        let files: Vec<PathBuf> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|n| cwd.join("commands").join(n))
+            .collect();
+        assert_eq!(describe_changes(&files[..1], cwd), "changed commands/a");
+        assert_eq!(
+            describe_changes(&files, cwd),
+            "changed commands/a, commands/b, commands/c, and 2 more"
+        );
+    }
+}
@@ -26,6 +26,12 @@ interface types {
     args: list<arg-def>,
   }
 
+  /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+  /// the same case count, so older plugins would stop composing). Until a
+  /// new major version adds an exit-code case, a `failed` message of the
+  /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+  /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+  /// `unknown-command` with 127 and `failed` / `io` with 1.
   variant command-error {
     unknown-command(string),
     invalid-args(string),
@@ -54,6 +60,15 @@ interface types {
     summary: string,
     path: string,
   }
+
+  /// A discovered pipe with the descriptive fields it embeds in its
+  /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+  record pipe-details {
+    info: pipe-info,
+    input-types: list<string>,
+    output-type: string,
+    version: string,
+  }
 }
 "#;
 
@@ -62,6 +77,13 @@ pub const HOST_ENV_WIT: &str = r#"package wacli:cli@2.0.0;
 interface host-env {
   args: func() -> list<string>;
   env: func() -> list<tuple<string, string>>;
+  /// The value of the environment variable `key` (case-sensitive).
+  get: func(key: string) -> option<string>;
+  /// The working directory relative paths resolve against (`.` when the
+  /// runtime reports none).
+  cwd: func() -> string;
+  /// Monotonic clock reading in nanoseconds, for measuring durations only.
+  monotonic-now: func() -> u64;
 }
 "#;
 
@@ -72,16 +94,112 @@ interface host-io {
   stderr-write: func(bytes: list<u8>);
   stdout-flush: func();
   stderr-flush: func();
+  /// Whether stdout is attached to a terminal (false when piped or captured).
+  stdout-is-terminal: func() -> bool;
+  /// Whether stderr is attached to a terminal (false when piped or captured).
+  stderr-is-terminal: func() -> bool;
+  /// Whether stdin is attached to a terminal (false when piped or empty).
+  stdin-is-terminal: func() -> bool;
+  /// Read one line from stdin, without its line ending; `none` at end of
+  /// input, `err` when the read fails.
+  stdin-read-line: func() -> result<option<string>, string>;
+  /// Write `prompt` to stderr and read one line from stdin like
+  /// `stdin-read-line`, without echoing it when stdin is a terminal (a plain
+  /// read otherwise). End of input is an `err`.
+  read-secret: func(prompt: string) -> result<string, string>;
+  /// Read up to `len` bytes from stdin; empty at end of input, `err` when
+  /// the read fails.
+  stdin-read: func(len: u64) -> result<list<u8>, string>;
+  /// Read the rest of stdin; empty at end of input, `err` when the read
+  /// fails.
+  stdin-read-all: func() -> result<list<u8>, string>;
+}
+"#;
+
+pub const HOST_LOG_WIT: &str = r#"package wacli:cli@2.0.0;
+
+interface host-log {
+  /// Message severity, least severe first.
+  enum level {
+    trace,
+    debug,
+    info,
+    warn,
+    error,
+  }
+
+  /// Write `message` to stderr as `[LEVEL target] message`, unless `level`
+  /// is below the max level (then nothing is formatted or written).
+  log: func(level: level, target: string, message: string);
+  /// Whether a message at `level` would be written; check it before
+  /// building an expensive message. The max level is `warn` unless core
+  /// changes it (see `host-control`).
+  enabled: func(level: level) -> bool;
 }
 "#;
 
 pub const HOST_FS_WIT: &str = r#"package wacli:cli@2.0.0;
 
 interface host-fs {
+  enum file-kind {
+    file,
+    dir,
+    symlink,
+    other,
+  }
+
+  record file-info {
+    kind: file-kind,
+    size: u64,
+    /// Last modification time in unix milliseconds (0 if unknown).
+    modified-at: u64,
+  }
+
+  record dir-entry {
+    /// Path relative to the walked directory, `/`-separated.
+    path: string,
+    kind: file-kind,
+  }
+
   read-file: func(path: string) -> result<list<u8>, string>;
   write-file: func(path: string, contents: list<u8>) -> result<_, string>;
   create-dir: func(path: string) -> result<_, string>;
   list-dir: func(path: string) -> result<list<string>, string>;
+  /// Copy `src` to `dst` (created or truncated), streaming in chunks so large
+  /// files are never held in memory. Returns the number of bytes copied.
+  copy-file: func(src: string, dst: string) -> result<u64, string>;
+  /// Move `src` to `dst`, replacing a file (or empty directory) already
+  /// there. Both must be under the same preopened directory; across mounts
+  /// it fails, and `copy-file` then `remove-file` has to be used instead.
+  rename: func(src: string, dst: string) -> result<_, string>;
+  /// Remove a file (or symlink).
+  remove-file: func(path: string) -> result<_, string>;
+  /// Remove an empty directory.
+  remove-dir: func(path: string) -> result<_, string>;
+  /// Remove a directory and everything in it. Symlinks are removed, not followed.
+  remove-dir-all: func(path: string) -> result<_, string>;
+  /// Metadata of `path` itself: a symlink is reported as `symlink`, not
+  /// as what it points at.
+  stat: func(path: string) -> result<file-info, string>;
+  /// Whether `path` exists (following symlinks). Never fails.
+  exists: func(path: string) -> bool;
+  /// Everything below `path`, depth-first with each directory's entries
+  /// sorted by name. `max-depth` 1 lists direct children only; `none` has no
+  /// limit. Symlinks are listed but never followed.
+  walk-dir: func(path: string, max-depth: option<u32>) -> result<list<dir-entry>, string>;
+  /// Append `contents` to `path`, creating it if missing.
+  append-file: func(path: string, contents: list<u8>) -> result<_, string>;
+  /// Open `path` for incremental writes, creating it if missing. Without
+  /// `append` it is truncated first.
+  open-writer: func(path: string, append: bool) -> result<writer, string>;
+
+  /// A file kept open across writes (see `open-writer`).
+  resource writer {
+    /// Write all of `bytes` after what was written so far.
+    write: func(bytes: list<u8>) -> result<_, string>;
+    /// Flush and close. Writing afterwards fails; closing again does nothing.
+    close: func() -> result<_, string>;
+  }
 }
 "#;
 
@@ -97,14 +215,40 @@ interface host-process {
 pub const HOST_PIPES_WIT: &str = r#"package wacli:cli@2.0.0;
 
 interface host-pipes {
-  use types.{pipe-meta, pipe-error, pipe-info};
+  use types.{pipe-meta, pipe-error, pipe-info, pipe-details};
 
   list-pipes: func() -> list<pipe-info>;
+  /// Like `list-pipes`, with each pipe's types and version. Read from the
+  /// pipes' metadata sections without instantiating them.
+  describe-pipes: func() -> list<pipe-details>;
+  /// Load a pipe by name. The error is the message of the `pipe-load-error`
+  /// that `open-pipe` returns; its type stays `string` so components built
+  /// against earlier `wacli:cli@2.0.0` releases still compose.
   load-pipe: func(name: string) -> result<pipe, string>;
+  /// Like `load-pipe`, saying why loading failed. Added within
+  /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+  /// import it.
+  open-pipe: func(name: string) -> result<pipe, pipe-load-error>;
+
+  /// Why `open-pipe` failed.
+  variant pipe-load-error {
+    /// No pipe by that name (a typo, or not installed).
+    not-found(string),
+    /// The name itself is malformed.
+    invalid-name(string),
+    /// The pipe exists but could not be read or instantiated.
+    load-failed(string),
+  }
 
   resource pipe {
     meta: func() -> pipe-meta;
     process: func(input: list<u8>, options: list<string>) -> result<list<u8>, pipe-error>;
+    /// Start a chunked run (see `pipe-stream`): feed the input to
+    /// `process-chunk` and finish with `process-end`, collecting the output
+    /// each returns. The options are checked like those of `process`.
+    process-start: func(options: list<string>) -> result<u32, pipe-error>;
+    process-chunk: func(session: u32, bytes: list<u8>) -> result<list<u8>, pipe-error>;
+    process-end: func(session: u32) -> result<list<u8>, pipe-error>;
   }
 }
 "#;
@@ -112,14 +256,40 @@ interface host-pipes {
 pub const PIPE_RUNTIME_WIT: &str = r#"package wacli:cli@2.0.0;
 
 interface pipe-runtime {
-  use types.{pipe-meta, pipe-error, pipe-info};
+  use types.{pipe-meta, pipe-error, pipe-info, pipe-details};
 
   list-pipes: func() -> list<pipe-info>;
+  /// Like `list-pipes`, with each pipe's types and version. Read from the
+  /// pipes' metadata sections without instantiating them.
+  describe-pipes: func() -> list<pipe-details>;
+  /// Load a pipe by name. The error is the message of the `pipe-load-error`
+  /// that `open-pipe` returns; its type stays `string` so components built
+  /// against earlier `wacli:cli@2.0.0` releases still compose.
   load-pipe: func(name: string) -> result<pipe, string>;
+  /// Like `load-pipe`, saying why loading failed. Added within
+  /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+  /// import it.
+  open-pipe: func(name: string) -> result<pipe, pipe-load-error>;
+
+  /// Why `open-pipe` failed.
+  variant pipe-load-error {
+    /// No pipe by that name (a typo, or not installed).
+    not-found(string),
+    /// The name itself is malformed.
+    invalid-name(string),
+    /// The pipe exists but could not be read or instantiated.
+    load-failed(string),
+  }
 
   resource pipe {
     meta: func() -> pipe-meta;
     process: func(input: list<u8>, options: list<string>) -> result<list<u8>, pipe-error>;
+    /// Start a chunked run (see `pipe-stream`): feed the input to
+    /// `process-chunk` and finish with `process-end`, collecting the output
+    /// each returns. The options are checked like those of `process`.
+    process-start: func(options: list<string>) -> result<u32, pipe-error>;
+    process-chunk: func(session: u32, bytes: list<u8>) -> result<list<u8>, pipe-error>;
+    process-end: func(session: u32) -> result<list<u8>, pipe-error>;
   }
 }
 
@@ -143,6 +313,7 @@ world plugin {
   ///   wacli:cli/host-<name>@2.0.0
   import host-env;
   import host-io;
+  import host-log;
   import host-fs;
   import host-process;
   import host-pipes;
@@ -151,6 +322,30 @@ world plugin {
 }
 "#;
 
+/// `wit/multi`: `multi-command`, published as `wacli:cli@2.1.0` so that the
+/// 2.0.0 package above stays as released.
+pub const MULTI_COMMAND_WIT: &str = r#"package wacli:cli@2.1.0;
+
+/// Several commands in one component, so they share one copy of the CDK.
+///
+/// `command` stays exported (as the first command), since every CDK
+/// component links the `plugin` world.
+interface multi-command {
+  use wacli:cli/types@2.0.0.{command-meta, command-result};
+
+  /// `meta()` of every command, in declaration order.
+  list-commands: func() -> list<command-meta>;
+  /// Run the command whose `meta.name` is `name`.
+  run-named: func(name: string, argv: list<string>) -> command-result;
+}
+
+world multi-plugin {
+  include wacli:cli/plugin@2.0.0;
+
+  export multi-command;
+}
+"#;
+
 pub const PIPE_WIT: &str = r#"package wacli:cli@2.0.0;
 
 interface pipe {
@@ -202,6 +397,32 @@ interface schema {
     conflicts-with: list<string>,
     requires: list<string>,
     hidden: bool,
+    /// Registry command that prints completion candidates for this arg's
+    /// value, one per line; it receives the word being completed as its
+    /// only argument (`my-cli __complete`).
+    complete-command: option<string>,
+    /// Name of the `arg-group` this arg belongs to.
+    group: option<string>,
+    /// The value may start with `-`: the next token is always taken as the
+    /// value, even when it names a flag.
+    allow-hyphen-values: bool,
+    /// A boolean flag that counts its occurrences (`-vvv`); help shows it as
+    /// `-v...` and it may repeat even when not `multiple`.
+    countable: bool,
+    /// On the last positional: take every remaining positional token
+    /// (`[FILES]...`) instead of leaving extras to the plugin.
+    var-arg: bool,
+  }
+
+  /// A named set of args validated together, e.g. exactly one of
+  /// `--json`/`--yaml`/`--toml`. A group args name without the command
+  /// declaring it is neither required nor multiple.
+  record arg-group {
+    name: string,
+    /// At least one member must be given in argv.
+    required: bool,
+    /// More than one member may be given.
+    multiple: bool,
   }
 
   record command-schema {
@@ -214,6 +435,31 @@ interface schema {
     description: string,
     examples: list<string>,
     args: list<arg-schema>,
+    /// Expand `@path` tokens in argv into the lines of `path` before parsing.
+    allow-arg-files: bool,
+    /// Nested commands, flattened depth-first (records cannot be recursive).
+    subcommands: list<subcommand-schema>,
+    groups: list<arg-group>,
+    /// Aliases that dispatch like `aliases` but are not shown in help.
+    hidden-aliases: list<string>,
+  }
+
+  /// A subcommand of a `command-schema`, selected by the first positional
+  /// token in argv.
+  record subcommand-schema {
+    /// Names from the first-level subcommand down, e.g. `["remote", "add"]`.
+    path: list<string>,
+    summary: string,
+    usage: string,
+    aliases: list<string>,
+    /// Empty to inherit the parent's version.
+    version: string,
+    hidden: bool,
+    description: string,
+    examples: list<string>,
+    args: list<arg-schema>,
+    groups: list<arg-group>,
+    hidden-aliases: list<string>,
   }
 }
 "#;
@@ -221,7 +467,19 @@ interface schema {
 pub const REGISTRY_SCHEMA_WIT: &str = r#"package wacli:cli@2.0.0;
 
 interface registry-schema {
-  use schema.{command-schema};
+  use schema.{arg-schema, command-schema};
+
+  /// Build provenance recorded by the builder (wacli).
+  ///
+  /// Rendered by core for `--version --build-info` (or `-V --verbose`).
+  record build-info {
+    /// Version of wacli that generated the registry.
+    wacli-version: string,
+    /// Source revision, only when explicitly provided to `wacli build`.
+    git-revision: option<string>,
+    /// Extra `key: value` build facts (e.g. an opt-in build timestamp).
+    built-with: list<string>,
+  }
 
   /// App-level metadata, provided by the builder (wacli).
   ///
@@ -230,11 +488,42 @@ interface registry-schema {
     name: string,
     version: string,
     description: string,
+    build-info: build-info,
+    /// Hidden command that core runs after each command with a JSON usage
+    /// event (command name, exit code, duration; never arguments). Users opt
+    /// out with `<APP>_NO_TELEMETRY`.
+    telemetry-command: option<string>,
+    /// Command core runs for an unknown command `foo`, with `foo` and the
+    /// rest of argv as its arguments. When it reports `foo` as unknown too,
+    /// core prints the usual unknown-command error.
+    fallback-command: option<string>,
+    /// Show an interactive command picker instead of the global help when
+    /// the app runs with no arguments on a terminal.
+    interactive-picker: bool,
+    /// Warn on stderr when an arg's environment variable is unset but a
+    /// near-miss of its name (another case, a typo) is set.
+    env-warnings: bool,
+    /// Options every command accepts (`build.globalArgs`). Core takes them
+    /// out of the command's argv and sets `WACLI_GLOBAL_<NAME>` instead.
+    global-args: list<arg-schema>,
+  }
+
+  /// A named group of commands, declared in the build manifest (`build.groups`).
+  ///
+  /// Member commands are listed by `list-schemas` under `<group>/<command>`
+  /// (e.g. `db/migrate`) and invoked as `<group> <command>`.
+  record command-group {
+    name: string,
+    summary: string,
+    description: string,
   }
 
   /// Return app-level metadata for the composed CLI.
   get-app-meta: func() -> app-meta;
 
+  /// Return command groups, in declaration order.
+  list-groups: func() -> list<command-group>;
+
   /// Return schemas for all commands.
   ///
   /// The schema is pure data and must be available without executing the plugin.
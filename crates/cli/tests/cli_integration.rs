@@ -1,9 +1,41 @@
 use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-fn make_temp_dir(prefix: &str) -> PathBuf {
+use sha2::{Digest, Sha256};
+
+/// A temp dir that is removed when dropped, so a failing test cleans up too.
+struct TempDir(PathBuf);
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<std::ffi::OsStr> for TempDir {
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn make_temp_dir(prefix: &str) -> TempDir {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("system clock is before UNIX_EPOCH")
@@ -11,7 +43,7 @@ fn make_temp_dir(prefix: &str) -> PathBuf {
     let pid = std::process::id();
     let dir = std::env::temp_dir().join(format!("wacli-integ-{prefix}-{pid}-{nanos}"));
     fs::create_dir_all(&dir).expect("failed to create temp dir");
-    dir
+    TempDir(dir)
 }
 
 fn repo_root() -> &'static Path {
@@ -72,8 +104,64 @@ fn init_creates_expected_layout() {
         dir.join("wacli.json").is_file(),
         "wacli.json (manifest) not created"
     );
+}
+
+#[test]
+fn init_scaffolds_plugin_crates_from_templates() {
+    let dir = make_temp_dir("init-template");
+    let init = |args: &[&str]| {
+        wacli()
+            .arg("init")
+            .arg(&dir)
+            .args(args)
+            .output()
+            .expect("failed to run wacli init")
+    };
+
+    let out = init(&["--template", "rust-command", "--name", "say-hi"]);
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let crate_dir = dir.join("commands-src/say-hi");
+    let cargo = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap();
+    assert!(cargo.contains("name = \"say-hi\""), "{cargo}");
+    assert!(
+        cargo.contains(&format!("wacli-cdk = \"={}\"", env!("CARGO_PKG_VERSION"))),
+        "{cargo}"
+    );
+    let lib = fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap();
+    assert!(
+        lib.contains("declare_command_metadata!(say_hi_meta"),
+        "{lib}"
+    );
+    assert!(lib.contains("wacli_cdk::export!(SayHi);"), "{lib}");
+    assert!(crate_dir.join(".cargo/config.toml").is_file());
+    assert!(crate_dir.join("Makefile").is_file());
 
-    let _ = fs::remove_dir_all(&dir);
+    // A second run keeps edits unless asked to overwrite.
+    fs::write(crate_dir.join("src/lib.rs"), "// edited").unwrap();
+    let out = init(&["--template", "rust-command", "--name", "say-hi"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--overwrite"));
+    let out = init(&[
+        "--template",
+        "rust-command",
+        "--name",
+        "say-hi",
+        "--overwrite",
+    ]);
+    assert!(out.status.success());
+    assert_ne!(
+        fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap(),
+        "// edited"
+    );
+
+    let out = init(&["--template", "pipe"]);
+    assert!(out.status.success());
+    let lib = fs::read_to_string(dir.join("pipes-src/case/src/lib.rs")).unwrap();
+    assert!(lib.contains("wacli_cdk::export_pipe!(Case);"), "{lib}");
 }
 
 #[test]
@@ -148,6 +236,3223 @@ fn build_composes_component_from_fixture_plugin() {
         wasmparser::Encoding::Component,
         "output is not a wasm component"
     );
+}
+
+/// Create a project with framework components and the `greet` fixture plugin.
+fn make_fixture_project(prefix: &str) -> TempDir {
+    let dir = make_temp_dir(prefix);
+    let defaults_dir = dir.join("defaults");
+    let commands_dir = dir.join("commands");
+    fs::create_dir_all(&defaults_dir).expect("failed to create defaults dir");
+    fs::create_dir_all(&commands_dir).expect("failed to create commands dir");
+    for name in ["host.component.wasm", "core.component.wasm"] {
+        fs::copy(
+            repo_root().join("components").join(name),
+            defaults_dir.join(name),
+        )
+        .unwrap_or_else(|e| panic!("failed to copy {name}: {e}"));
+    }
+    fs::copy(
+        repo_root().join("testdata/greet.component.wasm"),
+        commands_dir.join("greet.component.wasm"),
+    )
+    .expect("failed to copy greet.component.wasm fixture");
+    dir
+}
+
+/// Build the fixture project with extra `wacli build` args and env vars.
+fn build_fixture_cli(dir: &Path, extra_args: &[&str], envs: &[(&str, &str)]) -> PathBuf {
+    let output_path = dir.join("out.component.wasm");
+    let mut cmd = wacli();
+    cmd.current_dir(dir)
+        .env_remove("WACLI_GIT_REV")
+        .env_remove("GIT_SHA")
+        .env_remove("SOURCE_DATE_EPOCH")
+        .arg("build")
+        .arg("--name")
+        .arg("example:test-cli")
+        .arg("--version")
+        .arg("0.1.0")
+        .arg("--output")
+        .arg(&output_path)
+        .args(extra_args);
+    for (k, v) in envs {
+        cmd.env(k, v);
+    }
+    let out = cmd.output().expect("failed to run wacli build");
+    assert!(
+        out.status.success(),
+        "wacli build failed:\nstatus: {}\nstderr:\n{}",
+        out.status,
+        String::from_utf8_lossy(&out.stderr),
+    );
+    output_path
+}
+
+/// Run a composed CLI via `wacli run` and return its stdout.
+fn run_cli(component: &Path, args: &[&str]) -> String {
+    let out = wacli()
+        .arg("run")
+        .arg(component)
+        .arg("--")
+        .args(args)
+        .output()
+        .expect("failed to run wacli run");
+    assert!(
+        out.status.success(),
+        "wacli run failed:\nstatus: {}\nstderr:\n{}",
+        out.status,
+        String::from_utf8_lossy(&out.stderr),
+    );
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+#[test]
+fn check_reproducible_builds_twice_and_compares() {
+    let dir = make_fixture_project("reproducible");
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--check-reproducible", "--git-rev", "abc123"])
+        .output()
+        .expect("failed to run wacli build");
+    assert_success(&out, "wacli build --check-reproducible");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+
+    let output = dir.join("my-cli.component.wasm");
+    let bytes = fs::read(&output).expect("the first build is kept");
+    let digest: String = Sha256::digest(&bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    assert!(
+        stderr.contains(&format!(
+            "Reproducible: {} (sha256:{digest})",
+            output.display()
+        )),
+        "{stderr}"
+    );
+    assert!(!dir.join("my-cli.component.check.wasm").exists());
+
+    // A separate build from the same inputs gives the same bytes.
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let first = fs::read(&cli).unwrap();
+    build_fixture_cli(&dir, &[], &[]);
+    assert!(
+        fs::read(&cli).unwrap() == first,
+        "rebuild changed the output"
+    );
+}
+
+#[test]
+fn stripped_components_still_compose_and_run() {
+    let dir = make_fixture_project("strip");
+    let full = build_fixture_cli(&dir, &[], &[]);
+    let full_len = fs::metadata(&full).unwrap().len();
+
+    // --strip overrides build.strip; `none` keeps the output as is.
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "schemaVersion": 1, "build": { "strip": "all" } }"#,
+    )
+    .expect("failed to write wacli.json");
+    let build = |strip: &str, output: &str| {
+        let out = wacli()
+            .current_dir(&dir)
+            .args(["build", "--name", "example:test-cli", "--output", output])
+            .arg(strip)
+            .output()
+            .expect("failed to run wacli build");
+        assert_success(&out, strip);
+        (
+            dir.join(output),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    };
+    let (kept, stderr) = build("--strip=none", "kept.component.wasm");
+    assert_eq!(fs::metadata(&kept).unwrap().len(), full_len);
+    assert!(!stderr.contains("Stripped"), "{stderr}");
+
+    let (stripped, stderr) = build("--strip=all", "stripped.component.wasm");
+    assert!(
+        stderr.contains("Stripped wacli:cmd-greet: ")
+            && stderr.contains("Stripped 4 component(s): saved "),
+        "{stderr}"
+    );
+    assert!(fs::metadata(&stripped).unwrap().len() < full_len);
+
+    // Commands and their metadata survive.
+    assert_eq!(run_cli(&stripped, &["greet", "Ada"]), "Hello, Ada!\n");
+    assert!(run_cli(&stripped, &["--help"]).contains("Greet someone"));
+}
+
+#[test]
+fn build_report_lists_component_sizes() {
+    let dir = make_fixture_project("report");
+    let build = |report: &str| {
+        let out = wacli()
+            .current_dir(&dir)
+            .env("RUST_LOG", "warn")
+            .args(["build", "--output", "out.component.wasm", report])
+            .output()
+            .expect("failed to run wacli build");
+        assert_success(&out, report);
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let text = build("--report");
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("Component  Kind"), "{text}");
+    assert!(
+        lines[1].starts_with("greet      command") && lines[1].ends_with(env!("CARGO_PKG_VERSION")),
+        "{text}"
+    );
+    for (line, name) in lines[2..5].iter().zip(["host", "core", "registry"]) {
+        assert!(line.starts_with(name), "{text}");
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&build("--report=json")).unwrap();
+    let output_size = fs::metadata(dir.join("out.component.wasm")).unwrap().len();
+    assert_eq!(json["output-size"], output_size);
+    let greet = &json["components"][0];
+    assert_eq!(greet["name"], "greet");
+    assert_eq!(greet["cdk-version"], env!("CARGO_PKG_VERSION"));
+    assert!(greet["share"].as_f64().unwrap() > 0.0);
+    assert_eq!(json["components"].as_array().unwrap().len(), 4);
+}
+
+#[test]
+fn multi_command_components_register_every_command() {
+    let dir = make_project_with(
+        "multi-command",
+        "textkit",
+        r#""groups": [{ "name": "quiet", "commands": ["whisper"] }]"#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    // Each name (and alias) reaches its own command in the shared instance.
+    assert_eq!(run_cli(&cli, &["shout", "hello", "world"]), "HELLO WORLD\n");
+    assert_eq!(run_cli(&cli, &["yell", "hi"]), "HI\n");
+    assert_eq!(run_cli(&cli, &["quiet", "whisper", "HeLLo"]), "hello\n");
+    assert_eq!(run_cli(&cli, &["greet", "Ada"]), "Hello, Ada!\n");
+    let help = run_cli(&cli, &["--help"]);
+    assert!(
+        help.contains("  shout (yell)     Print the words in upper case\n"),
+        "{help}"
+    );
+
+    // The component is composed (and reported) once.
+    let out = wacli()
+        .current_dir(&dir)
+        .env("RUST_LOG", "warn")
+        .args(["build", "--output", "out.component.wasm", "--report=json"])
+        .output()
+        .expect("failed to run wacli build");
+    assert_success(&out, "wacli build --report=json");
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let textkit = json["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "textkit")
+        .expect("textkit is reported");
+    assert_eq!(
+        textkit["commands"],
+        serde_json::json!(["shout", "quiet/whisper"])
+    );
+    assert_eq!(json["components"].as_array().unwrap().len(), 5);
+}
+
+#[test]
+fn version_build_info_is_opt_in_at_runtime() {
+    let dir = make_fixture_project("build-info-default");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    assert_eq!(run_cli(&cli, &["--version"]), "example:test-cli 0.1.0\n");
+
+    let wacli_line = format!("wacli {}\n", env!("CARGO_PKG_VERSION"));
+    for args in [["--version", "--build-info"], ["-V", "--verbose"]] {
+        let stdout = run_cli(&cli, &args);
+        assert_eq!(stdout, format!("example:test-cli 0.1.0\n{wacli_line}"));
+    }
+}
+
+#[test]
+fn version_build_info_includes_git_rev_and_timestamp_when_requested() {
+    let dir = make_fixture_project("build-info-flags");
+
+    let cli = build_fixture_cli(
+        &dir,
+        &["--git-rev", "v1.2.3-4-gabcdef", "--build-timestamp"],
+        &[("SOURCE_DATE_EPOCH", "1700000000")],
+    );
+    let stdout = run_cli(&cli, &["--version", "--build-info"]);
+    assert!(
+        stdout.contains("git-revision: v1.2.3-4-gabcdef\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("build-timestamp: 1700000000\n"), "{stdout}");
+    assert_eq!(run_cli(&cli, &["--version"]), "example:test-cli 0.1.0\n");
+
+    // The env var is used when no flag is given; the timestamp stays off.
+    let cli = build_fixture_cli(&dir, &[], &[("WACLI_GIT_REV", "deadbeef")]);
+    let stdout = run_cli(&cli, &["-V", "--verbose"]);
+    assert!(stdout.contains("git-revision: deadbeef\n"), "{stdout}");
+    assert!(!stdout.contains("build-timestamp"), "{stdout}");
+
+    // The flag wins over the env var.
+    let cli = build_fixture_cli(
+        &dir,
+        &["--git-rev", "from-flag"],
+        &[("WACLI_GIT_REV", "from-env")],
+    );
+    let stdout = run_cli(&cli, &["-V", "--verbose"]);
+    assert!(stdout.contains("git-revision: from-flag\n"), "{stdout}");
+    assert!(!stdout.contains("from-env"), "{stdout}");
+
+    // GIT_SHA is the last fallback.
+    let cli = build_fixture_cli(&dir, &[], &[("GIT_SHA", "0123abc")]);
+    let stdout = run_cli(&cli, &["-V", "--verbose"]);
+    assert!(stdout.contains("git-revision: 0123abc\n"), "{stdout}");
+    let cli = build_fixture_cli(
+        &dir,
+        &[],
+        &[("WACLI_GIT_REV", "from-wacli"), ("GIT_SHA", "0123abc")],
+    );
+    let stdout = run_cli(&cli, &["-V", "--verbose"]);
+    assert!(stdout.contains("git-revision: from-wacli\n"), "{stdout}");
+
+    // Extra facts follow the timestamp, in the order given.
+    let cli = build_fixture_cli(
+        &dir,
+        &[
+            "--build-timestamp",
+            "--build-info",
+            "ci=run 42",
+            "--build-info",
+            "builder=alice",
+        ],
+        &[("SOURCE_DATE_EPOCH", "1700000000")],
+    );
+    let stdout = run_cli(&cli, &["--version", "--build-info"]);
+    assert!(
+        stdout.contains("build-timestamp: 1700000000\nci: run 42\nbuilder: alice\n"),
+        "{stdout}"
+    );
+
+    for (fact, err) in [
+        ("ci", "--build-info should be KEY=VALUE, got 'ci'"),
+        (
+            "build-timestamp=1",
+            "--build-info cannot set 'build-timestamp'",
+        ),
+        ("a b=1", "invalid --build-info key 'a b'"),
+    ] {
+        let out = wacli()
+            .current_dir(&dir)
+            .args(["build", "--print-wac", "--build-info", fact])
+            .output()
+            .expect("failed to run wacli build");
+        assert!(!out.status.success(), "--build-info {fact} must fail");
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains(err), "{stderr}");
+    }
+}
+
+/// Run `wacli` with `stdin` piped in and stdout/stderr captured.
+fn wacli_with_stdin(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = wacli()
+        .current_dir(repo_root())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn wacli");
+    // The child may exit before consuming stdin (e.g. on validation errors).
+    let _ = child.stdin.take().expect("stdin is piped").write_all(stdin);
+    child.wait_with_output().expect("failed to wait for wacli")
+}
+
+fn fixture_component(name: &str) -> Vec<u8> {
+    fs::read(repo_root().join("components").join(name))
+        .unwrap_or_else(|e| panic!("failed to read {name}: {e}"))
+}
+
+fn assert_success(out: &Output, what: &str) {
+    assert!(
+        out.status.success(),
+        "{what} failed:\nstatus: {}\nstderr:\n{}",
+        out.status,
+        String::from_utf8_lossy(&out.stderr),
+    );
+}
+
+#[test]
+fn plug_reads_stdin_and_writes_to_piped_stdout() {
+    let host = fixture_component("host.component.wasm");
+    let core = fixture_component("core.component.wasm");
+
+    // Plug from stdin, socket from a file.
+    let from_plug = wacli_with_stdin(
+        &["plug", "components/core.component.wasm", "--plug", "-"],
+        &host,
+    );
+    assert_success(&from_plug, "wacli plug --plug -");
+    assert!(from_plug.stdout.starts_with(b"\0asm"), "stdout is not wasm");
+
+    // Socket from stdin, plug from a file, explicit `-o -`.
+    let from_socket = wacli_with_stdin(
+        &[
+            "plug",
+            "-",
+            "--plug",
+            "components/host.component.wasm",
+            "-o",
+            "-",
+        ],
+        &core,
+    );
+    assert_success(&from_socket, "wacli plug -");
+    assert_eq!(from_plug.stdout, from_socket.stdout);
+}
+
+#[test]
+fn plug_rejects_multiple_stdin_inputs() {
+    let out = wacli_with_stdin(&["plug", "-", "--plug", "-"], &[]);
+    assert!(!out.status.success(), "expected failure");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("only one input may be read from stdin"),
+        "unexpected stderr:\n{stderr}"
+    );
+    assert!(out.stdout.is_empty());
+}
+
+#[test]
+fn compose_reads_wac_from_stdin() {
+    let wac = "package test:composition;\n\
+               let host = new wacli:host { ... };\n\
+               export host.host-env;\n";
+    let out = wacli_with_stdin(
+        &[
+            "compose",
+            "-",
+            "--dep",
+            "wacli:host=components/host.component.wasm",
+        ],
+        wac.as_bytes(),
+    );
+    assert_success(&out, "wacli compose -");
+    assert!(out.stdout.starts_with(b"\0asm"), "stdout is not wasm");
+}
+
+#[test]
+fn compose_pulls_registry_deps_through_the_lock_and_cache() {
+    let dir = make_temp_dir("compose-oci");
+    fs::write(
+        dir.join("app.wac"),
+        "package test:composition;\n\
+         let host = new wacli:host { ... };\n\
+         export host.host-env;\n",
+    )
+    .unwrap();
+    let compose = |args: &[&str]| {
+        let out = wacli()
+            .current_dir(&dir)
+            .env("MOLT_REGISTRY", "http://127.0.0.1:9")
+            .env_remove("WACLI_OFFLINE")
+            .args(["compose", "app.wac", "-o", "out.wasm"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli compose");
+        (
+            out.status.success(),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    };
+    let oci_dep = "wacli:host=oci://wacli/host:1.0.0";
+
+    let (ok, stderr) = compose(&["--dep", oci_dep, "--offline"]);
+    assert!(!ok);
+    assert!(
+        stderr.contains("  package wacli:host: wacli/host:1.0.0\n    not pinned in wacli.lock"),
+        "{stderr}"
+    );
+
+    let (ok, stderr) = compose(&["--dep", oci_dep]);
+    assert!(!ok);
+    assert!(
+        stderr.contains("registry pull failed for package wacli:host"),
+        "{stderr}"
+    );
+    assert!(!dir.join("wacli.lock").exists());
+
+    // A later path override wins without touching the registry.
+    let host = repo_root().join("components/host.component.wasm");
+    let path_dep = format!("wacli:host={}", host.display());
+    let (ok, stderr) = compose(&["--dep", oci_dep, "--dep", &path_dep]);
+    assert!(ok, "{stderr}");
+
+    // Once pinned and cached, the package composes offline.
+    let bytes = fs::read(&host).unwrap();
+    let layer = format!("sha256:{:x}", Sha256::digest(&bytes));
+    fs::write(
+        dir.join("wacli.lock"),
+        format!(
+            r#"{{
+  "lockfileVersion": 1,
+  "deps": [
+    {{ "name": "wacli:host", "repo": "wacli/host", "reference": "1.0.0", "digest": "sha256:h1", "layerDigest": "{layer}" }}
+  ]
+}}
+"#
+        ),
+    )
+    .unwrap();
+    let cached = dir.join(".wacli/deps/sha256_h1/wacli_host.wasm");
+    fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    fs::write(&cached, &bytes).unwrap();
+    let (ok, stderr) = compose(&["--dep", oci_dep, "--offline"]);
+    assert!(ok, "{stderr}");
+    assert!(
+        fs::read(dir.join("out.wasm"))
+            .unwrap()
+            .starts_with(b"\0asm")
+    );
+}
+
+#[test]
+fn wasm_push_checks_the_component_before_pushing() {
+    let dir = make_temp_dir("wasm-push");
+    let module = dir.join("module.wasm");
+    fs::write(&module, b"\0asm\x01\0\0\0").unwrap();
+    let push = |file: &Path, extra: &[&str]| {
+        let out = wacli()
+            .current_dir(&dir)
+            .env("MOLT_REGISTRY", "http://127.0.0.1:9")
+            .env_remove("MOLT_AUTH_HEADER")
+            .arg("wasm")
+            .arg("push")
+            .arg(file)
+            .arg("example/greet:1.0.0")
+            .args(extra)
+            .output()
+            .expect("failed to run wacli wasm push");
+        assert!(!out.status.success());
+        assert!(out.stdout.is_empty());
+        String::from_utf8_lossy(&out.stderr).into_owned()
+    };
+
+    let stderr = push(&module, &[]);
+    assert!(stderr.contains("is a core WebAssembly module"), "{stderr}");
+
+    let host = repo_root().join("components/host.component.wasm");
+    let stderr = push(&host, &["--lock"]);
+    assert!(
+        stderr.contains("--lock needs a command component"),
+        "{stderr}"
+    );
+
+    let greet = repo_root().join("testdata/greet.component.wasm");
+    let stderr = push(&greet, &["--lock"]);
+    assert!(stderr.contains("failed to push component"), "{stderr}");
+    assert!(!dir.join("wacli.lock").exists());
+}
+
+#[test]
+fn registry_requests_are_retried_and_summarized() {
+    let search = |retries: &str| {
+        let out = wacli()
+            .env("MOLT_REGISTRY", "http://127.0.0.1:9")
+            .env("MOLT_REGISTRY_RETRIES", retries)
+            .env_remove("MOLT_REGISTRY_TIMEOUT_SECS")
+            .args(["wasm", "search", "--export", "wacli:cli/command@2.0.0"])
+            .output()
+            .expect("failed to run wacli wasm search");
+        assert!(!out.status.success());
+        String::from_utf8_lossy(&out.stderr).into_owned()
+    };
+
+    let stderr = search("1");
+    assert!(stderr.contains("2 attempts failed:"), "{stderr}");
+    assert!(
+        stderr.contains("\n  2: failed to call registry"),
+        "{stderr}"
+    );
+
+    let stderr = search("0");
+    assert!(!stderr.contains("attempts failed"), "{stderr}");
+    assert!(stderr.contains("failed to call registry"), "{stderr}");
+
+    let stderr = search("many");
+    assert!(
+        stderr.contains("invalid MOLT_REGISTRY_RETRIES: expected a non-negative integer"),
+        "{stderr}"
+    );
+}
+
+/// Create a project with `greet` plus the `migrate`/`seed`/`reset` fixtures and
+/// a manifest declaring `groups`.
+fn make_grouped_project(prefix: &str, groups_json: &str) -> TempDir {
+    let dir = make_fixture_project(prefix);
+    for name in ["migrate", "seed", "reset"] {
+        add_fixture(&dir, name, "commands");
+    }
+    fs::write(
+        dir.join("wacli.json"),
+        format!(r#"{{ "schemaVersion": 1, "build": {{ "groups": {groups_json} }} }}"#),
+    )
+    .expect("failed to write wacli.json");
+    dir
+}
+
+#[test]
+fn build_groups_commands_under_a_prefix() {
+    let dir = make_grouped_project(
+        "groups",
+        r#"[{ "name": "db", "summary": "Database tasks", "commands": ["migrate", "seed", "reset"] }]"#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let help = run_cli(&cli, &["--help"]);
+    assert!(
+        help.contains(
+            "  db               Database tasks\n    migrate (up)   Apply pending migrations\n"
+        ),
+        "unexpected global help:\n{help}"
+    );
+    assert!(
+        help.contains("  greet (hi)       Greet someone\n"),
+        "{help}"
+    );
+
+    let group_help = run_cli(&cli, &["db"]);
+    assert!(
+        group_help.starts_with("db - Database tasks\n"),
+        "{group_help}"
+    );
+    assert!(group_help.contains("\nCommands:\n  migrate  Apply pending migrations\n  reset    Drop and recreate the database\n  seed     Load seed data\n"), "{group_help}");
+
+    assert_eq!(
+        run_cli(&cli, &["db", "migrate", "--steps", "2"]),
+        "migrate: steps=2 dry-run=false\n"
+    );
+    assert_eq!(
+        run_cli(&cli, &["db", "up", "--dry-run"]),
+        "migrate: steps=all dry-run=true\n"
+    );
+    assert_eq!(
+        run_cli(&cli, &["db", "seed", "data.json"]),
+        "seed: data.json\n"
+    );
+
+    let member_help = run_cli(&cli, &["help", "db", "seed"]);
+    assert!(
+        member_help.starts_with("db seed - Load seed data\n"),
+        "{member_help}"
+    );
+    assert!(
+        member_help.contains("Usage: db seed <FILE>\n"),
+        "{member_help}"
+    );
+
+    // Member args are validated against the member's schema.
+    let out = wacli()
+        .arg("run")
+        .arg(&cli)
+        .args(["--", "db", "seed"])
+        .output()
+        .expect("failed to run wacli run");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("missing required argument: <FILE>"),
+        "{stderr}"
+    );
+    assert!(stderr.contains(" db seed --help'\n"), "{stderr}");
+
+    // Members are not reachable at the top level, and unknown members fail.
+    for args in [["migrate", ""], ["db", "nope"]] {
+        let args: Vec<&str> = args.into_iter().filter(|a| !a.is_empty()).collect();
+        let out = wacli()
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(&args)
+            .output()
+            .expect("failed to run wacli run");
+        assert!(!out.status.success(), "expected {args:?} to fail");
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("Unknown command"), "{stderr}");
+    }
+}
+
+#[test]
+fn build_rejects_group_colliding_with_command() {
+    let dir = make_grouped_project(
+        "groups-collide",
+        r#"[{ "name": "greet", "commands": ["migrate"] }]"#,
+    );
+
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--output", "out.component.wasm"])
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success(), "expected build to fail");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("group name 'greet' collides with command 'greet'"),
+        "unexpected stderr:\n{stderr}"
+    );
+}
+
+/// `greet` with a `section` custom section placed before everything else, so
+/// it is the first one a scan finds.
+fn greet_with_leading_section(section: &str, data: &[u8]) -> Vec<u8> {
+    use wasm_encoder::{CustomSection, Section};
+    let greet = fs::read(repo_root().join("testdata/greet.component.wasm"))
+        .expect("failed to read greet.component.wasm fixture");
+    let mut out = greet[..8].to_vec();
+    CustomSection {
+        name: section.into(),
+        data: data.into(),
+    }
+    .append_to(&mut out);
+    out.extend_from_slice(&greet[8..]);
+    out
+}
+
+#[test]
+fn build_rejects_hostile_metadata_and_names_the_component() {
+    let build = |dir: &Path, envs: &[(&str, &str)]| {
+        let mut cmd = wacli();
+        cmd.current_dir(dir)
+            .args(["build", "--output", "out.component.wasm"]);
+        for (k, v) in envs {
+            cmd.env(k, v);
+        }
+        let out = cmd.output().expect("failed to run wacli build");
+        assert!(!out.status.success(), "expected build to fail");
+        String::from_utf8_lossy(&out.stderr).into_owned()
+    };
+    let section = "wacli:cli/command-metadata@1";
+
+    // Oversized: greet's real section is larger than a tiny cap.
+    let dir = make_fixture_project("scan-size");
+    let stderr = build(&dir, &[("WACLI_SCAN_MAX_SECTION_BYTES", "64")]);
+    assert!(
+        stderr.contains("greet.component.wasm: custom section 'wacli:cli/command-metadata@1' is"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("over the 64 byte limit (set WACLI_SCAN_MAX_SECTION_BYTES"),
+        "{stderr}"
+    );
+    drop(dir);
+
+    // Deeply nested JSON, which would otherwise reach serde_json.
+    let dir = make_fixture_project("scan-depth");
+    let json = format!(
+        r#"{{"format-version":1,"command-meta":{{"name":"hostile","examples":{}{}}}}}"#,
+        "[".repeat(100_000),
+        "]".repeat(100_000)
+    );
+    fs::write(
+        dir.join("commands/hostile.component.wasm"),
+        greet_with_leading_section(section, json.as_bytes()),
+    )
+    .unwrap();
+    let stderr = build(&dir, &[]);
+    assert!(
+        stderr.contains("failed to extract command metadata from ")
+            && stderr
+                .contains("hostile.component.wasm: metadata JSON is nested deeper than 64 levels"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn run_expands_arg_files_for_opted_in_commands() {
+    let dir = make_project_with("arg-files", "tag", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let args_dir = dir.join("args");
+    fs::create_dir_all(&args_dir).expect("failed to create args dir");
+    fs::write(
+        args_dir.join("ids.txt"),
+        "# ids to tag\n--id\n1\n--id=2\n\n@/args/more.txt\n",
+    )
+    .expect("failed to write ids.txt");
+    fs::write(args_dir.join("more.txt"), "--id\n3\n").expect("failed to write more.txt");
+    fs::write(args_dir.join("loop.txt"), "--id\n4\n@/args/loop.txt\n")
+        .expect("failed to write loop.txt");
+    let preopen = format!("{}::/args", args_dir.display());
+
+    let run = |args: &[&str]| {
+        wacli()
+            .arg("run")
+            .arg("--dir")
+            .arg(&preopen)
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = run(&["tag", "urgent", "@/args/ids.txt", "--", "@literal"]);
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "tag: ids=1,2,3 labels=urgent,@literal\n"
+    );
+
+    let out = run(&["tag", "@/args/loop.txt"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.starts_with("/args/loop.txt:3: arg file cycle: /args/loop.txt -> /args/loop.txt\n"),
+        "{stderr}"
+    );
+
+    // Commands that do not opt in receive `@...` tokens untouched.
+    assert_eq!(
+        run_cli(&cli, &["greet", "@/args/ids.txt"]),
+        "Hello, @/args/ids.txt!\n"
+    );
+}
+
+#[test]
+fn build_warns_or_fails_on_commands_without_schema() {
+    let dir = make_project_with("require-schema", "legacy", "");
+
+    let build = |extra: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .args(["build", "--output", "out.component.wasm"])
+            .args(extra)
+            .output()
+            .expect("failed to run wacli build")
+    };
+
+    // Default: a warning naming the command (logged with the build output),
+    // and the build still succeeds.
+    let out = build(&[]);
+    let log = format!(
+        "{}{}",
+        String::from_utf8_lossy(&out.stdout),
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(out.status.success(), "{log}");
+    assert!(
+        log.contains("command 'legacy'") && log.contains("has no embedded command schema"),
+        "{log}"
+    );
+    assert!(!log.contains("command 'greet'"), "{log}");
+    assert_eq!(
+        run_cli(&dir.join("out.component.wasm"), &["legacy"]),
+        "legacy: ok\n"
+    );
+
+    // Strict mode, from the flag or from wacli.json.
+    let legacy_path = dir.join("commands/legacy.component.wasm");
+    let check_strict_failure = |out: std::process::Output| {
+        assert!(!out.status.success(), "expected build to fail");
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(
+            stderr.contains(&format!(
+                "1 command(s) have no embedded command schema:\n  legacy: {}\n",
+                legacy_path.display()
+            )),
+            "{stderr}"
+        );
+        assert!(stderr.contains("wacli-cdk"), "{stderr}");
+    };
+    check_strict_failure(build(&["--require-schema"]));
+
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "schemaVersion": 1, "build": { "requireSchema": true } }"#,
+    )
+    .expect("failed to write wacli.json");
+    check_strict_failure(build(&[]));
+
+    // Commands built with the current macro always carry a schema.
+    fs::remove_file(&legacy_path).expect("failed to remove legacy fixture");
+    let out = build(&["--require-schema"]);
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn build_names_commands_importing_interfaces_the_host_lacks() {
+    let dir = make_fixture_project("import-mismatch");
+    // Pretend greet was built against a newer host-io (same length, so every
+    // section size is unchanged).
+    let greet_path = dir.join("commands/greet.component.wasm");
+    let greet = fs::read(&greet_path).expect("failed to read greet fixture");
+    let (from, to) = (
+        b"wacli:cli/host-io@2.0.0".as_slice(),
+        b"wacli:cli/host-io@2.1.0".as_slice(),
+    );
+    let mut patched = greet.clone();
+    let mut at = 0;
+    while let Some(pos) = patched[at..].windows(from.len()).position(|w| w == from) {
+        patched[at + pos..at + pos + to.len()].copy_from_slice(to);
+        at += pos + to.len();
+    }
+    assert_ne!(patched, greet, "greet fixture does not import host-io");
+    fs::write(&greet_path, &patched).expect("failed to write patched greet");
+
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--output", "out.component.wasm"])
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success(), "expected build to fail");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("Error: Component interface mismatch\n\n- commands/greet.component.wasm imports wacli:cli/host-io@2.1.0 but defaults/host.component.wasm provides @2.0.0\n"),
+        "unexpected stderr:\n{stderr}"
+    );
+    assert!(!stderr.contains("Details:"), "{stderr}");
+}
+
+#[test]
+fn build_reads_meta_of_commands_without_metadata_section_when_allowed() {
+    let dir = make_fixture_project("meta-exec");
+    // Rename greet's section (same length, so the section size is unchanged)
+    // so the build no longer finds it.
+    let greet_path = dir.join("commands/greet.component.wasm");
+    let greet = fs::read(&greet_path).expect("failed to read greet fixture");
+    let (from, to) = (
+        b"wacli:cli/command-metadata@1".as_slice(),
+        b"wacli:cli/command-metadata@X".as_slice(),
+    );
+    let at = greet
+        .windows(from.len())
+        .position(|w| w == from)
+        .expect("greet fixture has no metadata section");
+    let mut patched = greet.clone();
+    patched[at..at + to.len()].copy_from_slice(to);
+    fs::write(&greet_path, &patched).expect("failed to write patched greet");
+
+    let build = |extra: &[&str], envs: &[(&str, &str)]| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir)
+            .args(["build", "--output", "out.component.wasm"])
+            .args(extra);
+        for (k, v) in envs {
+            cmd.env(k, v);
+        }
+        let out = cmd.output().expect("failed to run wacli build");
+        let log = format!(
+            "{}{}",
+            String::from_utf8_lossy(&out.stdout),
+            String::from_utf8_lossy(&out.stderr)
+        );
+        (out.status.success(), log)
+    };
+
+    // Off by default: the error points at the opt-in.
+    let (ok, log) = build(&[], &[]);
+    assert!(!ok, "expected build to fail");
+    assert!(
+        log.contains("missing embedded command metadata") && log.contains("--allow-meta-exec"),
+        "{log}"
+    );
+
+    // With the flag, meta() supplies the summary and aliases.
+    let (ok, log) = build(&["--allow-meta-exec"], &[]);
+    assert!(ok, "{log}");
+    let cli = dir.join("out.component.wasm");
+    assert!(run_cli(&cli, &["--help"]).contains("Greet someone"));
+    assert_eq!(run_cli(&cli, &["hi", "Bob"]), "Hello, Bob!\n");
+
+    // From wacli.json; a meta() that runs out of fuel only warns.
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "schemaVersion": 1, "build": { "allowMetaExec": true } }"#,
+    )
+    .expect("failed to write wacli.json");
+    let (ok, log) = build(&[], &[("WACLI_SCAN_META_FUEL", "1")]);
+    assert!(ok, "{log}");
+    assert!(
+        log.contains("command 'greet'") && log.contains("ran out of fuel (limit: 1)"),
+        "{log}"
+    );
+    assert!(!run_cli(&cli, &["--help"]).contains("Greet someone"));
+    assert_eq!(run_cli(&cli, &["greet", "Bob"]), "Hello, Bob!\n");
+
+    // A meta() reporting another name only warns; the file name is kept.
+    fs::rename(&greet_path, dir.join("commands/hello.component.wasm"))
+        .expect("failed to rename greet");
+    let (ok, log) = build(&[], &[]);
+    assert!(ok, "{log}");
+    assert!(
+        log.contains("reports the name 'greet'; using 'hello'"),
+        "{log}"
+    );
+    assert_eq!(run_cli(&cli, &["hello", "Bob"]), "Hello, Bob!\n");
+}
+
+/// Copy `testdata/<component>.component.wasm` into `dir/<to>/`.
+fn add_fixture(dir: &Path, component: &str, to: &str) {
+    let to = dir.join(to);
+    fs::create_dir_all(&to).expect("failed to create fixture dir");
+    fs::copy(
+        repo_root().join(format!("testdata/{component}.component.wasm")),
+        to.join(format!("{component}.component.wasm")),
+    )
+    .unwrap_or_else(|e| panic!("failed to copy {component}.component.wasm fixture: {e}"));
+}
+
+/// A fixture project with `testdata/<component>.component.wasm` added as a
+/// command and `build_json` (the fields of the `build` object) in wacli.json.
+fn make_project_with(prefix: &str, component: &str, build_json: &str) -> TempDir {
+    let dir = make_fixture_project(prefix);
+    add_fixture(&dir, component, "commands");
+    fs::write(
+        dir.join("wacli.json"),
+        format!(r#"{{ "schemaVersion": 1, "build": {{ {build_json} }} }}"#),
+    )
+    .expect("failed to write wacli.json");
+    dir
+}
+
+#[test]
+fn run_reports_usage_to_the_telemetry_command() {
+    let dir = make_project_with(
+        "telemetry",
+        "telemetry",
+        r#""telemetryCommand": "telemetry""#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let log = dir.join("telemetry.log");
+
+    // The telemetry command is hidden from help.
+    assert!(!run_cli(&cli, &["--help"]).contains("telemetry"));
+
+    let run = |args: &[&str], envs: &[(&str, &str)]| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir)
+            .env_remove("TEST_CLI_NO_TELEMETRY")
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(args);
+        for (k, v) in envs {
+            cmd.env(k, v);
+        }
+        cmd.output().expect("failed to run wacli run")
+    };
+
+    let out = run(&["greet", "secret-arg"], &[]);
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello, secret-arg!\n");
+
+    let recorded = fs::read_to_string(&log).expect("telemetry event not recorded");
+    let lines: Vec<&str> = recorded.lines().collect();
+    assert_eq!(lines.len(), 2, "{recorded}");
+    assert!(
+        lines[0].starts_with("argc=1 command=greet exit_code=0 duration_ms="),
+        "{recorded}"
+    );
+    assert!(
+        lines[1].starts_with(r#"{"command":"greet","exit_code":0,"duration_ms":"#),
+        "{recorded}"
+    );
+    assert!(!recorded.contains("secret-arg"), "{recorded}");
+
+    // Opting out skips the telemetry command entirely.
+    fs::remove_file(&log).expect("failed to remove telemetry.log");
+    let out = run(&["greet", "x"], &[("TEST_CLI_NO_TELEMETRY", "1")]);
+    assert!(out.status.success());
+    assert!(!log.exists(), "telemetry ran despite opt-out");
+}
+
+#[test]
+fn command_errors_carry_their_exit_code_to_core() {
+    let dir = make_project_with(
+        "exit-code",
+        "telemetry",
+        r#""telemetryCommand": "telemetry""#,
+    );
+    add_fixture(&dir, "probe", "commands");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let out = wacli()
+        .current_dir(&dir)
+        .env_remove("TEST_CLI_NO_TELEMETRY")
+        .arg("run")
+        .arg(&cli)
+        .arg("--")
+        .args(["probe", "--exit", "3"])
+        .output()
+        .expect("failed to run wacli run");
+    assert_eq!(out.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.starts_with("probe: exiting with 3\n"), "{stderr}");
+    assert!(!stderr.contains("wacli-exit"), "{stderr}");
+
+    let recorded =
+        fs::read_to_string(dir.join("telemetry.log")).expect("telemetry event not recorded");
+    assert!(
+        recorded.contains(r#"{"command":"probe","exit_code":3,"#),
+        "{recorded}"
+    );
+}
+
+#[test]
+fn exit_codes_separate_usage_errors_from_failures() {
+    let dir = make_project_with("exit-status", "probe", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let run = |args: &[&str]| {
+        wacli()
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+    for (args, code) in [
+        (&["--help"][..], 0),
+        (&["--version"], 0),
+        (&["greet", "--help"], 0),
+        (&["greet", "--version"], 0),
+        // Rejected by core before dispatch, and by the command itself
+        // (`--exit` parses its value with `?`).
+        (&["greet", "--bogus"], 2),
+        (&["probe", "--exit", "three"], 2),
+        (&["nope"], 127),
+        (&["probe", "--fail"], 1),
+        (&["probe", "--exit", "4"], 4),
+    ] {
+        let out = run(args);
+        assert_eq!(
+            out.status.code(),
+            Some(code),
+            "{args:?}\nstderr:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    // Usage errors from the command are still reported with the help hint.
+    let out = run(&["probe", "--exit", "three"]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.starts_with("invalid digit found in string\n"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("probe --help'"), "{stderr}");
+}
+
+#[test]
+fn build_rejects_unknown_telemetry_command() {
+    let dir = make_project_with(
+        "telemetry-unknown",
+        "telemetry",
+        r#""telemetryCommand": "nope""#,
+    );
+
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--output", "out.component.wasm"])
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success(), "expected build to fail");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("build.telemetryCommand 'nope' does not match any command"),
+        "unexpected stderr:\n{stderr}"
+    );
+}
+
+#[test]
+fn unknown_commands_run_the_fallback_command() {
+    let dir = make_project_with(
+        "fallback",
+        "ext",
+        r#""fallbackCommand": "ext",
+            "globalArgs": [{ "name": "verbose", "long": "--verbose" }]"#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let run = |args: &[&str]| {
+        wacli()
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    // The unresolved name comes first, then the original arguments as given.
+    let out = run(&["hello", "a b", "--flag"]);
+    assert_success(&out, "fallback to ext");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "ext hello: a b|--flag\n"
+    );
+
+    // Global options are taken out first, as for any other command.
+    let out = run(&["hello", "--verbose", "x"]);
+    assert_success(&out, "fallback to ext with a global option");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "ext hello: x\n");
+
+    // Known commands never reach the fallback.
+    let out = run(&["greet", "World"]);
+    assert_success(&out, "greet");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello, World!\n");
+
+    // A fallback that does not know the name either: the usual error.
+    let out = run(&["nope", "x"]);
+    assert_eq!(out.status.code(), Some(127));
+    assert!(out.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "Unknown command: nope\nRun with --help to see available commands.\n"
+    );
+}
+
+#[test]
+fn build_rejects_unknown_fallback_command() {
+    let dir = make_project_with("fallback-unknown", "ext", r#""fallbackCommand": "nope""#);
+
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--output", "out.component.wasm"])
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success(), "expected build to fail");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("build.fallbackCommand 'nope' does not match any command"),
+        "unexpected stderr:\n{stderr}"
+    );
+}
+
+#[test]
+fn user_aliases_expand_before_dispatch() {
+    let dir = make_fixture_project("aliases");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let home = dir.join("home");
+    fs::create_dir_all(home.join(".test-cli")).expect("failed to create alias dir");
+    fs::write(
+        home.join(".test-cli/aliases.json"),
+        r#"{
+            "ada": "greet 'Ada Lovelace'",
+            "hey": "ada",
+            "greet": "ada",
+            "hi": "ada",
+            "loop1": "loop2 x",
+            "loop2": "loop1"
+        }"#,
+    )
+    .expect("failed to write aliases.json");
+    fs::write(dir.join("broken.json"), r#"{ "ada": "greet 'Ada" }"#)
+        .expect("failed to write broken.json");
+    let preopen = format!("{}::/", dir.display());
+
+    let run = |args: &[&str], aliases: Option<&str>| {
+        let mut cmd = wacli();
+        cmd.env("HOME", "/home").env_remove("TEST_CLI_ALIASES");
+        if let Some(path) = aliases {
+            cmd.env("TEST_CLI_ALIASES", path);
+        }
+        cmd.arg("run")
+            .arg("--dir")
+            .arg(&preopen)
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    // Quoted words stay together; aliases may name other aliases.
+    for alias in ["ada", "hey"] {
+        let out = run(&[alias], None);
+        assert_success(&out, alias);
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "Hello, Ada Lovelace!\n"
+        );
+    }
+
+    // Commands and their aliases cannot be shadowed.
+    let out = run(&["greet", "World"], None);
+    assert_success(&out, "greet");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello, World!\n");
+
+    let out = run(&["hi", "World"], None);
+    assert_success(&out, "hi");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello, World!\n");
+
+    let out = run(&["help", "hey"], None);
+    assert_success(&out, "help hey");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.starts_with("'hey' is an alias for 'ada'\n\n") && stdout.contains("greet"),
+        "unexpected help:\n{stdout}"
+    );
+
+    let out = run(&["loop1"], None);
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "alias cycle: loop1 -> loop2 -> loop1\n"
+    );
+
+    // TEST_CLI_ALIASES names another file; empty turns aliases off.
+    let out = run(&["ada"], Some("/broken.json"));
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "Invalid alias file /broken.json: alias 'ada': unterminated quote\n"
+    );
+    let out = run(&["ada"], Some(""));
+    assert_eq!(out.status.code(), Some(127));
+}
+
+#[test]
+fn verify_passes_for_grouped_fixture_commands() {
+    let dir = make_grouped_project(
+        "verify-ok",
+        r#"[{ "name": "db", "summary": "Database tasks", "commands": ["migrate", "seed", "reset"] }]"#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let output = wacli()
+        .current_dir(&dir)
+        .arg("verify")
+        .arg(&cli)
+        .output()
+        .expect("failed to run wacli verify");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "verify failed:\n{stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("db migrate  1 passed\n"), "{stdout}");
+    assert!(stdout.contains("greet       1 passed\n"), "{stdout}");
+    assert!(
+        stdout.ends_with("4 command(s): 4 passed, 0 failed\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn verify_reports_a_failing_example_once_with_its_stderr() {
+    let dir = make_project_with("verify-fail", "probe", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    for jobs in ["1", "4"] {
+        let output = wacli()
+            .current_dir(&dir)
+            .args(["verify", "--jobs", jobs])
+            .arg(&cli)
+            .output()
+            .expect("failed to run wacli verify");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "verify should fail:\n{stdout}");
+        assert!(stdout.contains("probe  5 passed, 2 failed\n"), "{stdout}");
+        assert_eq!(
+            stdout
+                .matches("  FAIL example `probe --fail`: exit code 1\n")
+                .count(),
+            1,
+            "{stdout}"
+        );
+        assert_eq!(
+            stdout.matches("probe: failing on purpose").count(),
+            1,
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains("    stderr:\n    | probe: failing on purpose\n"),
+            "{stdout}"
+        );
+        assert!(!stdout.contains("about to fail"), "{stdout}");
+        assert!(stderr.contains("2 of 8 check(s) failed"), "{stderr}");
+    }
+}
+
+#[test]
+fn verify_runs_declared_self_tests() {
+    let dir = make_project_with("verify-self-tests", "probe", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let output = wacli()
+        .current_dir(&dir)
+        .arg("verify")
+        .arg(&cli)
+        .output()
+        .expect("failed to run wacli verify");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "verify should fail:\n{stdout}");
+    // Passing self-tests (exact stdout, and `contains:` with exit code 1)
+    // are not listed; the wrong expectation is, with both outputs.
+    assert!(!stdout.contains("self-test `probe a 'b c'`"), "{stdout}");
+    assert!(!stdout.contains("self-test `probe --fail`"), "{stdout}");
+    assert!(
+        stdout.contains(
+            "  FAIL self-test `probe x`: expected stdout \"probe: y\", got \"probe: x\"\n"
+        ),
+        "{stdout}"
+    );
+    // greet declares no self-tests and only runs --help.
+    assert!(stdout.contains("greet  1 passed\n"), "{stdout}");
+}
+
+#[test]
+fn verify_checks_a_command_component_on_its_own() {
+    let verify = |args: &[&str]| {
+        wacli()
+            .current_dir(repo_root())
+            .arg("verify")
+            .args(args)
+            .output()
+            .expect("failed to run wacli verify")
+    };
+
+    let out = verify(&["testdata/greet.component.wasm", "--exec"]);
+    assert_success(&out, "wacli verify greet --exec");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "testdata/greet.component.wasm: conforms to the plugin world (greet)\n"
+    );
+
+    let out = verify(&["testdata/textkit.component.wasm", "--exec", "--json"]);
+    assert_success(&out, "wacli verify textkit --exec --json");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["world"], "multi-plugin");
+    assert_eq!(report["commands"], serde_json::json!(["shout", "whisper"]));
+    assert_eq!(report["problems"], serde_json::json!([]));
+
+    // A core module is reported, not rejected, so CI gets the JSON too.
+    let dir = make_temp_dir("verify-core-module");
+    let module = dir.join("plain.component.wasm");
+    fs::write(&module, wat::parse_str("(module)").unwrap()).unwrap();
+    let out = verify(&[module.to_str().unwrap(), "--json"]);
+    assert!(!out.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(
+        report["problems"][0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("is a core WebAssembly module"),
+        "{report}"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("1 problem(s) found in "), "{stderr}");
+}
+
+#[test]
+fn run_injects_variables_from_env_files() {
+    let dir = make_project_with("env-file", "envecho", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    fs::write(
+        dir.join("base.env"),
+        "# project defaults\nAPI_URL=\"https://api.example.com\" # prod\nbroken line\nREGION=eu\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("local.env"),
+        "export API_URL=http://localhost:8080\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str], env_file_var: Option<&str>| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir)
+            .env_remove("WACLI_ENV_FILE")
+            .arg("run");
+        if let Some(path) = env_file_var {
+            cmd.env("WACLI_ENV_FILE", path);
+        }
+        let out = cmd
+            .args(args)
+            .arg(&cli)
+            .args(["--", "envecho", "API_URL", "REGION"])
+            .output()
+            .expect("failed to run wacli run");
+        assert!(
+            out.status.success(),
+            "wacli run failed:\n{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let out = run(&["--env-file", "base.env"], None);
+    assert!(
+        out.ends_with("API_URL=https://api.example.com\nREGION=eu\n"),
+        "{out}"
+    );
+    assert!(out.contains("base.env:3: expected KEY=VALUE"), "{out}");
+
+    let out = run(&["--env-file", "base.env", "--env-file", "local.env"], None);
+    assert!(
+        out.ends_with("API_URL=http://localhost:8080\nREGION=eu\n"),
+        "{out}"
+    );
+
+    let out = run(&[], Some("local.env"));
+    assert!(
+        out.ends_with("API_URL=http://localhost:8080\nREGION=<unset>\n"),
+        "{out}"
+    );
+
+    let out = run(&["--env-file", "base.env"], Some("local.env"));
+    assert!(
+        out.ends_with("API_URL=https://api.example.com\nREGION=eu\n"),
+        "{out}"
+    );
+}
+
+#[test]
+fn run_redirects_guest_stdout_and_stderr_to_files() {
+    let dir = make_project_with("stdio-redirect", "envecho", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let run = |guest_args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .env("WACLI_T_VALUE", "caf\u{e9}")
+            .args([
+                "run",
+                "--stdout",
+                "out/stdout.txt",
+                "--stderr",
+                "out/stderr.txt",
+            ])
+            .arg(&cli)
+            .arg("--")
+            .args(guest_args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+    fs::create_dir_all(dir.join("out")).unwrap();
+
+    let out = run(&["envecho", "WACLI_T_VALUE"]);
+    assert_success(&out, "wacli run --stdout");
+    assert!(out.stdout.is_empty(), "stdout was not redirected");
+    assert_eq!(
+        fs::read(dir.join("out/stdout.txt")).unwrap(),
+        "WACLI_T_VALUE=caf\u{e9}\n".as_bytes()
+    );
+    assert!(fs::read(dir.join("out/stderr.txt")).unwrap().is_empty());
+
+    // A failing command: its exit code still comes through, and its error
+    // lands in the file (truncating the previous run's output).
+    let out = run(&["envecho", "--bogus"]);
+    assert_eq!(out.status.code(), Some(2), "unexpected exit status");
+    assert!(out.stderr.is_empty(), "stderr was not redirected");
+    assert!(fs::read(dir.join("out/stdout.txt")).unwrap().is_empty());
+    let stderr = fs::read_to_string(dir.join("out/stderr.txt")).unwrap();
+    assert!(stderr.contains("--bogus"), "{stderr}");
+
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["run", "--stdout", "missing/dir/out.txt"])
+        .arg(&cli)
+        .args(["--", "envecho"])
+        .output()
+        .expect("failed to run wacli run");
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("failed to create stdout file"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn env_lookups_are_case_sensitive_and_cwd_is_reported() {
+    let dir = make_project_with("env-get", "envecho", "");
+    fs::write(
+        dir.join("vars.env"),
+        "WACLI_T_REGION=eu\nwacli_t_region=lower\nWACLI_T_EMPTY=\n",
+    )
+    .unwrap();
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let names = [
+        "WACLI_T_REGION",
+        "wacli_t_region",
+        "WACLI_T_EMPTY",
+        "WACLI_T_MISSING",
+    ];
+    let expected =
+        "cwd=.\nWACLI_T_REGION=eu\nwacli_t_region=lower\nWACLI_T_EMPTY=\nWACLI_T_MISSING=<unset>\n";
+    for direct in [false, true] {
+        let out = wacli()
+            .current_dir(&dir)
+            .env_remove("WACLI_ENV_FILE")
+            .args(["run", "--env-file", "vars.env"])
+            .arg(&cli)
+            .args(["--", "envecho", "--cwd"])
+            .args(direct.then_some("--direct"))
+            .args(names)
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, "wacli run envecho");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            expected,
+            "direct={direct}"
+        );
+    }
+}
+
+#[test]
+fn env_warnings_report_misspelled_variables() {
+    let dir = make_project_with("env-warnings", "envecho", "");
+    fs::write(dir.join("typo.env"), "ENVECHO_PREFX=> \nREGION=eu\n").unwrap();
+    fs::write(dir.join("case.env"), "envecho_prefix=> \n").unwrap();
+    fs::write(dir.join("clean.env"), "ENVECHO_PREFIX=> \nENVECHO=1\n").unwrap();
+
+    let run = |cli: &Path, env_file: &str| {
+        let out = wacli()
+            .current_dir(&dir)
+            .env_remove("WACLI_ENV_FILE")
+            .args(["run", "--env-file", env_file])
+            .arg(cli)
+            .args(["--", "envecho", "REGION"])
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, "wacli run envecho");
+        String::from_utf8_lossy(&out.stderr).into_owned()
+    };
+    let warning = |found: &str| {
+        format!(
+            "warning: environment variable '{found}' is set but 'ENVECHO_PREFIX' is expected \u{2014} ignoring\n"
+        )
+    };
+
+    // Off by default.
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    assert!(!run(&cli, "typo.env").contains("warning:"));
+
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "envWarnings": true } }"#,
+    )
+    .unwrap();
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let stderr = run(&cli, "typo.env");
+    assert!(stderr.contains(&warning("ENVECHO_PREFX")), "{stderr}");
+    let stderr = run(&cli, "case.env");
+    assert!(stderr.contains(&warning("envecho_prefix")), "{stderr}");
+    let stderr = run(&cli, "clean.env");
+    assert!(!stderr.contains("warning:"), "{stderr}");
+}
+
+#[test]
+fn config_file_values_fill_in_missing_args() {
+    let dir = make_project_with(
+        "config-file",
+        "envecho",
+        r#""globalArgs": [
+            { "name": "config", "long": "--config", "takes-value": true }
+        ]"#,
+    );
+    fs::write(dir.join("cfg.json"), r#"{ "prefix": "cfg> " }"#).unwrap();
+    fs::write(dir.join("bad.json"), r#"{ "prefix": ["cfg> "] }"#).unwrap();
+    fs::write(dir.join("region.env"), "REGION=eu\n").unwrap();
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let run = |args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .env_remove("WACLI_ENV_FILE")
+            .args(["run", "--env-file", "region.env"])
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = run(&["envecho", "--config", "cfg.json", "REGION"]);
+    assert_success(&out, "wacli run envecho with a config file");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "cfg> REGION=eu\n");
+
+    // argv wins over the config file.
+    let out = run(&[
+        "envecho", "--config", "cfg.json", "--prefix", "arg> ", "REGION",
+    ]);
+    assert_success(&out, "wacli run envecho --prefix");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "arg> REGION=eu\n");
+
+    let out = run(&["envecho", "--config", "bad.json", "REGION"]);
+    assert!(!out.status.success(), "a bad config file must fail the run");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "Invalid config file bad.json: value of 'prefix' must be a string, number or boolean\n"
+    );
+}
+
+#[test]
+fn log_level_follows_app_env_var_and_verbosity_flags() {
+    let dir = make_project_with(
+        "host-log",
+        "logger",
+        r#""globalArgs": [
+            { "name": "verbose", "long": "--verbose", "short": "-v" },
+            { "name": "quiet", "long": "--quiet", "short": "-q" }
+        ]"#,
+    );
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let run = |log: Option<&str>, args: &[&str]| {
+        let mut cmd = wacli();
+        cmd.env_remove("TEST_CLI_LOG");
+        if let Some(log) = log {
+            cmd.env("TEST_CLI_LOG", log);
+        }
+        let out = cmd
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, "wacli run logger");
+        (
+            String::from_utf8_lossy(&out.stdout).into_owned(),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    };
+    let lines = |levels: &[&str]| -> String {
+        levels
+            .iter()
+            .map(|level| format!("[{} logger] {level} message\n", level.to_ascii_uppercase()))
+            .collect()
+    };
+
+    let (stdout, stderr) = run(None, &["logger"]);
+    assert_eq!(stdout, "debug enabled: false\n");
+    assert_eq!(stderr, lines(&["warn", "error"]));
+
+    let (_, stderr) = run(Some("info"), &["logger"]);
+    assert_eq!(stderr, lines(&["info", "warn", "error"]));
+    let (_, stderr) = run(Some("off"), &["logger"]);
+    assert_eq!(stderr, "");
+
+    // The flags win over the env var.
+    let (stdout, stderr) = run(Some("off"), &["logger", "-v"]);
+    assert_eq!(stdout, "debug enabled: true\n");
+    assert_eq!(stderr, lines(&["debug", "info", "warn", "error"]));
+    let (_, stderr) = run(Some("trace"), &["logger", "--quiet"]);
+    assert_eq!(stderr, lines(&["error"]));
+
+    let (_, stderr) = run(Some("loud"), &["logger"]);
+    assert_eq!(
+        stderr,
+        format!(
+            "warning: ignoring TEST_CLI_LOG=loud (expected trace, debug, info, warn, error or off)\n{}",
+            lines(&["warn", "error"])
+        )
+    );
+}
+
+#[test]
+fn prompts_read_piped_stdin_under_wacli_run() {
+    let dir = make_project_with("prompt", "login", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let cli = cli.to_str().unwrap();
+
+    // Piped stdin is not a terminal, so the secret is read as a plain line.
+    let out = wacli_with_stdin(&["run", cli, "--", "login"], b"alice\r\nhunter2\n");
+    assert_success(&out, "wacli run login");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "user=alice password=7 chars\n"
+    );
+    assert_eq!(String::from_utf8_lossy(&out.stderr), "user: password: ");
+
+    let out = wacli_with_stdin(&["run", cli, "--", "login"], b"");
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "user: no user given\n"
+    );
+
+    let out = wacli_with_stdin(&["run", cli, "--", "login"], b"bob\n");
+    assert!(
+        !out.status.success(),
+        "end of input at the secret must fail"
+    );
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("end of input"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn build_emits_man_pages_for_visible_commands() {
+    let dir = make_grouped_project(
+        "man-pages",
+        r#"[{ "name": "db", "summary": "Database tasks", "commands": ["migrate", "seed", "reset"] }]"#,
+    );
+    build_fixture_cli(&dir, &["--emit-man", "man"], &[]);
+
+    let mut pages: Vec<String> = fs::read_dir(dir.join("man"))
+        .expect("man dir")
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    pages.sort();
+    assert_eq!(
+        pages,
+        vec![
+            "test-cli-db-migrate.1",
+            "test-cli-db-reset.1",
+            "test-cli-db-seed.1",
+            "test-cli-greet.1",
+            "test-cli.1",
+        ]
+    );
+
+    let migrate = fs::read_to_string(dir.join("man/test-cli-db-migrate.1")).unwrap();
+    assert!(
+        migrate
+            .starts_with(".TH \"TEST\\-CLI\\-DB\\-MIGRATE\" \"1\" \"0.1.0\" \"test\\-cli 0.1.0\""),
+        "{migrate}"
+    );
+    assert!(
+        migrate.contains(".SH SYNOPSIS\n.B test\\-cli db migrate\n[OPTIONS]\n"),
+        "{migrate}"
+    );
+    assert!(
+        migrate.contains(
+            "\\fB\\-\\-steps\\fR \\fI<N>\\fR\nNumber of migrations to apply\n.br\nDefault: all.\n"
+        ),
+        "{migrate}"
+    );
+
+    let top = fs::read_to_string(dir.join("man/test-cli.1")).unwrap();
+    assert!(
+        top.contains(".TP\n\\fBdb migrate\\fR\nApply pending migrations\n"),
+        "{top}"
+    );
+
+    // Pages are a pure function of the inputs.
+    build_fixture_cli(&dir, &["--emit-man", "man"], &[]);
+    assert_eq!(
+        fs::read_to_string(dir.join("man/test-cli-db-migrate.1")).unwrap(),
+        migrate
+    );
+}
+
+#[test]
+fn build_emits_a_manifest_of_all_commands() {
+    let dir = make_grouped_project(
+        "emit-manifest",
+        r#"[{ "name": "db", "summary": "Database tasks", "commands": ["migrate", "seed", "reset"] }]"#,
+    );
+    build_fixture_cli(&dir, &["--emit-manifest", "docs/manifest.json"], &[]);
+
+    let json = fs::read_to_string(dir.join("docs/manifest.json")).expect("manifest written");
+    let manifest: serde_json::Value = serde_json::from_str(&json).expect("manifest is JSON");
+    assert_eq!(manifest["app"]["name"], "example:test-cli");
+    assert_eq!(manifest["app"]["version"], "0.1.0");
+    let names: Vec<&str> = manifest["commands"]
+        .as_array()
+        .expect("commands array")
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["db/migrate", "db/reset", "db/seed", "greet"]);
+    let migrate = &manifest["commands"][0];
+    assert_eq!(migrate["group"], "db");
+    assert_eq!(
+        migrate["command-schema"]["summary"],
+        "Apply pending migrations"
+    );
+}
+
+#[test]
+fn commands_read_stdin_to_the_end() {
+    let dir = make_project_with("stdin", "upper", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let cli = cli.to_str().unwrap();
+
+    for args in [&["upper"][..], &["upper", "--chunk", "3"]] {
+        let argv: Vec<&str> = ["run", cli, "--"].iter().chain(args).copied().collect();
+        let out = wacli_with_stdin(&argv, b"hello,\nworld\n");
+        assert_success(&out, "upper");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "HELLO,\nWORLD\n",
+            "{args:?}"
+        );
+
+        // Empty stdin is an empty read, not a hang.
+        let out = wacli_with_stdin(&argv, b"");
+        assert_success(&out, "upper with empty stdin");
+        assert!(out.stdout.is_empty(), "{args:?}");
+    }
+
+    let out = wacli_with_stdin(&["run", cli, "--", "upper"], b"\xff\xfe");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("stdin is not valid UTF-8"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn guests_see_whether_stdout_and_stderr_are_terminals() {
+    let dir = make_project_with("isatty", "isatty", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    // Piped: both streams are pipes owned by this test.
+    let out = run_cli(&cli, &["isatty"]);
+    assert!(out.ends_with("stdout=false stderr=false\n"), "{out}");
+
+    // Inherited: the guest sees whatever this test process is attached to.
+    if !(std::io::stdout().is_terminal() && std::io::stderr().is_terminal()) {
+        eprintln!("skipping inherited-tty check: test stdio is not a terminal");
+        return;
+    }
+    let status = wacli()
+        .current_dir(&dir)
+        .args(["run"])
+        .arg(&cli)
+        .args(["--", "isatty", "--out", "report.txt"])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .expect("failed to run wacli run");
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("report.txt")).unwrap(),
+        "stdout=true stderr=true\n"
+    );
+}
+
+#[test]
+fn pipe_options_are_validated_before_process_runs() {
+    let dir = make_project_with("pipe-options", "show", "");
+    add_fixture(&dir, "table", "plugins/show/format");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let show = |options: &[&str]| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir)
+            .arg("run")
+            .arg(&cli)
+            .args(["--", "show", "--format", "format/table"]);
+        for opt in options {
+            cmd.args(["--option", opt]);
+        }
+        cmd.arg(" Hello ")
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = show(&[]);
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout), " HELLO ");
+    let out = show(&["case=lower", "trim"]);
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "hello");
+
+    // The fixture would answer "table: unexpected ..."; these never reach it.
+    for (options, message) in [
+        (
+            &["colour=red"][..],
+            "pipe 'format/table': unknown flag: --colour",
+        ),
+        (
+            &["case=title"][..],
+            "pipe 'format/table': invalid value 'title' for '--case'",
+        ),
+        (
+            &["trim=yes"][..],
+            "pipe 'format/table': flag does not take a value: --trim",
+        ),
+    ] {
+        let out = show(options);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(!out.status.success(), "{options:?} should fail");
+        assert!(stderr.contains("InvalidOption"), "{stderr}");
+        assert!(stderr.contains(message), "{stderr}");
+        assert!(!stderr.contains("table: unexpected"), "{stderr}");
+    }
+}
+
+#[test]
+fn output_format_negotiation_covers_builtins_and_pipes() {
+    let dir = make_project_with("output-formats", "show", "");
+    add_fixture(&dir, "table", "plugins/show/format");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let show = |args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg(&cli)
+            .args(["--", "show"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    for (args, expected) in [
+        (&["hi"][..], "hi"),
+        (&["--format", "plain", "hi"][..], "hi"),
+        (&["--format", "json", "hi"][..], "{\"text\":\"hi\"}\n"),
+        (&["--format", "table", "hi"][..], "HI"),
+        (&["--format", "format/table", "hi"][..], "HI"),
+    ] {
+        let out = show(args);
+        assert_success(&out, "wacli run show");
+        assert_eq!(String::from_utf8_lossy(&out.stdout), expected, "{args:?}");
+    }
+
+    for args in [&["--format", "help"][..], &["--list-formats"][..]] {
+        let out = show(args);
+        assert_success(&out, "wacli run show");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(
+            stdout.starts_with("Formats:\n  plain  built-in\n"),
+            "{stdout}"
+        );
+        // The summary comes from the pipe's metadata section.
+        assert!(
+            stdout.contains("  table  Uppercase formatter (test) (pipe format/table)\n"),
+            "{stdout}"
+        );
+    }
+
+    let out = show(&["--format", "yaml"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains(
+            "invalid value 'yaml' for '--format'. possible values: plain, json, table, help"
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn run_limits_apply_to_the_component_and_its_pipes() {
+    let dir = make_project_with("run-limits", "show", "");
+    add_fixture(&dir, "table", "plugins/show/format");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let show = |limits: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .args(limits)
+            .arg(&cli)
+            .args(["--", "show", "--format", "table", "hi"])
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = show(&["--timeout", "120", "--fuel", "10000000000"]);
+    assert_success(&out, "wacli run with generous limits");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "HI");
+
+    let out = show(&["--fuel", "1000"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("component was stopped after running out of fuel (limit: 1000)"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn run_loads_pipes_from_the_configured_plugins_dir() {
+    let dir = make_project_with("run-plugins-dir", "show", "");
+    // Shared (not command-scoped) pipes, outside the default `plugins/`.
+    add_fixture(&dir, "table", "shared-pipes/format");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let show = |flags: &[&str], env: Option<&str>| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir).env_remove("WACLI_PLUGINS_DIR");
+        if let Some(env) = env {
+            cmd.env("WACLI_PLUGINS_DIR", env);
+        }
+        cmd.arg("run")
+            .args(flags)
+            .arg(&cli)
+            .args(["--", "show", "--format", "table", "hi"])
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = show(&[], None);
+    assert!(!out.status.success(), "no ./plugins means no table pipe");
+
+    let out = show(&["--plugins-dir", "shared-pipes"], None);
+    assert_success(&out, "wacli run --plugins-dir");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "HI");
+
+    let out = show(&[], Some("shared-pipes"));
+    assert_success(&out, "wacli run with WACLI_PLUGINS_DIR");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "HI");
+
+    let out = show(&["--plugins-dir", "missing"], Some("shared-pipes"));
+    assert!(!out.status.success(), "the flag wins over the environment");
+}
+
+#[test]
+fn build_profiles_select_their_own_framework_components() {
+    let dir = make_fixture_project("profiles");
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{
+  "build": {
+    "name": "example:test-cli",
+    "version": "0.1.0",
+    "output": "out.component.wasm",
+    "profiles": {
+      "stable": {},
+      "edge": {
+        "hostRepo": "acme/host",
+        "hostReference": "v0.2.0-rc1",
+        "coreRepo": "acme/core",
+        "coreReference": "v0.2.0-rc1",
+        "features": ["jit", "simd"]
+      }
+    }
+  }
+}
+"#,
+    )
+    .unwrap();
+
+    // The edge pair is pinned in the lock and already cached, so no registry
+    // is needed; the defaults/ pair serves the stable profile.
+    let lock = r#"{
+  "schemaVersion": 1,
+  "framework": {
+    "edge": {
+      "host": { "repo": "acme/host", "reference": "v0.2.0-rc1", "digest": "sha256:edgehost" },
+      "core": { "repo": "acme/core", "reference": "v0.2.0-rc1", "digest": "sha256:edgecore" }
+    }
+  }
+}
+"#;
+    fs::write(dir.join("wacli.lock"), lock).unwrap();
+    for (repo, digest, name) in [
+        ("acme_host", "sha256_edgehost", "host.component.wasm"),
+        ("acme_core", "sha256_edgecore", "core.component.wasm"),
+    ] {
+        let cached = dir.join(".wacli/framework").join(repo).join(digest);
+        fs::create_dir_all(&cached).unwrap();
+        fs::copy(repo_root().join("components").join(name), cached.join(name)).unwrap();
+    }
+
+    let build = |profile: &str| {
+        wacli()
+            .current_dir(&dir)
+            .env_remove("MOLT_REGISTRY")
+            .env_remove("WACLI_GIT_REV")
+            .env("RUST_LOG", "info")
+            .args(["build", "--profile", profile])
+            .output()
+            .expect("failed to run wacli build")
+    };
+
+    let edge = build("edge");
+    assert_success(&edge, "edge build");
+    // Logs name the cache entries that were composed.
+    let log = String::from_utf8_lossy(&edge.stdout);
+    assert!(log.contains("sha256_edgehost"), "{log}");
+    assert!(log.contains("sha256_edgecore"), "{log}");
+
+    let stable = build("stable");
+    assert_success(&stable, "stable build");
+    let log = String::from_utf8_lossy(&stable.stdout);
+    assert!(!log.contains("sha256_edge"), "{log}");
+
+    // Switching profiles leaves the lock alone.
+    assert_eq!(fs::read_to_string(dir.join("wacli.lock")).unwrap(), lock);
+    assert!(!dir.join("out.component.wasm").exists());
+
+    let info = run_cli(
+        &dir.join("out-edge.component.wasm"),
+        &["--version", "--build-info"],
+    );
+    assert!(
+        info.contains("profile: edge\nfeatures: jit, simd\n"),
+        "{info}"
+    );
+    let info = run_cli(
+        &dir.join("out-stable.component.wasm"),
+        &["--version", "--build-info"],
+    );
+    assert!(info.contains("profile: stable\n"), "{info}");
+    assert!(!info.contains("features"), "{info}");
+
+    let unknown = build("nightly");
+    assert!(!unknown.status.success());
+    assert!(
+        String::from_utf8_lossy(&unknown.stderr)
+            .contains("unknown profile 'nightly' (available: edge, stable)"),
+        "{}",
+        String::from_utf8_lossy(&unknown.stderr)
+    );
+}
+
+#[test]
+fn build_rejects_core_from_another_wacli_version() {
+    // The version checks themselves are covered in compat.rs; this only shows
+    // that the build runs them before composing.
+    let dir = make_fixture_project("compat-old");
+    fs::write(
+        dir.join("defaults/core.component.wasm"),
+        wat::parse_str("(component)").unwrap(),
+    )
+    .unwrap();
+    let out = wacli()
+        .current_dir(&dir)
+        .args(["build", "--output", "out.component.wasm"])
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success(), "build should fail");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("core.component.wasm is too old for this wacli (missing ")
+            && stderr.contains("wacli:cli/registry-schema@2.0.0"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn build_commands_resolve_local_path_and_git_sources() {
+    let dir = make_fixture_project("command-sources");
+    fs::remove_file(dir.join("commands/greet.component.wasm")).unwrap();
+    // Sibling build output that is not named `<command>.component.wasm`.
+    let target = dir.join("greet/target");
+    fs::create_dir_all(&target).unwrap();
+    fs::copy(
+        repo_root().join("testdata/greet.component.wasm"),
+        target.join("greet.wasm"),
+    )
+    .expect("failed to copy greet fixture");
+    // A git repository with the seed command committed and tagged.
+    let tools = dir.join("tools");
+    fs::create_dir_all(&tools).unwrap();
+    fs::copy(
+        repo_root().join("testdata/seed.component.wasm"),
+        tools.join("seed.component.wasm"),
+    )
+    .expect("failed to copy seed fixture");
+    let git = |args: &[&str]| {
+        let out = Command::new("git")
+            .current_dir(&tools)
+            .args([
+                "-c",
+                "user.name=wacli",
+                "-c",
+                "user.email=wacli@example.com",
+            ])
+            .args(args)
+            .output()
+            .expect("failed to run git");
+        assert_success(&out, &format!("git {args:?}"));
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    git(&["init", "--quiet"]);
+    git(&["add", "seed.component.wasm"]);
+    git(&["commit", "--quiet", "-m", "seed"]);
+    git(&["tag", "v1"]);
+    let commit = git(&["rev-parse", "HEAD"]);
+
+    let manifest = |seed_rev: &str| {
+        let json = format!(
+            r#"{{
+  "build": {{
+    "commands": [
+      {{ "name": "greet", "path": "greet/target/greet.wasm" }},
+      {{ "name": "seed", "git": "./tools", "rev": "{seed_rev}" }}
+    ]
+  }}
+}}"#
+        );
+        fs::write(dir.join("wacli.json"), json).unwrap();
+    };
+    manifest("v1");
+    let cli = build_fixture_cli(&dir, &[], &[("MOLT_REGISTRY", "")]);
+    assert_eq!(run_cli(&cli, &["seed", "data.json"]), "seed: data.json\n");
+    assert_eq!(run_cli(&cli, &["greet", "you"]), "Hello, you!\n");
+
+    let lock = fs::read_to_string(dir.join("wacli.lock")).unwrap();
+    assert!(lock.contains("\"gitCommands\""), "{lock}");
+    assert!(
+        lock.contains(&format!("\"commit\": \"{commit}\"")),
+        "{lock}"
+    );
+    assert!(
+        !lock.contains("greet"),
+        "path sources are not locked: {lock}"
+    );
+
+    // The locked commit is served from the cache, even with the repo gone.
+    fs::rename(&tools, dir.join("tools-moved")).unwrap();
+    build_fixture_cli(&dir, &[], &[("MOLT_REGISTRY", "")]);
+
+    manifest("main");
+    let out = wacli()
+        .current_dir(&dir)
+        .env("MOLT_REGISTRY", "")
+        .arg("build")
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("wacli.lock is out of date for command 'seed'"),
+        "{stderr}"
+    );
+
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "commands": [
+  { "name": "greet", "path": "greet/target/greet.wasm" },
+  { "name": "greet", "repo": "example/greet", "reference": "1.0.0" }
+] } }"#,
+    )
+    .unwrap();
+    let out = wacli()
+        .current_dir(&dir)
+        .arg("build")
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("duplicate command name 'greet' in build.commands"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn build_verifies_cached_registry_components_against_the_lock() {
+    use sha2::{Digest, Sha256};
+
+    let dir = make_fixture_project("cache-digest");
+    fs::remove_file(dir.join("commands/greet.component.wasm")).unwrap();
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "commands": [
+  { "name": "greet", "repo": "example/greet", "reference": "1.0.0" }
+] } }"#,
+    )
+    .unwrap();
+    let greet = fs::read(repo_root().join("testdata/greet.component.wasm")).unwrap();
+    let layer: String = Sha256::digest(&greet)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    fs::write(
+        dir.join("wacli.lock"),
+        format!(
+            r#"{{
+  "lockfileVersion": 1,
+  "commands": [
+    {{ "name": "greet", "repo": "example/greet", "reference": "1.0.0", "digest": "sha256:m1", "layerDigest": "sha256:{layer}" }}
+  ]
+}}
+"#
+        ),
+    )
+    .unwrap();
+    let cached = dir.join(".wacli/commands/example_greet/sha256_m1/greet.component.wasm");
+    fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    fs::write(&cached, &greet).unwrap();
+
+    let cli = build_fixture_cli(&dir, &[], &[("MOLT_REGISTRY", "")]);
+    assert_eq!(run_cli(&cli, &["greet", "you"]), "Hello, you!\n");
+
+    fs::write(&cached, b"tampered").unwrap();
+    let out = wacli()
+        .current_dir(&dir)
+        .env("MOLT_REGISTRY", "")
+        .arg("build")
+        .output()
+        .expect("failed to run wacli build");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("cache corrupted:"), "{stderr}");
+    assert!(
+        stderr.contains("digest mismatch: expected sha256:"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("wacli clean --cache"), "{stderr}");
+}
+
+#[test]
+fn offline_builds_list_every_component_missing_from_the_cache() {
+    let dir = make_fixture_project("offline");
+    fs::remove_file(dir.join("defaults/core.component.wasm")).unwrap();
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "commands": [
+  { "name": "hello", "repo": "example/hello", "reference": "1.0.0" },
+  { "name": "seed", "repo": "example/seed", "reference": "2.0.0" }
+] } }"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("wacli.lock"),
+        r#"{
+  "lockfileVersion": 1,
+  "commands": [
+    { "name": "seed", "repo": "example/seed", "reference": "2.0.0", "digest": "sha256:s2" }
+  ]
+}
+"#,
+    )
+    .unwrap();
+    // Unreachable: an offline build must not even try it.
+    let build = |args: &[&str], env: &[(&str, &str)]| {
+        let mut cmd = wacli();
+        cmd.current_dir(&dir)
+            .env("MOLT_REGISTRY", "http://127.0.0.1:9")
+            .env_remove("WACLI_OFFLINE")
+            .arg("build")
+            .args(args);
+        for (k, v) in env {
+            cmd.env(k, v);
+        }
+        let out = cmd.output().expect("failed to run wacli build");
+        (
+            out.status.success(),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    };
+
+    let (ok, stderr) = build(&["--offline"], &[]);
+    assert!(!ok);
+    let defaults = dir.join("defaults/core.component.wasm");
+    let seed = dir.join(".wacli/commands/example_seed/sha256_s2/seed.component.wasm");
+    for expected in [
+        "--offline: 3 components are not available locally:".to_string(),
+        "  framework core: wacli/core:v".to_string(),
+        format!("    expected at: {}", defaults.display()),
+        "  command hello: example/hello:1.0.0\n    not pinned in wacli.lock".to_string(),
+        format!(
+            "  command seed: example/seed@sha256:s2\n    expected at: {}",
+            seed.display()
+        ),
+    ] {
+        assert!(
+            stderr.contains(&expected),
+            "missing {expected:?} in:\n{stderr}"
+        );
+    }
+
+    let (ok, env_stderr) = build(&[], &[("WACLI_OFFLINE", "1")]);
+    assert!(!ok);
+    assert_eq!(env_stderr, stderr);
+
+    let (ok, stderr) = build(&["--offline", "--update-lock"], &[]);
+    assert!(!ok);
+    assert!(
+        stderr.contains("--offline cannot be combined with --update-lock"),
+        "{stderr}"
+    );
+
+    // Once everything is local, the offline build succeeds.
+    fs::copy(
+        repo_root().join("components/core.component.wasm"),
+        &defaults,
+    )
+    .unwrap();
+    fs::create_dir_all(seed.parent().unwrap()).unwrap();
+    fs::copy(repo_root().join("testdata/seed.component.wasm"), &seed).unwrap();
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "commands": [
+  { "name": "seed", "repo": "example/seed", "reference": "2.0.0" }
+] } }"#,
+    )
+    .unwrap();
+    let (ok, stderr) = build(&["--offline"], &[]);
+    assert!(ok, "{stderr}");
+}
+
+#[test]
+fn clean_removes_build_artifacts_inside_the_project_only() {
+    let dir = make_fixture_project("clean");
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "output": "out.component.wasm" } }"#,
+    )
+    .unwrap();
+    build_fixture_cli(&dir, &[], &[]);
+    let cached = dir.join(".wacli/commands/example_greet/greet.component.wasm");
+    fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    fs::write(&cached, b"cached").unwrap();
+    let clean = |args: &[&str]| {
+        let out = wacli()
+            .current_dir(&dir)
+            .arg("clean")
+            .args(args)
+            .output()
+            .expect("failed to run wacli clean");
+        (
+            out.status.success(),
+            String::from_utf8_lossy(&out.stderr).into_owned(),
+        )
+    };
+
+    let (ok, stderr) = clean(&[]);
+    assert!(!ok);
+    assert!(stderr.contains("nothing to clean"), "{stderr}");
+
+    let (ok, stderr) = clean(&["--all", "--dry-run"]);
+    assert!(ok, "{stderr}");
+    assert_eq!(
+        stderr,
+        "Would remove .wacli\nWould remove out.component.wasm\n"
+    );
+    assert!(dir.join("out.component.wasm").exists());
+
+    let (ok, stderr) = clean(&["--cache", "--registry"]);
+    assert!(ok, "{stderr}");
+    assert_eq!(
+        stderr,
+        "Removed .wacli/commands\nRemoved .wacli/registry.component.wasm\n"
+    );
+    assert!(dir.join(".wacli").is_dir());
+    assert!(dir.join("out.component.wasm").exists());
+    let (ok, stderr) = clean(&["--cache"]);
+    assert!(ok, "{stderr}");
+    assert_eq!(stderr, "Nothing to clean\n");
+
+    let outside = dir.join("../wacli-clean-outside.component.wasm");
+    fs::write(&outside, b"keep").unwrap();
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "output": "../wacli-clean-outside.component.wasm" } }"#,
+    )
+    .unwrap();
+    let (ok, stderr) = clean(&["--all"]);
+    assert!(!ok);
+    assert!(stderr.contains("refusing to remove"), "{stderr}");
+    assert!(stderr.contains("outside the project directory"), "{stderr}");
+    assert!(outside.exists());
+    assert!(dir.join(".wacli").exists(), "nothing is removed on refusal");
+
+    let _ = fs::remove_file(&outside);
+}
+
+#[test]
+fn lock_migrate_upgrades_legacy_locks_in_place() {
+    let dir = make_temp_dir("wacli-lock-migrate");
+    let legacy = r#"{
+  "schemaVersion": 1,
+  "commands": [
+    { "name": "greet", "repo": "example/greet", "reference": "1.0.0", "digest": "sha256:abc", "signature": "k1" }
+  ]
+}
+"#;
+    fs::write(dir.join("wacli.lock"), legacy).unwrap();
+    let migrate = |extra: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .args(["lock", "migrate"])
+            .args(extra)
+            .output()
+            .expect("failed to run wacli lock migrate")
+    };
+
+    let preview = migrate(&["--dry-run"]);
+    assert_success(&preview, "lock migrate --dry-run");
+    let diff = String::from_utf8_lossy(&preview.stdout);
+    assert!(diff.contains("-  \"schemaVersion\": 1,\n"), "{diff}");
+    assert!(diff.contains("+  \"lockfileVersion\": 1,\n"), "{diff}");
+    assert_eq!(fs::read_to_string(dir.join("wacli.lock")).unwrap(), legacy);
+
+    assert_success(&migrate(&[]), "lock migrate");
+    let migrated = fs::read_to_string(dir.join("wacli.lock")).unwrap();
+    assert!(migrated.contains("\"lockfileVersion\": 1"), "{migrated}");
+    assert!(migrated.contains("\"signature\": \"k1\""), "{migrated}");
+    assert!(!migrated.contains("schemaVersion"), "{migrated}");
+
+    let again = migrate(&[]);
+    assert_success(&again, "second lock migrate");
+    assert!(String::from_utf8_lossy(&again.stderr).contains("already at lockfileVersion 1"));
+    assert_eq!(
+        fs::read_to_string(dir.join("wacli.lock")).unwrap(),
+        migrated
+    );
+
+    fs::write(dir.join("wacli.lock"), r#"{ "lockfileVersion": 2 }"#).unwrap();
+    let newer = migrate(&[]);
+    assert!(!newer.status.success());
+    let stderr = String::from_utf8_lossy(&newer.stderr);
+    assert!(stderr.contains("upgrade wacli"), "{stderr}");
+}
+
+#[test]
+fn interactive_picker_dispatches_the_selected_command() {
+    let dir = make_project_with("picker", "seed", r#""interactivePicker": true"#);
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    // Piped stdin is not a terminal, so the test forces the picker on.
+    let pick = |answers: &str, interactive: &str| {
+        let mut child = wacli()
+            .env("TEST_CLI_INTERACTIVE", interactive)
+            .arg("run")
+            .arg(&cli)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn wacli run");
+        let _ = child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(answers.as_bytes());
+        child.wait_with_output().expect("failed to wait for wacli")
+    };
+
+    let out = pick("seed\nfixtures.sql\n", "1");
+    assert_success(&out, "picker selecting seed");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "seed: fixtures.sql\n");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.starts_with(
+            "Select a command:\n  1  greet  Greet someone\n  2  seed   Load seed data\n"
+        ),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("Command (number or name): <FILE>: "),
+        "{stderr}"
+    );
+
+    let out = pick("1\n", "1");
+    assert_success(&out, "picker selecting by number");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello, World!\n");
+
+    // End of input and unknown answers fall back to the help and exit 1.
+    for (answers, message) in [("", ""), ("nope\n", "no command matches 'nope'\n")] {
+        let out = pick(answers, "1");
+        assert_eq!(out.status.code(), Some(1), "{answers:?}");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.contains("Available commands:"), "{stdout}");
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with(message));
+    }
+
+    // Without the override, piped stdin keeps the static help.
+    let out = pick("1\n", "");
+    assert_success(&out, "bare run without a terminal");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Available commands:"));
+    assert!(out.stderr.is_empty());
+}
+
+#[test]
+fn complete_builtin_is_hidden_and_never_fails() {
+    let dir = make_fixture_project("complete");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    // `greet` declares no completable args; nothing resolves, nothing prints.
+    for args in [
+        &["__complete"][..],
+        &["__complete", "hi", "name", "W"],
+        &["__complete", "nope", "arg", ""],
+    ] {
+        assert_eq!(run_cli(&cli, args), "", "{args:?}");
+    }
+    assert!(!run_cli(&cli, &["--help"]).contains("__complete"));
+}
+
+#[test]
+fn completions_builtin_prints_a_script_bash_can_source() {
+    let dir = make_project_with("completions", "need", "");
+    add_fixture(&dir, "fileio", "commands");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    let script = run_cli(&cli, &["completions", "bash"]);
+    let function = script
+        .lines()
+        .find_map(|l| l.strip_prefix("complete -o default -F "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .expect("script registers a completion function")
+        .to_string();
+    let complete = |line: &[&str]| {
+        let words: Vec<String> = line.iter().map(|w| format!("'{w}'")).collect();
+        let out = Command::new("bash")
+            .arg("-c")
+            .arg(format!(
+                "{script}\nCOMP_WORDS=(cli {}); COMP_CWORD={}; {function}; printf '%s\\n' \"${{COMPREPLY[@]}}\"",
+                words.join(" "),
+                line.len()
+            ))
+            .output()
+            .expect("failed to run bash");
+        assert_success(&out, "bash completion");
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    assert_eq!(
+        complete(&[""]),
+        "fileio\ngreet\nhi\nneed\n--help\n-h\n--version\n-V\n"
+    );
+    assert_eq!(complete(&["fileio", "l"]), "log\nlist\nls\n");
+    assert_eq!(complete(&["need", "--case", ""]), "upper\nlower\n");
+
+    let out = wacli()
+        .arg("run")
+        .arg(&cli)
+        .args(["--", "completions", "powershell"])
+        .output()
+        .expect("failed to run wacli run");
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("completions <bash|zsh|fish>"), "{stderr}");
+    assert!(!run_cli(&cli, &["--help"]).contains("completions"));
+}
+
+#[test]
+fn inspect_reports_app_provenance_of_built_clis() {
+    let dir = make_fixture_project("inspect");
+    fs::write(
+        dir.join("wacli.json"),
+        r#"{ "build": { "profiles": { "stable": {} } } }"#,
+    )
+    .unwrap();
+    let cli = build_fixture_cli(&dir, &["--profile", "stable"], &[]);
+    let inspect = |args: &[&str]| {
+        let out = wacli()
+            .arg("inspect")
+            .args(args)
+            .output()
+            .expect("failed to run wacli inspect");
+        assert_success(&out, "inspect");
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+    let cli_path = cli.to_str().unwrap();
+
+    // The provenance matches what the build was given.
+    let greet = dir.join("commands/greet.component.wasm");
+    let single = inspect(&[greet.to_str().unwrap()]);
+    let greet_version = single
+        .lines()
+        .next()
+        .and_then(|l| l.strip_prefix("command component: greet"))
+        .unwrap_or_else(|| panic!("{single}"))
+        .trim();
+    let json: serde_json::Value = serde_json::from_str(&inspect(&["--json", cli_path])).unwrap();
+    let mut expected_greet = serde_json::json!({ "name": "greet" });
+    if !greet_version.is_empty() {
+        expected_greet["version"] = greet_version.into();
+    }
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "format-version": 1,
+            "name": "example:test-cli",
+            "version": "0.1.0",
+            "wacli-version": env!("CARGO_PKG_VERSION"),
+            "profile": "stable",
+            "commands": [expected_greet],
+        })
+    );
+
+    let text = inspect(&[cli_path]);
+    assert!(
+        text.starts_with(&format!(
+            "composed CLI: example:test-cli 0.1.0\nbuilt with: wacli {}\nprofile: stable\ncommands:\n  greet",
+            env!("CARGO_PKG_VERSION")
+        )),
+        "{text}"
+    );
+
+    // The section does not change what the CLI does.
+    assert!(run_cli(&cli, &["greet"]).contains("Hello"));
+
+    // Single components: their own metadata, as text or raw JSON.
+    assert!(
+        single.contains("\nexports: wacli:cli/command@2.0.0\n"),
+        "{single}"
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&inspect(&["--json", greet.to_str().unwrap()])).unwrap();
+    assert_eq!(json["command-meta"]["name"], "greet");
+
+    let table = repo_root().join("testdata/table.component.wasm");
+    let text = inspect(&[table.to_str().unwrap()]);
+    assert!(
+        text.starts_with(
+            "pipe component\nexports: wacli:cli/pipe@2.0.0\nsummary: Uppercase formatter (test)\n"
+        ),
+        "{text}"
+    );
+    assert!(text.contains("\noptions:\n  case  --case <CASE>"), "{text}");
+
+    let plain = dir.join("defaults/host.component.wasm");
+    let out = wacli()
+        .args(["inspect", "--json"])
+        .arg(&plain)
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr)
+            .contains("has no app provenance, command or pipe metadata section")
+    );
+}
+
+#[test]
+fn run_denies_network_access_unless_allowed() {
+    let probe = repo_root().join("testdata/netprobe.component.wasm");
+    // netprobe exits 0 when its DNS lookup is allowed and 1 when refused.
+    let run = |flags: &[&str]| {
+        wacli()
+            .env("RUST_LOG", "debug")
+            .arg("run")
+            .args(flags)
+            .arg(&probe)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let denied = run(&[]);
+    assert_eq!(denied.status.code(), Some(1));
+    let log = String::from_utf8_lossy(&denied.stdout);
+    assert!(log.contains("network policy: deny"), "{log}");
+
+    let allowed = run(&["--allow-network"]);
+    assert_success(&allowed, "run --allow-network");
+    let log = String::from_utf8_lossy(&allowed.stdout);
+    assert!(log.contains("network policy: allow (any host)"), "{log}");
+
+    let listed = run(&["--allow-network", "--allow-host", "127.0.0.1:8080"]);
+    assert_success(&listed, "run --allow-host");
+    let log = String::from_utf8_lossy(&listed.stdout);
+    assert!(
+        log.contains("network policy: allow (127.0.0.1:8080)"),
+        "{log}"
+    );
+
+    let orphan = run(&["--allow-host", "127.0.0.1:8080"]);
+    assert!(!orphan.status.success());
+    let stderr = String::from_utf8_lossy(&orphan.stderr);
+    assert!(stderr.contains("--allow-network"), "{stderr}");
+
+    let bad = run(&["--allow-network", "--allow-host", "example.com"]);
+    assert!(!bad.status.success());
+    let stderr = String::from_utf8_lossy(&bad.stderr);
+    assert!(stderr.contains("expected HOST:PORT"), "{stderr}");
+}
+
+#[test]
+fn subcommands_dispatch_with_their_own_help_and_errors() {
+    let dir = make_project_with("subcommands", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let run = |args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg(&cli)
+            .arg("--")
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = run(&["fileio", "--help"]);
+    assert_success(&out, "fileio --help");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("Commands:\n  read    Print a file\n"),
+        "{stdout}"
+    );
+
+    let out = run(&["fileio", "read", "--help"]);
+    assert_success(&out, "fileio read --help");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.starts_with("fileio read - Print a file\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("<PATH>  File to read"), "{stdout}");
+
+    let out = run(&["fileio", "read", "--help=json"]);
+    assert_success(&out, "fileio read --help=json");
+    let schema: serde_json::Value =
+        serde_json::from_slice(&out.stdout).expect("--help=json prints JSON");
+    assert_eq!(schema["name"], "fileio");
+    let read = &schema["subcommands"][0];
+    assert_eq!(read["name"], "read");
+    assert_eq!(read["args"][0]["help"], "File to read");
+
+    let out = run(&["--help=json"]);
+    assert_success(&out, "--help=json");
+    let schemas: serde_json::Value =
+        serde_json::from_slice(&out.stdout).expect("--help=json prints JSON");
+    assert!(
+        schemas
+            .as_array()
+            .expect("global --help=json prints an array")
+            .iter()
+            .any(|s| s["name"] == "fileio"),
+        "{schemas}"
+    );
+
+    let out = run(&["fileio", "bogus"]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains(
+            "unknown subcommand 'bogus'. valid subcommands: read, write, append, log, list, find, copy, mv, stat, rm, rmdir"
+        ),
+        "{stderr}"
+    );
+
+    let out = run(&["fileio", "write", "note.txt", "hello", "world"]);
+    assert_success(&out, "fileio write");
+    let out = run(&["fileio", "read", "note.txt"]);
+    assert_success(&out, "fileio read");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "hello world\n");
+}
+
+#[test]
+fn fs_copy_streams_large_files_through_the_host() {
+    let dir = make_project_with("fs-copy", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+
+    // 5 MiB + 123 bytes: several full chunks and a partial one.
+    let len = 5 * 1024 * 1024 + 123;
+    let mut state = 0x2545_f491_u32;
+    let data: Vec<u8> = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+    fs::write(dir.join("big.bin"), &data).unwrap();
+    fs::create_dir_all(dir.join("backup")).unwrap();
+    fs::write(dir.join("backup/big.bin"), b"stale and longer than nothing").unwrap();
+
+    let out = wacli()
+        .current_dir(&dir)
+        .arg("run")
+        .arg(&cli)
+        .args(["--", "fileio", "copy", "big.bin", "backup/big.bin"])
+        .output()
+        .expect("failed to run wacli run");
+    assert_success(&out, "fileio copy");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        format!("copied {len} bytes\n")
+    );
+    let copied = fs::read(dir.join("backup/big.bin")).unwrap();
+    assert_eq!(copied.len(), data.len());
+    assert!(copied == data, "copied content differs");
+
+    let out = wacli()
+        .current_dir(&dir)
+        .arg("run")
+        .arg(&cli)
+        .args(["--", "fileio", "copy", "missing.bin", "out.bin"])
+        .output()
+        .expect("failed to run wacli run");
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("missing.bin"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(!dir.join("out.bin").exists());
+
+    // The same file under another name is refused, not truncated.
+    let out = wacli()
+        .current_dir(&dir)
+        .arg("run")
+        .arg(&cli)
+        .args(["--", "fileio", "copy", "big.bin", "./backup/../big.bin"])
+        .output()
+        .expect("failed to run wacli run");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("copy: source and destination are the same file"),
+        "{stderr}"
+    );
+    assert_eq!(fs::read(dir.join("big.bin")).unwrap().len(), data.len());
+}
+
+#[test]
+fn fs_rename_moves_within_a_mount_only() {
+    let dir = make_project_with("fs-rename", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let mnt = dir.join("mnt");
+    fs::create_dir_all(&mnt).unwrap();
+    fs::create_dir_all(dir.join("src/sub")).unwrap();
+    fs::write(dir.join("src/a.txt"), "a").unwrap();
+    fs::write(dir.join("src/sub/b.txt"), "b").unwrap();
+    let run = |args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg("--dir")
+            .arg(format!("{}::/mnt", mnt.display()))
+            .arg(&cli)
+            .args(["--", "fileio"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+
+    let out = run(&["mv", "src/a.txt", "a.txt"]);
+    assert_success(&out, "fileio mv file");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "moved\n");
+    assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "a");
+    assert!(!dir.join("src/a.txt").exists());
+
+    let out = run(&["mv", "src", "dst"]);
+    assert_success(&out, "fileio mv dir");
+    assert_eq!(fs::read_to_string(dir.join("dst/sub/b.txt")).unwrap(), "b");
+    assert!(!dir.join("src").exists());
+
+    let out = run(&["mv", "a.txt", "/mnt/a.txt"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("cross-mount rename not supported, use copy: a.txt -> /mnt/a.txt"),
+        "{stderr}"
+    );
+    assert!(dir.join("a.txt").exists());
+    assert!(!mnt.join("a.txt").exists());
+
+    let out = run(&["copy", "a.txt", "/mnt/a.txt"]);
+    assert_success(&out, "fileio copy across mounts");
+    assert_eq!(fs::read_to_string(mnt.join("a.txt")).unwrap(), "a");
+}
+
+#[test]
+fn fs_append_and_writers_keep_files_open() {
+    let dir = make_project_with("fs-append", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let run = |args: &[&str]| {
+        let out = wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg(&cli)
+            .args(["--", "fileio"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, &format!("fileio {args:?}"));
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+    let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+
+    // Appending to a missing file creates it.
+    run(&["append", "notes.txt", "one"]);
+    run(&["append", "notes.txt", "two"]);
+    assert_eq!(read("notes.txt"), "one\ntwo\n");
+    // Larger than one stream write.
+    let big = "x".repeat(10_000);
+    run(&["append", "notes.txt", &big]);
+    assert_eq!(read("notes.txt"), format!("one\ntwo\n{big}\n"));
+
+    let out = run(&["log", "progress.log", "a", "b", "c"]);
+    assert_eq!(read("progress.log"), "a\nb\nc\n");
+    assert_eq!(out, "write: writer is closed: progress.log\n");
+
+    run(&["log", "--append", "progress.log", "d"]);
+    assert_eq!(read("progress.log"), "a\nb\nc\nd\n");
+    run(&["log", "progress.log", "fresh"]);
+    assert_eq!(read("progress.log"), "fresh\n");
+}
+
+#[test]
+fn fs_walk_lists_trees_in_sorted_order() {
+    let dir = make_project_with("fs-walk", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let tree = dir.join("tree");
+    fs::create_dir_all(tree.join("b/deep")).unwrap();
+    fs::create_dir_all(tree.join("a")).unwrap();
+    fs::write(tree.join("c.txt"), "c").unwrap();
+    fs::write(tree.join("b/deep/z.txt"), "z").unwrap();
+    fs::write(tree.join("a/y.txt"), "y").unwrap();
+    // A link back up the tree is listed, not followed.
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("..", tree.join("b/up")).unwrap();
+
+    let find = |args: &[&str]| {
+        let out = wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg("--dir")
+            .arg(format!("{}::/tree", tree.display()))
+            .arg(&cli)
+            .args(["--", "fileio", "find"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, "fileio find");
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let mut expected = vec![
+        "dir a",
+        "file a/y.txt",
+        "dir b",
+        "dir b/deep",
+        "file b/deep/z.txt",
+    ];
+    if cfg!(unix) {
+        expected.push("symlink b/up");
+    }
+    expected.push("file c.txt");
+    let all = find(&["tree"]);
+    assert_eq!(all.lines().collect::<Vec<_>>(), expected);
+    assert_eq!(find(&["/tree"]), all);
+
+    assert_eq!(
+        find(&["tree", "--depth", "1"]),
+        "dir a\ndir b\nfile c.txt\n"
+    );
+}
+
+#[test]
+fn fs_stat_reports_kind_size_and_mtime() {
+    let dir = make_project_with("fs-stat", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    fs::create_dir_all(dir.join("data/sub")).unwrap();
+    fs::write(dir.join("data/hello.txt"), "hello").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("sub", dir.join("data/link")).unwrap();
+
+    let stat = |path: &str| {
+        let out = wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg("--dir")
+            .arg(format!("{}::/data", dir.join("data").display()))
+            .arg(&cli)
+            .args(["--", "fileio", "stat", path])
+            .output()
+            .expect("failed to run wacli run");
+        assert_success(&out, "fileio stat");
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let out = stat("data/hello.txt");
+    assert!(out.starts_with("kind=file size=5 modified="), "{out}");
+    let modified = fs::metadata(dir.join("data/hello.txt"))
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    assert_eq!(out, format!("kind=file size=5 modified={modified}\n"));
+
+    assert!(stat("data/sub").starts_with("kind=dir "));
+    // Mount roots and paths below them.
+    assert!(stat("/data").starts_with("kind=dir "));
+    assert!(stat("/data/hello.txt").starts_with("kind=file size=5 "));
+    #[cfg(unix)]
+    assert!(stat("data/link").starts_with("kind=symlink "));
+    assert_eq!(stat("data/nope.txt"), "missing\n");
+}
+
+#[test]
+fn fs_remove_files_and_directories() {
+    let dir = make_project_with("fs-remove", "fileio", "");
+    let cli = build_fixture_cli(&dir, &[], &[]);
+    let run = |args: &[&str]| {
+        wacli()
+            .current_dir(&dir)
+            .arg("run")
+            .arg(&cli)
+            .args(["--", "fileio"])
+            .args(args)
+            .output()
+            .expect("failed to run wacli run")
+    };
+    let stderr = |out: &Output| String::from_utf8_lossy(&out.stderr).into_owned();
+
+    fs::create_dir_all(dir.join("cache/nested/deeper")).unwrap();
+    fs::write(dir.join("cache/a.txt"), "a").unwrap();
+    fs::write(dir.join("cache/nested/deeper/b.txt"), "b").unwrap();
+    fs::create_dir(dir.join("empty")).unwrap();
+
+    assert_success(&run(&["rm", "cache/a.txt"]), "fileio rm");
+    assert!(!dir.join("cache/a.txt").exists());
+
+    let out = run(&["rm", "cache/a.txt"]);
+    assert!(!out.status.success());
+    assert!(
+        stderr(&out).contains("remove-file: not found: cache/a.txt"),
+        "{}",
+        stderr(&out)
+    );
+
+    // Not recursive: a non-empty directory is left alone.
+    let out = run(&["rmdir", "cache"]);
+    assert!(!out.status.success());
+    assert!(
+        stderr(&out).contains("remove-dir: directory not empty: cache"),
+        "{}",
+        stderr(&out)
+    );
+    assert!(dir.join("cache/nested/deeper/b.txt").exists());
+
+    assert_success(&run(&["rmdir", "empty"]), "fileio rmdir");
+    assert!(!dir.join("empty").exists());
+
+    assert_success(&run(&["rmdir", "--all", "cache"]), "fileio rmdir --all");
+    assert!(!dir.join("cache").exists());
+    assert!(dir.join("commands").exists());
+
+    let out = run(&["rmdir", "--all", "cache"]);
+    assert!(
+        stderr(&out).contains("remove-dir-all: not found: cache"),
+        "{}",
+        stderr(&out)
+    );
 
-    let _ = fs::remove_dir_all(&dir);
+    // A symlinked directory is unlinked, not emptied.
+    #[cfg(unix)]
+    {
+        fs::create_dir(dir.join("outside")).unwrap();
+        fs::write(dir.join("outside/keep.txt"), "keep").unwrap();
+        std::os::unix::fs::symlink("outside", dir.join("link")).unwrap();
+        assert_success(&run(&["rmdir", "--all", "link"]), "fileio rmdir --all link");
+        assert!(fs::symlink_metadata(dir.join("link")).is_err());
+        assert!(dir.join("outside/keep.txt").exists());
+    }
 }
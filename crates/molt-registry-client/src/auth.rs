@@ -0,0 +1,727 @@
+//! Pluggable registry credential providers.
+//!
+//! [`AuthChain::from_env`] composes the built-in providers in precedence order:
+//!
+//! 1. `env`: `MOLT_AUTH_HEADER` / `MOLT_USERNAME` + `MOLT_PASSWORD` (see [`auth_from_env`])
+//! 2. `exec`: the command in `MOLT_REGISTRY_TOKEN_CMD`, for short-lived tokens
+//! 3. `docker-config`: `credHelpers` / `auths` / `credsStore` in `~/.docker/config.json`
+//!    (or `$DOCKER_CONFIG/config.json`)
+//!
+//! The first provider that has credentials for the registry wins; if none has
+//! any, requests are anonymous.
+
+use crate::util::auth_from_env;
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use oci_client::secrets::RegistryAuth;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A source of registry credentials.
+pub trait AuthProvider: Send + Sync {
+    /// Short name used in error messages (e.g. `docker-config`).
+    fn name(&self) -> &str;
+
+    /// Credentials for `registry` (`host[:port]`), or `None` if this provider
+    /// has nothing for it.
+    fn resolve(&self, registry: &str) -> Result<Option<RegistryAuth>>;
+
+    /// Drop cached credentials after the registry rejected them.
+    ///
+    /// Returns `true` if resolving again may yield different credentials.
+    fn invalidate(&self) -> bool {
+        false
+    }
+}
+
+/// Static credentials from the environment (the pre-provider behaviour).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnvAuthProvider;
+
+impl AuthProvider for EnvAuthProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn resolve(&self, _registry: &str) -> Result<Option<RegistryAuth>> {
+        match auth_from_env()? {
+            RegistryAuth::Anonymous => Ok(None),
+            auth => Ok(Some(auth)),
+        }
+    }
+}
+
+/// Credentials from a Docker `config.json`.
+///
+/// Lookup order for a registry host: `credHelpers[host]`, then an inline
+/// `auths[host]` entry, then the default `credsStore` (e.g. the OS keychain
+/// helper). Helpers are run as `docker-credential-<name> get`.
+#[derive(Debug, Clone)]
+pub struct DockerConfigAuthProvider {
+    path: PathBuf,
+    helper_prefix: PathBuf,
+}
+
+impl DockerConfigAuthProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            helper_prefix: PathBuf::from("docker-credential-"),
+        }
+    }
+
+    /// `$DOCKER_CONFIG/config.json`, or `~/.docker/config.json`.
+    pub fn from_env() -> Option<Self> {
+        let dir = match std::env::var_os("DOCKER_CONFIG") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => {
+                let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+                PathBuf::from(home).join(".docker")
+            }
+        };
+        Some(Self::new(dir.join("config.json")))
+    }
+
+    /// Look up credential helpers as `<dir>/docker-credential-<name>` instead of on `PATH`.
+    pub fn with_helper_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.helper_prefix = dir.into().join("docker-credential-");
+        self
+    }
+
+    fn run_helper(&self, helper: &str, registry: &str) -> Result<Option<RegistryAuth>> {
+        let mut program = self.helper_prefix.clone().into_os_string();
+        program.push(helper);
+
+        let mut child = Command::new(&program)
+            .arg("get")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!(
+                    "failed to run credential helper {}",
+                    PathBuf::from(&program).display()
+                )
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(registry.as_bytes())
+                .context("failed to write to credential helper")?;
+        }
+        let out = child
+            .wait_with_output()
+            .context("failed to wait for credential helper")?;
+
+        if !out.status.success() {
+            let msg = String::from_utf8_lossy(&out.stdout);
+            // Helpers report a missing entry on stdout with a non-zero exit.
+            if msg.contains("credentials not found") {
+                return Ok(None);
+            }
+            bail!(
+                "credential helper docker-credential-{helper} failed ({}): {}",
+                out.status,
+                String::from_utf8_lossy(&out.stderr).trim()
+            );
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct HelperCredentials {
+            username: String,
+            secret: String,
+        }
+        let creds: HelperCredentials = serde_json::from_slice(&out.stdout).with_context(|| {
+            format!("credential helper docker-credential-{helper} returned invalid JSON")
+        })?;
+        if creds.secret.is_empty() {
+            return Ok(None);
+        }
+        // `<token>` marks an identity token rather than a password.
+        if creds.username == "<token>" || creds.username.is_empty() {
+            return Ok(Some(RegistryAuth::Bearer(creds.secret)));
+        }
+        Ok(Some(RegistryAuth::Basic(creds.username, creds.secret)))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, DockerAuthEntry>,
+    #[serde(default)]
+    cred_helpers: HashMap<String, String>,
+    #[serde(default)]
+    creds_store: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DockerAuthEntry {
+    #[serde(default)]
+    auth: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    registrytoken: Option<String>,
+}
+
+impl DockerAuthEntry {
+    fn to_auth(&self) -> Result<Option<RegistryAuth>> {
+        if let Some(token) = self.registrytoken.as_deref().filter(|t| !t.is_empty()) {
+            return Ok(Some(RegistryAuth::Bearer(token.to_string())));
+        }
+        if let Some(b64) = self.auth.as_deref().filter(|a| !a.is_empty()) {
+            let decoded = base64::engine::general_purpose::STANDARD
+                .decode(b64.trim().as_bytes())
+                .context("invalid base64 in docker config `auth`")?;
+            let decoded =
+                String::from_utf8(decoded).context("docker config `auth` is not valid UTF-8")?;
+            let (u, p) = decoded
+                .split_once(':')
+                .context("docker config `auth` must decode to 'username:password'")?;
+            return Ok(Some(RegistryAuth::Basic(u.to_string(), p.to_string())));
+        }
+        match (&self.username, &self.password) {
+            (Some(u), Some(p)) if !u.is_empty() && !p.is_empty() => {
+                Ok(Some(RegistryAuth::Basic(u.clone(), p.clone())))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Reduce a docker config key (`https://host/v1/`, `host`, ...) to `host[:port]`.
+fn docker_config_host(key: &str) -> &str {
+    let key = key
+        .strip_prefix("https://")
+        .or_else(|| key.strip_prefix("http://"))
+        .unwrap_or(key);
+    key.split('/').next().unwrap_or(key)
+}
+
+impl AuthProvider for DockerConfigAuthProvider {
+    fn name(&self) -> &str {
+        "docker-config"
+    }
+
+    fn resolve(&self, registry: &str) -> Result<Option<RegistryAuth>> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read {}", self.path.display()));
+            }
+        };
+        let config: DockerConfig = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse {}", self.path.display()))?;
+
+        if let Some((_, helper)) = config
+            .cred_helpers
+            .iter()
+            .find(|(k, _)| docker_config_host(k) == registry)
+        {
+            return self.run_helper(helper, registry);
+        }
+
+        if let Some((_, entry)) = config
+            .auths
+            .iter()
+            .find(|(k, _)| docker_config_host(k) == registry)
+            && let Some(auth) = entry.to_auth()?
+        {
+            return Ok(Some(auth));
+        }
+
+        match config.creds_store.as_deref().filter(|s| !s.is_empty()) {
+            Some(store) => self.run_helper(store, registry),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Bearer tokens minted by an external command (`MOLT_REGISTRY_TOKEN_CMD`).
+///
+/// The command runs through the shell with `MOLT_REGISTRY_HOST` set and must
+/// print either a bare token or a JSON object:
+///
+/// ```json
+/// {"token": "...", "expires_in": 900}
+/// ```
+///
+/// (`access_token` is accepted for `token`; `expires_at` may be given instead
+/// of `expires_in`, as Unix seconds.) Tokens are cached until shortly before
+/// they expire, or until the registry rejects them.
+#[derive(Debug)]
+pub struct ExecAuthProvider {
+    command: String,
+    cache: Mutex<Option<CachedToken>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: Option<SystemTime>,
+}
+
+/// Refresh tokens this long before they expire.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+impl ExecAuthProvider {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// From `MOLT_REGISTRY_TOKEN_CMD`, if set.
+    pub fn from_env() -> Option<Self> {
+        let command = std::env::var("MOLT_REGISTRY_TOKEN_CMD").ok()?;
+        let command = command.trim();
+        (!command.is_empty()).then(|| Self::new(command))
+    }
+
+    fn mint(&self, registry: &str) -> Result<CachedToken> {
+        let mut cmd = if cfg!(windows) {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(&self.command);
+            cmd
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(&self.command);
+            cmd
+        };
+        let out = cmd
+            .env("MOLT_REGISTRY_HOST", registry)
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| format!("failed to run token command `{}`", self.command))?;
+        if !out.status.success() {
+            bail!(
+                "token command `{}` failed ({}): {}",
+                self.command,
+                out.status,
+                String::from_utf8_lossy(&out.stderr).trim()
+            );
+        }
+        let stdout =
+            String::from_utf8(out.stdout).context("token command output is not valid UTF-8")?;
+        parse_token_output(&stdout, SystemTime::now())
+    }
+}
+
+fn parse_token_output(stdout: &str, now: SystemTime) -> Result<CachedToken> {
+    let stdout = stdout.trim();
+    if !stdout.starts_with('{') {
+        if stdout.is_empty() {
+            bail!("token command printed no token");
+        }
+        return Ok(CachedToken {
+            token: stdout.to_string(),
+            expires_at: None,
+        });
+    }
+
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        #[serde(alias = "access_token")]
+        token: String,
+        #[serde(default)]
+        expires_in: Option<u64>,
+        #[serde(default)]
+        expires_at: Option<u64>,
+    }
+    let resp: TokenResponse =
+        serde_json::from_str(stdout).context("token command printed invalid JSON")?;
+    if resp.token.is_empty() {
+        bail!("token command printed an empty token");
+    }
+    let expires_at = match (resp.expires_in, resp.expires_at) {
+        (Some(secs), _) => Some(now + Duration::from_secs(secs)),
+        (None, Some(unix)) => Some(UNIX_EPOCH + Duration::from_secs(unix)),
+        (None, None) => None,
+    };
+    Ok(CachedToken {
+        token: resp.token,
+        expires_at,
+    })
+}
+
+impl CachedToken {
+    fn is_fresh(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .is_none_or(|at| now + TOKEN_EXPIRY_MARGIN < at)
+    }
+}
+
+impl AuthProvider for ExecAuthProvider {
+    fn name(&self) -> &str {
+        "exec"
+    }
+
+    fn resolve(&self, registry: &str) -> Result<Option<RegistryAuth>> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        let token = match cache.as_ref() {
+            Some(cached) if cached.is_fresh(SystemTime::now()) => cached.token.clone(),
+            _ => {
+                let minted = self.mint(registry)?;
+                let token = minted.token.clone();
+                *cache = Some(minted);
+                token
+            }
+        };
+        Ok(Some(RegistryAuth::Bearer(token)))
+    }
+
+    fn invalidate(&self) -> bool {
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        true
+    }
+}
+
+/// Providers tried in order; the first one with credentials wins.
+#[derive(Clone, Default)]
+pub struct AuthChain {
+    providers: Vec<Arc<dyn AuthProvider>>,
+}
+
+/// Credentials picked by an [`AuthChain`], and where they came from.
+#[derive(Clone)]
+pub struct ResolvedAuth {
+    pub auth: RegistryAuth,
+    /// Name of the provider that supplied `auth` (`None` when anonymous).
+    pub provider: Option<String>,
+    /// Names of every provider consulted, in order.
+    pub tried: Vec<String>,
+}
+
+impl ResolvedAuth {
+    /// Human-readable origin for error messages.
+    pub fn describe(&self) -> String {
+        match &self.provider {
+            Some(name) => format!("credentials from '{name}'"),
+            None if self.tried.is_empty() => "anonymous".to_string(),
+            None => format!("anonymous (tried: {})", self.tried.join(", ")),
+        }
+    }
+}
+
+impl AuthChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in providers: env, then exec (if configured), then docker-config.
+    pub fn from_env() -> Self {
+        let mut chain = Self::new().with(EnvAuthProvider);
+        if let Some(exec) = ExecAuthProvider::from_env() {
+            chain = chain.with(exec);
+        }
+        if let Some(docker) = DockerConfigAuthProvider::from_env() {
+            chain = chain.with(docker);
+        }
+        chain
+    }
+
+    /// Append a provider (lowest precedence so far).
+    pub fn with(mut self, provider: impl AuthProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    pub fn resolve(&self, registry: &str) -> Result<ResolvedAuth> {
+        let mut tried = Vec::new();
+        for provider in &self.providers {
+            tried.push(provider.name().to_string());
+            match provider.resolve(registry) {
+                Ok(Some(auth)) => {
+                    return Ok(ResolvedAuth {
+                        auth,
+                        provider: Some(provider.name().to_string()),
+                        tried,
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    return Err(anyhow!(
+                        "registry auth for {registry} failed in provider '{}' (tried: {}): {e:#}",
+                        provider.name(),
+                        tried.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(ResolvedAuth {
+            auth: RegistryAuth::Anonymous,
+            provider: None,
+            tried,
+        })
+    }
+
+    /// Invalidate the provider that supplied `resolved` and resolve again.
+    ///
+    /// Returns `None` when that provider cannot produce fresh credentials.
+    pub fn refresh(&self, registry: &str, resolved: &ResolvedAuth) -> Result<Option<ResolvedAuth>> {
+        let Some(name) = resolved.provider.as_deref() else {
+            return Ok(None);
+        };
+        let Some(provider) = self.providers.iter().find(|p| p.name() == name) else {
+            return Ok(None);
+        };
+        if !provider.invalidate() {
+            return Ok(None);
+        }
+        self.resolve(registry).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir =
+            std::env::temp_dir().join(format!("molt-auth-{prefix}-{}-{nanos}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    fn write_script(path: &Path, body: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        fs::write(path, format!("#!/bin/sh\n{body}")).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn basic(auth: Option<RegistryAuth>) -> Option<(String, String)> {
+        match auth {
+            Some(RegistryAuth::Basic(u, p)) => Some((u, p)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn docker_config_reads_inline_auths() {
+        let dir = temp_dir("docker-auths");
+        let config = dir.join("config.json");
+        fs::write(
+            &config,
+            r#"{
+  "auths": {
+    "https://registry.example.com/v1/": { "auth": "YWxpY2U6czNjcmV0" },
+    "other.example.com:5000": { "username": "bob", "password": "pw" },
+    "token.example.com": { "registrytoken": "tok" }
+  }
+}"#,
+        )
+        .unwrap();
+        let provider = DockerConfigAuthProvider::new(&config);
+
+        assert_eq!(
+            basic(provider.resolve("registry.example.com").unwrap()),
+            Some(("alice".to_string(), "s3cret".to_string()))
+        );
+        assert_eq!(
+            basic(provider.resolve("other.example.com:5000").unwrap()),
+            Some(("bob".to_string(), "pw".to_string()))
+        );
+        assert!(matches!(
+            provider.resolve("token.example.com").unwrap(),
+            Some(RegistryAuth::Bearer(t)) if t == "tok"
+        ));
+        assert!(provider.resolve("unknown.example.com").unwrap().is_none());
+
+        // A missing config file is not an error.
+        let missing = DockerConfigAuthProvider::new(dir.join("nope.json"));
+        assert!(missing.resolve("registry.example.com").unwrap().is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn docker_config_runs_cred_helpers_and_store() {
+        let dir = temp_dir("docker-helpers");
+        write_script(
+            &dir.join("docker-credential-ecr"),
+            r#"read host
+printf '{"ServerURL":"%s","Username":"AWS","Secret":"from-%s"}' "$host" "$host"
+"#,
+        );
+        write_script(
+            &dir.join("docker-credential-keychain"),
+            r#"read host
+if [ "$host" = "stored.example.com" ]; then
+  printf '{"ServerURL":"%s","Username":"<token>","Secret":"identity"}' "$host"
+else
+  echo "credentials not found in native keychain"
+  exit 1
+fi
+"#,
+        );
+        let config = dir.join("config.json");
+        fs::write(
+            &config,
+            r#"{
+  "credHelpers": { "123.dkr.ecr.example.com": "ecr" },
+  "credsStore": "keychain"
+}"#,
+        )
+        .unwrap();
+        let provider = DockerConfigAuthProvider::new(&config).with_helper_dir(&dir);
+
+        assert_eq!(
+            basic(provider.resolve("123.dkr.ecr.example.com").unwrap()),
+            Some((
+                "AWS".to_string(),
+                "from-123.dkr.ecr.example.com".to_string()
+            ))
+        );
+        assert!(matches!(
+            provider.resolve("stored.example.com").unwrap(),
+            Some(RegistryAuth::Bearer(t)) if t == "identity"
+        ));
+        assert!(provider.resolve("elsewhere.example.com").unwrap().is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn token_output_parses_bare_and_json_tokens() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+
+        let bare = parse_token_output("abc\n", now).unwrap();
+        assert_eq!(bare.token, "abc");
+        assert!(bare.expires_at.is_none());
+        assert!(bare.is_fresh(now + Duration::from_secs(86_400)));
+
+        let json = parse_token_output(r#"{"access_token":"xyz","expires_in":60}"#, now).unwrap();
+        assert_eq!(json.token, "xyz");
+        assert!(json.is_fresh(now));
+        assert!(!json.is_fresh(now + Duration::from_secs(31)));
+
+        let at = parse_token_output(r#"{"token":"t","expires_at":1100}"#, now).unwrap();
+        assert_eq!(at.expires_at, Some(UNIX_EPOCH + Duration::from_secs(1_100)));
+
+        assert!(parse_token_output("", now).is_err());
+        assert!(parse_token_output(r#"{"token":""}"#, now).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_provider_caches_until_invalidated() {
+        let dir = temp_dir("exec");
+        let script = dir.join("mint-token");
+        let counter = dir.join("count");
+        write_script(
+            &script,
+            &format!(
+                r#"n=$(cat {counter} 2>/dev/null || echo 0)
+n=$((n + 1))
+echo $n > {counter}
+printf '{{"token":"%s-%s","expires_in":3600}}' "$MOLT_REGISTRY_HOST" "$n"
+"#,
+                counter = counter.display()
+            ),
+        );
+        let provider = ExecAuthProvider::new(script.display().to_string());
+        let bearer = |p: &ExecAuthProvider| match p.resolve("r.example.com").unwrap() {
+            Some(RegistryAuth::Bearer(t)) => t,
+            _ => panic!("expected a bearer token"),
+        };
+
+        assert_eq!(bearer(&provider), "r.example.com-1");
+        assert_eq!(bearer(&provider), "r.example.com-1");
+        assert!(provider.invalidate());
+        assert_eq!(bearer(&provider), "r.example.com-2");
+
+        // The chain refreshes through the provider that supplied the token
+        // (this is what a 401 from the registry triggers).
+        let chain = AuthChain::new()
+            .with(Fixed("static", None))
+            .with(ExecAuthProvider::new(script.display().to_string()));
+        let first = chain.resolve("r.example.com").unwrap();
+        assert_eq!(first.provider.as_deref(), Some("exec"));
+        assert!(matches!(&first.auth, RegistryAuth::Bearer(t) if t == "r.example.com-3"));
+        let refreshed = chain.refresh("r.example.com", &first).unwrap().unwrap();
+        assert!(matches!(&refreshed.auth, RegistryAuth::Bearer(t) if t == "r.example.com-4"));
+
+        let failing = ExecAuthProvider::new("echo nope >&2; exit 3");
+        let err = failing.resolve("r.example.com").unwrap_err().to_string();
+        assert!(err.contains("nope"), "{err}");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    struct Fixed(&'static str, Option<RegistryAuth>);
+
+    impl AuthProvider for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn resolve(&self, _registry: &str) -> Result<Option<RegistryAuth>> {
+            Ok(self.1.clone())
+        }
+    }
+
+    struct Broken;
+
+    impl AuthProvider for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn resolve(&self, _registry: &str) -> Result<Option<RegistryAuth>> {
+            bail!("boom")
+        }
+    }
+
+    #[test]
+    fn chain_uses_first_provider_with_credentials() {
+        let chain = AuthChain::new()
+            .with(Fixed("a", None))
+            .with(Fixed(
+                "b",
+                Some(RegistryAuth::Basic("u".into(), "p".into())),
+            ))
+            .with(Broken);
+        let resolved = chain.resolve("r").unwrap();
+        assert_eq!(resolved.provider.as_deref(), Some("b"));
+        assert_eq!(resolved.describe(), "credentials from 'b'");
+        // Static providers cannot refresh.
+        assert!(chain.refresh("r", &resolved).unwrap().is_none());
+
+        let anonymous = AuthChain::new()
+            .with(Fixed("a", None))
+            .with(Fixed("c", None));
+        let resolved = anonymous.resolve("r").unwrap();
+        assert!(matches!(resolved.auth, RegistryAuth::Anonymous));
+        assert_eq!(resolved.describe(), "anonymous (tried: a, c)");
+    }
+
+    #[test]
+    fn chain_errors_name_the_providers_tried() {
+        let chain = AuthChain::new().with(Fixed("a", None)).with(Broken);
+        let err = chain.resolve("r.example.com").err().unwrap().to_string();
+        assert_eq!(
+            err,
+            "registry auth for r.example.com failed in provider 'broken' (tried: a, broken): boom"
+        );
+    }
+}
@@ -4,13 +4,19 @@
 //! (oras-project/rust-oci-client).
 //!
 //! It supports:
-//! - OCI Distribution API (`/v2`) helpers for pulling component wasm layers.
+//! - OCI Distribution API (`/v2`) helpers for pulling and pushing component wasm layers.
 //! - Molt extension API (`/wasm/v1`) helpers for WIT, interface index, and search.
 //!
 //! The Molt spec this targets is described in this repo's docs:
 //! - WIT referrers: `application/vnd.wasm.wit.v1+text`
 //! - `/wasm/v1` endpoints: `.../wit`, `.../interfaces`, `.../dependencies`, `/search`
 //!
+//! Both clients time out hung requests and retry idempotent reads on
+//! transient failures; see [`ClientConfig`].
+//!
+//! Credentials for the OCI client come from pluggable [`AuthProvider`]s
+//! (env vars, a token command, Docker's `config.json`); see [`AuthChain`].
+//!
 //! # Example (env-configured)
 //!
 //! ```no_run
@@ -30,13 +36,17 @@
 //! # Ok(()) }
 //! ```
 
+mod auth;
 mod media_types;
 mod oci;
+mod retry;
 mod util;
 mod wasm_v1;
 
+pub use auth::*;
 pub use media_types::*;
 pub use oci::*;
+pub use retry::ClientConfig;
 pub use util::RegistryEndpoint;
 pub use util::{auth_from_env, auth_from_header_line, sanitize_path_segment};
 pub use wasm_v1::*;
@@ -1,23 +1,39 @@
+use crate::auth::{AuthChain, ResolvedAuth};
 use crate::media_types::{
-    OCI_EMPTY_CONFIG_MEDIA_TYPE, OCI_IMAGE_MANIFEST_MEDIA_TYPE, WASM_COMPONENT_LAYER_MEDIA_TYPE,
-    WIT_ARTIFACT_TYPE_V1, WIT_LAYER_MEDIA_TYPE_V1,
+    OCI_EMPTY_CONFIG_MEDIA_TYPE, OCI_IMAGE_MANIFEST_MEDIA_TYPE, WASM_COMPONENT_ARTIFACT_TYPE,
+    WASM_COMPONENT_LAYER_MEDIA_TYPE, WASM_CONFIG_MEDIA_TYPE_V0, WIT_ARTIFACT_TYPE_V1,
+    WIT_LAYER_MEDIA_TYPE_V1,
 };
-use crate::util::{RegistryEndpoint, auth_from_env};
+use crate::retry::{ClientConfig, retry};
+use crate::util::{RegistryEndpoint, sha256_digest};
 use anyhow::{Context, Result, bail};
 use futures_util::StreamExt;
-use oci_client::client::ClientConfig;
+use oci_client::client::ClientConfig as OciClientConfig;
 use oci_client::client::{Config, ImageLayer};
+use oci_client::errors::OciDistributionError;
 use oci_client::manifest::OciDescriptor;
 use oci_client::manifest::OciImageManifest;
 use oci_client::secrets::RegistryAuth;
 use oci_client::{Client, Reference};
+use reqwest::header::HeaderValue;
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 
 #[derive(Clone)]
 pub struct OciWasmClient {
     endpoint: RegistryEndpoint,
-    auth: RegistryAuth,
+    session: Arc<RwLock<Session>>,
+    /// Set when credentials came from providers that may be refreshed.
+    chain: Option<AuthChain>,
+    config: ClientConfig,
+}
+
+/// Credentials and the `oci-client` instance (with its token cache) using them.
+#[derive(Clone)]
+struct Session {
+    auth: ResolvedAuth,
     client: Client,
 }
 
@@ -30,16 +46,51 @@ pub struct PulledComponentWasm {
 
 impl OciWasmClient {
     pub fn new(endpoint: RegistryEndpoint, auth: RegistryAuth) -> Result<Self> {
-        let cfg = ClientConfig {
-            user_agent: concat!("molt-registry-client/", env!("CARGO_PKG_VERSION")),
-            ..Default::default()
+        let auth = ResolvedAuth {
+            auth,
+            provider: None,
+            tried: Vec::new(),
         };
-        let client = Client::try_from(cfg).context("failed to create oci-client")?;
+        Self::with_session(endpoint, auth, None)
+    }
 
+    /// Resolve credentials through `chain`.
+    ///
+    /// If the registry answers 401 and the providing source can mint new
+    /// credentials (e.g. the exec provider), the request is retried once.
+    pub fn with_auth_chain(endpoint: RegistryEndpoint, chain: AuthChain) -> Result<Self> {
+        let auth = chain.resolve(&endpoint.registry)?;
+        Self::with_session(endpoint, auth, Some(chain))
+    }
+
+    fn with_session(
+        endpoint: RegistryEndpoint,
+        auth: ResolvedAuth,
+        chain: Option<AuthChain>,
+    ) -> Result<Self> {
+        let config = ClientConfig::default();
+        let session = Session {
+            auth,
+            client: new_oci_client(&config)?,
+        };
         Ok(Self {
             endpoint,
-            auth,
-            client,
+            session: Arc::new(RwLock::new(session)),
+            chain,
+            config,
+        })
+    }
+
+    /// Use `config` for timeouts and retries instead of the defaults.
+    pub fn with_config(self, config: ClientConfig) -> Result<Self> {
+        let session = Session {
+            auth: self.session().auth,
+            client: new_oci_client(&config)?,
+        };
+        Ok(Self {
+            session: Arc::new(RwLock::new(session)),
+            config,
+            ..self
         })
     }
 
@@ -52,20 +103,94 @@ impl OciWasmClient {
         }
 
         let endpoint = RegistryEndpoint::parse(&base_url)?;
-        let auth = auth_from_env()?;
-        Ok(Some(Self::new(endpoint, auth)?))
+        let client = Self::with_auth_chain(endpoint, AuthChain::from_env())?;
+        Ok(Some(client.with_config(ClientConfig::from_env()?)?))
     }
 
     pub fn endpoint(&self) -> &RegistryEndpoint {
         &self.endpoint
     }
 
-    pub fn auth(&self) -> &RegistryAuth {
-        &self.auth
+    pub fn auth(&self) -> RegistryAuth {
+        self.session().auth.auth
+    }
+
+    pub fn client(&self) -> Client {
+        self.session().client
+    }
+
+    fn session(&self) -> Session {
+        self.session
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Run `op` with the current credentials, refreshing them once on 401.
+    async fn with_auth_retry<T, F, Fut>(&self, op: F) -> Result<T>
+    where
+        F: Fn(Client, RegistryAuth) -> Fut,
+        Fut: Future<Output = oci_client::errors::Result<T>>,
+    {
+        let session = self.session();
+        let err = match op(session.client.clone(), session.auth.auth.clone()).await {
+            Err(e) if is_unauthorized(&e) => e,
+            other => return other.map_err(anyhow::Error::new),
+        };
+
+        let refreshed = match &self.chain {
+            Some(chain) => chain.refresh(&self.endpoint.registry, &session.auth)?,
+            None => None,
+        };
+        let Some(auth) = refreshed else {
+            return Err(self.rejected(err, &session.auth));
+        };
+        let session = Session {
+            auth,
+            client: new_oci_client(&self.config)?,
+        };
+        *self.session.write().unwrap_or_else(|e| e.into_inner()) = session.clone();
+
+        match op(session.client, session.auth.auth.clone()).await {
+            Err(e) if is_unauthorized(&e) => Err(self.rejected(e, &session.auth)),
+            other => other.map_err(anyhow::Error::new),
+        }
+    }
+
+    /// [`Self::with_auth_retry`] for idempotent reads, which are also
+    /// retried on transient failures (see [`ClientConfig`]).
+    async fn read_with_retry<T, F, Fut>(&self, op: F) -> Result<T>
+    where
+        F: Fn(Client, RegistryAuth) -> Fut,
+        Fut: Future<Output = oci_client::errors::Result<T>>,
+    {
+        retry(&self.config, || self.with_auth_retry(&op)).await
+    }
+
+    /// Download a blob, retrying transient failures. Each attempt starts
+    /// over, so a partial download is never kept.
+    async fn pull_blob(&self, image: &Reference, layer: &OciDescriptor) -> Result<Vec<u8>> {
+        self.pull_blob_with_progress(image, layer, &|_, _| {}).await
+    }
+
+    async fn pull_blob_with_progress(
+        &self,
+        image: &Reference,
+        layer: &OciDescriptor,
+        progress: &dyn Fn(u64, Option<u64>),
+    ) -> Result<Vec<u8>> {
+        retry(&self.config, || async {
+            pull_blob_to_bytes(&self.client(), image, layer, progress).await
+        })
+        .await
     }
 
-    pub fn client(&self) -> &Client {
-        &self.client
+    fn rejected(&self, err: OciDistributionError, auth: &ResolvedAuth) -> anyhow::Error {
+        anyhow::Error::new(err).context(format!(
+            "registry {} rejected {}",
+            self.endpoint.registry,
+            auth.describe()
+        ))
     }
 
     pub fn reference(&self, repo: &str, reference: &str) -> Result<Reference> {
@@ -92,10 +217,12 @@ impl OciWasmClient {
         reference: &str,
     ) -> Result<(OciImageManifest, String)> {
         let r = self.reference(repo, reference)?;
-        self.client
-            .pull_image_manifest(&r, &self.auth)
-            .await
-            .context("failed to pull manifest")
+        self.read_with_retry(|client, auth| {
+            let r = r.clone();
+            async move { client.pull_image_manifest(&r, &auth).await }
+        })
+        .await
+        .context("failed to pull manifest")
     }
 
     pub async fn pull_manifest_and_config_json(
@@ -105,8 +232,10 @@ impl OciWasmClient {
     ) -> Result<(OciImageManifest, String, Value)> {
         let r = self.reference(repo, reference)?;
         let (manifest, digest, config) = self
-            .client
-            .pull_manifest_and_config(&r, &self.auth)
+            .read_with_retry(|client, auth| {
+                let r = r.clone();
+                async move { client.pull_manifest_and_config(&r, &auth).await }
+            })
             .await
             .context("failed to pull manifest+config")?;
         let config_json: Value =
@@ -142,7 +271,7 @@ impl OciWasmClient {
         };
 
         let r = self.reference(repo, reference)?;
-        pull_blob_to_bytes(&self.client, &r, pick).await
+        self.pull_blob(&r, pick).await
     }
 
     /// Pull the component wasm bytes and return the resolved manifest and layer digests.
@@ -152,6 +281,20 @@ impl OciWasmClient {
         &self,
         repo: &str,
         reference: &str,
+    ) -> Result<PulledComponentWasm> {
+        self.pull_component_wasm_with_progress(repo, reference, &|_, _| {})
+            .await
+    }
+
+    /// Like [`Self::pull_component_wasm_with_digests`], calling `progress`
+    /// with the bytes downloaded so far and the total (from the response's
+    /// content length, else the layer size) as the layer arrives. A retried
+    /// download starts again from zero.
+    pub async fn pull_component_wasm_with_progress(
+        &self,
+        repo: &str,
+        reference: &str,
+        progress: &dyn Fn(u64, Option<u64>),
     ) -> Result<PulledComponentWasm> {
         let (manifest, manifest_digest) = self.pull_image_manifest(repo, reference).await?;
 
@@ -174,7 +317,7 @@ impl OciWasmClient {
 
         let layer_digest = pick.digest.clone();
         let r = self.reference(repo, reference)?;
-        let bytes = pull_blob_to_bytes(&self.client, &r, pick).await?;
+        let bytes = self.pull_blob_with_progress(&r, pick, progress).await?;
 
         Ok(PulledComponentWasm {
             manifest_digest,
@@ -213,6 +356,93 @@ impl OciWasmClient {
         Ok((manifest_digest, pick.digest.clone()))
     }
 
+    /// Push component wasm `bytes` as a single-layer artifact tagged `tag`.
+    ///
+    /// The manifest uses the component artifactType, an `application/wasm`
+    /// layer and a v0 wasm config, and carries `annotations` as manifest
+    /// annotations. Returns the digests of what was uploaded, so the result
+    /// can be pinned like a pulled component.
+    pub async fn push_component_wasm(
+        &self,
+        repo: &str,
+        tag: &str,
+        bytes: Vec<u8>,
+        annotations: BTreeMap<String, String>,
+    ) -> Result<PushComponentResult> {
+        let tag = tag.trim();
+        if tag.starts_with("sha256:") || tag.starts_with("sha512:") {
+            bail!("push needs a tag, not a digest ({tag})");
+        }
+        let r = self.reference(repo, tag)?;
+
+        let layers = vec![ImageLayer::new(
+            bytes,
+            WASM_COMPONENT_LAYER_MEDIA_TYPE.to_string(),
+            None,
+        )];
+        let layer_digest = layers[0].sha256_digest();
+        let config = serde_json::json!({
+            "architecture": "wasm",
+            "os": "wasip2",
+            "layerDigests": [layer_digest],
+        });
+        let config = Config::new(
+            serde_json::to_vec(&config).context("failed to encode config")?,
+            WASM_CONFIG_MEDIA_TYPE_V0.to_string(),
+            None,
+        );
+
+        let annotations = (!annotations.is_empty()).then_some(annotations);
+        let mut manifest =
+            oci_client::manifest::OciImageManifest::build(&layers, &config, annotations);
+        manifest.media_type = Some(OCI_IMAGE_MANIFEST_MEDIA_TYPE.to_string());
+        manifest.artifact_type = Some(WASM_COMPONENT_ARTIFACT_TYPE.to_string());
+
+        // Serialize the manifest here instead of letting `Client::push` do
+        // it, so the digest is that of the bytes uploaded. Resolving the tag
+        // afterwards could return the manifest of a concurrent push.
+        let manifest_bytes = serde_json::to_vec(&manifest).context("failed to encode manifest")?;
+        let manifest_digest = sha256_digest(&manifest_bytes);
+
+        let manifest_url = self
+            .with_auth_retry(|client, auth| {
+                let (r, layers, config, manifest_bytes) = (
+                    r.clone(),
+                    layers.clone(),
+                    config.clone(),
+                    manifest_bytes.clone(),
+                );
+                async move {
+                    client
+                        .store_auth_if_needed(r.resolve_registry(), &auth)
+                        .await;
+                    for layer in &layers {
+                        client
+                            .push_blob(&r, layer.data.clone(), &layer.sha256_digest())
+                            .await?;
+                    }
+                    client
+                        .push_blob(&r, config.data.clone(), &config.sha256_digest())
+                        .await?;
+                    client
+                        .push_manifest_raw(
+                            &r,
+                            manifest_bytes,
+                            HeaderValue::from_static(OCI_IMAGE_MANIFEST_MEDIA_TYPE),
+                        )
+                        .await
+                }
+            })
+            .await
+            .context("failed to push component")?;
+
+        Ok(PushComponentResult {
+            manifest_digest,
+            layer_digest,
+            manifest_url,
+        })
+    }
+
     /// Push a WIT referrer artifact (OCI Referrers) for the given component subject.
     ///
     /// `subject_reference` can be a tag or digest; the method resolves it to a
@@ -249,8 +479,14 @@ impl OciWasmClient {
         // Pull the subject manifest raw bytes to get size + digest.
         let subject_ref = self.reference(&repo, subject_reference)?;
         let (subject_bytes, subject_digest) = self
-            .client
-            .pull_manifest_raw(&subject_ref, &self.auth, &ACCEPT_MANIFEST)
+            .read_with_retry(|client, auth| {
+                let subject_ref = subject_ref.clone();
+                async move {
+                    client
+                        .pull_manifest_raw(&subject_ref, &auth, &ACCEPT_MANIFEST)
+                        .await
+                }
+            })
             .await
             .context("failed to pull subject manifest")?;
 
@@ -315,8 +551,19 @@ impl OciWasmClient {
         let wit_ref =
             Reference::with_tag(self.endpoint.registry.clone(), repo, wit_tag.to_string());
         let resp = self
-            .client
-            .push(&wit_ref, &layers, config, &self.auth, Some(manifest))
+            .with_auth_retry(|client, auth| {
+                let (wit_ref, layers, config, manifest) = (
+                    wit_ref.clone(),
+                    layers.clone(),
+                    config.clone(),
+                    manifest.clone(),
+                );
+                async move {
+                    client
+                        .push(&wit_ref, &layers, config, &auth, Some(manifest))
+                        .await
+                }
+            })
             .await
             .context("failed to push WIT referrer")?;
 
@@ -341,11 +588,14 @@ impl OciWasmClient {
         // Referrers API requires the subject to be referenced by digest.
         let subject_ref = self.reference(repo, &subject_digest)?;
         let artifact_type = artifact_type.unwrap_or(WIT_ARTIFACT_TYPE_V1);
-        let index = self
-            .client
-            .pull_referrers(&subject_ref, Some(artifact_type))
-            .await
-            .context("failed to pull referrers")?;
+        let index = retry(&self.config, || async {
+            self.client()
+                .pull_referrers(&subject_ref, Some(artifact_type))
+                .await
+                .map_err(anyhow::Error::new)
+        })
+        .await
+        .context("failed to pull referrers")?;
 
         if index.manifests.is_empty() {
             bail!("no referrers found for subject {subject_digest}");
@@ -358,8 +608,10 @@ impl OciWasmClient {
 
         let referrer_ref = self.reference(repo, &referrer_digest)?;
         let (referrer_manifest, _referrer_digest) = self
-            .client
-            .pull_image_manifest(&referrer_ref, &self.auth)
+            .read_with_retry(|client, auth| {
+                let referrer_ref = referrer_ref.clone();
+                async move { client.pull_image_manifest(&referrer_ref, &auth).await }
+            })
             .await
             .context("failed to pull referrer manifest")?;
 
@@ -370,11 +622,18 @@ impl OciWasmClient {
             .or_else(|| referrer_manifest.layers.first())
             .context("referrer manifest has no layers")?;
 
-        let bytes = pull_blob_to_bytes(&self.client, &referrer_ref, layer).await?;
+        let bytes = self.pull_blob(&referrer_ref, layer).await?;
         String::from_utf8(bytes).context("WIT blob is not valid UTF-8")
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PushComponentResult {
+    pub manifest_digest: String,
+    pub layer_digest: String,
+    pub manifest_url: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct PushWitResult {
     pub subject_digest: String,
@@ -382,20 +641,44 @@ pub struct PushWitResult {
     pub config_url: String,
 }
 
+fn new_oci_client(config: &ClientConfig) -> Result<Client> {
+    let cfg = OciClientConfig {
+        user_agent: concat!("molt-registry-client/", env!("CARGO_PKG_VERSION")),
+        read_timeout: config.timeout,
+        connect_timeout: config.timeout,
+        ..Default::default()
+    };
+    Client::try_from(cfg).context("failed to create oci-client")
+}
+
+fn is_unauthorized(err: &OciDistributionError) -> bool {
+    matches!(
+        err,
+        OciDistributionError::UnauthorizedError { .. }
+            | OciDistributionError::AuthenticationFailure(_)
+    )
+}
+
 async fn pull_blob_to_bytes(
     client: &Client,
     image: &Reference,
     layer: &oci_client::manifest::OciDescriptor,
+    progress: &dyn Fn(u64, Option<u64>),
 ) -> Result<Vec<u8>> {
     let mut stream = client
         .pull_blob_stream(image, layer)
         .await
         .context("failed to pull blob stream")?;
 
+    let total = stream
+        .content_length
+        .or_else(|| u64::try_from(layer.size).ok().filter(|&size| size > 0));
     let mut out = Vec::with_capacity(stream.content_length.unwrap_or(0) as usize);
+    progress(0, total);
     while let Some(chunk) = stream.next().await {
         let chunk = chunk.context("blob stream error")?;
         out.extend_from_slice(&chunk);
+        progress(out.len() as u64, total);
     }
     Ok(out)
 }
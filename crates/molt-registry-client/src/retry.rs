@@ -0,0 +1,247 @@
+//! Timeouts and retries for registry requests.
+//!
+//! Only idempotent reads (GET/HEAD) go through [`retry`]; pushes are sent once.
+
+use anyhow::{Context, Result, anyhow};
+use oci_client::errors::OciDistributionError;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Timeout and retry settings shared by [`crate::OciWasmClient`] and
+/// [`crate::WasmV1Client`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// Timeout for connecting and for each wait on response data, so a slow
+    /// download that keeps making progress is not cut off. `None` waits
+    /// forever.
+    pub timeout: Option<Duration>,
+    /// Extra attempts after the first one fails with a retryable error.
+    pub retries: u32,
+    /// Delay before the first retry; it doubles for each further retry (up
+    /// to 30s) and is jittered down by up to half.
+    pub backoff_base: Duration,
+    /// HTTP statuses worth retrying (connection errors and timeouts always are).
+    pub retry_on_status: Vec<u16>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(60)),
+            retries: 2,
+            backoff_base: Duration::from_millis(500),
+            retry_on_status: vec![429, 500, 502, 503, 504],
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Defaults, overridden by `MOLT_REGISTRY_TIMEOUT_SECS` (`0` disables the
+    /// timeout) and `MOLT_REGISTRY_RETRIES`.
+    pub fn from_env() -> Result<Self> {
+        let mut config = Self::default();
+        if let Some(secs) = env_u64("MOLT_REGISTRY_TIMEOUT_SECS")? {
+            config.timeout = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+        if let Some(retries) = env_u64("MOLT_REGISTRY_RETRIES")? {
+            config.retries = u32::try_from(retries)
+                .map_err(|_| anyhow!("MOLT_REGISTRY_RETRIES is too large: {retries}"))?;
+        }
+        Ok(config)
+    }
+
+    /// Delay before retry number `retry` (1-based).
+    fn backoff(&self, retry: u32) -> Duration {
+        let full = self
+            .backoff_base
+            .saturating_mul(1u32.checked_shl(retry - 1).unwrap_or(u32::MAX))
+            .min(MAX_BACKOFF);
+        // Uniform in [full / 2, full]; the hasher's random keys are enough here.
+        let jitter = (RandomState::new().build_hasher().finish() % 1001) as f64 / 1000.0;
+        full.mul_f64(0.5 + 0.5 * jitter)
+    }
+
+    fn is_retryable(&self, err: &anyhow::Error) -> bool {
+        err.chain().any(|cause| {
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                return e.is_connect()
+                    || e.is_timeout()
+                    || e.status()
+                        .is_some_and(|s| self.retry_on_status.contains(&s.as_u16()));
+            }
+            if let Some(HttpStatus { code, .. }) = cause.downcast_ref::<HttpStatus>() {
+                return self.retry_on_status.contains(code);
+            }
+            if let Some(OciDistributionError::ServerError { code, .. }) =
+                cause.downcast_ref::<OciDistributionError>()
+            {
+                return self.retry_on_status.contains(code);
+            }
+            if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                use std::io::ErrorKind::*;
+                return matches!(
+                    e.kind(),
+                    TimedOut | ConnectionReset | ConnectionAborted | BrokenPipe | UnexpectedEof
+                );
+            }
+            false
+        })
+    }
+}
+
+fn env_u64(key: &str) -> Result<Option<u64>> {
+    match std::env::var(key) {
+        Ok(v) if !v.trim().is_empty() => {
+            v.trim().parse().map(Some).with_context(|| {
+                format!("invalid {key}: expected a non-negative integer, got '{v}'")
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+/// A non-success HTTP status from a `/wasm/v1` call, kept in the error chain
+/// so [`retry`] can tell whether it is worth another attempt.
+#[derive(Debug)]
+pub(crate) struct HttpStatus {
+    pub(crate) code: u16,
+    pub(crate) body: String,
+}
+
+impl std::fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "registry returned HTTP {}.\n{}", self.code, self.body)
+    }
+}
+
+impl std::error::Error for HttpStatus {}
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or runs out
+/// of attempts. After more than one attempt, the error lists every failure.
+pub(crate) async fn retry<T, F, Fut>(config: &ClientConfig, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut failures = Vec::new();
+    loop {
+        if !failures.is_empty() {
+            let delay = config.backoff(failures.len() as u32);
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+        }
+        let err = match op().await {
+            Ok(v) => return Ok(v),
+            Err(err) => err,
+        };
+        let again = failures.len() < config.retries as usize && config.is_retryable(&err);
+        failures.push(err);
+        if !again {
+            return Err(summarize(failures));
+        }
+    }
+}
+
+fn summarize(mut failures: Vec<anyhow::Error>) -> anyhow::Error {
+    if failures.len() == 1 {
+        return failures.remove(0);
+    }
+    let mut msg = format!("{} attempts failed:", failures.len());
+    for (i, err) in failures.iter().enumerate() {
+        msg.push_str(&format!("\n  {}: {err:#}", i + 1));
+    }
+    // Keep the last error as the source so callers can still inspect it.
+    let last = failures.pop().expect("at least two failures");
+    last.context(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn status(code: u16) -> anyhow::Error {
+        anyhow::Error::new(HttpStatus {
+            code,
+            body: "busy".to_string(),
+        })
+    }
+
+    fn config(retries: u32) -> ClientConfig {
+        ClientConfig {
+            retries,
+            backoff_base: Duration::ZERO,
+            ..ClientConfig::default()
+        }
+    }
+
+    fn run<T>(fut: impl Future<Output = T>) -> T {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(fut)
+    }
+
+    #[test]
+    fn retryable_errors_are_retried_until_success() {
+        let calls = Cell::new(0);
+        let out = run(retry(&config(2), || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move { if n < 3 { Err(status(503)) } else { Ok(n) } }
+        }));
+        assert_eq!(out.unwrap(), 3);
+    }
+
+    #[test]
+    fn the_final_error_lists_every_attempt() {
+        let calls = Cell::new(0);
+        let err = run(retry(&config(2), || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(status(502)) }
+        }))
+        .unwrap_err();
+        assert_eq!(calls.get(), 3);
+        assert_eq!(
+            format!("{err:#}").lines().next(),
+            Some("3 attempts failed:")
+        );
+        assert!(format!("{err:#}").contains("\n  3: registry returned HTTP 502.\nbusy"));
+        assert!(err.downcast_ref::<HttpStatus>().is_some());
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let calls = Cell::new(0);
+        let err = run(retry(&config(2), || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(status(404).context("not found")) }
+        }))
+        .unwrap_err();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(err.to_string(), "not found");
+
+        let timeout = std::io::Error::new(std::io::ErrorKind::TimedOut, "slow");
+        assert!(config(0).is_retryable(&anyhow::Error::new(timeout).context("blob stream error")));
+    }
+
+    #[test]
+    fn backoff_doubles_with_jitter() {
+        let config = ClientConfig {
+            backoff_base: Duration::from_millis(100),
+            ..ClientConfig::default()
+        };
+        for (retry, full) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = config.backoff(retry);
+            let full = Duration::from_millis(full);
+            assert!(delay >= full / 2 && delay <= full, "{retry}: {delay:?}");
+        }
+        assert!(config.backoff(40) <= MAX_BACKOFF);
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::{Context, Result, bail};
 use base64::Engine;
 use oci_client::secrets::RegistryAuth;
 use reqwest::RequestBuilder;
+use sha2::{Digest, Sha256};
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -134,6 +135,13 @@ pub fn apply_reqwest_auth(req: RequestBuilder, auth: &RegistryAuth) -> RequestBu
     }
 }
 
+/// `sha256:<hex>` digest of `bytes`, in the form OCI registries use.
+pub fn sha256_digest(bytes: &[u8]) -> String {
+    let hash = Sha256::digest(bytes);
+    let hex: String = hash.iter().map(|b| format!("{b:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
 /// Sanitize a string for use as a directory name segment on common filesystems.
 ///
 /// This is used for cache paths like `.wacli/framework/<repo>/<reference>/...`.
@@ -173,4 +181,12 @@ mod tests {
     fn sanitize_path_segment_replaces_bad_chars() {
         assert_eq!(sanitize_path_segment("sha256:abc/def"), "sha256_abc_def");
     }
+
+    #[test]
+    fn sha256_digest_is_prefixed_lowercase_hex() {
+        assert_eq!(
+            sha256_digest(b""),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::media_types::WIT_ARTIFACT_TYPE_V1;
+use crate::retry::{ClientConfig, HttpStatus, retry};
 use crate::util::{RegistryEndpoint, apply_reqwest_auth, auth_from_env};
 use anyhow::{Context, Result, bail};
 use oci_client::secrets::RegistryAuth;
@@ -9,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub struct WasmV1Client {
     endpoint: RegistryEndpoint,
     auth: RegistryAuth,
+    headers: HeaderMap,
+    config: ClientConfig,
     http: reqwest::Client,
 }
 
@@ -33,15 +36,24 @@ impl WasmV1Client {
         );
         headers.extend(extra);
 
-        let http = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()
-            .context("failed to build HTTP client")?;
-
+        let config = ClientConfig::default();
+        let http = http_client(&headers, &config)?;
         Ok(Self {
             endpoint,
             auth,
+            headers,
+            config,
+            http,
+        })
+    }
+
+    /// Use `config` for timeouts and retries instead of the defaults.
+    pub fn with_config(self, config: ClientConfig) -> Result<Self> {
+        let http = http_client(&self.headers, &config)?;
+        Ok(Self {
+            config,
             http,
+            ..self
         })
     }
 
@@ -55,13 +67,42 @@ impl WasmV1Client {
 
         let endpoint = RegistryEndpoint::parse(&base_url)?;
         let auth = auth_from_env()?;
-        Ok(Some(Self::new(endpoint, auth)?))
+        Ok(Some(
+            Self::new(endpoint, auth)?.with_config(ClientConfig::from_env()?)?,
+        ))
     }
 
     pub fn endpoint(&self) -> &RegistryEndpoint {
         &self.endpoint
     }
 
+    /// Send a GET, retrying transient failures (see [`ClientConfig`]).
+    /// Statuses that are not retried are returned for the caller to map.
+    async fn get(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> Result<(reqwest::StatusCode, HeaderMap, String)> {
+        retry(&self.config, || async {
+            let req = req.try_clone().context("request cannot be retried")?;
+            let resp = req.send().await.context("failed to call registry")?;
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp
+                .text()
+                .await
+                .context("failed to read registry response")?;
+            if self.config.retry_on_status.contains(&status.as_u16()) {
+                return Err(HttpStatus {
+                    code: status.as_u16(),
+                    body,
+                }
+                .into());
+            }
+            Ok((status, headers, body))
+        })
+        .await
+    }
+
     fn url(&self, path: &str) -> String {
         let base = self.endpoint.base_url.as_str().trim_end_matches('/');
         let path = path.trim_start_matches('/');
@@ -92,26 +133,17 @@ impl WasmV1Client {
             req = req.query(&[("package", package)]);
         }
 
-        let resp = req.send().await.context("failed to call registry")?;
-        let status = resp.status();
+        let (status, headers, body) = self.get(req).await?;
 
-        let etag = resp
-            .headers()
-            .get("ETag")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
-        let subject = resp
-            .headers()
-            .get("OCI-Subject")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
-        let referrer_digest = resp
-            .headers()
-            .get("WIT-Referrer-Digest")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
-
-        let body = resp.text().await.unwrap_or_default();
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let etag = header("ETag");
+        let subject = header("OCI-Subject");
+        let referrer_digest = header("WIT-Referrer-Digest");
 
         match status.as_u16() {
             200 => Ok(WitTextResponse {
@@ -134,9 +166,7 @@ impl WasmV1Client {
             reference.trim_matches('/')
         ));
         let req = apply_reqwest_auth(self.http.get(url), &self.auth);
-        let resp = req.send().await.context("failed to call registry")?;
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
+        let (status, _, body) = self.get(req).await?;
         if !status.is_success() {
             bail!("registry returned HTTP {}.\n{body}", status.as_u16());
         }
@@ -150,9 +180,7 @@ impl WasmV1Client {
             reference.trim_matches('/')
         ));
         let req = apply_reqwest_auth(self.http.get(url), &self.auth);
-        let resp = req.send().await.context("failed to call registry")?;
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
+        let (status, _, body) = self.get(req).await?;
         if !status.is_success() {
             bail!("registry returned HTTP {}.\n{body}", status.as_u16());
         }
@@ -179,9 +207,7 @@ impl WasmV1Client {
             req = req.query(&[("cursor", cursor)]);
         }
 
-        let resp = req.send().await.context("failed to call registry")?;
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
+        let (status, _, body) = self.get(req).await?;
         if !status.is_success() {
             bail!("registry returned HTTP {}.\n{body}", status.as_u16());
         }
@@ -189,6 +215,14 @@ impl WasmV1Client {
     }
 }
 
+fn http_client(headers: &HeaderMap, config: &ClientConfig) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().default_headers(headers.clone());
+    if let Some(timeout) = config.timeout {
+        builder = builder.read_timeout(timeout).connect_timeout(timeout);
+    }
+    builder.build().context("failed to build HTTP client")
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct WitRequest {
     pub artifact_type: Option<String>,
@@ -86,6 +86,7 @@ impl<_T: Send + 'static> PipePluginPre<_T> {
 #[derive(Clone)]
 pub struct PipePluginIndices {
     interface0: exports::wacli::cli::pipe::GuestIndices,
+    interface1: exports::wacli::cli::pipe_stream::GuestIndices,
 }
 
 /// Auto-generated bindings for an instance a component which
@@ -115,6 +116,7 @@ pub struct PipePluginIndices {
 /// [`Linker`]: wasmtime::component::Linker
 pub struct PipePlugin {
     interface0: exports::wacli::cli::pipe::Guest,
+    interface1: exports::wacli::cli::pipe_stream::Guest,
 }
 const _: () = {
     #[allow(unused_imports)]
@@ -133,7 +135,11 @@ const _: () = {
             let _instance_type = _instance_pre.instance_type();
 
             let interface0 = exports::wacli::cli::pipe::GuestIndices::new(_instance_pre)?;
-            Ok(PipePluginIndices { interface0 })
+            let interface1 = exports::wacli::cli::pipe_stream::GuestIndices::new(_instance_pre)?;
+            Ok(PipePluginIndices {
+                interface0,
+                interface1,
+            })
         }
 
         /// Uses the indices stored in `self` to load an instance
@@ -150,7 +156,11 @@ const _: () = {
             let _instance = instance;
 
             let interface0 = self.interface0.load(&mut store, &_instance)?;
-            Ok(PipePlugin { interface0 })
+            let interface1 = self.interface1.load(&mut store, &_instance)?;
+            Ok(PipePlugin {
+                interface0,
+                interface1,
+            })
         }
     }
     impl PipePlugin {
@@ -206,6 +216,10 @@ const _: () = {
         pub fn wacli_cli_pipe(&self) -> &exports::wacli::cli::pipe::Guest {
             &self.interface0
         }
+
+        pub fn wacli_cli_pipe_stream(&self) -> &exports::wacli::cli::pipe_stream::Guest {
+            &self.interface1
+        }
     }
 };
 pub mod wacli {
@@ -312,6 +326,12 @@ pub mod wacli {
                 assert!(68 == <CommandMeta as wasmtime::component::ComponentType>::SIZE32);
                 assert!(4 == <CommandMeta as wasmtime::component::ComponentType>::ALIGN32);
             };
+            /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+            /// the same case count, so older plugins would stop composing). Until a
+            /// new major version adds an exit-code case, a `failed` message of the
+            /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+            /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+            /// `unknown-command` with 127 and `failed` / `io` with 1.
             #[derive(
                 wasmtime::component::ComponentType,
                 wasmtime::component::Lift,
@@ -458,6 +478,40 @@ pub mod wacli {
                 assert!(24 == <PipeInfo as wasmtime::component::ComponentType>::SIZE32);
                 assert!(4 == <PipeInfo as wasmtime::component::ComponentType>::ALIGN32);
             };
+            /// A discovered pipe with the descriptive fields it embeds in its
+            /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+            #[derive(
+                wasmtime::component::ComponentType,
+                wasmtime::component::Lift,
+                wasmtime::component::Lower,
+            )]
+            #[component(record)]
+            #[derive(Clone)]
+            pub struct PipeDetails {
+                #[component(name = "info")]
+                pub info: PipeInfo,
+                #[component(name = "input-types")]
+                pub input_types:
+                    wasmtime::component::__internal::Vec<wasmtime::component::__internal::String>,
+                #[component(name = "output-type")]
+                pub output_type: wasmtime::component::__internal::String,
+                #[component(name = "version")]
+                pub version: wasmtime::component::__internal::String,
+            }
+            impl core::fmt::Debug for PipeDetails {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.debug_struct("PipeDetails")
+                        .field("info", &self.info)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+            const _: () = {
+                assert!(48 == <PipeDetails as wasmtime::component::ComponentType>::SIZE32);
+                assert!(4 == <PipeDetails as wasmtime::component::ComponentType>::ALIGN32);
+            };
             pub trait HostWithStore: wasmtime::component::HasData {}
             impl<_T: ?Sized> HostWithStore for _T where _T: wasmtime::component::HasData {}
             pub trait Host {}
@@ -591,6 +645,143 @@ pub mod exports {
                     }
                 }
             }
+
+            #[allow(clippy::all)]
+            pub mod pipe_stream {
+                #[allow(unused_imports)]
+                use wasmtime::component::__internal::{anyhow, Box};
+
+                pub type PipeError = super::super::super::super::wacli::cli::types::PipeError;
+                const _: () = {
+                    assert!(12 == <PipeError as wasmtime::component::ComponentType>::SIZE32);
+                    assert!(4 == <PipeError as wasmtime::component::ComponentType>::ALIGN32);
+                };
+                #[derive(Clone)]
+                pub struct Guest {
+                    process_start: wasmtime::component::Func,
+                    process_chunk: wasmtime::component::Func,
+                    process_end: wasmtime::component::Func,
+                }
+                #[derive(Clone)]
+                pub struct GuestIndices {
+                    process_start: wasmtime::component::ComponentExportIndex,
+                    process_chunk: wasmtime::component::ComponentExportIndex,
+                    process_end: wasmtime::component::ComponentExportIndex,
+                }
+                impl GuestIndices {
+                    /// Constructor for [`GuestIndices`] which takes a
+                    /// [`Component`](wasmtime::component::Component) as input and can be executed
+                    /// before instantiation.
+                    ///
+                    /// This constructor can be used to front-load string lookups to find exports
+                    /// within a component.
+                    pub fn new<_T>(
+                        _instance_pre: &wasmtime::component::InstancePre<_T>,
+                    ) -> wasmtime::Result<GuestIndices> {
+                        let instance = _instance_pre
+                            .component()
+                            .get_export_index(None, "wacli:cli/pipe-stream@2.0.0")
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "no exported instance named `wacli:cli/pipe-stream@2.0.0`"
+                                )
+                            })?;
+                        let mut lookup = move |name| {
+                            _instance_pre
+                                .component()
+                                .get_export_index(Some(&instance), name)
+                                .ok_or_else(|| {
+                                    anyhow::anyhow!(
+                                        "instance export `wacli:cli/pipe-stream@2.0.0` does \
+                not have export `{name}`"
+                                    )
+                                })
+                        };
+                        let _ = &mut lookup;
+                        let process_start = lookup("process-start")?;
+                        let process_chunk = lookup("process-chunk")?;
+                        let process_end = lookup("process-end")?;
+                        Ok(GuestIndices {
+                            process_start,
+                            process_chunk,
+                            process_end,
+                        })
+                    }
+
+                    pub fn load(
+                        &self,
+                        mut store: impl wasmtime::AsContextMut,
+                        instance: &wasmtime::component::Instance,
+                    ) -> wasmtime::Result<Guest> {
+                        let _instance = instance;
+                        let _instance_pre = _instance.instance_pre(&store);
+                        let _instance_type = _instance_pre.instance_type();
+                        let mut store = store.as_context_mut();
+                        let _ = &mut store;
+                        let process_start = *_instance.get_typed_func::<(&[wasmtime::component::__internal::String], ), (Result<u32,PipeError>, )>(&mut store, &self.process_start)?.func();
+                        let process_chunk = *_instance.get_typed_func::<(u32, &[u8], ), (Result<wasmtime::component::__internal::Vec<u8>,PipeError>, )>(&mut store, &self.process_chunk)?.func();
+                        let process_end = *_instance.get_typed_func::<(u32, ), (Result<wasmtime::component::__internal::Vec<u8>,PipeError>, )>(&mut store, &self.process_end)?.func();
+                        Ok(Guest {
+                            process_start,
+                            process_chunk,
+                            process_end,
+                        })
+                    }
+                }
+                impl Guest {
+                    /// Start a run with `options`; the id names it in the calls below.
+                    pub fn call_process_start<S: wasmtime::AsContextMut>(
+                        &self,
+                        mut store: S,
+                        arg0: &[wasmtime::component::__internal::String],
+                    ) -> wasmtime::Result<Result<u32, PipeError>> {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (&[wasmtime::component::__internal::String],),
+                                (Result<u32, PipeError>,),
+                            >::new_unchecked(self.process_start)
+                        };
+                        let (ret0,) = callee.call(store.as_context_mut(), (arg0,))?;
+                        callee.post_return(store.as_context_mut())?;
+                        Ok(ret0)
+                    }
+                    /// Feed the next part of the input; returns whatever output is ready.
+                    pub fn call_process_chunk<S: wasmtime::AsContextMut>(
+                        &self,
+                        mut store: S,
+                        arg0: u32,
+                        arg1: &[u8],
+                    ) -> wasmtime::Result<Result<wasmtime::component::__internal::Vec<u8>, PipeError>>
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (u32, &[u8]),
+                                (Result<wasmtime::component::__internal::Vec<u8>, PipeError>,),
+                            >::new_unchecked(self.process_chunk)
+                        };
+                        let (ret0,) = callee.call(store.as_context_mut(), (arg0, arg1))?;
+                        callee.post_return(store.as_context_mut())?;
+                        Ok(ret0)
+                    }
+                    /// End the input and the session; returns the rest of the output.
+                    pub fn call_process_end<S: wasmtime::AsContextMut>(
+                        &self,
+                        mut store: S,
+                        arg0: u32,
+                    ) -> wasmtime::Result<Result<wasmtime::component::__internal::Vec<u8>, PipeError>>
+                    {
+                        let callee = unsafe {
+                            wasmtime::component::TypedFunc::<
+                                (u32,),
+                                (Result<wasmtime::component::__internal::Vec<u8>, PipeError>,),
+                            >::new_unchecked(self.process_end)
+                        };
+                        let (ret0,) = callee.call(store.as_context_mut(), (arg0,))?;
+                        callee.post_return(store.as_context_mut())?;
+                        Ok(ret0)
+                    }
+                }
+            }
         }
     }
 }
@@ -306,6 +306,12 @@ pub mod wacli {
                 assert!(68 == <CommandMeta as wasmtime::component::ComponentType>::SIZE32);
                 assert!(4 == <CommandMeta as wasmtime::component::ComponentType>::ALIGN32);
             };
+            /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+            /// the same case count, so older plugins would stop composing). Until a
+            /// new major version adds an exit-code case, a `failed` message of the
+            /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+            /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+            /// `unknown-command` with 127 and `failed` / `io` with 1.
             #[derive(
                 wasmtime::component::ComponentType,
                 wasmtime::component::Lift,
@@ -452,6 +458,40 @@ pub mod wacli {
                 assert!(24 == <PipeInfo as wasmtime::component::ComponentType>::SIZE32);
                 assert!(4 == <PipeInfo as wasmtime::component::ComponentType>::ALIGN32);
             };
+            /// A discovered pipe with the descriptive fields it embeds in its
+            /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+            #[derive(
+                wasmtime::component::ComponentType,
+                wasmtime::component::Lift,
+                wasmtime::component::Lower,
+            )]
+            #[component(record)]
+            #[derive(Clone)]
+            pub struct PipeDetails {
+                #[component(name = "info")]
+                pub info: PipeInfo,
+                #[component(name = "input-types")]
+                pub input_types:
+                    wasmtime::component::__internal::Vec<wasmtime::component::__internal::String>,
+                #[component(name = "output-type")]
+                pub output_type: wasmtime::component::__internal::String,
+                #[component(name = "version")]
+                pub version: wasmtime::component::__internal::String,
+            }
+            impl core::fmt::Debug for PipeDetails {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.debug_struct("PipeDetails")
+                        .field("info", &self.info)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+            const _: () = {
+                assert!(48 == <PipeDetails as wasmtime::component::ComponentType>::SIZE32);
+                assert!(4 == <PipeDetails as wasmtime::component::ComponentType>::ALIGN32);
+            };
             pub trait HostWithStore: wasmtime::component::HasData {}
             impl<_T: ?Sized> HostWithStore for _T where _T: wasmtime::component::HasData {}
             pub trait Host {}
@@ -492,6 +532,56 @@ pub mod wacli {
                 assert!(24 == <PipeInfo as wasmtime::component::ComponentType>::SIZE32);
                 assert!(4 == <PipeInfo as wasmtime::component::ComponentType>::ALIGN32);
             };
+            pub type PipeDetails = super::super::super::wacli::cli::types::PipeDetails;
+            const _: () = {
+                assert!(48 == <PipeDetails as wasmtime::component::ComponentType>::SIZE32);
+                assert!(4 == <PipeDetails as wasmtime::component::ComponentType>::ALIGN32);
+            };
+            /// Why `open-pipe` failed.
+            #[derive(
+                wasmtime::component::ComponentType,
+                wasmtime::component::Lift,
+                wasmtime::component::Lower,
+            )]
+            #[component(variant)]
+            #[derive(Clone)]
+            pub enum PipeLoadError {
+                /// No pipe by that name (a typo, or not installed).
+                #[component(name = "not-found")]
+                NotFound(wasmtime::component::__internal::String),
+                /// The name itself is malformed.
+                #[component(name = "invalid-name")]
+                InvalidName(wasmtime::component::__internal::String),
+                /// The pipe exists but could not be read or instantiated.
+                #[component(name = "load-failed")]
+                LoadFailed(wasmtime::component::__internal::String),
+            }
+            impl core::fmt::Debug for PipeLoadError {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    match self {
+                        PipeLoadError::NotFound(e) => {
+                            f.debug_tuple("PipeLoadError::NotFound").field(e).finish()
+                        }
+                        PipeLoadError::InvalidName(e) => f
+                            .debug_tuple("PipeLoadError::InvalidName")
+                            .field(e)
+                            .finish(),
+                        PipeLoadError::LoadFailed(e) => {
+                            f.debug_tuple("PipeLoadError::LoadFailed").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl core::fmt::Display for PipeLoadError {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl core::error::Error for PipeLoadError {}
+            const _: () = {
+                assert!(12 == <PipeLoadError as wasmtime::component::ComponentType>::SIZE32);
+                assert!(4 == <PipeLoadError as wasmtime::component::ComponentType>::ALIGN32);
+            };
             pub use super::super::super::__with_name0 as Pipe;
             pub trait HostPipeWithStore: wasmtime::component::HasData {}
             impl<_T: ?Sized> HostPipeWithStore for _T where _T: wasmtime::component::HasData {}
@@ -505,6 +595,27 @@ pub mod wacli {
                         wasmtime::component::__internal::String,
                     >,
                 ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError>;
+                /// Start a chunked run (see `pipe-stream`): feed the input to
+                /// `process-chunk` and finish with `process-end`, collecting the output
+                /// each returns. The options are checked like those of `process`.
+                fn process_start(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    options: wasmtime::component::__internal::Vec<
+                        wasmtime::component::__internal::String,
+                    >,
+                ) -> Result<u32, PipeError>;
+                fn process_chunk(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    session: u32,
+                    bytes: wasmtime::component::__internal::Vec<u8>,
+                ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError>;
+                fn process_end(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    session: u32,
+                ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError>;
                 fn drop(
                     &mut self,
                     rep: wasmtime::component::Resource<Pipe>,
@@ -524,6 +635,33 @@ pub mod wacli {
                 ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError> {
                     HostPipe::process(*self, self_, input, options)
                 }
+                /// Start a chunked run (see `pipe-stream`): feed the input to
+                /// `process-chunk` and finish with `process-end`, collecting the output
+                /// each returns. The options are checked like those of `process`.
+                fn process_start(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    options: wasmtime::component::__internal::Vec<
+                        wasmtime::component::__internal::String,
+                    >,
+                ) -> Result<u32, PipeError> {
+                    HostPipe::process_start(*self, self_, options)
+                }
+                fn process_chunk(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    session: u32,
+                    bytes: wasmtime::component::__internal::Vec<u8>,
+                ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError> {
+                    HostPipe::process_chunk(*self, self_, session, bytes)
+                }
+                fn process_end(
+                    &mut self,
+                    self_: wasmtime::component::Resource<Pipe>,
+                    session: u32,
+                ) -> Result<wasmtime::component::__internal::Vec<u8>, PipeError> {
+                    HostPipe::process_end(*self, self_, session)
+                }
 
                 fn drop(
                     &mut self,
@@ -536,6 +674,12 @@ pub mod wacli {
             impl<_T: ?Sized> HostWithStore for _T where _T: wasmtime::component::HasData + HostPipeWithStore {}
             pub trait Host: HostPipe {
                 fn list_pipes(&mut self) -> wasmtime::component::__internal::Vec<PipeInfo>;
+                /// Like `list-pipes`, with each pipe's types and version. Read from the
+                /// pipes' metadata sections without instantiating them.
+                fn describe_pipes(&mut self) -> wasmtime::component::__internal::Vec<PipeDetails>;
+                /// Load a pipe by name. The error is the message of the `pipe-load-error`
+                /// that `open-pipe` returns; its type stays `string` so components built
+                /// against earlier `wacli:cli@2.0.0` releases still compose.
                 fn load_pipe(
                     &mut self,
                     name: wasmtime::component::__internal::String,
@@ -543,11 +687,26 @@ pub mod wacli {
                     wasmtime::component::Resource<Pipe>,
                     wasmtime::component::__internal::String,
                 >;
+                /// Like `load-pipe`, saying why loading failed. Added within
+                /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+                /// import it.
+                fn open_pipe(
+                    &mut self,
+                    name: wasmtime::component::__internal::String,
+                ) -> Result<wasmtime::component::Resource<Pipe>, PipeLoadError>;
             }
             impl<_T: Host + ?Sized> Host for &mut _T {
                 fn list_pipes(&mut self) -> wasmtime::component::__internal::Vec<PipeInfo> {
                     Host::list_pipes(*self)
                 }
+                /// Like `list-pipes`, with each pipe's types and version. Read from the
+                /// pipes' metadata sections without instantiating them.
+                fn describe_pipes(&mut self) -> wasmtime::component::__internal::Vec<PipeDetails> {
+                    Host::describe_pipes(*self)
+                }
+                /// Load a pipe by name. The error is the message of the `pipe-load-error`
+                /// that `open-pipe` returns; its type stays `string` so components built
+                /// against earlier `wacli:cli@2.0.0` releases still compose.
                 fn load_pipe(
                     &mut self,
                     name: wasmtime::component::__internal::String,
@@ -557,6 +716,15 @@ pub mod wacli {
                 > {
                     Host::load_pipe(*self, name)
                 }
+                /// Like `load-pipe`, saying why loading failed. Added within
+                /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+                /// import it.
+                fn open_pipe(
+                    &mut self,
+                    name: wasmtime::component::__internal::String,
+                ) -> Result<wasmtime::component::Resource<Pipe>, PipeLoadError> {
+                    Host::open_pipe(*self, name)
+                }
             }
 
             pub fn add_to_linker<T, D>(
@@ -586,6 +754,14 @@ pub mod wacli {
                         Ok((r,))
                     },
                 )?;
+                inst.func_wrap(
+                    "describe-pipes",
+                    move |mut caller: wasmtime::StoreContextMut<'_, T>, (): ()| {
+                        let host = &mut host_getter(caller.data_mut());
+                        let r = Host::describe_pipes(host);
+                        Ok((r,))
+                    },
+                )?;
                 inst.func_wrap(
                     "load-pipe",
                     move |mut caller: wasmtime::StoreContextMut<'_, T>,
@@ -595,6 +771,15 @@ pub mod wacli {
                         Ok((r,))
                     },
                 )?;
+                inst.func_wrap(
+                    "open-pipe",
+                    move |mut caller: wasmtime::StoreContextMut<'_, T>,
+                          (arg0,): (wasmtime::component::__internal::String,)| {
+                        let host = &mut host_getter(caller.data_mut());
+                        let r = Host::open_pipe(host, arg0);
+                        Ok((r,))
+                    },
+                )?;
                 inst.func_wrap(
                     "[method]pipe.meta",
                     move |mut caller: wasmtime::StoreContextMut<'_, T>,
@@ -619,6 +804,39 @@ pub mod wacli {
                         Ok((r,))
                     },
                 )?;
+                inst.func_wrap(
+                    "[method]pipe.process-start",
+                    move |mut caller: wasmtime::StoreContextMut<'_, T>,
+                          (arg0, arg1): (
+                        wasmtime::component::Resource<Pipe>,
+                        wasmtime::component::__internal::Vec<
+                            wasmtime::component::__internal::String,
+                        >,
+                    )| {
+                        let host = &mut host_getter(caller.data_mut());
+                        let r = HostPipe::process_start(host, arg0, arg1);
+                        Ok((r,))
+                    },
+                )?;
+                inst.func_wrap(
+                    "[method]pipe.process-chunk",
+                    move |mut caller: wasmtime::StoreContextMut<'_, T>,
+                          (arg0, arg1, arg2): (
+                        wasmtime::component::Resource<Pipe>,
+                        u32,
+                        wasmtime::component::__internal::Vec<u8>,
+                    )| {
+                        let host = &mut host_getter(caller.data_mut());
+                        let r = HostPipe::process_chunk(host, arg0, arg1, arg2);
+                        Ok((r,))
+                    },
+                )?;
+                inst.func_wrap("[method]pipe.process-end", move |mut caller: wasmtime::StoreContextMut<'_, T>, (arg0,arg1,) : (wasmtime::component::Resource<Pipe>, u32, )| {
+                              let host = &mut host_getter(caller.data_mut());
+                              let r = HostPipe::process_end(host, arg0,arg1,);
+                              Ok((r,))
+                            }
+                            )?;
                 Ok(())
             }
         }
@@ -0,0 +1,261 @@
+// Generated by scripts/gen_plugin_loader_bindings.sh
+// Source: wit/cli (world=terminal-runtime-host)
+// wasmtime=41.0.2
+// DO NOT EDIT BY HAND
+
+/// Auto-generated bindings for a pre-instantiated version of a
+/// component which implements the world `terminal-runtime-host`.
+///
+/// This structure is created through [`TerminalRuntimeHostPre::new`] which
+/// takes a [`InstancePre`](wasmtime::component::InstancePre) that
+/// has been created through a [`Linker`](wasmtime::component::Linker).
+///
+/// For more information see [`TerminalRuntimeHost`] as well.
+pub struct TerminalRuntimeHostPre<T: 'static> {
+    instance_pre: wasmtime::component::InstancePre<T>,
+    indices: TerminalRuntimeHostIndices,
+}
+
+impl<T: 'static> Clone for TerminalRuntimeHostPre<T> {
+    fn clone(&self) -> Self {
+        Self {
+            instance_pre: self.instance_pre.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<_T: 'static> TerminalRuntimeHostPre<_T> {
+    /// Creates a new copy of `TerminalRuntimeHostPre` bindings which can then
+    /// be used to instantiate into a particular store.
+    ///
+    /// This method may fail if the component behind `instance_pre`
+    /// does not have the required exports.
+    pub fn new(instance_pre: wasmtime::component::InstancePre<_T>) -> wasmtime::Result<Self> {
+        let indices = TerminalRuntimeHostIndices::new(&instance_pre)?;
+        Ok(Self {
+            instance_pre,
+            indices,
+        })
+    }
+
+    pub fn engine(&self) -> &wasmtime::Engine {
+        self.instance_pre.engine()
+    }
+
+    pub fn instance_pre(&self) -> &wasmtime::component::InstancePre<_T> {
+        &self.instance_pre
+    }
+
+    /// Instantiates a new instance of [`TerminalRuntimeHost`] within the
+    /// `store` provided.
+    ///
+    /// This function will use `self` as the pre-instantiated
+    /// instance to perform instantiation. Afterwards the preloaded
+    /// indices in `self` are used to lookup all exports on the
+    /// resulting instance.
+    pub fn instantiate(
+        &self,
+        mut store: impl wasmtime::AsContextMut<Data = _T>,
+    ) -> wasmtime::Result<TerminalRuntimeHost> {
+        let mut store = store.as_context_mut();
+        let instance = self.instance_pre.instantiate(&mut store)?;
+        self.indices.load(&mut store, &instance)
+    }
+}
+
+impl<_T: Send + 'static> TerminalRuntimeHostPre<_T> {
+    /// Same as [`Self::instantiate`], except with `async`.
+    pub async fn instantiate_async(
+        &self,
+        mut store: impl wasmtime::AsContextMut<Data = _T>,
+    ) -> wasmtime::Result<TerminalRuntimeHost> {
+        let mut store = store.as_context_mut();
+        let instance = self.instance_pre.instantiate_async(&mut store).await?;
+        self.indices.load(&mut store, &instance)
+    }
+}
+
+/// Auto-generated bindings for index of the exports of
+/// `terminal-runtime-host`.
+///
+/// This is an implementation detail of [`TerminalRuntimeHostPre`] and can
+/// be constructed if needed as well.
+///
+/// For more information see [`TerminalRuntimeHost`] as well.
+#[derive(Clone)]
+pub struct TerminalRuntimeHostIndices {}
+
+/// Auto-generated bindings for an instance a component which
+/// implements the world `terminal-runtime-host`.
+///
+/// This structure can be created through a number of means
+/// depending on your requirements and what you have on hand:
+///
+/// * The most convenient way is to use
+///   [`TerminalRuntimeHost::instantiate`] which only needs a
+///   [`Store`], [`Component`], and [`Linker`].
+///
+/// * Alternatively you can create a [`TerminalRuntimeHostPre`] ahead of
+///   time with a [`Component`] to front-load string lookups
+///   of exports once instead of per-instantiation. This
+///   method then uses [`TerminalRuntimeHostPre::instantiate`] to
+///   create a [`TerminalRuntimeHost`].
+///
+/// * If you've instantiated the instance yourself already
+///   then you can use [`TerminalRuntimeHost::new`].
+///
+/// These methods are all equivalent to one another and move
+/// around the tradeoff of what work is performed when.
+///
+/// [`Store`]: wasmtime::Store
+/// [`Component`]: wasmtime::component::Component
+/// [`Linker`]: wasmtime::component::Linker
+pub struct TerminalRuntimeHost {}
+const _: () = {
+    #[allow(unused_imports)]
+    use wasmtime::component::__internal::anyhow;
+
+    impl TerminalRuntimeHostIndices {
+        /// Creates a new copy of `TerminalRuntimeHostIndices` bindings which can then
+        /// be used to instantiate into a particular store.
+        ///
+        /// This method may fail if the component does not have the
+        /// required exports.
+        pub fn new<_T>(
+            _instance_pre: &wasmtime::component::InstancePre<_T>,
+        ) -> wasmtime::Result<Self> {
+            let _component = _instance_pre.component();
+            let _instance_type = _instance_pre.instance_type();
+
+            Ok(TerminalRuntimeHostIndices {})
+        }
+
+        /// Uses the indices stored in `self` to load an instance
+        /// of [`TerminalRuntimeHost`] from the instance provided.
+        ///
+        /// Note that at this time this method will additionally
+        /// perform type-checks of all exports.
+        pub fn load(
+            &self,
+            mut store: impl wasmtime::AsContextMut,
+            instance: &wasmtime::component::Instance,
+        ) -> wasmtime::Result<TerminalRuntimeHost> {
+            let _ = &mut store;
+            let _instance = instance;
+
+            Ok(TerminalRuntimeHost {})
+        }
+    }
+    impl TerminalRuntimeHost {
+        /// Convenience wrapper around [`TerminalRuntimeHostPre::new`] and
+        /// [`TerminalRuntimeHostPre::instantiate`].
+        pub fn instantiate<_T>(
+            store: impl wasmtime::AsContextMut<Data = _T>,
+            component: &wasmtime::component::Component,
+            linker: &wasmtime::component::Linker<_T>,
+        ) -> wasmtime::Result<TerminalRuntimeHost> {
+            let pre = linker.instantiate_pre(component)?;
+            TerminalRuntimeHostPre::new(pre)?.instantiate(store)
+        }
+
+        /// Convenience wrapper around [`TerminalRuntimeHostIndices::new`] and
+        /// [`TerminalRuntimeHostIndices::load`].
+        pub fn new(
+            mut store: impl wasmtime::AsContextMut,
+            instance: &wasmtime::component::Instance,
+        ) -> wasmtime::Result<TerminalRuntimeHost> {
+            let indices = TerminalRuntimeHostIndices::new(&instance.instance_pre(&store))?;
+            indices.load(&mut store, instance)
+        }
+
+        /// Convenience wrapper around [`TerminalRuntimeHostPre::new`] and
+        /// [`TerminalRuntimeHostPre::instantiate_async`].
+        pub async fn instantiate_async<_T>(
+            store: impl wasmtime::AsContextMut<Data = _T>,
+            component: &wasmtime::component::Component,
+            linker: &wasmtime::component::Linker<_T>,
+        ) -> wasmtime::Result<TerminalRuntimeHost>
+        where
+            _T: Send,
+        {
+            let pre = linker.instantiate_pre(component)?;
+            TerminalRuntimeHostPre::new(pre)?
+                .instantiate_async(store)
+                .await
+        }
+
+        pub fn add_to_linker<T, D>(
+            linker: &mut wasmtime::component::Linker<T>,
+
+            host_getter: fn(&mut T) -> D::Data<'_>,
+        ) -> wasmtime::Result<()>
+        where
+            D: wacli::cli::terminal_runtime::HostWithStore,
+            for<'a> D::Data<'a>: wacli::cli::terminal_runtime::Host,
+            T: 'static,
+        {
+            wacli::cli::terminal_runtime::add_to_linker::<T, D>(linker, host_getter)?;
+            Ok(())
+        }
+    }
+};
+pub mod wacli {
+    pub mod cli {
+
+        #[allow(clippy::all)]
+        pub mod terminal_runtime {
+            #[allow(unused_imports)]
+            use wasmtime::component::__internal::{anyhow, Box};
+
+            pub trait HostWithStore: wasmtime::component::HasData {}
+            impl<_T: ?Sized> HostWithStore for _T where _T: wasmtime::component::HasData {}
+            pub trait Host {
+                /// Read one line from stdin, which is a terminal, with echo turned off.
+                /// The terminal mode is restored before this returns, also when the read
+                /// is interrupted (Ctrl-C, an `err`). `none` at end of input (Ctrl-D).
+                fn read_hidden_line(
+                    &mut self,
+                ) -> Result<
+                    Option<wasmtime::component::__internal::String>,
+                    wasmtime::component::__internal::String,
+                >;
+            }
+            impl<_T: Host + ?Sized> Host for &mut _T {
+                /// Read one line from stdin, which is a terminal, with echo turned off.
+                /// The terminal mode is restored before this returns, also when the read
+                /// is interrupted (Ctrl-C, an `err`). `none` at end of input (Ctrl-D).
+                fn read_hidden_line(
+                    &mut self,
+                ) -> Result<
+                    Option<wasmtime::component::__internal::String>,
+                    wasmtime::component::__internal::String,
+                > {
+                    Host::read_hidden_line(*self)
+                }
+            }
+
+            pub fn add_to_linker<T, D>(
+                linker: &mut wasmtime::component::Linker<T>,
+
+                host_getter: fn(&mut T) -> D::Data<'_>,
+            ) -> wasmtime::Result<()>
+            where
+                D: HostWithStore,
+                for<'a> D::Data<'a>: Host,
+                T: 'static,
+            {
+                let mut inst = linker.instance("wacli:cli/terminal-runtime@2.0.0")?;
+                inst.func_wrap(
+                    "read-hidden-line",
+                    move |mut caller: wasmtime::StoreContextMut<'_, T>, (): ()| {
+                        let host = &mut host_getter(caller.data_mut());
+                        let r = Host::read_hidden_line(host);
+                        Ok((r,))
+                    },
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
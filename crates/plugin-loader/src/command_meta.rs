@@ -0,0 +1,286 @@
+//! Read a command's `command-meta` by calling its `meta()` export.
+//!
+//! `wacli build --allow-meta-exec` uses this for commands that embed no
+//! `COMMAND_METADATA_SECTION`, and `wacli verify --exec` to compare it with
+//! the section. The component runs in a store of its own:
+//! WASI without preopens, args, env or stdio, every other import trapping,
+//! a fuel budget and a memory cap, so `meta()` can only compute its return
+//! value.
+
+use anyhow::{Context, Result, anyhow};
+use wacli_metadata::{ArgDef, CommandMeta};
+use wasmtime::component::{Component, Linker, ResourceTable, Val};
+use wasmtime::{Engine, Store, StoreLimits, StoreLimitsBuilder, Trap};
+use wasmtime_wasi::p2;
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
+
+/// Names the command interface may be exported under, newest first.
+const COMMAND_EXPORTS: [&str; 3] = ["wacli:cli/command@2.0.0", "wacli:cli/command", "command"];
+
+/// Names the multi-command interface may be exported under, newest first.
+const MULTI_COMMAND_EXPORTS: [&str; 3] = [
+    "wacli:cli/multi-command@2.1.0",
+    "wacli:cli/multi-command",
+    "multi-command",
+];
+
+/// Linear memory and table growth allowed while `meta()` runs.
+const MEMORY_LIMIT: usize = 64 << 20;
+const TABLE_ELEMENTS_LIMIT: usize = 100_000;
+
+struct MetaState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl WasiView for MetaState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.ctx,
+            table: &mut self.table,
+        }
+    }
+}
+
+/// Instantiate the command component in `bytes` and return what its
+/// `meta()` reports, spending at most `fuel` on instantiation and the call.
+pub fn call_command_meta(bytes: &[u8], fuel: u64) -> Result<CommandMeta> {
+    let result = call_export(bytes, fuel, &COMMAND_EXPORTS, "wacli:cli/command", "meta")?;
+    lift_command_meta(&result).context("meta() returned an unexpected value")
+}
+
+/// Like [`call_command_meta`], for a multi-command component: what its
+/// `list-commands()` reports.
+pub fn call_listed_command_metas(bytes: &[u8], fuel: u64) -> Result<Vec<CommandMeta>> {
+    let result = call_export(
+        bytes,
+        fuel,
+        &MULTI_COMMAND_EXPORTS,
+        "wacli:cli/multi-command",
+        "list-commands",
+    )?;
+    match &result {
+        Val::List(items) => items.iter().map(lift_command_meta).collect(),
+        other => Err(anyhow!("expected a list, got {other:?}")),
+    }
+    .context("list-commands() returned an unexpected value")
+}
+
+/// Call the parameterless `func` of the first of `exports` the component
+/// exports (`interface` names it in errors).
+fn call_export(
+    bytes: &[u8],
+    fuel: u64,
+    exports: &[&str],
+    interface: &str,
+    func: &str,
+) -> Result<Val> {
+    let mut config = wasmtime::Config::new();
+    config.wasm_component_model(true);
+    config.consume_fuel(true);
+    let engine = Engine::new(&config).context("failed to create wasmtime engine")?;
+    let component =
+        Component::from_binary(&engine, bytes).context("failed to compile component")?;
+
+    let mut linker = Linker::new(&engine);
+    p2::add_to_linker_sync(&mut linker).context("failed to add WASI to linker")?;
+    linker
+        .define_unknown_imports_as_traps(&component)
+        .context("failed to stub command imports")?;
+
+    let mut store = Store::new(
+        &engine,
+        MetaState {
+            ctx: WasiCtxBuilder::new().build(),
+            table: ResourceTable::new(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(MEMORY_LIMIT)
+                .table_elements(TABLE_ELEMENTS_LIMIT)
+                .build(),
+        },
+    );
+    store.limiter(|state| &mut state.limits);
+    store.set_fuel(fuel).context("failed to set fuel")?;
+    let out_of_fuel = |err: anyhow::Error| match err.downcast_ref::<Trap>() {
+        Some(Trap::OutOfFuel) => anyhow!("{func}() ran out of fuel (limit: {fuel})"),
+        _ => err,
+    };
+
+    let instance = linker
+        .instantiate(&mut store, &component)
+        .map_err(out_of_fuel)
+        .context("failed to instantiate component")?;
+    let iface = exports
+        .iter()
+        .find_map(|name| instance.get_export_index(&mut store, None, name))
+        .with_context(|| format!("component does not export {interface}"))?;
+    let callee = instance
+        .get_export_index(&mut store, Some(&iface), func)
+        .and_then(|index| instance.get_func(&mut store, index))
+        .with_context(|| format!("{interface} has no {func}() function"))?;
+
+    let mut results = [Val::Bool(false)];
+    callee
+        .call(&mut store, &[], &mut results)
+        .map_err(out_of_fuel)
+        .with_context(|| format!("{func}() failed"))?;
+    let [result] = results;
+    Ok(result)
+}
+
+fn lift_command_meta(val: &Val) -> Result<CommandMeta> {
+    let fields = Fields::of(val)?;
+    Ok(CommandMeta {
+        name: fields.string("name")?,
+        summary: fields.string("summary")?,
+        usage: fields.string("usage")?,
+        aliases: fields.strings("aliases")?,
+        // `command-meta` has no hidden aliases; only the section carries them.
+        hidden_aliases: Vec::new(),
+        version: fields.string("version")?,
+        hidden: fields.bool("hidden")?,
+        description: fields.string("description")?,
+        examples: fields.strings("examples")?,
+        args: fields
+            .list("args")?
+            .iter()
+            .map(lift_arg_def)
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn lift_arg_def(val: &Val) -> Result<ArgDef> {
+    let fields = Fields::of(val)?;
+    Ok(ArgDef {
+        name: fields.string("name")?,
+        short: fields.opt_string("short")?,
+        long: fields.opt_string("long")?,
+        help: fields.string("help")?,
+        required: fields.bool("required")?,
+        default_value: fields.opt_string("default-value")?,
+        value_name: fields.opt_string("value-name")?,
+        takes_value: fields.bool("takes-value")?,
+    })
+}
+
+/// Fields of a lifted record, read by name.
+struct Fields<'a>(&'a [(String, Val)]);
+
+impl<'a> Fields<'a> {
+    fn of(val: &'a Val) -> Result<Self> {
+        match val {
+            Val::Record(fields) => Ok(Self(fields)),
+            other => Err(anyhow!("expected a record, got {other:?}")),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<&'a Val> {
+        self.0
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, val)| val)
+            .with_context(|| format!("missing field `{name}`"))
+    }
+
+    fn string(&self, name: &str) -> Result<String> {
+        as_string(self.get(name)?).with_context(|| format!("field `{name}`"))
+    }
+
+    fn bool(&self, name: &str) -> Result<bool> {
+        match self.get(name)? {
+            Val::Bool(b) => Ok(*b),
+            other => Err(anyhow!("field `{name}`: expected a bool, got {other:?}")),
+        }
+    }
+
+    fn opt_string(&self, name: &str) -> Result<Option<String>> {
+        match self.get(name)? {
+            Val::Option(None) => Ok(None),
+            Val::Option(Some(val)) => as_string(val)
+                .map(Some)
+                .with_context(|| format!("field `{name}`")),
+            other => Err(anyhow!("field `{name}`: expected an option, got {other:?}")),
+        }
+    }
+
+    fn list(&self, name: &str) -> Result<&'a [Val]> {
+        match self.get(name)? {
+            Val::List(items) => Ok(items),
+            other => Err(anyhow!("field `{name}`: expected a list, got {other:?}")),
+        }
+    }
+
+    fn strings(&self, name: &str) -> Result<Vec<String>> {
+        self.list(name)?
+            .iter()
+            .map(|val| as_string(val).with_context(|| format!("field `{name}`")))
+            .collect()
+    }
+}
+
+fn as_string(val: &Val) -> Result<String> {
+    match val {
+        Val::String(s) => Ok(s.clone()),
+        other => Err(anyhow!("expected a string, got {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn greet() -> Vec<u8> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../testdata/greet.component.wasm");
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn meta_is_read_from_the_running_component() {
+        let meta = call_command_meta(&greet(), 10_000_000).unwrap();
+        assert_eq!(meta.name, "greet");
+        assert_eq!(meta.summary, "Greet someone");
+        assert_eq!(meta.usage, "greet [NAME]");
+        assert_eq!(meta.aliases, ["hi"]);
+    }
+
+    #[test]
+    fn listed_metas_are_read_from_a_multi_command_component() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../testdata/textkit.component.wasm");
+        let metas = call_listed_command_metas(&std::fs::read(path).unwrap(), 10_000_000).unwrap();
+        let names: Vec<&str> = metas.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["shout", "whisper"]);
+        assert_eq!(metas[0].aliases, ["yell"]);
+
+        let err = format!(
+            "{:#}",
+            call_listed_command_metas(&greet(), 10_000_000).unwrap_err()
+        );
+        assert!(
+            err.contains("component does not export wacli:cli/multi-command"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn meta_stops_when_fuel_runs_out() {
+        let err = format!("{:#}", call_command_meta(&greet(), 1).unwrap_err());
+        assert!(err.contains("ran out of fuel (limit: 1)"), "{err}");
+    }
+
+    #[test]
+    fn meta_cannot_take_more_memory_than_the_limit() {
+        let pages = MEMORY_LIMIT / 65536 + 1;
+        let component = wat::parse_str(format!(
+            "(component (core module $m (memory {pages})) (core instance (instantiate $m)))"
+        ))
+        .unwrap();
+        let err = format!(
+            "{:#}",
+            call_command_meta(&component, 10_000_000).unwrap_err()
+        );
+        assert!(err.contains("failed to instantiate component"), "{err}");
+    }
+}
@@ -1,12 +1,29 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use wacli_argparse::pipe_spec;
 use wasmtime::component::{Component, Linker, ResourceTable};
 use wasmtime::{Engine, Store};
+use wasmtime_wasi::cli::OutputFile;
 use wasmtime_wasi::p2;
 use wasmtime_wasi::p2::bindings::sync::Command;
+use wasmtime_wasi::p2::pipe::MemoryOutputPipe;
 use wasmtime_wasi::{DirPerms, FilePerms, I32Exit, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
+mod command_meta;
+mod limits;
+mod network;
+mod pipe_cache;
+mod pipe_metadata;
+mod pipe_options;
+mod terminal;
+
+pub use command_meta::{call_command_meta, call_listed_command_metas};
+pub use limits::RunnerConfig;
+pub use network::{HostPattern, NetworkPolicy};
+
 mod pipe_plugin_bindings {
     #![allow(clippy::all, dead_code, unused_imports, unused_mut, unused_variables)]
     include!("bindings/pipe_plugin.rs");
@@ -15,10 +32,64 @@ mod pipe_plugin_bindings {
 #[derive(Default)]
 struct PipeState;
 
+use pipe_plugin_bindings::exports::wacli::cli::{pipe as pipe_guest, pipe_stream as stream_guest};
+
 pub struct LoadedPipe {
     store: Store<PipeState>,
-    instance: pipe_plugin_bindings::PipePlugin,
+    guest: pipe_guest::Guest,
+    /// `None` for pipes built before `pipe-stream`; their chunked runs are
+    /// buffered here and passed to `process` at the end.
+    stream: Option<stream_guest::Guest>,
     meta: pipe_plugin_bindings::wacli::cli::types::PipeMeta,
+    /// Declared options; `None` when the pipe embeds no option schema.
+    options: Option<pipe_options::OptionSchema>,
+    /// Options from the spec the pipe was loaded by (`table?width=80`),
+    /// passed before those given to each `process()` call.
+    spec_options: Vec<String>,
+    /// Chunked runs started by `process-start`, by the id handed out.
+    sessions: HashMap<u32, PipeSession>,
+    next_session: u32,
+}
+
+enum PipeSession {
+    /// Run by the pipe's `pipe-stream` export under its own id.
+    Guest(u32),
+    Buffered {
+        options: Vec<String>,
+        input: Vec<u8>,
+    },
+}
+
+impl LoadedPipe {
+    /// The spec's inline options followed by `options`, checked against the
+    /// declared schema.
+    fn checked_options(
+        &self,
+        options: Vec<String>,
+    ) -> Result<Vec<String>, pipe_runtime::PipeError> {
+        let options = if self.spec_options.is_empty() {
+            options
+        } else {
+            [self.spec_options.clone(), options].concat()
+        };
+        if let Some(schema) = &self.options {
+            schema
+                .validate(&options)
+                .map_err(pipe_runtime::PipeError::InvalidOption)?;
+        }
+        Ok(options)
+    }
+
+    /// Give the store a fresh budget for one call into the pipe.
+    fn arm(&mut self, limits: &RunnerConfig) -> Result<(), pipe_runtime::PipeError> {
+        limits.arm(&mut self.store).map_err(|e| {
+            pipe_runtime::PipeError::TransformError(format!("pipe execution failed: {e}"))
+        })
+    }
+}
+
+fn unknown_session(pipe: &str, session: u32) -> pipe_runtime::PipeError {
+    pipe_runtime::PipeError::TransformError(format!("pipe '{pipe}' has no session {session}"))
 }
 
 #[derive(Debug, Clone)]
@@ -41,8 +112,16 @@ mod pipe_runtime_bindings {
     include!("bindings/pipe_runtime_host.rs");
 }
 
+use pipe_runtime::PipeLoadError;
 use pipe_runtime_bindings::wacli::cli::{pipe_runtime, types as pipe_types};
 
+mod terminal_runtime_bindings {
+    #![allow(clippy::all, dead_code, unused_imports, unused_mut, unused_variables)]
+    include!("bindings/terminal_runtime_host.rs");
+}
+
+use terminal_runtime_bindings::wacli::cli::terminal_runtime;
+
 #[cfg(feature = "regen-bindings")]
 mod regen_bindings {
     #![allow(clippy::all, dead_code, unused_imports, unused_mut, unused_variables)]
@@ -63,20 +142,118 @@ mod regen_bindings {
             },
         });
     }
+    mod terminal_runtime_host {
+        #![allow(clippy::all, dead_code, unused_imports, unused_mut, unused_variables)]
+        wasmtime::component::bindgen!({
+            path: "../../wit/cli",
+            world: "terminal-runtime-host",
+        });
+    }
+}
+
+/// Environment variables the guest sees.
+#[derive(Debug, Clone)]
+pub struct EnvPolicy {
+    /// Start from the host process environment.
+    pub inherit_host: bool,
+    /// Explicit additions layered over the base; later entries win.
+    pub vars: Vec<(String, String)>,
+}
+
+impl Default for EnvPolicy {
+    fn default() -> Self {
+        Self {
+            inherit_host: true,
+            vars: Vec::new(),
+        }
+    }
+}
+
+impl EnvPolicy {
+    /// The resulting environment: the base, then each addition replacing any
+    /// existing entry with the same name.
+    pub fn resolve(&self) -> Vec<(String, String)> {
+        let mut env: Vec<(String, String)> = if self.inherit_host {
+            std::env::vars_os()
+                .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        for (key, value) in &self.vars {
+            match env.iter_mut().find(|(k, _)| k == key) {
+                Some(entry) => entry.1 = value.clone(),
+                None => env.push((key.clone(), value.clone())),
+            }
+        }
+        env
+    }
 }
 
 /// Runs a composed CLI component with dynamic pipe loading.
 pub struct Runner {
     engine: Engine,
+    config: RunnerConfig,
+    env: EnvPolicy,
+    network: NetworkPolicy,
+    plugins_dir: PathBuf,
+    value_flags: Vec<String>,
+    pipe_cache: Arc<Mutex<pipe_cache::ComponentCache>>,
 }
 
 impl Runner {
     /// Create a runner with component model enabled.
     pub fn new() -> Result<Self> {
-        let mut config = wasmtime::Config::new();
-        config.wasm_component_model(true);
-        let engine = Engine::new(&config).context("failed to create wasmtime engine")?;
-        Ok(Self { engine })
+        Self::new_with_config(RunnerConfig::default())
+    }
+
+    /// Create a runner that enforces the execution limits in `config`.
+    pub fn new_with_config(config: RunnerConfig) -> Result<Self> {
+        let mut wasm_config = wasmtime::Config::new();
+        wasm_config.wasm_component_model(true);
+        config.configure(&mut wasm_config);
+        let engine = Engine::new(&wasm_config).context("failed to create wasmtime engine")?;
+        config.start_ticker(&engine);
+        Ok(Self {
+            engine,
+            config,
+            env: EnvPolicy::default(),
+            network: NetworkPolicy::default(),
+            plugins_dir: PathBuf::from(DEFAULT_PLUGINS_DIR),
+            value_flags: Vec::new(),
+            pipe_cache: Arc::default(),
+        })
+    }
+
+    /// Set the environment policy used for every run.
+    pub fn with_env_policy(mut self, env: EnvPolicy) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Set the network policy used for every run (default: deny).
+    pub fn with_network_policy(mut self, network: NetworkPolicy) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Set the directory pipes are loaded from (default: `plugins`, relative
+    /// to the current directory). See [`resolve_plugins_dir`].
+    pub fn with_plugins_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.plugins_dir = dir.into();
+        self
+    }
+
+    /// Declare top-level flags of the guest that take a separate value
+    /// (`--config prod.toml`), so the value is not mistaken for the command
+    /// that scopes pipe lookups. `--flag=value` forms need no declaration.
+    pub fn with_global_value_flags<I, S>(mut self, flags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.value_flags = flags.into_iter().map(Into::into).collect();
+        self
     }
 
     /// Run a composed CLI component (.component.wasm).
@@ -91,29 +268,134 @@ impl Runner {
         args: &[String],
         preopens: &[PreopenDir],
     ) -> Result<u32> {
+        let loaded = self.load(component_path)?;
+        self.run_loaded(&loaded, args, preopens)
+    }
+
+    /// Compile a composed CLI component once so it can be run many times.
+    ///
+    /// The returned [`LoadedComponent`] can be shared across threads; every
+    /// run gets its own `Store`.
+    pub fn load(&self, component_path: impl AsRef<Path>) -> Result<LoadedComponent> {
         let component_path = component_path.as_ref();
         let component = Component::from_file(&self.engine, component_path)
             .with_context(|| format!("failed to load component: {}", component_path.display()))?;
 
         let mut linker = Linker::new(&self.engine);
-        p2::add_to_linker_sync(&mut linker).context("failed to add WASI to linker")?;
+        // The host component reports core's exit code (2 for usage errors,
+        // 127 for unknown commands) through `exit-with-code`.
+        let mut wasi_options = p2::bindings::sync::LinkOptions::default();
+        wasi_options.cli_exit_with_code(true);
+        p2::add_to_linker_with_options_sync(&mut linker, &wasi_options)
+            .context("failed to add WASI to linker")?;
         pipe_runtime_bindings::PipeRuntimeHost::add_to_linker::<
             HostState,
             wasmtime::component::HasSelf<HostState>,
         >(&mut linker, |state: &mut HostState| state)
         .context("failed to add pipe-runtime to linker")?;
+        terminal_runtime_bindings::TerminalRuntimeHost::add_to_linker::<
+            HostState,
+            wasmtime::component::HasSelf<HostState>,
+        >(&mut linker, |state: &mut HostState| state)
+        .context("failed to add terminal-runtime to linker")?;
 
         let program_name = component_path
             .file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("wacli")
             .to_string();
+        Ok(LoadedComponent {
+            component,
+            linker,
+            program_name,
+        })
+    }
+
+    /// Run a loaded component with inherited stdio.
+    ///
+    /// The guest's `wasi:cli/terminal-*` answers mirror the host's stdio, so
+    /// `host-io.stdout-is-terminal` is true only when wacli's own stdout is.
+    pub fn run_loaded(
+        &self,
+        loaded: &LoadedComponent,
+        args: &[String],
+        preopens: &[PreopenDir],
+    ) -> Result<u32> {
+        let mut builder = WasiCtxBuilder::new();
+        builder.inherit_stdio();
+        self.run_with(loaded, builder, args, preopens)
+    }
+
+    /// Run a loaded component with inherited stdin and stdout/stderr sent
+    /// where `stdio` says.
+    ///
+    /// File targets receive the guest's bytes unchanged (no UTF-8 decoding);
+    /// [`RunOutput`] holds the streams with a [`OutputTarget::Capture`] target
+    /// and is empty for the others.
+    pub fn run_redirected(
+        &self,
+        loaded: &LoadedComponent,
+        args: &[String],
+        preopens: &[PreopenDir],
+        stdio: &StdioOptions,
+    ) -> Result<RunOutput> {
+        let mut builder = WasiCtxBuilder::new();
+        builder.inherit_stdin();
+        let stdout = OpenedTarget::open(&stdio.stdout, "stdout")?;
+        let stderr = OpenedTarget::open(&stdio.stderr, "stderr")?;
+        match &stdout {
+            OpenedTarget::Inherit => builder.inherit_stdout(),
+            OpenedTarget::File(file) => builder.stdout(file.clone()),
+            OpenedTarget::Capture(pipe) => builder.stdout(pipe.clone()),
+        };
+        match &stderr {
+            OpenedTarget::Inherit => builder.inherit_stderr(),
+            OpenedTarget::File(file) => builder.stderr(file.clone()),
+            OpenedTarget::Capture(pipe) => builder.stderr(pipe.clone()),
+        };
+        let code = self.run_with(loaded, builder, args, preopens)?;
+        Ok(RunOutput {
+            code,
+            stdout: stdout.captured(),
+            stderr: stderr.captured(),
+        })
+    }
+
+    /// Run a loaded component with stdout and stderr captured in memory.
+    ///
+    /// Stdin is empty. Each stream keeps at most [`CAPTURE_LIMIT`] bytes.
+    /// Captured streams never report as terminals.
+    pub fn run_captured(
+        &self,
+        loaded: &LoadedComponent,
+        args: &[String],
+        preopens: &[PreopenDir],
+    ) -> Result<RunOutput> {
+        let stdout = MemoryOutputPipe::new(CAPTURE_LIMIT);
+        let stderr = MemoryOutputPipe::new(CAPTURE_LIMIT);
+        let mut builder = WasiCtxBuilder::new();
+        builder.stdout(stdout.clone()).stderr(stderr.clone());
+        let code = self.run_with(loaded, builder, args, preopens)?;
+        Ok(RunOutput {
+            code,
+            stdout: stdout.contents().to_vec(),
+            stderr: stderr.contents().to_vec(),
+        })
+    }
+
+    fn run_with(
+        &self,
+        loaded: &LoadedComponent,
+        mut builder: WasiCtxBuilder,
+        args: &[String],
+        preopens: &[PreopenDir],
+    ) -> Result<u32> {
         let mut wasi_args = Vec::with_capacity(args.len() + 1);
-        wasi_args.push(program_name);
+        wasi_args.push(loaded.program_name.clone());
         wasi_args.extend_from_slice(args);
 
-        let mut builder = WasiCtxBuilder::new();
-        builder.inherit_stdio().inherit_env().args(&wasi_args);
+        builder.envs(&self.env.resolve()).args(&wasi_args);
+        self.network.apply(&mut builder)?;
         builder
             .preopened_dir(".", ".", DirPerms::all(), FilePerms::all())
             .context("failed to preopen current directory")?;
@@ -146,8 +428,8 @@ impl Runner {
         }
         let ctx = builder.build();
 
-        let current_command = detect_command(args);
-        let plugins_dir = PathBuf::from("plugins");
+        let current_command = detect_command(args, &self.value_flags);
+        let plugins_dir = self.plugins_dir.clone();
 
         let mut store = Store::new(
             &self.engine,
@@ -155,12 +437,15 @@ impl Runner {
                 ctx,
                 table: ResourceTable::new(),
                 engine: self.engine.clone(),
+                limits: self.config.clone(),
                 plugins_dir,
                 current_command,
+                pipe_cache: self.pipe_cache.clone(),
             },
         );
+        self.config.arm(&mut store)?;
 
-        let command = Command::instantiate(&mut store, &component, &linker)
+        let command = Command::instantiate(&mut store, &loaded.component, &loaded.linker)
             .context("failed to instantiate component")?;
         match command.wasi_cli_run().call_run(&mut store) {
             Ok(Ok(())) => Ok(0),
@@ -168,6 +453,8 @@ impl Runner {
             Err(err) => {
                 if let Some(exit) = err.downcast_ref::<I32Exit>() {
                     Ok(exit.0 as u32)
+                } else if let Some(reason) = self.config.describe_trap(&err) {
+                    Err(anyhow::anyhow!("component was {reason}"))
                 } else {
                     Err(err).context("failed to invoke wasi:cli/run")
                 }
@@ -176,12 +463,81 @@ impl Runner {
     }
 }
 
+/// Upper bound on bytes kept per captured stream in [`Runner::run_captured`].
+pub const CAPTURE_LIMIT: usize = 16 * 1024 * 1024;
+
+/// Where a guest output stream goes in [`Runner::run_redirected`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputTarget {
+    /// The host process's own stream.
+    #[default]
+    Inherit,
+    /// This file, created or truncated before the run.
+    File(PathBuf),
+    /// Memory, returned in [`RunOutput`] (at most [`CAPTURE_LIMIT`] bytes).
+    Capture,
+}
+
+/// Stdout/stderr redirection for [`Runner::run_redirected`].
+#[derive(Debug, Clone, Default)]
+pub struct StdioOptions {
+    pub stdout: OutputTarget,
+    pub stderr: OutputTarget,
+}
+
+/// An [`OutputTarget`] with its file opened or its buffer allocated.
+enum OpenedTarget {
+    Inherit,
+    File(OutputFile),
+    Capture(MemoryOutputPipe),
+}
+
+impl OpenedTarget {
+    fn open(target: &OutputTarget, stream: &str) -> Result<Self> {
+        Ok(match target {
+            OutputTarget::Inherit => Self::Inherit,
+            OutputTarget::File(path) => {
+                let file = fs::File::create(path).with_context(|| {
+                    format!("failed to create {stream} file: {}", path.display())
+                })?;
+                Self::File(OutputFile::new(file))
+            }
+            OutputTarget::Capture => Self::Capture(MemoryOutputPipe::new(CAPTURE_LIMIT)),
+        })
+    }
+
+    fn captured(&self) -> Vec<u8> {
+        match self {
+            Self::Capture(pipe) => pipe.contents().to_vec(),
+            Self::Inherit | Self::File(_) => Vec::new(),
+        }
+    }
+}
+
+/// A compiled component plus its linker, ready to be run by [`Runner`].
+pub struct LoadedComponent {
+    component: Component,
+    linker: Linker<HostState>,
+    program_name: String,
+}
+
+/// Exit code and captured output of [`Runner::run_captured`].
+#[derive(Debug, Clone, Default)]
+pub struct RunOutput {
+    pub code: u32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
 struct HostState {
     ctx: WasiCtx,
     table: ResourceTable,
     engine: Engine,
+    limits: RunnerConfig,
     plugins_dir: PathBuf,
     current_command: Option<String>,
+    /// Shared with the [`Runner`]; only stores and instances are per pipe.
+    pipe_cache: Arc<Mutex<pipe_cache::ComponentCache>>,
 }
 
 impl WasiView for HostState {
@@ -195,6 +551,12 @@ impl WasiView for HostState {
 
 impl pipe_types::Host for HostState {}
 
+impl terminal_runtime::Host for HostState {
+    fn read_hidden_line(&mut self) -> Result<Option<String>, String> {
+        terminal::read_hidden_line()
+    }
+}
+
 impl pipe_runtime::Host for HostState {
     fn list_pipes(&mut self) -> Vec<pipe_runtime::PipeInfo> {
         let base = self.pipes_root();
@@ -209,16 +571,48 @@ impl pipe_runtime::Host for HostState {
         pipes
     }
 
+    fn describe_pipes(&mut self) -> Vec<pipe_runtime::PipeDetails> {
+        self.list_pipes()
+            .into_iter()
+            .map(|info| {
+                let meta = pipe_metadata::cached(Path::new(&info.path));
+                let meta = meta.as_deref();
+                pipe_runtime::PipeDetails {
+                    input_types: meta.map(|m| m.input_types.clone()).unwrap_or_default(),
+                    output_type: meta.map(|m| m.output_type.clone()).unwrap_or_default(),
+                    version: meta.map(|m| m.version.clone()).unwrap_or_default(),
+                    info,
+                }
+            })
+            .collect()
+    }
+
     fn load_pipe(
         &mut self,
         name: String,
     ) -> Result<wasmtime::component::Resource<LoadedPipe>, String> {
-        let normalized = self.resolve_pipe_name(&name)?;
+        self.open_pipe(name).map_err(|e| match e {
+            PipeLoadError::NotFound(msg)
+            | PipeLoadError::InvalidName(msg)
+            | PipeLoadError::LoadFailed(msg) => msg,
+        })
+    }
+
+    fn open_pipe(
+        &mut self,
+        name: String,
+    ) -> Result<wasmtime::component::Resource<LoadedPipe>, PipeLoadError> {
+        let (normalized, spec_options) = self
+            .resolve_pipe_name(&name)
+            .map_err(PipeLoadError::InvalidName)?;
         let path = self.resolve_pipe_path(&normalized)?;
-        let pipe = self.instantiate_pipe(&path)?;
+        let mut pipe = self
+            .instantiate_pipe(&path)
+            .map_err(PipeLoadError::LoadFailed)?;
+        pipe.spec_options = spec_options;
         self.table
             .push(pipe)
-            .map_err(|e| format!("failed to register pipe: {e}"))
+            .map_err(|e| PipeLoadError::LoadFailed(format!("failed to register pipe: {e}")))
     }
 }
 
@@ -242,20 +636,83 @@ impl pipe_runtime::HostPipe for HostState {
         input: Vec<u8>,
         options: Vec<String>,
     ) -> Result<Vec<u8>, pipe_runtime::PipeError> {
-        let pipe = self.table.get_mut(&pipe).map_err(|e| {
-            pipe_runtime::PipeError::TransformError(format!("pipe handle is invalid: {e}"))
-        })?;
-        match pipe
-            .instance
-            .wacli_cli_pipe()
-            .call_process(&mut pipe.store, &input, &options)
-        {
-            Ok(Ok(bytes)) => Ok(bytes),
-            Ok(Err(err)) => Err(convert_pipe_error(err)),
-            Err(err) => Err(pipe_runtime::PipeError::TransformError(format!(
-                "pipe execution failed: {err}"
-            ))),
+        let pipe = self.table.get_mut(&pipe).map_err(invalid_handle)?;
+        let options = pipe.checked_options(options)?;
+        pipe.arm(&self.limits)?;
+        let result = pipe.guest.call_process(&mut pipe.store, &input, &options);
+        pipe_call_result(&self.limits, &pipe.meta.name, result)
+    }
+
+    fn process_start(
+        &mut self,
+        pipe: wasmtime::component::Resource<LoadedPipe>,
+        options: Vec<String>,
+    ) -> Result<u32, pipe_runtime::PipeError> {
+        let pipe = self.table.get_mut(&pipe).map_err(invalid_handle)?;
+        let options = pipe.checked_options(options)?;
+        pipe.arm(&self.limits)?;
+        let session = match &pipe.stream {
+            Some(stream) => {
+                let result = stream.call_process_start(&mut pipe.store, &options);
+                PipeSession::Guest(pipe_call_result(&self.limits, &pipe.meta.name, result)?)
+            }
+            None => PipeSession::Buffered {
+                options,
+                input: Vec::new(),
+            },
+        };
+        let id = pipe.next_session;
+        pipe.next_session = pipe.next_session.wrapping_add(1);
+        pipe.sessions.insert(id, session);
+        Ok(id)
+    }
+
+    fn process_chunk(
+        &mut self,
+        pipe: wasmtime::component::Resource<LoadedPipe>,
+        session: u32,
+        bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, pipe_runtime::PipeError> {
+        let pipe = self.table.get_mut(&pipe).map_err(invalid_handle)?;
+        let guest_session = match pipe.sessions.get_mut(&session) {
+            Some(PipeSession::Guest(id)) => *id,
+            Some(PipeSession::Buffered { input, .. }) => {
+                input.extend_from_slice(&bytes);
+                return Ok(Vec::new());
+            }
+            None => return Err(unknown_session(&pipe.meta.name, session)),
+        };
+        pipe.arm(&self.limits)?;
+        let Some(stream) = &pipe.stream else {
+            return Err(unknown_session(&pipe.meta.name, session));
+        };
+        let result = stream.call_process_chunk(&mut pipe.store, guest_session, &bytes);
+        let result = pipe_call_result(&self.limits, &pipe.meta.name, result);
+        if result.is_err() {
+            pipe.sessions.remove(&session);
         }
+        result
+    }
+
+    fn process_end(
+        &mut self,
+        pipe: wasmtime::component::Resource<LoadedPipe>,
+        session: u32,
+    ) -> Result<Vec<u8>, pipe_runtime::PipeError> {
+        let pipe = self.table.get_mut(&pipe).map_err(invalid_handle)?;
+        let session = pipe
+            .sessions
+            .remove(&session)
+            .ok_or_else(|| unknown_session(&pipe.meta.name, session))?;
+        pipe.arm(&self.limits)?;
+        let result = match (session, &pipe.stream) {
+            (PipeSession::Guest(id), Some(stream)) => stream.call_process_end(&mut pipe.store, id),
+            (PipeSession::Buffered { options, input }, _) => {
+                pipe.guest.call_process(&mut pipe.store, &input, &options)
+            }
+            (PipeSession::Guest(id), None) => return Err(unknown_session(&pipe.meta.name, id)),
+        };
+        pipe_call_result(&self.limits, &pipe.meta.name, result)
     }
 
     fn drop(&mut self, pipe: wasmtime::component::Resource<LoadedPipe>) -> wasmtime::Result<()> {
@@ -267,77 +724,164 @@ impl pipe_runtime::HostPipe for HostState {
 }
 
 impl HostState {
+    /// The command whose `plugins/<cmd>/` directory scopes pipe lookups, or
+    /// `None` to use the shared `plugins/` directory when the command has no
+    /// pipes of its own.
+    fn pipe_scope(&self) -> Option<&str> {
+        self.current_command
+            .as_deref()
+            .filter(|cmd| self.plugins_dir.join(cmd).is_dir())
+    }
+
     fn pipes_root(&self) -> PathBuf {
-        match &self.current_command {
+        match self.pipe_scope() {
             Some(cmd) => self.plugins_dir.join(cmd),
             None => self.plugins_dir.clone(),
         }
     }
 
-    fn resolve_pipe_name(&self, name: &str) -> Result<String, String> {
-        let trimmed = name.trim();
-        if trimmed.is_empty() {
-            return Err("pipe name is empty".to_string());
-        }
-        if trimmed.contains('\\') {
-            return Err("pipe name must use '/' separators".to_string());
-        }
-        let mut normalized = trimmed.trim_start_matches('/').to_string();
-        if let Some(stripped) = normalized.strip_suffix(".component.wasm") {
-            normalized = stripped.to_string();
-        }
-        if let Some(cmd) = &self.current_command {
-            let prefix = format!("{cmd}/");
-            if !normalized.starts_with(&prefix) {
-                normalized = format!("{prefix}{normalized}");
-            }
-        }
-        if !is_valid_pipe_name(&normalized) {
-            return Err(format!("invalid pipe name '{normalized}'"));
-        }
-        Ok(normalized)
+    fn resolve_pipe_name(&self, name: &str) -> Result<(String, Vec<String>), String> {
+        normalize_pipe_name(name, self.pipe_scope())
     }
 
-    fn resolve_pipe_path(&self, name: &str) -> Result<PathBuf, String> {
+    fn resolve_pipe_path(&self, name: &str) -> Result<PathBuf, PipeLoadError> {
         let base = self.plugins_dir.clone();
         if !base.exists() {
-            return Err(format!("pipe directory not found: {}", base.display()));
+            return Err(PipeLoadError::NotFound(format!(
+                "pipe directory not found: {}",
+                base.display()
+            )));
         }
         let mut path = base.join(name);
         path.set_extension("component.wasm");
         if !path.exists() {
-            return Err(format!("pipe not found: {}", path.display()));
+            return Err(PipeLoadError::NotFound(format!(
+                "pipe not found: {}",
+                path.display()
+            )));
         }
         if !path.is_file() {
-            return Err(format!("pipe is not a file: {}", path.display()));
+            return Err(PipeLoadError::LoadFailed(format!(
+                "pipe is not a file: {}",
+                path.display()
+            )));
         }
         Ok(path)
     }
 
     fn instantiate_pipe(&self, path: &Path) -> Result<LoadedPipe, String> {
-        let bytes =
-            fs::read(path).map_err(|e| format!("failed to read pipe {}: {e}", path.display()))?;
-        let component = Component::from_binary(&self.engine, &bytes)
-            .map_err(|e| format!("failed to parse pipe {}: {e}", path.display()))?;
+        let compiled = pipe_cache::get_or_compile(&self.pipe_cache, &self.engine, path)?;
         let linker = Linker::new(&self.engine);
         let mut store = Store::new(&self.engine, PipeState);
-        let instance =
-            pipe_plugin_bindings::PipePlugin::instantiate(&mut store, &component, &linker)
-                .map_err(|e| format!("failed to instantiate pipe {}: {e}", path.display()))?;
-        let meta = instance
-            .wacli_cli_pipe()
+        // Instantiation and `meta()` share one budget; each `process()` gets its own.
+        self.limits
+            .arm(&mut store)
+            .map_err(|e| format!("failed to prepare pipe {}: {e}", path.display()))?;
+        let describe = |err: wasmtime::Error, what: &str| match self.limits.describe_trap(&err) {
+            Some(reason) => format!("pipe {} was {reason}", path.display()),
+            None => format!("failed to {what} {}: {err}", path.display()),
+        };
+        let pre = linker
+            .instantiate_pre(&compiled.component)
+            .map_err(|e| describe(e, "instantiate pipe"))?;
+        let guest_indices =
+            pipe_guest::GuestIndices::new(&pre).map_err(|e| describe(e, "instantiate pipe"))?;
+        let stream_indices = stream_guest::GuestIndices::new(&pre).ok();
+        let instance = pre
+            .instantiate(&mut store)
+            .map_err(|e| describe(e, "instantiate pipe"))?;
+        let guest = guest_indices
+            .load(&mut store, &instance)
+            .map_err(|e| describe(e, "instantiate pipe"))?;
+        let stream = stream_indices
+            .map(|indices| indices.load(&mut store, &instance))
+            .transpose()
+            .map_err(|e| describe(e, "instantiate pipe"))?;
+        let meta = guest
             .call_meta(&mut store)
-            .map_err(|e| format!("failed to read pipe metadata {}: {e}", path.display()))?;
+            .map_err(|e| describe(e, "read pipe metadata"))?;
+        let options = pipe_options::OptionSchema::from_metadata(&meta.name, &compiled.metadata)
+            .map_err(|e| format!("invalid option schema in pipe {}: {e}", path.display()))?;
         Ok(LoadedPipe {
             store,
-            instance,
+            guest,
+            stream,
             meta,
+            options,
+            spec_options: Vec::new(),
+            sessions: HashMap::new(),
+            next_session: 0,
         })
     }
 }
 
-fn detect_command(args: &[String]) -> Option<String> {
-    args.iter().find(|arg| !arg.starts_with('-')).cloned()
+/// Split a pipe spec (`show/format/table?width=80`) into its normalized
+/// name, scoped under `current_command`, and its inline options.
+fn normalize_pipe_name(
+    name: &str,
+    current_command: Option<&str>,
+) -> Result<(String, Vec<String>), String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("pipe name is empty".to_string());
+    }
+    if trimmed.contains('\\') {
+        return Err("pipe name must use '/' separators".to_string());
+    }
+    let spec = pipe_spec::parse(trimmed.trim_start_matches('/'))?;
+    let mut normalized = spec.name.to_string();
+    if let Some(stripped) = normalized.strip_suffix(".component.wasm") {
+        normalized = stripped.to_string();
+    }
+    if let Some(cmd) = current_command {
+        let prefix = format!("{cmd}/");
+        if !normalized.starts_with(&prefix) {
+            normalized = format!("{prefix}{normalized}");
+        }
+    }
+    if !is_valid_pipe_name(&normalized) {
+        return Err(format!("invalid pipe name '{normalized}'"));
+    }
+    Ok((normalized, spec.options))
+}
+
+/// Environment variable consulted by [`resolve_plugins_dir`].
+pub const PLUGINS_DIR_ENV: &str = "WACLI_PLUGINS_DIR";
+
+/// Pipe directory used when neither a flag nor [`PLUGINS_DIR_ENV`] is set.
+pub const DEFAULT_PLUGINS_DIR: &str = "plugins";
+
+/// Pick the pipe directory: `flag`, then a non-empty `env` value, then
+/// [`DEFAULT_PLUGINS_DIR`]. Relative paths are joined onto `cwd`, so they
+/// never depend on where the component lives.
+pub fn resolve_plugins_dir(
+    flag: Option<PathBuf>,
+    env: Option<std::ffi::OsString>,
+    cwd: &Path,
+) -> PathBuf {
+    let dir = flag
+        .or_else(|| env.filter(|value| !value.is_empty()).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_PLUGINS_DIR));
+    cwd.join(dir)
+}
+
+/// The command named by the guest's argv: the first argument that is
+/// neither a flag nor the value of one of `value_flags`. Detection stops at
+/// `--`, since everything after it is an operand.
+fn detect_command(args: &[String], value_flags: &[String]) -> Option<String> {
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            return Some(arg.clone());
+        }
+        if !arg.contains('=') && value_flags.iter().any(|flag| flag == arg) {
+            args.next_if(|value| *value != "--");
+        }
+    }
+    None
 }
 
 fn collect_pipe_infos(
@@ -377,9 +921,12 @@ fn collect_pipe_infos(
         if !is_valid_pipe_name(&rel_str) {
             continue;
         }
+        let summary = pipe_metadata::cached(&path)
+            .map(|meta| meta.summary.clone())
+            .unwrap_or_default();
         out.push(pipe_runtime::PipeInfo {
             name: rel_str,
-            summary: String::new(),
+            summary,
             path: path.display().to_string(),
         });
     }
@@ -398,6 +945,29 @@ fn convert_pipe_meta(
     }
 }
 
+fn invalid_handle(err: wasmtime::component::ResourceTableError) -> pipe_runtime::PipeError {
+    pipe_runtime::PipeError::TransformError(format!("pipe handle is invalid: {err}"))
+}
+
+/// The outcome of a call into pipe `name`, with traps from the execution
+/// limits explained.
+fn pipe_call_result<T>(
+    limits: &RunnerConfig,
+    name: &str,
+    result: wasmtime::Result<Result<T, pipe_plugin_bindings::wacli::cli::types::PipeError>>,
+) -> Result<T, pipe_runtime::PipeError> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => Err(convert_pipe_error(err)),
+        Err(err) => Err(pipe_runtime::PipeError::TransformError(
+            match limits.describe_trap(&err) {
+                Some(reason) => format!("pipe '{name}' was {reason}"),
+                None => format!("pipe execution failed: {err}"),
+            },
+        )),
+    }
+}
+
 fn convert_pipe_error(
     err: pipe_plugin_bindings::wacli::cli::types::PipeError,
 ) -> pipe_runtime::PipeError {
@@ -442,3 +1012,188 @@ fn is_valid_command_name(name: &str) -> bool {
     }
     !name.ends_with('-')
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_command, normalize_pipe_name, resolve_plugins_dir};
+    use std::path::{Path, PathBuf};
+
+    /// A run's host state loading pipes from `testdata/`.
+    fn fixture_host() -> super::HostState {
+        let engine = super::Runner::new().unwrap().engine;
+        super::HostState {
+            ctx: super::WasiCtxBuilder::new().build(),
+            table: super::ResourceTable::new(),
+            engine,
+            limits: super::RunnerConfig::default(),
+            plugins_dir: Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata"),
+            current_command: None,
+            pipe_cache: Default::default(),
+        }
+    }
+
+    #[test]
+    fn open_pipe_says_why_loading_failed() {
+        use super::PipeLoadError;
+        use super::pipe_runtime::Host;
+
+        let mut host = fixture_host();
+        assert!(matches!(
+            host.open_pipe("a\\b".to_string()),
+            Err(PipeLoadError::InvalidName(msg)) if msg == "pipe name must use '/' separators"
+        ));
+        let Err(PipeLoadError::NotFound(msg)) = host.open_pipe("nope".to_string()) else {
+            panic!("expected NotFound");
+        };
+        assert!(msg.starts_with("pipe not found: "), "{msg}");
+        // `load-pipe` gets the bare message.
+        assert_eq!(host.load_pipe("nope".to_string()).unwrap_err(), msg);
+        assert!(host.open_pipe("table".to_string()).is_ok());
+    }
+
+    #[test]
+    fn large_inputs_stream_through_pipes_in_chunks() {
+        use super::pipe_runtime::{Host, HostPipe};
+
+        let line = "row one, row two, row three\n";
+        let input = line.repeat(4 * 1024 * 1024 / line.len());
+        let mut host = fixture_host();
+        // `table-nostream` predates `pipe-stream`: the host buffers for it.
+        for name in ["table", "table-nostream"] {
+            let pipe = host.load_pipe(name.to_string()).unwrap();
+            let rep = || wasmtime::component::Resource::<super::LoadedPipe>::new_borrow(pipe.rep());
+            assert_eq!(
+                host.table.get(&rep()).unwrap().stream.is_some(),
+                name == "table"
+            );
+            let session = host
+                .process_start(rep(), vec!["case=upper".to_string()])
+                .unwrap();
+            let mut output = Vec::new();
+            for chunk in input.as_bytes().chunks(64 * 1024) {
+                output.extend(host.process_chunk(rep(), session, chunk.to_vec()).unwrap());
+            }
+            output.extend(host.process_end(rep(), session).unwrap());
+            assert!(output == input.to_uppercase().into_bytes(), "{name}");
+
+            assert!(
+                matches!(
+                    host.process_end(rep(), session),
+                    Err(super::pipe_runtime::PipeError::TransformError(msg))
+                        if msg == format!("pipe 'format/table' has no session {session}")
+                ),
+                "{name}"
+            );
+            assert!(matches!(
+                host.process_start(rep(), vec!["case=title".to_string()]),
+                Err(super::pipe_runtime::PipeError::InvalidOption(_))
+            ));
+            HostPipe::drop(&mut host, pipe).unwrap();
+        }
+    }
+
+    #[test]
+    fn pipe_names_strip_inline_options_before_validation() {
+        let ok = |name: &str, options: &[&str]| {
+            Ok((
+                name.to_string(),
+                options.iter().map(|o| o.to_string()).collect::<Vec<_>>(),
+            ))
+        };
+        assert_eq!(
+            normalize_pipe_name("json?pretty", Some("show")),
+            ok("show/json", &["pretty"])
+        );
+        assert_eq!(
+            normalize_pipe_name("/show/format/table?width=80,header", Some("show")),
+            ok("show/format/table", &["width=80", "header"])
+        );
+        assert_eq!(normalize_pipe_name("table?", None), ok("table", &[]));
+        assert_eq!(
+            normalize_pipe_name("table.component.wasm?x=1", None),
+            ok("table", &["x=1"])
+        );
+    }
+
+    #[test]
+    fn pipe_names_reject_question_marks_inside_segments() {
+        assert_eq!(
+            normalize_pipe_name("format?x/table", Some("show")).unwrap_err(),
+            "pipe spec 'format?x/table' has '?' inside a path segment; \
+             options must follow the full pipe name"
+        );
+        assert_eq!(
+            normalize_pipe_name("Table?x", None).unwrap_err(),
+            "invalid pipe name 'Table'"
+        );
+    }
+
+    #[test]
+    fn plugins_dir_prefers_flag_then_env_then_default() {
+        let cwd = Path::new("/work");
+        let flag = || Some(PathBuf::from("from-flag"));
+        let env = || Some("from-env".into());
+        assert_eq!(
+            resolve_plugins_dir(flag(), env(), cwd),
+            Path::new("/work/from-flag")
+        );
+        assert_eq!(
+            resolve_plugins_dir(None, env(), cwd),
+            Path::new("/work/from-env")
+        );
+        assert_eq!(
+            resolve_plugins_dir(None, Some("".into()), cwd),
+            Path::new("/work/plugins")
+        );
+        assert_eq!(
+            resolve_plugins_dir(None, None, cwd),
+            Path::new("/work/plugins")
+        );
+        assert_eq!(
+            resolve_plugins_dir(Some(PathBuf::from("/opt/pipes")), env(), cwd),
+            Path::new("/opt/pipes")
+        );
+    }
+
+    fn detect(args: &[&str], value_flags: &[&str]) -> Option<String> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let flags: Vec<String> = value_flags.iter().map(|f| f.to_string()).collect();
+        detect_command(&args, &flags)
+    }
+
+    #[test]
+    fn command_detection_skips_flags_and_their_values() {
+        let flags = ["--config", "-c"];
+        assert_eq!(
+            detect(&["show", "--format", "json"], &flags).as_deref(),
+            Some("show")
+        );
+        assert_eq!(
+            detect(&["--config", "prod.toml", "show"], &flags).as_deref(),
+            Some("show")
+        );
+        assert_eq!(
+            detect(&["-c", "prod.toml", "show"], &flags).as_deref(),
+            Some("show")
+        );
+        assert_eq!(
+            detect(&["--config=prod.toml", "--verbose", "show"], &flags).as_deref(),
+            Some("show")
+        );
+        // Undeclared flags are assumed to be switches.
+        assert_eq!(
+            detect(&["--config", "prod.toml", "show"], &[]).as_deref(),
+            Some("prod.toml")
+        );
+    }
+
+    #[test]
+    fn command_detection_stops_at_double_dash_and_handles_no_command() {
+        assert_eq!(detect(&["--", "show"], &[]), None);
+        assert_eq!(detect(&["show", "--", "x"], &[]).as_deref(), Some("show"));
+        assert_eq!(detect(&["--config", "--", "show"], &["--config"]), None);
+        assert_eq!(detect(&[], &[]), None);
+        assert_eq!(detect(&["--help"], &[]), None);
+        assert_eq!(detect(&["--config"], &["--config"]), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! Execution limits for `wacli run`: a wall-clock timeout and a fuel budget.
+//!
+//! The timeout uses epoch interruption: a ticker thread bumps the engine's
+//! epoch every [`TICK`] for as long as the engine is alive, and every store
+//! gets a deadline of enough ticks to cover the timeout. Fuel is a
+//! deterministic budget of wasm instructions, refilled in the same places.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use wasmtime::{Engine, Store, Trap};
+
+/// Epoch granularity for [`RunnerConfig::timeout`].
+const TICK: Duration = Duration::from_millis(10);
+
+/// Execution limits applied by a [`Runner`](crate::Runner) to the composed
+/// component and to every pipe it loads. Both default to unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct RunnerConfig {
+    /// Wall-clock limit for a run, and for each call into a loaded pipe.
+    pub timeout: Option<Duration>,
+    /// Fuel for a run, and for each call into a loaded pipe.
+    pub fuel: Option<u64>,
+}
+
+impl RunnerConfig {
+    pub(crate) fn configure(&self, config: &mut wasmtime::Config) {
+        config.epoch_interruption(self.timeout.is_some());
+        config.consume_fuel(self.fuel.is_some());
+    }
+
+    /// Start the epoch ticker for `engine` if a timeout is set. The thread
+    /// exits once the engine is dropped.
+    pub(crate) fn start_ticker(&self, engine: &Engine) {
+        if self.timeout.is_none() {
+            return;
+        }
+        let engine = engine.weak();
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(TICK);
+                match engine.upgrade() {
+                    Some(engine) => engine.increment_epoch(),
+                    None => break,
+                }
+            }
+        });
+    }
+
+    /// Give `store` a fresh deadline and fuel budget.
+    pub(crate) fn arm<T>(&self, store: &mut Store<T>) -> Result<()> {
+        if let Some(timeout) = self.timeout {
+            let ticks = timeout.as_millis().div_ceil(TICK.as_millis()).max(1);
+            store.set_epoch_deadline(u64::try_from(ticks).unwrap_or(u64::MAX));
+        }
+        if let Some(fuel) = self.fuel {
+            store.set_fuel(fuel).context("failed to set fuel")?;
+        }
+        Ok(())
+    }
+
+    /// Explain a trap caused by one of the limits, or `None` for any other
+    /// error.
+    pub(crate) fn describe_trap(&self, err: &anyhow::Error) -> Option<String> {
+        match err.downcast_ref::<Trap>()? {
+            Trap::Interrupt => Some(format!(
+                "interrupted due to timeout (limit: {:?})",
+                self.timeout.unwrap_or_default()
+            )),
+            Trap::OutOfFuel => Some(format!(
+                "stopped after running out of fuel (limit: {})",
+                self.fuel.unwrap_or_default()
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Runner;
+
+    /// A `wasi:cli/run` component whose `run` loops `iterations` times
+    /// (forever for `None`) and then succeeds.
+    fn run_component(name: &str, iterations: Option<u32>) -> std::path::PathBuf {
+        let body = match iterations {
+            None => "(loop $l (br $l)) i32.const 0".to_string(),
+            Some(n) => format!(
+                "(local $i i32) (local.set $i (i32.const {n})) \
+                 (block $done (loop $l \
+                   (br_if $done (i32.eqz (local.get $i))) \
+                   (local.set $i (i32.sub (local.get $i) (i32.const 1))) \
+                   (br $l))) \
+                 i32.const 0"
+            ),
+        };
+        let wat = format!(
+            r#"(component
+                 (core module $m (func (export "run") (result i32) {body}))
+                 (core instance $i (instantiate $m))
+                 (func $run (result (result)) (canon lift (core func $i "run")))
+                 (instance $r (export "run" (func $run)))
+                 (export "wasi:cli/run@0.2.0" (instance $r)))"#
+        );
+        let path =
+            std::env::temp_dir().join(format!("wacli-limits-{}-{name}.wat", std::process::id()));
+        std::fs::write(&path, wat).unwrap();
+        path
+    }
+
+    fn run(config: RunnerConfig, component: &std::path::Path) -> Result<u32> {
+        Runner::new_with_config(config)?.run_component(component, &[])
+    }
+
+    #[test]
+    fn timeout_interrupts_a_spinning_component() {
+        let spin = run_component("spin-timeout", None);
+        let config = RunnerConfig {
+            timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let err = run(config, &spin).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "component was interrupted due to timeout (limit: 50ms)"
+        );
+        let _ = std::fs::remove_file(spin);
+    }
+
+    #[test]
+    fn fuel_stops_a_spinning_component() {
+        let spin = run_component("spin-fuel", None);
+        let config = RunnerConfig {
+            fuel: Some(10_000),
+            ..Default::default()
+        };
+        let err = run(config, &spin).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "component was stopped after running out of fuel (limit: 10000)"
+        );
+        let _ = std::fs::remove_file(spin);
+    }
+
+    #[test]
+    fn components_within_the_limits_finish() {
+        let finite = run_component("finite", Some(100));
+        let config = RunnerConfig {
+            timeout: Some(Duration::from_secs(30)),
+            fuel: Some(1_000_000),
+        };
+        assert_eq!(run(config, &finite).unwrap(), 0);
+        assert_eq!(run(RunnerConfig::default(), &finite).unwrap(), 0);
+        let _ = std::fs::remove_file(finite);
+    }
+}
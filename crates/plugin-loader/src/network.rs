@@ -0,0 +1,223 @@
+//! Which sockets a guest may use.
+//!
+//! `p2::add_to_linker_sync` links `wasi:sockets`, so a composed CLI that
+//! imports it could reach the network. The runner denies every socket
+//! operation and name lookup unless the policy allows them, optionally only
+//! for some `host:port` patterns.
+
+use anyhow::{Context, Result, bail};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::str::FromStr;
+use wasmtime_wasi::WasiCtxBuilder;
+
+/// Network access for the guest. The default denies everything.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkPolicy {
+    /// Allow TCP, UDP and name lookups.
+    pub allow: bool,
+    /// With `allow`, only connect/bind/send to addresses matching one of
+    /// these; empty means any address.
+    pub hosts: Vec<HostPattern>,
+}
+
+/// `HOST:PORT`, where `HOST` is a name, an IP (`[v6]` in brackets) or `*`,
+/// and `PORT` is a number or `*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostPattern {
+    host: Option<String>,
+    port: Option<u16>,
+}
+
+impl FromStr for HostPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |why: &str| format!("invalid host pattern '{s}': {why}");
+        let (host, port) = s
+            .trim()
+            .rsplit_once(':')
+            .ok_or_else(|| invalid("expected HOST:PORT"))?;
+        let host = match host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+            Some(v6) if v6.parse::<IpAddr>().is_ok() => v6,
+            Some(_) => return Err(invalid("expected an IPv6 address in brackets")),
+            None if host.contains(':') => {
+                return Err(invalid("IPv6 addresses must be in brackets"));
+            }
+            None => host,
+        };
+        if host.is_empty() {
+            return Err(invalid("empty host"));
+        }
+        let port = match port {
+            "*" => None,
+            p => Some(
+                p.parse::<u16>()
+                    .map_err(|_| invalid("port must be a number or *"))?,
+            ),
+        };
+        Ok(Self {
+            host: (host != "*").then(|| host.to_string()),
+            port,
+        })
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.host {
+            Some(host) if host.contains(':') => write!(f, "[{host}]")?,
+            Some(host) => f.write_str(host)?,
+            None => f.write_str("*")?,
+        }
+        match self.port {
+            Some(port) => write!(f, ":{port}"),
+            None => f.write_str(":*"),
+        }
+    }
+}
+
+impl fmt::Display for NetworkPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.allow {
+            return f.write_str("deny");
+        }
+        if self.hosts.is_empty() {
+            return f.write_str("allow (any host)");
+        }
+        let hosts: Vec<String> = self.hosts.iter().map(ToString::to_string).collect();
+        write!(f, "allow ({})", hosts.join(", "))
+    }
+}
+
+/// A host pattern with its name resolved to addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AddrRule {
+    /// `None` matches any address.
+    ips: Option<Vec<IpAddr>>,
+    port: Option<u16>,
+}
+
+impl AddrRule {
+    fn matches(&self, addr: SocketAddr) -> bool {
+        self.port.is_none_or(|p| p == addr.port())
+            && self.ips.as_ref().is_none_or(|ips| ips.contains(&addr.ip()))
+    }
+}
+
+impl NetworkPolicy {
+    /// Configure `builder` to enforce this policy.
+    ///
+    /// Host names in the allowlist are resolved here, once per run.
+    pub(crate) fn apply(&self, builder: &mut WasiCtxBuilder) -> Result<()> {
+        builder
+            .allow_tcp(self.allow)
+            .allow_udp(self.allow)
+            .allow_ip_name_lookup(self.allow);
+        if !self.allow {
+            // The default address check already refuses every address.
+            return Ok(());
+        }
+        if self.hosts.is_empty() {
+            builder.inherit_network();
+            return Ok(());
+        }
+        let rules = self.hosts.iter().map(resolve).collect::<Result<Vec<_>>>()?;
+        builder.socket_addr_check(move |addr, _| {
+            let allowed = rules.iter().any(|rule| rule.matches(addr));
+            Box::pin(async move { allowed })
+        });
+        Ok(())
+    }
+}
+
+fn resolve(pattern: &HostPattern) -> Result<AddrRule> {
+    let Some(host) = &pattern.host else {
+        return Ok(AddrRule {
+            ips: None,
+            port: pattern.port,
+        });
+    };
+    let ips: Vec<IpAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![ip],
+        Err(_) => (host.as_str(), 0)
+            .to_socket_addrs()
+            .with_context(|| format!("failed to resolve allowed host '{host}'"))?
+            .map(|addr| addr.ip())
+            .collect(),
+    };
+    if ips.is_empty() {
+        bail!("allowed host '{host}' resolved to no addresses");
+    }
+    Ok(AddrRule {
+        ips: Some(ips),
+        port: pattern.port,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(s: &str) -> HostPattern {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn host_patterns_parse_and_display() {
+        for s in ["example.com:443", "*:8080", "10.0.0.1:*", "[::1]:53", "*:*"] {
+            assert_eq!(pattern(s).to_string(), s);
+        }
+        assert_eq!(
+            "example.com".parse::<HostPattern>().unwrap_err(),
+            "invalid host pattern 'example.com': expected HOST:PORT"
+        );
+        assert_eq!(
+            ":80".parse::<HostPattern>().unwrap_err(),
+            "invalid host pattern ':80': empty host"
+        );
+        assert_eq!(
+            "::1:53".parse::<HostPattern>().unwrap_err(),
+            "invalid host pattern '::1:53': IPv6 addresses must be in brackets"
+        );
+        assert_eq!(
+            "a:http".parse::<HostPattern>().unwrap_err(),
+            "invalid host pattern 'a:http': port must be a number or *"
+        );
+    }
+
+    #[test]
+    fn policy_display_names_the_effective_policy() {
+        assert_eq!(NetworkPolicy::default().to_string(), "deny");
+        let any = NetworkPolicy {
+            allow: true,
+            hosts: Vec::new(),
+        };
+        assert_eq!(any.to_string(), "allow (any host)");
+        let some = NetworkPolicy {
+            allow: true,
+            hosts: vec![pattern("10.0.0.1:443"), pattern("*:53")],
+        };
+        assert_eq!(some.to_string(), "allow (10.0.0.1:443, *:53)");
+    }
+
+    #[test]
+    fn rules_match_addresses_and_ports() {
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+        let exact = resolve(&pattern("10.0.0.1:443")).unwrap();
+        assert!(exact.matches(addr("10.0.0.1:443")));
+        assert!(!exact.matches(addr("10.0.0.1:80")));
+        assert!(!exact.matches(addr("10.0.0.2:443")));
+
+        let any_port = resolve(&pattern("[::1]:*")).unwrap();
+        assert!(any_port.matches(addr("[::1]:1234")));
+        assert!(!any_port.matches(addr("127.0.0.1:1234")));
+
+        let any_host = resolve(&pattern("*:53")).unwrap();
+        assert!(any_host.matches(addr("192.0.2.7:53")));
+        assert!(!any_host.matches(addr("192.0.2.7:54")));
+
+        let localhost = resolve(&pattern("localhost:8080")).unwrap();
+        assert!(localhost.matches(addr("127.0.0.1:8080")) || localhost.matches(addr("[::1]:8080")));
+    }
+}
@@ -0,0 +1,209 @@
+//! Compiled pipe components, reused across `load-pipe` calls.
+//!
+//! Compiling a pipe is the expensive part of loading it; instantiating the
+//! compiled `Component` into a fresh store is cheap. A [`Runner`] keeps one
+//! cache for all its runs, keyed by the resolved path and checked against the
+//! file's mtime and size like `pipe_metadata`, so a rebuilt pipe is compiled
+//! again. Past its capacity the least recently used entry is dropped.
+//!
+//! The cache is shared behind a mutex, which [`get_or_compile`] holds only to
+//! look up and to insert: a pipe compiling does not hold up loads of others.
+//! Two loads of one uncached pipe may then both compile it; the last to
+//! finish keeps its entry.
+//!
+//! [`Runner`]: crate::Runner
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use wacli_metadata::PipeMetadataV1;
+use wasmtime::Engine;
+use wasmtime::component::Component;
+
+/// Entries kept by [`ComponentCache::default`].
+pub const DEFAULT_CAPACITY: usize = 16;
+
+type Stamp = (SystemTime, u64);
+
+/// A compiled pipe and what was read from its bytes.
+#[derive(Clone)]
+pub struct CompiledPipe {
+    pub component: Component,
+    /// The `PIPE_METADATA_SECTION`, or why it could not be read.
+    pub metadata: Arc<Result<Option<PipeMetadataV1>, String>>,
+}
+
+pub struct ComponentCache {
+    capacity: usize,
+    /// Most recently used last.
+    entries: Vec<(PathBuf, Stamp, CompiledPipe)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for ComponentCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ComponentCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The entry for `path` if the file is unchanged since it was compiled.
+    fn get(&mut self, path: &Path, stamp: Stamp) -> Option<CompiledPipe> {
+        if let Some(pos) = self.entries.iter().position(|(p, _, _)| p == path) {
+            let entry = self.entries.remove(pos);
+            if entry.1 == stamp {
+                self.hits += 1;
+                let pipe = entry.2.clone();
+                self.entries.push(entry);
+                return Some(pipe);
+            }
+        }
+        self.misses += 1;
+        None
+    }
+
+    fn insert(&mut self, path: &Path, stamp: Stamp, pipe: CompiledPipe) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Another load may have compiled the same file meanwhile.
+        self.entries.retain(|(p, _, _)| p != path);
+        if self.entries.len() >= self.capacity {
+            self.entries.remove(0);
+        }
+        self.entries.push((path.to_path_buf(), stamp, pipe));
+    }
+
+    /// Loads served from the cache.
+    #[cfg(test)]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Loads that compiled the pipe.
+    #[cfg(test)]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+/// The pipe at `path`, compiled with `engine` unless `cache` has an entry
+/// for the unchanged file. `cache` is not locked while compiling.
+pub fn get_or_compile(
+    cache: &Mutex<ComponentCache>,
+    engine: &Engine,
+    path: &Path,
+) -> Result<CompiledPipe, String> {
+    let lock = || cache.lock().unwrap_or_else(|e| e.into_inner());
+    let stat =
+        fs::metadata(path).map_err(|e| format!("failed to read pipe {}: {e}", path.display()))?;
+    let stamp = (
+        stat.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        stat.len(),
+    );
+    if let Some(pipe) = lock().get(path, stamp) {
+        return Ok(pipe);
+    }
+    let bytes =
+        fs::read(path).map_err(|e| format!("failed to read pipe {}: {e}", path.display()))?;
+    let component = Component::from_binary(engine, &bytes)
+        .map_err(|e| format!("failed to parse pipe {}: {e}", path.display()))?;
+    let pipe = CompiledPipe {
+        component,
+        metadata: Arc::new(crate::pipe_metadata::from_component(&bytes)),
+    };
+    lock().insert(path, stamp, pipe.clone());
+    Ok(pipe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata/table.component.wasm")
+    }
+
+    fn counts(cache: &Mutex<ComponentCache>) -> (u64, u64) {
+        let cache = cache.lock().unwrap();
+        (cache.hits(), cache.misses())
+    }
+
+    fn engine() -> Engine {
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        Engine::new(&config).unwrap()
+    }
+
+    #[test]
+    fn second_load_of_a_pipe_is_served_from_the_cache() {
+        let engine = engine();
+        let cache = Mutex::new(ComponentCache::default());
+        let first = get_or_compile(&cache, &engine, &table_fixture()).unwrap();
+        let second = get_or_compile(&cache, &engine, &table_fixture()).unwrap();
+        assert_eq!(counts(&cache), (1, 1));
+        assert!(Arc::ptr_eq(&first.metadata, &second.metadata));
+        let meta = second.metadata.as_ref().as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(meta.options.len(), 2);
+    }
+
+    #[test]
+    fn concurrent_loads_of_one_pipe_keep_one_entry() {
+        let engine = engine();
+        let cache = Mutex::new(ComponentCache::default());
+        std::thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| get_or_compile(&cache, &engine, &table_fixture()).unwrap());
+            }
+        });
+        let (hits, misses) = counts(&cache);
+        assert_eq!(hits + misses, 2);
+        assert_eq!(cache.lock().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn zero_capacity_compiles_every_load() {
+        let engine = engine();
+        let cache = Mutex::new(ComponentCache::new(0));
+        get_or_compile(&cache, &engine, &table_fixture()).unwrap();
+        get_or_compile(&cache, &engine, &table_fixture()).unwrap();
+        assert_eq!(counts(&cache), (0, 2));
+    }
+
+    #[test]
+    fn changed_files_are_recompiled_and_old_entries_evicted() {
+        let dir = std::env::temp_dir().join(format!("wacli-pipe-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.component.wasm"), dir.join("b.component.wasm"));
+        fs::copy(table_fixture(), &a).unwrap();
+        fs::copy(table_fixture(), &b).unwrap();
+        let engine = engine();
+        let cache = Mutex::new(ComponentCache::new(1));
+
+        get_or_compile(&cache, &engine, &a).unwrap();
+        get_or_compile(&cache, &engine, &b).unwrap();
+        // `b` evicted `a`.
+        get_or_compile(&cache, &engine, &a).unwrap();
+        assert_eq!(counts(&cache), (0, 3));
+
+        // A different size means a rebuilt pipe, even within one mtime tick.
+        fs::write(&a, b"\0asm\x01\0\0\0").unwrap();
+        let err = get_or_compile(&cache, &engine, &a).err().unwrap();
+        assert!(err.starts_with("failed to parse pipe"), "{err}");
+        assert_eq!(counts(&cache).1, 4);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,104 @@
+//! Reading a pipe's `PIPE_METADATA_SECTION` without instantiating it.
+//!
+//! `list-pipes` and `describe-pipes` run on every `--format help`, so parsed
+//! sections are cached per path and keyed by the file's mtime and size: an
+//! unchanged pipe is read once per process, a rebuilt one is read again.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use wacli_metadata::{PIPE_METADATA_SECTION, PipeMetadataV1};
+use wasmparser::{Parser, Payload};
+
+/// Parse the metadata section out of a pipe component, if it has one.
+pub fn from_component(bytes: &[u8]) -> Result<Option<PipeMetadataV1>, String> {
+    for payload in Parser::new(0).parse_all(bytes) {
+        let payload = payload.map_err(|e| format!("failed to parse WASM: {e}"))?;
+        let Payload::CustomSection(reader) = payload else {
+            continue;
+        };
+        if reader.name() != PIPE_METADATA_SECTION {
+            continue;
+        }
+        let meta: PipeMetadataV1 = serde_json::from_slice(reader.data())
+            .map_err(|e| format!("failed to parse pipe metadata JSON: {e}"))?;
+        if meta.format_version != 1 {
+            return Err(format!(
+                "unsupported pipe metadata format-version {} (expected 1)",
+                meta.format_version
+            ));
+        }
+        return Ok(Some(meta));
+    }
+    Ok(None)
+}
+
+type Stamp = (SystemTime, u64);
+type Cache = HashMap<PathBuf, (Stamp, Option<Arc<PipeMetadataV1>>)>;
+
+fn cache() -> &'static Mutex<Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// The metadata of the pipe at `path`, from the cache while the file is
+/// unchanged.
+///
+/// Unreadable files and invalid sections yield `None`: listing is best
+/// effort, and loading the pipe reports the actual error.
+pub fn cached(path: &Path) -> Option<Arc<PipeMetadataV1>> {
+    let stat = fs::metadata(path).ok()?;
+    let stamp = (stat.modified().ok()?, stat.len());
+    if let Some((seen, meta)) = cache().lock().ok()?.get(path)
+        && *seen == stamp
+    {
+        return meta.clone();
+    }
+    let meta = fs::read(path)
+        .ok()
+        .and_then(|bytes| from_component(&bytes).ok().flatten())
+        .map(Arc::new);
+    if let Ok(mut cache) = cache().lock() {
+        cache.insert(path.to_path_buf(), (stamp, meta.clone()));
+    }
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata/table.component.wasm")
+    }
+
+    #[test]
+    fn reads_descriptive_fields_from_the_table_fixture() {
+        let meta = cached(&table_fixture()).expect("table fixture embeds pipe metadata");
+        assert_eq!(meta.summary, "Uppercase formatter (test)");
+        assert_eq!(meta.input_types, ["text/plain"]);
+        assert_eq!(meta.output_type, "text/plain");
+        assert_eq!(meta.version, "0.1.0");
+        assert_eq!(meta.options.len(), 2);
+    }
+
+    #[test]
+    fn cache_is_refreshed_when_the_file_changes() {
+        let dir = std::env::temp_dir().join(format!("wacli-pipe-meta-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("p.component.wasm");
+
+        // A bare core module without the section.
+        fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+        assert!(cached(&path).is_none());
+
+        fs::copy(table_fixture(), &path).unwrap();
+        let meta = cached(&path).expect("rewritten file is read again");
+        assert_eq!(meta.summary, "Uppercase formatter (test)");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
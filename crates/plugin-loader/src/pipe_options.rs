@@ -0,0 +1,227 @@
+//! Host-side validation of the options passed to a pipe's `process()`.
+//!
+//! Pipes may declare their options in the `PIPE_METADATA_SECTION` custom
+//! section. Each option string is `key=value` or a bare `key` and is checked
+//! with the same parser commands use, so a typo or an out-of-range value is
+//! rejected before the guest runs. Pipes without the section accept anything.
+
+use wacli_argparse::claplike::{self, ParseOutcome};
+use wacli_metadata::{ArgSchema, PipeMetadataV1};
+
+/// Options declared by a pipe, checked against each `process()` call.
+pub struct OptionSchema {
+    name: String,
+    options: Vec<OptionDef>,
+}
+
+pub struct OptionDef(ArgSchema);
+
+impl OptionSchema {
+    /// The declared options from a pipe's metadata section, if it has one.
+    pub fn from_metadata(
+        name: &str,
+        metadata: &Result<Option<PipeMetadataV1>, String>,
+    ) -> Result<Option<Self>, String> {
+        match metadata {
+            Ok(meta) => Ok(meta
+                .as_ref()
+                .map(|meta| Self::new(name, meta.options.clone()))),
+            Err(e) => Err(e.clone()),
+        }
+    }
+
+    pub fn new(name: &str, options: Vec<ArgSchema>) -> Self {
+        Self {
+            name: name.to_string(),
+            options: options.into_iter().map(OptionDef).collect(),
+        }
+    }
+
+    /// Check `options` (`key=value` or `key`) against the declared schema.
+    pub fn validate(&self, options: &[String]) -> Result<(), String> {
+        let argv: Vec<String> = options
+            .iter()
+            .map(|opt| format!("--{}", opt.trim_start_matches('-')))
+            .collect();
+        match claplike::parse(self, &argv) {
+            Ok(ParseOutcome::Matches(_)) => Ok(()),
+            // The builtin `--help`/`--version` flags are not pipe options.
+            Ok(ParseOutcome::Help(_)) => Err(format!("pipe '{}': unknown flag: --help", self.name)),
+            Ok(ParseOutcome::Version(_)) => {
+                Err(format!("pipe '{}': unknown flag: --version", self.name))
+            }
+            Err(err) => Err(format!("pipe '{}': {}", self.name, err.message())),
+        }
+    }
+}
+
+impl claplike::ArgDefLike for OptionDef {
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    fn short(&self) -> Option<&str> {
+        None
+    }
+
+    fn long(&self) -> Option<&str> {
+        // Options are always addressed by key; fall back to the name.
+        Some(self.0.long.as_deref().unwrap_or(&self.0.name))
+    }
+
+    fn help(&self) -> &str {
+        &self.0.help
+    }
+
+    fn required(&self) -> bool {
+        self.0.required
+    }
+
+    fn default_value(&self) -> Option<&str> {
+        self.0.default_value.as_deref()
+    }
+
+    fn value_name(&self) -> Option<&str> {
+        self.0.value_name.as_deref()
+    }
+
+    fn takes_value(&self) -> bool {
+        self.0.takes_value
+    }
+
+    fn multiple(&self) -> bool {
+        self.0.multiple
+    }
+
+    fn value_type(&self) -> Option<&str> {
+        self.0.value_type.as_deref()
+    }
+
+    fn possible_values(&self) -> &[String] {
+        self.0.possible_values.as_slice()
+    }
+
+    fn conflicts_with(&self) -> &[String] {
+        self.0.conflicts_with.as_slice()
+    }
+
+    fn requires(&self) -> &[String] {
+        self.0.requires.as_slice()
+    }
+}
+
+impl claplike::CommandMetaLike for OptionSchema {
+    type ArgDef = OptionDef;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn summary(&self) -> &str {
+        ""
+    }
+
+    fn usage(&self) -> &str {
+        ""
+    }
+
+    fn aliases(&self) -> &[String] {
+        &[]
+    }
+
+    fn version(&self) -> &str {
+        ""
+    }
+
+    fn hidden(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        ""
+    }
+
+    fn examples(&self) -> &[String] {
+        &[]
+    }
+
+    fn args(&self) -> &[Self::ArgDef] {
+        self.options.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wacli_metadata::ArgSchemaBuilder;
+
+    fn schema() -> OptionSchema {
+        OptionSchema::new(
+            "format/table",
+            vec![
+                ArgSchemaBuilder::new("case")
+                    .long("case")
+                    .possible_values(["upper", "lower"])
+                    .build(),
+                ArgSchemaBuilder::new("trim").long("trim").build(),
+            ],
+        )
+    }
+
+    fn validate(options: &[&str]) -> Result<(), String> {
+        let options: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+        schema().validate(&options)
+    }
+
+    #[test]
+    fn accepts_declared_options() {
+        validate(&[]).unwrap();
+        validate(&["case=lower", "trim"]).unwrap();
+        validate(&["--case=upper"]).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_values() {
+        assert_eq!(
+            validate(&["colour=red"]).unwrap_err(),
+            "pipe 'format/table': unknown flag: --colour"
+        );
+        assert_eq!(
+            validate(&["help"]).unwrap_err(),
+            "pipe 'format/table': unknown flag: --help"
+        );
+        assert!(
+            validate(&["case=title"])
+                .unwrap_err()
+                .starts_with("pipe 'format/table': invalid value 'title' for '--case'")
+        );
+        assert_eq!(
+            validate(&["trim=yes"]).unwrap_err(),
+            "pipe 'format/table': flag does not take a value: --trim"
+        );
+    }
+
+    #[test]
+    fn accepted_options_read_back_on_the_pipe_side() {
+        use wacli_argparse::pipe_options::Options;
+
+        let options: Vec<String> = ["--case=upper", "trim", "case=lower"]
+            .iter()
+            .map(|o| o.to_string())
+            .collect();
+        schema().validate(&options).unwrap();
+        let opts = Options::new(&options);
+        assert_eq!(opts.get("case"), Some("lower"));
+        assert!(opts.has("trim"));
+        assert!(opts.unknown_keys(&["case", "trim"]).is_empty());
+    }
+
+    #[test]
+    fn options_without_long_are_addressed_by_name() {
+        let schema = OptionSchema::new(
+            "p",
+            vec![ArgSchemaBuilder::new("width").takes_value(true).build()],
+        );
+        schema.validate(&["width=80".to_string()]).unwrap();
+    }
+}
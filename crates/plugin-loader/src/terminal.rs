@@ -0,0 +1,128 @@
+//! `terminal-runtime`: terminal control the host component cannot do
+//! through WASI.
+//!
+//! The host component calls `read-hidden-line` for `host-io.read-secret`
+//! only when the guest's stdin is the runner's inherited terminal. The line
+//! is read from the same `std::io::stdin()` wasmtime reads the guest's stdin
+//! from, with echo, line editing and signals turned off while it is typed,
+//! and the terminal mode is put back however the read ends.
+
+use std::io::{self, Read};
+
+/// Read one line from the terminal on stdin without echoing it.
+pub(crate) fn read_hidden_line() -> Result<Option<String>, String> {
+    let stdin = io::stdin();
+    let _restore = echo::off(&stdin).map_err(|e| format!("failed to turn off echo: {e}"))?;
+    read_typed_line(&mut stdin.lock())
+}
+
+/// Read a line typed with the terminal in non-canonical mode, handling what
+/// the terminal would have: Enter ends the line, Backspace and Ctrl-U edit
+/// it, Ctrl-C interrupts the read and Ctrl-D on an empty line is end of
+/// input.
+fn read_typed_line(input: &mut impl Read) -> Result<Option<String>, String> {
+    let mut line = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) if line.is_empty() => return Ok(None),
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("failed to read stdin: {e}")),
+        }
+        match byte[0] {
+            b'\r' | b'\n' => break,
+            0x03 => return Err("interrupted".to_string()),
+            0x04 if line.is_empty() => return Ok(None),
+            0x04 => {}
+            0x08 | 0x7f => pop_char(&mut line),
+            0x15 => line.clear(),
+            b => line.push(b),
+        }
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+}
+
+/// Remove the last UTF-8 character of `line`.
+fn pop_char(line: &mut Vec<u8>) {
+    while let Some(b) = line.pop() {
+        if b & 0xc0 != 0x80 {
+            break;
+        }
+    }
+}
+
+#[cfg(unix)]
+mod echo {
+    use rustix::termios::{
+        LocalModes, OptionalActions, SpecialCodeIndex, Termios, tcgetattr, tcsetattr,
+    };
+    use std::io;
+    use std::os::fd::{AsFd, BorrowedFd};
+
+    /// Puts the saved terminal mode back when dropped.
+    pub(super) struct Restore<'a> {
+        fd: BorrowedFd<'a>,
+        saved: Termios,
+    }
+
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let _ = tcsetattr(self.fd, OptionalActions::Now, &self.saved);
+        }
+    }
+
+    /// Turn off echo, canonical mode and signal keys on the terminal `fd`,
+    /// so Ctrl-C arrives as a byte instead of killing wacli with echo off.
+    pub(super) fn off(fd: &impl AsFd) -> io::Result<Restore<'_>> {
+        let fd = fd.as_fd();
+        let saved = tcgetattr(fd)?;
+        let mut raw = saved.clone();
+        raw.local_modes
+            .remove(LocalModes::ECHO | LocalModes::ICANON | LocalModes::ISIG);
+        raw.special_codes[SpecialCodeIndex::VMIN] = 1;
+        raw.special_codes[SpecialCodeIndex::VTIME] = 0;
+        tcsetattr(fd, OptionalActions::Now, &raw)?;
+        Ok(Restore { fd, saved })
+    }
+}
+
+#[cfg(not(unix))]
+mod echo {
+    use std::io;
+
+    /// Echo cannot be turned off here; the line is read as typed.
+    pub(super) fn off<T>(_fd: &T) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(bytes: &[u8]) -> Result<Option<String>, String> {
+        read_typed_line(&mut &bytes[..])
+    }
+
+    #[test]
+    fn typed_lines_are_edited_like_the_terminal_would() {
+        assert_eq!(typed(b"hunter2\rnext"), Ok(Some("hunter2".to_string())));
+        assert_eq!(typed(b"pass\n"), Ok(Some("pass".to_string())));
+        assert_eq!(
+            typed(b"pa\xc3\xa9\x7f\x7fss\r"),
+            Ok(Some("pss".to_string()))
+        );
+        assert_eq!(typed(b"wrong\x15right\r"), Ok(Some("right".to_string())));
+        assert_eq!(typed(b"\x7fok\x04\r"), Ok(Some("ok".to_string())));
+        assert_eq!(typed(b"partial"), Ok(Some("partial".to_string())));
+    }
+
+    #[test]
+    fn ctrl_c_interrupts_and_ctrl_d_ends_input() {
+        assert_eq!(typed(b"sec\x03ret\r"), Err("interrupted".to_string()));
+        assert_eq!(typed(b"\x04"), Ok(None));
+        assert_eq!(typed(b""), Ok(None));
+    }
+}
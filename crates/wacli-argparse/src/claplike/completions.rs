@@ -0,0 +1,459 @@
+//! Shell completion scripts rendered from command schemas.
+//!
+//! Scripts complete command names (aliases included), flags, and flag values:
+//! `possible_values` (or `true`/`false` for `value-type: bool`), paths for
+//! `FILE`/`PATH`/`DIR`-like value names, and otherwise whatever
+//! `<program> __complete <command...> <arg> <word>` prints. Hidden commands
+//! and args are left out.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use super::{ArgDefLike, CommandMetaLike, messages};
+
+/// A shell `render` can write a script for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// Names accepted by [`Shell::from_str`].
+    pub const NAMES: &[&str] = &["bash", "zsh", "fish"];
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(messages::invalid_value(s, "<SHELL>", Self::NAMES)),
+        }
+    }
+}
+
+/// Render a completion script for `program`.
+///
+/// A command name may hold several words (`db seed` for a grouped command);
+/// the leading words complete as commands of their own. Subcommands are
+/// walked recursively.
+pub fn render<M: CommandMetaLike>(shell: Shell, program: &str, commands: &[M]) -> String {
+    let nodes = collect(commands);
+    match shell {
+        Shell::Bash => render_bash(program, &nodes),
+        Shell::Zsh => render_zsh(program, &nodes),
+        Shell::Fish => render_fish(program, &nodes),
+    }
+}
+
+/// A command position: the root, a command, or a subcommand.
+struct Node {
+    /// Canonical words after the program (empty for the root).
+    path: Vec<String>,
+    aliases: Vec<String>,
+    summary: String,
+    flags: Vec<Flag>,
+}
+
+impl Node {
+    fn key(&self) -> String {
+        self.path.join(" ")
+    }
+
+    fn children<'n>(&self, nodes: &'n [Node]) -> impl Iterator<Item = &'n Node> {
+        let depth = self.path.len();
+        nodes
+            .iter()
+            .filter(move |n| n.path.len() == depth + 1 && n.path[..depth] == self.path[..])
+    }
+
+    /// The word that selects this node, then its aliases.
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .last()
+            .into_iter()
+            .chain(&self.aliases)
+            .map(String::as_str)
+    }
+}
+
+struct Flag {
+    arg: String,
+    long: Option<String>,
+    short: Option<String>,
+    help: String,
+    value: Option<Value>,
+}
+
+impl Flag {
+    fn spellings(&self) -> impl Iterator<Item = &str> {
+        self.long.iter().chain(&self.short).map(String::as_str)
+    }
+}
+
+enum Value {
+    List(Vec<String>),
+    Files,
+    Dirs,
+    /// Ask `<program> __complete`.
+    Callback,
+    /// Nothing to suggest.
+    Any,
+}
+
+fn collect<M: CommandMetaLike>(commands: &[M]) -> Vec<Node> {
+    let mut nodes = vec![Node {
+        path: Vec::new(),
+        aliases: Vec::new(),
+        summary: String::new(),
+        flags: builtin_flags(&[], true),
+    }];
+    for command in commands.iter().filter(|c| !c.hidden()) {
+        let path: Vec<String> = command
+            .name()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        if path.is_empty() {
+            continue;
+        }
+        // Leading words of a multi-word name (a group) complete on their own.
+        for depth in 1..path.len() {
+            if !nodes.iter().any(|n| n.path[..] == path[..depth]) {
+                nodes.push(Node {
+                    path: path[..depth].to_vec(),
+                    aliases: Vec::new(),
+                    summary: String::new(),
+                    flags: builtin_flags(&[], false),
+                });
+            }
+        }
+        push_command(&mut nodes, path, command, true);
+    }
+    nodes
+}
+
+/// Push `meta` and its subcommands. Only `top` commands resolve through
+/// `__complete`, so only their values fall back to it.
+fn push_command<M: CommandMetaLike>(nodes: &mut Vec<Node>, path: Vec<String>, meta: &M, top: bool) {
+    let mut flags = Vec::new();
+    for def in meta.args().iter().filter(|d| !d.hidden()) {
+        if def.long().is_none() && def.short().is_none() {
+            continue;
+        }
+        flags.push(Flag {
+            arg: def.name().to_string(),
+            long: def.long().map(super::normalize_long),
+            short: def.short().map(super::normalize_short),
+            help: def.help().trim().to_string(),
+            value: def.takes_value().then(|| value_of(def, top)),
+        });
+    }
+    flags.extend(builtin_flags(&flags, true));
+    nodes.push(Node {
+        path: path.clone(),
+        aliases: meta.aliases().to_vec(),
+        summary: meta.summary().trim().to_string(),
+        flags,
+    });
+    for sub in meta.subcommands().iter().filter(|s| !s.hidden()) {
+        let mut sub_path = path.clone();
+        sub_path.push(sub.name().to_string());
+        push_command(nodes, sub_path, sub, false);
+    }
+}
+
+fn value_of(def: &dyn ArgDefLike, callback: bool) -> Value {
+    if !def.possible_values().is_empty() {
+        return Value::List(def.possible_values().to_vec());
+    }
+    if def.value_type() == Some("bool") {
+        return Value::List(vec!["true".to_string(), "false".to_string()]);
+    }
+    let hint = def.value_name().unwrap_or_default().to_ascii_uppercase();
+    if hint.contains("DIR") {
+        return Value::Dirs;
+    }
+    if def.value_type() == Some("path") || hint.contains("FILE") || hint.contains("PATH") {
+        return Value::Files;
+    }
+    if callback {
+        Value::Callback
+    } else {
+        Value::Any
+    }
+}
+
+/// `-h/--help` and (optionally) `-V/--version`, unless `declared` uses them.
+fn builtin_flags(declared: &[Flag], version: bool) -> Vec<Flag> {
+    let taken = |s: &str| declared.iter().any(|f| f.spellings().any(|d| d == s));
+    let mut out = Vec::new();
+    let mut builtins = vec![("--help", "-h", "Show help information")];
+    if version {
+        builtins.push(("--version", "-V", "Show version information"));
+    }
+    for (long, short, help) in builtins {
+        if taken(long) {
+            continue;
+        }
+        out.push(Flag {
+            arg: String::new(),
+            long: Some(long.to_string()),
+            short: (!taken(short)).then(|| short.to_string()),
+            help: help.to_string(),
+            value: None,
+        });
+    }
+    out
+}
+
+fn function_name(program: &str) -> String {
+    program
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Single-quote `s` for bash/zsh.
+fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Single-quote `s` for fish.
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+/// `case` arms that walk the typed words to the canonical command key.
+fn push_resolve_arms(out: &mut String, nodes: &[Node], indent: &str) {
+    for node in nodes.iter().filter(|n| !n.path.is_empty()) {
+        let parent = node.path[..node.path.len() - 1].join(" ");
+        let patterns: Vec<String> = node
+            .words()
+            .map(|w| sh_quote(&format!("{parent}:{w}")))
+            .collect();
+        let _ = writeln!(
+            out,
+            "{indent}{}) cmd={} ;;",
+            patterns.join("|"),
+            sh_quote(&node.key())
+        );
+    }
+}
+
+/// Everything offered at `node`: subcommand words, then flags.
+fn candidates(node: &Node, nodes: &[Node]) -> Vec<String> {
+    let mut words: Vec<String> = node
+        .children(nodes)
+        .flat_map(|c| c.words().map(str::to_string).collect::<Vec<_>>())
+        .collect();
+    for flag in &node.flags {
+        words.extend(flag.spellings().map(str::to_string));
+    }
+    words
+}
+
+/// `<program> __complete <path...> <arg>` (the partial word is appended).
+fn callback_command(program: &str, node: &Node, flag: &Flag, quote: fn(&str) -> String) -> String {
+    let mut words = vec![quote(program), "__complete".to_string()];
+    words.extend(node.path.iter().map(|w| quote(w)));
+    words.push(quote(&flag.arg));
+    words.join(" ")
+}
+
+fn render_bash(program: &str, nodes: &[Node]) -> String {
+    let func = format!("_{}", function_name(program));
+    let mut out = String::new();
+    let _ = writeln!(out, "# bash completion for {program}");
+    let _ = writeln!(out, "# Load with: source <({program} completions bash)");
+    let _ = writeln!(out, "{func}() {{");
+    out.push_str(
+        "    local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n",
+    );
+    out.push_str("    local cmd=\"\" i\n");
+    out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+    out.push_str("        case \"$cmd:${COMP_WORDS[i]}\" in\n");
+    push_resolve_arms(&mut out, nodes, "            ");
+    out.push_str("        esac\n    done\n");
+
+    out.push_str("    case \"$cmd:$prev\" in\n");
+    for node in nodes {
+        for flag in &node.flags {
+            let action = match &flag.value {
+                Some(Value::List(values)) => {
+                    let words: Vec<&str> = values.iter().map(String::as_str).collect();
+                    format!(
+                        "COMPREPLY=($(compgen -W {} -- \"$cur\"))",
+                        sh_quote(&words.join(" "))
+                    )
+                }
+                Some(Value::Files) => "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string(),
+                Some(Value::Dirs) => "COMPREPLY=($(compgen -d -- \"$cur\"))".to_string(),
+                Some(Value::Callback) => format!(
+                    "COMPREPLY=($({} \"$cur\" 2>/dev/null))",
+                    callback_command(program, node, flag, sh_quote)
+                ),
+                Some(Value::Any) => "COMPREPLY=()".to_string(),
+                None => continue,
+            };
+            let patterns: Vec<String> = flag
+                .spellings()
+                .map(|s| sh_quote(&format!("{}:{s}", node.key())))
+                .collect();
+            let _ = writeln!(out, "        {}) {action}; return ;;", patterns.join("|"));
+        }
+    }
+    out.push_str("    esac\n");
+
+    out.push_str("    case \"$cmd\" in\n");
+    for node in nodes {
+        let _ = writeln!(
+            out,
+            "        {}) COMPREPLY=($(compgen -W {} -- \"$cur\")) ;;",
+            sh_quote(&node.key()),
+            sh_quote(&candidates(node, nodes).join(" "))
+        );
+    }
+    out.push_str("    esac\n}\n");
+    // `-o default`: positionals (and values with nothing to suggest) fall back to files.
+    let _ = writeln!(out, "complete -o default -F {func} {program}");
+    out
+}
+
+fn render_zsh(program: &str, nodes: &[Node]) -> String {
+    let func = format!("_{}", function_name(program));
+    let mut out = String::new();
+    let _ = writeln!(out, "#compdef {program}");
+    let _ = writeln!(out, "# zsh completion for {program}");
+    let _ = writeln!(out, "# Load with: source <({program} completions zsh)");
+    let _ = writeln!(out, "{func}() {{");
+    out.push_str("    local cur=\"${words[CURRENT]}\" prev=\"${words[CURRENT-1]}\" cmd=\"\" i\n");
+    out.push_str("    for ((i = 2; i < CURRENT; i++)); do\n");
+    out.push_str("        case \"$cmd:${words[i]}\" in\n");
+    push_resolve_arms(&mut out, nodes, "            ");
+    out.push_str("        esac\n    done\n");
+
+    out.push_str("    case \"$cmd:$prev\" in\n");
+    for node in nodes {
+        for flag in &node.flags {
+            let action = match &flag.value {
+                Some(Value::List(values)) => {
+                    let words: Vec<String> = values.iter().map(|v| sh_quote(v)).collect();
+                    format!("compadd -- {}", words.join(" "))
+                }
+                Some(Value::Files) => "_files".to_string(),
+                Some(Value::Dirs) => "_files -/".to_string(),
+                Some(Value::Callback) => format!(
+                    "compadd -- ${{(f)\"$({} \"$cur\" 2>/dev/null)\"}}",
+                    callback_command(program, node, flag, sh_quote)
+                ),
+                Some(Value::Any) => ":".to_string(),
+                None => continue,
+            };
+            let patterns: Vec<String> = flag
+                .spellings()
+                .map(|s| sh_quote(&format!("{}:{s}", node.key())))
+                .collect();
+            let _ = writeln!(out, "        {}) {action}; return ;;", patterns.join("|"));
+        }
+    }
+    out.push_str("    esac\n");
+
+    out.push_str("    case \"$cmd\" in\n");
+    for node in nodes {
+        let words: Vec<String> = candidates(node, nodes)
+            .iter()
+            .map(|w| sh_quote(w))
+            .collect();
+        // Leaves take positionals, which are usually paths.
+        let files = if node.children(nodes).next().is_none() {
+            "; [[ $cur == -* ]] || _files"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "        {}) compadd -- {}{files} ;;",
+            sh_quote(&node.key()),
+            words.join(" ")
+        );
+    }
+    out.push_str("    esac\n}\n");
+    let _ = writeln!(
+        out,
+        "if [ \"$funcstack[1]\" = \"{func}\" ]; then {func} \"$@\"; else compdef {func} {program}; fi"
+    );
+    out
+}
+
+fn render_fish(program: &str, nodes: &[Node]) -> String {
+    let at = format!("__{}_at", function_name(program));
+    let mut out = String::new();
+    let _ = writeln!(out, "# fish completion for {program}");
+    let _ = writeln!(out, "# Load with: {program} completions fish | source");
+    // `<at> KEY`: whether the words typed so far select the command KEY.
+    let _ = writeln!(out, "function {at}");
+    out.push_str("    set -l cmd ''\n    set -l words (commandline -opc)\n    set -e words[1]\n");
+    out.push_str("    for w in $words\n        switch \"$cmd:$w\"\n");
+    for node in nodes.iter().filter(|n| !n.path.is_empty()) {
+        let parent = node.path[..node.path.len() - 1].join(" ");
+        let patterns: Vec<String> = node
+            .words()
+            .map(|w| fish_quote(&format!("{parent}:{w}")))
+            .collect();
+        let _ = writeln!(out, "            case {}", patterns.join(" "));
+        let _ = writeln!(out, "                set cmd {}", fish_quote(&node.key()));
+    }
+    out.push_str("        end\n    end\n    test \"$cmd\" = \"$argv[1]\"\nend\n");
+
+    let program_q = fish_quote(program);
+    for node in nodes {
+        let cond = fish_quote(&format!("{at} {}", fish_quote(&node.key())));
+        for child in node.children(nodes) {
+            for word in child.words() {
+                let _ = writeln!(
+                    out,
+                    "complete -c {program_q} -n {cond} -f -a {} -d {}",
+                    fish_quote(word),
+                    fish_quote(&child.summary)
+                );
+            }
+        }
+        for flag in &node.flags {
+            let mut line = format!("complete -c {program_q} -n {cond}");
+            if let Some(long) = flag.long.as_deref().and_then(|l| l.strip_prefix("--")) {
+                let _ = write!(line, " -l {}", fish_quote(long));
+            }
+            if let Some(short) = flag.short.as_deref().and_then(|s| s.strip_prefix('-')) {
+                let _ = write!(line, " -s {}", fish_quote(short));
+            }
+            match &flag.value {
+                Some(Value::List(values)) => {
+                    let _ = write!(line, " -r -f -a {}", fish_quote(&values.join(" ")));
+                }
+                Some(Value::Files) | Some(Value::Dirs) => line.push_str(" -r -F"),
+                Some(Value::Callback) => {
+                    let call = callback_command(program, node, flag, fish_quote);
+                    let _ = write!(
+                        line,
+                        " -r -f -a {}",
+                        fish_quote(&format!("({call} (commandline -ct))"))
+                    );
+                }
+                Some(Value::Any) => line.push_str(" -r"),
+                None => {}
+            }
+            if !flag.help.is_empty() {
+                let _ = write!(line, " -d {}", fish_quote(&flag.help));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
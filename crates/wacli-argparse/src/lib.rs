@@ -7,6 +7,10 @@
 pub mod args {
     use std::borrow::Cow;
     use std::collections::{HashMap, HashSet};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use crate::claplike::{ParseError, messages};
 
     /// Parsed arguments and values.
     ///
@@ -16,7 +20,68 @@ pub mod args {
         values: HashMap<String, Vec<Cow<'a, str>>>,
         present: HashSet<String>,
         explicit: HashSet<String>,
+        occurrences: HashMap<String, Vec<Occurrence<'a>>>,
         rest: Vec<&'a str>,
+        warnings: Vec<String>,
+        /// Tokens that set a `value-type: bool` arg without a value (`--cache`),
+        /// with the byte offset where a short bundle continues after it (`-cv`).
+        bare_bools: Vec<(usize, Option<usize>)>,
+        subcommand: Option<Box<(String, Matches<'a>)>>,
+    }
+
+    /// Where a matched value came from.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ValueSource {
+        /// Provided explicitly in argv.
+        Argv,
+        /// Read from the environment variable declared by the arg.
+        Env,
+        /// Taken from the config values the caller loaded, keyed by arg name
+        /// (see [`parse_with_sources`](crate::claplike::parse_with_sources)).
+        Config,
+        /// Filled in from the arg's default value.
+        Default,
+    }
+
+    /// A single occurrence of an argument, with its provenance.
+    ///
+    /// Indices are positions in the `argv` slice passed to the parser (the
+    /// command name itself is not part of it), also inside a subcommand's
+    /// matches.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Occurrence<'a> {
+        value: Option<Cow<'a, str>>,
+        argv_index: Option<usize>,
+        flag_index: Option<usize>,
+        source: ValueSource,
+    }
+
+    impl<'a> Occurrence<'a> {
+        /// The value for this occurrence (`None` for boolean flags).
+        pub fn value(&self) -> Option<&str> {
+            self.value.as_deref()
+        }
+
+        /// Index of the argv token holding the value.
+        ///
+        /// For boolean flags this is the flag token. For `-ofile` and
+        /// `--out=file` the value shares the flag's token. `None` for values
+        /// sourced from env/default.
+        pub fn argv_index(&self) -> Option<usize> {
+            self.argv_index
+        }
+
+        /// Index of the argv token holding the flag itself.
+        ///
+        /// `None` for positionals and env/default values.
+        pub fn flag_index(&self) -> Option<usize> {
+            self.flag_index
+        }
+
+        /// Where the value came from.
+        pub fn source(&self) -> ValueSource {
+            self.source
+        }
     }
 
     impl<'a> Matches<'a> {
@@ -32,6 +97,51 @@ pub mod args {
             self.values.get(name).map(|v| v.as_slice())
         }
 
+        /// Parse the last value of `name` as `T` (`None` when it has no value).
+        ///
+        /// Values of args with a `value_type` are already checked by the
+        /// parser; this reports any other mismatch as `InvalidArgs`.
+        pub fn get_parsed<T>(&self, name: &str) -> Result<Option<T>, ParseError>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            self.get(name).map(|v| parse_value(name, v)).transpose()
+        }
+
+        /// Parse every value of `name` as `T`, in match order.
+        pub fn get_all_parsed<T>(&self, name: &str) -> Result<Vec<T>, ParseError>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            self.get_all(name)
+                .unwrap_or_default()
+                .iter()
+                .map(|v| parse_value(name, v))
+                .collect()
+        }
+
+        /// A boolean argument: whether a boolean flag was given, or the value
+        /// of a value-taking one read with [`parse_bool`] (`--cache=off`).
+        ///
+        /// Unset arguments and values that are not boolean read as `false`.
+        pub fn get_flag(&self, name: &str) -> bool {
+            self.get_bool(name).unwrap_or(false)
+        }
+
+        /// A boolean argument that may be left unset: `None` when neither
+        /// argv, env nor a default gave it, so `--cache=false` can override a
+        /// setting that defaults to on elsewhere.
+        ///
+        /// Values that are not boolean read as `false`.
+        pub fn get_bool(&self, name: &str) -> Option<bool> {
+            match self.get(name) {
+                Some(value) => Some(parse_bool(value).unwrap_or(false)),
+                None => self.present.contains(name).then_some(true),
+            }
+        }
+
         /// Whether an argument was present (boolean flag) or has a value.
         pub fn is_present(&self, name: &str) -> bool {
             self.present.contains(name) || self.values.contains_key(name)
@@ -44,10 +154,95 @@ pub mod args {
             self.explicit.contains(name)
         }
 
-        /// Extra positional arguments not covered by declared positional arg defs.
+        /// Every occurrence of an argument in match order, including env/default
+        /// values (which come last and carry no argv index).
+        ///
+        /// Returns an empty slice if the argument was not matched at all.
+        pub fn occurrences(&self, name: &str) -> &[Occurrence<'a>] {
+            self.occurrences
+                .get(name)
+                .map(|v| v.as_slice())
+                .unwrap_or(&[])
+        }
+
+        /// How many times an argument was given in argv (`-vvv` and
+        /// `-v -v -v` both count 3). Env/default values do not count.
+        pub fn count(&self, name: &str) -> usize {
+            self.occurrences(name)
+                .iter()
+                .filter(|o| o.source == ValueSource::Argv)
+                .count()
+        }
+
+        /// Whether the value of `name` was filled in from the arg's default
+        /// (not given in argv and not found in the environment or config).
+        pub fn is_default(&self, name: &str) -> bool {
+            self.occurrences(name)
+                .last()
+                .is_some_and(|o| o.source == ValueSource::Default)
+        }
+
+        /// Names of the arguments given explicitly in argv, in the order they
+        /// first appear.
+        ///
+        /// Values sourced from env/config/default are not included.
+        pub fn provided_args(&self) -> Vec<&str> {
+            let mut provided: Vec<(usize, &str)> = self
+                .occurrences
+                .iter()
+                .filter_map(|(name, occ)| {
+                    let first = occ
+                        .iter()
+                        .filter(|o| o.source == ValueSource::Argv)
+                        .filter_map(|o| o.flag_index.or(o.argv_index))
+                        .min()?;
+                    Some((first, name.as_str()))
+                })
+                .collect();
+            provided.sort();
+            provided.into_iter().map(|(_, name)| name).collect()
+        }
+
+        /// Names of the arguments declared in `meta` that ended up unset: not
+        /// in argv, not in the environment and without a default. Returned in
+        /// declaration order.
+        pub fn unset_args<'m, M>(&self, meta: &'m M) -> Vec<&'m str>
+        where
+            M: super::claplike::CommandMetaLike,
+        {
+            use super::claplike::ArgDefLike;
+
+            meta.args()
+                .iter()
+                .map(|def| def.name())
+                .filter(|name| !self.is_present(name))
+                .collect()
+        }
+
+        /// Extra positional arguments not covered by declared positional arg
+        /// defs. Always empty when the last positional is a `var_arg`.
         pub fn rest(&self) -> &[&'a str] {
             self.rest.as_slice()
         }
+
+        /// Non-fatal problems noticed while parsing (e.g. a misspelled
+        /// environment variable), for the caller to print.
+        pub fn warnings(&self) -> &[String] {
+            self.warnings.as_slice()
+        }
+
+        /// The subcommand given in argv (by its declared name, even when an
+        /// alias was used) and the matches for the args after it.
+        pub fn subcommand(&self) -> Option<(&str, &Matches<'a>)> {
+            self.subcommand
+                .as_deref()
+                .map(|(name, m)| (name.as_str(), m))
+        }
+
+        /// Name of the subcommand given in argv, if any.
+        pub fn subcommand_name(&self) -> Option<&str> {
+            self.subcommand().map(|(name, _)| name)
+        }
     }
 
     impl<'a> Matches<'a> {
@@ -63,13 +258,172 @@ pub mod args {
             self.explicit.insert(name);
         }
 
+        /// Record a value-taking arg found in argv.
+        pub(crate) fn push_argv_value(
+            &mut self,
+            name: &str,
+            value: &'a str,
+            argv_index: usize,
+            flag_index: Option<usize>,
+        ) {
+            self.push_explicit(name.to_string());
+            self.push_value(name.to_string(), Cow::Borrowed(value));
+            self.push_occurrence(
+                name,
+                Occurrence {
+                    value: Some(Cow::Borrowed(value)),
+                    argv_index: Some(argv_index),
+                    flag_index,
+                    source: ValueSource::Argv,
+                },
+            );
+        }
+
+        /// Record a boolean flag found in argv.
+        pub(crate) fn push_argv_flag(&mut self, name: &str, flag_index: usize) {
+            self.push_explicit(name.to_string());
+            self.push_present(name.to_string());
+            self.push_occurrence(
+                name,
+                Occurrence {
+                    value: None,
+                    argv_index: Some(flag_index),
+                    flag_index: Some(flag_index),
+                    source: ValueSource::Argv,
+                },
+            );
+        }
+
+        /// Record a `value-type: bool` arg given without a value (`--cache`,
+        /// `-vc`), which reads as `true`. `bundle_rest` is the byte offset of the
+        /// flags that follow it in the same short bundle (`v` in `-cv`).
+        pub(crate) fn push_bare_bool(
+            &mut self,
+            name: &str,
+            flag_index: usize,
+            bundle_rest: Option<usize>,
+        ) {
+            self.push_explicit(name.to_string());
+            self.push_value(name.to_string(), Cow::Borrowed("true"));
+            self.push_occurrence(
+                name,
+                Occurrence {
+                    value: Some(Cow::Borrowed("true")),
+                    argv_index: Some(flag_index),
+                    flag_index: Some(flag_index),
+                    source: ValueSource::Argv,
+                },
+            );
+            self.bare_bools.push((flag_index, bundle_rest));
+        }
+
+        /// Indices of the tokens that set a boolean-typed arg without a value,
+        /// subcommands included, as recorded by [`Matches::push_bare_bool`].
+        pub(crate) fn bare_bools(&self) -> &[(usize, Option<usize>)] {
+            &self.bare_bools
+        }
+
+        /// Record a value sourced from env/default.
+        pub(crate) fn push_implicit_value(
+            &mut self,
+            name: &str,
+            value: String,
+            source: ValueSource,
+        ) {
+            self.push_value(name.to_string(), Cow::Owned(value.clone()));
+            self.push_occurrence(
+                name,
+                Occurrence {
+                    value: Some(Cow::Owned(value)),
+                    argv_index: None,
+                    flag_index: None,
+                    source,
+                },
+            );
+        }
+
+        fn push_occurrence(&mut self, name: &str, occurrence: Occurrence<'a>) {
+            self.occurrences
+                .entry(name.to_string())
+                .or_default()
+                .push(occurrence);
+        }
+
         pub(crate) fn push_rest(&mut self, value: &'a str) {
             self.rest.push(value);
         }
 
+        pub(crate) fn push_warning(&mut self, warning: String) {
+            self.warnings.push(warning);
+        }
+
+        /// Attach the matches of a subcommand whose args started at
+        /// `argv[offset]`. Its warnings (and bare boolean flags) are copied up
+        /// so that the top-level [`Matches::warnings`] covers the whole
+        /// invocation.
+        pub(crate) fn set_subcommand(&mut self, name: &str, mut sub: Matches<'a>, offset: usize) {
+            sub.shift_indices(offset);
+            self.warnings.extend(sub.warnings.iter().cloned());
+            self.bare_bools.extend_from_slice(&sub.bare_bools);
+            self.subcommand = Some(Box::new((name.to_string(), sub)));
+        }
+
+        fn shift_indices(&mut self, offset: usize) {
+            for occurrence in self.occurrences.values_mut().flatten() {
+                occurrence.argv_index = occurrence.argv_index.map(|i| i + offset);
+                occurrence.flag_index = occurrence.flag_index.map(|i| i + offset);
+            }
+            for (index, _) in &mut self.bare_bools {
+                *index += offset;
+            }
+            if let Some(sub) = &mut self.subcommand {
+                sub.1.shift_indices(offset);
+            }
+        }
+
         pub(crate) fn has_value_key(&self, name: &str) -> bool {
             self.values.contains_key(name)
         }
+
+        /// Replace every value of `name` with `f(value)`. Stops at the first
+        /// value `f` rejects and returns it.
+        pub(crate) fn try_map_values(
+            &mut self,
+            name: &str,
+            f: impl Fn(&str) -> Option<String>,
+        ) -> Result<(), String> {
+            for value in self.values.get_mut(name).into_iter().flatten() {
+                *value = Cow::Owned(f(value).ok_or_else(|| value.to_string())?);
+            }
+            for occurrence in self.occurrences.get_mut(name).into_iter().flatten() {
+                if let Some(value) = &mut occurrence.value {
+                    *value = Cow::Owned(f(value).ok_or_else(|| value.to_string())?);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn parse_value<T>(name: &str, value: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        value.parse().map_err(|err: T::Err| {
+            ParseError::InvalidArgs(messages::rejected_value(value, name, &err.to_string()))
+        })
+    }
+
+    /// Spellings accepted for `value-type: "bool"` arguments (case-insensitive).
+    pub const BOOL_VALUES: &[&str] = &["true", "false", "1", "0", "yes", "no", "on", "off"];
+
+    /// Read a boolean spelled any way in [`BOOL_VALUES`].
+    pub fn parse_bool(value: &str) -> Option<bool> {
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Some(true),
+            "false" | "0" | "no" | "off" => Some(false),
+            _ => None,
+        }
     }
 
     /// Declare which flags take a value in the *next* argument (e.g. `--output out.txt`).
@@ -103,6 +457,31 @@ pub mod args {
         fn takes_value(&self, flag: &str) -> bool {
             self.value_flags.iter().any(|s| s == flag)
         }
+
+        fn has_digit_short(&self) -> bool {
+            self.value_flags.iter().any(|s| is_digit_short(s))
+        }
+    }
+
+    /// Whether `token` reads as a negative number (`-5`, `-0.25`) rather than
+    /// a short flag. Parsers treat such tokens as positionals unless a short
+    /// flag is a digit.
+    pub fn is_negative_number(token: &str) -> bool {
+        let Some(number) = token.strip_prefix('-') else {
+            return false;
+        };
+        let (int, frac) = match number.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (number, None),
+        };
+        let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        digits(int) && frac.is_none_or(digits)
+    }
+
+    /// Whether `flag` is a short flag named by a digit (`-5`).
+    pub(crate) fn is_digit_short(flag: &str) -> bool {
+        let bytes = flag.as_bytes();
+        bytes.len() == 2 && bytes[0] == b'-' && bytes[1].is_ascii_digit()
     }
 
     /// Argument name collection for flag matching.
@@ -194,10 +573,14 @@ pub mod args {
     ///
     /// Any flag listed in `schema` is treated as taking a value in the next
     /// argument (e.g. `--output out.txt`), and that value is skipped.
+    ///
+    /// Negative numbers (`-5`, `-0.25`) are positionals, unless `schema`
+    /// declares a digit short flag.
     pub fn positional_args_with_schema<'a>(argv: &'a [String], schema: &Schema) -> Vec<&'a str> {
         let mut positionals = Vec::new();
         let mut i = 0;
         let mut after_separator = false;
+        let numbers_are_positional = !schema.has_digit_short();
 
         while i < argv.len() {
             let arg = &argv[i];
@@ -207,7 +590,10 @@ pub mod args {
                     i += 1;
                     continue;
                 }
-                if arg != "-" && arg.starts_with('-') {
+                if arg != "-"
+                    && arg.starts_with('-')
+                    && !(numbers_are_positional && is_negative_number(arg))
+                {
                     if arg.contains('=') {
                         i += 1;
                         continue;
@@ -257,13 +643,59 @@ pub mod args {
             &argv[start..]
         }
     }
+
+    /// Split `line` into words the way a shell would, without expansions:
+    /// whitespace separates words, single quotes keep everything up to the
+    /// next `'`, double quotes keep everything up to the next unescaped `"`,
+    /// and a backslash outside single quotes takes the next character as is.
+    pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+        let mut words = Vec::new();
+        let mut current = String::new();
+        let mut in_word = false;
+        let mut quote: Option<char> = None;
+        let mut chars = line.chars();
+
+        while let Some(c) = chars.next() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some('"') | None, '\\') => {
+                    let next = chars.next().ok_or("trailing backslash")?;
+                    current.push(next);
+                    in_word = true;
+                }
+                (Some(_), c) => current.push(c),
+                (None, '\'' | '"') => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                (None, c) if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut current));
+                        in_word = false;
+                    }
+                }
+                (None, c) => {
+                    current.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        if quote.is_some() {
+            return Err("unterminated quote".to_string());
+        }
+        if in_word {
+            words.push(current);
+        }
+        Ok(words)
+    }
 }
 
 pub mod claplike {
-    use super::args::Matches;
-    use std::borrow::Cow;
+    use super::args::{self, Matches, ValueSource};
     use std::collections::{HashMap, HashSet};
 
+    pub mod completions;
+
     const BUILTIN_HELP_NAME: &str = "__wacli_help";
     const BUILTIN_VERSION_NAME: &str = "__wacli_version";
 
@@ -300,6 +732,47 @@ pub mod claplike {
         fn hidden(&self) -> bool {
             false
         }
+        /// Name of the [`ArgGroupLike`] this argument belongs to.
+        ///
+        /// A group no command declares acts like one that is neither
+        /// required nor multiple.
+        fn group(&self) -> Option<&str> {
+            None
+        }
+        /// Whether the value may start with `-`: the token after the flag is
+        /// always its value, even when it names a known flag.
+        ///
+        /// Default is `false`: `--filter -v` with `-v` declared is a missing
+        /// value. Tokens naming no declared flag (`-`, `-x`) are still taken.
+        fn allow_hyphen_values(&self) -> bool {
+            false
+        }
+        /// Whether a boolean flag counts its occurrences (`-vvv`, read with
+        /// [`Matches::count`](args::Matches::count)). Help shows it as `-v...`,
+        /// and it may repeat even when not `multiple`.
+        ///
+        /// Default is `false`.
+        fn countable(&self) -> bool {
+            false
+        }
+        /// Whether the last positional takes every remaining positional token
+        /// (`[FILES]...`, read with `get_all`) instead of leaving extras in
+        /// [`Matches::rest`](args::Matches::rest). Ignored on flags and on
+        /// positionals that are not last.
+        ///
+        /// Default is `false`.
+        fn var_arg(&self) -> bool {
+            false
+        }
+    }
+
+    /// A named set of arguments validated together (`--json`/`--yaml`/`--toml`).
+    pub trait ArgGroupLike {
+        fn name(&self) -> &str;
+        /// At least one member must be given in argv.
+        fn required(&self) -> bool;
+        /// More than one member may be given.
+        fn multiple(&self) -> bool;
     }
 
     pub trait CommandMetaLike {
@@ -309,11 +782,53 @@ pub mod claplike {
         fn summary(&self) -> &str;
         fn usage(&self) -> &str;
         fn aliases(&self) -> &[String];
+        /// Aliases that dispatch like [`aliases`](Self::aliases) but are not
+        /// shown in help.
+        ///
+        /// Default is none.
+        fn hidden_aliases(&self) -> &[String] {
+            &[]
+        }
         fn version(&self) -> &str;
         fn hidden(&self) -> bool;
         fn description(&self) -> &str;
         fn examples(&self) -> &[String];
         fn args(&self) -> &[Self::ArgDef];
+        /// Argument groups referenced by [`ArgDefLike::group`].
+        ///
+        /// Default is none.
+        fn groups(&self) -> Vec<&dyn ArgGroupLike> {
+            Vec::new()
+        }
+        /// App-level options every command accepts, listed in help under
+        /// `Global options:` and taken out of argv by [`split_global_args`].
+        ///
+        /// Default is none.
+        fn global_args(&self) -> Vec<&dyn ArgDefLike> {
+            Vec::new()
+        }
+        /// Whether `@file` tokens are expanded by [`expand_arg_files`].
+        ///
+        /// Default is `false`; commands opt in explicitly.
+        fn allow_arg_files(&self) -> bool {
+            false
+        }
+        /// How the help returned by `parse` (`ParseOutcome::Help`) is styled,
+        /// for this command and its subcommands.
+        ///
+        /// Default is [`HelpStyle::plain`].
+        fn help_style(&self) -> HelpStyle {
+            HelpStyle::plain()
+        }
+        /// Nested commands, selected by the first positional token in argv.
+        ///
+        /// Default is none.
+        fn subcommands(&self) -> &[Self]
+        where
+            Self: Sized,
+        {
+            &[]
+        }
     }
 
     #[derive(Debug, Clone)]
@@ -336,8 +851,212 @@ pub mod claplike {
         }
     }
 
+    /// A [`ParseError`] and, when known, the part of argv it points at (see
+    /// [`parse_spanned`] and [`render_error`]).
+    #[derive(Debug, Clone)]
+    pub struct SpannedError {
+        pub error: ParseError,
+        pub span: Option<ArgSpan>,
+    }
+
+    impl SpannedError {
+        fn at(msg: String, span: Option<ArgSpan>) -> Self {
+            Self {
+                error: ParseError::InvalidArgs(msg),
+                span,
+            }
+        }
+
+        /// Re-base the span from `argv[offset..]` onto `argv`.
+        fn shifted(mut self, offset: usize) -> Self {
+            if let Some(span) = &mut self.span {
+                span.index += offset;
+            }
+            self
+        }
+    }
+
+    impl From<ParseError> for SpannedError {
+        fn from(error: ParseError) -> Self {
+            Self { error, span: None }
+        }
+    }
+
+    impl From<SpannedError> for ParseError {
+        fn from(err: SpannedError) -> Self {
+            err.error
+        }
+    }
+
+    /// Part of an argv token a parse error points at.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ArgSpan {
+        /// Index of the token in the argv given to the parser.
+        pub index: usize,
+        /// Byte range within the token.
+        pub range: std::ops::Range<usize>,
+    }
+
+    impl ArgSpan {
+        /// The whole token `argv[index]`.
+        pub fn token(index: usize, token: &str) -> Self {
+            Self {
+                index,
+                range: 0..token.len(),
+            }
+        }
+    }
+
+    /// The error message followed, when the error has a span, by `argv` as a
+    /// command line with carets under the offending part:
+    ///
+    /// ```text
+    /// unknown flag: --formt
+    ///   deploy --formt json
+    ///          ^^^^^^^
+    /// ```
+    ///
+    /// `argv` must be the one given to the parser. Tokens that are empty or
+    /// contain whitespace are shown single-quoted.
+    pub fn render_error(err: &SpannedError, argv: &[String]) -> String {
+        let msg = err.error.message().trim_end();
+        let Some(span) = err.span.as_ref().filter(|s| {
+            argv.get(s.index).is_some_and(|t| {
+                s.range.start <= s.range.end
+                    && t.is_char_boundary(s.range.start)
+                    && t.is_char_boundary(s.range.end)
+            })
+        }) else {
+            return msg.to_string();
+        };
+        let mut line = String::new();
+        let mut column = 0;
+        let mut width = 0;
+        for (i, token) in argv.iter().enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            let quoted = token.is_empty() || token.chars().any(char::is_whitespace);
+            if i == span.index {
+                let start = line.chars().count() + usize::from(quoted);
+                column = start + token[..span.range.start].chars().count();
+                width = token[span.range.clone()].chars().count().max(1);
+            }
+            if quoted {
+                line.push('\'');
+                line.push_str(token);
+                line.push('\'');
+            } else {
+                line.push_str(token);
+            }
+        }
+        format!(
+            "{msg}\n  {line}\n  {}{}",
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+
     pub type ParseResult<T> = Result<T, ParseError>;
 
+    /// User-facing wording of parse errors.
+    ///
+    /// Core (validating against the command schema) and plugins (parsing
+    /// their own argv, or rejecting values themselves) both build messages
+    /// here, so the same mistake reads the same whichever side catches it.
+    pub mod messages {
+        const MISSING_REQUIRED: &str = "missing required argument";
+
+        pub fn unknown_flag(flag: &str) -> String {
+            format!("unknown flag: {flag}")
+        }
+
+        pub fn flag_takes_no_value(flag: &str) -> String {
+            format!("flag does not take a value: {flag}")
+        }
+
+        pub fn missing_value(flag: &str) -> String {
+            format!("missing value for {flag}")
+        }
+
+        pub fn missing_value_found_flag(flag: &str, found: &str) -> String {
+            format!("missing value for {flag} (found flag {found})")
+        }
+
+        pub fn invalid_short_flags(arg: &str) -> String {
+            format!("invalid short flags: {arg}")
+        }
+
+        /// `args` are display forms such as `--out <FILE>` or `<FILE>`.
+        pub fn missing_required<S: AsRef<str>>(args: &[S]) -> String {
+            let list: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+            let plural = if list.len() == 1 { "" } else { "s" };
+            format!("{MISSING_REQUIRED}{plural}: {}", list.join(", "))
+        }
+
+        /// Whether `msg` reports missing required arguments (core follows
+        /// these with the full command help).
+        pub fn is_missing_required(msg: &str) -> bool {
+            msg.starts_with(MISSING_REQUIRED)
+        }
+
+        pub fn invalid_value<S: AsRef<str>>(value: &str, arg: &str, possible: &[S]) -> String {
+            let possible: Vec<&str> = possible.iter().map(AsRef::as_ref).collect();
+            format!(
+                "invalid value '{value}' for '{arg}'. possible values: {}",
+                possible.join(", ")
+            )
+        }
+
+        /// A value a plugin-side validator refused, with its reason.
+        pub fn rejected_value(value: &str, arg: &str, reason: &str) -> String {
+            format!("invalid value '{value}' for '{arg}': {reason}")
+        }
+
+        /// An environment variable that looks like a misspelling of the one an
+        /// argument reads.
+        pub fn env_near_miss(found: &str, expected: &str) -> String {
+            format!(
+                "environment variable '{found}' is set but '{expected}' is expected \u{2014} ignoring"
+            )
+        }
+
+        pub fn unknown_subcommand<S: AsRef<str>>(name: &str, valid: &[S]) -> String {
+            let valid: Vec<&str> = valid.iter().map(AsRef::as_ref).collect();
+            format!(
+                "unknown subcommand '{name}'. valid subcommands: {}",
+                valid.join(", ")
+            )
+        }
+
+        pub fn used_multiple_times(arg: &str) -> String {
+            format!("argument '{arg}' cannot be used multiple times")
+        }
+
+        pub fn conflict(arg: &str, other: &str) -> String {
+            format!("argument '{arg}' cannot be used with '{other}'")
+        }
+
+        pub fn requires(arg: &str, other: &str) -> String {
+            format!("argument '{arg}' requires '{other}'")
+        }
+
+        /// A required group none of whose members was given; `args` are
+        /// display forms such as `--json`.
+        pub fn missing_required_group<S: AsRef<str>>(args: &[S]) -> String {
+            let list: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+            format!("{MISSING_REQUIRED}: one of {}", list.join(", "))
+        }
+
+        /// Printed after a parse error; `command` is what the user typed to
+        /// reach the command (e.g. `my-cli db seed`).
+        pub fn help_hint(command: &str) -> String {
+            format!("see '{command} --help'")
+        }
+    }
+
+    // `Matches` is the common case; boxing it would only add an allocation.
+    #[allow(clippy::large_enum_variant)]
     #[derive(Debug, Clone)]
     pub enum ParseOutcome<'a> {
         Matches(Matches<'a>),
@@ -351,7 +1070,10 @@ pub mod claplike {
         short: Option<String>,
         long: Option<String>,
         takes_value: bool,
+        /// `value-type: bool`: the value is optional (`--cache` means true).
+        is_bool: bool,
         default_value: Option<String>,
+        allow_hyphen_values: bool,
     }
 
     #[derive(Debug, Clone)]
@@ -535,6 +1257,34 @@ pub mod claplike {
                 Self::Builtin(a) => a.hidden(),
             }
         }
+
+        fn group(&self) -> Option<&str> {
+            match self {
+                Self::User(a) => a.group(),
+                Self::Builtin(a) => a.group(),
+            }
+        }
+
+        fn allow_hyphen_values(&self) -> bool {
+            match self {
+                Self::User(a) => a.allow_hyphen_values(),
+                Self::Builtin(a) => a.allow_hyphen_values(),
+            }
+        }
+
+        fn countable(&self) -> bool {
+            match self {
+                Self::User(a) => a.countable(),
+                Self::Builtin(a) => a.countable(),
+            }
+        }
+
+        fn var_arg(&self) -> bool {
+            match self {
+                Self::User(a) => a.var_arg(),
+                Self::Builtin(a) => a.var_arg(),
+            }
+        }
     }
 
     fn normalize_short(raw: &str) -> String {
@@ -563,7 +1313,9 @@ pub mod claplike {
             short,
             long,
             takes_value: def.takes_value(),
+            is_bool: is_bool(def),
             default_value: def.default_value().map(|s| s.to_string()),
+            allow_hyphen_values: def.allow_hyphen_values(),
         }
     }
 
@@ -614,7 +1366,12 @@ pub mod claplike {
             .unwrap_or_else(|| def.name().to_ascii_uppercase())
     }
 
-    fn format_arg_left(def: &dyn ArgDefLike) -> String {
+    /// A boolean-typed flag's value name, `BOOL` unless one is declared.
+    fn bool_value_name(def: &dyn ArgDefLike) -> &str {
+        def.value_name().unwrap_or("BOOL")
+    }
+
+    fn format_arg_left(def: &dyn ArgDefLike, style: &HelpStyle) -> String {
         if def.short().is_none() && def.long().is_none() {
             let n = format_value_name(def);
             if def.required() {
@@ -625,27 +1382,42 @@ pub mod claplike {
         } else {
             let mut names: Vec<String> = Vec::new();
             if let Some(s) = def.short() {
-                names.push(normalize_short(s));
+                names.push(paint(style.literal, &normalize_short(s)));
             }
             if let Some(l) = def.long() {
-                names.push(normalize_long(l));
+                names.push(paint(style.literal, &normalize_long(l)));
             }
             let mut out = names.join(", ");
-            if def.takes_value() {
+            if is_bool(def) {
+                out.push_str(&format!("[=<{}>]", bool_value_name(def)));
+            } else if def.takes_value() {
                 let n = format_value_name(def);
                 out.push_str(&format!(" <{n}>"));
+            } else if def.countable() {
+                out.push_str("...");
             }
             out
         }
     }
 
-    fn format_arg_help(def: &dyn ArgDefLike) -> String {
+    fn format_arg_help(
+        def: &dyn ArgDefLike,
+        group_note: Option<&str>,
+        style: &HelpStyle,
+    ) -> String {
         let mut out = def.help().trim().to_string();
         if def.required() && !(def.short().is_none() && def.long().is_none()) {
             if out.is_empty() {
-                out.push_str("required");
+                out.push_str(&paint(style.required, "required"));
+            } else {
+                out.push_str(&format!(" {}", paint(style.required, "(required)")));
+            }
+        }
+        if let Some(note) = group_note {
+            if out.is_empty() {
+                out.push_str(&format!("({note})"));
             } else {
-                out.push_str(" (required)");
+                out.push_str(&format!(" ({note})"));
             }
         }
         if let Some(default_value) = def.default_value() {
@@ -658,30 +1430,135 @@ pub mod claplike {
         out
     }
 
-    /// Render a help message based on `CommandMeta`.
-    pub fn help<M: CommandMetaLike>(meta: &M) -> String {
-        let defs = schema_defs(meta);
+    /// ANSI styles for [`help_styled`]. Each field is an SGR parameter list
+    /// (`"1"` bold, `"36"` cyan, `"1;33"` bold yellow); an empty one leaves
+    /// that part plain.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct HelpStyle {
+        /// Section headers (`Usage:`, `Options:`, ...).
+        pub header: &'static str,
+        /// Flag and subcommand names.
+        pub literal: &'static str,
+        /// `(required)` markers.
+        pub required: &'static str,
+    }
 
-        let mut out = String::new();
-        if meta.summary().trim().is_empty() {
-            out.push_str(meta.name());
-            out.push('\n');
-        } else {
-            out.push_str(&format!("{} - {}\n", meta.name(), meta.summary().trim()));
+    impl HelpStyle {
+        /// No escape codes at all; what [`help`] renders.
+        pub const fn plain() -> Self {
+            Self {
+                header: "",
+                literal: "",
+                required: "",
+            }
         }
 
-        if meta.usage().trim().is_empty() {
-            out.push_str(&format!("\nUsage: {}\n", meta.name()));
-        } else {
-            out.push_str(&format!("\nUsage: {}\n", meta.usage().trim()));
+        /// Bold headers, cyan flag and subcommand names, yellow required markers.
+        pub const fn colored() -> Self {
+            Self {
+                header: "1",
+                literal: "36",
+                required: "33",
+            }
         }
+    }
 
-        if !meta.description().trim().is_empty() {
-            out.push('\n');
-            out.push_str(meta.description().trim_end());
+    impl Default for HelpStyle {
+        fn default() -> Self {
+            Self::plain()
+        }
+    }
+
+    /// Wrap `text` in the SGR sequence `sgr` (see [`HelpStyle`]), or return it
+    /// unchanged when `sgr` is empty.
+    pub fn paint(sgr: &str, text: &str) -> String {
+        if sgr.is_empty() || text.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{sgr}m{text}\x1b[0m")
+        }
+    }
+
+    /// The number of columns `text` takes on a terminal: its chars, not
+    /// counting ANSI escape sequences.
+    pub fn display_width(text: &str) -> usize {
+        let mut width = 0;
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '\x1b' {
+                width += 1;
+                continue;
+            }
+            // CSI (`ESC [ ... final`) ends at a byte in `@`..=`~`; any other
+            // escape is two chars long.
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        }
+        width
+    }
+
+    /// Render a help message based on `CommandMeta`.
+    pub fn help<M: CommandMetaLike>(meta: &M) -> String {
+        help_styled(meta, &HelpStyle::plain())
+    }
+
+    /// Render help like [`help`], with ANSI colors from `style`. Columns are
+    /// aligned by [`display_width`], so colored help lines up like plain help.
+    pub fn help_styled<M: CommandMetaLike>(meta: &M, style: &HelpStyle) -> String {
+        render_help(meta, meta.name(), style)
+    }
+
+    /// Render help with `path` (`git remote add` for a subcommand) in place
+    /// of the command name.
+    fn render_help<M: CommandMetaLike>(meta: &M, path: &str, style: &HelpStyle) -> String {
+        let defs = schema_defs(meta);
+
+        let mut out = String::new();
+        if meta.summary().trim().is_empty() {
+            out.push_str(path);
+            out.push('\n');
+        } else {
+            out.push_str(&format!("{} - {}\n", path, meta.summary().trim()));
+        }
+
+        let usage_header = paint(style.header, "Usage:");
+        if meta.usage().trim().is_empty() {
+            out.push_str(&format!("\n{usage_header} {}\n", path));
+        } else {
+            out.push_str(&format!("\n{usage_header} {}\n", meta.usage().trim()));
+        }
+        if !meta.aliases().is_empty() {
+            out.push_str(&format!(
+                "{} {}\n",
+                paint(style.header, "Aliases:"),
+                meta.aliases().join(", ")
+            ));
+        }
+
+        if !meta.description().trim().is_empty() {
+            out.push('\n');
+            out.push_str(meta.description().trim_end());
             out.push('\n');
         }
 
+        let commands: Vec<(String, String)> = meta
+            .subcommands()
+            .iter()
+            .filter(|sub| !sub.hidden())
+            .map(|sub| {
+                (
+                    paint(style.literal, sub.name()),
+                    sub.summary().trim().to_string(),
+                )
+            })
+            .collect();
+        push_rows(&mut out, "Commands", commands, style);
+
         let mut options: Vec<&dyn ArgDefLike> = Vec::new();
         let mut positionals: Vec<&dyn ArgDefLike> = Vec::new();
         for def in &defs {
@@ -696,40 +1573,41 @@ pub mod claplike {
             }
         }
 
-        if !positionals.is_empty() {
-            out.push_str("\nArguments:\n");
-            let rows: Vec<(String, String)> = positionals
-                .iter()
-                .map(|d| (format_arg_left(*d), format_arg_help(*d)))
-                .collect();
-            let width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
-            for (left, help) in rows {
-                if help.is_empty() {
-                    out.push_str(&format!("  {}\n", left));
-                } else {
-                    out.push_str(&format!("  {:width$}  {}\n", left, help, width = width));
-                }
-            }
-        }
-
-        if !options.is_empty() {
-            out.push_str("\nOptions:\n");
-            let rows: Vec<(String, String)> = options
-                .iter()
-                .map(|d| (format_arg_left(*d), format_arg_help(*d)))
-                .collect();
-            let width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
-            for (left, help) in rows {
-                if help.is_empty() {
-                    out.push_str(&format!("  {}\n", left));
-                } else {
-                    out.push_str(&format!("  {:width$}  {}\n", left, help, width = width));
-                }
+        let defs_dyn: Vec<&dyn ArgDefLike> = defs.iter().map(|d| d as &dyn ArgDefLike).collect();
+        let mut group_notes: HashMap<&str, String> = HashMap::new();
+        for group in resolve_groups(meta, &defs_dyn) {
+            let Some(note) = group_note(&group) else {
+                continue;
+            };
+            for def in &group.members {
+                group_notes.insert(def.name(), note.clone());
             }
         }
+        let trailing = trailing_positional(&defs_dyn).map(|d| d.name());
+        let arg_rows = |defs: Vec<&dyn ArgDefLike>| -> Vec<(String, String)> {
+            defs.iter()
+                .map(|d| {
+                    let note = group_notes.get(d.name()).map(String::as_str);
+                    let mut left = format_arg_left(*d, style);
+                    if trailing == Some(d.name()) {
+                        left.push_str("...");
+                    }
+                    (left, format_arg_help(*d, note, style))
+                })
+                .collect()
+        };
+        push_rows(&mut out, "Arguments", arg_rows(positionals), style);
+        push_rows(&mut out, "Options", arg_rows(options), style);
+        let globals: Vec<(String, String)> = meta
+            .global_args()
+            .into_iter()
+            .filter(|d| !d.hidden())
+            .map(|d| (format_arg_left(d, style), format_arg_help(d, None, style)))
+            .collect();
+        push_rows(&mut out, "Global options", globals, style);
 
         if !meta.examples().is_empty() {
-            out.push_str("\nExamples:\n");
+            out.push_str(&format!("\n{}\n", paint(style.header, "Examples:")));
             for ex in meta.examples() {
                 if ex.trim().is_empty() {
                     continue;
@@ -741,12 +1619,58 @@ pub mod claplike {
         out
     }
 
+    /// How members of `group` are annotated in help (`one of: --json, --yaml`),
+    /// or `None` when the group places no limit on them.
+    fn group_note(group: &GroupInfo<'_>) -> Option<String> {
+        let kind = match (group.required, group.multiple) {
+            (true, false) => "exactly one of",
+            (false, false) => "one of",
+            (true, true) => "at least one of",
+            (false, true) => return None,
+        };
+        let names: Vec<String> = group
+            .members
+            .iter()
+            .filter(|d| !d.hidden())
+            .map(|d| arg_display_name(*d))
+            .collect();
+        Some(format!("{kind}: {}", names.join(", ")))
+    }
+
+    /// Append a `Title:` section of two aligned columns, unless `rows` is empty.
+    fn push_rows(out: &mut String, title: &str, rows: Vec<(String, String)>, style: &HelpStyle) {
+        if rows.is_empty() {
+            return;
+        }
+        out.push_str(&format!(
+            "\n{}\n",
+            paint(style.header, &format!("{title}:"))
+        ));
+        let width = rows
+            .iter()
+            .map(|(l, _)| display_width(l))
+            .max()
+            .unwrap_or(0);
+        for (left, help) in rows {
+            if help.is_empty() {
+                out.push_str(&format!("  {}\n", left));
+            } else {
+                let pad = width - display_width(&left);
+                out.push_str(&format!("  {left}{:pad$}  {help}\n", ""));
+            }
+        }
+    }
+
     /// Render a version message based on `CommandMeta`.
     pub fn version<M: CommandMetaLike>(meta: &M) -> String {
-        if meta.version().trim().is_empty() {
-            format!("{}\n", meta.name())
+        render_version(meta.name(), meta.version())
+    }
+
+    fn render_version(path: &str, version: &str) -> String {
+        if version.trim().is_empty() {
+            format!("{path}\n")
         } else {
-            format!("{} {}\n", meta.name(), meta.version().trim())
+            format!("{path} {}\n", version.trim())
         }
     }
 
@@ -754,16 +1678,163 @@ pub mod claplike {
         env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
     }
 
-    fn arg_display_name(def: &dyn ArgDefLike) -> String {
+    /// The variable in `env` that most likely is a misspelling of `key`: same
+    /// name in another case, or within a small edit distance (1 for names of
+    /// 4-7 characters, 2 from 8 on; short names only match by case). Names in
+    /// `declared` belong to other args and are never suggested.
+    fn env_near_miss<'e>(
+        env: &'e [(String, String)],
+        key: &str,
+        declared: &HashSet<&str>,
+    ) -> Option<&'e str> {
+        let max_distance = match key.chars().count() {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        env.iter()
+            .map(|(k, _)| k.as_str())
+            .filter(|k| *k != key && !declared.contains(k))
+            .filter_map(|k| {
+                if k.eq_ignore_ascii_case(key) {
+                    return Some((0, k));
+                }
+                let d = edit_distance(k, key);
+                (d <= max_distance).then_some((d, k))
+            })
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, k)| k)
+    }
+
+    /// Edit distance counting insertions, deletions, substitutions and swaps
+    /// of adjacent characters (optimal string alignment).
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in d[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                d[i][j] = (d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1)
+                    .min(d[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    /// How an argument is named in error messages (`--long`, `-s`, or the positional name).
+    pub fn arg_display_name(def: &dyn ArgDefLike) -> String {
         def.long()
             .map(normalize_long)
             .or_else(|| def.short().map(normalize_short))
             .unwrap_or_else(|| def.name().to_string())
     }
 
-    fn validate_relations(defs: &[&dyn ArgDefLike]) -> ParseResult<()> {
+    /// How a required argument is shown when it is missing (`<FILE>`,
+    /// `--out <PATH>`).
+    pub fn required_arg_display(def: &dyn ArgDefLike) -> String {
+        if def.short().is_none() && def.long().is_none() {
+            return format!("<{}>", format_value_name(def));
+        }
+        let mut s = arg_display_name(def);
+        if is_bool(def) {
+            s.push_str(&format!("[=<{}>]", bool_value_name(def)));
+        } else if def.takes_value() {
+            s.push_str(&format!(" <{}>", format_value_name(def)));
+        }
+        s
+    }
+
+    /// The last positional, when it is a `var_arg` and so takes every
+    /// remaining positional token.
+    fn trailing_positional<'d>(defs: &[&'d dyn ArgDefLike]) -> Option<&'d dyn ArgDefLike> {
+        defs.iter()
+            .rev()
+            .find(|d| d.short().is_none() && d.long().is_none())
+            .copied()
+            .filter(|d| d.var_arg())
+    }
+
+    /// Whether values of `def` are normalized to `"true"`/`"false"`.
+    fn is_bool(def: &dyn ArgDefLike) -> bool {
+        def.takes_value() && def.value_type() == Some("bool")
+    }
+
+    /// An argument group with its members, in definition order.
+    struct GroupInfo<'d> {
+        name: String,
+        required: bool,
+        multiple: bool,
+        members: Vec<&'d dyn ArgDefLike>,
+    }
+
+    /// The groups `meta` declares, followed by those its args name without a
+    /// declaration.
+    fn resolve_groups<'d, M: CommandMetaLike>(
+        meta: &M,
+        defs: &[&'d dyn ArgDefLike],
+    ) -> Vec<GroupInfo<'d>> {
+        let mut out: Vec<GroupInfo<'d>> = meta
+            .groups()
+            .iter()
+            .map(|g| GroupInfo {
+                name: g.name().to_string(),
+                required: g.required(),
+                multiple: g.multiple(),
+                members: Vec::new(),
+            })
+            .collect();
+        for &def in defs {
+            let Some(name) = def.group().map(str::trim).filter(|g| !g.is_empty()) else {
+                continue;
+            };
+            match out.iter_mut().find(|g| g.name == name) {
+                Some(group) => group.members.push(def),
+                None => out.push(GroupInfo {
+                    name: name.to_string(),
+                    required: false,
+                    multiple: false,
+                    members: vec![def],
+                }),
+            }
+        }
+        out
+    }
+
+    fn validate_relations(defs: &[&dyn ArgDefLike], groups: &[GroupInfo<'_>]) -> ParseResult<()> {
+        let mut group_names: HashSet<&str> = HashSet::new();
+        for group in groups {
+            if !group_names.insert(group.name.as_str()) {
+                return Err(ParseError::Failed(format!(
+                    "schema error: group '{}' is declared more than once",
+                    group.name
+                )));
+            }
+            if group.members.is_empty() {
+                return Err(ParseError::Failed(format!(
+                    "schema error: group '{}' has no args",
+                    group.name
+                )));
+            }
+        }
         let names: HashSet<&str> = defs.iter().map(|d| d.name()).collect();
         for def in defs {
+            if is_bool(*def) && !def.possible_values().is_empty() {
+                return Err(ParseError::Failed(format!(
+                    "schema error: '{}' has value-type bool and cannot declare possible-values",
+                    def.name()
+                )));
+            }
             for other in def.conflicts_with() {
                 if other.trim().is_empty() {
                     continue;
@@ -792,21 +1863,73 @@ pub mod claplike {
         Ok(())
     }
 
-    fn validate_matches(defs: &[&dyn ArgDefLike], m: &Matches<'_>) -> ParseResult<()> {
+    /// Check `value` against a declared `value-type`. Unknown types (and
+    /// `bool`, normalized before this runs) accept anything.
+    fn check_value_type(value_type: &str, value: &str) -> Result<(), &'static str> {
+        let ok = match value_type {
+            "int" => value.parse::<i64>().is_ok(),
+            "uint" => value.parse::<u64>().is_ok(),
+            "float" => value.parse::<f64>().is_ok_and(f64::is_finite),
+            "path" => !value.is_empty() && !value.contains('\0'),
+            _ => true,
+        };
+        if ok {
+            return Ok(());
+        }
+        Err(match value_type {
+            "int" => "expected integer",
+            "uint" => "expected non-negative integer",
+            "float" => "expected number",
+            _ => "expected path",
+        })
+    }
+
+    /// The token of the `nth` time `name` was given in argv.
+    fn given_span(m: &Matches<'_>, name: &str, nth: usize, argv: &[String]) -> Option<ArgSpan> {
+        let index = m
+            .occurrences(name)
+            .iter()
+            .filter(|o| o.source() == ValueSource::Argv)
+            .nth(nth)
+            .and_then(|o| o.flag_index().or(o.argv_index()))?;
+        Some(ArgSpan::token(index, argv.get(index)?))
+    }
+
+    /// Where `value` of `name` appears in argv: the value token, or the tail
+    /// of the flag token for attached values (`--format=x`, `-ox`).
+    fn value_span(m: &Matches<'_>, name: &str, value: &str, argv: &[String]) -> Option<ArgSpan> {
+        let occurrence = m
+            .occurrences(name)
+            .iter()
+            .find(|o| o.source() == ValueSource::Argv && o.value() == Some(value))?;
+        let index = occurrence.argv_index()?;
+        let token = argv.get(index)?;
+        if occurrence.flag_index() == Some(index) && token.ends_with(value) {
+            return Some(ArgSpan {
+                index,
+                range: token.len() - value.len()..token.len(),
+            });
+        }
+        Some(ArgSpan::token(index, token))
+    }
+
+    fn validate_matches(
+        defs: &[&dyn ArgDefLike],
+        groups: &[GroupInfo<'_>],
+        m: &Matches<'_>,
+        argv: &[String],
+    ) -> Result<(), SpannedError> {
         let by_name: HashMap<&str, &dyn ArgDefLike> =
             defs.iter().copied().map(|d| (d.name(), d)).collect();
 
         for &def in defs {
             let name = def.name();
 
-            if !def.multiple()
-                && let Some(values) = m.get_all(name)
-                && values.len() > 1
-            {
-                return Err(ParseError::InvalidArgs(format!(
-                    "argument '{}' cannot be used multiple times",
-                    arg_display_name(def)
-                )));
+            if !def.multiple() && !def.countable() && !def.var_arg() && m.count(name) > 1 {
+                return Err(SpannedError::at(
+                    messages::used_multiple_times(&arg_display_name(def)),
+                    given_span(m, name, 1, argv),
+                ));
             }
 
             if !def.possible_values().is_empty()
@@ -815,12 +1938,27 @@ pub mod claplike {
                 for v in values {
                     let v = v.as_ref();
                     if !def.possible_values().iter().any(|p| p == v) {
-                        return Err(ParseError::InvalidArgs(format!(
-                            "invalid value '{}' for '{}'. possible values: {}",
-                            v,
-                            arg_display_name(def),
-                            def.possible_values().join(", ")
-                        )));
+                        return Err(SpannedError::at(
+                            messages::invalid_value(
+                                v,
+                                &arg_display_name(def),
+                                def.possible_values(),
+                            ),
+                            value_span(m, name, v, argv),
+                        ));
+                    }
+                }
+            }
+
+            if let Some(value_type) = def.value_type()
+                && let Some(values) = m.get_all(name)
+            {
+                for v in values {
+                    if let Err(expected) = check_value_type(value_type, v) {
+                        return Err(SpannedError::at(
+                            messages::rejected_value(v, &arg_display_name(def), expected),
+                            value_span(m, name, v, argv),
+                        ));
                     }
                 }
             }
@@ -835,11 +1973,10 @@ pub mod claplike {
                             .get(other.as_str())
                             .map(|d| arg_display_name(*d))
                             .unwrap_or_else(|| other.to_string());
-                        return Err(ParseError::InvalidArgs(format!(
-                            "argument '{}' cannot be used with '{}'",
-                            arg_display_name(def),
-                            other_display
-                        )));
+                        return Err(SpannedError::at(
+                            messages::conflict(&arg_display_name(def), &other_display),
+                            given_span(m, name, 0, argv),
+                        ));
                     }
                 }
                 for other in def.requires() {
@@ -851,15 +1988,47 @@ pub mod claplike {
                             .get(other.as_str())
                             .map(|d| arg_display_name(*d))
                             .unwrap_or_else(|| other.to_string());
-                        return Err(ParseError::InvalidArgs(format!(
-                            "argument '{}' requires '{}'",
-                            arg_display_name(def),
-                            other_display
-                        )));
+                        return Err(SpannedError::at(
+                            messages::requires(&arg_display_name(def), &other_display),
+                            given_span(m, name, 0, argv),
+                        ));
                     }
                 }
             }
         }
+
+        // Groups count members given in argv only, like conflicts and requires.
+        for group in groups {
+            let mut given: Vec<&dyn ArgDefLike> = group
+                .members
+                .iter()
+                .copied()
+                .filter(|d| m.is_explicit(d.name()))
+                .collect();
+            // Report the second member in argv order against the first.
+            given.sort_by_key(|d| {
+                m.occurrences(d.name())
+                    .iter()
+                    .filter_map(|o| o.flag_index().or(o.argv_index()))
+                    .min()
+            });
+            if group.required && given.is_empty() {
+                let names: Vec<String> =
+                    group.members.iter().map(|d| arg_display_name(*d)).collect();
+                return Err(SpannedError::at(
+                    messages::missing_required_group(&names),
+                    None,
+                ));
+            }
+            if !group.multiple
+                && let [first, second, ..] = given.as_slice()
+            {
+                return Err(SpannedError::at(
+                    messages::conflict(&arg_display_name(*second), &arg_display_name(*first)),
+                    given_span(m, second.name(), 0, argv),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -888,9 +2057,120 @@ pub mod claplike {
         argv: &'a [String],
         env: &[(String, String)],
     ) -> ParseResult<ParseOutcome<'a>> {
+        parse_with_sources(meta, argv, env, &[])
+    }
+
+    /// Parse like [`parse_with_env`], with `config` (values keyed by arg
+    /// name, e.g. loaded from a config file) between env and defaults:
+    ///
+    /// 1) CLI argv
+    /// 2) env
+    /// 3) config
+    /// 4) default-value
+    ///
+    /// Config values apply to value-taking args (in subcommands too), are
+    /// validated like any other value, and are not explicit
+    /// ([`ValueSource::Config`]).
+    pub fn parse_with_sources<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+        config: &[(String, String)],
+    ) -> ParseResult<ParseOutcome<'a>> {
+        let sources = Sources {
+            env,
+            config,
+            env_warnings: false,
+        };
+        parse_root(meta, argv, sources).map_err(Into::into)
+    }
+
+    /// Parse like [`parse_with_env`], and also warn (through
+    /// [`Matches::warnings`]) when an arg's environment variable is unset but
+    /// a near-miss of its name is set, e.g. `SHOW_FORMTA` for `SHOW_FORMAT`.
+    ///
+    /// Near-misses are never used as values, and at most one is reported per
+    /// arg.
+    pub fn parse_with_env_diagnostics<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+    ) -> ParseResult<ParseOutcome<'a>> {
+        parse_with_sources_diagnostics(meta, argv, env, &[])
+    }
+
+    /// Parse like [`parse_with_sources`], with the env warnings of
+    /// [`parse_with_env_diagnostics`].
+    pub fn parse_with_sources_diagnostics<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+        config: &[(String, String)],
+    ) -> ParseResult<ParseOutcome<'a>> {
+        let sources = Sources {
+            env,
+            config,
+            env_warnings: true,
+        };
+        parse_root(meta, argv, sources).map_err(Into::into)
+    }
+
+    /// Parse like [`parse_with_sources`] (or [`parse_with_sources_diagnostics`]
+    /// when `env_warnings`), keeping the part of `argv` an `InvalidArgs` error
+    /// points at, for [`render_error`].
+    pub fn parse_spanned<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+        config: &[(String, String)],
+        env_warnings: bool,
+    ) -> Result<ParseOutcome<'a>, SpannedError> {
+        let sources = Sources {
+            env,
+            config,
+            env_warnings,
+        };
+        parse_root(meta, argv, sources)
+    }
+
+    /// Where values missing from argv come from.
+    #[derive(Clone, Copy)]
+    struct Sources<'s> {
+        env: &'s [(String, String)],
+        config: &'s [(String, String)],
+        env_warnings: bool,
+    }
+
+    fn parse_root<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        sources: Sources<'_>,
+    ) -> Result<ParseOutcome<'a>, SpannedError> {
+        parse_env_impl(
+            meta,
+            argv,
+            sources,
+            meta.name(),
+            meta.version(),
+            &meta.help_style(),
+        )
+    }
+
+    /// `path` and `version` are what `--help`/`--version` show: a subcommand
+    /// is named by its full path and inherits the version when it has none.
+    /// Subcommands share the root's help `style`.
+    fn parse_env_impl<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        sources: Sources<'_>,
+        path: &str,
+        version: &str,
+        style: &HelpStyle,
+    ) -> Result<ParseOutcome<'a>, SpannedError> {
         let defs = schema_defs(meta);
         let defs_dyn: Vec<&dyn ArgDefLike> = defs.iter().map(|d| d as &dyn ArgDefLike).collect();
-        validate_relations(&defs_dyn)?;
+        let groups = resolve_groups(meta, &defs_dyn);
+        validate_relations(&defs_dyn, &groups)?;
 
         let infos: Vec<ArgInfo> = defs_dyn.iter().map(|d| build_arg_info(*d)).collect();
         let mut long_map: HashMap<String, usize> = HashMap::new();
@@ -910,7 +2190,8 @@ pub mod claplike {
                 return Err(ParseError::Failed(format!(
                     "arg definition conflict: {short} maps to both '{}' and '{}'",
                     infos[prev].name, info.name
-                )));
+                ))
+                .into());
             }
             if let Some(long) = &info.long
                 && let Some(prev) = long_map.insert(long.clone(), idx)
@@ -919,13 +2200,40 @@ pub mod claplike {
                 return Err(ParseError::Failed(format!(
                     "arg definition conflict: {long} maps to both '{}' and '{}'",
                     infos[prev].name, info.name
-                )));
+                ))
+                .into());
             }
         }
 
+        // `-5` is a number unless some short flag is a digit.
+        let numbers_are_positional = !short_map.keys().any(|s| args::is_digit_short(s));
+
+        // The flag a would-be value names (`--out`, `-v`, `-vx`), which a
+        // value-taking arg does not swallow unless it allows hyphen values.
+        let names_flag = |token: &str| -> Option<String> {
+            if token.starts_with("--") {
+                let flag = token.split_once('=').map_or(token, |(flag, _)| flag);
+                return long_map.contains_key(flag).then(|| flag.to_string());
+            }
+            if token.len() < 2 || (numbers_are_positional && args::is_negative_number(token)) {
+                return None;
+            }
+            let flag = token.get(..2)?;
+            short_map.contains_key(flag).then(|| flag.to_string())
+        };
+        let value_flag_error = |info: &ArgInfo, flag: &str, at: usize| {
+            let value = argv[at].as_str();
+            let found = names_flag(value).filter(|_| !info.allow_hyphen_values)?;
+            Some(SpannedError::at(
+                messages::missing_value_found_flag(flag, &found),
+                Some(ArgSpan::token(at, value)),
+            ))
+        };
+
         let mut m = Matches::default();
-        let mut positionals: Vec<&'a str> = Vec::new();
-        let mut parse_error: Option<ParseError> = None;
+        let mut positionals: Vec<(usize, &'a str)> = Vec::new();
+        let mut parse_error: Option<SpannedError> = None;
+        let mut subcommand_at: Option<usize> = None;
 
         let mut i = 0usize;
         let mut after_separator = false;
@@ -945,21 +2253,26 @@ pub mod claplike {
                         let info = &infos[idx];
                         if !info.takes_value {
                             if parse_error.is_none() {
-                                parse_error = Some(ParseError::InvalidArgs(format!(
-                                    "flag does not take a value: {flag}"
-                                )));
+                                parse_error = Some(SpannedError::at(
+                                    messages::flag_takes_no_value(flag),
+                                    Some(ArgSpan {
+                                        index: i,
+                                        range: flag.len()..arg.len(),
+                                    }),
+                                ));
                             }
                             i += 1;
                             continue;
                         }
-                        m.push_explicit(info.name.clone());
-                        m.push_value(info.name.clone(), Cow::Borrowed(value));
+                        m.push_argv_value(&info.name, value, i, Some(i));
                         i += 1;
                         continue;
                     }
                     if parse_error.is_none() {
-                        parse_error =
-                            Some(ParseError::InvalidArgs(format!("unknown flag: {flag}")));
+                        parse_error = Some(SpannedError::at(
+                            messages::unknown_flag(flag),
+                            Some(ArgSpan::token(i, flag)),
+                        ));
                     }
                     i += 1;
                     continue;
@@ -968,71 +2281,98 @@ pub mod claplike {
                 // --key value? (only if declared)
                 if let Some(&idx) = long_map.get(arg) {
                     let info = &infos[idx];
-                    if info.takes_value {
+                    // A boolean-typed flag takes its value only when attached
+                    // (`--cache=off`), so `--cache off` leaves `off` alone.
+                    if info.is_bool {
+                        m.push_bare_bool(&info.name, i, None);
+                        i += 1;
+                    } else if info.takes_value {
                         let Some(value) = argv.get(i + 1) else {
                             if parse_error.is_none() {
-                                parse_error = Some(ParseError::InvalidArgs(format!(
-                                    "missing value for {arg}"
-                                )));
+                                parse_error = Some(SpannedError::at(
+                                    messages::missing_value(arg),
+                                    Some(ArgSpan::token(i, arg)),
+                                ));
                             }
                             break;
                         };
-                        m.push_explicit(info.name.clone());
-                        m.push_value(info.name.clone(), Cow::Borrowed(value.as_str()));
+                        if let Some(err) = value_flag_error(info, arg, i + 1) {
+                            parse_error.get_or_insert(err);
+                            i += 1;
+                            continue;
+                        }
+                        m.push_argv_value(&info.name, value.as_str(), i + 1, Some(i));
                         i += 2;
                     } else {
-                        m.push_explicit(info.name.clone());
-                        m.push_present(info.name.clone());
+                        m.push_argv_flag(&info.name, i);
                         i += 1;
                     }
                     continue;
                 }
 
                 if parse_error.is_none() {
-                    parse_error = Some(ParseError::InvalidArgs(format!("unknown flag: {arg}")));
+                    parse_error = Some(SpannedError::at(
+                        messages::unknown_flag(arg),
+                        Some(ArgSpan::token(i, arg)),
+                    ));
                 }
                 i += 1;
                 continue;
             }
 
-            if !after_separator && arg.starts_with('-') && arg != "-" {
+            if !after_separator
+                && arg.starts_with('-')
+                && arg != "-"
+                && !(numbers_are_positional && args::is_negative_number(arg))
+            {
                 // Short flags: -v, -o value, -abc, -ofile
                 if arg.len() == 2 {
                     if let Some(&idx) = short_map.get(arg) {
                         let info = &infos[idx];
-                        if info.takes_value {
+                        if info.is_bool {
+                            m.push_bare_bool(&info.name, i, None);
+                            i += 1;
+                        } else if info.takes_value {
                             let Some(value) = argv.get(i + 1) else {
                                 if parse_error.is_none() {
-                                    parse_error = Some(ParseError::InvalidArgs(format!(
-                                        "missing value for {arg}"
-                                    )));
+                                    parse_error = Some(SpannedError::at(
+                                        messages::missing_value(arg),
+                                        Some(ArgSpan::token(i, arg)),
+                                    ));
                                 }
                                 break;
                             };
-                            m.push_explicit(info.name.clone());
-                            m.push_value(info.name.clone(), Cow::Borrowed(value.as_str()));
+                            if let Some(err) = value_flag_error(info, arg, i + 1) {
+                                parse_error.get_or_insert(err);
+                                i += 1;
+                                continue;
+                            }
+                            m.push_argv_value(&info.name, value.as_str(), i + 1, Some(i));
                             i += 2;
                         } else {
-                            m.push_explicit(info.name.clone());
-                            m.push_present(info.name.clone());
+                            m.push_argv_flag(&info.name, i);
                             i += 1;
                         }
                         continue;
                     }
                     if parse_error.is_none() {
-                        parse_error = Some(ParseError::InvalidArgs(format!("unknown flag: {arg}")));
+                        parse_error = Some(SpannedError::at(
+                            messages::unknown_flag(arg),
+                            Some(ArgSpan::token(i, arg)),
+                        ));
                     }
                     i += 1;
                     continue;
                 }
 
-                // Combined short flags.
+                // Combined short flags (`-vo=file` attaches like `-vofile`).
                 let bytes = arg.as_bytes();
                 if !bytes.is_ascii() {
                     if parse_error.is_none() {
-                        parse_error = Some(ParseError::InvalidArgs(format!(
-                            "invalid short flags: {arg}"
-                        )));
+                        parse_error = Some(SpannedError::at(
+                            messages::invalid_short_flags(arg),
+                            Some(ArgSpan::token(i, arg)),
+                        ));
                     }
                     i += 1;
                     continue;
@@ -1042,11 +2382,32 @@ pub mod claplike {
                 let mut consumed_next = false;
                 while k < bytes.len() {
                     let c = bytes[k] as char;
+                    if c == '=' && k > 1 {
+                        // `-v=x` for a flag without a value, like `--verbose=x`.
+                        if parse_error.is_none() {
+                            parse_error = Some(SpannedError::at(
+                                messages::flag_takes_no_value(&format!(
+                                    "-{}",
+                                    bytes[k - 1] as char
+                                )),
+                                Some(ArgSpan {
+                                    index: i,
+                                    range: k..arg.len(),
+                                }),
+                            ));
+                        }
+                        break;
+                    }
                     let flag = format!("-{c}");
                     let Some(&idx) = short_map.get(&flag) else {
                         if parse_error.is_none() {
-                            parse_error =
-                                Some(ParseError::InvalidArgs(format!("unknown flag: {flag}")));
+                            parse_error = Some(SpannedError::at(
+                                messages::unknown_flag(&flag),
+                                Some(ArgSpan {
+                                    index: i,
+                                    range: k..k + 1,
+                                }),
+                            ));
                         }
                         k += 1;
                         continue;
@@ -1054,26 +2415,44 @@ pub mod claplike {
                     let info = &infos[idx];
                     if info.takes_value {
                         let rest = &arg[k + 1..];
-                        if !rest.is_empty() {
-                            m.push_explicit(info.name.clone());
-                            m.push_value(info.name.clone(), Cow::Borrowed(rest));
+                        // A bool takes the rest of the bundle only when it spells
+                        // a value (`-coff`, `-c=x`); otherwise (`-cv`) it is bare
+                        // and the flags after it are read on.
+                        if info.is_bool
+                            && !rest.starts_with('=')
+                            && args::parse_bool(rest).is_none()
+                        {
+                            let bundle_rest = (!rest.is_empty()).then_some(k + 1);
+                            m.push_bare_bool(&info.name, i, bundle_rest);
+                            k += 1;
+                            continue;
+                        }
+                        let rest = rest.strip_prefix('=').unwrap_or(rest);
+                        if k + 1 < arg.len() {
+                            m.push_argv_value(&info.name, rest, i, Some(i));
                         } else {
                             let Some(value) = argv.get(i + 1) else {
                                 if parse_error.is_none() {
-                                    parse_error = Some(ParseError::InvalidArgs(format!(
-                                        "missing value for {flag}"
-                                    )));
+                                    parse_error = Some(SpannedError::at(
+                                        messages::missing_value(&flag),
+                                        Some(ArgSpan {
+                                            index: i,
+                                            range: k..k + 1,
+                                        }),
+                                    ));
                                 }
                                 break;
                             };
-                            m.push_explicit(info.name.clone());
-                            m.push_value(info.name.clone(), Cow::Borrowed(value.as_str()));
+                            if let Some(err) = value_flag_error(info, &flag, i + 1) {
+                                parse_error.get_or_insert(err);
+                                break;
+                            }
+                            m.push_argv_value(&info.name, value.as_str(), i + 1, Some(i));
                             consumed_next = true;
                         }
                         break;
                     } else {
-                        m.push_explicit(info.name.clone());
-                        m.push_present(info.name.clone());
+                        m.push_argv_flag(&info.name, i);
                     }
                     k += 1;
                 }
@@ -1082,54 +2461,137 @@ pub mod claplike {
                 continue;
             }
 
-            positionals.push(arg);
+            // The first positional selects the subcommand, which parses the rest.
+            if !after_separator && !meta.subcommands().is_empty() {
+                subcommand_at = Some(i);
+                break;
+            }
+
+            positionals.push((i, arg));
             i += 1;
         }
 
-        // Assign positional args by declaration order.
+        // Assign positional args by declaration order; a trailing `var_arg`
+        // positional takes the rest.
         let mut pos_iter = positionals.into_iter();
         for &idx in &positional_defs {
             let info = &infos[idx];
-            if let Some(v) = pos_iter.next() {
-                m.push_explicit(info.name.clone());
-                m.push_value(info.name.clone(), Cow::Borrowed(v));
+            if let Some((pos, v)) = pos_iter.next() {
+                m.push_argv_value(&info.name, v, pos, None);
             }
         }
-        for v in pos_iter {
-            m.push_rest(v);
+        let trailing = trailing_positional(&defs_dyn);
+        for (pos, v) in pos_iter {
+            match trailing {
+                Some(def) => m.push_argv_value(def.name(), v, pos, None),
+                None => m.push_rest(v),
+            }
         }
 
-        // Apply env/defaults for missing value-taking args.
+        // Apply env/config/defaults for missing value-taking args, and config
+        // for flags.
+        let Sources {
+            env,
+            config,
+            env_warnings,
+        } = sources;
+        let declared_env: HashSet<&str> = defs_dyn.iter().filter_map(|d| d.env()).collect();
         for (idx, info) in infos.iter().enumerate() {
-            if !info.takes_value || m.has_value_key(&info.name) {
+            let def = defs_dyn[idx];
+            if !info.takes_value {
+                if !def.countable()
+                    && !m.is_present(&info.name)
+                    && let Some(v) = env_lookup(config, &info.name)
+                {
+                    match args::parse_bool(v) {
+                        Some(b) => {
+                            m.push_implicit_value(&info.name, b.to_string(), ValueSource::Config)
+                        }
+                        None => {
+                            parse_error.get_or_insert(SpannedError::at(
+                                messages::invalid_value(
+                                    v,
+                                    &arg_display_name(def),
+                                    args::BOOL_VALUES,
+                                ),
+                                None,
+                            ));
+                        }
+                    }
+                }
+                continue;
+            }
+            if m.has_value_key(&info.name) {
                 continue;
             }
 
-            let def = defs_dyn[idx];
-            if let Some(key) = def.env()
-                && let Some(v) = env_lookup(env, key)
-            {
-                m.push_value(info.name.clone(), Cow::Owned(v.to_string()));
+            if let Some(key) = def.env() {
+                if let Some(v) = env_lookup(env, key) {
+                    m.push_implicit_value(&info.name, v.to_string(), ValueSource::Env);
+                    continue;
+                }
+                if env_warnings && let Some(found) = env_near_miss(env, key, &declared_env) {
+                    m.push_warning(messages::env_near_miss(found, key));
+                }
+            }
+
+            if let Some(v) = env_lookup(config, &info.name) {
+                m.push_implicit_value(&info.name, v.to_string(), ValueSource::Config);
                 continue;
             }
 
             if let Some(default_value) = info.default_value.clone() {
-                m.push_value(info.name.clone(), Cow::Owned(default_value));
+                m.push_implicit_value(&info.name, default_value, ValueSource::Default);
             }
         }
 
         // Built-in flags.
         if m.is_present(BUILTIN_HELP_NAME) {
-            return Ok(ParseOutcome::Help(help(meta)));
+            return Ok(ParseOutcome::Help(render_help(meta, path, style)));
         }
         if m.is_present(BUILTIN_VERSION_NAME) {
-            return Ok(ParseOutcome::Version(version(meta)));
+            return Ok(ParseOutcome::Version(render_version(path, version)));
         }
 
         if let Some(err) = parse_error {
             return Err(err);
         }
 
+        // Dispatch before the required checks below so that `cmd sub --help`
+        // works without the parent's required args.
+        let mut subcommand = None;
+        if let Some(at) = subcommand_at {
+            let subs = meta.subcommands();
+            let raw = argv[at].as_str();
+            let Some(sub) = subs
+                .iter()
+                .find(|sub| sub.name() == raw)
+                .or_else(|| subs.iter().find(|sub| has_alias(*sub, raw)))
+            else {
+                let valid: Vec<&str> = subs
+                    .iter()
+                    .filter(|sub| !sub.hidden())
+                    .map(|sub| sub.name())
+                    .collect();
+                return Err(SpannedError::at(
+                    messages::unknown_subcommand(raw, &valid),
+                    Some(ArgSpan::token(at, raw)),
+                ));
+            };
+            let sub_path = format!("{path} {}", sub.name());
+            let sub_version = if sub.version().trim().is_empty() {
+                version
+            } else {
+                sub.version()
+            };
+            match parse_env_impl(sub, &argv[at + 1..], sources, &sub_path, sub_version, style)
+                .map_err(|err| err.shifted(at + 1))?
+            {
+                ParseOutcome::Matches(sub_m) => subcommand = Some((sub.name(), sub_m, at + 1)),
+                outcome => return Ok(outcome),
+            }
+        }
+
         // Required checks.
         let mut missing: Vec<String> = Vec::new();
         for def in &defs {
@@ -1140,43 +2602,66 @@ pub mod claplike {
                 continue;
             }
 
-            if def.short().is_none() && def.long().is_none() {
-                missing.push(format!("<{}>", format_value_name(def)));
-                continue;
-            }
-
-            let mut s = def
-                .long()
-                .map(normalize_long)
-                .or_else(|| def.short().map(normalize_short))
-                .unwrap_or_else(|| def.name().to_string());
-            if def.takes_value() {
-                s.push(' ');
-                s.push('<');
-                s.push_str(&format_value_name(def));
-                s.push('>');
-            }
-            missing.push(s);
+            missing.push(required_arg_display(def));
         }
 
         if !missing.is_empty() {
-            if missing.len() == 1 {
-                return Err(ParseError::InvalidArgs(format!(
-                    "missing required argument: {}",
-                    missing[0]
-                )));
-            }
-            return Err(ParseError::InvalidArgs(format!(
-                "missing required arguments: {}",
-                missing.join(", ")
-            )));
+            return Err(SpannedError::at(messages::missing_required(&missing), None));
+        }
+
+        // Boolean values from any source are stored as "true"/"false".
+        for &def in defs_dyn.iter().filter(|d| is_bool(**d)) {
+            m.try_map_values(def.name(), |v| args::parse_bool(v).map(|b| b.to_string()))
+                .map_err(|v| {
+                    SpannedError::at(
+                        messages::invalid_value(&v, &arg_display_name(def), args::BOOL_VALUES),
+                        None,
+                    )
+                })?;
         }
 
-        validate_matches(&defs_dyn, &m)?;
+        validate_matches(&defs_dyn, &groups, &m, argv)?;
+
+        if let Some((name, sub_m, offset)) = subcommand {
+            m.set_subcommand(name, sub_m, offset);
+        }
 
         Ok(ParseOutcome::Matches(m))
     }
 
+    /// `argv` with every boolean-typed flag given without a value spelled out
+    /// (`--cache` becomes `--cache=true`, `-vc` becomes `-vc=true` and `-cv`
+    /// becomes `-c=true -v`), for a
+    /// parser that does not know value types, such as a plugin reading
+    /// `arg-def`. `m` must come from parsing this `argv`.
+    pub fn spell_out_bool_flags(argv: &[String], m: &Matches<'_>) -> Vec<String> {
+        let mut out = Vec::with_capacity(argv.len());
+        for (index, token) in argv.iter().enumerate() {
+            let mut bare: Vec<Option<usize>> = m
+                .bare_bools()
+                .iter()
+                .filter(|(i, _)| *i == index)
+                .map(|&(_, rest)| rest)
+                .collect();
+            if bare.is_empty() {
+                out.push(token.clone());
+                continue;
+            }
+            // A bare bool inside a bundle splits it: `-cv` becomes `-c=true -v`.
+            bare.sort();
+            let mut start = 0;
+            for &split in bare.iter().flatten() {
+                let dash = if start == 0 { "" } else { "-" };
+                out.push(format!("{dash}{}=true", &token[start..split]));
+                start = split;
+            }
+            let dash = if start == 0 { "" } else { "-" };
+            let suffix = if bare.contains(&None) { "=true" } else { "" };
+            out.push(format!("{dash}{}{suffix}", &token[start..]));
+        }
+        out
+    }
+
     /// Validate `argv` based on the `meta.args` schema.
     ///
     /// This is equivalent to parsing and ignoring the results. `--help`/`--version`
@@ -1198,23 +2683,269 @@ pub mod claplike {
         }
     }
 
-    /// Return the canonical command name for `raw`, matching either the command
-    /// name itself or an alias.
-    pub fn resolve_command_name<'a, M: CommandMetaLike>(
-        metas: &'a [M],
-        raw: &str,
+    /// How deep `@file` references may nest (a file named on the command line
+    /// is depth 1).
+    pub const ARG_FILE_MAX_DEPTH: usize = 2;
+
+    /// Expand `@path` arg files in `argv` when `meta` opts in via
+    /// [`CommandMetaLike::allow_arg_files`]; otherwise `argv` is returned as-is.
+    ///
+    /// Each `@path` token before `--` is replaced, in place, by the tokens read
+    /// from `path`: one token per line, trimmed, with blank lines and lines
+    /// starting with `#` ignored. Arg files may reference other arg files up to
+    /// [`ARG_FILE_MAX_DEPTH`] levels deep; cycles are rejected. Errors name the
+    /// file and line that caused them.
+    ///
+    /// `read` returns the contents of `path` (or an error message), so this
+    /// crate stays free of filesystem access.
+    pub fn expand_arg_files<M, R>(meta: &M, argv: &[String], read: R) -> ParseResult<Vec<String>>
+    where
+        M: CommandMetaLike,
+        R: FnMut(&str) -> Result<String, String>,
+    {
+        expand_arg_files_with_origins(meta, argv, read).map(|(args, _)| args)
+    }
+
+    /// Expand like [`expand_arg_files`], also returning for each expanded
+    /// token the index of the `argv` token it came from (the `@path` token,
+    /// for tokens read from a file).
+    pub fn expand_arg_files_with_origins<M, R>(
+        meta: &M,
+        argv: &[String],
+        mut read: R,
+    ) -> ParseResult<(Vec<String>, Vec<usize>)>
+    where
+        M: CommandMetaLike,
+        R: FnMut(&str) -> Result<String, String>,
+    {
+        if !meta.allow_arg_files() {
+            return Ok((argv.to_vec(), (0..argv.len()).collect()));
+        }
+
+        let mut state = ArgFileExpansion {
+            read: &mut read,
+            stack: Vec::new(),
+            after_separator: false,
+            out: Vec::with_capacity(argv.len()),
+            origins: Vec::with_capacity(argv.len()),
+            index: 0,
+        };
+        for (index, arg) in argv.iter().enumerate() {
+            state.index = index;
+            state.push(arg, None)?;
+        }
+        Ok((state.out, state.origins))
+    }
+
+    struct ArgFileExpansion<'r> {
+        read: &'r mut dyn FnMut(&str) -> Result<String, String>,
+        /// Arg files currently being expanded (outermost first).
+        stack: Vec<String>,
+        after_separator: bool,
+        out: Vec<String>,
+        /// `argv` index of each token in `out`.
+        origins: Vec<usize>,
+        /// `argv` index of the token being expanded.
+        index: usize,
+    }
+
+    impl ArgFileExpansion<'_> {
+        /// Push `token`, expanding it if it is an `@path` reference. `origin` is
+        /// the arg file and line the token came from.
+        fn push(&mut self, token: &str, origin: Option<(&str, usize)>) -> ParseResult<()> {
+            if token == "--" {
+                self.after_separator = true;
+            }
+            let path = match token.strip_prefix('@') {
+                Some(path) if !path.is_empty() && !self.after_separator => path,
+                _ => {
+                    self.out.push(token.to_string());
+                    self.origins.push(self.index);
+                    return Ok(());
+                }
+            };
+
+            let at = |msg: String| match origin {
+                Some((file, line)) => ParseError::InvalidArgs(format!("{file}:{line}: {msg}")),
+                None => ParseError::InvalidArgs(msg),
+            };
+            if self.stack.iter().any(|p| p == path) {
+                return Err(at(format!(
+                    "arg file cycle: {} -> {path}",
+                    self.stack.join(" -> ")
+                )));
+            }
+            if self.stack.len() >= ARG_FILE_MAX_DEPTH {
+                return Err(at(format!(
+                    "arg file {path} is nested more than {ARG_FILE_MAX_DEPTH} levels deep"
+                )));
+            }
+            let contents = (self.read)(path)
+                .map_err(|e| at(format!("failed to read arg file {path}: {e}")))?;
+
+            self.stack.push(path.to_string());
+            for (idx, line) in contents.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                self.push(line, Some((path, idx + 1)))?;
+            }
+            self.stack.pop();
+            Ok(())
+        }
+    }
+
+    /// Split `argv` into the tokens of `meta`'s [`CommandMetaLike::global_args`]
+    /// and everything else, both in their original order.
+    ///
+    /// Global options are recognized anywhere before `--` as `--long`,
+    /// `--long=value`, `-s` or (when they take a value) `-svalue`, with the
+    /// value in the next token otherwise. The value of a command option
+    /// (`--message --verbose`) is never mistaken for a global one. Bundled
+    /// short flags (`-vq`) are left to the command. A boolean-typed option
+    /// never takes the next token, and takes the rest of its token only when
+    /// that spells a value (`-doff`), as in [`parse`].
+    pub fn split_global_args<M: CommandMetaLike>(
+        meta: &M,
+        argv: &[String],
+    ) -> (Vec<String>, Vec<String>) {
+        let (global, rest) = split_global_arg_indices(meta, argv);
+        let tokens = |indices: Vec<usize>| indices.into_iter().map(|i| argv[i].clone()).collect();
+        (tokens(global), tokens(rest))
+    }
+
+    /// Split like [`split_global_args`], returning indices into `argv`
+    /// instead of tokens.
+    pub fn split_global_arg_indices<M: CommandMetaLike>(
+        meta: &M,
+        argv: &[String],
+    ) -> (Vec<usize>, Vec<usize>) {
+        let globals: Vec<ArgInfo> = meta.global_args().into_iter().map(build_arg_info).collect();
+        if globals.is_empty() {
+            return (Vec::new(), (0..argv.len()).collect());
+        }
+        let mut local_value_flags = Vec::new();
+        collect_value_flags(meta, &mut local_value_flags);
+
+        let (mut global, mut rest) = (Vec::new(), Vec::new());
+        let mut i = 0;
+        while i < argv.len() {
+            let token = &argv[i];
+            if token == "--" {
+                rest.extend(i..argv.len());
+                break;
+            }
+            let (dst, takes_next) = match match_global_token(&globals, token) {
+                Some(takes_next) => (&mut global, takes_next),
+                None => (&mut rest, local_value_flags.contains(token)),
+            };
+            dst.push(i);
+            if takes_next && i + 1 < argv.len() {
+                i += 1;
+                dst.push(i);
+            }
+            i += 1;
+        }
+        (global, rest)
+    }
+
+    /// `Some(takes_next)` when `token` is one of `globals`; `takes_next` says
+    /// whether its value is the following token.
+    fn match_global_token(globals: &[ArgInfo], token: &str) -> Option<bool> {
+        if let Some(body) = token.strip_prefix("--") {
+            let (name, attached) = match body.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (body, false),
+            };
+            let info = globals
+                .iter()
+                .find(|g| g.long.as_deref() == Some(&format!("--{name}")))?;
+            return Some(info.takes_value && !attached && !info.is_bool);
+        }
+        let mut chars = token.strip_prefix('-')?.chars();
+        let short = format!("-{}", chars.next()?);
+        let info = globals
+            .iter()
+            .find(|g| g.short.as_deref() == Some(short.as_str()))?;
+        let rest = chars.as_str();
+        match (rest.is_empty(), info.takes_value) {
+            (true, takes_value) => Some(takes_value && !info.is_bool),
+            // A bool takes only a value spelled out (`-doff`), so `-dv` is a
+            // bundle and is left to the command like `-vq`.
+            (false, true) if info.is_bool => {
+                (rest.starts_with('=') || args::parse_bool(rest).is_some()).then_some(false)
+            }
+            (false, true) => Some(false),
+            (false, false) => None,
+        }
+    }
+
+    /// Value-taking flags of `meta` and its subcommands, as they appear in
+    /// argv without an attached value. Boolean-typed ones never take the next
+    /// token, so they are left out.
+    fn collect_value_flags<M: CommandMetaLike>(meta: &M, out: &mut Vec<String>) {
+        for def in meta
+            .args()
+            .iter()
+            .filter(|d| d.takes_value() && !is_bool(*d))
+        {
+            out.extend(def.short().map(normalize_short));
+            out.extend(def.long().map(normalize_long));
+        }
+        for sub in meta.subcommands() {
+            collect_value_flags(sub, out);
+        }
+    }
+
+    /// The environment variable core uses to hand the global arg `name` to
+    /// commands: `WACLI_GLOBAL_` and the name upper-cased, with
+    /// non-alphanumerics mapped to `_` (`dry-run` => `WACLI_GLOBAL_DRY_RUN`).
+    pub fn global_arg_env_var(name: &str) -> String {
+        handoff_env_var("WACLI_GLOBAL_", name)
+    }
+
+    /// The environment variable core uses to hand the config value of the
+    /// arg `name` to commands, mangled like [`global_arg_env_var`]
+    /// (`dry-run` => `WACLI_CONFIG_DRY_RUN`).
+    pub fn config_env_var(name: &str) -> String {
+        handoff_env_var("WACLI_CONFIG_", name)
+    }
+
+    fn handoff_env_var(prefix: &str, name: &str) -> String {
+        let mut var = String::from(prefix);
+        var.extend(name.chars().map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        }));
+        var
+    }
+
+    /// Whether `raw` is one of `meta`'s aliases, hidden or not.
+    fn has_alias<M: CommandMetaLike>(meta: &M, raw: &str) -> bool {
+        meta.aliases()
+            .iter()
+            .chain(meta.hidden_aliases())
+            .any(|a| a == raw)
+    }
+
+    /// Return the canonical command name for `raw`, matching either the command
+    /// name itself or an alias (hidden ones included).
+    pub fn resolve_command_name<'a, M: CommandMetaLike>(
+        metas: &'a [M],
+        raw: &str,
     ) -> Option<&'a str> {
         if let Some(m) = metas.iter().find(|m| m.name() == raw) {
             return Some(m.name());
         }
-        metas
-            .iter()
-            .find(|m| m.aliases().iter().any(|a| a == raw))
-            .map(|m| m.name())
+        metas.iter().find(|m| has_alias(*m, raw)).map(|m| m.name())
     }
 
     /// Detect invalid alias configuration (duplicate aliases or alias colliding
-    /// with a command name).
+    /// with a command name). Hidden aliases are checked the same way.
     pub fn validate_aliases<M: CommandMetaLike>(metas: &[M]) -> ParseResult<()> {
         let mut names: HashSet<&str> = HashSet::new();
         for m in metas {
@@ -1225,7 +2956,7 @@ pub mod claplike {
 
         let mut alias_map: HashMap<&str, &str> = HashMap::new();
         for m in metas {
-            for alias in m.aliases() {
+            for alias in m.aliases().iter().chain(m.hidden_aliases()) {
                 let alias = alias.trim();
                 if alias.is_empty() || alias == m.name() {
                     continue;
@@ -1251,9 +2982,159 @@ pub mod claplike {
     }
 }
 
+/// Pipe specs: a pipe name with inline options, as passed to `--format`.
+///
+/// `format/table?width=80,header` names the pipe `format/table` and carries the
+/// options `["width=80", "header"]`. Options are split on `,` and taken
+/// verbatim (no percent-decoding), so they may contain `=`, `/`, `:` or
+/// spaces; empty items are dropped.
+pub mod pipe_spec {
+    /// A pipe name split from its inline options.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PipeSpec<'a> {
+        pub name: &'a str,
+        pub options: Vec<String>,
+    }
+
+    /// Split `spec` at its first `?`.
+    ///
+    /// The name itself is not validated here, but a `?` inside a path segment
+    /// (`format?x/table`) is rejected: an option whose key contains `/` means
+    /// the `?` did not follow the last segment.
+    pub fn parse(spec: &str) -> Result<PipeSpec<'_>, String> {
+        let (name, query) = match spec.split_once('?') {
+            Some((name, query)) => (name, Some(query)),
+            None => (spec, None),
+        };
+        if name.is_empty() {
+            return Err(format!("pipe spec '{spec}' has no pipe name"));
+        }
+        let mut options = Vec::new();
+        for option in query.unwrap_or_default().split(',') {
+            if option.is_empty() {
+                continue;
+            }
+            let key = option.split_once('=').map_or(option, |(key, _)| key);
+            if key.is_empty() {
+                return Err(format!(
+                    "pipe spec '{spec}' has an option without a name: '{option}'"
+                ));
+            }
+            if key.contains('/') {
+                return Err(format!(
+                    "pipe spec '{spec}' has '?' inside a path segment; \
+                     options must follow the full pipe name"
+                ));
+            }
+            options.push(option.to_string());
+        }
+        Ok(PipeSpec { name, options })
+    }
+}
+
+/// Reading the options a pipe's `process()` receives.
+pub mod pipe_options {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use crate::claplike::messages;
+
+    /// Options given as `key=value` strings, or a bare `key` for flags.
+    ///
+    /// Entries split at their first `=`, so values may contain `=`; empty
+    /// entries are skipped and leading `-`s on keys are dropped, as the host
+    /// does when it checks them against a declared schema. A key given more
+    /// than once answers [`Options::get`] with its last value.
+    #[derive(Debug, Clone, Default)]
+    pub struct Options<'a> {
+        entries: Vec<(&'a str, Option<&'a str>)>,
+    }
+
+    impl<'a> Options<'a> {
+        pub fn new(options: &'a [String]) -> Self {
+            let entries = options
+                .iter()
+                .filter(|opt| !opt.is_empty())
+                .map(|opt| match opt.split_once('=') {
+                    Some((key, value)) => (key.trim_start_matches('-'), Some(value)),
+                    None => (opt.trim_start_matches('-'), None),
+                })
+                .collect();
+            Self { entries }
+        }
+
+        /// The last value given for `key`. Bare entries carry no value.
+        pub fn get(&self, key: &str) -> Option<&'a str> {
+            self.entries
+                .iter()
+                .rev()
+                .find_map(|&(k, v)| if k == key { v } else { None })
+        }
+
+        /// Every value given for `key`, in order.
+        pub fn get_all(&self, key: &str) -> Vec<&'a str> {
+            self.entries
+                .iter()
+                .filter_map(|&(k, v)| if k == key { v } else { None })
+                .collect()
+        }
+
+        /// Parse the value of `key` as `T`.
+        pub fn get_parsed<T>(&self, key: &str) -> Result<Option<T>, String>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            self.get(key)
+                .map(|value| {
+                    value.parse().map_err(|err: T::Err| {
+                        messages::rejected_value(value, key, &err.to_string())
+                    })
+                })
+                .transpose()
+        }
+
+        /// Whether `key` was given, bare or with a value.
+        pub fn has(&self, key: &str) -> bool {
+            self.entries.iter().any(|&(k, _)| k == key)
+        }
+
+        /// The keys given, without duplicates, in order.
+        pub fn keys(&self) -> Vec<&'a str> {
+            let mut keys: Vec<&'a str> = Vec::new();
+            for &(key, _) in &self.entries {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+            keys
+        }
+
+        /// The keys given that are not in `allowed`.
+        pub fn unknown_keys(&self, allowed: &[&str]) -> Vec<&'a str> {
+            self.keys()
+                .into_iter()
+                .filter(|key| !allowed.contains(key))
+                .collect()
+        }
+
+        /// Fail on the first key not in `allowed`, listing the valid ones:
+        /// `unknown option 'colour'. valid options: case, trim`.
+        pub fn check_known(&self, allowed: &[&str]) -> Result<(), String> {
+            match self.unknown_keys(allowed).first() {
+                Some(key) => Err(format!(
+                    "unknown option '{key}'. valid options: {}",
+                    allowed.join(", ")
+                )),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::claplike;
+    use super::{args, claplike};
 
     #[derive(Debug, Clone)]
     struct ArgDef {
@@ -1267,10 +3148,15 @@ mod tests {
         value_name: Option<String>,
         takes_value: bool,
         multiple: bool,
+        value_type: Option<String>,
         possible_values: Vec<String>,
         conflicts_with: Vec<String>,
         requires: Vec<String>,
         hidden: bool,
+        group: Option<String>,
+        allow_hyphen_values: bool,
+        countable: bool,
+        var_arg: bool,
     }
 
     impl Default for ArgDef {
@@ -1286,10 +3172,15 @@ mod tests {
                 value_name: None,
                 takes_value: false,
                 multiple: true,
+                value_type: None,
                 possible_values: Vec::new(),
                 conflicts_with: Vec::new(),
                 requires: Vec::new(),
                 hidden: false,
+                group: None,
+                allow_hyphen_values: false,
+                countable: false,
+                var_arg: false,
             }
         }
     }
@@ -1325,6 +3216,9 @@ mod tests {
         fn multiple(&self) -> bool {
             self.multiple
         }
+        fn value_type(&self) -> Option<&str> {
+            self.value_type.as_deref()
+        }
         fn possible_values(&self) -> &[String] {
             self.possible_values.as_slice()
         }
@@ -1337,6 +3231,37 @@ mod tests {
         fn hidden(&self) -> bool {
             self.hidden
         }
+        fn group(&self) -> Option<&str> {
+            self.group.as_deref()
+        }
+        fn allow_hyphen_values(&self) -> bool {
+            self.allow_hyphen_values
+        }
+        fn countable(&self) -> bool {
+            self.countable
+        }
+        fn var_arg(&self) -> bool {
+            self.var_arg
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct Group {
+        name: String,
+        required: bool,
+        multiple: bool,
+    }
+
+    impl claplike::ArgGroupLike for Group {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn required(&self) -> bool {
+            self.required
+        }
+        fn multiple(&self) -> bool {
+            self.multiple
+        }
     }
 
     #[derive(Debug, Clone, Default)]
@@ -1345,11 +3270,17 @@ mod tests {
         summary: String,
         usage: String,
         aliases: Vec<String>,
+        hidden_aliases: Vec<String>,
         version: String,
         hidden: bool,
         description: String,
         examples: Vec<String>,
         args: Vec<ArgDef>,
+        groups: Vec<Group>,
+        global_args: Vec<ArgDef>,
+        allow_arg_files: bool,
+        help_style: claplike::HelpStyle,
+        subcommands: Vec<Meta>,
     }
 
     impl claplike::CommandMetaLike for Meta {
@@ -1367,6 +3298,9 @@ mod tests {
         fn aliases(&self) -> &[String] {
             self.aliases.as_slice()
         }
+        fn hidden_aliases(&self) -> &[String] {
+            self.hidden_aliases.as_slice()
+        }
         fn version(&self) -> &str {
             &self.version
         }
@@ -1382,6 +3316,27 @@ mod tests {
         fn args(&self) -> &[Self::ArgDef] {
             self.args.as_slice()
         }
+        fn groups(&self) -> Vec<&dyn claplike::ArgGroupLike> {
+            self.groups
+                .iter()
+                .map(|g| g as &dyn claplike::ArgGroupLike)
+                .collect()
+        }
+        fn global_args(&self) -> Vec<&dyn claplike::ArgDefLike> {
+            self.global_args
+                .iter()
+                .map(|a| a as &dyn claplike::ArgDefLike)
+                .collect()
+        }
+        fn allow_arg_files(&self) -> bool {
+            self.allow_arg_files
+        }
+        fn help_style(&self) -> claplike::HelpStyle {
+            self.help_style
+        }
+        fn subcommands(&self) -> &[Self] {
+            self.subcommands.as_slice()
+        }
     }
 
     #[test]
@@ -1459,114 +3414,1314 @@ mod tests {
         assert!(m.is_present("verbose"));
         assert_eq!(m.get("output"), Some("out.txt"));
         assert_eq!(m.get("file"), Some("in.txt"));
-    }
 
-    #[test]
-    fn validate_aliases_rejects_conflicts() {
-        let a = Meta {
-            name: "alpha".to_string(),
-            aliases: vec!["beta".to_string()],
-            ..Default::default()
-        };
-        let b = Meta {
-            name: "beta".to_string(),
-            ..Default::default()
+        for (argv, verbose) in [
+            (&["-o=out.txt", "in.txt"][..], false),
+            (&["-o", "out.txt", "in.txt"], false),
+            (&["-oout.txt", "in.txt"], false),
+            (&["-vo=out.txt", "in.txt"], true),
+            (&["-v", "-o=out.txt", "in.txt"], true),
+        ] {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+                panic!("expected Matches");
+            };
+            assert_eq!(m.get("output"), Some("out.txt"), "{argv:?}");
+            assert_eq!(m.is_present("verbose"), verbose, "{argv:?}");
+            assert_eq!(m.get("file"), Some("in.txt"), "{argv:?}");
+        }
+
+        // Only the first `=` is a separator; an empty attached value stays empty.
+        let argv = vec!["-o==x".to_string(), "-o=".to_string(), "in.txt".to_string()];
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected Matches");
         };
+        let outputs: Vec<&str> = m
+            .get_all("output")
+            .unwrap()
+            .iter()
+            .map(|v| v.as_ref())
+            .collect();
+        assert_eq!(outputs, ["=x", ""]);
 
-        let err = claplike::validate_aliases(&[a, b]).unwrap_err();
-        match err {
-            claplike::ParseError::Failed(msg) => assert!(msg.contains("alias conflict")),
-            other => panic!("expected Failed, got: {other:?}"),
-        }
+        let argv = vec!["-v=yes".to_string(), "in.txt".to_string()];
+        let err = claplike::parse(&meta, &argv).unwrap_err();
+        assert_eq!(err.message(), "flag does not take a value: -v");
     }
 
-    #[test]
-    fn parse_with_env_respects_precedence() {
-        let meta = Meta {
-            name: "cmd".to_string(),
-            args: vec![ArgDef {
-                name: "format".to_string(),
-                long: Some("--format".to_string()),
-                value_name: Some("FMT".to_string()),
+    fn calc_meta(digit_short: bool) -> Meta {
+        let mut args = vec![
+            ArgDef {
+                name: "verbose".to_string(),
+                short: Some("-v".to_string()),
+                ..Default::default()
+            },
+            ArgDef {
+                name: "a".to_string(),
                 takes_value: true,
-                default_value: Some("plain".to_string()),
-                env: Some("FORMAT".to_string()),
-                possible_values: vec!["plain".to_string(), "json".to_string(), "xml".to_string()],
-                multiple: true,
                 ..Default::default()
-            }],
+            },
+            ArgDef {
+                name: "b".to_string(),
+                takes_value: true,
+                ..Default::default()
+            },
+        ];
+        if digit_short {
+            args.push(ArgDef {
+                name: "five".to_string(),
+                short: Some("-5".to_string()),
+                ..Default::default()
+            });
+        }
+        Meta {
+            name: "calc".to_string(),
+            args,
             ..Default::default()
-        };
+        }
+    }
 
-        // env beats default
-        let argv: Vec<String> = vec![];
-        let env = vec![("FORMAT".to_string(), "json".to_string())];
-        let claplike::ParseOutcome::Matches(m) =
-            claplike::parse_with_env(&meta, &argv, &env).unwrap()
-        else {
-            panic!("expected Matches");
-        };
-        assert_eq!(m.get("format"), Some("json"));
+    #[test]
+    fn negative_numbers_are_positionals_unless_a_short_flag_is_a_digit() {
+        let strings =
+            |argv: &[&str]| -> Vec<String> { argv.iter().map(|s| s.to_string()).collect() };
 
-        // default used when env absent
-        let env: Vec<(String, String)> = vec![];
-        let claplike::ParseOutcome::Matches(m) =
-            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        let argv = strings(&["-5", "-v", "-0.25"]);
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&calc_meta(false), &argv).unwrap()
         else {
             panic!("expected Matches");
         };
-        assert_eq!(m.get("format"), Some("plain"));
+        assert!(m.get_flag("verbose"));
+        assert_eq!(m.get("a"), Some("-5"));
+        assert_eq!(m.get("b"), Some("-0.25"));
+
+        for (token, message) in [
+            ("-5x", "unknown flag: -5"),
+            ("--5", "unknown flag: --5"),
+            ("-.5", "unknown flag: -."),
+            ("-5.", "unknown flag: -5"),
+        ] {
+            let err = claplike::parse(&calc_meta(false), &strings(&[token])).unwrap_err();
+            assert_eq!(err.message(), message, "{token}");
+        }
 
-        // argv beats env
-        let argv = vec!["--format".to_string(), "xml".to_string()];
-        let env = vec![("FORMAT".to_string(), "json".to_string())];
-        let claplike::ParseOutcome::Matches(m) =
-            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        // A declared digit flag wins over numbers.
+        let argv = strings(&["-5", "3"]);
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&calc_meta(true), &argv).unwrap()
         else {
             panic!("expected Matches");
         };
-        assert_eq!(m.get("format"), Some("xml"));
-    }
-
-    #[test]
-    fn validate_rejects_invalid_value() {
-        let meta = Meta {
-            name: "cmd".to_string(),
-            args: vec![ArgDef {
-                name: "format".to_string(),
-                long: Some("--format".to_string()),
-                value_name: Some("FMT".to_string()),
-                takes_value: true,
-                possible_values: vec!["plain".to_string(), "json".to_string()],
-                multiple: true,
-                ..Default::default()
-            }],
-            ..Default::default()
-        };
-        let argv = vec!["--format".to_string(), "xml".to_string()];
-        let err = claplike::validate(&meta, &argv).unwrap_err();
-        match err {
-            claplike::ParseError::InvalidArgs(msg) => assert!(msg.contains("invalid value")),
-            other => panic!("expected InvalidArgs, got: {other:?}"),
-        }
+        assert!(m.get_flag("five"));
+        assert_eq!(m.get("a"), Some("3"));
+        let err = claplike::parse(&calc_meta(true), &strings(&["-0.25"])).unwrap_err();
+        assert_eq!(err.message(), "unknown flag: -0");
     }
 
     #[test]
-    fn validate_rejects_conflicts_with() {
-        let meta = Meta {
-            name: "cmd".to_string(),
+    fn value_flags_do_not_swallow_known_flags_unless_hyphen_values_are_allowed() {
+        let strings =
+            |argv: &[&str]| -> Vec<String> { argv.iter().map(|s| s.to_string()).collect() };
+        let meta = |allow: bool| Meta {
+            name: "grep".to_string(),
             args: vec![
                 ArgDef {
-                    name: "a".to_string(),
-                    long: Some("--a".to_string()),
-                    takes_value: false,
-                    conflicts_with: vec!["b".to_string()],
+                    name: "filter".to_string(),
+                    short: Some("-f".to_string()),
+                    long: Some("--filter".to_string()),
+                    takes_value: true,
+                    allow_hyphen_values: allow,
                     ..Default::default()
                 },
                 ArgDef {
-                    name: "b".to_string(),
-                    long: Some("--b".to_string()),
-                    takes_value: false,
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    long: Some("--verbose".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        for (argv, message) in [
+            (
+                &["--filter", "-v"][..],
+                "missing value for --filter (found flag -v)",
+            ),
+            (
+                &["--filter", "-vx"],
+                "missing value for --filter (found flag -v)",
+            ),
+            (
+                &["-f", "--verbose"],
+                "missing value for -f (found flag --verbose)",
+            ),
+            (
+                &["-vf", "--verbose=1"],
+                "missing value for -f (found flag --verbose)",
+            ),
+        ] {
+            let err = claplike::parse(&meta(false), &strings(argv)).unwrap_err();
+            assert_eq!(err.message(), message, "{argv:?}");
+        }
+
+        // Tokens that name no flag are still values.
+        for value in ["-", "-x", "--bar", "-5"] {
+            let argv = strings(&["--filter", value]);
+            let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta(false), &argv).unwrap()
+            else {
+                panic!("expected Matches");
+            };
+            assert_eq!(m.get("filter"), Some(value));
+        }
+
+        let argv = strings(&["--filter", "-v", "-f", "--verbose"]);
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta(true), &argv).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        let filters: Vec<&str> = m
+            .get_all("filter")
+            .unwrap()
+            .iter()
+            .map(|v| v.as_ref())
+            .collect();
+        assert_eq!(filters, ["-v", "--verbose"]);
+        assert!(!m.is_present("verbose"));
+    }
+
+    #[test]
+    fn countable_flags_count_every_occurrence() {
+        let strings =
+            |argv: &[&str]| -> Vec<String> { argv.iter().map(|s| s.to_string()).collect() };
+        let meta = Meta {
+            name: "log".to_string(),
+            args: vec![
+                ArgDef {
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    long: Some("--verbose".to_string()),
+                    multiple: false,
+                    countable: true,
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "quiet".to_string(),
+                    short: Some("-q".to_string()),
+                    multiple: false,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        for (argv, count) in [
+            (&["-vvv"][..], 3),
+            (&["-v", "-v", "--verbose"], 3),
+            (&["-vqv"], 2),
+            (&[], 0),
+        ] {
+            let argv = strings(argv);
+            let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+                panic!("expected Matches");
+            };
+            assert_eq!(m.count("verbose"), count, "{argv:?}");
+        }
+
+        let err = claplike::parse(&meta, &strings(&["-qq"])).unwrap_err();
+        assert_eq!(err.message(), claplike::messages::used_multiple_times("-q"));
+        assert!(claplike::help(&meta).contains("-v, --verbose..."));
+    }
+
+    #[test]
+    fn positional_args_keep_negative_numbers() {
+        let argv: Vec<String> = ["-5", "-v", "-0.25", "-5x", "--5", "--", "-x"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(args::positional_args(&argv), ["-5", "-0.25", "-x"]);
+
+        let schema = args::Schema::new().value_flag("-5");
+        assert_eq!(args::positional_args_with_schema(&argv, &schema), ["-x"]);
+        assert!(args::is_negative_number("-12.50"));
+        assert!(!args::is_negative_number("-1e5"));
+        assert!(!args::is_negative_number("5"));
+    }
+
+    #[test]
+    fn split_words_handles_quotes_and_escapes() {
+        assert_eq!(
+            args::split_words(r#"status --short "two words" 'it''s' a\ b"#).unwrap(),
+            ["status", "--short", "two words", "its", "a b"]
+        );
+        assert_eq!(
+            args::split_words(r#"log "say \"hi\"" 'c:\dir' x\'y"#).unwrap(),
+            ["log", r#"say "hi""#, r"c:\dir", "x'y"]
+        );
+        assert_eq!(args::split_words("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(args::split_words("x '' \"\"").unwrap(), ["x", "", ""]);
+        assert_eq!(
+            args::split_words("show 'open").unwrap_err(),
+            "unterminated quote"
+        );
+        assert_eq!(
+            args::split_words("tail\\").unwrap_err(),
+            "trailing backslash"
+        );
+    }
+
+    fn occurrence_meta() -> Meta {
+        Meta {
+            name: "cp".to_string(),
+            args: vec![
+                ArgDef {
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    takes_value: false,
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "output".to_string(),
+                    short: Some("-o".to_string()),
+                    long: Some("--output".to_string()),
+                    takes_value: true,
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "mode".to_string(),
+                    long: Some("--mode".to_string()),
+                    takes_value: true,
+                    default_value: Some("copy".to_string()),
+                    env: Some("CP_MODE".to_string()),
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "src".to_string(),
+                    takes_value: true,
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "dst".to_string(),
+                    takes_value: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn occurrence_indices(
+        m: &args::Matches<'_>,
+        name: &str,
+    ) -> Vec<(Option<usize>, Option<usize>)> {
+        m.occurrences(name)
+            .iter()
+            .map(|o| (o.flag_index(), o.argv_index()))
+            .collect()
+    }
+
+    #[test]
+    fn occurrences_track_combined_short_flags() {
+        let meta = occurrence_meta();
+        let argv: Vec<String> = ["a", "-vo", "out.txt", "-vofile", "b"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected Matches");
+        };
+
+        assert_eq!(
+            occurrence_indices(&m, "verbose"),
+            vec![(Some(1), Some(1)), (Some(3), Some(3))]
+        );
+        assert_eq!(
+            occurrence_indices(&m, "output"),
+            vec![(Some(1), Some(2)), (Some(3), Some(3))]
+        );
+        let values: Vec<Option<&str>> = m.occurrences("output").iter().map(|o| o.value()).collect();
+        assert_eq!(values, vec![Some("out.txt"), Some("file")]);
+        assert!(m.occurrences("verbose").iter().all(|o| o.value().is_none()));
+    }
+
+    #[test]
+    fn occurrences_track_equals_and_separate_value_forms() {
+        let meta = occurrence_meta();
+        let argv: Vec<String> = ["--output=a.txt", "--output", "b.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected Matches");
+        };
+
+        assert_eq!(
+            occurrence_indices(&m, "output"),
+            vec![(Some(0), Some(0)), (Some(1), Some(2))]
+        );
+        assert_eq!(m.occurrences("output")[0].value(), Some("a.txt"));
+        assert!(m.occurrences("src").is_empty());
+    }
+
+    #[test]
+    fn occurrences_track_positionals_after_separator() {
+        let meta = occurrence_meta();
+        let argv: Vec<String> = ["-v", "--", "-src", "dst", "extra"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected Matches");
+        };
+
+        assert_eq!(occurrence_indices(&m, "src"), vec![(None, Some(2))]);
+        assert_eq!(occurrence_indices(&m, "dst"), vec![(None, Some(3))]);
+        assert_eq!(m.occurrences("src")[0].value(), Some("-src"));
+        assert_eq!(m.rest(), &["extra"]);
+    }
+
+    #[test]
+    fn trailing_var_arg_positional_takes_remaining_tokens() {
+        let positional = |name: &str, required: bool, var_arg: bool| ArgDef {
+            name: name.to_string(),
+            required,
+            takes_value: true,
+            var_arg,
+            ..Default::default()
+        };
+        let meta = |var_arg: bool| Meta {
+            name: "cp".to_string(),
+            args: vec![
+                positional("dest", true, false),
+                positional("files", true, var_arg),
+                ArgDef {
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let argv: Vec<String> = ["out", "a", "-v", "b", "--", "-c"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta(true), &argv).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(m.get("dest"), Some("out"));
+        assert_eq!(m.get_all("files").unwrap(), ["a", "b", "-c"]);
+        assert!(m.rest().is_empty());
+
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta(false), &argv).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(m.get_all("files").unwrap(), ["a"]);
+        assert_eq!(m.rest(), &["b", "-c"]);
+
+        // Required means at least one.
+        let err = claplike::parse(&meta(true), &argv[..1]).unwrap_err();
+        assert!(claplike::messages::is_missing_required(err.message()));
+
+        let help = claplike::help(&meta(true));
+        assert!(help.contains("  <DEST>\n"), "{help}");
+        assert!(help.contains("  <FILES>...\n"), "{help}");
+        let help = claplike::help(&meta(false));
+        assert!(help.contains("  <FILES>\n"), "{help}");
+    }
+
+    #[test]
+    fn occurrences_report_env_and_default_sources() {
+        let meta = occurrence_meta();
+        let argv: Vec<String> = vec![];
+
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected Matches");
+        };
+        let occ = m.occurrences("mode");
+        assert_eq!(occ.len(), 1);
+        assert_eq!(occ[0].source(), args::ValueSource::Default);
+        assert_eq!(occ[0].argv_index(), None);
+        assert_eq!(occ[0].value(), Some("copy"));
+
+        let env = vec![("CP_MODE".to_string(), "move".to_string())];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        let occ = m.occurrences("mode");
+        assert_eq!(occ[0].source(), args::ValueSource::Env);
+        assert_eq!(occ[0].value(), Some("move"));
+
+        let argv = vec!["--mode".to_string(), "link".to_string()];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        let occ = m.occurrences("mode");
+        assert_eq!(occ.len(), 1);
+        assert_eq!(occ[0].source(), args::ValueSource::Argv);
+        assert_eq!(occ[0].argv_index(), Some(1));
+    }
+
+    #[test]
+    fn config_values_sit_between_env_and_defaults() {
+        fn pairs(kv: &[(&str, &str)]) -> Vec<(String, String)> {
+            kv.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        }
+        fn parse<'a>(
+            argv: &'a [String],
+            env: &[(&str, &str)],
+            config: &[(&str, &str)],
+        ) -> args::Matches<'a> {
+            let meta = occurrence_meta();
+            match claplike::parse_with_sources(&meta, argv, &pairs(env), &pairs(config)) {
+                Ok(claplike::ParseOutcome::Matches(m)) => m,
+                other => panic!("expected Matches, got: {other:?}"),
+            }
+        }
+
+        let none: Vec<String> = vec![];
+        let config = [("mode", "link"), ("output", "out.txt"), ("verbose", "true")];
+        let m = parse(&none, &[], &config);
+        assert_eq!(m.get("mode"), Some("link"));
+        assert_eq!(m.get("output"), Some("out.txt"));
+        assert_eq!(m.occurrences("mode")[0].source(), args::ValueSource::Config);
+        assert_eq!(m.occurrences("mode")[0].argv_index(), None);
+        assert!(!m.is_explicit("mode"));
+        assert!(!m.is_default("mode"));
+        assert!(m.provided_args().is_empty());
+        // Flags read config too.
+        assert!(m.get_flag("verbose"));
+        assert!(!m.is_explicit("verbose"));
+        let m = parse(&none, &[], &[("verbose", "off")]);
+        assert!(!m.get_flag("verbose"));
+
+        let m = parse(&none, &[("CP_MODE", "move")], &config);
+        assert_eq!(m.get("mode"), Some("move"));
+        assert_eq!(m.occurrences("mode")[0].source(), args::ValueSource::Env);
+
+        let argv = vec!["--mode".to_string(), "copy".to_string()];
+        let m = parse(&argv, &[("CP_MODE", "move")], &config);
+        assert_eq!(m.get("mode"), Some("copy"));
+        assert!(m.is_explicit("mode"));
+
+        let m = parse(&none, &[], &[]);
+        assert_eq!(m.get("mode"), Some("copy"));
+        assert!(m.is_default("mode"));
+    }
+
+    #[test]
+    fn config_values_are_validated() {
+        let mut meta = occurrence_meta();
+        meta.args[2].possible_values = vec!["copy".to_string(), "move".to_string()];
+        let config = vec![("mode".to_string(), "link".to_string())];
+        let err = claplike::parse_spanned(&meta, &[], &[], &config, false).unwrap_err();
+        assert!(
+            err.error.message().contains("'link'"),
+            "{}",
+            err.error.message()
+        );
+        assert!(err.span.is_none());
+
+        let config = vec![("count".to_string(), "-1".to_string())];
+        let err = claplike::parse_with_sources(&typed_meta(), &[], &[], &config).unwrap_err();
+        assert!(err.message().contains("'-1'"), "{}", err.message());
+
+        let config = vec![("verbose".to_string(), "sometimes".to_string())];
+        let err = claplike::parse_with_sources(&meta, &[], &[], &config).unwrap_err();
+        assert!(err.message().contains("'sometimes'"), "{}", err.message());
+    }
+
+    #[test]
+    fn provided_and_unset_args_follow_value_sources() {
+        let meta = occurrence_meta();
+        let env = vec![("CP_MODE".to_string(), "move".to_string())];
+
+        let parse = |argv: &[&str], env: &[(String, String)]| {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let claplike::ParseOutcome::Matches(m) =
+                claplike::parse_with_env(&meta, &argv, env).unwrap()
+            else {
+                panic!("expected Matches");
+            };
+            let provided: Vec<String> = m.provided_args().iter().map(|s| s.to_string()).collect();
+            let unset: Vec<String> = m.unset_args(&meta).iter().map(|s| s.to_string()).collect();
+            (provided, unset, m.is_default("mode"))
+        };
+
+        // Nothing given: only `mode` is satisfied, by its default.
+        let (provided, unset, is_default) = parse(&[], &[]);
+        assert!(provided.is_empty());
+        assert_eq!(unset, ["verbose", "output", "src", "dst"]);
+        assert!(is_default);
+
+        // Env satisfies `mode` without counting as provided.
+        let (provided, unset, is_default) = parse(&["a.txt"], &env);
+        assert_eq!(provided, ["src"]);
+        assert_eq!(unset, ["verbose", "output", "dst"]);
+        assert!(!is_default);
+
+        // Argv, in first-appearance order.
+        let (provided, unset, is_default) =
+            parse(&["a.txt", "-o", "out.txt", "--mode", "link", "-v"], &env);
+        assert_eq!(provided, ["src", "output", "mode", "verbose"]);
+        assert_eq!(unset, ["dst"]);
+        assert!(!is_default);
+    }
+
+    fn subcommand_meta() -> Meta {
+        let force = ArgDef {
+            name: "force".to_string(),
+            short: Some("-f".to_string()),
+            long: Some("--force".to_string()),
+            ..Default::default()
+        };
+        Meta {
+            name: "remote".to_string(),
+            version: "1.0.0".to_string(),
+            args: vec![ArgDef {
+                name: "verbose".to_string(),
+                short: Some("-v".to_string()),
+                long: Some("--verbose".to_string()),
+                ..Default::default()
+            }],
+            subcommands: vec![
+                Meta {
+                    name: "add".to_string(),
+                    summary: "Add a remote".to_string(),
+                    aliases: vec!["a".to_string()],
+                    args: vec![
+                        ArgDef {
+                            name: "name".to_string(),
+                            required: true,
+                            takes_value: true,
+                            ..Default::default()
+                        },
+                        force,
+                    ],
+                    ..Default::default()
+                },
+                Meta {
+                    name: "list".to_string(),
+                    summary: "List remotes".to_string(),
+                    ..Default::default()
+                },
+                Meta {
+                    name: "debug".to_string(),
+                    hidden: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// Token index and byte range of an `ArgSpan`.
+    type Span = (usize, std::ops::Range<usize>);
+
+    fn error_span(meta: &Meta, argv: &[&str]) -> Option<Span> {
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        let err = claplike::parse_spanned(meta, &argv, &[], &[], false)
+            .expect_err("expected a parse error");
+        err.span.map(|s| (s.index, s.range))
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_offending_token() {
+        let mut meta = occurrence_meta();
+        meta.args[0].conflicts_with = vec!["output".to_string()];
+        meta.args[2].possible_values = vec!["copy".to_string(), "move".to_string()];
+
+        let cases: &[(&[&str], Span)] = &[
+            (&["a", "--formt", "b"], (1, 0..7)),
+            (&["--outptu=x"], (0, 0..8)),
+            (&["-vz"], (0, 2..3)),
+            (&["-v=x"], (0, 2..4)),
+            (&["a", "-o"], (1, 0..2)),
+            (&["--mode"], (0, 0..6)),
+            (&["-o", "-v"], (1, 0..2)),
+            (&["--mode=mv"], (0, 7..9)),
+            (&["--mode", "mv"], (1, 0..2)),
+            (&["-o", "x", "-v"], (2, 0..2)),
+        ];
+        for (argv, span) in cases {
+            assert_eq!(error_span(&meta, argv), Some(span.clone()), "{argv:?}");
+        }
+
+        // Subcommand errors point into the whole argv.
+        let meta = subcommand_meta();
+        assert_eq!(
+            error_span(&meta, &["-v", "add", "origin", "--forse"]),
+            Some((3, 0..7))
+        );
+        assert_eq!(error_span(&meta, &["-v", "ad"]), Some((1, 0..2)));
+        assert_eq!(error_span(&meta, &["add"]), None);
+    }
+
+    #[test]
+    fn render_error_underlines_the_span() {
+        let meta = occurrence_meta();
+        let render = |argv: &[&str]| {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let err = claplike::parse_spanned(&meta, &argv, &[], &[], false)
+                .expect_err("expected a parse error");
+            claplike::render_error(&err, &argv)
+        };
+
+        assert_eq!(
+            render(&["a", "--formt", "b"]),
+            "unknown flag: --formt\n  a --formt b\n    ^^^^^^^"
+        );
+        assert_eq!(
+            render(&["two words", "-vz"]),
+            "unknown flag: -z\n  'two words' -vz\n                ^"
+        );
+
+        // Without a span (or with argv it does not fit) only the message is left.
+        let err = claplike::SpannedError::from(claplike::ParseError::from("bad".to_string()));
+        assert_eq!(claplike::render_error(&err, &[]), "bad");
+        let err = claplike::SpannedError {
+            error: claplike::ParseError::InvalidArgs("bad".to_string()),
+            span: Some(claplike::ArgSpan::token(3, "x")),
+        };
+        assert_eq!(claplike::render_error(&err, &["x".to_string()]), "bad");
+
+        // The other entry points drop the span.
+        let argv = vec!["--formt".to_string()];
+        match claplike::parse(&meta, &argv) {
+            Err(claplike::ParseError::InvalidArgs(msg)) => assert_eq!(msg, "unknown flag: --formt"),
+            other => panic!("expected InvalidArgs, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn subcommands_dispatch_on_the_first_positional() {
+        let meta = subcommand_meta();
+        let argv: Vec<String> = ["-v", "a", "origin", "--force"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected matches");
+        };
+        assert!(m.get_flag("verbose"));
+        assert_eq!(m.subcommand_name(), Some("add"));
+        let (_, sub) = m.subcommand().unwrap();
+        assert_eq!(sub.get("name"), Some("origin"));
+        assert!(sub.get_flag("force"));
+        // Indices stay relative to the whole argv.
+        assert_eq!(sub.occurrences("name")[0].argv_index(), Some(2));
+        assert_eq!(sub.occurrences("force")[0].flag_index(), Some(3));
+
+        let argv = vec!["-v".to_string()];
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected matches");
+        };
+        assert!(m.subcommand().is_none());
+    }
+
+    #[test]
+    fn subcommands_render_their_own_help_and_errors() {
+        let meta = subcommand_meta();
+        let help = claplike::help(&meta);
+        assert!(help.contains("\nCommands:\n  add   Add a remote\n  list  List remotes\n"));
+        assert!(!help.contains("debug"));
+
+        let argv: Vec<String> = vec!["add".to_string(), "--help".to_string()];
+        let claplike::ParseOutcome::Help(text) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected help");
+        };
+        assert!(text.starts_with("remote add - Add a remote\n\nUsage: remote add\n"));
+
+        let argv: Vec<String> = vec!["list".to_string(), "-V".to_string()];
+        let claplike::ParseOutcome::Version(text) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected version");
+        };
+        assert_eq!(text, "remote list 1.0.0\n");
+
+        let argv: Vec<String> = vec!["rename".to_string()];
+        let err = claplike::parse(&meta, &argv).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "unknown subcommand 'rename'. valid subcommands: add, list"
+        );
+
+        let argv: Vec<String> = vec!["add".to_string()];
+        let err = claplike::parse(&meta, &argv).unwrap_err();
+        assert_eq!(err.message(), "missing required argument: <NAME>");
+    }
+
+    #[test]
+    fn validate_aliases_rejects_conflicts() {
+        let a = Meta {
+            name: "alpha".to_string(),
+            aliases: vec!["beta".to_string()],
+            ..Default::default()
+        };
+        let b = Meta {
+            name: "beta".to_string(),
+            ..Default::default()
+        };
+
+        let err = claplike::validate_aliases(&[a, b]).unwrap_err();
+        match err {
+            claplike::ParseError::Failed(msg) => assert!(msg.contains("alias conflict")),
+            other => panic!("expected Failed, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hidden_aliases_dispatch_but_are_not_shown() {
+        let greet = Meta {
+            name: "greet".to_string(),
+            aliases: vec!["hi".to_string(), "hello".to_string()],
+            hidden_aliases: vec!["g".to_string()],
+            ..Default::default()
+        };
+        let other = Meta {
+            name: "other".to_string(),
+            hidden_aliases: vec!["g".to_string()],
+            ..Default::default()
+        };
+
+        let metas = [greet.clone()];
+        assert_eq!(claplike::resolve_command_name(&metas, "g"), Some("greet"));
+        assert_eq!(claplike::resolve_command_name(&metas, "hi"), Some("greet"));
+        let help = claplike::help(&greet);
+        assert!(
+            help.contains("\nUsage: greet\nAliases: hi, hello\n"),
+            "{help}"
+        );
+
+        let err = claplike::validate_aliases(&[greet, other]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "alias conflict: 'g' refers to both 'greet' and 'other'"
+        );
+    }
+
+    #[test]
+    fn parse_with_env_respects_precedence() {
+        let meta = Meta {
+            name: "cmd".to_string(),
+            args: vec![ArgDef {
+                name: "format".to_string(),
+                long: Some("--format".to_string()),
+                value_name: Some("FMT".to_string()),
+                takes_value: true,
+                default_value: Some("plain".to_string()),
+                env: Some("FORMAT".to_string()),
+                possible_values: vec!["plain".to_string(), "json".to_string(), "xml".to_string()],
+                multiple: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // env beats default
+        let argv: Vec<String> = vec![];
+        let env = vec![("FORMAT".to_string(), "json".to_string())];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(m.get("format"), Some("json"));
+
+        // default used when env absent
+        let env: Vec<(String, String)> = vec![];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(m.get("format"), Some("plain"));
+
+        // argv beats env
+        let argv = vec!["--format".to_string(), "xml".to_string()];
+        let env = vec![("FORMAT".to_string(), "json".to_string())];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert_eq!(m.get("format"), Some("xml"));
+    }
+
+    fn env_warning_meta() -> Meta {
+        let env_arg = |name: &str, env: &str| ArgDef {
+            name: name.to_string(),
+            long: Some(format!("--{name}")),
+            takes_value: true,
+            env: Some(env.to_string()),
+            ..Default::default()
+        };
+        Meta {
+            name: "show".to_string(),
+            args: vec![
+                ArgDef {
+                    default_value: Some("plain".to_string()),
+                    ..env_arg("format", "SHOW_FORMAT")
+                },
+                env_arg("color", "SHOW_COLOR"),
+                env_arg("user", "USER"),
+                env_arg("level", "SHOW_LEVEL"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn env_warnings(argv: &[&str], env: &[(&str, &str)]) -> (Option<String>, Vec<String>) {
+        let meta = env_warning_meta();
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        let env: Vec<(String, String)> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env_diagnostics(&meta, &argv, &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        (m.get("format").map(str::to_string), m.warnings().to_vec())
+    }
+
+    #[test]
+    fn env_near_misses_are_reported_and_ignored() {
+        let (format, warnings) = env_warnings(&[], &[("SHOW_FORMTA", "json")]);
+        assert_eq!(format.as_deref(), Some("plain"));
+        assert_eq!(
+            warnings,
+            [
+                "environment variable 'SHOW_FORMTA' is set but 'SHOW_FORMAT' is expected \u{2014} ignoring"
+            ]
+        );
+
+        // One suggestion per arg: the closest, then the first in env order.
+        let (_, warnings) = env_warnings(
+            &[],
+            &[
+                ("SHOW_FORMATS", "a"),
+                ("SHOW_FORMAT_", "b"),
+                ("show_format", "c"),
+            ],
+        );
+        assert_eq!(
+            warnings,
+            [
+                "environment variable 'show_format' is set but 'SHOW_FORMAT' is expected \u{2014} ignoring"
+            ]
+        );
+
+        // Only consulted when argv leaves the env var in play.
+        let (format, warnings) = env_warnings(&["--format", "xml"], &[("SHOW_FORMTA", "json")]);
+        assert_eq!(format.as_deref(), Some("xml"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn env_case_mismatches_are_reported() {
+        let (_, warnings) = env_warnings(&[], &[("Show_Color", "always"), ("user", "bob")]);
+        assert_eq!(
+            warnings,
+            [
+                "environment variable 'Show_Color' is set but 'SHOW_COLOR' is expected \u{2014} ignoring",
+                "environment variable 'user' is set but 'USER' is expected \u{2014} ignoring",
+            ]
+        );
+    }
+
+    #[test]
+    fn env_warnings_ignore_unrelated_variables() {
+        let env = [
+            ("PATH", "/usr/bin"),
+            ("HOME", "/home/bob"),
+            ("USERNAME", "bob"),
+            ("SHELL", "/bin/sh"),
+            ("SHOW_FORMAT", "json"),
+            // Declared by `level`; not a typo of `color` although close.
+            ("SHOW_LEVEL", "debug"),
+            ("SHOW_COLUMNS", "80"),
+        ];
+        let (format, warnings) = env_warnings(&[], &env);
+        assert_eq!(format.as_deref(), Some("json"));
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        // Plain `parse_with_env` never warns.
+        let meta = env_warning_meta();
+        let env = vec![("SHOW_FORMTA".to_string(), "json".to_string())];
+        let claplike::ParseOutcome::Matches(m) =
+            claplike::parse_with_env(&meta, &[], &env).unwrap()
+        else {
+            panic!("expected Matches");
+        };
+        assert!(m.warnings().is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_value() {
+        let meta = Meta {
+            name: "cmd".to_string(),
+            args: vec![ArgDef {
+                name: "format".to_string(),
+                long: Some("--format".to_string()),
+                value_name: Some("FMT".to_string()),
+                takes_value: true,
+                possible_values: vec!["plain".to_string(), "json".to_string()],
+                multiple: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let argv = vec!["--format".to_string(), "xml".to_string()];
+        let err = claplike::validate(&meta, &argv).unwrap_err();
+        match err {
+            claplike::ParseError::InvalidArgs(msg) => assert!(msg.contains("invalid value")),
+            other => panic!("expected InvalidArgs, got: {other:?}"),
+        }
+    }
+
+    fn bool_meta(default_value: Option<&str>) -> Meta {
+        Meta {
+            name: "cmd".to_string(),
+            args: vec![
+                ArgDef {
+                    name: "cache".to_string(),
+                    long: Some("--cache".to_string()),
+                    takes_value: true,
+                    value_type: Some("bool".to_string()),
+                    env: Some("CMD_CACHE".to_string()),
+                    default_value: default_value.map(str::to_string),
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn parse_bool_args<'a>(
+        meta: &Meta,
+        argv: &'a [String],
+        env: &[(&str, &str)],
+    ) -> Result<args::Matches<'a>, claplike::ParseError> {
+        let env: Vec<(String, String)> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        match claplike::parse_with_env(meta, argv, &env)? {
+            claplike::ParseOutcome::Matches(m) => Ok(m),
+            _ => panic!("expected Matches"),
+        }
+    }
+
+    #[test]
+    fn bool_values_are_normalized_from_every_spelling() {
+        let meta = bool_meta(None);
+        for (spelling, expected) in [
+            ("true", "true"),
+            ("TRUE", "true"),
+            ("1", "true"),
+            ("yes", "true"),
+            ("On", "true"),
+            ("false", "false"),
+            ("False", "false"),
+            ("0", "false"),
+            ("no", "false"),
+            ("OFF", "false"),
+        ] {
+            let argv = vec![format!("--cache={spelling}")];
+            let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+            assert_eq!(m.get("cache"), Some(expected), "{argv:?}");
+            assert_eq!(m.occurrences("cache")[0].value(), Some(expected));
+            assert_eq!(m.get_flag("cache"), expected == "true");
+        }
+    }
+
+    #[test]
+    fn bool_values_from_env_and_default_are_normalized() {
+        let meta = bool_meta(Some("off"));
+        let m = parse_bool_args(&meta, &[], &[("CMD_CACHE", "Yes")]).unwrap();
+        assert_eq!(m.get("cache"), Some("true"));
+        assert_eq!(m.occurrences("cache")[0].source(), args::ValueSource::Env);
+
+        let m = parse_bool_args(&meta, &[], &[]).unwrap();
+        assert_eq!(m.get("cache"), Some("false"));
+        assert!(!m.get_flag("cache"));
+
+        let err = parse_bool_args(&meta, &[], &[("CMD_CACHE", "maybe")]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "invalid value 'maybe' for '--cache'. possible values: true, false, 1, 0, yes, no, on, off"
+        );
+    }
+
+    #[test]
+    fn bool_values_reject_other_spellings() {
+        let meta = bool_meta(None);
+        for bad in ["y", "2", "enabled", ""] {
+            let argv = vec![format!("--cache={bad}")];
+            match parse_bool_args(&meta, &argv, &[]).unwrap_err() {
+                claplike::ParseError::InvalidArgs(msg) => assert_eq!(
+                    msg,
+                    format!(
+                        "invalid value '{bad}' for '--cache'. possible values: true, false, 1, 0, yes, no, on, off"
+                    )
+                ),
+                other => panic!("expected InvalidArgs, got: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn get_flag_reads_boolean_flags_and_values() {
+        let meta = bool_meta(None);
+        let argv = vec!["-v".to_string()];
+        let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+        assert!(m.get_flag("verbose"));
+        assert!(!m.get_flag("cache"));
+        assert!(!m.get_flag("unknown"));
+    }
+
+    #[test]
+    fn bool_flags_may_be_given_without_a_value() {
+        let mut meta = bool_meta(None);
+        meta.args[0].short = Some("-c".to_string());
+        meta.args.push(ArgDef {
+            name: "file".to_string(),
+            takes_value: true,
+            ..Default::default()
+        });
+        meta.args.push(ArgDef {
+            name: "output".to_string(),
+            short: Some("-o".to_string()),
+            takes_value: true,
+            ..Default::default()
+        });
+        for (argv, cache, file, spelled) in [
+            (
+                &["--cache", "a.txt"][..],
+                "true",
+                Some("a.txt"),
+                &["--cache=true", "a.txt"][..],
+            ),
+            (
+                &["a.txt", "--cache"],
+                "true",
+                Some("a.txt"),
+                &["a.txt", "--cache=true"],
+            ),
+            (
+                &["-c", "--", "x"],
+                "true",
+                Some("x"),
+                &["-c=true", "--", "x"],
+            ),
+            (&["-vc"], "true", None, &["-vc=true"]),
+            // A value that spells a boolean is still a separate token.
+            (&["--cache", "1"], "true", Some("1"), &["--cache=true", "1"]),
+            (&["-vc", "no"], "true", Some("no"), &["-vc=true", "no"]),
+            (&["-vcno"], "false", None, &["-vcno"]),
+            // Flags after a bare bool in a bundle are read on, in any order.
+            (&["-cv"], "true", None, &["-c=true", "-v"]),
+            (&["-co", "x"], "true", None, &["-c=true", "-o", "x"]),
+            (&["-cvo", "x"], "true", None, &["-c=true", "-vo", "x"]),
+            // ...unless the rest of the bundle spells a bool.
+            (&["-coff"], "false", None, &["-coff"]),
+            (&["--cache=0"], "false", None, &["--cache=0"]),
+        ] {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+            assert_eq!(m.get("cache"), Some(cache), "{argv:?}");
+            assert_eq!(m.get("file"), file, "{argv:?}");
+            assert_eq!(
+                m.occurrences("cache")[0].flag_index(),
+                argv.iter().position(|a| a.starts_with('-'))
+            );
+            assert_eq!(
+                claplike::spell_out_bool_flags(&argv, &m),
+                spelled,
+                "{argv:?}"
+            );
+
+            // The spelled-out argv reads the same without the value type.
+            let mut untyped = meta.clone();
+            untyped.args[0].value_type = None;
+            let spelled: Vec<String> = spelled.iter().map(|s| s.to_string()).collect();
+            let m = parse_bool_args(&untyped, &spelled, &[]).unwrap();
+            assert_eq!(m.get_flag("cache"), cache == "true", "{spelled:?}");
+            assert_eq!(m.get("file"), file, "{spelled:?}");
+        }
+        for argv in [&["-cv"][..], &["-vc"]] {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+            assert!(m.get_flag("verbose"), "{argv:?}");
+        }
+        let argv = vec!["-co".to_string(), "x".to_string()];
+        let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+        assert_eq!(m.get("output"), Some("x"));
+        let argv = vec!["-c=x".to_string()];
+        assert!(parse_bool_args(&meta, &argv, &[]).is_err());
+
+        let help = claplike::help(&meta);
+        assert!(help.contains("  -c, --cache[=<BOOL>]\n"), "{help}");
+        meta.args[0].value_name = Some("ON".to_string());
+        meta.args[0].required = true;
+        let err = claplike::validate(&meta, &[]).unwrap_err();
+        assert_eq!(err.message(), "missing required argument: --cache[=<ON>]");
+    }
+
+    #[test]
+    fn get_bool_tells_unset_from_false() {
+        let meta = bool_meta(None);
+        let m = parse_bool_args(&meta, &[], &[]).unwrap();
+        assert_eq!(m.get_bool("cache"), None);
+        assert_eq!(m.get_bool("verbose"), None);
+        assert!(!m.get_flag("cache"));
+
+        let argv = vec!["--cache=false".to_string(), "-v".to_string()];
+        let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+        assert_eq!(m.get_bool("cache"), Some(false));
+        assert_eq!(m.get_bool("verbose"), Some(true));
+
+        let m = parse_bool_args(&meta, &[], &[("CMD_CACHE", "on")]).unwrap();
+        assert_eq!(m.get_bool("cache"), Some(true));
+        let meta = bool_meta(Some("no"));
+        let m = parse_bool_args(&meta, &[], &[]).unwrap();
+        assert_eq!(m.get_bool("cache"), Some(false));
+    }
+
+    #[test]
+    fn bool_value_type_rejects_possible_values() {
+        let mut meta = bool_meta(None);
+        meta.args[0].possible_values = vec!["true".to_string(), "false".to_string()];
+        match claplike::validate(&meta, &[]).unwrap_err() {
+            claplike::ParseError::Failed(msg) => assert_eq!(
+                msg,
+                "schema error: 'cache' has value-type bool and cannot declare possible-values"
+            ),
+            other => panic!("expected Failed, got: {other:?}"),
+        }
+    }
+
+    fn typed_meta() -> Meta {
+        let typed = |name: &str, value_type: &str| ArgDef {
+            name: name.to_string(),
+            long: Some(format!("--{name}")),
+            takes_value: true,
+            multiple: true,
+            value_type: Some(value_type.to_string()),
+            ..Default::default()
+        };
+        Meta {
+            name: "cmd".to_string(),
+            args: vec![
+                typed("offset", "int"),
+                typed("count", "uint"),
+                typed("ratio", "float"),
+                typed("cache", "bool"),
+                typed("out", "path"),
+                ArgDef {
+                    default_value: Some("ten".to_string()),
+                    ..typed("limit", "int")
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn value_types_are_checked_for_every_value() {
+        let mut meta = typed_meta();
+        meta.args.pop();
+        let argv: Vec<String> = [
+            "--offset",
+            "-5",
+            "--offset=7",
+            "--count",
+            "3",
+            "--ratio",
+            "-0.25",
+            "--cache",
+            "yes",
+            "--out",
+            "a.txt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let m = parse_bool_args(&meta, &argv, &[]).unwrap();
+        assert_eq!(m.get_parsed::<i64>("offset").unwrap(), Some(7));
+        assert_eq!(m.get_all_parsed::<i64>("offset").unwrap(), [-5, 7]);
+        assert_eq!(m.get_parsed::<u64>("count").unwrap(), Some(3));
+        assert_eq!(m.get_parsed::<f64>("ratio").unwrap(), Some(-0.25));
+        assert_eq!(m.get_parsed::<bool>("cache").unwrap(), Some(true));
+        assert_eq!(m.get_parsed::<u8>("missing").unwrap(), None);
+        match m.get_parsed::<u8>("ratio").unwrap_err() {
+            claplike::ParseError::InvalidArgs(msg) => assert_eq!(
+                msg,
+                "invalid value '-0.25' for 'ratio': invalid digit found in string"
+            ),
+            other => panic!("expected InvalidArgs, got: {other:?}"),
+        }
+
+        for (argv, msg) in [
+            (
+                &["--offset", "1", "--offset", "abc"][..],
+                "invalid value 'abc' for '--offset': expected integer",
+            ),
+            (
+                &["--count", "-1"],
+                "invalid value '-1' for '--count': expected non-negative integer",
+            ),
+            (
+                &["--ratio", "NaN"],
+                "invalid value 'NaN' for '--ratio': expected number",
+            ),
+            (&["--out="], "invalid value '' for '--out': expected path"),
+        ] {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            match parse_bool_args(&meta, &argv, &[]).unwrap_err() {
+                claplike::ParseError::InvalidArgs(got) => assert_eq!(got, msg),
+                other => panic!("expected InvalidArgs, got: {other:?}"),
+            }
+        }
+
+        // Defaults go through the same check.
+        let err = parse_bool_args(&typed_meta(), &[], &[]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "invalid value 'ten' for '--limit': expected integer"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_conflicts_with() {
+        let meta = Meta {
+            name: "cmd".to_string(),
+            args: vec![
+                ArgDef {
+                    name: "a".to_string(),
+                    long: Some("--a".to_string()),
+                    takes_value: false,
+                    conflicts_with: vec!["b".to_string()],
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "b".to_string(),
+                    long: Some("--b".to_string()),
+                    takes_value: false,
                     ..Default::default()
                 },
             ],
@@ -1575,7 +4730,9 @@ mod tests {
         let argv = vec!["--a".to_string(), "--b".to_string()];
         let err = claplike::validate(&meta, &argv).unwrap_err();
         match err {
-            claplike::ParseError::InvalidArgs(msg) => assert!(msg.contains("cannot be used with")),
+            claplike::ParseError::InvalidArgs(msg) => {
+                assert!(msg.contains("cannot be used with"))
+            }
             other => panic!("expected InvalidArgs, got: {other:?}"),
         }
     }
@@ -1609,6 +4766,407 @@ mod tests {
         }
     }
 
+    fn format_meta(required: bool, multiple: bool) -> Meta {
+        let flag = |name: &str| ArgDef {
+            name: name.to_string(),
+            long: Some(format!("--{name}")),
+            group: Some("format".to_string()),
+            ..Default::default()
+        };
+        Meta {
+            name: "cmd".to_string(),
+            args: vec![flag("json"), flag("yaml"), flag("toml")],
+            groups: vec![Group {
+                name: "format".to_string(),
+                required,
+                multiple,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn invalid_args(meta: &Meta, argv: &[&str]) -> String {
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        match claplike::validate(meta, &argv).unwrap_err() {
+            claplike::ParseError::InvalidArgs(msg) => msg,
+            other => panic!("expected InvalidArgs, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_enforces_required_groups() {
+        let meta = format_meta(true, false);
+        let msg = invalid_args(&meta, &[]);
+        assert_eq!(
+            msg,
+            "missing required argument: one of --json, --yaml, --toml"
+        );
+        assert!(claplike::messages::is_missing_required(&msg));
+        claplike::validate(&meta, &["--yaml".to_string()]).unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_two_members_of_a_single_group() {
+        let meta = format_meta(false, false);
+        claplike::validate(&meta, &[]).unwrap();
+        assert_eq!(
+            invalid_args(&meta, &["--toml", "--json"]),
+            "argument '--json' cannot be used with '--toml'"
+        );
+
+        let multiple = format_meta(true, true);
+        claplike::validate(&multiple, &["--toml".to_string(), "--json".to_string()]).unwrap();
+    }
+
+    #[test]
+    fn groups_only_count_args_given_in_argv() {
+        let mut meta = format_meta(true, false);
+        meta.args[0].takes_value = true;
+        meta.args[0].env = Some("CMD_JSON".to_string());
+        meta.args[1].takes_value = true;
+        meta.args[1].default_value = Some("x".to_string());
+        let env = vec![("CMD_JSON".to_string(), "1".to_string())];
+        let err = claplike::validate_with_env(&meta, &[], &env).unwrap_err();
+        assert!(claplike::messages::is_missing_required(err.message()));
+        claplike::validate_with_env(&meta, &["--toml".to_string()], &env).unwrap();
+    }
+
+    #[test]
+    fn undeclared_groups_allow_at_most_one_member() {
+        let mut meta = format_meta(false, false);
+        meta.groups.clear();
+        claplike::validate(&meta, &["--json".to_string()]).unwrap();
+        assert_eq!(
+            invalid_args(&meta, &["--json", "--yaml"]),
+            "argument '--yaml' cannot be used with '--json'"
+        );
+    }
+
+    #[test]
+    fn schema_errors_on_empty_or_duplicate_groups() {
+        let mut meta = format_meta(false, false);
+        meta.groups.push(Group {
+            name: "output".to_string(),
+            ..Default::default()
+        });
+        let err = claplike::validate(&meta, &[]).unwrap_err();
+        assert_eq!(err.message(), "schema error: group 'output' has no args");
+
+        let mut meta = format_meta(false, false);
+        meta.groups.push(meta.groups[0].clone());
+        let err = claplike::validate(&meta, &[]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "schema error: group 'format' is declared more than once"
+        );
+    }
+
+    /// `deploy` with a `--message` value option, a `rollback` subcommand
+    /// taking `--to`, and the global options `--verbose`/`-v` and
+    /// `--color`/`-c <WHEN>`.
+    fn global_meta() -> Meta {
+        Meta {
+            name: "deploy".to_string(),
+            args: vec![ArgDef {
+                name: "message".to_string(),
+                short: Some("-m".to_string()),
+                long: Some("--message".to_string()),
+                takes_value: true,
+                ..Default::default()
+            }],
+            global_args: vec![
+                ArgDef {
+                    name: "verbose".to_string(),
+                    short: Some("-v".to_string()),
+                    long: Some("--verbose".to_string()),
+                    help: "Print more".to_string(),
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "color".to_string(),
+                    short: Some("-c".to_string()),
+                    long: Some("--color".to_string()),
+                    value_name: Some("WHEN".to_string()),
+                    takes_value: true,
+                    default_value: Some("auto".to_string()),
+                    ..Default::default()
+                },
+            ],
+            subcommands: vec![Meta {
+                name: "rollback".to_string(),
+                args: vec![ArgDef {
+                    name: "to".to_string(),
+                    long: Some("--to".to_string()),
+                    takes_value: true,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn split(meta: &Meta, argv: &[&str]) -> (Vec<String>, Vec<String>) {
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        claplike::split_global_args(meta, &argv)
+    }
+
+    #[test]
+    fn global_args_are_split_from_command_args() {
+        let meta = global_meta();
+        assert_eq!(
+            split(
+                &meta,
+                &[
+                    "-v",
+                    "app",
+                    "--color",
+                    "never",
+                    "-m",
+                    "x",
+                    "--color=always",
+                    "-cnever"
+                ]
+            ),
+            (
+                vec![
+                    "-v".to_string(),
+                    "--color".to_string(),
+                    "never".to_string(),
+                    "--color=always".to_string(),
+                    "-cnever".to_string(),
+                ],
+                vec!["app".to_string(), "-m".to_string(), "x".to_string()],
+            )
+        );
+
+        let argv: Vec<String> = ["app", "--color", "never", "--", "-v"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            claplike::split_global_arg_indices(&meta, &argv),
+            (vec![1, 2], vec![0, 3, 4])
+        );
+    }
+
+    #[test]
+    fn boolean_options_never_take_the_next_token() {
+        let bool_arg = |name: &str| ArgDef {
+            name: name.to_string(),
+            long: Some(format!("--{name}")),
+            takes_value: true,
+            value_type: Some("bool".to_string()),
+            ..Default::default()
+        };
+        let mut meta = global_meta();
+        meta.args.push(bool_arg("cache"));
+        meta.global_args.push(ArgDef {
+            short: Some("-d".to_string()),
+            ..bool_arg("dry-run")
+        });
+
+        assert_eq!(
+            split(&meta, &["--dry-run", "app", "--cache", "--verbose"]),
+            (
+                vec!["--dry-run".to_string(), "--verbose".to_string()],
+                vec!["app".to_string(), "--cache".to_string()],
+            )
+        );
+        assert_eq!(
+            split(&meta, &["--dry-run", "off", "--cache", "no", "app"]),
+            (
+                vec!["--dry-run".to_string()],
+                vec![
+                    "off".to_string(),
+                    "--cache".to_string(),
+                    "no".to_string(),
+                    "app".to_string(),
+                ],
+            )
+        );
+        assert_eq!(
+            split(&meta, &["--dry-run=off", "app", "--cache=no"]),
+            (
+                vec!["--dry-run=off".to_string()],
+                vec!["app".to_string(), "--cache=no".to_string()],
+            )
+        );
+        assert_eq!(
+            split(&meta, &["-doff", "app", "-dm", "x"]),
+            (
+                vec!["-doff".to_string()],
+                vec!["app".to_string(), "-dm".to_string(), "x".to_string()],
+            )
+        );
+    }
+
+    #[test]
+    fn global_args_never_take_command_values_or_escaped_tokens() {
+        let meta = global_meta();
+        let (global, rest) = split(
+            &meta,
+            &[
+                "--message",
+                "--verbose",
+                "rollback",
+                "--to",
+                "-v",
+                "--",
+                "--verbose",
+            ],
+        );
+        assert!(global.is_empty(), "{global:?}");
+        assert_eq!(
+            rest,
+            [
+                "--message",
+                "--verbose",
+                "rollback",
+                "--to",
+                "-v",
+                "--",
+                "--verbose"
+            ]
+        );
+
+        // Bundled shorts stay with the command; a flag with an attached value
+        // is still global (the parser reports the stray value).
+        let (global, rest) = split(&meta, &["-vm", "x", "--verbose=1"]);
+        assert_eq!(global, ["--verbose=1"]);
+        assert_eq!(rest, ["-vm", "x"]);
+
+        let plain = Meta::default();
+        assert_eq!(split(&plain, &["--verbose"]).1, ["--verbose"]);
+    }
+
+    #[test]
+    fn help_lists_global_options_after_command_options() {
+        let mut meta = global_meta();
+        meta.global_args.push(ArgDef {
+            name: "trace".to_string(),
+            long: Some("--trace".to_string()),
+            hidden: true,
+            ..Default::default()
+        });
+        let help = claplike::help(&meta);
+        let tail = help.split_once("\nGlobal options:\n").expect(&help).1;
+        assert_eq!(
+            tail,
+            "  -v, --verbose       Print more\n  -c, --color <WHEN>  [default: auto]\n"
+        );
+        assert!(help.find("Options:").unwrap() < help.find("Global options:").unwrap());
+        assert!(!claplike::help(&Meta::default()).contains("Global options:"));
+    }
+
+    /// `text` without its ANSI escape sequences.
+    fn strip_ansi(text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(at) = rest.find('\x1b') {
+            out.push_str(&rest[..at]);
+            let end = rest[at..].find('m').expect(text);
+            rest = &rest[at + end + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    #[test]
+    fn styled_help_colors_headers_flags_and_required_markers() {
+        let mut meta = subcommand_meta();
+        meta.args.push(ArgDef {
+            name: "remote-url".to_string(),
+            long: Some("--remote-url".to_string()),
+            help: "Remote URL".to_string(),
+            takes_value: true,
+            required: true,
+            ..Default::default()
+        });
+        let style = claplike::HelpStyle::colored();
+        let help = claplike::help_styled(&meta, &style);
+        assert!(help.contains("\n\x1b[1mUsage:\x1b[0m remote\n"));
+        assert!(help.contains("\n\x1b[1mOptions:\x1b[0m\n"));
+        assert!(help.contains("  \x1b[36madd\x1b[0m   Add a remote\n"));
+        assert!(help.contains(
+            "  \x1b[36m--remote-url\x1b[0m <REMOTE-URL>  Remote URL \x1b[33m(required)\x1b[0m\n"
+        ));
+        // Escape codes do not count towards the column widths.
+        assert_eq!(strip_ansi(&help), claplike::help(&meta));
+        assert_eq!(
+            claplike::help_styled(&meta, &claplike::HelpStyle::plain()),
+            claplike::help(&meta)
+        );
+    }
+
+    #[test]
+    fn display_width_skips_escape_sequences() {
+        assert_eq!(claplike::display_width("--force"), 7);
+        assert_eq!(claplike::display_width("\x1b[1;36m-f\x1b[0m, é"), 5);
+        assert_eq!(claplike::paint("", "x"), "x");
+        assert_eq!(claplike::paint("36", "x"), "\x1b[36mx\x1b[0m");
+    }
+
+    #[test]
+    fn parse_renders_subcommand_help_in_the_root_style() {
+        let mut meta = subcommand_meta();
+        meta.help_style = claplike::HelpStyle::colored();
+        let argv: Vec<String> = vec!["add".to_string(), "--help".to_string()];
+        let claplike::ParseOutcome::Help(text) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected help");
+        };
+        assert!(text.contains("\x1b[36m--force\x1b[0m"));
+        assert_eq!(
+            strip_ansi(&text),
+            claplike::help_styled(&meta.subcommands[0], &claplike::HelpStyle::plain()).replacen(
+                "add",
+                "remote add",
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn global_arg_env_vars_are_upper_snake_case() {
+        assert_eq!(
+            claplike::global_arg_env_var("verbose"),
+            "WACLI_GLOBAL_VERBOSE"
+        );
+        assert_eq!(
+            claplike::global_arg_env_var("dry-run"),
+            "WACLI_GLOBAL_DRY_RUN"
+        );
+        assert_eq!(
+            claplike::config_env_var("log.level"),
+            "WACLI_CONFIG_LOG_LEVEL"
+        );
+    }
+
+    #[test]
+    fn help_annotates_group_members() {
+        let mut meta = format_meta(true, false);
+        meta.args[0].help = "Print JSON".to_string();
+        meta.args[2].hidden = true;
+        let help = claplike::help(&meta);
+        assert!(
+            help.contains("  --json         Print JSON (exactly one of: --json, --yaml)\n"),
+            "{help}"
+        );
+        assert!(
+            help.contains("  --yaml         (exactly one of: --json, --yaml)\n"),
+            "{help}"
+        );
+
+        for (required, multiple, note) in [
+            (false, false, "(one of: "),
+            (true, true, "(at least one of: "),
+        ] {
+            let help = claplike::help(&format_meta(required, multiple));
+            assert!(help.contains(note), "{help}");
+        }
+        assert!(!claplike::help(&format_meta(false, true)).contains("of: "));
+    }
+
     #[test]
     fn validate_rejects_multiple_when_disabled() {
         let meta = Meta {
@@ -1664,4 +5222,390 @@ mod tests {
         assert!(text.contains("--visible"));
         assert!(!text.contains("--secret"));
     }
+
+    fn arg_file_meta() -> Meta {
+        Meta {
+            name: "tag".to_string(),
+            allow_arg_files: true,
+            args: vec![ArgDef {
+                name: "id".to_string(),
+                long: Some("--id".to_string()),
+                takes_value: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn expand_with(
+        meta: &Meta,
+        argv: &[&str],
+        files: &[(&str, &str)],
+    ) -> claplike::ParseResult<Vec<String>> {
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        let files: std::collections::HashMap<&str, &str> = files.iter().copied().collect();
+        claplike::expand_arg_files(meta, &argv, |path| {
+            files
+                .get(path)
+                .map(|s| s.to_string())
+                .ok_or_else(|| "not found".to_string())
+        })
+    }
+
+    #[test]
+    fn expand_arg_files_splices_tokens_in_place() {
+        let meta = arg_file_meta();
+        let out = expand_with(
+            &meta,
+            &["first", "@ids.txt", "last"],
+            &[("ids.txt", "# ids to tag\n--id\n1\n\n  --id=2  \n")],
+        )
+        .unwrap();
+        assert_eq!(out, vec!["first", "--id", "1", "--id=2", "last"]);
+
+        let argv: Vec<String> = ["first", "@ids.txt", "last"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (_, origins) =
+            claplike::expand_arg_files_with_origins(&meta, &argv, |_| Ok("--id\n1\n".to_string()))
+                .unwrap();
+        assert_eq!(origins, vec![0, 1, 1, 2]);
+
+        // A value position works too: `--id @one.txt` splices the value in place.
+        let out = expand_with(&meta, &["--id", "@one.txt"], &[("one.txt", "7\n")]).unwrap();
+        assert_eq!(out, vec!["--id", "7"]);
+
+        let argv: Vec<String> = out;
+        let claplike::ParseOutcome::Matches(m) = claplike::parse(&meta, &argv).unwrap() else {
+            panic!("expected matches");
+        };
+        assert_eq!(m.get("id"), Some("7"));
+    }
+
+    #[test]
+    fn expand_arg_files_is_opt_in() {
+        let meta = Meta {
+            allow_arg_files: false,
+            ..arg_file_meta()
+        };
+        let out = expand_with(&meta, &["@ids.txt"], &[("ids.txt", "--id\n1\n")]).unwrap();
+        assert_eq!(out, vec!["@ids.txt"]);
+    }
+
+    #[test]
+    fn expand_arg_files_leaves_literal_tokens_alone() {
+        let meta = arg_file_meta();
+        let out = expand_with(
+            &meta,
+            &["@", "--id=@x", "--", "@ids.txt"],
+            &[("ids.txt", "1\n")],
+        )
+        .unwrap();
+        assert_eq!(out, vec!["@", "--id=@x", "--", "@ids.txt"]);
+
+        // A `--` inside an arg file ends expansion for everything after it.
+        let out = expand_with(
+            &meta,
+            &["@a.txt", "@b.txt"],
+            &[("a.txt", "--\n@b.txt\n"), ("b.txt", "1\n")],
+        )
+        .unwrap();
+        assert_eq!(out, vec!["--", "@b.txt", "@b.txt"]);
+    }
+
+    #[test]
+    fn expand_arg_files_supports_nesting_up_to_the_limit() {
+        let meta = arg_file_meta();
+        let out = expand_with(
+            &meta,
+            &["@outer.txt"],
+            &[
+                ("outer.txt", "--id\n1\n@inner.txt\n"),
+                ("inner.txt", "--id\n2\n"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(out, vec!["--id", "1", "--id", "2"]);
+
+        let err = expand_with(
+            &meta,
+            &["@a.txt"],
+            &[
+                ("a.txt", "@b.txt\n"),
+                ("b.txt", "# deeper\n@c.txt\n"),
+                ("c.txt", "1\n"),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "b.txt:2: arg file c.txt is nested more than 2 levels deep"
+        );
+    }
+
+    #[test]
+    fn expand_arg_files_rejects_cycles() {
+        let meta = arg_file_meta();
+        let err = expand_with(&meta, &["@a.txt"], &[("a.txt", "@a.txt\n")]).unwrap_err();
+        assert_eq!(err.message(), "a.txt:1: arg file cycle: a.txt -> a.txt");
+
+        let err = expand_with(
+            &meta,
+            &["@a.txt"],
+            &[("a.txt", "--id\n1\n@b.txt\n"), ("b.txt", "@a.txt\n")],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.message(),
+            "b.txt:1: arg file cycle: a.txt -> b.txt -> a.txt"
+        );
+    }
+
+    #[test]
+    fn expand_arg_files_reports_read_errors() {
+        let meta = arg_file_meta();
+        let err = expand_with(&meta, &["@missing.txt"], &[]).unwrap_err();
+        assert!(matches!(err, claplike::ParseError::InvalidArgs(..)));
+        assert_eq!(
+            err.message(),
+            "failed to read arg file missing.txt: not found"
+        );
+
+        let err = expand_with(&meta, &["@a.txt"], &[("a.txt", "\n@missing.txt\n")]).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "a.txt:2: failed to read arg file missing.txt: not found"
+        );
+    }
+
+    fn completion_commands() -> Vec<Meta> {
+        let export = Meta {
+            name: "export".to_string(),
+            summary: "Export data".to_string(),
+            aliases: vec!["ex".to_string()],
+            args: vec![
+                ArgDef {
+                    name: "format".to_string(),
+                    short: Some("-f".to_string()),
+                    long: Some("--format".to_string()),
+                    takes_value: true,
+                    possible_values: vec!["json".to_string(), "csv".to_string()],
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "out".to_string(),
+                    long: Some("--out".to_string()),
+                    value_name: Some("FILE".to_string()),
+                    takes_value: true,
+                    ..Default::default()
+                },
+                ArgDef {
+                    name: "debug".to_string(),
+                    long: Some("--debug-dump".to_string()),
+                    hidden: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let seed = Meta {
+            name: "db seed".to_string(),
+            summary: "Seed the database".to_string(),
+            ..Default::default()
+        };
+        let secret = Meta {
+            name: "secret".to_string(),
+            hidden: true,
+            ..Default::default()
+        };
+        vec![export, seed, secret, subcommand_meta()]
+    }
+
+    /// Source a bash script, complete `line` (the last word is the one being
+    /// completed) and return the candidates. `None` without bash.
+    fn bash_complete(script: &str, line: &[&str]) -> Option<Vec<String>> {
+        let words: Vec<String> = line.iter().map(|w| format!("'{w}'")).collect();
+        let program = format!(
+            "{script}\nCOMP_WORDS=({}); COMP_CWORD={}; _demo_cli; printf '%s\\n' \"${{COMPREPLY[@]}}\"",
+            words.join(" "),
+            line.len() - 1
+        );
+        let out = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(program)
+            .output()
+            .ok()?;
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        Some(
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn bash_completions_cover_commands_aliases_and_values() {
+        use claplike::completions::{self, Shell};
+
+        let script = completions::render(Shell::Bash, "demo-cli", &completion_commands());
+        assert!(script.ends_with("complete -o default -F _demo_cli demo-cli\n"));
+        assert!(!script.contains("secret"));
+        assert!(!script.contains("--debug-dump"));
+        assert!(!script.contains("debug'"), "hidden subcommand leaked");
+
+        let Some(top) = bash_complete(&script, &["demo-cli", "e"]) else {
+            return;
+        };
+        assert_eq!(top, ["export", "ex"]);
+        let cases: [(&[&str], &[&str]); 6] = [
+            (&["demo-cli", "ex", "--format", ""], &["json", "csv"]),
+            (&["demo-cli", "export", "-f", "j"], &["json"]),
+            (
+                &["demo-cli", "ex", "--"],
+                &["--format", "--out", "--help", "--version"],
+            ),
+            (&["demo-cli", "db", ""], &["seed", "--help", "-h"]),
+            (&["demo-cli", "remote", "a"], &["add", "a"]),
+            (&["demo-cli", "remote", "a", "--f"], &["--force"]),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(bash_complete(&script, line).unwrap(), expected, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn zsh_and_fish_completions_list_the_same_words() {
+        use claplike::completions::{self, Shell};
+
+        let zsh = completions::render(Shell::Zsh, "demo-cli", &completion_commands());
+        assert!(zsh.starts_with("#compdef demo-cli\n"));
+        assert!(zsh.contains("            ':export'|':ex') cmd='export' ;;\n"));
+        assert!(zsh.contains("'export:--format'|'export:-f') compadd -- 'json' 'csv'; return ;;"));
+        assert!(zsh.contains("'export:--out') _files; return ;;"));
+        assert!(!zsh.contains("secret"));
+
+        let fish = completions::render(Shell::Fish, "demo-cli", &completion_commands());
+        assert!(fish.contains(
+            "complete -c 'demo-cli' -n '__demo_cli_at \\'\\'' -f -a 'ex' -d 'Export data'\n"
+        ));
+        assert!(fish.contains(" -l 'format' -s 'f' -r -f -a 'json csv'"));
+        assert!(fish.contains(" -l 'out' -r -F"));
+        assert!(!fish.contains("secret"));
+
+        assert_eq!("zsh".parse::<completions::Shell>(), Ok(Shell::Zsh));
+        assert_eq!(
+            "tcsh".parse::<completions::Shell>().unwrap_err(),
+            "invalid value 'tcsh' for '<SHELL>'. possible values: bash, zsh, fish"
+        );
+    }
+
+    #[test]
+    fn pipe_specs_split_name_and_options() {
+        use super::pipe_spec::{PipeSpec, parse};
+
+        let spec = |name, options: &[&str]| PipeSpec {
+            name,
+            options: options.iter().map(|o| o.to_string()).collect(),
+        };
+        let cases: &[(&str, PipeSpec<'_>)] = &[
+            ("table", spec("table", &[])),
+            ("format/table", spec("format/table", &[])),
+            ("table?", spec("table", &[])),
+            ("table?,,", spec("table", &[])),
+            (
+                "format/table?width=80,header",
+                spec("format/table", &["width=80", "header"]),
+            ),
+            ("json?pretty", spec("json", &["pretty"])),
+            (
+                "csv?sep=;,quote=\",path=/tmp/a b?c,tz=+09:00",
+                spec(
+                    "csv",
+                    &["sep=;", "quote=\"", "path=/tmp/a b?c", "tz=+09:00"],
+                ),
+            ),
+            ("t?pct=%20", spec("t", &["pct=%20"])),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input).as_ref(), Ok(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn pipe_specs_reject_misplaced_question_marks() {
+        use super::pipe_spec::parse;
+
+        assert_eq!(
+            parse("format?x/table").unwrap_err(),
+            "pipe spec 'format?x/table' has '?' inside a path segment; \
+             options must follow the full pipe name"
+        );
+        assert_eq!(
+            parse("?width=80").unwrap_err(),
+            "pipe spec '?width=80' has no pipe name"
+        );
+        assert_eq!(
+            parse("table?=80").unwrap_err(),
+            "pipe spec 'table?=80' has an option without a name: '=80'"
+        );
+    }
+
+    #[test]
+    fn pipe_options_read_values_flags_and_duplicates() {
+        use super::pipe_options::Options;
+
+        let raw: Vec<String> = [
+            "width=80",
+            "header",
+            "",
+            "expr=a=b",
+            "--width=120",
+            "tag=x",
+            "tag=y",
+            "empty=",
+        ]
+        .iter()
+        .map(|o| o.to_string())
+        .collect();
+        let opts = Options::new(&raw);
+        assert_eq!(opts.get("width"), Some("120"));
+        assert_eq!(opts.get_all("width"), ["80", "120"]);
+        assert_eq!(opts.get("expr"), Some("a=b"));
+        assert_eq!(opts.get("empty"), Some(""));
+        assert_eq!(opts.get("header"), None);
+        assert!(opts.has("header") && opts.has("width") && !opts.has("footer"));
+        assert_eq!(opts.get_all("tag"), ["x", "y"]);
+        assert_eq!(opts.get_parsed::<u32>("width"), Ok(Some(120)));
+        assert_eq!(opts.get_parsed::<u32>("footer"), Ok(None));
+        assert_eq!(
+            opts.get_parsed::<u32>("tag").unwrap_err(),
+            "invalid value 'y' for 'tag': invalid digit found in string"
+        );
+        assert_eq!(opts.keys(), ["width", "header", "expr", "tag", "empty"]);
+    }
+
+    #[test]
+    fn pipe_options_report_unknown_keys() {
+        use super::pipe_options::Options;
+
+        let raw: Vec<String> = ["case=upper", "colour=red", "trim", "colour=blue", "sz"]
+            .iter()
+            .map(|o| o.to_string())
+            .collect();
+        let opts = Options::new(&raw);
+        assert_eq!(opts.unknown_keys(&["case", "trim"]), ["colour", "sz"]);
+        assert_eq!(
+            opts.check_known(&["case", "trim"]).unwrap_err(),
+            "unknown option 'colour'. valid options: case, trim"
+        );
+        opts.check_known(&["case", "trim", "colour", "sz"]).unwrap();
+        Options::new(&[]).check_known(&[]).unwrap();
+    }
 }
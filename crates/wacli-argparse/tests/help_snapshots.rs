@@ -0,0 +1,590 @@
+//! Golden-file tests for everything `claplike` renders: `help()`, `version()`
+//! and the error messages returned by `parse()`.
+//!
+//! Each fixture below is rendered into `tests/snapshots/<name>.txt`. When the
+//! output changes on purpose, regenerate the files with:
+//!
+//! ```sh
+//! REGENERATE=1 cargo test -p wacli-argparse --test help_snapshots
+//! ```
+//!
+//! The CDK reads the same files to check that its renderer produces identical
+//! output, so the section layout (`--- help`, `--- version`, `--- error: ...`,
+//! `--- output: ...`) is part of the contract.
+
+use std::path::PathBuf;
+
+use wacli_argparse::claplike::{self, ArgDefLike, ArgGroupLike, CommandMetaLike, ParseOutcome};
+
+#[derive(Debug, Clone, Default)]
+struct Arg {
+    name: String,
+    short: Option<String>,
+    long: Option<String>,
+    help: String,
+    required: bool,
+    default_value: Option<String>,
+    env: Option<String>,
+    value_name: Option<String>,
+    takes_value: bool,
+    possible_values: Vec<String>,
+    hidden: bool,
+    group: Option<String>,
+}
+
+impl Arg {
+    fn positional(name: &str, value_name: &str, help: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            help: help.to_string(),
+            value_name: Some(value_name.to_string()),
+            takes_value: true,
+            ..Default::default()
+        }
+    }
+
+    fn flag(name: &str, short: Option<&str>, long: &str, help: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            short: short.map(str::to_string),
+            long: Some(long.to_string()),
+            help: help.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn option(name: &str, short: Option<&str>, long: &str, value_name: &str, help: &str) -> Self {
+        Self {
+            value_name: Some(value_name.to_string()),
+            takes_value: true,
+            ..Self::flag(name, short, long, help)
+        }
+    }
+
+    fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    fn default_value(mut self, value: &str) -> Self {
+        self.default_value = Some(value.to_string());
+        self
+    }
+
+    fn env(mut self, key: &str) -> Self {
+        self.env = Some(key.to_string());
+        self
+    }
+
+    fn possible_values(mut self, values: &[&str]) -> Self {
+        self.possible_values = values.iter().map(|v| v.to_string()).collect();
+        self
+    }
+
+    fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+}
+
+impl ArgDefLike for Arg {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn short(&self) -> Option<&str> {
+        self.short.as_deref()
+    }
+    fn long(&self) -> Option<&str> {
+        self.long.as_deref()
+    }
+    fn help(&self) -> &str {
+        &self.help
+    }
+    fn required(&self) -> bool {
+        self.required
+    }
+    fn default_value(&self) -> Option<&str> {
+        self.default_value.as_deref()
+    }
+    fn env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+    fn value_name(&self) -> Option<&str> {
+        self.value_name.as_deref()
+    }
+    fn takes_value(&self) -> bool {
+        self.takes_value
+    }
+    fn possible_values(&self) -> &[String] {
+        self.possible_values.as_slice()
+    }
+    fn hidden(&self) -> bool {
+        self.hidden
+    }
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Group {
+    name: String,
+    required: bool,
+    multiple: bool,
+}
+
+impl Group {
+    fn new(name: &str, required: bool, multiple: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            required,
+            multiple,
+        }
+    }
+}
+
+impl ArgGroupLike for Group {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn required(&self) -> bool {
+        self.required
+    }
+    fn multiple(&self) -> bool {
+        self.multiple
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Meta {
+    name: String,
+    summary: String,
+    usage: String,
+    aliases: Vec<String>,
+    version: String,
+    hidden: bool,
+    description: String,
+    examples: Vec<String>,
+    args: Vec<Arg>,
+    groups: Vec<Group>,
+    subcommands: Vec<Meta>,
+}
+
+impl CommandMetaLike for Meta {
+    type ArgDef = Arg;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn summary(&self) -> &str {
+        &self.summary
+    }
+    fn usage(&self) -> &str {
+        &self.usage
+    }
+    fn aliases(&self) -> &[String] {
+        self.aliases.as_slice()
+    }
+    fn version(&self) -> &str {
+        &self.version
+    }
+    fn hidden(&self) -> bool {
+        self.hidden
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn examples(&self) -> &[String] {
+        self.examples.as_slice()
+    }
+    fn args(&self) -> &[Arg] {
+        self.args.as_slice()
+    }
+    fn groups(&self) -> Vec<&dyn ArgGroupLike> {
+        self.groups.iter().map(|g| g as &dyn ArgGroupLike).collect()
+    }
+    fn subcommands(&self) -> &[Meta] {
+        self.subcommands.as_slice()
+    }
+}
+
+/// A schema plus the argv lines whose parse errors, and help or version
+/// output (`outputs`), are snapshotted.
+struct Fixture {
+    name: &'static str,
+    meta: Meta,
+    errors: &'static [&'static str],
+    outputs: &'static [&'static str],
+}
+
+fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture {
+            name: "positionals",
+            meta: Meta {
+                name: "cp".to_string(),
+                summary: "Copy a file".to_string(),
+                usage: "cp <SRC> <DEST> [MODE]".to_string(),
+                args: vec![
+                    Arg::positional("src", "SRC", "File to copy").required(),
+                    Arg::positional("dest", "DEST", "Where to write the copy").required(),
+                    Arg::positional("mode", "MODE", "Permissions for the copy")
+                        .default_value("0644"),
+                ],
+                ..Default::default()
+            },
+            errors: &["", "a.txt", "a.txt b.txt --force", "a.txt b.txt -x"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "options",
+            meta: Meta {
+                name: "fetch".to_string(),
+                summary: "Download a URL".to_string(),
+                version: "1.2.0".to_string(),
+                args: vec![
+                    Arg::flag("verbose", Some("-v"), "--verbose", "Print progress"),
+                    Arg::option("output", Some("-o"), "--output", "FILE", "Write to FILE")
+                        .required(),
+                    Arg::option("retries", None, "--retries", "N", "Retry count")
+                        .default_value("3"),
+                    Arg::flag("quiet", Some("-q"), "--quiet", ""),
+                ],
+                ..Default::default()
+            },
+            errors: &["", "--output", "-o out.txt --verbose=yes", "-o out.txt -vz"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "mixed",
+            meta: Meta {
+                name: "show".to_string(),
+                summary: "Show a file".to_string(),
+                usage: "show [OPTIONS] <FILE>".to_string(),
+                version: "0.3.1".to_string(),
+                description: "Display a file to stdout.".to_string(),
+                examples: vec![
+                    "show hello.txt".to_string(),
+                    "show --lines 10 hello.txt".to_string(),
+                ],
+                args: vec![
+                    Arg::positional("file", "FILE", "File to show").required(),
+                    Arg::option("format", None, "--format", "FORMAT", "Output format")
+                        .default_value("text"),
+                    Arg::option(
+                        "lines",
+                        Some("-n"),
+                        "--lines",
+                        "N",
+                        "Only show the first N lines",
+                    ),
+                    Arg::flag("number", None, "--number", "Prefix lines with their number"),
+                ],
+                ..Default::default()
+            },
+            errors: &["--number", "hello.txt --lines", "hello.txt --frobnicate"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "hidden",
+            meta: Meta {
+                name: "deploy".to_string(),
+                summary: "Deploy the current build".to_string(),
+                usage: "deploy [OPTIONS] <TARGET>".to_string(),
+                args: vec![
+                    Arg::positional("target", "TARGET", "Environment to deploy to").required(),
+                    Arg::positional("build-id", "BUILD_ID", "Internal build override").hidden(),
+                    Arg::flag("dry-run", Some("-n"), "--dry-run", "Print the plan only"),
+                    Arg::option(
+                        "token",
+                        None,
+                        "--debug-token",
+                        "TOKEN",
+                        "Internal debug token",
+                    )
+                    .hidden(),
+                ],
+                ..Default::default()
+            },
+            errors: &["", "staging --debug-token"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "env_default",
+            meta: Meta {
+                name: "serve".to_string(),
+                summary: "Serve a directory over HTTP".to_string(),
+                usage: "serve [OPTIONS] [DIR]".to_string(),
+                args: vec![
+                    Arg::positional("dir", "DIR", "Directory to serve").default_value("."),
+                    Arg::option("port", Some("-p"), "--port", "PORT", "Port to listen on")
+                        .env("SERVE_PORT")
+                        .default_value("8080"),
+                    Arg::option("host", None, "--host", "HOST", "Address to bind")
+                        .env("SERVE_HOST"),
+                    Arg::option("log-level", None, "--log-level", "LEVEL", "Log verbosity")
+                        .env("SERVE_LOG")
+                        .default_value("info")
+                        .possible_values(&["debug", "info", "warn"]),
+                    Arg::option("token", None, "--token", "TOKEN", "Access token")
+                        .env("SERVE_TOKEN")
+                        .required(),
+                ],
+                ..Default::default()
+            },
+            errors: &["", "--log-level trace --token t", "--port"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "aliases",
+            meta: Meta {
+                name: "remove".to_string(),
+                summary: "Remove files".to_string(),
+                usage: "remove [OPTIONS] <PATH>".to_string(),
+                aliases: vec!["rm".to_string(), "del".to_string()],
+                version: "2.0.0".to_string(),
+                args: vec![
+                    Arg::positional("path", "PATH", "Path to remove").required(),
+                    Arg::flag("recursive", Some("-r"), "--recursive", "Remove directories"),
+                    Arg::flag("force", Some("-f"), "--force", "Ignore missing files"),
+                ],
+                ..Default::default()
+            },
+            errors: &["-rf", "dir -rx"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "long_description",
+            meta: Meta {
+                name: "sync".to_string(),
+                description: "Synchronize a local directory with a remote mirror.\n\n\
+                    Files are compared by size and modification time; use --checksum\n\
+                    to compare contents instead. Nothing is deleted on the remote\n\
+                    unless --delete is given.\n"
+                    .to_string(),
+                examples: vec![
+                    "sync ./site s3://bucket/site".to_string(),
+                    "  ".to_string(),
+                    "sync --checksum --delete ./site s3://bucket/site".to_string(),
+                ],
+                args: vec![
+                    Arg::positional("src", "SRC", "").required(),
+                    Arg::positional("dest", "DEST", "").required(),
+                    Arg::flag(
+                        "checksum",
+                        Some("-c"),
+                        "--checksum",
+                        "Compare file contents instead of size and modification time",
+                    ),
+                    Arg::flag(
+                        "delete",
+                        None,
+                        "--delete",
+                        "Delete remote files that are missing locally",
+                    ),
+                    Arg::option("exclude", Some("-e"), "--exclude", "GLOB", ""),
+                ],
+                ..Default::default()
+            },
+            errors: &["./site", "./site s3://bucket --exclude"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "builtin_overrides",
+            meta: Meta {
+                name: "query".to_string(),
+                summary: "Query a server".to_string(),
+                usage: "query [OPTIONS]".to_string(),
+                version: "0.9.0".to_string(),
+                args: vec![
+                    Arg::option("host", Some("-h"), "--host", "HOST", "Server to query")
+                        .default_value("localhost"),
+                    Arg::flag("invert", Some("-V"), "--invert", "Invert the match"),
+                ],
+                ..Default::default()
+            },
+            errors: &["--host", "--version"],
+            outputs: &[],
+        },
+        Fixture {
+            name: "subcommands",
+            meta: Meta {
+                name: "remote".to_string(),
+                summary: "Manage remotes".to_string(),
+                usage: "remote [OPTIONS] <COMMAND>".to_string(),
+                version: "1.4.0".to_string(),
+                args: vec![Arg::flag(
+                    "verbose",
+                    Some("-v"),
+                    "--verbose",
+                    "Print details",
+                )],
+                subcommands: vec![
+                    Meta {
+                        name: "add".to_string(),
+                        summary: "Add a remote".to_string(),
+                        aliases: vec!["new".to_string()],
+                        args: vec![
+                            Arg::positional("name", "NAME", "Remote name").required(),
+                            Arg::positional("url", "URL", "Remote URL").required(),
+                            Arg::flag("fetch", Some("-f"), "--fetch", "Fetch after adding"),
+                        ],
+                        ..Default::default()
+                    },
+                    Meta {
+                        name: "remove".to_string(),
+                        summary: "Remove a remote".to_string(),
+                        usage: "remote remove <NAME>".to_string(),
+                        version: "2.0.0".to_string(),
+                        args: vec![Arg::positional("name", "NAME", "Remote name").required()],
+                        ..Default::default()
+                    },
+                    Meta {
+                        name: "prune".to_string(),
+                        summary: "Internal cleanup".to_string(),
+                        hidden: true,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            errors: &["rename origin", "add origin", "-v new origin url --force"],
+            outputs: &["add --help", "new -V", "remove --version", "-v remove -h"],
+        },
+        Fixture {
+            name: "groups",
+            meta: Meta {
+                name: "export".to_string(),
+                summary: "Export records".to_string(),
+                usage: "export [OPTIONS] <--json|--yaml|--toml>".to_string(),
+                args: vec![
+                    Arg::flag("json", None, "--json", "Write JSON").group("format"),
+                    Arg::flag("yaml", None, "--yaml", "Write YAML").group("format"),
+                    Arg::flag("toml", None, "--toml", "Write TOML").group("format"),
+                    Arg::option("to", Some("-o"), "--to", "FILE", "Write to FILE").group("sink"),
+                    Arg::flag("stdout", None, "--stdout", "Write to stdout").group("sink"),
+                    Arg::option("field", Some("-f"), "--field", "NAME", "Only export NAME")
+                        .group("select"),
+                    Arg::flag("all", Some("-a"), "--all", "Export every field").group("select"),
+                ],
+                groups: vec![
+                    Group::new("format", true, false),
+                    Group::new("select", true, true),
+                ],
+                ..Default::default()
+            },
+            errors: &[
+                "",
+                "--stdout",
+                "--json --toml -a",
+                "--yaml -a --stdout -o out.yaml",
+            ],
+            outputs: &[],
+        },
+    ]
+}
+
+fn render(fixture: &Fixture) -> String {
+    let mut out = String::new();
+    out.push_str("--- help\n");
+    out.push_str(&claplike::help(&fixture.meta));
+    out.push_str("--- version\n");
+    out.push_str(&claplike::version(&fixture.meta));
+    for line in fixture.errors {
+        let argv: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        let message = match claplike::parse(&fixture.meta, &argv) {
+            Err(err) => err.message().to_string(),
+            Ok(_) => panic!("{}: `{line}` was expected to fail", fixture.name),
+        };
+        let command = format!("{} {line}", fixture.meta.name);
+        out.push_str(&format!("--- error: {}\n{message}\n", command.trim_end()));
+    }
+    for line in fixture.outputs {
+        let argv: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        let text = match claplike::parse(&fixture.meta, &argv) {
+            Ok(ParseOutcome::Help(text) | ParseOutcome::Version(text)) => text,
+            _ => panic!(
+                "{}: `{line}` was expected to print help or version",
+                fixture.name
+            ),
+        };
+        out.push_str(&format!("--- output: {} {line}\n{text}", fixture.meta.name));
+    }
+    out
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.txt"))
+}
+
+/// Line diff of `expected` against `actual` (longest common subsequence).
+fn diff(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push_str(&format!("  {}\n", a[i]));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push_str(&format!("+ {}\n", b[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("- {}\n", a[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+#[test]
+fn help_version_and_errors_match_snapshots() {
+    let regenerate = std::env::var_os("REGENERATE").is_some_and(|v| v == "1");
+    let mut failures = Vec::new();
+
+    for fixture in fixtures() {
+        let actual = render(&fixture);
+        let path = snapshot_path(fixture.name);
+        if regenerate {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{} changed (- snapshot, + actual):\n{}",
+                path.display(),
+                diff(&expected, &actual)
+            )),
+            Err(err) => failures.push(format!("{}: {err}", path.display())),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\nrerun with REGENERATE=1 if the change is intended",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,423 @@
+//! `#[wacli_command]`: a struct whose fields are the command's args.
+//!
+//! The attribute and `#[arg(...)]` entries are turned into the input of
+//! `declare_command_metadata!` (same keys, same builders), so the embedded
+//! section is byte-for-byte what the function-like macro would emit.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    Attribute, Expr, ExprLit, Fields, GenericArgument, Ident, ItemStruct, Lit, LitChar, LitStr,
+    Meta, Path, PathArguments, Result, Token, Type,
+    meta::ParseNestedMeta,
+    parse::Parser,
+    punctuated::Punctuated,
+    token::{Brace, Bracket, Paren},
+};
+
+use crate::shared_args::SharedArgsCtx;
+use crate::{Body, Decl, Field, Object, ObjectField, Value, arg_from_object};
+
+/// Name of the generated metadata function, private to the expansion.
+const META_FN: &str = "__wacli_command_meta";
+
+/// How a field is read from the matches, by its type.
+enum Kind {
+    /// `bool`: whether the flag was given.
+    Flag,
+    /// `Option<T>`: the last value, if any.
+    Optional(Type),
+    /// `Vec<T>`: every value, in order.
+    Many(Type),
+    /// Any other `T`: the last value, required unless it has a default.
+    Required(Type),
+}
+
+/// A struct field and the arg it reads.
+pub(crate) struct ArgField {
+    ident: Ident,
+    kind: Kind,
+    /// `name` in the schema (the key `Matches` is read with).
+    name: String,
+    /// How a missing value is named in the error (`--format <FORMAT>`).
+    display: String,
+}
+
+/// The `declare_command_metadata!` input for `attr` on `item`, the fields
+/// to read back, and the `run = ...` function, if any. `#[arg]` attributes
+/// are removed from `item`.
+pub(crate) fn command_decl(
+    attr: TokenStream,
+    item: &mut ItemStruct,
+) -> Result<(Decl, Vec<ArgField>, Option<Path>)> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "#[wacli_command] structs cannot be generic",
+        ));
+    }
+
+    let mut fields = Vec::new();
+    let mut run = None;
+    let parser = syn::meta::parser(|meta| {
+        let key = meta_key(&meta)?;
+        match key.to_string().as_str() {
+            "run" => run = Some(meta.value()?.parse::<Path>()?),
+            "args" | "subcommands" | "tests" | "shared_args_path" => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("`{key}` is not supported by #[wacli_command]"),
+                ));
+            }
+            _ => {
+                let value = meta_value(&meta)?.unwrap_or_else(|| bool_value(true, key.span()));
+                fields.push(field(key, value));
+            }
+        }
+        Ok(())
+    });
+    parser.parse2(attr)?;
+
+    if !fields.iter().any(|f| f.key == "summary")
+        && let Some(summary) = doc_lines(&item.attrs).into_iter().next()
+    {
+        fields.push(field(
+            Ident::new("summary", Span::call_site()),
+            Value::Str(LitStr::new(&summary, Span::call_site())),
+        ));
+    }
+
+    let Fields::Named(named) = &mut item.fields else {
+        return Err(syn::Error::new_spanned(
+            &item.ident,
+            "#[wacli_command] expects a struct with named fields",
+        ));
+    };
+    let mut args = Vec::new();
+    let mut arg_fields = Vec::new();
+    for f in &mut named.named {
+        let ident = f.ident.clone().expect("named field");
+        let kind = kind(&f.ty);
+        let (obj, name) = arg_object(&ident, &kind, &f.attrs)?;
+        f.attrs.retain(|a| !a.path().is_ident("arg"));
+
+        let schema = arg_from_object(&obj, &SharedArgsCtx::default(), false)?.build();
+        let positional = schema.short.is_none() && schema.long.is_none();
+        if positional && matches!(kind, Kind::Flag) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "a bool field needs `long` or `short`",
+            ));
+        }
+        let value_name = schema.value_name.as_deref().unwrap_or(&name);
+        let display = match schema.long.as_deref().or(schema.short.as_deref()) {
+            Some(flag) => format!("{flag} <{value_name}>"),
+            None => format!("<{value_name}>"),
+        };
+        args.push(obj);
+        arg_fields.push(ArgField {
+            ident,
+            kind,
+            name,
+            display,
+        });
+    }
+    fields.push(field(
+        Ident::new("args", Span::call_site()),
+        Value::ObjectArray(args),
+    ));
+
+    let decl = Decl {
+        func_ident: Ident::new(META_FN, Span::call_site()),
+        _comma: Default::default(),
+        body: Body {
+            _brace: Brace::default(),
+            fields: fields.into_iter().collect(),
+        },
+    };
+    Ok((decl, arg_fields, run))
+}
+
+#[cfg(test)]
+impl ArgField {
+    pub(crate) fn display(&self) -> &str {
+        &self.display
+    }
+}
+
+/// The struct, its metadata, `command_meta()`/`from_matches()` and, with
+/// `run = ...`, the `Command` impl.
+pub(crate) fn expand(attr: TokenStream, mut item: ItemStruct) -> Result<TokenStream> {
+    let (decl, fields, run) = command_decl(attr, &mut item)?;
+    let name = LitStr::new(
+        &crate::decl_payload(&decl)?.0.command_meta.name,
+        Span::call_site(),
+    );
+    let metadata = crate::expand_decl(decl)?;
+    let meta_fn = Ident::new(META_FN, Span::call_site());
+    let tree_fn = Ident::new(&format!("{META_FN}_tree"), Span::call_site());
+    let ident = &item.ident;
+
+    let inits = fields.iter().map(|f| {
+        let field = &f.ident;
+        let name = LitStr::new(&f.name, Span::call_site());
+        let value = match &f.kind {
+            Kind::Flag => quote! { matches.get_flag(#name) },
+            Kind::Optional(ty) => quote! { matches.get_parsed::<#ty>(#name)? },
+            Kind::Many(ty) => quote! { matches.get_all_parsed::<#ty>(#name)? },
+            Kind::Required(ty) => {
+                let display = LitStr::new(&f.display, Span::call_site());
+                quote! {
+                    matches.get_parsed::<#ty>(#name)?.ok_or_else(|| {
+                        ::wacli_cdk::CommandError::InvalidArgs(
+                            ::wacli_cdk::args::messages::missing_required(&[#display]),
+                        )
+                    })?
+                }
+            }
+        };
+        quote! { #field: #value }
+    });
+
+    let command_impl = run.map(|run| {
+        quote! {
+            impl ::wacli_cdk::Command for #ident {
+                const NAME: ::core::option::Option<&'static str> =
+                    ::core::option::Option::Some(#name);
+
+                fn meta() -> ::wacli_cdk::CommandMeta {
+                    Self::command_meta()
+                }
+
+                fn run(argv: ::std::vec::Vec<::std::string::String>) -> ::wacli_cdk::CommandResult {
+                    let ctx = ::wacli_cdk::Context::new(argv);
+                    let tree = Self::command_tree();
+                    let matches = ctx.matches(&tree)?;
+                    #run(Self::from_matches(&matches)?)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #item
+
+        const _: () = {
+            #metadata
+
+            impl #ident {
+                /// The command's metadata, as embedded in its custom section.
+                pub fn command_meta() -> ::wacli_cdk::CommandMeta {
+                    #meta_fn()
+                }
+
+                /// The command's metadata with what `arg-def` cannot hold,
+                /// to parse with.
+                pub fn command_tree() -> ::wacli_cdk::CommandTree {
+                    #tree_fn()
+                }
+
+                /// Read the fields from `matches` parsed against
+                /// [`Self::command_tree`].
+                pub fn from_matches(
+                    matches: &::wacli_cdk::args::Matches<'_>,
+                ) -> ::core::result::Result<Self, ::wacli_cdk::CommandError> {
+                    ::core::result::Result::Ok(Self { #(#inits),* })
+                }
+            }
+
+            #command_impl
+        };
+    })
+}
+
+/// The arg entry for a field: its `#[arg]` keys, plus what the field's type
+/// implies (value name, `required`, `multiple`, `var_arg`, `value_type`) and its doc
+/// comment as `help`, unless given.
+fn arg_object(ident: &Ident, kind: &Kind, attrs: &[Attribute]) -> Result<(Object, String)> {
+    let mut name = ident.to_string().trim_start_matches("r#").to_string();
+    let mut entries: Vec<(Ident, Option<Value>)> = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("arg")) {
+        if matches!(attr.meta, Meta::Path(_)) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let key = meta_key(&meta)?;
+            let value = meta_value(&meta)?;
+            if key == "name" {
+                match &value {
+                    Some(Value::Str(lit)) => name = lit.value(),
+                    _ => return Err(syn::Error::new(key.span(), "expected string literal")),
+                }
+            } else {
+                entries.push((key, value));
+            }
+            Ok(())
+        })?;
+    }
+
+    let span = ident.span();
+    let str_value = |s: &str| Value::Str(LitStr::new(s, span));
+    let mut fields = vec![object_field(Ident::new("name", span), str_value(&name))];
+    for (key, value) in &entries {
+        let value = match (value, key.to_string().as_str()) {
+            (Some(value), _) => value.clone(),
+            (None, "long") => str_value(&name.replace('_', "-")),
+            (None, "short") => str_value(&name.chars().next().unwrap_or_default().to_string()),
+            (None, _) => bool_value(true, key.span()),
+        };
+        fields.push(object_field(key.clone(), value));
+    }
+
+    let has = |key: &str| entries.iter().any(|(k, _)| k == key);
+    let mut implied = Vec::new();
+    if !has("help") {
+        let doc = doc_lines(attrs).join(" ");
+        if !doc.is_empty() {
+            implied.push(("help", str_value(&doc)));
+        }
+    }
+    let value_ty = match kind {
+        Kind::Flag => None,
+        Kind::Optional(ty) | Kind::Many(ty) | Kind::Required(ty) => Some(ty),
+    };
+    if let Some(ty) = value_ty {
+        if !has("value_name") {
+            implied.push((
+                "value_name",
+                str_value(&name.to_uppercase().replace('-', "_")),
+            ));
+        }
+        if !has("value_type")
+            && let Some(value_type) = value_type(ty)
+        {
+            implied.push(("value_type", str_value(value_type)));
+        }
+    }
+    if matches!(kind, Kind::Required(_)) && !has("required") && !has("default_value") {
+        implied.push(("required", bool_value(true, span)));
+    }
+    if matches!(kind, Kind::Many(_)) && !has("multiple") {
+        implied.push(("multiple", bool_value(true, span)));
+    }
+    if matches!(kind, Kind::Many(_)) && !has("short") && !has("long") && !has("var_arg") {
+        implied.push(("var_arg", bool_value(true, span)));
+    }
+    for (key, value) in implied {
+        fields.push(object_field(Ident::new(key, span), value));
+    }
+
+    let object = Object {
+        _brace: Brace::default(),
+        fields: fields.into_iter().collect(),
+    };
+    Ok((object, name))
+}
+
+fn kind(ty: &Type) -> Kind {
+    let Type::Path(path) = ty else {
+        return Kind::Required(ty.clone());
+    };
+    let Some(last) = path.path.segments.last() else {
+        return Kind::Required(ty.clone());
+    };
+    let inner = match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    match (last.ident.to_string().as_str(), inner) {
+        ("bool", None) => Kind::Flag,
+        ("Option", Some(inner)) => Kind::Optional(inner),
+        ("Vec", Some(inner)) => Kind::Many(inner),
+        _ => Kind::Required(ty.clone()),
+    }
+}
+
+/// The `value_type` for a primitive numeric field, checked by the parser.
+fn value_type(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let ident = path.path.get_ident()?.to_string();
+    match ident.as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => Some("int"),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Some("uint"),
+        "f32" | "f64" => Some("float"),
+        _ => None,
+    }
+}
+
+/// Lines of the `///` comments in `attrs`, trimmed, without empty ones.
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn meta_key(meta: &ParseNestedMeta) -> Result<Ident> {
+    meta.path
+        .get_ident()
+        .cloned()
+        .ok_or_else(|| meta.error("expected an identifier"))
+}
+
+/// `key = "str" | true | 3 | 'c' | ["a", "b"]`, `key("a", "b")`, or `None`
+/// for a bare `key`.
+fn meta_value(meta: &ParseNestedMeta) -> Result<Option<Value>> {
+    if meta.input.peek(Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        let items: Punctuated<LitStr, Token![,]> =
+            content.parse_terminated(|p| p.parse::<LitStr>(), Token![,])?;
+        return Ok(Some(Value::StrArray(items.into_iter().collect())));
+    }
+    if !meta.input.peek(Token![=]) {
+        return Ok(None);
+    }
+    let input = meta.value()?;
+    if input.peek(LitChar) {
+        let c: LitChar = input.parse()?;
+        return Ok(Some(Value::Str(LitStr::new(
+            &c.value().to_string(),
+            c.span(),
+        ))));
+    }
+    if input.peek(Bracket) || input.peek(Lit) {
+        return Ok(Some(input.parse()?));
+    }
+    Err(input.error("expected a literal or [\"a\", \"b\"]"))
+}
+
+fn bool_value(value: bool, span: Span) -> Value {
+    Value::Bool(syn::LitBool::new(value, span))
+}
+
+fn field(key: Ident, value: Value) -> Field {
+    Field {
+        key,
+        _colon: Default::default(),
+        value,
+    }
+}
+
+fn object_field(key: Ident, value: Value) -> ObjectField {
+    ObjectField {
+        key,
+        _colon: Default::default(),
+        value,
+    }
+}
@@ -1,7 +1,16 @@
 use proc_macro::TokenStream;
 use quote::quote;
+use wacli_metadata::{
+    ArgGroup, ArgSchemaBuilder, CommandMetaBuilder, CommandMetadataV1, PipeMetadataV1, SelfTest,
+};
+
+mod command_attr;
+mod shared_args;
+
+use shared_args::SharedArgsCtx;
 use syn::{
-    Ident, LitBool, LitByteStr, LitStr, Result, Token, braced, bracketed,
+    Ident, LitBool, LitByteStr, LitInt, LitStr, Result, Token, braced, bracketed,
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
@@ -12,7 +21,11 @@ use syn::{
 ///
 /// This generates:
 /// - a function `<ident>() -> wacli_cdk::CommandMeta`
-/// - a `#[link_section]` static containing JSON metadata (no plugin execution required)
+/// - a function `<ident>_tree() -> wacli_cdk::CommandTree`, to parse with: it
+///   also holds subcommands and what `arg-def` cannot (`var_arg`,
+///   `allow_hyphen_values`, `value_type`)
+/// - a `#[link_section]` static containing JSON metadata (no plugin execution required);
+///   with the `metadata-v2` feature of `wacli-cdk`, a second one for the `@2` section
 ///
 /// Syntax (kebab-case JSON keys are derived; this is Rust syntax):
 ///
@@ -22,18 +35,48 @@ use syn::{
 ///   summary: "Show text",
 ///   usage: "show [OPTIONS] [TEXT]",
 ///   aliases: ["s"],
+///   // Dispatch like `aliases` but are left out of help.
+///   hidden_aliases: ["sh"],
 ///   version: "0.1.0",
 ///   hidden: false,
 ///   description: "Longer help...",
 ///   examples: ["show hello"],
+///   allow_arg_files: false,
+///   // Dispatched on the first positional; parse with `show_meta_tree()`.
+///   // Same keys as the command (no `tests`); an omitted version is inherited.
+///   subcommands: [
+///     { name: "raw", summary: "Show bytes", aliases: ["r"],
+///       args: [{ name: "file", value_name: "FILE", required: true }] },
+///   ],
+///   // Run by `wacli verify`; `expect_stdout` also accepts "contains:TEXT".
+///   tests: [
+///     { cmd: "show hello", expect_stdout: "hello" },
+///     { cmd: "show --format nope", expect_exit: 2 },
+///   ],
 ///   args: [
 ///     { name: "format", long: "--format", value_name: "PIPE", help: "Pipe name",
 ///       env: "SHOW_FORMAT", possible_values: ["plain", "json"], multiple: false,
-///       conflicts_with: ["raw"], requires: ["text"] },
-///     { name: "text", value_name: "TEXT", help: "Text to show" },
+///       conflicts_with: ["raw"], requires: ["text"], allow_hyphen_values: false },
+///     // `var_arg: true` on the last positional takes all remaining ones.
+///     { name: "text", value_name: "TEXT", help: "Text to show", var_arg: true },
+///     { name: "upper", long: "--upper", group: "case" },
+///     { name: "lower", long: "--lower", group: "case" },
+///     // `-vvv` counts 3; read it with `matches.count("verbose")`.
+///     { name: "verbose", short: "-v", countable: true },
 ///   ],
+///   // At most one member (`multiple: true` lifts that); `required: true`
+///   // asks for at least one. Groups args name without a declaration here
+///   // are neither.
+///   groups: [{ name: "case", required: false, multiple: false }],
 /// });
 /// ```
+///
+/// An arg entry can start from a shared template instead (see
+/// `wacli_metadata::SharedArgs`): `{ use: "output" }` splices the template,
+/// and any other field in the entry replaces the template's. The templates are
+/// read from `shared_args_path: "argdefs.json"` (relative to the crate's
+/// manifest dir), or from the path in `WACLI_SHARED_ARGS` when that field is
+/// absent.
 #[proc_macro]
 pub fn declare_command_metadata(input: TokenStream) -> TokenStream {
     let decl = parse_macro_input!(input as Decl);
@@ -44,6 +87,194 @@ pub fn declare_command_metadata(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Declare command metadata from a struct whose fields are the command's args,
+/// like clap's derive.
+///
+/// Takes the command-level keys of [`declare_command_metadata!`] (except
+/// `args`, `subcommands`, `tests` and `shared_args_path`); each field's
+/// `#[arg(...)]` takes the keys of an arg entry. This generates:
+/// - the struct, without its `#[arg]` attributes
+/// - the same custom section `declare_command_metadata!` embeds
+/// - `Self::command_meta() -> wacli_cdk::CommandMeta`
+/// - `Self::command_tree() -> wacli_cdk::CommandTree`, to parse with
+/// - `Self::from_matches(&Matches) -> Result<Self, CommandError>`
+/// - with `run = path`, `impl wacli_cdk::Command` that parses `argv` and calls
+///   `path(Self)`, so `wacli_cdk::export!` takes the struct as is
+///
+/// ```ignore
+/// /// Show text
+/// #[wacli_cdk::wacli_command(name = "show", aliases("s"), run = Self::exec)]
+/// struct Show {
+///     /// Output format
+///     #[arg(long, short = 'f', default_value = "plain", possible_values("plain", "json"))]
+///     format: String,
+///     /// Uppercase the text
+///     #[arg(long)]
+///     upper: bool,
+///     #[arg(long, value_name = "N")]
+///     width: Option<u32>,
+///     /// Text to show
+///     text: Vec<String>,
+/// }
+///
+/// impl Show {
+///     fn exec(self) -> wacli_cdk::CommandResult { /* ... */ Ok(0) }
+/// }
+///
+/// wacli_cdk::export!(Show);
+/// ```
+///
+/// The field name is the arg name (`name = "..."` overrides it); a bare
+/// `long` is `--<name>` with `_` as `-`, a bare `short` its first letter, and
+/// other bare keys are `true`. The type decides how the field is read and
+/// fills in what is not given: `bool` is a flag; `Option<T>` is optional,
+/// `Vec<T>` repeatable (`multiple`, and `var_arg` when positional), and any
+/// other `T` is required unless it has a `default_value`. Non-flags get an
+/// upper-case `value_name`, and integer and float types a `value_type`;
+/// values are parsed with `FromStr`.
+/// Doc comments become `help`, and the struct's first doc line the `summary`.
+#[proc_macro_attribute]
+pub fn wacli_command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as syn::ItemStruct);
+
+    match command_attr::expand(attr.into(), item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Declare a pipe's options and embed them into a WASM custom section.
+///
+/// The host validates every `process()` call against these before the pipe
+/// runs. Options are passed as `key=value` (or a bare `key` for flags); each
+/// entry accepts the same fields as a command arg, and `long` defaults to
+/// `name`.
+///
+/// ```ignore
+/// wacli_cdk::declare_pipe_options!([
+///   { name: "case", possible_values: ["upper", "lower"], default_value: "upper",
+///     help: "Letter case" },
+///   { name: "trim", help: "Strip surrounding whitespace" },
+/// ]);
+/// ```
+#[proc_macro]
+pub fn declare_pipe_options(input: TokenStream) -> TokenStream {
+    let value = parse_macro_input!(input as Value);
+
+    match expand_pipe_options(&value) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Declare a pipe's metadata and options and embed them into a WASM custom
+/// section.
+///
+/// Like [`declare_pipe_options!`], plus the descriptive fields of the pipe's
+/// `meta()`, so hosts can list the pipe with its summary and types without
+/// instantiating it. Every field is optional; `version` defaults to the
+/// crate version.
+///
+/// ```ignore
+/// wacli_cdk::declare_pipe_metadata!({
+///   summary: "Render rows as a table",
+///   input_types: ["application/json"],
+///   output_type: "text/plain",
+///   version: "0.1.0",
+///   options: [
+///     { name: "width", value_name: "N", value_type: "u32", help: "Wrap width" },
+///   ],
+/// });
+/// ```
+#[proc_macro]
+pub fn declare_pipe_metadata(input: TokenStream) -> TokenStream {
+    let body = parse_macro_input!(input as Body);
+
+    match expand_pipe_metadata(&body) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_pipe_options(value: &Value) -> Result<proc_macro2::TokenStream> {
+    let shared = SharedArgsCtx::default();
+    let options = pipe_options_value(value, &shared)?;
+    Ok(pipe_metadata_tokens(&PipeMetadataV1::new(options), &shared))
+}
+
+fn expand_pipe_metadata(body: &Body) -> Result<proc_macro2::TokenStream> {
+    let shared = SharedArgsCtx::default();
+    let payload = pipe_metadata_payload(body, &shared)?;
+    Ok(pipe_metadata_tokens(&payload, &shared))
+}
+
+fn pipe_metadata_payload(body: &Body, shared: &SharedArgsCtx) -> Result<PipeMetadataV1> {
+    let mut payload = PipeMetadataV1::new(Vec::new());
+    let mut version = None;
+    for field in &body.fields {
+        let key = field.key.to_string();
+        match key.as_str() {
+            "summary" => payload.summary = expect_string_value(&field.value)?,
+            "input_types" => payload.input_types = expect_string_array_value(&field.value)?,
+            "output_type" => payload.output_type = expect_string_value(&field.value)?,
+            "version" => version = Some(expect_string_value(&field.value)?),
+            "options" => payload.options = pipe_options_value(&field.value, shared)?,
+            other => {
+                return Err(syn::Error::new(
+                    field.key.span(),
+                    format!("unknown field: {other}"),
+                ));
+            }
+        }
+    }
+    payload.version = version
+        .or_else(|| std::env::var("CARGO_PKG_VERSION").ok())
+        .unwrap_or_default();
+    Ok(payload)
+}
+
+fn pipe_options_value(
+    value: &Value,
+    shared: &SharedArgsCtx,
+) -> Result<Vec<wacli_metadata::ArgSchema>> {
+    match value {
+        Value::StrArray(items) if items.is_empty() => Ok(Vec::new()),
+        // Options are addressed by key, so each one is a long flag.
+        Value::ObjectArray(items) => items
+            .iter()
+            .map(|obj| Ok(arg_from_object(obj, shared, true)?.build()))
+            .collect(),
+        _ => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected [ { name: \"...\", ... }, ... ]",
+        )),
+    }
+}
+
+fn pipe_metadata_tokens(
+    payload: &PipeMetadataV1,
+    shared: &SharedArgsCtx,
+) -> proc_macro2::TokenStream {
+    let bytes = payload.to_json_bytes();
+    let bytes_len = bytes.len();
+    let bytes_lit = LitByteStr::new(&bytes, proc_macro2::Span::call_site());
+    let section_name = LitStr::new(
+        wacli_metadata::PIPE_METADATA_SECTION,
+        proc_macro2::Span::call_site(),
+    );
+
+    let dependency = shared.dependency();
+
+    quote! {
+        #[doc(hidden)]
+        #[used]
+        #[unsafe(link_section = #section_name)]
+        pub static __WACLI_PIPE_METADATA: [u8; #bytes_len] = *#bytes_lit;
+
+        #dependency
+    }
+}
+
 struct Decl {
     func_ident: Ident,
     _comma: Token![,],
@@ -92,11 +323,13 @@ impl Parse for Field {
     }
 }
 
+#[derive(Clone)]
 enum Value {
     Str(LitStr),
     Bool(LitBool),
+    Int(LitInt),
     StrArray(Vec<LitStr>),
-    ArgsArray(Vec<ArgObject>),
+    ObjectArray(Vec<Object>),
 }
 
 impl Parse for Value {
@@ -107,6 +340,9 @@ impl Parse for Value {
         if input.peek(LitBool) {
             return Ok(Self::Bool(input.parse()?));
         }
+        if input.peek(LitInt) {
+            return Ok(Self::Int(input.parse()?));
+        }
         if input.peek(Bracket) {
             let content;
             bracketed!(content in input);
@@ -115,11 +351,12 @@ impl Parse for Value {
                 return Ok(Self::StrArray(Vec::new()));
             }
 
-            // If the first element is a `{ ... }`, treat this as `args: [ {..}, .. ]`.
+            // If the first element is a `{ ... }`, treat this as `args: [ {..}, .. ]`
+            // (or `tests: [ {..}, .. ]`).
             if content.peek(Brace) {
-                let elems: Punctuated<ArgObject, Token![,]> =
-                    content.parse_terminated(ArgObject::parse, Token![,])?;
-                return Ok(Self::ArgsArray(elems.into_iter().collect()));
+                let elems: Punctuated<Object, Token![,]> =
+                    content.parse_terminated(Object::parse, Token![,])?;
+                return Ok(Self::ObjectArray(elems.into_iter().collect()));
             }
 
             // Otherwise expect `[ "a", "b" ]`.
@@ -130,37 +367,40 @@ impl Parse for Value {
 
         Err(syn::Error::new(
             input.span(),
-            "expected string literal, boolean literal, or [ ... ] array",
+            "expected string literal, boolean literal, integer literal, or [ ... ] array",
         ))
     }
 }
 
-struct ArgObject {
+#[derive(Clone)]
+struct Object {
     _brace: Brace,
-    fields: Punctuated<ArgField, Token![,]>,
+    fields: Punctuated<ObjectField, Token![,]>,
 }
 
-impl Parse for ArgObject {
+impl Parse for Object {
     fn parse(input: ParseStream) -> Result<Self> {
         let content;
         let brace = braced!(content in input);
         Ok(Self {
             _brace: brace,
-            fields: content.parse_terminated(ArgField::parse, Token![,])?,
+            fields: content.parse_terminated(ObjectField::parse, Token![,])?,
         })
     }
 }
 
-struct ArgField {
+#[derive(Clone)]
+struct ObjectField {
     key: Ident,
     _colon: Token![:],
     value: Value,
 }
 
-impl Parse for ArgField {
+impl Parse for ObjectField {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            key: input.parse()?,
+            // `parse_any` so that `use` (a keyword) works as a key.
+            key: input.call(Ident::parse_any)?,
             _colon: input.parse()?,
             value: input.parse()?,
         })
@@ -173,35 +413,35 @@ struct CommandSpec {
     summary: String,
     usage: String,
     aliases: Vec<String>,
+    hidden_aliases: Vec<String>,
     version: Option<String>,
     hidden: bool,
     description: String,
     examples: Vec<String>,
-    args: Vec<ArgSpec>,
+    args: Vec<ArgSchemaBuilder>,
+    groups: Vec<ArgGroup>,
+    allow_arg_files: bool,
+    tests: Vec<SelfTest>,
+    subcommands: Vec<CommandMetaBuilder>,
 }
 
-#[derive(Default)]
-struct ArgSpec {
-    name: Option<String>,
-    short: Option<String>,
-    long: Option<String>,
-    help: String,
-    required: bool,
-    default_value: Option<String>,
-    env: Option<String>,
-    value_name: Option<String>,
-    takes_value: Option<bool>,
-    multiple: Option<bool>,
-    value_type: Option<String>,
-    possible_values: Vec<String>,
-    conflicts_with: Vec<String>,
-    requires: Vec<String>,
-    hidden: bool,
-}
-
-fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
+/// The payload `decl` embeds, and the shared-args context its entries used.
+fn decl_payload(decl: &Decl) -> Result<(CommandMetadataV1, SharedArgsCtx)> {
     let mut spec = CommandSpec::default();
 
+    // Needed before `args`, wherever it appears.
+    let shared_args_path = decl
+        .body
+        .fields
+        .iter()
+        .find(|field| field.key == "shared_args_path")
+        .map(|field| match &field.value {
+            Value::Str(lit) => Ok(lit.clone()),
+            _ => Err(syn::Error::new(field.key.span(), "expected string literal")),
+        })
+        .transpose()?;
+    let shared = SharedArgsCtx::new(shared_args_path);
+
     for field in &decl.body.fields {
         let key = field.key.to_string();
         match key.as_str() {
@@ -209,11 +449,19 @@ fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
             "summary" => spec.summary = expect_string_value(&field.value)?,
             "usage" => spec.usage = expect_string_value(&field.value)?,
             "aliases" => spec.aliases = expect_string_array_value(&field.value)?,
+            "hidden_aliases" => spec.hidden_aliases = expect_string_array_value(&field.value)?,
             "version" => spec.version = Some(expect_string_value(&field.value)?),
             "hidden" => spec.hidden = expect_bool_value(&field.value)?,
             "description" => spec.description = expect_string_value(&field.value)?,
             "examples" => spec.examples = expect_string_array_value(&field.value)?,
-            "args" => spec.args = expect_args_array_value(&field.value)?,
+            "args" => spec.args = expect_args_array_value(&field.value, &shared)?,
+            "groups" => spec.groups = expect_groups_array_value(&field.value)?,
+            "shared_args_path" => {}
+            "allow_arg_files" => spec.allow_arg_files = expect_bool_value(&field.value)?,
+            "tests" => spec.tests = expect_tests_array_value(&field.value)?,
+            "subcommands" => {
+                spec.subcommands = expect_subcommands_array_value(&field.value, &shared)?
+            }
             other => {
                 return Err(syn::Error::new(
                     field.key.span(),
@@ -237,65 +485,44 @@ fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
         .or_else(|| std::env::var("CARGO_PKG_VERSION").ok())
         .unwrap_or_default();
 
-    // Build metadata payload for embedding.
-    let cmd_meta = wacli_metadata::CommandMeta {
-        name: name.clone(),
-        summary: spec.summary.clone(),
-        usage: spec.usage.clone(),
-        aliases: spec.aliases.clone(),
-        version: version.clone(),
-        hidden: spec.hidden,
-        description: spec.description.clone(),
-        examples: spec.examples.clone(),
-        args: spec
-            .args
-            .iter()
-            .map(|a| wacli_metadata::ArgDef {
-                name: a.name.clone().unwrap_or_default(),
-                short: a.short.clone(),
-                long: a.long.clone(),
-                help: a.help.clone(),
-                required: a.required,
-                default_value: a.default_value.clone(),
-                value_name: a.value_name.clone(),
-                takes_value: infer_takes_value(a),
-            })
-            .collect(),
-    };
-
-    let cmd_schema = wacli_metadata::CommandSchema {
-        name: name.clone(),
-        summary: spec.summary.clone(),
-        usage: spec.usage.clone(),
-        aliases: spec.aliases.clone(),
-        version: version.clone(),
-        hidden: spec.hidden,
-        description: spec.description.clone(),
-        examples: spec.examples.clone(),
-        args: spec
-            .args
-            .iter()
-            .map(|a| wacli_metadata::ArgSchema {
-                name: a.name.clone().unwrap_or_default(),
-                short: a.short.clone(),
-                long: a.long.clone(),
-                help: a.help.clone(),
-                required: a.required,
-                default_value: a.default_value.clone(),
-                env: a.env.clone(),
-                value_name: a.value_name.clone(),
-                takes_value: infer_takes_value(a),
-                multiple: a.multiple.unwrap_or(true),
-                value_type: a.value_type.clone(),
-                possible_values: a.possible_values.clone(),
-                conflicts_with: a.conflicts_with.clone(),
-                requires: a.requires.clone(),
-                hidden: a.hidden,
-            })
-            .collect(),
-    };
+    // Build the metadata payload through the shared builders so flag
+    // normalization and takes-value inference match non-macro tooling.
+    let mut builder = CommandMetaBuilder::new(name.clone())
+        .summary(spec.summary.clone())
+        .usage(spec.usage.clone())
+        .version(version.clone())
+        .hidden(spec.hidden)
+        .description(spec.description.clone())
+        .allow_arg_files(spec.allow_arg_files);
+    for alias in &spec.aliases {
+        builder = builder.alias(alias.clone());
+    }
+    for alias in &spec.hidden_aliases {
+        builder = builder.hidden_alias(alias.clone());
+    }
+    for example in &spec.examples {
+        builder = builder.example(example.clone());
+    }
+    for arg in spec.args {
+        builder = builder.arg(arg);
+    }
+    for group in spec.groups {
+        builder = builder.group(group);
+    }
+    for test in spec.tests {
+        builder = builder.self_test(test);
+    }
+    for sub in spec.subcommands {
+        builder = builder.subcommand(sub);
+    }
+    let mut payload = builder.build();
+    // The macro crate is released in lockstep with wacli-cdk.
+    payload.cdk_version = Some(env!("CARGO_PKG_VERSION").to_string());
+    Ok((payload, shared))
+}
 
-    let payload = wacli_metadata::CommandMetadataV1::new(cmd_meta.clone(), Some(cmd_schema));
+fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
+    let (payload, shared) = decl_payload(&decl)?;
     let bytes = payload.to_json_bytes();
     let bytes_len = bytes.len();
 
@@ -307,26 +534,50 @@ fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
         proc_macro2::Span::call_site(),
     );
 
-    // Generate runtime CommandMeta construction as normal Rust allocations.
-    let summary_expr = lit_or_empty(&spec.summary);
-    let usage_expr = lit_or_empty(&spec.usage);
-    let description_expr = lit_or_empty(&spec.description);
-    let version_expr = LitStr::new(&version, proc_macro2::Span::call_site());
-    let name_expr = LitStr::new(&name, proc_macro2::Span::call_site());
-
-    let aliases_expr = vec_expr(&spec.aliases);
-    let examples_expr = vec_expr(&spec.examples);
-    let args_expr = meta_args_expr(&spec.args);
-
     let section_name = LitStr::new(
         wacli_metadata::COMMAND_METADATA_SECTION,
         proc_macro2::Span::call_site(),
     );
-    let hidden_tokens = if spec.hidden {
-        quote!(true)
+    // With `metadata-v2`, also embed the `@2` section. The `@1` one stays so
+    // wacli releases that only read it keep working.
+    let section_v2 = if cfg!(feature = "metadata-v2") {
+        let bytes = wacli_metadata::CommandMetadataV2::from(payload.clone()).to_json_bytes();
+        let bytes_len = bytes.len();
+        let bytes_lit = LitByteStr::new(&bytes, proc_macro2::Span::call_site());
+        let section_ident = Ident::new(
+            &format!("__WACLI_COMMAND_METADATA_V2_{}", func_ident),
+            proc_macro2::Span::call_site(),
+        );
+        let section_name = LitStr::new(
+            wacli_metadata::COMMAND_METADATA_SECTION_V2,
+            proc_macro2::Span::call_site(),
+        );
+        quote! {
+            #[doc(hidden)]
+            #[used]
+            #[unsafe(link_section = #section_name)]
+            pub static #section_ident: [u8; #bytes_len] = *#bytes_lit;
+        }
     } else {
-        quote!(false)
+        quote!()
     };
+    let dependency = shared.dependency();
+
+    // Generate runtime CommandTree construction as normal Rust allocations,
+    // from the same (normalized) data that was embedded.
+    let schema = payload.command_schema.as_ref();
+    let tree_ident = Ident::new(
+        &format!("{}_tree", func_ident),
+        proc_macro2::Span::call_site(),
+    );
+    let tree_expr = tree_expr(
+        &payload.command_meta,
+        schema.map(|s| s.args.as_slice()).unwrap_or_default(),
+        &payload.command_meta.hidden_aliases,
+        schema
+            .map(|schema| schema.subcommands.as_slice())
+            .unwrap_or_default(),
+    );
 
     Ok(quote! {
         #[doc(hidden)]
@@ -334,8 +585,59 @@ fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
         #[unsafe(link_section = #section_name)]
         pub static #section_ident: [u8; #bytes_len] = *#bytes_lit;
 
+        #section_v2
+
         pub fn #func_ident() -> ::wacli_cdk::CommandMeta {
-            ::wacli_cdk::CommandMeta {
+            #tree_ident().meta
+        }
+
+        pub fn #tree_ident() -> ::wacli_cdk::CommandTree {
+            #tree_expr
+        }
+
+        #dependency
+    })
+}
+
+/// A `CommandTree` expression for `meta`; `schema_args` are the schema view
+/// of its args, for what `arg-def` cannot hold.
+fn tree_expr(
+    meta: &wacli_metadata::CommandMeta,
+    schema_args: &[wacli_metadata::ArgSchema],
+    hidden_aliases: &[String],
+    subcommands: &[wacli_metadata::CommandSchema],
+) -> proc_macro2::TokenStream {
+    let summary_expr = lit_or_empty(&meta.summary);
+    let usage_expr = lit_or_empty(&meta.usage);
+    let description_expr = lit_or_empty(&meta.description);
+    let version_expr = LitStr::new(&meta.version, proc_macro2::Span::call_site());
+    let name_expr = LitStr::new(&meta.name, proc_macro2::Span::call_site());
+
+    let aliases_expr = vec_expr(&meta.aliases);
+    let examples_expr = vec_expr(&meta.examples);
+    let args_expr = args_expr(&meta.args, schema_args);
+    let hidden_tokens = if meta.hidden {
+        quote!(true)
+    } else {
+        quote!(false)
+    };
+    let hidden_aliases = vec_expr(hidden_aliases);
+    let subs: Vec<proc_macro2::TokenStream> = subcommands
+        .iter()
+        .map(|sub| {
+            tree_expr(
+                &sub.to_meta(),
+                &sub.args,
+                &sub.hidden_aliases,
+                &sub.subcommands,
+            )
+        })
+        .collect();
+
+    quote! {{
+        let args: ::std::vec::Vec<::wacli_cdk::Arg> = #args_expr;
+        ::wacli_cdk::CommandTree {
+            meta: ::wacli_cdk::CommandMeta {
                 name: (#name_expr).to_string(),
                 summary: (#summary_expr).to_string(),
                 usage: (#usage_expr).to_string(),
@@ -344,25 +646,13 @@ fn expand_decl(decl: Decl) -> Result<proc_macro2::TokenStream> {
                 hidden: #hidden_tokens,
                 description: (#description_expr).to_string(),
                 examples: #examples_expr,
-                args: #args_expr,
-            }
+                args: args.iter().map(|arg| arg.def.clone()).collect(),
+            },
+            args,
+            hidden_aliases: #hidden_aliases,
+            subcommands: vec![ #(#subs),* ],
         }
-    })
-}
-
-fn infer_takes_value(a: &ArgSpec) -> bool {
-    if let Some(v) = a.takes_value {
-        return v;
-    }
-    let positional = a.short.is_none() && a.long.is_none();
-    if positional {
-        return true;
-    }
-    a.value_name.is_some()
-        || a.default_value.is_some()
-        || a.env.is_some()
-        || a.value_type.is_some()
-        || !a.possible_values.is_empty()
+    }}
 }
 
 fn lit_or_empty(s: &str) -> LitStr {
@@ -377,32 +667,47 @@ fn vec_expr(items: &[String]) -> proc_macro2::TokenStream {
     quote! { vec![ #( (#lits).to_string() ),* ] }
 }
 
-fn meta_args_expr(args: &[ArgSpec]) -> proc_macro2::TokenStream {
+fn args_expr(
+    args: &[wacli_metadata::ArgDef],
+    schema_args: &[wacli_metadata::ArgSchema],
+) -> proc_macro2::TokenStream {
     let entries: Vec<proc_macro2::TokenStream> = args
         .iter()
-        .map(|a| {
-            let name = LitStr::new(
-                a.name.as_deref().unwrap_or_default(),
-                proc_macro2::Span::call_site(),
-            );
+        .zip(schema_args.iter().map(Some).chain(std::iter::repeat(None)))
+        .map(|(a, schema)| {
+            let name = LitStr::new(&a.name, proc_macro2::Span::call_site());
             let help = LitStr::new(&a.help, proc_macro2::Span::call_site());
             let short = opt_string_expr(a.short.as_deref());
             let long = opt_string_expr(a.long.as_deref());
             let default_value = opt_string_expr(a.default_value.as_deref());
             let value_name = opt_string_expr(a.value_name.as_deref());
             let required = a.required;
-            let takes_value = infer_takes_value(a);
+            let takes_value = a.takes_value;
+            let var_arg = schema.is_some_and(|s| s.var_arg);
+            let allow_hyphen_values = schema.is_some_and(|s| s.allow_hyphen_values);
+            let value_type = match schema.and_then(|s| s.value_type.as_deref()) {
+                Some(t) => {
+                    let lit = LitStr::new(t, proc_macro2::Span::call_site());
+                    quote! { Some(#lit) }
+                }
+                None => quote! { None },
+            };
 
             quote! {
-                ::wacli_cdk::ArgDef {
-                    name: (#name).to_string(),
-                    short: #short,
-                    long: #long,
-                    help: (#help).to_string(),
-                    required: #required,
-                    default_value: #default_value,
-                    value_name: #value_name,
-                    takes_value: #takes_value,
+                ::wacli_cdk::Arg {
+                    def: ::wacli_cdk::ArgDef {
+                        name: (#name).to_string(),
+                        short: #short,
+                        long: #long,
+                        help: (#help).to_string(),
+                        required: #required,
+                        default_value: #default_value,
+                        value_name: #value_name,
+                        takes_value: #takes_value,
+                    },
+                    var_arg: #var_arg,
+                    allow_hyphen_values: #allow_hyphen_values,
+                    value_type: #value_type,
                 }
             }
         })
@@ -441,6 +746,16 @@ fn expect_bool_value(v: &Value) -> Result<bool> {
     }
 }
 
+fn expect_u32_value(v: &Value) -> Result<u32> {
+    match v {
+        Value::Int(i) => i.base10_parse(),
+        _ => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected integer literal",
+        )),
+    }
+}
+
 fn expect_string_array_value(v: &Value) -> Result<Vec<String>> {
     match v {
         Value::StrArray(items) => Ok(items.iter().map(|s| s.value()).collect()),
@@ -451,9 +766,12 @@ fn expect_string_array_value(v: &Value) -> Result<Vec<String>> {
     }
 }
 
-fn expect_args_array_value(v: &Value) -> Result<Vec<ArgSpec>> {
+fn expect_args_array_value(v: &Value, shared: &SharedArgsCtx) -> Result<Vec<ArgSchemaBuilder>> {
     match v {
-        Value::ArgsArray(items) => items.iter().map(arg_from_object).collect(),
+        Value::ObjectArray(items) => items
+            .iter()
+            .map(|obj| arg_from_object(obj, shared, false))
+            .collect(),
         _ => Err(syn::Error::new(
             proc_macro2::Span::call_site(),
             "expected args: [ { ... }, { ... } ]",
@@ -461,60 +779,550 @@ fn expect_args_array_value(v: &Value) -> Result<Vec<ArgSpec>> {
     }
 }
 
-fn arg_from_object(obj: &ArgObject) -> Result<ArgSpec> {
-    let mut a = ArgSpec::default();
+fn expect_groups_array_value(v: &Value) -> Result<Vec<ArgGroup>> {
+    match v {
+        Value::ObjectArray(items) => items.iter().map(group_from_object).collect(),
+        _ => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected groups: [ { name: \"...\", ... }, ... ]",
+        )),
+    }
+}
 
+fn group_from_object(obj: &Object) -> Result<ArgGroup> {
+    let mut name = None;
+    let mut group = ArgGroup::default();
     for field in &obj.fields {
-        let key = field.key.to_string();
-        match key.as_str() {
-            "name" => a.name = Some(expect_string_value(&field.value)?),
-            "short" => a.short = Some(expect_string_value(&field.value)?),
-            "long" => a.long = Some(expect_string_value(&field.value)?),
-            "help" => a.help = expect_string_value(&field.value)?,
-            "required" => a.required = expect_bool_value(&field.value)?,
-            "default_value" => a.default_value = Some(expect_string_value(&field.value)?),
-            "env" => a.env = Some(expect_string_value(&field.value)?),
-            "value_name" => a.value_name = Some(expect_string_value(&field.value)?),
-            "takes_value" => a.takes_value = Some(expect_bool_value(&field.value)?),
-            "multiple" => a.multiple = Some(expect_bool_value(&field.value)?),
-            "value_type" => a.value_type = Some(expect_string_value(&field.value)?),
-            "possible_values" => a.possible_values = expect_string_array_value(&field.value)?,
-            "conflicts_with" => a.conflicts_with = expect_string_array_value(&field.value)?,
-            "requires" => a.requires = expect_string_array_value(&field.value)?,
-            "hidden" => a.hidden = expect_bool_value(&field.value)?,
+        let v = &field.value;
+        match field.key.to_string().as_str() {
+            "name" => name = Some(expect_string_value(v)?),
+            "required" => group.required = expect_bool_value(v)?,
+            "multiple" => group.multiple = expect_bool_value(v)?,
             other => {
                 return Err(syn::Error::new(
                     field.key.span(),
-                    format!("unknown arg field: {other}"),
+                    format!("unknown group field: {other}"),
                 ));
             }
         }
     }
+    group.name = name.ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "group is missing required field: name",
+        )
+    })?;
+    Ok(group)
+}
 
-    if a.name.is_none() {
-        return Err(syn::Error::new(
+fn expect_subcommands_array_value(
+    v: &Value,
+    shared: &SharedArgsCtx,
+) -> Result<Vec<CommandMetaBuilder>> {
+    match v {
+        Value::ObjectArray(items) => items
+            .iter()
+            .map(|obj| subcommand_from_object(obj, shared))
+            .collect(),
+        _ => Err(syn::Error::new(
             proc_macro2::Span::call_site(),
-            "arg is missing required field: name",
-        ));
+            "expected subcommands: [ { name: \"...\", ... }, ... ]",
+        )),
+    }
+}
+
+/// Build a subcommand from its macro entry. Unlike the top-level command, an
+/// omitted `version` stays empty so the parent's version is inherited.
+fn subcommand_from_object(obj: &Object, shared: &SharedArgsCtx) -> Result<CommandMetaBuilder> {
+    let name = match obj.fields.iter().find(|field| field.key == "name") {
+        Some(field) => expect_string_value(&field.value)?,
+        None => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "subcommand is missing required field: name",
+            ));
+        }
+    };
+    let mut builder = CommandMetaBuilder::new(name);
+    for field in &obj.fields {
+        let v = &field.value;
+        builder = match field.key.to_string().as_str() {
+            "name" => builder,
+            "summary" => builder.summary(expect_string_value(v)?),
+            "usage" => builder.usage(expect_string_value(v)?),
+            "aliases" => expect_string_array_value(v)?
+                .into_iter()
+                .fold(builder, |b, alias| b.alias(alias)),
+            "hidden_aliases" => expect_string_array_value(v)?
+                .into_iter()
+                .fold(builder, |b, alias| b.hidden_alias(alias)),
+            "version" => builder.version(expect_string_value(v)?),
+            "hidden" => builder.hidden(expect_bool_value(v)?),
+            "description" => builder.description(expect_string_value(v)?),
+            "examples" => expect_string_array_value(v)?
+                .into_iter()
+                .fold(builder, |b, example| b.example(example)),
+            "args" => expect_args_array_value(v, shared)?
+                .into_iter()
+                .fold(builder, |b, arg| b.arg(arg)),
+            "groups" => expect_groups_array_value(v)?
+                .into_iter()
+                .fold(builder, |b, group| b.group(group)),
+            "subcommands" => expect_subcommands_array_value(v, shared)?
+                .into_iter()
+                .fold(builder, |b, sub| b.subcommand(sub)),
+            other => {
+                return Err(syn::Error::new(
+                    field.key.span(),
+                    format!("unknown subcommand field: {other}"),
+                ));
+            }
+        };
     }
+    Ok(builder)
+}
 
-    // Normalize short/long like the runtime builder.
-    if let Some(s) = a.short.clone() {
-        let trimmed = s.trim().to_string();
-        a.short = Some(if trimmed.starts_with('-') {
-            trimmed
-        } else {
-            format!("-{trimmed}")
-        });
+fn expect_tests_array_value(v: &Value) -> Result<Vec<SelfTest>> {
+    match v {
+        Value::ObjectArray(items) => items.iter().map(test_from_object).collect(),
+        _ => Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected tests: [ { cmd: \"...\", ... }, ... ]",
+        )),
     }
-    if let Some(s) = a.long.clone() {
-        let trimmed = s.trim().to_string();
-        a.long = Some(if trimmed.starts_with('-') {
-            trimmed
-        } else {
-            format!("--{trimmed}")
-        });
+}
+
+fn test_from_object(obj: &Object) -> Result<SelfTest> {
+    let mut cmd = None;
+    let mut test = SelfTest::default();
+    for field in &obj.fields {
+        let v = &field.value;
+        match field.key.to_string().as_str() {
+            "cmd" => cmd = Some(expect_string_value(v)?),
+            "expect_stdout" => test.expect_stdout = Some(expect_string_value(v)?),
+            "expect_exit" => test.expect_exit = expect_u32_value(v)?,
+            other => {
+                return Err(syn::Error::new(
+                    field.key.span(),
+                    format!("unknown test field: {other}"),
+                ));
+            }
+        }
     }
+    test.cmd = cmd.ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "test is missing required field: cmd",
+        )
+    })?;
+    Ok(test)
+}
 
+/// Build an arg from its macro entry, starting from the shared template when
+/// the entry has `use`. With `long_from_name`, an arg without `long` is
+/// addressed as `--<name>`.
+fn arg_from_object(
+    obj: &Object,
+    shared: &SharedArgsCtx,
+    long_from_name: bool,
+) -> Result<ArgSchemaBuilder> {
+    // (key, span for errors, value, template it came from)
+    let mut fields: Vec<(String, proc_macro2::Span, Value, Option<String>)> = Vec::new();
+    let mut reference = None;
+    if let Some(field) = obj.fields.iter().find(|field| field.key == "use") {
+        let Value::Str(template) = &field.value else {
+            return Err(syn::Error::new(field.key.span(), "expected string literal"));
+        };
+        let (template_fields, shared_ref) = shared.template(template)?;
+        for (key, value) in template_fields {
+            fields.push((key, template.span(), value, Some(template.value())));
+        }
+        reference = Some(shared_ref);
+    }
+    for field in obj.fields.iter().filter(|field| field.key != "use") {
+        let key = field.key.to_string();
+        fields.retain(|(k, ..)| *k != key);
+        fields.push((key, field.key.span(), field.value.clone(), None));
+    }
+
+    let name = match fields.iter().find(|(key, ..)| key == "name") {
+        Some((_, _, value, _)) => expect_string_value(value)?,
+        None => match &reference {
+            Some(shared_ref) => shared_ref.name.clone(),
+            None => {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "arg is missing required field: name",
+                ));
+            }
+        },
+    };
+
+    let mut a = ArgSchemaBuilder::new(name.clone());
+    for (key, span, value, template) in &fields {
+        a = apply_arg_field(a, key, *span, value).map_err(|err| match template {
+            Some(template) => {
+                syn::Error::new(*span, format!("shared arg template '{template}': {err}"))
+            }
+            None => err,
+        })?;
+    }
+    if long_from_name && !fields.iter().any(|(key, ..)| key == "long") {
+        a = a.long(name);
+    }
+    if let Some(shared_ref) = reference {
+        a = a.shared(shared_ref);
+    }
     Ok(a)
 }
+
+fn apply_arg_field(
+    a: ArgSchemaBuilder,
+    key: &str,
+    span: proc_macro2::Span,
+    v: &Value,
+) -> Result<ArgSchemaBuilder> {
+    Ok(match key {
+        "name" => a,
+        "short" => a.short(expect_string_value(v)?),
+        "long" => a.long(expect_string_value(v)?),
+        "help" => a.help(expect_string_value(v)?),
+        "required" => a.required(expect_bool_value(v)?),
+        "default_value" => a.default_value(expect_string_value(v)?),
+        "env" => a.env(expect_string_value(v)?),
+        "value_name" => a.value_name(expect_string_value(v)?),
+        "takes_value" => a.takes_value(expect_bool_value(v)?),
+        "multiple" => a.multiple(expect_bool_value(v)?),
+        "value_type" => a.value_type(expect_string_value(v)?),
+        "possible_values" => a.possible_values(expect_string_array_value(v)?),
+        "conflicts_with" => expect_string_array_value(v)?
+            .into_iter()
+            .fold(a, |a, name| a.conflicts_with(name)),
+        "requires" => expect_string_array_value(v)?
+            .into_iter()
+            .fold(a, |a, name| a.requires(name)),
+        "hidden" => a.hidden(expect_bool_value(v)?),
+        "complete_command" => a.complete_command(expect_string_value(v)?),
+        "group" => a.group(expect_string_value(v)?),
+        "allow_hyphen_values" => a.allow_hyphen_values(expect_bool_value(v)?),
+        "countable" => a.countable(expect_bool_value(v)?),
+        "var_arg" => a.var_arg(expect_bool_value(v)?),
+        other => {
+            return Err(syn::Error::new(span, format!("unknown arg field: {other}")));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGDEFS: &str = r#"{
+        "output": { "short": "-o", "long": "--output", "value-name": "FILE",
+                    "help": "Write the result to FILE" },
+        "level": { "long": "--level", "possible-values": ["low", "high"] },
+        "broken": { "long": "--broken", "colour": "red" }
+    }"#;
+
+    fn shared() -> SharedArgsCtx {
+        SharedArgsCtx::preloaded("argdefs.json", ARGDEFS)
+    }
+
+    fn arg(entry: &str) -> Result<wacli_metadata::ArgSchema> {
+        let obj: Object = syn::parse_str(entry).unwrap();
+        Ok(arg_from_object(&obj, &shared(), false)?.build())
+    }
+
+    #[test]
+    fn v2_section_is_embedded_with_the_feature() {
+        let decl: Decl = syn::parse_str(r#"show_meta, { name: "show" }"#).unwrap();
+        let tokens = expand_decl(decl).unwrap().to_string();
+        assert!(tokens.contains(wacli_metadata::COMMAND_METADATA_SECTION));
+        assert_eq!(
+            tokens.contains(wacli_metadata::COMMAND_METADATA_SECTION_V2),
+            cfg!(feature = "metadata-v2")
+        );
+    }
+
+    #[test]
+    fn payload_records_the_cdk_version() {
+        let decl: Decl = syn::parse_str(r#"show_meta, { name: "show" }"#).unwrap();
+        assert_eq!(
+            decl_payload(&decl).unwrap().0.cdk_version.as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn use_splices_the_template() {
+        let a = arg(r#"{ use: "output" }"#).unwrap();
+        assert_eq!(a.name, "output");
+        assert_eq!(a.short.as_deref(), Some("-o"));
+        assert_eq!(a.long.as_deref(), Some("--output"));
+        assert_eq!(a.value_name.as_deref(), Some("FILE"));
+        assert!(a.takes_value);
+        let shared_ref = a.shared.unwrap();
+        assert_eq!(shared_ref.name, "output");
+        assert_eq!(
+            Some(shared_ref.digest),
+            wacli_metadata::SharedArgs::from_json(ARGDEFS)
+                .unwrap()
+                .digest("output")
+        );
+    }
+
+    #[test]
+    fn entry_fields_replace_template_fields() {
+        let a =
+            arg(r#"{ use: "output", name: "out", help: "Report path", required: true }"#).unwrap();
+        assert_eq!(a.name, "out");
+        assert_eq!(a.help, "Report path");
+        assert!(a.required);
+        assert_eq!(a.short.as_deref(), Some("-o"));
+
+        // Lists are replaced, not appended to.
+        let a = arg(r#"{ use: "level", possible_values: ["max"] }"#).unwrap();
+        assert_eq!(a.possible_values, ["max"]);
+    }
+
+    #[test]
+    fn plain_entries_are_unchanged() {
+        let a = arg(r#"{ name: "text", value_name: "TEXT" }"#).unwrap();
+        assert_eq!(a.name, "text");
+        assert!(a.shared.is_none());
+        let err = arg(r#"{ help: "no name" }"#).err().unwrap();
+        assert_eq!(err.to_string(), "arg is missing required field: name");
+    }
+
+    #[test]
+    fn allow_hyphen_values_is_opt_in() {
+        let a = arg(r#"{ name: "filter", long: "filter", allow_hyphen_values: true }"#).unwrap();
+        assert!(a.allow_hyphen_values);
+        assert!(
+            !arg(r#"{ name: "out", long: "out" }"#)
+                .unwrap()
+                .allow_hyphen_values
+        );
+    }
+
+    #[test]
+    fn countable_is_a_bool_field() {
+        let a = arg(r#"{ name: "verbose", short: "-v", countable: true }"#).unwrap();
+        assert!(a.countable);
+        let err = arg(r#"{ name: "verbose", countable: "yes" }"#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("bool"), "{err}");
+    }
+
+    #[test]
+    fn missing_template_is_a_compile_error() {
+        let err = arg(r#"{ use: "outptu" }"#).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "unknown shared arg template 'outptu' (defined in argdefs.json: broken, level, output)"
+        );
+        assert!(err.to_compile_error().to_string().contains("compile_error"));
+    }
+
+    #[test]
+    fn template_errors_name_the_template() {
+        let err = arg(r#"{ use: "broken" }"#).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "shared arg template 'broken': unknown arg field: colour"
+        );
+    }
+
+    #[test]
+    fn unreadable_file_is_reported() {
+        let obj: Object = syn::parse_str(r#"{ use: "output" }"#).unwrap();
+        let missing = LitStr::new("/nonexistent/argdefs.json", proc_macro2::Span::call_site());
+        let err = arg_from_object(&obj, &SharedArgsCtx::new(Some(missing)), false)
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string()
+                .starts_with("failed to read shared args file /nonexistent/argdefs.json: "),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn subcommands_nest_and_keep_an_empty_version() {
+        let obj: Object = syn::parse_str(
+            r#"{ name: "remote", summary: "Manage remotes", subcommands: [
+                { name: "add", aliases: ["new"], hidden_aliases: ["mk"], version: "2.0.0",
+                  args: [{ name: "url", long: "url", takes_value: true }] },
+            ] }"#,
+        )
+        .unwrap();
+        let schema = subcommand_from_object(&obj, &shared())
+            .unwrap()
+            .build_schema();
+        assert_eq!(schema.name, "remote");
+        assert_eq!(schema.version, "");
+        let add = &schema.subcommands[0];
+        assert_eq!(add.aliases, ["new"]);
+        assert_eq!(add.hidden_aliases, ["mk"]);
+        assert_eq!(add.version, "2.0.0");
+        assert_eq!(add.args[0].long.as_deref(), Some("--url"));
+
+        let obj: Object = syn::parse_str(r#"{ summary: "x" }"#).unwrap();
+        let err = subcommand_from_object(&obj, &shared()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "subcommand is missing required field: name"
+        );
+        let obj: Object = syn::parse_str(r#"{ name: "x", tests: [] }"#).unwrap();
+        let err = subcommand_from_object(&obj, &shared()).err().unwrap();
+        assert_eq!(err.to_string(), "unknown subcommand field: tests");
+    }
+
+    #[test]
+    fn groups_are_declared_on_commands_and_joined_by_args() {
+        let obj: Object = syn::parse_str(
+            r#"{ name: "export", args: [
+                { name: "json", long: "json", group: "format" },
+                { name: "yaml", long: "yaml", group: "format" },
+            ], groups: [{ name: "format", required: true }] }"#,
+        )
+        .unwrap();
+        let schema = subcommand_from_object(&obj, &shared())
+            .unwrap()
+            .build_schema();
+        assert_eq!(schema.args[1].group.as_deref(), Some("format"));
+        assert_eq!(schema.groups, [ArgGroup::new("format").required(true)]);
+
+        let obj: Object = syn::parse_str(r#"{ required: true }"#).unwrap();
+        let err = group_from_object(&obj).err().unwrap();
+        assert_eq!(err.to_string(), "group is missing required field: name");
+        let obj: Object = syn::parse_str(r#"{ name: "format", exclusive: true }"#).unwrap();
+        let err = group_from_object(&obj).err().unwrap();
+        assert_eq!(err.to_string(), "unknown group field: exclusive");
+    }
+
+    #[test]
+    fn pipe_options_default_long_to_the_name() {
+        let obj: Object = syn::parse_str(r#"{ use: "level", long: "--lvl" }"#).unwrap();
+        let a = arg_from_object(&obj, &shared(), true).unwrap().build();
+        assert_eq!(a.long.as_deref(), Some("--lvl"));
+
+        let obj: Object = syn::parse_str(r#"{ name: "trim" }"#).unwrap();
+        let a = arg_from_object(&obj, &shared(), true).unwrap().build();
+        assert_eq!(a.long.as_deref(), Some("--trim"));
+        assert!(!a.takes_value);
+    }
+
+    #[test]
+    fn wacli_command_embeds_the_declare_payload() {
+        let mut item: syn::ItemStruct = syn::parse_str(
+            r#"
+            /// Show text
+            struct Show {
+                /// Output format
+                #[arg(long, short = 'f', default_value = "plain", possible_values("plain", "json"))]
+                format: String,
+                #[arg(long)]
+                upper: bool,
+                #[arg(long = "max-width")]
+                width: Option<u32>,
+                #[arg(long, hidden)]
+                tag: Vec<String>,
+                /// Text to show
+                text: Vec<String>,
+            }
+            "#,
+        )
+        .unwrap();
+        let attr = quote!(
+            name = "show",
+            version = "1.0.0",
+            aliases("s"),
+            run = Self::exec
+        );
+        let (decl, fields, run) = command_attr::command_decl(attr, &mut item).unwrap();
+        assert!(run.is_some());
+        assert!(
+            item.fields
+                .iter()
+                .all(|f| f.attrs.iter().all(|a| !a.path().is_ident("arg")))
+        );
+
+        let declared: Decl = syn::parse_str(
+            r#"show_meta, {
+                name: "show", version: "1.0.0", aliases: ["s"], summary: "Show text",
+                args: [
+                    { name: "format", long: "--format", short: "-f", default_value: "plain",
+                      possible_values: ["plain", "json"], help: "Output format",
+                      value_name: "FORMAT" },
+                    { name: "upper", long: "--upper" },
+                    { name: "width", long: "--max-width", value_name: "WIDTH",
+                      value_type: "uint" },
+                    { name: "tag", long: "--tag", hidden: true, value_name: "TAG",
+                      multiple: true },
+                    { name: "text", help: "Text to show", value_name: "TEXT", multiple: true,
+                      var_arg: true },
+                ],
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(decl_payload(&decl).unwrap().0.to_json_bytes()).unwrap(),
+            String::from_utf8(decl_payload(&declared).unwrap().0.to_json_bytes()).unwrap()
+        );
+        assert_eq!(fields[0].display(), "--format <FORMAT>");
+        assert_eq!(fields[2].display(), "--max-width <WIDTH>");
+        assert_eq!(fields[4].display(), "<TEXT>");
+    }
+
+    #[test]
+    fn wacli_command_requires_plain_fields_and_flags_for_bools() {
+        let attr = || quote!(name = "x");
+        let mut item: syn::ItemStruct =
+            syn::parse_str("struct X { path: String, #[arg(long, default_value = \"1\")] n: u8 }")
+                .unwrap();
+        let (decl, ..) = command_attr::command_decl(attr(), &mut item).unwrap();
+        let args = decl_payload(&decl).unwrap().0.command_meta.args;
+        assert!(args[0].required);
+        assert!(!args[1].required);
+
+        let mut item: syn::ItemStruct = syn::parse_str("struct X { verbose: bool }").unwrap();
+        let err = command_attr::command_decl(attr(), &mut item).err().unwrap();
+        assert_eq!(err.to_string(), "a bool field needs `long` or `short`");
+
+        let mut item: syn::ItemStruct =
+            syn::parse_str("struct X { #[arg(long, colour = \"red\")] a: String }").unwrap();
+        let err = command_attr::command_decl(attr(), &mut item).err().unwrap();
+        assert_eq!(err.to_string(), "unknown arg field: colour");
+
+        let mut item: syn::ItemStruct = syn::parse_str("struct X { a: String }").unwrap();
+        let err = command_attr::command_decl(quote!(name = "x", args = ["a"]), &mut item)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "`args` is not supported by #[wacli_command]"
+        );
+    }
+
+    #[test]
+    fn pipe_metadata_carries_descriptive_fields() {
+        let body: Body = syn::parse_str(
+            r#"{ summary: "Render a table", input_types: ["application/json"],
+                 output_type: "text/plain", version: "1.2.0",
+                 options: [{ name: "width", value_name: "N" }] }"#,
+        )
+        .unwrap();
+        let meta = pipe_metadata_payload(&body, &shared()).unwrap();
+        assert_eq!(meta.summary, "Render a table");
+        assert_eq!(meta.input_types, ["application/json"]);
+        assert_eq!(meta.output_type, "text/plain");
+        assert_eq!(meta.version, "1.2.0");
+        assert_eq!(meta.options[0].long.as_deref(), Some("--width"));
+
+        let body: Body = syn::parse_str(r#"{ summary: "x", output: "text/plain" }"#).unwrap();
+        let err = pipe_metadata_payload(&body, &shared()).err().unwrap();
+        assert_eq!(err.to_string(), "unknown field: output");
+    }
+}
@@ -0,0 +1,137 @@
+//! Loading shared arg templates for `{ use: "..." }` entries.
+//!
+//! The file is `shared_args_path` (relative to the crate's manifest dir) or,
+//! without it, `WACLI_SHARED_ARGS`. It is read only when an entry uses a
+//! template, and at most once per macro call.
+
+use proc_macro2::Span;
+use quote::quote;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use syn::{LitBool, LitStr, Result};
+use wacli_metadata::{SharedArgRef, SharedArgs};
+
+use crate::Value;
+
+pub(crate) const ENV_VAR: &str = "WACLI_SHARED_ARGS";
+
+/// A template field as a macro value: `(snake_case key, value)`.
+pub(crate) type TemplateField = (String, Value);
+
+#[derive(Default)]
+pub(crate) struct SharedArgsCtx {
+    /// `shared_args_path: "..."` from the macro input.
+    explicit: Option<LitStr>,
+    loaded: RefCell<Option<(PathBuf, SharedArgs)>>,
+}
+
+impl SharedArgsCtx {
+    pub(crate) fn new(explicit: Option<LitStr>) -> Self {
+        Self {
+            explicit,
+            loaded: RefCell::new(None),
+        }
+    }
+
+    /// The fields of template `name`, and the reference to record on the arg.
+    pub(crate) fn template(&self, name: &LitStr) -> Result<(Vec<TemplateField>, SharedArgRef)> {
+        self.load(name.span())?;
+        let loaded = self.loaded.borrow();
+        let (path, shared) = loaded.as_ref().expect("loaded above");
+        let template = name.value();
+        let Some(fields) = shared.get(&template) else {
+            let defined: Vec<&str> = shared.names().collect();
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "unknown shared arg template '{template}' (defined in {}: {})",
+                    path.display(),
+                    defined.join(", ")
+                ),
+            ));
+        };
+
+        let mut out = Vec::with_capacity(fields.len());
+        for (key, value) in fields {
+            let value = match value {
+                serde_json::Value::String(s) => Value::Str(LitStr::new(s, name.span())),
+                serde_json::Value::Bool(b) => Value::Bool(LitBool::new(*b, name.span())),
+                serde_json::Value::Array(items) => Value::StrArray(
+                    items
+                        .iter()
+                        .map(|item| item.as_str().map(|s| LitStr::new(s, name.span())))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| field_type_error(name, &template, key))?,
+                ),
+                _ => return Err(field_type_error(name, &template, key)),
+            };
+            out.push((key.replace('-', "_"), value));
+        }
+        let reference = shared.reference(&template).expect("template exists");
+        Ok((out, reference))
+    }
+
+    /// `include_bytes!` of the file, so editing it rebuilds the command.
+    pub(crate) fn dependency(&self) -> proc_macro2::TokenStream {
+        match self.loaded.borrow().as_ref() {
+            Some((path, _)) => {
+                let path = LitStr::new(&path.to_string_lossy(), Span::call_site());
+                quote! { const _: &[u8] = include_bytes!(#path); }
+            }
+            None => quote! {},
+        }
+    }
+
+    fn load(&self, span: Span) -> Result<()> {
+        if self.loaded.borrow().is_some() {
+            return Ok(());
+        }
+        let (raw, span) = match &self.explicit {
+            Some(lit) => (lit.value(), lit.span()),
+            None => match std::env::var(ENV_VAR) {
+                Ok(raw) if !raw.is_empty() => (raw, span),
+                _ => {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "`use` needs a shared args file: set shared_args_path or {ENV_VAR}"
+                        ),
+                    ));
+                }
+            },
+        };
+        let mut path = PathBuf::from(&raw);
+        if path.is_relative()
+            && let Ok(dir) = std::env::var("CARGO_MANIFEST_DIR")
+        {
+            path = PathBuf::from(dir).join(path);
+        }
+        let json = std::fs::read_to_string(&path).map_err(|e| {
+            syn::Error::new(
+                span,
+                format!("failed to read shared args file {}: {e}", path.display()),
+            )
+        })?;
+        let shared = SharedArgs::from_json(&json)
+            .map_err(|e| syn::Error::new(span, format!("{}: {e}", path.display())))?;
+        *self.loaded.borrow_mut() = Some((path, shared));
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn preloaded(path: &str, json: &str) -> Self {
+        let ctx = Self::default();
+        *ctx.loaded.borrow_mut() =
+            Some((PathBuf::from(path), SharedArgs::from_json(json).unwrap()));
+        ctx
+    }
+}
+
+fn field_type_error(name: &LitStr, template: &str, key: &str) -> syn::Error {
+    syn::Error::new(
+        name.span(),
+        format!(
+            "shared arg template '{template}': field '{key}' must be a string, boolean or list of strings"
+        ),
+    )
+}
@@ -132,6 +132,121 @@ pub mod wacli {
                     result11
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            /// The value of the environment variable `key` (case-sensitive).
+            #[allow(async_fn_in_trait)]
+            pub fn get(key: &str) -> Option<_rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "get"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// The working directory relative paths resolve against (`.` when the
+            /// runtime reports none).
+            #[allow(async_fn_in_trait)]
+            pub fn cwd() -> _rt::String {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "cwd"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import1(ptr0);
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len4 = l3;
+                    let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                    let result5 = _rt::string_lift(bytes4);
+                    result5
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Monotonic clock reading in nanoseconds, for measuring durations only.
+            #[allow(async_fn_in_trait)]
+            pub fn monotonic_now() -> u64 {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-env@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "monotonic-now"]
+                        fn wit_import0() -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import0();
+                    ret as u64
+                }
+            }
         }
         #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod host_io {
@@ -213,16 +328,153 @@ pub mod wacli {
                     wit_import0();
                 }
             }
-        }
-        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-        pub mod host_fs {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
             #[allow(unused_unsafe, clippy::all)]
+            /// Whether stdout is attached to a terminal (false when piped or captured).
             #[allow(async_fn_in_trait)]
-            pub fn read_file(path: &str) -> Result<_rt::Vec<u8>, _rt::String> {
+            pub fn stdout_is_terminal() -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "stdout-is-terminal"]
+                        fn wit_import0() -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import0();
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Whether stderr is attached to a terminal (false when piped or captured).
+            #[allow(async_fn_in_trait)]
+            pub fn stderr_is_terminal() -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "stderr-is-terminal"]
+                        fn wit_import0() -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import0();
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Whether stdin is attached to a terminal (false when piped or empty).
+            #[allow(async_fn_in_trait)]
+            pub fn stdin_is_terminal() -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "stdin-is-terminal"]
+                        fn wit_import0() -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0() -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import0();
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Read one line from stdin, without its line ending; `none` at end of
+            /// input, `err` when the read fails.
+            #[allow(async_fn_in_trait)]
+            pub fn stdin_read_line() -> Result<Option<_rt::String>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 4 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 4
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "stdin-read-line"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import1(ptr0);
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result10 = match l2 {
+                        0 => {
+                            let e = {
+                                let l3 = i32::from(
+                                    *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l3 {
+                                    0 => None,
+                                    1 => {
+                                        let e = {
+                                            let l4 = *ptr0
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l5 = *ptr0
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len6 = l5;
+                                            let bytes6 = _rt::Vec::from_raw_parts(
+                                                l4.cast(),
+                                                len6,
+                                                len6,
+                                            );
+                                            _rt::string_lift(bytes6)
+                                        };
+                                        Some(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l8 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
+                                );
+                                _rt::string_lift(bytes9)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result10
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Write `prompt` to stderr and read one line from stdin like
+            /// `stdin-read-line`, without echoing it when stdin is a terminal (a plain
+            /// read otherwise). End of input is an `err`.
+            #[allow(async_fn_in_trait)]
+            pub fn read_secret(prompt: &str) -> Result<_rt::String, _rt::String> {
                 unsafe {
                     #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                     #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
@@ -235,14 +487,14 @@ pub mod wacli {
                         [::core::mem::MaybeUninit::uninit(); 3
                             * ::core::mem::size_of::<*const u8>()],
                     );
-                    let vec0 = path;
+                    let vec0 = prompt;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
                     unsafe extern "C" {
-                        #[link_name = "read-file"]
+                        #[link_name = "read-secret"]
                         fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
@@ -261,9 +513,12 @@ pub mod wacli {
                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>();
                                 let len6 = l5;
-                                <_ as From<
-                                    _rt::Vec<_>,
-                                >>::from(_rt::Vec::from_raw_parts(l4.cast(), len6, len6))
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
                             };
                             Ok(e)
                         }
@@ -291,8 +546,10 @@ pub mod wacli {
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
+            /// Read up to `len` bytes from stdin; empty at end of input, `err` when
+            /// the read fails.
             #[allow(async_fn_in_trait)]
-            pub fn write_file(path: &str, contents: &[u8]) -> Result<(), _rt::String> {
+            pub fn stdin_read(len: u64) -> Result<_rt::Vec<u8>, _rt::String> {
                 unsafe {
                     #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                     #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
@@ -305,68 +562,63 @@ pub mod wacli {
                         [::core::mem::MaybeUninit::uninit(); 3
                             * ::core::mem::size_of::<*const u8>()],
                     );
-                    let vec0 = path;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let vec1 = contents;
-                    let ptr1 = vec1.as_ptr().cast::<u8>();
-                    let len1 = vec1.len();
-                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
                     unsafe extern "C" {
-                        #[link_name = "write-file"]
-                        fn wit_import3(
-                            _: *mut u8,
-                            _: usize,
-                            _: *mut u8,
-                            _: usize,
-                            _: *mut u8,
-                        );
+                        #[link_name = "stdin-read"]
+                        fn wit_import1(_: i64, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn wit_import3(
-                        _: *mut u8,
-                        _: usize,
-                        _: *mut u8,
-                        _: usize,
-                        _: *mut u8,
-                    ) {
+                    unsafe extern "C" fn wit_import1(_: i64, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
-                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
-                    let result8 = match l4 {
+                    wit_import1(_rt::as_i64(&len), ptr0);
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result9 = match l2 {
                         0 => {
-                            let e = ();
+                            let e = {
+                                let l3 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l4 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len5 = l4;
+                                <_ as From<
+                                    _rt::Vec<_>,
+                                >>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
+                            };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l5 = *ptr2
+                                let l6 = *ptr0
                                     .add(::core::mem::size_of::<*const u8>())
                                     .cast::<*mut u8>();
-                                let l6 = *ptr2
+                                let l7 = *ptr0
                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>();
-                                let len7 = l6;
-                                let bytes7 = _rt::Vec::from_raw_parts(
-                                    l5.cast(),
-                                    len7,
-                                    len7,
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
                                 );
-                                _rt::string_lift(bytes7)
+                                _rt::string_lift(bytes8)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
                     };
-                    result8
+                    result9
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
+            /// Read the rest of stdin; empty at end of input, `err` when the read
+            /// fails.
             #[allow(async_fn_in_trait)]
-            pub fn create_dir(path: &str) -> Result<(), _rt::String> {
+            pub fn stdin_read_all() -> Result<_rt::Vec<u8>, _rt::String> {
                 unsafe {
                     #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                     #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
@@ -379,53 +631,292 @@ pub mod wacli {
                         [::core::mem::MaybeUninit::uninit(); 3
                             * ::core::mem::size_of::<*const u8>()],
                     );
-                    let vec0 = path;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    #[link(wasm_import_module = "wacli:cli/host-io@2.0.0")]
                     unsafe extern "C" {
-                        #[link_name = "create-dir"]
-                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                        #[link_name = "stdin-read-all"]
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import2(ptr0.cast_mut(), len0, ptr1);
-                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
-                    let result7 = match l3 {
+                    wit_import1(ptr0);
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result9 = match l2 {
                         0 => {
-                            let e = ();
+                            let e = {
+                                let l3 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l4 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len5 = l4;
+                                <_ as From<
+                                    _rt::Vec<_>,
+                                >>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
+                            };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l4 = *ptr1
+                                let l6 = *ptr0
                                     .add(::core::mem::size_of::<*const u8>())
                                     .cast::<*mut u8>();
-                                let l5 = *ptr1
+                                let l7 = *ptr0
                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>();
-                                let len6 = l5;
-                                let bytes6 = _rt::Vec::from_raw_parts(
-                                    l4.cast(),
-                                    len6,
-                                    len6,
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
                                 );
-                                _rt::string_lift(bytes6)
+                                _rt::string_lift(bytes8)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
                     };
-                    result7
+                    result9
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod host_log {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// Message severity, least severe first.
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum Level {
+                Trace,
+                Debug,
+                Info,
+                Warn,
+                Error,
+            }
+            impl ::core::fmt::Debug for Level {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        Level::Trace => f.debug_tuple("Level::Trace").finish(),
+                        Level::Debug => f.debug_tuple("Level::Debug").finish(),
+                        Level::Info => f.debug_tuple("Level::Info").finish(),
+                        Level::Warn => f.debug_tuple("Level::Warn").finish(),
+                        Level::Error => f.debug_tuple("Level::Error").finish(),
+                    }
+                }
+            }
+            impl Level {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> Level {
+                    if !cfg!(debug_assertions) {
+                        return unsafe { ::core::mem::transmute(val) };
+                    }
+                    match val {
+                        0 => Level::Trace,
+                        1 => Level::Debug,
+                        2 => Level::Info,
+                        3 => Level::Warn,
+                        4 => Level::Error,
+                        _ => panic!("invalid enum discriminant"),
+                    }
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
+            /// Write `message` to stderr as `[LEVEL target] message`, unless `level`
+            /// is below the max level (then nothing is formatted or written).
             #[allow(async_fn_in_trait)]
-            pub fn list_dir(path: &str) -> Result<_rt::Vec<_rt::String>, _rt::String> {
+            pub fn log(level: Level, target: &str, message: &str) -> () {
+                unsafe {
+                    let vec0 = target;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = message;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-log@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "log"]
+                        fn wit_import2(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import2(
+                        level.clone() as i32,
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                    );
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Whether a message at `level` would be written; check it before
+            /// building an expensive message. The max level is `warn` unless core
+            /// changes it (see `host-control`).
+            #[allow(async_fn_in_trait)]
+            pub fn enabled(level: Level) -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-log@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "enabled"]
+                        fn wit_import0(_: i32) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i32) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import0(level.clone() as i32);
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod host_fs {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum FileKind {
+                File,
+                Dir,
+                Symlink,
+                Other,
+            }
+            impl ::core::fmt::Debug for FileKind {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        FileKind::File => f.debug_tuple("FileKind::File").finish(),
+                        FileKind::Dir => f.debug_tuple("FileKind::Dir").finish(),
+                        FileKind::Symlink => f.debug_tuple("FileKind::Symlink").finish(),
+                        FileKind::Other => f.debug_tuple("FileKind::Other").finish(),
+                    }
+                }
+            }
+            impl FileKind {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> FileKind {
+                    if !cfg!(debug_assertions) {
+                        return unsafe { ::core::mem::transmute(val) };
+                    }
+                    match val {
+                        0 => FileKind::File,
+                        1 => FileKind::Dir,
+                        2 => FileKind::Symlink,
+                        3 => FileKind::Other,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct FileInfo {
+                pub kind: FileKind,
+                pub size: u64,
+                /// Last modification time in unix milliseconds (0 if unknown).
+                pub modified_at: u64,
+            }
+            impl ::core::fmt::Debug for FileInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("FileInfo")
+                        .field("kind", &self.kind)
+                        .field("size", &self.size)
+                        .field("modified-at", &self.modified_at)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct DirEntry {
+                /// Path relative to the walked directory, `/`-separated.
+                pub path: _rt::String,
+                pub kind: FileKind,
+            }
+            impl ::core::fmt::Debug for DirEntry {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("DirEntry")
+                        .field("path", &self.path)
+                        .field("kind", &self.kind)
+                        .finish()
+                }
+            }
+            /// A file kept open across writes (see `open-writer`).
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct Writer {
+                handle: _rt::Resource<Writer>,
+            }
+            impl Writer {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for Writer {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "[resource-drop]writer"]
+                        fn drop(_: i32);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn drop(_: i32) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        drop(_handle as i32);
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            #[allow(async_fn_in_trait)]
+            pub fn read_file(path: &str) -> Result<_rt::Vec<u8>, _rt::String> {
                 unsafe {
                     #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                     #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
@@ -445,7 +936,7 @@ pub mod wacli {
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
                     unsafe extern "C" {
-                        #[link_name = "list-dir"]
+                        #[link_name = "read-file"]
                         fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
@@ -454,7 +945,7 @@ pub mod wacli {
                     }
                     wit_import2(ptr0.cast_mut(), len0, ptr1);
                     let l3 = i32::from(*ptr1.add(0).cast::<u8>());
-                    let result13 = match l3 {
+                    let result10 = match l3 {
                         0 => {
                             let e = {
                                 let l4 = *ptr1
@@ -463,346 +954,1510 @@ pub mod wacli {
                                 let l5 = *ptr1
                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>();
-                                let base9 = l4;
-                                let len9 = l5;
-                                let mut result9 = _rt::Vec::with_capacity(len9);
-                                for i in 0..len9 {
-                                    let base = base9
-                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
-                                    let e9 = {
-                                        let l6 = *base.add(0).cast::<*mut u8>();
-                                        let l7 = *base
-                                            .add(::core::mem::size_of::<*const u8>())
-                                            .cast::<usize>();
-                                        let len8 = l7;
-                                        let bytes8 = _rt::Vec::from_raw_parts(
-                                            l6.cast(),
-                                            len8,
-                                            len8,
-                                        );
-                                        _rt::string_lift(bytes8)
-                                    };
-                                    result9.push(e9);
-                                }
-                                _rt::cabi_dealloc(
-                                    base9,
-                                    len9 * (2 * ::core::mem::size_of::<*const u8>()),
-                                    ::core::mem::size_of::<*const u8>(),
-                                );
-                                result9
+                                let len6 = l5;
+                                <_ as From<
+                                    _rt::Vec<_>,
+                                >>::from(_rt::Vec::from_raw_parts(l4.cast(), len6, len6))
                             };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l10 = *ptr1
+                                let l7 = *ptr1
                                     .add(::core::mem::size_of::<*const u8>())
                                     .cast::<*mut u8>();
-                                let l11 = *ptr1
+                                let l8 = *ptr1
                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                     .cast::<usize>();
-                                let len12 = l11;
-                                let bytes12 = _rt::Vec::from_raw_parts(
-                                    l10.cast(),
-                                    len12,
-                                    len12,
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
                                 );
-                                _rt::string_lift(bytes12)
+                                _rt::string_lift(bytes9)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
                     };
-                    result13
-                }
-            }
-        }
-        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-        pub mod types {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
-            pub type ExitCode = u32;
-            #[derive(Clone)]
-            pub struct ArgDef {
-                pub name: _rt::String,
-                pub short: Option<_rt::String>,
-                pub long: Option<_rt::String>,
-                pub help: _rt::String,
-                pub required: bool,
-                pub default_value: Option<_rt::String>,
-                pub value_name: Option<_rt::String>,
-                pub takes_value: bool,
-            }
-            impl ::core::fmt::Debug for ArgDef {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    f.debug_struct("ArgDef")
-                        .field("name", &self.name)
-                        .field("short", &self.short)
-                        .field("long", &self.long)
-                        .field("help", &self.help)
-                        .field("required", &self.required)
-                        .field("default-value", &self.default_value)
-                        .field("value-name", &self.value_name)
-                        .field("takes-value", &self.takes_value)
-                        .finish()
-                }
-            }
-            #[derive(Clone)]
-            pub struct CommandMeta {
-                pub name: _rt::String,
-                pub summary: _rt::String,
-                pub usage: _rt::String,
-                pub aliases: _rt::Vec<_rt::String>,
-                pub version: _rt::String,
-                pub hidden: bool,
-                pub description: _rt::String,
-                pub examples: _rt::Vec<_rt::String>,
-                pub args: _rt::Vec<ArgDef>,
-            }
-            impl ::core::fmt::Debug for CommandMeta {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    f.debug_struct("CommandMeta")
-                        .field("name", &self.name)
-                        .field("summary", &self.summary)
-                        .field("usage", &self.usage)
-                        .field("aliases", &self.aliases)
-                        .field("version", &self.version)
-                        .field("hidden", &self.hidden)
-                        .field("description", &self.description)
-                        .field("examples", &self.examples)
-                        .field("args", &self.args)
-                        .finish()
-                }
-            }
-            #[derive(Clone)]
-            pub enum CommandError {
-                UnknownCommand(_rt::String),
-                InvalidArgs(_rt::String),
-                Failed(_rt::String),
-                Io(_rt::String),
-            }
-            impl ::core::fmt::Debug for CommandError {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    match self {
-                        CommandError::UnknownCommand(e) => {
-                            f.debug_tuple("CommandError::UnknownCommand")
-                                .field(e)
-                                .finish()
-                        }
-                        CommandError::InvalidArgs(e) => {
-                            f.debug_tuple("CommandError::InvalidArgs").field(e).finish()
-                        }
-                        CommandError::Failed(e) => {
-                            f.debug_tuple("CommandError::Failed").field(e).finish()
-                        }
-                        CommandError::Io(e) => {
-                            f.debug_tuple("CommandError::Io").field(e).finish()
-                        }
-                    }
-                }
-            }
-            pub type CommandResult = Result<ExitCode, CommandError>;
-            #[derive(Clone)]
-            pub struct PipeMeta {
-                pub name: _rt::String,
-                pub summary: _rt::String,
-                pub input_types: _rt::Vec<_rt::String>,
-                pub output_type: _rt::String,
-                pub version: _rt::String,
-            }
-            impl ::core::fmt::Debug for PipeMeta {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    f.debug_struct("PipeMeta")
-                        .field("name", &self.name)
-                        .field("summary", &self.summary)
-                        .field("input-types", &self.input_types)
-                        .field("output-type", &self.output_type)
-                        .field("version", &self.version)
-                        .finish()
-                }
-            }
-            #[derive(Clone)]
-            pub enum PipeError {
-                ParseError(_rt::String),
-                TransformError(_rt::String),
-                InvalidOption(_rt::String),
-            }
-            impl ::core::fmt::Debug for PipeError {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    match self {
-                        PipeError::ParseError(e) => {
-                            f.debug_tuple("PipeError::ParseError").field(e).finish()
-                        }
-                        PipeError::TransformError(e) => {
-                            f.debug_tuple("PipeError::TransformError").field(e).finish()
-                        }
-                        PipeError::InvalidOption(e) => {
-                            f.debug_tuple("PipeError::InvalidOption").field(e).finish()
-                        }
-                    }
-                }
-            }
-            impl ::core::fmt::Display for PipeError {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    write!(f, "{:?}", self)
-                }
-            }
-            impl ::core::error::Error for PipeError {}
-            #[derive(Clone)]
-            pub struct PipeInfo {
-                pub name: _rt::String,
-                pub summary: _rt::String,
-                pub path: _rt::String,
-            }
-            impl ::core::fmt::Debug for PipeInfo {
-                fn fmt(
-                    &self,
-                    f: &mut ::core::fmt::Formatter<'_>,
-                ) -> ::core::fmt::Result {
-                    f.debug_struct("PipeInfo")
-                        .field("name", &self.name)
-                        .field("summary", &self.summary)
-                        .field("path", &self.path)
-                        .finish()
-                }
-            }
-        }
-        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-        pub mod host_process {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
-            pub type ExitCode = super::super::super::wacli::cli::types::ExitCode;
-            #[allow(unused_unsafe, clippy::all)]
-            #[allow(async_fn_in_trait)]
-            pub fn exit(code: ExitCode) -> () {
-                unsafe {
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-process@2.0.0")]
-                    unsafe extern "C" {
-                        #[link_name = "exit"]
-                        fn wit_import0(_: i32);
-                    }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn wit_import0(_: i32) {
-                        unreachable!()
-                    }
-                    wit_import0(_rt::as_i32(code));
-                }
-            }
-        }
-        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
-        pub mod host_pipes {
-            #[used]
-            #[doc(hidden)]
-            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
-            use super::super::super::_rt;
-            pub type PipeMeta = super::super::super::wacli::cli::types::PipeMeta;
-            pub type PipeError = super::super::super::wacli::cli::types::PipeError;
-            pub type PipeInfo = super::super::super::wacli::cli::types::PipeInfo;
-            #[derive(Debug)]
-            #[repr(transparent)]
-            pub struct Pipe {
-                handle: _rt::Resource<Pipe>,
-            }
-            impl Pipe {
-                #[doc(hidden)]
-                pub unsafe fn from_handle(handle: u32) -> Self {
-                    Self {
-                        handle: unsafe { _rt::Resource::from_handle(handle) },
-                    }
-                }
-                #[doc(hidden)]
-                pub fn take_handle(&self) -> u32 {
-                    _rt::Resource::take_handle(&self.handle)
-                }
-                #[doc(hidden)]
-                pub fn handle(&self) -> u32 {
-                    _rt::Resource::handle(&self.handle)
-                }
-            }
-            unsafe impl _rt::WasmResource for Pipe {
-                #[inline]
-                unsafe fn drop(_handle: u32) {
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
-                    unsafe extern "C" {
-                        #[link_name = "[resource-drop]pipe"]
-                        fn drop(_: i32);
-                    }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn drop(_: i32) {
-                        unreachable!()
-                    }
-                    unsafe {
-                        drop(_handle as i32);
-                    }
+                    result10
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             #[allow(async_fn_in_trait)]
-            pub fn list_pipes() -> _rt::Vec<PipeInfo> {
+            pub fn write_file(path: &str, contents: &[u8]) -> Result<(), _rt::String> {
                 unsafe {
                     #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                     #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
                     struct RetArea(
                         [::core::mem::MaybeUninit<
                             u8,
-                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
                     );
                     let mut ret_area = RetArea(
-                        [::core::mem::MaybeUninit::uninit(); 2
+                        [::core::mem::MaybeUninit::uninit(); 3
                             * ::core::mem::size_of::<*const u8>()],
                     );
-                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = contents;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
                     unsafe extern "C" {
-                        #[link_name = "list-pipes"]
-                        fn wit_import1(_: *mut u8);
+                        #[link_name = "write-file"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
                         unreachable!()
                     }
-                    wit_import1(ptr0);
-                    let l2 = *ptr0.add(0).cast::<*mut u8>();
-                    let l3 = *ptr0
-                        .add(::core::mem::size_of::<*const u8>())
-                        .cast::<usize>();
-                    let base13 = l2;
-                    let len13 = l3;
-                    let mut result13 = _rt::Vec::with_capacity(len13);
-                    for i in 0..len13 {
-                        let base = base13
-                            .add(i * (6 * ::core::mem::size_of::<*const u8>()));
-                        let e13 = {
-                            let l4 = *base.add(0).cast::<*mut u8>();
-                            let l5 = *base
-                                .add(::core::mem::size_of::<*const u8>())
-                                .cast::<usize>();
-                            let len6 = l5;
+                    wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            #[allow(async_fn_in_trait)]
+            pub fn create_dir(path: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "create-dir"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            #[allow(async_fn_in_trait)]
+            pub fn list_dir(path: &str) -> Result<_rt::Vec<_rt::String>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "list-dir"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result13 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base9 = l4;
+                                let len9 = l5;
+                                let mut result9 = _rt::Vec::with_capacity(len9);
+                                for i in 0..len9 {
+                                    let base = base9
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    let e9 = {
+                                        let l6 = *base.add(0).cast::<*mut u8>();
+                                        let l7 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len8 = l7;
+                                        let bytes8 = _rt::Vec::from_raw_parts(
+                                            l6.cast(),
+                                            len8,
+                                            len8,
+                                        );
+                                        _rt::string_lift(bytes8)
+                                    };
+                                    result9.push(e9);
+                                }
+                                _rt::cabi_dealloc(
+                                    base9,
+                                    len9 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result9
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l10 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l11 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len12 = l11;
+                                let bytes12 = _rt::Vec::from_raw_parts(
+                                    l10.cast(),
+                                    len12,
+                                    len12,
+                                );
+                                _rt::string_lift(bytes12)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result13
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Copy `src` to `dst` (created or truncated), streaming in chunks so large
+            /// files are never held in memory. Returns the number of bytes copied.
+            #[allow(async_fn_in_trait)]
+            pub fn copy_file(src: &str, dst: &str) -> Result<u64, _rt::String> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 8 + 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 8
+                            + 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = src;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = dst;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "copy-file"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result9 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = *ptr2.add(8).cast::<i64>();
+                                l5 as u64
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l6 = *ptr2.add(8).cast::<*mut u8>();
+                                let l7 = *ptr2
+                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
+                                );
+                                _rt::string_lift(bytes8)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Move `src` to `dst`, replacing a file (or empty directory) already
+            /// there. Both must be under the same preopened directory; across mounts
+            /// it fails, and `copy-file` then `remove-file` has to be used instead.
+            #[allow(async_fn_in_trait)]
+            pub fn rename(src: &str, dst: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = src;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = dst;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "rename"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Remove a file (or symlink).
+            #[allow(async_fn_in_trait)]
+            pub fn remove_file(path: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-file"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Remove an empty directory.
+            #[allow(async_fn_in_trait)]
+            pub fn remove_dir(path: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-dir"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Remove a directory and everything in it. Symlinks are removed, not followed.
+            #[allow(async_fn_in_trait)]
+            pub fn remove_dir_all(path: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-dir-all"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Metadata of `path` itself: a symlink is reported as `symlink`, not
+            /// as what it points at.
+            #[allow(async_fn_in_trait)]
+            pub fn stat(path: &str) -> Result<FileInfo, _rt::String> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 32]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 32]);
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "stat"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result10 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = i32::from(*ptr1.add(8).cast::<u8>());
+                                let l5 = *ptr1.add(16).cast::<i64>();
+                                let l6 = *ptr1.add(24).cast::<i64>();
+                                FileInfo {
+                                    kind: FileKind::_lift(l4 as u8),
+                                    size: l5 as u64,
+                                    modified_at: l6 as u64,
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = *ptr1.add(8).cast::<*mut u8>();
+                                let l8 = *ptr1
+                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
+                                );
+                                _rt::string_lift(bytes9)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result10
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Whether `path` exists (following symlinks). Never fails.
+            #[allow(async_fn_in_trait)]
+            pub fn exists(path: &str) -> bool {
+                unsafe {
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "exists"]
+                        fn wit_import1(_: *mut u8, _: usize) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import1(ptr0.cast_mut(), len0);
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Everything below `path`, depth-first with each directory's entries
+            /// sorted by name. `max-depth` 1 lists direct children only; `none` has no
+            /// limit. Symlinks are listed but never followed.
+            #[allow(async_fn_in_trait)]
+            pub fn walk_dir(
+                path: &str,
+                max_depth: Option<u32>,
+            ) -> Result<_rt::Vec<DirEntry>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result1_0, result1_1) = match max_depth {
+                        Some(e) => (1i32, _rt::as_i32(e)),
+                        None => (0i32, 0i32),
+                    };
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "walk-dir"]
+                        fn wit_import3(_: *mut u8, _: usize, _: i32, _: i32, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: i32,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import3(ptr0.cast_mut(), len0, result1_0, result1_1, ptr2);
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result15 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base11 = l5;
+                                let len11 = l6;
+                                let mut result11 = _rt::Vec::with_capacity(len11);
+                                for i in 0..len11 {
+                                    let base = base11
+                                        .add(i * (3 * ::core::mem::size_of::<*const u8>()));
+                                    let e11 = {
+                                        let l7 = *base.add(0).cast::<*mut u8>();
+                                        let l8 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len9 = l8;
+                                        let bytes9 = _rt::Vec::from_raw_parts(
+                                            l7.cast(),
+                                            len9,
+                                            len9,
+                                        );
+                                        let l10 = i32::from(
+                                            *base
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
+                                        DirEntry {
+                                            path: _rt::string_lift(bytes9),
+                                            kind: FileKind::_lift(l10 as u8),
+                                        }
+                                    };
+                                    result11.push(e11);
+                                }
+                                _rt::cabi_dealloc(
+                                    base11,
+                                    len11 * (3 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result11
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l12 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l13 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len14 = l13;
+                                let bytes14 = _rt::Vec::from_raw_parts(
+                                    l12.cast(),
+                                    len14,
+                                    len14,
+                                );
+                                _rt::string_lift(bytes14)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result15
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Append `contents` to `path`, creating it if missing.
+            #[allow(async_fn_in_trait)]
+            pub fn append_file(path: &str, contents: &[u8]) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = contents;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "append-file"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Open `path` for incremental writes, creating it if missing. Without
+            /// `append` it is truncated first.
+            #[allow(async_fn_in_trait)]
+            pub fn open_writer(path: &str, append: bool) -> Result<Writer, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "open-writer"]
+                        fn wit_import2(_: *mut u8, _: usize, _: i32, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    wit_import2(
+                        ptr0.cast_mut(),
+                        len0,
+                        match &append {
+                            true => 1,
+                            false => 0,
+                        },
+                        ptr1,
+                    );
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result8 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>();
+                                Writer::from_handle(l4 as u32)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            impl Writer {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Write all of `bytes` after what was written so far.
+                #[allow(async_fn_in_trait)]
+                pub fn write(&self, bytes: &[u8]) -> Result<(), _rt::String> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 3 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 3
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec0 = bytes;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]writer.write"]
+                            fn wit_import2(_: i32, _: *mut u8, _: usize, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import2(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import2((self).handle() as i32, ptr0.cast_mut(), len0, ptr1);
+                        let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                        let result7 = match l3 {
+                            0 => {
+                                let e = ();
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l4 = *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len6 = l5;
+                                    let bytes6 = _rt::Vec::from_raw_parts(
+                                        l4.cast(),
+                                        len6,
+                                        len6,
+                                    );
+                                    _rt::string_lift(bytes6)
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result7
+                    }
+                }
+            }
+            impl Writer {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Flush and close. Writing afterwards fails; closing again does nothing.
+                #[allow(async_fn_in_trait)]
+                pub fn close(&self) -> Result<(), _rt::String> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 3 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 3
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "wacli:cli/host-fs@2.0.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]writer.close"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import1((self).handle() as i32, ptr0);
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result6 = match l2 {
+                            0 => {
+                                let e = ();
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l3 = *ptr0
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *ptr0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len5 = l4;
+                                    let bytes5 = _rt::Vec::from_raw_parts(
+                                        l3.cast(),
+                                        len5,
+                                        len5,
+                                    );
+                                    _rt::string_lift(bytes5)
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result6
+                    }
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod types {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type ExitCode = u32;
+            #[derive(Clone)]
+            pub struct ArgDef {
+                pub name: _rt::String,
+                pub short: Option<_rt::String>,
+                pub long: Option<_rt::String>,
+                pub help: _rt::String,
+                pub required: bool,
+                pub default_value: Option<_rt::String>,
+                pub value_name: Option<_rt::String>,
+                pub takes_value: bool,
+            }
+            impl ::core::fmt::Debug for ArgDef {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ArgDef")
+                        .field("name", &self.name)
+                        .field("short", &self.short)
+                        .field("long", &self.long)
+                        .field("help", &self.help)
+                        .field("required", &self.required)
+                        .field("default-value", &self.default_value)
+                        .field("value-name", &self.value_name)
+                        .field("takes-value", &self.takes_value)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct CommandMeta {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub usage: _rt::String,
+                pub aliases: _rt::Vec<_rt::String>,
+                pub version: _rt::String,
+                pub hidden: bool,
+                pub description: _rt::String,
+                pub examples: _rt::Vec<_rt::String>,
+                pub args: _rt::Vec<ArgDef>,
+            }
+            impl ::core::fmt::Debug for CommandMeta {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("CommandMeta")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("usage", &self.usage)
+                        .field("aliases", &self.aliases)
+                        .field("version", &self.version)
+                        .field("hidden", &self.hidden)
+                        .field("description", &self.description)
+                        .field("examples", &self.examples)
+                        .field("args", &self.args)
+                        .finish()
+                }
+            }
+            /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+            /// the same case count, so older plugins would stop composing). Until a
+            /// new major version adds an exit-code case, a `failed` message of the
+            /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+            /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+            /// `unknown-command` with 127 and `failed` / `io` with 1.
+            #[derive(Clone)]
+            pub enum CommandError {
+                UnknownCommand(_rt::String),
+                InvalidArgs(_rt::String),
+                Failed(_rt::String),
+                Io(_rt::String),
+            }
+            impl ::core::fmt::Debug for CommandError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        CommandError::UnknownCommand(e) => {
+                            f.debug_tuple("CommandError::UnknownCommand")
+                                .field(e)
+                                .finish()
+                        }
+                        CommandError::InvalidArgs(e) => {
+                            f.debug_tuple("CommandError::InvalidArgs").field(e).finish()
+                        }
+                        CommandError::Failed(e) => {
+                            f.debug_tuple("CommandError::Failed").field(e).finish()
+                        }
+                        CommandError::Io(e) => {
+                            f.debug_tuple("CommandError::Io").field(e).finish()
+                        }
+                    }
+                }
+            }
+            pub type CommandResult = Result<ExitCode, CommandError>;
+            #[derive(Clone)]
+            pub struct PipeMeta {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub input_types: _rt::Vec<_rt::String>,
+                pub output_type: _rt::String,
+                pub version: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeMeta {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeMeta")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub enum PipeError {
+                ParseError(_rt::String),
+                TransformError(_rt::String),
+                InvalidOption(_rt::String),
+            }
+            impl ::core::fmt::Debug for PipeError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        PipeError::ParseError(e) => {
+                            f.debug_tuple("PipeError::ParseError").field(e).finish()
+                        }
+                        PipeError::TransformError(e) => {
+                            f.debug_tuple("PipeError::TransformError").field(e).finish()
+                        }
+                        PipeError::InvalidOption(e) => {
+                            f.debug_tuple("PipeError::InvalidOption").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for PipeError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl ::core::error::Error for PipeError {}
+            #[derive(Clone)]
+            pub struct PipeInfo {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub path: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeInfo")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("path", &self.path)
+                        .finish()
+                }
+            }
+            /// A discovered pipe with the descriptive fields it embeds in its
+            /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+            #[derive(Clone)]
+            pub struct PipeDetails {
+                pub info: PipeInfo,
+                pub input_types: _rt::Vec<_rt::String>,
+                pub output_type: _rt::String,
+                pub version: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeDetails {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeDetails")
+                        .field("info", &self.info)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod host_process {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type ExitCode = super::super::super::wacli::cli::types::ExitCode;
+            #[allow(unused_unsafe, clippy::all)]
+            #[allow(async_fn_in_trait)]
+            pub fn exit(code: ExitCode) -> () {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-process@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "exit"]
+                        fn wit_import0(_: i32);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i32) {
+                        unreachable!()
+                    }
+                    wit_import0(_rt::as_i32(code));
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod host_pipes {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type PipeMeta = super::super::super::wacli::cli::types::PipeMeta;
+            pub type PipeError = super::super::super::wacli::cli::types::PipeError;
+            pub type PipeInfo = super::super::super::wacli::cli::types::PipeInfo;
+            pub type PipeDetails = super::super::super::wacli::cli::types::PipeDetails;
+            /// Why `open-pipe` failed.
+            #[derive(Clone)]
+            pub enum PipeLoadError {
+                /// No pipe by that name (a typo, or not installed).
+                NotFound(_rt::String),
+                /// The name itself is malformed.
+                InvalidName(_rt::String),
+                /// The pipe exists but could not be read or instantiated.
+                LoadFailed(_rt::String),
+            }
+            impl ::core::fmt::Debug for PipeLoadError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        PipeLoadError::NotFound(e) => {
+                            f.debug_tuple("PipeLoadError::NotFound").field(e).finish()
+                        }
+                        PipeLoadError::InvalidName(e) => {
+                            f.debug_tuple("PipeLoadError::InvalidName").field(e).finish()
+                        }
+                        PipeLoadError::LoadFailed(e) => {
+                            f.debug_tuple("PipeLoadError::LoadFailed").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for PipeLoadError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl ::core::error::Error for PipeLoadError {}
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct Pipe {
+                handle: _rt::Resource<Pipe>,
+            }
+            impl Pipe {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for Pipe {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "[resource-drop]pipe"]
+                        fn drop(_: i32);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn drop(_: i32) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        drop(_handle as i32);
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            #[allow(async_fn_in_trait)]
+            pub fn list_pipes() -> _rt::Vec<PipeInfo> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "list-pipes"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import1(ptr0);
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base13 = l2;
+                    let len13 = l3;
+                    let mut result13 = _rt::Vec::with_capacity(len13);
+                    for i in 0..len13 {
+                        let base = base13
+                            .add(i * (6 * ::core::mem::size_of::<*const u8>()));
+                        let e13 = {
+                            let l4 = *base.add(0).cast::<*mut u8>();
+                            let l5 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
+                            let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+                            let l7 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l8 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len9 = l8;
+                            let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                            let l10 = *base
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l11 = *base
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len12 = l11;
+                            let bytes12 = _rt::Vec::from_raw_parts(
+                                l10.cast(),
+                                len12,
+                                len12,
+                            );
+                            super::super::super::wacli::cli::types::PipeInfo {
+                                name: _rt::string_lift(bytes6),
+                                summary: _rt::string_lift(bytes9),
+                                path: _rt::string_lift(bytes12),
+                            }
+                        };
+                        result13.push(e13);
+                    }
+                    _rt::cabi_dealloc(
+                        base13,
+                        len13 * (6 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result14 = result13;
+                    result14
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Like `list-pipes`, with each pipe's types and version. Read from the
+            /// pipes' metadata sections without instantiating them.
+            #[allow(async_fn_in_trait)]
+            pub fn describe_pipes() -> _rt::Vec<PipeDetails> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "describe-pipes"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import1(ptr0);
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base25 = l2;
+                    let len25 = l3;
+                    let mut result25 = _rt::Vec::with_capacity(len25);
+                    for i in 0..len25 {
+                        let base = base25
+                            .add(i * (12 * ::core::mem::size_of::<*const u8>()));
+                        let e25 = {
+                            let l4 = *base.add(0).cast::<*mut u8>();
+                            let l5 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
                             let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
                             let l7 = *base
                                 .add(2 * ::core::mem::size_of::<*const u8>())
@@ -824,195 +2479,831 @@ pub mod wacli {
                                 len12,
                                 len12,
                             );
-                            super::super::super::wacli::cli::types::PipeInfo {
-                                name: _rt::string_lift(bytes6),
-                                summary: _rt::string_lift(bytes9),
-                                path: _rt::string_lift(bytes12),
+                            let l13 = *base
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l14 = *base
+                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base18 = l13;
+                            let len18 = l14;
+                            let mut result18 = _rt::Vec::with_capacity(len18);
+                            for i in 0..len18 {
+                                let base = base18
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                let e18 = {
+                                    let l15 = *base.add(0).cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len17 = l16;
+                                    let bytes17 = _rt::Vec::from_raw_parts(
+                                        l15.cast(),
+                                        len17,
+                                        len17,
+                                    );
+                                    _rt::string_lift(bytes17)
+                                };
+                                result18.push(e18);
+                            }
+                            _rt::cabi_dealloc(
+                                base18,
+                                len18 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let l19 = *base
+                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l20 = *base
+                                .add(9 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len21 = l20;
+                            let bytes21 = _rt::Vec::from_raw_parts(
+                                l19.cast(),
+                                len21,
+                                len21,
+                            );
+                            let l22 = *base
+                                .add(10 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l23 = *base
+                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len24 = l23;
+                            let bytes24 = _rt::Vec::from_raw_parts(
+                                l22.cast(),
+                                len24,
+                                len24,
+                            );
+                            super::super::super::wacli::cli::types::PipeDetails {
+                                info: super::super::super::wacli::cli::types::PipeInfo {
+                                    name: _rt::string_lift(bytes6),
+                                    summary: _rt::string_lift(bytes9),
+                                    path: _rt::string_lift(bytes12),
+                                },
+                                input_types: result18,
+                                output_type: _rt::string_lift(bytes21),
+                                version: _rt::string_lift(bytes24),
+                            }
+                        };
+                        result25.push(e25);
+                    }
+                    _rt::cabi_dealloc(
+                        base25,
+                        len25 * (12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result26 = result25;
+                    result26
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Load a pipe by name. The error is the message of the `pipe-load-error`
+            /// that `open-pipe` returns; its type stays `string` so components built
+            /// against earlier `wacli:cli@2.0.0` releases still compose.
+            #[allow(async_fn_in_trait)]
+            pub fn load_pipe(name: &str) -> Result<Pipe, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "load-pipe"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result8 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>();
+                                Pipe::from_handle(l4 as u32)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Like `load-pipe`, saying why loading failed. Added within
+            /// `wacli:cli@2.0.0`: hosts from before it cannot run components that
+            /// import it.
+            #[allow(async_fn_in_trait)]
+            pub fn open_pipe(name: &str) -> Result<Pipe, PipeLoadError> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 4 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 4
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                    unsafe extern "C" {
+                        #[link_name = "open-pipe"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import2(ptr0.cast_mut(), len0, ptr1);
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result16 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>();
+                                Pipe::from_handle(l4 as u32)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = i32::from(
+                                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                let v15 = match l5 {
+                                    0 => {
+                                        let e15 = {
+                                            let l6 = *ptr1
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l7 = *ptr1
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len8 = l7;
+                                            let bytes8 = _rt::Vec::from_raw_parts(
+                                                l6.cast(),
+                                                len8,
+                                                len8,
+                                            );
+                                            _rt::string_lift(bytes8)
+                                        };
+                                        PipeLoadError::NotFound(e15)
+                                    }
+                                    1 => {
+                                        let e15 = {
+                                            let l9 = *ptr1
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l10 = *ptr1
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len11 = l10;
+                                            let bytes11 = _rt::Vec::from_raw_parts(
+                                                l9.cast(),
+                                                len11,
+                                                len11,
+                                            );
+                                            _rt::string_lift(bytes11)
+                                        };
+                                        PipeLoadError::InvalidName(e15)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                        let e15 = {
+                                            let l12 = *ptr1
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l13 = *ptr1
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len14 = l13;
+                                            let bytes14 = _rt::Vec::from_raw_parts(
+                                                l12.cast(),
+                                                len14,
+                                                len14,
+                                            );
+                                            _rt::string_lift(bytes14)
+                                        };
+                                        PipeLoadError::LoadFailed(e15)
+                                    }
+                                };
+                                v15
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result16
+                }
+            }
+            impl Pipe {
+                #[allow(unused_unsafe, clippy::all)]
+                #[allow(async_fn_in_trait)]
+                pub fn meta(&self) -> PipeMeta {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 10 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 10
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]pipe.meta"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import1((self).handle() as i32, ptr0);
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len4 = l3;
+                        let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                        let l5 = *ptr0
+                            .add(2 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l6 = *ptr0
+                            .add(3 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len7 = l6;
+                        let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+                        let l8 = *ptr0
+                            .add(4 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l9 = *ptr0
+                            .add(5 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base13 = l8;
+                        let len13 = l9;
+                        let mut result13 = _rt::Vec::with_capacity(len13);
+                        for i in 0..len13 {
+                            let base = base13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            let e13 = {
+                                let l10 = *base.add(0).cast::<*mut u8>();
+                                let l11 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len12 = l11;
+                                let bytes12 = _rt::Vec::from_raw_parts(
+                                    l10.cast(),
+                                    len12,
+                                    len12,
+                                );
+                                _rt::string_lift(bytes12)
+                            };
+                            result13.push(e13);
+                        }
+                        _rt::cabi_dealloc(
+                            base13,
+                            len13 * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let l14 = *ptr0
+                            .add(6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l15 = *ptr0
+                            .add(7 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len16 = l15;
+                        let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+                        let l17 = *ptr0
+                            .add(8 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l18 = *ptr0
+                            .add(9 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len19 = l18;
+                        let bytes19 = _rt::Vec::from_raw_parts(l17.cast(), len19, len19);
+                        let result20 = super::super::super::wacli::cli::types::PipeMeta {
+                            name: _rt::string_lift(bytes4),
+                            summary: _rt::string_lift(bytes7),
+                            input_types: result13,
+                            output_type: _rt::string_lift(bytes16),
+                            version: _rt::string_lift(bytes19),
+                        };
+                        result20
+                    }
+                }
+            }
+            impl Pipe {
+                #[allow(unused_unsafe, clippy::all)]
+                #[allow(async_fn_in_trait)]
+                pub fn process(
+                    &self,
+                    input: &[u8],
+                    options: &[_rt::String],
+                ) -> Result<_rt::Vec<u8>, PipeError> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec0 = input;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec2 = options;
+                        let len2 = vec2.len();
+                        let layout2 = _rt::alloc::Layout::from_size_align(
+                                vec2.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            )
+                            .unwrap();
+                        let (result2, _cleanup2) = wit_bindgen::rt::Cleanup::new(
+                            layout2,
+                        );
+                        for (i, e) in vec2.into_iter().enumerate() {
+                            let base = result2
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let vec1 = e;
+                                let ptr1 = vec1.as_ptr().cast::<u8>();
+                                let len1 = vec1.len();
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len1;
+                                *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
+                            }
+                        }
+                        let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]pipe.process"]
+                            fn wit_import4(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import4(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import4(
+                            (self).handle() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                            result2,
+                            len2,
+                            ptr3,
+                        );
+                        let l5 = i32::from(*ptr3.add(0).cast::<u8>());
+                        let result20 = match l5 {
+                            0 => {
+                                let e = {
+                                    let l6 = *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len8 = l7;
+                                    <_ as From<
+                                        _rt::Vec<_>,
+                                    >>::from(_rt::Vec::from_raw_parts(l6.cast(), len8, len8))
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l9 = i32::from(
+                                        *ptr3.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    use super::super::super::wacli::cli::types::PipeError as V19;
+                                    let v19 = match l9 {
+                                        0 => {
+                                            let e19 = {
+                                                let l10 = *ptr3
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l11 = *ptr3
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                _rt::string_lift(bytes12)
+                                            };
+                                            V19::ParseError(e19)
+                                        }
+                                        1 => {
+                                            let e19 = {
+                                                let l13 = *ptr3
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l14 = *ptr3
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                _rt::string_lift(bytes15)
+                                            };
+                                            V19::TransformError(e19)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e19 = {
+                                                let l16 = *ptr3
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l17 = *ptr3
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len18 = l17;
+                                                let bytes18 = _rt::Vec::from_raw_parts(
+                                                    l16.cast(),
+                                                    len18,
+                                                    len18,
+                                                );
+                                                _rt::string_lift(bytes18)
+                                            };
+                                            V19::InvalidOption(e19)
+                                        }
+                                    };
+                                    v19
+                                };
+                                Err(e)
                             }
+                            _ => _rt::invalid_enum_discriminant(),
                         };
-                        result13.push(e13);
+                        result20
                     }
-                    _rt::cabi_dealloc(
-                        base13,
-                        len13 * (6 * ::core::mem::size_of::<*const u8>()),
-                        ::core::mem::size_of::<*const u8>(),
-                    );
-                    let result14 = result13;
-                    result14
                 }
             }
-            #[allow(unused_unsafe, clippy::all)]
-            #[allow(async_fn_in_trait)]
-            pub fn load_pipe(name: &str) -> Result<Pipe, _rt::String> {
-                unsafe {
-                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
-                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
-                    struct RetArea(
-                        [::core::mem::MaybeUninit<
-                            u8,
-                        >; 3 * ::core::mem::size_of::<*const u8>()],
-                    );
-                    let mut ret_area = RetArea(
-                        [::core::mem::MaybeUninit::uninit(); 3
-                            * ::core::mem::size_of::<*const u8>()],
-                    );
-                    let vec0 = name;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
-                    #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
-                    unsafe extern "C" {
-                        #[link_name = "load-pipe"]
-                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
-                    }
-                    #[cfg(not(target_arch = "wasm32"))]
-                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
-                        unreachable!()
-                    }
-                    wit_import2(ptr0.cast_mut(), len0, ptr1);
-                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
-                    let result8 = match l3 {
-                        0 => {
-                            let e = {
-                                let l4 = *ptr1
+            impl Pipe {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Start a chunked run (see `pipe-stream`): feed the input to
+                /// `process-chunk` and finish with `process-end`, collecting the output
+                /// each returns. The options are checked like those of `process`.
+                #[allow(async_fn_in_trait)]
+                pub fn process_start(
+                    &self,
+                    options: &[_rt::String],
+                ) -> Result<u32, PipeError> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec1 = options;
+                        let len1 = vec1.len();
+                        let layout1 = _rt::alloc::Layout::from_size_align(
+                                vec1.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            )
+                            .unwrap();
+                        let (result1, _cleanup1) = wit_bindgen::rt::Cleanup::new(
+                            layout1,
+                        );
+                        for (i, e) in vec1.into_iter().enumerate() {
+                            let base = result1
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let vec0 = e;
+                                let ptr0 = vec0.as_ptr().cast::<u8>();
+                                let len0 = vec0.len();
+                                *base
                                     .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<i32>();
-                                Pipe::from_handle(l4 as u32)
-                            };
-                            Ok(e)
+                                    .cast::<usize>() = len0;
+                                *base.add(0).cast::<*mut u8>() = ptr0.cast_mut();
+                            }
                         }
-                        1 => {
-                            let e = {
-                                let l5 = *ptr1
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<*mut u8>();
-                                let l6 = *ptr1
-                                    .add(2 * ::core::mem::size_of::<*const u8>())
-                                    .cast::<usize>();
-                                let len7 = l6;
-                                let bytes7 = _rt::Vec::from_raw_parts(
-                                    l5.cast(),
-                                    len7,
-                                    len7,
-                                );
-                                _rt::string_lift(bytes7)
-                            };
-                            Err(e)
+                        let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]pipe.process-start"]
+                            fn wit_import3(_: i32, _: *mut u8, _: usize, _: *mut u8);
                         }
-                        _ => _rt::invalid_enum_discriminant(),
-                    };
-                    result8
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import3(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import3((self).handle() as i32, result1, len1, ptr2);
+                        let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                        let result17 = match l4 {
+                            0 => {
+                                let e = {
+                                    let l5 = *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<i32>();
+                                    l5 as u32
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l6 = i32::from(
+                                        *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    use super::super::super::wacli::cli::types::PipeError as V16;
+                                    let v16 = match l6 {
+                                        0 => {
+                                            let e16 = {
+                                                let l7 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l8 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                _rt::string_lift(bytes9)
+                                            };
+                                            V16::ParseError(e16)
+                                        }
+                                        1 => {
+                                            let e16 = {
+                                                let l10 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l11 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                _rt::string_lift(bytes12)
+                                            };
+                                            V16::TransformError(e16)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e16 = {
+                                                let l13 = *ptr2
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l14 = *ptr2
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                _rt::string_lift(bytes15)
+                                            };
+                                            V16::InvalidOption(e16)
+                                        }
+                                    };
+                                    v16
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result17
+                    }
                 }
             }
             impl Pipe {
                 #[allow(unused_unsafe, clippy::all)]
                 #[allow(async_fn_in_trait)]
-                pub fn meta(&self) -> PipeMeta {
+                pub fn process_chunk(
+                    &self,
+                    session: u32,
+                    bytes: &[u8],
+                ) -> Result<_rt::Vec<u8>, PipeError> {
                     unsafe {
                         #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
                         #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
                         struct RetArea(
                             [::core::mem::MaybeUninit<
                                 u8,
-                            >; 10 * ::core::mem::size_of::<*const u8>()],
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
                         );
                         let mut ret_area = RetArea(
-                            [::core::mem::MaybeUninit::uninit(); 10
+                            [::core::mem::MaybeUninit::uninit(); 4
                                 * ::core::mem::size_of::<*const u8>()],
                         );
-                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        let vec0 = bytes;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]pipe.meta"]
-                            fn wit_import1(_: i32, _: *mut u8);
+                            #[link_name = "[method]pipe.process-chunk"]
+                            fn wit_import2(
+                                _: i32,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import2(
+                            _: i32,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
                             unreachable!()
                         }
-                        wit_import1((self).handle() as i32, ptr0);
-                        let l2 = *ptr0.add(0).cast::<*mut u8>();
-                        let l3 = *ptr0
-                            .add(::core::mem::size_of::<*const u8>())
-                            .cast::<usize>();
-                        let len4 = l3;
-                        let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
-                        let l5 = *ptr0
-                            .add(2 * ::core::mem::size_of::<*const u8>())
-                            .cast::<*mut u8>();
-                        let l6 = *ptr0
-                            .add(3 * ::core::mem::size_of::<*const u8>())
-                            .cast::<usize>();
-                        let len7 = l6;
-                        let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
-                        let l8 = *ptr0
-                            .add(4 * ::core::mem::size_of::<*const u8>())
-                            .cast::<*mut u8>();
-                        let l9 = *ptr0
-                            .add(5 * ::core::mem::size_of::<*const u8>())
-                            .cast::<usize>();
-                        let base13 = l8;
-                        let len13 = l9;
-                        let mut result13 = _rt::Vec::with_capacity(len13);
-                        for i in 0..len13 {
-                            let base = base13
-                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
-                            let e13 = {
-                                let l10 = *base.add(0).cast::<*mut u8>();
-                                let l11 = *base
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<usize>();
-                                let len12 = l11;
-                                let bytes12 = _rt::Vec::from_raw_parts(
-                                    l10.cast(),
-                                    len12,
-                                    len12,
-                                );
-                                _rt::string_lift(bytes12)
-                            };
-                            result13.push(e13);
-                        }
-                        _rt::cabi_dealloc(
-                            base13,
-                            len13 * (2 * ::core::mem::size_of::<*const u8>()),
-                            ::core::mem::size_of::<*const u8>(),
+                        wit_import2(
+                            (self).handle() as i32,
+                            _rt::as_i32(&session),
+                            ptr0.cast_mut(),
+                            len0,
+                            ptr1,
                         );
-                        let l14 = *ptr0
-                            .add(6 * ::core::mem::size_of::<*const u8>())
-                            .cast::<*mut u8>();
-                        let l15 = *ptr0
-                            .add(7 * ::core::mem::size_of::<*const u8>())
-                            .cast::<usize>();
-                        let len16 = l15;
-                        let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
-                        let l17 = *ptr0
-                            .add(8 * ::core::mem::size_of::<*const u8>())
-                            .cast::<*mut u8>();
-                        let l18 = *ptr0
-                            .add(9 * ::core::mem::size_of::<*const u8>())
-                            .cast::<usize>();
-                        let len19 = l18;
-                        let bytes19 = _rt::Vec::from_raw_parts(l17.cast(), len19, len19);
-                        let result20 = super::super::super::wacli::cli::types::PipeMeta {
-                            name: _rt::string_lift(bytes4),
-                            summary: _rt::string_lift(bytes7),
-                            input_types: result13,
-                            output_type: _rt::string_lift(bytes16),
-                            version: _rt::string_lift(bytes19),
+                        let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                        let result18 = match l3 {
+                            0 => {
+                                let e = {
+                                    let l4 = *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len6 = l5;
+                                    <_ as From<
+                                        _rt::Vec<_>,
+                                    >>::from(_rt::Vec::from_raw_parts(l4.cast(), len6, len6))
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l7 = i32::from(
+                                        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    use super::super::super::wacli::cli::types::PipeError as V17;
+                                    let v17 = match l7 {
+                                        0 => {
+                                            let e17 = {
+                                                let l8 = *ptr1
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l9 = *ptr1
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len10 = l9;
+                                                let bytes10 = _rt::Vec::from_raw_parts(
+                                                    l8.cast(),
+                                                    len10,
+                                                    len10,
+                                                );
+                                                _rt::string_lift(bytes10)
+                                            };
+                                            V17::ParseError(e17)
+                                        }
+                                        1 => {
+                                            let e17 = {
+                                                let l11 = *ptr1
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l12 = *ptr1
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len13 = l12;
+                                                let bytes13 = _rt::Vec::from_raw_parts(
+                                                    l11.cast(),
+                                                    len13,
+                                                    len13,
+                                                );
+                                                _rt::string_lift(bytes13)
+                                            };
+                                            V17::TransformError(e17)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e17 = {
+                                                let l14 = *ptr1
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l15 = *ptr1
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len16 = l15;
+                                                let bytes16 = _rt::Vec::from_raw_parts(
+                                                    l14.cast(),
+                                                    len16,
+                                                    len16,
+                                                );
+                                                _rt::string_lift(bytes16)
+                                            };
+                                            V17::InvalidOption(e17)
+                                        }
+                                    };
+                                    v17
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
                         };
-                        result20
+                        result18
                     }
                 }
             }
             impl Pipe {
                 #[allow(unused_unsafe, clippy::all)]
                 #[allow(async_fn_in_trait)]
-                pub fn process(
+                pub fn process_end(
                     &self,
-                    input: &[u8],
-                    options: &[_rt::String],
+                    session: u32,
                 ) -> Result<_rt::Vec<u8>, PipeError> {
                     unsafe {
                         #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
@@ -1026,95 +3317,66 @@ pub mod wacli {
                             [::core::mem::MaybeUninit::uninit(); 4
                                 * ::core::mem::size_of::<*const u8>()],
                         );
-                        let vec0 = input;
-                        let ptr0 = vec0.as_ptr().cast::<u8>();
-                        let len0 = vec0.len();
-                        let vec2 = options;
-                        let len2 = vec2.len();
-                        let layout2 = _rt::alloc::Layout::from_size_align(
-                                vec2.len() * (2 * ::core::mem::size_of::<*const u8>()),
-                                ::core::mem::size_of::<*const u8>(),
-                            )
-                            .unwrap();
-                        let (result2, _cleanup2) = wit_bindgen::rt::Cleanup::new(
-                            layout2,
-                        );
-                        for (i, e) in vec2.into_iter().enumerate() {
-                            let base = result2
-                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
-                            {
-                                let vec1 = e;
-                                let ptr1 = vec1.as_ptr().cast::<u8>();
-                                let len1 = vec1.len();
-                                *base
-                                    .add(::core::mem::size_of::<*const u8>())
-                                    .cast::<usize>() = len1;
-                                *base.add(0).cast::<*mut u8>() = ptr1.cast_mut();
-                            }
-                        }
-                        let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "wacli:cli/host-pipes@2.0.0")]
                         unsafe extern "C" {
-                            #[link_name = "[method]pipe.process"]
-                            fn wit_import4(
-                                _: i32,
-                                _: *mut u8,
-                                _: usize,
-                                _: *mut u8,
-                                _: usize,
-                                _: *mut u8,
-                            );
+                            #[link_name = "[method]pipe.process-end"]
+                            fn wit_import1(_: i32, _: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        unsafe extern "C" fn wit_import4(
-                            _: i32,
-                            _: *mut u8,
-                            _: usize,
-                            _: *mut u8,
-                            _: usize,
-                            _: *mut u8,
-                        ) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import4(
-                            (self).handle() as i32,
-                            ptr0.cast_mut(),
-                            len0,
-                            result2,
-                            len2,
-                            ptr3,
-                        );
-                        let l5 = i32::from(*ptr3.add(0).cast::<u8>());
-                        let result20 = match l5 {
+                        wit_import1((self).handle() as i32, _rt::as_i32(&session), ptr0);
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result17 = match l2 {
                             0 => {
                                 let e = {
-                                    let l6 = *ptr3
+                                    let l3 = *ptr0
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l7 = *ptr3
+                                    let l4 = *ptr0
                                         .add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    let len8 = l7;
+                                    let len5 = l4;
                                     <_ as From<
                                         _rt::Vec<_>,
-                                    >>::from(_rt::Vec::from_raw_parts(l6.cast(), len8, len8))
+                                    >>::from(_rt::Vec::from_raw_parts(l3.cast(), len5, len5))
                                 };
                                 Ok(e)
                             }
                             1 => {
                                 let e = {
-                                    let l9 = i32::from(
-                                        *ptr3.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    let l6 = i32::from(
+                                        *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
                                     );
-                                    use super::super::super::wacli::cli::types::PipeError as V19;
-                                    let v19 = match l9 {
+                                    use super::super::super::wacli::cli::types::PipeError as V16;
+                                    let v16 = match l6 {
                                         0 => {
-                                            let e19 = {
-                                                let l10 = *ptr3
+                                            let e16 = {
+                                                let l7 = *ptr0
                                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                                     .cast::<*mut u8>();
-                                                let l11 = *ptr3
+                                                let l8 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len9 = l8;
+                                                let bytes9 = _rt::Vec::from_raw_parts(
+                                                    l7.cast(),
+                                                    len9,
+                                                    len9,
+                                                );
+                                                _rt::string_lift(bytes9)
+                                            };
+                                            V16::ParseError(e16)
+                                        }
+                                        1 => {
+                                            let e16 = {
+                                                let l10 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l11 = *ptr0
                                                     .add(3 * ::core::mem::size_of::<*const u8>())
                                                     .cast::<usize>();
                                                 let len12 = l11;
@@ -1125,14 +3387,15 @@ pub mod wacli {
                                                 );
                                                 _rt::string_lift(bytes12)
                                             };
-                                            V19::ParseError(e19)
+                                            V16::TransformError(e16)
                                         }
-                                        1 => {
-                                            let e19 = {
-                                                let l13 = *ptr3
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e16 = {
+                                                let l13 = *ptr0
                                                     .add(2 * ::core::mem::size_of::<*const u8>())
                                                     .cast::<*mut u8>();
-                                                let l14 = *ptr3
+                                                let l14 = *ptr0
                                                     .add(3 * ::core::mem::size_of::<*const u8>())
                                                     .cast::<usize>();
                                                 let len15 = l14;
@@ -1143,35 +3406,16 @@ pub mod wacli {
                                                 );
                                                 _rt::string_lift(bytes15)
                                             };
-                                            V19::TransformError(e19)
-                                        }
-                                        n => {
-                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
-                                            let e19 = {
-                                                let l16 = *ptr3
-                                                    .add(2 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<*mut u8>();
-                                                let l17 = *ptr3
-                                                    .add(3 * ::core::mem::size_of::<*const u8>())
-                                                    .cast::<usize>();
-                                                let len18 = l17;
-                                                let bytes18 = _rt::Vec::from_raw_parts(
-                                                    l16.cast(),
-                                                    len18,
-                                                    len18,
-                                                );
-                                                _rt::string_lift(bytes18)
-                                            };
-                                            V19::InvalidOption(e19)
+                                            V16::InvalidOption(e16)
                                         }
                                     };
-                                    v19
+                                    v16
                                 };
                                 Err(e)
                             }
                             _ => _rt::invalid_enum_discriminant(),
                         };
-                        result20
+                        result17
                     }
                 }
             }
@@ -1907,63 +4151,38 @@ mod _rt {
             unsafe { core::hint::unreachable_unchecked() }
         }
     }
-    pub fn as_i32<T: AsI32>(t: T) -> i32 {
-        t.as_i32()
-    }
-    pub trait AsI32 {
-        fn as_i32(self) -> i32;
-    }
-    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
-        fn as_i32(self) -> i32 {
-            (*self).as_i32()
-        }
-    }
-    impl AsI32 for i32 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
-        }
-    }
-    impl AsI32 for u32 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
-        }
-    }
-    impl AsI32 for i16 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
+    pub unsafe fn bool_lift(val: u8) -> bool {
+        if cfg!(debug_assertions) {
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
+        } else {
+            val != 0
         }
     }
-    impl AsI32 for u16 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
-        }
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
     }
-    impl AsI32 for i8 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
-        }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
     }
-    impl AsI32 for u8 {
-        #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
         }
     }
-    impl AsI32 for char {
+    impl AsI64 for i64 {
         #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
+        fn as_i64(self) -> i64 {
+            self as i64
         }
     }
-    impl AsI32 for usize {
+    impl AsI64 for u64 {
         #[inline]
-        fn as_i32(self) -> i32 {
-            self as i32
+        fn as_i64(self) -> i64 {
+            self as i64
         }
     }
     use core::fmt;
@@ -2040,6 +4259,65 @@ mod _rt {
             }
         }
     }
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
     pub use alloc_crate::alloc;
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
@@ -2084,44 +4362,73 @@ pub(crate) use __export_plugin_impl as export;
 )]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1578] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xad\x0b\x01A\x02\x01\
-A\x14\x01B\x07\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\
-\0\x03\x04\0\x03env\x01\x04\x03\0\x18wacli:cli/host-env@2.0.0\x05\0\x01B\x07\x01\
-p}\x01@\x01\x05bytes\0\x01\0\x04\0\x0cstdout-write\x01\x01\x04\0\x0cstderr-write\
-\x01\x01\x01@\0\x01\0\x04\0\x0cstdout-flush\x01\x02\x04\0\x0cstderr-flush\x01\x02\
-\x03\0\x17wacli:cli/host-io@2.0.0\x05\x01\x01B\x0d\x01p}\x01j\x01\0\x01s\x01@\x01\
-\x04paths\0\x01\x04\0\x09read-file\x01\x02\x01j\0\x01s\x01@\x02\x04paths\x08cont\
-ents\0\0\x03\x04\0\x0awrite-file\x01\x04\x01@\x01\x04paths\0\x03\x04\0\x0acreate\
--dir\x01\x05\x01ps\x01j\x01\x06\x01s\x01@\x01\x04paths\0\x07\x04\0\x08list-dir\x01\
-\x08\x03\0\x17wacli:cli/host-fs@2.0.0\x05\x02\x01B\x13\x01y\x04\0\x09exit-code\x03\
-\0\0\x01ks\x01r\x08\x04names\x05short\x02\x04long\x02\x04helps\x08required\x7f\x0d\
-default-value\x02\x0avalue-name\x02\x0btakes-value\x7f\x04\0\x07arg-def\x03\0\x03\
-\x01ps\x01p\x04\x01r\x09\x04names\x07summarys\x05usages\x07aliases\x05\x07versio\
-ns\x06hidden\x7f\x0bdescriptions\x08examples\x05\x04args\x06\x04\0\x0ccommand-me\
-ta\x03\0\x07\x01q\x04\x0funknown-command\x01s\0\x0cinvalid-args\x01s\0\x06failed\
-\x01s\0\x02io\x01s\0\x04\0\x0dcommand-error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\
-\x0ecommand-result\x03\0\x0b\x01r\x05\x04names\x07summarys\x0binput-types\x05\x0b\
-output-types\x07versions\x04\0\x09pipe-meta\x03\0\x0d\x01q\x03\x0bparse-error\x01\
-s\0\x0ftransform-error\x01s\0\x0einvalid-option\x01s\0\x04\0\x0apipe-error\x03\0\
-\x0f\x01r\x03\x04names\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x03\0\x15\
-wacli:cli/types@2.0.0\x05\x03\x02\x03\0\x03\x09exit-code\x01B\x04\x02\x03\x02\x01\
-\x04\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\x02\
-\x03\0\x1cwacli:cli/host-process@2.0.0\x05\x05\x02\x03\0\x03\x09pipe-meta\x02\x03\
-\0\x03\x0apipe-error\x02\x03\0\x03\x09pipe-info\x01B\x16\x02\x03\x02\x01\x06\x04\
-\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x07\x04\0\x0apipe-error\x03\0\x02\x02\x03\
-\x02\x01\x08\x04\0\x09pipe-info\x03\0\x04\x04\0\x04pipe\x03\x01\x01h\x06\x01@\x01\
-\x04self\x07\0\x01\x04\0\x11[method]pipe.meta\x01\x08\x01p}\x01ps\x01j\x01\x09\x01\
-\x03\x01@\x03\x04self\x07\x05input\x09\x07options\x0a\0\x0b\x04\0\x14[method]pip\
-e.process\x01\x0c\x01p\x05\x01@\0\0\x0d\x04\0\x0alist-pipes\x01\x0e\x01i\x06\x01\
-j\x01\x0f\x01s\x01@\x01\x04names\0\x10\x04\0\x09load-pipe\x01\x11\x03\0\x1awacli\
-:cli/host-pipes@2.0.0\x05\x09\x02\x03\0\x03\x0ccommand-meta\x02\x03\0\x03\x0ecom\
-mand-result\x01B\x09\x02\x03\x02\x01\x0a\x04\0\x0ccommand-meta\x03\0\0\x02\x03\x02\
-\x01\x0b\x04\0\x0ecommand-result\x03\0\x02\x01@\0\0\x01\x04\0\x04meta\x01\x04\x01\
-ps\x01@\x01\x04argv\x05\0\x03\x04\0\x03run\x01\x06\x04\0\x17wacli:cli/command@2.\
-0.0\x05\x0c\x04\0\x16wacli:cli/plugin@2.0.0\x04\0\x0b\x0c\x01\0\x06plugin\x03\0\0\
-\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.244.0\x10wit-bind\
-gen-rust\x060.52.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2832] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x93\x15\x01A\x02\x01\
+A\x17\x01B\x0e\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\
+\0\x03\x04\0\x03env\x01\x04\x01ks\x01@\x01\x03keys\0\x05\x04\0\x03get\x01\x06\x01\
+@\0\0s\x04\0\x03cwd\x01\x07\x01@\0\0w\x04\0\x0dmonotonic-now\x01\x08\x03\0\x18wa\
+cli:cli/host-env@2.0.0\x05\0\x01B\x17\x01p}\x01@\x01\x05bytes\0\x01\0\x04\0\x0cs\
+tdout-write\x01\x01\x04\0\x0cstderr-write\x01\x01\x01@\0\x01\0\x04\0\x0cstdout-f\
+lush\x01\x02\x04\0\x0cstderr-flush\x01\x02\x01@\0\0\x7f\x04\0\x12stdout-is-termi\
+nal\x01\x03\x04\0\x12stderr-is-terminal\x01\x03\x04\0\x11stdin-is-terminal\x01\x03\
+\x01ks\x01j\x01\x04\x01s\x01@\0\0\x05\x04\0\x0fstdin-read-line\x01\x06\x01j\x01s\
+\x01s\x01@\x01\x06prompts\0\x07\x04\0\x0bread-secret\x01\x08\x01j\x01\0\x01s\x01\
+@\x01\x03lenw\0\x09\x04\0\x0astdin-read\x01\x0a\x01@\0\0\x09\x04\0\x0estdin-read\
+-all\x01\x0b\x03\0\x17wacli:cli/host-io@2.0.0\x05\x01\x01B\x06\x01m\x05\x05trace\
+\x05debug\x04info\x04warn\x05error\x04\0\x05level\x03\0\0\x01@\x03\x05level\x01\x06\
+targets\x07messages\x01\0\x04\0\x03log\x01\x02\x01@\x01\x05level\x01\0\x7f\x04\0\
+\x07enabled\x01\x03\x03\0\x18wacli:cli/host-log@2.0.0\x05\x02\x01B0\x01m\x04\x04\
+file\x03dir\x07symlink\x05other\x04\0\x09file-kind\x03\0\0\x01r\x03\x04kind\x01\x04\
+sizew\x0bmodified-atw\x04\0\x09file-info\x03\0\x02\x01r\x02\x04paths\x04kind\x01\
+\x04\0\x09dir-entry\x03\0\x04\x04\0\x06writer\x03\x01\x01h\x06\x01p}\x01j\0\x01s\
+\x01@\x02\x04self\x07\x05bytes\x08\0\x09\x04\0\x14[method]writer.write\x01\x0a\x01\
+@\x01\x04self\x07\0\x09\x04\0\x14[method]writer.close\x01\x0b\x01j\x01\x08\x01s\x01\
+@\x01\x04paths\0\x0c\x04\0\x09read-file\x01\x0d\x01@\x02\x04paths\x08contents\x08\
+\0\x09\x04\0\x0awrite-file\x01\x0e\x01@\x01\x04paths\0\x09\x04\0\x0acreate-dir\x01\
+\x0f\x01ps\x01j\x01\x10\x01s\x01@\x01\x04paths\0\x11\x04\0\x08list-dir\x01\x12\x01\
+j\x01w\x01s\x01@\x02\x03srcs\x03dsts\0\x13\x04\0\x09copy-file\x01\x14\x01@\x02\x03\
+srcs\x03dsts\0\x09\x04\0\x06rename\x01\x15\x04\0\x0bremove-file\x01\x0f\x04\0\x0a\
+remove-dir\x01\x0f\x04\0\x0eremove-dir-all\x01\x0f\x01j\x01\x03\x01s\x01@\x01\x04\
+paths\0\x16\x04\0\x04stat\x01\x17\x01@\x01\x04paths\0\x7f\x04\0\x06exists\x01\x18\
+\x01ky\x01p\x05\x01j\x01\x1a\x01s\x01@\x02\x04paths\x09max-depth\x19\0\x1b\x04\0\
+\x08walk-dir\x01\x1c\x04\0\x0bappend-file\x01\x0e\x01i\x06\x01j\x01\x1d\x01s\x01\
+@\x02\x04paths\x06append\x7f\0\x1e\x04\0\x0bopen-writer\x01\x1f\x03\0\x17wacli:c\
+li/host-fs@2.0.0\x05\x03\x01B\x15\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\
+\x04names\x05short\x02\x04long\x02\x04helps\x08required\x7f\x0ddefault-value\x02\
+\x0avalue-name\x02\x0btakes-value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01\
+r\x09\x04names\x07summarys\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0b\
+descriptions\x08examples\x05\x04args\x06\x04\0\x0ccommand-meta\x03\0\x07\x01q\x04\
+\x0funknown-command\x01s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\
+\0\x0dcommand-error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\x0ecommand-result\x03\0\
+\x0b\x01r\x05\x04names\x07summarys\x0binput-types\x05\x0boutput-types\x07version\
+s\x04\0\x09pipe-meta\x03\0\x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\
+\x01s\0\x0einvalid-option\x01s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\
+\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x01r\x04\x04info\x12\x0binput\
+-types\x05\x0boutput-types\x07versions\x04\0\x0cpipe-details\x03\0\x13\x03\0\x15\
+wacli:cli/types@2.0.0\x05\x04\x02\x03\0\x04\x09exit-code\x01B\x04\x02\x03\x02\x01\
+\x05\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\x02\
+\x03\0\x1cwacli:cli/host-process@2.0.0\x05\x06\x02\x03\0\x04\x09pipe-meta\x02\x03\
+\0\x04\x0apipe-error\x02\x03\0\x04\x09pipe-info\x02\x03\0\x04\x0cpipe-details\x01\
+B'\x02\x03\x02\x01\x07\x04\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x08\x04\0\x0ap\
+ipe-error\x03\0\x02\x02\x03\x02\x01\x09\x04\0\x09pipe-info\x03\0\x04\x02\x03\x02\
+\x01\x0a\x04\0\x0cpipe-details\x03\0\x06\x01q\x03\x09not-found\x01s\0\x0cinvalid\
+-name\x01s\0\x0bload-failed\x01s\0\x04\0\x0fpipe-load-error\x03\0\x08\x04\0\x04p\
+ipe\x03\x01\x01h\x0a\x01@\x01\x04self\x0b\0\x01\x04\0\x11[method]pipe.meta\x01\x0c\
+\x01p}\x01ps\x01j\x01\x0d\x01\x03\x01@\x03\x04self\x0b\x05input\x0d\x07options\x0e\
+\0\x0f\x04\0\x14[method]pipe.process\x01\x10\x01j\x01y\x01\x03\x01@\x02\x04self\x0b\
+\x07options\x0e\0\x11\x04\0\x1a[method]pipe.process-start\x01\x12\x01@\x03\x04se\
+lf\x0b\x07sessiony\x05bytes\x0d\0\x0f\x04\0\x1a[method]pipe.process-chunk\x01\x13\
+\x01@\x02\x04self\x0b\x07sessiony\0\x0f\x04\0\x18[method]pipe.process-end\x01\x14\
+\x01p\x05\x01@\0\0\x15\x04\0\x0alist-pipes\x01\x16\x01p\x07\x01@\0\0\x17\x04\0\x0e\
+describe-pipes\x01\x18\x01i\x0a\x01j\x01\x19\x01s\x01@\x01\x04names\0\x1a\x04\0\x09\
+load-pipe\x01\x1b\x01j\x01\x19\x01\x09\x01@\x01\x04names\0\x1c\x04\0\x09open-pip\
+e\x01\x1d\x03\0\x1awacli:cli/host-pipes@2.0.0\x05\x0b\x02\x03\0\x04\x0ccommand-m\
+eta\x02\x03\0\x04\x0ecommand-result\x01B\x09\x02\x03\x02\x01\x0c\x04\0\x0ccomman\
+d-meta\x03\0\0\x02\x03\x02\x01\x0d\x04\0\x0ecommand-result\x03\0\x02\x01@\0\0\x01\
+\x04\0\x04meta\x01\x04\x01ps\x01@\x01\x04argv\x05\0\x03\x04\0\x03run\x01\x06\x04\
+\0\x17wacli:cli/command@2.0.0\x05\x0e\x04\0\x16wacli:cli/plugin@2.0.0\x04\0\x0b\x0c\
+\x01\0\x06plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-componen\
+t\x070.244.0\x10wit-bindgen-rust\x060.52.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -21,19 +21,61 @@
 //! wacli_cdk::export!(Hello);
 //! ```
 
+#[cfg(feature = "command")]
 #[doc(hidden)]
 #[allow(unused_imports)]
 pub mod bindings;
 
+#[cfg(feature = "command")]
+#[doc(hidden)]
+#[allow(unused_imports)]
+pub mod multi_bindings;
+
+#[cfg(feature = "pipe")]
+#[doc(hidden)]
+#[allow(unused_imports)]
+pub mod pipe_bindings;
+
+#[cfg(feature = "pipe")]
+pub mod pipe;
+
+#[cfg(all(feature = "command", not(target_arch = "wasm32")))]
+pub mod testing;
+
+/// `$host` against the host imports, or `$fake` against the
+/// [`testing::TestHost`] running the current command.
+#[cfg(all(feature = "command", not(target_arch = "wasm32")))]
+macro_rules! route {
+    ($host:expr, |$t:ident| $fake:expr $(,)?) => {
+        match $crate::testing::with_active(|$t| $fake) {
+            Some(value) => value,
+            None => $host,
+        }
+    };
+}
+
+/// `$host` against the host imports (there is no test host on wasm32).
+#[cfg(all(feature = "command", target_arch = "wasm32"))]
+macro_rules! route {
+    ($host:expr, |$t:ident| $fake:expr $(,)?) => {
+        $host
+    };
+}
+
+#[cfg(feature = "command")]
 pub use bindings::wacli::cli::types::{
-    ArgDef, CommandError, CommandMeta, CommandResult, PipeError, PipeInfo, PipeMeta,
+    ArgDef, CommandError, CommandMeta, CommandResult, PipeDetails, PipeError, PipeInfo, PipeMeta,
 };
-pub use bindings::wacli::cli::{host_env, host_fs, host_io, host_pipes, host_process};
+#[cfg(feature = "command")]
+pub use bindings::wacli::cli::{host_env, host_fs, host_io, host_log, host_pipes, host_process};
 
 // Proc-macro helpers (compile-time only).
-pub use wacli_cdk_macros::declare_command_metadata;
+pub use wacli_cdk_macros::{
+    declare_command_metadata, declare_pipe_metadata, declare_pipe_options, wacli_command,
+};
 
 // Trait impls for shared argparse helpers.
+#[cfg(feature = "command")]
 impl wacli_argparse::claplike::ArgDefLike for ArgDef {
     fn name(&self) -> &str {
         &self.name
@@ -68,6 +110,79 @@ impl wacli_argparse::claplike::ArgDefLike for ArgDef {
     }
 }
 
+/// An `ArgDef` with the schema fields the frozen `arg-def` record has no room
+/// for, so the plugin parses argv the way core does. [`CommandTree`] holds
+/// these; `def` is what ends up in `CommandMeta`.
+#[cfg(feature = "command")]
+#[derive(Debug, Clone)]
+pub struct Arg {
+    pub def: ArgDef,
+    pub var_arg: bool,
+    pub allow_hyphen_values: bool,
+    /// One of the value types `parse()` checks (`int`, `bool`, ...).
+    pub value_type: Option<&'static str>,
+}
+
+#[cfg(feature = "command")]
+impl From<ArgDef> for Arg {
+    fn from(def: ArgDef) -> Self {
+        Self {
+            def,
+            var_arg: false,
+            allow_hyphen_values: false,
+            value_type: None,
+        }
+    }
+}
+
+#[cfg(feature = "command")]
+impl wacli_argparse::claplike::ArgDefLike for Arg {
+    fn name(&self) -> &str {
+        &self.def.name
+    }
+
+    fn short(&self) -> Option<&str> {
+        self.def.short.as_deref()
+    }
+
+    fn long(&self) -> Option<&str> {
+        self.def.long.as_deref()
+    }
+
+    fn help(&self) -> &str {
+        &self.def.help
+    }
+
+    fn required(&self) -> bool {
+        self.def.required
+    }
+
+    fn default_value(&self) -> Option<&str> {
+        self.def.default_value.as_deref()
+    }
+
+    fn value_name(&self) -> Option<&str> {
+        self.def.value_name.as_deref()
+    }
+
+    fn takes_value(&self) -> bool {
+        self.def.takes_value
+    }
+
+    fn allow_hyphen_values(&self) -> bool {
+        self.allow_hyphen_values
+    }
+
+    fn var_arg(&self) -> bool {
+        self.var_arg
+    }
+
+    fn value_type(&self) -> Option<&str> {
+        self.value_type
+    }
+}
+
+#[cfg(feature = "command")]
 impl wacli_argparse::claplike::CommandMetaLike for CommandMeta {
     type ArgDef = ArgDef;
 
@@ -108,6 +223,142 @@ impl wacli_argparse::claplike::CommandMetaLike for CommandMeta {
     }
 }
 
+/// A `CommandMeta` together with its subcommands and full args.
+///
+/// `CommandMeta` is a WIT record and cannot nest, so subcommands live here
+/// (and in the registry's `command-schema`). Parse with the tree and dispatch
+/// on [`args::Matches::subcommand`]; `meta()` keeps returning the root.
+#[cfg(feature = "command")]
+#[derive(Debug, Clone)]
+pub struct CommandTree {
+    pub meta: CommandMeta,
+    /// What parsing reads; `meta.args` holds their `def`s.
+    pub args: Vec<Arg>,
+    /// Aliases that dispatch but are not shown in help (`command-meta` has
+    /// no such field).
+    pub hidden_aliases: Vec<String>,
+    pub subcommands: Vec<CommandTree>,
+}
+
+#[cfg(feature = "command")]
+impl From<CommandMeta> for CommandTree {
+    fn from(meta: CommandMeta) -> Self {
+        Self {
+            args: meta.args.iter().cloned().map(Arg::from).collect(),
+            meta,
+            hidden_aliases: Vec::new(),
+            subcommands: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "command")]
+impl wacli_argparse::claplike::CommandMetaLike for CommandTree {
+    type ArgDef = Arg;
+
+    fn name(&self) -> &str {
+        &self.meta.name
+    }
+
+    fn summary(&self) -> &str {
+        &self.meta.summary
+    }
+
+    fn usage(&self) -> &str {
+        &self.meta.usage
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.meta.aliases.as_slice()
+    }
+
+    fn hidden_aliases(&self) -> &[String] {
+        self.hidden_aliases.as_slice()
+    }
+
+    fn version(&self) -> &str {
+        &self.meta.version
+    }
+
+    fn hidden(&self) -> bool {
+        self.meta.hidden
+    }
+
+    fn description(&self) -> &str {
+        &self.meta.description
+    }
+
+    fn examples(&self) -> &[String] {
+        self.meta.examples.as_slice()
+    }
+
+    fn args(&self) -> &[Self::ArgDef] {
+        self.args.as_slice()
+    }
+
+    fn subcommands(&self) -> &[Self] {
+        &self.subcommands
+    }
+}
+
+#[cfg(feature = "command")]
+impl CommandError {
+    /// An error that makes the CLI print `message` and exit with `code`:
+    /// `Err(CommandError::exit(2, "usage: ..."))`. A code of 0 exits with 1,
+    /// since an error never succeeds.
+    pub fn exit(code: u32, message: impl Into<String>) -> Self {
+        Self::Failed(wacli_exit_code::encode_exit_code(code, &message.into()))
+    }
+
+    /// The same message, exiting with `code` (1 for 0). The error is then
+    /// reported like `Failed` (an `InvalidArgs` loses its usage hint).
+    pub fn with_code(self, code: u32) -> Self {
+        let message = self.message().to_string();
+        Self::exit(code, message)
+    }
+
+    /// The exit code core uses for this error: 2 for `InvalidArgs`, 127 for
+    /// `UnknownCommand`, the one given to [`CommandError::exit`], otherwise 1.
+    pub fn exit_code(&self) -> u32 {
+        match self {
+            Self::InvalidArgs(_) => 2,
+            Self::UnknownCommand(_) => 127,
+            _ => self.split_exit_code().map_or(1, |(code, _)| code),
+        }
+    }
+
+    /// The message core prints, without any exit-code prefix.
+    pub fn message(&self) -> &str {
+        match self.split_exit_code() {
+            Some((_, message)) => message,
+            None => match self {
+                Self::UnknownCommand(msg)
+                | Self::InvalidArgs(msg)
+                | Self::Failed(msg)
+                | Self::Io(msg) => msg,
+            },
+        }
+    }
+
+    fn split_exit_code(&self) -> Option<(u32, &str)> {
+        let Self::Failed(msg) = self else {
+            return None;
+        };
+        wacli_exit_code::decode_exit_code(msg)
+    }
+}
+
+#[cfg(feature = "command")]
+impl From<wacli_argparse::claplike::ParseError> for CommandError {
+    fn from(err: wacli_argparse::claplike::ParseError) -> Self {
+        match err {
+            wacli_argparse::claplike::ParseError::InvalidArgs(msg) => Self::InvalidArgs(msg),
+            wacli_argparse::claplike::ParseError::Failed(msg) => Self::Failed(msg),
+        }
+    }
+}
+
+#[cfg(feature = "command")]
 impl From<String> for CommandError {
     fn from(s: String) -> Self {
         // A plain String is an unclassified error message.
@@ -116,48 +367,63 @@ impl From<String> for CommandError {
     }
 }
 
+#[cfg(feature = "command")]
 impl From<&str> for CommandError {
     fn from(s: &str) -> Self {
         CommandError::Failed(s.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::io::Error> for CommandError {
     fn from(e: std::io::Error) -> Self {
         CommandError::Io(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::string::FromUtf8Error> for CommandError {
     fn from(e: std::string::FromUtf8Error) -> Self {
         CommandError::Failed(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::str::Utf8Error> for CommandError {
     fn from(e: std::str::Utf8Error) -> Self {
         CommandError::Failed(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::num::ParseIntError> for CommandError {
     fn from(e: std::num::ParseIntError) -> Self {
         CommandError::InvalidArgs(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::num::ParseFloatError> for CommandError {
     fn from(e: std::num::ParseFloatError) -> Self {
         CommandError::InvalidArgs(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
 impl From<std::str::ParseBoolError> for CommandError {
     fn from(e: std::str::ParseBoolError) -> Self {
         CommandError::InvalidArgs(e.to_string())
     }
 }
 
+#[cfg(feature = "command")]
+impl From<pipes::LoadError> for CommandError {
+    fn from(e: pipes::LoadError) -> Self {
+        CommandError::Failed(e.to_string())
+    }
+}
+
+#[cfg(feature = "command")]
 impl From<PipeError> for CommandError {
     fn from(e: PipeError) -> Self {
         match e {
@@ -168,6 +434,7 @@ impl From<PipeError> for CommandError {
     }
 }
 
+#[cfg(feature = "command")]
 #[doc(hidden)]
 #[allow(dead_code)]
 #[used]
@@ -189,8 +456,10 @@ static __WACLI_FORCE_HOST_IMPORTS: ForceHostImports = ForceHostImports {
     pipe_process: host_pipes::Pipe::process,
 };
 
+#[cfg(feature = "command")]
 type PipeProcessFn = fn(&host_pipes::Pipe, &[u8], &[String]) -> Result<Vec<u8>, PipeError>;
 
+#[cfg(feature = "command")]
 #[doc(hidden)]
 #[allow(dead_code)]
 struct ForceHostImports {
@@ -212,40 +481,133 @@ struct ForceHostImports {
 }
 
 /// Convenience facade over the split host interfaces.
+#[cfg(feature = "command")]
 pub mod host {
-    pub use super::host_env::{args, env};
-    pub use super::host_fs::{create_dir, list_dir, read_file, write_file};
-    pub use super::host_io::{stderr_flush, stderr_write, stdout_flush, stdout_write};
-    pub use super::host_pipes::{Pipe, list_pipes, load_pipe};
+    pub use super::host_env::{args, cwd, env, get as env_var};
+    pub use super::host_fs::{
+        Writer, append_file, copy_file, create_dir, exists, list_dir, open_writer, read_file,
+        remove_dir, remove_dir_all, remove_file, rename, stat, walk_dir, write_file,
+    };
+    pub use super::host_io::{
+        read_secret, stderr_flush, stderr_is_terminal, stderr_write, stdin_read, stdin_read_all,
+        stdin_read_line, stdout_flush, stdout_is_terminal, stdout_write,
+    };
+    pub use super::host_log::{enabled as log_enabled, log};
+    pub use super::host_pipes::{Pipe, describe_pipes, list_pipes, load_pipe, open_pipe};
     pub use super::host_process::exit;
 }
 
 /// Common imports for wacli command implementations.
+#[cfg(feature = "command")]
 pub mod prelude {
     pub use super::{
-        Command, CommandError, CommandMeta, CommandResult, Context, arg, args, fs, io, meta, pipes,
+        Command, CommandError, CommandMeta, CommandResult, CommandTree, Context, arg, args, env,
+        fs, io, log, meta, output, pipes, validators,
     };
 }
 
 /// Exit code type for commands.
+#[cfg(feature = "command")]
 pub type ExitCode = u32;
 
 /// Execution context for commands.
+///
+/// # Migrating to schema-aware lookups
+///
+/// The raw helpers [`Context::value`], [`Context::arg`] and
+/// [`Context::positional_args`] inspect `argv` without knowing which flags take
+/// a value, so `ctx.value("--format")` happily returns the *next* token even
+/// when `--format` is a boolean flag. Prefer [`Context::value_of`] and
+/// [`Context::positional_of`], which parse `argv` against the command's
+/// `CommandMeta` once and answer every lookup from the resulting matches:
+///
+/// ```rust,ignore
+/// // Before
+/// let format = ctx.value("--format");
+/// let file = ctx.arg(0);
+///
+/// // After
+/// let meta = Self::meta();
+/// let format = ctx.value_of(&meta, "format")?;
+/// let file = ctx.positional_of(&meta, 0)?;
+/// ```
+///
+/// Enabling the `strict-context` cargo feature marks the raw helpers as
+/// `#[deprecated]` so remaining call sites show up as warnings.
+#[cfg(feature = "command")]
 #[derive(Debug, Clone)]
 pub struct Context {
     pub argv: Vec<String>,
     pub env: Vec<(String, String)>,
+    /// `argv` parsed by the first schema-aware lookup ([`Context::value_of`]
+    /// and friends), or the error that parse reported.
+    parsed: std::sync::OnceLock<Result<ParsedArgs, CommandError>>,
+    /// `env` by key (the first of duplicates), built by the first
+    /// [`Context::env_var`] call.
+    env_vars: std::sync::OnceLock<std::collections::HashMap<String, String>>,
+}
+
+/// What the schema-aware [`Context`] lookups read, taken from one
+/// [`Context::matches`] call. `Matches` borrows `argv`, so the context keeps
+/// an owned copy of the answers instead.
+#[cfg(feature = "command")]
+#[derive(Debug, Clone)]
+struct ParsedArgs {
+    /// The value of each arg that has one, from any source.
+    values: std::collections::HashMap<String, String>,
+    /// The argv index of the last value given for each arg.
+    argv_indices: std::collections::HashMap<String, usize>,
+    /// Declared positional args in declaration order, then extra ones.
+    positionals: Vec<Option<String>>,
 }
 
+#[cfg(feature = "command")]
 impl Context {
     pub fn new(argv: Vec<String>) -> Self {
+        Self::with_env(argv, env::vars())
+    }
+
+    /// A context over `argv` and `env` without asking the host (e.g. in tests).
+    pub fn with_env(argv: Vec<String>, env: Vec<(String, String)>) -> Self {
         Self {
             argv,
-            env: host::env(),
+            env,
+            parsed: Default::default(),
+            env_vars: Default::default(),
         }
     }
 
+    /// The value of the environment variable `key` (case-sensitive) in
+    /// [`Context::env`], or `None` if it is not set. The first of duplicate
+    /// keys wins, as in `env::get`. `env` is indexed on the first call, so
+    /// later changes to it are not seen.
+    pub fn env_var(&self, key: &str) -> Option<&str> {
+        self.env_vars
+            .get_or_init(|| {
+                let mut vars = std::collections::HashMap::with_capacity(self.env.len());
+                for (k, v) in &self.env {
+                    vars.entry(k.clone()).or_insert_with(|| v.clone());
+                }
+                vars
+            })
+            .get(key)
+            .map(String::as_str)
+    }
+
+    /// The value of the app-level global arg `name` (from `build.globalArgs`).
+    ///
+    /// Core strips global args from `argv` and passes them as
+    /// `WACLI_GLOBAL_<NAME>`: the value for a value arg, `"1"` for a flag
+    /// that was given, `None` otherwise.
+    pub fn global_arg(&self, name: &str) -> Option<&str> {
+        self.env_var(&args::global_arg_env_var(name))
+    }
+
     /// Get the positional argument at the given index.
+    #[cfg_attr(
+        feature = "strict-context",
+        deprecated(note = "use `Context::positional_of` with the command's `CommandMeta`")
+    )]
     pub fn arg(&self, index: usize) -> Option<&str> {
         args::positional(&self.argv, index)
     }
@@ -254,6 +616,10 @@ impl Context {
     ///
     /// This does not guess which flags take a value, so values for `--key value`
     /// are not skipped unless you use `positional_args_with_schema`.
+    #[cfg_attr(
+        feature = "strict-context",
+        deprecated(note = "use `Context::positional_of` with the command's `CommandMeta`")
+    )]
     pub fn positional_args(&self) -> Vec<&str> {
         args::positional_args(&self.argv)
     }
@@ -279,19 +645,114 @@ impl Context {
     }
 
     /// Get a flag value such as `--name=value` or `--name value`.
+    ///
+    /// The token after `--name` is returned even if `--name` is a boolean flag.
+    /// Use [`Context::value_of`] to resolve values against the command schema.
+    #[cfg_attr(
+        feature = "strict-context",
+        deprecated(note = "use `Context::value_of` with the command's `CommandMeta`")
+    )]
     pub fn value(&self, name: &str) -> Option<&str> {
         args::value(&self.argv, name)
     }
 
+    /// Parse `argv` (with `env` and the app's config file as value sources)
+    /// against `meta`.
+    ///
+    /// Pass a [`CommandTree`] to dispatch on subcommands.
+    pub fn matches<M: args::CommandMetaLike>(
+        &self,
+        meta: &M,
+    ) -> Result<args::Matches<'_>, CommandError> {
+        let config = args::config_from_env(meta, &self.env);
+        args::parse_with_sources(meta, &self.argv, &self.env, &config)
+    }
+
+    /// `argv` parsed against `meta` by the first schema-aware lookup; later
+    /// lookups reuse that result (or its error) instead of parsing again, so
+    /// they should all be given the same `meta`.
+    fn parsed<M: args::CommandMetaLike>(&self, meta: &M) -> Result<&ParsedArgs, CommandError> {
+        use wacli_argparse::claplike::ArgDefLike;
+        self.parsed
+            .get_or_init(|| {
+                let m = self.matches(meta)?;
+                let names = || meta.args().iter().map(|a| a.name());
+                Ok(ParsedArgs {
+                    values: names()
+                        .filter_map(|name| Some((name.to_string(), m.get(name)?.to_string())))
+                        .collect(),
+                    argv_indices: names()
+                        .filter_map(|name| {
+                            let index = m.occurrences(name).last()?.argv_index()?;
+                            Some((name.to_string(), index))
+                        })
+                        .collect(),
+                    positionals: meta
+                        .args()
+                        .iter()
+                        .filter(|a| a.short().is_none() && a.long().is_none())
+                        .map(|a| m.get(a.name()).map(str::to_string))
+                        .chain(m.rest().iter().map(|rest| Some(rest.to_string())))
+                        .collect(),
+                })
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Get the value of the argument named `name` (as declared in `meta.args`).
+    ///
+    /// Defaults declared in the schema are applied. Returns `Ok(None)` when the
+    /// argument has no value (e.g. a boolean flag or an omitted option).
+    /// `argv` is parsed once, on the first of these lookups.
+    pub fn value_of<M: args::CommandMetaLike>(
+        &self,
+        meta: &M,
+        name: &str,
+    ) -> Result<Option<String>, CommandError> {
+        Ok(self.parsed(meta)?.values.get(name).cloned())
+    }
+
+    /// Get the argv index of the last value given for `name`.
+    ///
+    /// Returns `Ok(None)` when the value came from env/default or the argument
+    /// was not provided. Use [`args::Matches::occurrences`] for every occurrence.
+    pub fn argv_index_of<M: args::CommandMetaLike>(
+        &self,
+        meta: &M,
+        name: &str,
+    ) -> Result<Option<usize>, CommandError> {
+        Ok(self.parsed(meta)?.argv_indices.get(name).copied())
+    }
+
+    /// Get the positional argument at `index` using `meta` to skip flag values.
+    ///
+    /// Indices cover declared positional args first (in declaration order),
+    /// followed by any extra positional arguments.
+    pub fn positional_of<M: args::CommandMetaLike>(
+        &self,
+        meta: &M,
+        index: usize,
+    ) -> Result<Option<String>, CommandError> {
+        Ok(self.parsed(meta)?.positionals.get(index).cloned().flatten())
+    }
+
     /// Require a positional argument by index.
     pub fn require_arg(&self, index: usize, name: &str) -> Result<&str, CommandError> {
+        #[allow(deprecated)]
         self.arg(index)
-            .ok_or_else(|| CommandError::InvalidArgs(format!("missing required argument: {name}")))
+            .ok_or_else(|| CommandError::InvalidArgs(args::messages::missing_required(&[name])))
     }
 }
 
 /// Trait for implementing a wacli command.
+#[cfg(feature = "command")]
 pub trait Command {
+    /// The command's name (`meta().name`), when it is known up front.
+    /// [`export_multi!`] dispatches on it without building every command's
+    /// metadata; `#[wacli_command]` sets it.
+    const NAME: Option<&'static str> = None;
+
     /// Return command metadata.
     fn meta() -> CommandMeta;
 
@@ -320,6 +781,7 @@ pub trait Command {
 ///
 /// wacli_cdk::export!(MyCommand);
 /// ```
+#[cfg(feature = "command")]
 #[macro_export]
 macro_rules! export {
     ($ty:ty) => {
@@ -373,6 +835,113 @@ macro_rules! export {
     };
 }
 
+/// Whether `name` names `C`: by [`Command::NAME`], or by its metadata when
+/// that is not set.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+pub fn __command_is<C: Command>(name: &str) -> bool {
+    match C::NAME {
+        Some(own) => own == name,
+        None => C::meta().name == name,
+    }
+}
+
+/// Export several commands from one component (a multi-command plugin).
+///
+/// Each type implements [`Command`] and declares its own metadata with
+/// [`declare_command_metadata!`]; `wacli build` registers every one of them,
+/// and they share one copy of the CDK. The component also exports the first
+/// type as its single `command`. Commands that set [`Command::NAME`] are
+/// matched without building their metadata.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// wacli_cdk::export_multi!(Upper, Lower);
+/// ```
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! export_multi {
+    ($first:ty $(, $rest:ty)* $(,)?) => {
+        $crate::export!($first);
+
+        const _: () = {
+            struct __WacliMultiShim;
+
+            impl $crate::multi_bindings::exports::wacli::cli::multi_command::Guest
+                for __WacliMultiShim
+            {
+                fn list_commands() -> Vec<$crate::CommandMeta> {
+                    vec![
+                        <$first as $crate::Command>::meta(),
+                        $(<$rest as $crate::Command>::meta(),)*
+                    ]
+                }
+
+                fn run_named(name: String, argv: Vec<String>) -> $crate::CommandResult {
+                    if $crate::__command_is::<$first>(&name) {
+                        return <$first as $crate::Command>::run(argv);
+                    }
+                    $(
+                        if $crate::__command_is::<$rest>(&name) {
+                            return <$rest as $crate::Command>::run(argv);
+                        }
+                    )*
+                    Err($crate::CommandError::UnknownCommand(name))
+                }
+            }
+
+            // The `multi-plugin` world, so the component exports `multi-command`.
+            #[cfg(target_arch = "wasm32")]
+            #[used]
+            #[unsafe(link_section = "component-type:wacli-cdk:wacli:cli@2.1.0:multi-plugin")]
+            static __WACLI_MULTI_PLUGIN_TYPE: [u8; $crate::multi_bindings::MULTI_PLUGIN_COMPONENT_TYPE
+                .len()] = $crate::multi_bindings::MULTI_PLUGIN_COMPONENT_TYPE;
+
+            #[unsafe(export_name = "wacli:cli/multi-command@2.1.0#list-commands")]
+            unsafe extern "C" fn __export_list_commands() -> *mut u8 {
+                unsafe {
+                    $crate::multi_bindings::exports::wacli::cli::multi_command::_export_list_commands_cabi::<
+                        __WacliMultiShim,
+                    >()
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/multi-command@2.1.0#run-named")]
+            unsafe extern "C" fn __export_run_named(
+                arg0: *mut u8,
+                arg1: usize,
+                arg2: *mut u8,
+                arg3: usize,
+            ) -> *mut u8 {
+                unsafe {
+                    $crate::multi_bindings::exports::wacli::cli::multi_command::_export_run_named_cabi::<
+                        __WacliMultiShim,
+                    >(arg0, arg1, arg2, arg3)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/multi-command@2.1.0#list-commands")]
+            unsafe extern "C" fn __post_return_list_commands(arg0: *mut u8) {
+                unsafe {
+                    $crate::multi_bindings::exports::wacli::cli::multi_command::__post_return_list_commands::<
+                        __WacliMultiShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/multi-command@2.1.0#run-named")]
+            unsafe extern "C" fn __post_return_run_named(arg0: *mut u8) {
+                unsafe {
+                    $crate::multi_bindings::exports::wacli::cli::multi_command::__post_return_run_named::<
+                        __WacliMultiShim,
+                    >(arg0)
+                }
+            }
+        };
+    };
+}
+
 /// Create a metadata builder.
 ///
 /// # Example
@@ -384,6 +953,7 @@ macro_rules! export {
 ///     .version("1.0.0")
 ///     .build()
 /// ```
+#[cfg(feature = "command")]
 pub fn meta(name: impl Into<String>) -> MetaBuilder {
     MetaBuilder::new(name)
 }
@@ -405,35 +975,207 @@ pub fn meta(name: impl Into<String>) -> MetaBuilder {
 ///         .build()
 /// }
 /// ```
+#[cfg(feature = "command")]
 pub fn arg(name: impl Into<String>) -> ArgBuilder {
     ArgBuilder::new(name)
 }
 
 /// Parse `argv` according to the declarative argument definitions in `meta`.
-pub fn parse<'a>(
-    meta: &CommandMeta,
+#[cfg(feature = "command")]
+pub fn parse<'a, M: args::CommandMetaLike>(
+    meta: &M,
     argv: &'a [String],
 ) -> Result<args::Matches<'a>, CommandError> {
     args::parse(meta, argv)
 }
 
+/// Parse `argv` like [`parse`], then run plugin-side value validators.
+///
+/// Each validator runs on every value of its argument (including defaults)
+/// after the standard checks pass. A failure becomes
+/// `CommandError::InvalidArgs("invalid value '<v>' for '<arg>': <message>")`.
+///
+/// Validators live in the plugin only: core never sees them, so `core`'s own
+/// pre-dispatch validation (and `--help`) is unaffected.
+///
+/// ```rust,ignore
+/// use wacli_cdk::validators::{self, Validators};
+///
+/// let checks = Validators::new()
+///     .with("endpoint", validators::url)
+///     .with("config", validators::existing_file)
+///     .with("port", |v: &str| match v.parse::<u16>() {
+///         Ok(p) if p >= 1024 => Ok(()),
+///         _ => Err("expected a port number >= 1024".to_string()),
+///     });
+/// let m = wacli_cdk::parse_with_validators(&meta, &argv, &checks)?;
+/// ```
+#[cfg(feature = "command")]
+pub fn parse_with_validators<'a, M: args::CommandMetaLike>(
+    meta: &M,
+    argv: &'a [String],
+    validators: &validators::Validators,
+) -> Result<args::Matches<'a>, CommandError> {
+    let m = args::parse(meta, argv)?;
+    validators.check(meta, &m)?;
+    Ok(m)
+}
+
+/// Custom value validators for [`parse_with_validators`].
+#[cfg(feature = "command")]
+pub mod validators {
+    use super::{
+        CommandError,
+        args::{CommandMetaLike, Matches},
+    };
+    use wacli_argparse::claplike::{self, ArgDefLike};
+
+    type ValidatorFn = dyn Fn(&str) -> Result<(), String>;
+
+    /// Validators keyed by argument name (`ArgDef::name`).
+    #[derive(Default)]
+    pub struct Validators {
+        entries: Vec<(String, Box<ValidatorFn>)>,
+    }
+
+    impl Validators {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Add a validator for `arg`. Several validators may share an argument;
+        /// they run in registration order.
+        pub fn with(
+            mut self,
+            arg: impl Into<String>,
+            validator: impl Fn(&str) -> Result<(), String> + 'static,
+        ) -> Self {
+            self.entries.push((arg.into(), Box::new(validator)));
+            self
+        }
+
+        /// Run every validator against the values in `m`.
+        pub fn check<M: CommandMetaLike>(
+            &self,
+            meta: &M,
+            m: &Matches<'_>,
+        ) -> Result<(), CommandError> {
+            for (name, validator) in &self.entries {
+                let Some(def) = meta.args().iter().find(|a| a.name() == name) else {
+                    return Err(CommandError::Failed(format!(
+                        "schema error: validator for unknown arg '{name}'"
+                    )));
+                };
+                for value in m.get_all(name).unwrap_or(&[]) {
+                    if let Err(msg) = validator(value) {
+                        return Err(CommandError::InvalidArgs(
+                            claplike::messages::rejected_value(
+                                value,
+                                &claplike::arg_display_name(def),
+                                &msg,
+                            ),
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Reject empty or whitespace-only values.
+    pub fn non_empty(value: &str) -> Result<(), String> {
+        if value.trim().is_empty() {
+            Err("must not be empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Require an absolute URL: `scheme:rest`, with a host when `rest` starts
+    /// with `//` (except for `file:`). This is a syntax check only.
+    pub fn url(value: &str) -> Result<(), String> {
+        let invalid = || Err("not a valid URL (expected e.g. https://example.com)".to_string());
+        let Some((scheme, rest)) = value.split_once(':') else {
+            return invalid();
+        };
+        let mut chars = scheme.chars();
+        let scheme_ok = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if !scheme_ok || rest.is_empty() || value.chars().any(char::is_whitespace) {
+            return invalid();
+        }
+        if let Some(authority) = rest.strip_prefix("//")
+            && !scheme.eq_ignore_ascii_case("file")
+        {
+            let host = authority
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or_default()
+                .rsplit('@')
+                .next()
+                .unwrap_or_default();
+            if host.is_empty() || host.starts_with(':') {
+                return invalid();
+            }
+        }
+        Ok(())
+    }
+
+    /// Require `value` to name a readable file.
+    ///
+    /// `host-fs` has no stat call, so inside a plugin this reads the file through
+    /// the host (subject to the host's preopened directories); directories and
+    /// missing paths are rejected.
+    pub fn existing_file(value: &str) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        let exists = super::host::read_file(value).is_ok();
+        #[cfg(not(target_arch = "wasm32"))]
+        let exists = std::fs::metadata(value).is_ok_and(|m| m.is_file());
+        if exists {
+            Ok(())
+        } else {
+            Err("file does not exist".to_string())
+        }
+    }
+}
+
 /// Minimal argument helpers (no extra dependencies).
+#[cfg(feature = "command")]
 pub mod args {
     pub use wacli_argparse::args::{
-        FlagNames, Matches, Schema, flag, positional, positional_args, positional_args_with_schema,
-        positional_with_schema, rest, value,
+        FlagNames, Matches, Occurrence, Schema, ValueSource, flag, positional, positional_args,
+        positional_args_with_schema, positional_with_schema, rest, value,
     };
-
-    use super::{CommandError, CommandMeta};
-    use wacli_argparse::claplike::{self, ParseOutcome};
+    /// Implemented by `CommandMeta` and `CommandTree`.
+    pub use wacli_argparse::claplike::CommandMetaLike;
+    /// Colors for [`help_styled`].
+    pub use wacli_argparse::claplike::HelpStyle;
+    /// Name of the env var core sets for a config value (`WACLI_CONFIG_<NAME>`).
+    pub use wacli_argparse::claplike::config_env_var;
+    /// Name of the env var core sets for a global arg (`WACLI_GLOBAL_<NAME>`).
+    pub use wacli_argparse::claplike::global_arg_env_var;
+    /// Error wording shared with core, for commands that reject arguments
+    /// themselves (`CommandError::InvalidArgs(messages::conflict(..))`).
+    pub use wacli_argparse::claplike::messages;
+
+    use super::CommandError;
+    use wacli_argparse::claplike::{self, ArgDefLike, ParseOutcome};
 
     /// Render a help message based on `CommandMeta`.
-    pub fn help(meta: &CommandMeta) -> String {
+    ///
+    /// For a `CommandTree`, subcommands are listed under `Commands:`.
+    pub fn help<M: CommandMetaLike>(meta: &M) -> String {
         claplike::help(meta)
     }
 
+    /// Render help like [`help`], with ANSI colors from `style`
+    /// (`HelpStyle::colored()`, e.g. when `io::stdout_is_tty()`).
+    pub fn help_styled<M: CommandMetaLike>(meta: &M, style: &HelpStyle) -> String {
+        claplike::help_styled(meta, style)
+    }
+
     /// Render a version message based on `CommandMeta`.
-    pub fn version(meta: &CommandMeta) -> String {
+    pub fn version<M: CommandMetaLike>(meta: &M) -> String {
         claplike::version(meta)
     }
 
@@ -444,8 +1186,79 @@ pub mod args {
     /// - `-V/--version` prints version and exits 0
     /// - required argument checks
     /// - unknown flag detection
-    pub fn parse<'a>(meta: &CommandMeta, argv: &'a [String]) -> Result<Matches<'a>, CommandError> {
-        match claplike::parse(meta, argv) {
+    /// - subcommand dispatch on the first positional (for a `CommandTree`);
+    ///   `cmd sub --help` prints the subcommand's help
+    pub fn parse<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+    ) -> Result<Matches<'a>, CommandError> {
+        parse_with_env(meta, argv, &[])
+    }
+
+    /// Parse `argv` like [`parse`], using `env` as a value source.
+    pub fn parse_with_env<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+    ) -> Result<Matches<'a>, CommandError> {
+        into_matches(claplike::parse_with_env(meta, argv, env))
+    }
+
+    /// Parse `argv` like [`parse_with_env`], with `config` (values keyed by
+    /// arg name) between env and defaults. Config values are validated like
+    /// any other, but are not explicit ([`ValueSource::Config`]).
+    pub fn parse_with_sources<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+        config: &[(String, String)],
+    ) -> Result<Matches<'a>, CommandError> {
+        into_matches(claplike::parse_with_sources(meta, argv, env, config))
+    }
+
+    /// The config values core found for the args of `meta` (and its
+    /// subcommands), read back from their `WACLI_CONFIG_<NAME>` variables.
+    pub fn config_from_env<M: CommandMetaLike>(
+        meta: &M,
+        env: &[(String, String)],
+    ) -> Vec<(String, String)> {
+        let mut config = Vec::new();
+        collect_config(meta, env, &mut config);
+        config
+    }
+
+    fn collect_config<M: CommandMetaLike>(
+        meta: &M,
+        env: &[(String, String)],
+        out: &mut Vec<(String, String)>,
+    ) {
+        for def in meta.args() {
+            let var = config_env_var(def.name());
+            if let Some((_, value)) = env.iter().find(|(key, _)| *key == var) {
+                out.push((def.name().to_string(), value.clone()));
+            }
+        }
+        for sub in meta.subcommands() {
+            collect_config(sub, env, out);
+        }
+    }
+
+    /// Parse `argv` like [`parse_with_env`], and also collect warnings for
+    /// environment variables that look like misspellings of the ones args
+    /// read (`SHOW_FORMTA` for `SHOW_FORMAT`). Print them from
+    /// [`Matches::warnings`]; core does the same for its own validation.
+    pub fn parse_with_env_diagnostics<'a, M: CommandMetaLike>(
+        meta: &M,
+        argv: &'a [String],
+        env: &[(String, String)],
+    ) -> Result<Matches<'a>, CommandError> {
+        into_matches(claplike::parse_with_env_diagnostics(meta, argv, env))
+    }
+
+    fn into_matches(
+        outcome: claplike::ParseResult<ParseOutcome<'_>>,
+    ) -> Result<Matches<'_>, CommandError> {
+        match outcome {
             Ok(ParseOutcome::Matches(m)) => Ok(m),
             Ok(ParseOutcome::Help(msg)) | Ok(ParseOutcome::Version(msg)) => {
                 #[cfg(target_arch = "wasm32")]
@@ -462,15 +1275,14 @@ pub mod args {
                     Err(CommandError::InvalidArgs(msg))
                 }
             }
-            Err(claplike::ParseError::InvalidArgs(msg)) => Err(CommandError::InvalidArgs(msg)),
-            Err(claplike::ParseError::Failed(msg)) => Err(CommandError::Failed(msg)),
+            Err(err) => Err(err.into()),
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "command"))]
 mod tests {
-    use super::{arg, args, meta, parse};
+    use super::{CommandError, arg, args, meta, parse};
 
     #[test]
     fn positional_skips_flags() {
@@ -568,29 +1380,216 @@ mod tests {
     }
 
     #[test]
-    fn parse_consumes_value_for_value_flag_even_if_it_starts_with_dash() {
-        let meta = meta("show")
-            .arg(
-                arg("output")
-                    .long("--output")
-                    .value_name("FILE")
-                    .help("Output file"),
-            )
-            .arg(
-                arg("file")
-                    .required(true)
-                    .value_name("FILE")
-                    .help("Input file"),
-            )
+    fn parse_treats_negative_numbers_as_positionals() {
+        let meta = meta("calc")
+            .arg(arg("verbose").short("-v"))
+            .arg(arg("lhs").required(true).value_name("LHS"))
+            .arg(arg("rhs").required(true).value_name("RHS"))
             .build();
-        let argv = vec![
-            "--output".to_string(),
-            "-".to_string(),
-            "in.txt".to_string(),
-        ];
+        let argv: Vec<String> = ["-5", "-v", "-0.25"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
         let m = parse(&meta, &argv).unwrap();
-        assert_eq!(m.get("output"), Some("-"));
-        assert_eq!(m.get("file"), Some("in.txt"));
+        assert!(m.is_present("verbose"));
+        assert_eq!(m.get("lhs"), Some("-5"));
+        assert_eq!(m.get("rhs"), Some("-0.25"));
+
+        let argv: Vec<String> = ["-5x", "3"].iter().map(|s| s.to_string()).collect();
+        assert!(matches!(
+            parse(&meta, &argv),
+            Err(super::CommandError::InvalidArgs(msg)) if msg == "unknown flag: -5"
+        ));
+    }
+
+    #[test]
+    fn var_arg_positional_collects_remaining_args() {
+        let meta = meta("cp")
+            .arg(arg("dest").required(true))
+            .arg(arg("files").required(true).value_name("FILE").var_arg(true))
+            .build_tree();
+        assert_eq!(meta.meta.args[1].value_name.as_deref(), Some("FILE"));
+
+        let argv: Vec<String> = ["out", "a", "b"].iter().map(|s| s.to_string()).collect();
+        let m = parse(&meta, &argv).unwrap();
+        assert_eq!(m.get("dest"), Some("out"));
+        assert_eq!(m.get_all("files").unwrap(), ["a", "b"]);
+        assert!(m.rest().is_empty());
+        assert!(args::help(&meta).contains("  <FILE>...\n"));
+        assert!(parse(&meta, &argv[..1]).is_err());
+
+        // Without the flag, extras are left in `rest()`.
+        let meta = super::meta("cp")
+            .arg(arg("dest").required(true))
+            .arg(arg("files").required(true).value_name("FILE"))
+            .build();
+        let m = parse(&meta, &argv).unwrap();
+        assert_eq!(m.get_all("files").unwrap(), ["a"]);
+        assert_eq!(m.rest(), ["b"]);
+    }
+
+    #[test]
+    fn arg_extras_stay_with_their_tree() {
+        let files = || arg("files").value_name("FILE");
+        let greedy = meta("cp").arg(files().var_arg(true)).build_tree();
+        let single = meta("cp").arg(files()).build_tree();
+        // Same `arg-def`, so only the tree tells them apart.
+        assert_eq!(
+            format!("{:?}", greedy.meta.args),
+            format!("{:?}", single.meta.args)
+        );
+
+        let argv: Vec<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        for _ in 0..2 {
+            let m = parse(&greedy, &argv).unwrap();
+            assert_eq!(m.get_all("files").unwrap(), ["a", "b"]);
+            let m = parse(&single, &argv).unwrap();
+            assert_eq!(m.rest(), ["b"]);
+        }
+
+        // `CommandMeta` cannot carry the extras; parsing it uses the defaults.
+        let lowered = meta("cp").arg(files().var_arg(true)).build();
+        assert_eq!(
+            format!("{:?}", lowered.args),
+            format!("{:?}", single.meta.args)
+        );
+        let m = parse(&lowered, &argv).unwrap();
+        assert_eq!(m.rest(), ["b"]);
+        let m = parse(&super::CommandTree::from(lowered), &argv).unwrap();
+        assert_eq!(m.rest(), ["b"]);
+    }
+
+    #[test]
+    fn value_types_are_checked_by_parse() {
+        let meta = meta("fetch")
+            .arg(
+                arg("count")
+                    .long("count")
+                    .value_name("N")
+                    .value_type("uint"),
+            )
+            .arg(
+                arg("cache")
+                    .long("cache")
+                    .takes_value(true)
+                    .value_type("bool"),
+            )
+            .build_tree();
+        let argv = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+        let given = argv(&["--count", "3", "--cache"]);
+        let m = parse(&meta, &given).unwrap();
+        assert_eq!(m.get("count"), Some("3"));
+        assert!(m.get_flag("cache"));
+        let given = argv(&["--cache=off"]);
+        let m = parse(&meta, &given).unwrap();
+        assert_eq!(m.get_bool("cache"), Some(false));
+
+        let err = parse(&meta, &argv(&["--count", "-1"])).unwrap_err();
+        assert!(
+            err.message().contains("expected non-negative integer"),
+            "{err:?}"
+        );
+        assert!(parse(&meta, &argv(&["--cache=maybe"])).is_err());
+    }
+
+    #[test]
+    fn hyphen_values_follow_the_declared_flag() {
+        let filter = || arg("filter").long("filter").value_name("EXPR");
+        let argv: Vec<String> = ["--filter", "-v"].iter().map(|s| s.to_string()).collect();
+
+        let meta = meta("grep")
+            .arg(filter())
+            .arg(arg("verbose").short("v"))
+            .build();
+        let err = parse(&meta, &argv).unwrap_err();
+        assert!(err.message().contains("found flag -v"), "{err:?}");
+
+        let meta = super::meta("grep")
+            .arg(filter().allow_hyphen_values(true))
+            .arg(arg("verbose").short("v"))
+            .build_tree();
+        let m = parse(&meta, &argv).unwrap();
+        assert_eq!(m.get("filter"), Some("-v"));
+        assert!(!m.get_flag("verbose"));
+    }
+
+    #[test]
+    fn errors_carry_exit_codes() {
+        let err = CommandError::exit(3, "usage: probe <WORD>");
+        assert_eq!(err.exit_code(), 3);
+        assert_eq!(err.message(), "usage: probe <WORD>");
+        assert!(
+            matches!(&err, CommandError::Failed(msg) if msg == "wacli-exit:3:usage: probe <WORD>")
+        );
+
+        let err = CommandError::from("bad input").with_code(2);
+        assert_eq!((err.exit_code(), err.message()), (2, "bad input"));
+        for err in [
+            CommandError::exit(0, "still a failure"),
+            CommandError::from("still a failure").with_code(0),
+            CommandError::Failed("wacli-exit:0:still a failure".to_string()),
+        ] {
+            assert_eq!((err.exit_code(), err.message()), (1, "still a failure"));
+        }
+
+        for err in [
+            CommandError::Io("disk full".to_string()),
+            CommandError::Failed("wacli-exit:x:not a code".to_string()),
+        ] {
+            assert_eq!(err.exit_code(), 1);
+        }
+        assert_eq!(
+            CommandError::InvalidArgs("missing value".to_string()).exit_code(),
+            2
+        );
+        assert_eq!(
+            CommandError::UnknownCommand("nope".to_string()).exit_code(),
+            127
+        );
+        assert_eq!(
+            CommandError::Failed("wacli-exit:x:not a code".to_string()).message(),
+            "wacli-exit:x:not a code"
+        );
+    }
+
+    #[test]
+    fn hidden_aliases_select_subcommands() {
+        let tree = meta("remote")
+            .subcommand(meta("add").alias("new").hidden_alias("mk"))
+            .build_tree();
+        assert_eq!(tree.subcommands[0].hidden_aliases, ["mk"]);
+
+        let argv = vec!["mk".to_string()];
+        let m = parse(&tree, &argv).unwrap();
+        assert_eq!(m.subcommand_name(), Some("add"));
+        assert!(!args::help(&tree.subcommands[0]).contains("mk"));
+    }
+
+    #[test]
+    fn parse_consumes_value_for_value_flag_even_if_it_starts_with_dash() {
+        let meta = meta("show")
+            .arg(
+                arg("output")
+                    .long("--output")
+                    .value_name("FILE")
+                    .help("Output file"),
+            )
+            .arg(
+                arg("file")
+                    .required(true)
+                    .value_name("FILE")
+                    .help("Input file"),
+            )
+            .build();
+        let argv = vec![
+            "--output".to_string(),
+            "-".to_string(),
+            "in.txt".to_string(),
+        ];
+        let m = parse(&meta, &argv).unwrap();
+        assert_eq!(m.get("output"), Some("-"));
+        assert_eq!(m.get("file"), Some("in.txt"));
     }
 
     #[test]
@@ -674,6 +1673,160 @@ mod tests {
         }
     }
 
+    fn context(argv: &[&str]) -> super::Context {
+        super::Context::with_env(argv.iter().map(|s| s.to_string()).collect(), Vec::new())
+    }
+
+    fn format_flag_meta() -> super::CommandMeta {
+        meta("show")
+            .arg(arg("format").long("--format").help("Enable formatting"))
+            .arg(arg("file").value_name("FILE").help("File to show"))
+            .build()
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn raw_context_helpers_misread_boolean_flags() {
+        let ctx = context(&["--format", "hello.txt"]);
+        // Without a schema, the positional is treated as the flag's value.
+        assert_eq!(ctx.value("--format"), Some("hello.txt"));
+        assert_eq!(ctx.arg(0), Some("hello.txt"));
+        assert_eq!(ctx.positional_args(), vec!["hello.txt"]);
+    }
+
+    #[test]
+    fn schema_context_helpers_respect_boolean_flags() {
+        let meta = format_flag_meta();
+        let ctx = context(&["--format", "hello.txt"]);
+        assert_eq!(ctx.value_of(&meta, "format").unwrap(), None);
+        assert!(ctx.matches(&meta).unwrap().is_present("format"));
+        assert_eq!(
+            ctx.positional_of(&meta, 0).unwrap().as_deref(),
+            Some("hello.txt")
+        );
+        assert_eq!(ctx.positional_of(&meta, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn argv_index_of_points_at_value_token() {
+        let meta = meta("copy")
+            .arg(arg("output").short("-o").value_name("FILE").help("Output"))
+            .arg(arg("src").value_name("SRC").help("Source"))
+            .build();
+        let ctx = context(&["a.txt", "-o", "out.txt"]);
+        assert_eq!(ctx.argv_index_of(&meta, "output").unwrap(), Some(2));
+        assert_eq!(ctx.argv_index_of(&meta, "src").unwrap(), Some(0));
+        assert_eq!(ctx.argv_index_of(&meta, "missing").unwrap(), None);
+    }
+
+    #[test]
+    fn positional_of_skips_values_and_reaches_extra_positionals() {
+        let meta = meta("copy")
+            .arg(arg("output").short("-o").value_name("FILE").help("Output"))
+            .arg(arg("src").value_name("SRC").help("Source"))
+            .build();
+        let ctx = context(&["-o", "out.txt", "a.txt", "b.txt"]);
+        assert_eq!(
+            ctx.value_of(&meta, "output").unwrap().as_deref(),
+            Some("out.txt")
+        );
+        assert_eq!(
+            ctx.positional_of(&meta, 0).unwrap().as_deref(),
+            Some("a.txt")
+        );
+        assert_eq!(
+            ctx.positional_of(&meta, 1).unwrap().as_deref(),
+            Some("b.txt")
+        );
+    }
+
+    #[test]
+    fn value_of_reports_parse_errors() {
+        let meta = format_flag_meta();
+        let ctx = context(&["--nope"]);
+        match ctx.value_of(&meta, "format").unwrap_err() {
+            super::CommandError::InvalidArgs(msg) => assert_eq!(msg, "unknown flag: --nope"),
+            other => panic!("expected InvalidArgs, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pipe_load_errors_keep_the_host_kind() {
+        use super::host_pipes::PipeLoadError;
+        use super::pipes::LoadError;
+
+        let err = LoadError::from(PipeLoadError::NotFound("pipe not found: x".into()));
+        assert_eq!(err, LoadError::NotFound("pipe not found: x".into()));
+        assert_eq!(err.kind(), "not-found");
+        assert_eq!(err.to_string(), "pipe not found: x");
+        let err = LoadError::from(PipeLoadError::InvalidName("invalid pipe name 'A'".into()));
+        assert_eq!(err.kind(), "invalid-name");
+        let err = LoadError::from(PipeLoadError::LoadFailed("bad magic".into()));
+        assert_eq!(err.kind(), "load-failed");
+        assert_eq!(err.message(), "bad magic");
+    }
+
+    #[test]
+    fn schema_lookups_share_one_parse() {
+        let meta = format_flag_meta();
+        let mut ctx = context(&["--nope"]);
+        let first = ctx.value_of(&meta, "format").unwrap_err();
+        // Later lookups report the same error without parsing `argv` again.
+        ctx.argv = vec!["hello.txt".to_string()];
+        assert_eq!(
+            ctx.positional_of(&meta, 0).unwrap_err().message(),
+            first.message()
+        );
+
+        let ctx = context(&["--format", "hello.txt"]);
+        assert_eq!(
+            ctx.positional_of(&meta, 0).unwrap().as_deref(),
+            Some("hello.txt")
+        );
+        assert_eq!(
+            ctx.value_of(&meta, "file").unwrap().as_deref(),
+            Some("hello.txt")
+        );
+        assert!(ctx.parsed.get().is_some_and(Result::is_ok));
+    }
+
+    #[test]
+    fn matches_report_provided_and_unset_args() {
+        let meta = meta("copy")
+            .arg(arg("output").short("-o").value_name("FILE").help("Output"))
+            .arg(
+                arg("mode")
+                    .long("--mode")
+                    .value_name("MODE")
+                    .default_value("copy"),
+            )
+            .arg(arg("src").value_name("SRC").help("Source"))
+            .build();
+        let ctx = context(&["-o", "out.txt"]);
+        let m = ctx.matches(&meta).unwrap();
+        assert_eq!(m.provided_args(), ["output"]);
+        assert_eq!(m.unset_args(&meta), ["src"]);
+        assert!(m.is_default("mode"));
+        assert!(!m.is_default("output"));
+    }
+
+    #[cfg(feature = "strict-context")]
+    #[test]
+    fn strict_context_keeps_raw_helpers_callable() {
+        // The raw helpers are deprecated (not removed) under `strict-context`.
+        #[allow(deprecated)]
+        let raw = context(&["--format", "hello.txt"])
+            .value("--format")
+            .map(str::to_string);
+        assert_eq!(raw.as_deref(), Some("hello.txt"));
+        assert_eq!(
+            context(&["--format", "hello.txt"])
+                .value_of(&format_flag_meta(), "format")
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn help_renders_options_and_args_sections() {
         let meta = meta("show")
@@ -705,22 +1858,695 @@ mod tests {
         assert!(text.contains("Examples:"));
         assert!(text.contains("show hello.txt"));
     }
+
+    /// Render `meta` in the layout of `wacli-argparse/tests/snapshots/*.txt`,
+    /// parsing the same `--- error:` command lines the snapshot lists.
+    fn render_like_snapshot<M: args::CommandMetaLike>(meta: &M, snapshot: &str) -> String {
+        let mut out = format!("--- help\n{}", args::help(meta));
+        out.push_str(&format!("--- version\n{}", args::version(meta)));
+        for command in snapshot
+            .lines()
+            .filter_map(|l| l.strip_prefix("--- error: "))
+        {
+            let argv: Vec<String> = command
+                .split_whitespace()
+                .skip(1)
+                .map(String::from)
+                .collect();
+            let message = match args::parse(meta, &argv) {
+                Err(super::CommandError::InvalidArgs(msg)) => msg,
+                other => panic!("`{command}`: expected InvalidArgs, got {other:?}"),
+            };
+            out.push_str(&format!("--- error: {command}\n{message}\n"));
+        }
+        for command in snapshot
+            .lines()
+            .filter_map(|l| l.strip_prefix("--- output: "))
+        {
+            let argv: Vec<String> = command
+                .split_whitespace()
+                .skip(1)
+                .map(String::from)
+                .collect();
+            // Natively, help/version come back as the error text instead of exiting.
+            let text = match args::parse(meta, &argv) {
+                Err(super::CommandError::InvalidArgs(text)) => text,
+                other => panic!("`{command}`: expected help or version, got {other:?}"),
+            };
+            out.push_str(&format!("--- output: {command}\n{text}"));
+        }
+        out
+    }
+
+    #[test]
+    fn help_matches_argparse_snapshots() {
+        // Only schemas the CDK builders can express (no env, hidden args or
+        // possible values) are checked here.
+        let cases = [
+            (
+                include_str!("../../wacli-argparse/tests/snapshots/positionals.txt"),
+                meta("cp")
+                    .summary("Copy a file")
+                    .usage("cp <SRC> <DEST> [MODE]")
+                    .arg(
+                        arg("src")
+                            .value_name("SRC")
+                            .required(true)
+                            .help("File to copy"),
+                    )
+                    .arg(
+                        arg("dest")
+                            .value_name("DEST")
+                            .required(true)
+                            .help("Where to write the copy"),
+                    )
+                    .arg(
+                        arg("mode")
+                            .value_name("MODE")
+                            .default_value("0644")
+                            .help("Permissions for the copy"),
+                    )
+                    .build(),
+            ),
+            (
+                include_str!("../../wacli-argparse/tests/snapshots/options.txt"),
+                meta("fetch")
+                    .summary("Download a URL")
+                    .version("1.2.0")
+                    .arg(
+                        arg("verbose")
+                            .short("-v")
+                            .long("--verbose")
+                            .help("Print progress"),
+                    )
+                    .arg(
+                        arg("output")
+                            .short("-o")
+                            .long("--output")
+                            .value_name("FILE")
+                            .required(true)
+                            .help("Write to FILE"),
+                    )
+                    .arg(
+                        arg("retries")
+                            .long("--retries")
+                            .value_name("N")
+                            .default_value("3")
+                            .help("Retry count"),
+                    )
+                    .arg(arg("quiet").short("-q").long("--quiet"))
+                    .build(),
+            ),
+            (
+                include_str!("../../wacli-argparse/tests/snapshots/mixed.txt"),
+                meta("show")
+                    .summary("Show a file")
+                    .usage("show [OPTIONS] <FILE>")
+                    .version("0.3.1")
+                    .description("Display a file to stdout.")
+                    .example("show hello.txt")
+                    .example("show --lines 10 hello.txt")
+                    .arg(
+                        arg("file")
+                            .value_name("FILE")
+                            .required(true)
+                            .help("File to show"),
+                    )
+                    .arg(
+                        arg("format")
+                            .long("--format")
+                            .value_name("FORMAT")
+                            .default_value("text")
+                            .help("Output format"),
+                    )
+                    .arg(
+                        arg("lines")
+                            .short("-n")
+                            .long("--lines")
+                            .value_name("N")
+                            .help("Only show the first N lines"),
+                    )
+                    .arg(
+                        arg("number")
+                            .long("--number")
+                            .help("Prefix lines with their number"),
+                    )
+                    .build(),
+            ),
+            (
+                include_str!("../../wacli-argparse/tests/snapshots/aliases.txt"),
+                meta("remove")
+                    .summary("Remove files")
+                    .usage("remove [OPTIONS] <PATH>")
+                    .alias("rm")
+                    .alias("del")
+                    .version("2.0.0")
+                    .arg(
+                        arg("path")
+                            .value_name("PATH")
+                            .required(true)
+                            .help("Path to remove"),
+                    )
+                    .arg(
+                        arg("recursive")
+                            .short("-r")
+                            .long("--recursive")
+                            .help("Remove directories"),
+                    )
+                    .arg(
+                        arg("force")
+                            .short("-f")
+                            .long("--force")
+                            .help("Ignore missing files"),
+                    )
+                    .build(),
+            ),
+            (
+                include_str!("../../wacli-argparse/tests/snapshots/builtin_overrides.txt"),
+                meta("query")
+                    .summary("Query a server")
+                    .usage("query [OPTIONS]")
+                    .version("0.9.0")
+                    .arg(
+                        arg("host")
+                            .short("-h")
+                            .long("--host")
+                            .value_name("HOST")
+                            .default_value("localhost")
+                            .help("Server to query"),
+                    )
+                    .arg(
+                        arg("invert")
+                            .short("-V")
+                            .long("--invert")
+                            .help("Invert the match"),
+                    )
+                    .build(),
+            ),
+        ];
+
+        for (snapshot, meta) in cases {
+            assert_eq!(render_like_snapshot(&meta, snapshot), snapshot);
+        }
+
+        let snapshot = include_str!("../../wacli-argparse/tests/snapshots/subcommands.txt");
+        let tree = meta("remote")
+            .summary("Manage remotes")
+            .usage("remote [OPTIONS] <COMMAND>")
+            .version("1.4.0")
+            .arg(
+                arg("verbose")
+                    .short("-v")
+                    .long("--verbose")
+                    .help("Print details"),
+            )
+            .subcommand(
+                meta("add")
+                    .summary("Add a remote")
+                    .alias("new")
+                    .arg(
+                        arg("name")
+                            .value_name("NAME")
+                            .required(true)
+                            .help("Remote name"),
+                    )
+                    .arg(
+                        arg("url")
+                            .value_name("URL")
+                            .required(true)
+                            .help("Remote URL"),
+                    )
+                    .arg(
+                        arg("fetch")
+                            .short("-f")
+                            .long("--fetch")
+                            .help("Fetch after adding"),
+                    ),
+            )
+            .subcommand(
+                meta("remove")
+                    .summary("Remove a remote")
+                    .usage("remote remove <NAME>")
+                    .version("2.0.0")
+                    .arg(
+                        arg("name")
+                            .value_name("NAME")
+                            .required(true)
+                            .help("Remote name"),
+                    ),
+            )
+            .subcommand(meta("prune").summary("Internal cleanup").hidden())
+            .build_tree();
+        assert_eq!(render_like_snapshot(&tree, snapshot), snapshot);
+    }
+
+    #[test]
+    fn short_flags_accept_attached_values() {
+        let meta = meta("show")
+            .arg(arg("verbose").short("-v"))
+            .arg(arg("output").short("-o").value_name("FILE"))
+            .build();
+        for argv in [
+            &["-o=out.txt"][..],
+            &["-o", "out.txt"],
+            &["-oout.txt"],
+            &["-vo=out.txt"],
+        ] {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            let m = parse(&meta, &argv).unwrap();
+            assert_eq!(m.get("output"), Some("out.txt"), "{argv:?}");
+        }
+    }
+
+    #[test]
+    fn env_var_is_case_sensitive_and_none_when_missing() {
+        let env = [
+            ("HOME", "/home/me"),
+            ("EMPTY", ""),
+            ("home", "lower"),
+            ("HOME", "later"),
+        ];
+        let ctx = super::Context::with_env(
+            Vec::new(),
+            env.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        assert_eq!(ctx.env_var("HOME"), Some("/home/me"));
+        assert_eq!(ctx.env_var("home"), Some("lower"));
+        assert_eq!(ctx.env_var("EMPTY"), Some(""));
+        assert_eq!(ctx.env_var("Home"), None);
+        assert_eq!(ctx.env_var("MISSING"), None);
+    }
+
+    #[test]
+    fn global_arg_reads_the_env_var_set_by_core() {
+        let ctx = super::Context::with_env(
+            Vec::new(),
+            vec![
+                ("WACLI_GLOBAL_OUTPUT_FORMAT".to_string(), "json".to_string()),
+                ("WACLI_GLOBAL_VERBOSE".to_string(), "1".to_string()),
+            ],
+        );
+        assert_eq!(ctx.global_arg("output-format"), Some("json"));
+        assert_eq!(ctx.global_arg("verbose"), Some("1"));
+        assert_eq!(ctx.global_arg("quiet"), None);
+    }
+
+    #[test]
+    fn matches_reads_config_values_set_by_core() {
+        let meta = meta("show")
+            .arg(arg("format").long("--format").value_name("FORMAT"))
+            .arg(arg("level").long("--level").default_value("info"))
+            .build();
+        let ctx = super::Context::with_env(
+            vec!["--format".to_string(), "plain".to_string()],
+            vec![
+                ("WACLI_CONFIG_FORMAT".to_string(), "json".to_string()),
+                ("WACLI_CONFIG_LEVEL".to_string(), "debug".to_string()),
+            ],
+        );
+        let m = ctx.matches(&meta).unwrap();
+        assert_eq!(m.get("format"), Some("plain"));
+        assert_eq!(m.get("level"), Some("debug"));
+        assert_eq!(
+            m.occurrences("level")[0].source(),
+            super::args::ValueSource::Config
+        );
+        assert!(!m.is_explicit("level"));
+    }
+
+    #[test]
+    fn typed_getters_report_invalid_args() {
+        let meta = meta("sum")
+            .arg(arg("n").long("--n").value_name("N"))
+            .build();
+        let argv: Vec<String> = ["--n", "-3"].iter().map(|s| s.to_string()).collect();
+        let m = parse(&meta, &argv).unwrap();
+        assert_eq!(m.get_parsed::<i32>("n").unwrap(), Some(-3));
+        let err: super::CommandError = m.get_parsed::<u32>("n").unwrap_err().into();
+        assert!(matches!(
+            err,
+            super::CommandError::InvalidArgs(msg)
+                if msg == "invalid value '-3' for 'n': invalid digit found in string"
+        ));
+    }
+
+    #[test]
+    fn builtin_validators() {
+        use super::validators::{existing_file, non_empty, url};
+
+        assert!(non_empty("x").is_ok());
+        assert_eq!(non_empty("  ").unwrap_err(), "must not be empty");
+
+        for ok in [
+            "https://example.com",
+            "http://user@localhost:8080/path?q=1",
+            "file:///tmp/x",
+            "mailto:someone@example.com",
+        ] {
+            assert!(url(ok).is_ok(), "{ok}");
+        }
+        for bad in [
+            "example.com",
+            "https://",
+            "http://:80",
+            "1http://x",
+            "https://a b",
+            "x:",
+        ] {
+            assert!(url(bad).is_err(), "{bad}");
+        }
+
+        let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        assert!(existing_file(manifest).is_ok());
+        assert!(existing_file(env!("CARGO_MANIFEST_DIR")).is_err());
+        assert_eq!(
+            existing_file("/definitely/not/here").unwrap_err(),
+            "file does not exist"
+        );
+    }
+
+    #[test]
+    fn parse_with_validators_reports_arg_and_message() {
+        use super::validators::{Validators, url};
+
+        let meta = meta("fetch")
+            .arg(arg("url").long("--url").value_name("URL"))
+            .arg(
+                arg("retries")
+                    .long("--retries")
+                    .value_name("N")
+                    .default_value("3"),
+            )
+            .arg(arg("target"))
+            .build();
+        let checks = Validators::new()
+            .with("url", url)
+            .with("retries", |v: &str| match v.parse::<u8>() {
+                Ok(n) if n <= 5 => Ok(()),
+                _ => Err("expected 0..=5".to_string()),
+            })
+            .with("target", |v: &str| {
+                if v.starts_with('-') {
+                    Err("must not look like a flag".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+
+        let argv: Vec<String> = ["--url", "https://example.com", "out"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let m = super::parse_with_validators(&meta, &argv, &checks).unwrap();
+        assert_eq!(m.get("retries"), Some("3"));
+
+        let invalid = |argv: &[&str]| {
+            let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+            match super::parse_with_validators(&meta, &argv, &checks) {
+                Err(super::CommandError::InvalidArgs(msg)) => msg,
+                _ => panic!("expected InvalidArgs for {argv:?}"),
+            }
+        };
+        assert_eq!(
+            invalid(&["--url", "nope"]),
+            "invalid value 'nope' for '--url': not a valid URL (expected e.g. https://example.com)"
+        );
+        assert_eq!(
+            invalid(&["--retries", "9"]),
+            "invalid value '9' for '--retries': expected 0..=5"
+        );
+        assert_eq!(
+            invalid(&["--", "-x"]),
+            "invalid value '-x' for 'target': must not look like a flag"
+        );
+
+        let unknown = Validators::new().with("nope", url);
+        assert!(matches!(
+            super::parse_with_validators(&meta, &argv, &unknown),
+            Err(super::CommandError::Failed(msg)) if msg.contains("unknown arg 'nope'")
+        ));
+    }
+
+    #[test]
+    fn telemetry_event_parses_core_payload() {
+        use super::telemetry::TelemetryEvent;
+
+        let event =
+            TelemetryEvent::parse(r#"{"command":"db/migrate","exit_code":2,"duration_ms":15}"#)
+                .unwrap();
+        assert_eq!(
+            event,
+            TelemetryEvent {
+                command: "db/migrate".to_string(),
+                exit_code: 2,
+                duration_ms: 15,
+            }
+        );
+
+        let argv = vec![
+            r#" { "extra": null, "command": "a\"bA", "v": 1.5,
+            "duration_ms": 0, "exit_code": 0 } "#
+                .to_string(),
+        ];
+        let event = TelemetryEvent::from_argv(&argv).unwrap();
+        assert_eq!(event.command, "a\"bA");
+    }
+
+    #[test]
+    fn telemetry_event_rejects_malformed_payloads() {
+        use super::telemetry::TelemetryEvent;
+
+        for bad in [
+            "",
+            "{",
+            r#"{"command":"x","exit_code":0}"#,
+            r#"{"command":"x","exit_code":-1,"duration_ms":0}"#,
+            r#"{"command":"x","exit_code":0,"duration_ms":0} x"#,
+        ] {
+            assert!(
+                matches!(
+                    TelemetryEvent::parse(bad),
+                    Err(super::CommandError::InvalidArgs(_))
+                ),
+                "{bad:?}"
+            );
+        }
+        assert!(TelemetryEvent::from_argv(&[]).is_err());
+    }
+
+    fn output_meta() -> super::CommandMeta {
+        meta("show")
+            .arg(arg("format").long("--format").value_name("FORMAT"))
+            .arg(arg("option").long("--option").value_name("KEY=VALUE"))
+            .arg(arg("list-formats").long("--list-formats"))
+            .build()
+    }
+
+    fn negotiate_output(
+        argv: &[&str],
+    ) -> (
+        Result<super::output::Format, super::CommandError>,
+        Vec<String>,
+    ) {
+        let pipe = |name: &str, summary: &str| super::PipeInfo {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            path: format!("plugins/show/{name}.component.wasm"),
+        };
+        let pipes = [
+            pipe("format/json", "JSON via jq"),
+            pipe("format/table", "Uppercase table"),
+            pipe("lint/strict", ""),
+        ];
+        let meta = output_meta();
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        let m = parse(&meta, &argv).unwrap();
+        let mut warnings = Vec::new();
+        let format =
+            super::output::negotiate_with(&m, &["plain", "json"], "format/", &pipes, |w| {
+                warnings.push(w.to_string())
+            });
+        (format, warnings)
+    }
+
+    #[test]
+    fn output_negotiates_builtin_formats() {
+        use super::output::Format;
+
+        let (format, warnings) = negotiate_output(&[]);
+        assert_eq!(format.unwrap(), Format::Builtin("plain".into()));
+        assert!(warnings.is_empty());
+
+        let (format, _) = negotiate_output(&["--format", "plain"]);
+        assert_eq!(format.unwrap(), Format::Builtin("plain".into()));
+
+        let (format, warnings) = negotiate_output(&["--format", "plain", "--option", "x=1"]);
+        assert_eq!(format.unwrap(), Format::Builtin("plain".into()));
+        assert_eq!(
+            warnings,
+            ["--option is ignored by the built-in 'plain' format"]
+        );
+    }
+
+    #[test]
+    fn output_negotiates_pipes_by_short_or_full_name() {
+        use super::output::Format;
+
+        for name in ["table", "format/table"] {
+            let (format, warnings) = negotiate_output(&["--format", name, "--option", "trim"]);
+            assert_eq!(
+                format.unwrap(),
+                Format::Pipe {
+                    name: "format/table".into(),
+                    options: vec!["trim".into()],
+                }
+            );
+            assert!(warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn output_passes_inline_pipe_options_first() {
+        use super::output::Format;
+
+        for name in ["table?width=80,header", "format/table?width=80,header"] {
+            let (format, _) = negotiate_output(&["--format", name, "--option", "trim"]);
+            assert_eq!(
+                format.unwrap(),
+                Format::Pipe {
+                    name: "format/table".into(),
+                    options: vec!["width=80".into(), "header".into(), "trim".into()],
+                }
+            );
+        }
+
+        let (format, _) = negotiate_output(&["--format", "table?"]);
+        assert_eq!(
+            format.unwrap(),
+            Format::Pipe {
+                name: "format/table".into(),
+                options: Vec::new(),
+            }
+        );
+
+        match negotiate_output(&["--format", "format?x/table"]).0 {
+            Err(super::CommandError::InvalidArgs(msg)) => assert_eq!(
+                msg,
+                "pipe spec 'format?x/table' has '?' inside a path segment; \
+                 options must follow the full pipe name"
+            ),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn output_prefers_builtin_over_pipe_with_the_same_name() {
+        use super::output::Format;
+
+        let (format, warnings) = negotiate_output(&["--format", "json"]);
+        assert_eq!(format.unwrap(), Format::Builtin("json".into()));
+        assert_eq!(
+            warnings,
+            [
+                "format 'json' is both built-in and the pipe 'format/json'; using the built-in \
+                 (pass --format format/json for the pipe)"
+            ]
+        );
+
+        let (format, _) = negotiate_output(&["--format", "format/json"]);
+        assert_eq!(
+            format.unwrap(),
+            Format::Pipe {
+                name: "format/json".into(),
+                options: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn output_rejects_unknown_formats_with_every_choice() {
+        // `lint/strict` is outside the prefix, so it is not a choice.
+        for name in ["yaml", "strict", "lint/strict"] {
+            let (format, _) = negotiate_output(&["--format", name]);
+            match format {
+                Err(super::CommandError::InvalidArgs(msg)) => assert_eq!(
+                    msg,
+                    format!(
+                        "invalid value '{name}' for '--format'. possible values: \
+                         plain, json, table, help"
+                    )
+                ),
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn output_lists_formats() {
+        use super::output::Format;
+
+        let expected = "Formats:\n\
+                        \x20 plain        built-in\n\
+                        \x20 json         built-in\n\
+                        \x20 format/json  JSON via jq (pipe format/json)\n\
+                        \x20 table        Uppercase table (pipe format/table)\n";
+        for argv in [&["--format", "help"][..], &["--list-formats"][..]] {
+            let (format, _) = negotiate_output(argv);
+            assert_eq!(format.unwrap(), Format::List(expected.to_string()));
+        }
+    }
+
+    #[test]
+    fn commands_are_named_by_name_or_by_meta() {
+        use super::{__command_is, Command, CommandMeta, CommandResult};
+
+        struct Named;
+        impl Command for Named {
+            const NAME: Option<&'static str> = Some("named");
+            fn meta() -> CommandMeta {
+                unreachable!("NAME is enough")
+            }
+            fn run(_: Vec<String>) -> CommandResult {
+                Ok(0)
+            }
+        }
+        struct Unnamed;
+        impl Command for Unnamed {
+            fn meta() -> CommandMeta {
+                meta("unnamed").build()
+            }
+            fn run(_: Vec<String>) -> CommandResult {
+                Ok(0)
+            }
+        }
+
+        assert!(__command_is::<Named>("named"));
+        assert!(!__command_is::<Named>("unnamed"));
+        assert!(__command_is::<Unnamed>("unnamed"));
+        assert!(!__command_is::<Unnamed>("named"));
+    }
 }
 
 /// Builder for `CommandMeta`.
+#[cfg(feature = "command")]
 #[derive(Default)]
 pub struct MetaBuilder {
     name: String,
     summary: String,
     usage: String,
     aliases: Vec<String>,
+    hidden_aliases: Vec<String>,
     version: String,
     hidden: bool,
     description: String,
     examples: Vec<String>,
-    args: Vec<ArgDef>,
+    args: Vec<Arg>,
+    subcommands: Vec<CommandTree>,
 }
 
+#[cfg(feature = "command")]
 impl MetaBuilder {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
@@ -744,6 +2570,13 @@ impl MetaBuilder {
         self
     }
 
+    /// An alias that dispatches but is not shown in help. Kept only by
+    /// [`MetaBuilder::build_tree`]; `CommandMeta` cannot carry it.
+    pub fn hidden_alias(mut self, alias: impl Into<String>) -> Self {
+        self.hidden_aliases.push(alias.into());
+        self
+    }
+
     pub fn version(mut self, version: impl Into<String>) -> Self {
         self.version = version.into();
         self
@@ -765,12 +2598,24 @@ impl MetaBuilder {
     }
 
     pub fn arg(mut self, arg: ArgBuilder) -> Self {
-        self.args.push(arg.build());
+        self.args.push(arg.build_arg());
+        self
+    }
+
+    pub fn subcommand(mut self, subcommand: MetaBuilder) -> Self {
+        self.subcommands.push(subcommand.build_tree());
         self
     }
 
+    /// Build the `CommandMeta`, dropping subcommands and what `arg-def`
+    /// cannot hold (use [`MetaBuilder::build_tree`]).
     pub fn build(self) -> CommandMeta {
-        CommandMeta {
+        self.build_tree().meta
+    }
+
+    /// Build the command together with its subcommands.
+    pub fn build_tree(self) -> CommandTree {
+        let meta = CommandMeta {
             name: self.name,
             summary: self.summary,
             usage: self.usage,
@@ -779,12 +2624,19 @@ impl MetaBuilder {
             hidden: self.hidden,
             description: self.description,
             examples: self.examples,
+            args: self.args.iter().map(|arg| arg.def.clone()).collect(),
+        };
+        CommandTree {
+            meta,
             args: self.args,
+            hidden_aliases: self.hidden_aliases,
+            subcommands: self.subcommands,
         }
     }
 }
 
 /// Builder for `ArgDef`.
+#[cfg(feature = "command")]
 #[derive(Default)]
 pub struct ArgBuilder {
     name: String,
@@ -795,8 +2647,12 @@ pub struct ArgBuilder {
     default_value: Option<String>,
     value_name: Option<String>,
     takes_value: Option<bool>,
+    var_arg: bool,
+    allow_hyphen_values: bool,
+    value_type: Option<&'static str>,
 }
 
+#[cfg(feature = "command")]
 impl ArgBuilder {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
@@ -840,7 +2696,34 @@ impl ArgBuilder {
         self
     }
 
+    /// Let the last positional take every remaining positional token
+    /// (`[FILES]...`, read with `get_all`) instead of leaving extras in
+    /// `rest()`.
+    pub fn var_arg(mut self, var_arg: bool) -> Self {
+        self.var_arg = var_arg;
+        self
+    }
+
+    /// Take the next token as the value even when it names a declared flag
+    /// (`--filter -v`).
+    pub fn allow_hyphen_values(mut self, allow: bool) -> Self {
+        self.allow_hyphen_values = allow;
+        self
+    }
+
+    /// Check values the way core does: `int`, `uint`, `float`, `path`, or
+    /// `bool` (whose value is then optional).
+    pub fn value_type(mut self, value_type: &str) -> Self {
+        self.value_type = VALUE_TYPES.iter().copied().find(|t| *t == value_type);
+        self
+    }
+
     pub fn build(self) -> ArgDef {
+        self.build_arg().def
+    }
+
+    /// Build the arg with what `ArgDef` cannot hold, for a [`CommandTree`].
+    pub fn build_arg(self) -> Arg {
         let short = self.short.map(|s| {
             let s = s.trim().to_string();
             if s.starts_with('-') {
@@ -864,90 +2747,1035 @@ impl ArgBuilder {
         } else {
             self.value_name.is_some() || self.default_value.is_some()
         };
-        ArgDef {
-            name: self.name,
-            short,
-            long,
-            help: self.help,
-            required: self.required,
-            default_value: self.default_value,
-            value_name: self.value_name,
-            takes_value: self.takes_value.unwrap_or(inferred_takes_value),
+        Arg {
+            def: ArgDef {
+                name: self.name,
+                short,
+                long,
+                help: self.help,
+                required: self.required,
+                default_value: self.default_value,
+                value_name: self.value_name,
+                takes_value: self.takes_value.unwrap_or(inferred_takes_value),
+            },
+            var_arg: self.var_arg,
+            allow_hyphen_values: self.allow_hyphen_values,
+            value_type: self.value_type,
         }
     }
 }
 
+/// The value types core knows; the rest accept any value, so they are
+/// dropped rather than kept around as `'static` strings.
+#[cfg(feature = "command")]
+const VALUE_TYPES: &[&str] = &["string", "int", "uint", "float", "bool", "path"];
+
 /// I/O helpers for stdout/stderr.
+#[cfg(feature = "command")]
 pub mod io {
-    use super::host;
+    use super::{CommandError, host};
 
     /// Write to stdout.
     pub fn print(s: impl AsRef<str>) {
-        host::stdout_write(s.as_ref().as_bytes());
+        let bytes = s.as_ref().as_bytes();
+        route!(host::stdout_write(bytes), |t| t.stdout_write(bytes));
     }
 
     /// Write to stderr.
     pub fn eprint(s: impl AsRef<str>) {
-        host::stderr_write(s.as_ref().as_bytes());
+        let bytes = s.as_ref().as_bytes();
+        route!(host::stderr_write(bytes), |t| t.stderr_write(bytes));
     }
 
     /// Write to stdout with newline.
     pub fn println(s: impl AsRef<str>) {
         let mut buf = s.as_ref().as_bytes().to_vec();
         buf.push(b'\n');
-        host::stdout_write(&buf);
+        route!(host::stdout_write(&buf), |t| t.stdout_write(&buf));
     }
 
     /// Write to stderr with newline.
     pub fn eprintln(s: impl AsRef<str>) {
         let mut buf = s.as_ref().as_bytes().to_vec();
         buf.push(b'\n');
-        host::stderr_write(&buf);
+        route!(host::stderr_write(&buf), |t| t.stderr_write(&buf));
     }
 
     /// Flush stdout.
     pub fn flush() {
-        host::stdout_flush();
+        route!(host::stdout_flush(), |_t| ());
+    }
+
+    /// Whether stdout is a terminal. False when piped, redirected or captured
+    /// (e.g. by `wacli verify`), so commands can skip color and progress output.
+    pub fn stdout_is_tty() -> bool {
+        route!(host::stdout_is_terminal(), |_t| false)
+    }
+
+    /// Whether stderr is a terminal.
+    pub fn stderr_is_tty() -> bool {
+        route!(host::stderr_is_terminal(), |_t| false)
+    }
+
+    /// Read up to `max_len` bytes from stdin. Empty at end of input, including
+    /// on every read after it.
+    pub fn read(max_len: u64) -> Result<Vec<u8>, CommandError> {
+        route!(host::stdin_read(max_len), |t| t.stdin_read(max_len)).map_err(CommandError::Io)
+    }
+
+    /// Read the rest of stdin. Empty stdin gives an empty vec.
+    pub fn read_all() -> Result<Vec<u8>, CommandError> {
+        route!(host::stdin_read_all(), |t| t.stdin_read_all()).map_err(CommandError::Io)
+    }
+
+    /// Read the rest of stdin as UTF-8 text.
+    pub fn read_to_string() -> Result<String, CommandError> {
+        String::from_utf8(read_all()?)
+            .map_err(|e| CommandError::Io(format!("stdin is not valid UTF-8: {e}")))
+    }
+
+    /// Read one line from stdin, without its line ending. `None` at end of
+    /// input. Nothing past the line is consumed.
+    pub fn read_line() -> Result<Option<String>, CommandError> {
+        route!(host::stdin_read_line(), |t| t.stdin_read_line()).map_err(CommandError::Io)
+    }
+
+    /// Write `msg` to stderr and read the answer with [`read_line`].
+    pub fn prompt(msg: impl AsRef<str>) -> Result<Option<String>, CommandError> {
+        eprint(msg);
+        route!(host::stderr_flush(), |_t| ());
+        read_line()
+    }
+
+    /// Like [`prompt`], without echoing the answer when stdin is a terminal
+    /// (piped stdin is read as is). End of input is an error.
+    pub fn prompt_secret(msg: impl AsRef<str>) -> Result<String, CommandError> {
+        let msg = msg.as_ref();
+        route!(host::read_secret(msg), |t| t.read_secret(msg)).map_err(CommandError::Io)
+    }
+}
+
+/// Environment helpers via the host interface.
+#[cfg(feature = "command")]
+pub mod env {
+    use super::host;
+
+    /// The value of the environment variable `key` (case-sensitive), or `None`
+    /// if it is not set. One host call; use [`super::Context::env_var`] for
+    /// repeated lookups.
+    pub fn get(key: impl AsRef<str>) -> Option<String> {
+        let key = key.as_ref();
+        route!(host::env_var(key), |t| t.env_var(key))
+    }
+
+    /// The working directory relative paths resolve against (`.` when the
+    /// runtime reports none).
+    pub fn cwd() -> String {
+        route!(host::cwd(), |t| t.cwd())
+    }
+
+    /// Every environment variable, in the order the host reports them.
+    pub fn vars() -> Vec<(String, String)> {
+        route!(host::env(), |t| t.env())
+    }
+}
+
+/// Leveled logging via the host interface.
+///
+/// Messages go to stderr as `[LEVEL target] message`. Core sets the least
+/// severe level written (`warn` unless the app's `<APP>_LOG` variable or its
+/// `verbose`/`quiet` global flags say otherwise); the macros skip formatting
+/// below it.
+///
+/// ```rust,ignore
+/// use wacli_cdk::log;
+///
+/// log::info!("copying {} files", paths.len());
+/// if log::enabled(log::Level::Trace) {
+///     log::trace!("paths: {}", paths.join(", "));
+/// }
+/// ```
+#[cfg(feature = "command")]
+pub mod log {
+    use super::host;
+
+    pub use super::host_log::Level;
+
+    pub use crate::{
+        __wacli_log_debug as debug, __wacli_log_error as error, __wacli_log_info as info,
+        __wacli_log_trace as trace, __wacli_log_warn as warn,
+    };
+
+    /// Log `message` at `level` with `target` (the macros use the module
+    /// path). Dropped by the host when `level` is below the max level.
+    pub fn log(level: Level, target: &str, message: impl AsRef<str>) {
+        let message = message.as_ref();
+        route!(host::log(level, target, message), |t| t
+            .log(level, target, message));
+    }
+
+    /// Whether a message at `level` would be written.
+    pub fn enabled(level: Level) -> bool {
+        route!(host::log_enabled(level), |t| t.log_enabled(level))
     }
 }
 
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::log::enabled(level) {
+            $crate::log::log(level, ::core::module_path!(), ::std::format!($($arg)+));
+        }
+    }};
+}
+
+/// Log at [`Level::Trace`](crate::log::Level::Trace), formatting like `format!`.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log_trace {
+    ($($arg:tt)+) => { $crate::__wacli_log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+/// Log at [`Level::Debug`](crate::log::Level::Debug), formatting like `format!`.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log_debug {
+    ($($arg:tt)+) => { $crate::__wacli_log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+/// Log at [`Level::Info`](crate::log::Level::Info), formatting like `format!`.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log_info {
+    ($($arg:tt)+) => { $crate::__wacli_log!($crate::log::Level::Info, $($arg)+) };
+}
+
+/// Log at [`Level::Warn`](crate::log::Level::Warn), formatting like `format!`.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log_warn {
+    ($($arg:tt)+) => { $crate::__wacli_log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+/// Log at [`Level::Error`](crate::log::Level::Error), formatting like `format!`.
+#[doc(hidden)]
+#[cfg(feature = "command")]
+#[macro_export]
+macro_rules! __wacli_log_error {
+    ($($arg:tt)+) => { $crate::__wacli_log!($crate::log::Level::Error, $($arg)+) };
+}
+
 /// File system helpers via the host interface.
+#[cfg(feature = "command")]
 pub mod fs {
-    use super::{CommandError, host};
+    use super::{CommandError, host, host_fs};
+
+    pub use super::host_fs::{DirEntry, FileInfo, FileKind};
 
     /// Read an entire file into memory.
     pub fn read(path: impl AsRef<str>) -> Result<Vec<u8>, CommandError> {
-        host::read_file(path.as_ref()).map_err(CommandError::Io)
+        let path = path.as_ref();
+        route!(host::read_file(path), |t| t.read_file(path)).map_err(CommandError::Io)
     }
 
     /// Write a file, creating or truncating it.
     pub fn write(path: impl AsRef<str>, contents: impl AsRef<[u8]>) -> Result<(), CommandError> {
-        host::write_file(path.as_ref(), contents.as_ref()).map_err(CommandError::Io)
+        let (path, contents) = (path.as_ref(), contents.as_ref());
+        route!(host::write_file(path, contents), |t| t
+            .write_file("write", path, contents))
+        .map_err(CommandError::Io)
+    }
+
+    /// Append to a file, creating it if missing.
+    pub fn append(path: impl AsRef<str>, contents: impl AsRef<[u8]>) -> Result<(), CommandError> {
+        let (path, contents) = (path.as_ref(), contents.as_ref());
+        route!(host::append_file(path, contents), |t| t
+            .append_file("append", path, contents))
+        .map_err(CommandError::Io)
+    }
+
+    /// Open a file for incremental writes, creating it if missing: appended
+    /// to with `append`, truncated otherwise. The host keeps it open until
+    /// [`Writer::close`]; writing after that returns an error.
+    ///
+    /// ```rust,ignore
+    /// let log = fs::open_writer("progress.log", true)?;
+    /// log.write(b"step 1 done\n")?;
+    /// log.close()?;
+    /// ```
+    pub fn open_writer(path: impl AsRef<str>, append: bool) -> Result<Writer, CommandError> {
+        let path = path.as_ref();
+        route!(
+            host::open_writer(path, append).map(|w| Writer(WriterHandle::Host(w))),
+            |t| {
+                let opened = if append {
+                    t.append_file("open-writer", path, &[])
+                } else {
+                    t.write_file("open-writer", path, &[])
+                };
+                opened.map(|()| {
+                    Writer(WriterHandle::Fake {
+                        path: path.to_string(),
+                        closed: Default::default(),
+                    })
+                })
+            }
+        )
+        .map_err(CommandError::Io)
+    }
+
+    /// A file opened by [`open_writer`].
+    #[derive(Debug)]
+    pub struct Writer(WriterHandle);
+
+    #[derive(Debug)]
+    enum WriterHandle {
+        Host(host_fs::Writer),
+        #[cfg(not(target_arch = "wasm32"))]
+        Fake {
+            path: String,
+            closed: std::cell::Cell<bool>,
+        },
+    }
+
+    impl Writer {
+        /// Write `bytes` after everything written so far.
+        pub fn write(&self, bytes: &[u8]) -> Result<(), String> {
+            match &self.0 {
+                WriterHandle::Host(w) => w.write(bytes),
+                #[cfg(not(target_arch = "wasm32"))]
+                WriterHandle::Fake { path, closed } if closed.get() => {
+                    Err(format!("write: writer is closed: {path}"))
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                WriterHandle::Fake { path, .. } => {
+                    super::testing::with_active(|t| t.append_file("write", path, bytes))
+                        .unwrap_or_else(|| Err(format!("write: test host is not running: {path}")))
+                }
+            }
+        }
+
+        /// Close the file. Closing twice is not an error.
+        pub fn close(&self) -> Result<(), String> {
+            match &self.0 {
+                WriterHandle::Host(w) => w.close(),
+                #[cfg(not(target_arch = "wasm32"))]
+                WriterHandle::Fake { closed, .. } => {
+                    closed.set(true);
+                    Ok(())
+                }
+            }
+        }
     }
 
     /// Create a directory.
     pub fn create_dir(path: impl AsRef<str>) -> Result<(), CommandError> {
-        host::create_dir(path.as_ref()).map_err(CommandError::Io)
+        let path = path.as_ref();
+        route!(host::create_dir(path), |t| t.create_dir(path)).map_err(CommandError::Io)
     }
 
     /// List entries in a directory.
     pub fn list_dir(path: impl AsRef<str>) -> Result<Vec<String>, CommandError> {
-        host::list_dir(path.as_ref()).map_err(CommandError::Io)
+        let path = path.as_ref();
+        route!(host::list_dir(path), |t| t.list_dir(path)).map_err(CommandError::Io)
+    }
+
+    /// Every entry below `path` in one host call, depth-first with each
+    /// directory's entries sorted by name (`a`, `a/b.txt`, `c.txt`), so output
+    /// built from it is reproducible. `max_depth` 1 lists direct children only;
+    /// `None` has no limit. Symlinks are listed but never followed.
+    pub fn walk(
+        path: impl AsRef<str>,
+        max_depth: Option<u32>,
+    ) -> Result<Vec<DirEntry>, CommandError> {
+        let path = path.as_ref();
+        route!(host::walk_dir(path, max_depth), |t| t
+            .walk_dir(path, max_depth))
+        .map_err(CommandError::Io)
+    }
+
+    /// Copy `src` to `dst` (created or truncated) and return the number of
+    /// bytes copied.
+    ///
+    /// The host streams the data, so unlike `write(dst, read(src)?)` this
+    /// never holds the whole file in memory. Copying a file onto itself, under
+    /// any name, is an error rather than a truncation.
+    pub fn copy(src: impl AsRef<str>, dst: impl AsRef<str>) -> Result<u64, CommandError> {
+        let (src, dst) = (src.as_ref(), dst.as_ref());
+        route!(host::copy_file(src, dst), |t| t.copy_file(src, dst)).map_err(CommandError::Io)
+    }
+
+    /// Move `src` to `dst`, replacing a file or empty directory already there.
+    ///
+    /// Both paths must be under the same preopened directory. Moving to
+    /// another mount fails with "cross-mount rename not supported"; `copy`
+    /// then `remove_file` instead.
+    pub fn rename(src: impl AsRef<str>, dst: impl AsRef<str>) -> Result<(), CommandError> {
+        let (src, dst) = (src.as_ref(), dst.as_ref());
+        route!(host::rename(src, dst), |t| t.rename(src, dst)).map_err(CommandError::Io)
+    }
+
+    /// Remove a file.
+    pub fn remove_file(path: impl AsRef<str>) -> Result<(), CommandError> {
+        let path = path.as_ref();
+        route!(host::remove_file(path), |t| t.remove_file(path)).map_err(CommandError::Io)
+    }
+
+    /// Remove an empty directory. A non-empty one is an error.
+    pub fn remove_dir(path: impl AsRef<str>) -> Result<(), CommandError> {
+        let path = path.as_ref();
+        route!(host::remove_dir(path), |t| t.remove_dir(path)).map_err(CommandError::Io)
+    }
+
+    /// Remove a directory and everything in it, without following symlinks.
+    pub fn remove_dir_all(path: impl AsRef<str>) -> Result<(), CommandError> {
+        let path = path.as_ref();
+        route!(host::remove_dir_all(path), |t| t.remove_dir_all(path)).map_err(CommandError::Io)
+    }
+
+    /// Kind, size and modification time of `path`. A symlink is reported as
+    /// [`FileKind::Symlink`], not followed.
+    pub fn stat(path: impl AsRef<str>) -> Result<FileInfo, CommandError> {
+        let path = path.as_ref();
+        route!(host::stat(path), |t| t.stat(path)).map_err(CommandError::Io)
+    }
+
+    /// Whether `path` exists. Broken symlinks and unreachable paths count as missing.
+    pub fn exists(path: impl AsRef<str>) -> bool {
+        let path = path.as_ref();
+        route!(host::exists(path), |t| t.exists(path))
     }
 }
 
 /// Pipe loader helpers via the host-pipes interface.
+#[cfg(feature = "command")]
 pub mod pipes {
-    use super::PipeInfo;
     use super::host_pipes;
+    use super::{PipeDetails, PipeError, PipeInfo, PipeMeta};
+    use std::io::Read as _;
 
     /// List available pipes.
+    ///
+    /// Summaries come from each pipe's embedded metadata
+    /// ([`declare_pipe_metadata!`](crate::declare_pipe_metadata)); the pipes
+    /// are not instantiated.
     pub fn list() -> Vec<PipeInfo> {
-        host_pipes::list_pipes()
+        route!(host_pipes::list_pipes(), |t| t.list_pipes())
+    }
+
+    /// List available pipes with their input/output types and versions,
+    /// read the same way as [`list`].
+    pub fn describe() -> Vec<PipeDetails> {
+        route!(host_pipes::describe_pipes(), |t| t.describe_pipes())
+    }
+
+    pub use wacli_argparse::pipe_options::Options;
+    pub use wacli_argparse::pipe_spec::PipeSpec;
+
+    /// Why [`open`] failed: [`LoadError::NotFound`] for a name with no pipe
+    /// behind it (list the choices with [`list`]), [`LoadError::InvalidName`]
+    /// or [`LoadError::LoadFailed`] for a broken install.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum LoadError {
+        /// No pipe by that name (a typo, or not installed).
+        NotFound(String),
+        /// The name itself is malformed.
+        InvalidName(String),
+        /// The pipe exists but could not be read or instantiated.
+        LoadFailed(String),
+    }
+
+    impl LoadError {
+        /// The `pipe-load-error` case name: `not-found`, `invalid-name` or
+        /// `load-failed`.
+        pub fn kind(&self) -> &'static str {
+            match self {
+                Self::NotFound(_) => "not-found",
+                Self::InvalidName(_) => "invalid-name",
+                Self::LoadFailed(_) => "load-failed",
+            }
+        }
+
+        pub fn message(&self) -> &str {
+            match self {
+                Self::NotFound(msg) | Self::InvalidName(msg) | Self::LoadFailed(msg) => msg,
+            }
+        }
     }
 
+    impl From<host_pipes::PipeLoadError> for LoadError {
+        fn from(e: host_pipes::PipeLoadError) -> Self {
+            match e {
+                host_pipes::PipeLoadError::NotFound(msg) => Self::NotFound(msg),
+                host_pipes::PipeLoadError::InvalidName(msg) => Self::InvalidName(msg),
+                host_pipes::PipeLoadError::LoadFailed(msg) => Self::LoadFailed(msg),
+            }
+        }
+    }
+
+    impl std::fmt::Display for LoadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.message())
+        }
+    }
+
+    impl std::error::Error for LoadError {}
+
     /// Load a pipe by name.
-    pub fn load(name: impl AsRef<str>) -> Result<host_pipes::Pipe, String> {
-        host_pipes::load_pipe(name.as_ref())
+    ///
+    /// The name may carry inline options (`table?width=80,header`); the host
+    /// passes them to every `process()` call ahead of the explicit options.
+    pub fn load(name: impl AsRef<str>) -> Result<Pipe, String> {
+        let name = name.as_ref();
+        route!(
+            host_pipes::load_pipe(name).map(|p| Pipe(PipeHandle::Host(p))),
+            |t| fake_open(t, name).map_err(|e| e.message().to_string())
+        )
+    }
+
+    /// Load a pipe like [`load`], saying why loading failed.
+    ///
+    /// This imports `open-pipe`, which hosts from before it lack; a plugin
+    /// that calls it needs a host that has it.
+    pub fn open(name: impl AsRef<str>) -> Result<Pipe, LoadError> {
+        let name = name.as_ref();
+        route!(
+            host_pipes::open_pipe(name)
+                .map(|p| Pipe(PipeHandle::Host(p)))
+                .map_err(LoadError::from),
+            |t| fake_open(t, name)
+        )
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fake_open(t: &super::testing::TestHost, name: &str) -> Result<Pipe, LoadError> {
+        let spec = parse_spec(name).map_err(LoadError::InvalidName)?;
+        match t.pipe(spec.name) {
+            Some(_) => Ok(Pipe(PipeHandle::Fake {
+                name: spec.name.to_string(),
+                options: spec.options,
+            })),
+            None => Err(LoadError::NotFound(format!(
+                "pipe not found: {}",
+                spec.name
+            ))),
+        }
+    }
+
+    /// A pipe returned by [`load`] or [`open`].
+    #[derive(Debug)]
+    pub struct Pipe(PipeHandle);
+
+    #[derive(Debug)]
+    enum PipeHandle {
+        Host(host_pipes::Pipe),
+        #[cfg(not(target_arch = "wasm32"))]
+        Fake {
+            name: String,
+            options: Vec<String>,
+        },
+    }
+
+    impl Pipe {
+        /// The pipe's metadata.
+        pub fn meta(&self) -> PipeMeta {
+            match &self.0 {
+                PipeHandle::Host(p) => p.meta(),
+                #[cfg(not(target_arch = "wasm32"))]
+                PipeHandle::Fake { name, .. } => super::testing::pipe_meta(name),
+            }
+        }
+
+        /// Transform `input`, passing `options` after any inline ones.
+        pub fn process(&self, input: &[u8], options: &[String]) -> Result<Vec<u8>, PipeError> {
+            match &self.0 {
+                PipeHandle::Host(p) => p.process(input, options),
+                #[cfg(not(target_arch = "wasm32"))]
+                PipeHandle::Fake {
+                    name,
+                    options: inline,
+                } => fake_process(name, inline, input, options),
+            }
+        }
+
+        /// Transform everything `reader` yields, sending it to the pipe in
+        /// [`CHUNK_SIZE`] pieces and writing the output to `writer` as it
+        /// comes back, so a large input is never held in memory whole.
+        /// Returns the number of bytes written.
+        ///
+        /// Pipes that do not stream themselves get the input buffered on the
+        /// host and output it all at the end.
+        pub fn process_stream(
+            &self,
+            mut reader: impl std::io::Read,
+            mut writer: impl std::io::Write,
+            options: &[String],
+        ) -> Result<u64, PipeError> {
+            let mut written = 0u64;
+            let mut emit = |bytes: &[u8]| {
+                writer.write_all(bytes).map_err(|e| {
+                    PipeError::TransformError(format!("failed to write pipe output: {e}"))
+                })?;
+                written += bytes.len() as u64;
+                Ok::<_, PipeError>(())
+            };
+            let read_error = |e: std::io::Error| {
+                PipeError::TransformError(format!("failed to read pipe input: {e}"))
+            };
+            match &self.0 {
+                PipeHandle::Host(p) => {
+                    let session = p.process_start(options)?;
+                    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+                    loop {
+                        chunk.clear();
+                        (&mut reader)
+                            .take(CHUNK_SIZE as u64)
+                            .read_to_end(&mut chunk)
+                            .map_err(read_error)?;
+                        if chunk.is_empty() {
+                            break;
+                        }
+                        emit(&p.process_chunk(session, &chunk)?)?;
+                    }
+                    emit(&p.process_end(session)?)?;
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                PipeHandle::Fake {
+                    name,
+                    options: inline,
+                } => {
+                    let mut input = Vec::new();
+                    reader.read_to_end(&mut input).map_err(read_error)?;
+                    emit(&fake_process(name, inline, &input, options)?)?;
+                }
+            }
+            writer.flush().map_err(|e| {
+                PipeError::TransformError(format!("failed to write pipe output: {e}"))
+            })?;
+            Ok(written)
+        }
+
+        /// [`process_stream`](Self::process_stream) into a buffer.
+        pub fn process_reader(
+            &self,
+            reader: impl std::io::Read,
+            options: &[String],
+        ) -> Result<Vec<u8>, PipeError> {
+            let mut out = Vec::new();
+            self.process_stream(reader, &mut out, options)?;
+            Ok(out)
+        }
+    }
+
+    /// Input bytes sent per call by [`Pipe::process_stream`].
+    pub const CHUNK_SIZE: usize = 64 * 1024;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fake_process(
+        name: &str,
+        inline: &[String],
+        input: &[u8],
+        options: &[String],
+    ) -> Result<Vec<u8>, PipeError> {
+        // Called outside the borrow so the fake may use the CDK itself.
+        let Some(process) = super::testing::with_active(|t| t.pipe(name)).flatten() else {
+            return Err(PipeError::TransformError(format!(
+                "test host is not running: {name}"
+            )));
+        };
+        let options: Vec<String> = inline.iter().chain(options).cloned().collect();
+        process(input, &options)
+    }
+
+    /// Split a pipe spec such as `format/table?width=80,header` into the pipe
+    /// name and its options (`["width=80", "header"]`).
+    pub fn parse_spec(spec: &str) -> Result<PipeSpec<'_>, String> {
+        wacli_argparse::pipe_spec::parse(spec)
+    }
+}
+
+/// The `--format` / `--option` convention for commands that format output both
+/// themselves and through pipes.
+///
+/// A command following it declares:
+///
+/// - `format` (`--format <FORMAT>`): a built-in format, or a pipe under the
+///   command's pipe prefix, named without the prefix (`table` for
+///   `format/table`) or in full, optionally with inline options
+///   (`table?width=80,header`);
+/// - `option` (`--option <KEY=VALUE>`, repeatable): passed to the pipe's
+///   `process()` after any inline options;
+/// - optionally `list-formats` (`--list-formats`), which like `--format help`
+///   lists every choice.
+///
+/// Without `--format` the first built-in format is used. A name that is both a
+/// built-in and a pipe resolves to the built-in, with a warning; the pipe stays
+/// reachable by its full name.
+///
+/// ```rust,ignore
+/// let m = ctx.matches(&meta)?;
+/// match output::negotiate(&m, &["plain", "json"], "format/")? {
+///     Format::Builtin(kind) if kind == "json" => io::println(to_json(&rows)),
+///     Format::Builtin(_) => io::println(to_plain(&rows)),
+///     Format::Pipe { name, options } => {
+///         let out = pipes::load(&name)?.process(render(&rows).as_bytes(), &options)?;
+///         io::print(String::from_utf8_lossy(&out));
+///     }
+///     Format::List(text) => io::print(text),
+/// }
+/// ```
+#[cfg(feature = "command")]
+pub mod output {
+    use super::{CommandError, PipeInfo, args::Matches, args::messages};
+
+    /// Arg carrying the format name.
+    pub const FORMAT_ARG: &str = "format";
+    /// Repeatable arg carrying pipe options.
+    pub const OPTION_ARG: &str = "option";
+    /// Optional flag listing the available formats.
+    pub const LIST_FORMATS_ARG: &str = "list-formats";
+
+    /// What a command should do with its output.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Format {
+        /// One of the command's own formats.
+        Builtin(String),
+        /// Run the output through the pipe `name` (full name) with `options`.
+        Pipe { name: String, options: Vec<String> },
+        /// `--format help` or `--list-formats`: the text to print.
+        List(String),
+    }
+
+    /// Resolve the `--format` value in `m` against `builtin` and the pipes
+    /// named `<pipe_prefix><name>`. Warnings go to stderr.
+    pub fn negotiate(
+        m: &Matches<'_>,
+        builtin: &[&str],
+        pipe_prefix: &str,
+    ) -> Result<Format, CommandError> {
+        negotiate_with(m, builtin, pipe_prefix, &super::pipes::list(), |w| {
+            super::io::eprintln(format!("warning: {w}"))
+        })
+    }
+
+    /// [`negotiate`] against an explicit pipe list, reporting warnings to `warn`.
+    pub fn negotiate_with(
+        m: &Matches<'_>,
+        builtin: &[&str],
+        pipe_prefix: &str,
+        pipes: &[PipeInfo],
+        mut warn: impl FnMut(&str),
+    ) -> Result<Format, CommandError> {
+        let pipes: Vec<(&str, &PipeInfo)> = pipes
+            .iter()
+            .filter_map(|p| Some((p.name.strip_prefix(pipe_prefix)?, p)))
+            .collect();
+        let value = m.get(FORMAT_ARG);
+        if value == Some("help") || m.get_flag(LIST_FORMATS_ARG) {
+            return Ok(Format::List(list(builtin, &pipes)));
+        }
+
+        let spec = value
+            .map(super::pipes::parse_spec)
+            .transpose()
+            .map_err(CommandError::InvalidArgs)?;
+        let mut options = spec.as_ref().map(|s| s.options.clone()).unwrap_or_default();
+        options.extend(
+            m.get_all(OPTION_ARG)
+                .unwrap_or_default()
+                .iter()
+                .map(|o| o.to_string()),
+        );
+        let builtin_format = |kind: &str, warn: &mut dyn FnMut(&str)| {
+            if !options.is_empty() {
+                warn(&format!(
+                    "--option is ignored by the built-in '{kind}' format"
+                ));
+            }
+            Format::Builtin(kind.to_string())
+        };
+
+        let Some(value) = spec.map(|s| s.name) else {
+            return match builtin.first() {
+                Some(kind) => Ok(builtin_format(kind, &mut warn)),
+                None => Err(CommandError::InvalidArgs(messages::missing_required(&[
+                    "--format <FORMAT>",
+                ]))),
+            };
+        };
+        let short = value.strip_prefix(pipe_prefix).unwrap_or(value);
+        let pipe = pipes.iter().find(|(name, _)| *name == short);
+        if builtin.contains(&value) {
+            if let Some((_, info)) = pipe {
+                warn(&format!(
+                    "format '{value}' is both built-in and the pipe '{}'; using the built-in \
+                     (pass --format {} for the pipe)",
+                    info.name, info.name
+                ));
+            }
+            return Ok(builtin_format(value, &mut warn));
+        }
+        if let Some((_, info)) = pipe {
+            return Ok(Format::Pipe {
+                name: info.name.clone(),
+                options,
+            });
+        }
+
+        let mut choices: Vec<&str> = builtin.to_vec();
+        for (name, _) in &pipes {
+            if !choices.contains(name) {
+                choices.push(name);
+            }
+        }
+        choices.push("help");
+        Err(CommandError::InvalidArgs(messages::invalid_value(
+            value, "--format", &choices,
+        )))
+    }
+
+    /// The `--format help` text: built-ins first, then pipes. A pipe shadowed
+    /// by a built-in is listed under its full name.
+    fn list(builtin: &[&str], pipes: &[(&str, &PipeInfo)]) -> String {
+        let mut rows: Vec<(&str, String)> =
+            builtin.iter().map(|b| (*b, "built-in".into())).collect();
+        for (name, info) in pipes {
+            let name = if builtin.contains(name) {
+                info.name.as_str()
+            } else {
+                name
+            };
+            let what = if info.summary.is_empty() {
+                format!("pipe {}", info.name)
+            } else {
+                format!("{} (pipe {})", info.summary, info.name)
+            };
+            rows.push((name, what));
+        }
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let mut out = String::from("Formats:\n");
+        for (name, what) in rows {
+            out.push_str(&format!("  {name:<width$}  {what}\n"));
+        }
+        out
+    }
+}
+
+/// Helpers for an app's telemetry command.
+///
+/// When `build.telemetryCommand` names a (hidden) command, core runs it after
+/// every other command with a single JSON argument describing the run:
+///
+/// ```json
+/// {"command":"greet","exit_code":0,"duration_ms":3}
+/// ```
+///
+/// The event never contains argv, environment values or paths; users opt out
+/// with `<APP>_NO_TELEMETRY`, in which case the command is not run at all.
+/// Core performs no network I/O itself: what happens to the event is up to the
+/// telemetry command. It should stay quiet (no stdout/stderr output), since it
+/// runs after the user's command has already printed its results.
+///
+/// ```rust,ignore
+/// fn run(argv: Vec<String>) -> CommandResult {
+///     let event = wacli_cdk::telemetry::TelemetryEvent::from_argv(&argv)?;
+///     wacli_cdk::fs::write("usage.log", format!("{} {}\n", event.command, event.exit_code))?;
+///     Ok(0)
+/// }
+/// ```
+#[cfg(feature = "command")]
+pub mod telemetry {
+    use super::CommandError;
+
+    /// One usage event, as sent by core.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TelemetryEvent {
+        /// Canonical command name (`db/migrate` for grouped commands).
+        pub command: String,
+        pub exit_code: u32,
+        pub duration_ms: u64,
+    }
+
+    impl TelemetryEvent {
+        /// Parse the event from a telemetry command's `argv`.
+        pub fn from_argv(argv: &[String]) -> Result<Self, CommandError> {
+            match argv {
+                [json] => Self::parse(json),
+                _ => Err(CommandError::InvalidArgs(format!(
+                    "expected one telemetry event argument, got {}",
+                    argv.len()
+                ))),
+            }
+        }
+
+        /// Parse the JSON event object. Unknown keys are ignored.
+        pub fn parse(json: &str) -> Result<Self, CommandError> {
+            let mut p = Parser {
+                s: json.as_bytes(),
+                pos: 0,
+            };
+            let mut command = None;
+            let mut exit_code = None;
+            let mut duration_ms = None;
+
+            p.expect(b'{')?;
+            if !p.eat(b'}') {
+                loop {
+                    let key = p.string()?;
+                    p.expect(b':')?;
+                    match key.as_str() {
+                        "command" => command = Some(p.string()?),
+                        "exit_code" => {
+                            exit_code = Some(u32::try_from(p.number()?).map_err(|_| {
+                                CommandError::InvalidArgs("exit_code out of range".into())
+                            })?)
+                        }
+                        "duration_ms" => duration_ms = Some(p.number()?),
+                        _ => p.skip_value()?,
+                    }
+                    if p.eat(b',') {
+                        continue;
+                    }
+                    p.expect(b'}')?;
+                    break;
+                }
+            }
+            p.ws();
+            if p.pos != p.s.len() {
+                return Err(p.error("trailing characters"));
+            }
+
+            let missing =
+                |key: &str| CommandError::InvalidArgs(format!("telemetry event missing '{key}'"));
+            Ok(Self {
+                command: command.ok_or_else(|| missing("command"))?,
+                exit_code: exit_code.ok_or_else(|| missing("exit_code"))?,
+                duration_ms: duration_ms.ok_or_else(|| missing("duration_ms"))?,
+            })
+        }
+    }
+
+    /// Minimal parser for the flat object core emits.
+    struct Parser<'a> {
+        s: &'a [u8],
+        pos: usize,
+    }
+
+    impl Parser<'_> {
+        fn error(&self, msg: &str) -> CommandError {
+            CommandError::InvalidArgs(format!(
+                "invalid telemetry event at byte {}: {msg}",
+                self.pos
+            ))
+        }
+
+        fn ws(&mut self) {
+            while self.s.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            }
+        }
+
+        fn eat(&mut self, b: u8) -> bool {
+            self.ws();
+            if self.s.get(self.pos) == Some(&b) {
+                self.pos += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn expect(&mut self, b: u8) -> Result<(), CommandError> {
+            if self.eat(b) {
+                Ok(())
+            } else {
+                Err(self.error(&format!("expected '{}'", b as char)))
+            }
+        }
+
+        fn string(&mut self) -> Result<String, CommandError> {
+            self.expect(b'"')?;
+            let mut out = String::new();
+            loop {
+                let rest = std::str::from_utf8(&self.s[self.pos..])
+                    .map_err(|_| self.error("invalid UTF-8"))?;
+                let Some(c) = rest.chars().next() else {
+                    return Err(self.error("unterminated string"));
+                };
+                self.pos += c.len_utf8();
+                match c {
+                    '"' => return Ok(out),
+                    '\\' => {
+                        let esc = self.s.get(self.pos).copied();
+                        self.pos += 1;
+                        match esc {
+                            Some(b'"') => out.push('"'),
+                            Some(b'\\') => out.push('\\'),
+                            Some(b'/') => out.push('/'),
+                            Some(b'b') => out.push('\u{8}'),
+                            Some(b'f') => out.push('\u{c}'),
+                            Some(b'n') => out.push('\n'),
+                            Some(b'r') => out.push('\r'),
+                            Some(b't') => out.push('\t'),
+                            Some(b'u') => {
+                                let hex = self
+                                    .s
+                                    .get(self.pos..self.pos + 4)
+                                    .and_then(|h| std::str::from_utf8(h).ok())
+                                    .and_then(|h| u32::from_str_radix(h, 16).ok())
+                                    .ok_or_else(|| self.error("invalid \\u escape"))?;
+                                self.pos += 4;
+                                out.push(char::from_u32(hex).unwrap_or('\u{fffd}'));
+                            }
+                            _ => return Err(self.error("invalid escape")),
+                        }
+                    }
+                    c => out.push(c),
+                }
+            }
+        }
+
+        fn number(&mut self) -> Result<u64, CommandError> {
+            self.ws();
+            let start = self.pos;
+            while self.s.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                self.pos += 1;
+            }
+            std::str::from_utf8(&self.s[start..self.pos])
+                .ok()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| self.error("expected a number"))
+        }
+
+        /// Skip a scalar value (string, number, `true`, `false`, `null`).
+        fn skip_value(&mut self) -> Result<(), CommandError> {
+            self.ws();
+            match self.s.get(self.pos) {
+                Some(b'"') => self.string().map(drop),
+                Some(_) => {
+                    let start = self.pos;
+                    while self
+                        .s
+                        .get(self.pos)
+                        .is_some_and(|b| b.is_ascii_alphanumeric() || b"+-.".contains(b))
+                    {
+                        self.pos += 1;
+                    }
+                    if self.pos == start {
+                        Err(self.error("unsupported value"))
+                    } else {
+                        Ok(())
+                    }
+                }
+                None => Err(self.error("unexpected end of input")),
+            }
+        }
     }
 }
@@ -0,0 +1,1041 @@
+// Generated by `wit-bindgen` 0.52.0 for the `multi-plugin` world of
+// `wacli:cli@2.1.0` (`wit/multi`). DO NOT EDIT!
+//
+// Only the `multi-command` export is kept, under the unversioned module path
+// of `bindings`; everything else is shared with `bindings`. The world's type is a `const` rather than a linked section, so
+// it only reaches components that use `export_multi!`.
+#[rustfmt::skip]
+#[allow(dead_code, clippy::all)]
+pub mod exports {
+    pub mod wacli {
+        pub mod cli {
+            /// Several commands in one component, so they share one copy of the CDK.
+            ///
+            /// `command` stays exported (as the first command), since every CDK
+            /// component links the `plugin` world.
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod multi_command {
+                use super::super::super::super::_rt;
+                pub type CommandMeta = crate::bindings::wacli::cli::types::CommandMeta;
+                pub type CommandResult = crate::bindings::wacli::cli::types::CommandResult;
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_list_commands_cabi<T: Guest>() -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result0 = { T::list_commands() };
+                        let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        let vec20 = result0;
+                        let len20 = vec20.len();
+                        let layout20 = _rt::alloc::Layout::from_size_align(
+                                vec20.len() * (17 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            )
+                            .unwrap();
+                        let (result20, _cleanup20) = wit_bindgen::rt::Cleanup::new(
+                            layout20,
+                        );
+                        if let Some(cleanup) = _cleanup20 {
+                            cleanup.forget();
+                        }
+                        for (i, e) in vec20.into_iter().enumerate() {
+                            let base = result20
+                                .add(i * (17 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let crate::bindings::wacli::cli::types::CommandMeta {
+                                    name: name2,
+                                    summary: summary2,
+                                    usage: usage2,
+                                    aliases: aliases2,
+                                    version: version2,
+                                    hidden: hidden2,
+                                    description: description2,
+                                    examples: examples2,
+                                    args: args2,
+                                } = e;
+                                let vec3 = (name2.into_bytes()).into_boxed_slice();
+                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                let len3 = vec3.len();
+                                ::core::mem::forget(vec3);
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len3;
+                                *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                                let vec4 = (summary2.into_bytes()).into_boxed_slice();
+                                let ptr4 = vec4.as_ptr().cast::<u8>();
+                                let len4 = vec4.len();
+                                ::core::mem::forget(vec4);
+                                *base
+                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len4;
+                                *base
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr4.cast_mut();
+                                let vec5 = (usage2.into_bytes()).into_boxed_slice();
+                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                let len5 = vec5.len();
+                                ::core::mem::forget(vec5);
+                                *base
+                                    .add(5 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len5;
+                                *base
+                                    .add(4 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr5.cast_mut();
+                                let vec7 = aliases2;
+                                let len7 = vec7.len();
+                                let layout7 = _rt::alloc::Layout::from_size_align(
+                                        vec7.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    )
+                                    .unwrap();
+                                let (result7, _cleanup7) = wit_bindgen::rt::Cleanup::new(
+                                    layout7,
+                                );
+                                if let Some(cleanup) = _cleanup7 {
+                                    cleanup.forget();
+                                }
+                                for (i, e) in vec7.into_iter().enumerate() {
+                                    let base = result7
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let vec6 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                        let len6 = vec6.len();
+                                        ::core::mem::forget(vec6);
+                                        *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len6;
+                                        *base.add(0).cast::<*mut u8>() = ptr6.cast_mut();
+                                    }
+                                }
+                                *base
+                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len7;
+                                *base
+                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = result7;
+                                let vec8 = (version2.into_bytes()).into_boxed_slice();
+                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                let len8 = vec8.len();
+                                ::core::mem::forget(vec8);
+                                *base
+                                    .add(9 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len8;
+                                *base
+                                    .add(8 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr8.cast_mut();
+                                *base
+                                    .add(10 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>() = (match hidden2 {
+                                    true => 1,
+                                    false => 0,
+                                }) as u8;
+                                let vec9 = (description2.into_bytes()).into_boxed_slice();
+                                let ptr9 = vec9.as_ptr().cast::<u8>();
+                                let len9 = vec9.len();
+                                ::core::mem::forget(vec9);
+                                *base
+                                    .add(12 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len9;
+                                *base
+                                    .add(11 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr9.cast_mut();
+                                let vec11 = examples2;
+                                let len11 = vec11.len();
+                                let layout11 = _rt::alloc::Layout::from_size_align(
+                                        vec11.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    )
+                                    .unwrap();
+                                let (result11, _cleanup11) = wit_bindgen::rt::Cleanup::new(
+                                    layout11,
+                                );
+                                if let Some(cleanup) = _cleanup11 {
+                                    cleanup.forget();
+                                }
+                                for (i, e) in vec11.into_iter().enumerate() {
+                                    let base = result11
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let vec10 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr10 = vec10.as_ptr().cast::<u8>();
+                                        let len10 = vec10.len();
+                                        ::core::mem::forget(vec10);
+                                        *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len10;
+                                        *base.add(0).cast::<*mut u8>() = ptr10.cast_mut();
+                                    }
+                                }
+                                *base
+                                    .add(14 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len11;
+                                *base
+                                    .add(13 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = result11;
+                                let vec19 = args2;
+                                let len19 = vec19.len();
+                                let layout19 = _rt::alloc::Layout::from_size_align(
+                                        vec19.len() * (18 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    )
+                                    .unwrap();
+                                let (result19, _cleanup19) = wit_bindgen::rt::Cleanup::new(
+                                    layout19,
+                                );
+                                if let Some(cleanup) = _cleanup19 {
+                                    cleanup.forget();
+                                }
+                                for (i, e) in vec19.into_iter().enumerate() {
+                                    let base = result19
+                                        .add(i * (18 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let crate::bindings::wacli::cli::types::ArgDef {
+                                            name: name12,
+                                            short: short12,
+                                            long: long12,
+                                            help: help12,
+                                            required: required12,
+                                            default_value: default_value12,
+                                            value_name: value_name12,
+                                            takes_value: takes_value12,
+                                        } = e;
+                                        let vec13 = (name12.into_bytes()).into_boxed_slice();
+                                        let ptr13 = vec13.as_ptr().cast::<u8>();
+                                        let len13 = vec13.len();
+                                        ::core::mem::forget(vec13);
+                                        *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len13;
+                                        *base.add(0).cast::<*mut u8>() = ptr13.cast_mut();
+                                        match short12 {
+                                            Some(e) => {
+                                                *base
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (1i32) as u8;
+                                                let vec14 = (e.into_bytes()).into_boxed_slice();
+                                                let ptr14 = vec14.as_ptr().cast::<u8>();
+                                                let len14 = vec14.len();
+                                                ::core::mem::forget(vec14);
+                                                *base
+                                                    .add(4 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len14;
+                                                *base
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>() = ptr14.cast_mut();
+                                            }
+                                            None => {
+                                                *base
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (0i32) as u8;
+                                            }
+                                        };
+                                        match long12 {
+                                            Some(e) => {
+                                                *base
+                                                    .add(5 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (1i32) as u8;
+                                                let vec15 = (e.into_bytes()).into_boxed_slice();
+                                                let ptr15 = vec15.as_ptr().cast::<u8>();
+                                                let len15 = vec15.len();
+                                                ::core::mem::forget(vec15);
+                                                *base
+                                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len15;
+                                                *base
+                                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>() = ptr15.cast_mut();
+                                            }
+                                            None => {
+                                                *base
+                                                    .add(5 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (0i32) as u8;
+                                            }
+                                        };
+                                        let vec16 = (help12.into_bytes()).into_boxed_slice();
+                                        let ptr16 = vec16.as_ptr().cast::<u8>();
+                                        let len16 = vec16.len();
+                                        ::core::mem::forget(vec16);
+                                        *base
+                                            .add(9 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len16;
+                                        *base
+                                            .add(8 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr16.cast_mut();
+                                        *base
+                                            .add(10 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (match required12 {
+                                            true => 1,
+                                            false => 0,
+                                        }) as u8;
+                                        match default_value12 {
+                                            Some(e) => {
+                                                *base
+                                                    .add(11 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (1i32) as u8;
+                                                let vec17 = (e.into_bytes()).into_boxed_slice();
+                                                let ptr17 = vec17.as_ptr().cast::<u8>();
+                                                let len17 = vec17.len();
+                                                ::core::mem::forget(vec17);
+                                                *base
+                                                    .add(13 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len17;
+                                                *base
+                                                    .add(12 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>() = ptr17.cast_mut();
+                                            }
+                                            None => {
+                                                *base
+                                                    .add(11 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (0i32) as u8;
+                                            }
+                                        };
+                                        match value_name12 {
+                                            Some(e) => {
+                                                *base
+                                                    .add(14 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (1i32) as u8;
+                                                let vec18 = (e.into_bytes()).into_boxed_slice();
+                                                let ptr18 = vec18.as_ptr().cast::<u8>();
+                                                let len18 = vec18.len();
+                                                ::core::mem::forget(vec18);
+                                                *base
+                                                    .add(16 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len18;
+                                                *base
+                                                    .add(15 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>() = ptr18.cast_mut();
+                                            }
+                                            None => {
+                                                *base
+                                                    .add(14 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<u8>() = (0i32) as u8;
+                                            }
+                                        };
+                                        *base
+                                            .add(17 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (match takes_value12 {
+                                            true => 1,
+                                            false => 0,
+                                        }) as u8;
+                                    }
+                                }
+                                *base
+                                    .add(16 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len19;
+                                *base
+                                    .add(15 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = result19;
+                            }
+                        }
+                        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len20;
+                        *ptr1.add(0).cast::<*mut u8>() = result20;
+                        ptr1
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_list_commands<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = *arg0.add(0).cast::<*mut u8>();
+                        let l1 = *arg0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base41 = l0;
+                        let len41 = l1;
+                        for i in 0..len41 {
+                            let base = base41
+                                .add(i * (17 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let l2 = *base.add(0).cast::<*mut u8>();
+                                let l3 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l2, l3, 1);
+                                let l4 = *base
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *base
+                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l4, l5, 1);
+                                let l6 = *base
+                                    .add(4 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l7 = *base
+                                    .add(5 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l6, l7, 1);
+                                let l8 = *base
+                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l9 = *base
+                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base12 = l8;
+                                let len12 = l9;
+                                for i in 0..len12 {
+                                    let base = base12
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let l10 = *base.add(0).cast::<*mut u8>();
+                                        let l11 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l10, l11, 1);
+                                    }
+                                }
+                                _rt::cabi_dealloc(
+                                    base12,
+                                    len12 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                let l13 = *base
+                                    .add(8 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l14 = *base
+                                    .add(9 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l13, l14, 1);
+                                let l15 = *base
+                                    .add(11 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l16 = *base
+                                    .add(12 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l15, l16, 1);
+                                let l17 = *base
+                                    .add(13 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l18 = *base
+                                    .add(14 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base21 = l17;
+                                let len21 = l18;
+                                for i in 0..len21 {
+                                    let base = base21
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let l19 = *base.add(0).cast::<*mut u8>();
+                                        let l20 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l19, l20, 1);
+                                    }
+                                }
+                                _rt::cabi_dealloc(
+                                    base21,
+                                    len21 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                let l22 = *base
+                                    .add(15 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l23 = *base
+                                    .add(16 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base40 = l22;
+                                let len40 = l23;
+                                for i in 0..len40 {
+                                    let base = base40
+                                        .add(i * (18 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        let l24 = *base.add(0).cast::<*mut u8>();
+                                        let l25 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l24, l25, 1);
+                                        let l26 = i32::from(
+                                            *base
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
+                                        match l26 {
+                                            0 => {}
+                                            _ => {
+                                                let l27 = *base
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l28 = *base
+                                                    .add(4 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                _rt::cabi_dealloc(l27, l28, 1);
+                                            }
+                                        }
+                                        let l29 = i32::from(
+                                            *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
+                                        match l29 {
+                                            0 => {}
+                                            _ => {
+                                                let l30 = *base
+                                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l31 = *base
+                                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                _rt::cabi_dealloc(l30, l31, 1);
+                                            }
+                                        }
+                                        let l32 = *base
+                                            .add(8 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l33 = *base
+                                            .add(9 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l32, l33, 1);
+                                        let l34 = i32::from(
+                                            *base
+                                                .add(11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
+                                        match l34 {
+                                            0 => {}
+                                            _ => {
+                                                let l35 = *base
+                                                    .add(12 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l36 = *base
+                                                    .add(13 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                _rt::cabi_dealloc(l35, l36, 1);
+                                            }
+                                        }
+                                        let l37 = i32::from(
+                                            *base
+                                                .add(14 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>(),
+                                        );
+                                        match l37 {
+                                            0 => {}
+                                            _ => {
+                                                let l38 = *base
+                                                    .add(15 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l39 = *base
+                                                    .add(16 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                _rt::cabi_dealloc(l38, l39, 1);
+                                            }
+                                        }
+                                    }
+                                }
+                                _rt::cabi_dealloc(
+                                    base40,
+                                    len40 * (18 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                            }
+                        }
+                        _rt::cabi_dealloc(
+                            base41,
+                            len41 * (17 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_run_named_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result5 = {
+                            let len0 = arg1;
+                            let bytes0 = _rt::Vec::from_raw_parts(
+                                arg0.cast(),
+                                len0,
+                                len0,
+                            );
+                            let base4 = arg2;
+                            let len4 = arg3;
+                            let mut result4 = _rt::Vec::with_capacity(len4);
+                            for i in 0..len4 {
+                                let base = base4
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                let e4 = {
+                                    let l1 = *base.add(0).cast::<*mut u8>();
+                                    let l2 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len3 = l2;
+                                    let bytes3 = _rt::Vec::from_raw_parts(
+                                        l1.cast(),
+                                        len3,
+                                        len3,
+                                    );
+                                    _rt::string_lift(bytes3)
+                                };
+                                result4.push(e4);
+                            }
+                            _rt::cabi_dealloc(
+                                base4,
+                                len4 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            T::run_named(_rt::string_lift(bytes0), result4)
+                        };
+                        let ptr6 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        match result5 {
+                            Ok(e) => {
+                                *ptr6.add(0).cast::<u8>() = (0i32) as u8;
+                                *ptr6
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>() = _rt::as_i32(e);
+                            }
+                            Err(e) => {
+                                *ptr6.add(0).cast::<u8>() = (1i32) as u8;
+                                use crate::bindings::wacli::cli::types::CommandError as V11;
+                                match e {
+                                    V11::UnknownCommand(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (0i32) as u8;
+                                        let vec7 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr7 = vec7.as_ptr().cast::<u8>();
+                                        let len7 = vec7.len();
+                                        ::core::mem::forget(vec7);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len7;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr7.cast_mut();
+                                    }
+                                    V11::InvalidArgs(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (1i32) as u8;
+                                        let vec8 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                                        let len8 = vec8.len();
+                                        ::core::mem::forget(vec8);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len8;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr8.cast_mut();
+                                    }
+                                    V11::Failed(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (2i32) as u8;
+                                        let vec9 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr9 = vec9.as_ptr().cast::<u8>();
+                                        let len9 = vec9.len();
+                                        ::core::mem::forget(vec9);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len9;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr9.cast_mut();
+                                    }
+                                    V11::Io(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (3i32) as u8;
+                                        let vec10 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr10 = vec10.as_ptr().cast::<u8>();
+                                        let len10 = vec10.len();
+                                        ::core::mem::forget(vec10);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len10;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr10.cast_mut();
+                                    }
+                                }
+                            }
+                        };
+                        ptr6
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_run_named<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                        match l0 {
+                            0 => {}
+                            _ => {
+                                let l1 = i32::from(
+                                    *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l1 {
+                                    0 => {
+                                        let l2 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l3 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l2, l3, 1);
+                                    }
+                                    1 => {
+                                        let l4 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l5 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l4, l5, 1);
+                                    }
+                                    2 => {
+                                        let l6 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l7 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l6, l7, 1);
+                                    }
+                                    _ => {
+                                        let l8 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l9 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l8, l9, 1);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                pub trait Guest {
+                    /// `meta()` of every command, in declaration order.
+                    #[allow(async_fn_in_trait)]
+                    fn list_commands() -> _rt::Vec<CommandMeta>;
+                    /// Run the command whose `meta.name` is `name`.
+                    #[allow(async_fn_in_trait)]
+                    fn run_named(
+                        name: _rt::String,
+                        argv: _rt::Vec<_rt::String>,
+                    ) -> CommandResult;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_wacli_cli_multi_command_2_0_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[unsafe (export_name =
+                        "wacli:cli/multi-command@2.1.0#list-commands")] unsafe extern "C"
+                        fn export_list_commands() -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_list_commands_cabi::<$ty > () } }
+                        #[unsafe (export_name =
+                        "cabi_post_wacli:cli/multi-command@2.1.0#list-commands")] unsafe
+                        extern "C" fn _post_return_list_commands(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_list_commands::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "wacli:cli/multi-command@2.1.0#run-named")] unsafe extern "C" fn
+                        export_run_named(arg0 : * mut u8, arg1 : usize, arg2 : * mut u8,
+                        arg3 : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_run_named_cabi::<$ty > (arg0, arg1, arg2, arg3) } }
+                        #[unsafe (export_name =
+                        "cabi_post_wacli:cli/multi-command@2.1.0#run-named")] unsafe
+                        extern "C" fn _post_return_run_named(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_run_named::<$ty > (arg0) } }
+                        };
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_wacli_cli_multi_command_2_0_0_cabi;
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 4 * ::core::mem::size_of::<*const u8>()],
+                );
+                static mut _RET_AREA: _RetArea = _RetArea(
+                    [::core::mem::MaybeUninit::uninit(); 4
+                        * ::core::mem::size_of::<*const u8>()],
+                );
+            }
+        }
+    }
+}
+#[rustfmt::skip]
+mod _rt {
+    #![allow(dead_code, unused_imports, clippy::all)]
+    pub use alloc_crate::vec::Vec;
+    pub use alloc_crate::string::String;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            unsafe { String::from_utf8_unchecked(bytes) }
+        }
+    }
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            alloc::dealloc(ptr, layout);
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            unsafe { core::hint::unreachable_unchecked() }
+        }
+    }
+    pub unsafe fn bool_lift(val: u8) -> bool {
+        if cfg!(debug_assertions) {
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
+        } else {
+            val != 0
+        }
+    }
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+    use core::fmt;
+    use core::marker;
+    use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+    /// A type which represents a component model resource, either imported or
+    /// exported into this component.
+    ///
+    /// This is a low-level wrapper which handles the lifetime of the resource
+    /// (namely this has a destructor). The `T` provided defines the component model
+    /// intrinsics that this wrapper uses.
+    ///
+    /// One of the chief purposes of this type is to provide `Deref` implementations
+    /// to access the underlying data when it is owned.
+    ///
+    /// This type is primarily used in generated code for exported and imported
+    /// resources.
+    #[repr(transparent)]
+    pub struct Resource<T: WasmResource> {
+        handle: AtomicU32,
+        _marker: marker::PhantomData<T>,
+    }
+    /// A trait which all wasm resources implement, namely providing the ability to
+    /// drop a resource.
+    ///
+    /// This generally is implemented by generated code, not user-facing code.
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe trait WasmResource {
+        /// Invokes the `[resource-drop]...` intrinsic.
+        unsafe fn drop(handle: u32);
+    }
+    impl<T: WasmResource> Resource<T> {
+        #[doc(hidden)]
+        pub unsafe fn from_handle(handle: u32) -> Self {
+            debug_assert!(handle != 0 && handle != u32::MAX);
+            Self {
+                handle: AtomicU32::new(handle),
+                _marker: marker::PhantomData,
+            }
+        }
+        /// Takes ownership of the handle owned by `resource`.
+        ///
+        /// Note that this ideally would be `into_handle` taking `Resource<T>` by
+        /// ownership. The code generator does not enable that in all situations,
+        /// unfortunately, so this is provided instead.
+        ///
+        /// Also note that `take_handle` is in theory only ever called on values
+        /// owned by a generated function. For example a generated function might
+        /// take `Resource<T>` as an argument but then call `take_handle` on a
+        /// reference to that argument. In that sense the dynamic nature of
+        /// `take_handle` should only be exposed internally to generated code, not
+        /// to user code.
+        #[doc(hidden)]
+        pub fn take_handle(resource: &Resource<T>) -> u32 {
+            resource.handle.swap(u32::MAX, Relaxed)
+        }
+        #[doc(hidden)]
+        pub fn handle(resource: &Resource<T>) -> u32 {
+            resource.handle.load(Relaxed)
+        }
+    }
+    impl<T: WasmResource> fmt::Debug for Resource<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Resource").field("handle", &self.handle).finish()
+        }
+    }
+    impl<T: WasmResource> Drop for Resource<T> {
+        fn drop(&mut self) {
+            unsafe {
+                match self.handle.load(Relaxed) {
+                    u32::MAX => {}
+                    other => T::drop(other),
+                }
+            }
+        }
+    }
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    pub use alloc_crate::alloc;
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_ctors_once() {
+        wit_bindgen::rt::run_ctors_once();
+    }
+    extern crate alloc as alloc_crate;
+}
+#[rustfmt::skip]
+#[doc(hidden)]
+#[allow(clippy::octal_escapes)]
+pub const MULTI_PLUGIN_COMPONENT_TYPE: [u8; 2989] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xaa\x16\x01A\x02\x01\
+A\x19\x01B\x0e\x01ps\x01@\0\0\0\x04\0\x04args\x01\x01\x01o\x02ss\x01p\x02\x01@\0\
+\0\x03\x04\0\x03env\x01\x04\x01ks\x01@\x01\x03keys\0\x05\x04\0\x03get\x01\x06\x01\
+@\0\0s\x04\0\x03cwd\x01\x07\x01@\0\0w\x04\0\x0dmonotonic-now\x01\x08\x03\0\x18wa\
+cli:cli/host-env@2.0.0\x05\0\x01B\x17\x01p}\x01@\x01\x05bytes\0\x01\0\x04\0\x0cs\
+tdout-write\x01\x01\x04\0\x0cstderr-write\x01\x01\x01@\0\x01\0\x04\0\x0cstdout-f\
+lush\x01\x02\x04\0\x0cstderr-flush\x01\x02\x01@\0\0\x7f\x04\0\x12stdout-is-termi\
+nal\x01\x03\x04\0\x12stderr-is-terminal\x01\x03\x04\0\x11stdin-is-terminal\x01\x03\
+\x01ks\x01j\x01\x04\x01s\x01@\0\0\x05\x04\0\x0fstdin-read-line\x01\x06\x01j\x01s\
+\x01s\x01@\x01\x06prompts\0\x07\x04\0\x0bread-secret\x01\x08\x01j\x01\0\x01s\x01\
+@\x01\x03lenw\0\x09\x04\0\x0astdin-read\x01\x0a\x01@\0\0\x09\x04\0\x0estdin-read\
+-all\x01\x0b\x03\0\x17wacli:cli/host-io@2.0.0\x05\x01\x01B\x06\x01m\x05\x05trace\
+\x05debug\x04info\x04warn\x05error\x04\0\x05level\x03\0\0\x01@\x03\x05level\x01\x06\
+targets\x07messages\x01\0\x04\0\x03log\x01\x02\x01@\x01\x05level\x01\0\x7f\x04\0\
+\x07enabled\x01\x03\x03\0\x18wacli:cli/host-log@2.0.0\x05\x02\x01B0\x01m\x04\x04\
+file\x03dir\x07symlink\x05other\x04\0\x09file-kind\x03\0\0\x01r\x03\x04kind\x01\x04\
+sizew\x0bmodified-atw\x04\0\x09file-info\x03\0\x02\x01r\x02\x04paths\x04kind\x01\
+\x04\0\x09dir-entry\x03\0\x04\x04\0\x06writer\x03\x01\x01h\x06\x01p}\x01j\0\x01s\
+\x01@\x02\x04self\x07\x05bytes\x08\0\x09\x04\0\x14[method]writer.write\x01\x0a\x01\
+@\x01\x04self\x07\0\x09\x04\0\x14[method]writer.close\x01\x0b\x01j\x01\x08\x01s\x01\
+@\x01\x04paths\0\x0c\x04\0\x09read-file\x01\x0d\x01@\x02\x04paths\x08contents\x08\
+\0\x09\x04\0\x0awrite-file\x01\x0e\x01@\x01\x04paths\0\x09\x04\0\x0acreate-dir\x01\
+\x0f\x01ps\x01j\x01\x10\x01s\x01@\x01\x04paths\0\x11\x04\0\x08list-dir\x01\x12\x01\
+j\x01w\x01s\x01@\x02\x03srcs\x03dsts\0\x13\x04\0\x09copy-file\x01\x14\x01@\x02\x03\
+srcs\x03dsts\0\x09\x04\0\x06rename\x01\x15\x04\0\x0bremove-file\x01\x0f\x04\0\x0a\
+remove-dir\x01\x0f\x04\0\x0eremove-dir-all\x01\x0f\x01j\x01\x03\x01s\x01@\x01\x04\
+paths\0\x16\x04\0\x04stat\x01\x17\x01@\x01\x04paths\0\x7f\x04\0\x06exists\x01\x18\
+\x01ky\x01p\x05\x01j\x01\x1a\x01s\x01@\x02\x04paths\x09max-depth\x19\0\x1b\x04\0\
+\x08walk-dir\x01\x1c\x04\0\x0bappend-file\x01\x0e\x01i\x06\x01j\x01\x1d\x01s\x01\
+@\x02\x04paths\x06append\x7f\0\x1e\x04\0\x0bopen-writer\x01\x1f\x03\0\x17wacli:c\
+li/host-fs@2.0.0\x05\x03\x01B\x15\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\
+\x04names\x05short\x02\x04long\x02\x04helps\x08required\x7f\x0ddefault-value\x02\
+\x0avalue-name\x02\x0btakes-value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01\
+r\x09\x04names\x07summarys\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0b\
+descriptions\x08examples\x05\x04args\x06\x04\0\x0ccommand-meta\x03\0\x07\x01q\x04\
+\x0funknown-command\x01s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\
+\0\x0dcommand-error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\x0ecommand-result\x03\0\
+\x0b\x01r\x05\x04names\x07summarys\x0binput-types\x05\x0boutput-types\x07version\
+s\x04\0\x09pipe-meta\x03\0\x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\
+\x01s\0\x0einvalid-option\x01s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\
+\x07summarys\x04paths\x04\0\x09pipe-info\x03\0\x11\x01r\x04\x04info\x12\x0binput\
+-types\x05\x0boutput-types\x07versions\x04\0\x0cpipe-details\x03\0\x13\x03\0\x15\
+wacli:cli/types@2.0.0\x05\x04\x02\x03\0\x04\x09exit-code\x01B\x04\x02\x03\x02\x01\
+\x05\x04\0\x09exit-code\x03\0\0\x01@\x01\x04code\x01\x01\0\x04\0\x04exit\x01\x02\
+\x03\0\x1cwacli:cli/host-process@2.0.0\x05\x06\x02\x03\0\x04\x09pipe-meta\x02\x03\
+\0\x04\x0apipe-error\x02\x03\0\x04\x09pipe-info\x02\x03\0\x04\x0cpipe-details\x01\
+B'\x02\x03\x02\x01\x07\x04\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x08\x04\0\x0ap\
+ipe-error\x03\0\x02\x02\x03\x02\x01\x09\x04\0\x09pipe-info\x03\0\x04\x02\x03\x02\
+\x01\x0a\x04\0\x0cpipe-details\x03\0\x06\x01q\x03\x09not-found\x01s\0\x0cinvalid\
+-name\x01s\0\x0bload-failed\x01s\0\x04\0\x0fpipe-load-error\x03\0\x08\x04\0\x04p\
+ipe\x03\x01\x01h\x0a\x01@\x01\x04self\x0b\0\x01\x04\0\x11[method]pipe.meta\x01\x0c\
+\x01p}\x01ps\x01j\x01\x0d\x01\x03\x01@\x03\x04self\x0b\x05input\x0d\x07options\x0e\
+\0\x0f\x04\0\x14[method]pipe.process\x01\x10\x01j\x01y\x01\x03\x01@\x02\x04self\x0b\
+\x07options\x0e\0\x11\x04\0\x1a[method]pipe.process-start\x01\x12\x01@\x03\x04se\
+lf\x0b\x07sessiony\x05bytes\x0d\0\x0f\x04\0\x1a[method]pipe.process-chunk\x01\x13\
+\x01@\x02\x04self\x0b\x07sessiony\0\x0f\x04\0\x18[method]pipe.process-end\x01\x14\
+\x01p\x05\x01@\0\0\x15\x04\0\x0alist-pipes\x01\x16\x01p\x07\x01@\0\0\x17\x04\0\x0e\
+describe-pipes\x01\x18\x01i\x0a\x01j\x01\x19\x01s\x01@\x01\x04names\0\x1a\x04\0\x09\
+load-pipe\x01\x1b\x01j\x01\x19\x01\x09\x01@\x01\x04names\0\x1c\x04\0\x09open-pip\
+e\x01\x1d\x03\0\x1awacli:cli/host-pipes@2.0.0\x05\x0b\x02\x03\0\x04\x0ccommand-m\
+eta\x02\x03\0\x04\x0ecommand-result\x01B\x0a\x02\x03\x02\x01\x0c\x04\0\x0ccomman\
+d-meta\x03\0\0\x02\x03\x02\x01\x0d\x04\0\x0ecommand-result\x03\0\x02\x01p\x01\x01\
+@\0\0\x04\x04\0\x0dlist-commands\x01\x05\x01ps\x01@\x02\x04names\x04argv\x06\0\x03\
+\x04\0\x09run-named\x01\x07\x04\0\x1dwacli:cli/multi-command@2.1.0\x05\x0e\x01B\x09\
+\x02\x03\x02\x01\x0c\x04\0\x0ccommand-meta\x03\0\0\x02\x03\x02\x01\x0d\x04\0\x0e\
+command-result\x03\0\x02\x01@\0\0\x01\x04\0\x04meta\x01\x04\x01ps\x01@\x01\x04ar\
+gv\x05\0\x03\x04\0\x03run\x01\x06\x04\0\x17wacli:cli/command@2.0.0\x05\x0f\x04\0\
+\x1cwacli:cli/multi-plugin@2.1.0\x04\0\x0b\x12\x01\0\x0cmulti-plugin\x03\0\0\0G\x09\
+producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.244.0\x10wit-bindgen-rus\
+t\x060.52.0";
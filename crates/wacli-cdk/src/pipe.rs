@@ -0,0 +1,455 @@
+//! Pipe development kit (the `pipe-plugin` world).
+//!
+//! Enable with `default-features = false, features = ["pipe"]`: a pipe must
+//! not import the host interfaces that the `command` feature links in.
+//!
+//! ```rust,ignore
+//! use wacli_cdk::pipe::{Options, Pipe, PipeError, PipeMeta, pipe_meta};
+//!
+//! struct Upper;
+//!
+//! impl Pipe for Upper {
+//!     fn meta() -> PipeMeta {
+//!         pipe_meta("format/upper")
+//!             .summary("Uppercase text")
+//!             .input_type("text/plain")
+//!             .output_type("text/plain")
+//!             .build()
+//!     }
+//!
+//!     fn process(input: Vec<u8>, options: Vec<String>) -> Result<Vec<u8>, PipeError> {
+//!         let opts = Options::new(&options);
+//!         opts.check_known(&["trim"]).map_err(PipeError::InvalidOption)?;
+//!         let text = String::from_utf8(input)?;
+//!         let text = if opts.has("trim") { text.trim() } else { &text };
+//!         Ok(text.to_uppercase().into_bytes())
+//!     }
+//! }
+//!
+//! wacli_cdk::export_pipe!(Upper);
+//! ```
+//!
+//! These `PipeMeta` / `PipeError` belong to the `pipe-plugin` bindings; with
+//! both features on they are distinct from the crate-root types that commands
+//! get from `wacli_cdk::pipes`.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+pub use crate::pipe_bindings::wacli::cli::types::{PipeError, PipeMeta};
+pub use wacli_argparse::pipe_options::Options;
+
+/// Trait for implementing a wacli pipe.
+pub trait Pipe {
+    /// Return pipe metadata.
+    fn meta() -> PipeMeta;
+
+    /// Transform `input`. `options` are `key=value` strings, or a bare `key`
+    /// for flags, already checked against any declared option schema; read
+    /// them with [`Options`].
+    fn process(input: Vec<u8>, options: Vec<String>) -> Result<Vec<u8>, PipeError>;
+
+    /// Start a chunked run (the `pipe-stream` export) and return its id.
+    ///
+    /// The three streaming methods default to buffering the chunks and
+    /// calling [`Pipe::process`] once in [`Pipe::process_end`]; override all
+    /// three to emit output while the input is still arriving.
+    fn process_start(options: Vec<String>) -> Result<u32, PipeError> {
+        Ok(buffered::start(options))
+    }
+
+    /// Take the next chunk of input for `session`; return any output ready.
+    fn process_chunk(session: u32, bytes: Vec<u8>) -> Result<Vec<u8>, PipeError> {
+        buffered::push(session, &bytes)?;
+        Ok(Vec::new())
+    }
+
+    /// The input for `session` is complete; return the rest of the output.
+    fn process_end(session: u32) -> Result<Vec<u8>, PipeError> {
+        let (options, input) = buffered::finish(session)?;
+        Self::process(input, options)
+    }
+}
+
+/// Sessions of the default, buffering [`Pipe::process_start`].
+mod buffered {
+    use super::{BTreeMap, Mutex, PipeError};
+
+    struct Sessions {
+        next: u32,
+        open: BTreeMap<u32, (Vec<String>, Vec<u8>)>,
+    }
+
+    static SESSIONS: Mutex<Sessions> = Mutex::new(Sessions {
+        next: 0,
+        open: BTreeMap::new(),
+    });
+
+    fn with<R>(f: impl FnOnce(&mut Sessions) -> R) -> R {
+        f(&mut SESSIONS.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn unknown(session: u32) -> PipeError {
+        PipeError::TransformError(format!("unknown pipe session {session}"))
+    }
+
+    pub(super) fn start(options: Vec<String>) -> u32 {
+        with(|s| {
+            let id = s.next;
+            s.next = s.next.wrapping_add(1);
+            s.open.insert(id, (options, Vec::new()));
+            id
+        })
+    }
+
+    pub(super) fn push(session: u32, bytes: &[u8]) -> Result<(), PipeError> {
+        with(|s| match s.open.get_mut(&session) {
+            Some((_, input)) => {
+                input.extend_from_slice(bytes);
+                Ok(())
+            }
+            None => Err(unknown(session)),
+        })
+    }
+
+    pub(super) fn finish(session: u32) -> Result<(Vec<String>, Vec<u8>), PipeError> {
+        with(|s| s.open.remove(&session)).ok_or_else(|| unknown(session))
+    }
+}
+
+/// Export a pipe implementation.
+///
+/// This macro generates the WASM exports required by the `pipe-plugin` world:
+/// `pipe` and, through the [`Pipe`] streaming methods, `pipe-stream`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// struct MyPipe;
+///
+/// impl wacli_cdk::pipe::Pipe for MyPipe {
+///     fn meta() -> wacli_cdk::pipe::PipeMeta {
+///         wacli_cdk::pipe::pipe_meta("format/my-pipe").build()
+///     }
+///
+///     fn process(
+///         input: Vec<u8>,
+///         options: Vec<String>,
+///     ) -> Result<Vec<u8>, wacli_cdk::pipe::PipeError> {
+///         Ok(input)
+///     }
+/// }
+///
+/// wacli_cdk::export_pipe!(MyPipe);
+/// ```
+#[macro_export]
+macro_rules! export_pipe {
+    ($ty:ty) => {
+        const _: () = {
+            struct __WacliPipeShim;
+
+            impl $crate::pipe_bindings::exports::wacli::cli::pipe::Guest for __WacliPipeShim {
+                fn meta() -> $crate::pipe::PipeMeta {
+                    <$ty as $crate::pipe::Pipe>::meta()
+                }
+
+                fn process(
+                    input: Vec<u8>,
+                    options: Vec<String>,
+                ) -> Result<Vec<u8>, $crate::pipe::PipeError> {
+                    <$ty as $crate::pipe::Pipe>::process(input, options)
+                }
+            }
+
+            impl $crate::pipe_bindings::exports::wacli::cli::pipe_stream::Guest for __WacliPipeShim {
+                fn process_start(options: Vec<String>) -> Result<u32, $crate::pipe::PipeError> {
+                    <$ty as $crate::pipe::Pipe>::process_start(options)
+                }
+
+                fn process_chunk(
+                    session: u32,
+                    bytes: Vec<u8>,
+                ) -> Result<Vec<u8>, $crate::pipe::PipeError> {
+                    <$ty as $crate::pipe::Pipe>::process_chunk(session, bytes)
+                }
+
+                fn process_end(session: u32) -> Result<Vec<u8>, $crate::pipe::PipeError> {
+                    <$ty as $crate::pipe::Pipe>::process_end(session)
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/pipe@2.0.0#meta")]
+            unsafe extern "C" fn __export_meta() -> *mut u8 {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe::_export_meta_cabi::<
+                        __WacliPipeShim,
+                    >()
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/pipe@2.0.0#process")]
+            unsafe extern "C" fn __export_process(
+                arg0: *mut u8,
+                arg1: usize,
+                arg2: *mut u8,
+                arg3: usize,
+            ) -> *mut u8 {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe::_export_process_cabi::<
+                        __WacliPipeShim,
+                    >(arg0, arg1, arg2, arg3)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/pipe@2.0.0#meta")]
+            unsafe extern "C" fn __post_return_meta(arg0: *mut u8) {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe::__post_return_meta::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/pipe@2.0.0#process")]
+            unsafe extern "C" fn __post_return_process(arg0: *mut u8) {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe::__post_return_process::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/pipe-stream@2.0.0#process-start")]
+            unsafe extern "C" fn __export_process_start(arg0: *mut u8, arg1: usize) -> *mut u8 {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::_export_process_start_cabi::<
+                        __WacliPipeShim,
+                    >(arg0, arg1)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/pipe-stream@2.0.0#process-start")]
+            unsafe extern "C" fn __post_return_process_start(arg0: *mut u8) {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::__post_return_process_start::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/pipe-stream@2.0.0#process-chunk")]
+            unsafe extern "C" fn __export_process_chunk(
+                arg0: i32,
+                arg1: *mut u8,
+                arg2: usize,
+            ) -> *mut u8 {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::_export_process_chunk_cabi::<
+                        __WacliPipeShim,
+                    >(arg0, arg1, arg2)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/pipe-stream@2.0.0#process-chunk")]
+            unsafe extern "C" fn __post_return_process_chunk(arg0: *mut u8) {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::__post_return_process_chunk::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "wacli:cli/pipe-stream@2.0.0#process-end")]
+            unsafe extern "C" fn __export_process_end(arg0: i32) -> *mut u8 {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::_export_process_end_cabi::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+
+            #[unsafe(export_name = "cabi_post_wacli:cli/pipe-stream@2.0.0#process-end")]
+            unsafe extern "C" fn __post_return_process_end(arg0: *mut u8) {
+                unsafe {
+                    $crate::pipe_bindings::exports::wacli::cli::pipe_stream::__post_return_process_end::<
+                        __WacliPipeShim,
+                    >(arg0)
+                }
+            }
+        };
+    };
+}
+
+/// Create a pipe metadata builder.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// wacli_cdk::pipe::pipe_meta("format/table")
+///     .summary("Render rows as a table")
+///     .input_type("text/plain")
+///     .output_type("text/plain")
+///     .version("1.0.0")
+///     .build()
+/// ```
+pub fn pipe_meta(name: impl Into<String>) -> PipeMetaBuilder {
+    PipeMetaBuilder::new(name)
+}
+
+/// Builder for `PipeMeta`.
+#[derive(Default)]
+pub struct PipeMetaBuilder {
+    name: String,
+    summary: String,
+    input_types: Vec<String>,
+    output_type: String,
+    version: String,
+}
+
+impl PipeMetaBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = summary.into();
+        self
+    }
+
+    /// Add an accepted input type; call once per type.
+    pub fn input_type(mut self, input_type: impl Into<String>) -> Self {
+        self.input_types.push(input_type.into());
+        self
+    }
+
+    pub fn output_type(mut self, output_type: impl Into<String>) -> Self {
+        self.output_type = output_type.into();
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn build(self) -> PipeMeta {
+        PipeMeta {
+            name: self.name,
+            summary: self.summary,
+            input_types: self.input_types,
+            output_type: self.output_type,
+            version: self.version,
+        }
+    }
+}
+
+impl From<String> for PipeError {
+    fn from(s: String) -> Self {
+        PipeError::TransformError(s)
+    }
+}
+
+impl From<&str> for PipeError {
+    fn from(s: &str) -> Self {
+        PipeError::TransformError(s.to_string())
+    }
+}
+
+impl From<std::io::Error> for PipeError {
+    fn from(e: std::io::Error) -> Self {
+        PipeError::TransformError(e.to_string())
+    }
+}
+
+impl From<std::string::FromUtf8Error> for PipeError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        PipeError::ParseError(e.to_string())
+    }
+}
+
+impl From<std::str::Utf8Error> for PipeError {
+    fn from(e: std::str::Utf8Error) -> Self {
+        PipeError::ParseError(e.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for PipeError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        PipeError::InvalidOption(e.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for PipeError {
+    fn from(e: std::num::ParseFloatError) -> Self {
+        PipeError::InvalidOption(e.to_string())
+    }
+}
+
+impl From<std::str::ParseBoolError> for PipeError {
+    fn from(e: std::str::ParseBoolError) -> Self {
+        PipeError::InvalidOption(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pipe, PipeError, PipeMeta, pipe_meta};
+
+    #[test]
+    fn builder_collects_input_types() {
+        let meta = pipe_meta("format/table")
+            .summary("Table")
+            .input_type("text/plain")
+            .input_type("application/json")
+            .output_type("text/plain")
+            .version("1.0.0")
+            .build();
+        assert_eq!(meta.name, "format/table");
+        assert_eq!(meta.summary, "Table");
+        assert_eq!(meta.input_types, vec!["text/plain", "application/json"]);
+        assert_eq!(meta.output_type, "text/plain");
+        assert_eq!(meta.version, "1.0.0");
+    }
+
+    #[test]
+    fn errors_convert_by_kind() {
+        let utf8 = String::from_utf8(vec![0xff]).unwrap_err();
+        assert!(matches!(PipeError::from(utf8), PipeError::ParseError(_)));
+        let int = "x".parse::<u32>().unwrap_err();
+        assert!(matches!(PipeError::from(int), PipeError::InvalidOption(_)));
+        assert!(matches!(
+            PipeError::from("boom"),
+            PipeError::TransformError(msg) if msg == "boom"
+        ));
+    }
+
+    struct Echo;
+
+    impl Pipe for Echo {
+        fn meta() -> PipeMeta {
+            pipe_meta("format/echo").build()
+        }
+
+        fn process(input: Vec<u8>, options: Vec<String>) -> Result<Vec<u8>, PipeError> {
+            Ok([options.concat().into_bytes(), input].concat())
+        }
+    }
+
+    #[test]
+    fn default_streaming_buffers_into_process() {
+        let a = Echo::process_start(vec!["x".into()]).unwrap();
+        let b = Echo::process_start(vec!["y".into()]).unwrap();
+        assert!(Echo::process_chunk(a, b"ab".to_vec()).unwrap().is_empty());
+        Echo::process_chunk(b, b"1".to_vec()).unwrap();
+        Echo::process_chunk(a, b"cd".to_vec()).unwrap();
+        assert_eq!(Echo::process_end(a).unwrap(), b"xabcd");
+        assert_eq!(Echo::process_end(b).unwrap(), b"y1");
+        assert!(matches!(
+            Echo::process_end(a),
+            Err(PipeError::TransformError(_))
+        ));
+    }
+}
@@ -0,0 +1,1224 @@
+// Generated by `wit-bindgen` 0.52.0. DO NOT EDIT!
+// Options used:
+#[rustfmt::skip]
+#[allow(dead_code, clippy::all)]
+pub mod wacli {
+    pub mod cli {
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod types {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type ExitCode = u32;
+            #[derive(Clone)]
+            pub struct ArgDef {
+                pub name: _rt::String,
+                pub short: Option<_rt::String>,
+                pub long: Option<_rt::String>,
+                pub help: _rt::String,
+                pub required: bool,
+                pub default_value: Option<_rt::String>,
+                pub value_name: Option<_rt::String>,
+                pub takes_value: bool,
+            }
+            impl ::core::fmt::Debug for ArgDef {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ArgDef")
+                        .field("name", &self.name)
+                        .field("short", &self.short)
+                        .field("long", &self.long)
+                        .field("help", &self.help)
+                        .field("required", &self.required)
+                        .field("default-value", &self.default_value)
+                        .field("value-name", &self.value_name)
+                        .field("takes-value", &self.takes_value)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct CommandMeta {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub usage: _rt::String,
+                pub aliases: _rt::Vec<_rt::String>,
+                pub version: _rt::String,
+                pub hidden: bool,
+                pub description: _rt::String,
+                pub examples: _rt::Vec<_rt::String>,
+                pub args: _rt::Vec<ArgDef>,
+            }
+            impl ::core::fmt::Debug for CommandMeta {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("CommandMeta")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("usage", &self.usage)
+                        .field("aliases", &self.aliases)
+                        .field("version", &self.version)
+                        .field("hidden", &self.hidden)
+                        .field("description", &self.description)
+                        .field("examples", &self.examples)
+                        .field("args", &self.args)
+                        .finish()
+                }
+            }
+            /// Cases cannot be added within `wacli:cli@2.0.0` (composition requires
+            /// the same case count, so older plugins would stop composing). Until a
+            /// new major version adds an exit-code case, a `failed` message of the
+            /// form `wacli-exit:<code>:<message>` makes core print `<message>` and
+            /// exit with `<code>`. Otherwise `invalid-args` exits with 2,
+            /// `unknown-command` with 127 and `failed` / `io` with 1.
+            #[derive(Clone)]
+            pub enum CommandError {
+                UnknownCommand(_rt::String),
+                InvalidArgs(_rt::String),
+                Failed(_rt::String),
+                Io(_rt::String),
+            }
+            impl ::core::fmt::Debug for CommandError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        CommandError::UnknownCommand(e) => {
+                            f.debug_tuple("CommandError::UnknownCommand")
+                                .field(e)
+                                .finish()
+                        }
+                        CommandError::InvalidArgs(e) => {
+                            f.debug_tuple("CommandError::InvalidArgs").field(e).finish()
+                        }
+                        CommandError::Failed(e) => {
+                            f.debug_tuple("CommandError::Failed").field(e).finish()
+                        }
+                        CommandError::Io(e) => {
+                            f.debug_tuple("CommandError::Io").field(e).finish()
+                        }
+                    }
+                }
+            }
+            pub type CommandResult = Result<ExitCode, CommandError>;
+            #[derive(Clone)]
+            pub struct PipeMeta {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub input_types: _rt::Vec<_rt::String>,
+                pub output_type: _rt::String,
+                pub version: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeMeta {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeMeta")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub enum PipeError {
+                ParseError(_rt::String),
+                TransformError(_rt::String),
+                InvalidOption(_rt::String),
+            }
+            impl ::core::fmt::Debug for PipeError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        PipeError::ParseError(e) => {
+                            f.debug_tuple("PipeError::ParseError").field(e).finish()
+                        }
+                        PipeError::TransformError(e) => {
+                            f.debug_tuple("PipeError::TransformError").field(e).finish()
+                        }
+                        PipeError::InvalidOption(e) => {
+                            f.debug_tuple("PipeError::InvalidOption").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for PipeError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl ::core::error::Error for PipeError {}
+            #[derive(Clone)]
+            pub struct PipeInfo {
+                pub name: _rt::String,
+                pub summary: _rt::String,
+                pub path: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeInfo")
+                        .field("name", &self.name)
+                        .field("summary", &self.summary)
+                        .field("path", &self.path)
+                        .finish()
+                }
+            }
+            /// A discovered pipe with the descriptive fields it embeds in its
+            /// `wacli:cli/pipe-metadata@1` section (empty when it embeds none).
+            #[derive(Clone)]
+            pub struct PipeDetails {
+                pub info: PipeInfo,
+                pub input_types: _rt::Vec<_rt::String>,
+                pub output_type: _rt::String,
+                pub version: _rt::String,
+            }
+            impl ::core::fmt::Debug for PipeDetails {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("PipeDetails")
+                        .field("info", &self.info)
+                        .field("input-types", &self.input_types)
+                        .field("output-type", &self.output_type)
+                        .field("version", &self.version)
+                        .finish()
+                }
+            }
+        }
+    }
+}
+#[rustfmt::skip]
+#[allow(dead_code, clippy::all)]
+pub mod exports {
+    pub mod wacli {
+        pub mod cli {
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod pipe {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                pub type PipeMeta = super::super::super::super::wacli::cli::types::PipeMeta;
+                pub type PipeError = super::super::super::super::wacli::cli::types::PipeError;
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_meta_cabi<T: Guest>() -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result0 = { T::meta() };
+                        let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        let super::super::super::super::wacli::cli::types::PipeMeta {
+                            name: name2,
+                            summary: summary2,
+                            input_types: input_types2,
+                            output_type: output_type2,
+                            version: version2,
+                        } = result0;
+                        let vec3 = (name2.into_bytes()).into_boxed_slice();
+                        let ptr3 = vec3.as_ptr().cast::<u8>();
+                        let len3 = vec3.len();
+                        ::core::mem::forget(vec3);
+                        *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                        *ptr1.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                        let vec4 = (summary2.into_bytes()).into_boxed_slice();
+                        let ptr4 = vec4.as_ptr().cast::<u8>();
+                        let len4 = vec4.len();
+                        ::core::mem::forget(vec4);
+                        *ptr1
+                            .add(3 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>() = len4;
+                        *ptr1
+                            .add(2 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>() = ptr4.cast_mut();
+                        let vec6 = input_types2;
+                        let len6 = vec6.len();
+                        let layout6 = _rt::alloc::Layout::from_size_align(
+                                vec6.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            )
+                            .unwrap();
+                        let (result6, _cleanup6) = wit_bindgen::rt::Cleanup::new(
+                            layout6,
+                        );
+                        if let Some(cleanup) = _cleanup6 {
+                            cleanup.forget();
+                        }
+                        for (i, e) in vec6.into_iter().enumerate() {
+                            let base = result6
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let vec5 = (e.into_bytes()).into_boxed_slice();
+                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                let len5 = vec5.len();
+                                ::core::mem::forget(vec5);
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len5;
+                                *base.add(0).cast::<*mut u8>() = ptr5.cast_mut();
+                            }
+                        }
+                        *ptr1
+                            .add(5 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>() = len6;
+                        *ptr1
+                            .add(4 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>() = result6;
+                        let vec7 = (output_type2.into_bytes()).into_boxed_slice();
+                        let ptr7 = vec7.as_ptr().cast::<u8>();
+                        let len7 = vec7.len();
+                        ::core::mem::forget(vec7);
+                        *ptr1
+                            .add(7 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>() = len7;
+                        *ptr1
+                            .add(6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>() = ptr7.cast_mut();
+                        let vec8 = (version2.into_bytes()).into_boxed_slice();
+                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                        let len8 = vec8.len();
+                        ::core::mem::forget(vec8);
+                        *ptr1
+                            .add(9 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>() = len8;
+                        *ptr1
+                            .add(8 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>() = ptr8.cast_mut();
+                        ptr1
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_meta<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = *arg0.add(0).cast::<*mut u8>();
+                        let l1 = *arg0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        _rt::cabi_dealloc(l0, l1, 1);
+                        let l2 = *arg0
+                            .add(2 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l3 = *arg0
+                            .add(3 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        _rt::cabi_dealloc(l2, l3, 1);
+                        let l4 = *arg0
+                            .add(4 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l5 = *arg0
+                            .add(5 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base8 = l4;
+                        let len8 = l5;
+                        for i in 0..len8 {
+                            let base = base8
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let l6 = *base.add(0).cast::<*mut u8>();
+                                let l7 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                _rt::cabi_dealloc(l6, l7, 1);
+                            }
+                        }
+                        _rt::cabi_dealloc(
+                            base8,
+                            len8 * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let l9 = *arg0
+                            .add(6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l10 = *arg0
+                            .add(7 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        _rt::cabi_dealloc(l9, l10, 1);
+                        let l11 = *arg0
+                            .add(8 * ::core::mem::size_of::<*const u8>())
+                            .cast::<*mut u8>();
+                        let l12 = *arg0
+                            .add(9 * ::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        _rt::cabi_dealloc(l11, l12, 1);
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_process_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result5 = {
+                            let len0 = arg1;
+                            let base4 = arg2;
+                            let len4 = arg3;
+                            let mut result4 = _rt::Vec::with_capacity(len4);
+                            for i in 0..len4 {
+                                let base = base4
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                let e4 = {
+                                    let l1 = *base.add(0).cast::<*mut u8>();
+                                    let l2 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len3 = l2;
+                                    let bytes3 = _rt::Vec::from_raw_parts(
+                                        l1.cast(),
+                                        len3,
+                                        len3,
+                                    );
+                                    _rt::string_lift(bytes3)
+                                };
+                                result4.push(e4);
+                            }
+                            _rt::cabi_dealloc(
+                                base4,
+                                len4 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            T::process(
+                                <_ as From<
+                                    _rt::Vec<_>,
+                                >>::from(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)),
+                                result4,
+                            )
+                        };
+                        let ptr6 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        match result5 {
+                            Ok(e) => {
+                                *ptr6.add(0).cast::<u8>() = (0i32) as u8;
+                                let vec7 = <_ as Into<_rt::Vec<_>>>::into(e)
+                                    .into_boxed_slice();
+                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                let len7 = vec7.len();
+                                ::core::mem::forget(vec7);
+                                *ptr6
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len7;
+                                *ptr6
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr7.cast_mut();
+                            }
+                            Err(e) => {
+                                *ptr6.add(0).cast::<u8>() = (1i32) as u8;
+                                use super::super::super::super::wacli::cli::types::PipeError as V11;
+                                match e {
+                                    V11::ParseError(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (0i32) as u8;
+                                        let vec8 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                                        let len8 = vec8.len();
+                                        ::core::mem::forget(vec8);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len8;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr8.cast_mut();
+                                    }
+                                    V11::TransformError(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (1i32) as u8;
+                                        let vec9 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr9 = vec9.as_ptr().cast::<u8>();
+                                        let len9 = vec9.len();
+                                        ::core::mem::forget(vec9);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len9;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr9.cast_mut();
+                                    }
+                                    V11::InvalidOption(e) => {
+                                        *ptr6
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (2i32) as u8;
+                                        let vec10 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr10 = vec10.as_ptr().cast::<u8>();
+                                        let len10 = vec10.len();
+                                        ::core::mem::forget(vec10);
+                                        *ptr6
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len10;
+                                        *ptr6
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr10.cast_mut();
+                                    }
+                                }
+                            }
+                        };
+                        ptr6
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_process<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                        match l0 {
+                            0 => {
+                                let l1 = *arg0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l2 = *arg0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base3 = l1;
+                                let len3 = l2;
+                                _rt::cabi_dealloc(base3, len3 * 1, 1);
+                            }
+                            _ => {
+                                let l4 = i32::from(
+                                    *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l4 {
+                                    0 => {
+                                        let l5 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l6 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l5, l6, 1);
+                                    }
+                                    1 => {
+                                        let l7 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l8 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l7, l8, 1);
+                                    }
+                                    _ => {
+                                        let l9 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l10 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l9, l10, 1);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                pub trait Guest {
+                    #[allow(async_fn_in_trait)]
+                    fn meta() -> PipeMeta;
+                    #[allow(async_fn_in_trait)]
+                    fn process(
+                        input: _rt::Vec<u8>,
+                        options: _rt::Vec<_rt::String>,
+                    ) -> Result<_rt::Vec<u8>, PipeError>;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_wacli_cli_pipe_2_0_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[unsafe (export_name =
+                        "wacli:cli/pipe@2.0.0#meta")] unsafe extern "C" fn export_meta()
+                        -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_meta_cabi::<$ty > () } } #[unsafe (export_name =
+                        "cabi_post_wacli:cli/pipe@2.0.0#meta")] unsafe extern "C" fn
+                        _post_return_meta(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_meta::<$ty > (arg0) } }
+                        #[unsafe (export_name = "wacli:cli/pipe@2.0.0#process")] unsafe
+                        extern "C" fn export_process(arg0 : * mut u8, arg1 : usize, arg2
+                        : * mut u8, arg3 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_process_cabi::<$ty > (arg0, arg1,
+                        arg2, arg3) } } #[unsafe (export_name =
+                        "cabi_post_wacli:cli/pipe@2.0.0#process")] unsafe extern "C" fn
+                        _post_return_process(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_process::<$ty > (arg0) } } };
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_wacli_cli_pipe_2_0_0_cabi;
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 10 * ::core::mem::size_of::<*const u8>()],
+                );
+                static mut _RET_AREA: _RetArea = _RetArea(
+                    [::core::mem::MaybeUninit::uninit(); 10
+                        * ::core::mem::size_of::<*const u8>()],
+                );
+            }
+            /// Chunked form of `pipe.process`, for inputs too large to pass at once.
+            ///
+            /// Pipes built without it still load; the runtime then buffers the chunks
+            /// and calls `process` once.
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod pipe_stream {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                pub type PipeError = super::super::super::super::wacli::cli::types::PipeError;
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_process_start_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result4 = {
+                            let base3 = arg0;
+                            let len3 = arg1;
+                            let mut result3 = _rt::Vec::with_capacity(len3);
+                            for i in 0..len3 {
+                                let base = base3
+                                    .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                let e3 = {
+                                    let l0 = *base.add(0).cast::<*mut u8>();
+                                    let l1 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len2 = l1;
+                                    let bytes2 = _rt::Vec::from_raw_parts(
+                                        l0.cast(),
+                                        len2,
+                                        len2,
+                                    );
+                                    _rt::string_lift(bytes2)
+                                };
+                                result3.push(e3);
+                            }
+                            _rt::cabi_dealloc(
+                                base3,
+                                len3 * (2 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            T::process_start(result3)
+                        };
+                        let ptr5 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        match result4 {
+                            Ok(e) => {
+                                *ptr5.add(0).cast::<u8>() = (0i32) as u8;
+                                *ptr5
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<i32>() = _rt::as_i32(e);
+                            }
+                            Err(e) => {
+                                *ptr5.add(0).cast::<u8>() = (1i32) as u8;
+                                use super::super::super::super::wacli::cli::types::PipeError as V9;
+                                match e {
+                                    V9::ParseError(e) => {
+                                        *ptr5
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (0i32) as u8;
+                                        let vec6 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                        let len6 = vec6.len();
+                                        ::core::mem::forget(vec6);
+                                        *ptr5
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len6;
+                                        *ptr5
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr6.cast_mut();
+                                    }
+                                    V9::TransformError(e) => {
+                                        *ptr5
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (1i32) as u8;
+                                        let vec7 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr7 = vec7.as_ptr().cast::<u8>();
+                                        let len7 = vec7.len();
+                                        ::core::mem::forget(vec7);
+                                        *ptr5
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len7;
+                                        *ptr5
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr7.cast_mut();
+                                    }
+                                    V9::InvalidOption(e) => {
+                                        *ptr5
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (2i32) as u8;
+                                        let vec8 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr8 = vec8.as_ptr().cast::<u8>();
+                                        let len8 = vec8.len();
+                                        ::core::mem::forget(vec8);
+                                        *ptr5
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len8;
+                                        *ptr5
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr8.cast_mut();
+                                    }
+                                }
+                            }
+                        };
+                        ptr5
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_process_start<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                        match l0 {
+                            0 => {}
+                            _ => {
+                                let l1 = i32::from(
+                                    *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l1 {
+                                    0 => {
+                                        let l2 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l3 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l2, l3, 1);
+                                    }
+                                    1 => {
+                                        let l4 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l5 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l4, l5, 1);
+                                    }
+                                    _ => {
+                                        let l6 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l7 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l6, l7, 1);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_process_chunk_cabi<T: Guest>(
+                    arg0: i32,
+                    arg1: *mut u8,
+                    arg2: usize,
+                ) -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result1 = {
+                            let len0 = arg2;
+                            T::process_chunk(
+                                arg0 as u32,
+                                <_ as From<
+                                    _rt::Vec<_>,
+                                >>::from(_rt::Vec::from_raw_parts(arg1.cast(), len0, len0)),
+                            )
+                        };
+                        let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        match result1 {
+                            Ok(e) => {
+                                *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                                let vec3 = <_ as Into<_rt::Vec<_>>>::into(e)
+                                    .into_boxed_slice();
+                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                let len3 = vec3.len();
+                                ::core::mem::forget(vec3);
+                                *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len3;
+                                *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr3.cast_mut();
+                            }
+                            Err(e) => {
+                                *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                                use super::super::super::super::wacli::cli::types::PipeError as V7;
+                                match e {
+                                    V7::ParseError(e) => {
+                                        *ptr2
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (0i32) as u8;
+                                        let vec4 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr4 = vec4.as_ptr().cast::<u8>();
+                                        let len4 = vec4.len();
+                                        ::core::mem::forget(vec4);
+                                        *ptr2
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len4;
+                                        *ptr2
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr4.cast_mut();
+                                    }
+                                    V7::TransformError(e) => {
+                                        *ptr2
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (1i32) as u8;
+                                        let vec5 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr5 = vec5.as_ptr().cast::<u8>();
+                                        let len5 = vec5.len();
+                                        ::core::mem::forget(vec5);
+                                        *ptr2
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len5;
+                                        *ptr2
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr5.cast_mut();
+                                    }
+                                    V7::InvalidOption(e) => {
+                                        *ptr2
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (2i32) as u8;
+                                        let vec6 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                        let len6 = vec6.len();
+                                        ::core::mem::forget(vec6);
+                                        *ptr2
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len6;
+                                        *ptr2
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr6.cast_mut();
+                                    }
+                                }
+                            }
+                        };
+                        ptr2
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_process_chunk<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                        match l0 {
+                            0 => {
+                                let l1 = *arg0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l2 = *arg0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base3 = l1;
+                                let len3 = l2;
+                                _rt::cabi_dealloc(base3, len3 * 1, 1);
+                            }
+                            _ => {
+                                let l4 = i32::from(
+                                    *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l4 {
+                                    0 => {
+                                        let l5 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l6 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l5, l6, 1);
+                                    }
+                                    1 => {
+                                        let l7 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l8 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l7, l8, 1);
+                                    }
+                                    _ => {
+                                        let l9 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l10 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l9, l10, 1);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case, unused_unsafe)]
+                pub unsafe fn _export_process_end_cabi<T: Guest>(arg0: i32) -> *mut u8 {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                        let result0 = { T::process_end(arg0 as u32) };
+                        let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                        match result0 {
+                            Ok(e) => {
+                                *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+                                let vec2 = <_ as Into<_rt::Vec<_>>>::into(e)
+                                    .into_boxed_slice();
+                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                let len2 = vec2.len();
+                                ::core::mem::forget(vec2);
+                                *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len2;
+                                *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>() = ptr2.cast_mut();
+                            }
+                            Err(e) => {
+                                *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                                use super::super::super::super::wacli::cli::types::PipeError as V6;
+                                match e {
+                                    V6::ParseError(e) => {
+                                        *ptr1
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (0i32) as u8;
+                                        let vec3 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr3 = vec3.as_ptr().cast::<u8>();
+                                        let len3 = vec3.len();
+                                        ::core::mem::forget(vec3);
+                                        *ptr1
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len3;
+                                        *ptr1
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr3.cast_mut();
+                                    }
+                                    V6::TransformError(e) => {
+                                        *ptr1
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (1i32) as u8;
+                                        let vec4 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr4 = vec4.as_ptr().cast::<u8>();
+                                        let len4 = vec4.len();
+                                        ::core::mem::forget(vec4);
+                                        *ptr1
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len4;
+                                        *ptr1
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr4.cast_mut();
+                                    }
+                                    V6::InvalidOption(e) => {
+                                        *ptr1
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>() = (2i32) as u8;
+                                        let vec5 = (e.into_bytes()).into_boxed_slice();
+                                        let ptr5 = vec5.as_ptr().cast::<u8>();
+                                        let len5 = vec5.len();
+                                        ::core::mem::forget(vec5);
+                                        *ptr1
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>() = len5;
+                                        *ptr1
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>() = ptr5.cast_mut();
+                                    }
+                                }
+                            }
+                        };
+                        ptr1
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_process_end<T: Guest>(arg0: *mut u8) {
+                    unsafe {
+                        let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                        match l0 {
+                            0 => {
+                                let l1 = *arg0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l2 = *arg0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base3 = l1;
+                                let len3 = l2;
+                                _rt::cabi_dealloc(base3, len3 * 1, 1);
+                            }
+                            _ => {
+                                let l4 = i32::from(
+                                    *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                match l4 {
+                                    0 => {
+                                        let l5 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l6 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l5, l6, 1);
+                                    }
+                                    1 => {
+                                        let l7 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l8 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l7, l8, 1);
+                                    }
+                                    _ => {
+                                        let l9 = *arg0
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l10 = *arg0
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        _rt::cabi_dealloc(l9, l10, 1);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                pub trait Guest {
+                    /// Start a run with `options`; the id names it in the calls below.
+                    #[allow(async_fn_in_trait)]
+                    fn process_start(
+                        options: _rt::Vec<_rt::String>,
+                    ) -> Result<u32, PipeError>;
+                    /// Feed the next part of the input; returns whatever output is ready.
+                    #[allow(async_fn_in_trait)]
+                    fn process_chunk(
+                        session: u32,
+                        bytes: _rt::Vec<u8>,
+                    ) -> Result<_rt::Vec<u8>, PipeError>;
+                    /// End the input and the session; returns the rest of the output.
+                    #[allow(async_fn_in_trait)]
+                    fn process_end(session: u32) -> Result<_rt::Vec<u8>, PipeError>;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_wacli_cli_pipe_stream_2_0_0_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[unsafe (export_name =
+                        "wacli:cli/pipe-stream@2.0.0#process-start")] unsafe extern "C"
+                        fn export_process_start(arg0 : * mut u8, arg1 : usize,) -> * mut
+                        u8 { unsafe { $($path_to_types)*::
+                        _export_process_start_cabi::<$ty > (arg0, arg1) } } #[unsafe
+                        (export_name =
+                        "cabi_post_wacli:cli/pipe-stream@2.0.0#process-start")] unsafe
+                        extern "C" fn _post_return_process_start(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_process_start::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "wacli:cli/pipe-stream@2.0.0#process-chunk")] unsafe extern "C"
+                        fn export_process_chunk(arg0 : i32, arg1 : * mut u8, arg2 :
+                        usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_process_chunk_cabi::<$ty > (arg0, arg1, arg2) } }
+                        #[unsafe (export_name =
+                        "cabi_post_wacli:cli/pipe-stream@2.0.0#process-chunk")] unsafe
+                        extern "C" fn _post_return_process_chunk(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_process_chunk::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "wacli:cli/pipe-stream@2.0.0#process-end")] unsafe extern "C" fn
+                        export_process_end(arg0 : i32,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_process_end_cabi::<$ty > (arg0) } }
+                        #[unsafe (export_name =
+                        "cabi_post_wacli:cli/pipe-stream@2.0.0#process-end")] unsafe
+                        extern "C" fn _post_return_process_end(arg0 : * mut u8,) { unsafe
+                        { $($path_to_types)*:: __post_return_process_end::<$ty > (arg0) }
+                        } };
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_wacli_cli_pipe_stream_2_0_0_cabi;
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 4 * ::core::mem::size_of::<*const u8>()],
+                );
+                static mut _RET_AREA: _RetArea = _RetArea(
+                    [::core::mem::MaybeUninit::uninit(); 4
+                        * ::core::mem::size_of::<*const u8>()],
+                );
+            }
+        }
+    }
+}
+#[rustfmt::skip]
+mod _rt {
+    #![allow(dead_code, unused_imports, clippy::all)]
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_ctors_once() {
+        wit_bindgen::rt::run_ctors_once();
+    }
+    pub use alloc_crate::alloc;
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        unsafe {
+            let layout = alloc::Layout::from_size_align_unchecked(size, align);
+            alloc::dealloc(ptr, layout);
+        }
+    }
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            unsafe { String::from_utf8_unchecked(bytes) }
+        }
+    }
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    extern crate alloc as alloc_crate;
+}
+/// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
+/// the root implementation of all generated traits.
+///
+/// For more information see the documentation of `wit_bindgen::generate!`.
+///
+/// ```rust
+/// # macro_rules! export{ ($($t:tt)*) => (); }
+/// # trait Guest {}
+/// struct MyType;
+///
+/// impl Guest for MyType {
+///     // ...
+/// }
+///
+/// export!(MyType);
+/// ```
+#[allow(unused_macros)]
+#[doc(hidden)]
+macro_rules! __export_pipe_plugin_impl {
+    ($ty:ident) => {
+        self::export!($ty with_types_in self);
+    };
+    ($ty:ident with_types_in $($path_to_types_root:tt)*) => {
+        $($path_to_types_root)*::
+        exports::wacli::cli::pipe::__export_wacli_cli_pipe_2_0_0_cabi!($ty with_types_in
+        $($path_to_types_root)*:: exports::wacli::cli::pipe); $($path_to_types_root)*::
+        exports::wacli::cli::pipe_stream::__export_wacli_cli_pipe_stream_2_0_0_cabi!($ty
+        with_types_in $($path_to_types_root)*:: exports::wacli::cli::pipe_stream);
+    };
+}
+#[doc(inline)]
+pub(crate) use __export_pipe_plugin_impl as export;
+#[rustfmt::skip]
+#[cfg(target_arch = "wasm32")]
+#[unsafe(
+    link_section = "component-type:wit-bindgen:0.52.0:wacli:cli@2.0.0:pipe-plugin:encoded world"
+)]
+#[doc(hidden)]
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1083] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb9\x07\x01A\x02\x01\
+A\x08\x01B\x15\x01y\x04\0\x09exit-code\x03\0\0\x01ks\x01r\x08\x04names\x05short\x02\
+\x04long\x02\x04helps\x08required\x7f\x0ddefault-value\x02\x0avalue-name\x02\x0b\
+takes-value\x7f\x04\0\x07arg-def\x03\0\x03\x01ps\x01p\x04\x01r\x09\x04names\x07s\
+ummarys\x05usages\x07aliases\x05\x07versions\x06hidden\x7f\x0bdescriptions\x08ex\
+amples\x05\x04args\x06\x04\0\x0ccommand-meta\x03\0\x07\x01q\x04\x0funknown-comma\
+nd\x01s\0\x0cinvalid-args\x01s\0\x06failed\x01s\0\x02io\x01s\0\x04\0\x0dcommand-\
+error\x03\0\x09\x01j\x01\x01\x01\x0a\x04\0\x0ecommand-result\x03\0\x0b\x01r\x05\x04\
+names\x07summarys\x0binput-types\x05\x0boutput-types\x07versions\x04\0\x09pipe-m\
+eta\x03\0\x0d\x01q\x03\x0bparse-error\x01s\0\x0ftransform-error\x01s\0\x0einvali\
+d-option\x01s\0\x04\0\x0apipe-error\x03\0\x0f\x01r\x03\x04names\x07summarys\x04p\
+aths\x04\0\x09pipe-info\x03\0\x11\x01r\x04\x04info\x12\x0binput-types\x05\x0bout\
+put-types\x07versions\x04\0\x0cpipe-details\x03\0\x13\x03\0\x15wacli:cli/types@2\
+.0.0\x05\0\x02\x03\0\0\x09pipe-meta\x02\x03\0\0\x0apipe-error\x01B\x0b\x02\x03\x02\
+\x01\x01\x04\0\x09pipe-meta\x03\0\0\x02\x03\x02\x01\x02\x04\0\x0apipe-error\x03\0\
+\x02\x01@\0\0\x01\x04\0\x04meta\x01\x04\x01p}\x01ps\x01j\x01\x05\x01\x03\x01@\x02\
+\x05input\x05\x07options\x06\0\x07\x04\0\x07process\x01\x08\x04\0\x14wacli:cli/p\
+ipe@2.0.0\x05\x03\x01B\x0c\x02\x03\x02\x01\x02\x04\0\x0apipe-error\x03\0\0\x01ps\
+\x01j\x01y\x01\x01\x01@\x01\x07options\x02\0\x03\x04\0\x0dprocess-start\x01\x04\x01\
+p}\x01j\x01\x05\x01\x01\x01@\x02\x07sessiony\x05bytes\x05\0\x06\x04\0\x0dprocess\
+-chunk\x01\x07\x01@\x01\x07sessiony\0\x06\x04\0\x0bprocess-end\x01\x08\x04\0\x1b\
+wacli:cli/pipe-stream@2.0.0\x05\x04\x04\0\x1bwacli:cli/pipe-plugin@2.0.0\x04\0\x0b\
+\x11\x01\0\x0bpipe-plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit\
+-component\x070.244.0\x10wit-bindgen-rust\x060.52.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen::rt::maybe_link_cabi_realloc();
+}
@@ -0,0 +1,855 @@
+//! Run a [`Command`] natively against in-memory fakes of the host interfaces.
+//!
+//! Off wasm32 the host imports do not exist, so [`io`](crate::io),
+//! [`env`](crate::env), [`log`](crate::log), [`fs`](crate::fs) and
+//! [`pipes`](crate::pipes) call into the [`TestHost`] running the command
+//! instead:
+//!
+//! ```rust,ignore
+//! use wacli_cdk::testing::TestHost;
+//!
+//! #[test]
+//! fn shouts() {
+//!     let mut host = TestHost::new().with_file("in.txt", "hi");
+//!     let out = host.run::<Shout>(["in.txt"]);
+//!     assert_eq!(out.exit_code(), 0);
+//!     assert_eq!(out.stdout_str(), "HI\n");
+//! }
+//! ```
+//!
+//! Outside [`TestHost::run`] the helpers still call the host imports, which
+//! panic off wasm32.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+use super::{
+    Command, CommandResult, PipeDetails, PipeError, PipeInfo, PipeMeta,
+    fs::{DirEntry, FileInfo, FileKind},
+    log::Level,
+};
+
+/// A fake pipe's `process()`: input bytes and options to output bytes.
+pub type PipeFn = dyn Fn(&[u8], &[String]) -> Result<Vec<u8>, PipeError>;
+
+thread_local! {
+    /// The host of the command running on this thread, if any.
+    static ACTIVE: RefCell<Option<TestHost>> = const { RefCell::new(None) };
+}
+
+/// Apply `f` to the running [`TestHost`], or `None` outside [`TestHost::run`].
+pub(crate) fn with_active<R>(f: impl FnOnce(&mut TestHost) -> R) -> Option<R> {
+    ACTIVE.with(|active| active.borrow_mut().as_mut().map(f))
+}
+
+/// In-memory stdout/stderr/stdin, environment, filesystem and pipes for
+/// running a command off wasm32.
+///
+/// Paths are `/`-separated and relative to a fake root (`./a` and `a` name
+/// the same file); a file's parent directories exist implicitly.
+#[derive(Default)]
+pub struct TestHost {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    stdin: Vec<u8>,
+    stdin_pos: usize,
+    env: Vec<(String, String)>,
+    cwd: Option<String>,
+    files: BTreeMap<String, Vec<u8>>,
+    dirs: BTreeSet<String>,
+    pipes: BTreeMap<String, Rc<PipeFn>>,
+    log_level: MaxLogLevel,
+}
+
+/// Least severe level `log` writes; `warn` by default, like the host.
+struct MaxLogLevel(Option<Level>);
+
+impl Default for MaxLogLevel {
+    fn default() -> Self {
+        Self(Some(Level::Warn))
+    }
+}
+
+/// What a command did under [`TestHost::run`].
+#[derive(Debug, Clone)]
+pub struct TestOutput {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub result: CommandResult,
+}
+
+impl TestOutput {
+    /// Captured stdout, lossily decoded as UTF-8.
+    pub fn stdout_str(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    /// Captured stderr, lossily decoded as UTF-8.
+    pub fn stderr_str(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+
+    /// The exit code core would use: the returned code, or
+    /// [`CommandError::exit_code`](crate::CommandError::exit_code) for an error.
+    pub fn exit_code(&self) -> u32 {
+        match &self.result {
+            Ok(code) => *code,
+            Err(err) => err.exit_code(),
+        }
+    }
+}
+
+impl TestHost {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the environment variable `key` (visible to `env` and `Context::new`).
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the directory [`env::cwd`](crate::env::cwd) reports (default `.`).
+    pub fn with_cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Set the least severe level [`log`](crate::log) writes to stderr, as core
+    /// would from `<APP>_LOG` (default `warn`; `None` drops every message).
+    pub fn with_log_level(mut self, level: Option<Level>) -> Self {
+        self.log_level = MaxLogLevel(level);
+        self
+    }
+
+    /// Set what the command reads from stdin.
+    pub fn with_stdin(mut self, stdin: impl Into<Vec<u8>>) -> Self {
+        self.stdin = stdin.into();
+        self.stdin_pos = 0;
+        self
+    }
+
+    /// Add a file, along with its parent directories.
+    pub fn with_file(mut self, path: &str, contents: impl Into<Vec<u8>>) -> Self {
+        self.files.insert(normalize(path), contents.into());
+        self
+    }
+
+    /// Add an (empty) directory, along with its parents.
+    pub fn with_dir(mut self, path: &str) -> Self {
+        self.dirs.insert(normalize(path));
+        self
+    }
+
+    /// Register a pipe `pipes::load(name)` finds. Inline options in the loaded
+    /// spec (`name?width=80`) come before the options given to `process()`.
+    pub fn with_pipe(
+        mut self,
+        name: impl Into<String>,
+        process: impl Fn(&[u8], &[String]) -> Result<Vec<u8>, PipeError> + 'static,
+    ) -> Self {
+        self.pipes.insert(name.into(), Rc::new(process));
+        self
+    }
+
+    /// The contents of the file at `path`, if there is one.
+    pub fn file(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(&normalize(path)).map(Vec::as_slice)
+    }
+
+    /// Every file, by normalized path.
+    pub fn files(&self) -> &BTreeMap<String, Vec<u8>> {
+        &self.files
+    }
+
+    /// Whether `path` is a directory, explicit or implied by a file below it.
+    pub fn is_dir(&self, path: &str) -> bool {
+        self.dir_exists(&normalize(path))
+    }
+
+    /// Run `C::run(argv)` with the host interfaces routed to this host.
+    ///
+    /// `argv` is what core passes a command: the arguments after its name.
+    /// Output is drained into the returned [`TestOutput`]; files, stdin and the
+    /// environment carry over to the next run.
+    ///
+    /// # Panics
+    ///
+    /// If another `TestHost` is running a command on this thread.
+    pub fn run<C: Command>(
+        &mut self,
+        argv: impl IntoIterator<Item = impl Into<String>>,
+    ) -> TestOutput {
+        let argv = argv.into_iter().map(Into::into).collect();
+        let result = self.enter(|| C::run(argv));
+        TestOutput {
+            stdout: std::mem::take(&mut self.stdout),
+            stderr: std::mem::take(&mut self.stderr),
+            result,
+        }
+    }
+
+    fn enter<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        /// Moves the host back out of `ACTIVE`, also when `f` panics.
+        struct Restore<'a>(&'a mut TestHost);
+
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                if let Some(host) = ACTIVE.with(|active| active.borrow_mut().take()) {
+                    *self.0 = host;
+                }
+            }
+        }
+
+        ACTIVE.with(|active| {
+            let mut active = active.borrow_mut();
+            assert!(
+                active.is_none(),
+                "a TestHost is already running a command on this thread"
+            );
+            *active = Some(std::mem::take(self));
+        });
+        let _restore = Restore(self);
+        f()
+    }
+
+    // --- host-io ---
+
+    pub(crate) fn stdout_write(&mut self, bytes: &[u8]) {
+        self.stdout.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn stderr_write(&mut self, bytes: &[u8]) {
+        self.stderr.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn stdin_read(&mut self, max_len: u64) -> Result<Vec<u8>, String> {
+        let rest = &self.stdin[self.stdin_pos..];
+        let len = rest
+            .len()
+            .min(usize::try_from(max_len).unwrap_or(usize::MAX));
+        self.stdin_pos += len;
+        Ok(rest[..len].to_vec())
+    }
+
+    pub(crate) fn stdin_read_line(&mut self) -> Result<Option<String>, String> {
+        let rest = &self.stdin[self.stdin_pos..];
+        if rest.is_empty() {
+            return Ok(None);
+        }
+        let len = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        let mut line = &rest[..len];
+        self.stdin_pos += (len + 1).min(rest.len());
+        if line.last() == Some(&b'\r') {
+            line = &line[..line.len() - 1];
+        }
+        Ok(Some(String::from_utf8_lossy(line).into_owned()))
+    }
+
+    pub(crate) fn read_secret(&mut self, prompt: &str) -> Result<String, String> {
+        // Fake stdin is never a terminal, so this is a plain read.
+        self.stderr_write(prompt.as_bytes());
+        self.stdin_read_line()?
+            .ok_or_else(|| "end of input".to_string())
+    }
+
+    pub(crate) fn stdin_read_all(&mut self) -> Result<Vec<u8>, String> {
+        let rest = self.stdin[self.stdin_pos..].to_vec();
+        self.stdin_pos = self.stdin.len();
+        Ok(rest)
+    }
+
+    // --- host-env ---
+
+    pub(crate) fn env_var(&self, key: &str) -> Option<String> {
+        self.env
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+    }
+
+    pub(crate) fn env(&self) -> Vec<(String, String)> {
+        self.env.clone()
+    }
+
+    pub(crate) fn cwd(&self) -> String {
+        self.cwd.clone().unwrap_or_else(|| ".".to_string())
+    }
+
+    // --- host-log ---
+
+    pub(crate) fn log(&mut self, level: Level, target: &str, message: &str) {
+        if !self.log_enabled(level) {
+            return;
+        }
+        let level = match level {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        };
+        let line = if target.is_empty() {
+            format!("[{level}] {message}\n")
+        } else {
+            format!("[{level} {target}] {message}\n")
+        };
+        self.stderr.extend_from_slice(line.as_bytes());
+    }
+
+    pub(crate) fn log_enabled(&self, level: Level) -> bool {
+        self.log_level.0.is_some_and(|max| level >= max)
+    }
+
+    // --- host-fs ---
+
+    pub(crate) fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+        let key = normalize(path);
+        match self.files.get(&key) {
+            Some(contents) => Ok(contents.clone()),
+            None if self.dir_exists(&key) => Err(fs_error("read", "is a directory", path)),
+            None => Err(fs_error("read", "not found", path)),
+        }
+    }
+
+    pub(crate) fn write_file(
+        &mut self,
+        op: &str,
+        path: &str,
+        contents: &[u8],
+    ) -> Result<(), String> {
+        let key = self.writable(op, path)?;
+        self.files.insert(key, contents.to_vec());
+        Ok(())
+    }
+
+    pub(crate) fn append_file(
+        &mut self,
+        op: &str,
+        path: &str,
+        contents: &[u8],
+    ) -> Result<(), String> {
+        let key = self.writable(op, path)?;
+        self.files
+            .entry(key)
+            .or_default()
+            .extend_from_slice(contents);
+        Ok(())
+    }
+
+    pub(crate) fn create_dir(&mut self, path: &str) -> Result<(), String> {
+        let key = normalize(path);
+        if self.files.contains_key(&key) || self.dir_exists(&key) {
+            return Err(fs_error("create-dir", "already exists", path));
+        }
+        if !self.dir_exists(parent(&key)) {
+            return Err(fs_error("create-dir", "not found", path));
+        }
+        self.dirs.insert(key);
+        Ok(())
+    }
+
+    pub(crate) fn list_dir(&self, path: &str) -> Result<Vec<String>, String> {
+        let key = self.dir("list-dir", path)?;
+        Ok(self.children(&key).into_keys().collect())
+    }
+
+    pub(crate) fn walk_dir(
+        &self,
+        path: &str,
+        max_depth: Option<u32>,
+    ) -> Result<Vec<DirEntry>, String> {
+        let key = self.dir("walk-dir", path)?;
+        let mut out = Vec::new();
+        self.walk(&key, "", 1, max_depth, &mut out);
+        Ok(out)
+    }
+
+    pub(crate) fn copy_file(&mut self, src: &str, dst: &str) -> Result<u64, String> {
+        let contents = self
+            .read_file(src)
+            .map_err(|e| e.replacen("read", "copy", 1))?;
+        if normalize(src) == normalize(dst) {
+            return Err(format!(
+                "copy: source and destination are the same file: {src} -> {dst}"
+            ));
+        }
+        self.write_file("copy", dst, &contents)?;
+        Ok(contents.len() as u64)
+    }
+
+    pub(crate) fn rename(&mut self, src: &str, dst: &str) -> Result<(), String> {
+        let (from, to) = (normalize(src), normalize(dst));
+        let paths = format!("{src} -> {dst}");
+        for (path, key) in [(src, &from), (dst, &to)] {
+            if key.is_empty() {
+                return Err(format!(
+                    "cannot rename the preopened directory itself: {path}"
+                ));
+            }
+        }
+        if self.files.contains_key(&from) {
+            if self.dir_exists(&to) {
+                return Err(fs_error("rename", "is a directory", &paths));
+            }
+            if !self.dir_exists(parent(&to)) {
+                return Err(fs_error("rename", "not found", &paths));
+            }
+            let contents = self.files.remove(&from).unwrap_or_default();
+            self.files.insert(to, contents);
+            return Ok(());
+        }
+        if !self.dir_exists(&from) {
+            return Err(fs_error("rename", "not found", &paths));
+        }
+        let prefix = format!("{from}/");
+        if to == from || to.starts_with(&prefix) {
+            return Err(fs_error("rename", "filesystem error", &paths));
+        }
+        if self.files.contains_key(&to) {
+            return Err(fs_error("rename", "not a directory", &paths));
+        }
+        if !self.children(&to).is_empty() {
+            return Err(fs_error("rename", "directory not empty", &paths));
+        }
+        if !self.dir_exists(parent(&to)) {
+            return Err(fs_error("rename", "not found", &paths));
+        }
+        let moved = |p: &String| match p.strip_prefix(&prefix) {
+            Some(rest) => format!("{to}/{rest}"),
+            None if *p == from => to.clone(),
+            None => p.clone(),
+        };
+        self.files = std::mem::take(&mut self.files)
+            .into_iter()
+            .map(|(p, contents)| (moved(&p), contents))
+            .collect();
+        self.dirs = self.dirs.iter().map(moved).collect();
+        self.dirs.insert(to.clone());
+        Ok(())
+    }
+
+    pub(crate) fn remove_file(&mut self, path: &str) -> Result<(), String> {
+        let key = normalize(path);
+        if self.files.remove(&key).is_some() {
+            Ok(())
+        } else if self.dir_exists(&key) {
+            Err(fs_error("remove-file", "is a directory", path))
+        } else {
+            Err(fs_error("remove-file", "not found", path))
+        }
+    }
+
+    pub(crate) fn remove_dir(&mut self, path: &str) -> Result<(), String> {
+        let key = self.dir("remove-dir", path)?;
+        if !self.children(&key).is_empty() {
+            return Err(fs_error("remove-dir", "directory not empty", path));
+        }
+        self.dirs.remove(&key);
+        Ok(())
+    }
+
+    pub(crate) fn remove_dir_all(&mut self, path: &str) -> Result<(), String> {
+        let key = self.dir("remove-dir-all", path)?;
+        let prefix = format!("{key}/");
+        self.files.retain(|p, _| !p.starts_with(&prefix));
+        self.dirs.retain(|p| *p != key && !p.starts_with(&prefix));
+        Ok(())
+    }
+
+    pub(crate) fn stat(&self, path: &str) -> Result<FileInfo, String> {
+        let key = normalize(path);
+        let (kind, size) = match self.files.get(&key) {
+            Some(contents) => (FileKind::File, contents.len() as u64),
+            None if self.dir_exists(&key) => (FileKind::Dir, 0),
+            None => return Err(fs_error("stat", "not found", path)),
+        };
+        Ok(FileInfo {
+            kind,
+            size,
+            modified_at: 0,
+        })
+    }
+
+    pub(crate) fn exists(&self, path: &str) -> bool {
+        let key = normalize(path);
+        self.files.contains_key(&key) || self.dir_exists(&key)
+    }
+
+    /// The normalized `path` of a file that may be written: not a directory,
+    /// and in one that exists.
+    fn writable(&self, op: &str, path: &str) -> Result<String, String> {
+        let key = normalize(path);
+        if self.dir_exists(&key) {
+            return Err(fs_error(op, "is a directory", path));
+        }
+        if !self.dir_exists(parent(&key)) {
+            return Err(fs_error(op, "not found", path));
+        }
+        Ok(key)
+    }
+
+    /// The normalized `path` of an existing directory.
+    fn dir(&self, op: &str, path: &str) -> Result<String, String> {
+        let key = normalize(path);
+        if self.files.contains_key(&key) {
+            Err(fs_error(op, "not a directory", path))
+        } else if self.dir_exists(&key) {
+            Ok(key)
+        } else {
+            Err(fs_error(op, "not found", path))
+        }
+    }
+
+    fn dir_exists(&self, key: &str) -> bool {
+        if key.is_empty() || self.dirs.contains(key) {
+            return true;
+        }
+        let prefix = format!("{key}/");
+        self.files
+            .keys()
+            .chain(&self.dirs)
+            .any(|p| p.starts_with(&prefix))
+    }
+
+    /// Direct children of the directory `key` by name.
+    fn children(&self, key: &str) -> BTreeMap<String, FileKind> {
+        let prefix = if key.is_empty() {
+            String::new()
+        } else {
+            format!("{key}/")
+        };
+        let mut out = BTreeMap::new();
+        let files = self.files.keys().map(|p| (p, FileKind::File));
+        let dirs = self.dirs.iter().map(|p| (p, FileKind::Dir));
+        for (path, kind) in files.chain(dirs) {
+            let Some(rest) = path.strip_prefix(&prefix).filter(|r| !r.is_empty()) else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((name, _)) => out.insert(name.to_string(), FileKind::Dir),
+                None => out.insert(rest.to_string(), kind),
+            };
+        }
+        out
+    }
+
+    /// Same order as the host's `walk-dir`: depth-first, each directory's
+    /// entries sorted by name.
+    fn walk(
+        &self,
+        key: &str,
+        prefix: &str,
+        depth: u32,
+        max_depth: Option<u32>,
+        out: &mut Vec<DirEntry>,
+    ) {
+        if max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+        for (name, kind) in self.children(key) {
+            let path = format!("{prefix}{name}");
+            out.push(DirEntry {
+                path: path.clone(),
+                kind,
+            });
+            if matches!(kind, FileKind::Dir) {
+                let child = if key.is_empty() {
+                    name
+                } else {
+                    format!("{key}/{name}")
+                };
+                self.walk(&child, &format!("{path}/"), depth + 1, max_depth, out);
+            }
+        }
+    }
+
+    // --- host-pipes ---
+
+    pub(crate) fn pipe(&self, name: &str) -> Option<Rc<PipeFn>> {
+        self.pipes.get(name).cloned()
+    }
+
+    pub(crate) fn list_pipes(&self) -> Vec<PipeInfo> {
+        self.pipes.keys().map(|name| pipe_info(name)).collect()
+    }
+
+    pub(crate) fn describe_pipes(&self) -> Vec<PipeDetails> {
+        self.pipes
+            .keys()
+            .map(|name| PipeDetails {
+                info: pipe_info(name),
+                input_types: Vec::new(),
+                output_type: String::new(),
+                version: String::new(),
+            })
+            .collect()
+    }
+}
+
+/// Metadata for a fake pipe, which has none beyond its name.
+pub(crate) fn pipe_meta(name: &str) -> PipeMeta {
+    PipeMeta {
+        name: name.to_string(),
+        summary: String::new(),
+        input_types: Vec::new(),
+        output_type: String::new(),
+        version: String::new(),
+    }
+}
+
+fn pipe_info(name: &str) -> PipeInfo {
+    PipeInfo {
+        name: name.to_string(),
+        summary: String::new(),
+        path: String::new(),
+    }
+}
+
+/// The host's error format, without the WASI error code.
+fn fs_error(op: &str, what: &str, path: &str) -> String {
+    format!("{op}: {what}: {path}")
+}
+
+/// `path` with `.` segments, empty segments and leading `/` dropped; the root
+/// is `""`.
+fn normalize(path: &str) -> String {
+    path.split('/')
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parent(key: &str) -> &str {
+    key.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestHost;
+    use crate::{Command, CommandError, CommandMeta, CommandResult, fs, io, log, meta, pipes};
+
+    struct Cat;
+
+    impl Command for Cat {
+        fn meta() -> CommandMeta {
+            meta("cat").build()
+        }
+
+        fn run(argv: Vec<String>) -> CommandResult {
+            if argv.is_empty() {
+                io::print(io::read_to_string()?);
+            }
+            for path in argv {
+                io::print(String::from_utf8_lossy(&fs::read(path)?));
+            }
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn run_captures_output_and_errors() {
+        let mut host = TestHost::new()
+            .with_file("./a.txt", "one\n")
+            .with_stdin("piped");
+        let out = host.run::<Cat>(["a.txt"]);
+        assert_eq!(out.stdout_str(), "one\n");
+        assert_eq!(out.exit_code(), 0);
+
+        let out = host.run::<Cat>(Vec::<String>::new());
+        assert_eq!(out.stdout_str(), "piped");
+
+        let out = host.run::<Cat>(["missing.txt"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg == "read: not found: missing.txt"),
+            "{out:?}"
+        );
+        assert_eq!(out.exit_code(), 1);
+    }
+
+    struct Tree;
+
+    impl Command for Tree {
+        fn meta() -> CommandMeta {
+            meta("tree").build()
+        }
+
+        fn run(_argv: Vec<String>) -> CommandResult {
+            fs::create_dir("out")?;
+            fs::write("out/b.txt", "b")?;
+            fs::copy("src/a.txt", "out/a.txt")?;
+            for entry in fs::walk(".", None)? {
+                io::println(entry.path);
+            }
+            let err = fs::remove_dir("out").unwrap_err();
+            io::eprintln(err.message());
+            fs::rename("out/b.txt", "b.txt")?;
+            fs::rename("out", "done")?;
+            fs::remove_dir_all("src")?;
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn fake_fs_behaves_like_the_host() {
+        let mut host = TestHost::new().with_file("src/a.txt", "aaa");
+        let out = host.run::<Tree>(Vec::<String>::new());
+        assert_eq!(out.exit_code(), 0, "{out:?}");
+        assert_eq!(
+            out.stdout_str(),
+            "out\nout/a.txt\nout/b.txt\nsrc\nsrc/a.txt\n"
+        );
+        assert_eq!(out.stderr_str(), "remove-dir: directory not empty: out\n");
+        assert_eq!(host.file("done/a.txt"), Some(&b"aaa"[..]));
+        assert_eq!(host.file("b.txt"), Some(&b"b"[..]));
+        assert!(!host.is_dir("out"));
+        assert!(!host.is_dir("src"));
+    }
+
+    struct Format;
+
+    impl Command for Format {
+        fn meta() -> CommandMeta {
+            meta("format").build()
+        }
+
+        fn run(argv: Vec<String>) -> CommandResult {
+            let pipe = match pipes::open(&argv[0]) {
+                Err(pipes::LoadError::NotFound(msg)) => return Err(CommandError::InvalidArgs(msg)),
+                other => other?,
+            };
+            let out = pipe
+                .process(b"text", &["extra".to_string()])
+                .map_err(|e| CommandError::Failed(format!("{e:?}")))?;
+            io::print(String::from_utf8_lossy(&out));
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn fake_pipes_get_inline_options_first() {
+        let mut host = TestHost::new().with_pipe("format/echo", |input, options| {
+            Ok(format!("{} {}", String::from_utf8_lossy(input), options.join(",")).into_bytes())
+        });
+        let out = host.run::<Format>(["format/echo?width=80"]);
+        assert_eq!(out.stdout_str(), "text width=80,extra");
+
+        let out = host.run::<Format>(["format/none"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::InvalidArgs(msg)) if msg == "pipe not found: format/none"),
+            "{out:?}"
+        );
+    }
+
+    struct Stream;
+
+    impl Command for Stream {
+        fn meta() -> CommandMeta {
+            meta("stream").build()
+        }
+
+        fn run(argv: Vec<String>) -> CommandResult {
+            let pipe = pipes::load(&argv[0])?;
+            let input = vec![b'a'; 3 * pipes::CHUNK_SIZE + 1];
+            let out = pipe
+                .process_reader(&input[..], &[])
+                .map_err(|e| CommandError::Failed(format!("{e:?}")))?;
+            io::print(format!(
+                "{} {}",
+                out.len(),
+                String::from_utf8_lossy(&out[..3])
+            ));
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn fake_pipes_take_streamed_input_whole() {
+        let mut host = TestHost::new().with_pipe("format/upper", |input, options| {
+            assert_eq!(options, ["case=upper"]);
+            Ok(input.to_ascii_uppercase())
+        });
+        let out = host.run::<Stream>(["format/upper?case=upper"]);
+        assert_eq!(
+            out.stdout_str(),
+            format!("{} AAA", 3 * pipes::CHUNK_SIZE + 1)
+        );
+    }
+
+    struct Chatty;
+
+    impl Command for Chatty {
+        fn meta() -> CommandMeta {
+            meta("chatty").build()
+        }
+
+        fn run(_argv: Vec<String>) -> CommandResult {
+            log::debug!("starting {}", 1);
+            log::warn!("careful");
+            if log::enabled(log::Level::Trace) {
+                log::log(log::Level::Trace, "", "details");
+            }
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn log_writes_at_or_above_the_max_level() {
+        let out = TestHost::new().run::<Chatty>(Vec::<String>::new());
+        assert_eq!(
+            out.stderr_str(),
+            "[WARN wacli_cdk::testing::tests] careful\n"
+        );
+
+        let mut host = TestHost::new().with_log_level(Some(log::Level::Trace));
+        let out = host.run::<Chatty>(Vec::<String>::new());
+        assert_eq!(
+            out.stderr_str(),
+            "[DEBUG wacli_cdk::testing::tests] starting 1\n\
+             [WARN wacli_cdk::testing::tests] careful\n\
+             [TRACE] details\n"
+        );
+
+        let mut host = TestHost::new().with_log_level(None);
+        assert_eq!(host.run::<Chatty>(Vec::<String>::new()).stderr_str(), "");
+    }
+
+    struct Login;
+
+    impl Command for Login {
+        fn meta() -> CommandMeta {
+            meta("login").build()
+        }
+
+        fn run(_argv: Vec<String>) -> CommandResult {
+            let user = io::prompt("user: ")?.unwrap_or_default();
+            let password = io::prompt_secret("password: ")?;
+            io::println(format!("{user}:{}", password.len()));
+            while let Some(line) = io::read_line()? {
+                io::println(format!("rest: {line}"));
+            }
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn prompts_read_lines_from_stdin() {
+        let mut host = TestHost::new().with_stdin("alice\r\nhunter2\nmore\n\nlast");
+        let out = host.run::<Login>(Vec::<String>::new());
+        assert_eq!(out.exit_code(), 0, "{out:?}");
+        assert_eq!(out.stderr_str(), "user: password: ");
+        assert_eq!(
+            out.stdout_str(),
+            "alice:7\nrest: more\nrest: \nrest: last\n"
+        );
+
+        let mut host = TestHost::new().with_stdin("bob\n");
+        let out = host.run::<Login>(Vec::<String>::new());
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg == "end of input"),
+            "{out:?}"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! `#[wacli_command]` end to end: parsing, typed fields and the generated
+//! `Command` impl, run through `TestHost`.
+
+use wacli_cdk::testing::TestHost;
+use wacli_cdk::{CommandError, CommandResult, args, io, wacli_command};
+
+/// Repeat text
+#[wacli_command(name = "repeat", version = "1.0.0", run = Self::exec)]
+struct Repeat {
+    /// How many times
+    #[arg(long, short = 'n', default_value = "1")]
+    times: u32,
+    /// Uppercase the text
+    #[arg(long)]
+    upper: bool,
+    #[arg(long)]
+    sep: Option<String>,
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Text to repeat
+    text: String,
+}
+
+impl Repeat {
+    fn exec(self) -> CommandResult {
+        let text = if self.upper {
+            self.text.to_uppercase()
+        } else {
+            self.text
+        };
+        let sep = self.sep.as_deref().unwrap_or(" ");
+        let mut out = vec![text; self.times as usize].join(sep);
+        for tag in &self.tags {
+            out = format!("[{tag}] {out}");
+        }
+        io::println(out);
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Repeat);
+
+#[test]
+fn fields_are_read_from_argv() {
+    let mut host = TestHost::new();
+    let out = host.run::<Repeat>(["hi"]);
+    assert_eq!(out.stdout_str(), "hi\n");
+
+    let out = host.run::<Repeat>([
+        "-n", "3", "--upper", "--sep", ",", "hi", "--tag", "a", "--tag", "b",
+    ]);
+    assert_eq!(out.stdout_str(), "[b] [a] HI,HI,HI\n");
+}
+
+#[test]
+fn bad_values_are_usage_errors() {
+    let mut host = TestHost::new();
+    let out = host.run::<Repeat>(["-n", "many", "hi"]);
+    assert!(
+        matches!(out.result, Err(CommandError::InvalidArgs(_))),
+        "{out:?}"
+    );
+
+    let out = host.run::<Repeat>(Vec::<String>::new());
+    assert!(
+        matches!(&out.result, Err(CommandError::InvalidArgs(msg)) if msg.contains("<TEXT>")),
+        "{out:?}"
+    );
+}
+
+#[test]
+fn metadata_comes_from_the_struct() {
+    let meta = Repeat::command_meta();
+    assert_eq!(meta.name, "repeat");
+    assert_eq!(meta.summary, "Repeat text");
+    let names: Vec<&str> = meta.args.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, ["times", "upper", "sep", "tags", "text"]);
+    assert_eq!(meta.args[0].short.as_deref(), Some("-n"));
+    assert_eq!(meta.args[3].long.as_deref(), Some("--tag"));
+    assert!(meta.args[4].required);
+
+    let argv = vec!["x".to_string(), "--sep=-".to_string()];
+    let matches = args::parse(&meta, &argv).unwrap();
+    let parsed = Repeat::from_matches(&matches).unwrap();
+    assert_eq!(parsed.times, 1);
+    assert_eq!(parsed.sep.as_deref(), Some("-"));
+}
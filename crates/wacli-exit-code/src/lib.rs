@@ -0,0 +1,45 @@
+//! Exit codes carried in a `failed` command-error message
+//! (`wacli-exit:2:message`). `command-error` cannot grow a case within
+//! `wacli:cli@2.0.0`, so the CDK encodes the code into the message and core
+//! decodes it.
+//!
+//! A failure never exits with 0: core skips the exit call for 0, so the
+//! command would look successful. Both sides treat a code of 0 as 1.
+
+/// Prefix of a `failed` message that carries an exit code.
+pub const EXIT_CODE_PREFIX: &str = "wacli-exit:";
+
+/// A `failed` message that makes core print `message` and exit with `code`
+/// (1 when `code` is 0).
+pub fn encode_exit_code(code: u32, message: &str) -> String {
+    format!("{EXIT_CODE_PREFIX}{}:{message}", code.max(1))
+}
+
+/// The code and message of a `failed` message made by [`encode_exit_code`];
+/// `None` for any other message. A code of 0 decodes as 1.
+pub fn decode_exit_code(msg: &str) -> Option<(u32, &str)> {
+    let (code, message) = msg.strip_prefix(EXIT_CODE_PREFIX)?.split_once(':')?;
+    Some((code.parse::<u32>().ok()?.max(1), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_round_trip_through_failed_messages() {
+        let msg = encode_exit_code(3, "usage: probe <WORD>");
+        assert_eq!(msg, "wacli-exit:3:usage: probe <WORD>");
+        assert_eq!(decode_exit_code(&msg), Some((3, "usage: probe <WORD>")));
+
+        for msg in ["plain failure", "wacli-exit:three:oops", "wacli-exit:3"] {
+            assert_eq!(decode_exit_code(msg), None, "{msg}");
+        }
+    }
+
+    #[test]
+    fn code_zero_still_fails() {
+        assert_eq!(encode_exit_code(0, "a:b"), "wacli-exit:1:a:b");
+        assert_eq!(decode_exit_code("wacli-exit:0:a:b"), Some((1, "a:b")));
+    }
+}
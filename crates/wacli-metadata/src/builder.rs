@@ -0,0 +1,536 @@
+//! Typed builders for command metadata.
+//!
+//! These apply the same normalization as the CDK builders and the
+//! `declare_command_metadata!` macro (which builds its payload through them),
+//! so tooling that is not written against the CDK still produces the payload
+//! `wacli build` expects.
+
+use crate::{
+    ArgDef, ArgGroup, ArgSchema, CommandMeta, CommandMetadataV1, CommandSchema, SelfTest,
+    SharedArgRef,
+};
+
+/// Builder for a command's metadata payload.
+///
+/// ```
+/// use wacli_metadata::{ArgSchemaBuilder, CommandMetaBuilder};
+///
+/// let payload = CommandMetaBuilder::new("show")
+///     .summary("Show text")
+///     .arg(ArgSchemaBuilder::new("format").long("format").possible_values(["plain", "json"]))
+///     .arg(ArgSchemaBuilder::new("text"))
+///     .build();
+/// assert_eq!(payload.command_meta.args[0].long.as_deref(), Some("--format"));
+/// assert!(payload.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CommandMetaBuilder {
+    name: String,
+    summary: String,
+    usage: String,
+    aliases: Vec<String>,
+    hidden_aliases: Vec<String>,
+    version: String,
+    hidden: bool,
+    description: String,
+    examples: Vec<String>,
+    args: Vec<ArgSchema>,
+    groups: Vec<ArgGroup>,
+    allow_arg_files: bool,
+    subcommands: Vec<CommandSchema>,
+    self_tests: Vec<SelfTest>,
+}
+
+impl CommandMetaBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = summary.into();
+        self
+    }
+
+    pub fn usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = usage.into();
+        self
+    }
+
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// An alias that dispatches but is not shown in help.
+    pub fn hidden_alias(mut self, alias: impl Into<String>) -> Self {
+        self.hidden_aliases.push(alias.into());
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn example(mut self, example: impl Into<String>) -> Self {
+        self.examples.push(example.into());
+        self
+    }
+
+    pub fn allow_arg_files(mut self, allow: bool) -> Self {
+        self.allow_arg_files = allow;
+        self
+    }
+
+    pub fn arg(mut self, arg: ArgSchemaBuilder) -> Self {
+        self.args.push(arg.build());
+        self
+    }
+
+    /// Declare a group that args join with [`ArgSchemaBuilder::group`].
+    pub fn group(mut self, group: ArgGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Add a nested command. Only its schema is kept (self-tests belong to
+    /// the top-level command).
+    pub fn subcommand(mut self, subcommand: CommandMetaBuilder) -> Self {
+        self.subcommands.push(subcommand.build_schema());
+        self
+    }
+
+    pub fn self_test(mut self, test: SelfTest) -> Self {
+        self.self_tests.push(test);
+        self
+    }
+
+    /// Build only the command schema.
+    pub fn build_schema(self) -> CommandSchema {
+        CommandSchema {
+            name: self.name,
+            summary: self.summary,
+            usage: self.usage,
+            aliases: self.aliases,
+            hidden_aliases: self.hidden_aliases,
+            version: self.version,
+            hidden: self.hidden,
+            description: self.description,
+            examples: self.examples,
+            args: self.args,
+            groups: self.groups,
+            allow_arg_files: self.allow_arg_files,
+            subcommands: self.subcommands,
+        }
+    }
+
+    /// Build the full payload: `command-meta` derived from the schema, plus
+    /// the schema itself and any self-tests.
+    pub fn build(mut self) -> CommandMetadataV1 {
+        let self_tests = std::mem::take(&mut self.self_tests);
+        let schema = self.build_schema();
+        let mut payload = CommandMetadataV1::new(schema.to_meta(), Some(schema));
+        payload.self_tests = self_tests;
+        payload
+    }
+}
+
+/// Builder for `ArgSchema`.
+#[derive(Debug, Clone, Default)]
+pub struct ArgSchemaBuilder {
+    name: String,
+    short: Option<String>,
+    long: Option<String>,
+    help: String,
+    required: bool,
+    default_value: Option<String>,
+    env: Option<String>,
+    value_name: Option<String>,
+    takes_value: Option<bool>,
+    multiple: Option<bool>,
+    value_type: Option<String>,
+    possible_values: Vec<String>,
+    conflicts_with: Vec<String>,
+    requires: Vec<String>,
+    hidden: bool,
+    complete_command: Option<String>,
+    shared: Option<SharedArgRef>,
+    group: Option<String>,
+    allow_hyphen_values: bool,
+    countable: bool,
+    var_arg: bool,
+}
+
+impl ArgSchemaBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Short flag; `"v"` and `"-v"` are equivalent.
+    pub fn short(mut self, short: impl Into<String>) -> Self {
+        self.short = Some(short.into());
+        self
+    }
+
+    /// Long flag; `"verbose"` and `"--verbose"` are equivalent.
+    pub fn long(mut self, long: impl Into<String>) -> Self {
+        self.long = Some(long.into());
+        self
+    }
+
+    pub fn help(mut self, help: impl Into<String>) -> Self {
+        self.help = help.into();
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn default_value(mut self, value: impl Into<String>) -> Self {
+        self.default_value = Some(value.into());
+        self
+    }
+
+    pub fn env(mut self, env: impl Into<String>) -> Self {
+        self.env = Some(env.into());
+        self
+    }
+
+    pub fn value_name(mut self, value_name: impl Into<String>) -> Self {
+        self.value_name = Some(value_name.into());
+        self
+    }
+
+    /// Override `takes-value` inference (see [`ArgSchemaBuilder::build`]).
+    pub fn takes_value(mut self, takes_value: bool) -> Self {
+        self.takes_value = Some(takes_value);
+        self
+    }
+
+    /// Whether the arg may be given more than once [default: true].
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = Some(multiple);
+        self
+    }
+
+    pub fn value_type(mut self, value_type: impl Into<String>) -> Self {
+        self.value_type = Some(value_type.into());
+        self
+    }
+
+    pub fn possible_value(mut self, value: impl Into<String>) -> Self {
+        self.possible_values.push(value.into());
+        self
+    }
+
+    pub fn possible_values<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.possible_values
+            .extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn conflicts_with(mut self, name: impl Into<String>) -> Self {
+        self.conflicts_with.push(name.into());
+        self
+    }
+
+    pub fn requires(mut self, name: impl Into<String>) -> Self {
+        self.requires.push(name.into());
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Registry command (`pipes/list`) run by `__complete` to suggest values.
+    pub fn complete_command(mut self, command: impl Into<String>) -> Self {
+        self.complete_command = Some(command.into());
+        self
+    }
+
+    /// Name of the [`ArgGroup`] this arg belongs to.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Take the next token as the value even when it names a flag
+    /// (`--filter -v`).
+    pub fn allow_hyphen_values(mut self, allow: bool) -> Self {
+        self.allow_hyphen_values = allow;
+        self
+    }
+
+    /// Count occurrences of this boolean flag (`-vvv`).
+    pub fn countable(mut self, countable: bool) -> Self {
+        self.countable = countable;
+        self
+    }
+
+    /// Let the last positional take every remaining positional token
+    /// (`[FILES]...`).
+    pub fn var_arg(mut self, var_arg: bool) -> Self {
+        self.var_arg = var_arg;
+        self
+    }
+
+    /// Record the shared template this arg was spliced from.
+    pub fn shared(mut self, shared: SharedArgRef) -> Self {
+        self.shared = Some(shared);
+        self
+    }
+
+    /// Build the schema.
+    ///
+    /// Flags get their dash prefix added if missing. Unless set explicitly,
+    /// `takes-value` is true for positionals and for flags that declare a
+    /// value name, default, env var, value type, possible values or
+    /// completion command.
+    pub fn build(self) -> ArgSchema {
+        let short = self.short.map(|s| normalize_short(&s));
+        let long = self.long.map(|s| normalize_long(&s));
+
+        let positional = short.is_none() && long.is_none();
+        let inferred_takes_value = positional
+            || self.value_name.is_some()
+            || self.default_value.is_some()
+            || self.env.is_some()
+            || self.value_type.is_some()
+            || self.complete_command.is_some()
+            || !self.possible_values.is_empty();
+
+        ArgSchema {
+            name: self.name,
+            short,
+            long,
+            help: self.help,
+            required: self.required,
+            default_value: self.default_value,
+            env: self.env,
+            value_name: self.value_name,
+            takes_value: self.takes_value.unwrap_or(inferred_takes_value),
+            multiple: self.multiple.unwrap_or(true),
+            value_type: self.value_type,
+            possible_values: self.possible_values,
+            conflicts_with: self.conflicts_with,
+            requires: self.requires,
+            hidden: self.hidden,
+            complete_command: self.complete_command,
+            shared: self.shared,
+            group: self.group,
+            allow_hyphen_values: self.allow_hyphen_values,
+            countable: self.countable,
+            var_arg: self.var_arg,
+        }
+    }
+}
+
+/// Trim and add a leading `-` if missing (`"v"` -> `"-v"`).
+pub fn normalize_short(short: &str) -> String {
+    let s = short.trim();
+    if s.starts_with('-') {
+        s.to_string()
+    } else {
+        format!("-{s}")
+    }
+}
+
+/// Trim and add a leading `--` if missing (`"verbose"` -> `"--verbose"`).
+pub fn normalize_long(long: &str) -> String {
+    let s = long.trim();
+    if s.starts_with('-') {
+        s.to_string()
+    } else {
+        format!("--{s}")
+    }
+}
+
+impl ArgSchema {
+    /// The `command-meta` view of this arg.
+    pub fn to_arg_def(&self) -> ArgDef {
+        ArgDef {
+            name: self.name.clone(),
+            short: self.short.clone(),
+            long: self.long.clone(),
+            help: self.help.clone(),
+            required: self.required,
+            default_value: self.default_value.clone(),
+            value_name: self.value_name.clone(),
+            takes_value: self.takes_value,
+        }
+    }
+}
+
+impl CommandSchema {
+    /// The `command-meta` view of this schema (drops schema-only fields,
+    /// including subcommands).
+    pub fn to_meta(&self) -> CommandMeta {
+        CommandMeta {
+            name: self.name.clone(),
+            summary: self.summary.clone(),
+            usage: self.usage.clone(),
+            aliases: self.aliases.clone(),
+            hidden_aliases: self.hidden_aliases.clone(),
+            version: self.version.clone(),
+            hidden: self.hidden,
+            description: self.description.clone(),
+            examples: self.examples.clone(),
+            args: self.args.iter().map(ArgSchema::to_arg_def).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arg_builder_normalizes_flags_and_infers_takes_value() {
+        let arg = ArgSchemaBuilder::new("verbose")
+            .short(" v ")
+            .long("verbose")
+            .build();
+        assert_eq!(arg.short.as_deref(), Some("-v"));
+        assert_eq!(arg.long.as_deref(), Some("--verbose"));
+        assert!(!arg.takes_value);
+        assert!(arg.multiple);
+
+        let dashed = ArgSchemaBuilder::new("out")
+            .short("-o")
+            .long("--out")
+            .build();
+        assert_eq!(dashed.short.as_deref(), Some("-o"));
+        assert_eq!(dashed.long.as_deref(), Some("--out"));
+
+        assert!(ArgSchemaBuilder::new("file").build().takes_value);
+        for arg in [
+            ArgSchemaBuilder::new("a").long("a").value_name("N"),
+            ArgSchemaBuilder::new("a").long("a").default_value("1"),
+            ArgSchemaBuilder::new("a").long("a").env("A"),
+            ArgSchemaBuilder::new("a").long("a").value_type("int"),
+            ArgSchemaBuilder::new("a").long("a").possible_value("x"),
+        ] {
+            assert!(arg.build().takes_value);
+        }
+        let forced = ArgSchemaBuilder::new("a")
+            .long("a")
+            .env("A")
+            .takes_value(false);
+        assert!(!forced.build().takes_value);
+    }
+
+    #[test]
+    fn builder_payload_round_trips_through_json() {
+        let payload = CommandMetaBuilder::new("show")
+            .summary("Show text")
+            .usage("show [OPTIONS] [TEXT]")
+            .alias("s")
+            .version("0.1.0")
+            .description("Longer help")
+            .example("show hello")
+            .allow_arg_files(true)
+            .arg(
+                ArgSchemaBuilder::new("format")
+                    .short("f")
+                    .long("format")
+                    .help("Output format")
+                    .env("SHOW_FORMAT")
+                    .possible_values(["plain", "json"])
+                    .default_value("plain")
+                    .multiple(false)
+                    .conflicts_with("raw"),
+            )
+            .arg(
+                ArgSchemaBuilder::new("raw")
+                    .long("raw")
+                    .hidden(true)
+                    .group("mode"),
+            )
+            .arg(
+                ArgSchemaBuilder::new("text")
+                    .required(true)
+                    .requires("format"),
+            )
+            .group(ArgGroup::new("mode").required(true))
+            .build();
+
+        let meta = &payload.command_meta;
+        let schema = payload.command_schema.as_ref().unwrap();
+        assert_eq!(meta.name, "show");
+        assert_eq!(meta.aliases, vec!["s"]);
+        assert_eq!(meta.args.len(), 3);
+        assert_eq!(meta.args[0].short.as_deref(), Some("-f"));
+        assert!(meta.args[0].takes_value);
+        assert!(!meta.args[1].takes_value);
+        assert!(schema.allow_arg_files);
+        assert!(!schema.args[0].multiple);
+        assert!(schema.args[1].hidden);
+        assert_eq!(schema.args[1].group.as_deref(), Some("mode"));
+        assert_eq!(schema.groups, vec![ArgGroup::new("mode").required(true)]);
+
+        let decoded: CommandMetadataV1 = serde_json::from_slice(&payload.to_json_bytes()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&payload).unwrap()
+        );
+        assert!(decoded.validate().is_ok());
+    }
+
+    #[test]
+    fn var_arg_stays_in_the_schema() {
+        let payload = CommandMetaBuilder::new("cp")
+            .arg(ArgSchemaBuilder::new("dest"))
+            .arg(
+                ArgSchemaBuilder::new("files")
+                    .value_name("FILE")
+                    .var_arg(true),
+            )
+            .build();
+        let schema = payload.command_schema.as_ref().unwrap();
+        assert!(schema.args.iter().all(|a| a.multiple));
+        assert!(!schema.args[0].var_arg);
+        assert!(schema.args[1].var_arg);
+        assert_eq!(
+            payload.command_meta.args[1].value_name.as_deref(),
+            Some("FILE")
+        );
+
+        let json = serde_json::to_value(schema).unwrap();
+        assert_eq!(json["args"][1]["var-arg"], true);
+        assert!(json["args"][0].get("var-arg").is_none());
+    }
+
+    #[test]
+    fn schemas_without_groups_keep_their_json() {
+        let json = r#"{"name":"show","args":[{"name":"raw","long":"--raw"}]}"#;
+        let schema: CommandSchema = serde_json::from_str(json).unwrap();
+        assert!(schema.groups.is_empty());
+        assert_eq!(schema.args[0].group, None);
+        let encoded = serde_json::to_string(&schema).unwrap();
+        assert!(!encoded.contains("group"), "{encoded}");
+    }
+}
@@ -0,0 +1,355 @@
+//! JSON Schema (draft 2020-12) for the `CommandMetadataV1` payload.
+
+use serde_json::{Value, json};
+
+/// JSON Schema describing the JSON embedded in [`crate::COMMAND_METADATA_SECTION`].
+///
+/// Tooling that writes the payload without this crate can validate against
+/// it. Keys are kebab-case; unknown keys are rejected so that misspellings
+/// (e.g. `default_value`) are caught. Cross-field rules (duplicate flags,
+/// relation targets, ...) are only checked by
+/// [`CommandMetadataV1::validate`](crate::CommandMetadataV1::validate).
+pub fn schema() -> Value {
+    let string = json!({ "type": "string" });
+    let boolean = json!({ "type": "boolean" });
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let command_name = json!({ "type": "string", "pattern": "^[a-z]([a-z0-9-]*[a-z0-9])?$" });
+    let short = json!({ "type": "string", "pattern": "^-[^-\\s]$" });
+    let long = json!({ "type": "string", "pattern": "^--[^-=\\s][^=\\s]*$" });
+
+    let command_fields = json!({
+        "name": command_name,
+        "summary": string,
+        "usage": string,
+        "aliases": { "type": "array", "items": command_name },
+        "hidden-aliases": { "type": "array", "items": command_name },
+        "version": string,
+        "hidden": boolean,
+        "description": string,
+        "examples": strings,
+    });
+
+    let mut meta_props = command_fields.clone();
+    meta_props["args"] = json!({ "type": "array", "items": { "$ref": "#/$defs/arg-def" } });
+
+    let mut schema_props = command_fields;
+    schema_props["args"] = json!({ "type": "array", "items": { "$ref": "#/$defs/arg-schema" } });
+    schema_props["groups"] = json!({ "type": "array", "items": { "$ref": "#/$defs/arg-group" } });
+    schema_props["allow-arg-files"] = boolean.clone();
+    schema_props["subcommands"] =
+        json!({ "type": "array", "items": { "$ref": "#/$defs/command-schema" } });
+
+    let arg_fields = json!({
+        "name": { "type": "string", "minLength": 1 },
+        "short": short,
+        "long": long,
+        "help": string,
+        "required": boolean,
+        "default-value": string,
+        "value-name": string,
+        "takes-value": boolean,
+    });
+
+    let mut arg_schema_props = arg_fields.clone();
+    for key in ["env", "value-type", "complete-command", "group"] {
+        arg_schema_props[key] = string.clone();
+    }
+    for key in [
+        "multiple",
+        "hidden",
+        "allow-hyphen-values",
+        "countable",
+        "var-arg",
+    ] {
+        arg_schema_props[key] = boolean.clone();
+    }
+    for key in ["possible-values", "conflicts-with", "requires"] {
+        arg_schema_props[key] = strings.clone();
+    }
+    arg_schema_props["shared"] = json!({
+        "type": "object",
+        "required": ["name", "digest"],
+        "additionalProperties": false,
+        "properties": { "name": string, "digest": string },
+    });
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "wacli command metadata (format-version 1)",
+        "type": "object",
+        "required": ["format-version", "command-meta"],
+        "additionalProperties": false,
+        "properties": {
+            "format-version": { "const": 1 },
+            "command-meta": { "$ref": "#/$defs/command-meta" },
+            "command-schema": { "$ref": "#/$defs/command-schema" },
+            "self-tests": { "type": "array", "items": { "$ref": "#/$defs/self-test" } },
+            "cdk-version": string,
+        },
+        "$defs": {
+            "command-meta": {
+                "type": "object",
+                "required": ["name"],
+                "additionalProperties": false,
+                "properties": meta_props,
+            },
+            "command-schema": {
+                "type": "object",
+                "required": ["name"],
+                "additionalProperties": false,
+                "properties": schema_props,
+            },
+            "arg-def": {
+                "type": "object",
+                "required": ["name"],
+                "additionalProperties": false,
+                "properties": arg_fields,
+            },
+            "arg-schema": {
+                "type": "object",
+                "required": ["name"],
+                "additionalProperties": false,
+                "properties": arg_schema_props,
+            },
+            "arg-group": {
+                "type": "object",
+                "required": ["name"],
+                "additionalProperties": false,
+                "properties": {
+                    "name": { "type": "string", "minLength": 1 },
+                    "required": boolean,
+                    "multiple": boolean,
+                },
+            },
+            "self-test": {
+                "type": "object",
+                "required": ["cmd"],
+                "additionalProperties": false,
+                "properties": {
+                    "cmd": { "type": "string", "minLength": 1 },
+                    "expect-stdout": string,
+                    "expect-exit": { "type": "integer", "minimum": 0 },
+                },
+            },
+        },
+    })
+}
+
+/// Paths of the fields in a command metadata payload that [`schema`] does not
+/// declare, at any depth (`value-types`, `command-meta.value-types`,
+/// `command-schema.args[0].completion`), in key order.
+///
+/// Readers use it to name what a payload from a newer format version carries
+/// that they skip; a value of the wrong type is not reported.
+pub fn unknown_fields(payload: &Value) -> Vec<String> {
+    let root = schema();
+    let mut fields = Vec::new();
+    collect_unknown(&root, &root, payload, "", &mut fields);
+    fields
+}
+
+fn collect_unknown(root: &Value, schema: &Value, value: &Value, path: &str, out: &mut Vec<String>) {
+    if let Some(name) = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+    {
+        return collect_unknown(root, &root["$defs"][name], value, path, out);
+    }
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            collect_unknown(root, item_schema, item, &format!("{path}[{i}]"), out);
+        }
+    }
+    let (Some(obj), Some(props)) = (value.as_object(), schema.get("properties")) else {
+        return;
+    };
+    for (key, item) in obj {
+        let item_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        match props.get(key) {
+            Some(prop) => collect_unknown(root, prop, item, &item_path, out),
+            None => out.push(item_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{schema, unknown_fields};
+    use crate::{ArgGroup, ArgSchemaBuilder, CommandMetaBuilder, SelfTest, SharedArgRef};
+    use serde_json::{Value, json};
+
+    /// Minimal validator for the keywords `schema()` uses, except `pattern`
+    /// (there is no regex engine in this crate's dependencies).
+    fn check(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+        if let Some(r) = schema.get("$ref").and_then(Value::as_str) {
+            let name = r.strip_prefix("#/$defs/").expect("local $ref");
+            return check(root, &root["$defs"][name], value, path, errors);
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            errors.push(format!("{path}: expected {expected}"));
+        }
+        let ty = schema.get("type").and_then(Value::as_str);
+        let ok = match ty {
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            Some("string") => value.is_string(),
+            Some("boolean") => value.is_boolean(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            _ => true,
+        };
+        if !ok {
+            errors.push(format!("{path}: expected {}", ty.unwrap()));
+            return;
+        }
+        if let (Some(min), Some(s)) = (
+            schema.get("minLength").and_then(Value::as_u64),
+            value.as_str(),
+        ) && (s.chars().count() as u64) < min
+        {
+            errors.push(format!("{path}: shorter than {min}"));
+        }
+        if let Some(items) = value.as_array() {
+            for (i, item) in items.iter().enumerate() {
+                check(
+                    root,
+                    &schema["items"],
+                    item,
+                    &format!("{path}[{i}]"),
+                    errors,
+                );
+            }
+        }
+        if let Some(obj) = value.as_object() {
+            for key in schema["required"].as_array().into_iter().flatten() {
+                if !obj.contains_key(key.as_str().unwrap()) {
+                    errors.push(format!("{path}: missing {key}"));
+                }
+            }
+            for (key, item) in obj {
+                match schema["properties"].get(key) {
+                    Some(prop) => check(root, prop, item, &format!("{path}.{key}"), errors),
+                    None if schema["additionalProperties"] == json!(false) => {
+                        errors.push(format!("{path}: unexpected key '{key}'"));
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    fn errors(value: &Value) -> Vec<String> {
+        let root = schema();
+        let mut errors = Vec::new();
+        check(&root, &root, value, "$", &mut errors);
+        errors
+    }
+
+    #[test]
+    fn schema_accepts_a_fully_populated_payload() {
+        let payload = CommandMetaBuilder::new("show")
+            .summary("Show text")
+            .usage("show [OPTIONS] [TEXT]")
+            .alias("s")
+            .version("0.1.0")
+            .hidden(true)
+            .description("Longer help")
+            .example("show hello")
+            .allow_arg_files(true)
+            .arg(
+                ArgSchemaBuilder::new("format")
+                    .short("f")
+                    .long("format")
+                    .help("Output format")
+                    .required(true)
+                    .env("SHOW_FORMAT")
+                    .value_name("FMT")
+                    .value_type("string")
+                    .possible_values(["plain", "json"])
+                    .default_value("plain")
+                    .conflicts_with("text")
+                    .requires("text")
+                    .hidden(true)
+                    .complete_command("formats")
+                    .shared(SharedArgRef {
+                        name: "format".to_string(),
+                        digest: "fnv1a64:0123456789abcdef".to_string(),
+                    }),
+            )
+            .arg(ArgSchemaBuilder::new("text"))
+            .arg(ArgSchemaBuilder::new("json").long("json").group("output"))
+            .group(ArgGroup::new("output").required(true).multiple(true))
+            .subcommand(
+                CommandMetaBuilder::new("raw")
+                    .summary("Show raw bytes")
+                    .arg(ArgSchemaBuilder::new("file").required(true)),
+            )
+            .self_test(
+                SelfTest::new("show hello")
+                    .expect_stdout("contains:hello")
+                    .expect_exit(0),
+            )
+            .build();
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(errors(&value), Vec::<String>::new());
+    }
+
+    #[test]
+    fn schema_rejects_misspelled_and_missing_keys() {
+        let value = json!({
+            "format-version": 1,
+            "command-meta": {
+                "summary": "no name",
+                "args": [{ "name": "a", "default_value": "x" }],
+            },
+        });
+        assert_eq!(
+            errors(&value),
+            vec![
+                "$.command-meta: missing \"name\"",
+                "$.command-meta.args[0]: unexpected key 'default_value'",
+            ]
+        );
+        assert_eq!(
+            errors(&json!({ "format-version": 2, "command-meta": { "name": "x" } })),
+            vec!["$.format-version: expected 1"]
+        );
+    }
+
+    #[test]
+    fn unknown_fields_walk_nested_objects() {
+        let value = json!({
+            "format-version": 3,
+            "command-meta": { "name": "x", "value-types": ["path"] },
+            "command-schema": {
+                "name": "x",
+                "args": [
+                    { "name": "a", "shared": { "name": "s", "digest": "d", "scope": "app" } },
+                    { "name": "b", "completion": "path" },
+                ],
+                "subcommands": [{ "name": "y", "stability": "beta" }],
+            },
+            "value-types": {},
+        });
+        assert_eq!(
+            unknown_fields(&value),
+            vec![
+                "command-meta.value-types",
+                "command-schema.args[0].shared.scope",
+                "command-schema.args[1].completion",
+                "command-schema.subcommands[0].stability",
+                "value-types",
+            ]
+        );
+        assert_eq!(
+            unknown_fields(&json!({ "format-version": 1, "command-meta": { "name": "x" } })),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -5,14 +5,38 @@
 //! and are used for:
 //! - embedding metadata into a WASM custom section (no plugin execution)
 //! - extracting metadata during `wacli build` (registry generation)
+//! - describing a composed CLI ([`AppProvenanceV1`], read by `wacli inspect`)
+//! - producing valid payloads from other tooling ([`CommandMetaBuilder`],
+//!   [`CommandMetadataV1::validate`], and the JSON Schema from [`schema`])
+//! - sharing arg definitions across commands ([`SharedArgs`])
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+mod builder;
+mod json_schema;
+mod shared_args;
+mod validate;
+
+pub use builder::{ArgSchemaBuilder, CommandMetaBuilder, normalize_long, normalize_short};
+pub use json_schema::{schema, unknown_fields};
+pub use shared_args::{SharedArgRef, SharedArgs};
+pub use validate::{ValidationError, global_arg_issues};
 
 /// Custom section name containing JSON-encoded command metadata.
 ///
 /// The payload is a JSON object `CommandMetadataV1`.
 pub const COMMAND_METADATA_SECTION: &str = "wacli:cli/command-metadata@1";
 
+/// Custom section name containing a `CommandMetadataV2` payload.
+///
+/// During the transition, components may carry both sections; readers prefer
+/// this one and fall back to [`COMMAND_METADATA_SECTION`].
+pub const COMMAND_METADATA_SECTION_V2: &str = "wacli:cli/command-metadata@2";
+
+/// Newest command metadata `format-version` this crate understands.
+pub const COMMAND_METADATA_FORMAT_VERSION: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct ArgDef {
@@ -43,6 +67,9 @@ pub struct CommandMeta {
     pub usage: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub aliases: Vec<String>,
+    /// Aliases that dispatch like `aliases` but are not shown in help.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_aliases: Vec<String>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub version: String,
     #[serde(default)]
@@ -91,6 +118,62 @@ pub struct ArgSchema {
     pub requires: Vec<String>,
     #[serde(default)]
     pub hidden: bool,
+    /// Registry command that prints completion candidates for this arg's
+    /// value, one per line, given the partial word.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complete_command: Option<String>,
+    /// Set when the arg was spliced from a shared template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared: Option<SharedArgRef>,
+    /// [`ArgGroup`] this arg belongs to. A group the command does not list in
+    /// `groups` is neither required nor multiple.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Take the next token as the value even when it looks like a flag
+    /// (`--filter -foo`). Otherwise a known flag there is a missing value.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_hyphen_values: bool,
+    /// A boolean flag that counts its occurrences (`-vvv`); shown as `-v...`
+    /// in help and allowed to repeat even when not `multiple`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub countable: bool,
+    /// On the last positional: take every remaining positional token
+    /// (`[FILES]...`) instead of leaving extras to the plugin.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub var_arg: bool,
+}
+
+/// A named set of args validated together, e.g. exactly one of
+/// `--json`/`--yaml`/`--toml`. Members name the group in [`ArgSchema::group`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArgGroup {
+    pub name: String,
+    /// At least one member must be given in argv.
+    #[serde(default)]
+    pub required: bool,
+    /// More than one member may be given.
+    #[serde(default)]
+    pub multiple: bool,
+}
+
+impl ArgGroup {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -103,6 +186,9 @@ pub struct CommandSchema {
     pub usage: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub aliases: Vec<String>,
+    /// Aliases that dispatch like `aliases` but are not shown in help.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_aliases: Vec<String>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub version: String,
     #[serde(default)]
@@ -113,6 +199,17 @@ pub struct CommandSchema {
     pub examples: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub args: Vec<ArgSchema>,
+    /// Groups referenced by [`ArgSchema::group`]. Not mirrored in
+    /// `command-meta`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<ArgGroup>,
+    /// Expand `@file` tokens in argv into the file's lines before parsing.
+    #[serde(default)]
+    pub allow_arg_files: bool,
+    /// Nested commands (`remote add`), selected by the first positional in
+    /// argv. Not mirrored in `command-meta`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subcommands: Vec<CommandSchema>,
 }
 
 impl CommandSchema {
@@ -122,6 +219,7 @@ impl CommandSchema {
             summary: meta.summary.clone(),
             usage: meta.usage.clone(),
             aliases: meta.aliases.clone(),
+            hidden_aliases: meta.hidden_aliases.clone(),
             version: meta.version.clone(),
             hidden: meta.hidden,
             description: meta.description.clone(),
@@ -146,8 +244,17 @@ impl CommandSchema {
                     conflicts_with: Vec::new(),
                     requires: Vec::new(),
                     hidden: false,
+                    complete_command: None,
+                    shared: None,
+                    group: None,
+                    allow_hyphen_values: false,
+                    countable: false,
+                    var_arg: false,
                 })
                 .collect(),
+            groups: Vec::new(),
+            allow_arg_files: false,
+            subcommands: Vec::new(),
         }
     }
 }
@@ -160,6 +267,68 @@ pub struct CommandMetadataV1 {
     pub command_meta: CommandMeta,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub command_schema: Option<CommandSchema>,
+    /// Executable examples checked by `wacli verify`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub self_tests: Vec<SelfTest>,
+    /// Version of the wacli-cdk that embedded this payload (`wacli build --report`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cdk_version: Option<String>,
+}
+
+/// An invocation with expected results, run by `wacli verify`.
+///
+/// `cmd` is written like an example (`show hello --format table`): it is
+/// split shell-style and a leading command name or alias is replaced by the
+/// full invocation, including any group prefix.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SelfTest {
+    pub cmd: String,
+    /// Expected stdout, compared ignoring trailing newlines. A `contains:`
+    /// prefix matches a substring instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_stdout: Option<String>,
+    /// Expected exit code: 2 for rejected arguments, 127 for an unknown
+    /// command, the command's own code otherwise (1 for plain failures).
+    #[serde(default)]
+    pub expect_exit: u32,
+}
+
+impl SelfTest {
+    pub fn new(cmd: impl Into<String>) -> Self {
+        Self {
+            cmd: cmd.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn expect_stdout(mut self, stdout: impl Into<String>) -> Self {
+        self.expect_stdout = Some(stdout.into());
+        self
+    }
+
+    pub fn expect_exit(mut self, code: u32) -> Self {
+        self.expect_exit = code;
+        self
+    }
+
+    /// Check captured stdout against [`SelfTest::expect_stdout`]; on a
+    /// mismatch, returns a one-line reason.
+    pub fn check_stdout(&self, stdout: &str) -> Result<(), String> {
+        let Some(expected) = &self.expect_stdout else {
+            return Ok(());
+        };
+        match expected.strip_prefix("contains:") {
+            Some(needle) if stdout.contains(needle) => Ok(()),
+            Some(needle) => Err(format!("stdout does not contain {needle:?}")),
+            None if stdout.trim_end_matches('\n') == expected.trim_end_matches('\n') => Ok(()),
+            None => Err(format!(
+                "expected stdout {:?}, got {:?}",
+                expected.trim_end_matches('\n'),
+                stdout.trim_end_matches('\n')
+            )),
+        }
+    }
 }
 
 impl CommandMetadataV1 {
@@ -168,6 +337,8 @@ impl CommandMetadataV1 {
             format_version: 1,
             command_meta,
             command_schema,
+            self_tests: Vec::new(),
+            cdk_version: None,
         }
     }
 
@@ -178,3 +349,257 @@ impl CommandMetadataV1 {
         serde_json::to_vec(self).unwrap_or_default()
     }
 }
+
+/// JSON payload embedded into the `COMMAND_METADATA_SECTION_V2` custom section.
+///
+/// A superset of [`CommandMetadataV1`]: any V1 payload also reads as V2.
+/// Unknown fields are not rejected, so a payload from a newer format version
+/// still reads; its extra top-level fields land in `unknown`, and
+/// [`unknown_fields`] names the ones at any depth for the reader to report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommandMetadataV2 {
+    pub format_version: u32,
+    pub command_meta: CommandMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_schema: Option<CommandSchema>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub self_tests: Vec<SelfTest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cdk_version: Option<String>,
+    /// Top-level fields this crate does not know.
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>,
+}
+
+impl CommandMetadataV2 {
+    /// Encode as JSON bytes for embedding.
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+}
+
+impl From<CommandMetadataV1> for CommandMetadataV2 {
+    fn from(v1: CommandMetadataV1) -> Self {
+        Self {
+            format_version: 2,
+            command_meta: v1.command_meta,
+            command_schema: v1.command_schema,
+            self_tests: v1.self_tests,
+            cdk_version: v1.cdk_version,
+            unknown: BTreeMap::new(),
+        }
+    }
+}
+
+/// Downgrade to the V1 model, dropping `unknown`.
+impl From<CommandMetadataV2> for CommandMetadataV1 {
+    fn from(v2: CommandMetadataV2) -> Self {
+        Self {
+            format_version: 1,
+            command_meta: v2.command_meta,
+            command_schema: v2.command_schema,
+            self_tests: v2.self_tests,
+            cdk_version: v2.cdk_version,
+        }
+    }
+}
+
+/// Custom section name containing a pipe's JSON-encoded metadata.
+///
+/// The payload is a JSON object `PipeMetadataV1`. Pipes without it accept
+/// any options, and are listed without a summary or types.
+pub const PIPE_METADATA_SECTION: &str = "wacli:cli/pipe-metadata@1";
+
+/// JSON payload embedded into the `PIPE_METADATA_SECTION` custom section.
+///
+/// Each option string passed to `process()` is `key=value` or a bare `key`
+/// (a flag), matched against the `long` names of `options`. The descriptive
+/// fields mirror the pipe's `meta()` so hosts can list pipes without
+/// instantiating them; payloads carrying only `options` remain valid.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct PipeMetadataV1 {
+    pub format_version: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<ArgSchema>,
+}
+
+impl PipeMetadataV1 {
+    pub fn new(options: Vec<ArgSchema>) -> Self {
+        Self {
+            format_version: 1,
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Encode as JSON bytes for embedding.
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+}
+
+/// Custom section name that `wacli build` appends to a composed CLI.
+///
+/// The payload is a JSON object `AppProvenanceV1`. It lives at the top level
+/// of the composed component, next to (not inside) the embedded commands, and
+/// is meant for external tooling; the CLI itself never reads it.
+pub const APP_PROVENANCE_SECTION: &str = "wacli:cli/app-provenance@1";
+
+/// JSON payload embedded into the `APP_PROVENANCE_SECTION` custom section.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct AppProvenanceV1 {
+    pub format_version: u32,
+    /// App package name (`example:my-cli`).
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    /// Version of the wacli that composed the app.
+    pub wacli_version: String,
+    /// Build profile selected with `wacli build --profile`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<ProvenanceCommand>,
+}
+
+/// A command composed into the app.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProvenanceCommand {
+    /// Registry name (`db/seed` for grouped commands).
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    /// Manifest digest pinned in `wacli.lock`; only set for commands pulled
+    /// from a registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+}
+
+impl AppProvenanceV1 {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            format_version: 1,
+            name: name.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Encode as JSON bytes for embedding.
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// Parse the payload of the section, checking its format version.
+    pub fn from_json(raw: &[u8]) -> Result<Self, String> {
+        let meta: Self = serde_json::from_slice(raw)
+            .map_err(|e| format!("failed to parse app provenance JSON: {e}"))?;
+        if meta.format_version != 1 {
+            return Err(format!(
+                "unsupported app provenance format-version {} (expected 1)",
+                meta.format_version
+            ));
+        }
+        Ok(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_test_stdout_is_exact_or_contains() {
+        let exact = SelfTest::new("show hi").expect_stdout("HI\n");
+        assert_eq!(exact.check_stdout("HI"), Ok(()));
+        assert_eq!(exact.check_stdout("HI\n\n"), Ok(()));
+        assert_eq!(
+            exact.check_stdout("HI there\n"),
+            Err(r#"expected stdout "HI", got "HI there""#.to_string())
+        );
+
+        let contains = SelfTest::new("show hi").expect_stdout("contains:HI");
+        assert_eq!(contains.check_stdout("say HI!\n"), Ok(()));
+        assert_eq!(
+            contains.check_stdout("hi"),
+            Err(r#"stdout does not contain "HI""#.to_string())
+        );
+
+        assert_eq!(SelfTest::new("show").check_stdout("anything"), Ok(()));
+    }
+
+    #[test]
+    fn v2_reads_v1_payloads_and_keeps_unknown_fields() {
+        let v1 = CommandMetadataV1::new(
+            CommandMeta {
+                name: "show".into(),
+                ..Default::default()
+            },
+            None,
+        );
+        let v2: CommandMetadataV2 = serde_json::from_slice(&v1.to_json_bytes()).unwrap();
+        assert_eq!(v2.format_version, 1);
+        assert!(v2.unknown.is_empty());
+
+        let json = r#"{"format-version":3,"command-meta":{"name":"show","value-types":["path"]},
+            "value-types":{"path":"string"}}"#;
+        let v2: CommandMetadataV2 = serde_json::from_str(json).unwrap();
+        assert_eq!(v2.unknown.keys().collect::<Vec<_>>(), ["value-types"]);
+        let round_trip: serde_json::Value = serde_json::from_slice(&v2.to_json_bytes()).unwrap();
+        assert_eq!(round_trip["value-types"]["path"], "string");
+
+        let v1 = CommandMetadataV1::from(v2);
+        assert_eq!(v1.format_version, 1);
+        assert_eq!(v1.command_meta.name, "show");
+    }
+
+    #[test]
+    fn app_provenance_round_trips_through_json() {
+        let mut meta = AppProvenanceV1::new("example:my-cli", "0.1.0");
+        meta.wacli_version = "1.2.3".to_string();
+        meta.profile = Some("edge".to_string());
+        meta.commands = vec![
+            ProvenanceCommand {
+                name: "db/seed".to_string(),
+                version: "2.0.0".to_string(),
+                digest: Some("sha256:abc".to_string()),
+            },
+            ProvenanceCommand {
+                name: "greet".to_string(),
+                ..Default::default()
+            },
+        ];
+        let json = String::from_utf8(meta.to_json_bytes()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"format-version":1,"name":"example:my-cli","version":"0.1.0","wacli-version":"1.2.3","profile":"edge","commands":[{"name":"db/seed","version":"2.0.0","digest":"sha256:abc"},{"name":"greet"}]}"#
+        );
+
+        assert_eq!(AppProvenanceV1::from_json(json.as_bytes()), Ok(meta));
+    }
+
+    #[test]
+    fn app_provenance_rejects_bad_input() {
+        assert!(
+            AppProvenanceV1::from_json(b"{}")
+                .unwrap_err()
+                .starts_with("failed to parse app provenance JSON: ")
+        );
+        assert_eq!(
+            AppProvenanceV1::from_json(br#"{"format-version":2,"name":"a","wacli-version":"9"}"#),
+            Err("unsupported app provenance format-version 2 (expected 1)".to_string())
+        );
+    }
+}
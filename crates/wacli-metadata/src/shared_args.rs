@@ -0,0 +1,134 @@
+//! Shared arg templates (`argdefs.json`).
+//!
+//! A project can define args used by several commands once, as a JSON object
+//! mapping template names to arg fields (the kebab-case keys of
+//! [`ArgSchema`](crate::ArgSchema); `name` defaults to the template name):
+//!
+//! ```json
+//! {
+//!   "output": { "short": "-o", "long": "--output", "value-name": "FILE",
+//!               "help": "Write the result to FILE" }
+//! }
+//! ```
+//!
+//! `declare_command_metadata!` splices a template into an arg declared as
+//! `{ use: "output" }` and records a [`SharedArgRef`] on it, so `wacli build`
+//! can tell when commands were built against different versions of the same
+//! template.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Which shared template an arg was spliced from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SharedArgRef {
+    pub name: String,
+    /// [`SharedArgs::digest`] of the template, before per-command overrides.
+    pub digest: String,
+}
+
+/// The templates of one `argdefs.json`.
+#[derive(Debug, Clone, Default)]
+pub struct SharedArgs {
+    templates: BTreeMap<String, Map<String, Value>>,
+}
+
+impl SharedArgs {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| format!("invalid JSON: {e}"))?;
+        let Value::Object(entries) = value else {
+            return Err("expected an object mapping template names to args".to_string());
+        };
+        let mut templates = BTreeMap::new();
+        for (name, fields) in entries {
+            let Value::Object(fields) = fields else {
+                return Err(format!("template '{name}' must be an object"));
+            };
+            templates.insert(name, fields);
+        }
+        Ok(Self { templates })
+    }
+
+    /// The template's fields (kebab-case keys, as written).
+    pub fn get(&self, name: &str) -> Option<&Map<String, Value>> {
+        self.templates.get(name)
+    }
+
+    /// Template names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// A stable hash of the template's content, independent of key order and
+    /// formatting in the file.
+    pub fn digest(&self, name: &str) -> Option<String> {
+        let fields = self.templates.get(name)?;
+        let sorted: BTreeMap<&String, &Value> = fields.iter().collect();
+        let bytes = serde_json::to_vec(&sorted).expect("template fields serialize");
+        Some(format!("fnv1a64:{:016x}", fnv1a64(&bytes)))
+    }
+
+    /// The [`SharedArgRef`] to record for an arg spliced from `name`.
+    pub fn reference(&self, name: &str) -> Option<SharedArgRef> {
+        Some(SharedArgRef {
+            name: name.to_string(),
+            digest: self.digest(name)?,
+        })
+    }
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_templates() {
+        let shared = SharedArgs::from_json(
+            r#"{ "output": { "short": "-o", "long": "--output" }, "verbose": {} }"#,
+        )
+        .unwrap();
+        assert_eq!(shared.names().collect::<Vec<_>>(), ["output", "verbose"]);
+        assert_eq!(shared.get("output").unwrap()["short"], "-o");
+        assert!(shared.get("nope").is_none());
+        assert!(shared.reference("nope").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert_eq!(
+            SharedArgs::from_json("[]").unwrap_err(),
+            "expected an object mapping template names to args"
+        );
+        assert_eq!(
+            SharedArgs::from_json(r#"{ "output": "-o" }"#).unwrap_err(),
+            "template 'output' must be an object"
+        );
+        assert!(
+            SharedArgs::from_json("{")
+                .unwrap_err()
+                .starts_with("invalid JSON: ")
+        );
+    }
+
+    #[test]
+    fn digest_ignores_key_order_but_not_content() {
+        let digest = |json: &str| SharedArgs::from_json(json).unwrap().digest("o").unwrap();
+        let a = digest(r#"{ "o": { "short": "-o", "help": "Output" } }"#);
+        let b = digest(r#"{"o":{"help":"Output","short":"-o"}}"#);
+        let c = digest(r#"{ "o": { "short": "-o", "help": "Output file" } }"#);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(
+            a.starts_with("fnv1a64:") && a.len() == "fnv1a64:".len() + 16,
+            "{a}"
+        );
+    }
+}
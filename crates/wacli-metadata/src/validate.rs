@@ -0,0 +1,721 @@
+//! Schema-consistency checks for command metadata payloads.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{ArgSchema, CommandMetadataV1, CommandSchema};
+
+/// Every problem found by [`CommandMetadataV1::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Command name as declared in the payload.
+    pub command: String,
+    pub issues: Vec<String>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid command metadata for '{}':", self.command)?;
+        for issue in &self.issues {
+            write!(f, "\n  - {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl CommandMetadataV1 {
+    /// Run the full set of schema-consistency checks.
+    ///
+    /// Checks the command schema when present (otherwise the schema implied
+    /// by `command-meta`), and that `command-meta` agrees with it. All issues
+    /// are collected rather than stopping at the first one.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut issues = Vec::new();
+        if self.format_version != 1 {
+            issues.push(format!(
+                "unsupported format-version {} (expected 1)",
+                self.format_version
+            ));
+        }
+
+        let derived;
+        let schema = match &self.command_schema {
+            Some(schema) => {
+                check_meta_matches_schema(self, schema, &mut issues);
+                schema
+            }
+            None => {
+                derived = CommandSchema::from_meta(&self.command_meta);
+                &derived
+            }
+        };
+        check_schema(schema, &mut issues);
+        for (i, test) in self.self_tests.iter().enumerate() {
+            if test.cmd.trim().is_empty() {
+                issues.push(format!("self-test #{} has an empty cmd", i + 1));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                command: self.command_meta.name.clone(),
+                issues,
+            })
+        }
+    }
+}
+
+/// Problems with app-level global args (`build.globalArgs`), which every
+/// command accepts as options: the per-arg checks of
+/// [`CommandMetadataV1::validate`], plus that each is an optional flag that
+/// does not shadow `-h/--help` or `-V/--version`.
+pub fn global_arg_issues(args: &[ArgSchema]) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    let mut flags: HashMap<&str, &str> = HashMap::new();
+    let mut vars: HashMap<String, &str> = HashMap::new();
+    for (i, arg) in args.iter().enumerate() {
+        if arg.name.trim().is_empty() {
+            issues.push(format!("arg #{} has an empty name", i + 1));
+            continue;
+        }
+        if names.insert(&arg.name, i).is_some() {
+            issues.push(format!("duplicate arg name '{}'", arg.name));
+        }
+        check_handoff_var("WACLI_GLOBAL_", arg, &mut vars, &mut issues);
+        check_flags(arg, &mut flags, &mut issues);
+        check_values(arg, &mut issues);
+        if arg.short.is_none() && arg.long.is_none() {
+            issues.push(format!("'{}' needs a short or long flag", arg.name));
+        }
+        for flag in [arg.short.as_deref(), arg.long.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            if matches!(flag, "-h" | "--help" | "-V" | "--version") {
+                issues.push(format!("'{}': flag '{flag}' is built in", arg.name));
+            }
+        }
+        if arg.required {
+            issues.push(format!("'{}' cannot be required", arg.name));
+        }
+        if arg.group.is_some() {
+            issues.push(format!("'{}' cannot join a group", arg.name));
+        }
+    }
+    for arg in args {
+        for (relation, targets) in [
+            ("conflicts-with", &arg.conflicts_with),
+            ("requires", &arg.requires),
+        ] {
+            for target in targets {
+                if !names.contains_key(target.as_str()) {
+                    issues.push(format!(
+                        "'{}' {relation} '{target}', which is not a global arg",
+                        arg.name
+                    ));
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn check_meta_matches_schema(
+    payload: &CommandMetadataV1,
+    schema: &CommandSchema,
+    issues: &mut Vec<String>,
+) {
+    let meta = &payload.command_meta;
+    if meta.name != schema.name {
+        issues.push(format!(
+            "command-schema name '{}' does not match command-meta name '{}'",
+            schema.name, meta.name
+        ));
+    }
+    for arg in &schema.args {
+        if !meta.args.iter().any(|a| a.name == arg.name) {
+            issues.push(format!(
+                "arg '{}' is in command-schema but not in command-meta",
+                arg.name
+            ));
+        }
+    }
+    for arg in &meta.args {
+        if !schema.args.iter().any(|a| a.name == arg.name) {
+            issues.push(format!(
+                "arg '{}' is in command-meta but not in command-schema",
+                arg.name
+            ));
+        }
+    }
+}
+
+fn check_schema(schema: &CommandSchema, issues: &mut Vec<String>) {
+    if !is_valid_command_name(&schema.name) {
+        issues.push(format!(
+            "invalid command name '{}' (expected [a-z][a-z0-9-]*, not ending in '-')",
+            schema.name
+        ));
+    }
+    let aliases: Vec<&String> = schema
+        .aliases
+        .iter()
+        .chain(&schema.hidden_aliases)
+        .collect();
+    for (i, alias) in aliases.iter().enumerate() {
+        if !is_valid_command_name(alias) {
+            issues.push(format!("invalid alias '{alias}'"));
+        } else if **alias == schema.name {
+            issues.push(format!("alias '{alias}' repeats the command name"));
+        } else if aliases[..i].contains(alias) {
+            issues.push(format!("duplicate alias '{alias}'"));
+        }
+    }
+
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    let mut flags: HashMap<&str, &str> = HashMap::new();
+    let mut vars: HashMap<String, &str> = HashMap::new();
+    for (i, arg) in schema.args.iter().enumerate() {
+        if arg.name.trim().is_empty() {
+            issues.push(format!("arg #{} has an empty name", i + 1));
+            continue;
+        }
+        if names.insert(&arg.name, i).is_some() {
+            issues.push(format!("duplicate arg name '{}'", arg.name));
+        }
+        check_handoff_var("WACLI_CONFIG_", arg, &mut vars, issues);
+        check_flags(arg, &mut flags, issues);
+        check_values(arg, issues);
+    }
+
+    for arg in &schema.args {
+        for (relation, targets) in [
+            ("conflicts-with", &arg.conflicts_with),
+            ("requires", &arg.requires),
+        ] {
+            for target in targets {
+                if *target == arg.name {
+                    issues.push(format!("'{}' {relation} itself", arg.name));
+                } else if !names.contains_key(target.as_str()) {
+                    issues.push(format!("'{}' {relation} unknown arg '{target}'", arg.name));
+                }
+            }
+        }
+    }
+
+    for (i, group) in schema.groups.iter().enumerate() {
+        if group.name.trim().is_empty() {
+            issues.push(format!("group #{} has an empty name", i + 1));
+        } else if schema.groups[..i].iter().any(|g| g.name == group.name) {
+            issues.push(format!("duplicate group name '{}'", group.name));
+        } else if !schema
+            .args
+            .iter()
+            .any(|a| a.group.as_deref() == Some(group.name.as_str()))
+        {
+            issues.push(format!("group '{}' has no args", group.name));
+        }
+    }
+
+    let mut sub_names: Vec<&str> = Vec::new();
+    for sub in &schema.subcommands {
+        for name in std::iter::once(&sub.name)
+            .chain(&sub.aliases)
+            .chain(&sub.hidden_aliases)
+        {
+            if sub_names.contains(&name.as_str()) {
+                issues.push(format!("duplicate subcommand name '{name}'"));
+            }
+            sub_names.push(name);
+        }
+        let mut sub_issues = Vec::new();
+        check_schema(sub, &mut sub_issues);
+        issues.extend(
+            sub_issues
+                .into_iter()
+                .map(|issue| format!("subcommand '{}': {issue}", sub.name)),
+        );
+    }
+}
+
+fn check_flags<'a>(
+    arg: &'a ArgSchema,
+    seen: &mut HashMap<&'a str, &'a str>,
+    issues: &mut Vec<String>,
+) {
+    if let Some(short) = arg.short.as_deref() {
+        let rest = short.strip_prefix('-').unwrap_or("");
+        let mut chars = rest.chars();
+        let ok = matches!((chars.next(), chars.next()), (Some(c), None) if c != '-' && !c.is_whitespace());
+        if !ok {
+            issues.push(format!(
+                "arg '{}': short flag '{short}' must be a single character like '-v'",
+                arg.name
+            ));
+        }
+    }
+    if let Some(long) = arg.long.as_deref() {
+        let rest = long.strip_prefix("--").unwrap_or("");
+        let ok = !rest.is_empty()
+            && !rest.starts_with('-')
+            && !rest.contains(|c: char| c == '=' || c.is_whitespace());
+        if !ok {
+            issues.push(format!(
+                "arg '{}': long flag '{long}' must look like '--name'",
+                arg.name
+            ));
+        }
+    }
+    for flag in [arg.short.as_deref(), arg.long.as_deref()]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(other) = seen.insert(flag, &arg.name) {
+            issues.push(format!(
+                "flag '{flag}' is declared by both '{other}' and '{}'",
+                arg.name
+            ));
+        }
+    }
+}
+
+/// Core hands arg values to commands in environment variables named
+/// `prefix` and the arg name upper-cased, with non-alphanumerics mapped to
+/// `_` (`dry-run` => `WACLI_CONFIG_DRY_RUN`), so two names must not map to
+/// the same one.
+fn check_handoff_var<'a>(
+    prefix: &str,
+    arg: &'a ArgSchema,
+    seen: &mut HashMap<String, &'a str>,
+    issues: &mut Vec<String>,
+) {
+    let mut var = String::from(prefix);
+    var.extend(arg.name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() {
+            c.to_ascii_uppercase()
+        } else {
+            '_'
+        }
+    }));
+    if let Some(other) = seen.get(&var)
+        && *other != arg.name
+    {
+        issues.push(format!(
+            "args '{other}' and '{}' are both handed to commands as {var}",
+            arg.name
+        ));
+        return;
+    }
+    seen.insert(var, &arg.name);
+}
+
+/// `value-type`s the parser understands (`string` is accepted as-is).
+const VALUE_TYPES: &[&str] = &["string", "int", "uint", "float", "bool", "path"];
+
+fn check_values(arg: &ArgSchema, issues: &mut Vec<String>) {
+    if arg.countable && arg.takes_value {
+        issues.push(format!("arg '{}' is countable but takes a value", arg.name));
+    }
+    if !arg.takes_value {
+        for (field, set) in [
+            ("default-value", arg.default_value.is_some()),
+            ("possible-values", !arg.possible_values.is_empty()),
+            ("value-type", arg.value_type.is_some()),
+            ("complete-command", arg.complete_command.is_some()),
+        ] {
+            if set {
+                issues.push(format!(
+                    "arg '{}' does not take a value but declares {field}",
+                    arg.name
+                ));
+            }
+        }
+        return;
+    }
+    if let Some(value_type) = &arg.value_type
+        && !VALUE_TYPES.contains(&value_type.as_str())
+    {
+        issues.push(format!(
+            "arg '{}' has unknown value-type '{value_type}' (expected one of: {})",
+            arg.name,
+            VALUE_TYPES.join(", ")
+        ));
+    }
+    if arg.value_type.as_deref() == Some("bool") && !arg.possible_values.is_empty() {
+        issues.push(format!(
+            "arg '{}' has value-type bool and cannot declare possible-values",
+            arg.name
+        ));
+    }
+    if let Some(default) = &arg.default_value
+        && !arg.possible_values.is_empty()
+        && !arg.possible_values.contains(default)
+    {
+        issues.push(format!(
+            "arg '{}': default-value '{default}' is not one of possible-values [{}]",
+            arg.name,
+            arg.possible_values.join(", ")
+        ));
+    }
+}
+
+/// `[a-z][a-z0-9-]*`, not ending in `-` (same rule as `wacli build`).
+fn is_valid_command_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.ends_with('-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::global_arg_issues;
+    use crate::{
+        ArgGroup, ArgSchemaBuilder, CommandMeta, CommandMetaBuilder, CommandMetadataV1, SelfTest,
+    };
+
+    fn issues(payload: &CommandMetadataV1) -> Vec<String> {
+        payload
+            .validate()
+            .expect_err("expected validation to fail")
+            .issues
+    }
+
+    fn one_arg(arg: ArgSchemaBuilder) -> Vec<String> {
+        issues(&CommandMetaBuilder::new("cmd").arg(arg).build())
+    }
+
+    #[test]
+    fn valid_payload_passes() {
+        let payload = CommandMetaBuilder::new("show")
+            .alias("s")
+            .arg(
+                ArgSchemaBuilder::new("format")
+                    .short("f")
+                    .long("format")
+                    .possible_values(["plain", "json"])
+                    .default_value("json")
+                    .conflicts_with("raw"),
+            )
+            .arg(ArgSchemaBuilder::new("raw").long("raw").requires("text"))
+            .arg(ArgSchemaBuilder::new("text"))
+            .build();
+        assert_eq!(payload.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bad_names_and_aliases() {
+        let payload = CommandMetaBuilder::new("Show-")
+            .alias("Show-")
+            .alias("x")
+            .alias("x")
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "invalid command name 'Show-' (expected [a-z][a-z0-9-]*, not ending in '-')",
+                "invalid alias 'Show-'",
+                "duplicate alias 'x'",
+            ]
+        );
+
+        let payload = CommandMetaBuilder::new("show").alias("show").build();
+        assert_eq!(
+            issues(&payload),
+            vec!["alias 'show' repeats the command name"]
+        );
+
+        let payload = CommandMetaBuilder::new("show")
+            .alias("s")
+            .hidden_alias("s")
+            .hidden_alias("X")
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec!["duplicate alias 's'", "invalid alias 'X'"]
+        );
+
+        let mut payload = CommandMetaBuilder::new("show").build();
+        payload.format_version = 2;
+        assert_eq!(
+            issues(&payload),
+            vec!["unsupported format-version 2 (expected 1)"]
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_args_and_flags() {
+        let payload = CommandMetaBuilder::new("cmd")
+            .arg(ArgSchemaBuilder::new("a").short("v"))
+            .arg(ArgSchemaBuilder::new("a").long("all"))
+            .arg(ArgSchemaBuilder::new("b").short("v").long("all"))
+            .arg(ArgSchemaBuilder::new(" "))
+            .arg(ArgSchemaBuilder::new("dry-run").long("dry-run"))
+            .arg(ArgSchemaBuilder::new("dry_run").long("dry_run"))
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "duplicate arg name 'a'",
+                "flag '-v' is declared by both 'a' and 'b'",
+                "flag '--all' is declared by both 'a' and 'b'",
+                "arg #4 has an empty name",
+                "args 'dry-run' and 'dry_run' are both handed to commands as WACLI_CONFIG_DRY_RUN",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_flags() {
+        assert_eq!(
+            one_arg(ArgSchemaBuilder::new("a").short("vv")),
+            vec!["arg 'a': short flag '-vv' must be a single character like '-v'"]
+        );
+        assert_eq!(
+            one_arg(ArgSchemaBuilder::new("a").short("--")),
+            vec!["arg 'a': short flag '--' must be a single character like '-v'"]
+        );
+        assert_eq!(
+            one_arg(ArgSchemaBuilder::new("a").long("out=x")),
+            vec!["arg 'a': long flag '--out=x' must look like '--name'"]
+        );
+        assert_eq!(
+            one_arg(ArgSchemaBuilder::new("a").long("-x")),
+            vec!["arg 'a': long flag '-x' must look like '--name'"]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_or_self_relation_targets() {
+        let payload = CommandMetaBuilder::new("cmd")
+            .arg(
+                ArgSchemaBuilder::new("a")
+                    .long("a")
+                    .conflicts_with("nope")
+                    .requires("a"),
+            )
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "'a' conflicts-with unknown arg 'nope'",
+                "'a' requires itself"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_and_duplicate_groups() {
+        let payload = CommandMetaBuilder::new("cmd")
+            .arg(ArgSchemaBuilder::new("json").long("json").group("format"))
+            .arg(ArgSchemaBuilder::new("yaml").long("yaml").group("format"))
+            .arg(ArgSchemaBuilder::new("all").long("all").group("select"))
+            .group(ArgGroup::new("format").required(true))
+            .build();
+        assert_eq!(payload.validate(), Ok(()));
+
+        let payload = CommandMetaBuilder::new("cmd")
+            .arg(ArgSchemaBuilder::new("json").long("json").group("format"))
+            .group(ArgGroup::new("format"))
+            .group(ArgGroup::new("format").multiple(true))
+            .group(ArgGroup::new("sink"))
+            .group(ArgGroup::new(" "))
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "duplicate group name 'format'",
+                "group 'sink' has no args",
+                "group #4 has an empty name",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_default_outside_possible_values() {
+        assert_eq!(
+            one_arg(
+                ArgSchemaBuilder::new("mode")
+                    .long("mode")
+                    .possible_values(["fast", "slow"])
+                    .default_value("medium")
+            ),
+            vec!["arg 'mode': default-value 'medium' is not one of possible-values [fast, slow]"]
+        );
+    }
+
+    #[test]
+    fn rejects_possible_values_on_bool_args() {
+        assert_eq!(
+            one_arg(
+                ArgSchemaBuilder::new("cache")
+                    .long("cache")
+                    .value_type("bool")
+                    .possible_values(["yes", "no"])
+            ),
+            vec!["arg 'cache' has value-type bool and cannot declare possible-values"]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_value_types() {
+        assert_eq!(
+            one_arg(
+                ArgSchemaBuilder::new("count")
+                    .long("count")
+                    .value_type("integer")
+            ),
+            vec![
+                "arg 'count' has unknown value-type 'integer' (expected one of: string, int, uint, float, bool, path)"
+            ]
+        );
+        let uint = ArgSchemaBuilder::new("count")
+            .long("count")
+            .value_type("uint");
+        assert!(
+            CommandMetaBuilder::new("cmd")
+                .arg(uint)
+                .build()
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_values_on_flags_without_values() {
+        assert_eq!(
+            one_arg(
+                ArgSchemaBuilder::new("quiet")
+                    .long("quiet")
+                    .default_value("1")
+                    .takes_value(false)
+            ),
+            vec!["arg 'quiet' does not take a value but declares default-value"]
+        );
+        assert_eq!(
+            one_arg(
+                ArgSchemaBuilder::new("level")
+                    .long("level")
+                    .takes_value(true)
+                    .countable(true)
+            ),
+            vec!["arg 'level' is countable but takes a value"]
+        );
+    }
+
+    #[test]
+    fn rejects_meta_that_disagrees_with_schema() {
+        let mut payload = CommandMetaBuilder::new("cmd")
+            .arg(ArgSchemaBuilder::new("a"))
+            .build();
+        payload.command_meta = CommandMeta {
+            name: "other".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "command-schema name 'cmd' does not match command-meta name 'other'",
+                "arg 'a' is in command-schema but not in command-meta",
+            ]
+        );
+        let err = payload.validate().unwrap_err();
+        assert!(
+            err.to_string().starts_with(
+                "invalid command metadata for 'other':\n  - command-schema name 'cmd'"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn checks_subcommands_recursively() {
+        let payload = CommandMetaBuilder::new("remote")
+            .subcommand(
+                CommandMetaBuilder::new("add")
+                    .alias("new")
+                    .arg(ArgSchemaBuilder::new("a").short("x"))
+                    .arg(ArgSchemaBuilder::new("b").short("x"))
+                    .subcommand(CommandMetaBuilder::new("Bad")),
+            )
+            .subcommand(CommandMetaBuilder::new("new"))
+            .build();
+        assert_eq!(
+            issues(&payload),
+            vec![
+                "subcommand 'add': flag '-x' is declared by both 'a' and 'b'",
+                "subcommand 'add': subcommand 'Bad': invalid command name 'Bad' (expected [a-z][a-z0-9-]*, not ending in '-')",
+                "duplicate subcommand name 'new'",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_self_tests_without_a_command() {
+        let payload = CommandMetaBuilder::new("cmd")
+            .self_test(SelfTest::new("cmd a").expect_stdout("a"))
+            .self_test(SelfTest::new(" ").expect_exit(1))
+            .build();
+        assert_eq!(issues(&payload), vec!["self-test #2 has an empty cmd"]);
+    }
+
+    #[test]
+    fn meta_only_payload_is_checked_too() {
+        let mut payload = CommandMetaBuilder::new("cmd")
+            .arg(ArgSchemaBuilder::new("a").short("x"))
+            .arg(ArgSchemaBuilder::new("b").short("x"))
+            .build();
+        payload.command_schema = None;
+        assert_eq!(
+            issues(&payload),
+            vec!["flag '-x' is declared by both 'a' and 'b'"]
+        );
+    }
+
+    #[test]
+    fn global_args_must_be_optional_flags() {
+        let valid = [
+            ArgSchemaBuilder::new("verbose").short("v").long("verbose"),
+            ArgSchemaBuilder::new("color")
+                .long("color")
+                .takes_value(true)
+                .possible_values(["auto", "always", "never"])
+                .conflicts_with("verbose"),
+        ]
+        .map(ArgSchemaBuilder::build);
+        assert_eq!(global_arg_issues(&valid), Vec::<String>::new());
+
+        let invalid = [
+            ArgSchemaBuilder::new("file"),
+            ArgSchemaBuilder::new("help").long("help"),
+            ArgSchemaBuilder::new("level")
+                .long("level")
+                .takes_value(true)
+                .required(true),
+            ArgSchemaBuilder::new("json")
+                .long("json")
+                .group("format")
+                .requires("file-name"),
+            ArgSchemaBuilder::new("no.color").long("no-color"),
+            ArgSchemaBuilder::new("no_color").long("no_color"),
+        ]
+        .map(ArgSchemaBuilder::build);
+        assert_eq!(
+            global_arg_issues(&invalid),
+            vec![
+                "'file' needs a short or long flag",
+                "'help': flag '--help' is built in",
+                "'level' cannot be required",
+                "'json' cannot join a group",
+                "args 'no.color' and 'no_color' are both handed to commands as \
+                 WACLI_GLOBAL_NO_COLOR",
+                "'json' requires 'file-name', which is not a global arg",
+            ]
+        );
+    }
+}
@@ -21,7 +21,8 @@ build_component() {
     exit 1
   fi
 
-  wasm-tools component embed "${root_dir}/wit/cli" "${wasm_in}" -o "${wasm_out}" --encoding utf8 --world "${world}"
+  wasm-tools component embed "${root_dir}/wit/cli" "${wasm_in}" -o "${wasm_out}" --encoding utf8 --world "${world}" \
+    --features cli-exit-with-code
   wasm-tools component new "${wasm_out}" -o "${component_out}"
   cp "${component_out}" "${root_component_out}"
 }
@@ -18,8 +18,9 @@ unset WASMTIME_DEBUG_BINDGEN
 build_dir="$root/target/${profile}/build"
 pipe_plugin_src="$(ls -t "$build_dir"/wasmtime-internal-component-macro-*/out/pipe-plugin*.rs 2>/dev/null | head -n 1 || true)"
 pipe_runtime_src="$(ls -t "$build_dir"/wasmtime-internal-component-macro-*/out/pipe-runtime-host*.rs 2>/dev/null | head -n 1 || true)"
+terminal_runtime_src="$(ls -t "$build_dir"/wasmtime-internal-component-macro-*/out/terminal-runtime-host*.rs 2>/dev/null | head -n 1 || true)"
 
-if [[ -z "$pipe_plugin_src" || -z "$pipe_runtime_src" ]]; then
+if [[ -z "$pipe_plugin_src" || -z "$pipe_runtime_src" || -z "$terminal_runtime_src" ]]; then
   echo "failed to locate generated bindings under $build_dir" >&2
   echo "try running: WASMTIME_DEBUG_BINDGEN=1 cargo check -p plugin-loader ${profile_flag}" >&2
   exit 1
@@ -52,10 +53,13 @@ write_with_header() {
 
 write_with_header "$pipe_plugin_src" "pipe-plugin" "$dest_dir/pipe_plugin.rs"
 write_with_header "$pipe_runtime_src" "pipe-runtime-host" "$dest_dir/pipe_runtime_host.rs"
+write_with_header "$terminal_runtime_src" "terminal-runtime-host" "$dest_dir/terminal_runtime_host.rs"
 
 echo "generated from:"
 echo "  $pipe_plugin_src"
 echo "  $pipe_runtime_src"
+echo "  $terminal_runtime_src"
 echo "updated:"
 echo "  $dest_dir/pipe_plugin.rs"
 echo "  $dest_dir/pipe_runtime_host.rs"
+echo "  $dest_dir/terminal_runtime_host.rs"
@@ -0,0 +1,56 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(envecho_meta, {
+    name: "envecho",
+    summary: "Print environment variables",
+    usage: "envecho [--prefix <PREFIX>] [--direct] [--cwd] [NAME]...",
+    args: [
+        {
+            name: "prefix",
+            long: "--prefix",
+            value_name: "PREFIX",
+            env: "ENVECHO_PREFIX",
+            help: "Printed before each line"
+        },
+        {
+            name: "direct",
+            long: "--direct",
+            help: "Look each name up with a host call instead of the context"
+        },
+        {
+            name: "cwd",
+            long: "--cwd",
+            help: "Print the working directory first"
+        }
+    ],
+});
+
+struct EnvEcho;
+
+impl Command for EnvEcho {
+    fn meta() -> CommandMeta {
+        envecho_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
+        let prefix = m.get("prefix").unwrap_or_default();
+        if m.get_flag("cwd") {
+            wacli_cdk::io::println(format!("{prefix}cwd={}", wacli_cdk::env::cwd()));
+        }
+        for name in m.rest() {
+            let value = if m.get_flag("direct") {
+                wacli_cdk::env::get(name)
+            } else {
+                ctx.env_var(name).map(str::to_string)
+            };
+            let value = value.as_deref().unwrap_or("<unset>");
+            wacli_cdk::io::println(format!("{prefix}{name}={value}"));
+        }
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(EnvEcho);
@@ -0,0 +1,32 @@
+use wacli_cdk::{Command, CommandError, CommandMeta, CommandResult};
+
+wacli_cdk::declare_command_metadata!(ext_meta, {
+    name: "ext",
+    summary: "Run installed extensions",
+    usage: "ext <EXTENSION> [ARGS]...",
+    hidden: true,
+});
+
+struct Ext;
+
+/// Extensions this fallback knows; any other name is reported unknown.
+const EXTENSIONS: [&str; 1] = ["hello"];
+
+impl Command for Ext {
+    fn meta() -> CommandMeta {
+        ext_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let Some((name, args)) = argv.split_first() else {
+            return Err(CommandError::InvalidArgs("missing extension name".into()));
+        };
+        if !EXTENSIONS.contains(&name.as_str()) {
+            return Err(CommandError::UnknownCommand(name.clone()));
+        }
+        wacli_cdk::io::println(&format!("ext {name}: {}", args.join("|")));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Ext);
@@ -1,9 +1,56 @@
-use wacli_cdk::{Command, CommandError, CommandMeta, CommandResult};
+use wacli_cdk::fs::FileKind;
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
 
 wacli_cdk::declare_command_metadata!(fileio_meta, {
     name: "fileio",
-    summary: "Read/write/list files",
-    usage: "fileio <read|write|list> ...",
+    summary: "Read/write/append/list/find/copy/move/stat/remove files",
+    usage: "fileio <COMMAND>",
+    subcommands: [
+        { name: "read", summary: "Print a file",
+          args: [{ name: "path", value_name: "PATH", required: true, help: "File to read" }] },
+        { name: "write", summary: "Write text to a file",
+          args: [
+            { name: "path", value_name: "PATH", required: true, help: "File to write" },
+            { name: "text", value_name: "TEXT", required: true, help: "Text to write" },
+          ] },
+        { name: "append", summary: "Append a line to a file",
+          args: [
+            { name: "path", value_name: "PATH", required: true, help: "File to append to" },
+            { name: "text", value_name: "TEXT", required: true, help: "Line to append" },
+          ] },
+        { name: "log", summary: "Write lines through one open writer",
+          args: [
+            { name: "path", value_name: "PATH", required: true, help: "File to write" },
+            { name: "line", value_name: "LINE", help: "Lines to write" },
+            { name: "append", long: "--append", help: "Append instead of truncating" },
+          ] },
+        { name: "list", summary: "List a directory", aliases: ["ls"],
+          args: [{ name: "path", value_name: "PATH", default_value: ".", help: "Directory to list" }] },
+        { name: "find", summary: "List everything below a directory",
+          args: [
+            { name: "path", value_name: "PATH", default_value: ".", help: "Directory to walk" },
+            { name: "depth", long: "--depth", value_name: "N", value_type: "uint", help: "Maximum depth" },
+          ] },
+        { name: "copy", summary: "Copy a file through the host",
+          args: [
+            { name: "src", value_name: "SRC", required: true, help: "File to copy" },
+            { name: "dst", value_name: "DST", required: true, help: "Destination path" },
+          ] },
+        { name: "mv", summary: "Move a file or directory through the host",
+          args: [
+            { name: "src", value_name: "SRC", required: true, help: "Path to move" },
+            { name: "dst", value_name: "DST", required: true, help: "Destination path" },
+          ] },
+        { name: "stat", summary: "Show the kind, size and mtime of a path",
+          args: [{ name: "path", value_name: "PATH", required: true, help: "Path to inspect" }] },
+        { name: "rm", summary: "Remove a file",
+          args: [{ name: "path", value_name: "PATH", required: true, help: "File to remove" }] },
+        { name: "rmdir", summary: "Remove a directory",
+          args: [
+            { name: "path", value_name: "PATH", required: true, help: "Directory to remove" },
+            { name: "all", long: "--all", help: "Also remove everything in it" },
+          ] },
+    ],
 });
 
 struct FileIo;
@@ -14,49 +61,227 @@ impl Command for FileIo {
     }
 
     fn run(argv: Vec<String>) -> CommandResult {
-        if argv.is_empty() {
-            return Err(CommandError::InvalidArgs(
-                "usage: fileio <read|write|list> ...".into(),
-            ));
-        }
+        let ctx = Context::new(argv);
+        let m = ctx.matches(&fileio_meta_tree())?;
+        let Some((name, sub)) = m.subcommand() else {
+            wacli_cdk::io::print(wacli_cdk::args::help(&fileio_meta_tree()));
+            return Ok(0);
+        };
+        let value = |name: &str| sub.get(name).unwrap_or_default();
 
-        match argv[0].as_str() {
+        match name {
             "read" => {
-                let path = argv.get(1).ok_or_else(|| {
-                    CommandError::InvalidArgs("usage: fileio read <path>".into())
-                })?;
-                let bytes = wacli_cdk::fs::read(path)?;
+                let bytes = wacli_cdk::fs::read(value("path"))?;
                 let text = String::from_utf8_lossy(&bytes);
                 wacli_cdk::io::println(text);
-                Ok(0)
             }
             "write" => {
-                let path = argv.get(1).ok_or_else(|| {
-                    CommandError::InvalidArgs("usage: fileio write <path> <text>".into())
-                })?;
-                let text = argv.get(2..).unwrap_or(&[]).join(" ");
-                if text.is_empty() {
-                    return Err(CommandError::InvalidArgs(
-                        "usage: fileio write <path> <text>".into(),
-                    ));
-                }
-                wacli_cdk::fs::write(path, text.as_bytes())?;
+                // Extra words are part of the text: `fileio write f hello world`.
+                let mut words = vec![value("text")];
+                words.extend(sub.rest());
+                wacli_cdk::fs::write(value("path"), words.join(" ").as_bytes())?;
                 wacli_cdk::io::println("ok");
-                Ok(0)
+            }
+            "append" => {
+                wacli_cdk::fs::append(value("path"), format!("{}\n", value("text")))?;
+                wacli_cdk::io::println("ok");
+            }
+            "log" => {
+                let writer = wacli_cdk::fs::open_writer(value("path"), sub.get_flag("append"))?;
+                for line in sub.get("line").into_iter().chain(sub.rest().iter().copied()) {
+                    writer.write(format!("{line}\n").as_bytes())?;
+                }
+                writer.close()?;
+                // Writing after close is an error, not a trap.
+                let late = writer.write(b"late\n").unwrap_err();
+                wacli_cdk::io::println(late);
+            }
+            "find" => {
+                for entry in wacli_cdk::fs::walk(value("path"), sub.get_parsed("depth")?)? {
+                    wacli_cdk::io::println(format!("{} {}", kind_name(entry.kind), entry.path));
+                }
+            }
+            "copy" => {
+                let bytes = wacli_cdk::fs::copy(value("src"), value("dst"))?;
+                wacli_cdk::io::println(format!("copied {bytes} bytes"));
+            }
+            "mv" => {
+                wacli_cdk::fs::rename(value("src"), value("dst"))?;
+                wacli_cdk::io::println("moved");
+            }
+            "stat" if !wacli_cdk::fs::exists(value("path")) => {
+                wacli_cdk::io::println("missing");
+            }
+            "stat" => {
+                let info = wacli_cdk::fs::stat(value("path"))?;
+                wacli_cdk::io::println(format!(
+                    "kind={} size={} modified={}",
+                    kind_name(info.kind),
+                    info.size,
+                    info.modified_at
+                ));
+            }
+            "rm" => {
+                wacli_cdk::fs::remove_file(value("path"))?;
+                wacli_cdk::io::println("removed");
+            }
+            "rmdir" if sub.get_flag("all") => {
+                wacli_cdk::fs::remove_dir_all(value("path"))?;
+                wacli_cdk::io::println("removed");
+            }
+            "rmdir" => {
+                wacli_cdk::fs::remove_dir(value("path"))?;
+                wacli_cdk::io::println("removed");
             }
             "list" => {
-                let path = argv.get(1).map(|s| s.as_str()).unwrap_or(".");
-                let entries = wacli_cdk::fs::list_dir(path)?;
-                for entry in entries {
+                for entry in wacli_cdk::fs::list_dir(value("path"))? {
                     wacli_cdk::io::println(entry);
                 }
-                Ok(0)
             }
-            _ => Err(CommandError::InvalidArgs(
-                "usage: fileio <read|write|list> ...".into(),
-            )),
+            _ => unreachable!("subcommands are checked by the parser"),
         }
+        Ok(0)
+    }
+}
+
+fn kind_name(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::File => "file",
+        FileKind::Dir => "dir",
+        FileKind::Symlink => "symlink",
+        FileKind::Other => "other",
     }
 }
 
 wacli_cdk::export!(FileIo);
+
+#[cfg(test)]
+mod tests {
+    use super::FileIo;
+    use wacli_cdk::CommandError;
+    use wacli_cdk::testing::TestHost;
+
+    #[test]
+    fn write_append_and_read() {
+        let mut host = TestHost::new();
+        assert_eq!(
+            host.run::<FileIo>(["write", "notes.txt", "hello", "world"])
+                .stdout_str(),
+            "ok\n"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["append", "notes.txt", "again"])
+                .stdout_str(),
+            "ok\n"
+        );
+        assert_eq!(host.file("notes.txt"), Some(&b"hello worldagain\n"[..]));
+        assert_eq!(
+            host.run::<FileIo>(["read", "notes.txt"]).stdout_str(),
+            "hello worldagain\n\n"
+        );
+    }
+
+    #[test]
+    fn log_reports_writes_after_close() {
+        let mut host = TestHost::new().with_file("log.txt", "old\n");
+        let out = host.run::<FileIo>(["log", "log.txt", "a", "b", "--append"]);
+        assert_eq!(out.stdout_str(), "write: writer is closed: log.txt\n");
+        assert_eq!(host.file("log.txt"), Some(&b"old\na\nb\n"[..]));
+
+        host.run::<FileIo>(["log", "log.txt", "c"]);
+        assert_eq!(host.file("log.txt"), Some(&b"c\n"[..]));
+    }
+
+    #[test]
+    fn find_copy_and_stat() {
+        let mut host = TestHost::new()
+            .with_file("src/a.txt", "abc")
+            .with_file("src/sub/b.txt", "")
+            .with_dir("empty");
+        assert_eq!(
+            host.run::<FileIo>(["find", "src"]).stdout_str(),
+            "file a.txt\ndir sub\nfile sub/b.txt\n"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["find", "src", "--depth", "1"])
+                .stdout_str(),
+            "file a.txt\ndir sub\n"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["copy", "src/a.txt", "empty/a.txt"])
+                .stdout_str(),
+            "copied 3 bytes\n"
+        );
+        let out = host.run::<FileIo>(["copy", "src/a.txt", "./src/a.txt"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg.contains("same file")),
+            "{out:?}"
+        );
+        assert_eq!(host.file("src/a.txt"), Some(&b"abc"[..]));
+        assert_eq!(
+            host.run::<FileIo>(["stat", "empty/a.txt"]).stdout_str(),
+            "kind=file size=3 modified=0\n"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["stat", "nope"]).stdout_str(),
+            "missing\n"
+        );
+        assert_eq!(host.run::<FileIo>(["ls", "."]).stdout_str(), "empty\nsrc\n");
+    }
+
+    #[test]
+    fn remove_files_and_dirs() {
+        let mut host = TestHost::new().with_file("dir/a.txt", "a");
+        let out = host.run::<FileIo>(["rmdir", "dir"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg.contains("directory not empty")),
+            "{out:?}"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["rm", "dir/a.txt"]).stdout_str(),
+            "removed\n"
+        );
+        assert!(!host.is_dir("dir"));
+
+        let mut host = TestHost::new().with_file("dir/sub/a.txt", "a");
+        assert_eq!(
+            host.run::<FileIo>(["rmdir", "dir", "--all"]).stdout_str(),
+            "removed\n"
+        );
+        assert!(host.files().is_empty());
+    }
+
+    #[test]
+    fn move_files_and_dirs() {
+        let mut host = TestHost::new()
+            .with_file("src/a.txt", "a")
+            .with_file("src/sub/b.txt", "b");
+        assert_eq!(
+            host.run::<FileIo>(["mv", "src/a.txt", "a.txt"]).stdout_str(),
+            "moved\n"
+        );
+        assert_eq!(
+            host.run::<FileIo>(["mv", "src", "dst"]).stdout_str(),
+            "moved\n"
+        );
+        assert_eq!(host.file("a.txt"), Some(&b"a"[..]));
+        assert_eq!(host.file("dst/sub/b.txt"), Some(&b"b"[..]));
+        assert!(!host.is_dir("src"));
+
+        let out = host.run::<FileIo>(["mv", "nope", "x"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg == "rename: not found: nope -> x"),
+            "{out:?}"
+        );
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let out = TestHost::new().run::<FileIo>(["read", "nope.txt"]);
+        assert!(
+            matches!(&out.result, Err(CommandError::Io(msg)) if msg == "read: not found: nope.txt"),
+            "{out:?}"
+        );
+        assert_eq!(out.exit_code(), 1);
+    }
+}
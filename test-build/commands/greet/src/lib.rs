@@ -1,4 +1,4 @@
-use wacli_cdk::{Command, CommandMeta, CommandResult};
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
 
 wacli_cdk::declare_command_metadata!(greet_meta, {
     name: "greet",
@@ -15,7 +15,10 @@ impl Command for Greet {
     }
 
     fn run(argv: Vec<String>) -> CommandResult {
-        let name = argv.first().map(|s| s.as_str()).unwrap_or("World");
+        let ctx = Context::new(argv);
+        let name = ctx
+            .positional_of(&Self::meta(), 0)?
+            .unwrap_or_else(|| "World".to_string());
         wacli_cdk::io::println(&format!("Hello, {}!", name));
         Ok(0)
     }
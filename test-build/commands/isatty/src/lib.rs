@@ -0,0 +1,41 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(isatty_meta, {
+    name: "isatty",
+    summary: "Report whether stdout/stderr are terminals",
+    usage: "isatty [--out FILE]",
+    args: [
+        {
+            name: "out",
+            long: "--out",
+            value_name: "FILE",
+            help: "Also write the report to FILE"
+        },
+    ],
+});
+
+struct IsAtty;
+
+impl Command for IsAtty {
+    fn meta() -> CommandMeta {
+        isatty_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
+        let report = format!(
+            "stdout={} stderr={}\n",
+            wacli_cdk::io::stdout_is_tty(),
+            wacli_cdk::io::stderr_is_tty()
+        );
+        if let Some(path) = m.get("out") {
+            wacli_cdk::fs::write(path, &report)?;
+        }
+        wacli_cdk::io::print(&report);
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(IsAtty);
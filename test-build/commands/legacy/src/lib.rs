@@ -0,0 +1,37 @@
+//! Mimics a plugin built with an old `declare_command_metadata!` that embedded
+//! only the flattened `command-meta` (no `command-schema`).
+
+use wacli_cdk::{Command, CommandMeta, CommandResult, meta};
+
+const PAYLOAD: &[u8] =
+    br#"{"format-version":1,"command-meta":{"name":"legacy","summary":"Built with an old wacli-cdk","version":"0.1.0"}}"#;
+
+#[used]
+#[unsafe(link_section = "wacli:cli/command-metadata@1")]
+static COMMAND_METADATA: [u8; PAYLOAD.len()] = {
+    let mut out = [0u8; PAYLOAD.len()];
+    let mut i = 0;
+    while i < PAYLOAD.len() {
+        out[i] = PAYLOAD[i];
+        i += 1;
+    }
+    out
+};
+
+struct Legacy;
+
+impl Command for Legacy {
+    fn meta() -> CommandMeta {
+        meta("legacy")
+            .summary("Built with an old wacli-cdk")
+            .version("0.1.0")
+            .build()
+    }
+
+    fn run(_argv: Vec<String>) -> CommandResult {
+        wacli_cdk::io::println("legacy: ok");
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Legacy);
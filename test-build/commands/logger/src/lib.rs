@@ -0,0 +1,30 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, log};
+
+wacli_cdk::declare_command_metadata!(logger_meta, {
+    name: "logger",
+    summary: "Log one message at every level",
+    usage: "logger",
+});
+
+struct Logger;
+
+impl Command for Logger {
+    fn meta() -> CommandMeta {
+        logger_meta()
+    }
+
+    fn run(_argv: Vec<String>) -> CommandResult {
+        log::trace!("trace message");
+        log::debug!("debug message");
+        log::info!("info message");
+        log::warn!("warn message");
+        log::error!("error message");
+        wacli_cdk::io::println(format!(
+            "debug enabled: {}",
+            log::enabled(log::Level::Debug)
+        ));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Logger);
@@ -0,0 +1,27 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, io};
+
+wacli_cdk::declare_command_metadata!(login_meta, {
+    name: "login",
+    summary: "Prompt for a user name and password",
+    usage: "login",
+});
+
+struct Login;
+
+impl Command for Login {
+    fn meta() -> CommandMeta {
+        login_meta()
+    }
+
+    fn run(_argv: Vec<String>) -> CommandResult {
+        let Some(user) = io::prompt("user: ")? else {
+            io::eprintln("no user given");
+            return Ok(1);
+        };
+        let password = io::prompt_secret("password: ")?;
+        io::println(format!("user={user} password={} chars", password.chars().count()));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Login);
@@ -0,0 +1,42 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(migrate_meta, {
+    name: "migrate",
+    summary: "Apply pending migrations",
+    usage: "migrate [OPTIONS]",
+    aliases: ["up"],
+    args: [
+        {
+            name: "steps",
+            long: "--steps",
+            value_name: "N",
+            help: "Number of migrations to apply",
+            default_value: "all"
+        },
+        {
+            name: "dry_run",
+            long: "--dry-run",
+            help: "Print the plan without applying it"
+        },
+    ],
+});
+
+struct Migrate;
+
+impl Command for Migrate {
+    fn meta() -> CommandMeta {
+        migrate_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
+        let steps = m.get("steps").unwrap_or("all");
+        let dry_run = m.is_present("dry_run");
+        wacli_cdk::io::println(&format!("migrate: steps={steps} dry-run={dry_run}"));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Migrate);
@@ -1,4 +1,4 @@
-use wacli_cdk::{Command, CommandMeta, CommandResult, Context, parse};
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
 
 wacli_cdk::declare_command_metadata!(need_meta, {
     name: "need",
@@ -58,7 +58,8 @@ impl Command for Need {
 
     fn run(argv: Vec<String>) -> CommandResult {
         let ctx = Context::new(argv);
-        let m = parse(&Self::meta(), &ctx.argv)?;
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
         let text = m.get("text").unwrap_or_default();
         let raw = m.is_present("raw");
         let case = m.get("case").unwrap_or("upper");
@@ -88,3 +89,49 @@ impl Command for Need {
 }
 
 wacli_cdk::export!(Need);
+
+#[cfg(test)]
+mod tests {
+    use super::Need;
+    use wacli_cdk::CommandError;
+    use wacli_cdk::testing::TestHost;
+
+    #[test]
+    fn uppercases_by_default() {
+        let out = TestHost::new().run::<Need>(["hello"]);
+        assert_eq!(out.exit_code(), 0);
+        assert_eq!(out.stdout_str(), "HELLO\n");
+    }
+
+    #[test]
+    fn case_raw_and_tag() {
+        let mut host = TestHost::new();
+        assert_eq!(
+            host.run::<Need>(["Hi", "--case", "lower"]).stdout_str(),
+            "hi\n"
+        );
+        assert_eq!(host.run::<Need>(["Hi", "--raw"]).stdout_str(), "Hi\n");
+        assert_eq!(
+            host.run::<Need>(["hi", "--with-tag", "--tag", "t"])
+                .stdout_str(),
+            "[t] HI\n"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_args() {
+        let mut host = TestHost::new();
+        let out = host.run::<Need>(Vec::<String>::new());
+        assert!(
+            matches!(out.result, Err(CommandError::InvalidArgs(_))),
+            "{out:?}"
+        );
+        assert_eq!(out.stdout, b"");
+
+        let out = host.run::<Need>(["hi", "--nope"]);
+        assert!(
+            matches!(out.result, Err(CommandError::InvalidArgs(_))),
+            "{out:?}"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use wacli_cdk::{Command, CommandError, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(probe_meta, {
+    name: "probe",
+    summary: "Echo words; fail on request",
+    usage: "probe [OPTIONS] [WORD]...",
+    examples: ["probe", "probe 'two words' three", "probe --fail"],
+    tests: [
+        { cmd: "probe a 'b c'", expect_stdout: "probe: a|b c" },
+        { cmd: "probe --fail", expect_stdout: "contains:about to fail", expect_exit: 1 },
+        // Deliberately wrong, to exercise failure reporting.
+        { cmd: "probe x", expect_stdout: "probe: y" },
+    ],
+    args: [
+        {
+            name: "fail",
+            long: "--fail",
+            help: "Exit with code 1 after writing to stderr"
+        },
+        {
+            name: "exit",
+            long: "--exit",
+            value_name: "CODE",
+            help: "Fail with this exit code (via CommandError::exit)"
+        },
+    ],
+});
+
+struct Probe;
+
+impl Command for Probe {
+    fn meta() -> CommandMeta {
+        probe_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
+        if m.is_present("fail") {
+            wacli_cdk::io::println("probe: about to fail");
+            wacli_cdk::io::eprintln("probe: failing on purpose");
+            return Ok(1);
+        }
+        if let Some(code) = m.get("exit") {
+            let code: u32 = code.parse()?;
+            return Err(CommandError::exit(code, format!("probe: exiting with {code}")));
+        }
+        wacli_cdk::io::println(&format!("probe: {}", m.rest().join("|")));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Probe);
@@ -0,0 +1,22 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult};
+
+wacli_cdk::declare_command_metadata!(reset_meta, {
+    name: "reset",
+    summary: "Drop and recreate the database",
+    usage: "reset",
+});
+
+struct Reset;
+
+impl Command for Reset {
+    fn meta() -> CommandMeta {
+        reset_meta()
+    }
+
+    fn run(_argv: Vec<String>) -> CommandResult {
+        wacli_cdk::io::println("reset: done");
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Reset);
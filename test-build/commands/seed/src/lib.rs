@@ -0,0 +1,32 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(seed_meta, {
+    name: "seed",
+    summary: "Load seed data",
+    usage: "seed <FILE>",
+    args: [
+        {
+            name: "file",
+            value_name: "FILE",
+            help: "Seed data file",
+            required: true
+        },
+    ],
+});
+
+struct Seed;
+
+impl Command for Seed {
+    fn meta() -> CommandMeta {
+        seed_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let file = ctx.value_of(&Self::meta(), "file")?.unwrap_or_default();
+        wacli_cdk::io::println(&format!("seed: {file}"));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Seed);
@@ -1,16 +1,24 @@
-use wacli_cdk::{parse, pipes, Command, CommandError, CommandMeta, CommandResult, Context};
+use wacli_cdk::output::{self, Format};
+use wacli_cdk::{pipes, Command, CommandError, CommandMeta, CommandResult, Context};
 
 wacli_cdk::declare_command_metadata!(show_meta, {
     name: "show",
     summary: "Show text with optional pipe formatting",
-    usage: "show [--format <PIPE>] [TEXT]",
+    usage: "show [--format <FORMAT>] [--option <KEY=VALUE>]... [TEXT]",
     args: [
         {
             name: "format",
             long: "--format",
-            value_name: "PIPE",
-            help: "Pipe to apply to the input"
+            value_name: "FORMAT",
+            help: "Output format: plain, json, or a format/ pipe (`help` lists them)"
         },
+        {
+            name: "option",
+            long: "--option",
+            value_name: "KEY=VALUE",
+            help: "Option passed to the pipe (repeatable)"
+        },
+        { name: "list-formats", long: "--list-formats", help: "List the available formats" },
         { name: "text", value_name: "TEXT", help: "Text to show" }
     ],
 });
@@ -24,22 +32,26 @@ impl Command for Show {
 
     fn run(argv: Vec<String>) -> CommandResult {
         let ctx = Context::new(argv);
-        let matches = parse(&Self::meta(), &ctx.argv)?;
-        let format = matches.get("format");
+        let meta = Self::meta();
+        let matches = ctx.matches(&meta)?;
         let input = matches.get("text").unwrap_or("hello world");
 
-        if let Some(pipe_name) = format {
-            let pipe = pipes::load(pipe_name)
-                .map_err(|e| CommandError::Failed(format!("failed to load pipe '{pipe_name}': {e}")))?;
-            let output = pipe
-                .process(input.as_bytes(), &[])
-                .map_err(|e| CommandError::Failed(format!("pipe error: {e:?}")))?;
-            wacli_cdk::io::print(String::from_utf8_lossy(&output));
-            Ok(0)
-        } else {
-            wacli_cdk::io::print(input);
-            Ok(0)
+        match output::negotiate(&matches, &["plain", "json"], "format/")? {
+            Format::Builtin(kind) if kind == "json" => {
+                wacli_cdk::io::println(format!("{{\"text\":{input:?}}}"));
+            }
+            Format::Builtin(_) => wacli_cdk::io::print(input),
+            Format::Pipe { name, options } => {
+                let pipe = pipes::load(&name)
+                    .map_err(|e| CommandError::Failed(format!("failed to load pipe '{name}': {e}")))?;
+                let output = pipe
+                    .process(input.as_bytes(), &options)
+                    .map_err(|e| CommandError::Failed(format!("pipe error: {e:?}")))?;
+                wacli_cdk::io::print(String::from_utf8_lossy(&output));
+            }
+            Format::List(text) => wacli_cdk::io::print(text),
         }
+        Ok(0)
     }
 }
 
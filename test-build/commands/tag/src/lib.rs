@@ -0,0 +1,37 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(tag_meta, {
+    name: "tag",
+    summary: "Tag items by id",
+    usage: "tag [OPTIONS] [LABEL]...",
+    allow_arg_files: true,
+    args: [
+        {
+            name: "id",
+            long: "--id",
+            value_name: "ID",
+            help: "Item id (repeatable)",
+            takes_value: true
+        },
+    ],
+});
+
+struct Tag;
+
+impl Command for Tag {
+    fn meta() -> CommandMeta {
+        tag_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let meta = Self::meta();
+        let m = ctx.matches(&meta)?;
+        let ids = m.get_all("id").unwrap_or(&[]).join(",");
+        let labels = m.rest().join(",");
+        wacli_cdk::io::println(&format!("tag: ids={ids} labels={labels}"));
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Tag);
@@ -0,0 +1,38 @@
+use wacli_cdk::telemetry::TelemetryEvent;
+use wacli_cdk::{Command, CommandMeta, CommandResult};
+
+wacli_cdk::declare_command_metadata!(telemetry_meta, {
+    name: "telemetry",
+    summary: "Record usage events",
+    usage: "telemetry <EVENT>",
+    hidden: true,
+});
+
+struct Telemetry;
+
+const LOG: &str = "telemetry.log";
+
+impl Command for Telemetry {
+    fn meta() -> CommandMeta {
+        telemetry_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let event = TelemetryEvent::from_argv(&argv)?;
+        let mut log = wacli_cdk::fs::read(LOG)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        log.push_str(&format!(
+            "argc={} command={} exit_code={} duration_ms={}\n{}\n",
+            argv.len(),
+            event.command,
+            event.exit_code,
+            event.duration_ms,
+            argv[0]
+        ));
+        wacli_cdk::fs::write(LOG, log)?;
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Telemetry);
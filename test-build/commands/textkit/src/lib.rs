@@ -0,0 +1,65 @@
+use wacli_cdk::{Command, CommandError, CommandMeta, CommandResult, CommandTree, Context};
+
+wacli_cdk::declare_command_metadata!(shout_meta, {
+    name: "shout",
+    summary: "Print the words in upper case",
+    usage: "shout [WORDS]...",
+    aliases: ["yell"],
+    args: [
+        { name: "words", value_name: "WORDS", help: "Words to print", var_arg: true },
+    ],
+});
+
+wacli_cdk::declare_command_metadata!(whisper_meta, {
+    name: "whisper",
+    summary: "Print the words in lower case",
+    usage: "whisper [WORDS]...",
+    args: [
+        { name: "words", value_name: "WORDS", help: "Words to print", var_arg: true },
+    ],
+});
+
+/// The `words` positionals joined by spaces.
+fn words(tree: &CommandTree, argv: Vec<String>) -> Result<String, CommandError> {
+    let ctx = Context::new(argv);
+    let matches = ctx.matches(tree)?;
+    let words: Vec<&str> = matches
+        .get_all("words")
+        .unwrap_or_default()
+        .iter()
+        .map(|w| w.as_ref())
+        .collect();
+    Ok(words.join(" "))
+}
+
+struct Shout;
+
+impl Command for Shout {
+    const NAME: Option<&'static str> = Some("shout");
+
+    fn meta() -> CommandMeta {
+        shout_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        wacli_cdk::io::println(&words(&shout_meta_tree(), argv)?.to_uppercase());
+        Ok(0)
+    }
+}
+
+struct Whisper;
+
+impl Command for Whisper {
+    const NAME: Option<&'static str> = Some("whisper");
+
+    fn meta() -> CommandMeta {
+        whisper_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        wacli_cdk::io::println(&words(&whisper_meta_tree(), argv)?.to_lowercase());
+        Ok(0)
+    }
+}
+
+wacli_cdk::export_multi!(Shout, Whisper);
@@ -0,0 +1,49 @@
+use wacli_cdk::{Command, CommandMeta, CommandResult, Context};
+
+wacli_cdk::declare_command_metadata!(upper_meta, {
+    name: "upper",
+    summary: "Uppercase stdin",
+    usage: "upper [--chunk N]",
+    args: [
+        {
+            name: "chunk",
+            long: "--chunk",
+            value_name: "N",
+            value_type: "uint",
+            help: "Read stdin N bytes at a time instead of all at once"
+        },
+    ],
+});
+
+struct Upper;
+
+impl Command for Upper {
+    fn meta() -> CommandMeta {
+        upper_meta()
+    }
+
+    fn run(argv: Vec<String>) -> CommandResult {
+        let ctx = Context::new(argv);
+        let m = ctx.matches(&upper_meta_tree())?;
+        let text = match m.get_parsed::<u64>("chunk")? {
+            Some(len) => {
+                let mut bytes = Vec::new();
+                loop {
+                    let chunk = wacli_cdk::io::read(len)?;
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                // Past the end, reads keep returning nothing.
+                assert!(wacli_cdk::io::read(len)?.is_empty());
+                String::from_utf8(bytes)?
+            }
+            None => wacli_cdk::io::read_to_string()?,
+        };
+        wacli_cdk::io::print(text.to_uppercase());
+        Ok(0)
+    }
+}
+
+wacli_cdk::export!(Upper);
@@ -0,0 +1,15 @@
+;; Starts a DNS lookup of "localhost" through wasi:sockets and exits 0 if the
+;; host allowed it, 1 if it refused. The lookup itself is never awaited.
+(module
+  (import "wasi:sockets/instance-network@0.2.9" "instance-network"
+    (func $instance_network (result i32)))
+  (import "wasi:sockets/ip-name-lookup@0.2.9" "resolve-addresses"
+    (func $resolve_addresses (param i32 i32 i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 16) "localhost")
+  (func (export "wasi:cli/run@0.2.9#run") (result i32)
+    ;; result<resolve-address-stream, error-code> is written at offset 32;
+    ;; its discriminant is 0 for ok and 1 for err, like run's result.
+    (call $resolve_addresses
+      (call $instance_network) (i32.const 16) (i32.const 9) (i32.const 32))
+    (i32.load8_u (i32.const 32))))
@@ -1,28 +1,44 @@
-wit_bindgen::generate!({
-    path: "../../../../../wit/cli",
-    world: "pipe-plugin",
-});
+use wacli_cdk::pipe::{Options, Pipe, PipeError, PipeMeta, pipe_meta};
 
-use exports::wacli::cli::pipe::Guest;
-use wacli::cli::types::{PipeError, PipeMeta};
+// Listed by the host without instantiating the pipe; `options` are checked
+// before `process()` runs.
+wacli_cdk::declare_pipe_metadata!({
+    summary: "Uppercase formatter (test)",
+    input_types: ["text/plain"],
+    output_type: "text/plain",
+    version: "0.1.0",
+    options: [
+        { name: "case", possible_values: ["upper", "lower"], default_value: "upper",
+          help: "Letter case of the output" },
+        { name: "trim", help: "Strip surrounding whitespace" },
+    ],
+});
 
 struct TablePipe;
 
-impl Guest for TablePipe {
+impl Pipe for TablePipe {
     fn meta() -> PipeMeta {
-        PipeMeta {
-            name: "format/table".to_string(),
-            summary: "Uppercase formatter (test)".to_string(),
-            input_types: vec!["text/plain".to_string()],
-            output_type: "text/plain".to_string(),
-            version: "0.1.0".to_string(),
-        }
+        pipe_meta("format/table")
+            .summary("Uppercase formatter (test)")
+            .input_type("text/plain")
+            .output_type("text/plain")
+            .version("0.1.0")
+            .build()
     }
 
-    fn process(input: Vec<u8>, _options: Vec<String>) -> Result<Vec<u8>, PipeError> {
-        let s = String::from_utf8(input).map_err(|e| PipeError::ParseError(e.to_string()))?;
-        Ok(s.to_uppercase().into_bytes())
+    fn process(input: Vec<u8>, options: Vec<String>) -> Result<Vec<u8>, PipeError> {
+        let opts = Options::new(&options);
+        // Never fails: the host rejects undeclared options.
+        opts.check_known(&["case", "trim"])
+            .map_err(|e| PipeError::InvalidOption(format!("table: {e}")))?;
+        let s = String::from_utf8(input)?;
+        let s = if opts.has("trim") { s.trim() } else { &s };
+        Ok(match opts.get("case") {
+            Some("lower") => s.to_lowercase(),
+            _ => s.to_uppercase(),
+        }
+        .into_bytes())
     }
 }
 
-export!(TablePipe);
+wacli_cdk::export_pipe!(TablePipe);